@@ -71,6 +71,7 @@ async fn main() -> Result<()> {
         table_partition_cols: vec![],
         output_ordering: vec![],
         column_hints: None,
+        listing_pruning: None,
     };
 
     let result =
@@ -136,6 +136,26 @@ macro_rules! config_namespace {
                 }
             }
 
+            fn reset(&mut self, key: &str) -> Result<()> {
+                let (key, rem) = key.split_once('.').unwrap_or((key, ""));
+                match key {
+                    "" => {
+                        *self = Self::default();
+                        Ok(())
+                    }
+                    $(
+                       stringify!($field_name) if rem.is_empty() => {
+                           self.$field_name = $default;
+                           Ok(())
+                       }
+                       stringify!($field_name) => self.$field_name.reset(rem),
+                    )*
+                    _ => return Err(DataFusionError::Configuration(format!(
+                        "Config value \"{}\" not found on {}", key, stringify!($struct_name)
+                    )))
+                }
+            }
+
             fn visit<V: Visit>(&self, v: &mut V, key_prefix: &str, _description: &'static str) {
                 $(
                 let key = format!(concat!("{}.", stringify!($field_name)), key_prefix);
@@ -224,6 +244,20 @@ config_namespace! {
         /// target batch size is determined by the configuration setting
         pub coalesce_batches: bool, default = true
 
+        /// If greater than 0, the batches are coalesced into batches of about this
+        /// number of bytes instead of `batch_size` rows, the number of rows being
+        /// estimated from the average size of the rows coalesced so far, and
+        /// bounded by `coalesce_batches_min_rows` and `coalesce_batches_max_rows`
+        pub coalesce_batches_target_bytes: usize, default = 0
+
+        /// The minimum number of rows of the coalesced batches when
+        /// `coalesce_batches_target_bytes` is set
+        pub coalesce_batches_min_rows: usize, default = 1024
+
+        /// The maximum number of rows of the coalesced batches when
+        /// `coalesce_batches_target_bytes` is set
+        pub coalesce_batches_max_rows: usize, default = 65536
+
         /// Should DataFusion collect statistics after listing files
         pub collect_statistics: bool, default = false
 
@@ -268,9 +302,44 @@ config_namespace! {
         /// batches and merged.
         pub sort_in_place_threshold_bytes: usize, default = 1024 * 1024
 
+        /// Compression codec for the Arrow IPC files written when sorts and
+        /// aggregations spill to disk: `uncompressed`, `lz4_frame` or `zstd`.
+        /// Compression trades CPU time for less local disk usage and IO.
+        pub spill_compression: SpillCompression, default = SpillCompression::Uncompressed
+
+        /// Hash function used to hash the rows of the hash repartitions and
+        /// of the hash joins: `ahash`, `xxhash64` or `crc32`. The xxhash64
+        /// and crc32 hashes are the same on all platforms, for the rows to
+        /// be partitioned the same way by different processes.
+        pub hash_function: HashFunction, default = HashFunction::AHash
+
+        /// Seed of the hash function set by `hash_function`
+        pub hash_seed: u64, default = 0
+
+        /// If set, the longest time in milliseconds that a sort preserving
+        /// merge buffers merged rows while it waits for more input, before it
+        /// emits them in a batch smaller than `batch_size`. This bounds the
+        /// latency of streaming queries at the cost of smaller batches. If not
+        /// set, batches are only emitted once `batch_size` rows are merged or
+        /// the input is exhausted.
+        pub sort_preserving_merge_max_batch_delay_ms: Option<u64>, default = None
+
         /// Number of files to read in parallel when inferring schema and statistics
         pub meta_fetch_concurrency: usize, default = 32
 
+        /// Maximum number of batches for which async scalar functions are
+        /// evaluated concurrently, in each partition of an `AsyncFuncExec`
+        pub async_udf_concurrency: usize, default = 4
+
+        /// If greater than 0, filters evaluate the conjuncts of their predicates
+        /// one after the other, each on the rows selected by the previous ones,
+        /// and every this number of batches reorder them by their measured
+        /// selectivity and cost, so the cheap and selective conjuncts go first.
+        /// The conjuncts that may fail, such as divisions, casts or function
+        /// calls, keep their position.
+        /// If 0, the predicates are evaluated as a whole on all the rows
+        pub filter_reorder_interval: usize, default = 0
+
         /// Guarantees a minimum level of output files running in parallel.
         /// RecordBatches will be distributed in round robin fashion to each
         /// parallel writer. Each writer is closed and a new file opened once
@@ -295,8 +364,30 @@ config_namespace! {
         /// tables (e.g. `/table/year=2021/month=01/data.parquet`).
         pub listing_table_ignore_subdirectory: bool, default = true
 
+        /// Should the schema of a listing table be the union of the schemas
+        /// of all its files, merged by name recursively into the structs,
+        /// instead of the schema inferred from its first files. The files
+        /// missing some fields of the union are read with nulls for them.
+        pub listing_table_merge_schemas: bool, default = false
+
         /// Should DataFusion support recursive CTEs
         pub enable_recursive_ctes: bool, default = true
+
+        /// Should the execution of each partition of each operator be
+        /// recorded in a `tracing` span, with the id of the operator in the
+        /// plan. The planning phases are always recorded in spans.
+        pub operator_spans: bool, default = false
+
+        /// If set, the id of the external trace (such as a W3C `traceparent`)
+        /// the queries of the session are part of. It is recorded on the
+        /// planning and execution spans, so that they can be linked to the
+        /// trace of the caller.
+        pub trace_parent: Option<String>, default = None
+
+        /// When set to true, `DESCRIBE` lists the fields of the struct
+        /// columns, and of the structs in the list columns, in rows following
+        /// their column, with their names indented by their nesting depth
+        pub describe_nested_fields: bool, default = false
     }
 }
 
@@ -337,6 +428,12 @@ config_namespace! {
         /// the filters are applied in the same order as written in the query
         pub reorder_filters: bool, default = false
 
+        /// If true, and a scan reads fewer parquet files than `target_partitions`,
+        /// the row groups of the files are split across the partitions, in
+        /// contiguous ranges of about the same compressed size of the projected
+        /// columns. This parallelizes the scans of a few large files
+        pub split_row_groups: bool, default = false
+
         // The following map to parquet::file::properties::WriterProperties
 
         /// Sets best effort maximum size of data page in bytes
@@ -395,7 +492,9 @@ config_namespace! {
         /// default parquet writer setting
         pub encoding: Option<String>, default = None
 
-        /// Sets if bloom filter is enabled for any column
+        /// Sets if bloom filter is enabled for any column. When reading, also
+        /// enables pruning the row groups by the bloom filters and the
+        /// dictionaries of their columns, including the leaves of structs
         pub bloom_filter_enabled: bool, default = false
 
         /// Sets bloom filter false positive probability. If NULL, uses
@@ -434,6 +533,13 @@ config_namespace! {
         /// data frame.
         pub maximum_buffered_record_batches_per_stream: usize, default = 2
 
+        /// If true, the parquet writer computes a HyperLogLog sketch of the
+        /// distinct values of each column supported by `approx_distinct`, and
+        /// stores it in the key-value metadata of the written files. The
+        /// sketches are read back as column statistics, so that the number
+        /// of distinct values of a table can be estimated without scanning it
+        pub distinct_sketches_enabled: bool, default = false
+
     }
 }
 
@@ -455,6 +561,26 @@ config_namespace! {
         /// By adjusting the `scalar_update_factor`, you can balance the trade-off between
         /// more efficient accumulator updates and the number of groups affected.
         pub scalar_update_factor: usize, default = 10
+
+        /// Number of input rows a partial aggregation reads before checking
+        /// whether it reduces its input enough to be worth continuing.
+        pub skip_partial_aggregation_probe_rows_threshold: usize, default = 100_000
+
+        /// Ratio of distinct groups to input rows above which a partial
+        /// aggregation stops grouping its input, once it has read
+        /// `skip_partial_aggregation_probe_rows_threshold` rows, and instead
+        /// passes each row through as an intermediate state of its own. Only
+        /// applies if all the aggregate functions support this pass-through mode.
+        /// A value of 1.0 or more never skips partial aggregation.
+        pub skip_partial_aggregation_probe_ratio_threshold: f64, default = 0.8
+
+        /// How `SUM` and `AVG` of decimals handle results that do not fit in
+        /// their data type: `unchecked` does not check the sums, which wrap
+        /// around if they overflow, `error` returns an error, `saturate`
+        /// returns the largest (or smallest) value of the type, and `promote`
+        /// computes the aggregates of `Decimal128` values as `Decimal256`,
+        /// erroring if they still overflow.
+        pub decimal_overflow_mode: DecimalOverflowMode, default = DecimalOverflowMode::Unchecked
     }
 }
 
@@ -484,6 +610,46 @@ config_namespace! {
         /// predicate push down.
         pub filter_null_join_keys: bool, default = false
 
+        /// When set to true, the physical plan optimizer pushes the predicates of the
+        /// filters above parquet scans into the scans, once the projections between them
+        /// were merged into the scans, to prune the files and, with
+        /// `datafusion.execution.parquet.pushdown_filters`, filter their rows while decoding
+        pub push_down_parquet_filters: bool, default = true
+
+        /// When set to true, the optimizer will remove casts from equijoin keys whose
+        /// sides only differ in their dictionary encoding, so that dictionary keys are
+        /// hashed directly instead of being encoded or unpacked for every batch
+        pub normalize_join_keys: bool, default = true
+
+        /// When set to true, the optimizer will deduplicate the output of uncorrelated
+        /// `IN` subqueries before joining them with a semi join, reducing the size of
+        /// the join build side when the subquery returns many duplicate values
+        pub deduplicate_semi_join_build_side: bool, default = false
+
+        /// When set to true, uncorrelated scalar subqueries are not rewritten
+        /// to joins. Instead, each distinct subquery is executed once before
+        /// the query runs, and its value is shared by all the expressions
+        /// referencing it
+        pub evaluate_scalar_subqueries_once: bool, default = false
+
+        /// When set to true, the optimizer replaces exact `COUNT(DISTINCT x)`
+        /// aggregates with `approx_distinct(x)`, which estimates the number of
+        /// distinct values with a fixed size HyperLogLog sketch per group
+        /// instead of keeping every distinct value
+        pub prefer_approx_distinct: bool, default = false
+
+        /// When set to true, the optimizer merges the inputs of a `UNION ALL`
+        /// which scan the same table with the same filters into a single scan,
+        /// whose rows are output once per input with the projection of the
+        /// input, so that the table is only read once
+        pub merge_union_scans: bool, default = false
+
+        /// When set to true, the optimizer removes the aggregations without
+        /// aggregate functions, e.g. of a `DISTINCT`, grouping on a primary
+        /// key of their input. Primary keys are not enforced, so that this
+        /// returns duplicate rows if the data violates them
+        pub eliminate_unique_group_by: bool, default = false
+
         /// Should DataFusion repartition data using the aggregate keys to execute aggregates
         /// in parallel using the provided `target_partitions` level
         pub repartition_aggregations: bool, default = true
@@ -543,6 +709,14 @@ config_namespace! {
         /// `RepartitionExec` even if this requires subsequently resorting data using a `SortExec`.
         pub prefer_existing_sort: bool, default = false
 
+        /// When set to true, the inputs of a `UNION ALL` that all have the same
+        /// number of partitions are combined partition by partition using an
+        /// `InterleaveExec` instead of being concatenated by a `UnionExec`. This
+        /// keeps the partition count of the inputs (avoiding a subsequent
+        /// `RepartitionExec`) and, when all the inputs share an ordering, keeps
+        /// that ordering as well.
+        pub prefer_union_interleave: bool, default = false
+
         /// When set to true, the logical plan optimizer will produce warning
         /// messages if any optimization rules produce errors and then proceed to the next
         /// rule. When set to false, any rules that produce errors will cause the query to fail
@@ -551,6 +725,11 @@ config_namespace! {
         /// Number of times that the optimizer will attempt to optimize the plan
         pub max_passes: usize, default = 3
 
+        /// When set to true, the optimizer records the time each rule takes in
+        /// each pass and the diff of the plan when the rule changes it, which
+        /// `EXPLAIN VERBOSE` shows as `optimizer_rule_runs`
+        pub record_rule_runs: bool, default = false
+
         /// When set to true, the physical plan optimizer will run a top down
         /// process to reorder the join keys
         pub top_down_join_key_reordering: bool, default = true
@@ -559,6 +738,11 @@ config_namespace! {
         /// HashJoin can work more efficiently than SortMergeJoin but consumes more memory
         pub prefer_hash_join: bool, default = true
 
+        /// When set to true, the physical planner will use RangeJoin instead of
+        /// NestedLoopJoin for inner joins without equijoin keys whose filter
+        /// restricts a column to a range, such as `a.ts BETWEEN b.start AND b.end`
+        pub enable_range_join: bool, default = true
+
         /// The maximum estimated size in bytes for one input side of a HashJoin
         /// will be collected into a single partition
         pub hash_join_single_partition_threshold: usize, default = 1024 * 1024
@@ -567,6 +751,14 @@ config_namespace! {
         /// will be collected into a single partition
         pub hash_join_single_partition_threshold_rows: usize, default = 1024 * 128
 
+        /// When set to true, hash joins are planned with a partition mode chosen from
+        /// statistics even if `datafusion.execution.collect_statistics` is disabled, and
+        /// an input is collected into a single partition when its size estimated after
+        /// filtering is below the thresholds above. Filters whose selectivity can't be
+        /// derived from column statistics are assumed to keep `default_filter_selectivity`
+        /// percent of their input
+        pub hash_join_broadcast_by_estimate: bool, default = false
+
         /// The default filter selectivity used by Filter Statistics
         /// when an exact selectivity cannot be determined. Valid values are
         /// between 0 (no selectivity) and 100 (all rows are selected).
@@ -641,6 +833,18 @@ impl ConfigField for ConfigOptions {
         }
     }
 
+    fn reset(&mut self, key: &str) -> Result<()> {
+        let (key, rem) = key.split_once('.').unwrap_or((key, ""));
+        match key {
+            "catalog" => self.catalog.reset(rem),
+            "execution" => self.execution.reset(rem),
+            "optimizer" => self.optimizer.reset(rem),
+            "explain" => self.explain.reset(rem),
+            "sql_parser" => self.sql_parser.reset(rem),
+            _ => _config_err!("Config value \"{key}\" not found on ConfigOptions"),
+        }
+    }
+
     fn visit<V: Visit>(&self, v: &mut V, _key_prefix: &str, _description: &'static str) {
         self.catalog.visit(v, "datafusion.catalog", "");
         self.execution.visit(v, "datafusion.execution", "");
@@ -683,6 +887,31 @@ impl ConfigOptions {
         e.0.set(key, value)
     }
 
+    /// Reset a configuration option to its default value
+    ///
+    /// Only the options of the `datafusion` namespace can be reset: the
+    /// defaults of the extensions are not known.
+    pub fn reset(&mut self, key: &str) -> Result<()> {
+        let (prefix, key) = key.split_once('.').ok_or_else(|| {
+            DataFusionError::Configuration(format!(
+                "could not find config namespace for key \"{key}\"",
+            ))
+        })?;
+
+        if prefix == "datafusion" {
+            return ConfigField::reset(self, key);
+        }
+
+        _config_err!("Config value \"{prefix}.{key}\" cannot be reset")
+    }
+
+    /// Reset all the options of the `datafusion` namespace to their default
+    /// values, keeping the extensions
+    pub fn reset_all(&mut self) {
+        let extensions = std::mem::take(&mut self.extensions);
+        *self = Self::new().with_extensions(extensions);
+    }
+
     /// Create new ConfigOptions struct, taking values from
     /// environment variables where possible.
     ///
@@ -723,7 +952,8 @@ impl ConfigOptions {
     /// Create new ConfigOptions struct, taking values from a string hash map.
     ///
     /// Only the built-in configurations will be extracted from the hash map
-    /// and other key value pairs will be ignored.
+    /// and the key value pairs of other namespaces will be ignored. Unknown
+    /// keys of the `datafusion` namespace are rejected.
     pub fn from_string_hash_map(settings: HashMap<String, String>) -> Result<Self> {
         struct Visitor(Vec<String>);
 
@@ -741,6 +971,14 @@ impl ConfigOptions {
         let mut ret = Self::default();
         ret.visit(&mut keys, "datafusion", "");
 
+        if let Some(unknown) = settings
+            .keys()
+            .filter(|key| key.starts_with("datafusion."))
+            .find(|key| !keys.0.contains(key))
+        {
+            return _config_err!("Unknown config key \"{unknown}\"");
+        }
+
         for key in keys.0 {
             if let Some(var) = settings.get(&key) {
                 ret.set(&key, var)?;
@@ -750,6 +988,77 @@ impl ConfigOptions {
         Ok(ret)
     }
 
+    /// Set the options of the [`ConfigProfile`] `profile`, keeping the other
+    /// options unchanged
+    pub fn apply_profile(&mut self, profile: ConfigProfile) {
+        match profile {
+            ConfigProfile::LowMemory => {
+                self.execution.batch_size = 4096;
+                self.execution.sort_spill_reservation_bytes = 1024 * 1024;
+                self.execution.sort_in_place_threshold_bytes = 256 * 1024;
+                self.execution.spill_compression = SpillCompression::Lz4Frame;
+                self.execution.max_buffered_batches_per_output_file = 1;
+                self.execution.parquet.allow_single_file_parallelism = false;
+                self.execution
+                    .parquet
+                    .maximum_buffered_record_batches_per_stream = 1;
+                self.optimizer.prefer_hash_join = false;
+            }
+            ConfigProfile::Interactive => {
+                self.catalog.information_schema = true;
+                self.execution.collect_statistics = false;
+                self.execution.parquet.pushdown_filters = true;
+                self.execution.parquet.reorder_filters = true;
+            }
+            ConfigProfile::Etl => {
+                self.execution.batch_size = 16384;
+                self.execution.collect_statistics = true;
+                self.execution.parquet.allow_single_file_parallelism = true;
+                self.execution.parquet.maximum_parallel_row_group_writers = 4;
+                self.execution
+                    .parquet
+                    .maximum_buffered_record_batches_per_stream = 8;
+            }
+        }
+    }
+
+    /// Check that the options are valid and consistent with each other
+    pub fn validate(&self) -> Result<()> {
+        if self.execution.batch_size == 0 {
+            return _config_err!(
+                "datafusion.execution.batch_size must be greater than 0"
+            );
+        }
+        if self.execution.target_partitions == 0 {
+            return _config_err!(
+                "datafusion.execution.target_partitions must be greater than 0"
+            );
+        }
+        if self.execution.planning_concurrency == 0 {
+            return _config_err!(
+                "datafusion.execution.planning_concurrency must be greater than 0"
+            );
+        }
+        if self.catalog.location.is_some() != self.catalog.format.is_some() {
+            return _config_err!(
+                "datafusion.catalog.location and datafusion.catalog.format must be set together"
+            );
+        }
+        if self.execution.parquet.reorder_filters
+            && !self.execution.parquet.pushdown_filters
+        {
+            return _config_err!(
+                "datafusion.execution.parquet.reorder_filters requires datafusion.execution.parquet.pushdown_filters"
+            );
+        }
+        if self.explain.logical_plan_only && self.explain.physical_plan_only {
+            return _config_err!(
+                "datafusion.explain.logical_plan_only and datafusion.explain.physical_plan_only cannot both be set"
+            );
+        }
+        Ok(())
+    }
+
     /// Returns the [`ConfigEntry`] stored within this [`ConfigOptions`]
     pub fn entries(&self) -> Vec<ConfigEntry> {
         struct Visitor(Vec<ConfigEntry>);
@@ -892,6 +1201,12 @@ pub trait ConfigField {
     fn visit<V: Visit>(&self, v: &mut V, key: &str, description: &'static str);
 
     fn set(&mut self, key: &str, value: &str) -> Result<()>;
+
+    /// Reset the option at `key` to its default value, or all the options
+    /// of the namespace if `key` is empty
+    fn reset(&mut self, key: &str) -> Result<()> {
+        _config_err!("Config value \"{key}\" cannot be reset")
+    }
 }
 
 impl<F: ConfigField + Default> ConfigField for Option<F> {
@@ -905,6 +1220,13 @@ impl<F: ConfigField + Default> ConfigField for Option<F> {
     fn set(&mut self, key: &str, value: &str) -> Result<()> {
         self.get_or_insert_with(Default::default).set(key, value)
     }
+
+    fn reset(&mut self, key: &str) -> Result<()> {
+        match self {
+            Some(s) => s.reset(key),
+            None => Ok(()),
+        }
+    }
 }
 
 #[macro_export]
@@ -976,6 +1298,183 @@ impl ConfigField for CompressionTypeVariant {
     }
 }
 
+/// How aggregates of decimals handle results that do not fit in their data type
+///
+/// See [`AggregateOptions::decimal_overflow_mode`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum DecimalOverflowMode {
+    /// Do not check the results, whose sums wrap around if they overflow the
+    /// native type, as before the other modes were added
+    #[default]
+    Unchecked,
+    /// Return an error
+    Error,
+    /// Return the largest (or smallest) value of the data type
+    Saturate,
+    /// Aggregate `Decimal128` values as `Decimal256`, and return an error if
+    /// the result still does not fit
+    Promote,
+}
+
+impl FromStr for DecimalOverflowMode {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "unchecked" => Ok(Self::Unchecked),
+            "error" => Ok(Self::Error),
+            "saturate" => Ok(Self::Saturate),
+            "promote" => Ok(Self::Promote),
+            _ => _config_err!(
+                "Invalid decimal overflow mode: {s}. Expected one of: unchecked, error, saturate, promote"
+            ),
+        }
+    }
+}
+
+impl Display for DecimalOverflowMode {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let mode = match self {
+            Self::Unchecked => "unchecked",
+            Self::Error => "error",
+            Self::Saturate => "saturate",
+            Self::Promote => "promote",
+        };
+        write!(f, "{mode}")
+    }
+}
+
+config_field!(DecimalOverflowMode);
+
+/// Compression codec used for spill files
+///
+/// See [`ExecutionOptions::spill_compression`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum SpillCompression {
+    /// Write spill files without compression
+    #[default]
+    Uncompressed,
+    /// Compress spill files with LZ4 (frame format)
+    Lz4Frame,
+    /// Compress spill files with Zstandard
+    Zstd,
+}
+
+impl FromStr for SpillCompression {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "uncompressed" => Ok(Self::Uncompressed),
+            "lz4_frame" => Ok(Self::Lz4Frame),
+            "zstd" => Ok(Self::Zstd),
+            _ => _config_err!(
+                "Invalid spill compression: {s}. Expected one of: uncompressed, lz4_frame, zstd"
+            ),
+        }
+    }
+}
+
+impl Display for SpillCompression {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let compression = match self {
+            Self::Uncompressed => "uncompressed",
+            Self::Lz4Frame => "lz4_frame",
+            Self::Zstd => "zstd",
+        };
+        write!(f, "{compression}")
+    }
+}
+
+config_field!(SpillCompression);
+
+/// Hash function used to hash rows
+///
+/// See [`ExecutionOptions::hash_function`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum HashFunction {
+    /// ahash, the fastest, whose hashes depend on the CPU features
+    #[default]
+    AHash,
+    /// xxHash64
+    XxHash64,
+    /// CRC-32
+    Crc32,
+}
+
+impl FromStr for HashFunction {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "ahash" => Ok(Self::AHash),
+            "xxhash64" => Ok(Self::XxHash64),
+            "crc32" => Ok(Self::Crc32),
+            _ => _config_err!(
+                "Invalid hash function: {s}. Expected one of: ahash, xxhash64, crc32"
+            ),
+        }
+    }
+}
+
+impl Display for HashFunction {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let function = match self {
+            Self::AHash => "ahash",
+            Self::XxHash64 => "xxhash64",
+            Self::Crc32 => "crc32",
+        };
+        write!(f, "{function}")
+    }
+}
+
+config_field!(HashFunction);
+
+/// A named group of coherent options for a kind of workload
+///
+/// See [`ConfigOptions::apply_profile`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ConfigProfile {
+    /// Bound the memory used by the queries: smaller batches and sort
+    /// reservations, compressed spill files, sort merge joins rather than
+    /// hash joins, and fewer batches buffered by the writers
+    LowMemory,
+    /// Answer ad-hoc queries quickly: filters evaluated while decoding the
+    /// parquet files, no statistics collected when registering tables, and
+    /// `information_schema` enabled for `SHOW`
+    Interactive,
+    /// Favor the throughput of large batch jobs: larger batches, statistics
+    /// collected when registering tables, and parquet files written in
+    /// parallel
+    Etl,
+}
+
+impl FromStr for ConfigProfile {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_lowercase().as_str() {
+            "low_memory" => Ok(Self::LowMemory),
+            "interactive" => Ok(Self::Interactive),
+            "etl" => Ok(Self::Etl),
+            _ => _config_err!(
+                "Invalid config profile: {s}. Expected one of: low_memory, interactive, etl"
+            ),
+        }
+    }
+}
+
+impl Display for ConfigProfile {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let profile = match self {
+            Self::LowMemory => "low_memory",
+            Self::Interactive => "interactive",
+            Self::Etl => "etl",
+        };
+        write!(f, "{profile}")
+    }
+}
+
 /// An implementation trait used to recursively walk configuration
 pub trait Visit {
     fn some<V: Display>(&mut self, key: &str, value: V, description: &'static str);
@@ -1660,7 +2159,8 @@ mod tests {
     use std::collections::HashMap;
 
     use crate::config::{
-        ConfigEntry, ConfigExtension, ExtensionOptions, Extensions, TableOptions,
+        ConfigEntry, ConfigExtension, ConfigOptions, ConfigProfile, ExtensionOptions,
+        Extensions, TableOptions,
     };
     use crate::FileType;
 
@@ -1734,6 +2234,122 @@ mod tests {
         );
     }
 
+    #[test]
+    fn reset_config_options() {
+        let mut extension = Extensions::new();
+        extension.insert(TestExtensionConfig::default());
+        let mut config = ConfigOptions::new().with_extensions(extension);
+        config
+            .set("datafusion.execution.batch_size", "1024")
+            .unwrap();
+        config
+            .set("datafusion.execution.parquet.pushdown_filters", "true")
+            .unwrap();
+        config
+            .set("datafusion.execution.time_zone", "Europe/Paris")
+            .unwrap();
+
+        config.reset("datafusion.execution.batch_size").unwrap();
+        assert_eq!(config.execution.batch_size, 8192);
+        assert!(config.execution.parquet.pushdown_filters);
+
+        // resetting a namespace resets all its options
+        config.reset("datafusion.execution.parquet").unwrap();
+        assert!(!config.execution.parquet.pushdown_filters);
+        config
+            .set("datafusion.sql_parser.dialect", "postgresql")
+            .unwrap();
+        config.reset("datafusion.sql_parser").unwrap();
+        assert_eq!(config.sql_parser.dialect, "generic");
+
+        config.reset("datafusion.execution.time_zone").unwrap();
+        assert_eq!(config.execution.time_zone.as_deref(), Some("+00:00"));
+
+        let err = config.reset("datafusion.execution.no_such_option");
+        assert!(err.is_err());
+        let err = config.reset("test.bootstrap.servers");
+        assert!(err.is_err());
+
+        config.set("datafusion.optimizer.max_passes", "1").unwrap();
+        config.reset_all();
+        assert_eq!(config.optimizer.max_passes, 3);
+        assert!(config.extensions.get::<TestExtensionConfig>().is_some());
+    }
+
+    #[test]
+    fn config_profiles() {
+        let profile: ConfigProfile = "LOW_MEMORY".parse().unwrap();
+        assert_eq!(profile, ConfigProfile::LowMemory);
+        assert_eq!(profile.to_string(), "low_memory");
+        assert!("olap".parse::<ConfigProfile>().is_err());
+
+        for profile in [
+            ConfigProfile::LowMemory,
+            ConfigProfile::Interactive,
+            ConfigProfile::Etl,
+        ] {
+            let mut config = ConfigOptions::new();
+            config.apply_profile(profile);
+            config.validate().unwrap();
+        }
+
+        let mut config = ConfigOptions::new();
+        config
+            .set("datafusion.execution.batch_size", "100")
+            .unwrap();
+        config.apply_profile(ConfigProfile::Interactive);
+        assert_eq!(config.execution.batch_size, 100);
+        assert!(config.execution.parquet.reorder_filters);
+    }
+
+    #[test]
+    fn validate_config_options() {
+        let mut config = ConfigOptions::new();
+        config.validate().unwrap();
+
+        config.catalog.location = Some("/tmp".to_string());
+        let err = config.validate().unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Invalid or Unsupported Configuration: datafusion.catalog.location and \
+            datafusion.catalog.format must be set together"
+        );
+        config.catalog.format = Some("csv".to_string());
+        config.validate().unwrap();
+
+        config.explain.logical_plan_only = true;
+        config.explain.physical_plan_only = true;
+        assert!(config.validate().is_err());
+        config.explain.physical_plan_only = false;
+
+        config.execution.batch_size = 0;
+        assert!(config.validate().is_err());
+    }
+
+    #[test]
+    fn from_string_hash_map_rejects_unknown_keys() {
+        let settings = HashMap::from([
+            (
+                "datafusion.execution.batch_size".to_string(),
+                "10".to_string(),
+            ),
+            ("other.key".to_string(), "ignored".to_string()),
+        ]);
+        let config = ConfigOptions::from_string_hash_map(settings).unwrap();
+        assert_eq!(config.execution.batch_size, 10);
+
+        let settings = HashMap::from([(
+            "datafusion.execution.bach_size".to_string(),
+            "10".to_string(),
+        )]);
+        let err = ConfigOptions::from_string_hash_map(settings).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Invalid or Unsupported Configuration: Unknown config key \
+            \"datafusion.execution.bach_size\""
+        );
+    }
+
     #[test]
     fn csv_u8_table_options() {
         let mut table_config = TableOptions::new();
@@ -43,6 +43,9 @@ pub enum PlanType {
         /// The name of the optimizer which produced this plan
         optimizer_name: String,
     },
+    /// The elapsed time of each optimizer rule and the changes it made to
+    /// the LogicalPlan, in each optimizer pass
+    OptimizerRuleRuns,
     /// The final, fully optimized LogicalPlan that was converted to a physical plan
     FinalLogicalPlan,
     /// The initial physical plan, prepared for execution
@@ -71,6 +74,7 @@ impl Display for PlanType {
             PlanType::OptimizedLogicalPlan { optimizer_name } => {
                 write!(f, "logical_plan after {optimizer_name}")
             }
+            PlanType::OptimizerRuleRuns => write!(f, "optimizer_rule_runs"),
             PlanType::FinalLogicalPlan => write!(f, "logical_plan"),
             PlanType::InitialPhysicalPlan => write!(f, "initial_physical_plan"),
             PlanType::InitialPhysicalPlanWithStats => {
@@ -38,6 +38,7 @@ mod tests {
         FileType, Result,
     };
 
+    use arrow_schema::{DataType, Field, Schema};
     use parquet::{
         basic::{Compression, Encoding, ZstdLevel},
         file::properties::{EnabledStatistics, WriterVersion},
@@ -266,6 +267,71 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_writeroptions_parquet_nested_columns() -> Result<()> {
+        let mut option_map: HashMap<String, String> = HashMap::new();
+        option_map.insert(
+            "format.bloom_filter_enabled::s".to_owned(),
+            "true".to_owned(),
+        );
+        option_map.insert(
+            "format.bloom_filter_enabled::s.b".to_owned(),
+            "false".to_owned(),
+        );
+        option_map.insert(
+            "format.bloom_filter_ndv::tags.item".to_owned(),
+            "100".to_owned(),
+        );
+        option_map.insert(
+            "format.compression::missing".to_owned(),
+            "snappy".to_owned(),
+        );
+
+        let mut table_config = TableOptions::new();
+        table_config.set_file_format(FileType::PARQUET);
+        table_config.alter_with_string_hash_map(&option_map)?;
+
+        let schema = Schema::new(vec![
+            Field::new(
+                "s",
+                DataType::Struct(
+                    vec![
+                        Field::new("a", DataType::Int32, true),
+                        Field::new("b", DataType::Utf8, true),
+                    ]
+                    .into(),
+                ),
+                true,
+            ),
+            Field::new_list("tags", Field::new("item", DataType::Utf8, true), true),
+        ]);
+        let parquet_options =
+            ParquetWriterOptions::try_new_with_schema(&table_config.parquet, &schema)?;
+        let properties = parquet_options.writer_options();
+
+        let path = |path: &str| {
+            ColumnPath::from(path.split('.').map(String::from).collect::<Vec<_>>())
+        };
+        // the options of the struct apply to its leaves, unless overridden
+        assert!(properties.bloom_filter_properties(&path("s.a")).is_some());
+        assert!(properties.bloom_filter_properties(&path("s.b")).is_none());
+        // the repeated group of the list can be omitted
+        assert_eq!(
+            properties
+                .bloom_filter_properties(&path("tags.list.item"))
+                .expect("expected bloom filter enabled for tags")
+                .ndv,
+            100
+        );
+        // the options of the other columns are kept
+        assert_eq!(
+            properties.compression(&path("missing")),
+            Compression::SNAPPY
+        );
+
+        Ok(())
+    }
+
     #[test]
     // for StatementOptions
     fn test_writeroptions_csv_from_statement_options() -> Result<()> {
@@ -17,12 +17,19 @@
 
 //! Options related to how parquet files should be written
 
-use crate::{config::TableParquetOptions, DataFusionError, Result};
+use std::collections::HashMap;
 
+use crate::{
+    config::{ColumnOptions, TableParquetOptions},
+    DataFusionError, Result,
+};
+
+use arrow_schema::Schema;
 use parquet::{
-    basic::{BrotliLevel, GzipLevel, ZstdLevel},
+    arrow::arrow_to_parquet_schema,
+    basic::{BrotliLevel, ConvertedType, GzipLevel, LogicalType, Repetition, ZstdLevel},
     file::properties::{EnabledStatistics, WriterProperties, WriterVersion},
-    schema::types::ColumnPath,
+    schema::types::{ColumnPath, Type},
 };
 
 /// Options for writing parquet files
@@ -41,6 +48,157 @@ impl ParquetWriterOptions {
     pub fn writer_options(&self) -> &WriterProperties {
         &self.writer_options
     }
+
+    /// Creates the options for writing parquet files of the schema `schema`.
+    ///
+    /// Unlike the options created from the [`TableParquetOptions`] alone,
+    /// the column specific options are resolved against the leaves of the
+    /// parquet schema: the options of a struct, list or map column apply to
+    /// all the leaves below it, unless they are overridden by the options of
+    /// a nested column, and the paths of the columns in lists and maps can
+    /// omit their repeated groups, e.g. `tags.item` for the leaf
+    /// `tags.list.item`.
+    pub fn try_new_with_schema(
+        parquet_options: &TableParquetOptions,
+        schema: &Schema,
+    ) -> Result<Self> {
+        if parquet_options.column_specific_options.is_empty() {
+            return Self::try_from(parquet_options);
+        }
+        let mut resolved = parquet_options.clone();
+        resolved.column_specific_options =
+            resolve_column_options(&parquet_options.column_specific_options, schema)?;
+        Self::try_from(&resolved)
+    }
+}
+
+/// Returns the column specific options `options` for the leaves of the
+/// parquet schema of `schema`, by their path. The options of the columns
+/// which are not in the schema are kept as they are.
+fn resolve_column_options(
+    options: &HashMap<String, ColumnOptions>,
+    schema: &Schema,
+) -> Result<HashMap<String, ColumnOptions>> {
+    let schema_descr = arrow_to_parquet_schema(schema)?;
+    let mut leaves = vec![];
+    for field in schema_descr.root_schema().get_fields() {
+        collect_leaves(field, false, &mut vec![], &mut vec![], &mut leaves);
+    }
+
+    // the options of the outer columns are applied first, for the options
+    // of the nested columns to override them
+    let mut options = options.iter().collect::<Vec<_>>();
+    options.sort_by(|(a, _), (b, _)| {
+        (a.split('.').count(), a).cmp(&(b.split('.').count(), b))
+    });
+
+    let mut resolved: HashMap<String, ColumnOptions> = HashMap::new();
+    for (column, column_options) in options {
+        let parts = column.split('.').collect::<Vec<_>>();
+        let starts_with = |path: &[String]| {
+            path.len() >= parts.len() && path.iter().zip(&parts).all(|(a, b)| a == b)
+        };
+        let mut matched = false;
+        for (path, logical_path) in &leaves {
+            if starts_with(path) || starts_with(logical_path) {
+                matched = true;
+                override_column_options(
+                    resolved.entry(path.join(".")).or_default(),
+                    column_options,
+                );
+            }
+        }
+        if !matched {
+            override_column_options(
+                resolved.entry(column.clone()).or_default(),
+                column_options,
+            );
+        }
+    }
+    Ok(resolved)
+}
+
+/// Appends the paths of the leaves of the parquet type `parquet_type` to
+/// `leaves`, along with their paths without the repeated groups of the lists
+/// and the maps. `skip` is true if `parquet_type` is such a repeated group.
+fn collect_leaves(
+    parquet_type: &Type,
+    skip: bool,
+    path: &mut Vec<String>,
+    logical_path: &mut Vec<String>,
+    leaves: &mut Vec<(Vec<String>, Vec<String>)>,
+) {
+    path.push(parquet_type.name().to_owned());
+    if !skip {
+        logical_path.push(parquet_type.name().to_owned());
+    }
+    if parquet_type.is_primitive() {
+        leaves.push((path.clone(), logical_path.clone()));
+    } else {
+        let info = parquet_type.get_basic_info();
+        let is_list_or_map = matches!(
+            info.converted_type(),
+            ConvertedType::LIST | ConvertedType::MAP
+        ) || matches!(
+            info.logical_type(),
+            Some(LogicalType::List | LogicalType::Map)
+        );
+        for field in parquet_type.get_fields() {
+            let field_info = field.get_basic_info();
+            let repeated_group = field.is_group()
+                && field_info.has_repetition()
+                && field_info.repetition() == Repetition::REPEATED;
+            collect_leaves(
+                field,
+                is_list_or_map && repeated_group,
+                path,
+                logical_path,
+                leaves,
+            );
+        }
+    }
+    path.pop();
+    if !skip {
+        logical_path.pop();
+    }
+}
+
+/// Sets the options of `target` which are set in `options`
+fn override_column_options(target: &mut ColumnOptions, options: &ColumnOptions) {
+    let ColumnOptions {
+        bloom_filter_enabled,
+        encoding,
+        dictionary_enabled,
+        compression,
+        statistics_enabled,
+        bloom_filter_fpp,
+        bloom_filter_ndv,
+        max_statistics_size,
+    } = options;
+    if bloom_filter_enabled.is_some() {
+        target.bloom_filter_enabled = *bloom_filter_enabled;
+    }
+    if encoding.is_some() {
+        target.encoding.clone_from(encoding);
+    }
+    if dictionary_enabled.is_some() {
+        target.dictionary_enabled = *dictionary_enabled;
+    }
+    if compression.is_some() {
+        target.compression.clone_from(compression);
+    }
+    if statistics_enabled.is_some() {
+        target.statistics_enabled.clone_from(statistics_enabled);
+    }
+    if bloom_filter_fpp.is_some() {
+        target.bloom_filter_fpp = *bloom_filter_fpp;
+    }
+    if bloom_filter_ndv.is_some() {
+        target.bloom_filter_ndv = *bloom_filter_ndv;
+    }
+    if max_statistics_size.is_some() {
+        target.max_statistics_size = *max_statistics_size;
+    }
 }
 
 impl TryFrom<&TableParquetOptions> for ParquetWriterOptions {
@@ -371,6 +371,20 @@ impl FunctionalDependencies {
         FunctionalDependencies::new(projected_func_dependencies)
     }
 
+    /// Returns true if the rows are uniquely identified by the columns at
+    /// `indices`, i.e. if they include the determinant key of a dependence
+    /// whose key occurs only once and can not be NULL.
+    pub fn is_unique_key(&self, indices: &[usize]) -> bool {
+        self.deps.iter().any(|dependence| {
+            dependence.mode == Dependency::Single
+                && !dependence.nullable
+                && dependence
+                    .source_indices
+                    .iter()
+                    .all(|idx| indices.contains(idx))
+        })
+    }
+
     /// This function joins this set of functional dependencies with the `other`
     /// according to the given `join_type`.
     pub fn join(
@@ -378,6 +392,45 @@ impl FunctionalDependencies {
         other: &FunctionalDependencies,
         join_type: &JoinType,
         left_cols_len: usize,
+    ) -> FunctionalDependencies {
+        self.join_impl(other, join_type, left_cols_len, false, false)
+    }
+
+    /// Joins this set of functional dependencies with the `other` like
+    /// [`Self::join`], for an equijoin on the columns at the indices `on` of
+    /// the left and the right sides.
+    ///
+    /// If the join keys of a side are a unique key of the other side, each
+    /// row of the side matches at most one row of the other side, and its
+    /// determinant keys which occur only once still occur only once after the
+    /// join. Their target indices are not extended to the columns of the other
+    /// side, see [`Self::extend_target_indices`].
+    pub fn join_on(
+        &self,
+        other: &FunctionalDependencies,
+        join_type: &JoinType,
+        left_cols_len: usize,
+        on: &[(usize, usize)],
+    ) -> FunctionalDependencies {
+        let (left_keys, right_keys): (Vec<_>, Vec<_>) = on.iter().copied().unzip();
+        let left_matches_once = !on.is_empty() && other.is_unique_key(&right_keys);
+        let right_matches_once = !on.is_empty() && self.is_unique_key(&left_keys);
+        self.join_impl(
+            other,
+            join_type,
+            left_cols_len,
+            left_matches_once,
+            right_matches_once,
+        )
+    }
+
+    fn join_impl(
+        &self,
+        other: &FunctionalDependencies,
+        join_type: &JoinType,
+        left_cols_len: usize,
+        left_matches_once: bool,
+        right_matches_once: bool,
     ) -> FunctionalDependencies {
         // Get mutable copies of left and right side dependencies:
         let mut right_func_dependencies = other.clone();
@@ -388,11 +441,17 @@ impl FunctionalDependencies {
                 // Add offset to right schema:
                 right_func_dependencies.add_offset(left_cols_len);
 
-                // Result may have multiple values, update the dependency mode:
-                left_func_dependencies =
-                    left_func_dependencies.with_dependency(Dependency::Multi);
-                right_func_dependencies =
-                    right_func_dependencies.with_dependency(Dependency::Multi);
+                // Result may have multiple values, unless the rows of a side
+                // match at most one row of the other side, update the
+                // dependency mode:
+                if !left_matches_once {
+                    left_func_dependencies =
+                        left_func_dependencies.with_dependency(Dependency::Multi);
+                }
+                if !right_matches_once {
+                    right_func_dependencies =
+                        right_func_dependencies.with_dependency(Dependency::Multi);
+                }
 
                 if *join_type == JoinType::Left {
                     // Downgrade the right side, since it may have additional NULL values:
@@ -678,4 +737,41 @@ mod tests {
         )]);
         assert_eq!(res, expected);
     }
+
+    #[test]
+    fn join_on_unique_keys() {
+        // Primary keys of tables with 2 columns
+        let primary_key = |idx| {
+            FunctionalDependencies::new(vec![FunctionalDependence::new(
+                vec![idx],
+                vec![0, 1],
+                false,
+            )
+            .with_mode(Dependency::Single)])
+        };
+        let left = primary_key(0);
+        let right = primary_key(0);
+
+        // The left rows match at most one right row on its primary key
+        let mut res = left.join_on(&right, &JoinType::Inner, 2, &[(1, 0)]);
+        res.extend_target_indices(4);
+        let expected = FunctionalDependencies::new(vec![
+            FunctionalDependence::new(vec![0], vec![0, 1, 2, 3], false)
+                .with_mode(Dependency::Single),
+            FunctionalDependence::new(vec![2], vec![2, 3], false),
+        ]);
+        assert_eq!(res, expected);
+        assert!(res.is_unique_key(&[0, 3]));
+        assert!(!res.is_unique_key(&[2]));
+
+        // Without join keys, the rows may match multiple rows
+        let res = left.join_on(&right, &JoinType::Inner, 2, &[]);
+        assert_eq!(res, left.join(&right, &JoinType::Inner, 2));
+        assert!(!res.is_unique_key(&[0, 1, 2, 3]));
+
+        // The unique keys of the right side of a left join may be NULL
+        let res = left.join_on(&right, &JoinType::Left, 2, &[(0, 0)]);
+        assert!(res.is_unique_key(&[0]));
+        assert!(!res.is_unique_key(&[2]));
+    }
 }
@@ -17,6 +17,7 @@
 
 //! Functionality used both on logical and physical plans
 
+use std::hash::{Hash, Hasher};
 use std::sync::Arc;
 
 use ahash::RandomState;
@@ -30,8 +31,104 @@ use crate::cast::{
     as_large_list_array, as_list_array, as_primitive_array, as_string_array,
     as_struct_array,
 };
+use crate::config::HashFunction;
 use crate::error::{Result, _internal_err};
 
+/// The state of a hash function hashing the values of arrays, such as
+/// [`RandomState`] or [`SeededHashState`]
+pub trait HashState {
+    /// Returns the hash of `value`
+    fn hash_one<T: Hash>(&self, value: T) -> u64;
+
+    /// Returns the hash of `value`, whose little-endian bytes are `bytes`.
+    ///
+    /// Hashes that must be the same on all platforms hash `bytes`, as the
+    /// [`Hash`] implementations write native-endian integers and lengths.
+    fn hash_le_bytes<T: Hash + ?Sized>(&self, value: &T, _bytes: &[u8]) -> u64 {
+        self.hash_one(value)
+    }
+}
+
+impl HashState for RandomState {
+    fn hash_one<T: Hash>(&self, value: T) -> u64 {
+        RandomState::hash_one(self, value)
+    }
+}
+
+/// A [`HashState`] of the [`HashFunction`] and the seed configured with
+/// [`ExecutionOptions::hash_function`] and [`ExecutionOptions::hash_seed`].
+///
+/// The xxHash64 and CRC-32 hashes of a value are the hashes of its
+/// little-endian bytes, so they are the same on all platforms, unlike its
+/// ahash hashes which depend on the CPU features, and can be used to partition
+/// rows the same way in different processes.
+///
+/// [`ExecutionOptions::hash_function`]: crate::config::ExecutionOptions::hash_function
+/// [`ExecutionOptions::hash_seed`]: crate::config::ExecutionOptions::hash_seed
+#[derive(Debug, Clone)]
+pub enum SeededHashState {
+    /// ahash, with all its seeds set to the seed
+    AHash(RandomState),
+    /// xxHash64 with a seed
+    XxHash64(u64),
+    /// CRC-32 with the seed as the initial value
+    Crc32(u32),
+}
+
+impl SeededHashState {
+    /// Creates the state of `function` with `seed`. The CRC-32 hashes use the
+    /// lower 32 bits of `seed`.
+    pub fn new(function: HashFunction, seed: u64) -> Self {
+        match function {
+            HashFunction::AHash => {
+                Self::AHash(RandomState::with_seeds(seed, seed, seed, seed))
+            }
+            HashFunction::XxHash64 => Self::XxHash64(seed),
+            HashFunction::Crc32 => Self::Crc32(seed as u32),
+        }
+    }
+}
+
+impl Default for SeededHashState {
+    fn default() -> Self {
+        Self::new(HashFunction::default(), 0)
+    }
+}
+
+impl HashState for SeededHashState {
+    fn hash_one<T: Hash>(&self, value: T) -> u64 {
+        match self {
+            Self::AHash(state) => state.hash_one(value),
+            Self::XxHash64(seed) => {
+                let mut hasher = twox_hash::XxHash64::with_seed(*seed);
+                value.hash(&mut hasher);
+                hasher.finish()
+            }
+            Self::Crc32(seed) => {
+                let mut hasher = crc32fast::Hasher::new_with_initial(*seed);
+                value.hash(&mut hasher);
+                hasher.finish()
+            }
+        }
+    }
+
+    fn hash_le_bytes<T: Hash + ?Sized>(&self, value: &T, bytes: &[u8]) -> u64 {
+        match self {
+            Self::AHash(state) => state.hash_one(value),
+            Self::XxHash64(seed) => {
+                let mut hasher = twox_hash::XxHash64::with_seed(*seed);
+                hasher.write(bytes);
+                hasher.finish()
+            }
+            Self::Crc32(seed) => {
+                let mut hasher = crc32fast::Hasher::new_with_initial(*seed);
+                hasher.write(bytes);
+                hasher.finish()
+            }
+        }
+    }
+}
+
 // Combines two hashes into one hash
 #[inline]
 fn combine_hashes(l: u64, r: u64) -> u64 {
@@ -39,25 +136,29 @@ fn combine_hashes(l: u64, r: u64) -> u64 {
     hash.wrapping_mul(37).wrapping_add(r)
 }
 
-fn hash_null(random_state: &RandomState, hashes_buffer: &'_ mut [u64], mul_col: bool) {
+fn hash_null<S: HashState>(
+    random_state: &S,
+    hashes_buffer: &'_ mut [u64],
+    mul_col: bool,
+) {
     if mul_col {
         hashes_buffer.iter_mut().for_each(|hash| {
             // stable hash for null value
-            *hash = combine_hashes(random_state.hash_one(1), *hash);
+            *hash = combine_hashes(1i32.hash_one(random_state), *hash);
         })
     } else {
         hashes_buffer.iter_mut().for_each(|hash| {
-            *hash = random_state.hash_one(1);
+            *hash = 1i32.hash_one(random_state);
         })
     }
 }
 
 pub trait HashValue {
-    fn hash_one(&self, state: &RandomState) -> u64;
+    fn hash_one<S: HashState>(&self, state: &S) -> u64;
 }
 
 impl<'a, T: HashValue + ?Sized> HashValue for &'a T {
-    fn hash_one(&self, state: &RandomState) -> u64 {
+    fn hash_one<S: HashState>(&self, state: &S) -> u64 {
         T::hash_one(self, state)
     }
 }
@@ -65,20 +166,38 @@ impl<'a, T: HashValue + ?Sized> HashValue for &'a T {
 macro_rules! hash_value {
     ($($t:ty),+) => {
         $(impl HashValue for $t {
-            fn hash_one(&self, state: &RandomState) -> u64 {
-                state.hash_one(self)
+            fn hash_one<S: HashState>(&self, state: &S) -> u64 {
+                state.hash_le_bytes(self, &self.to_le_bytes())
             }
         })+
     };
 }
 hash_value!(i8, i16, i32, i64, i128, i256, u8, u16, u32, u64);
-hash_value!(bool, str, [u8]);
+
+impl HashValue for bool {
+    fn hash_one<S: HashState>(&self, state: &S) -> u64 {
+        state.hash_le_bytes(self, &[*self as u8])
+    }
+}
+
+impl HashValue for str {
+    fn hash_one<S: HashState>(&self, state: &S) -> u64 {
+        state.hash_le_bytes(self, self.as_bytes())
+    }
+}
+
+impl HashValue for [u8] {
+    fn hash_one<S: HashState>(&self, state: &S) -> u64 {
+        state.hash_le_bytes(self, self)
+    }
+}
 
 macro_rules! hash_float_value {
     ($(($t:ty, $i:ty)),+) => {
         $(impl HashValue for $t {
-            fn hash_one(&self, state: &RandomState) -> u64 {
-                state.hash_one(<$i>::from_ne_bytes(self.to_ne_bytes()))
+            fn hash_one<S: HashState>(&self, state: &S) -> u64 {
+                let bits = <$i>::from_ne_bytes(self.to_ne_bytes());
+                state.hash_le_bytes(&bits, &self.to_le_bytes())
             }
         })+
     };
@@ -88,9 +207,9 @@ hash_float_value!((half::f16, u16), (f32, u32), (f64, u64));
 /// Builds hash values of PrimitiveArray and writes them into `hashes_buffer`
 /// If `rehash==true` this combines the previous hash value in the buffer
 /// with the new hash using `combine_hashes`
-fn hash_array_primitive<T>(
+fn hash_array_primitive<T, S: HashState>(
     array: &PrimitiveArray<T>,
-    random_state: &RandomState,
+    random_state: &S,
     hashes_buffer: &mut [u64],
     rehash: bool,
 ) where
@@ -133,9 +252,9 @@ fn hash_array_primitive<T>(
 /// Hashes one array into the `hashes_buffer`
 /// If `rehash==true` this combines the previous hash value in the buffer
 /// with the new hash using `combine_hashes`
-fn hash_array<T>(
+fn hash_array<T, S: HashState>(
     array: T,
-    random_state: &RandomState,
+    random_state: &S,
     hashes_buffer: &mut [u64],
     rehash: bool,
 ) where
@@ -178,9 +297,9 @@ fn hash_array<T>(
 }
 
 /// Hash the values in a dictionary array
-fn hash_dictionary<K: ArrowDictionaryKeyType>(
+fn hash_dictionary<K: ArrowDictionaryKeyType, S: HashState>(
     array: &DictionaryArray<K>,
-    random_state: &RandomState,
+    random_state: &S,
     hashes_buffer: &mut [u64],
     multi_col: bool,
 ) -> Result<()> {
@@ -208,9 +327,9 @@ fn hash_dictionary<K: ArrowDictionaryKeyType>(
     Ok(())
 }
 
-fn hash_struct_array(
+fn hash_struct_array<S: HashState>(
     array: &StructArray,
-    random_state: &RandomState,
+    random_state: &S,
     hashes_buffer: &mut [u64],
 ) -> Result<()> {
     let nulls = array.nulls();
@@ -234,9 +353,9 @@ fn hash_struct_array(
     Ok(())
 }
 
-fn hash_list_array<OffsetSize>(
+fn hash_list_array<OffsetSize, S: HashState>(
     array: &GenericListArray<OffsetSize>,
-    random_state: &RandomState,
+    random_state: &S,
     hashes_buffer: &mut [u64],
 ) -> Result<()>
 where
@@ -267,9 +386,9 @@ where
     Ok(())
 }
 
-fn hash_fixed_list_array(
+fn hash_fixed_list_array<S: HashState>(
     array: &FixedSizeListArray,
-    random_state: &RandomState,
+    random_state: &S,
     hashes_buffer: &mut [u64],
 ) -> Result<()> {
     let values = array.values().clone();
@@ -304,9 +423,9 @@ fn hash_fixed_list_array(
 ///
 /// See comments on `hashes_buffer` for more details
 #[cfg(feature = "force_hash_collisions")]
-pub fn create_hashes<'a>(
+pub fn create_hashes<'a, S: HashState>(
     _arrays: &[ArrayRef],
-    _random_state: &RandomState,
+    _random_state: &S,
     hashes_buffer: &'a mut Vec<u64>,
 ) -> Result<&'a mut Vec<u64>> {
     for hash in hashes_buffer.iter_mut() {
@@ -353,9 +472,9 @@ pub fn create_row_hashes<'a>(
 /// The number of rows to hash is determined by `hashes_buffer.len()`.
 /// `hashes_buffer` should be pre-sized appropriately
 #[cfg(not(feature = "force_hash_collisions"))]
-pub fn create_hashes<'a>(
+pub fn create_hashes<'a, S: HashState>(
     arrays: &[ArrayRef],
-    random_state: &RandomState,
+    random_state: &S,
     hashes_buffer: &'a mut Vec<u64>,
 ) -> Result<&'a mut Vec<u64>> {
     for (i, col) in arrays.iter().enumerate() {
@@ -484,6 +603,78 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    #[cfg(not(feature = "force_hash_collisions"))]
+    fn create_hashes_with_seeded_hash_state() -> Result<()> {
+        let array: ArrayRef = Arc::new(StringArray::from(vec![
+            Some("foo"),
+            None,
+            Some("bar"),
+            Some("foo"),
+        ]));
+        let hashes = |function, seed| {
+            let mut hashes = vec![0; array.len()];
+            let state = SeededHashState::new(function, seed);
+            create_hashes(&[array.clone()], &state, &mut hashes).unwrap();
+            hashes
+        };
+
+        for function in [
+            HashFunction::AHash,
+            HashFunction::XxHash64,
+            HashFunction::Crc32,
+        ] {
+            let seeded = hashes(function, 1);
+            assert_eq!(seeded[0], seeded[3]);
+            assert_ne!(seeded[0], seeded[2]);
+            // the hashes only depend on the function and the seed
+            assert_eq!(seeded, hashes(function, 1));
+            assert_ne!(seeded, hashes(function, 2));
+        }
+        assert_ne!(
+            hashes(HashFunction::XxHash64, 0),
+            hashes(HashFunction::Crc32, 0)
+        );
+
+        // the default state is ahash with a zero seed
+        let mut expected = vec![0; array.len()];
+        create_hashes(
+            &[array.clone()],
+            &RandomState::with_seeds(0, 0, 0, 0),
+            &mut expected,
+        )?;
+        assert_eq!(hashes(HashFunction::AHash, 0), expected);
+        Ok(())
+    }
+
+    #[test]
+    #[cfg(not(feature = "force_hash_collisions"))]
+    fn seeded_hashes_of_little_endian_bytes() -> Result<()> {
+        let arrays: [(ArrayRef, &[u8]); 3] = [
+            (Arc::new(Int32Array::from(vec![258])), &258i32.to_le_bytes()),
+            (
+                Arc::new(Float64Array::from(vec![1.5])),
+                &1.5f64.to_le_bytes(),
+            ),
+            (Arc::new(StringArray::from(vec!["foo"])), b"foo"),
+        ];
+        for (array, bytes) in arrays {
+            let mut hashes = vec![0; 1];
+            let state = SeededHashState::new(HashFunction::XxHash64, 7);
+            create_hashes(&[array.clone()], &state, &mut hashes)?;
+            let mut hasher = twox_hash::XxHash64::with_seed(7);
+            hasher.write(bytes);
+            assert_eq!(hashes[0], hasher.finish());
+
+            let state = SeededHashState::new(HashFunction::Crc32, 7);
+            create_hashes(&[array], &state, &mut hashes)?;
+            let mut hasher = crc32fast::Hasher::new_with_initial(7);
+            hasher.write(bytes);
+            assert_eq!(hashes[0], hasher.finish());
+        }
+        Ok(())
+    }
+
     #[test]
     fn create_hashes_binary() -> Result<()> {
         let byte_array = Arc::new(BinaryArray::from_vec(vec![
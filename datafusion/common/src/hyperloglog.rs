@@ -20,8 +20,9 @@
 //! `hyperloglog` is a module that contains a modified version
 //! of [redis's implementation](https://github.com/redis/redis/blob/4930d19e70c391750479951022e207e19111eb55/src/hyperloglog.c)
 //! with some modification based on strong assumption of usage
-//! within datafusion, so that [`datafusion_expr::approx_distinct`] function can
-//! be efficiently implemented.
+//! within datafusion, so that the `approx_distinct` function can
+//! be efficiently implemented, and its sketches stored as column statistics,
+//! see [`DistinctSketch`](crate::stats::DistinctSketch).
 //!
 //! Specifically, like Redis's version, this HLL structure uses
 //! 2**14 = 16384 registers, which means the standard error is
@@ -34,6 +35,8 @@
 //!
 //! This module also borrows some code structure from [pdatastructs.rs](https://github.com/crepererum/pdatastructs.rs/blob/3997ed50f6b6871c9e53c4c5e0f48f431405fc63/src/hyperloglog.rs).
 
+use crate::error::_internal_err;
+use crate::{DataFusionError, Result, ScalarValue};
 use ahash::RandomState;
 use std::hash::Hash;
 use std::marker::PhantomData;
@@ -42,12 +45,16 @@ use std::marker::PhantomData;
 const HLL_P: usize = 14_usize;
 /// The number of bits of the hash value used determining the number of leading zeros
 const HLL_Q: usize = 64_usize - HLL_P;
-const NUM_REGISTERS: usize = 1_usize << HLL_P;
+/// The maximum value of a register
+pub(crate) const MAX_REGISTER_VALUE: u8 = (HLL_Q + 1) as u8;
+/// The number of registers of a [`HyperLogLog`]
+pub const NUM_REGISTERS: usize = 1_usize << HLL_P;
 /// Mask to obtain index into the registers
 const HLL_P_MASK: u64 = (NUM_REGISTERS as u64) - 1;
 
+/// A HyperLogLog sketch of the distinct values of type `T`
 #[derive(Clone, Debug)]
-pub(crate) struct HyperLogLog<T>
+pub struct HyperLogLog<T>
 where
     T: Hash + ?Sized,
 {
@@ -90,7 +97,7 @@ where
     /// Creates a HyperLogLog from already populated registers
     /// note that this method should not be invoked in untrusted environment
     /// because the internal structure of registers are not examined.
-    pub(crate) fn new_with_registers(registers: [u8; NUM_REGISTERS]) -> Self {
+    pub fn new_with_registers(registers: [u8; NUM_REGISTERS]) -> Self {
         Self {
             registers,
             phantom: PhantomData,
@@ -231,6 +238,31 @@ where
     }
 }
 
+impl<T: Hash + ?Sized> TryFrom<&[u8]> for HyperLogLog<T> {
+    type Error = DataFusionError;
+    fn try_from(v: &[u8]) -> Result<HyperLogLog<T>> {
+        let arr: [u8; NUM_REGISTERS] = v.try_into().map_err(|_| {
+            DataFusionError::Internal(
+                "Impossibly got invalid binary array from states".into(),
+            )
+        })?;
+        Ok(HyperLogLog::<T>::new_with_registers(arr))
+    }
+}
+
+impl<T: Hash + ?Sized> TryFrom<&ScalarValue> for HyperLogLog<T> {
+    type Error = DataFusionError;
+    fn try_from(v: &ScalarValue) -> Result<HyperLogLog<T>> {
+        if let ScalarValue::Binary(Some(slice)) = v {
+            slice.as_slice().try_into()
+        } else {
+            _internal_err!(
+                "Impossibly got invalid scalar value while converting to HyperLogLog"
+            )
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::{HyperLogLog, NUM_REGISTERS};
@@ -34,6 +34,7 @@ pub mod display;
 pub mod file_options;
 pub mod format;
 pub mod hash_utils;
+pub mod hyperloglog;
 pub mod instant;
 pub mod parsers;
 pub mod rounding;
@@ -18,8 +18,11 @@
 //! This module provides data structures to represent statistics
 
 use std::fmt::{self, Debug, Display};
+use std::sync::Arc;
 
-use crate::ScalarValue;
+use crate::error::_plan_err;
+use crate::hyperloglog::{HyperLogLog, MAX_REGISTER_VALUE, NUM_REGISTERS};
+use crate::{Result, ScalarValue};
 
 use arrow_schema::Schema;
 
@@ -259,6 +262,7 @@ impl Statistics {
                     max_value: cs.max_value.to_inexact(),
                     min_value: cs.min_value.to_inexact(),
                     distinct_count: cs.distinct_count.to_inexact(),
+                    distinct_sketch: cs.distinct_sketch,
                 })
                 .collect::<Vec<_>>(),
         }
@@ -321,6 +325,10 @@ pub struct ColumnStatistics {
     pub min_value: Precision<ScalarValue>,
     /// Number of distinct values
     pub distinct_count: Precision<usize>,
+    /// Sketch of the distinct values, which can be merged with the sketches
+    /// of other files or partitions of the same column to estimate their
+    /// combined number of distinct values
+    pub distinct_sketch: Option<DistinctSketch>,
 }
 
 impl ColumnStatistics {
@@ -342,6 +350,67 @@ impl ColumnStatistics {
             max_value: Precision::Absent,
             min_value: Precision::Absent,
             distinct_count: Precision::Absent,
+            distinct_sketch: None,
+        }
+    }
+}
+
+/// A [`HyperLogLog`] sketch of the distinct values of a column, in the
+/// format of the `approx_distinct` aggregate state.
+///
+/// Unlike distinct counts, sketches of the same column can be merged, e.g.
+/// to estimate the number of distinct values of a table from the sketches
+/// of its files.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct DistinctSketch {
+    registers: Arc<[u8; NUM_REGISTERS]>,
+}
+
+impl DistinctSketch {
+    /// Creates a sketch from the registers of a [`HyperLogLog`]
+    pub fn try_new(registers: &[u8]) -> Result<Self> {
+        if registers.iter().any(|r| *r > MAX_REGISTER_VALUE) {
+            return _plan_err!("Invalid distinct sketch register value");
+        }
+        match <[u8; NUM_REGISTERS]>::try_from(registers) {
+            Ok(registers) => Ok(Self {
+                registers: Arc::new(registers),
+            }),
+            Err(_) => _plan_err!(
+                "Invalid distinct sketch of {} registers, expected {NUM_REGISTERS}",
+                registers.len()
+            ),
+        }
+    }
+
+    /// Returns the registers of the sketch
+    pub fn registers(&self) -> &[u8] {
+        self.registers.as_ref()
+    }
+
+    /// Returns the sketch of the union of the values of both sketches
+    pub fn merge(&self, other: &DistinctSketch) -> DistinctSketch {
+        let mut registers = *self.registers;
+        for (register, other) in registers.iter_mut().zip(other.registers.iter()) {
+            *register = (*register).max(*other);
+        }
+        Self {
+            registers: Arc::new(registers),
+        }
+    }
+
+    /// Returns the estimated number of distinct values
+    pub fn count(&self) -> usize {
+        HyperLogLog::<[u8]>::new_with_registers(*self.registers).count()
+    }
+}
+
+impl<T: std::hash::Hash + ?Sized> From<&HyperLogLog<T>> for DistinctSketch {
+    fn from(hll: &HyperLogLog<T>) -> Self {
+        let mut registers = [0; NUM_REGISTERS];
+        registers.copy_from_slice(hll.as_ref());
+        Self {
+            registers: Arc::new(registers),
         }
     }
 }
@@ -459,4 +528,30 @@ mod tests {
         assert_eq!(precision2.multiply(&precision3), Precision::Inexact(15));
         assert_eq!(precision1.multiply(&absent_precision), Precision::Absent);
     }
+
+    #[test]
+    fn test_distinct_sketch() -> Result<()> {
+        let mut hll1 = HyperLogLog::<u32>::new();
+        hll1.extend(0..1000);
+        let mut hll2 = HyperLogLog::<u32>::new();
+        hll2.extend(500..2000);
+
+        let sketch1 = DistinctSketch::from(&hll1);
+        let sketch2 = DistinctSketch::try_new(hll2.as_ref())?;
+        assert_eq!(sketch1.count(), hll1.count());
+        assert_eq!(sketch2.count(), hll2.count());
+
+        hll1.merge(&hll2);
+        assert_eq!(sketch1.merge(&sketch2).count(), hll1.count());
+
+        let err = DistinctSketch::try_new(&[0; 10]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid distinct sketch of 10 registers, expected 16384"));
+        let err = DistinctSketch::try_new(&[100; NUM_REGISTERS]).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Invalid distinct sketch register value"));
+        Ok(())
+    }
 }
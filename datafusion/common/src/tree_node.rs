@@ -930,13 +930,128 @@ impl<T: ConcreteTreeNode> TreeNode for T {
     }
 }
 
+/// A node of a tree of [`DynTreeNode`]s, such as `Arc<dyn ExecutionPlan>`,
+/// annotated with a payload of type `T`.
+///
+/// A rule attaches its payloads to the nodes with [`Self::new_default`] or
+/// [`Self::annotate`], rewrites the annotated tree with the [`TreeNode`] API,
+/// then detaches the payloads with [`Self::into_node`]. Since the children
+/// can be accessed both through the node and through the child contexts,
+/// the node is rebuilt from the child contexts whenever they are reattached,
+/// and mutable operations should go through
+/// [`Self::update_node_from_children`].
+#[derive(Debug)]
+pub struct NodeContext<N: DynTreeNode + ?Sized, T> {
+    /// The node associated with this context.
+    pub node: Arc<N>,
+    /// Custom data payload of the node.
+    pub data: T,
+    /// Child contexts of this node.
+    pub children: Vec<Self>,
+}
+
+impl<N: DynTreeNode + ?Sized, T> NodeContext<N, T> {
+    /// Creates a context for `node` with the payload `data`, whose child
+    /// contexts `children` must be in the order of the children of `node`
+    pub fn new(node: Arc<N>, data: T, children: Vec<Self>) -> Self {
+        Self {
+            node,
+            data,
+            children,
+        }
+    }
+
+    /// Attaches the payload `f` returns for each node of the tree `node`,
+    /// calling it on the parents before their children
+    pub fn annotate<F: FnMut(&Arc<N>) -> Result<T>>(
+        node: Arc<N>,
+        mut f: F,
+    ) -> Result<Self> {
+        Self::annotate_with(node, &mut f)
+    }
+
+    fn annotate_with<F: FnMut(&Arc<N>) -> Result<T>>(
+        node: Arc<N>,
+        f: &mut F,
+    ) -> Result<Self> {
+        let data = f(&node)?;
+        let children = node
+            .arc_children()
+            .into_iter()
+            .map(|child| Self::annotate_with(child, f))
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Self::new(node, data, children))
+    }
+
+    /// Rebuilds the node from the nodes of the child contexts
+    pub fn update_node_from_children(mut self) -> Result<Self> {
+        if !self.children.is_empty() {
+            let children = self.children.iter().map(|c| Arc::clone(&c.node)).collect();
+            self.node = self
+                .node
+                .with_new_arc_children(Arc::clone(&self.node), children)?;
+        }
+        Ok(self)
+    }
+
+    /// Replaces the payloads of the tree by the ones `f` returns, so that
+    /// an annotated tree can be reused by a rule needing other payloads
+    pub fn map_data<U, F: FnMut(T) -> U>(self, mut f: F) -> NodeContext<N, U> {
+        self.map_data_with(&mut f)
+    }
+
+    fn map_data_with<U, F: FnMut(T) -> U>(self, f: &mut F) -> NodeContext<N, U> {
+        let data = f(self.data);
+        let children = self
+            .children
+            .into_iter()
+            .map(|child| child.map_data_with(f))
+            .collect();
+        NodeContext::new(self.node, data, children)
+    }
+
+    /// Detaches the payloads, returning the node
+    pub fn into_node(self) -> Arc<N> {
+        self.node
+    }
+}
+
+impl<N: DynTreeNode + ?Sized, T: Default> NodeContext<N, T> {
+    /// Attaches the default payload to each node of the tree `node`
+    pub fn new_default(node: Arc<N>) -> Self {
+        let children = node
+            .arc_children()
+            .into_iter()
+            .map(Self::new_default)
+            .collect();
+        Self::new(node, T::default(), children)
+    }
+}
+
+impl<N: DynTreeNode + ?Sized, T> ConcreteTreeNode for NodeContext<N, T> {
+    fn children(&self) -> Vec<&Self> {
+        self.children.iter().collect()
+    }
+
+    fn take_children(mut self) -> (Self, Vec<Self>) {
+        let children = std::mem::take(&mut self.children);
+        (self, children)
+    }
+
+    fn with_new_children(mut self, children: Vec<Self>) -> Result<Self> {
+        self.children = children;
+        self.update_node_from_children()
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::fmt::Display;
+    use std::sync::Arc;
 
     use crate::tree_node::{
-        Transformed, TreeNode, TreeNodeIterator, TreeNodeRecursion, TreeNodeRewriter,
-        TreeNodeVisitor,
+        DynTreeNode, NodeContext, Transformed, TransformedResult, TreeNode,
+        TreeNodeIterator, TreeNodeRecursion, TreeNodeRewriter, TreeNodeVisitor,
     };
     use crate::Result;
 
@@ -1906,4 +2021,73 @@ mod tests {
             TreeNodeRecursion::Stop
         )
     );
+
+    #[derive(Debug)]
+    struct DynTestNode {
+        name: String,
+        children: Vec<Arc<DynTestNode>>,
+    }
+
+    impl DynTreeNode for DynTestNode {
+        fn arc_children(&self) -> Vec<Arc<Self>> {
+            self.children.clone()
+        }
+
+        fn with_new_arc_children(
+            &self,
+            _arc_self: Arc<Self>,
+            new_children: Vec<Arc<Self>>,
+        ) -> Result<Arc<Self>> {
+            Ok(dyn_node(&self.name, new_children))
+        }
+    }
+
+    fn dyn_node(name: &str, children: Vec<Arc<DynTestNode>>) -> Arc<DynTestNode> {
+        Arc::new(DynTestNode {
+            name: name.to_string(),
+            children,
+        })
+    }
+
+    #[test]
+    fn test_node_context() -> Result<()> {
+        //    a
+        //  /   \
+        // b     c
+        //       |
+        //       d
+        let tree = dyn_node(
+            "a",
+            vec![
+                dyn_node("b", vec![]),
+                dyn_node("c", vec![dyn_node("d", vec![])]),
+            ],
+        );
+        let context = NodeContext::annotate(tree, |node| Ok(node.name.clone()))?;
+        assert_eq!(context.data, "a");
+        assert_eq!(context.children[1].children[0].data, "d");
+
+        // Count the nodes of each subtree, and rename the leaves
+        let context = context
+            .map_data(|_| 0)
+            .transform_up(|mut context| {
+                context.data = 1 + context.children.iter().map(|c| c.data).sum::<usize>();
+                if context.children.is_empty() {
+                    context.node = dyn_node(&format!("{}'", context.node.name), vec![]);
+                }
+                Ok(Transformed::yes(context))
+            })
+            .data()?;
+        assert_eq!(context.data, 4);
+        assert_eq!(context.children[0].data, 1);
+        assert_eq!(context.children[1].data, 2);
+
+        let mut names = vec![];
+        context.into_node().apply(|node| {
+            names.push(node.name.clone());
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+        assert_eq!(names, ["a", "b'", "c", "d'"]);
+        Ok(())
+    }
 }
@@ -142,6 +142,7 @@ impl ListingSchemaProvider {
                             has_header: self.has_header,
                             delimiter: ',',
                             table_partition_cols: vec![],
+                            partition_transforms: Default::default(),
                             if_not_exists: false,
                             definition: None,
                             file_compression_type: CompressionTypeVariant::UNCOMPRESSED,
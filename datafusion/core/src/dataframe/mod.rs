@@ -34,10 +34,10 @@ use crate::logical_expr::utils::find_window_exprs;
 use crate::logical_expr::{
     col, Expr, JoinType, LogicalPlan, LogicalPlanBuilder, Partitioning, TableType,
 };
-use crate::physical_plan::{
-    collect, collect_partitioned, execute_stream, execute_stream_partitioned,
-    ExecutionPlan, SendableRecordBatchStream,
+use crate::physical_plan::scheduler::{
+    collect_partitioned_with_scheduler, collect_with_scheduler,
 };
+use crate::physical_plan::{ExecutionPlan, SendableRecordBatchStream};
 use crate::prelude::SessionContext;
 
 use arrow::array::{Array, ArrayRef, Int64Array, StringArray};
@@ -882,8 +882,9 @@ impl DataFrame {
     /// ```
     pub async fn collect(self) -> Result<Vec<RecordBatch>> {
         let task_ctx = Arc::new(self.task_ctx());
+        let scheduler = self.session_state.scheduler();
         let plan = self.create_physical_plan().await?;
-        collect(plan, task_ctx).await
+        collect_with_scheduler(scheduler.as_ref(), plan, task_ctx).await
     }
 
     /// Execute the `DataFrame` and print the results to the console.
@@ -953,8 +954,9 @@ impl DataFrame {
     /// any allocated resources
     pub async fn execute_stream(self) -> Result<SendableRecordBatchStream> {
         let task_ctx = Arc::new(self.task_ctx());
+        let scheduler = self.session_state.scheduler();
         let plan = self.create_physical_plan().await?;
-        execute_stream(plan, task_ctx)
+        scheduler.execute_stream(plan, task_ctx)
     }
 
     /// Executes this DataFrame and collects all results into a vector of vector of RecordBatch
@@ -974,8 +976,9 @@ impl DataFrame {
     /// ```
     pub async fn collect_partitioned(self) -> Result<Vec<Vec<RecordBatch>>> {
         let task_ctx = Arc::new(self.task_ctx());
+        let scheduler = self.session_state.scheduler();
         let plan = self.create_physical_plan().await?;
-        collect_partitioned(plan, task_ctx).await
+        collect_partitioned_with_scheduler(scheduler.as_ref(), plan, task_ctx).await
     }
 
     /// Executes this DataFrame and returns one stream per partition.
@@ -1000,8 +1003,9 @@ impl DataFrame {
         self,
     ) -> Result<Vec<SendableRecordBatchStream>> {
         let task_ctx = Arc::new(self.task_ctx());
+        let scheduler = self.session_state.scheduler();
         let plan = self.create_physical_plan().await?;
-        execute_stream_partitioned(plan, task_ctx)
+        scheduler.execute_stream_partitioned(plan, task_ctx)
     }
 
     /// Returns the `DFSchema` describing the output of this DataFrame.
@@ -1420,6 +1424,10 @@ impl DataFrame {
     /// Replace all parameters in logical plan with the specified
     /// values, in preparation for execution.
     ///
+    /// The access policies of the tables read by a `PREPARE` statement are
+    /// those of the user of the session of this DataFrame, see
+    /// [`AccessPolicyProvider`](crate::execution::context::AccessPolicyProvider).
+    ///
     /// # Example
     ///
     /// ```
@@ -1475,7 +1483,13 @@ impl DataFrame {
     /// # }
     /// ```
     pub fn with_param_values(self, query_values: impl Into<ParamValues>) -> Result<Self> {
-        let plan = self.plan.with_param_values(query_values)?;
+        // the statement of a PREPARE is planned without the access policies,
+        // which are enforced for the user executing it
+        let prepared = matches!(self.plan, LogicalPlan::Prepare(_));
+        let mut plan = self.plan.with_param_values(query_values)?;
+        if prepared {
+            plan = self.session_state.apply_table_policies(plan)?;
+        }
         Ok(DataFrame {
             session_state: self.session_state,
             plan,
@@ -1501,7 +1515,10 @@ impl DataFrame {
         let plan = self.clone().create_physical_plan().await?;
         let schema = plan.schema();
         let task_ctx = Arc::new(self.task_ctx());
-        let partitions = collect_partitioned(plan, task_ctx).await?;
+        let scheduler = self.session_state.scheduler();
+        let partitions =
+            collect_partitioned_with_scheduler(scheduler.as_ref(), plan, task_ctx)
+                .await?;
         let mem_table = MemTable::try_new(schema, partitions)?;
         context.read_table(Arc::new(mem_table))
     }
@@ -1881,9 +1898,9 @@ mod tests {
         assert_physical_plan(
             &df,
             vec![
-            "CoalesceBatchesExec: target_batch_size=8192",
-            "  FilterExec: id@0 = 1 AND name@1 = a",
-            "    AggregateExec: mode=Single, gby=[id@0 as id, name@1 as name], aggr=[]",
+            "AggregateExec: mode=Single, gby=[id@0 as id, name@1 as name], aggr=[], ordering_mode=Sorted",
+            "  CoalesceBatchesExec: target_batch_size=8192",
+            "    FilterExec: id@0 = 1 AND name@1 = a",
             "      MemoryExec: partitions=1, partition_sizes=[1]",
         ],
         )
@@ -1930,9 +1947,9 @@ mod tests {
         assert_physical_plan(
             &df,
             vec![
-            "CoalesceBatchesExec: target_batch_size=8192",
-            "  FilterExec: id@0 = 1",
-            "    AggregateExec: mode=Single, gby=[id@0 as id, name@1 as name], aggr=[]",
+            "AggregateExec: mode=Single, gby=[id@0 as id, name@1 as name], aggr=[], ordering_mode=PartiallySorted([0])",
+            "  CoalesceBatchesExec: target_batch_size=8192",
+            "    FilterExec: id@0 = 1",
             "      MemoryExec: partitions=1, partition_sizes=[1]",
         ],
         )
@@ -1981,9 +1998,9 @@ mod tests {
         assert_physical_plan(
             &df,
             vec![
-                "CoalesceBatchesExec: target_batch_size=8192",
-                "  FilterExec: id@0 = 1",
-                "    AggregateExec: mode=Single, gby=[id@0 as id], aggr=[]",
+                "AggregateExec: mode=Single, gby=[id@0 as id], aggr=[], ordering_mode=Sorted",
+                "  CoalesceBatchesExec: target_batch_size=8192",
+                "    FilterExec: id@0 = 1",
                 "      MemoryExec: partitions=1, partition_sizes=[1]",
             ],
         )
@@ -3053,4 +3070,46 @@ mod tests {
         assert!(result.is_err());
         Ok(())
     }
+
+    #[tokio::test]
+    async fn execute_with_custom_scheduler() -> Result<()> {
+        use crate::physical_plan::scheduler::{DefaultScheduler, ExecutionScheduler};
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        #[derive(Debug, Default)]
+        struct CountingScheduler {
+            calls: AtomicUsize,
+        }
+
+        impl ExecutionScheduler for CountingScheduler {
+            fn name(&self) -> &str {
+                "counting"
+            }
+
+            fn execute_stream_partitioned(
+                &self,
+                plan: Arc<dyn ExecutionPlan>,
+                context: Arc<TaskContext>,
+            ) -> Result<Vec<SendableRecordBatchStream>> {
+                self.calls.fetch_add(1, Ordering::SeqCst);
+                DefaultScheduler.execute_stream_partitioned(plan, context)
+            }
+        }
+
+        let scheduler = Arc::new(CountingScheduler::default());
+        let state = SessionContext::new()
+            .state()
+            .with_scheduler(scheduler.clone());
+        let ctx = SessionContext::new_with_state(state);
+
+        let partitions = ctx
+            .sql("SELECT * FROM (VALUES (1), (2), (3)) AS t(a)")
+            .await?
+            .collect_partitioned()
+            .await?;
+        let rows: usize = partitions.iter().flatten().map(|b| b.num_rows()).sum();
+        assert_eq!(rows, 3);
+        assert_eq!(scheduler.calls.load(Ordering::SeqCst), 1);
+        Ok(())
+    }
 }
@@ -0,0 +1,189 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Change data feed: the rows changed between two versions of a table
+
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use async_trait::async_trait;
+use datafusion_common::{plan_err, DFSchema, Result, ScalarValue, TableReference};
+use datafusion_expr::execution_props::ExecutionProps;
+use datafusion_expr::simplify::SimplifyContext;
+use datafusion_expr::{Expr, TableType, TableVersion};
+
+use crate::datasource::TableProvider;
+use crate::execution::context::SessionState;
+use crate::optimizer::simplify_expressions::ExprSimplifier;
+use crate::physical_plan::ExecutionPlan;
+
+/// The name of the table function scanning the changes of a table:
+///
+/// ```sql
+/// SELECT * FROM table_changes('t', <start version>[, <end version>])
+/// ```
+pub const TABLE_CHANGES_FUNCTION: &str = "table_changes";
+
+/// The name of the column of the change rows holding the kind of the change,
+/// one of [`CHANGE_TYPE_INSERT`], [`CHANGE_TYPE_UPDATE`] or
+/// [`CHANGE_TYPE_DELETE`]
+pub const CHANGE_TYPE_COLUMN: &str = "_change_type";
+
+/// The change type of an inserted row
+pub const CHANGE_TYPE_INSERT: &str = "insert";
+
+/// The change type of the new values of an updated row
+pub const CHANGE_TYPE_UPDATE: &str = "update";
+
+/// The change type of a deleted row
+pub const CHANGE_TYPE_DELETE: &str = "delete";
+
+/// The changes of a table committed after the version `start`, up to the
+/// version `end` included, or to the current version if `end` is `None`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct TableChanges {
+    /// The version the changes apply to, which is excluded
+    pub start: TableVersion,
+    /// The last version whose changes are included, the current one if `None`
+    pub end: Option<TableVersion>,
+}
+
+impl TableChanges {
+    /// Parses the arguments of [`TABLE_CHANGES_FUNCTION`]: the name of the
+    /// table, the start version and the optional end version. Each version is
+    /// a constant, a [`TableVersion::Timestamp`] if it is a timestamp and a
+    /// [`TableVersion::Version`] otherwise.
+    pub fn try_from_args(args: &[Expr]) -> Result<(TableReference, Self)> {
+        let (table, start, end) = match args {
+            [table, start] => (table, start, None),
+            [table, start, end] => (table, start, Some(end)),
+            _ => {
+                return plan_err!(
+                    "{TABLE_CHANGES_FUNCTION} expects a table name, a start version and an optional end version"
+                )
+            }
+        };
+        let table = match table {
+            Expr::Literal(ScalarValue::Utf8(Some(table))) => {
+                TableReference::from(table.as_str())
+            }
+            _ => return plan_err!("{TABLE_CHANGES_FUNCTION} expects a table name"),
+        };
+
+        let props = ExecutionProps::new();
+        let context =
+            SimplifyContext::new(&props).with_schema(Arc::new(DFSchema::empty()));
+        let simplifier = ExprSimplifier::new(context);
+        let version = |expr: &Expr| match simplifier.simplify(expr.clone())? {
+            Expr::Literal(value @ ScalarValue::TimestampNanosecond(..))
+            | Expr::Literal(value @ ScalarValue::TimestampMicrosecond(..))
+            | Expr::Literal(value @ ScalarValue::TimestampMillisecond(..))
+            | Expr::Literal(value @ ScalarValue::TimestampSecond(..)) => {
+                TableVersion::try_new_timestamp(value)
+            }
+            Expr::Literal(value) => TableVersion::try_new_version(value),
+            expr => plan_err!(
+                "{TABLE_CHANGES_FUNCTION} expects a constant version, got {expr}"
+            ),
+        };
+        let changes = Self {
+            start: version(start)?,
+            end: end.map(version).transpose()?,
+        };
+        Ok((table, changes))
+    }
+}
+
+impl fmt::Display for TableChanges {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "changes after {}", self.start)?;
+        if let Some(end) = &self.end {
+            write!(f, " up to {end}")?;
+        }
+        Ok(())
+    }
+}
+
+/// The changes of a table between two versions, scanned with
+/// [`TableProvider::scan_changes`], as a table of the columns of the table
+/// followed by [`CHANGE_TYPE_COLUMN`]
+pub struct TableChangesProvider {
+    table: Arc<dyn TableProvider>,
+    changes: TableChanges,
+    schema: SchemaRef,
+}
+
+impl TableChangesProvider {
+    /// Create the table of the changes `changes` of `table`
+    pub fn new(table: Arc<dyn TableProvider>, changes: TableChanges) -> Self {
+        let table_schema = table.schema();
+        let mut fields = table_schema.fields().to_vec();
+        fields.push(Arc::new(Field::new(
+            CHANGE_TYPE_COLUMN,
+            DataType::Utf8,
+            false,
+        )));
+        let schema = Arc::new(Schema::new_with_metadata(
+            fields,
+            table_schema.metadata().clone(),
+        ));
+        Self {
+            table,
+            changes,
+            schema,
+        }
+    }
+
+    /// The table whose changes are scanned
+    pub fn table(&self) -> &Arc<dyn TableProvider> {
+        &self.table
+    }
+
+    /// The changes scanned
+    pub fn changes(&self) -> &TableChanges {
+        &self.changes
+    }
+}
+
+#[async_trait]
+impl TableProvider for TableChangesProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Temporary
+    }
+
+    async fn scan(
+        &self,
+        state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.table
+            .scan_changes(state, projection, filters, limit, &self.changes)
+            .await
+    }
+}
@@ -0,0 +1,383 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`CompactionPlanner`] plans the rewrite of the small files of a table
+//! into larger files, as by an `OPTIMIZE` command
+
+use std::collections::BTreeMap;
+use std::sync::Arc;
+
+use arrow::datatypes::SchemaRef;
+use datafusion_common::{plan_err, GetExt, Statistics};
+use datafusion_physical_expr::{LexOrdering, PhysicalSortRequirement};
+use object_store::path::{Path, PathPart};
+use uuid::Uuid;
+
+use crate::datasource::file_format::FileFormat;
+use crate::datasource::listing::{ListingTableUrl, PartitionedFile};
+use crate::datasource::object_store::ObjectStoreUrl;
+use crate::datasource::physical_plan::{FileScanConfig, FileSinkConfig};
+use crate::error::Result;
+use crate::execution::context::SessionState;
+use crate::physical_plan::sorts::sort::SortExec;
+use crate::physical_plan::union::UnionExec;
+use crate::physical_plan::ExecutionPlan;
+
+/// Plans the compaction of the small files of a table: the files smaller
+/// than a target size are packed into groups of files of the same directory,
+/// so of the same partition, of at most the target size in total, and each
+/// group is rewritten into a single file of the directory, sorted by the
+/// output ordering if any.
+///
+/// The planner doesn't commit the rewrite: the [`CompactionManifest`] of the
+/// plan lists the files to remove and to add, for the table format to commit
+/// once the plan was executed, or to delete the added files if it failed.
+///
+/// ```ignore
+/// let planner = CompactionPlanner::new(object_store_url, schema, format, 128 << 20);
+/// if let Some(compaction) = planner.create_physical_plan(&state, files).await? {
+///     collect(compaction.plan, state.task_ctx()).await?;
+///     commit(compaction.manifest).await?;
+/// }
+/// ```
+#[derive(Debug, Clone)]
+pub struct CompactionPlanner {
+    object_store_url: ObjectStoreUrl,
+    file_schema: SchemaRef,
+    format: Arc<dyn FileFormat>,
+    target_file_size: u64,
+    min_files: usize,
+    output_ordering: Option<LexOrdering>,
+}
+
+impl CompactionPlanner {
+    /// Create a planner rewriting the files of `format` and of the schema
+    /// `file_schema` of the store `object_store_url` into files of about
+    /// `target_file_size` bytes
+    pub fn new(
+        object_store_url: ObjectStoreUrl,
+        file_schema: SchemaRef,
+        format: Arc<dyn FileFormat>,
+        target_file_size: u64,
+    ) -> Self {
+        Self {
+            object_store_url,
+            file_schema,
+            format,
+            target_file_size,
+            min_files: 2,
+            output_ordering: None,
+        }
+    }
+
+    /// Set the minimum number of small files of a group to rewrite, 2 by
+    /// default
+    pub fn with_min_files(mut self, min_files: usize) -> Self {
+        self.min_files = min_files;
+        self
+    }
+
+    /// Set the ordering of the rows of the rewritten files
+    pub fn with_output_ordering(mut self, output_ordering: LexOrdering) -> Self {
+        self.output_ordering = Some(output_ordering);
+        self
+    }
+
+    /// Returns the groups of the files `files` to rewrite into a single file.
+    ///
+    /// The small files of each directory are packed by increasing size, a
+    /// group being full when the next file would exceed the target size. The
+    /// groups of less than the minimum number of files are left as is.
+    pub fn plan_groups(&self, files: Vec<PartitionedFile>) -> Vec<Vec<PartitionedFile>> {
+        let mut directories = BTreeMap::<String, Vec<PartitionedFile>>::new();
+        for file in files {
+            if file.object_meta.size as u64 >= self.target_file_size {
+                continue;
+            }
+            let location = file.path().as_ref();
+            let directory = location.rsplit_once('/').map_or("", |(dir, _)| dir);
+            directories
+                .entry(directory.to_string())
+                .or_default()
+                .push(file);
+        }
+
+        let mut groups = vec![];
+        for mut files in directories.into_values() {
+            files.sort_by(|a, b| {
+                (a.object_meta.size, &a.object_meta.location)
+                    .cmp(&(b.object_meta.size, &b.object_meta.location))
+            });
+            let mut group = vec![];
+            let mut group_size = 0;
+            for file in files {
+                let size = file.object_meta.size as u64;
+                if !group.is_empty() && group_size + size > self.target_file_size {
+                    groups.push(std::mem::take(&mut group));
+                    group_size = 0;
+                }
+                group_size += size;
+                group.push(file);
+            }
+            groups.push(group);
+        }
+        groups.retain(|group| group.len() >= self.min_files.max(1));
+        groups
+    }
+
+    /// Create the physical plan rewriting the groups of the files `files`
+    /// returned by [`Self::plan_groups`], or `None` if there are no files to
+    /// rewrite.
+    ///
+    /// Each group is scanned as a single partition, entirely, without the
+    /// column hints of deep projections, sorted by the output ordering if any
+    /// and written to a new file of its directory. The plan returns a row of
+    /// the number of rows written per group.
+    pub async fn create_physical_plan(
+        &self,
+        state: &SessionState,
+        files: Vec<PartitionedFile>,
+    ) -> Result<Option<CompactionPlan>> {
+        let groups = self.plan_groups(files);
+        if groups.is_empty() {
+            return Ok(None);
+        }
+
+        let extension = self.format.file_type().get_ext();
+        let order_requirements = self.output_ordering.as_ref().map(|ordering| {
+            ordering
+                .iter()
+                .cloned()
+                .map(PhysicalSortRequirement::from)
+                .collect::<Vec<_>>()
+        });
+        let mut plans = Vec::with_capacity(groups.len());
+        let mut rewrites = Vec::with_capacity(groups.len());
+        for group in groups {
+            let mut parts = group[0].path().parts().collect::<Vec<_>>();
+            parts.pop();
+            parts.push(PathPart::from(format!("{}{extension}", Uuid::new_v4())));
+            let added = Path::from_iter(parts);
+
+            let scan_config = FileScanConfig {
+                object_store_url: self.object_store_url.clone(),
+                file_schema: self.file_schema.clone(),
+                file_groups: vec![group.clone()],
+                statistics: Statistics::new_unknown(&self.file_schema),
+                projection: None,
+                column_hints: None,
+                listing_pruning: None,
+                limit: None,
+                table_partition_cols: vec![],
+                output_ordering: vec![],
+            };
+            let mut input = self
+                .format
+                .create_physical_plan(state, scan_config, None)
+                .await?;
+            if let Some(ordering) = &self.output_ordering {
+                input = Arc::new(SortExec::new(ordering.clone(), input));
+            }
+
+            let output_url = ListingTableUrl::parse(format!(
+                "{}{added}",
+                self.object_store_url.as_str()
+            ))?;
+            if output_url.is_collection() {
+                return plan_err!("Cannot write the compacted file {added}");
+            }
+            let sink_config = FileSinkConfig {
+                object_store_url: self.object_store_url.clone(),
+                file_groups: group.clone(),
+                table_paths: vec![output_url],
+                output_schema: self.file_schema.clone(),
+                table_partition_cols: vec![],
+                overwrite: false,
+            };
+            plans.push(
+                self.format
+                    .create_writer_physical_plan(
+                        input,
+                        state,
+                        sink_config,
+                        order_requirements.clone(),
+                    )
+                    .await?,
+            );
+            rewrites.push(FileRewrite {
+                removed: group,
+                added,
+            });
+        }
+
+        let plan: Arc<dyn ExecutionPlan> = match plans.len() {
+            1 => plans.remove(0),
+            _ => Arc::new(UnionExec::new(plans)),
+        };
+        Ok(Some(CompactionPlan {
+            plan,
+            manifest: CompactionManifest { rewrites },
+        }))
+    }
+}
+
+/// The plan of a compaction created by a [`CompactionPlanner`]
+#[derive(Debug, Clone)]
+pub struct CompactionPlan {
+    /// The plan writing the compacted files
+    pub plan: Arc<dyn ExecutionPlan>,
+    /// The files rewritten by the plan
+    pub manifest: CompactionManifest,
+}
+
+/// The files rewritten by a [`CompactionPlan`]
+#[derive(Debug, Clone, Default)]
+pub struct CompactionManifest {
+    /// The rewrites of the groups of files, one per written file
+    pub rewrites: Vec<FileRewrite>,
+}
+
+impl CompactionManifest {
+    /// Returns the files to remove from the table
+    pub fn removed_files(&self) -> impl Iterator<Item = &PartitionedFile> {
+        self.rewrites.iter().flat_map(|rewrite| &rewrite.removed)
+    }
+
+    /// Returns the files to add to the table
+    pub fn added_files(&self) -> impl Iterator<Item = &Path> {
+        self.rewrites.iter().map(|rewrite| &rewrite.added)
+    }
+}
+
+/// The rewrite of a group of files of a directory into a single file
+#[derive(Debug, Clone)]
+pub struct FileRewrite {
+    /// The files rewritten
+    pub removed: Vec<PartitionedFile>,
+    /// The path of the written file, in the directory of the removed files
+    pub added: Path,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasource::file_format::parquet::ParquetFormat;
+    use crate::physical_plan::{collect, expressions::col};
+    use crate::prelude::{ParquetReadOptions, SessionContext};
+
+    use arrow::array::Int32Array;
+    use arrow::compute::SortOptions;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow::record_batch::RecordBatch;
+    use datafusion_physical_expr::PhysicalSortExpr;
+    use parquet::arrow::ArrowWriter;
+
+    fn planner(target_file_size: u64) -> CompactionPlanner {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, false)]));
+        CompactionPlanner::new(
+            ObjectStoreUrl::local_filesystem(),
+            schema,
+            Arc::new(ParquetFormat::default()),
+            target_file_size,
+        )
+    }
+
+    #[test]
+    fn plan_groups() {
+        let files = vec![
+            PartitionedFile::new("t/x=1/a.parquet", 40),
+            PartitionedFile::new("t/x=1/b.parquet", 30),
+            PartitionedFile::new("t/x=1/c.parquet", 50),
+            PartitionedFile::new("t/x=1/d.parquet", 100),
+            PartitionedFile::new("t/x=2/e.parquet", 10),
+            PartitionedFile::new("t/x=2/f.parquet", 10),
+            PartitionedFile::new("t/x=3/g.parquet", 10),
+        ];
+        let groups = planner(100)
+            .plan_groups(files)
+            .into_iter()
+            .map(|group| {
+                group
+                    .iter()
+                    .map(|file| file.path().to_string())
+                    .collect::<Vec<_>>()
+            })
+            .collect::<Vec<_>>();
+        // d is not small, c doesn't fit with b and a, and g is alone
+        assert_eq!(
+            groups,
+            vec![
+                vec!["t/x=1/b.parquet", "t/x=1/a.parquet"],
+                vec!["t/x=2/e.parquet", "t/x=2/f.parquet"],
+            ]
+        );
+    }
+
+    #[tokio::test]
+    async fn compact_small_files() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let planner = planner(1 << 20);
+        let mut files = vec![];
+        for values in [vec![3, 1], vec![2], vec![5, 4]] {
+            let path = dir.path().join(format!("{}.parquet", values[0]));
+            let batch = RecordBatch::try_new(
+                planner.file_schema.clone(),
+                vec![Arc::new(Int32Array::from(values))],
+            )?;
+            let mut writer = ArrowWriter::try_new(
+                std::fs::File::create(&path)?,
+                planner.file_schema.clone(),
+                None,
+            )?;
+            writer.write(&batch)?;
+            writer.close()?;
+            let size = std::fs::metadata(&path)?.len();
+            files.push(PartitionedFile::new(path.to_str().unwrap(), size));
+        }
+
+        let ordering = vec![PhysicalSortExpr {
+            expr: col("a", &planner.file_schema)?,
+            options: SortOptions::default(),
+        }];
+        let planner = planner.with_output_ordering(ordering);
+        let ctx = SessionContext::new();
+        let state = ctx.state();
+        let compaction = planner.create_physical_plan(&state, files).await?.unwrap();
+        collect(compaction.plan, state.task_ctx()).await?;
+
+        let manifest = compaction.manifest;
+        assert_eq!(manifest.rewrites.len(), 1);
+        assert_eq!(manifest.removed_files().count(), 3);
+        for file in manifest.removed_files() {
+            std::fs::remove_file(format!("/{}", file.path()))?;
+        }
+        let added = manifest.added_files().next().unwrap();
+        let results = ctx
+            .read_parquet(format!("/{added}"), ParquetReadOptions::default())
+            .await?
+            .collect()
+            .await?;
+        crate::assert_batches_eq!(
+            [
+                "+---+", "| a |", "+---+", "| 1 |", "| 2 |", "| 3 |", "| 4 |", "| 5 |",
+                "+---+"
+            ],
+            &results
+        );
+        Ok(())
+    }
+}
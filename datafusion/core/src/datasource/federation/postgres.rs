@@ -0,0 +1,296 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`SqlSource`] reading the tables of a PostgreSQL database
+
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use arrow::array::{
+    ArrayRef, BinaryArray, BooleanArray, Date32Array, Float32Array, Float64Array,
+    Int16Array, Int32Array, Int64Array, StringArray, TimestampMicrosecondArray,
+};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use async_trait::async_trait;
+use chrono::{DateTime, Datelike, NaiveDate, NaiveDateTime, Utc};
+use futures::{StreamExt, TryStreamExt};
+use tokio_postgres::types::{FromSql, ToSql};
+use tokio_postgres::{Client, NoTls, Row};
+
+use datafusion_common::{not_impl_err, plan_err, DataFusionError, TableReference};
+use datafusion_common_runtime::SpawnedTask;
+use datafusion_execution::TaskContext;
+use datafusion_expr::CreateExternalTable;
+use datafusion_sql::unparser::dialect::{Dialect, PostgreSqlDialect};
+
+use super::{SqlSource, SqlTable, SQL_TABLE_OPTION};
+use crate::datasource::provider::TableProviderFactory;
+use crate::datasource::TableProvider;
+use crate::error::Result;
+use crate::execution::context::SessionState;
+use crate::physical_plan::stream::RecordBatchStreamAdapter;
+use crate::physical_plan::SendableRecordBatchStream;
+
+/// The number of days from 0001-01-01 to 1970-01-01
+const EPOCH_DAYS_FROM_CE: i32 = 719_163;
+
+/// A [`TableProviderFactory`] for the [`SqlTable`]s of a PostgreSQL database,
+/// connecting without TLS to the database of the `LOCATION` of the table,
+/// either a `postgresql://` URL or `key=value` pairs
+///
+/// The schema of the table is fetched from the database unless the columns
+/// of the table are declared.
+#[derive(Debug, Default)]
+pub struct PostgresTableFactory {}
+
+#[async_trait]
+impl TableProviderFactory for PostgresTableFactory {
+    async fn create(
+        &self,
+        _state: &SessionState,
+        cmd: &CreateExternalTable,
+    ) -> Result<Arc<dyn TableProvider>> {
+        let table = match cmd.options.get(SQL_TABLE_OPTION) {
+            Some(table) => TableReference::from(table.as_str()),
+            None => TableReference::bare(cmd.name.table()),
+        };
+        let source = Arc::new(PostgresSource::try_new(&cmd.location).await?);
+        let table = if cmd.schema.fields().is_empty() {
+            SqlTable::try_new(source, table).await?
+        } else {
+            let schema = Arc::new(cmd.schema.as_ref().into());
+            SqlTable::new(source, table, schema)
+        };
+        Ok(Arc::new(table))
+    }
+}
+
+/// A PostgreSQL database
+///
+/// The columns of the tables are read as the Arrow type of their PostgreSQL
+/// type: `boolean`, `smallint`, `integer`, `bigint`, `real`,
+/// `double precision`, `text`, `character varying`, `character`, `bytea`,
+/// `date`, `timestamp` and `timestamp with time zone`. The columns of other
+/// types can be read through a view casting them to one of these types.
+pub struct PostgresSource {
+    client: Arc<Client>,
+    /// The task driving the connection of `client`, if spawned by the source
+    connection: Option<SpawnedTask<()>>,
+}
+
+impl PostgresSource {
+    /// Connect without TLS to the database `config`, either a
+    /// `postgresql://` URL or `key=value` pairs
+    pub async fn try_new(config: &str) -> Result<Self> {
+        let (client, connection) = tokio_postgres::connect(config, NoTls)
+            .await
+            .map_err(postgres_error)?;
+        let connection = SpawnedTask::spawn(async move {
+            if let Err(e) = connection.await {
+                log::error!("PostgreSQL connection error: {e}");
+            }
+        });
+        Ok(Self {
+            client: Arc::new(client),
+            connection: Some(connection),
+        })
+    }
+
+    /// Read the database of the connected `client`, e.g. connected with TLS,
+    /// whose connection is driven by the caller
+    pub fn new(client: Arc<Client>) -> Self {
+        Self {
+            client,
+            connection: None,
+        }
+    }
+}
+
+impl Debug for PostgresSource {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PostgresSource")
+            .field("closed", &self.client.is_closed())
+            .field("owns_connection", &self.connection.is_some())
+            .finish()
+    }
+}
+
+#[async_trait]
+impl SqlSource for PostgresSource {
+    fn dialect(&self) -> &dyn Dialect {
+        &PostgreSqlDialect {}
+    }
+
+    async fn table_schema(&self, table: &TableReference) -> Result<SchemaRef> {
+        let rows = self
+            .client
+            .query(
+                "SELECT column_name::text, data_type::text, is_nullable::text \
+                 FROM information_schema.columns \
+                 WHERE table_schema = COALESCE($1::text, current_schema()) \
+                 AND table_name = $2::text \
+                 ORDER BY ordinal_position",
+                &[&table.schema(), &table.table()],
+            )
+            .await
+            .map_err(postgres_error)?;
+        if rows.is_empty() {
+            return plan_err!("PostgreSQL table {table} not found");
+        }
+        let fields = rows
+            .iter()
+            .map(|row| {
+                let name: &str = row.try_get(0).map_err(postgres_error)?;
+                let type_name: &str = row.try_get(1).map_err(postgres_error)?;
+                let nullable: &str = row.try_get(2).map_err(postgres_error)?;
+                let Some(data_type) = data_type(type_name) else {
+                    return not_impl_err!(
+                        "Unsupported type {type_name} of the column {name} of the PostgreSQL table {table}"
+                    );
+                };
+                Ok(Field::new(name, data_type, nullable == "YES"))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(Arc::new(Schema::new(fields)))
+    }
+
+    fn execute(
+        &self,
+        query: &str,
+        schema: SchemaRef,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let client = self.client.clone();
+        let query = query.to_string();
+        let batch_size = context.session_config().batch_size();
+        let stream_schema = schema.clone();
+        let stream = futures::stream::once(async move {
+            let params: [&(dyn ToSql + Sync); 0] = [];
+            client
+                .query_raw(query.as_str(), params)
+                .await
+                .map_err(postgres_error)
+        })
+        .map_ok(|rows| rows.map_err(postgres_error))
+        .try_flatten()
+        .try_chunks(batch_size)
+        .map(move |rows| rows_to_batch(&rows.map_err(|e| e.1)?, &stream_schema));
+        Ok(Box::pin(RecordBatchStreamAdapter::new(schema, stream)))
+    }
+}
+
+/// Returns the Arrow type of the PostgreSQL type named `name` in
+/// `information_schema.columns`
+fn data_type(name: &str) -> Option<DataType> {
+    Some(match name {
+        "boolean" => DataType::Boolean,
+        "smallint" => DataType::Int16,
+        "integer" => DataType::Int32,
+        "bigint" => DataType::Int64,
+        "real" => DataType::Float32,
+        "double precision" => DataType::Float64,
+        "text" | "character varying" | "character" | "name" => DataType::Utf8,
+        "bytea" => DataType::Binary,
+        "date" => DataType::Date32,
+        "timestamp without time zone" => DataType::Timestamp(TimeUnit::Microsecond, None),
+        "timestamp with time zone" => {
+            DataType::Timestamp(TimeUnit::Microsecond, Some("+00:00".into()))
+        }
+        _ => return None,
+    })
+}
+
+/// Converts the rows `rows` to a batch of the schema `schema`
+fn rows_to_batch(rows: &[Row], schema: &SchemaRef) -> Result<RecordBatch> {
+    let columns = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, field)| column(rows, i, field.data_type()))
+        .collect::<Result<Vec<_>>>()?;
+    let options = RecordBatchOptions::new().with_row_count(Some(rows.len()));
+    Ok(RecordBatch::try_new_with_options(
+        schema.clone(),
+        columns,
+        &options,
+    )?)
+}
+
+/// Returns the values of the column `i` of the rows `rows` as an array of the
+/// type `data_type`
+fn column(rows: &[Row], i: usize, data_type: &DataType) -> Result<ArrayRef> {
+    fn values<'a, T: FromSql<'a>>(rows: &'a [Row], i: usize) -> Result<Vec<Option<T>>> {
+        rows.iter()
+            .map(|row| row.try_get(i).map_err(postgres_error))
+            .collect()
+    }
+
+    Ok(match data_type {
+        DataType::Boolean => Arc::new(BooleanArray::from(values::<bool>(rows, i)?)),
+        DataType::Int16 => Arc::new(Int16Array::from(values::<i16>(rows, i)?)),
+        DataType::Int32 => Arc::new(Int32Array::from(values::<i32>(rows, i)?)),
+        DataType::Int64 => Arc::new(Int64Array::from(values::<i64>(rows, i)?)),
+        DataType::Float32 => Arc::new(Float32Array::from(values::<f32>(rows, i)?)),
+        DataType::Float64 => Arc::new(Float64Array::from(values::<f64>(rows, i)?)),
+        DataType::Utf8 => Arc::new(StringArray::from(values::<&str>(rows, i)?)),
+        DataType::Binary => Arc::new(BinaryArray::from(values::<&[u8]>(rows, i)?)),
+        DataType::Date32 => Arc::new(
+            values::<NaiveDate>(rows, i)?
+                .into_iter()
+                .map(|date| date.map(|date| date.num_days_from_ce() - EPOCH_DAYS_FROM_CE))
+                .collect::<Date32Array>(),
+        ),
+        DataType::Timestamp(TimeUnit::Microsecond, None) => Arc::new(
+            values::<NaiveDateTime>(rows, i)?
+                .into_iter()
+                .map(|timestamp| timestamp.map(|t| t.and_utc().timestamp_micros()))
+                .collect::<TimestampMicrosecondArray>(),
+        ),
+        DataType::Timestamp(TimeUnit::Microsecond, Some(tz)) => Arc::new(
+            values::<DateTime<Utc>>(rows, i)?
+                .into_iter()
+                .map(|timestamp| timestamp.map(|t| t.timestamp_micros()))
+                .collect::<TimestampMicrosecondArray>()
+                .with_timezone(tz.clone()),
+        ),
+        other => return not_impl_err!("Unsupported type {other} of a PostgreSQL column"),
+    })
+}
+
+fn postgres_error(e: tokio_postgres::Error) -> DataFusionError {
+    DataFusionError::External(Box::new(e))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn postgres_data_types() {
+        assert_eq!(data_type("integer"), Some(DataType::Int32));
+        assert_eq!(data_type("character varying"), Some(DataType::Utf8));
+        assert_eq!(
+            data_type("timestamp with time zone"),
+            Some(DataType::Timestamp(
+                TimeUnit::Microsecond,
+                Some("+00:00".into())
+            ))
+        );
+        assert_eq!(data_type("numeric"), None);
+    }
+}
@@ -0,0 +1,693 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A stable C ABI for [`TableProvider`]s
+//!
+//! [`FFI_TableProvider`] exposes a table behind a `#[repr(C)]` struct of
+//! function pointers, exchanging schemas through the [Arrow C data interface]
+//! and scan results through the [Arrow C stream interface]. This allows
+//! tables implemented in other languages, or compiled with another version of
+//! the Rust compiler, to be loaded at runtime (e.g. from a shared library)
+//! and registered with [`ForeignTableProvider`], and Rust [`TableProvider`]s
+//! to be exported with [`FFI_TableProvider::new`].
+//!
+//! The C definition of the struct is:
+//!
+//! ```c
+//! struct FFI_TableProvider {
+//!   // Writes the schema of the table to `out`
+//!   int (*schema)(const struct FFI_TableProvider*, struct ArrowSchema* out);
+//!   // Writes a stream of the rows of the table to `out`. `projection` lists
+//!   // the indices of the columns to read, or is NULL to read all columns.
+//!   // A negative `limit` reads all rows.
+//!   int (*scan)(const struct FFI_TableProvider*, const uintptr_t* projection,
+//!               uintptr_t projection_len, int64_t limit,
+//!               struct ArrowArrayStream* out);
+//!   // Returns the description of the last error, valid until the next call
+//!   const char* (*get_last_error)(const struct FFI_TableProvider*);
+//!   // Releases the provider, setting `release` to NULL
+//!   void (*release)(struct FFI_TableProvider*);
+//!   void* private_data;
+//! };
+//! ```
+//!
+//! Functions returning `int` return 0 on success, and an `errno`-compatible
+//! error code otherwise. All functions but `release` may be called
+//! concurrently from several threads.
+//!
+//! Filters are not passed to `scan`, the filters of a query on a foreign table
+//! are applied to the rows it returns.
+//!
+//! [Arrow C data interface]: https://arrow.apache.org/docs/format/CDataInterface.html
+//! [Arrow C stream interface]: https://arrow.apache.org/docs/format/CStreamInterface.html
+
+use std::any::Any;
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+use std::fmt::{self, Debug, Formatter};
+use std::future::Future;
+use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
+use std::ptr;
+use std::sync::{Arc, Mutex};
+
+use arrow::datatypes::{Schema, SchemaRef};
+use arrow::error::ArrowError;
+use arrow::ffi::FFI_ArrowSchema;
+use arrow::ffi_stream::{ArrowArrayStreamReader, FFI_ArrowArrayStream};
+use arrow::record_batch::{RecordBatch, RecordBatchReader};
+use async_trait::async_trait;
+use futures::StreamExt;
+use tokio::runtime::Handle;
+
+use datafusion_common::{exec_err, internal_datafusion_err, DataFusionError};
+use datafusion_execution::TaskContext;
+use datafusion_physical_expr::EquivalenceProperties;
+
+use crate::datasource::{TableProvider, TableType};
+use crate::error::Result;
+use crate::execution::context::{SessionContext, SessionState};
+use crate::logical_expr::Expr;
+use crate::logical_expr::TableProviderFilterPushDown;
+use crate::physical_plan::stream::RecordBatchReceiverStreamBuilder;
+use crate::physical_plan::{
+    execute_stream, DisplayAs, DisplayFormatType, ExecutionMode, ExecutionPlan,
+    Partitioning, PlanProperties, SendableRecordBatchStream,
+};
+
+/// `errno` value returned by [`FFI_TableProvider`] functions that fail
+const EIO: c_int = 5;
+
+/// A [`TableProvider`] behind a stable C ABI, see the [module
+/// documentation](self)
+#[repr(C)]
+#[allow(non_camel_case_types)]
+pub struct FFI_TableProvider {
+    /// Writes the schema of the table to `out`
+    pub schema: Option<
+        unsafe extern "C" fn(
+            provider: *const FFI_TableProvider,
+            out: *mut FFI_ArrowSchema,
+        ) -> c_int,
+    >,
+    /// Writes a stream of the rows of the table to `out`
+    pub scan: Option<
+        unsafe extern "C" fn(
+            provider: *const FFI_TableProvider,
+            projection: *const usize,
+            projection_len: usize,
+            limit: i64,
+            out: *mut FFI_ArrowArrayStream,
+        ) -> c_int,
+    >,
+    /// Returns the description of the last error
+    pub get_last_error:
+        Option<unsafe extern "C" fn(provider: *const FFI_TableProvider) -> *const c_char>,
+    /// Releases the provider
+    pub release: Option<unsafe extern "C" fn(provider: *mut FFI_TableProvider)>,
+    /// Opaque data of the implementation
+    pub private_data: *mut c_void,
+}
+
+// The functions of a FFI_TableProvider must be thread safe
+unsafe impl Send for FFI_TableProvider {}
+unsafe impl Sync for FFI_TableProvider {}
+
+impl Debug for FFI_TableProvider {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("FFI_TableProvider")
+            .field("released", &self.release.is_none())
+            .finish_non_exhaustive()
+    }
+}
+
+impl Drop for FFI_TableProvider {
+    fn drop(&mut self) {
+        if let Some(release) = self.release {
+            unsafe { release(self) }
+        }
+    }
+}
+
+impl FFI_TableProvider {
+    /// Export `provider`, scanning it with the state of `ctx`
+    ///
+    /// Scans block on `runtime`, from another thread when the functions of
+    /// the returned struct are called from a thread of a Tokio runtime.
+    pub fn new(
+        provider: Arc<dyn TableProvider>,
+        ctx: SessionContext,
+        runtime: Handle,
+    ) -> Self {
+        let private_data = Box::new(ExportedProvider {
+            provider,
+            ctx,
+            runtime,
+            last_error: Mutex::new(None),
+        });
+        Self {
+            schema: Some(export_schema),
+            scan: Some(export_scan),
+            get_last_error: Some(export_get_last_error),
+            release: Some(export_release),
+            private_data: Box::into_raw(private_data) as *mut c_void,
+        }
+    }
+
+    /// Returns the schema of the table
+    fn table_schema(&self) -> Result<SchemaRef> {
+        let schema_fn = self.schema.ok_or_else(released)?;
+        let mut out = FFI_ArrowSchema::empty();
+        let code = unsafe { schema_fn(self, &mut out) };
+        if code != 0 {
+            return exec_err!(
+                "Failed to get the schema of foreign table (error {code}): {}",
+                self.last_error()
+            );
+        }
+        Ok(Arc::new(Schema::try_from(&out)?))
+    }
+
+    /// Returns a reader of the `projection` of the rows of the table, up to
+    /// `limit` rows
+    fn scan_table(
+        &self,
+        projection: Option<&[usize]>,
+        limit: Option<usize>,
+    ) -> Result<ArrowArrayStreamReader> {
+        let scan_fn = self.scan.ok_or_else(released)?;
+        let (projection, projection_len) = match projection {
+            Some(projection) => (projection.as_ptr(), projection.len()),
+            None => (ptr::null(), 0),
+        };
+        let limit = limit.map_or(-1, |limit| limit as i64);
+        let mut out = FFI_ArrowArrayStream::empty();
+        let code = unsafe { scan_fn(self, projection, projection_len, limit, &mut out) };
+        if code != 0 {
+            return exec_err!(
+                "Failed to scan foreign table (error {code}): {}",
+                self.last_error()
+            );
+        }
+        Ok(ArrowArrayStreamReader::try_new(out)?)
+    }
+
+    fn last_error(&self) -> String {
+        let Some(get_last_error) = self.get_last_error else {
+            return "unknown error".to_string();
+        };
+        let error = unsafe { get_last_error(self) };
+        if error.is_null() {
+            "unknown error".to_string()
+        } else {
+            unsafe { CStr::from_ptr(error) }
+                .to_string_lossy()
+                .into_owned()
+        }
+    }
+}
+
+fn released() -> DataFusionError {
+    internal_datafusion_err!("FFI_TableProvider has been released")
+}
+
+/// The private data of a [`FFI_TableProvider`] exported by
+/// [`FFI_TableProvider::new`]
+struct ExportedProvider {
+    provider: Arc<dyn TableProvider>,
+    ctx: SessionContext,
+    runtime: Handle,
+    last_error: Mutex<Option<CString>>,
+}
+
+impl ExportedProvider {
+    /// # Safety
+    ///
+    /// `provider` must point to a provider created by [`FFI_TableProvider::new`]
+    unsafe fn from_ffi<'a>(provider: *const FFI_TableProvider) -> &'a Self {
+        &*((*provider).private_data as *const Self)
+    }
+
+    fn set_error(&self, error: impl ToString) -> c_int {
+        let error = CString::new(error.to_string().replace('\0', " "))
+            .expect("interior NUL bytes are replaced");
+        *self.last_error.lock().unwrap() = Some(error);
+        EIO
+    }
+
+    fn scan(
+        &self,
+        projection: Option<&Vec<usize>>,
+        limit: Option<usize>,
+    ) -> Result<Box<dyn RecordBatchReader + Send>> {
+        let stream = block_on(&self.runtime, async {
+            let state = self.ctx.state();
+            let plan = self.provider.scan(&state, projection, &[], limit).await?;
+            execute_stream(plan, self.ctx.task_ctx())
+        })?;
+        Ok(Box::new(BlockingStreamReader {
+            stream,
+            runtime: self.runtime.clone(),
+        }))
+    }
+}
+
+/// Runs `future` to completion on `runtime`.
+///
+/// [`Handle::block_on`] panics when called from an asynchronous context, e.g.
+/// by a foreign table provider of this process scanned by a query, so
+/// `future` is run from another thread when called from a thread of a Tokio
+/// runtime.
+fn block_on<F: Future + Send>(runtime: &Handle, future: F) -> F::Output
+where
+    F::Output: Send,
+{
+    if Handle::try_current().is_err() {
+        return runtime.block_on(future);
+    }
+    std::thread::scope(|scope| {
+        scope
+            .spawn(|| runtime.block_on(future))
+            .join()
+            .unwrap_or_else(|panic| resume_unwind(panic))
+    })
+}
+
+/// Returns the message of the `panic` caught by [`catch_unwind`]
+fn panic_message(panic: Box<dyn Any + Send>) -> String {
+    let message = panic
+        .downcast_ref::<&str>()
+        .copied()
+        .or_else(|| panic.downcast_ref::<String>().map(String::as_str))
+        .unwrap_or("unknown panic");
+    format!("FFI_TableProvider panicked: {message}")
+}
+
+// The functions below catch the panics of the exported provider, as unwinding
+// across the C ABI is undefined behavior
+
+unsafe extern "C" fn export_schema(
+    provider: *const FFI_TableProvider,
+    out: *mut FFI_ArrowSchema,
+) -> c_int {
+    let exported = ExportedProvider::from_ffi(provider);
+    let schema = catch_unwind(AssertUnwindSafe(|| {
+        FFI_ArrowSchema::try_from(exported.provider.schema().as_ref())
+            .map_err(|e| e.to_string())
+    }));
+    match schema.unwrap_or_else(|panic| Err(panic_message(panic))) {
+        Ok(schema) => {
+            ptr::write(out, schema);
+            0
+        }
+        Err(e) => exported.set_error(e),
+    }
+}
+
+unsafe extern "C" fn export_scan(
+    provider: *const FFI_TableProvider,
+    projection: *const usize,
+    projection_len: usize,
+    limit: i64,
+    out: *mut FFI_ArrowArrayStream,
+) -> c_int {
+    let exported = ExportedProvider::from_ffi(provider);
+    let projection = (!projection.is_null())
+        .then(|| std::slice::from_raw_parts(projection, projection_len).to_vec());
+    let limit = usize::try_from(limit).ok();
+    let reader = catch_unwind(AssertUnwindSafe(|| {
+        exported
+            .scan(projection.as_ref(), limit)
+            .map_err(|e| e.to_string())
+    }));
+    match reader.unwrap_or_else(|panic| Err(panic_message(panic))) {
+        Ok(reader) => {
+            ptr::write(out, FFI_ArrowArrayStream::new(reader));
+            0
+        }
+        Err(e) => exported.set_error(e),
+    }
+}
+
+unsafe extern "C" fn export_get_last_error(
+    provider: *const FFI_TableProvider,
+) -> *const c_char {
+    let exported = ExportedProvider::from_ffi(provider);
+    // a poisoned lock still holds the last error
+    let last_error = exported
+        .last_error
+        .lock()
+        .unwrap_or_else(|poisoned| poisoned.into_inner());
+    match last_error.as_ref() {
+        Some(error) => error.as_ptr(),
+        None => ptr::null(),
+    }
+}
+
+unsafe extern "C" fn export_release(provider: *mut FFI_TableProvider) {
+    let provider = &mut *provider;
+    let exported = Box::from_raw(provider.private_data as *mut ExportedProvider);
+    // the panics of the provider when it is dropped can not be reported
+    let _ = catch_unwind(AssertUnwindSafe(|| drop(exported)));
+    provider.private_data = ptr::null_mut();
+    provider.release = None;
+}
+
+/// Reads a [`SendableRecordBatchStream`] synchronously by blocking on
+/// `runtime`
+///
+/// It is called through the C stream interface, so it reports the panics of
+/// the stream as errors.
+struct BlockingStreamReader {
+    stream: SendableRecordBatchStream,
+    runtime: Handle,
+}
+
+impl Iterator for BlockingStreamReader {
+    type Item = std::result::Result<RecordBatch, ArrowError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let next = catch_unwind(AssertUnwindSafe(|| {
+            block_on(&self.runtime, self.stream.next())
+        }));
+        match next {
+            Ok(batch) => batch.map(|batch| batch.map_err(Into::into)),
+            Err(panic) => {
+                Some(Err(ArrowError::ExternalError(panic_message(panic).into())))
+            }
+        }
+    }
+}
+
+impl RecordBatchReader for BlockingStreamReader {
+    fn schema(&self) -> SchemaRef {
+        self.stream.schema()
+    }
+}
+
+/// A [`TableProvider`] for a table exposed through a [`FFI_TableProvider`],
+/// e.g. implemented in another language
+#[derive(Debug)]
+pub struct ForeignTableProvider {
+    ffi: Arc<FFI_TableProvider>,
+    schema: SchemaRef,
+}
+
+impl ForeignTableProvider {
+    /// Create a provider for the table `ffi`, which is released when the
+    /// provider and its scans are dropped
+    pub fn try_new(ffi: FFI_TableProvider) -> Result<Self> {
+        let schema = ffi.table_schema()?;
+        Ok(Self {
+            ffi: Arc::new(ffi),
+            schema,
+        })
+    }
+}
+
+#[async_trait]
+impl TableProvider for ForeignTableProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    /// The filters are not passed to the foreign table, see the [module
+    /// documentation](self)
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        Ok(vec![
+            TableProviderFilterPushDown::Unsupported;
+            filters.len()
+        ])
+    }
+
+    async fn scan(
+        &self,
+        _state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(ForeignScanExec::try_new(
+            self.ffi.clone(),
+            &self.schema,
+            projection.cloned(),
+            limit,
+        )?))
+    }
+}
+
+/// Execution plan scanning a [`ForeignTableProvider`]
+#[derive(Debug)]
+pub struct ForeignScanExec {
+    ffi: Arc<FFI_TableProvider>,
+    projection: Option<Vec<usize>>,
+    limit: Option<usize>,
+    cache: PlanProperties,
+}
+
+impl ForeignScanExec {
+    fn try_new(
+        ffi: Arc<FFI_TableProvider>,
+        schema: &SchemaRef,
+        projection: Option<Vec<usize>>,
+        limit: Option<usize>,
+    ) -> Result<Self> {
+        let schema = match &projection {
+            Some(projection) => Arc::new(schema.project(projection)?),
+            None => schema.clone(),
+        };
+        let cache = PlanProperties::new(
+            EquivalenceProperties::new(schema),
+            Partitioning::UnknownPartitioning(1),
+            ExecutionMode::Bounded,
+        );
+        Ok(Self {
+            ffi,
+            projection,
+            limit,
+            cache,
+        })
+    }
+}
+
+impl DisplayAs for ForeignScanExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "ForeignScanExec")?;
+                if let Some(projection) = &self.projection {
+                    write!(f, ": projection={projection:?}")?;
+                }
+                if let Some(limit) = self.limit {
+                    write!(f, ", limit={limit}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for ForeignScanExec {
+    fn name(&self) -> &'static str {
+        "ForeignScanExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return exec_err!("ForeignScanExec invalid partition {partition}");
+        }
+        let ffi = self.ffi.clone();
+        let projection = self.projection.clone();
+        let limit = self.limit;
+        let mut builder = RecordBatchReceiverStreamBuilder::new(self.schema(), 2);
+        let tx = builder.tx();
+        // Foreign scans may block
+        builder.spawn_blocking(move || {
+            let reader = ffi.scan_table(projection.as_deref(), limit)?;
+            for batch in reader {
+                if tx.blocking_send(batch.map_err(Into::into)).is_err() {
+                    break;
+                }
+            }
+            Ok(())
+        });
+        Ok(builder.build())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use crate::assert_batches_eq;
+    use crate::datasource::MemTable;
+
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field};
+
+    fn mem_table() -> Result<Arc<dyn TableProvider>> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, false),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec!["x", "y", "z"])),
+            ],
+        )?;
+        Ok(Arc::new(MemTable::try_new(schema, vec![vec![batch]])?))
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn roundtrip_table_provider() -> Result<()> {
+        let ffi = FFI_TableProvider::new(
+            mem_table()?,
+            SessionContext::new(),
+            Handle::current(),
+        );
+        let provider = ForeignTableProvider::try_new(ffi)?;
+        assert_eq!(provider.schema(), mem_table()?.schema());
+
+        let ctx = SessionContext::new();
+        ctx.register_table("t", Arc::new(provider))?;
+        let batches = ctx
+            .sql("SELECT b FROM t WHERE a > 1 ORDER BY a")
+            .await?
+            .collect()
+            .await?;
+        let expected = ["+---+", "| b |", "+---+", "| y |", "| z |", "+---+"];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn foreign_scan_error() -> Result<()> {
+        let ffi = FFI_TableProvider::new(
+            mem_table()?,
+            SessionContext::new(),
+            Handle::current(),
+        );
+        let provider = ForeignTableProvider::try_new(ffi)?;
+
+        // The projection is out of bounds of the exported table
+        let err = tokio::task::spawn_blocking(move || {
+            provider.ffi.scan_table(Some(&[5]), None).map(|_| ())
+        })
+        .await
+        .unwrap()
+        .unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Execution error: Failed to scan foreign table (error 5)"));
+        Ok(())
+    }
+
+    #[tokio::test(flavor = "current_thread")]
+    async fn scan_from_async_context() -> Result<()> {
+        let ffi = FFI_TableProvider::new(
+            mem_table()?,
+            SessionContext::new(),
+            Handle::current(),
+        );
+        // blocking on the runtime of the current thread would panic
+        let reader = ffi.scan_table(Some(&[0]), None)?;
+        let num_rows = reader
+            .map(|batch| batch.map(|batch| batch.num_rows()))
+            .sum::<std::result::Result<usize, _>>()?;
+        assert_eq!(num_rows, 3);
+        Ok(())
+    }
+
+    /// A table whose scans panic
+    #[derive(Debug)]
+    struct PanickingTable;
+
+    #[async_trait]
+    impl TableProvider for PanickingTable {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn schema(&self) -> SchemaRef {
+            Arc::new(Schema::empty())
+        }
+
+        fn table_type(&self) -> TableType {
+            TableType::Base
+        }
+
+        async fn scan(
+            &self,
+            _state: &SessionState,
+            _projection: Option<&Vec<usize>>,
+            _filters: &[Expr],
+            _limit: Option<usize>,
+        ) -> Result<Arc<dyn ExecutionPlan>> {
+            panic!("scan failed")
+        }
+    }
+
+    #[tokio::test(flavor = "multi_thread")]
+    async fn foreign_scan_panic() -> Result<()> {
+        let ffi = FFI_TableProvider::new(
+            Arc::new(PanickingTable),
+            SessionContext::new(),
+            Handle::current(),
+        );
+        let err = ffi.scan_table(None, None).unwrap_err();
+        assert!(
+            err.to_string()
+                .ends_with("FFI_TableProvider panicked: scan failed"),
+            "{err}"
+        );
+        Ok(())
+    }
+}
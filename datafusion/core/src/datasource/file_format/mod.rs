@@ -167,6 +167,7 @@ pub(crate) mod test_util {
                     table_partition_cols: vec![],
                     output_ordering: vec![],
                     column_hints: None,
+                    listing_pruning: None,
                 },
                 None,
             )
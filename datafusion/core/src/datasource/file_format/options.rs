@@ -489,6 +489,7 @@ impl ReadOptions<'_> for CsvReadOptions<'_> {
         ListingOptions::new(Arc::new(file_format))
             .with_file_extension(self.file_extension)
             .with_target_partitions(config.target_partitions())
+            .with_merge_schemas(config.options().execution.listing_table_merge_schemas)
             .with_table_partition_cols(self.table_partition_cols.clone())
             .with_file_sort_order(self.file_sort_order.clone())
     }
@@ -512,7 +513,9 @@ impl ReadOptions<'_> for ParquetReadOptions<'_> {
         config: &SessionConfig,
         table_options: TableOptions,
     ) -> ListingOptions {
-        let mut file_format = ParquetFormat::new().with_options(table_options.parquet);
+        let mut file_format = ParquetFormat::new()
+            .with_options(table_options.parquet)
+            .with_session_options(config.options().execution.parquet.clone());
 
         if let Some(parquet_pruning) = self.parquet_pruning {
             file_format = file_format.with_enable_pruning(parquet_pruning)
@@ -524,6 +527,7 @@ impl ReadOptions<'_> for ParquetReadOptions<'_> {
         ListingOptions::new(Arc::new(file_format))
             .with_file_extension(self.file_extension)
             .with_target_partitions(config.target_partitions())
+            .with_merge_schemas(config.options().execution.listing_table_merge_schemas)
             .with_table_partition_cols(self.table_partition_cols.clone())
             .with_file_sort_order(self.file_sort_order.clone())
             .with_column_hints(self.column_hints.clone())
@@ -555,6 +559,7 @@ impl ReadOptions<'_> for NdJsonReadOptions<'_> {
         ListingOptions::new(Arc::new(file_format))
             .with_file_extension(self.file_extension)
             .with_target_partitions(config.target_partitions())
+            .with_merge_schemas(config.options().execution.listing_table_merge_schemas)
             .with_table_partition_cols(self.table_partition_cols.clone())
             .with_file_sort_order(self.file_sort_order.clone())
     }
@@ -582,6 +587,7 @@ impl ReadOptions<'_> for AvroReadOptions<'_> {
         ListingOptions::new(Arc::new(file_format))
             .with_file_extension(self.file_extension)
             .with_target_partitions(config.target_partitions())
+            .with_merge_schemas(config.options().execution.listing_table_merge_schemas)
             .with_table_partition_cols(self.table_partition_cols.clone())
     }
 
@@ -608,6 +614,7 @@ impl ReadOptions<'_> for ArrowReadOptions<'_> {
         ListingOptions::new(Arc::new(file_format))
             .with_file_extension(self.file_extension)
             .with_target_partitions(config.target_partitions())
+            .with_merge_schemas(config.options().execution.listing_table_merge_schemas)
             .with_table_partition_cols(self.table_partition_cols.clone())
     }
 
@@ -30,6 +30,7 @@ use crate::arrow::array::{
 };
 use crate::arrow::datatypes::{DataType, Fields, Schema, SchemaRef};
 use crate::datasource::file_format::file_compression_type::FileCompressionType;
+use crate::datasource::listing::{FileRange, PartitionedFile};
 use crate::datasource::physical_plan::{
     FileGroupDisplay, FileSinkConfig, ParquetExec, SchemaAdapter,
 };
@@ -43,15 +44,17 @@ use crate::physical_plan::{
     Statistics,
 };
 
-use datafusion_common::config::TableParquetOptions;
+use datafusion_common::config::{ConfigOptions, ParquetOptions, TableParquetOptions};
 use datafusion_common::file_options::parquet_writer::ParquetWriterOptions;
-use datafusion_common::stats::Precision;
+use datafusion_common::hyperloglog::HyperLogLog;
+use datafusion_common::stats::{DistinctSketch, Precision};
 use datafusion_common::{
     exec_err, internal_datafusion_err, not_impl_err, DataFusionError, FileType,
 };
 use datafusion_common_runtime::SpawnedTask;
 use datafusion_execution::TaskContext;
-use datafusion_physical_expr::{PhysicalExpr, PhysicalSortRequirement};
+use datafusion_physical_expr::expressions::{ApproxDistinct, Column};
+use datafusion_physical_expr::{AggregateExpr, PhysicalExpr, PhysicalSortRequirement};
 use datafusion_physical_plan::metrics::MetricsSet;
 
 use async_trait::async_trait;
@@ -61,19 +64,23 @@ use parquet::arrow::arrow_writer::{
     compute_leaves, get_column_writers, ArrowColumnChunk, ArrowColumnWriter,
     ArrowLeafColumn,
 };
-use parquet::arrow::{arrow_to_parquet_schema, parquet_to_arrow_schema, AsyncArrowWriter, parquet_to_arrow_schema_by_columns, ProjectionMask};
+use parquet::arrow::{
+    arrow_to_parquet_schema, parquet_to_arrow_schema, parquet_to_arrow_schema_by_columns,
+    AsyncArrowWriter, ProjectionMask,
+};
 use parquet::file::footer::{decode_footer, decode_metadata};
 use parquet::file::metadata::ParquetMetaData;
 use parquet::file::properties::WriterProperties;
 use parquet::file::statistics::Statistics as ParquetStatistics;
 use parquet::file::writer::SerializedFileWriter;
-use parquet::format::FileMetaData;
+use parquet::format::{FileMetaData, KeyValue};
 use tokio::io::{AsyncWrite, AsyncWriteExt};
 use tokio::sync::mpsc::{self, Receiver, Sender};
+use tokio::sync::oneshot;
 use tokio::task::JoinSet;
 
 use futures::{StreamExt, TryStreamExt};
-use hashbrown::HashMap;
+use hashbrown::{HashMap, HashSet};
 use object_store::path::Path;
 use object_store::{ObjectMeta, ObjectStore};
 use parquet::schema::types::SchemaDescriptor;
@@ -86,10 +93,20 @@ const INITIAL_BUFFER_BYTES: usize = 1048576;
 /// this size, it is flushed to object store
 const BUFFER_FLUSH_BYTES: usize = 1024000;
 
+/// Prefix of the parquet key-value metadata under which the distinct sketch of
+/// each column is stored, followed by the column name.
+///
+/// See [`ParquetOptions::distinct_sketches_enabled`]
+///
+/// [`ParquetOptions::distinct_sketches_enabled`]: datafusion_common::config::ParquetOptions::distinct_sketches_enabled
+pub const DISTINCT_SKETCH_METADATA_PREFIX: &str = "datafusion.distinct_sketch.";
+
 /// The Apache Parquet `FileFormat` implementation
 #[derive(Debug, Default)]
 pub struct ParquetFormat {
     options: TableParquetOptions,
+    /// The session options `options` were created from, if any
+    session_options: Option<ParquetOptions>,
 }
 
 impl ParquetFormat {
@@ -142,6 +159,21 @@ impl ParquetFormat {
         self.options.global.skip_metadata
     }
 
+    /// Split the row groups of the files across the partitions of the scans
+    /// reading fewer files than `target_partitions`.
+    ///
+    /// - If `None`, defaults to value on `config_options`
+    pub fn with_split_row_groups(mut self, split_row_groups: bool) -> Self {
+        self.options.global.split_row_groups = split_row_groups;
+        self
+    }
+
+    /// Returns `true` if the row groups of the files are split across the
+    /// partitions of the scans
+    pub fn split_row_groups(&self) -> bool {
+        self.options.global.split_row_groups
+    }
+
     /// Set Parquet options for the ParquetFormat
     pub fn with_options(mut self, options: TableParquetOptions) -> Self {
         self.options = options;
@@ -152,6 +184,66 @@ impl ParquetFormat {
     pub fn options(&self) -> &TableParquetOptions {
         &self.options
     }
+
+    /// Record that the options were created from the session options
+    /// `session_options`.
+    ///
+    /// The read options (pruning, filter pushdown, page index, bloom
+    /// filters, metadata size hint, row group splitting) still equal to their session value then
+    /// follow the later changes of the session, e.g. with `SET`, instead of
+    /// keeping the value they had when the table was registered.
+    pub fn with_session_options(mut self, session_options: ParquetOptions) -> Self {
+        self.session_options = Some(session_options);
+        self
+    }
+
+    /// Returns the options to read the files with, given the current
+    /// options of the session
+    fn read_options(&self, config: &ConfigOptions) -> TableParquetOptions {
+        let mut options = self.options.clone();
+        let Some(snapshot) = &self.session_options else {
+            return options;
+        };
+        fn follow<T: PartialEq + Clone>(value: &mut T, snapshot: &T, current: &T) {
+            if value == snapshot {
+                *value = current.clone();
+            }
+        }
+        let current = &config.execution.parquet;
+        let global = &mut options.global;
+        follow(&mut global.pruning, &snapshot.pruning, &current.pruning);
+        follow(
+            &mut global.pushdown_filters,
+            &snapshot.pushdown_filters,
+            &current.pushdown_filters,
+        );
+        follow(
+            &mut global.reorder_filters,
+            &snapshot.reorder_filters,
+            &current.reorder_filters,
+        );
+        follow(
+            &mut global.enable_page_index,
+            &snapshot.enable_page_index,
+            &current.enable_page_index,
+        );
+        follow(
+            &mut global.bloom_filter_enabled,
+            &snapshot.bloom_filter_enabled,
+            &current.bloom_filter_enabled,
+        );
+        follow(
+            &mut global.metadata_size_hint,
+            &snapshot.metadata_size_hint,
+            &current.metadata_size_hint,
+        );
+        follow(
+            &mut global.split_row_groups,
+            &snapshot.split_row_groups,
+            &current.split_row_groups,
+        );
+        options
+    }
 }
 
 /// Clears all metadata (Schema level and field level) on an iterator
@@ -175,7 +267,7 @@ async fn fetch_schema_with_location(
     store: &dyn ObjectStore,
     file: &ObjectMeta,
     metadata_size_hint: Option<usize>,
-    columns: Option<Vec<String>>
+    columns: Option<Vec<String>>,
 ) -> Result<(Path, Schema)> {
     let loc_path = file.location.clone();
     let schema = fetch_schema(store, file, metadata_size_hint, columns).await?;
@@ -193,7 +285,7 @@ impl FileFormat for ParquetFormat {
         state: &SessionState,
         store: &Arc<dyn ObjectStore>,
         objects: &[ObjectMeta],
-        columns: Option<Vec<String>>
+        columns: Option<Vec<String>>,
     ) -> Result<SchemaRef> {
         let columns = columns.clone();
         let mut schemas: Vec<_> = futures::stream::iter(objects)
@@ -251,20 +343,38 @@ impl FileFormat for ParquetFormat {
 
     async fn create_physical_plan(
         &self,
-        _state: &SessionState,
-        conf: FileScanConfig,
+        state: &SessionState,
+        mut conf: FileScanConfig,
         filters: Option<&Arc<dyn PhysicalExpr>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
+        let options = self.read_options(state.config_options());
         // If enable pruning then combine the filters to build the predicate.
         // If disable pruning then set the predicate to None, thus readers
         // will not prune data based on the statistics.
-        let predicate = self.enable_pruning().then(|| filters.cloned()).flatten();
+        let predicate = options.global.pruning.then(|| filters.cloned()).flatten();
+        let metadata_size_hint = options.global.metadata_size_hint;
+
+        if options.global.split_row_groups
+            && state.config_options().optimizer.repartition_file_scans
+        {
+            let store = state.runtime_env().object_store(&conf.object_store_url)?;
+            let split = split_row_groups(
+                store.as_ref(),
+                &conf,
+                state.config().target_partitions(),
+                metadata_size_hint,
+            )
+            .await?;
+            if let Some(file_groups) = split {
+                conf.file_groups = file_groups;
+            }
+        }
 
         Ok(Arc::new(ParquetExec::new(
             conf,
             predicate,
-            self.metadata_size_hint(),
-            self.options.clone(),
+            metadata_size_hint,
+            options,
         )))
     }
 
@@ -439,6 +549,180 @@ pub async fn fetch_parquet_metadata(
     }
 }
 
+/// Splits the row groups of the files of the scan `conf` across
+/// `target_partitions` partitions, if it reads fewer files than partitions.
+/// Returns `None` if the files are not split.
+///
+/// Each file is split in contiguous ranges of row groups, of about the same
+/// compressed size of the columns read by the scan, the number of ranges of
+/// each file being proportional to its size. Each range is read by its own
+/// partition, so that the partitions keep the order of the files.
+///
+/// See [`ParquetOptions::split_row_groups`]
+async fn split_row_groups(
+    store: &dyn ObjectStore,
+    conf: &FileScanConfig,
+    target_partitions: usize,
+    metadata_size_hint: Option<usize>,
+) -> Result<Option<Vec<Vec<PartitionedFile>>>> {
+    let files = conf.file_groups.iter().flatten().collect::<Vec<_>>();
+    // the files already split in ranges are left as they are
+    if files.is_empty()
+        || files.len() >= target_partitions
+        || files.iter().any(|file| file.range.is_some())
+    {
+        return Ok(None);
+    }
+
+    // the metadata of the files are owned by the futures, for the stream to
+    // be `Send`
+    let objects = files
+        .iter()
+        .map(|file| file.object_meta.clone())
+        .collect::<Vec<_>>();
+    let row_groups = futures::stream::iter(objects)
+        .map(|object| async move {
+            let metadata =
+                fetch_parquet_metadata(store, &object, metadata_size_hint).await?;
+            Ok::<_, DataFusionError>(row_group_sizes(conf, &metadata))
+        })
+        .buffered(files.len())
+        .try_collect::<Vec<_>>()
+        .await?;
+    let total_size = row_groups
+        .iter()
+        .flatten()
+        .map(|(_, size)| size)
+        .sum::<u64>();
+    if total_size == 0 {
+        return Ok(None);
+    }
+
+    let mut file_groups = vec![];
+    for (file, row_groups) in files.into_iter().zip(row_groups) {
+        let file_size = row_groups.iter().map(|(_, size)| size).sum::<u64>();
+        let num_ranges =
+            (target_partitions as u128 * file_size as u128 / total_size as u128) as usize;
+        let ranges =
+            split_file_row_groups(&row_groups, num_ranges, file.object_meta.size);
+        file_groups.extend(ranges.into_iter().map(|range| {
+            vec![PartitionedFile {
+                range,
+                ..file.clone()
+            }]
+        }));
+    }
+    Ok(Some(file_groups))
+}
+
+/// Returns the offset of each row group of `metadata`, and the compressed
+/// size of the leaf columns the scan `conf` reads, or its number of rows if
+/// it reads no column
+fn row_group_sizes(conf: &FileScanConfig, metadata: &ParquetMetaData) -> Vec<(i64, u64)> {
+    let schema = metadata.file_metadata().schema_descr();
+    let leaves = projected_leaves(conf, schema);
+    metadata
+        .row_groups()
+        .iter()
+        .map(|row_group| {
+            // the readers assign a row group to the range containing the
+            // first page of its first column
+            let column = row_group.column(0);
+            let offset = column
+                .dictionary_page_offset()
+                .unwrap_or_else(|| column.data_page_offset());
+            let size = if leaves.is_empty() {
+                row_group.num_rows()
+            } else {
+                leaves
+                    .iter()
+                    .map(|leaf| row_group.column(*leaf).compressed_size())
+                    .sum()
+            };
+            (offset, size.max(0) as u64)
+        })
+        .collect()
+}
+
+/// Returns the indices of the leaf columns of `schema` read by the scan
+/// `conf`: the leaves of its column hints if any, or else the leaves of its
+/// projected columns
+fn projected_leaves(conf: &FileScanConfig, schema: &SchemaDescriptor) -> Vec<usize> {
+    if let Some(column_hints) = &conf.column_hints {
+        if let Ok(leaves) = find_leaf_ids(schema, column_hints.clone()) {
+            return leaves;
+        }
+    }
+    let names = match conf.file_column_projection_indices() {
+        Some(projection) => projection
+            .into_iter()
+            .map(|i| conf.file_schema.field(i).name().as_str())
+            .collect::<HashSet<_>>(),
+        None => conf
+            .file_schema
+            .fields()
+            .iter()
+            .map(|field| field.name().as_str())
+            .collect(),
+    };
+    (0..schema.num_columns())
+        .filter(|i| {
+            let column = schema.column(*i);
+            column
+                .path()
+                .parts()
+                .first()
+                .is_some_and(|name| names.contains(name.as_str()))
+        })
+        .collect()
+}
+
+/// Splits the row groups of a file of `file_size` bytes, given by their
+/// offsets and sizes, in up to `num_ranges` contiguous ranges of about the
+/// same size.
+///
+/// The ranges start at the offsets of their first row group, except the first
+/// one which starts at the start of the file, and end at the start of the
+/// next range, or at the end of the file. The file is not split, which
+/// returns a single `None` range, if it has fewer than two row groups, or if
+/// their offsets are not increasing.
+fn split_file_row_groups(
+    row_groups: &[(i64, u64)],
+    num_ranges: usize,
+    file_size: usize,
+) -> Vec<Option<FileRange>> {
+    let increasing = row_groups.windows(2).all(|pair| pair[0].0 < pair[1].0);
+    if num_ranges < 2 || row_groups.len() < 2 || !increasing {
+        return vec![None];
+    }
+    let total_size = row_groups
+        .iter()
+        .map(|(_, size)| *size as u128)
+        .sum::<u128>();
+    let mut ranges = vec![];
+    let mut start = 0;
+    let mut size = 0;
+    for (i, (_, row_group_size)) in row_groups.iter().enumerate() {
+        size += *row_group_size as u128;
+        let Some((next_offset, _)) = row_groups.get(i + 1) else {
+            break;
+        };
+        // end the range once it reaches its share of the size of the file
+        if size * num_ranges as u128 >= total_size * (ranges.len() as u128 + 1) {
+            ranges.push(Some(FileRange {
+                start,
+                end: *next_offset,
+            }));
+            start = *next_offset;
+        }
+    }
+    ranges.push(Some(FileRange {
+        start,
+        end: file_size as i64,
+    }));
+    ranges
+}
+
 /// Returns the index from the schema descriptor for a certain column path
 pub fn find_leaf_id(schema_desc_ptr: &SchemaDescriptor, name: &str) -> Result<usize> {
     let pos = schema_desc_ptr
@@ -472,21 +756,20 @@ async fn fetch_schema(
     let file_metadata = metadata.file_metadata();
     let schema = match column_hints {
         Some(cols) => {
-            let ids = find_leaf_ids(
-                metadata.file_metadata().schema_descr(),
-                cols.clone()
-            )?;
-            let mask = ProjectionMask::leaves(metadata.file_metadata().schema_descr(), ids);
+            let ids =
+                find_leaf_ids(metadata.file_metadata().schema_descr(), cols.clone())?;
+            let mask =
+                ProjectionMask::leaves(metadata.file_metadata().schema_descr(), ids);
             parquet_to_arrow_schema_by_columns(
                 file_metadata.schema_descr(),
                 mask,
-                file_metadata.key_value_metadata()
+                file_metadata.key_value_metadata(),
             )?
-        },
+        }
         _ => parquet_to_arrow_schema(
             file_metadata.schema_descr(),
             file_metadata.key_value_metadata(),
-        )?
+        )?,
     };
     Ok(schema)
 }
@@ -557,12 +840,20 @@ async fn fetch_statistics(
         }
     }
 
-    let column_stats = if has_statistics {
+    let mut column_stats = if has_statistics {
         get_col_stats(&table_schema, null_counts, &mut max_values, &mut min_values)
     } else {
         Statistics::unknown_column(&table_schema)
     };
 
+    let mut sketches = read_distinct_sketches(file_metadata.key_value_metadata());
+    for (field, column_stats) in fields.iter().zip(column_stats.iter_mut()) {
+        if let Some(sketch) = sketches.remove(field.name().as_str()) {
+            column_stats.distinct_count = Precision::Inexact(sketch.count());
+            column_stats.distinct_sketch = Some(sketch);
+        }
+    }
+
     let statistics = Statistics {
         num_rows: Precision::Exact(num_rows as usize),
         total_byte_size: Precision::Exact(total_byte_size as usize),
@@ -572,6 +863,92 @@ async fn fetch_statistics(
     Ok(statistics)
 }
 
+/// Reads the distinct sketches of the columns stored in the key-value metadata
+/// of a parquet file, by column name. Sketches that cannot be decoded are
+/// ignored.
+fn read_distinct_sketches(
+    key_value_metadata: Option<&Vec<KeyValue>>,
+) -> HashMap<&str, DistinctSketch> {
+    key_value_metadata
+        .into_iter()
+        .flatten()
+        .filter_map(|kv| {
+            let name = kv.key.strip_prefix(DISTINCT_SKETCH_METADATA_PREFIX)?;
+            let sketch = decode_distinct_sketch(kv.value.as_deref()?).ok()?;
+            Some((name, sketch))
+        })
+        .collect()
+}
+
+/// Encodes the registers of a distinct sketch with one printable ASCII
+/// character per register, offset from `'0'`
+fn encode_distinct_sketch(sketch: &DistinctSketch) -> String {
+    sketch
+        .registers()
+        .iter()
+        .map(|register| char::from(b'0' + register))
+        .collect()
+}
+
+/// Decodes a distinct sketch encoded by [`encode_distinct_sketch`]
+fn decode_distinct_sketch(value: &str) -> Result<DistinctSketch> {
+    let registers = value
+        .bytes()
+        .map(|c| c.checked_sub(b'0'))
+        .collect::<Option<Vec<_>>>()
+        .ok_or_else(|| internal_datafusion_err!("Invalid distinct sketch {value}"))?;
+    DistinctSketch::try_new(&registers)
+}
+
+/// Computes the distinct sketches of the columns of the batches written to a
+/// parquet file, for the columns supported by `approx_distinct`
+struct DistinctSketchWriter {
+    /// Name, index and `approx_distinct` accumulator of each column
+    columns: Vec<(String, usize, Box<dyn Accumulator>)>,
+}
+
+impl DistinctSketchWriter {
+    fn new(schema: &Schema) -> Self {
+        let columns = schema
+            .fields()
+            .iter()
+            .enumerate()
+            .filter_map(|(index, field)| {
+                let accumulator = ApproxDistinct::new(
+                    Arc::new(Column::new(field.name(), index)),
+                    field.name(),
+                    field.data_type().clone(),
+                )
+                .create_accumulator()
+                .ok()?;
+                Some((field.name().clone(), index, accumulator))
+            })
+            .collect();
+        Self { columns }
+    }
+
+    fn update(&mut self, batch: &RecordBatch) -> Result<()> {
+        for (_, index, accumulator) in self.columns.iter_mut() {
+            accumulator.update_batch(&[batch.column(*index).clone()])?;
+        }
+        Ok(())
+    }
+
+    /// Returns the key-value metadata storing the sketches
+    fn finish(self) -> Result<Vec<KeyValue>> {
+        self.columns
+            .into_iter()
+            .map(|(name, _, mut accumulator)| {
+                let hll = HyperLogLog::<[u8]>::try_from(&accumulator.state()?[0])?;
+                Ok(KeyValue::new(
+                    format!("{DISTINCT_SKETCH_METADATA_PREFIX}{name}"),
+                    encode_distinct_sketch(&DistinctSketch::from(&hll)),
+                ))
+            })
+            .collect()
+    }
+}
+
 /// Implements [`DataSink`] for writing to a parquet file.
 pub struct ParquetSink {
     /// Config options for writing data
@@ -685,7 +1062,10 @@ impl DataSink for ParquetSink {
         data: SendableRecordBatchStream,
         context: &Arc<TaskContext>,
     ) -> Result<u64> {
-        let parquet_props = ParquetWriterOptions::try_from(&self.parquet_options)?;
+        let parquet_props = ParquetWriterOptions::try_new_with_schema(
+            &self.parquet_options,
+            self.config.output_schema(),
+        )?;
 
         let object_store = context
             .runtime_env()
@@ -694,6 +1074,7 @@ impl DataSink for ParquetSink {
         let parquet_opts = &self.parquet_options;
         let allow_single_file_parallelism =
             parquet_opts.global.allow_single_file_parallelism;
+        let distinct_sketches_enabled = parquet_opts.global.distinct_sketches_enabled;
 
         let part_col = if !self.config.table_partition_cols.is_empty() {
             Some(self.config.table_partition_cols.clone())
@@ -723,6 +1104,8 @@ impl DataSink for ParquetSink {
         > = JoinSet::new();
 
         while let Some((path, mut rx)) = file_stream_rx.recv().await {
+            let mut distinct_sketches = distinct_sketches_enabled
+                .then(|| DistinctSketchWriter::new(&self.get_writer_schema()));
             if !allow_single_file_parallelism {
                 let mut writer = self
                     .create_async_arrow_writer(
@@ -733,8 +1116,16 @@ impl DataSink for ParquetSink {
                     .await?;
                 file_write_tasks.spawn(async move {
                     while let Some(batch) = rx.recv().await {
+                        if let Some(distinct_sketches) = &mut distinct_sketches {
+                            distinct_sketches.update(&batch)?;
+                        }
                         writer.write(&batch).await?;
                     }
+                    if let Some(distinct_sketches) = distinct_sketches {
+                        for kv in distinct_sketches.finish()? {
+                            writer.append_key_value_metadata(kv);
+                        }
+                    }
                     let file_metadata = writer
                         .close()
                         .await
@@ -760,6 +1151,7 @@ impl DataSink for ParquetSink {
                         schema,
                         props.writer_options(),
                         parallel_options_clone,
+                        distinct_sketches,
                     )
                     .await?;
                     Ok((path, file_metadata))
@@ -898,12 +1290,17 @@ fn spawn_rg_join_and_finalize_task(
 /// on the next row group in parallel. So, parquet serialization is parallelized
 /// accross both columns and row_groups, with a theoretical max number of parallel tasks
 /// given by n_columns * num_row_groups.
+///
+/// The distinct sketches of the written columns, if any, are sent to the
+/// concatenation task through `metadata_tx` once all the data is consumed.
 fn spawn_parquet_parallel_serialization_task(
     mut data: Receiver<RecordBatch>,
     serialize_tx: Sender<SpawnedTask<RBStreamSerializeResult>>,
+    metadata_tx: oneshot::Sender<Vec<KeyValue>>,
     schema: Arc<Schema>,
     writer_props: Arc<WriterProperties>,
     parallel_options: ParallelParquetWriterOptions,
+    mut distinct_sketches: Option<DistinctSketchWriter>,
 ) -> SpawnedTask<Result<(), DataFusionError>> {
     SpawnedTask::spawn(async move {
         let max_buffer_rb = parallel_options.max_buffered_record_batches_per_stream;
@@ -917,6 +1314,9 @@ fn spawn_parquet_parallel_serialization_task(
         let mut current_rg_rows = 0;
 
         while let Some(mut rb) = data.recv().await {
+            if let Some(distinct_sketches) = &mut distinct_sketches {
+                distinct_sketches.update(&rb)?;
+            }
             // This loop allows the "else" block to repeatedly split the RecordBatch to handle the case
             // when max_row_group_rows < execution.batch_size as an alternative to a recursive async
             // function.
@@ -973,6 +1373,16 @@ fn spawn_parquet_parallel_serialization_task(
             })?;
         }
 
+        let key_value_metadata = distinct_sketches
+            .map(|distinct_sketches| distinct_sketches.finish())
+            .transpose()?
+            .unwrap_or_default();
+        metadata_tx.send(key_value_metadata).map_err(|_| {
+            DataFusionError::Internal(
+                "Unable to send key value metadata to concat task!".into(),
+            )
+        })?;
+
         Ok(())
     })
 }
@@ -981,6 +1391,7 @@ fn spawn_parquet_parallel_serialization_task(
 /// to the final parquet file, while flushing finalized bytes to an [ObjectStore]
 async fn concatenate_parallel_row_groups(
     mut serialize_rx: Receiver<SpawnedTask<RBStreamSerializeResult>>,
+    metadata_rx: oneshot::Receiver<Vec<KeyValue>>,
     schema: Arc<Schema>,
     writer_props: Arc<WriterProperties>,
     mut object_store_writer: Box<dyn AsyncWrite + Send + Unpin>,
@@ -1011,6 +1422,14 @@ async fn concatenate_parallel_row_groups(
         rg_out.close()?;
     }
 
+    // The sender is dropped without sending if the serialization task failed,
+    // in which case its error is reported when joining it
+    if let Ok(key_value_metadata) = metadata_rx.await {
+        for kv in key_value_metadata {
+            parquet_writer.append_key_value_metadata(kv);
+        }
+    }
+
     let file_metadata = parquet_writer.close()?;
     let final_buff = merged_buff.buffer.try_lock().unwrap();
 
@@ -1030,22 +1449,27 @@ async fn output_single_parquet_file_parallelized(
     output_schema: Arc<Schema>,
     parquet_props: &WriterProperties,
     parallel_options: ParallelParquetWriterOptions,
+    distinct_sketches: Option<DistinctSketchWriter>,
 ) -> Result<FileMetaData> {
     let max_rowgroups = parallel_options.max_parallel_row_groups;
     // Buffer size of this channel limits maximum number of RowGroups being worked on in parallel
     let (serialize_tx, serialize_rx) =
         mpsc::channel::<SpawnedTask<RBStreamSerializeResult>>(max_rowgroups);
+    let (metadata_tx, metadata_rx) = oneshot::channel();
 
     let arc_props = Arc::new(parquet_props.clone());
     let launch_serialization_task = spawn_parquet_parallel_serialization_task(
         data,
         serialize_tx,
+        metadata_tx,
         output_schema.clone(),
         arc_props.clone(),
         parallel_options,
+        distinct_sketches,
     );
     let file_metadata = concatenate_parallel_row_groups(
         serialize_rx,
+        metadata_rx,
         output_schema.clone(),
         arc_props.clone(),
         object_store_writer,
@@ -1139,7 +1563,7 @@ pub(crate) mod test_util {
 mod tests {
     use super::super::test_util::scan_format;
     use crate::datasource::listing::{ListingTableUrl, PartitionedFile};
-    use crate::physical_plan::collect;
+    use crate::physical_plan::{collect, ExecutionPlanProperties};
     use std::fmt::{Display, Formatter};
     use std::sync::atomic::{AtomicUsize, Ordering};
 
@@ -1147,7 +1571,7 @@ mod tests {
 
     use crate::datasource::file_format::parquet::test_util::store_parquet;
     use crate::physical_plan::metrics::MetricValue;
-    use crate::prelude::{SessionConfig, SessionContext};
+    use crate::prelude::{ParquetReadOptions, SessionConfig, SessionContext};
     use arrow::array::{Array, ArrayRef, StringArray};
     use arrow_schema::Field;
     use async_trait::async_trait;
@@ -1168,7 +1592,7 @@ mod tests {
         GetOptions, GetResult, ListResult, MultipartId, PutOptions, PutResult,
     };
     use parquet::arrow::arrow_reader::ArrowReaderOptions;
-    use parquet::arrow::ParquetRecordBatchStreamBuilder;
+    use parquet::arrow::{ArrowWriter, ParquetRecordBatchStreamBuilder};
     use parquet::file::metadata::{ParquetColumnIndex, ParquetOffsetIndex};
     use parquet::file::page_index::index::Index;
     use tokio::fs::File;
@@ -1189,7 +1613,10 @@ mod tests {
         let session = SessionContext::new();
         let ctx = session.state();
         let format = ParquetFormat::default();
-        let schema = format.infer_schema(&ctx, &store, &meta, None).await.unwrap();
+        let schema = format
+            .infer_schema(&ctx, &store, &meta, None)
+            .await
+            .unwrap();
 
         let stats =
             fetch_statistics(store.as_ref(), schema.clone(), &meta[0], None).await?;
@@ -1238,7 +1665,10 @@ mod tests {
         let session = SessionContext::new();
         let ctx = session.state();
         let format = ParquetFormat::default();
-        let schema = format.infer_schema(&ctx, &store, &meta, None).await.unwrap();
+        let schema = format
+            .infer_schema(&ctx, &store, &meta, None)
+            .await
+            .unwrap();
 
         let order: Vec<_> = ["a", "b", "c", "d"]
             .into_iter()
@@ -1500,6 +1930,75 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn split_row_groups_in_ranges() {
+        let row_groups = [(4, 10), (100, 10), (200, 30), (300, 10), (400, 10)];
+        let range = |start, end| Some(FileRange { start, end });
+        assert_eq!(
+            split_file_row_groups(&row_groups, 3, 500),
+            [range(0, 300), range(300, 400), range(400, 500)]
+        );
+        assert_eq!(split_file_row_groups(&row_groups, 1, 500), [None]);
+        assert_eq!(split_file_row_groups(&row_groups[..1], 3, 500), [None]);
+        // a row group larger than the share of each range is not split
+        let row_groups = [(4, 10), (100, 100), (200, 10)];
+        assert_eq!(
+            split_file_row_groups(&row_groups, 4, 300),
+            [range(0, 200), range(200, 300)]
+        );
+    }
+
+    #[tokio::test]
+    async fn read_split_row_groups() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let batch = RecordBatch::try_from_iter([(
+            "a",
+            Arc::new(Int64Array::from_iter_values(0..100)) as ArrayRef,
+        )])?;
+        let props = WriterProperties::builder()
+            .set_max_row_group_size(10)
+            .build();
+        let file = std::fs::File::create(dir.path().join("a.parquet"))?;
+        let mut writer = ArrowWriter::try_new(file, batch.schema(), Some(props))?;
+        writer.write(&batch)?;
+        writer.close()?;
+
+        let config = SessionConfig::new()
+            .with_target_partitions(4)
+            .set_bool("datafusion.execution.parquet.split_row_groups", true);
+        let ctx = SessionContext::new_with_config(config);
+        ctx.sql(&format!(
+            "CREATE EXTERNAL TABLE t (a BIGINT) STORED AS PARQUET \
+            WITH ORDER (a ASC) LOCATION '{}'",
+            dir.path().display()
+        ))
+        .await?;
+
+        // the ranges of row groups of the file keep its order
+        let plan = ctx
+            .sql("SELECT a FROM t")
+            .await?
+            .create_physical_plan()
+            .await?;
+        assert_eq!(plan.output_partitioning().partition_count(), 4);
+        assert!(plan.output_ordering().is_some());
+
+        let batches = ctx
+            .sql("SELECT count(*), sum(a) FROM t")
+            .await?
+            .collect()
+            .await?;
+        let expected = [
+            "+----------+----------+",
+            "| COUNT(*) | SUM(t.a) |",
+            "+----------+----------+",
+            "| 100      | 4950     |",
+            "+----------+----------+",
+        ];
+        crate::assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn read_limit() -> Result<()> {
         let session_ctx = SessionContext::new();
@@ -1950,6 +2449,64 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn parquet_write_distinct_sketches() -> Result<()> {
+        for allow_single_file_parallelism in [false, true] {
+            let config = SessionConfig::new().with_collect_statistics(true);
+            let ctx = SessionContext::new_with_config(config);
+
+            let tmp_dir = tempfile::TempDir::new()?;
+            for (start, end) in [(0, 999), (1000, 1999)] {
+                let path = tmp_dir.path().join(format!("{start}.parquet"));
+                ctx.sql(&format!(
+                    "COPY (SELECT v % 100 AS a, v AS b, 'x' AS c, v * 0.5 AS d \
+                     FROM (SELECT unnest(generate_series({start}, {end})) AS v)) \
+                     TO '{}' STORED AS PARQUET OPTIONS (\
+                     'format.distinct_sketches_enabled' 'true', \
+                     'format.allow_single_file_parallelism' \
+                     '{allow_single_file_parallelism}')",
+                    path.display()
+                ))
+                .await?
+                .collect()
+                .await?;
+            }
+            ctx.register_parquet(
+                "t",
+                tmp_dir.path().to_str().unwrap(),
+                ParquetReadOptions::default(),
+            )
+            .await?;
+
+            let table = ctx.table_provider("t").await?;
+            let exec = table.scan(&ctx.state(), None, &[], None).await?;
+            let column_statistics = exec.statistics()?.column_statistics;
+
+            // The sketches of both files are merged to estimate the distinct
+            // values of the table
+            let assert_distinct_count = |index: usize, expected: usize| {
+                let Precision::Inexact(count) = column_statistics[index].distinct_count
+                else {
+                    panic!("expected an inexact distinct count for column {index}")
+                };
+                assert!(
+                    count.abs_diff(expected) * 100 <= expected,
+                    "expected about {expected} distinct values, got {count}"
+                );
+                assert!(column_statistics[index].distinct_sketch.is_some());
+            };
+            assert_distinct_count(0, 100);
+            assert_distinct_count(1, 2000);
+            assert_distinct_count(2, 1);
+
+            // Floats are not supported by approx_distinct
+            assert_eq!(column_statistics[3].distinct_count, Precision::Absent);
+            assert_eq!(column_statistics[3].distinct_sketch, None);
+        }
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn parquet_sink_write_partitions() -> Result<()> {
         let field_a = Field::new("a", DataType::Utf8, false);
@@ -0,0 +1,756 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! TableProvider reading a table served by an [Arrow Flight] endpoint
+//!
+//! A [`FlightTable`] fetches the schema of the remote table once, with
+//! `GetSchema` on a descriptor whose path is the name of the table, and
+//! scans it with `DoGet`. The ticket is a [`FlightTicket`], naming the
+//! projected columns of the table, the pushed down filters and the limit of
+//! the scan, which a DataFusion instance serving its tables over Flight reads
+//! with [`FlightTicket::to_dataframe`]. The tables are created with:
+//!
+//! ```sql
+//! CREATE EXTERNAL TABLE t STORED AS FLIGHT
+//! LOCATION 'grpc://localhost:50051'
+//! OPTIONS ('table' 'remote_table');
+//! ```
+//!
+//! [Arrow Flight]: https://arrow.apache.org/docs/format/Flight.html
+
+use std::any::Any;
+use std::fmt::{self, Formatter};
+use std::sync::Arc;
+
+use arrow::compute::cast;
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use arrow_flight::error::FlightError;
+use arrow_flight::{FlightClient, FlightDescriptor, Ticket};
+use async_trait::async_trait;
+use futures::{StreamExt, TryStreamExt};
+use prost::Message;
+use sqlparser::ast::Ident;
+use sqlparser::dialect::GenericDialect;
+use sqlparser::parser::Parser;
+use sqlparser::tokenizer::Token;
+use tonic::transport::Channel;
+
+use datafusion_common::{exec_err, plan_datafusion_err, plan_err, DataFusionError};
+use datafusion_execution::TaskContext;
+use datafusion_expr::{CreateExternalTable, TableProviderFilterPushDown};
+use datafusion_physical_expr::EquivalenceProperties;
+use datafusion_sql::unparser::dialect::DefaultDialect;
+
+use crate::dataframe::DataFrame;
+use crate::datasource::federation::{filter_pushdown, filter_to_sql};
+use crate::datasource::provider::TableProviderFactory;
+use crate::datasource::{TableProvider, TableType};
+use crate::error::Result;
+use crate::execution::context::{SQLOptions, SessionContext, SessionState};
+use crate::logical_expr::Expr;
+use crate::physical_plan::stream::RecordBatchStreamAdapter;
+use crate::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionMode, ExecutionPlan, Partitioning,
+    PlanProperties, SendableRecordBatchStream,
+};
+
+/// The option of `CREATE EXTERNAL TABLE ... STORED AS FLIGHT` naming the
+/// remote table, the name of the created table if not set
+pub const FLIGHT_TABLE_OPTION: &str = "table";
+
+/// A [`TableProviderFactory`] for [`FlightTable`], connecting to the endpoint
+/// of the `LOCATION` of the table
+///
+/// The schema of the remote table is fetched from the endpoint unless the
+/// columns of the table are declared.
+#[derive(Debug, Default)]
+pub struct FlightTableFactory {}
+
+#[async_trait]
+impl TableProviderFactory for FlightTableFactory {
+    async fn create(
+        &self,
+        _state: &SessionState,
+        cmd: &CreateExternalTable,
+    ) -> Result<Arc<dyn TableProvider>> {
+        let table = match cmd.options.get(FLIGHT_TABLE_OPTION) {
+            Some(table) => table.clone(),
+            None => cmd.name.table().to_string(),
+        };
+        let table = if cmd.schema.fields().is_empty() {
+            FlightTable::try_new(cmd.location.clone(), table).await?
+        } else {
+            let schema = Arc::new(cmd.schema.as_ref().into());
+            FlightTable::new(cmd.location.clone(), table, schema)
+        };
+        Ok(Arc::new(table))
+    }
+}
+
+/// A table served by an Arrow Flight endpoint, see the [module
+/// documentation](self)
+///
+/// The filters which can be written in SQL are evaluated by the endpoint, as
+/// is the limit of the scans. Like for the tables of SQL databases, only the
+/// filters the endpoint is known to evaluate exactly like DataFusion are not
+/// evaluated again.
+#[derive(Debug, Clone)]
+pub struct FlightTable {
+    endpoint: String,
+    table: String,
+    schema: SchemaRef,
+}
+
+impl FlightTable {
+    /// Fetch the schema of the table `table` of the Flight endpoint
+    /// `endpoint`, e.g. `grpc://localhost:50051`
+    pub async fn try_new(
+        endpoint: impl Into<String>,
+        table: impl Into<String>,
+    ) -> Result<Self> {
+        let endpoint = endpoint.into();
+        let table = table.into();
+        let descriptor = FlightDescriptor::new_path(vec![table.clone()]);
+        let schema = FlightClient::new(connect(&endpoint).await?)
+            .get_schema(descriptor)
+            .await
+            .map_err(flight_error)?;
+        Ok(Self::new(endpoint, table, Arc::new(schema)))
+    }
+
+    /// Create the table `table` of the Flight endpoint `endpoint`, whose
+    /// schema is known to be `schema`
+    pub fn new(
+        endpoint: impl Into<String>,
+        table: impl Into<String>,
+        schema: SchemaRef,
+    ) -> Self {
+        Self {
+            endpoint: endpoint.into(),
+            table: table.into(),
+            schema,
+        }
+    }
+
+    /// The Flight endpoint serving the table
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// The name of the remote table
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// Returns the ticket scanning the columns `projection` of the rows
+    /// matching `filters`, up to `limit` rows
+    fn ticket(
+        &self,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<FlightTicket> {
+        let schema = match projection {
+            Some(projection) => Arc::new(self.schema.project(projection)?),
+            None => self.schema.clone(),
+        };
+        let filters = filters
+            .iter()
+            .map(|filter| filter_to_sql(&DefaultDialect {}, filter))
+            .collect::<Result<_>>()?;
+        Ok(FlightTicket {
+            table: self.table.clone(),
+            columns: schema.fields().iter().map(|f| f.name().clone()).collect(),
+            filters,
+            limit: limit.map(|limit| limit as u64),
+        })
+    }
+}
+
+#[async_trait]
+impl TableProvider for FlightTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        _state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let schema = match projection {
+            Some(projection) => Arc::new(self.schema.project(projection)?),
+            None => self.schema.clone(),
+        };
+        let ticket = self.ticket(projection, filters, limit)?;
+        Ok(Arc::new(FlightExec::new(
+            self.endpoint.clone(),
+            ticket,
+            schema,
+        )))
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        Ok(filters
+            .iter()
+            .map(|filter| filter_pushdown(&DefaultDialect {}, &self.schema, filter))
+            .collect())
+    }
+}
+
+/// The ticket of the `DoGet` calls scanning a [`FlightTable`], encoded in
+/// protobuf
+///
+/// The filters are SQL expressions on the columns of the remote table: the
+/// protobuf serialization of expressions of `datafusion-proto` depends on
+/// this crate.
+#[derive(Clone, PartialEq, prost::Message)]
+pub struct FlightTicket {
+    /// The name of the remote table
+    #[prost(string, tag = "1")]
+    pub table: String,
+    /// The names of the projected columns, none if only the number of rows
+    /// is needed
+    #[prost(string, repeated, tag = "2")]
+    pub columns: Vec<String>,
+    /// The filters matched by the rows
+    #[prost(string, repeated, tag = "3")]
+    pub filters: Vec<String>,
+    /// The maximum number of rows
+    #[prost(uint64, optional, tag = "4")]
+    pub limit: Option<u64>,
+}
+
+impl FlightTicket {
+    /// Decode the ticket of a `DoGet` call
+    pub fn try_from_ticket(ticket: &Ticket) -> Result<Self> {
+        Self::decode(ticket.ticket.clone())
+            .map_err(|e| DataFusionError::External(Box::new(e)))
+    }
+
+    /// Encode as the ticket of a `DoGet` call
+    pub fn to_ticket(&self) -> Ticket {
+        Ticket::new(self.encode_to_vec())
+    }
+
+    /// Returns the query selecting the rows of the ticket, each filter being
+    /// checked to be a single SQL expression
+    pub fn query(&self) -> Result<String> {
+        let ident = |name: &str| Ident::with_quote('"', name).to_string();
+        let columns = match self.columns.is_empty() {
+            true => "1".to_string(),
+            false => self
+                .columns
+                .iter()
+                .map(|column| ident(column))
+                .collect::<Vec<_>>()
+                .join(", "),
+        };
+        let mut query = format!("SELECT {columns} FROM {}", ident(&self.table));
+        let filters = self
+            .filters
+            .iter()
+            .map(|filter| Ok(format!("({})", parse_filter(filter)?)))
+            .collect::<Result<Vec<_>>>()?;
+        if !filters.is_empty() {
+            query.push_str(&format!(" WHERE {}", filters.join(" AND ")));
+        }
+        if let Some(limit) = self.limit {
+            query.push_str(&format!(" LIMIT {limit}"));
+        }
+        Ok(query)
+    }
+
+    /// Returns the rows of the ticket, read from the tables of `ctx`
+    ///
+    /// This is how a DataFusion instance serving its tables over Flight
+    /// answers the `DoGet` calls of [`FlightTable`]s.
+    pub async fn to_dataframe(&self, ctx: &SessionContext) -> Result<DataFrame> {
+        let options = SQLOptions::new()
+            .with_allow_ddl(false)
+            .with_allow_dml(false)
+            .with_allow_statements(false);
+        ctx.sql_with_options(&self.query()?, options).await
+    }
+}
+
+/// Parses the filter `filter` of a ticket, which must be a single expression
+fn parse_filter(filter: &str) -> Result<sqlparser::ast::Expr> {
+    let mut parser = Parser::new(&GenericDialect {}).try_with_sql(filter)?;
+    let expr = parser.parse_expr()?;
+    if parser.peek_token().token != Token::EOF {
+        return plan_err!("Flight ticket filter is not an expression: {filter}");
+    }
+    Ok(expr)
+}
+
+/// Execution plan scanning a [`FlightTable`] with a single `DoGet` call
+#[derive(Debug)]
+pub struct FlightExec {
+    endpoint: String,
+    ticket: FlightTicket,
+    cache: PlanProperties,
+}
+
+impl FlightExec {
+    /// Create the scan of the rows of the ticket `ticket` of the Flight
+    /// endpoint `endpoint`, read with the schema `schema`
+    pub fn new(
+        endpoint: impl Into<String>,
+        ticket: FlightTicket,
+        schema: SchemaRef,
+    ) -> Self {
+        let cache = PlanProperties::new(
+            EquivalenceProperties::new(schema),
+            Partitioning::UnknownPartitioning(1),
+            ExecutionMode::Bounded,
+        );
+        Self {
+            endpoint: endpoint.into(),
+            ticket,
+            cache,
+        }
+    }
+
+    /// The Flight endpoint serving the table
+    pub fn endpoint(&self) -> &str {
+        &self.endpoint
+    }
+
+    /// The ticket of the `DoGet` call
+    pub fn ticket(&self) -> &FlightTicket {
+        &self.ticket
+    }
+}
+
+impl DisplayAs for FlightExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "FlightExec: table={}, columns=[{}]",
+                    self.ticket.table,
+                    self.ticket.columns.join(", ")
+                )?;
+                if !self.ticket.filters.is_empty() {
+                    write!(f, ", filters=[{}]", self.ticket.filters.join(", "))?;
+                }
+                if let Some(limit) = self.ticket.limit {
+                    write!(f, ", limit={limit}")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for FlightExec {
+    fn name(&self) -> &'static str {
+        "FlightExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if partition != 0 {
+            return exec_err!("FlightExec invalid partition {partition}");
+        }
+        let schema = self.schema();
+        let endpoint = self.endpoint.clone();
+        let ticket = self.ticket.to_ticket();
+        let stream_schema = schema.clone();
+        let stream = futures::stream::once(async move {
+            let mut client = FlightClient::new(connect(&endpoint).await?);
+            client.do_get(ticket).await.map_err(flight_error)
+        })
+        .map_ok(|batches| batches.map_err(flight_error))
+        .try_flatten()
+        .map(move |batch| cast_batch(batch?, &stream_schema));
+        Ok(Box::pin(RecordBatchStreamAdapter::new(schema, stream)))
+    }
+}
+
+/// Returns `batch` with the schema `schema` of the scan, casting its columns,
+/// as the endpoint may return fields with other types, nullability or
+/// metadata, and a batch with the constant column of an empty projection
+fn cast_batch(batch: RecordBatch, schema: &SchemaRef) -> Result<RecordBatch> {
+    let columns = if schema.fields().is_empty() {
+        vec![]
+    } else if batch.num_columns() != schema.fields().len() {
+        return exec_err!(
+            "FlightExec expected {} columns, got {}",
+            schema.fields().len(),
+            batch.num_columns()
+        );
+    } else {
+        batch
+            .columns()
+            .iter()
+            .zip(schema.fields())
+            .map(|(column, field)| Ok(cast(column, field.data_type())?))
+            .collect::<Result<_>>()?
+    };
+    let options = RecordBatchOptions::new().with_row_count(Some(batch.num_rows()));
+    Ok(RecordBatch::try_new_with_options(
+        schema.clone(),
+        columns,
+        &options,
+    )?)
+}
+
+/// Connects to the Flight endpoint `endpoint`, whose `grpc` and `grpc+tcp`
+/// schemes are plain HTTP/2 connections
+async fn connect(endpoint: &str) -> Result<Channel> {
+    let endpoint = match endpoint.split_once("://") {
+        Some(("grpc" | "grpc+tcp", address)) => format!("http://{address}"),
+        _ => endpoint.to_string(),
+    };
+    Channel::from_shared(endpoint.clone())
+        .map_err(|e| plan_datafusion_err!("Invalid Flight endpoint {endpoint}: {e}"))?
+        .connect()
+        .await
+        .map_err(|e| DataFusionError::External(Box::new(e)))
+}
+
+fn flight_error(e: FlightError) -> DataFusionError {
+    match e {
+        FlightError::Arrow(e) => e.into(),
+        e => DataFusionError::External(Box::new(e)),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+    use crate::datasource::MemTable;
+    use crate::physical_plan::{collect, displayable};
+
+    use arrow::array::{Int32Array, StringArray};
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow_flight::encode::FlightDataEncoderBuilder;
+    use arrow_flight::flight_service_server::{FlightService, FlightServiceServer};
+    use arrow_flight::{
+        Action, ActionType, Criteria, Empty, FlightData, FlightInfo, HandshakeRequest,
+        HandshakeResponse, PollInfo, PutResult, SchemaAsIpc, SchemaResult,
+    };
+    use datafusion_common::Column;
+    use datafusion_expr::{col, lit};
+    use futures::stream::BoxStream;
+    use tokio::net::TcpListener;
+    use tonic::transport::Server;
+    use tonic::{Request, Response, Status, Streaming};
+
+    /// A Flight endpoint serving the tables of a [`SessionContext`]
+    struct TestFlightService {
+        ctx: SessionContext,
+    }
+
+    fn to_status(e: DataFusionError) -> Status {
+        Status::internal(e.to_string())
+    }
+
+    #[tonic::async_trait]
+    impl FlightService for TestFlightService {
+        type HandshakeStream = BoxStream<'static, Result<HandshakeResponse, Status>>;
+        type ListFlightsStream = BoxStream<'static, Result<FlightInfo, Status>>;
+        type DoGetStream = BoxStream<'static, Result<FlightData, Status>>;
+        type DoPutStream = BoxStream<'static, Result<PutResult, Status>>;
+        type DoActionStream = BoxStream<'static, Result<arrow_flight::Result, Status>>;
+        type ListActionsStream = BoxStream<'static, Result<ActionType, Status>>;
+        type DoExchangeStream = BoxStream<'static, Result<FlightData, Status>>;
+
+        async fn get_schema(
+            &self,
+            request: Request<FlightDescriptor>,
+        ) -> Result<Response<SchemaResult>, Status> {
+            let table = request.into_inner().path.join(".");
+            let provider = self.ctx.table_provider(table).await.map_err(to_status)?;
+            let options = arrow::ipc::writer::IpcWriteOptions::default();
+            let schema = SchemaAsIpc::new(&provider.schema(), &options)
+                .try_into()
+                .map_err(|e: arrow::error::ArrowError| Status::internal(e.to_string()))?;
+            Ok(Response::new(schema))
+        }
+
+        async fn do_get(
+            &self,
+            request: Request<Ticket>,
+        ) -> Result<Response<Self::DoGetStream>, Status> {
+            let ticket = FlightTicket::try_from_ticket(&request.into_inner())
+                .map_err(to_status)?;
+            let stream = ticket
+                .to_dataframe(&self.ctx)
+                .await
+                .map_err(to_status)?
+                .execute_stream()
+                .await
+                .map_err(to_status)?
+                .map_err(|e| FlightError::ExternalError(Box::new(e)));
+            let flights = FlightDataEncoderBuilder::new()
+                .build(stream)
+                .map_err(Status::from);
+            Ok(Response::new(Box::pin(flights)))
+        }
+
+        async fn handshake(
+            &self,
+            _request: Request<Streaming<HandshakeRequest>>,
+        ) -> Result<Response<Self::HandshakeStream>, Status> {
+            Err(Status::unimplemented("Not yet implemented"))
+        }
+
+        async fn list_flights(
+            &self,
+            _request: Request<Criteria>,
+        ) -> Result<Response<Self::ListFlightsStream>, Status> {
+            Err(Status::unimplemented("Not yet implemented"))
+        }
+
+        async fn get_flight_info(
+            &self,
+            _request: Request<FlightDescriptor>,
+        ) -> Result<Response<FlightInfo>, Status> {
+            Err(Status::unimplemented("Not yet implemented"))
+        }
+
+        async fn poll_flight_info(
+            &self,
+            _request: Request<FlightDescriptor>,
+        ) -> Result<Response<PollInfo>, Status> {
+            Err(Status::unimplemented("Not yet implemented"))
+        }
+
+        async fn do_put(
+            &self,
+            _request: Request<Streaming<FlightData>>,
+        ) -> Result<Response<Self::DoPutStream>, Status> {
+            Err(Status::unimplemented("Not yet implemented"))
+        }
+
+        async fn do_action(
+            &self,
+            _request: Request<Action>,
+        ) -> Result<Response<Self::DoActionStream>, Status> {
+            Err(Status::unimplemented("Not yet implemented"))
+        }
+
+        async fn list_actions(
+            &self,
+            _request: Request<Empty>,
+        ) -> Result<Response<Self::ListActionsStream>, Status> {
+            Err(Status::unimplemented("Not yet implemented"))
+        }
+
+        async fn do_exchange(
+            &self,
+            _request: Request<Streaming<FlightData>>,
+        ) -> Result<Response<Self::DoExchangeStream>, Status> {
+            Err(Status::unimplemented("Not yet implemented"))
+        }
+    }
+
+    /// Serves the tables of `ctx` on a local port, returning the endpoint
+    async fn serve(ctx: SessionContext) -> Result<String> {
+        let listener = TcpListener::bind("127.0.0.1:0").await?;
+        let endpoint = format!("grpc://{}", listener.local_addr()?);
+        let incoming = futures::stream::unfold(listener, |listener| async move {
+            let stream = listener.accept().await.map(|(stream, _)| stream);
+            Some((stream, listener))
+        });
+        let service = FlightServiceServer::new(TestFlightService { ctx });
+        tokio::spawn(
+            Server::builder()
+                .add_service(service)
+                .serve_with_incoming(incoming),
+        );
+        Ok(endpoint)
+    }
+
+    fn remote_schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Utf8, true),
+        ]))
+    }
+
+    fn remote_ctx() -> Result<SessionContext> {
+        let batch = RecordBatch::try_new(
+            remote_schema(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(StringArray::from(vec![Some("x"), None, Some("z")])),
+            ],
+        )?;
+        let ctx = SessionContext::new();
+        let table = MemTable::try_new(remote_schema(), vec![vec![batch]])?;
+        ctx.register_table("remote", Arc::new(table))?;
+        Ok(ctx)
+    }
+
+    #[test]
+    fn flight_ticket_query() -> Result<()> {
+        let table = FlightTable::new("grpc://localhost:50051", "remote", remote_schema());
+        assert_eq!(
+            table.ticket(None, &[], None)?.query()?,
+            r#"SELECT "a", "b" FROM "remote""#
+        );
+        assert_eq!(
+            table.ticket(Some(&vec![1]), &[], Some(10))?.query()?,
+            r#"SELECT "b" FROM "remote" LIMIT 10"#
+        );
+        assert_eq!(
+            table.ticket(Some(&vec![]), &[], None)?.query()?,
+            r#"SELECT 1 FROM "remote""#
+        );
+
+        // the filters refer to the columns of the remote table
+        let filters = [
+            Expr::Column(Column::new(Some("t"), "a")).gt(lit(1)),
+            col("b").is_not_null(),
+        ];
+        let ticket = table.ticket(Some(&vec![0]), &filters, Some(5))?;
+        assert_eq!(ticket.filters, vec![r#"("a" > 1)"#, r#""b" IS NOT NULL"#]);
+        assert_eq!(
+            ticket.query()?,
+            r#"SELECT "a" FROM "remote" WHERE (("a" > 1)) AND ("b" IS NOT NULL) LIMIT 5"#
+        );
+
+        // the tickets are encoded in protobuf
+        assert_eq!(FlightTicket::try_from_ticket(&ticket.to_ticket())?, ticket);
+        Ok(())
+    }
+
+    #[test]
+    fn flight_ticket_invalid_filter() {
+        let ticket = FlightTicket {
+            table: "remote".to_string(),
+            columns: vec!["a".to_string()],
+            filters: vec!["a > 1; DROP TABLE remote".to_string()],
+            limit: None,
+        };
+        let err = ticket.query().unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Flight ticket filter is not an expression"),
+            "{err}"
+        );
+    }
+
+    #[test]
+    fn flight_filters_pushdown() -> Result<()> {
+        let table = FlightTable::new("grpc://localhost:50051", "remote", remote_schema());
+        let exact = col("a").eq(lit(1));
+        let inexact = col("b").eq(lit("x"));
+        let unsupported = (col("a") + lit(1)).gt(lit(2));
+        assert_eq!(
+            table.supports_filters_pushdown(&[&exact, &inexact, &unsupported])?,
+            vec![
+                TableProviderFilterPushDown::Exact,
+                TableProviderFilterPushDown::Inexact,
+                TableProviderFilterPushDown::Unsupported
+            ]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn flight_table_do_get() -> Result<()> {
+        let endpoint = serve(remote_ctx()?).await?;
+        let ctx = SessionContext::new();
+        let table = FlightTable::try_new(endpoint.clone(), "remote").await?;
+        assert_eq!(table.schema(), remote_schema());
+        ctx.register_table("t", Arc::new(table))?;
+
+        let df = ctx.sql("SELECT b FROM t WHERE a > 1").await?;
+        let plan = df.clone().create_physical_plan().await?;
+        let plan = displayable(plan.as_ref()).indent(true).to_string();
+        assert!(
+            plan.contains(
+                r#"FlightExec: table=remote, columns=[b], filters=[("a" > 1)]"#
+            ),
+            "{plan}"
+        );
+        let expected = ["+---+", "| b |", "+---+", "|   |", "| z |", "+---+"];
+        assert_batches_eq!(expected, &df.collect().await?);
+
+        let df = ctx.sql("SELECT count(*) FROM t").await?;
+        let expected = [
+            "+----------+",
+            "| COUNT(*) |",
+            "+----------+",
+            "| 3        |",
+            "+----------+",
+        ];
+        assert_batches_eq!(expected, &df.collect().await?);
+
+        // the columns are cast to the types of the table
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, false),
+            Field::new("b", DataType::LargeUtf8, true),
+        ]));
+        let table = FlightTable::new(endpoint, "remote", schema.clone());
+        let plan = table.scan(&ctx.state(), None, &[], Some(1)).await?;
+        let batches = collect(plan, ctx.task_ctx()).await?;
+        assert_eq!(batches[0].schema(), schema);
+        let expected = [
+            "+---+---+",
+            "| a | b |",
+            "+---+---+",
+            "| 1 | x |",
+            "+---+---+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+}
@@ -19,9 +19,16 @@
 
 use super::TableProvider;
 
-use datafusion_common::Result;
-use datafusion_expr::Expr;
+use arrow::array::{Array, ArrayRef, ListArray, StructArray};
+use arrow::buffer::OffsetBuffer;
+use arrow::compute::concat_batches;
+use arrow::datatypes::{DataType, Field, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{exec_err, Result, ScalarValue};
+use datafusion_expr::{ColumnarValue, Expr, ScalarUDFImpl, Signature, Volatility};
 
+use std::any::Any;
+use std::fmt::Debug;
 use std::sync::Arc;
 
 /// A trait for table function implementations
@@ -54,3 +61,128 @@ impl TableFunction {
         self.fun.call(args)
     }
 }
+
+/// A trait for table functions whose arguments may reference the columns of
+/// the preceding items of the `FROM` clause, as in
+/// `SELECT * FROM t CROSS JOIN LATERAL f(t.a)`
+///
+/// Unlike [`TableFunctionImpl`], whose arguments are constants known when
+/// planning, the function is called with the argument values of each input
+/// row, and returns the rows of the table for that input row. Each input row
+/// is joined with the rows returned for it, and input rows for which the
+/// function returns no rows are dropped.
+pub trait LateralTableFunctionImpl: Debug + Sync + Send {
+    /// Returns the schema of the tables returned for arguments of the types
+    /// `arg_types`
+    fn return_schema(&self, arg_types: &[DataType]) -> Result<SchemaRef>;
+
+    /// Returns the table for a single row of arguments
+    fn call_row(&self, args: &[ScalarValue]) -> Result<RecordBatch>;
+
+    /// Returns the tables for each of the `num_rows` rows of the arguments
+    /// `args`, in order
+    ///
+    /// The default implementation calls [`Self::call_row`] for every row.
+    /// Functions that can process a whole batch of arguments at once should
+    /// override it.
+    fn call_batch(&self, args: &[ArrayRef], num_rows: usize) -> Result<Vec<RecordBatch>> {
+        (0..num_rows)
+            .map(|row| {
+                let args = args
+                    .iter()
+                    .map(|arg| ScalarValue::try_from_array(arg, row))
+                    .collect::<Result<Vec<_>>>()?;
+                self.call_row(&args)
+            })
+            .collect()
+    }
+}
+
+/// A lateral table function, planned as a scalar function returning, for each
+/// input row, the rows of the table for that row as a list of structs
+///
+/// The SQL planner unnests the list and expands the structs into the columns
+/// of the table.
+#[derive(Debug)]
+pub struct LateralTableFunction {
+    /// Name of the table function
+    name: String,
+    /// Function implementation
+    fun: Arc<dyn LateralTableFunctionImpl>,
+    signature: Signature,
+}
+
+impl LateralTableFunction {
+    /// Create a new lateral table function
+    pub fn new(name: String, fun: Arc<dyn LateralTableFunctionImpl>) -> Self {
+        Self {
+            name,
+            fun,
+            signature: Signature::variadic_any(Volatility::Volatile),
+        }
+    }
+
+    /// Get the function implementation
+    pub fn fun(&self) -> &Arc<dyn LateralTableFunctionImpl> {
+        &self.fun
+    }
+}
+
+impl ScalarUDFImpl for LateralTableFunction {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        let schema = self.fun.return_schema(arg_types)?;
+        Ok(DataType::new_list(
+            DataType::Struct(schema.fields().clone()),
+            true,
+        ))
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        let is_scalar = args
+            .iter()
+            .all(|arg| matches!(arg, ColumnarValue::Scalar(_)));
+        let args = ColumnarValue::values_to_arrays(args)?;
+        let num_rows = args.first().map(|arg| arg.len()).unwrap_or(1);
+        let arg_types = args
+            .iter()
+            .map(|arg| arg.data_type().clone())
+            .collect::<Vec<_>>();
+        let schema = self.fun.return_schema(&arg_types)?;
+
+        let tables = self.fun.call_batch(&args, num_rows)?;
+        if tables.len() != num_rows {
+            return exec_err!(
+                "Lateral table function {} returned {} tables for {num_rows} rows",
+                self.name,
+                tables.len()
+            );
+        }
+        let offsets = OffsetBuffer::from_lengths(tables.iter().map(|t| t.num_rows()));
+        let values = concat_batches(&schema, &tables)?;
+        let values = StructArray::try_new(
+            schema.fields().clone(),
+            values.columns().to_vec(),
+            None,
+        )?;
+        let field = Arc::new(Field::new("item", values.data_type().clone(), true));
+        let list = ListArray::try_new(field, offsets, Arc::new(values), None)?;
+
+        Ok(if is_scalar {
+            ColumnarValue::Scalar(ScalarValue::List(Arc::new(list)))
+        } else {
+            ColumnarValue::Array(Arc::new(list))
+        })
+    }
+}
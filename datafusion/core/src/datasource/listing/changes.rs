@@ -0,0 +1,147 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`FileChanges`] of the files of a [`ListingTable`], and the
+//! [`FileChangeNotifier`]s keeping the tables up to date
+//!
+//! [`ListingTable`]: super::ListingTable
+
+use std::collections::BTreeMap;
+use std::fmt::Debug;
+
+use super::ListingTableUrl;
+use crate::error::Result;
+
+use object_store::{path::Path, ObjectMeta};
+
+/// The changes of the files of a [`ListingTable`] between two listings
+///
+/// [`ListingTable`]: super::ListingTable
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct FileChanges {
+    /// The files which were not listed before
+    pub added: Vec<ObjectMeta>,
+    /// The files whose size or last modification time changed, with their
+    /// new metadata
+    pub modified: Vec<ObjectMeta>,
+    /// The locations of the files which are not listed anymore
+    pub removed: Vec<Path>,
+}
+
+impl FileChanges {
+    /// Returns the changes from the files `old` to the files `new`, in the
+    /// order of their locations
+    pub fn diff(old: &[ObjectMeta], new: &[ObjectMeta]) -> Self {
+        let mut old = by_location(old);
+        let mut changes = Self::default();
+        for file in by_location(new).into_values() {
+            match old.remove(&file.location) {
+                None => changes.added.push(file.clone()),
+                Some(previous)
+                    if previous.size != file.size
+                        || previous.last_modified != file.last_modified =>
+                {
+                    changes.modified.push(file.clone())
+                }
+                Some(_) => {}
+            }
+        }
+        changes.removed = old.into_keys().cloned().collect();
+        changes
+    }
+
+    /// Returns true if no file changed
+    pub fn is_empty(&self) -> bool {
+        self.added.is_empty() && self.modified.is_empty() && self.removed.is_empty()
+    }
+
+    /// Returns the files `files` with these changes applied, in the order of
+    /// their locations. The added files which are already in `files` replace
+    /// them, and the removed files which are not are ignored, so that the
+    /// changes can be applied more than once.
+    pub fn apply(&self, files: &[ObjectMeta]) -> Vec<ObjectMeta> {
+        let mut files = by_location(files);
+        for file in self.added.iter().chain(&self.modified) {
+            files.insert(&file.location, file);
+        }
+        for location in &self.removed {
+            files.remove(location);
+        }
+        files.into_values().cloned().collect()
+    }
+}
+
+fn by_location(files: &[ObjectMeta]) -> BTreeMap<&Path, &ObjectMeta> {
+    files.iter().map(|file| (&file.location, file)).collect()
+}
+
+/// Notifies the [`ListingTable`]s of the changes of their files, e.g. from
+/// the event notifications of their object store, for the tables to keep
+/// their files up to date without listing them again at each scan.
+///
+/// A table with a notifier lists its files at its first scan, and then
+/// applies the changes returned by [`Self::take_changes`] before each scan.
+/// See [`ListingTable::with_change_notifier`].
+///
+/// [`ListingTable`]: super::ListingTable
+/// [`ListingTable::with_change_notifier`]: super::ListingTable::with_change_notifier
+pub trait FileChangeNotifier: Debug + Send + Sync {
+    /// Returns the changes of the files at `table_path` since the last call,
+    /// without waiting for new changes. The changes may include changes the
+    /// table already knows of, e.g. the changes since before its listing.
+    fn take_changes(&self, table_path: &ListingTableUrl) -> Result<FileChanges>;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::{TimeZone, Utc};
+
+    fn file(location: &str, size: usize) -> ObjectMeta {
+        ObjectMeta {
+            location: Path::from(location),
+            last_modified: Utc.timestamp_nanos(0),
+            size,
+            e_tag: None,
+            version: None,
+        }
+    }
+
+    #[test]
+    fn diff_and_apply() {
+        let old = [file("a", 1), file("b", 2), file("c", 3)];
+        let new = [file("d", 4), file("b", 2), file("a", 10)];
+        let changes = FileChanges::diff(&old, &new);
+        assert_eq!(
+            changes,
+            FileChanges {
+                added: vec![file("d", 4)],
+                modified: vec![file("a", 10)],
+                removed: vec![Path::from("c")],
+            }
+        );
+        assert!(!changes.is_empty());
+        assert!(FileChanges::diff(&new, &new).is_empty());
+
+        let expected = [file("a", 10), file("b", 2), file("d", 4)];
+        let applied = changes.apply(&old);
+        assert_eq!(applied, expected);
+        // the changes can be applied again
+        assert_eq!(changes.apply(&applied), expected);
+    }
+}
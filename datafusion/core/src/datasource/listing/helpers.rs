@@ -17,21 +17,25 @@
 
 //! Helper functions for the table implementation
 
+use std::collections::HashMap;
 use std::sync::Arc;
 
 use super::PartitionedFile;
 use crate::datasource::listing::ListingTableUrl;
+use crate::datasource::physical_plan::ListingPruning;
 use crate::execution::context::SessionState;
 use crate::{error::Result, scalar::ScalarValue};
 
 use arrow::{
-    array::{Array, ArrayRef, AsArray, StringBuilder},
+    array::{Array, ArrayRef, AsArray, BooleanArray, StringArray, StringBuilder},
     compute::{and, cast, prep_null_mask_filter},
     datatypes::{DataType, Field, Schema},
     record_batch::RecordBatch,
 };
 use arrow_schema::Fields;
 use datafusion_expr::execution_props::ExecutionProps;
+use datafusion_expr::simplify::SimplifyContext;
+use datafusion_optimizer::simplify_expressions::ExprSimplifier;
 use futures::stream::FuturesUnordered;
 use futures::{stream::BoxStream, StreamExt, TryStreamExt};
 use log::{debug, trace};
@@ -269,16 +273,39 @@ async fn prune_partitions(
 
     let batch = RecordBatch::try_new(schema.clone(), arrays)?;
 
+    let prepared = match filter_mask(filters, &df_schema, &batch) {
+        Some(mask) => mask,
+        None => return Ok(partitions),
+    };
+
+    // Sanity check
+    assert_eq!(prepared.len(), partitions.len());
+
+    let filtered = partitions
+        .into_iter()
+        .zip(prepared.values())
+        .filter_map(|(p, f)| f.then_some(p))
+        .collect();
+
+    Ok(filtered)
+}
+
+/// Evaluates the conjunction of `filters` on `batch`, ignoring the filters
+/// that fail to evaluate, and returns the mask of the rows passing them, or
+/// `None` if none of the filters could be evaluated. The rows for which the
+/// filters evaluate to null don't pass.
+fn filter_mask(
+    filters: &[Expr],
+    df_schema: &DFSchema,
+    batch: &RecordBatch,
+) -> Option<BooleanArray> {
     // TODO: Plumb this down
     let props = ExecutionProps::new();
 
     // Applies `filter` to `batch` returning `None` on error
     let do_filter = |filter| -> Option<ArrayRef> {
-        let expr = create_physical_expr(filter, &df_schema, &props).ok()?;
-        expr.evaluate(&batch)
-            .ok()?
-            .into_array(partitions.len())
-            .ok()
+        let expr = create_physical_expr(filter, df_schema, &props).ok()?;
+        expr.evaluate(batch).ok()?.into_array(batch.num_rows()).ok()
     };
 
     //.Compute the conjunction of the filters, ignoring errors
@@ -290,33 +317,21 @@ async fn prune_partitions(
             (r, None) => r,
         });
 
-    let mask = match mask {
-        Some(mask) => mask,
-        None => return Ok(partitions),
-    };
-
-    // Don't retain partitions that evaluated to null
-    let prepared = match mask.null_count() {
+    // Don't retain rows that evaluated to null
+    mask.map(|mask| match mask.null_count() {
         0 => mask,
         _ => prep_null_mask_filter(&mask),
-    };
-
-    // Sanity check
-    assert_eq!(prepared.len(), partitions.len());
-
-    let filtered = partitions
-        .into_iter()
-        .zip(prepared.values())
-        .filter_map(|(p, f)| f.then_some(p))
-        .collect();
-
-    Ok(filtered)
+    })
 }
 
 /// Discover the partitions on the given path and prune out files
 /// that belong to irrelevant partitions using `filters` expressions.
 /// `filters` might contain expressions that can be resolved only at the
 /// file level (e.g. Parquet row group pruning).
+///
+/// The partition columns with an expression in `partition_transforms` are
+/// derived from the paths of the files, see `derive_partition_values`, and
+/// the other ones are read from the folders.
 pub async fn pruned_partition_list<'a>(
     ctx: &'a SessionState,
     store: &'a dyn ObjectStore,
@@ -324,24 +339,120 @@ pub async fn pruned_partition_list<'a>(
     filters: &'a [Expr],
     file_extension: &'a str,
     partition_cols: &'a [(String, DataType)],
+    partition_transforms: &'a HashMap<String, Expr>,
 ) -> Result<BoxStream<'a, Result<PartitionedFile>>> {
+    let (files, _) = pruned_partition_list_with_counts(
+        ctx,
+        store,
+        table_path,
+        filters,
+        file_extension,
+        partition_cols,
+        partition_transforms,
+    )
+    .await?;
+    Ok(files)
+}
+
+/// Like [`pruned_partition_list`], also returning the numbers of partitions
+/// listed and kept. The files are not counted, as they are only listed when
+/// the returned stream is polled.
+pub async fn pruned_partition_list_with_counts<'a>(
+    ctx: &'a SessionState,
+    store: &'a dyn ObjectStore,
+    table_path: &'a ListingTableUrl,
+    filters: &'a [Expr],
+    file_extension: &'a str,
+    partition_cols: &'a [(String, DataType)],
+    partition_transforms: &'a HashMap<String, Expr>,
+) -> Result<(BoxStream<'a, Result<PartitionedFile>>, ListingPruning)> {
+    if partition_transforms.is_empty() {
+        return list_folder_partitions(
+            ctx,
+            store,
+            table_path,
+            filters,
+            file_extension,
+            partition_cols,
+        )
+        .await;
+    }
+
+    let folder_cols = partition_cols
+        .iter()
+        .filter(|(name, _)| !partition_transforms.contains_key(name))
+        .cloned()
+        .collect::<Vec<_>>();
+    let (files, pruning) = if folder_cols.is_empty() && table_path.is_collection() {
+        // the files are listed in the subdirectories too, whose names the
+        // partition columns are usually derived from, as in the partitions
+        let files = store
+            .list(Some(table_path.prefix()))
+            .try_filter(|o| {
+                let extension_match = o.location.as_ref().ends_with(file_extension);
+                let glob_match = table_path.contains(&o.location, false);
+                futures::future::ready(extension_match && glob_match)
+            })
+            .map_ok(PartitionedFile::from)
+            .try_collect()
+            .await?;
+        (files, ListingPruning::default())
+    } else {
+        let (files, pruning) = list_folder_partitions(
+            ctx,
+            store,
+            table_path,
+            filters,
+            file_extension,
+            &folder_cols,
+        )
+        .await?;
+        (files.try_collect::<Vec<_>>().await?, pruning)
+    };
+    let files = derive_partition_values(
+        table_path,
+        files,
+        filters,
+        partition_cols,
+        partition_transforms,
+    )?;
+    let files = futures::stream::iter(files.into_iter().map(Ok));
+    Ok((Box::pin(files), pruning))
+}
+
+/// Lists the files of the partitions on the given path which are not pruned
+/// by `filters`, with the values of the partition columns `partition_cols`
+/// read from their folders, and the numbers of partitions listed and kept
+async fn list_folder_partitions<'a>(
+    ctx: &'a SessionState,
+    store: &'a dyn ObjectStore,
+    table_path: &'a ListingTableUrl,
+    filters: &'a [Expr],
+    file_extension: &'a str,
+    partition_cols: &'a [(String, DataType)],
+) -> Result<(BoxStream<'a, Result<PartitionedFile>>, ListingPruning)> {
     // if no partition col => simply list all the files
     if partition_cols.is_empty() {
-        return Ok(Box::pin(
-            table_path
-                .list_all_files(ctx, store, file_extension)
-                .await?
-                .map_ok(|object_meta| object_meta.into()),
-        ));
+        let files = table_path
+            .list_all_files(ctx, store, file_extension)
+            .await?
+            .map_ok(|object_meta| object_meta.into());
+        return Ok((Box::pin(files), ListingPruning::default()));
     }
 
     let partitions = list_partitions(store, table_path, partition_cols.len()).await?;
     debug!("Listed {} partitions", partitions.len());
+    let partitions_considered = partitions.len();
 
     let pruned =
         prune_partitions(table_path, partitions, filters, partition_cols).await?;
 
     debug!("Pruning yielded {} partitions", pruned.len());
+    let pruning = ListingPruning {
+        partitions_considered,
+        partitions_kept: pruned.len(),
+        ..Default::default()
+    };
 
     let stream = futures::stream::iter(pruned)
         .map(move |partition: Partition| async move {
@@ -385,7 +496,207 @@ pub async fn pruned_partition_list<'a>(
         .buffer_unordered(CONCURRENCY_LIMIT)
         .try_flatten()
         .boxed();
-    Ok(stream)
+    Ok((stream, pruning))
+}
+
+/// Prunes the files `files` of the table at `table_path`, listed in a
+/// manifest rather than on the object store, like [`pruned_partition_list`]
+/// does for the files it lists. The files whose folders don't hold the
+/// values of all the partition columns read from the folders are ignored.
+pub fn prune_files(
+    table_path: &ListingTableUrl,
+    files: &[ObjectMeta],
+    filters: &[Expr],
+    file_extension: &str,
+    partition_cols: &[(String, DataType)],
+    partition_transforms: &HashMap<String, Expr>,
+) -> Result<Vec<PartitionedFile>> {
+    let folder_cols = partition_cols
+        .iter()
+        .filter(|(name, _)| !partition_transforms.contains_key(name))
+        .collect::<Vec<_>>();
+    let mut partitioned_files = vec![];
+    for object_meta in files {
+        if !object_meta.location.as_ref().ends_with(file_extension)
+            || !table_path.contains(&object_meta.location, false)
+        {
+            continue;
+        }
+        let cols = folder_cols.iter().map(|(name, _)| name.as_str());
+        let Some(parsed) =
+            parse_partitions_for_path(table_path, &object_meta.location, cols)
+        else {
+            continue;
+        };
+        if parsed.len() < folder_cols.len() {
+            continue;
+        }
+        let partition_values = parsed
+            .into_iter()
+            .zip(&folder_cols)
+            .map(|(parsed, (_, datatype))| {
+                ScalarValue::try_from_string(parsed.to_string(), datatype)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        partitioned_files.push(PartitionedFile {
+            object_meta: object_meta.clone(),
+            partition_values,
+            range: None,
+            extensions: None,
+        });
+    }
+    derive_partition_values(
+        table_path,
+        partitioned_files,
+        filters,
+        partition_cols,
+        partition_transforms,
+    )
+}
+
+/// Keeps the files `files` whose values of the partition columns
+/// `partition_cols` pass `filters`, for the files whose partition values are
+/// known rather than parsed from their paths, e.g. from the metadata of a
+/// table format. The filters which don't apply to the partition columns
+/// are ignored.
+pub fn prune_partitioned_files(
+    files: Vec<PartitionedFile>,
+    filters: &[Expr],
+    partition_cols: &[Field],
+) -> Result<Vec<PartitionedFile>> {
+    if files.is_empty() || filters.is_empty() || partition_cols.is_empty() {
+        return Ok(files);
+    }
+    if let Some(file) = files
+        .iter()
+        .find(|file| file.partition_values.len() != partition_cols.len())
+    {
+        return internal_err!(
+            "Expected {} partition values for the file {}, got {}",
+            partition_cols.len(),
+            file.path(),
+            file.partition_values.len()
+        );
+    }
+
+    let arrays = partition_cols
+        .iter()
+        .enumerate()
+        .map(|(index, field)| {
+            let array = ScalarValue::iter_to_array(
+                files
+                    .iter()
+                    .map(|file| file.partition_values[index].clone()),
+            )?;
+            Ok(cast(&array, field.data_type())?)
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let fields: Fields = partition_cols
+        .iter()
+        .map(|field| Field::new(field.name(), field.data_type().clone(), true))
+        .collect();
+    let df_schema = DFSchema::from_unqualifed_fields(fields.clone(), Default::default())?;
+    let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?;
+    let Some(mask) = filter_mask(filters, &df_schema, &batch) else {
+        return Ok(files);
+    };
+    Ok(files
+        .into_iter()
+        .zip(mask.values())
+        .filter_map(|(file, keep)| keep.then_some(file))
+        .collect())
+}
+
+/// Computes the values of the partition columns derived from the paths of
+/// `files` by the expressions `partition_transforms`, and keeps the files
+/// whose values of all the partition columns pass `filters`.
+///
+/// The expressions refer to the column `path`, the path of each file
+/// relative to `table_path`, and their values are cast to the types of the
+/// partition columns. The partition values of `files` are the values of the
+/// other partition columns, in their order.
+fn derive_partition_values(
+    table_path: &ListingTableUrl,
+    files: Vec<PartitionedFile>,
+    filters: &[Expr],
+    partition_cols: &[(String, DataType)],
+    partition_transforms: &HashMap<String, Expr>,
+) -> Result<Vec<PartitionedFile>> {
+    if files.is_empty() || partition_cols.is_empty() {
+        return Ok(files);
+    }
+
+    let paths: StringArray = files
+        .iter()
+        .map(|file| {
+            let parts = table_path.strip_prefix(&file.object_meta.location);
+            Some(
+                parts
+                    .map(|parts| parts.collect::<Vec<_>>().join("/"))
+                    .unwrap_or_default(),
+            )
+        })
+        .collect();
+    let path_schema =
+        Arc::new(Schema::new(vec![Field::new("path", DataType::Utf8, false)]));
+    let path_df_schema = Arc::new(DFSchema::from_unqualifed_fields(
+        path_schema.fields().clone(),
+        Default::default(),
+    )?);
+    let path_batch = RecordBatch::try_new(path_schema, vec![Arc::new(paths)])?;
+    let props = ExecutionProps::new();
+    let simplifier = ExprSimplifier::new(
+        SimplifyContext::new(&props).with_schema(path_df_schema.clone()),
+    );
+
+    let mut folder_col = 0;
+    let arrays = partition_cols
+        .iter()
+        .map(|(name, data_type)| {
+            let array = match partition_transforms.get(name) {
+                Some(transform) => simplifier
+                    .coerce(transform.clone(), path_df_schema.clone())
+                    .and_then(|expr| create_physical_expr(&expr, &path_df_schema, &props))
+                    .and_then(|expr| expr.evaluate(&path_batch))
+                    .and_then(|value| value.into_array(files.len()))
+                    .map_err(|e| {
+                        e.context(format!("Failed to derive the partition column {name}"))
+                    })?,
+                None => {
+                    let index = folder_col;
+                    folder_col += 1;
+                    ScalarValue::iter_to_array(
+                        files
+                            .iter()
+                            .map(|file| file.partition_values[index].clone()),
+                    )?
+                }
+            };
+            Ok(cast(&array, data_type)?)
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let fields: Fields = partition_cols
+        .iter()
+        .map(|(n, d)| Field::new(n, d.clone(), true))
+        .collect();
+    let df_schema = DFSchema::from_unqualifed_fields(fields.clone(), Default::default())?;
+    let batch = RecordBatch::try_new(Arc::new(Schema::new(fields)), arrays)?;
+    let mask = filter_mask(filters, &df_schema, &batch);
+
+    files
+        .into_iter()
+        .enumerate()
+        .filter(|(i, _)| mask.as_ref().map_or(true, |mask| mask.value(*i)))
+        .map(|(i, mut file)| {
+            file.partition_values = batch
+                .columns()
+                .iter()
+                .map(|array| ScalarValue::try_from_array(array, i))
+                .collect::<Result<_>>()?;
+            Ok(file)
+        })
+        .collect()
 }
 
 /// Extract the partition values for the given `file_path` (in the given `table_path`)
@@ -423,7 +734,8 @@ where
 mod tests {
     use std::ops::Not;
 
-    use crate::logical_expr::{case, col, lit};
+    use crate::functions::expr_fn::split_part;
+    use crate::logical_expr::{case, cast, col, lit};
     use crate::test::object_store::make_test_store_and_state;
 
     use super::*;
@@ -482,6 +794,7 @@ mod tests {
             &[filter],
             ".parquet",
             &[(String::from("mypartition"), DataType::Utf8)],
+            &HashMap::new(),
         )
         .await
         .expect("partition pruning failed")
@@ -506,6 +819,7 @@ mod tests {
             &[filter],
             ".parquet",
             &[(String::from("mypartition"), DataType::Utf8)],
+            &HashMap::new(),
         )
         .await
         .expect("partition pruning failed")
@@ -551,6 +865,7 @@ mod tests {
                 (String::from("part1"), DataType::Utf8),
                 (String::from("part2"), DataType::Utf8),
             ],
+            &HashMap::new(),
         )
         .await
         .expect("partition pruning failed")
@@ -579,6 +894,63 @@ mod tests {
         );
     }
 
+    #[tokio::test]
+    async fn test_pruned_partition_list_derived() {
+        let (store, state) = make_test_store_and_state(&[
+            ("tablepath/region=eu/2024-11-30/file.parquet", 100),
+            ("tablepath/region=eu/2024-12-01/file1.parquet", 100),
+            ("tablepath/region=eu/2024-12-01/file2.parquet", 100),
+            ("tablepath/region=us/2024-12-01/file.parquet", 100),
+            ("tablepath/region=eu/2024-12-02/file.parquet", 100),
+        ]);
+        let partition_cols = [
+            (String::from("region"), DataType::Utf8),
+            (String::from("day"), DataType::Date32),
+        ];
+        let partition_transforms = HashMap::from([(
+            String::from("day"),
+            split_part(col("path"), lit("/"), lit(2i64)),
+        )]);
+        let date = |s: &str| cast(lit(s), DataType::Date32);
+        let filter1 = Expr::eq(col("region"), lit("eu"));
+        let filter2 = col("day").between(date("2024-12-01"), date("2024-12-01"));
+        // filter3 cannot be resolved at partition pruning
+        let filter3 = Expr::eq(col("day"), col("other"));
+        let pruned = pruned_partition_list(
+            &state,
+            store.as_ref(),
+            &ListingTableUrl::parse("file:///tablepath/").unwrap(),
+            &[filter1, filter2, filter3],
+            ".parquet",
+            &partition_cols,
+            &partition_transforms,
+        )
+        .await
+        .expect("partition pruning failed")
+        .try_collect::<Vec<_>>()
+        .await
+        .unwrap();
+
+        let mut locations = pruned
+            .iter()
+            .map(|f| f.object_meta.location.as_ref())
+            .collect::<Vec<_>>();
+        locations.sort();
+        assert_eq!(
+            locations,
+            [
+                "tablepath/region=eu/2024-12-01/file1.parquet",
+                "tablepath/region=eu/2024-12-01/file2.parquet",
+            ]
+        );
+        for file in &pruned {
+            assert_eq!(
+                &file.partition_values,
+                &[ScalarValue::from("eu"), ScalarValue::Date32(Some(20058))]
+            );
+        }
+    }
+
     #[test]
     fn test_parse_partitions_for_path() {
         assert_eq!(
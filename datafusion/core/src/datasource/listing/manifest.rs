@@ -0,0 +1,292 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ListingTableManifest`], a file listing the files of a table, read
+//! in place of listing the table path on the object store
+
+use std::io::Cursor;
+use std::str::FromStr;
+use std::sync::Arc;
+
+use super::ListingTableUrl;
+use crate::error::{DataFusionError, Result};
+use crate::execution::context::SessionState;
+
+use arrow::array::{Array, AsArray};
+use arrow::compute::cast;
+use arrow::datatypes::{
+    DataType, Field, Schema, TimeUnit, TimestampMillisecondType, UInt64Type,
+};
+use arrow::json::ReaderBuilder;
+use arrow::record_batch::RecordBatch;
+use chrono::{TimeZone, Utc};
+use datafusion_common::plan_err;
+use object_store::{path::Path, ObjectMeta};
+
+/// The format of a [`ListingTableManifest`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ManifestFormat {
+    /// Newline-delimited JSON objects
+    Json,
+    /// A parquet file
+    Parquet,
+}
+
+impl ManifestFormat {
+    /// Returns the format of the manifest at `url`, from its extension.
+    /// Defaults to JSON.
+    pub fn from_url(url: &ListingTableUrl) -> Self {
+        if url.prefix().extension() == Some("parquet") {
+            Self::Parquet
+        } else {
+            Self::Json
+        }
+    }
+}
+
+impl FromStr for ManifestFormat {
+    type Err = DataFusionError;
+
+    fn from_str(s: &str) -> Result<Self> {
+        match s.to_ascii_lowercase().as_str() {
+            "json" => Ok(Self::Json),
+            "parquet" => Ok(Self::Parquet),
+            _ => plan_err!("Unknown manifest format {s}, expected json or parquet"),
+        }
+    }
+}
+
+/// A file of an object store listing the files of a [`ListingTable`], which
+/// the table reads instead of listing its path, which can take long for the
+/// tables with many files.
+///
+/// The manifest has a row per file, with the columns:
+/// - `path`: the path of the file relative to the path of the table
+/// - `size`: the size of the file in bytes
+/// - `last_modified` (optional): the time of the last modification of the
+///   file, in milliseconds since the epoch
+/// - `num_rows` (optional): the number of rows of the file, used as an
+///   estimate of its statistics instead of reading them from the file if
+///   `last_modified` is given too
+///
+/// The table reads the manifest at its first scan, and keeps its files
+/// until [`ListingTable::refresh_manifest`].
+///
+/// [`ListingTable`]: super::ListingTable
+/// [`ListingTable::refresh_manifest`]: super::ListingTable::refresh_manifest
+#[derive(Debug, Clone)]
+pub struct ListingTableManifest {
+    url: ListingTableUrl,
+    format: ManifestFormat,
+}
+
+/// A file listed in a [`ListingTableManifest`]
+#[derive(Debug, Clone)]
+pub struct ManifestEntry {
+    /// The location and the size of the file. Its time of last modification
+    /// is the epoch if the manifest does not give it
+    pub object_meta: ObjectMeta,
+    /// Whether the manifest gives the time of the last modification of the
+    /// file
+    pub has_last_modified: bool,
+    /// The number of rows of the file, if known
+    pub num_rows: Option<usize>,
+}
+
+impl ListingTableManifest {
+    /// Create the manifest at `url` in the format `format`
+    pub fn new(url: ListingTableUrl, format: ManifestFormat) -> Self {
+        Self { url, format }
+    }
+
+    /// The location of the manifest
+    pub fn url(&self) -> &ListingTableUrl {
+        &self.url
+    }
+
+    /// The format of the manifest
+    pub fn format(&self) -> ManifestFormat {
+        self.format
+    }
+
+    /// Reads the files listed in the manifest, located relatively to
+    /// `table_path`
+    pub async fn read(
+        &self,
+        state: &SessionState,
+        table_path: &ListingTableUrl,
+    ) -> Result<Vec<ManifestEntry>> {
+        let store = state.runtime_env().object_store(&self.url)?;
+        let bytes = store.get(self.url.prefix()).await?.bytes().await?;
+        let batches = match self.format {
+            ManifestFormat::Json => ReaderBuilder::new(Arc::new(manifest_schema()))
+                .build(Cursor::new(bytes))?
+                .collect::<Result<Vec<_>, _>>()?,
+            #[cfg(feature = "parquet")]
+            ManifestFormat::Parquet => {
+                parquet::arrow::arrow_reader::ParquetRecordBatchReaderBuilder::try_new(
+                    bytes,
+                )?
+                .build()?
+                .collect::<Result<Vec<_>, _>>()?
+            }
+            #[cfg(not(feature = "parquet"))]
+            ManifestFormat::Parquet => {
+                return datafusion_common::not_impl_err!(
+                    "Reading a parquet manifest requires the parquet feature"
+                )
+            }
+        };
+
+        let mut entries = vec![];
+        for batch in &batches {
+            read_entries(batch, table_path, &mut entries)
+                .map_err(|e| e.context(format!("Invalid manifest {}", self.url)))?;
+        }
+        Ok(entries)
+    }
+}
+
+/// The schema the JSON manifests are read with
+fn manifest_schema() -> Schema {
+    Schema::new(vec![
+        Field::new("path", DataType::Utf8, true),
+        Field::new("size", DataType::UInt64, true),
+        Field::new(
+            "last_modified",
+            DataType::Timestamp(TimeUnit::Millisecond, None),
+            true,
+        ),
+        Field::new("num_rows", DataType::UInt64, true),
+    ])
+}
+
+/// Appends the files listed in `batch` to `entries`
+fn read_entries(
+    batch: &RecordBatch,
+    table_path: &ListingTableUrl,
+    entries: &mut Vec<ManifestEntry>,
+) -> Result<()> {
+    let column = |name: &str, data_type: &DataType| {
+        batch
+            .column_by_name(name)
+            .map(|array| cast(array, data_type))
+            .transpose()
+    };
+    let (Some(paths), Some(sizes)) = (
+        column("path", &DataType::Utf8)?,
+        column("size", &DataType::UInt64)?,
+    ) else {
+        return plan_err!("Expected the columns path and size");
+    };
+    let last_modified = column(
+        "last_modified",
+        &DataType::Timestamp(TimeUnit::Millisecond, None),
+    )?;
+    let num_rows = column("num_rows", &DataType::UInt64)?;
+
+    let paths = paths.as_string::<i32>();
+    let sizes = sizes.as_primitive::<UInt64Type>();
+    let last_modified = last_modified
+        .as_ref()
+        .map(|array| array.as_primitive::<TimestampMillisecondType>());
+    let num_rows = num_rows
+        .as_ref()
+        .map(|array| array.as_primitive::<UInt64Type>());
+    for i in 0..batch.num_rows() {
+        if paths.is_null(i) || sizes.is_null(i) {
+            return plan_err!("Expected a path and a size for each file");
+        }
+        let location = Path::from_iter(
+            table_path
+                .prefix()
+                .parts()
+                .chain(Path::from(paths.value(i)).parts()),
+        );
+        let modified_ms = last_modified
+            .filter(|array| array.is_valid(i))
+            .map(|array| array.value(i));
+        let has_last_modified = modified_ms.is_some();
+        let modified_ms = modified_ms.unwrap_or_default();
+        let Some(modified) = Utc.timestamp_millis_opt(modified_ms).single() else {
+            return plan_err!("Invalid last modification time {modified_ms}");
+        };
+        entries.push(ManifestEntry {
+            object_meta: ObjectMeta {
+                location,
+                last_modified: modified,
+                size: sizes.value(i) as usize,
+                e_tag: None,
+                version: None,
+            },
+            has_last_modified,
+            num_rows: num_rows
+                .filter(|array| array.is_valid(i))
+                .map(|array| array.value(i) as usize),
+        });
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::prelude::SessionContext;
+
+    #[tokio::test]
+    async fn read_json_manifest() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let manifest_path = dir.path().join("manifest.json");
+        std::fs::write(
+            &manifest_path,
+            "{\"path\": \"a/1.parquet\", \"size\": 10, \"num_rows\": 3}\n\
+             {\"path\": \"2.parquet\", \"size\": 20, \"last_modified\": 1000}\n",
+        )?;
+        let manifest_url = ListingTableUrl::parse(manifest_path.to_str().unwrap())?;
+        assert_eq!(
+            ManifestFormat::from_url(&manifest_url),
+            ManifestFormat::Json
+        );
+        let manifest = ListingTableManifest::new(manifest_url, ManifestFormat::Json);
+
+        let table_path = ListingTableUrl::parse("file:///data/table/")?;
+        let state = SessionContext::new().state();
+        let entries = manifest.read(&state, &table_path).await?;
+        assert_eq!(entries.len(), 2);
+        assert_eq!(
+            entries[0].object_meta.location.as_ref(),
+            "data/table/a/1.parquet"
+        );
+        assert_eq!(entries[0].object_meta.size, 10);
+        assert_eq!(entries[0].num_rows, Some(3));
+        assert!(!entries[0].has_last_modified);
+        assert!(entries[1].has_last_modified);
+        assert_eq!(
+            entries[1].object_meta.last_modified.timestamp_millis(),
+            1000
+        );
+        assert_eq!(entries[1].num_rows, None);
+
+        std::fs::write(&manifest_path, "{\"path\": \"3.parquet\"}\n")?;
+        let err = manifest.read(&state, &table_path).await.unwrap_err();
+        assert!(
+            err.strip_backtrace().starts_with("Invalid manifest"),
+            "{err}"
+        );
+        Ok(())
+    }
+}
@@ -18,9 +18,13 @@
 //! A table that uses the `ObjectStore` listing capability
 //! to get the list of files to process.
 
+mod changes;
 mod helpers;
+mod manifest;
 mod table;
+mod text_index;
 mod url;
+mod vacuum;
 
 use crate::error::Result;
 use chrono::TimeZone;
@@ -31,7 +35,14 @@ use std::pin::Pin;
 use std::sync::Arc;
 
 pub use self::url::ListingTableUrl;
+pub use changes::{FileChangeNotifier, FileChanges};
+pub(crate) use helpers::{
+    expr_applicable_for_cols, prune_partitioned_files, split_files,
+};
+pub use manifest::{ListingTableManifest, ManifestEntry, ManifestFormat};
 pub use table::{ListingOptions, ListingTable, ListingTableConfig};
+pub use text_index::{SelectedRows, TextIndex, TextIndexMatches, MATCHES_FUNCTION};
+pub use vacuum::{VacuumOptions, VacuumReport};
 
 /// Stream of files get listed from object store
 pub type PartitionedFileStream =
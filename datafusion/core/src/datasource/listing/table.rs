@@ -17,12 +17,20 @@
 
 //! The table implementation.
 
-use std::collections::HashMap;
+use std::collections::{HashMap, HashSet};
+use std::hash::Hasher;
 use std::str::FromStr;
 use std::{any::Any, sync::Arc};
 
-use super::helpers::{expr_applicable_for_cols, pruned_partition_list, split_files};
-use super::PartitionedFile;
+use super::helpers::{
+    expr_applicable_for_cols, prune_files, pruned_partition_list,
+    pruned_partition_list_with_counts, split_files,
+};
+use super::text_index::text_search;
+use super::{
+    FileChangeNotifier, FileChanges, ListingTableManifest, PartitionedFile, TextIndex,
+    TextIndexMatches, VacuumOptions, VacuumReport,
+};
 
 #[cfg(feature = "parquet")]
 use crate::datasource::file_format::parquet::ParquetFormat;
@@ -39,19 +47,23 @@ use crate::datasource::{
         FileFormat,
     },
     listing::ListingTableUrl,
-    physical_plan::{FileScanConfig, FileSinkConfig},
+    physical_plan::{FileScanConfig, FileSinkConfig, ListingPruning},
 };
 use crate::{
     error::{DataFusionError, Result},
     execution::context::SessionState,
-    logical_expr::{utils::conjunction, Expr, TableProviderFilterPushDown},
+    logical_expr::{
+        utils::conjunction, Expr, TableProviderFilterPushDown, TableSample,
+        TableSampleMethod,
+    },
     physical_plan::{empty::EmptyExec, ExecutionPlan, Statistics},
 };
 
-use arrow::datatypes::{DataType, Field, SchemaBuilder, SchemaRef};
+use arrow::datatypes::{DataType, Field, FieldRef, SchemaBuilder, SchemaRef};
 use arrow_schema::Schema;
 use datafusion_common::{
-    internal_err, plan_err, project_schema, Constraints, FileType, SchemaExt, ToDFSchema,
+    internal_err, plan_err, project_schema, stats::Precision, Constraints, FileType,
+    SchemaExt, ToDFSchema,
 };
 use datafusion_execution::cache::cache_manager::FileStatisticsCache;
 use datafusion_execution::cache::cache_unit::DefaultFileStatisticsCache;
@@ -60,9 +72,13 @@ use datafusion_physical_expr::{
 };
 
 use async_trait::async_trait;
+use futures::stream::BoxStream;
 use futures::{future, stream, StreamExt, TryStreamExt};
 use itertools::Itertools;
-use object_store::ObjectStore;
+use object_store::{ObjectMeta, ObjectStore};
+use parking_lot::Mutex;
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
 
 /// Configuration for creating a [`ListingTable`]
 #[derive(Debug, Clone)]
@@ -171,9 +187,13 @@ impl ListingTableConfig {
                 Arc::new(CsvFormat::default().with_options(table_options.csv))
             }
             #[cfg(feature = "parquet")]
-            FileType::PARQUET => {
-                Arc::new(ParquetFormat::default().with_options(table_options.parquet))
-            }
+            FileType::PARQUET => Arc::new(
+                ParquetFormat::default()
+                    .with_options(table_options.parquet)
+                    .with_session_options(
+                        state.config_options().execution.parquet.clone(),
+                    ),
+            ),
             FileType::AVRO => Arc::new(AvroFormat),
             FileType::JSON => {
                 Arc::new(JsonFormat::default().with_options(table_options.json))
@@ -196,7 +216,13 @@ impl ListingTableConfig {
     pub async fn infer_schema(self, state: &SessionState) -> Result<Self> {
         match self.options {
             Some(options) => {
-                let schema = if let Some(url) = self.table_paths.first() {
+                let schema = if options.merge_schemas {
+                    let mut schemas = Vec::with_capacity(self.table_paths.len());
+                    for url in &self.table_paths {
+                        schemas.push(options.infer_schema(state, url, None).await?);
+                    }
+                    Arc::new(merge_schemas(&schemas)?)
+                } else if let Some(url) = self.table_paths.first() {
                     options.infer_schema(state, url, None).await?
                 } else {
                     Arc::new(Schema::empty())
@@ -252,7 +278,16 @@ pub struct ListingOptions {
     ///       single element.
     pub file_sort_order: Vec<Vec<Expr>>,
     /// used to pass column load hints to underlying implementations
-    pub column_hints: Option<Vec<String>>
+    pub column_hints: Option<Vec<String>>,
+    /// Set true to infer the schema as the union of the schemas of all the
+    /// files. See [Self::with_merge_schemas] for details
+    pub merge_schemas: bool,
+    /// The expressions of the partition columns derived from the paths of
+    /// the files. See [Self::with_partition_transforms] for details
+    pub partition_transforms: HashMap<String, Expr>,
+    /// The manifest listing the files of the table, read instead of listing
+    /// the table path. See [Self::with_manifest] for details
+    pub manifest: Option<ListingTableManifest>,
 }
 
 impl ListingOptions {
@@ -271,6 +306,9 @@ impl ListingOptions {
             target_partitions: 1,
             file_sort_order: vec![],
             column_hints: None,
+            merge_schemas: false,
+            partition_transforms: HashMap::new(),
+            manifest: None,
         }
     }
 
@@ -361,6 +399,52 @@ impl ListingOptions {
         self
     }
 
+    /// Set the expressions deriving some of the partition columns from the
+    /// paths of the files on [`ListingOptions`] and returns self.
+    ///
+    /// The expression of a partition column refers to the `Utf8` column
+    /// `path`, the path of each file relative to the table path, and its
+    /// value is cast to the type of the partition column. This supports the
+    /// layouts other than [Hive Partitioning], such as
+    /// `/mnt/data/2024-12-01/05/data.parquet`, and the partition values
+    /// that need parsing, such as dates, for the filters on the partition
+    /// columns to compare typed values. The files whose derived values don't
+    /// pass the filters are not read.
+    ///
+    /// The other partition columns are read from the folders `name=value`,
+    /// in their order, before the derived columns are computed.
+    ///
+    /// ```
+    /// # use std::collections::HashMap;
+    /// # use std::sync::Arc;
+    /// # use arrow::datatypes::DataType;
+    /// # use datafusion::prelude::{col, lit};
+    /// # use datafusion::functions::expr_fn::split_part;
+    /// # use datafusion::datasource::{listing::ListingOptions, file_format::parquet::ParquetFormat};
+    ///
+    /// // listing options for files with paths such as `/mnt/data/2024-12-01/05/data.parquet`
+    /// let listing_options = ListingOptions::new(Arc::new(
+    ///     ParquetFormat::default()
+    ///   ))
+    ///   .with_table_partition_cols(vec![("day".to_string(), DataType::Date32),
+    ///       ("hour".to_string(), DataType::Int32)])
+    ///   .with_partition_transforms(HashMap::from([
+    ///       ("day".to_string(), split_part(col("path"), lit("/"), lit(1))),
+    ///       ("hour".to_string(), split_part(col("path"), lit("/"), lit(2))),
+    ///   ]));
+    ///
+    /// assert_eq!(listing_options.partition_transforms.len(), 2);
+    /// ```
+    ///
+    /// [Hive Partitioning]: https://docs.cloudera.com/HDPDocuments/HDP2/HDP-2.1.3/bk_system-admin-guide/content/hive_partitioned_tables.html
+    pub fn with_partition_transforms(
+        mut self,
+        partition_transforms: HashMap<String, Expr>,
+    ) -> Self {
+        self.partition_transforms = partition_transforms;
+        self
+    }
+
     /// Set stat collection on [`ListingOptions`] and returns self.
     ///
     /// ```
@@ -428,6 +512,86 @@ impl ListingOptions {
         self
     }
 
+    /// Set schema merging on [`ListingOptions`] and returns self.
+    ///
+    /// When set, [`Self::infer_schema`] infers the schema of each file on
+    /// its own, and returns the union of their fields, matched by name and
+    /// merged recursively into the structs and the lists of structs. A
+    /// field missing from some files is nullable in the union, and the
+    /// files without it are read with nulls in its place. Otherwise, the
+    /// schema is inferred by the format from the first files, and a file
+    /// with more nested fields fails to read.
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use datafusion::datasource::{listing::ListingOptions, file_format::parquet::ParquetFormat};
+    ///
+    /// let listing_options = ListingOptions::new(Arc::new(
+    ///     ParquetFormat::default()
+    ///   ))
+    ///   .with_merge_schemas(true);
+    ///
+    /// assert_eq!(listing_options.merge_schemas, true);
+    /// ```
+    pub fn with_merge_schemas(mut self, merge_schemas: bool) -> Self {
+        self.merge_schemas = merge_schemas;
+        self
+    }
+
+    /// Set the manifest listing the files of the table on [`ListingOptions`]
+    /// and returns self.
+    ///
+    /// When set, the files of the table are the files listed in the
+    /// manifest, with the file extension and under the table path, instead
+    /// of the files listed on the object store, which saves listing the
+    /// table path at each scan. See [`ListingTableManifest`] for its format.
+    ///
+    /// ```
+    /// # use std::sync::Arc;
+    /// # use datafusion::datasource::{
+    /// #     file_format::parquet::ParquetFormat,
+    /// #     listing::{ListingOptions, ListingTableManifest, ListingTableUrl, ManifestFormat},
+    /// # };
+    /// # use datafusion_common::Result;
+    /// # fn main() -> Result<()> {
+    /// let manifest_url = ListingTableUrl::parse("s3://bucket/table/_manifest.json")?;
+    /// let listing_options = ListingOptions::new(Arc::new(
+    ///     ParquetFormat::default()
+    ///   ))
+    ///   .with_manifest(Some(ListingTableManifest::new(manifest_url, ManifestFormat::Json)));
+    ///
+    /// assert!(listing_options.manifest.is_some());
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub fn with_manifest(mut self, manifest: Option<ListingTableManifest>) -> Self {
+        self.manifest = manifest;
+        self
+    }
+
+    /// Lists the files with the file extension at `table_path`, or in the
+    /// manifest if any
+    async fn list_all_files<'a>(
+        &'a self,
+        state: &'a SessionState,
+        store: &'a dyn ObjectStore,
+        table_path: &'a ListingTableUrl,
+    ) -> Result<BoxStream<'a, Result<ObjectMeta>>> {
+        let Some(manifest) = &self.manifest else {
+            return table_path
+                .list_all_files(state, store, &self.file_extension)
+                .await;
+        };
+        let files = manifest
+            .read(state, table_path)
+            .await?
+            .into_iter()
+            .map(|entry| entry.object_meta)
+            .filter(|file| file.location.as_ref().ends_with(&self.file_extension))
+            .collect::<Vec<_>>();
+        Ok(stream::iter(files.into_iter().map(Ok)).boxed())
+    }
+
     /// Infer the schema of the files at the given path on the provided object store.
     /// The inferred schema does not include the partitioning columns.
     ///
@@ -442,13 +606,40 @@ impl ListingOptions {
     ) -> Result<SchemaRef> {
         let store = state.runtime_env().object_store(table_path)?;
 
-        let files: Vec<_> = table_path
-            .list_all_files(state, store.as_ref(), &self.file_extension)
+        let files: Vec<_> = self
+            .list_all_files(state, store.as_ref(), table_path)
             .await?
             .try_collect()
             .await?;
 
-        self.format.infer_schema(state, &store, &files, column_hints).await
+        if !self.merge_schemas {
+            return self
+                .format
+                .infer_schema(state, &store, &files, column_hints)
+                .await;
+        }
+
+        // the schema of each file is inferred on its own, for the format not
+        // to stop at the first files
+        let concurrency = state.config_options().execution.meta_fetch_concurrency;
+        // the futures are created before streaming them, for the stream not
+        // to hold a closure over the borrowed files and to remain `Send`
+        let inferences = files
+            .iter()
+            .map(|file| {
+                self.format.infer_schema(
+                    state,
+                    &store,
+                    std::slice::from_ref(file),
+                    column_hints.clone(),
+                )
+            })
+            .collect::<Vec<_>>();
+        let schemas: Vec<_> = stream::iter(inferences)
+            .buffered(concurrency)
+            .try_collect()
+            .await?;
+        Ok(Arc::new(merge_schemas(&schemas)?))
     }
 
     /// Infers the partition columns stored in `LOCATION` and compares
@@ -472,18 +663,27 @@ impl ListingOptions {
             );
         }
 
-        let inferred = self.infer_partitions(state, table_path).await?;
-
-        // no partitioned files found on disk
-        if inferred.is_empty() {
-            return Ok(());
-        }
-
+        // the derived partition columns are not in the folders
         let table_partition_names = self
             .table_partition_cols
             .iter()
             .map(|(col_name, _)| col_name.clone())
+            .filter(|col_name| !self.partition_transforms.contains_key(col_name))
             .collect_vec();
+        if table_partition_names.is_empty() {
+            return Ok(());
+        }
+
+        // the folders below the partition columns read from the folders may
+        // be named after the values of the derived ones
+        let max_depth = (!self.partition_transforms.is_empty())
+            .then_some(table_partition_names.len());
+        let inferred = self.infer_partitions(state, table_path, max_depth).await?;
+
+        // no partitioned files found on disk
+        if inferred.is_empty() {
+            return Ok(());
+        }
 
         if inferred.len() < table_partition_names.len() {
             return plan_err!(
@@ -507,21 +707,23 @@ impl ListingOptions {
         Ok(())
     }
 
-    /// Infer the partitioning at the given path on the provided object store.
+    /// Infer the partitioning at the given path on the provided object store,
+    /// up to `max_depth` folders if any.
     /// For performance reasons, it doesn't read all the files on disk
     /// and therefore may fail to detect invalid partitioning.
     async fn infer_partitions(
         &self,
         state: &SessionState,
         table_path: &ListingTableUrl,
+        max_depth: Option<usize>,
     ) -> Result<Vec<String>> {
         let store = state.runtime_env().object_store(table_path)?;
 
         // only use 10 files for inference
         // This can fail to detect inconsistent partition keys
         // A DFS traversal approach of the store can help here
-        let files: Vec<_> = table_path
-            .list_all_files(state, store.as_ref(), &self.file_extension)
+        let files: Vec<_> = self
+            .list_all_files(state, store.as_ref(), table_path)
             .await?
             .take(10)
             .try_collect()
@@ -541,6 +743,7 @@ impl ListingOptions {
                     .rev()
                     .skip(1) // get parents only; skip the file itself
                     .rev()
+                    .take(max_depth.unwrap_or(usize::MAX))
                     .map(|s| s.split('=').take(1).collect())
                     .collect_vec()
             })
@@ -634,6 +837,14 @@ pub struct ListingTable {
     collected_statistics: FileStatisticsCache,
     constraints: Constraints,
     column_defaults: HashMap<String, Expr>,
+    /// Table statistics set by [`TableProvider::set_statistics`], used in
+    /// place of the statistics of the scanned files
+    statistics: Mutex<Option<Statistics>>,
+    /// The files of the table kept for the scans, once read from its
+    /// manifest or listed by [`Self::refresh_files`]
+    files: Mutex<Option<Arc<Vec<ObjectMeta>>>>,
+    change_notifier: Option<Arc<dyn FileChangeNotifier>>,
+    text_index: Option<Arc<dyn TextIndex>>,
 }
 
 impl ListingTable {
@@ -655,6 +866,9 @@ impl ListingTable {
         let options = config.options.ok_or_else(|| {
             DataFusionError::Internal("No ListingOptions provided".into())
         })?;
+        if options.manifest.is_some() && config.table_paths.len() != 1 {
+            return plan_err!("A ListingTable with a manifest must have a single path");
+        }
 
         // Add the partition columns to the file schema
         let mut builder = SchemaBuilder::from(file_schema.as_ref().to_owned());
@@ -671,6 +885,10 @@ impl ListingTable {
             collected_statistics: Arc::new(DefaultFileStatisticsCache::default()),
             constraints: Constraints::empty(),
             column_defaults: HashMap::new(),
+            statistics: Mutex::new(None),
+            files: Mutex::new(None),
+            change_notifier: None,
+            text_index: None,
         };
 
         Ok(table)
@@ -719,6 +937,257 @@ impl ListingTable {
         &self.options
     }
 
+    /// Set the [`FileChangeNotifier`] keeping the files of the table up to
+    /// date: the table lists its files at its first scan, and then applies
+    /// the changes of the notifier before each scan instead of listing them
+    /// again.
+    pub fn with_change_notifier(
+        mut self,
+        change_notifier: Option<Arc<dyn FileChangeNotifier>>,
+    ) -> Self {
+        self.change_notifier = change_notifier;
+        self
+    }
+
+    /// Set the [`TextIndex`] finding the rows matching the full-text filters
+    /// `matches(column, '<query>')` of the scans on the columns it covers,
+    /// in place of evaluating them on every row of the table
+    pub fn with_text_index(mut self, text_index: Option<Arc<dyn TextIndex>>) -> Self {
+        self.text_index = text_index;
+        self
+    }
+
+    /// Reads the manifest of the table again, for the next scans to read the
+    /// files it lists now. Does nothing if the table has no manifest.
+    ///
+    /// The number of rows of the files given by the manifest are added to
+    /// the statistics cache of the table as estimates, for them not to be
+    /// read from the files, unless the manifest does not give the time of
+    /// their last modification, which the cached statistics are checked
+    /// against.
+    pub async fn refresh_manifest(&self, state: &SessionState) -> Result<()> {
+        if self.options.manifest.is_none() {
+            return Ok(());
+        }
+        self.refresh_files(state).await.map(|_| ())
+    }
+
+    /// Lists the files of the table again, or reads its manifest again, and
+    /// returns their changes since the previous listing. All the files are
+    /// added at the first listing.
+    ///
+    /// The listed files are kept for the next scans, which don't list them
+    /// anymore, until the next refresh. They can be kept up to date in the
+    /// meantime with [`Self::apply_changes`] or a [`FileChangeNotifier`].
+    /// The files written by [`TableProvider::insert_into`] are only scanned
+    /// once they are listed again or applied.
+    pub async fn refresh_files(&self, state: &SessionState) -> Result<FileChanges> {
+        let files = match &self.options.manifest {
+            Some(manifest) => self.read_manifest(state, manifest).await?,
+            None => self.list_table_files(state).await?,
+        };
+        let files = Arc::new(files);
+        let previous = self.files.lock().replace(Arc::clone(&files));
+        let previous = previous.as_deref().map_or(&[][..], Vec::as_slice);
+        Ok(FileChanges::diff(previous, &files))
+    }
+
+    /// Applies the changes `changes` to the files of the table kept for the
+    /// scans, without listing them. The files must have been listed by
+    /// [`Self::refresh_files`] first.
+    pub fn apply_changes(&self, changes: &FileChanges) -> Result<()> {
+        let mut files = self.files.lock();
+        let Some(current) = files.as_ref() else {
+            return plan_err!(
+                "The files of the table must be listed before applying their changes"
+            );
+        };
+        let applied = changes.apply(current);
+        *files = Some(Arc::new(applied));
+        Ok(())
+    }
+
+    /// Finds the orphaned files of the table, the files with the extension of
+    /// the table under its path which are not listed in the manifest of the
+    /// live files `live_files` and were modified before the retention period
+    /// of `options`, and deletes them unless it is a dry run.
+    ///
+    /// As a safeguard against deleting live data, the vacuum is aborted if
+    /// the table has several paths, if a live file is not found under the
+    /// path of the table, or if the manifest lists no live file unless
+    /// [`VacuumOptions::force`] is set.
+    ///
+    /// The deleted files are removed from the files kept for the scans, but
+    /// not from the list files cache of the session.
+    pub async fn vacuum(
+        &self,
+        state: &SessionState,
+        live_files: &ListingTableManifest,
+        options: &VacuumOptions,
+    ) -> Result<VacuumReport> {
+        let Ok(retention) = chrono::Duration::from_std(options.retention) else {
+            return plan_err!("Invalid retention {:?}", options.retention);
+        };
+        let [table_path] = self.table_paths.as_slice() else {
+            return plan_err!("Cannot vacuum a table with several paths");
+        };
+        let cutoff = chrono::Utc::now() - retention;
+        let live = live_files
+            .read(state, table_path)
+            .await?
+            .into_iter()
+            .map(|entry| entry.object_meta.location)
+            .collect::<HashSet<_>>();
+        if live.is_empty() && !options.force {
+            return plan_err!(
+                "The manifest {} lists no live file, set VacuumOptions::force to delete all the files of the table",
+                live_files.url()
+            );
+        }
+
+        let files = self.list_table_files(state).await?;
+        // a live file missing from the listing means that the paths of the
+        // manifest do not match those of the table, or that the manifest is
+        // stale: its other entries cannot be trusted either
+        let listed = files
+            .iter()
+            .map(|file| &file.location)
+            .collect::<HashSet<_>>();
+        let mut missing = live
+            .iter()
+            .filter(|location| !listed.contains(location))
+            .collect::<Vec<_>>();
+        if !missing.is_empty() {
+            missing.sort();
+            return plan_err!(
+                "{} live files of the manifest {} are not files of the table, such as {}",
+                missing.len(),
+                live_files.url(),
+                missing[0]
+            );
+        }
+
+        let mut orphans = files
+            .into_iter()
+            .filter(|file| {
+                !live.contains(&file.location)
+                    && file.location != *live_files.url().prefix()
+                    && file.last_modified <= cutoff
+            })
+            .collect::<Vec<_>>();
+        orphans.sort_by(|a, b| a.location.cmp(&b.location));
+        if options.dry_run || orphans.is_empty() {
+            return Ok(VacuumReport {
+                orphans,
+                deleted: false,
+            });
+        }
+
+        let store = state.runtime_env().object_store(table_path)?;
+        let locations = orphans
+            .iter()
+            .map(|file| Ok(file.location.clone()))
+            .collect::<Vec<_>>();
+        store
+            .delete_stream(stream::iter(locations).boxed())
+            .try_collect::<Vec<_>>()
+            .await?;
+        if self.files.lock().is_some() {
+            self.apply_changes(&FileChanges {
+                removed: orphans.iter().map(|file| file.location.clone()).collect(),
+                ..Default::default()
+            })?;
+        }
+        Ok(VacuumReport {
+            orphans,
+            deleted: true,
+        })
+    }
+
+    /// Returns the files of the table kept for the scans, if any. They are
+    /// listed first for the tables with a manifest or a change notifier, and
+    /// the pending changes of the notifier are applied to them.
+    async fn kept_files(
+        &self,
+        state: &SessionState,
+    ) -> Result<Option<Arc<Vec<ObjectMeta>>>> {
+        let listed = self.files.lock().is_some();
+        if !listed {
+            if self.options.manifest.is_none() && self.change_notifier.is_none() {
+                return Ok(None);
+            }
+            self.refresh_files(state).await?;
+        }
+        if let Some(notifier) = &self.change_notifier {
+            for table_path in &self.table_paths {
+                let changes = notifier.take_changes(table_path)?;
+                if !changes.is_empty() {
+                    self.apply_changes(&changes)?;
+                }
+            }
+        }
+        Ok(self.files.lock().clone())
+    }
+
+    /// Lists all the files of the table on the object store, without the
+    /// list files cache of the session
+    async fn list_table_files(&self, state: &SessionState) -> Result<Vec<ObjectMeta>> {
+        // the partitions are in subdirectories
+        let ignore_subdirectory = self.options.table_partition_cols.is_empty()
+            && state
+                .config_options()
+                .execution
+                .listing_table_ignore_subdirectory;
+        let mut files = vec![];
+        for table_path in &self.table_paths {
+            let store = state.runtime_env().object_store(table_path)?;
+            let listed: Vec<ObjectMeta> = if table_path.is_collection() {
+                store.list(Some(table_path.prefix())).try_collect().await?
+            } else {
+                vec![store.head(table_path.prefix()).await?]
+            };
+            files.extend(listed.into_iter().filter(|file| {
+                file.location
+                    .as_ref()
+                    .ends_with(&self.options.file_extension)
+                    && table_path.contains(&file.location, ignore_subdirectory)
+            }));
+        }
+        Ok(files)
+    }
+
+    /// Reads the files listed in `manifest`, and caches the number of rows it
+    /// gives for them as inexact statistics, which are not validated against
+    /// the files. The files without a time of last modification are not
+    /// cached, as their cached statistics could not be invalidated when they
+    /// change.
+    async fn read_manifest(
+        &self,
+        state: &SessionState,
+        manifest: &ListingTableManifest,
+    ) -> Result<Vec<ObjectMeta>> {
+        let entries = manifest.read(state, &self.table_paths[0]).await?;
+        let mut files = Vec::with_capacity(entries.len());
+        for entry in entries {
+            let cached = self
+                .collected_statistics
+                .get_with_extra(&entry.object_meta.location, &entry.object_meta);
+            if let (Some(num_rows), None, true) =
+                (entry.num_rows, cached, entry.has_last_modified)
+            {
+                let mut statistics = Statistics::new_unknown(&self.file_schema);
+                statistics.num_rows = Precision::Inexact(num_rows);
+                self.collected_statistics.put_with_extra(
+                    &entry.object_meta.location,
+                    Arc::new(statistics),
+                    &entry.object_meta,
+                );
+            }
+            files.push(entry.object_meta);
+        }
+        Ok(files)
+    }
+
     /// If file_sort_order is specified, creates the appropriate physical expressions
     fn try_create_output_ordering(&self) -> Result<Vec<LexOrdering>> {
         create_ordering(&self.table_schema, &self.options.file_sort_order)
@@ -750,58 +1219,25 @@ impl TableProvider for ListingTable {
         filters: &[Expr],
         limit: Option<usize>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        let (partitioned_file_lists, statistics) =
-            self.list_files_for_scan(state, filters, limit).await?;
+        self.scan_files(state, projection, filters, limit, None)
+            .await
+    }
 
-        // if no files need to be read, return an `EmptyExec`
-        if partitioned_file_lists.is_empty() {
-            let schema = self.schema();
-            let projected_schema = project_schema(&schema, projection)?;
-            return Ok(Arc::new(EmptyExec::new(projected_schema)));
+    async fn scan_sample(
+        &self,
+        state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        sample: &TableSample,
+    ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+        // Whole files are sampled, which is only allowed for SYSTEM sampling
+        match sample.method {
+            TableSampleMethod::System => self
+                .scan_files(state, projection, filters, None, Some(sample))
+                .await
+                .map(Some),
+            TableSampleMethod::Bernoulli => Ok(None),
         }
-
-        // extract types of partition columns
-        let table_partition_cols = self
-            .options
-            .table_partition_cols
-            .iter()
-            .map(|col| Ok(self.table_schema.field_with_name(&col.0)?.clone()))
-            .collect::<Result<Vec<_>>>()?;
-
-        let filters = if let Some(expr) = conjunction(filters.to_vec()) {
-            // NOTE: Use the table schema (NOT file schema) here because `expr` may contain references to partition columns.
-            let table_df_schema = self.table_schema.as_ref().clone().to_dfschema()?;
-            let filters =
-                create_physical_expr(&expr, &table_df_schema, state.execution_props())?;
-            Some(filters)
-        } else {
-            None
-        };
-
-        let object_store_url = if let Some(url) = self.table_paths.first() {
-            url.object_store()
-        } else {
-            return Ok(Arc::new(EmptyExec::new(Arc::new(Schema::empty()))));
-        };
-        // create the execution plan
-        self.options
-            .format
-            .create_physical_plan(
-                state,
-                FileScanConfig {
-                    object_store_url,
-                    file_schema: Arc::clone(&self.file_schema),
-                    file_groups: partitioned_file_lists,
-                    statistics,
-                    projection: projection.cloned(),
-                    column_hints: self.options.column_hints.clone(),
-                    limit,
-                    output_ordering: self.try_create_output_ordering()?,
-                    table_partition_cols,
-                },
-                filters.as_ref(),
-            )
-            .await
     }
 
     fn supports_filters_pushdown(
@@ -824,7 +1260,10 @@ impl TableProvider for ListingTable {
                     TableProviderFilterPushDown::Exact
                 } else {
                     // otherwise, we still might be able to handle the filter with file
-                    // level mechanisms such as Parquet row group pruning.
+                    // level mechanisms such as Parquet row group pruning, or with the
+                    // text index. The rows found by the text index are filtered again,
+                    // as the selected rows do not survive the serialization of a
+                    // plan by all the codecs and the index may not know all the files
                     TableProviderFilterPushDown::Inexact
                 }
             })
@@ -836,12 +1275,31 @@ impl TableProvider for ListingTable {
         self.definition.as_deref()
     }
 
+    fn statistics(&self) -> Option<Statistics> {
+        self.statistics.lock().clone()
+    }
+
+    fn set_statistics(&self, statistics: Statistics) -> Result<()> {
+        if statistics.column_statistics.len() != self.table_schema.fields().len() {
+            return plan_err!(
+                "Expected statistics for {} columns, got {}",
+                self.table_schema.fields().len(),
+                statistics.column_statistics.len()
+            );
+        }
+        *self.statistics.lock() = Some(statistics);
+        Ok(())
+    }
+
     async fn insert_into(
         &self,
         state: &SessionState,
         input: Arc<dyn ExecutionPlan>,
         overwrite: bool,
     ) -> Result<Arc<dyn ExecutionPlan>> {
+        // The written files invalidate the statistics of the table
+        *self.statistics.lock() = None;
+
         // Check that the schema of the plan matches the schema of this table.
         if !self
             .schema()
@@ -861,6 +1319,18 @@ impl TableProvider for ListingTable {
             );
         }
 
+        if !self.options.partition_transforms.is_empty() {
+            return plan_err!(
+                "Inserting into a ListingTable with partition columns derived from the paths of the files is not supported"
+            );
+        }
+
+        if self.options.manifest.is_some() {
+            return plan_err!(
+                "Inserting into a ListingTable with a manifest is not supported"
+            );
+        }
+
         // Get the object store for the table path.
         let store = state.runtime_env().object_store(table_path)?;
 
@@ -871,6 +1341,7 @@ impl TableProvider for ListingTable {
             &[],
             &self.options.file_extension,
             &self.options.table_partition_cols,
+            &self.options.partition_transforms,
         )
         .await?;
 
@@ -919,33 +1390,176 @@ impl TableProvider for ListingTable {
 }
 
 impl ListingTable {
+    /// Create an [`ExecutionPlan`] scanning the files of the table, or a
+    /// random sample of them
+    async fn scan_files(
+        &self,
+        state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+        sample: Option<&TableSample>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let (partitioned_file_lists, statistics, listing_pruning) = self
+            .list_files_for_scan(state, filters, limit, sample)
+            .await?;
+        // The table may have changed since its statistics were set, so they
+        // are only estimates. They describe the file columns followed by the
+        // partition columns, while the scan expects the file columns only.
+        let statistics = match self.statistics.lock().clone() {
+            Some(table_statistics) => {
+                let mut table_statistics = table_statistics.into_inexact();
+                table_statistics
+                    .column_statistics
+                    .truncate(self.file_schema.fields().len());
+                if let Some(sample) = sample {
+                    // the sampled files hold a fraction of the rows of the table
+                    table_statistics.num_rows = table_statistics
+                        .num_rows
+                        .with_estimated_selectivity(sample.fraction());
+                    table_statistics.total_byte_size = table_statistics
+                        .total_byte_size
+                        .with_estimated_selectivity(sample.fraction());
+                }
+                table_statistics
+            }
+            None => statistics,
+        };
+
+        // if no files need to be read, return an `EmptyExec`
+        if partitioned_file_lists.is_empty() {
+            let schema = self.schema();
+            let projected_schema = project_schema(&schema, projection)?;
+            return Ok(Arc::new(EmptyExec::new(projected_schema)));
+        }
+
+        // extract types of partition columns
+        let table_partition_cols = self
+            .options
+            .table_partition_cols
+            .iter()
+            .map(|col| Ok(self.table_schema.field_with_name(&col.0)?.clone()))
+            .collect::<Result<Vec<_>>>()?;
+
+        let filters = if let Some(expr) = conjunction(filters.to_vec()) {
+            // NOTE: Use the table schema (NOT file schema) here because `expr` may contain references to partition columns.
+            let table_df_schema = self.table_schema.as_ref().clone().to_dfschema()?;
+            let filters =
+                create_physical_expr(&expr, &table_df_schema, state.execution_props())?;
+            Some(filters)
+        } else {
+            None
+        };
+
+        let object_store_url = if let Some(url) = self.table_paths.first() {
+            url.object_store()
+        } else {
+            return Ok(Arc::new(EmptyExec::new(Arc::new(Schema::empty()))));
+        };
+        // create the execution plan
+        self.options
+            .format
+            .create_physical_plan(
+                state,
+                FileScanConfig {
+                    object_store_url,
+                    file_schema: Arc::clone(&self.file_schema),
+                    file_groups: partitioned_file_lists,
+                    statistics,
+                    projection: projection.cloned(),
+                    column_hints: self.options.column_hints.clone(),
+                    listing_pruning: Some(listing_pruning),
+                    limit,
+                    output_ordering: self.try_create_output_ordering()?,
+                    table_partition_cols,
+                },
+                filters.as_ref(),
+            )
+            .await
+    }
+
     /// Get the list of files for a scan as well as the file level statistics.
     /// The list is grouped to let the execution plan know how the files should
-    /// be distributed to different threads / executors.
+    /// be distributed to different threads / executors. The numbers of
+    /// partitions and files listed are returned for the metrics of the scan.
     async fn list_files_for_scan<'a>(
         &'a self,
         ctx: &'a SessionState,
         filters: &'a [Expr],
         limit: Option<usize>,
-    ) -> Result<(Vec<Vec<PartitionedFile>>, Statistics)> {
+        sample: Option<&'a TableSample>,
+    ) -> Result<(Vec<Vec<PartitionedFile>>, Statistics, ListingPruning)> {
         let store = if let Some(url) = self.table_paths.first() {
             ctx.runtime_env().object_store(url)?
         } else {
-            return Ok((vec![], Statistics::new_unknown(&self.file_schema)));
+            return Ok((
+                vec![],
+                Statistics::new_unknown(&self.file_schema),
+                ListingPruning::default(),
+            ));
         };
-        // list files (with partitions)
-        let file_list = future::try_join_all(self.table_paths.iter().map(|table_path| {
-            pruned_partition_list(
-                ctx,
-                store.as_ref(),
-                table_path,
-                filters,
-                &self.options.file_extension,
-                &self.options.table_partition_cols,
-            )
-        }))
-        .await?;
-        let file_list = stream::iter(file_list).flatten();
+        let mut pruning = ListingPruning::default();
+        let text_matches = self.search_text_index(filters).await?;
+        // list files (with partitions), unless they are kept
+        let file_list: BoxStream<'_, Result<PartitionedFile>> =
+            if let Some(kept_files) = self.kept_files(ctx).await? {
+                let mut files = vec![];
+                for table_path in &self.table_paths {
+                    files.extend(prune_files(
+                        table_path,
+                        &kept_files,
+                        filters,
+                        &self.options.file_extension,
+                        &self.options.table_partition_cols,
+                        &self.options.partition_transforms,
+                    )?);
+                }
+                stream::iter(files.into_iter().map(Ok)).boxed()
+            } else {
+                let file_list =
+                    future::try_join_all(self.table_paths.iter().map(|table_path| {
+                        pruned_partition_list_with_counts(
+                            ctx,
+                            store.as_ref(),
+                            table_path,
+                            filters,
+                            &self.options.file_extension,
+                            &self.options.table_partition_cols,
+                            &self.options.partition_transforms,
+                        )
+                    }))
+                    .await?;
+                let mut streams = Vec::with_capacity(file_list.len());
+                for (stream, listing) in file_list {
+                    pruning.partitions_considered += listing.partitions_considered;
+                    pruning.partitions_kept += listing.partitions_kept;
+                    streams.push(stream);
+                }
+                stream::iter(streams).flatten().boxed()
+            };
+        // the files are counted as they are listed, which stops at the limit
+        let mut files_considered = 0;
+        let file_list = file_list.inspect_ok(|_| files_considered += 1);
+        // files left out of a sample are neither read nor inspected
+        let file_list = file_list.try_filter(move |part_file| {
+            future::ready(sample.map_or(true, |sample| is_sampled(part_file, sample)))
+        });
+        // files without rows found by the text index are not read either
+        let selects_rows = self.selects_rows();
+        let file_list = file_list.try_filter_map(|mut part_file| {
+            let part_file = match &text_matches {
+                Some(text_matches) => text_matches
+                    .file(&part_file.object_meta.location)
+                    .map(|rows| {
+                        if selects_rows {
+                            part_file.extensions = Some(Arc::new(rows.clone()));
+                        }
+                        part_file
+                    }),
+                None => Some(part_file),
+            };
+            future::ready(Ok(part_file))
+        });
         // collect the statistics if required by the config
         let files = file_list
             .map(|part_file| async {
@@ -962,20 +1576,71 @@ impl ListingTable {
             .boxed()
             .buffered(ctx.config_options().execution.meta_fetch_concurrency);
 
-        let (files, statistics) = get_statistics_with_limit(
+        // the statistics of the files count the rows not found by the text
+        // index, so they can not tell when the limit is met
+        let limit = if text_matches.is_some() { None } else { limit };
+        let (files, mut statistics) = get_statistics_with_limit(
             files,
             self.schema(),
             limit,
             self.options.collect_stat,
         )
         .await?;
+        if text_matches.is_some() {
+            statistics = statistics.into_inexact();
+        }
+        pruning.files_considered = files_considered;
 
         Ok((
             split_files(files, self.options.target_partitions),
             statistics,
+            pruning,
         ))
     }
 
+    /// Returns the column and the query of `filter` if it is a full-text
+    /// search `matches(column, '<query>')` on a file column covered by the
+    /// text index of the table
+    fn text_index_search<'a>(&self, filter: &'a Expr) -> Option<(&'a str, &'a str)> {
+        let text_index = self.text_index.as_ref()?;
+        let (column, query) = text_search(filter)?;
+        (self.file_schema.field_with_name(column).is_ok() && text_index.covers(column))
+            .then_some((column, query))
+    }
+
+    /// Searches the text index of the table for the rows matching all the
+    /// full-text searches of `filters` it covers, `None` if there are none
+    async fn search_text_index(
+        &self,
+        filters: &[Expr],
+    ) -> Result<Option<TextIndexMatches>> {
+        let Some(text_index) = &self.text_index else {
+            return Ok(None);
+        };
+        let mut matches: Option<TextIndexMatches> = None;
+        for (column, query) in filters
+            .iter()
+            .filter_map(|filter| self.text_index_search(filter))
+        {
+            let found = text_index.search(column, query).await?;
+            matches = Some(match matches {
+                Some(matches) => matches.intersect(&found),
+                None => found,
+            });
+        }
+        Ok(matches)
+    }
+
+    /// Returns true if the scans of the table skip the rows of the files not
+    /// selected by their [`SelectedRows`](super::SelectedRows)
+    fn selects_rows(&self) -> bool {
+        #[cfg(feature = "parquet")]
+        if self.options.format.as_any().is::<ParquetFormat>() {
+            return true;
+        }
+        false
+    }
+
     /// Collects statistics for a given partitioned file.
     ///
     /// This method first checks if the statistics for the given file are already cached.
@@ -1014,10 +1679,89 @@ impl ListingTable {
     }
 }
 
+/// Returns whether `file` is part of `sample`. Each file is kept with the
+/// sampling probability, decided from the seed and the path of the file alone
+/// so that the sample does not depend on the order in which files are listed.
+///
+/// The path is hashed with XxHash64, whose output is fixed across Rust
+/// versions and platforms, so that a seed returns the same files everywhere.
+fn is_sampled(file: &PartitionedFile, sample: &TableSample) -> bool {
+    let mut hasher = twox_hash::XxHash64::with_seed(sample.seed.unwrap_or_default());
+    hasher.write(file.object_meta.location.as_ref().as_bytes());
+    StdRng::seed_from_u64(hasher.finish()).gen::<f64>() < sample.fraction()
+}
+
+/// Returns the union of the fields of `schemas`, matched by name. A field
+/// missing from some of the schemas is nullable in the union.
+fn merge_schemas(schemas: &[SchemaRef]) -> Result<Schema> {
+    let Some((first, others)) = schemas.split_first() else {
+        return Ok(Schema::empty());
+    };
+    let mut fields = first.fields().to_vec();
+    let mut metadata = first.metadata().clone();
+    for schema in others {
+        fields = merge_fields(&fields, schema.fields())?;
+        metadata.extend(schema.metadata().clone());
+    }
+    Ok(Schema::new_with_metadata(fields, metadata))
+}
+
+/// Returns the union of the fields `fields` and `others`, matched by name,
+/// in the order of `fields` followed by the new fields of `others`
+fn merge_fields(fields: &[FieldRef], others: &[FieldRef]) -> Result<Vec<FieldRef>> {
+    let mut merged = fields
+        .iter()
+        .map(|field| {
+            if others.iter().any(|f| f.name() == field.name()) {
+                field.clone()
+            } else {
+                Arc::new(field.as_ref().clone().with_nullable(true))
+            }
+        })
+        .collect::<Vec<_>>();
+    for other in others {
+        match merged.iter().position(|f| f.name() == other.name()) {
+            Some(i) => merged[i] = Arc::new(merge_field(&merged[i], other)?),
+            None => merged.push(Arc::new(other.as_ref().clone().with_nullable(true))),
+        }
+    }
+    Ok(merged)
+}
+
+/// Merges the fields `field` and `other` of the same name, whose types must
+/// be the same but for the fields of their structs
+fn merge_field(field: &Field, other: &Field) -> Result<Field> {
+    let data_type = match (field.data_type(), other.data_type()) {
+        (DataType::Struct(fields), DataType::Struct(others)) => {
+            DataType::Struct(merge_fields(fields, others)?.into())
+        }
+        (DataType::List(element), DataType::List(other_element)) => {
+            DataType::List(Arc::new(merge_field(element, other_element)?))
+        }
+        (DataType::LargeList(element), DataType::LargeList(other_element)) => {
+            DataType::LargeList(Arc::new(merge_field(element, other_element)?))
+        }
+        (DataType::Null, data_type) | (data_type, DataType::Null) => data_type.clone(),
+        (data_type, other_type) if data_type == other_type => data_type.clone(),
+        (data_type, other_type) => {
+            return plan_err!(
+                "Cannot merge the field {} of type {data_type:?} with a field of type \
+                {other_type:?} in the schemas of the files",
+                field.name()
+            )
+        }
+    };
+    Ok(field
+        .clone()
+        .with_data_type(data_type)
+        .with_nullable(field.is_nullable() || other.is_nullable()))
+}
+
 #[cfg(test)]
 mod tests {
 
     use super::*;
+    use crate::datasource::listing::ManifestFormat;
     #[cfg(feature = "parquet")]
     use crate::datasource::{provider_as_source, MemTable};
     use crate::execution::options::ArrowReadOptions;
@@ -1036,6 +1780,7 @@ mod tests {
     use datafusion_physical_expr::PhysicalSortExpr;
     use datafusion_physical_plan::ExecutionPlanProperties;
 
+    use std::time::Duration;
     use tempfile::TempDir;
 
     #[tokio::test]
@@ -1109,6 +1854,372 @@ mod tests {
     }
 
     #[cfg(feature = "parquet")]
+    #[tokio::test]
+    async fn read_files_of_manifest() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().to_str().unwrap();
+        std::fs::write(dir.path().join("1.csv"), "a\n1\n2\n")?;
+        std::fs::write(dir.path().join("2.csv"), "a\n3\n")?;
+        let manifest_path = format!("{path}/_manifest.json");
+        std::fs::write(
+            &manifest_path,
+            "{\"path\": \"1.csv\", \"size\": 6, \"last_modified\": 1000, \"num_rows\": 2}\n",
+        )?;
+
+        let ctx = SessionContext::new();
+        let state = ctx.state();
+        let table_path = ListingTableUrl::parse(format!("{path}/"))?;
+        let manifest = ListingTableManifest::new(
+            ListingTableUrl::parse(&manifest_path)?,
+            ManifestFormat::Json,
+        );
+        let opt = ListingOptions::new(Arc::new(CsvFormat::default()))
+            .with_file_extension(".csv")
+            .with_manifest(Some(manifest));
+        let schema = opt.infer_schema(&state, &table_path, None).await?;
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(opt)
+            .with_schema(schema);
+        let table = ListingTable::try_new(config)?;
+
+        // only the files of the manifest are read, with its estimated statistics
+        let exec = table.scan(&state, None, &[], None).await?;
+        assert_eq!(exec.statistics()?.num_rows, Precision::Inexact(2));
+        let batches = collect(exec, ctx.task_ctx()).await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 2);
+
+        // the files are kept until the manifest is refreshed, and the number of
+        // rows of the files without a time of last modification is not cached
+        std::fs::write(
+            &manifest_path,
+            "{\"path\": \"1.csv\", \"size\": 6}\n\
+             {\"path\": \"2.csv\", \"size\": 4, \"num_rows\": 1}\n",
+        )?;
+        let exec = table.scan(&state, None, &[], None).await?;
+        assert_eq!(exec.statistics()?.num_rows, Precision::Inexact(2));
+        table.refresh_manifest(&state).await?;
+        let exec = table.scan(&state, None, &[], None).await?;
+        assert_eq!(exec.statistics()?.num_rows, Precision::Absent);
+        let batches = collect(exec, ctx.task_ctx()).await?;
+        assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 3);
+
+        let err = table
+            .insert_into(&state, Arc::new(EmptyExec::new(table.schema())), false)
+            .await
+            .unwrap_err();
+        assert_contains!(err.to_string(), "with a manifest is not supported");
+        Ok(())
+    }
+
+    /// Returns the changes of the files it was given, once
+    #[derive(Debug, Default)]
+    struct QueuedChanges(Mutex<FileChanges>);
+
+    impl FileChangeNotifier for QueuedChanges {
+        fn take_changes(&self, _table_path: &ListingTableUrl) -> Result<FileChanges> {
+            Ok(std::mem::take(&mut *self.0.lock()))
+        }
+    }
+
+    #[tokio::test]
+    async fn refresh_files_incrementally() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().to_str().unwrap();
+        std::fs::write(dir.path().join("1.csv"), "a\n1\n2\n")?;
+
+        let ctx = SessionContext::new();
+        let state = ctx.state();
+        let table_path = ListingTableUrl::parse(format!("{path}/"))?;
+        let opt = ListingOptions::new(Arc::new(CsvFormat::default()))
+            .with_file_extension(".csv");
+        let schema = opt.infer_schema(&state, &table_path, None).await?;
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(opt)
+            .with_schema(schema);
+        let notifier = Arc::new(QueuedChanges::default());
+        let table = ListingTable::try_new(config)?
+            .with_change_notifier(Some(notifier.clone() as _));
+        let num_rows = || async {
+            let exec = table.scan(&state, None, &[], None).await?;
+            let batches = collect(exec, ctx.task_ctx()).await?;
+            Ok::<_, DataFusionError>(batches.iter().map(|b| b.num_rows()).sum::<usize>())
+        };
+
+        // the files are listed at the first scan, and then kept
+        assert_eq!(num_rows().await?, 2);
+        std::fs::write(dir.path().join("2.csv"), "a\n3\n")?;
+        assert_eq!(num_rows().await?, 2);
+
+        // the changes are found by listing the files again
+        let changes = table.refresh_files(&state).await?;
+        assert_eq!(changes.added.len(), 1);
+        assert!(changes.added[0].location.as_ref().ends_with("2.csv"));
+        assert!(changes.modified.is_empty() && changes.removed.is_empty());
+        assert_eq!(num_rows().await?, 3);
+
+        // or notified, without listing the files
+        *notifier.0.lock() = FileChanges {
+            removed: vec![changes.added[0].location.clone()],
+            ..Default::default()
+        };
+        assert_eq!(num_rows().await?, 2);
+        table.apply_changes(&FileChanges {
+            added: changes.added.clone(),
+            ..Default::default()
+        })?;
+        assert_eq!(num_rows().await?, 3);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn vacuum_orphaned_files() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().to_str().unwrap();
+        std::fs::write(dir.path().join("1.csv"), "a\n1\n2\n")?;
+        std::fs::write(dir.path().join("2.csv"), "a\n3\n")?;
+        std::fs::write(dir.path().join("3.json"), "{\"a\": 4}\n")?;
+        let manifest_path = format!("{path}/_manifest.json");
+        std::fs::write(&manifest_path, "{\"path\": \"1.csv\", \"size\": 6}\n")?;
+
+        let ctx = SessionContext::new();
+        let state = ctx.state();
+        let table_path = ListingTableUrl::parse(format!("{path}/"))?;
+        let manifest = ListingTableManifest::new(
+            ListingTableUrl::parse(&manifest_path)?,
+            ManifestFormat::Json,
+        );
+        let opt = ListingOptions::new(Arc::new(CsvFormat::default()))
+            .with_file_extension(".csv");
+        let schema = opt.infer_schema(&state, &table_path, None).await?;
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(opt)
+            .with_schema(schema);
+        let table = ListingTable::try_new(config)?;
+
+        // the files modified during the retention period are kept
+        let options = VacuumOptions::new(Duration::from_secs(3600)).with_dry_run(true);
+        let report = table.vacuum(&state, &manifest, &options).await?;
+        assert_eq!(report, VacuumReport::default());
+
+        let options = VacuumOptions::new(Duration::ZERO).with_dry_run(true);
+        let report = table.vacuum(&state, &manifest, &options).await?;
+        assert_eq!(report.orphans.len(), 1);
+        assert!(report.orphans[0].location.as_ref().ends_with("2.csv"));
+        assert_eq!(report.orphans_size(), 4);
+        assert!(!report.deleted);
+        assert!(dir.path().join("2.csv").exists());
+
+        let report = table
+            .vacuum(&state, &manifest, &options.with_dry_run(false))
+            .await?;
+        assert!(report.deleted);
+        assert!(!dir.path().join("2.csv").exists());
+        // the files of other formats are not the table's
+        assert!(dir.path().join("1.csv").exists());
+        assert!(dir.path().join("3.json").exists());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn vacuum_safeguards() -> Result<()> {
+        let dir = TempDir::new()?;
+        let path = dir.path().to_str().unwrap();
+        std::fs::write(dir.path().join("1.csv"), "a\n1\n2\n")?;
+        std::fs::write(dir.path().join("2.csv"), "a\n3\n")?;
+        let manifest_path = format!("{path}/_manifest.json");
+
+        let ctx = SessionContext::new();
+        let state = ctx.state();
+        let table_path = ListingTableUrl::parse(format!("{path}/"))?;
+        let manifest = ListingTableManifest::new(
+            ListingTableUrl::parse(&manifest_path)?,
+            ManifestFormat::Json,
+        );
+        let opt = ListingOptions::new(Arc::new(CsvFormat::default()))
+            .with_file_extension(".csv");
+        let schema = opt.infer_schema(&state, &table_path, None).await?;
+        let config = ListingTableConfig::new(table_path.clone())
+            .with_listing_options(opt.clone())
+            .with_schema(schema.clone());
+        let table = ListingTable::try_new(config)?;
+        let options = VacuumOptions::new(Duration::ZERO);
+        let files_exist =
+            || dir.path().join("1.csv").exists() && dir.path().join("2.csv").exists();
+
+        // a live file which is not a file of the table aborts the vacuum
+        std::fs::write(&manifest_path, "{\"path\": \"0.csv\", \"size\": 6}\n")?;
+        let err = table.vacuum(&state, &manifest, &options).await.unwrap_err();
+        assert!(
+            err.to_string().contains("are not files of the table"),
+            "{err}"
+        );
+        assert!(files_exist());
+
+        // an empty manifest deletes all the files only if forced
+        std::fs::write(&manifest_path, "")?;
+        let err = table.vacuum(&state, &manifest, &options).await.unwrap_err();
+        assert!(err.to_string().contains("lists no live file"), "{err}");
+        assert!(files_exist());
+        let report = table
+            .vacuum(
+                &state,
+                &manifest,
+                &options.clone().with_force(true).with_dry_run(true),
+            )
+            .await?;
+        assert_eq!(report.orphans.len(), 2);
+
+        // the live files of a table with several paths cannot be resolved
+        let config = ListingTableConfig::new_with_multi_paths(vec![
+            table_path.clone(),
+            ListingTableUrl::parse(format!("{path}/other/"))?,
+        ])
+        .with_listing_options(opt)
+        .with_schema(schema);
+        let table = ListingTable::try_new(config)?;
+        std::fs::write(&manifest_path, "{\"path\": \"1.csv\", \"size\": 6}\n")?;
+        let err = table.vacuum(&state, &manifest, &options).await.unwrap_err();
+        assert!(err.to_string().contains("several paths"), "{err}");
+        assert!(files_exist());
+        Ok(())
+    }
+
+    #[cfg(feature = "parquet")]
+    #[tokio::test]
+    async fn scan_with_text_index() -> Result<()> {
+        use crate::datasource::listing::SelectedRows;
+        use crate::functions::string::tokenize;
+        use crate::physical_plan::displayable;
+        use arrow::array::{Int32Array, StringArray};
+        use object_store::path::Path;
+        use parquet::arrow::ArrowWriter;
+        use std::sync::atomic::{AtomicUsize, Ordering};
+
+        /// An inverted index of the column `body`, counting its searches
+        #[derive(Debug, Default)]
+        struct TestTextIndex {
+            postings: HashMap<String, HashMap<Path, Vec<u64>>>,
+            searches: AtomicUsize,
+        }
+
+        impl TestTextIndex {
+            fn add(&mut self, location: &Path, position: u64, text: &str) {
+                for term in tokenize(text) {
+                    let postings = self.postings.entry(term).or_default();
+                    postings.entry(location.clone()).or_default().push(position);
+                }
+            }
+        }
+
+        #[async_trait]
+        impl TextIndex for TestTextIndex {
+            fn covers(&self, column: &str) -> bool {
+                column == "body"
+            }
+
+            async fn search(
+                &self,
+                _column: &str,
+                query: &str,
+            ) -> Result<TextIndexMatches> {
+                self.searches.fetch_add(1, Ordering::SeqCst);
+                let matches = tokenize(query).map(|term| {
+                    let files = self.postings.get(&term).cloned().unwrap_or_default();
+                    files.into_iter().fold(
+                        TextIndexMatches::new(),
+                        |matches, (location, rows)| {
+                            matches.with_file(location, SelectedRows::new(rows))
+                        },
+                    )
+                });
+                Ok(matches
+                    .reduce(|matches, other| matches.intersect(&other))
+                    .unwrap_or_default())
+            }
+        }
+
+        let dir = TempDir::new()?;
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("title", DataType::Utf8, false),
+            Field::new("body", DataType::Utf8, false),
+        ]));
+        let mut index = TestTextIndex::default();
+        let files = [
+            (
+                "1.parquet",
+                vec![
+                    (0, "fox", "The quick brown fox"),
+                    (1, "dog", "Lazy dogs"),
+                    (2, "fox", "A quick fox jumps"),
+                ],
+            ),
+            (
+                "2.parquet",
+                vec![(3, "fox", "Foxes are quick"), (4, "turtle", "Slow turtles")],
+            ),
+        ];
+        for (name, rows) in files {
+            let file_path = dir.path().join(name);
+            std::fs::File::create(&file_path)?;
+            let location = Path::from_filesystem_path(&file_path).unwrap();
+            for (position, (_, _, body)) in rows.iter().enumerate() {
+                index.add(&location, position as u64, body);
+            }
+            let batch = RecordBatch::try_new(
+                schema.clone(),
+                vec![
+                    Arc::new(Int32Array::from_iter_values(rows.iter().map(|r| r.0))),
+                    Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.1))),
+                    Arc::new(StringArray::from_iter_values(rows.iter().map(|r| r.2))),
+                ],
+            )?;
+            let mut writer = ArrowWriter::try_new(
+                std::fs::File::create(file_path)?,
+                schema.clone(),
+                None,
+            )?;
+            writer.write(&batch)?;
+            writer.close()?;
+        }
+        let index = Arc::new(index);
+
+        let ctx = SessionContext::new();
+        let path = dir.path().to_str().unwrap();
+        let table_path = ListingTableUrl::parse(format!("{path}/"))?;
+        let opt = ListingOptions::new(Arc::new(ParquetFormat::default()));
+        let config = ListingTableConfig::new(table_path)
+            .with_listing_options(opt)
+            .with_schema(schema);
+        let table = ListingTable::try_new(config)?.with_text_index(Some(index.clone()));
+        ctx.register_table("t", Arc::new(table))?;
+
+        // the filter on the indexed column is searched in the index, and
+        // evaluated again on the rows it selects
+        let df = ctx
+            .sql("SELECT id FROM t WHERE matches(body, 'QUICK fox') ORDER BY id")
+            .await?;
+        let plan = df.clone().create_physical_plan().await?;
+        let plan = displayable(plan.as_ref()).indent(true).to_string();
+        assert_contains!(&plan, "FilterExec: matches(body@");
+        let expected = ["+----+", "| id |", "+----+", "| 0  |", "| 2  |", "+----+"];
+        assert_batches_eq!(expected, &df.collect().await?);
+        assert_eq!(index.searches.load(Ordering::SeqCst), 2);
+
+        // the other filters are evaluated by tokenizing the values
+        let df = ctx
+            .sql("SELECT id FROM t WHERE matches(title, 'fox') AND matches(body, 'quick') ORDER BY id")
+            .await?;
+        let plan = df.clone().create_physical_plan().await?;
+        let plan = displayable(plan.as_ref()).indent(true).to_string();
+        assert_contains!(&plan, "FilterExec: matches(title@");
+        let expected = [
+            "+----+", "| id |", "+----+", "| 0  |", "| 2  |", "| 3  |", "+----+",
+        ];
+        assert_batches_eq!(expected, &df.collect().await?);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_try_create_output_ordering() {
         let testdata = crate::test_util::parquet_test_data();
@@ -1422,7 +2533,9 @@ mod tests {
 
         let table = ListingTable::try_new(config)?;
 
-        let (file_list, _) = table.list_files_for_scan(&ctx.state(), &[], None).await?;
+        let (file_list, _, _) = table
+            .list_files_for_scan(&ctx.state(), &[], None, None)
+            .await?;
 
         assert_eq!(file_list.len(), output_partitioning);
 
@@ -1458,7 +2571,9 @@ mod tests {
 
         let table = ListingTable::try_new(config)?;
 
-        let (file_list, _) = table.list_files_for_scan(&ctx.state(), &[], None).await?;
+        let (file_list, _, _) = table
+            .list_files_for_scan(&ctx.state(), &[], None, None)
+            .await?;
 
         assert_eq!(file_list.len(), output_partitioning);
 
@@ -1591,7 +2706,7 @@ mod tests {
             "100".into(),
         );
         config_map.insert(
-            "datafusion.execution.parquet.staistics_enabled".into(),
+            "datafusion.execution.parquet.statistics_enabled".into(),
             "none".into(),
         );
         config_map.insert(
@@ -1665,7 +2780,7 @@ mod tests {
             "100".into(),
         );
         config_map.insert(
-            "datafusion.execution.parquet.staistics_enabled".into(),
+            "datafusion.execution.parquet.statistics_enabled".into(),
             "none".into(),
         );
         config_map.insert(
@@ -0,0 +1,197 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Full-text search of the [`ListingTable`]s by a [`TextIndex`]
+//!
+//! [`ListingTable`]: super::ListingTable
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+
+use crate::error::Result;
+
+use async_trait::async_trait;
+use datafusion_common::ScalarValue;
+use datafusion_expr::Expr;
+use object_store::path::Path;
+
+/// The name of the function matching a text with a full-text query, see
+/// [`tokenize`] for how the text and the query are split into terms
+///
+/// [`tokenize`]: crate::functions::string::tokenize
+pub const MATCHES_FUNCTION: &str = "matches";
+
+/// An inverted index of the text columns of a [`ListingTable`], finding the
+/// rows matching the filters `matches(column, '<query>')` of the scans of the
+/// table in place of evaluating them.
+///
+/// The filters on the columns the index covers are pushed down to the scans
+/// as inexact filters: the index prunes the files without matching rows and,
+/// if the format of the table skips the rows not selected by the index (see
+/// [`SelectedRows`]), the other rows of the files, and the filters are then
+/// evaluated on the rows read. The other filters are evaluated by tokenizing
+/// the values of the column. See
+/// [`ListingTable::with_text_index`].
+///
+/// [`ListingTable`]: super::ListingTable
+/// [`ListingTable::with_text_index`]: super::ListingTable::with_text_index
+#[async_trait]
+pub trait TextIndex: Debug + Send + Sync {
+    /// Returns true if the index covers the column `column` of all the files
+    /// of the table
+    fn covers(&self, column: &str) -> bool;
+
+    /// Returns the rows whose values of the column `column` contain all the
+    /// terms of `query`, the files without such rows being left out
+    async fn search(&self, column: &str, query: &str) -> Result<TextIndexMatches>;
+}
+
+/// The rows matching a full-text query, by file
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct TextIndexMatches {
+    files: HashMap<Path, SelectedRows>,
+}
+
+impl TextIndexMatches {
+    /// Create the matches of no rows
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Add the matching rows `rows` of the file at `location`
+    pub fn with_file(mut self, location: Path, rows: SelectedRows) -> Self {
+        self.files.insert(location, rows);
+        self
+    }
+
+    /// The matching rows of the file at `location`, `None` if it has none
+    pub fn file(&self, location: &Path) -> Option<&SelectedRows> {
+        self.files.get(location)
+    }
+
+    /// Returns the number of files with matching rows
+    pub fn num_files(&self) -> usize {
+        self.files.len()
+    }
+
+    /// Returns the rows matching both `self` and `other`
+    pub fn intersect(&self, other: &TextIndexMatches) -> Self {
+        let files = self
+            .files
+            .iter()
+            .filter_map(|(location, rows)| {
+                let rows = rows.intersect(other.files.get(location)?);
+                (!rows.is_empty()).then(|| (location.clone(), rows))
+            })
+            .collect();
+        Self { files }
+    }
+}
+
+/// The rows of a file to scan, the other rows being skipped, e.g. the rows
+/// matching the full-text queries of a scan found by a [`TextIndex`]
+///
+/// The rows are selected by setting them as the
+/// [`PartitionedFile::extensions`] of the file. Only the parquet scans skip
+/// the other rows.
+///
+/// [`PartitionedFile::extensions`]: super::PartitionedFile::extensions
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct SelectedRows {
+    positions: Vec<u64>,
+}
+
+impl SelectedRows {
+    /// Create the selected rows at the positions `positions`, from 0 for the
+    /// first row of the file
+    pub fn new(mut positions: Vec<u64>) -> Self {
+        positions.sort_unstable();
+        positions.dedup();
+        Self { positions }
+    }
+
+    /// The sorted positions of the selected rows
+    pub fn positions(&self) -> &[u64] {
+        &self.positions
+    }
+
+    /// Returns the number of selected rows
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns true if no rows are selected
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+
+    /// Returns the rows selected by both `self` and `other`
+    pub fn intersect(&self, other: &SelectedRows) -> Self {
+        let mut positions = vec![];
+        let mut other_positions = other.positions.iter().peekable();
+        for position in &self.positions {
+            while other_positions.next_if(|other| *other < position).is_some() {}
+            if other_positions.next_if_eq(&position).is_some() {
+                positions.push(*position);
+            }
+        }
+        Self { positions }
+    }
+}
+
+/// Returns the column and the query of `filter` if it is a full-text search
+/// `matches(column, '<query>')`
+pub(crate) fn text_search(filter: &Expr) -> Option<(&str, &str)> {
+    let Expr::ScalarFunction(func) = filter else {
+        return None;
+    };
+    if func.name() != MATCHES_FUNCTION {
+        return None;
+    }
+    match func.args.as_slice() {
+        [Expr::Column(column), Expr::Literal(
+            ScalarValue::Utf8(Some(query)) | ScalarValue::LargeUtf8(Some(query)),
+        )] => Some((&column.name, query)),
+        _ => None,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn intersect_matches() {
+        let matches = TextIndexMatches::new()
+            .with_file(Path::from("a"), SelectedRows::new(vec![7, 1, 3, 1]))
+            .with_file(Path::from("b"), SelectedRows::new(vec![2]));
+        let other = TextIndexMatches::new()
+            .with_file(Path::from("a"), SelectedRows::new(vec![0, 3, 7, 8]))
+            .with_file(Path::from("b"), SelectedRows::new(vec![4]))
+            .with_file(Path::from("c"), SelectedRows::new(vec![1]));
+        assert_eq!(
+            matches.intersect(&other),
+            TextIndexMatches::new()
+                .with_file(Path::from("a"), SelectedRows::new(vec![3, 7]))
+        );
+        assert_eq!(
+            matches.file(&Path::from("a")).unwrap().positions(),
+            &[1, 3, 7]
+        );
+        assert_eq!(matches.num_files(), 2);
+    }
+}
@@ -0,0 +1,93 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The options and the report of [`ListingTable::vacuum`]
+//!
+//! [`ListingTable::vacuum`]: super::ListingTable::vacuum
+
+use std::time::Duration;
+
+use object_store::ObjectMeta;
+
+/// The options of [`ListingTable::vacuum`]
+///
+/// [`ListingTable::vacuum`]: super::ListingTable::vacuum
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct VacuumOptions {
+    /// The files modified less than `retention` ago are kept, e.g. for the
+    /// queries reading previous versions of the table or for the files being
+    /// written and not committed yet
+    pub retention: Duration,
+    /// Whether to only report the orphaned files, without deleting them
+    pub dry_run: bool,
+    /// Whether to vacuum a table whose manifest lists no live file, which
+    /// deletes all its files modified before the retention period
+    pub force: bool,
+}
+
+impl Default for VacuumOptions {
+    /// A retention of 7 days, deleting the orphaned files
+    fn default() -> Self {
+        Self {
+            retention: Duration::from_secs(7 * 24 * 60 * 60),
+            dry_run: false,
+            force: false,
+        }
+    }
+}
+
+impl VacuumOptions {
+    /// Create the options keeping the files modified less than `retention`
+    /// ago
+    pub fn new(retention: Duration) -> Self {
+        Self {
+            retention,
+            ..Default::default()
+        }
+    }
+
+    /// Set whether to only report the orphaned files, without deleting them
+    pub fn with_dry_run(mut self, dry_run: bool) -> Self {
+        self.dry_run = dry_run;
+        self
+    }
+
+    /// Set whether to vacuum a table whose manifest lists no live file
+    pub fn with_force(mut self, force: bool) -> Self {
+        self.force = force;
+        self
+    }
+}
+
+/// The orphaned files found by [`ListingTable::vacuum`]
+///
+/// [`ListingTable::vacuum`]: super::ListingTable::vacuum
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VacuumReport {
+    /// The files of the table which are not live and were modified before
+    /// the retention period, in the order of their locations
+    pub orphans: Vec<ObjectMeta>,
+    /// Whether the orphaned files were deleted, false for a dry run
+    pub deleted: bool,
+}
+
+impl VacuumReport {
+    /// Returns the total size of the orphaned files in bytes
+    pub fn orphans_size(&self) -> usize {
+        self.orphans.iter().map(|file| file.size).sum()
+    }
+}
@@ -27,15 +27,20 @@ use crate::datasource::file_format::{
     arrow::ArrowFormat, avro::AvroFormat, csv::CsvFormat, json::JsonFormat, FileFormat,
 };
 use crate::datasource::listing::{
-    ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl,
+    ListingOptions, ListingTable, ListingTableConfig, ListingTableManifest,
+    ListingTableUrl, ManifestFormat,
 };
 use crate::datasource::provider::TableProviderFactory;
 use crate::datasource::TableProvider;
 use crate::execution::context::SessionState;
 
-use arrow::datatypes::{DataType, SchemaRef};
-use datafusion_common::{arrow_datafusion_err, DataFusionError, FileType};
-use datafusion_expr::CreateExternalTable;
+use arrow::datatypes::{DataType, Schema, SchemaRef};
+use datafusion_common::{
+    arrow_datafusion_err, plan_err, Constraint, DataFusionError, FileType, Result,
+};
+use datafusion_expr::{CreateExternalTable, Expr};
+use datafusion_sql::unparser::Unparser;
+use sqlparser::ast::Ident;
 
 use async_trait::async_trait;
 
@@ -62,7 +67,11 @@ impl TableProviderFactory for ListingTableFactory {
             DataFusionError::Execution(format!("Unknown FileType {}", cmd.file_type))
         })?;
         table_options.set_file_format(file_type.clone());
-        table_options.alter_with_string_hash_map(&cmd.options)?;
+        // the options of the manifest are not options of the format
+        let mut format_options = cmd.options.clone();
+        let manifest_location = format_options.remove("manifest.location");
+        let manifest_format = format_options.remove("manifest.format");
+        table_options.alter_with_string_hash_map(&format_options)?;
         let file_extension = get_extension(cmd.location.as_str());
         let file_format: Arc<dyn FileFormat> = match file_type {
             FileType::CSV => {
@@ -73,9 +82,13 @@ impl TableProviderFactory for ListingTableFactory {
                 Arc::new(CsvFormat::default().with_options(csv_options))
             }
             #[cfg(feature = "parquet")]
-            FileType::PARQUET => {
-                Arc::new(ParquetFormat::default().with_options(table_options.parquet))
-            }
+            FileType::PARQUET => Arc::new(
+                ParquetFormat::default()
+                    .with_options(table_options.parquet)
+                    .with_session_options(
+                        state.config_options().execution.parquet.clone(),
+                    ),
+            ),
             FileType::AVRO => Arc::new(AvroFormat),
             FileType::JSON => {
                 let mut json_options = table_options.json;
@@ -129,13 +142,32 @@ impl TableProviderFactory for ListingTableFactory {
         };
 
         let table_path = ListingTableUrl::parse(&cmd.location)?;
+        let manifest = match manifest_location {
+            Some(location) => {
+                let url = ListingTableUrl::parse(location)?;
+                let format = match manifest_format {
+                    Some(format) => format.parse()?,
+                    None => ManifestFormat::from_url(&url),
+                };
+                Some(ListingTableManifest::new(url, format))
+            }
+            None if manifest_format.is_some() => {
+                return plan_err!("manifest.format requires manifest.location");
+            }
+            None => None,
+        };
 
         let options = ListingOptions::new(file_format)
             .with_collect_stat(state.config().collect_statistics())
             .with_file_extension(file_extension)
             .with_target_partitions(state.config().target_partitions())
             .with_table_partition_cols(table_partition_cols)
-            .with_file_sort_order(cmd.order_exprs.clone());
+            .with_partition_transforms(cmd.partition_transforms.clone())
+            .with_manifest(manifest)
+            .with_file_sort_order(cmd.order_exprs.clone())
+            .with_merge_schemas(
+                state.config_options().execution.listing_table_merge_schemas,
+            );
 
         options.validate_partitions(state, &table_path).await?;
 
@@ -143,19 +175,140 @@ impl TableProviderFactory for ListingTableFactory {
             None => options.infer_schema(state, &table_path, None).await?,
             Some(s) => s,
         };
+        // the statement is reconstructed with the resolved types of the
+        // columns, or kept as written if a type can't be written in SQL
+        let definition = create_statement(cmd, &resolved_schema, &options)
+            .ok()
+            .or_else(|| cmd.definition.clone());
         let config = ListingTableConfig::new(table_path)
             .with_listing_options(options)
             .with_schema(resolved_schema);
         let provider = ListingTable::try_new(config)?
             .with_cache(state.runtime_env().cache_manager.get_file_statistic_cache());
         let table = provider
-            .with_definition(cmd.definition.clone())
+            .with_definition(definition)
             .with_constraints(cmd.constraints.clone())
             .with_column_defaults(cmd.column_defaults.clone());
         Ok(Arc::new(table))
     }
 }
 
+/// Reconstructs the `CREATE EXTERNAL TABLE` statement `cmd`, declaring the
+/// columns of the files with their types in `file_schema`, with their default
+/// values and the constraints of `cmd`, and the partition columns with their
+/// types in `options`
+fn create_statement(
+    cmd: &CreateExternalTable,
+    file_schema: &Schema,
+    options: &ListingOptions,
+) -> Result<String> {
+    let unparser = Unparser::default();
+    let column = |name: &str, data_type: &DataType, nullable: bool| -> Result<String> {
+        let data_type = unparser.arrow_dtype_to_ast_dtype(data_type)?;
+        let not_null = if nullable { "" } else { " NOT NULL" };
+        Ok(format!(
+            "{} {data_type}{not_null}",
+            Ident::with_quote('"', name)
+        ))
+    };
+
+    let mut sql = String::from("CREATE ");
+    if cmd.unbounded {
+        sql.push_str("UNBOUNDED ");
+    }
+    let mut columns = file_schema
+        .fields()
+        .iter()
+        .map(|f| {
+            let column = column(f.name(), f.data_type(), f.is_nullable())?;
+            match cmd.column_defaults.get(f.name()) {
+                Some(default) => Ok(format!(
+                    "{column} DEFAULT {}",
+                    unparser.expr_to_sql(default)?
+                )),
+                None => Ok(column),
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+    for constraint in cmd.constraints.iter() {
+        let (keyword, indices) = match constraint {
+            Constraint::PrimaryKey(indices) => ("PRIMARY KEY", indices),
+            Constraint::Unique(indices) => ("UNIQUE", indices),
+        };
+        let names = indices
+            .iter()
+            .map(|i| Ident::with_quote('"', cmd.schema.field(*i).name()).to_string())
+            .collect::<Vec<_>>();
+        columns.push(format!("{keyword} ({})", names.join(", ")));
+    }
+    sql.push_str(&format!(
+        "EXTERNAL TABLE {} ({})",
+        cmd.name,
+        columns.join(", ")
+    ));
+    sql.push_str(&format!(" STORED AS {}", cmd.file_type));
+    if !options.table_partition_cols.is_empty() {
+        let columns = options
+            .table_partition_cols
+            .iter()
+            .map(|(name, data_type)| {
+                let column = column(name, data_type, true)?;
+                match options.partition_transforms.get(name) {
+                    Some(expr) => {
+                        Ok(format!("{column} FROM {}", unparser.expr_to_sql(expr)?))
+                    }
+                    None => Ok(column),
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        sql.push_str(&format!(" PARTITIONED BY ({})", columns.join(", ")));
+    }
+    if cmd.has_header {
+        sql.push_str(" WITH HEADER ROW");
+    }
+    if cmd.delimiter != ',' {
+        sql.push_str(&format!(
+            " DELIMITER {}",
+            quote_literal(&cmd.delimiter.to_string())
+        ));
+    }
+    if cmd.file_compression_type.is_compressed() {
+        sql.push_str(&format!(" COMPRESSION TYPE {}", cmd.file_compression_type));
+    }
+    for ordering in &cmd.order_exprs {
+        let ordering = ordering
+            .iter()
+            .map(|expr| match expr {
+                Expr::Sort(sort) => Ok(format!(
+                    "{} {} NULLS {}",
+                    unparser.expr_to_sql(&sort.expr)?,
+                    if sort.asc { "ASC" } else { "DESC" },
+                    if sort.nulls_first { "FIRST" } else { "LAST" }
+                )),
+                expr => Ok(unparser.expr_to_sql(expr)?.to_string()),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        sql.push_str(&format!(" WITH ORDER ({})", ordering.join(", ")));
+    }
+    if !cmd.options.is_empty() {
+        let mut options = cmd.options.iter().collect::<Vec<_>>();
+        options.sort();
+        let options = options
+            .into_iter()
+            .map(|(key, value)| {
+                format!("{} {}", quote_literal(key), quote_literal(value))
+            })
+            .collect::<Vec<_>>();
+        sql.push_str(&format!(" OPTIONS ({})", options.join(", ")));
+    }
+    sql.push_str(&format!(" LOCATION {}", quote_literal(&cmd.location)));
+    Ok(sql)
+}
+
+fn quote_literal(s: &str) -> String {
+    format!("'{}'", s.replace('\'', "''"))
+}
+
 // Get file extension from path
 fn get_extension(path: &str) -> String {
     let res = Path::new(path).extension().and_then(|ext| ext.to_str());
@@ -195,6 +348,7 @@ mod tests {
             delimiter: ',',
             schema: Arc::new(DFSchema::empty()),
             table_partition_cols: vec![],
+            partition_transforms: HashMap::new(),
             if_not_exists: false,
             file_compression_type: CompressionTypeVariant::UNCOMPRESSED,
             definition: None,
@@ -236,6 +390,7 @@ mod tests {
             delimiter: ',',
             schema: Arc::new(DFSchema::empty()),
             table_partition_cols: vec![],
+            partition_transforms: HashMap::new(),
             if_not_exists: false,
             file_compression_type: CompressionTypeVariant::UNCOMPRESSED,
             definition: None,
@@ -258,4 +413,40 @@ mod tests {
         let listing_options = listing_table.options();
         assert_eq!(".tbl", listing_options.file_extension);
     }
+
+    #[tokio::test]
+    async fn reconstruct_create_statement() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.sql(
+            "CREATE EXTERNAL TABLE t (
+                id BIGINT NOT NULL,
+                s STRUCT<a INT, \"B\" ARRAY<STRUCT<c VARCHAR>>>
+            )
+            STORED AS JSON
+            PARTITIONED BY (part INT)
+            WITH ORDER (id DESC)
+            OPTIONS ('format.schema_infer_max_rec' '10')
+            LOCATION 'tests/data/partitioned_table_json'",
+        )
+        .await?;
+        let table = ctx.table_provider("t").await?;
+        let definition = table.get_table_definition().unwrap().to_string();
+        assert_eq!(
+            definition,
+            "CREATE EXTERNAL TABLE t (\"id\" BIGINT NOT NULL, \
+            \"s\" STRUCT<\"a\" INTEGER, \"B\" ARRAY<STRUCT<\"c\" VARCHAR>>>) \
+            STORED AS JSON PARTITIONED BY (\"part\" INTEGER) \
+            WITH ORDER (\"id\" DESC NULLS FIRST) \
+            OPTIONS ('format.schema_infer_max_rec' '10') \
+            LOCATION 'tests/data/partitioned_table_json'"
+        );
+
+        // the statement creates the same table again
+        ctx.deregister_table("t")?;
+        ctx.sql(&definition).await?;
+        let recreated = ctx.table_provider("t").await?;
+        assert_eq!(recreated.schema(), table.schema());
+        assert_eq!(recreated.get_table_definition(), Some(definition.as_str()));
+        Ok(())
+    }
 }
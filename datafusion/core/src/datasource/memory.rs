@@ -29,6 +29,7 @@ use crate::logical_expr::Expr;
 use crate::physical_plan::insert::{DataSink, DataSinkExec};
 use crate::physical_plan::memory::MemoryExec;
 use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::transaction::StagedWrite;
 use crate::physical_plan::{
     common, DisplayAs, DisplayFormatType, ExecutionPlan, ExecutionPlanProperties,
     Partitioning, SendableRecordBatchStream,
@@ -37,7 +38,9 @@ use crate::physical_planner::create_physical_sort_exprs;
 
 use arrow::datatypes::SchemaRef;
 use arrow::record_batch::RecordBatch;
-use datafusion_common::{not_impl_err, plan_err, Constraints, DFSchema, SchemaExt};
+use datafusion_common::{
+    not_impl_err, plan_err, Constraints, DFSchema, SchemaExt, Statistics,
+};
 use datafusion_execution::TaskContext;
 use datafusion_physical_plan::metrics::MetricsSet;
 
@@ -64,6 +67,9 @@ pub struct MemTable {
     /// Optional pre-known sort order(s). Must be `SortExpr`s.
     /// inserting data into this table removes the order
     pub sort_order: Arc<Mutex<Vec<Vec<Expr>>>>,
+    /// Statistics set by [`TableProvider::set_statistics`], inserting data
+    /// into this table removes them
+    statistics: Mutex<Option<Statistics>>,
 }
 
 impl MemTable {
@@ -89,6 +95,7 @@ impl MemTable {
             constraints: Constraints::empty(),
             column_defaults: HashMap::new(),
             sort_order: Arc::new(Mutex::new(vec![])),
+            statistics: Mutex::new(None),
         })
     }
 
@@ -223,6 +230,12 @@ impl TableProvider for MemTable {
         let show_sizes = state.config_options().explain.show_sizes;
         exec = exec.with_show_sizes(show_sizes);
 
+        // The row and null counts are computed from the batches, the other
+        // statistics set on the table are estimates
+        if let Some(statistics) = self.statistics.lock().clone() {
+            exec = exec.with_column_statistics(statistics.column_statistics)?;
+        }
+
         // add sort information if present
         let sort_order = self.sort_order.lock();
         if !sort_order.is_empty() {
@@ -244,6 +257,22 @@ impl TableProvider for MemTable {
         Ok(Arc::new(exec))
     }
 
+    fn statistics(&self) -> Option<Statistics> {
+        self.statistics.lock().clone()
+    }
+
+    fn set_statistics(&self, statistics: Statistics) -> Result<()> {
+        if statistics.column_statistics.len() != self.schema.fields().len() {
+            return plan_err!(
+                "Expected statistics for {} columns, got {}",
+                self.schema.fields().len(),
+                statistics.column_statistics.len()
+            );
+        }
+        *self.statistics.lock() = Some(statistics);
+        Ok(())
+    }
+
     /// Returns an ExecutionPlan that inserts the execution results of a given [`ExecutionPlan`] into this [`MemTable`].
     ///
     /// The [`ExecutionPlan`] must have the same schema as this [`MemTable`].
@@ -264,6 +293,7 @@ impl TableProvider for MemTable {
     ) -> Result<Arc<dyn ExecutionPlan>> {
         // If we are inserting into the table, any sort order may be messed up so reset it here
         *self.sort_order.lock() = vec![];
+        *self.statistics.lock() = None;
 
         // Create a physical plan from the logical plan.
         // Check that the schema of the plan matches the schema of this table.
@@ -335,13 +365,36 @@ impl DataSink for MemSink {
 
     async fn write_all(
         &self,
-        mut data: SendableRecordBatchStream,
+        data: SendableRecordBatchStream,
         _context: &Arc<TaskContext>,
     ) -> Result<u64> {
-        let num_partitions = self.batches.len();
+        let (new_batches, row_count) = self.buffer(data).await?;
+        append_batches(&self.batches, new_batches).await;
+        Ok(row_count as u64)
+    }
 
-        // buffer up the data round robin style into num_partitions
+    async fn stage_all(
+        &self,
+        data: SendableRecordBatchStream,
+        _context: &Arc<TaskContext>,
+    ) -> Result<(u64, Arc<dyn StagedWrite>)> {
+        let (new_batches, row_count) = self.buffer(data).await?;
+        let staged = MemStagedWrite {
+            targets: self.batches.clone(),
+            new_batches: Mutex::new(new_batches),
+        };
+        Ok((row_count as u64, Arc::new(staged)))
+    }
+}
 
+impl MemSink {
+    /// Buffers up the data round robin style into the partitions, returns
+    /// the batches of each partition and the number of rows
+    async fn buffer(
+        &self,
+        mut data: SendableRecordBatchStream,
+    ) -> Result<(Vec<Vec<RecordBatch>>, usize)> {
+        let num_partitions = self.batches.len();
         let mut new_batches = vec![vec![]; num_partitions];
         let mut i = 0;
         let mut row_count = 0;
@@ -350,14 +403,37 @@ impl DataSink for MemSink {
             new_batches[i].push(batch);
             i = (i + 1) % num_partitions;
         }
+        Ok((new_batches, row_count))
+    }
+}
 
-        // write the outputs into the batches
-        for (target, mut batches) in self.batches.iter().zip(new_batches.into_iter()) {
-            // Append all the new batches in one go to minimize locking overhead
-            target.write().await.append(&mut batches);
-        }
+/// Writes the outputs into the batches of the partitions
+async fn append_batches(targets: &[PartitionData], new_batches: Vec<Vec<RecordBatch>>) {
+    for (target, mut batches) in targets.iter().zip(new_batches.into_iter()) {
+        // Append all the new batches in one go to minimize locking overhead
+        target.write().await.append(&mut batches);
+    }
+}
 
-        Ok(row_count as u64)
+/// The batches written to a [`MemTable`] in a write transaction, appended
+/// to its partitions on commit
+#[derive(Debug)]
+struct MemStagedWrite {
+    targets: Vec<PartitionData>,
+    new_batches: Mutex<Vec<Vec<RecordBatch>>>,
+}
+
+#[async_trait]
+impl StagedWrite for MemStagedWrite {
+    async fn commit(&self) -> Result<()> {
+        let new_batches = std::mem::take(&mut *self.new_batches.lock());
+        append_batches(&self.targets, new_batches).await;
+        Ok(())
+    }
+
+    async fn abort(&self) -> Result<()> {
+        self.new_batches.lock().clear();
+        Ok(())
     }
 }
 
@@ -372,7 +448,8 @@ mod tests {
     use arrow::array::{AsArray, Int32Array};
     use arrow::datatypes::{DataType, Field, Schema, UInt64Type};
     use arrow::error::ArrowError;
-    use datafusion_common::DataFusionError;
+    use datafusion_common::stats::Precision;
+    use datafusion_common::{ColumnStatistics, DataFusionError, ScalarValue};
     use datafusion_expr::LogicalPlanBuilder;
 
     #[tokio::test]
@@ -413,6 +490,66 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_set_statistics() -> Result<()> {
+        let session_ctx = SessionContext::new();
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![1, 2, 3])),
+                Arc::new(Int32Array::from(vec![None, None, Some(9)])),
+            ],
+        )?;
+        let provider = MemTable::try_new(schema, vec![vec![batch]])?;
+
+        let column_statistics = |min: i32, max: i32| ColumnStatistics {
+            null_count: Precision::Exact(5),
+            min_value: Precision::Exact(ScalarValue::Int32(Some(min))),
+            max_value: Precision::Exact(ScalarValue::Int32(Some(max))),
+            distinct_count: Precision::Exact(3),
+            distinct_sketch: None,
+        };
+        let err = provider
+            .set_statistics(Statistics {
+                num_rows: Precision::Exact(3),
+                total_byte_size: Precision::Absent,
+                column_statistics: vec![column_statistics(1, 3)],
+            })
+            .unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: Expected statistics for 2 columns, got 1"
+        );
+        provider.set_statistics(Statistics {
+            num_rows: Precision::Exact(3),
+            total_byte_size: Precision::Absent,
+            column_statistics: vec![column_statistics(1, 3), column_statistics(9, 9)],
+        })?;
+
+        // the counts are computed from the batches, the other statistics
+        // are estimates
+        let exec = provider
+            .scan(&session_ctx.state(), Some(&vec![1]), &[], None)
+            .await?;
+        let statistics = exec.statistics()?;
+        assert_eq!(statistics.num_rows, Precision::Exact(3));
+        assert_eq!(
+            statistics.column_statistics,
+            vec![ColumnStatistics {
+                null_count: Precision::Exact(2),
+                min_value: Precision::Inexact(ScalarValue::Int32(Some(9))),
+                max_value: Precision::Inexact(ScalarValue::Int32(Some(9))),
+                distinct_count: Precision::Inexact(3),
+                distinct_sketch: None,
+            }]
+        );
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_without_projection() -> Result<()> {
         let session_ctx = SessionContext::new();
@@ -20,10 +20,17 @@
 //! [`ListingTable`]: crate::datasource::listing::ListingTable
 
 pub mod avro_to_arrow;
+pub mod changes;
+pub mod compaction;
 pub mod cte_worktable;
 pub mod default_table_source;
 pub mod empty;
+pub mod federation;
+#[cfg(feature = "ffi")]
+pub mod ffi;
 pub mod file_format;
+#[cfg(feature = "flight")]
+pub mod flight;
 pub mod function;
 pub mod listing;
 pub mod listing_table_factory;
@@ -33,6 +40,7 @@ pub mod provider;
 mod statistics;
 pub mod stream;
 pub mod streaming;
+pub mod table_format;
 pub mod view;
 
 // backwards compatibility
@@ -42,7 +50,7 @@ pub use self::default_table_source::{
     provider_as_source, source_as_provider, DefaultTableSource,
 };
 pub use self::memory::MemTable;
-pub use self::provider::TableProvider;
+pub use self::provider::{SnapshotToken, TableProvider};
 pub use self::view::ViewTable;
 pub use crate::logical_expr::TableType;
 pub use statistics::get_statistics_with_limit;
@@ -0,0 +1,311 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Execution plan recording the audit events of a table scan
+
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+#[cfg(feature = "parquet")]
+use super::ParquetExec;
+use super::{ArrowExec, AvroExec, CsvExec, FileScanConfig, NdJsonExec};
+use crate::error::Result;
+use crate::physical_plan::metrics::{MetricValue, MetricsSet};
+use crate::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, RecordBatchStream,
+    SendableRecordBatchStream,
+};
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::Statistics;
+use datafusion_execution::audit::{AuditSink, ReadAuditEvent};
+use datafusion_execution::TaskContext;
+use datafusion_physical_plan::PlanProperties;
+
+use futures::{Stream, StreamExt};
+
+/// Records a [`ReadAuditEvent`] in the [`AuditSink`] of the runtime for each
+/// partition of a table scan it executes.
+///
+/// The physical planner puts it above the scans of the tables when an audit
+/// sink is registered. The files and the nested columns read are known for
+/// the file scans (such as [`ParquetExec`]), the bytes read for the scans
+/// reporting a `bytes_scanned` metric.
+///
+/// [`ParquetExec`]: super::ParquetExec
+#[derive(Debug)]
+pub struct ReadAuditExec {
+    /// The table scan
+    input: Arc<dyn ExecutionPlan>,
+    /// The user on whose behalf the table is read
+    user: Option<String>,
+    /// The table, as referred to by the query
+    table: String,
+    /// The filters pushed down to the scan
+    filters: Vec<String>,
+    cache: PlanProperties,
+}
+
+impl ReadAuditExec {
+    /// Create a new ReadAuditExec auditing the scan `input` of `table`
+    pub fn new(
+        input: Arc<dyn ExecutionPlan>,
+        user: Option<String>,
+        table: impl Into<String>,
+        filters: Vec<String>,
+    ) -> Self {
+        // Auditing does not change the output of the scan
+        let cache = input.properties().clone();
+        Self {
+            input,
+            user,
+            table: table.into(),
+            filters,
+            cache,
+        }
+    }
+
+    /// The table scan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// The table, as referred to by the query
+    pub fn table(&self) -> &str {
+        &self.table
+    }
+
+    /// Returns the event of the partition `partition` of the scan, without
+    /// the bytes read
+    fn audit_event(&self, partition: usize) -> ReadAuditEvent {
+        let config = file_scan_config(self.input.as_ref());
+        let files = config
+            .and_then(|config| config.file_groups.get(partition))
+            .map(|files| {
+                files
+                    .iter()
+                    .map(|file| file.object_meta.location.to_string())
+                    .collect()
+            })
+            .unwrap_or_default();
+        let columns = match config.and_then(|config| config.column_hints.as_ref()) {
+            Some(column_hints) if !column_hints.is_empty() => column_hints.clone(),
+            _ => self
+                .schema()
+                .fields()
+                .iter()
+                .map(|field| field.name().clone())
+                .collect(),
+        };
+        ReadAuditEvent {
+            user: self.user.clone(),
+            table: self.table.clone(),
+            partition,
+            files,
+            columns,
+            filters: self.filters.clone(),
+            bytes_scanned: 0,
+        }
+    }
+}
+
+/// Returns the configuration of `plan` if it is a file scan
+fn file_scan_config(plan: &dyn ExecutionPlan) -> Option<&FileScanConfig> {
+    let plan = plan.as_any();
+    #[cfg(feature = "parquet")]
+    if let Some(exec) = plan.downcast_ref::<ParquetExec>() {
+        return Some(exec.base_config());
+    }
+    if let Some(exec) = plan.downcast_ref::<CsvExec>() {
+        Some(exec.base_config())
+    } else if let Some(exec) = plan.downcast_ref::<NdJsonExec>() {
+        Some(exec.base_config())
+    } else if let Some(exec) = plan.downcast_ref::<AvroExec>() {
+        Some(exec.base_config())
+    } else {
+        plan.downcast_ref::<ArrowExec>()
+            .map(|exec| exec.base_config())
+    }
+}
+
+/// Returns the bytes read by the partition `partition` of the scan
+fn bytes_scanned(metrics: Option<MetricsSet>, partition: usize) -> usize {
+    metrics
+        .iter()
+        .flat_map(|metrics| metrics.iter())
+        .filter(|metric| metric.partition() == Some(partition))
+        .map(|metric| match metric.value() {
+            MetricValue::Count { name, count } if name == "bytes_scanned" => {
+                count.value()
+            }
+            _ => 0,
+        })
+        .sum()
+}
+
+impl DisplayAs for ReadAuditExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "ReadAuditExec: table={}", self.table)
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for ReadAuditExec {
+    fn name(&self) -> &'static str {
+        "ReadAuditExec"
+    }
+
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true]
+    }
+
+    fn benefits_from_input_partitioning(&self) -> Vec<bool> {
+        vec![false]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(ReadAuditExec::new(
+            children[0].clone(),
+            self.user.clone(),
+            self.table.clone(),
+            self.filters.clone(),
+        )))
+    }
+
+    /// Repartitions the scan, so that auditing does not prevent reading
+    /// the files in parallel
+    fn repartitioned(
+        &self,
+        target_partitions: usize,
+        config: &ConfigOptions,
+    ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+        Ok(self
+            .input
+            .repartitioned(target_partitions, config)?
+            .map(|input| {
+                Arc::new(ReadAuditExec::new(
+                    input,
+                    self.user.clone(),
+                    self.table.clone(),
+                    self.filters.clone(),
+                )) as _
+            }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let stream = self.input.execute(partition, context.clone())?;
+        let Some(sink) = context.runtime_env().audit_sink.clone() else {
+            return Ok(stream);
+        };
+        Ok(Box::pin(ReadAuditStream {
+            input: stream,
+            scan: self.input.clone(),
+            event: Some(self.audit_event(partition)),
+            sink,
+        }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        None
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        self.input.statistics()
+    }
+}
+
+/// Records the audit event of the partition once the scan is finished or
+/// dropped
+struct ReadAuditStream {
+    input: SendableRecordBatchStream,
+    /// The table scan, whose metrics tell the bytes read
+    scan: Arc<dyn ExecutionPlan>,
+    /// The event to record, `None` once recorded
+    event: Option<ReadAuditEvent>,
+    sink: Arc<dyn AuditSink>,
+}
+
+impl ReadAuditStream {
+    fn record(&mut self) {
+        if let Some(mut event) = self.event.take() {
+            event.bytes_scanned = bytes_scanned(self.scan.metrics(), event.partition);
+            self.sink.record_read(event);
+        }
+    }
+}
+
+impl Stream for ReadAuditStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let poll = self.input.poll_next_unpin(cx);
+        if let Poll::Ready(None) = poll {
+            self.record();
+        }
+        poll
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl RecordBatchStream for ReadAuditStream {
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+}
+
+impl Drop for ReadAuditStream {
+    fn drop(&mut self) {
+        self.record();
+    }
+}
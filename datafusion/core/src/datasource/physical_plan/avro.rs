@@ -283,6 +283,7 @@ mod tests {
             table_partition_cols: vec![],
             output_ordering: vec![],
             column_hints: None,
+            listing_pruning: None,
         });
         assert_eq!(
             avro_exec
@@ -361,6 +362,7 @@ mod tests {
             table_partition_cols: vec![],
             output_ordering: vec![],
             column_hints: None,
+            listing_pruning: None,
         });
         assert_eq!(
             avro_exec
@@ -438,6 +440,7 @@ mod tests {
             table_partition_cols: vec![Field::new("date", DataType::Utf8, false)],
             output_ordering: vec![],
             column_hints: None,
+            listing_pruning: None,
         });
         assert_eq!(
             avro_exec
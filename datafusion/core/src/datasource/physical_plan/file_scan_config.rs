@@ -22,7 +22,7 @@ use std::{
     borrow::Cow, collections::HashMap, fmt::Debug, marker::PhantomData, sync::Arc, vec,
 };
 
-use super::{get_projected_output_ordering, FileGroupPartitioner};
+use super::{get_projected_output_ordering, FileGroupPartitioner, ListingPruning};
 use crate::datasource::{listing::PartitionedFile, object_store::ObjectStoreUrl};
 use crate::{error::Result, scalar::ScalarValue};
 
@@ -91,6 +91,11 @@ pub struct FileScanConfig {
     pub projection: Option<Vec<usize>>,
     /// used to pass column load hints to underlying implementation
     pub column_hints: Option<Vec<String>>,
+    /// The pruning of the partitions and files listed for the scan, if they
+    /// were listed by a table, reported by the [`PruningMetrics`] of the scan
+    ///
+    /// [`PruningMetrics`]: super::PruningMetrics
+    pub listing_pruning: Option<ListingPruning>,
     /// The maximum number of records to read from this plan. If `None`,
     /// all records after filtering are returned.
     pub limit: Option<usize>,
@@ -789,6 +794,7 @@ mod tests {
             table_partition_cols,
             output_ordering: vec![],
             column_hints: None,
+            listing_pruning: None,
         }
     }
 
@@ -656,6 +656,7 @@ mod tests {
                 table_partition_cols: vec![],
                 output_ordering: vec![],
                 column_hints: None,
+                listing_pruning: None,
             };
             let metrics_set = ExecutionPlanMetricsSet::new();
             let file_stream = FileStream::new(&config, 0, self.opener, &metrics_set)
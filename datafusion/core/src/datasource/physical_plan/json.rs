@@ -535,6 +535,7 @@ mod tests {
                 table_partition_cols: vec![],
                 output_ordering: vec![],
                 column_hints: None,
+                listing_pruning: None,
 
             },
             file_compression_type.to_owned(),
@@ -615,6 +616,7 @@ mod tests {
                 table_partition_cols: vec![],
                 output_ordering: vec![],
                 column_hints: None,
+                listing_pruning: None,
 
             },
             file_compression_type.to_owned(),
@@ -664,6 +666,7 @@ mod tests {
                 table_partition_cols: vec![],
                 output_ordering: vec![],
                 column_hints: None,
+                listing_pruning: None,
 
             },
             file_compression_type.to_owned(),
@@ -718,6 +721,7 @@ mod tests {
                 table_partition_cols: vec![],
                 output_ordering: vec![],
                 column_hints: None,
+                listing_pruning: None,
 
             },
             file_compression_type.to_owned(),
@@ -18,6 +18,7 @@
 //! Execution plans that read file formats
 
 mod arrow_file;
+mod audit;
 mod avro;
 mod csv;
 mod file_groups;
@@ -26,6 +27,7 @@ mod file_stream;
 mod json;
 #[cfg(feature = "parquet")]
 pub mod parquet;
+mod pruning;
 
 pub(crate) use self::csv::plan_to_csv;
 pub(crate) use self::json::plan_to_json;
@@ -33,6 +35,7 @@ pub(crate) use self::json::plan_to_json;
 pub use self::parquet::{ParquetExec, ParquetFileMetrics, ParquetFileReaderFactory};
 
 pub use arrow_file::ArrowExec;
+pub use audit::ReadAuditExec;
 pub use avro::AvroExec;
 pub use csv::{CsvConfig, CsvExec, CsvOpener};
 pub use file_groups::FileGroupPartitioner;
@@ -41,6 +44,7 @@ pub use file_scan_config::{
 };
 pub use file_stream::{FileOpenFuture, FileOpener, FileStream, OnError};
 pub use json::{JsonOpener, NdJsonExec};
+pub use pruning::{ListingPruning, PruningMetrics};
 
 use std::{
     fmt::{Debug, Formatter, Result as FmtResult},
@@ -61,7 +65,9 @@ use crate::{
 };
 
 use arrow::{
-    array::new_null_array,
+    array::{
+        new_null_array, Array, ArrayRef, AsArray, LargeListArray, ListArray, StructArray,
+    },
     compute::{can_cast_types, cast},
     datatypes::{DataType, Schema, SchemaRef},
     record_batch::{RecordBatch, RecordBatchOptions},
@@ -281,7 +287,8 @@ impl SchemaAdapter {
     ///
     /// If the provided `file_schema` contains columns of a different type to the expected
     /// `table_schema`, the method will attempt to cast the array data from the file schema
-    /// to the table schema where possible.
+    /// to the table schema where possible. The struct columns are adapted field by field,
+    /// matched by name, with nulls for the fields missing from the file.
     ///
     /// Returns a [`SchemaMapping`] that can be applied to the output batch
     /// along with an ordered list of columns to project from the file
@@ -296,7 +303,7 @@ impl SchemaAdapter {
             if let Some((table_idx, table_field)) =
                 self.table_schema.fields().find(file_field.name())
             {
-                match can_cast_types(file_field.data_type(), table_field.data_type()) {
+                match can_adapt_types(file_field.data_type(), table_field.data_type()) {
                     true => {
                         field_mappings[table_idx] = Some(projection.len());
                         projection.push(file_idx);
@@ -345,10 +352,12 @@ impl SchemaMapping {
             .iter()
             .zip(&self.field_mappings)
             .map(|(field, file_idx)| match file_idx {
-                Some(batch_idx) => cast(&batch_cols[*batch_idx], field.data_type()),
+                Some(batch_idx) => {
+                    adapt_array(&batch_cols[*batch_idx], field.data_type())
+                }
                 None => Ok(new_null_array(field.data_type(), batch_rows)),
             })
-            .collect::<Result<Vec<_>, _>>()?;
+            .collect::<Result<Vec<_>>>()?;
 
         // Necessary to handle empty batches
         let options = RecordBatchOptions::new().with_row_count(Some(batch.num_rows()));
@@ -359,6 +368,75 @@ impl SchemaMapping {
     }
 }
 
+/// Returns true if the arrays of the type `from` can be adapted to the type
+/// `to` by [`adapt_array`]
+fn can_adapt_types(from: &DataType, to: &DataType) -> bool {
+    match (from, to) {
+        (DataType::Struct(from_fields), DataType::Struct(to_fields)) => to_fields
+            .iter()
+            .all(|to_field| match from_fields.find(to_field.name()) {
+                Some((_, from_field)) => {
+                    can_adapt_types(from_field.data_type(), to_field.data_type())
+                }
+                None => to_field.is_nullable(),
+            }),
+        (DataType::List(from_field), DataType::List(to_field))
+        | (DataType::LargeList(from_field), DataType::LargeList(to_field)) => {
+            can_adapt_types(from_field.data_type(), to_field.data_type())
+        }
+        _ => can_cast_types(from, to),
+    }
+}
+
+/// Adapts `array` to the type `data_type`. The fields of the structs are
+/// matched by name: the fields missing from `data_type` are dropped, and the
+/// ones missing from `array` are filled with nulls. The other types are cast.
+fn adapt_array(array: &ArrayRef, data_type: &DataType) -> Result<ArrayRef> {
+    match (array.data_type(), data_type) {
+        (DataType::Struct(_), DataType::Struct(fields)) => {
+            let array = array.as_struct();
+            let columns = fields
+                .iter()
+                .map(|field| match array.column_by_name(field.name()) {
+                    Some(column) => adapt_array(column, field.data_type()),
+                    None => Ok(new_null_array(field.data_type(), array.len())),
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let nulls = array.nulls().cloned();
+            Ok(Arc::new(StructArray::try_new(
+                fields.clone(),
+                columns,
+                nulls,
+            )?))
+        }
+        (DataType::List(_), DataType::List(field)) => {
+            let array = array.as_list::<i32>();
+            let values = adapt_array(array.values(), field.data_type())?;
+            let offsets = array.offsets().clone();
+            let nulls = array.nulls().cloned();
+            Ok(Arc::new(ListArray::try_new(
+                field.clone(),
+                offsets,
+                values,
+                nulls,
+            )?))
+        }
+        (DataType::LargeList(_), DataType::LargeList(field)) => {
+            let array = array.as_list::<i64>();
+            let values = adapt_array(array.values(), field.data_type())?;
+            let offsets = array.offsets().clone();
+            let nulls = array.nulls().cloned();
+            Ok(Arc::new(LargeListArray::try_new(
+                field.clone(),
+                offsets,
+                values,
+                nulls,
+            )?))
+        }
+        _ => Ok(cast(array, data_type)?),
+    }
+}
+
 /// A single file or part of a file that should be read, along with its schema, statistics
 pub struct FileMeta {
     /// Path for the file (e.g. URL, filesystem path, etc)
@@ -588,13 +666,16 @@ mod tests {
     use super::*;
     use crate::physical_plan::{DefaultDisplay, VerboseDisplay};
 
+    use arrow::buffer::OffsetBuffer;
     use arrow_array::cast::AsArray;
-    use arrow_array::types::{Float32Type, Float64Type, UInt32Type};
+    use arrow_array::types::{
+        Float32Type, Float64Type, Int32Type, Int64Type, UInt32Type,
+    };
     use arrow_array::{
         BinaryArray, BooleanArray, Float32Array, Int32Array, Int64Array, StringArray,
         UInt64Array,
     };
-    use arrow_schema::Field;
+    use arrow_schema::{Field, Fields};
 
     use chrono::Utc;
 
@@ -643,6 +724,88 @@ mod tests {
         assert_eq!(c3.value(1), 7.0_f64);
     }
 
+    #[test]
+    fn schema_mapping_map_nested_batch() {
+        let x = Field::new("x", DataType::Int32, true);
+        let table_element =
+            Fields::from(vec![x.clone(), Field::new("y", DataType::Int32, true)]);
+        let table_schema = Arc::new(Schema::new(vec![
+            Field::new(
+                "s",
+                DataType::Struct(Fields::from(vec![
+                    Field::new("a", DataType::Int64, true),
+                    Field::new("b", DataType::Utf8, true),
+                ])),
+                true,
+            ),
+            Field::new_list("l", Field::new_struct("item", table_element, true), true),
+        ]));
+        let adapter = SchemaAdapter::new(table_schema.clone());
+
+        // the struct of the file has a field `c` missing from the table, and
+        // misses the field `b`, like the elements of the list miss `y`
+        let s = StructArray::from(vec![
+            (
+                Arc::new(Field::new("c", DataType::Boolean, true)),
+                Arc::new(BooleanArray::from(vec![true, false])) as ArrayRef,
+            ),
+            (
+                Arc::new(Field::new("a", DataType::Int32, true)),
+                Arc::new(Int32Array::from(vec![1, 2])) as ArrayRef,
+            ),
+        ]);
+        let element = StructArray::from(vec![(
+            Arc::new(x),
+            Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef,
+        )]);
+        let l = ListArray::new(
+            Arc::new(Field::new("item", element.data_type().clone(), true)),
+            OffsetBuffer::from_lengths([2, 1]),
+            Arc::new(element),
+            None,
+        );
+        let file_schema = Schema::new(vec![
+            Field::new("s", s.data_type().clone(), true),
+            Field::new("l", l.data_type().clone(), true),
+        ]);
+        let (mapping, _) = adapter.map_schema(&file_schema).unwrap();
+        let batch =
+            RecordBatch::try_new(Arc::new(file_schema), vec![Arc::new(s), Arc::new(l)])
+                .unwrap();
+        let mapped_batch = mapping.map_batch(batch).unwrap();
+        assert_eq!(mapped_batch.schema(), table_schema);
+
+        let s = mapped_batch.column(0).as_struct();
+        let a = s.column_by_name("a").unwrap().as_primitive::<Int64Type>();
+        assert_eq!(a.values(), &[1, 2]);
+        assert_eq!(s.column_by_name("b").unwrap().null_count(), 2);
+
+        let l = mapped_batch.column(1).as_list::<i32>();
+        assert_eq!(l.value_offsets(), &[0, 2, 3]);
+        let element = l.values().as_struct();
+        let x = element
+            .column_by_name("x")
+            .unwrap()
+            .as_primitive::<Int32Type>();
+        assert_eq!(x.values(), &[1, 2, 3]);
+        assert_eq!(element.column_by_name("y").unwrap().null_count(), 3);
+
+        // the missing fields must be nullable
+        let file_schema = Schema::new(vec![Field::new(
+            "s",
+            DataType::Struct(Fields::from(vec![Field::new("b", DataType::Utf8, true)])),
+            true,
+        )]);
+        let table_schema = Arc::new(Schema::new(vec![Field::new(
+            "s",
+            DataType::Struct(Fields::from(vec![Field::new("a", DataType::Int64, false)])),
+            true,
+        )]));
+        assert!(SchemaAdapter::new(table_schema)
+            .map_schema(&file_schema)
+            .is_err());
+    }
+
     #[test]
     fn schema_adapter_map_schema_with_projection() {
         let table_schema = Arc::new(Schema::new(vec![
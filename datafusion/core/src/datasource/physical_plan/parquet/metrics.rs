@@ -33,6 +33,10 @@ pub struct ParquetFileMetrics {
     pub row_groups_matched_bloom_filter: Count,
     /// Number of row groups pruned by bloom filters
     pub row_groups_pruned_bloom_filter: Count,
+    /// Number of row groups whose dictionaries were checked and matched
+    pub row_groups_matched_dictionary: Count,
+    /// Number of row groups pruned by dictionaries
+    pub row_groups_pruned_dictionary: Count,
     /// Number of row groups whose statistics were checked and matched
     pub row_groups_matched_statistics: Count,
     /// Number of row groups pruned by statistics
@@ -68,6 +72,14 @@ impl ParquetFileMetrics {
             .with_new_label("filename", filename.to_string())
             .counter("row_groups_pruned_bloom_filter", partition);
 
+        let row_groups_matched_dictionary = MetricBuilder::new(metrics)
+            .with_new_label("filename", filename.to_string())
+            .counter("row_groups_matched_dictionary", partition);
+
+        let row_groups_pruned_dictionary = MetricBuilder::new(metrics)
+            .with_new_label("filename", filename.to_string())
+            .counter("row_groups_pruned_dictionary", partition);
+
         let row_groups_matched_statistics = MetricBuilder::new(metrics)
             .with_new_label("filename", filename.to_string())
             .counter("row_groups_matched_statistics", partition);
@@ -99,6 +111,8 @@ impl ParquetFileMetrics {
             predicate_evaluation_errors,
             row_groups_matched_bloom_filter,
             row_groups_pruned_bloom_filter,
+            row_groups_matched_dictionary,
+            row_groups_pruned_dictionary,
             row_groups_matched_statistics,
             row_groups_pruned_statistics,
             bytes_scanned,
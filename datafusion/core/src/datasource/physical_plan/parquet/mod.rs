@@ -22,14 +22,15 @@ use std::fmt::Debug;
 use std::ops::Range;
 use std::sync::Arc;
 
-use crate::datasource::listing::PartitionedFile;
+use crate::datasource::listing::{PartitionedFile, SelectedRows};
 use crate::datasource::physical_plan::file_stream::{
     FileOpenFuture, FileOpener, FileStream,
 };
 use crate::datasource::physical_plan::{
     parquet::page_filter::PagePruningPredicate, DisplayAs, FileGroupPartitioner,
-    FileMeta, FileScanConfig, SchemaAdapter,
+    FileMeta, FileScanConfig, PruningMetrics, SchemaAdapter,
 };
+use crate::datasource::table_format::DeletedRows;
 use crate::{
     config::{ConfigOptions, TableParquetOptions},
     datasource::listing::ListingTableUrl,
@@ -56,7 +57,7 @@ use log::debug;
 use object_store::buffered::BufWriter;
 use object_store::path::Path;
 use object_store::ObjectStore;
-use parquet::arrow::arrow_reader::ArrowReaderOptions;
+use parquet::arrow::arrow_reader::{ArrowReaderOptions, RowSelection, RowSelector};
 use parquet::arrow::async_reader::{AsyncFileReader, ParquetObjectReader};
 use parquet::arrow::{AsyncArrowWriter, ParquetRecordBatchStreamBuilder, ProjectionMask};
 use parquet::basic::{ConvertedType, LogicalType};
@@ -66,11 +67,13 @@ use tokio::task::JoinSet;
 
 mod metrics;
 mod page_filter;
+mod pruning_pipeline;
 mod row_filter;
 mod row_groups;
 mod statistics;
 
 pub use metrics::ParquetFileMetrics;
+use pruning_pipeline::{PrunedFile, PruningPipeline};
 use crate::datasource::file_format::parquet::find_leaf_ids;
 
 /// Execution plan for scanning one or more Parquet partitions
@@ -81,6 +84,8 @@ pub struct ParquetExec {
     projected_statistics: Statistics,
     /// Execution metrics
     metrics: ExecutionPlanMetricsSet,
+    /// Metrics of the pruning of the files by their metadata
+    pruning_metrics: PruningMetrics,
     /// Optional predicate for row filtering during parquet scan
     predicate: Option<Arc<dyn PhysicalExpr>>,
     /// Optional predicate for pruning row groups
@@ -109,6 +114,7 @@ impl ParquetExec {
         base_config.file_groups, base_config.projection, predicate, base_config.limit);
 
         let metrics = ExecutionPlanMetricsSet::new();
+        let pruning_metrics = PruningMetrics::new(&base_config, &metrics);
         let predicate_creation_errors =
             MetricBuilder::new(&metrics).global_counter("num_predicate_creation_errors");
 
@@ -152,6 +158,7 @@ impl ParquetExec {
             base_config,
             projected_statistics,
             metrics,
+            pruning_metrics,
             predicate,
             pruning_predicate,
             page_pruning_predicate,
@@ -177,6 +184,19 @@ impl ParquetExec {
         self.predicate.as_ref()
     }
 
+    /// Returns this scan with the predicate `predicate` instead of its
+    /// current one, keeping its other settings
+    pub fn with_predicate(&self, predicate: Option<Arc<dyn PhysicalExpr>>) -> Self {
+        let mut exec = Self::new(
+            self.base_config.clone(),
+            predicate,
+            self.metadata_size_hint,
+            self.table_parquet_options.clone(),
+        );
+        exec.parquet_file_reader_factory = self.parquet_file_reader_factory.clone();
+        exec
+    }
+
     /// Optional reference to this parquet scan's pruning predicate
     pub fn pruning_predicate(&self) -> Option<&Arc<PruningPredicate>> {
         self.pruning_predicate.as_ref()
@@ -407,6 +427,7 @@ impl ExecutionPlan for ParquetExec {
             table_schema: self.base_config.file_schema.clone(),
             metadata_size_hint: self.metadata_size_hint,
             metrics: self.metrics.clone(),
+            pruning_metrics: self.pruning_metrics.clone(),
             parquet_file_reader_factory,
             pushdown_filters: self.pushdown_filters(),
             reorder_filters: self.reorder_filters(),
@@ -442,6 +463,7 @@ struct ParquetOpener {
     table_schema: SchemaRef,
     metadata_size_hint: Option<usize>,
     metrics: ExecutionPlanMetricsSet,
+    pruning_metrics: PruningMetrics,
     parquet_file_reader_factory: Arc<dyn ParquetFileReaderFactory>,
     pushdown_filters: bool,
     reorder_filters: bool,
@@ -459,6 +481,39 @@ impl FileOpener for ParquetOpener {
             &self.metrics,
         );
 
+        // the dictionaries are read with a reader of their own, as the reader
+        // of the file is owned by the builder of the stream
+        let dictionary_reader: Option<Box<dyn AsyncFileReader + Send>> = if self
+            .enable_bloom_filter
+            && self
+                .pruning_predicate
+                .as_ref()
+                .is_some_and(|p| !p.literal_columns().is_empty())
+        {
+            let dictionary_file_meta = FileMeta {
+                object_meta: file_meta.object_meta.clone(),
+                range: None,
+                extensions: file_meta.extensions.clone(),
+            };
+            Some(self.parquet_file_reader_factory.create_reader(
+                self.partition_index,
+                dictionary_file_meta,
+                self.metadata_size_hint,
+                &self.metrics,
+            )?)
+        } else {
+            None
+        };
+
+        // the rows deleted by a table format, see `DeletedRows`, or the only
+        // rows selected by a text index, see `SelectedRows`
+        let extensions = file_meta.extensions.clone();
+        let deleted_rows = extensions
+            .clone()
+            .and_then(|extensions| extensions.downcast::<DeletedRows>().ok());
+        let selected_rows =
+            extensions.and_then(|extensions| extensions.downcast::<SelectedRows>().ok());
+
         let reader: Box<dyn AsyncFileReader> =
             self.parquet_file_reader_factory.create_reader(
                 self.partition_index,
@@ -484,6 +539,7 @@ impl FileOpener for ParquetOpener {
         );
         let enable_bloom_filter = self.enable_bloom_filter;
         let limit = self.limit;
+        let pruning_metrics = self.pruning_metrics.clone();
 
         Ok(Box::pin(async move {
             let options = ArrowReaderOptions::new().with_page_index(enable_page_index);
@@ -552,51 +608,45 @@ impl FileOpener for ParquetOpener {
                 };
             };
 
-            // Row group pruning by statistics: attempt to skip entire row_groups
-            // using metadata on the row groups
-            let file_metadata = builder.metadata().clone();
-            let predicate = pruning_predicate.as_ref().map(|p| p.as_ref());
-            let mut row_groups = row_groups::prune_row_groups_by_statistics(
-                &file_schema,
-                builder.parquet_schema(),
-                file_metadata.row_groups(),
-                file_range,
-                predicate,
-                &file_metrics,
-            );
-
-            // Bloom filter pruning: if bloom filters are enabled and then attempt to skip entire row_groups
-            // using bloom filters on the row groups
-            if enable_bloom_filter && !row_groups.is_empty() {
-                if let Some(predicate) = predicate {
-                    row_groups = row_groups::prune_row_groups_by_bloom_filters(
-                        &file_schema,
-                        &mut builder,
-                        &row_groups,
-                        file_metadata.row_groups(),
-                        predicate,
-                        &file_metrics,
-                    )
-                    .await;
-                }
-            }
-
-            // page index pruning: if all data on individual pages can
-            // be ruled using page metadata, rows from other columns
-            // with that range can be skipped as well
-            if enable_page_index && !row_groups.is_empty() {
-                if let Some(p) = page_pruning_predicate {
-                    let pruned = p.prune(
-                        &file_schema,
-                        builder.parquet_schema(),
-                        &row_groups,
-                        file_metadata.as_ref(),
-                        &file_metrics,
-                    )?;
-                    if let Some(row_selection) = pruned {
-                        builder = builder.with_row_selection(row_selection);
-                    }
+            let pruning = PruningPipeline {
+                file_schema: &file_schema,
+                pruning_predicate: pruning_predicate.as_deref(),
+                page_pruning_predicate: page_pruning_predicate.as_deref(),
+                enable_bloom_filter,
+                enable_page_index,
+                file_metrics: &file_metrics,
+                pruning_metrics: &pruning_metrics,
+            };
+            let PrunedFile {
+                row_groups,
+                row_selection,
+            } = pruning
+                .prune(&mut builder, dictionary_reader, file_range)
+                .await?;
+            let metadata = builder.metadata();
+            let file_selection = match (deleted_rows, selected_rows) {
+                (Some(rows), _) => Some(rows_selection(
+                    rows.positions(),
+                    false,
+                    &row_groups,
+                    metadata,
+                )),
+                (None, Some(rows)) => Some(rows_selection(
+                    rows.positions(),
+                    true,
+                    &row_groups,
+                    metadata,
+                )),
+                (None, None) => None,
+            };
+            let row_selection = match (row_selection, file_selection) {
+                (Some(row_selection), Some(file_selection)) => {
+                    Some(row_selection.intersection(&file_selection))
                 }
+                (row_selection, file_selection) => row_selection.or(file_selection),
+            };
+            if let Some(row_selection) = row_selection {
+                builder = builder.with_row_selection(row_selection);
             }
 
             if let Some(limit) = limit {
@@ -621,6 +671,48 @@ impl FileOpener for ParquetOpener {
     }
 }
 
+/// Returns the selection of the rows of the row groups `row_groups` of a
+/// file: the rows at `positions` if `select`, the other rows otherwise.
+/// `positions` are sorted positions of rows in the whole file.
+fn rows_selection(
+    positions: &[u64],
+    select: bool,
+    row_groups: &[usize],
+    metadata: &ParquetMetaData,
+) -> RowSelection {
+    let mut offsets = Vec::with_capacity(metadata.num_row_groups());
+    let mut offset = 0;
+    for row_group in metadata.row_groups() {
+        offsets.push(offset);
+        offset += row_group.num_rows() as u64;
+    }
+
+    // the selectors of the rows at `positions` and of the rows between them
+    let (at, between): (fn(usize) -> RowSelector, fn(usize) -> RowSelector) = if select {
+        (RowSelector::select, RowSelector::skip)
+    } else {
+        (RowSelector::skip, RowSelector::select)
+    };
+    let mut selectors = vec![];
+    for &index in row_groups {
+        let start = offsets[index];
+        let end = start + metadata.row_group(index).num_rows() as u64;
+        let first = positions.partition_point(|position| *position < start);
+        let mut current = start;
+        for &position in positions[first..].iter().take_while(|p| **p < end) {
+            if position > current {
+                selectors.push(between((position - current) as usize));
+            }
+            selectors.push(at(1));
+            current = position + 1;
+        }
+        if end > current {
+            selectors.push(between((end - current) as usize));
+        }
+    }
+    RowSelection::from(selectors)
+}
+
 fn should_enable_page_index(
     enable_page_index: bool,
     page_pruning_predicate: &Option<Arc<PagePruningPredicate>>,
@@ -933,6 +1025,7 @@ mod tests {
                     table_partition_cols: vec![],
                     output_ordering: vec![],
                     column_hints: None,
+                    listing_pruning: None,
                 },
                 predicate,
                 None,
@@ -1591,6 +1684,7 @@ mod tests {
                     table_partition_cols: vec![],
                     output_ordering: vec![],
                     column_hints: None,
+                    listing_pruning: None,
                 },
                 None,
                 None,
@@ -1713,6 +1807,7 @@ mod tests {
                 ],
                 output_ordering: vec![],
                 column_hints: None,
+                listing_pruning: None,
             },
             None,
             None,
@@ -1781,6 +1876,7 @@ mod tests {
                 table_partition_cols: vec![],
                 output_ordering: vec![],
                 column_hints: None,
+                listing_pruning: None,
             },
             None,
             None,
@@ -0,0 +1,185 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`PruningPipeline`] prunes the row groups and the pages of a parquet file
+//! by its metadata
+
+use arrow_schema::Schema;
+use parquet::arrow::arrow_reader::RowSelection;
+use parquet::arrow::async_reader::AsyncFileReader;
+use parquet::arrow::ParquetRecordBatchStreamBuilder;
+use parquet::file::metadata::ParquetMetaData;
+
+use super::page_filter::PagePruningPredicate;
+use super::{row_groups, ParquetFileMetrics};
+use crate::datasource::listing::FileRange;
+use crate::datasource::physical_plan::PruningMetrics;
+use crate::error::Result;
+use crate::physical_optimizer::pruning::PruningPredicate;
+
+/// The pruning of a range of a parquet file by its metadata, one level after
+/// the other:
+///
+/// 1. the row groups of the range by their statistics, then by their bloom
+///    filters and by their dictionaries if enabled
+/// 2. the rows of the kept row groups by the page index, if enabled
+///
+/// The numbers of row groups and rows considered and kept by each level are
+/// added to the [`PruningMetrics`] of the scan, the row groups pruned by each
+/// stage to the [`ParquetFileMetrics`] of the file.
+pub(crate) struct PruningPipeline<'a> {
+    /// The schema of the file
+    pub file_schema: &'a Schema,
+    /// The predicate pruning the row groups
+    pub pruning_predicate: Option<&'a PruningPredicate>,
+    /// The predicate pruning the pages
+    pub page_pruning_predicate: Option<&'a PagePruningPredicate>,
+    /// Whether to prune the row groups by their bloom filters
+    pub enable_bloom_filter: bool,
+    /// Whether to prune the pages by the page index
+    pub enable_page_index: bool,
+    /// The metrics of the file
+    pub file_metrics: &'a ParquetFileMetrics,
+    /// The metrics of the scan
+    pub pruning_metrics: &'a PruningMetrics,
+}
+
+/// The row groups and the rows of a parquet file left to scan by a
+/// [`PruningPipeline`]
+pub(crate) struct PrunedFile {
+    /// The indexes of the row groups to scan
+    pub row_groups: Vec<usize>,
+    /// The rows of the row groups to scan, all of them if `None`
+    pub row_selection: Option<RowSelection>,
+}
+
+impl PruningPipeline<'_> {
+    /// Prunes the row groups of the range `file_range` of the file read by
+    /// `builder`, whose dictionaries are read with `dictionary_reader`
+    pub(crate) async fn prune(
+        &self,
+        builder: &mut ParquetRecordBatchStreamBuilder<Box<dyn AsyncFileReader>>,
+        dictionary_reader: Option<Box<dyn AsyncFileReader + Send>>,
+        file_range: Option<FileRange>,
+    ) -> Result<PrunedFile> {
+        let file_metadata = builder.metadata().clone();
+        let groups = file_metadata.row_groups();
+        let parquet_schema = file_metadata.file_metadata().schema_descr();
+        let predicate = self.pruning_predicate;
+
+        let considered = row_groups::prune_row_groups_by_statistics(
+            self.file_schema,
+            parquet_schema,
+            groups,
+            file_range.clone(),
+            None,
+            self.file_metrics,
+        );
+        self.pruning_metrics
+            .row_groups_considered
+            .add(considered.len());
+        self.pruning_metrics
+            .rows_considered
+            .add(row_count(&considered, &file_metadata));
+
+        // Row group pruning by statistics: attempt to skip entire row_groups
+        // using metadata on the row groups
+        let mut row_groups = match predicate {
+            Some(_) => row_groups::prune_row_groups_by_statistics(
+                self.file_schema,
+                parquet_schema,
+                groups,
+                file_range,
+                predicate,
+                self.file_metrics,
+            ),
+            None => considered,
+        };
+
+        // Bloom filter pruning: if bloom filters are enabled and then attempt to skip entire row_groups
+        // using bloom filters on the row groups
+        if self.enable_bloom_filter && !row_groups.is_empty() {
+            if let Some(predicate) = predicate {
+                row_groups = row_groups::prune_row_groups_by_bloom_filters(
+                    self.file_schema,
+                    builder,
+                    &row_groups,
+                    groups,
+                    predicate,
+                    self.file_metrics,
+                )
+                .await;
+            }
+        }
+
+        // Dictionary pruning: skip the row groups whose columns are dictionary
+        // encoded without any of the values the predicate requires
+        if let (Some(mut reader), Some(predicate)) = (dictionary_reader, predicate) {
+            if !row_groups.is_empty() {
+                row_groups = row_groups::prune_row_groups_by_dictionaries(
+                    self.file_schema,
+                    parquet_schema,
+                    reader.as_mut(),
+                    &row_groups,
+                    groups,
+                    predicate,
+                    self.file_metrics,
+                )
+                .await;
+            }
+        }
+        self.pruning_metrics.row_groups_kept.add(row_groups.len());
+
+        // page index pruning: if all data on individual pages can
+        // be ruled using page metadata, rows from other columns
+        // with that range can be skipped as well
+        let mut row_selection = None;
+        if self.enable_page_index && !row_groups.is_empty() {
+            if let Some(p) = self.page_pruning_predicate {
+                row_selection = p.prune(
+                    self.file_schema,
+                    parquet_schema,
+                    &row_groups,
+                    file_metadata.as_ref(),
+                    self.file_metrics,
+                )?;
+            }
+        }
+        let rows_selected = match &row_selection {
+            Some(row_selection) => row_selection
+                .iter()
+                .filter(|selector| !selector.skip)
+                .map(|selector| selector.row_count)
+                .sum(),
+            None => row_count(&row_groups, &file_metadata),
+        };
+        self.pruning_metrics.rows_selected.add(rows_selected);
+
+        Ok(PrunedFile {
+            row_groups,
+            row_selection,
+        })
+    }
+}
+
+/// Returns the number of rows of the row groups `row_groups` of a file
+fn row_count(row_groups: &[usize], file_metadata: &ParquetMetaData) -> usize {
+    row_groups
+        .iter()
+        .map(|index| file_metadata.row_group(*index).num_rows() as usize)
+        .sum()
+}
@@ -15,24 +15,32 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use arrow::{array::ArrayRef, datatypes::Schema};
+use arrow::{
+    array::ArrayRef,
+    datatypes::{DataType, Schema},
+};
 use arrow_array::BooleanArray;
 use arrow_schema::FieldRef;
-use datafusion_common::{Column, ScalarValue};
-use parquet::basic::Type;
+use bytes::{Buf, Bytes};
+use datafusion_common::{Column, Result, ScalarValue};
+use parquet::basic::{Encoding, PageType, Type};
+use parquet::column::page::{Page, PageReader};
 use parquet::data_type::Decimal;
 use parquet::file::metadata::ColumnChunkMetaData;
-use parquet::schema::types::SchemaDescriptor;
+use parquet::file::reader::{ChunkReader, Length};
+use parquet::file::serialized_reader::SerializedPageReader;
+use parquet::schema::types::{ColumnDescriptor, SchemaDescriptor};
 use parquet::{
     arrow::{async_reader::AsyncFileReader, ParquetRecordBatchStreamBuilder},
     bloom_filter::Sbbf,
     file::metadata::RowGroupMetaData,
 };
 use std::collections::{HashMap, HashSet};
+use std::sync::Arc;
 
 use crate::datasource::listing::FileRange;
 use crate::datasource::physical_plan::parquet::statistics::{
-    max_statistics, min_statistics, parquet_column,
+    max_statistics, min_statistics, parquet_leaf_column,
 };
 use crate::physical_optimizer::pruning::{PruningPredicate, PruningStatistics};
 
@@ -127,9 +135,9 @@ pub(crate) async fn prune_row_groups_by_bloom_filters<
         let literal_columns = predicate.literal_columns();
         let mut column_sbbf = HashMap::with_capacity(literal_columns.len());
 
-        for column_name in literal_columns {
+        for column in literal_columns {
             let Some((column_idx, _field)) =
-                parquet_column(builder.parquet_schema(), arrow_schema, &column_name)
+                parquet_leaf_column(builder.parquet_schema(), arrow_schema, &column)
             else {
                 continue;
             };
@@ -149,7 +157,7 @@ pub(crate) async fn prune_row_groups_by_bloom_filters<
             let physical_type =
                 builder.parquet_schema().column(column_idx).physical_type();
 
-            column_sbbf.insert(column_name.to_string(), (bf, physical_type));
+            column_sbbf.insert(column, (bf, physical_type));
         }
 
         let stats = BloomFilterStatistics { column_sbbf };
@@ -178,8 +186,8 @@ pub(crate) async fn prune_row_groups_by_bloom_filters<
 
 /// Implements `PruningStatistics` for Parquet Split Block Bloom Filters (SBBF)
 struct BloomFilterStatistics {
-    /// Maps column name to the parquet bloom filter and parquet physical type
-    column_sbbf: HashMap<String, (Sbbf, Type)>,
+    /// Maps column to the parquet bloom filter and parquet physical type
+    column_sbbf: HashMap<Column, (Sbbf, Type)>,
 }
 
 impl PruningStatistics for BloomFilterStatistics {
@@ -213,7 +221,7 @@ impl PruningStatistics for BloomFilterStatistics {
         column: &Column,
         values: &HashSet<ScalarValue>,
     ) -> Option<BooleanArray> {
-        let (sbbf, parquet_type) = self.column_sbbf.get(column.name.as_str())?;
+        let (sbbf, parquet_type) = self.column_sbbf.get(column)?;
 
         // Bloom filters are probabilistic data structures that can return false
         // positives (i.e. it might return true even if the value is not
@@ -299,6 +307,319 @@ impl PruningStatistics for BloomFilterStatistics {
     }
 }
 
+/// Prune row groups by the dictionaries of their columns
+///
+/// Returns a vector of indexes into `groups` which should be scanned, as
+/// [`prune_row_groups_by_bloom_filters`] does.
+///
+/// The values of a column chunk whose data pages are all dictionary encoded
+/// are among the values of its dictionary page, which is read with `reader`
+/// for the columns compared with literals in the predicate, including the
+/// leaves of structs.
+pub(crate) async fn prune_row_groups_by_dictionaries(
+    arrow_schema: &Schema,
+    parquet_schema: &SchemaDescriptor,
+    reader: &mut (dyn AsyncFileReader + Send),
+    row_groups: &[usize],
+    groups: &[RowGroupMetaData],
+    predicate: &PruningPredicate,
+    metrics: &ParquetFileMetrics,
+) -> Vec<usize> {
+    let literal_columns = predicate.literal_columns();
+    let mut filtered = Vec::with_capacity(groups.len());
+    for idx in row_groups {
+        let mut column_dictionaries = HashMap::with_capacity(literal_columns.len());
+        for column in &literal_columns {
+            let Some((column_idx, field)) =
+                parquet_leaf_column(parquet_schema, arrow_schema, column)
+            else {
+                continue;
+            };
+
+            let chunk = groups[*idx].column(column_idx);
+            match read_dictionary(reader, chunk).await {
+                Ok(Some(values)) => {
+                    column_dictionaries
+                        .insert(column.clone(), (values, field.data_type().clone()));
+                }
+                Ok(None) => {} // not all the values are in the dictionary
+                Err(e) => {
+                    log::debug!("Ignoring error reading dictionary: {e}");
+                    metrics.predicate_evaluation_errors.add(1);
+                }
+            }
+        }
+
+        let stats = DictionaryStatistics {
+            column_dictionaries,
+        };
+
+        // Can this group be pruned?
+        let prune_group = match predicate.prune(&stats) {
+            Ok(values) => !values[0],
+            Err(e) => {
+                log::debug!("Error evaluating row group predicate on dictionary: {e}");
+                metrics.predicate_evaluation_errors.add(1);
+                false
+            }
+        };
+
+        if prune_group {
+            metrics.row_groups_pruned_dictionary.add(1);
+        } else {
+            if !stats.column_dictionaries.is_empty() {
+                metrics.row_groups_matched_dictionary.add(1);
+            }
+            filtered.push(*idx);
+        }
+    }
+    filtered
+}
+
+/// Reads the values of the dictionary page of `column`, in their plain
+/// encoding, if all its data pages are dictionary encoded
+///
+/// The writer may have fallen back to plain encoding for some data pages.
+/// Without the encodings of the pages in the metadata, which the arrow writer
+/// does not record, the whole column chunk is read to check the encodings of
+/// its data pages.
+async fn read_dictionary(
+    reader: &mut dyn AsyncFileReader,
+    column: &ColumnChunkMetaData,
+) -> Result<Option<HashSet<Bytes>>> {
+    let Some(dictionary_offset) = column.dictionary_page_offset() else {
+        return Ok(None);
+    };
+    let data_offset = column.data_page_offset();
+    if dictionary_offset < 0 || data_offset <= dictionary_offset {
+        return Ok(None);
+    }
+
+    let end = match column.page_encoding_stats() {
+        Some(encoding_stats) => {
+            let all_dictionary_encoded = encoding_stats.iter().all(|stats| {
+                !matches!(
+                    stats.page_type,
+                    PageType::DATA_PAGE | PageType::DATA_PAGE_V2
+                ) || is_dictionary_encoding(stats.encoding)
+            });
+            if !all_dictionary_encoded {
+                return Ok(None);
+            }
+            // the dictionary page is before the first data page
+            data_offset
+        }
+        None => dictionary_offset + column.compressed_size(),
+    };
+    let bytes = reader
+        .get_bytes(dictionary_offset as usize..end as usize)
+        .await?;
+    let chunk = DictionaryChunk {
+        offset: dictionary_offset as u64,
+        bytes,
+    };
+    let mut pages = SerializedPageReader::new(
+        Arc::new(chunk),
+        column,
+        column.num_values() as usize,
+        None,
+    )?;
+    let values = match pages.get_next_page()? {
+        Some(Page::DictionaryPage {
+            buf,
+            num_values,
+            encoding: Encoding::PLAIN | Encoding::PLAIN_DICTIONARY,
+            ..
+        }) => decode_plain(&buf, num_values as usize, column.column_descr()),
+        _ => return Ok(None),
+    };
+
+    if column.page_encoding_stats().is_none() {
+        while let Some(page) = pages.get_next_page()? {
+            if !is_dictionary_encoding(page.encoding()) {
+                return Ok(None);
+            }
+        }
+    }
+    Ok(values)
+}
+
+fn is_dictionary_encoding(encoding: Encoding) -> bool {
+    matches!(
+        encoding,
+        Encoding::PLAIN_DICTIONARY | Encoding::RLE_DICTIONARY
+    )
+}
+
+/// The bytes at `offset` in a parquet file of the dictionary page, or of all
+/// the pages, of a column chunk, read as the [`ChunkReader`] of the chunk
+struct DictionaryChunk {
+    offset: u64,
+    bytes: Bytes,
+}
+
+impl DictionaryChunk {
+    fn slice(&self, start: u64, length: usize) -> parquet::errors::Result<Bytes> {
+        let start = start.checked_sub(self.offset).map(|start| start as usize);
+        match start {
+            Some(start) if start + length <= self.bytes.len() => {
+                Ok(self.bytes.slice(start..start + length))
+            }
+            _ => Err(parquet::errors::ParquetError::EOF(
+                "Read outside of the pages read".to_string(),
+            )),
+        }
+    }
+}
+
+impl Length for DictionaryChunk {
+    fn len(&self) -> u64 {
+        self.offset + self.bytes.len() as u64
+    }
+}
+
+impl ChunkReader for DictionaryChunk {
+    type T = bytes::buf::Reader<Bytes>;
+
+    fn get_read(&self, start: u64) -> parquet::errors::Result<Self::T> {
+        let length = self.len().saturating_sub(start) as usize;
+        Ok(self.slice(start, length)?.reader())
+    }
+
+    fn get_bytes(&self, start: u64, length: usize) -> parquet::errors::Result<Bytes> {
+        self.slice(start, length)
+    }
+}
+
+/// Decodes the `num_values` plain encoded values of `buf`, values of the
+/// column `descr`, into their bytes. Returns `None` for the types whose
+/// plain encoding isn't a sequence of bytes per value.
+fn decode_plain(
+    buf: &Bytes,
+    num_values: usize,
+    descr: &ColumnDescriptor,
+) -> Option<HashSet<Bytes>> {
+    let width = match descr.physical_type() {
+        Type::BYTE_ARRAY => None,
+        Type::INT32 | Type::FLOAT => Some(4),
+        Type::INT64 | Type::DOUBLE => Some(8),
+        Type::INT96 => Some(12),
+        Type::FIXED_LEN_BYTE_ARRAY => Some(descr.type_length() as usize),
+        Type::BOOLEAN => return None,
+    };
+
+    let mut values = HashSet::with_capacity(num_values);
+    let mut offset = 0;
+    for _ in 0..num_values {
+        let length = match width {
+            Some(width) => width,
+            // byte arrays are prefixed by their length
+            None => {
+                let prefix = buf.get(offset..offset + 4)?;
+                offset += 4;
+                u32::from_le_bytes(prefix.try_into().ok()?) as usize
+            }
+        };
+        if offset + length > buf.len() {
+            return None;
+        }
+        values.insert(buf.slice(offset..offset + length));
+        offset += length;
+    }
+    Some(values)
+}
+
+/// Returns the plain encoding of `value`, a value of a column of type
+/// `data_type`, if it is one of the types whose values are equal if and only
+/// if their encodings are. Floating point numbers are not, as `0.0 = -0.0`.
+fn plain_encoding(value: &ScalarValue, data_type: &DataType) -> Option<Vec<u8>> {
+    let value = match value {
+        ScalarValue::Dictionary(_, value) => value.as_ref(),
+        value => value,
+    };
+    let data_type = match data_type {
+        DataType::Dictionary(_, value_type) => value_type.as_ref(),
+        data_type => data_type,
+    };
+    if value.data_type() != *data_type {
+        return None;
+    }
+
+    let bytes = match value {
+        ScalarValue::Utf8(Some(v)) | ScalarValue::LargeUtf8(Some(v)) => {
+            v.as_bytes().to_vec()
+        }
+        ScalarValue::Binary(Some(v))
+        | ScalarValue::LargeBinary(Some(v))
+        | ScalarValue::FixedSizeBinary(_, Some(v)) => v.clone(),
+        // the integers of at most 32 bits are stored as INT32
+        ScalarValue::Int8(Some(v)) => (*v as i32).to_le_bytes().to_vec(),
+        ScalarValue::Int16(Some(v)) => (*v as i32).to_le_bytes().to_vec(),
+        ScalarValue::Int32(Some(v)) | ScalarValue::Date32(Some(v)) => {
+            v.to_le_bytes().to_vec()
+        }
+        ScalarValue::UInt8(Some(v)) => (*v as i32).to_le_bytes().to_vec(),
+        ScalarValue::UInt16(Some(v)) => (*v as i32).to_le_bytes().to_vec(),
+        ScalarValue::UInt32(Some(v)) => v.to_le_bytes().to_vec(),
+        ScalarValue::Int64(Some(v)) => v.to_le_bytes().to_vec(),
+        ScalarValue::UInt64(Some(v)) => v.to_le_bytes().to_vec(),
+        _ => return None,
+    };
+    Some(bytes)
+}
+
+/// Implements `PruningStatistics` for the dictionaries of column chunks
+struct DictionaryStatistics {
+    /// Maps column to the plain encoded values of its dictionary, and the
+    /// type of the column
+    column_dictionaries: HashMap<Column, (HashSet<Bytes>, DataType)>,
+}
+
+impl PruningStatistics for DictionaryStatistics {
+    fn min_values(&self, _column: &Column) -> Option<ArrayRef> {
+        None
+    }
+
+    fn max_values(&self, _column: &Column) -> Option<ArrayRef> {
+        None
+    }
+
+    fn num_containers(&self) -> usize {
+        1
+    }
+
+    fn null_counts(&self, _column: &Column) -> Option<ArrayRef> {
+        None
+    }
+
+    fn row_counts(&self, _column: &Column) -> Option<ArrayRef> {
+        None
+    }
+
+    /// Unlike bloom filters, dictionaries tell both whether the column
+    /// contains none of `values` and whether it contains only `values`
+    fn contained(
+        &self,
+        column: &Column,
+        values: &HashSet<ScalarValue>,
+    ) -> Option<BooleanArray> {
+        let (dictionary, data_type) = self.column_dictionaries.get(column)?;
+        let values = values
+            .iter()
+            .map(|value| plain_encoding(value, data_type))
+            .collect::<Option<HashSet<_>>>()?;
+
+        let contains = if dictionary.iter().all(|v| values.contains(v.as_ref())) {
+            Some(true)
+        } else if values.iter().all(|v| !dictionary.contains(v.as_slice())) {
+            Some(false)
+        } else {
+            None
+        };
+        Some(BooleanArray::from(vec![contains]))
+    }
+}
+
 /// Wraps [`RowGroupMetaData`] in a way that implements [`PruningStatistics`]
 ///
 /// Note: This should be implemented for an array of [`RowGroupMetaData`] instead
@@ -310,21 +631,22 @@ struct RowGroupPruningStatistics<'a> {
 }
 
 impl<'a> RowGroupPruningStatistics<'a> {
-    /// Lookups up the parquet column by name
-    fn column(&self, name: &str) -> Option<(&ColumnChunkMetaData, &FieldRef)> {
-        let (idx, field) = parquet_column(self.parquet_schema, self.arrow_schema, name)?;
+    /// Lookups up the parquet leaf column of `column`
+    fn column(&self, column: &Column) -> Option<(&ColumnChunkMetaData, &FieldRef)> {
+        let (idx, field) =
+            parquet_leaf_column(self.parquet_schema, self.arrow_schema, column)?;
         Some((self.row_group_metadata.column(idx), field))
     }
 }
 
 impl<'a> PruningStatistics for RowGroupPruningStatistics<'a> {
     fn min_values(&self, column: &Column) -> Option<ArrayRef> {
-        let (column, field) = self.column(&column.name)?;
+        let (column, field) = self.column(column)?;
         min_statistics(field.data_type(), std::iter::once(column.statistics())).ok()
     }
 
     fn max_values(&self, column: &Column) -> Option<ArrayRef> {
-        let (column, field) = self.column(&column.name)?;
+        let (column, field) = self.column(column)?;
         max_statistics(field.data_type(), std::iter::once(column.statistics())).ok()
     }
 
@@ -333,13 +655,13 @@ impl<'a> PruningStatistics for RowGroupPruningStatistics<'a> {
     }
 
     fn null_counts(&self, column: &Column) -> Option<ArrayRef> {
-        let (c, _) = self.column(&column.name)?;
+        let (c, _) = self.column(column)?;
         let scalar = ScalarValue::UInt64(Some(c.statistics()?.null_count()));
         scalar.to_array().ok()
     }
 
     fn row_counts(&self, column: &Column) -> Option<ArrayRef> {
-        let (c, _) = self.column(&column.name)?;
+        let (c, _) = self.column(column)?;
         let scalar = ScalarValue::UInt64(Some(c.num_values() as u64));
         scalar.to_array().ok()
     }
@@ -1340,4 +1662,113 @@ mod tests {
 
         Ok(pruned_row_group)
     }
+
+    /// A parquet file with a struct column `s` with a string leaf `x`, with a
+    /// row group per list of values of `x`
+    fn nested_parquet_file(
+        row_groups: &[&[&str]],
+        bloom_filter: bool,
+    ) -> (Schema, Bytes) {
+        use arrow::array::{StringArray, StructArray};
+        use arrow::record_batch::RecordBatch;
+        use parquet::arrow::ArrowWriter;
+        use parquet::file::properties::WriterProperties;
+
+        let leaf = Arc::new(Field::new("x", DataType::Utf8, false));
+        let schema = Schema::new(vec![Field::new(
+            "s",
+            DataType::Struct(vec![leaf.clone()].into()),
+            false,
+        )]);
+        let props = WriterProperties::builder()
+            .set_bloom_filter_enabled(bloom_filter)
+            .build();
+        let mut data = vec![];
+        let mut writer =
+            ArrowWriter::try_new(&mut data, Arc::new(schema.clone()), Some(props))
+                .unwrap();
+        for values in row_groups {
+            let s = StructArray::from(vec![(
+                leaf.clone(),
+                Arc::new(StringArray::from(values.to_vec())) as ArrayRef,
+            )]);
+            let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![Arc::new(s)])
+                .unwrap();
+            writer.write(&batch).unwrap();
+            // a row group per batch
+            writer.flush().unwrap();
+        }
+        writer.close().unwrap();
+        (schema, Bytes::from(data))
+    }
+
+    fn nested_pruning_predicate(schema: &Schema, negated: bool) -> PruningPredicate {
+        use crate::functions::core::expr_fn::get_field;
+
+        let expr =
+            get_field(col("s"), lit("x")).in_list(vec![lit("b"), lit("d")], negated);
+        let expr = logical2physical(&expr, schema);
+        PruningPredicate::try_new(expr, Arc::new(schema.clone())).unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_row_group_bloom_filter_pruning_nested_in_list() {
+        let (schema, data) =
+            nested_parquet_file(&[&["a", "b"], &["c", "d"], &["a", "c"]], true);
+        let pruning_predicate = nested_pruning_predicate(&schema, false);
+        let pruned_row_groups = test_row_group_bloom_filter_pruning_predicate(
+            "nested.parquet",
+            data,
+            &pruning_predicate,
+            &[0, 1, 2],
+        )
+        .await
+        .unwrap();
+        assert_eq!(pruned_row_groups, vec![0, 1]);
+    }
+
+    #[tokio::test]
+    async fn test_row_group_dictionary_pruning_nested_in_list() {
+        use parquet::file::reader::{FileReader, SerializedFileReader};
+
+        let (schema, data) =
+            nested_parquet_file(&[&["a", "b"], &["c", "d"], &["a", "c"], &["b"]], false);
+        let metadata = SerializedFileReader::new(data.clone())
+            .unwrap()
+            .metadata()
+            .clone();
+        let parquet_schema = metadata.file_metadata().schema_descr();
+        let mut reader = std::io::Cursor::new(data.to_vec());
+
+        // s['x'] IN ('b', 'd') prunes the row groups without b nor d
+        let pruning_predicate = nested_pruning_predicate(&schema, false);
+        let metrics = parquet_file_metrics();
+        let pruned_row_groups = prune_row_groups_by_dictionaries(
+            &schema,
+            parquet_schema,
+            &mut reader,
+            &[0, 1, 2, 3],
+            metadata.row_groups(),
+            &pruning_predicate,
+            &metrics,
+        )
+        .await;
+        assert_eq!(pruned_row_groups, vec![0, 1, 3]);
+        assert_eq!(metrics.row_groups_pruned_dictionary.value(), 1);
+        assert_eq!(metrics.row_groups_matched_dictionary.value(), 3);
+
+        // s['x'] NOT IN ('b', 'd') prunes the row groups with only b or d
+        let pruning_predicate = nested_pruning_predicate(&schema, true);
+        let pruned_row_groups = prune_row_groups_by_dictionaries(
+            &schema,
+            parquet_schema,
+            &mut reader,
+            &[0, 1, 2, 3],
+            metadata.row_groups(),
+            &pruning_predicate,
+            &parquet_file_metrics(),
+        )
+        .await;
+        assert_eq!(pruned_row_groups, vec![0, 1, 2]);
+    }
 }
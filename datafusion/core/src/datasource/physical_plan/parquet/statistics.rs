@@ -19,10 +19,12 @@
 
 // TODO: potentially move this to arrow-rs: https://github.com/apache/arrow-rs/issues/4328
 
+use crate::physical_optimizer::pruning::nested_leaf_path;
+
 use arrow::{array::ArrayRef, datatypes::DataType};
 use arrow_array::new_empty_array;
 use arrow_schema::{FieldRef, Schema};
-use datafusion_common::{Result, ScalarValue};
+use datafusion_common::{Column, Result, ScalarValue};
 use parquet::file::statistics::Statistics as ParquetStatistics;
 use parquet::schema::types::SchemaDescriptor;
 
@@ -175,6 +177,49 @@ pub(crate) fn parquet_column<'a>(
     Some((parquet_idx, field))
 }
 
+/// Lookups up the parquet leaf column of `column`, by name as [`parquet_column`]
+/// does for a top level column, or by its path for a leaf nested in structs,
+/// which the pruning predicates name with [`nested_leaf_column`]
+///
+/// Returns the parquet column index and the arrow field of the leaf
+///
+/// [`nested_leaf_column`]: crate::physical_optimizer::pruning::nested_leaf_column
+pub(crate) fn parquet_leaf_column<'a>(
+    parquet_schema: &SchemaDescriptor,
+    arrow_schema: &'a Schema,
+    column: &Column,
+) -> Option<(usize, &'a FieldRef)> {
+    let Some(leaf_path) = nested_leaf_path(column) else {
+        return parquet_column(parquet_schema, arrow_schema, &column.name);
+    };
+
+    let mut parts = leaf_path.iter();
+    let (root_idx, mut field) = arrow_schema.fields.find(parts.next()?)?;
+    let mut path = vec![field.name().as_str()];
+    for part in parts {
+        let DataType::Struct(children) = field.data_type() else {
+            return None;
+        };
+        field = children.find(part)?.1;
+        path.push(field.name().as_str());
+    }
+    if path.len() < 2 || field.data_type().is_nested() {
+        return None;
+    }
+
+    let parquet_idx = (0..parquet_schema.num_columns()).find(|x| {
+        parquet_schema.get_column_root_idx(*x) == root_idx
+            && parquet_schema
+                .column(*x)
+                .path()
+                .parts()
+                .iter()
+                .map(String::as_str)
+                .eq(path.iter().copied())
+    })?;
+    Some((parquet_idx, field))
+}
+
 /// Extracts the min statistics from an iterator of [`ParquetStatistics`] to an [`ArrayRef`]
 pub(crate) fn min_statistics<'a, I: Iterator<Item = Option<&'a ParquetStatistics>>>(
     data_type: &DataType,
@@ -213,6 +258,7 @@ fn collect_scalars<I: Iterator<Item = Option<ScalarValue>>>(
 #[cfg(test)]
 mod test {
     use super::*;
+    use crate::physical_optimizer::pruning::nested_leaf_column;
     use arrow_array::{
         new_null_array, Array, BinaryArray, BooleanArray, Decimal128Array, Float32Array,
         Float64Array, Int32Array, Int64Array, RecordBatch, StringArray, StructArray,
@@ -551,6 +597,38 @@ mod test {
         let (idx, _) = parquet_column(parquet_schema, &schema, "int_col").unwrap();
         assert_eq!(idx, 2);
 
+        // the leaves of the struct column are looked up by their path
+        let leaf_column = |path: &[&str]| {
+            nested_leaf_column(&path.iter().map(|p| p.to_string()).collect::<Vec<_>>())
+        };
+        let (leaf_idx, leaf) = parquet_leaf_column(
+            parquet_schema,
+            &schema,
+            &leaf_column(&["struct_col", "int_col"]),
+        )
+        .unwrap();
+        assert_eq!(leaf_idx, 1);
+        assert_eq!(leaf.data_type(), &DataType::Int32);
+        assert!(parquet_leaf_column(
+            parquet_schema,
+            &schema,
+            &Column::from_name("struct_col")
+        )
+        .is_none());
+        assert!(parquet_leaf_column(
+            parquet_schema,
+            &schema,
+            &leaf_column(&["struct_col", "x"])
+        )
+        .is_none());
+        // a top level column is never a leaf of a struct
+        assert!(parquet_leaf_column(
+            parquet_schema,
+            &schema,
+            &Column::from_name("struct_col.int_col")
+        )
+        .is_none());
+
         let row_groups = metadata.row_groups();
         let iter = row_groups.iter().map(|x| x.column(idx).statistics());
 
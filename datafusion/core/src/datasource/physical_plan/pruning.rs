@@ -0,0 +1,112 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! The report of the pruning of a file scan by the metadata of its files
+
+use std::collections::HashSet;
+
+use super::FileScanConfig;
+use crate::physical_plan::metrics::{Count, ExecutionPlanMetricsSet, MetricBuilder};
+
+/// The numbers of the partitions and files of a table considered and kept
+/// when listing the files of a scan, before its execution
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct ListingPruning {
+    /// Number of partitions listed
+    pub partitions_considered: usize,
+    /// Number of partitions whose values may match the filters
+    pub partitions_kept: usize,
+    /// Number of files listed in the kept partitions
+    pub files_considered: usize,
+}
+
+/// The metrics of the pruning of a file scan by the metadata of its files,
+/// one level after the other:
+///
+/// 1. the partitions of the table by their values, and the files of the kept
+///    partitions by the sample or the limit of the scan, when listing the table,
+///    see [`ListingPruning`]
+/// 2. the row groups of the kept files by their statistics, bloom filters
+///    and dictionaries
+/// 3. the rows of the kept row groups by the page index
+///
+/// Each level reports the numbers of items it considered and kept, so that
+/// `EXPLAIN ANALYZE` shows at which level a filter didn't prune. The metrics
+/// of the row groups and rows are only reported by the [`ParquetExec`], the
+/// metrics of the levels of each stage by the [`ParquetFileMetrics`].
+///
+/// [`ParquetExec`]: super::ParquetExec
+/// [`ParquetFileMetrics`]: super::ParquetFileMetrics
+#[derive(Debug, Clone)]
+pub struct PruningMetrics {
+    /// Number of partitions listed
+    pub partitions_considered: Count,
+    /// Number of partitions whose values may match the filters
+    pub partitions_kept: Count,
+    /// Number of files listed in the kept partitions
+    pub files_considered: Count,
+    /// Number of files scanned
+    pub files_kept: Count,
+    /// Number of row groups in the ranges of the scanned files
+    pub row_groups_considered: Count,
+    /// Number of row groups whose metadata may match the filters
+    pub row_groups_kept: Count,
+    /// Number of rows of the kept row groups
+    pub rows_considered: Count,
+    /// Number of rows of the pages whose index may match the filters
+    pub rows_selected: Count,
+}
+
+impl PruningMetrics {
+    /// Create the metrics of the scan of `config`, with the numbers of the
+    /// partitions and files considered and kept when listing them
+    pub fn new(config: &FileScanConfig, metrics: &ExecutionPlanMetricsSet) -> Self {
+        let counter = |name| MetricBuilder::new(metrics).global_counter(name);
+        let pruning_metrics = Self {
+            partitions_considered: counter("partitions_considered"),
+            partitions_kept: counter("partitions_kept"),
+            files_considered: counter("files_considered"),
+            files_kept: counter("files_kept"),
+            row_groups_considered: counter("row_groups_considered"),
+            row_groups_kept: counter("row_groups_kept"),
+            rows_considered: counter("rows_considered"),
+            rows_selected: counter("rows_selected"),
+        };
+
+        // The files may be split in several ranges
+        let files_kept = config
+            .file_groups
+            .iter()
+            .flatten()
+            .map(|file| &file.object_meta.location)
+            .collect::<HashSet<_>>()
+            .len();
+        let listing = config.listing_pruning.unwrap_or(ListingPruning {
+            files_considered: files_kept,
+            ..Default::default()
+        });
+        pruning_metrics
+            .partitions_considered
+            .add(listing.partitions_considered);
+        pruning_metrics.partitions_kept.add(listing.partitions_kept);
+        pruning_metrics
+            .files_considered
+            .add(listing.files_considered);
+        pruning_metrics.files_kept.add(files_kept);
+        pruning_metrics
+    }
+}
@@ -22,10 +22,11 @@ use std::sync::Arc;
 
 use async_trait::async_trait;
 use datafusion_common::{not_impl_err, Constraints, Statistics};
-use datafusion_expr::{CreateExternalTable, LogicalPlan};
+use datafusion_expr::{CreateExternalTable, LogicalPlan, TableSample, TableVersion};
 pub use datafusion_expr::{TableProviderFilterPushDown, TableType};
 
 use crate::arrow::datatypes::SchemaRef;
+use crate::datasource::changes::TableChanges;
 use crate::datasource::listing_table_factory::ListingTableFactory;
 use crate::datasource::stream::StreamTableFactory;
 use crate::error::Result;
@@ -158,6 +159,116 @@ pub trait TableProvider: Sync + Send {
         limit: Option<usize>,
     ) -> Result<Arc<dyn ExecutionPlan>>;
 
+    /// Create an [`ExecutionPlan`] for scanning a random sample of the rows
+    /// of the table (`TABLESAMPLE`), with `projection` and `filters` as in
+    /// [`Self::scan`]. The seed of `sample` is always set.
+    ///
+    /// Returns `None` if the table can not sample its rows more efficiently
+    /// than by scanning all of them, which is the default. DataFusion then
+    /// samples the rows returned by [`Self::scan`] instead.
+    async fn scan_sample(
+        &self,
+        _state: &SessionState,
+        _projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _sample: &TableSample,
+    ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+        Ok(None)
+    }
+
+    /// Returns the current version of the table, or `None` if the table is
+    /// not versioned, which is the default.
+    ///
+    /// The version is resolved once per query, when it is planned, so that
+    /// all the scans of the table in the query read the same version with
+    /// [`Self::scan_snapshot`], even if the table changes in between, e.g.
+    /// while its files are compacted. A version can also be pinned for all
+    /// the queries of a session with
+    /// [`SessionState::with_table_snapshot`].
+    async fn snapshot(&self, _state: &SessionState) -> Result<Option<SnapshotToken>> {
+        Ok(None)
+    }
+
+    /// Create an [`ExecutionPlan`] for scanning the version `snapshot` of the
+    /// table, returned by [`Self::snapshot`], with `projection`, `filters`
+    /// and `limit` as in [`Self::scan`].
+    ///
+    /// Defaults to [`Self::scan`], for the tables which are not versioned.
+    async fn scan_snapshot(
+        &self,
+        state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+        _snapshot: &SnapshotToken,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        self.scan(state, projection, filters, limit).await
+    }
+
+    /// Create an [`ExecutionPlan`] for scanning a random sample of the rows
+    /// of the version `snapshot` of the table, as in [`Self::scan_sample`]
+    /// and [`Self::scan_snapshot`].
+    ///
+    /// Returns `None` by default, DataFusion then samples the rows returned
+    /// by [`Self::scan_snapshot`] instead.
+    async fn scan_snapshot_sample(
+        &self,
+        _state: &SessionState,
+        _projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _sample: &TableSample,
+        _snapshot: &SnapshotToken,
+    ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+        Ok(None)
+    }
+
+    /// Create an [`ExecutionPlan`] for scanning the past version `version` of
+    /// the table (`FOR SYSTEM_TIME AS OF` or `VERSION AS OF`), with
+    /// `projection`, `filters` and `limit` as in [`Self::scan`].
+    ///
+    /// Returns an error by default, for the tables which do not keep their
+    /// past versions.
+    async fn scan_at_version(
+        &self,
+        _state: &SessionState,
+        _projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+        version: &TableVersion,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Table does not support scanning the {version}")
+    }
+
+    /// Create an [`ExecutionPlan`] for scanning the rows changed by the
+    /// versions of the table in `changes` (change data feed), as planned for
+    /// `table_changes('t', <start version>[, <end version>])`.
+    ///
+    /// The rows have the columns of the table followed by a non null Utf8
+    /// [`CHANGE_TYPE_COLUMN`], which `projection` indexes after the columns
+    /// of the table, and `filters` and `limit` are as in [`Self::scan`]:
+    ///
+    /// * the inserted rows, of the type [`CHANGE_TYPE_INSERT`]
+    /// * the new values of the updated rows, of the type [`CHANGE_TYPE_UPDATE`]
+    /// * the deleted rows, of the type [`CHANGE_TYPE_DELETE`]
+    ///
+    /// Returns an error by default, for the tables which do not track their
+    /// changes.
+    ///
+    /// [`CHANGE_TYPE_COLUMN`]: crate::datasource::changes::CHANGE_TYPE_COLUMN
+    /// [`CHANGE_TYPE_INSERT`]: crate::datasource::changes::CHANGE_TYPE_INSERT
+    /// [`CHANGE_TYPE_UPDATE`]: crate::datasource::changes::CHANGE_TYPE_UPDATE
+    /// [`CHANGE_TYPE_DELETE`]: crate::datasource::changes::CHANGE_TYPE_DELETE
+    async fn scan_changes(
+        &self,
+        _state: &SessionState,
+        _projection: Option<&Vec<usize>>,
+        _filters: &[Expr],
+        _limit: Option<usize>,
+        changes: &TableChanges,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        not_impl_err!("Table does not support scanning its {changes}")
+    }
+
     /// Specify if DataFusion should provide filter expressions to the
     /// TableProvider to apply *during* the scan.
     ///
@@ -253,6 +364,17 @@ pub trait TableProvider: Sync + Send {
         None
     }
 
+    /// Store the statistics computed for this table, e.g. by `ANALYZE TABLE`,
+    /// to be returned by [`Self::statistics`] and used for later planning
+    /// decisions.
+    ///
+    /// The statistics describe the whole table at the time they were
+    /// computed; the table may have changed since, so tables should treat
+    /// them as estimates when planning scans.
+    fn set_statistics(&self, _statistics: Statistics) -> Result<()> {
+        not_impl_err!("Setting statistics is not supported by this table")
+    }
+
     /// Return an [`ExecutionPlan`] to insert data into this table, if
     /// supported.
     ///
@@ -283,6 +405,29 @@ pub trait TableProvider: Sync + Send {
     }
 }
 
+/// An opaque version of a table, returned by [`TableProvider::snapshot`],
+/// such as the id of the manifest listing the files of the version.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SnapshotToken(Arc<str>);
+
+impl SnapshotToken {
+    /// Create the token `token`
+    pub fn new(token: impl Into<Arc<str>>) -> Self {
+        Self(token.into())
+    }
+
+    /// The token, as set by the table
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+}
+
+impl std::fmt::Display for SnapshotToken {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
 /// A factory which creates [`TableProvider`]s at runtime given a URL.
 ///
 /// For example, this can be used to create a table "on the fly"
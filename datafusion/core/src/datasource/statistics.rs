@@ -21,7 +21,7 @@ use crate::error::Result;
 use crate::physical_plan::expressions::{MaxAccumulator, MinAccumulator};
 use crate::physical_plan::{Accumulator, ColumnStatistics, Statistics};
 
-use datafusion_common::stats::Precision;
+use datafusion_common::stats::{DistinctSketch, Precision};
 use datafusion_common::ScalarValue;
 
 use futures::{Stream, StreamExt};
@@ -50,6 +50,7 @@ pub async fn get_statistics_with_limit(
     let mut null_counts: Vec<Precision<usize>> = vec![Precision::Absent; size];
     let mut max_values: Vec<Precision<ScalarValue>> = vec![Precision::Absent; size];
     let mut min_values: Vec<Precision<ScalarValue>> = vec![Precision::Absent; size];
+    let mut distinct_sketches: Vec<Option<DistinctSketch>> = vec![None; size];
     let mut num_rows = Precision::<usize>::Absent;
     let mut total_byte_size = Precision::<usize>::Absent;
 
@@ -67,6 +68,7 @@ pub async fn get_statistics_with_limit(
             null_counts[index] = file_column.null_count;
             max_values[index] = file_column.max_value;
             min_values[index] = file_column.min_value;
+            distinct_sketches[index] = file_column.distinct_sketch;
         }
 
         // If the number of rows exceeds the limit, we can stop processing
@@ -94,12 +96,13 @@ pub async fn get_statistics_with_limit(
                 total_byte_size =
                     add_row_stats(file_stats.total_byte_size, total_byte_size);
 
-                (null_counts, max_values, min_values) = multiunzip(
+                (null_counts, max_values, min_values, distinct_sketches) = multiunzip(
                     izip!(
                         file_stats.column_statistics.into_iter(),
                         null_counts.into_iter(),
                         max_values.into_iter(),
-                        min_values.into_iter()
+                        min_values.into_iter(),
+                        distinct_sketches.into_iter()
                     )
                     .map(
                         |(
@@ -108,15 +111,18 @@ pub async fn get_statistics_with_limit(
                                 max_value: file_max,
                                 min_value: file_min,
                                 distinct_count: _,
+                                distinct_sketch: file_sketch,
                             },
                             null_count,
                             max_value,
                             min_value,
+                            distinct_sketch,
                         )| {
                             (
                                 add_row_stats(file_nc, null_count),
                                 set_max_if_greater(file_max, max_value),
                                 set_min_if_lesser(file_min, min_value),
+                                merge_sketches(file_sketch, distinct_sketch),
                             )
                         },
                     ),
@@ -138,7 +144,12 @@ pub async fn get_statistics_with_limit(
     let mut statistics = Statistics {
         num_rows,
         total_byte_size,
-        column_statistics: get_col_stats_vec(null_counts, max_values, min_values),
+        column_statistics: get_col_stats_vec(
+            null_counts,
+            max_values,
+            min_values,
+            distinct_sketches,
+        ),
     };
     if all_files.next().await.is_some() {
         // If we still have files in the stream, it means that the limit kicked
@@ -181,14 +192,23 @@ pub(crate) fn get_col_stats_vec(
     null_counts: Vec<Precision<usize>>,
     max_values: Vec<Precision<ScalarValue>>,
     min_values: Vec<Precision<ScalarValue>>,
+    distinct_sketches: Vec<Option<DistinctSketch>>,
 ) -> Vec<ColumnStatistics> {
-    izip!(null_counts, max_values, min_values)
-        .map(|(null_count, max_value, min_value)| ColumnStatistics {
-            null_count,
-            max_value,
-            min_value,
-            distinct_count: Precision::Absent,
-        })
+    izip!(null_counts, max_values, min_values, distinct_sketches)
+        .map(
+            |(null_count, max_value, min_value, distinct_sketch)| ColumnStatistics {
+                null_count,
+                max_value,
+                min_value,
+                // Distinct counts cannot be added across files, but can be
+                // estimated from the merged sketches of all the files
+                distinct_count: distinct_sketch
+                    .as_ref()
+                    .map(|sketch| Precision::Inexact(sketch.count()))
+                    .unwrap_or(Precision::Absent),
+                distinct_sketch,
+            },
+        )
         .collect()
 }
 
@@ -213,6 +233,7 @@ pub(crate) fn get_col_stats(
                 max_value: max_value.map(Precision::Exact).unwrap_or(Precision::Absent),
                 min_value: min_value.map(Precision::Exact).unwrap_or(Precision::Absent),
                 distinct_count: Precision::Absent,
+                distinct_sketch: None,
             }
         })
         .collect()
@@ -263,3 +284,15 @@ fn set_min_if_lesser(
         _ => min_values,
     }
 }
+
+/// Merges the distinct sketches of a column of two files. The merged sketch
+/// is only known if both files have a sketch.
+fn merge_sketches(
+    file_sketch: Option<DistinctSketch>,
+    sketch: Option<DistinctSketch>,
+) -> Option<DistinctSketch> {
+    match (file_sketch, sketch) {
+        (Some(file_sketch), Some(sketch)) => Some(file_sketch.merge(&sketch)),
+        _ => None,
+    }
+}
@@ -24,13 +24,21 @@ use std::io::BufReader;
 use std::path::PathBuf;
 use std::str::FromStr;
 use std::sync::Arc;
-
-use arrow_array::{RecordBatch, RecordBatchReader, RecordBatchWriter};
-use arrow_schema::SchemaRef;
+use std::time::Duration;
+
+use arrow::compute::{cast, concat_batches, filter_record_batch, sort_to_indices, take};
+use arrow_array::cast::AsArray;
+use arrow_array::types::Int64Type;
+use arrow_array::{
+    BooleanArray, Int64Array, RecordBatch, RecordBatchReader, RecordBatchWriter,
+};
+use arrow_schema::{DataType, Schema, SchemaRef, TimeUnit};
 use async_trait::async_trait;
 use futures::StreamExt;
 
-use datafusion_common::{plan_err, Constraints, DataFusionError, Result};
+use datafusion_common::{
+    plan_datafusion_err, plan_err, Column, Constraints, DataFusionError, Result,
+};
 use datafusion_common_runtime::SpawnedTask;
 use datafusion_execution::{SendableRecordBatchStream, TaskContext};
 use datafusion_expr::{CreateExternalTable, Expr, TableType};
@@ -59,13 +67,28 @@ impl TableProviderFactory for StreamTableFactory {
         let location = cmd.location.clone();
         let encoding = cmd.file_type.parse()?;
 
-        let config = StreamConfig::new_file(schema, location.into())
+        let mut config = StreamConfig::new_file(schema, location.into())
             .with_encoding(encoding)
             .with_order(cmd.order_exprs.clone())
             .with_header(cmd.has_header)
             .with_batch_size(state.config().batch_size())
             .with_constraints(cmd.constraints.clone());
 
+        if let Some(column) = cmd.options.get("watermark.column") {
+            let delay = match cmd.options.get("watermark.delay_ms") {
+                Some(delay) => delay.parse().map_err(|_| {
+                    plan_datafusion_err!(
+                        "Expected a number of milliseconds for watermark.delay_ms, got {delay}"
+                    )
+                })?,
+                None => 0,
+            };
+            config = config.with_watermark(StreamWatermark::new(
+                column,
+                Duration::from_millis(delay),
+            ));
+        }
+
         Ok(Arc::new(StreamTable(Arc::new(config))))
     }
 }
@@ -91,6 +114,70 @@ impl FromStr for StreamEncoding {
     }
 }
 
+/// The watermark of a [`StreamTable`]
+///
+/// Rows of an append-only stream may arrive out of order with respect to
+/// a timestamp `column`, but at most `delay` after rows with a later
+/// timestamp. The watermark, the largest timestamp seen so far minus
+/// `delay`, is then a lower bound of the timestamps of all rows yet to
+/// arrive.
+///
+/// A [`StreamTable`] with a watermark buffers the rows it reads and emits them
+/// sorted by `column` once the watermark has passed them. The table is then
+/// ordered by `column`, so that operators such as aggregates grouped by
+/// `date_bin(.., column)` can emit finalized groups while the stream is still
+/// being read. Rows arriving after the watermark has passed them, and rows
+/// with a `NULL` timestamp, are dropped.
+#[derive(Debug, Clone)]
+pub struct StreamWatermark {
+    column: String,
+    delay: Duration,
+}
+
+impl StreamWatermark {
+    /// Create a watermark on the timestamp `column`, allowing rows to arrive
+    /// at most `delay` late
+    pub fn new(column: impl Into<String>, delay: Duration) -> Self {
+        Self {
+            column: column.into(),
+            delay,
+        }
+    }
+
+    /// The timestamp column of the watermark
+    pub fn column(&self) -> &str {
+        &self.column
+    }
+
+    /// The maximum delay of the rows of the stream
+    pub fn delay(&self) -> Duration {
+        self.delay
+    }
+
+    /// Returns the index of the watermark column in `schema`, and the delay
+    /// in the time unit of the column
+    fn resolve(&self, schema: &Schema) -> Result<(usize, i64)> {
+        let index = schema.index_of(&self.column)?;
+        let delay = match schema.field(index).data_type() {
+            DataType::Timestamp(TimeUnit::Second, _) => self.delay.as_secs() as i64,
+            DataType::Timestamp(TimeUnit::Millisecond, _) => {
+                self.delay.as_millis() as i64
+            }
+            DataType::Timestamp(TimeUnit::Microsecond, _) => {
+                self.delay.as_micros() as i64
+            }
+            DataType::Timestamp(TimeUnit::Nanosecond, _) => self.delay.as_nanos() as i64,
+            other => {
+                return plan_err!(
+                    "Watermark column {} must be a timestamp, got {other}",
+                    self.column
+                )
+            }
+        };
+        Ok((index, delay))
+    }
+}
+
 /// The configuration for a [`StreamTable`]
 #[derive(Debug)]
 pub struct StreamConfig {
@@ -101,6 +188,7 @@ pub struct StreamConfig {
     header: bool,
     order: Vec<Vec<Expr>>,
     constraints: Constraints,
+    watermark: Option<StreamWatermark>,
 }
 
 impl StreamConfig {
@@ -120,6 +208,7 @@ impl StreamConfig {
             order: vec![],
             header: false,
             constraints: Constraints::empty(),
+            watermark: None,
         }
     }
 
@@ -153,6 +242,36 @@ impl StreamConfig {
         self
     }
 
+    /// Specify a watermark for the stream
+    ///
+    /// Rows read from the stream are then sorted by the watermark column,
+    /// which replaces any sort order specified with [`Self::with_order`]
+    pub fn with_watermark(mut self, watermark: StreamWatermark) -> Self {
+        self.watermark = Some(watermark);
+        self
+    }
+
+    /// The sort orders of the rows read from the stream
+    fn read_order(&self) -> Vec<Vec<Expr>> {
+        match &self.watermark {
+            Some(watermark) => {
+                let column = Column::new_unqualified(watermark.column.as_str());
+                vec![vec![Expr::Column(column).sort(true, false)]]
+            }
+            None => self.order.clone(),
+        }
+    }
+
+    fn watermark_buffer(&self) -> Result<Option<WatermarkBuffer>> {
+        self.watermark
+            .as_ref()
+            .map(|watermark| {
+                let (column, delay) = watermark.resolve(&self.schema)?;
+                Ok(WatermarkBuffer::new(self.schema.clone(), column, delay))
+            })
+            .transpose()
+    }
+
     fn reader(&self) -> Result<Box<dyn RecordBatchReader>> {
         let file = File::open(&self.location)?;
         let schema = self.schema.clone();
@@ -244,12 +363,15 @@ impl TableProvider for StreamTable {
         _filters: &[Expr],
         _limit: Option<usize>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
+        // Validate the watermark at planning time
+        self.0.watermark_buffer()?;
+        let order = self.0.read_order();
         let projected_schema = match projection {
             Some(p) => {
                 let projected = self.0.schema.project(p)?;
-                create_ordering(&projected, &self.0.order)?
+                create_ordering(&projected, &order)?
             }
-            None => create_ordering(self.0.schema.as_ref(), &self.0.order)?,
+            None => create_ordering(self.0.schema.as_ref(), &order)?,
         };
 
         Ok(Arc::new(StreamingTableExec::try_new(
@@ -300,17 +422,121 @@ impl PartitionStream for StreamRead {
         let tx = builder.tx();
         builder.spawn_blocking(move || {
             let reader = config.reader()?;
+            let mut watermark = config.watermark_buffer()?;
             for b in reader {
-                if tx.blocking_send(b.map_err(Into::into)).is_err() {
+                let b = match &mut watermark {
+                    Some(watermark) => {
+                        match b.map_err(Into::into).and_then(|b| watermark.push(b)) {
+                            Ok(Some(b)) => Ok(b),
+                            Ok(None) => continue,
+                            Err(e) => Err(e),
+                        }
+                    }
+                    None => b.map_err(Into::into),
+                };
+                if tx.blocking_send(b).is_err() {
                     break;
                 }
             }
+            if let Some(watermark) = &mut watermark {
+                if let Some(b) = watermark.finish()? {
+                    // The receiver may already be gone
+                    let _ = tx.blocking_send(Ok(b));
+                }
+            }
             Ok(())
         });
         builder.build()
     }
 }
 
+/// Sorts the rows of a stream by the column of its [`StreamWatermark`],
+/// holding back rows until the watermark has passed them
+struct WatermarkBuffer {
+    schema: SchemaRef,
+    /// Index of the watermark column
+    column: usize,
+    /// Delay in the time unit of the watermark column
+    delay: i64,
+    /// Rows the watermark has not passed yet
+    pending: Vec<RecordBatch>,
+    /// Largest timestamp seen so far
+    max_seen: Option<i64>,
+    /// Largest timestamp emitted so far, earlier rows arrive too late
+    max_emitted: Option<i64>,
+}
+
+impl WatermarkBuffer {
+    fn new(schema: SchemaRef, column: usize, delay: i64) -> Self {
+        Self {
+            schema,
+            column,
+            delay,
+            pending: vec![],
+            max_seen: None,
+            max_emitted: None,
+        }
+    }
+
+    fn timestamps(&self, batch: &RecordBatch) -> Result<Int64Array> {
+        let timestamps = cast(batch.column(self.column), &DataType::Int64)?;
+        Ok(timestamps.as_primitive::<Int64Type>().clone())
+    }
+
+    /// Adds `batch` to the buffer, returning the rows passed by the new
+    /// watermark, if any
+    fn push(&mut self, batch: RecordBatch) -> Result<Option<RecordBatch>> {
+        let timestamps = self.timestamps(&batch)?;
+        let on_time = timestamps
+            .iter()
+            .map(|ts| ts.map(|ts| self.max_emitted.map_or(true, |max| ts >= max)))
+            .collect::<BooleanArray>();
+        self.pending.push(filter_record_batch(&batch, &on_time)?);
+        self.max_seen = self.max_seen.max(arrow::compute::max(&timestamps));
+        match self.max_seen {
+            Some(max_seen) => self.emit(Some(max_seen.saturating_sub(self.delay))),
+            None => Ok(None),
+        }
+    }
+
+    /// Returns all remaining rows at the end of the stream
+    fn finish(&mut self) -> Result<Option<RecordBatch>> {
+        self.emit(None)
+    }
+
+    /// Returns the pending rows up to `watermark`, or all of them if `None`,
+    /// sorted by timestamp
+    fn emit(&mut self, watermark: Option<i64>) -> Result<Option<RecordBatch>> {
+        let pending = concat_batches(&self.schema, &self.pending)?;
+        self.pending.clear();
+        if pending.num_rows() == 0 {
+            return Ok(None);
+        }
+
+        let indices = sort_to_indices(&self.timestamps(&pending)?, None, None)?;
+        let columns = pending
+            .columns()
+            .iter()
+            .map(|c| take(c, &indices, None))
+            .collect::<Result<Vec<_>, _>>()?;
+        let sorted = RecordBatch::try_new(self.schema.clone(), columns)?;
+        let timestamps = self.timestamps(&sorted)?;
+        let ready = match watermark {
+            Some(watermark) => timestamps.values().partition_point(|ts| *ts <= watermark),
+            None => sorted.num_rows(),
+        };
+        if ready < sorted.num_rows() {
+            self.pending
+                .push(sorted.slice(ready, sorted.num_rows() - ready));
+        }
+        if ready == 0 {
+            return Ok(None);
+        }
+        self.max_emitted = Some(timestamps.value(ready - 1));
+        Ok(Some(sorted.slice(0, ready)))
+    }
+}
+
 #[derive(Debug)]
 struct StreamWrite(Arc<StreamConfig>);
 
@@ -362,3 +588,75 @@ impl DataSink for StreamWrite {
         write_task.join_unwind().await
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use arrow_array::TimestampMillisecondArray;
+    use arrow_schema::Field;
+
+    fn batch(timestamps: Vec<Option<i64>>) -> RecordBatch {
+        let schema = Schema::new(vec![Field::new(
+            "ts",
+            DataType::Timestamp(TimeUnit::Millisecond, None),
+            true,
+        )]);
+        RecordBatch::try_new(
+            Arc::new(schema),
+            vec![Arc::new(TimestampMillisecondArray::from(timestamps))],
+        )
+        .unwrap()
+    }
+
+    fn timestamps(batch: Option<RecordBatch>) -> Vec<i64> {
+        batch
+            .map(|batch| {
+                batch
+                    .column(0)
+                    .as_primitive::<arrow_array::types::TimestampMillisecondType>()
+                    .values()
+                    .to_vec()
+            })
+            .unwrap_or_default()
+    }
+
+    #[test]
+    fn watermark_buffer() -> Result<()> {
+        let watermark = StreamWatermark::new("ts", Duration::from_millis(10));
+        let schema = batch(vec![]).schema();
+        let (column, delay) = watermark.resolve(&schema)?;
+        assert_eq!((column, delay), (0, 10));
+        let mut buffer = WatermarkBuffer::new(schema, column, delay);
+
+        // Watermark at 5
+        assert_eq!(
+            timestamps(buffer.push(batch(vec![Some(15), Some(3)]))?),
+            [3]
+        );
+        // Watermark at 20, 2 arrives too late and NULL is dropped
+        assert_eq!(
+            timestamps(buffer.push(batch(vec![Some(2), Some(30), None, Some(8)]))?),
+            [8, 15]
+        );
+        // Watermark unchanged
+        assert_eq!(
+            timestamps(buffer.push(batch(vec![Some(25)]))?),
+            [] as [i64; 0]
+        );
+        assert_eq!(timestamps(buffer.finish()?), [25, 30]);
+        assert_eq!(timestamps(buffer.finish()?), [] as [i64; 0]);
+        Ok(())
+    }
+
+    #[test]
+    fn watermark_requires_timestamp() {
+        let schema = Schema::new(vec![Field::new("ts", DataType::Int64, true)]);
+        let err = StreamWatermark::new("ts", Duration::from_secs(1))
+            .resolve(&schema)
+            .unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Watermark column ts must be a timestamp, got Int64"));
+    }
+}
@@ -27,16 +27,21 @@ use datafusion_common::{plan_err, Result};
 use datafusion_expr::{Expr, TableType};
 use log::debug;
 
-use crate::datasource::TableProvider;
+use crate::datasource::{create_ordering, TableProvider};
 use crate::execution::context::SessionState;
 use crate::physical_plan::streaming::{PartitionStream, StreamingTableExec};
 use crate::physical_plan::ExecutionPlan;
 
 /// A [`TableProvider`] that streams a set of [`PartitionStream`]
+///
+/// The partitions may be infinite, see [`Self::with_infinite_table`], in
+/// which case the table can only be used by queries that can run on unbounded
+/// inputs, e.g. a table function returning an endless series of values.
 pub struct StreamingTable {
     schema: SchemaRef,
     partitions: Vec<Arc<dyn PartitionStream>>,
     infinite: bool,
+    sort_order: Vec<Vec<Expr>>,
 }
 
 impl StreamingTable {
@@ -60,6 +65,7 @@ impl StreamingTable {
             schema,
             partitions,
             infinite: false,
+            sort_order: vec![],
         })
     }
     /// Sets streaming table can be infinite.
@@ -67,6 +73,21 @@ impl StreamingTable {
         self.infinite = infinite;
         self
     }
+
+    /// Specify the sort orders that the rows of every partition satisfy
+    ///
+    /// Declaring the order of an infinite table lets queries that require
+    /// it, such as `ORDER BY` with a `LIMIT` or windows over the ordered
+    /// columns, run on the table without sorting the whole stream.
+    pub fn with_sort_order(mut self, sort_order: Vec<Vec<Expr>>) -> Self {
+        self.sort_order = sort_order;
+        self
+    }
+
+    /// Returns true if the partitions of the table may be infinite
+    pub fn is_infinite(&self) -> bool {
+        self.infinite
+    }
 }
 
 #[async_trait]
@@ -90,12 +111,16 @@ impl TableProvider for StreamingTable {
         _filters: &[Expr],
         _limit: Option<usize>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
+        let projected_ordering = match projection {
+            Some(p) => create_ordering(&self.schema.project(p)?, &self.sort_order)?,
+            None => create_ordering(&self.schema, &self.sort_order)?,
+        };
         // TODO: push limit down
         Ok(Arc::new(StreamingTableExec::try_new(
             self.schema.clone(),
             self.partitions.clone(),
             projection,
-            None,
+            projected_ordering,
             self.infinite,
         )?))
     }
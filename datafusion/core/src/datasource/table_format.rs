@@ -0,0 +1,544 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`TableFormat`]s, the formats of the tables whose versions list their
+//! files in their own metadata, such as Iceberg, Delta Lake or Hudi tables,
+//! scanned by a [`TableFormatProvider`]
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use arrow::datatypes::{Field, Schema, SchemaRef};
+use async_trait::async_trait;
+use datafusion_common::{
+    not_impl_err, plan_err, project_schema, FileType, ScalarValue, Statistics, ToDFSchema,
+};
+use datafusion_expr::utils::conjunction;
+use datafusion_expr::{Expr, TableProviderFilterPushDown, TableType, TableVersion};
+use datafusion_physical_expr::create_physical_expr;
+use parking_lot::Mutex;
+
+use crate::datasource::file_format::FileFormat;
+use crate::datasource::listing::{
+    expr_applicable_for_cols, prune_partitioned_files, split_files, PartitionedFile,
+};
+use crate::datasource::object_store::ObjectStoreUrl;
+use crate::datasource::physical_plan::{FileScanConfig, ListingPruning};
+use crate::datasource::{SnapshotToken, TableProvider};
+use crate::error::Result;
+use crate::execution::context::SessionState;
+use crate::physical_plan::empty::EmptyExec;
+use crate::physical_plan::ExecutionPlan;
+
+/// The format of a table whose versions, or snapshots, list their data files
+/// in the metadata of the table, such as Iceberg, Delta Lake or Hudi tables.
+///
+/// A format only discovers the snapshots of its tables: the
+/// [`TableFormatProvider`] scans the files of a snapshot like a
+/// [`ListingTable`] scans the files it lists, pruning them by their partition
+/// values and their metadata, reading the columns of the column hints only,
+/// and adapting the files written with previous schemas to the schema of
+/// the table.
+///
+/// [`ListingTable`]: crate::datasource::listing::ListingTable
+#[async_trait]
+pub trait TableFormat: Debug + Send + Sync {
+    /// Returns the format as [`Any`] so that it can be downcast to a
+    /// specific implementation
+    fn as_any(&self) -> &dyn Any;
+
+    /// The format of the data files of the table
+    fn file_format(&self) -> Arc<dyn FileFormat>;
+
+    /// Discovers the snapshot of the table at the version `version`, or the
+    /// current snapshot if `None`.
+    ///
+    /// The snapshots pinned by a [`SnapshotToken`] are discovered at the
+    /// [`TableVersion::Version`] of the string of the token, which the format
+    /// parses back into the version of the [`TableSnapshot::version`] it
+    /// returned.
+    async fn snapshot(
+        &self,
+        state: &SessionState,
+        version: Option<&TableVersion>,
+    ) -> Result<TableSnapshot>;
+}
+
+/// A snapshot of a table of a [`TableFormat`]: its files, the rows deleted
+/// from them and its schema
+#[derive(Debug, Clone)]
+pub struct TableSnapshot {
+    /// The version of the snapshot
+    pub version: SnapshotToken,
+    /// The object store of the files
+    pub object_store_url: ObjectStoreUrl,
+    /// The schema of the table, the columns of the files followed by the
+    /// partition columns
+    pub schema: SchemaRef,
+    /// The number of partition columns at the end of `schema`
+    pub num_partition_cols: usize,
+    /// The data files of the snapshot, with their values of the partition
+    /// columns and the [`DeletedRows`] of their extensions, if any rows were
+    /// deleted from them
+    pub files: Vec<PartitionedFile>,
+}
+
+impl TableSnapshot {
+    /// The schema of the files
+    pub fn file_schema(&self) -> SchemaRef {
+        let num_file_cols = self.schema.fields().len() - self.num_partition_cols;
+        Arc::new(Schema::new_with_metadata(
+            self.schema.fields()[..num_file_cols].to_vec(),
+            self.schema.metadata().clone(),
+        ))
+    }
+
+    /// The partition columns
+    pub fn partition_cols(&self) -> Vec<Field> {
+        let num_file_cols = self.schema.fields().len() - self.num_partition_cols;
+        self.schema.fields()[num_file_cols..]
+            .iter()
+            .map(|field| field.as_ref().clone())
+            .collect()
+    }
+}
+
+/// The positions of the rows deleted from a data file of a [`TableSnapshot`],
+/// such as the positions of a positional delete file of Iceberg or of a
+/// deletion vector of Delta Lake, which the scans skip.
+///
+/// The rows are deleted from the file by setting them as the
+/// [`PartitionedFile::extensions`] of the file. Only the parquet scans skip
+/// the deleted rows.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct DeletedRows {
+    positions: Vec<u64>,
+}
+
+impl DeletedRows {
+    /// Create the deleted rows at the positions `positions`, from 0 for the
+    /// first row of the file
+    pub fn new(mut positions: Vec<u64>) -> Self {
+        positions.sort_unstable();
+        positions.dedup();
+        Self { positions }
+    }
+
+    /// The sorted positions of the deleted rows
+    pub fn positions(&self) -> &[u64] {
+        &self.positions
+    }
+
+    /// Returns the number of deleted rows
+    pub fn len(&self) -> usize {
+        self.positions.len()
+    }
+
+    /// Returns true if no rows are deleted
+    pub fn is_empty(&self) -> bool {
+        self.positions.is_empty()
+    }
+}
+
+/// A [`TableProvider`] scanning the snapshots of a table of a
+/// [`TableFormat`].
+///
+/// The schema of the provider is the schema of the current snapshot when it
+/// is created. The scans read the current snapshot, or the snapshot resolved
+/// for the query, see [`TableProvider::snapshot`], or the version of the
+/// scans of past versions.
+#[derive(Debug)]
+pub struct TableFormatProvider {
+    format: Arc<dyn TableFormat>,
+    schema: SchemaRef,
+    file_schema: SchemaRef,
+    table_partition_cols: Vec<Field>,
+    column_hints: Option<Vec<String>>,
+    /// The last snapshot discovered, scanned if its version is resolved
+    last_snapshot: Mutex<Option<Arc<TableSnapshot>>>,
+}
+
+impl TableFormatProvider {
+    /// Create a provider of the table of `format`, of the schema of its
+    /// current snapshot
+    pub async fn try_new(
+        state: &SessionState,
+        format: Arc<dyn TableFormat>,
+    ) -> Result<Self> {
+        let snapshot = format.snapshot(state, None).await?;
+        Ok(Self {
+            format,
+            schema: snapshot.schema.clone(),
+            file_schema: snapshot.file_schema(),
+            table_partition_cols: snapshot.partition_cols(),
+            column_hints: None,
+            last_snapshot: Mutex::new(Some(Arc::new(snapshot))),
+        })
+    }
+
+    /// Set the column hints of the scans, the paths of the nested fields to
+    /// read, see [`FileScanConfig::column_hints`]
+    pub fn with_column_hints(mut self, column_hints: Option<Vec<String>>) -> Self {
+        self.column_hints = column_hints;
+        self
+    }
+
+    /// The format of the table
+    pub fn format(&self) -> &Arc<dyn TableFormat> {
+        &self.format
+    }
+
+    /// Discovers the snapshot at `version`, or the current one
+    async fn discover(
+        &self,
+        state: &SessionState,
+        version: Option<&TableVersion>,
+    ) -> Result<Arc<TableSnapshot>> {
+        let snapshot = Arc::new(self.format.snapshot(state, version).await?);
+        if version.is_none() {
+            *self.last_snapshot.lock() = Some(Arc::clone(&snapshot));
+        }
+        Ok(snapshot)
+    }
+
+    /// Create an [`ExecutionPlan`] scanning the files of `snapshot`
+    async fn scan_snapshot_files(
+        &self,
+        state: &SessionState,
+        snapshot: &TableSnapshot,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if snapshot.partition_cols() != self.table_partition_cols {
+            return plan_err!(
+                "The partition columns of the version {} of the table differ from the ones of the table",
+                snapshot.version
+            );
+        }
+
+        let file_format = self.format.file_format();
+        let has_deletes = snapshot.files.iter().any(|file| {
+            file.extensions
+                .as_ref()
+                .and_then(|extensions| extensions.downcast_ref::<DeletedRows>())
+                .is_some_and(|deleted_rows| !deleted_rows.is_empty())
+        });
+        if has_deletes && file_format.file_type() != FileType::PARQUET {
+            return not_impl_err!(
+                "Deleted rows are only supported for the tables of parquet files"
+            );
+        }
+
+        let partitions = |files: &[PartitionedFile]| {
+            files
+                .iter()
+                .map(|file| &file.partition_values)
+                .collect::<HashSet<&Vec<ScalarValue>>>()
+                .len()
+        };
+        let files_considered = snapshot.files.len();
+        let partitions_considered = partitions(&snapshot.files);
+        let files = prune_partitioned_files(
+            snapshot.files.clone(),
+            filters,
+            &self.table_partition_cols,
+        )?;
+        if files.is_empty() {
+            let projected_schema = project_schema(&self.schema, projection)?;
+            return Ok(Arc::new(EmptyExec::new(projected_schema)));
+        }
+        let listing_pruning = ListingPruning {
+            partitions_considered,
+            partitions_kept: partitions(&files),
+            files_considered,
+        };
+
+        let filters = match conjunction(filters.to_vec()) {
+            Some(expr) => {
+                // the filters may refer to the partition columns
+                let table_df_schema = self.schema.as_ref().clone().to_dfschema()?;
+                Some(create_physical_expr(
+                    &expr,
+                    &table_df_schema,
+                    state.execution_props(),
+                )?)
+            }
+            None => None,
+        };
+        let file_groups = split_files(files, state.config().target_partitions());
+        file_format
+            .create_physical_plan(
+                state,
+                FileScanConfig {
+                    object_store_url: snapshot.object_store_url.clone(),
+                    statistics: Statistics::new_unknown(&self.file_schema),
+                    file_schema: Arc::clone(&self.file_schema),
+                    file_groups,
+                    projection: projection.cloned(),
+                    column_hints: self.column_hints.clone(),
+                    listing_pruning: Some(listing_pruning),
+                    limit,
+                    table_partition_cols: self.table_partition_cols.clone(),
+                    output_ordering: vec![],
+                },
+                filters.as_ref(),
+            )
+            .await
+    }
+}
+
+#[async_trait]
+impl TableProvider for TableFormatProvider {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        Arc::clone(&self.schema)
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let snapshot = self.discover(state, None).await?;
+        self.scan_snapshot_files(state, &snapshot, projection, filters, limit)
+            .await
+    }
+
+    fn supports_filters_pushdown(
+        &self,
+        filters: &[&Expr],
+    ) -> Result<Vec<TableProviderFilterPushDown>> {
+        let partition_cols = self
+            .table_partition_cols
+            .iter()
+            .map(|field| field.name().clone())
+            .collect::<Vec<_>>();
+        Ok(filters
+            .iter()
+            .map(|filter| {
+                // the filters of the partition columns prune the files
+                if !partition_cols.is_empty()
+                    && expr_applicable_for_cols(&partition_cols, filter)
+                {
+                    TableProviderFilterPushDown::Exact
+                } else {
+                    TableProviderFilterPushDown::Inexact
+                }
+            })
+            .collect())
+    }
+
+    async fn snapshot(&self, state: &SessionState) -> Result<Option<SnapshotToken>> {
+        let snapshot = self.discover(state, None).await?;
+        Ok(Some(snapshot.version.clone()))
+    }
+
+    async fn scan_snapshot(
+        &self,
+        state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+        snapshot: &SnapshotToken,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let last_snapshot = self
+            .last_snapshot
+            .lock()
+            .clone()
+            .filter(|last| &last.version == snapshot);
+        let snapshot = match last_snapshot {
+            Some(snapshot) => snapshot,
+            None => {
+                let version = TableVersion::Version(ScalarValue::Utf8(Some(
+                    snapshot.as_str().to_string(),
+                )));
+                self.discover(state, Some(&version)).await?
+            }
+        };
+        self.scan_snapshot_files(state, &snapshot, projection, filters, limit)
+            .await
+    }
+
+    async fn scan_at_version(
+        &self,
+        state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+        version: &TableVersion,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let snapshot = self.discover(state, Some(version)).await?;
+        self.scan_snapshot_files(state, &snapshot, projection, filters, limit)
+            .await
+    }
+}
+
+#[cfg(all(test, feature = "parquet"))]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+    use crate::datasource::file_format::parquet::ParquetFormat;
+    use crate::prelude::SessionContext;
+
+    use arrow::array::Int32Array;
+    use arrow::datatypes::DataType;
+    use arrow::record_batch::RecordBatch;
+    use parquet::arrow::ArrowWriter;
+    use parquet::file::properties::WriterProperties;
+
+    /// A table whose version 1 has the file `a.parquet` of the partition
+    /// `p=x`, and whose version 2 deletes some rows of `a.parquet` and adds
+    /// the file `b.parquet` of the partition `p=y`
+    #[derive(Debug)]
+    struct TestFormat {
+        dir: String,
+    }
+
+    impl TestFormat {
+        fn file(&self, name: &str, partition: &str) -> Result<PartitionedFile> {
+            let path = format!("{}/{name}", self.dir);
+            let size = std::fs::metadata(&path)?.len();
+            let mut file = PartitionedFile::new(path, size);
+            file.partition_values = vec![ScalarValue::from(partition)];
+            Ok(file)
+        }
+    }
+
+    #[async_trait]
+    impl TableFormat for TestFormat {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn file_format(&self) -> Arc<dyn FileFormat> {
+            Arc::new(ParquetFormat::default())
+        }
+
+        async fn snapshot(
+            &self,
+            _state: &SessionState,
+            version: Option<&TableVersion>,
+        ) -> Result<TableSnapshot> {
+            let version = match version {
+                None => 2,
+                Some(TableVersion::Version(ScalarValue::Int64(Some(version)))) => {
+                    *version
+                }
+                Some(TableVersion::Version(ScalarValue::Utf8(Some(version)))) => {
+                    version.parse().unwrap()
+                }
+                Some(version) => return plan_err!("Unsupported version {version}"),
+            };
+            let mut files = vec![self.file("a.parquet", "x")?];
+            if version >= 2 {
+                files[0].extensions = Some(Arc::new(DeletedRows::new(vec![4, 1, 2])));
+                files.push(self.file("b.parquet", "y")?);
+            }
+            Ok(TableSnapshot {
+                version: SnapshotToken::new(version.to_string()),
+                object_store_url: ObjectStoreUrl::local_filesystem(),
+                schema: Arc::new(Schema::new(vec![
+                    Field::new("v", DataType::Int32, false),
+                    Field::new("p", DataType::Utf8, false),
+                ])),
+                num_partition_cols: 1,
+                files,
+            })
+        }
+    }
+
+    #[tokio::test]
+    async fn scan_table_format() -> Result<()> {
+        let dir = tempfile::tempdir()?;
+        let file_schema =
+            Arc::new(Schema::new(vec![Field::new("v", DataType::Int32, false)]));
+        for (name, values) in [
+            ("a.parquet", vec![0, 1, 2, 3, 4]),
+            ("b.parquet", vec![10, 11]),
+        ] {
+            // row groups of 2 rows, for the deleted rows to span several
+            let properties = WriterProperties::builder()
+                .set_max_row_group_size(2)
+                .build();
+            let mut writer = ArrowWriter::try_new(
+                std::fs::File::create(dir.path().join(name))?,
+                file_schema.clone(),
+                Some(properties),
+            )?;
+            writer.write(&RecordBatch::try_new(
+                file_schema.clone(),
+                vec![Arc::new(Int32Array::from(values))],
+            )?)?;
+            writer.close()?;
+        }
+
+        let ctx = SessionContext::new();
+        let format = Arc::new(TestFormat {
+            dir: dir.path().to_str().unwrap().to_string(),
+        });
+        let table = TableFormatProvider::try_new(&ctx.state(), format).await?;
+        ctx.register_table("t", Arc::new(table))?;
+
+        // the deleted rows of the current version are skipped
+        let results = ctx
+            .sql("SELECT v, p FROM t ORDER BY v")
+            .await?
+            .collect()
+            .await?;
+        let expected = [
+            "+----+---+",
+            "| v  | p |",
+            "+----+---+",
+            "| 0  | x |",
+            "| 3  | x |",
+            "| 10 | y |",
+            "| 11 | y |",
+            "+----+---+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        // the files are pruned by their partition values
+        let results = ctx
+            .sql("SELECT v FROM t WHERE p = 'y' ORDER BY v")
+            .await?
+            .collect()
+            .await?;
+        let expected = ["+----+", "| v  |", "+----+", "| 10 |", "| 11 |", "+----+"];
+        assert_batches_eq!(expected, &results);
+
+        let results = ctx
+            .sql("SELECT count(*) AS n FROM t VERSION AS OF 1")
+            .await?
+            .collect()
+            .await?;
+        let expected = ["+---+", "| n |", "+---+", "| 5 |", "+---+"];
+        assert_batches_eq!(expected, &results);
+        Ok(())
+    }
+}
@@ -403,12 +403,13 @@ mod tests {
             .collect()
             .await?;
 
+        // the join column is the one of the left side of the inner join
         let expected = [
             "+---------+---------+---------+",
-            "| column2 | column1 | column3 |",
+            "| column1 | column2 | column3 |",
             "+---------+---------+---------+",
-            "| 2       | 1       | 3       |",
-            "| 5       | 4       | 6       |",
+            "| 1       | 2       | 3       |",
+            "| 4       | 5       | 6       |",
             "+---------+---------+---------+",
         ];
 
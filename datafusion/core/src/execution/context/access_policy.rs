@@ -0,0 +1,86 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Row-level security and column masking policies
+
+use datafusion_common::{ResolvedTableReference, Result};
+use datafusion_expr::TablePolicy;
+
+/// The user on whose behalf a session runs queries, as seen by the
+/// [`AccessPolicyProvider`].
+///
+/// The identity is set by the application embedding DataFusion, with
+/// [`SessionState::with_identity`](super::SessionState::with_identity); it
+/// cannot be changed from SQL.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SessionIdentity {
+    user: String,
+    roles: Vec<String>,
+}
+
+impl SessionIdentity {
+    /// Create the identity of `user`, with no roles
+    pub fn new(user: impl Into<String>) -> Self {
+        Self {
+            user: user.into(),
+            roles: vec![],
+        }
+    }
+
+    /// Grant the roles `roles` to the user
+    pub fn with_roles(
+        mut self,
+        roles: impl IntoIterator<Item = impl Into<String>>,
+    ) -> Self {
+        self.roles.extend(roles.into_iter().map(Into::into));
+        self
+    }
+
+    /// The name of the user
+    pub fn user(&self) -> &str {
+        &self.user
+    }
+
+    /// The roles granted to the user
+    pub fn roles(&self) -> &[String] {
+        &self.roles
+    }
+
+    /// Returns true if the user was granted the role `role`
+    pub fn has_role(&self, role: &str) -> bool {
+        self.roles.iter().any(|r| r == role)
+    }
+}
+
+/// Decides which rows and column values of each table the users may read.
+///
+/// The provider registered with
+/// [`SessionState::with_access_policy_provider`](super::SessionState::with_access_policy_provider)
+/// is consulted whenever a query plans a scan of a table, from SQL or with
+/// [`SessionContext::table`](super::SessionContext::table). The
+/// [`TablePolicy`] it returns is enforced by filtering and projecting the
+/// scan, so it applies to every query without rewriting its SQL.
+pub trait AccessPolicyProvider: Send + Sync {
+    /// Returns the policy to enforce when the user `identity` (`None` for an
+    /// anonymous session) scans the table `table`, or `None` to allow
+    /// reading the whole table.
+    fn table_policy(
+        &self,
+        identity: Option<&SessionIdentity>,
+        table: &ResolvedTableReference,
+    ) -> Result<Option<TablePolicy>>;
+}
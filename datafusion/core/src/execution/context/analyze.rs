@@ -0,0 +1,346 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use super::{DataFrame, Result, SessionContext};
+
+use arrow::array::{StringArray, UInt64Array};
+use arrow::datatypes::DataType;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::stats::Precision;
+use datafusion_common::{
+    internal_datafusion_err, plan_datafusion_err, plan_err, Column, ColumnStatistics,
+    ScalarValue, Statistics,
+};
+use datafusion_expr::{approx_distinct, cast, count, lit, max, min, AnalyzeTable, Expr};
+
+/// A column whose statistics are computed by `ANALYZE TABLE`
+struct AnalyzedColumn {
+    /// Path of the column, e.g. `s.a` for the field `a` of the struct column `s`
+    name: String,
+    data_type: DataType,
+    /// Expression of the column in terms of the table columns
+    expr: Expr,
+    /// Index of the column in the table schema, for top level columns
+    index: Option<usize>,
+}
+
+impl SessionContext {
+    /// Computes the statistics of the columns of a table for `ANALYZE TABLE`,
+    /// stores them on the table with [`TableProvider::set_statistics`], and
+    /// returns them with a row per column.
+    ///
+    /// [`TableProvider::set_statistics`]: crate::datasource::TableProvider::set_statistics
+    pub(super) async fn analyze_table(&self, stmt: AnalyzeTable) -> Result<DataFrame> {
+        let AnalyzeTable {
+            table_name,
+            columns,
+            ..
+        } = stmt;
+        // the statistics are stored on the table, shared by all the users,
+        // so they must not be computed from a filtered or masked view of it
+        if self.state().table_policy(table_name.clone())?.is_some() {
+            return plan_err!(
+                "ANALYZE TABLE is not supported on table {table_name}, which has an access policy"
+            );
+        }
+        let provider = self.table_provider(table_name).await?;
+        let schema = provider.schema();
+        let names = if columns.is_empty() {
+            schema.fields().iter().map(|f| f.name().clone()).collect()
+        } else {
+            columns
+        };
+
+        let mut analyzed = vec![];
+        for name in names {
+            let (index, field) = schema
+                .column_with_name(&name)
+                .ok_or_else(|| plan_datafusion_err!("Column {name} not found"))?;
+            push_columns(
+                AnalyzedColumn {
+                    name: name.clone(),
+                    data_type: field.data_type().clone(),
+                    expr: Expr::Column(Column::from_name(name)),
+                    index: Some(index),
+                },
+                &mut analyzed,
+            );
+        }
+
+        // Compute all the statistics with a single scan of the table
+        let mut aggregates = vec![count(lit(1)).alias("row_count")];
+        for (i, column) in analyzed.iter().enumerate() {
+            aggregates.push(count(column.expr.clone()).alias(format!("count_{i}")));
+            if has_value_statistics(&column.data_type) {
+                let expr = column.expr.clone();
+                aggregates.push(min(expr.clone()).alias(format!("min_{i}")));
+                aggregates.push(max(expr.clone()).alias(format!("max_{i}")));
+                let expr = match column.data_type {
+                    DataType::Int8
+                    | DataType::Int16
+                    | DataType::Int32
+                    | DataType::Int64
+                    | DataType::UInt8
+                    | DataType::UInt16
+                    | DataType::UInt32
+                    | DataType::UInt64
+                    | DataType::Utf8
+                    | DataType::LargeUtf8
+                    | DataType::Binary
+                    | DataType::LargeBinary => expr,
+                    _ => cast(expr, DataType::Utf8),
+                };
+                aggregates.push(approx_distinct(expr).alias(format!("distinct_{i}")));
+            }
+        }
+        let batches = self
+            .read_table(provider.clone())?
+            .aggregate(vec![], aggregates)?
+            .collect()
+            .await?;
+        let batch = batches
+            .iter()
+            .find(|batch| batch.num_rows() > 0)
+            .ok_or_else(|| internal_datafusion_err!("Expected a row of statistics"))?;
+        let value = |name: &str| -> Result<ScalarValue> {
+            let array = batch
+                .column_by_name(name)
+                .ok_or_else(|| internal_datafusion_err!("Missing statistic {name}"))?;
+            ScalarValue::try_from_array(array, 0)
+        };
+
+        let row_count = as_u64(&value("row_count")?);
+        let mut column_statistics = provider
+            .statistics()
+            .map(|statistics| statistics.column_statistics)
+            .filter(|statistics| statistics.len() == schema.fields().len())
+            .unwrap_or_else(|| Statistics::unknown_column(&schema));
+        let mut null_counts = vec![];
+        let mut min_values = vec![];
+        let mut max_values = vec![];
+        let mut distinct_counts = vec![];
+        for (i, column) in analyzed.iter().enumerate() {
+            let null_count = row_count - as_u64(&value(&format!("count_{i}"))?);
+            let (min_value, max_value, distinct_count) =
+                if has_value_statistics(&column.data_type) {
+                    (
+                        value(&format!("min_{i}"))?,
+                        value(&format!("max_{i}"))?,
+                        Some(as_u64(&value(&format!("distinct_{i}"))?)),
+                    )
+                } else {
+                    (ScalarValue::Null, ScalarValue::Null, None)
+                };
+
+            if let Some(index) = column.index {
+                column_statistics[index] = ColumnStatistics {
+                    null_count: Precision::Exact(null_count as usize),
+                    max_value: to_precision(&max_value),
+                    min_value: to_precision(&min_value),
+                    // The number of distinct values is estimated
+                    distinct_count: distinct_count
+                        .map(|count| Precision::Inexact(count as usize))
+                        .unwrap_or(Precision::Absent),
+                    distinct_sketch: None,
+                };
+            }
+            null_counts.push(null_count);
+            min_values.push((!min_value.is_null()).then(|| min_value.to_string()));
+            max_values.push((!max_value.is_null()).then(|| max_value.to_string()));
+            distinct_counts.push(distinct_count);
+        }
+
+        provider.set_statistics(Statistics {
+            num_rows: Precision::Exact(row_count as usize),
+            total_byte_size: Precision::Absent,
+            column_statistics,
+        })?;
+
+        let batch = RecordBatch::try_new(
+            Arc::new(AnalyzeTable::output_schema()),
+            vec![
+                Arc::new(StringArray::from_iter_values(
+                    analyzed.iter().map(|column| &column.name),
+                )),
+                Arc::new(StringArray::from_iter_values(
+                    analyzed.iter().map(|column| column.data_type.to_string()),
+                )),
+                Arc::new(UInt64Array::from(vec![row_count; analyzed.len()])),
+                Arc::new(UInt64Array::from(null_counts)),
+                Arc::new(StringArray::from(min_values)),
+                Arc::new(StringArray::from(max_values)),
+                Arc::new(UInt64Array::from(distinct_counts)),
+            ],
+        )?;
+        self.read_batch(batch)
+    }
+}
+
+/// Appends `column` to `columns`, followed by its nested leaf columns if it
+/// is a struct column
+fn push_columns(column: AnalyzedColumn, columns: &mut Vec<AnalyzedColumn>) {
+    let nested = match &column.data_type {
+        DataType::Struct(fields) => fields
+            .iter()
+            .map(|field| AnalyzedColumn {
+                name: format!("{}.{}", column.name, field.name()),
+                data_type: field.data_type().clone(),
+                expr: column.expr.clone().field(field.name()),
+                index: None,
+            })
+            .collect(),
+        _ => vec![],
+    };
+    columns.push(column);
+    for column in nested {
+        push_columns(column, columns);
+    }
+}
+
+/// Returns true if the minimum, maximum and number of distinct values of
+/// columns of type `data_type` are computed, in addition to their null count
+fn has_value_statistics(data_type: &DataType) -> bool {
+    match data_type {
+        DataType::Boolean
+        | DataType::Utf8
+        | DataType::LargeUtf8
+        | DataType::Binary
+        | DataType::LargeBinary => true,
+        DataType::Float16
+        | DataType::Decimal256(_, _)
+        | DataType::Interval(_)
+        | DataType::Duration(_) => false,
+        data_type => data_type.is_primitive(),
+    }
+}
+
+fn as_u64(value: &ScalarValue) -> u64 {
+    match value {
+        ScalarValue::Int64(Some(value)) => *value as u64,
+        ScalarValue::UInt64(Some(value)) => *value,
+        _ => 0,
+    }
+}
+
+fn to_precision(value: &ScalarValue) -> Precision<ScalarValue> {
+    if value.is_null() {
+        Precision::Absent
+    } else {
+        Precision::Exact(value.clone())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+    use crate::datasource::{MemTable, TableProvider};
+    use crate::execution::options::CsvReadOptions;
+
+    use arrow::array::{ArrayRef, Float64Array, Int64Array, StructArray};
+    use arrow::datatypes::Field;
+
+    #[tokio::test]
+    async fn analyze_memory_table() -> Result<()> {
+        let x = Float64Array::from(vec![Some(1.5), None, Some(2.5)]);
+        let s = StructArray::from(vec![(
+            Arc::new(Field::new("x", DataType::Float64, true)),
+            Arc::new(x) as ArrayRef,
+        )]);
+        let batch = RecordBatch::try_from_iter(vec![
+            ("a", Arc::new(Int64Array::from(vec![1, 2, 2])) as ArrayRef),
+            ("s", Arc::new(s) as ArrayRef),
+        ])?;
+        let table = Arc::new(MemTable::try_new(batch.schema(), vec![vec![batch]])?);
+        let ctx = SessionContext::new();
+        ctx.register_table("t", table.clone())?;
+
+        let results = ctx
+            .sql("ANALYZE TABLE t")
+            .await?
+            .select_columns(&[
+                "column_name",
+                "row_count",
+                "null_count",
+                "min_value",
+                "max_value",
+                "distinct_count",
+            ])?
+            .collect()
+            .await?;
+        let expected = [
+            "+-------------+-----------+------------+-----------+-----------+----------------+",
+            "| column_name | row_count | null_count | min_value | max_value | distinct_count |",
+            "+-------------+-----------+------------+-----------+-----------+----------------+",
+            "| a           | 3         | 0          | 1         | 2         | 2              |",
+            "| s           | 3         | 0          |           |           |                |",
+            "| s.x         | 3         | 1          | 1.5       | 2.5       | 2              |",
+            "+-------------+-----------+------------+-----------+-----------+----------------+",
+        ];
+        assert_batches_eq!(expected, &results);
+
+        let statistics = table.statistics().unwrap();
+        assert_eq!(statistics.num_rows, Precision::Exact(3));
+        assert_eq!(
+            statistics.column_statistics[0],
+            ColumnStatistics {
+                null_count: Precision::Exact(0),
+                max_value: Precision::Exact(ScalarValue::Int64(Some(2))),
+                min_value: Precision::Exact(ScalarValue::Int64(Some(1))),
+                distinct_count: Precision::Inexact(2),
+                distinct_sketch: None,
+            }
+        );
+        assert_eq!(
+            statistics.column_statistics[1].null_count,
+            Precision::Exact(0)
+        );
+        assert_eq!(statistics.column_statistics[1].min_value, Precision::Absent);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn analyze_listing_table() -> Result<()> {
+        let ctx = SessionContext::new();
+        ctx.register_csv("t", "tests/data/example.csv", CsvReadOptions::new())
+            .await?;
+        ctx.sql("ANALYZE TABLE t COLUMNS (a)")
+            .await?
+            .collect()
+            .await?;
+
+        let table = ctx.table_provider("t").await?;
+        let statistics = table.statistics().unwrap();
+        assert_eq!(statistics.num_rows, Precision::Exact(1));
+        assert_eq!(
+            statistics.column_statistics[0].min_value,
+            Precision::Exact(ScalarValue::Int64(Some(1)))
+        );
+        assert_eq!(
+            statistics.column_statistics[1],
+            ColumnStatistics::new_unknown()
+        );
+
+        // The table may change after it is analyzed, so scans use the
+        // statistics as estimates
+        let plan = ctx.table("t").await?.create_physical_plan().await?;
+        assert_eq!(plan.statistics()?.num_rows, Precision::Inexact(1));
+        Ok(())
+    }
+}
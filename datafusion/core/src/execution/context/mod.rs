@@ -34,26 +34,37 @@ use crate::{
     config::ConfigOptions,
     dataframe::DataFrame,
     datasource::{
+        changes::{TableChanges, TableChangesProvider, TABLE_CHANGES_FUNCTION},
         cte_worktable::CteWorkTable,
-        function::{TableFunction, TableFunctionImpl},
+        function::{
+            LateralTableFunction, LateralTableFunctionImpl, TableFunction,
+            TableFunctionImpl,
+        },
         listing::{ListingOptions, ListingTable, ListingTableConfig, ListingTableUrl},
         object_store::ObjectStoreUrl,
         provider::{DefaultTableFactory, TableProviderFactory},
     },
-    datasource::{provider_as_source, MemTable, TableProvider, ViewTable},
+    datasource::{
+        provider_as_source, source_as_provider, MemTable, SnapshotToken, TableProvider,
+        ViewTable,
+    },
     error::{DataFusionError, Result},
     execution::{options::ArrowReadOptions, runtime_env::RuntimeEnv, FunctionRegistry},
     logical_expr::AggregateUDF,
     logical_expr::{
         CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateFunction,
         CreateMemoryTable, CreateView, DropCatalogSchema, DropFunction, DropTable,
-        DropView, Explain, LogicalPlan, LogicalPlanBuilder, PlanType, SetVariable,
-        TableSource, TableType, ToStringifiedPlan, UNNAMED_TABLE,
+        DropView, Explain, LogicalPlan, LogicalPlanBuilder, PlanType, ResetVariable,
+        SetVariable, TableSource, TableType, ToStringifiedPlan, UNNAMED_TABLE,
     },
     optimizer::analyzer::{Analyzer, AnalyzerRule},
-    optimizer::optimizer::{Optimizer, OptimizerConfig, OptimizerRule},
+    optimizer::optimizer::{Optimizer, OptimizerConfig, OptimizerRule, OptimizerRuleRun},
     physical_optimizer::optimizer::{PhysicalOptimizer, PhysicalOptimizerRule},
-    physical_plan::{udf::ScalarUDF, ExecutionPlan},
+    physical_plan::{
+        scheduler::{DefaultScheduler, ExecutionScheduler, TracingScheduler},
+        udf::ScalarUDF,
+        ExecutionPlan,
+    },
     physical_planner::{DefaultPhysicalPlanner, PhysicalPlanner},
     variable::{VarProvider, VarType},
 };
@@ -69,14 +80,16 @@ use datafusion_common::{
     alias::AliasGenerator,
     config::{ConfigExtension, TableOptions},
     exec_err, not_impl_err, plan_datafusion_err, plan_err,
-    tree_node::{TreeNodeRecursion, TreeNodeVisitor},
+    tree_node::{
+        Transformed, TransformedResult, TreeNode, TreeNodeRecursion, TreeNodeVisitor,
+    },
     SchemaReference, TableReference,
 };
 use datafusion_execution::registry::SerializerRegistry;
 use datafusion_expr::{
     logical_plan::{DdlStatement, Statement},
     var_provider::is_system_variables,
-    Expr, StringifiedPlan, UserDefinedLogicalNode, WindowUDF,
+    Expr, StringifiedPlan, TablePolicy, UserDefinedLogicalNode, WindowUDF,
 };
 use datafusion_sql::{
     parser::{CopyToSource, CopyToStatement, DFParser},
@@ -88,6 +101,7 @@ use async_trait::async_trait;
 use chrono::{DateTime, Utc};
 use parking_lot::RwLock;
 use sqlparser::dialect::dialect_from_str;
+use tracing::{info_span, Instrument};
 use url::Url;
 use uuid::Uuid;
 
@@ -96,11 +110,22 @@ pub use datafusion_execution::TaskContext;
 pub use datafusion_expr::execution_props::ExecutionProps;
 use datafusion_expr::expr_rewriter::FunctionRewrite;
 
+mod access_policy;
+mod analyze;
 mod avro;
 mod csv;
 mod json;
 #[cfg(feature = "parquet")]
 mod parquet;
+mod script;
+mod sql_function;
+mod statement_rewriter;
+mod transaction;
+
+pub use access_policy::{AccessPolicyProvider, SessionIdentity};
+pub use script::StatementResult;
+pub use sql_function::{InlineSqlFunctions, SqlFunction};
+pub use statement_rewriter::{StatementKind, StatementMetadata, StatementRewriter};
 
 /// DataFilePaths adds a method to convert strings and vector of strings to vector of [`ListingTableUrl`] URLs.
 /// This allows methods such [`SessionContext::read_csv`] and [`SessionContext::read_avro`]
@@ -274,6 +299,9 @@ impl SessionContext {
     ///
     /// See [`Self::new_with_config_rt`] for more details on resource
     /// limits.
+    ///
+    /// The options of `config` are not validated, see
+    /// [`Self::try_new_with_config_rt`] to reject invalid options.
     pub fn new_with_config(config: SessionConfig) -> Self {
         let runtime = Arc::new(RuntimeEnv::default());
         Self::new_with_config_rt(config, runtime)
@@ -299,11 +327,27 @@ impl SessionContext {
     /// memory used) across all DataFusion queries in a process,
     /// all `SessionContext`'s should be configured with the
     /// same `RuntimeEnv`.
+    ///
+    /// The options of `config` are not validated, see
+    /// [`Self::try_new_with_config_rt`] to reject invalid options.
     pub fn new_with_config_rt(config: SessionConfig, runtime: Arc<RuntimeEnv>) -> Self {
         let state = SessionState::new_with_config_rt(config, runtime);
         Self::new_with_state(state)
     }
 
+    /// Creates a new `SessionContext` using the provided
+    /// [`SessionConfig`] and a [`RuntimeEnv`], after checking that the
+    /// options are valid and consistent with each other.
+    ///
+    /// See [`SessionConfig::validate`] for the checked options.
+    pub fn try_new_with_config_rt(
+        config: SessionConfig,
+        runtime: Arc<RuntimeEnv>,
+    ) -> Result<Self> {
+        config.validate()?;
+        Ok(Self::new_with_config_rt(config, runtime))
+    }
+
     /// Creates a new `SessionContext` using the provided
     /// [`SessionConfig`] and a [`RuntimeEnv`].
     #[deprecated(since = "32.0.0", note = "Use SessionState::new_with_config_rt")]
@@ -339,6 +383,29 @@ impl SessionContext {
         self
     }
 
+    /// Registers an [`AccessPolicyProvider`] deciding which rows and column
+    /// values the queries of this session may read
+    pub fn with_access_policy_provider(
+        self,
+        access_policy_provider: Arc<dyn AccessPolicyProvider>,
+    ) -> Self {
+        self.state
+            .write()
+            .set_access_policy_provider(access_policy_provider);
+        self
+    }
+
+    /// Sets the user on whose behalf this session runs queries
+    pub fn set_identity(&self, identity: SessionIdentity) {
+        self.state.write().set_identity(identity);
+    }
+
+    /// Add `rewriter` to the end of the list of [`StatementRewriter`]s
+    /// rewriting the plans of the SQL statements before their optimization
+    pub fn add_statement_rewriter(&self, rewriter: Arc<dyn StatementRewriter>) {
+        self.state.write().statement_rewriters.push(rewriter);
+    }
+
     /// Registers the [`RecordBatch`] as the specified table name
     pub fn register_batch(
         &self,
@@ -501,10 +568,21 @@ impl SessionContext {
                 }
                 .await
             }
-            // TODO what about the other statements (like TransactionStart and TransactionEnd)
+            LogicalPlan::Statement(Statement::TransactionStart(stmt)) => {
+                self.transaction_start(stmt).await
+            }
+            LogicalPlan::Statement(Statement::TransactionEnd(stmt)) => {
+                self.transaction_end(stmt).await
+            }
             LogicalPlan::Statement(Statement::SetVariable(stmt)) => {
                 self.set_variable(stmt).await
             }
+            LogicalPlan::Statement(Statement::ResetVariable(stmt)) => {
+                self.reset_variable(stmt).await
+            }
+            LogicalPlan::Statement(Statement::AnalyzeTable(stmt)) => {
+                self.analyze_table(stmt).await
+            }
 
             plan => Ok(DataFrame::new(self.state(), plan)),
         }
@@ -758,6 +836,17 @@ impl SessionContext {
         self.return_empty_dataframe()
     }
 
+    async fn reset_variable(&self, stmt: ResetVariable) -> Result<DataFrame> {
+        let mut state = self.state.write();
+        match stmt.variable {
+            Some(variable) => state.config.options_mut().reset(&variable)?,
+            None => state.config.options_mut().reset_all(),
+        }
+        drop(state);
+
+        self.return_empty_dataframe()
+    }
+
     async fn create_custom_table(
         &self,
         cmd: &CreateExternalTable,
@@ -813,7 +902,11 @@ impl SessionContext {
 
             match function_factory {
                 Some(f) => f.create(&state, stmt).await?,
-                _ => Err(DataFusionError::Configuration(
+                // without a factory, functions with a SQL body are macros
+                None if stmt.params.return_.is_some() => RegisterFunction::Scalar(
+                    Arc::new(ScalarUDF::new_from_impl(SqlFunction::try_new(stmt)?)),
+                ),
+                None => Err(DataFusionError::Configuration(
                     "Function factory has not been configured".into(),
                 ))?,
             }
@@ -875,6 +968,29 @@ impl SessionContext {
         );
     }
 
+    /// Register a lateral table UDF with this context
+    ///
+    /// Unlike the functions registered with [`Self::register_udtf`], the
+    /// arguments of lateral table functions may reference the columns of the
+    /// preceding items of the `FROM` clause:
+    ///
+    /// ```sql
+    /// SELECT * FROM t CROSS JOIN LATERAL f(t.a)
+    /// ```
+    pub fn register_lateral_udtf(
+        &self,
+        name: &str,
+        fun: Arc<dyn LateralTableFunctionImpl>,
+    ) {
+        self.state.write().lateral_table_functions.insert(
+            name.to_owned(),
+            Arc::new(ScalarUDF::new_from_impl(LateralTableFunction::new(
+                name.to_owned(),
+                fun,
+            ))),
+        );
+    }
+
     /// Registers a scalar UDF within this context.
     ///
     /// Note in SQL queries, function names are looked up using
@@ -1053,7 +1169,15 @@ impl SessionContext {
         let table_path = ListingTableUrl::parse(table_path)?;
         let resolved_schema = match provided_schema {
             Some(s) => s,
-            None => options.infer_schema(&self.state(), &table_path, options.column_hints.clone()).await?,
+            None => {
+                options
+                    .infer_schema(
+                        &self.state(),
+                        &table_path,
+                        options.column_hints.clone(),
+                    )
+                    .await?
+            }
         };
         let config = ListingTableConfig::new(table_path)
             .with_listing_options(options)
@@ -1174,13 +1298,16 @@ impl SessionContext {
     ) -> Result<DataFrame> {
         let table_ref: TableReference = table_ref.into();
         let provider = self.table_provider(table_ref.clone()).await?;
-        let plan = LogicalPlanBuilder::scan(
-            table_ref,
-            provider_as_source(Arc::clone(&provider)),
-            None,
-        )?
-        .build()?;
-        Ok(DataFrame::new(self.state(), plan))
+        let state = self.state();
+        let policy = state.table_policy(table_ref.clone())?;
+        let source = provider_as_source(Arc::clone(&provider));
+        let source = state.view_with_policies(&source)?.unwrap_or(source);
+        let plan = LogicalPlanBuilder::scan(table_ref, source, None)?.build()?;
+        let plan = match policy {
+            Some(policy) => policy.apply(plan)?,
+            None => plan,
+        };
+        Ok(DataFrame::new(state, plan))
     }
 
     /// Return a [`TableProvider`] for the specified table.
@@ -1330,6 +1457,8 @@ pub enum RegisterFunction {
 pub struct SessionState {
     /// A unique UUID that identifies the session
     session_id: String,
+    /// Rewrite the logical plans of the SQL statements before their analysis
+    statement_rewriters: Vec<Arc<dyn StatementRewriter>>,
     /// Responsible for analyzing and rewrite a logical plan before optimization
     analyzer: Analyzer,
     /// Responsible for optimizing a logical plan
@@ -1342,6 +1471,9 @@ pub struct SessionState {
     catalog_list: Arc<dyn CatalogProviderList>,
     /// Table Functions
     table_functions: HashMap<String, Arc<TableFunction>>,
+    /// Lateral table functions, planned as scalar functions returning the
+    /// rows of the table for each input row
+    lateral_table_functions: HashMap<String, Arc<ScalarUDF>>,
     /// Scalar functions that are registered with the context
     scalar_functions: HashMap<String, Arc<ScalarUDF>>,
     /// Aggregate functions registered in the context
@@ -1372,6 +1504,14 @@ pub struct SessionState {
     /// It will be invoked on `CREATE FUNCTION` statements.
     /// thus, changing dialect o PostgreSql is required
     function_factory: Option<Arc<dyn FunctionFactory>>,
+    /// Decides which rows and column values the scans of each table return
+    access_policy_provider: Option<Arc<dyn AccessPolicyProvider>>,
+    /// The user on whose behalf the queries are run
+    identity: Option<SessionIdentity>,
+    /// Responsible for driving the execution of physical plans
+    scheduler: Arc<dyn ExecutionScheduler>,
+    /// The versions of the tables read by the scans, by resolved table name
+    table_snapshots: HashMap<String, SnapshotToken>,
 }
 
 impl Debug for SessionState {
@@ -1386,6 +1526,9 @@ impl Debug for SessionState {
 impl SessionState {
     /// Returns new [`SessionState`] using the provided
     /// [`SessionConfig`] and [`RuntimeEnv`].
+    ///
+    /// The options of `config` are not validated, see
+    /// [`SessionConfig::validate`] to check them beforehand.
     pub fn new_with_config_rt(config: SessionConfig, runtime: Arc<RuntimeEnv>) -> Self {
         let catalog_list =
             Arc::new(MemoryCatalogProviderList::new()) as Arc<dyn CatalogProviderList>;
@@ -1418,6 +1561,18 @@ impl SessionState {
         table_factories.insert("NDJSON".into(), Arc::new(DefaultTableFactory::new()));
         table_factories.insert("AVRO".into(), Arc::new(DefaultTableFactory::new()));
         table_factories.insert("ARROW".into(), Arc::new(DefaultTableFactory::new()));
+        #[cfg(feature = "flight")]
+        table_factories.insert(
+            "FLIGHT".into(),
+            Arc::new(crate::datasource::flight::FlightTableFactory::default()),
+        );
+        #[cfg(feature = "postgres")]
+        table_factories.insert(
+            "POSTGRES".into(),
+            Arc::new(
+                crate::datasource::federation::postgres::PostgresTableFactory::default(),
+            ),
+        );
 
         if config.create_default_catalog_and_schema() {
             let default_catalog = MemoryCatalogProvider::new();
@@ -1450,6 +1605,7 @@ impl SessionState {
             query_planner: Arc::new(DefaultQueryPlanner {}),
             catalog_list,
             table_functions: HashMap::new(),
+            lateral_table_functions: HashMap::new(),
             scalar_functions: HashMap::new(),
             aggregate_functions: HashMap::new(),
             window_functions: HashMap::new(),
@@ -1462,6 +1618,11 @@ impl SessionState {
             runtime_env: runtime,
             table_factories,
             function_factory: None,
+            access_policy_provider: None,
+            identity: None,
+            scheduler: Arc::new(DefaultScheduler),
+            statement_rewriters: vec![],
+            table_snapshots: HashMap::new(),
         };
 
         // register built in functions
@@ -1476,6 +1637,19 @@ impl SessionState {
         functions_aggregate::register_all(&mut new_self)
             .expect("can not register aggregate functions");
 
+        // inline the functions created with a SQL body by `CREATE FUNCTION`
+        new_self
+            .analyzer
+            .add_function_rewrite(Arc::new(InlineSqlFunctions::default()));
+
+        // compare, sort and group strings in their collation (if enabled)
+        #[cfg(feature = "string_expressions")]
+        new_self.analyzer.rules.push(Arc::new(
+            crate::optimizer::analyzer::collation::ApplyCollations::new(
+                functions::string::collation_key(),
+            ),
+        ));
+
         new_self
     }
     /// Returns new [`SessionState`] using the provided
@@ -1625,8 +1799,21 @@ impl SessionState {
         self
     }
 
+    /// Add `rewriter` to the end of the list of [`StatementRewriter`]s
+    /// rewriting the plans of the SQL statements before their analysis
+    pub fn add_statement_rewriter(
+        mut self,
+        rewriter: Arc<dyn StatementRewriter>,
+    ) -> Self {
+        self.statement_rewriters.push(rewriter);
+        self
+    }
+
     /// Add `optimizer_rule` to the end of the list of
     /// [`OptimizerRule`]s used to rewrite queries.
+    ///
+    /// The rule runs at the end of each pass, unless it declares the rules it
+    /// must run after or before, see [`Optimizer::ordered_rules`].
     pub fn add_optimizer_rule(
         mut self,
         optimizer_rule: Arc<dyn OptimizerRule + Send + Sync>,
@@ -1668,6 +1855,170 @@ impl SessionState {
         self.function_factory = Some(function_factory);
     }
 
+    /// Registers an [`AccessPolicyProvider`] deciding which rows and column
+    /// values the queries of this session may read
+    pub fn with_access_policy_provider(
+        mut self,
+        access_policy_provider: Arc<dyn AccessPolicyProvider>,
+    ) -> Self {
+        self.access_policy_provider = Some(access_policy_provider);
+        self
+    }
+
+    /// Registers an [`AccessPolicyProvider`] deciding which rows and column
+    /// values the queries of this session may read
+    pub fn set_access_policy_provider(
+        &mut self,
+        access_policy_provider: Arc<dyn AccessPolicyProvider>,
+    ) {
+        self.access_policy_provider = Some(access_policy_provider);
+    }
+
+    /// Sets the user on whose behalf this session runs queries
+    pub fn with_identity(mut self, identity: SessionIdentity) -> Self {
+        self.identity = Some(identity);
+        self
+    }
+
+    /// Sets the user on whose behalf this session runs queries
+    pub fn set_identity(&mut self, identity: SessionIdentity) {
+        self.identity = Some(identity);
+    }
+
+    /// Return the user on whose behalf this session runs queries, if any
+    pub fn identity(&self) -> Option<&SessionIdentity> {
+        self.identity.as_ref()
+    }
+
+    /// Returns the policy of the registered [`AccessPolicyProvider`] for
+    /// scans of the table `table_ref` by the session user
+    pub(crate) fn table_policy(
+        &self,
+        table_ref: impl Into<TableReference>,
+    ) -> Result<Option<TablePolicy>> {
+        match &self.access_policy_provider {
+            Some(provider) => provider
+                .table_policy(self.identity.as_ref(), &self.resolve_table_ref(table_ref)),
+            None => Ok(None),
+        }
+    }
+
+    /// Enforces the policies of the session user on the table scans of
+    /// `plan`, a plan planned without policies, such as the plan of a view
+    /// or of a prepared statement. The scans of the views read by `plan`
+    /// are rewritten the same way.
+    pub(crate) fn apply_table_policies(&self, plan: LogicalPlan) -> Result<LogicalPlan> {
+        if self.access_policy_provider.is_none() {
+            return Ok(plan);
+        }
+        plan.transform_up(|plan| {
+            let LogicalPlan::TableScan(mut scan) = plan else {
+                return Ok(Transformed::no(plan));
+            };
+            if let Some(source) = self.view_with_policies(&scan.source)? {
+                scan.source = source;
+                return Ok(Transformed::yes(LogicalPlan::TableScan(scan)));
+            }
+            match self.table_policy(scan.table_name.clone())? {
+                Some(policy) => Ok(Transformed::yes(
+                    policy.apply(LogicalPlan::TableScan(scan))?,
+                )),
+                None => Ok(Transformed::no(LogicalPlan::TableScan(scan))),
+            }
+        })
+        .data()
+    }
+
+    /// If `source` is a view, returns the view with the policies of the
+    /// session user enforced on the scans of its plan, which was planned
+    /// without policies when the view was created
+    fn view_with_policies(
+        &self,
+        source: &Arc<dyn TableSource>,
+    ) -> Result<Option<Arc<dyn TableSource>>> {
+        if self.access_policy_provider.is_none() {
+            return Ok(None);
+        }
+        let Ok(provider) = source_as_provider(source) else {
+            return Ok(None);
+        };
+        let Some(view) = provider.as_any().downcast_ref::<ViewTable>() else {
+            return Ok(None);
+        };
+        let plan = self.apply_table_policies(view.logical_plan().clone())?;
+        let view = ViewTable::try_new(plan, view.definition().cloned())?;
+        Ok(Some(provider_as_source(Arc::new(view))))
+    }
+
+    /// Pins the version `snapshot` of the table `table_ref` for the scans of
+    /// all the queries planned with this state, instead of the version
+    /// returned by [`TableProvider::snapshot`] when each query is planned
+    pub fn with_table_snapshot(
+        mut self,
+        table_ref: impl Into<TableReference>,
+        snapshot: SnapshotToken,
+    ) -> Self {
+        let table_ref = self.resolve_table_ref(table_ref).to_string();
+        self.table_snapshots.insert(table_ref, snapshot);
+        self
+    }
+
+    /// Returns the version of the table `table_ref` the scans read, if the
+    /// table is versioned
+    pub fn table_snapshot(
+        &self,
+        table_ref: impl Into<TableReference>,
+    ) -> Option<&SnapshotToken> {
+        self.table_snapshots
+            .get(&self.resolve_table_ref(table_ref).to_string())
+    }
+
+    /// Resolves the current versions of the tables scanned by `plan` which
+    /// are not pinned, returning the state to plan it with if any is
+    /// versioned
+    async fn with_resolved_snapshots(
+        &self,
+        plan: &LogicalPlan,
+    ) -> Result<Option<SessionState>> {
+        let mut scans = vec![];
+        plan.apply_with_subqueries(|plan| {
+            // the scans of a past version don't read the current one
+            if let LogicalPlan::TableScan(scan) = plan {
+                let table_ref = self.resolve_table_ref(scan.table_name.clone());
+                let table_ref = table_ref.to_string();
+                if scan.version.is_none()
+                    && !self.table_snapshots.contains_key(&table_ref)
+                    && scans.iter().all(|(name, _)| name != &table_ref)
+                {
+                    if let Ok(provider) = source_as_provider(&scan.source) {
+                        scans.push((table_ref, provider));
+                    }
+                }
+            }
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+
+        let mut snapshots = vec![];
+        for (table_ref, provider) in scans {
+            if let Some(snapshot) = provider.snapshot(self).await? {
+                snapshots.push((table_ref, snapshot));
+            }
+        }
+        if snapshots.is_empty() {
+            return Ok(None);
+        }
+        let mut state = self.clone();
+        state.table_snapshots.extend(snapshots);
+        Ok(Some(state))
+    }
+
+    /// Override the default [`ExecutionScheduler`] used to execute
+    /// physical plans, e.g. with a morsel-driven scheduler
+    pub fn with_scheduler(mut self, scheduler: Arc<dyn ExecutionScheduler>) -> Self {
+        self.scheduler = scheduler;
+        self
+    }
+
     /// Replace the extension [`SerializerRegistry`]
     pub fn with_serializer_registry(
         mut self,
@@ -1696,6 +2047,11 @@ impl SessionState {
         sql: &str,
         dialect: &str,
     ) -> Result<datafusion_sql::parser::Statement> {
+        let _span = info_span!(
+            "datafusion.parse_sql",
+            trace_parent = self.config_options().execution.trace_parent.as_deref(),
+        )
+        .entered();
         let dialect = dialect_from_str(dialect).ok_or_else(|| {
             plan_datafusion_err!(
                 "Unsupported SQL dialect: {dialect}. Available dialects: \
@@ -1729,8 +2085,13 @@ impl SessionState {
         // Getting `TableProviders` is async but planing is not -- thus pre-fetch
         // table providers for all relations referenced in this query
         let mut relations = hashbrown::HashSet::with_capacity(10);
+        // The tables whose changes are scanned by `table_changes('t', ...)`
+        let mut changed_tables = vec![];
 
-        struct RelationVisitor<'a>(&'a mut hashbrown::HashSet<ObjectName>);
+        struct RelationVisitor<'a>(
+            &'a mut hashbrown::HashSet<ObjectName>,
+            &'a mut Vec<TableReference>,
+        );
 
         impl<'a> RelationVisitor<'a> {
             /// Record that `relation` was used in this statement
@@ -1747,6 +2108,31 @@ impl SessionState {
                 ControlFlow::Continue(())
             }
 
+            fn pre_visit_table_factor(
+                &mut self,
+                table_factor: &TableFactor,
+            ) -> ControlFlow<()> {
+                if let TableFactor::Table {
+                    name,
+                    args: Some(args),
+                    ..
+                } = table_factor
+                {
+                    if let (
+                        [Ident { value, .. }],
+                        Some(FunctionArg::Unnamed(FunctionArgExpr::Expr(Expr::Value(
+                            Value::SingleQuotedString(table),
+                        )))),
+                    ) = (name.0.as_slice(), args.first())
+                    {
+                        if value.eq_ignore_ascii_case(TABLE_CHANGES_FUNCTION) {
+                            self.1.push(TableReference::from(table.as_str()));
+                        }
+                    }
+                }
+                ControlFlow::Continue(())
+            }
+
             fn pre_visit_statement(&mut self, statement: &Statement) -> ControlFlow<()> {
                 if let Statement::ShowCreate {
                     obj_type: ShowCreateObject::Table | ShowCreateObject::View,
@@ -1759,7 +2145,7 @@ impl SessionState {
             }
         }
 
-        let mut visitor = RelationVisitor(&mut relations);
+        let mut visitor = RelationVisitor(&mut relations, &mut changed_tables);
         fn visit_statement(statement: &DFStatement, visitor: &mut RelationVisitor<'_>) {
             match statement {
                 DFStatement::Statement(s) => {
@@ -1781,6 +2167,10 @@ impl SessionState {
                 DFStatement::Explain(explain) => {
                     visit_statement(&explain.statement, visitor)
                 }
+                DFStatement::AnalyzeTable(analyze) => {
+                    visitor.insert(&analyze.table_name);
+                }
+                DFStatement::Reset(_) => {}
             }
         }
 
@@ -1798,10 +2188,16 @@ impl SessionState {
 
         let enable_ident_normalization =
             self.config.options().sql_parser.enable_ident_normalization;
-        relations
+        let mut references = relations
             .into_iter()
             .map(|x| object_name_to_table_reference(x, enable_ident_normalization))
-            .collect::<Result<_>>()
+            .collect::<Result<Vec<_>>>()?;
+        for table in changed_tables {
+            if !references.contains(&table) {
+                references.push(table);
+            }
+        }
+        Ok(references)
     }
 
     /// Convert an AST Statement into a LogicalPlan
@@ -1810,10 +2206,25 @@ impl SessionState {
         statement: datafusion_sql::parser::Statement,
     ) -> Result<LogicalPlan> {
         let references = self.resolve_table_references(&statement)?;
-
+        let metadata = (!self.statement_rewriters.is_empty())
+            .then(|| StatementMetadata::new(&statement, references.clone()));
+
+        // Views and prepared statements are planned without the policies,
+        // which are enforced for the user reading the view or executing the
+        // statement, see `apply_table_policies`
+        let table_policies = !matches!(
+            &statement,
+            datafusion_sql::parser::Statement::Statement(statement)
+                if matches!(
+                    statement.as_ref(),
+                    sqlparser::ast::Statement::CreateView { .. }
+                        | sqlparser::ast::Statement::Prepare { .. }
+                )
+        );
         let mut provider = SessionContextProvider {
             state: self,
             tables: HashMap::with_capacity(references.len()),
+            table_policies,
         };
 
         let enable_ident_normalization =
@@ -1838,7 +2249,33 @@ impl SessionState {
                 enable_ident_normalization,
             },
         );
-        query.statement_to_plan(statement)
+        let plan = info_span!(
+            "datafusion.sql_to_logical_plan",
+            trace_parent = self.config_options().execution.trace_parent.as_deref(),
+        )
+        .in_scope(|| query.statement_to_plan(statement))?;
+
+        match metadata {
+            Some(metadata) => self.rewrite_statement_plan(plan, &metadata),
+            None => Ok(plan),
+        }
+    }
+
+    /// Rewrite `plan`, the plan of the statement `metadata`, with the
+    /// [`StatementRewriter`]s applying to its kind
+    fn rewrite_statement_plan(
+        &self,
+        mut plan: LogicalPlan,
+        metadata: &StatementMetadata,
+    ) -> Result<LogicalPlan> {
+        for rewriter in &self.statement_rewriters {
+            if rewriter.applies_to(metadata.kind()) {
+                plan = rewriter.rewrite(plan, metadata, self).map_err(|e| {
+                    e.context(format!("Statement rewriter '{}' failed", rewriter.name()))
+                })?;
+            }
+        }
+        Ok(plan)
     }
 
     /// Creates a [`LogicalPlan`] from the provided SQL string. This
@@ -1859,6 +2296,11 @@ impl SessionState {
 
     /// Optimizes the logical plan by applying optimizer rules.
     pub fn optimize(&self, plan: &LogicalPlan) -> Result<LogicalPlan> {
+        let _span = info_span!(
+            "datafusion.optimize_logical_plan",
+            trace_parent = self.config_options().execution.trace_parent.as_deref(),
+        )
+        .entered();
         if let LogicalPlan::Explain(e) = plan {
             let mut stringified_plans = e.stringified_plans.clone();
 
@@ -1895,15 +2337,30 @@ impl SessionState {
                 .push(analyzed_plan.to_stringified(PlanType::FinalAnalyzedLogicalPlan));
 
             // optimize the child plan, capturing the output of each optimizer
-            let optimized_plan = self.optimizer.optimize(
-                analyzed_plan,
-                self,
-                |optimized_plan, optimizer| {
-                    let optimizer_name = optimizer.name().to_string();
-                    let plan_type = PlanType::OptimizedLogicalPlan { optimizer_name };
-                    stringified_plans.push(optimized_plan.to_stringified(plan_type));
-                },
-            );
+            let observer = |optimized_plan: &LogicalPlan,
+                            optimizer: &dyn OptimizerRule| {
+                let optimizer_name = optimizer.name().to_string();
+                let plan_type = PlanType::OptimizedLogicalPlan { optimizer_name };
+                stringified_plans.push(optimized_plan.to_stringified(plan_type));
+            };
+            let mut runs = vec![];
+            let optimized_plan = if self.config_options().optimizer.record_rule_runs {
+                self.optimizer.optimize_with_runs(
+                    analyzed_plan,
+                    self,
+                    observer,
+                    &mut runs,
+                )
+            } else {
+                self.optimizer.optimize(analyzed_plan, self, observer)
+            };
+            if !runs.is_empty() {
+                let runs = runs.iter().map(ToString::to_string).collect::<Vec<_>>();
+                stringified_plans.push(StringifiedPlan::new(
+                    PlanType::OptimizerRuleRuns,
+                    runs.join("\n"),
+                ));
+            }
             let (plan, logical_optimization_succeeded) = match optimized_plan {
                 Ok(plan) => (Arc::new(plan), true),
                 Err(DataFusionError::Context(optimizer_name, err)) => {
@@ -1930,6 +2387,26 @@ impl SessionState {
         }
     }
 
+    /// Optimizes the logical plan like [`Self::optimize`], also returning
+    /// the time each optimizer rule took in each pass and the changes it
+    /// made to the plan
+    pub fn optimize_with_runs(
+        &self,
+        plan: &LogicalPlan,
+    ) -> Result<(LogicalPlan, Vec<OptimizerRuleRun>)> {
+        let analyzed_plan =
+            self.analyzer
+                .execute_and_check(plan, self.options(), |_, _| {})?;
+        let mut runs = vec![];
+        let optimized_plan = self.optimizer.optimize_with_runs(
+            analyzed_plan,
+            self,
+            |_, _| {},
+            &mut runs,
+        )?;
+        Ok((optimized_plan, runs))
+    }
+
     /// Creates a physical plan from a logical plan.
     ///
     /// Note: this first calls [`Self::optimize`] on the provided
@@ -1941,10 +2418,22 @@ impl SessionState {
         &self,
         logical_plan: &LogicalPlan,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        let logical_plan = self.optimize(logical_plan)?;
-        self.query_planner
-            .create_physical_plan(&logical_plan, self)
-            .await
+        let span = info_span!(
+            "datafusion.create_physical_plan",
+            trace_parent = self.config_options().execution.trace_parent.as_deref(),
+        );
+        async {
+            let logical_plan = self.optimize(logical_plan)?;
+            // all the scans of a table in the query read the same version
+            let state = self.with_resolved_snapshots(&logical_plan).await?;
+            let state = state.as_ref().unwrap_or(self);
+            state
+                .query_planner
+                .create_physical_plan(&logical_plan, state)
+                .await
+        }
+        .instrument(span)
+        .await
     }
 
     /// Return the session ID
@@ -1962,6 +2451,11 @@ impl SessionState {
         &self.execution_props
     }
 
+    /// Return the mutable execution properties
+    pub fn execution_props_mut(&mut self) -> &mut ExecutionProps {
+        &mut self.execution_props
+    }
+
     /// Return the [`SessionConfig`]
     pub fn config(&self) -> &SessionConfig {
         &self.config
@@ -2018,6 +2512,21 @@ impl SessionState {
         self.serializer_registry.clone()
     }
 
+    /// Return the [`ExecutionScheduler`] used to execute physical plans,
+    /// wrapped in a [`TracingScheduler`] if
+    /// `datafusion.execution.operator_spans` is set
+    pub fn scheduler(&self) -> Arc<dyn ExecutionScheduler> {
+        let options = &self.config_options().execution;
+        if options.operator_spans {
+            Arc::new(TracingScheduler::new(
+                self.scheduler.clone(),
+                options.trace_parent.clone(),
+            ))
+        } else {
+            self.scheduler.clone()
+        }
+    }
+
     /// Return version of the cargo package that produced this query
     pub fn version(&self) -> &str {
         env!("CARGO_PKG_VERSION")
@@ -2027,15 +2536,22 @@ impl SessionState {
 struct SessionContextProvider<'a> {
     state: &'a SessionState,
     tables: HashMap<String, Arc<dyn TableSource>>,
+    /// Whether the policies of the session user are enforced on the scans
+    table_policies: bool,
 }
 
 impl<'a> ContextProvider for SessionContextProvider<'a> {
     fn get_table_source(&self, name: TableReference) -> Result<Arc<dyn TableSource>> {
         let name = self.state.resolve_table_ref(name).to_string();
-        self.tables
+        let source = self
+            .tables
             .get(&name)
             .cloned()
-            .ok_or_else(|| plan_datafusion_err!("table '{name}' not found"))
+            .ok_or_else(|| plan_datafusion_err!("table '{name}' not found"))?;
+        if !self.table_policies {
+            return Ok(source);
+        }
+        Ok(self.state.view_with_policies(&source)?.unwrap_or(source))
     }
 
     fn get_table_function_source(
@@ -2043,17 +2559,49 @@ impl<'a> ContextProvider for SessionContextProvider<'a> {
         name: &str,
         args: Vec<Expr>,
     ) -> Result<Arc<dyn TableSource>> {
-        let tbl_func = self
-            .state
-            .table_functions
-            .get(name)
-            .cloned()
-            .ok_or_else(|| plan_datafusion_err!("table function '{name}' not found"))?;
+        let tbl_func = match self.state.table_functions.get(name) {
+            Some(tbl_func) => tbl_func.clone(),
+            // the changes of a table, unless a function of the same name is
+            // registered
+            None if name == TABLE_CHANGES_FUNCTION => {
+                let (table_ref, changes) = TableChanges::try_from_args(&args)?;
+                let table =
+                    source_as_provider(&self.get_table_source(table_ref.clone())?)?;
+                let provider = Arc::new(TableChangesProvider::new(table, changes));
+                // the changed rows are read under the policy of the table, as
+                // a view filtering and masking them
+                return match self.get_table_policy(&table_ref)? {
+                    Some(policy) => {
+                        let plan = LogicalPlanBuilder::scan(
+                            table_ref,
+                            provider_as_source(provider),
+                            None,
+                        )?
+                        .build()?;
+                        let view = ViewTable::try_new(policy.apply(plan)?, None)?;
+                        Ok(provider_as_source(Arc::new(view)))
+                    }
+                    None => Ok(provider_as_source(provider)),
+                };
+            }
+            None => return plan_err!("table function '{name}' not found"),
+        };
         let provider = tbl_func.create_table_provider(&args)?;
 
         Ok(provider_as_source(provider))
     }
 
+    fn get_lateral_table_function(&self, name: &str) -> Option<Arc<ScalarUDF>> {
+        self.state.lateral_table_functions.get(name).cloned()
+    }
+
+    fn get_table_policy(&self, name: &TableReference) -> Result<Option<TablePolicy>> {
+        if !self.table_policies {
+            return Ok(None);
+        }
+        self.state.table_policy(name.clone())
+    }
+
     /// Create a new CTE work table for a recursive CTE logical plan
     /// This table will be used in conjunction with a Worktable physical plan
     /// to read and write each iteration of a recursive CTE
@@ -2366,6 +2914,9 @@ mod tests {
     use crate::test;
     use crate::test_util::{plan_and_collect, populate_csv_partitions};
 
+    use datafusion_common::cast::as_string_array;
+    use datafusion_common::config::ConfigProfile;
+
     use datafusion_common_runtime::SpawnedTask;
 
     use async_trait::async_trait;
@@ -2406,6 +2957,71 @@ mod tests {
         ));
     }
 
+    #[test]
+    fn validate_config_on_creation() -> Result<()> {
+        let runtime = Arc::new(RuntimeEnv::default());
+        let config = SessionConfig::new()
+            .with_profile(ConfigProfile::Interactive)
+            .with_information_schema(false);
+        let ctx = SessionContext::try_new_with_config_rt(config, runtime.clone())?;
+        assert!(
+            ctx.copied_config()
+                .options()
+                .execution
+                .parquet
+                .pushdown_filters
+        );
+
+        let config = SessionConfig::new()
+            .set_bool("datafusion.execution.parquet.reorder_filters", true);
+        let err = SessionContext::try_new_with_config_rt(config, runtime)
+            .err()
+            .unwrap();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Invalid or Unsupported Configuration: datafusion.execution.parquet.reorder_filters \
+            requires datafusion.execution.parquet.pushdown_filters"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn explain_optimizer_rule_runs() -> Result<()> {
+        let config =
+            SessionConfig::new().set_bool("datafusion.optimizer.record_rule_runs", true);
+        let ctx = SessionContext::new_with_config(config);
+        let sql = "SELECT a FROM (VALUES (1), (2)) AS t(a) WHERE a > 1 + 1";
+
+        let batches = ctx
+            .sql(&format!("EXPLAIN VERBOSE {sql}"))
+            .await?
+            .collect()
+            .await?;
+        let runs = batches
+            .iter()
+            .find_map(|batch| {
+                let plan_types = as_string_array(batch.column(0)).unwrap();
+                let plans = as_string_array(batch.column(1)).unwrap();
+                (0..batch.num_rows())
+                    .find(|i| plan_types.value(*i) == "optimizer_rule_runs")
+                    .map(|i| plans.value(i).to_string())
+            })
+            .unwrap();
+        assert!(runs.contains("simplify_expressions (pass 0): "), "{runs}");
+        assert!(
+            runs.contains("-  Filter: t.a > Int64(1) + Int64(1)"),
+            "{runs}"
+        );
+        assert!(runs.contains("+  Filter: t.a > Int64(2)"), "{runs}");
+
+        let plan = ctx.sql(sql).await?.into_unoptimized_plan();
+        let (_, runs) = ctx.state().optimize_with_runs(&plan)?;
+        assert!(runs
+            .iter()
+            .any(|run| run.rule == "simplify_expressions" && run.diff.is_some()));
+        Ok(())
+    }
+
     #[tokio::test]
     async fn create_variable_expr() -> Result<()> {
         let tmp_dir = TempDir::new()?;
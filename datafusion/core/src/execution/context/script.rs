@@ -0,0 +1,359 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Execution of scripts of SQL statements
+
+use std::collections::HashMap;
+
+use arrow::record_batch::RecordBatch;
+use datafusion_common::plan_err;
+
+use super::{Result, SessionContext};
+
+/// The result of a statement of a script run by
+/// [`SessionContext::sql_script`]
+#[derive(Debug, Clone)]
+pub struct StatementResult {
+    /// The statement, after the substitution of the variables
+    pub sql: String,
+    /// The batches returned by the statement, none for `SET VAR`
+    pub batches: Vec<RecordBatch>,
+}
+
+impl SessionContext {
+    /// Runs the script `sql`, made of statements separated by semicolons,
+    /// one statement after the other, and returns the results of each
+    /// statement.
+    ///
+    /// The statements `SET VAR <name> = <value>` define the variables of the
+    /// script: the SQL text `<value>` replaces the references `${<name>}` in
+    /// the following statements, including in their string literals. The
+    /// value can itself refer to the variables defined before it.
+    ///
+    /// Unlike [`Self::sql`], each statement is executed before the next one
+    /// is planned, so that a statement can use the tables created by the
+    /// previous ones. The execution stops at the first failing statement,
+    /// rolling back the write transaction the script started, if any.
+    ///
+    /// ```
+    /// use datafusion::prelude::*;
+    /// # use datafusion::{error::Result, assert_batches_eq};
+    /// # #[tokio::main]
+    /// # async fn main() -> Result<()> {
+    /// let ctx = SessionContext::new();
+    /// let results = ctx
+    ///     .sql_script(
+    ///         "CREATE TABLE t AS VALUES (1, 'a;b'), (2, 'c');
+    ///          SET VAR min_id = 2;
+    ///          SELECT column2 FROM t WHERE column1 >= ${min_id};",
+    ///     )
+    ///     .await?;
+    /// assert_eq!(results.len(), 3);
+    /// assert_batches_eq!(
+    ///     [
+    ///         "+---------+",
+    ///         "| column2 |",
+    ///         "+---------+",
+    ///         "| c       |",
+    ///         "+---------+",
+    ///     ],
+    ///     &results[2].batches
+    /// );
+    /// # Ok(())
+    /// # }
+    /// ```
+    pub async fn sql_script(&self, sql: &str) -> Result<Vec<StatementResult>> {
+        self.sql_script_with_variables(sql, HashMap::new()).await
+    }
+
+    /// Runs the script `sql` like [`Self::sql_script`], with the variables
+    /// `variables` defined from its start
+    pub async fn sql_script_with_variables(
+        &self,
+        sql: &str,
+        mut variables: HashMap<String, String>,
+    ) -> Result<Vec<StatementResult>> {
+        let in_transaction = self.write_transaction().is_some();
+        let mut results = vec![];
+        for (i, statement) in split_statements(sql).into_iter().enumerate() {
+            match self.run_script_statement(statement, &mut variables).await {
+                Ok(result) => results.push(result),
+                Err(e) => {
+                    if !in_transaction && self.write_transaction().is_some() {
+                        self.rollback_write_transaction().await?;
+                    }
+                    return Err(
+                        e.context(format!("Statement {} of the script failed", i + 1))
+                    );
+                }
+            }
+        }
+        Ok(results)
+    }
+
+    async fn run_script_statement(
+        &self,
+        statement: &str,
+        variables: &mut HashMap<String, String>,
+    ) -> Result<StatementResult> {
+        let sql = substitute_variables(statement, variables)?;
+        if let Some((name, value)) = parse_set_variable(&sql)? {
+            variables.insert(name, value);
+            return Ok(StatementResult {
+                sql,
+                batches: vec![],
+            });
+        }
+        let batches = self.sql(&sql).await?.collect().await?;
+        Ok(StatementResult { sql, batches })
+    }
+}
+
+/// Splits `sql` at the semicolons which are not in a string literal, such as
+/// the dollar-quoted bodies `$$...$$` or `$tag$...$tag$` of functions, a
+/// quoted identifier or a comment. The statements start at their first SQL
+/// token, after their leading comments, and the ones with no SQL are skipped.
+fn split_statements(sql: &str) -> Vec<&str> {
+    let mut statements = vec![];
+    // the start of the current statement, once it has SQL other than comments
+    let mut start = None;
+    let mut chars = sql.char_indices().peekable();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '\'' | '"' | '`' => {
+                // quotes are escaped by doubling them, which reads as two
+                // consecutive literals
+                for (_, next) in chars.by_ref() {
+                    if next == c {
+                        break;
+                    }
+                }
+            }
+            '$' => {
+                // dollar-quoted strings start and end with `$<tag>$`, whose
+                // tag may be empty but does not start with a digit, unlike the
+                // placeholders `$1`
+                let rest = &sql[i + 1..];
+                let tag_len = rest
+                    .find(|c: char| !(c.is_alphanumeric() || c == '_'))
+                    .unwrap_or(rest.len());
+                if rest[tag_len..].starts_with('$')
+                    && !rest.starts_with(|c: char| c.is_ascii_digit())
+                {
+                    let quote = &sql[i..i + tag_len + 2];
+                    let body = i + quote.len();
+                    let end = sql[body..]
+                        .find(quote)
+                        .map_or(sql.len(), |len| body + len + quote.len());
+                    while chars.next_if(|(j, _)| *j < end).is_some() {}
+                }
+            }
+            '-' if matches!(chars.peek(), Some((_, '-'))) => {
+                for (_, next) in chars.by_ref() {
+                    if next == '\n' {
+                        break;
+                    }
+                }
+                continue;
+            }
+            '/' if matches!(chars.peek(), Some((_, '*'))) => {
+                chars.next();
+                let mut depth = 1;
+                while depth > 0 {
+                    match chars.next() {
+                        Some((_, '*')) if matches!(chars.peek(), Some((_, '/'))) => {
+                            chars.next();
+                            depth -= 1;
+                        }
+                        Some((_, '/')) if matches!(chars.peek(), Some((_, '*'))) => {
+                            chars.next();
+                            depth += 1;
+                        }
+                        Some(_) => {}
+                        None => break,
+                    }
+                }
+                continue;
+            }
+            ';' => {
+                if let Some(start) = start.take() {
+                    statements.push(sql[start..i].trim_end());
+                }
+                continue;
+            }
+            _ => {}
+        }
+        if start.is_none() && !c.is_whitespace() {
+            start = Some(i);
+        }
+    }
+    if let Some(start) = start {
+        statements.push(sql[start..].trim_end());
+    }
+    statements
+}
+
+/// Replaces the references `${<name>}` in `sql` by the values of the
+/// variables
+fn substitute_variables(
+    sql: &str,
+    variables: &HashMap<String, String>,
+) -> Result<String> {
+    let mut substituted = String::with_capacity(sql.len());
+    let mut rest = sql;
+    while let Some(start) = rest.find("${") {
+        substituted.push_str(&rest[..start]);
+        let Some(len) = rest[start..].find('}') else {
+            return plan_err!("Unterminated variable reference in {sql}");
+        };
+        let name = rest[start + 2..start + len].trim();
+        let Some(value) = variables.get(name) else {
+            return plan_err!("Undefined script variable {name}");
+        };
+        substituted.push_str(value);
+        rest = &rest[start + len + 1..];
+    }
+    substituted.push_str(rest);
+    Ok(substituted)
+}
+
+/// Returns the name and the value of the variable defined by `sql`, if it is
+/// a `SET VAR <name> = <value>` or `SET VAR <name> TO <value>` statement
+fn parse_set_variable(sql: &str) -> Result<Option<(String, String)>> {
+    let Some(rest) =
+        strip_keyword(sql, "SET").and_then(|rest| strip_keyword(rest, "VAR"))
+    else {
+        return Ok(None);
+    };
+    let name_len = rest
+        .find(|c: char| c.is_whitespace() || c == '=')
+        .unwrap_or(rest.len());
+    let (name, rest) = rest.split_at(name_len);
+    let rest = rest.trim_start();
+    let value = rest
+        .strip_prefix('=')
+        .or_else(|| strip_keyword(rest, "TO"))
+        .map(str::trim);
+    match value {
+        Some(value)
+            if !name.is_empty()
+                && !value.is_empty()
+                && name.chars().all(|c| c.is_alphanumeric() || c == '_') =>
+        {
+            Ok(Some((name.to_string(), value.to_string())))
+        }
+        _ => plan_err!(
+            "Invalid variable definition, expected SET VAR <name> = <value>: {sql}"
+        ),
+    }
+}
+
+/// Strips the keyword `keyword`, followed by whitespace, from the start of
+/// `sql`
+fn strip_keyword<'a>(sql: &'a str, keyword: &str) -> Option<&'a str> {
+    let sql = sql.trim_start();
+    let rest = sql.get(keyword.len()..)?;
+    (sql[..keyword.len()].eq_ignore_ascii_case(keyword)
+        && rest.starts_with(char::is_whitespace))
+    .then(|| rest.trim_start())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+
+    #[test]
+    fn split_script() {
+        let script = "SELECT 'a;b', \"c;\"\"d\" FROM t; -- one; two\n\
+            /* three; /* four; */ five; */ SELECT 1;;\n\
+            -- trailing comment";
+        assert_eq!(
+            split_statements(script),
+            ["SELECT 'a;b', \"c;\"\"d\" FROM t", "SELECT 1"]
+        );
+
+        let script = "CREATE FUNCTION f() AS $$ SELECT 1; SELECT 2 $$;\n\
+            SELECT $tag$a;$$b$tag$, $1; SELECT ${v}";
+        assert_eq!(
+            split_statements(script),
+            [
+                "CREATE FUNCTION f() AS $$ SELECT 1; SELECT 2 $$",
+                "SELECT $tag$a;$$b$tag$, $1",
+                "SELECT ${v}"
+            ]
+        );
+    }
+
+    #[test]
+    fn set_variables() -> Result<()> {
+        assert_eq!(
+            parse_set_variable("set var d = DATE '2024-01-01'")?,
+            Some(("d".to_string(), "DATE '2024-01-01'".to_string()))
+        );
+        assert_eq!(
+            parse_set_variable("SET VAR n TO 1")?,
+            Some(("n".to_string(), "1".to_string()))
+        );
+        assert_eq!(parse_set_variable("SET datafusion.a = 1")?, None);
+        assert!(parse_set_variable("SET VAR n").is_err());
+
+        let variables = HashMap::from([("d".to_string(), "'x'".to_string())]);
+        assert_eq!(
+            substitute_variables("SELECT ${d}, '${ d }'", &variables)?,
+            "SELECT 'x', ''x''"
+        );
+        let err = substitute_variables("SELECT ${e}", &variables).unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: Undefined script variable e"
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn run_script() -> Result<()> {
+        let ctx = SessionContext::new();
+        let results = ctx
+            .sql_script_with_variables(
+                "CREATE TABLE t (d DATE, v VARCHAR);
+                INSERT INTO t VALUES ('2024-01-01', 'a;'), ('2024-01-02', 'b');
+                SET VAR day = DATE ${start};
+                SELECT v FROM t WHERE d = ${day}",
+                HashMap::from([("start".to_string(), "'2024-01-02'".to_string())]),
+            )
+            .await?;
+        assert_eq!(results.len(), 4);
+        assert_eq!(
+            results[3].sql,
+            "SELECT v FROM t WHERE d = DATE '2024-01-02'"
+        );
+        let expected = ["+---+", "| v |", "+---+", "| b |", "+---+"];
+        assert_batches_eq!(expected, &results[3].batches);
+
+        let err = ctx
+            .sql_script("SELECT 1; SELECT * FROM missing")
+            .await
+            .unwrap_err();
+        assert!(
+            err.strip_backtrace()
+                .starts_with("Statement 2 of the script failed"),
+            "{err}"
+        );
+        Ok(())
+    }
+}
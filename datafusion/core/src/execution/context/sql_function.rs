@@ -0,0 +1,217 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Scalar functions defined in SQL by `CREATE FUNCTION`
+
+use std::any::Any;
+
+use arrow::datatypes::DataType;
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRecursion};
+use datafusion_common::{internal_err, plan_err, DFSchema, Result};
+use datafusion_expr::expr::{Placeholder, ScalarFunction};
+use datafusion_expr::expr_rewriter::FunctionRewrite;
+use datafusion_expr::type_coercion::functions::can_coerce_from;
+use datafusion_expr::{
+    ColumnarValue, CreateFunction, Expr, ExprSchemable, ScalarFunctionDefinition,
+    ScalarUDFImpl, Signature, Volatility,
+};
+
+/// A scalar function whose body is a SQL expression, such as
+///
+/// ```sql
+/// CREATE FUNCTION add_one(x INT) RETURNS INT AS 'x + 1'
+/// ```
+///
+/// `CREATE FUNCTION` registers one in the session when no
+/// [`FunctionFactory`](super::FunctionFactory) is configured. The function is
+/// a macro: its calls are replaced by its body by [`InlineSqlFunctions`]
+/// before type coercion, so it is never invoked.
+#[derive(Debug)]
+pub struct SqlFunction {
+    name: String,
+    arg_types: Vec<DataType>,
+    signature: Signature,
+    return_type: DataType,
+    /// The body of the function, referring to the arguments with the `$n`
+    /// placeholders
+    body: Expr,
+}
+
+impl SqlFunction {
+    /// Create the function defined by `statement`, whose body must be a SQL
+    /// expression
+    pub fn try_new(statement: CreateFunction) -> Result<Self> {
+        let CreateFunction {
+            name,
+            args,
+            return_type,
+            params,
+            ..
+        } = statement;
+        let Some(body) = params.return_ else {
+            return plan_err!("Function {name} has no SQL body");
+        };
+        let arg_types = args
+            .unwrap_or_default()
+            .into_iter()
+            .map(|arg| arg.data_type)
+            .collect::<Vec<_>>();
+
+        body.apply(|expr| {
+            if let Expr::Placeholder(Placeholder { id, .. }) = expr {
+                if arg_index(id, arg_types.len()).is_none() {
+                    return plan_err!("Function {name} has no argument {id}");
+                }
+            }
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+
+        // the placeholders are typed, so the body needs no schema
+        let schema = DFSchema::empty();
+        let (body, return_type) = match return_type {
+            Some(return_type) => (body.cast_to(&return_type, &schema)?, return_type),
+            None => {
+                let return_type = body.get_type(&schema)?;
+                (body, return_type)
+            }
+        };
+
+        Ok(Self {
+            name,
+            signature: Signature::exact(
+                arg_types.clone(),
+                params.behavior.unwrap_or(Volatility::Volatile),
+            ),
+            arg_types,
+            return_type,
+            body,
+        })
+    }
+
+    /// The body of the function, referring to the arguments with the `$n`
+    /// placeholders
+    pub fn body(&self) -> &Expr {
+        &self.body
+    }
+
+    /// Returns the body of the function called with `args`
+    fn inline(&self, args: Vec<Expr>, schema: &DFSchema) -> Result<Expr> {
+        if args.len() != self.arg_types.len() {
+            return plan_err!(
+                "Function {} expects {} arguments, got {}",
+                self.name,
+                self.arg_types.len(),
+                args.len()
+            );
+        }
+        let args = args
+            .into_iter()
+            .zip(&self.arg_types)
+            .enumerate()
+            .map(|(i, (arg, arg_type))| {
+                let data_type = arg.get_type(schema)?;
+                if !can_coerce_from(arg_type, &data_type) {
+                    return plan_err!(
+                        "Function {} expects {arg_type} for argument {}, got {data_type}",
+                        self.name,
+                        i + 1
+                    );
+                }
+                arg.cast_to(arg_type, schema)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        self.body
+            .clone()
+            .transform_up(|expr| {
+                Ok(match expr {
+                    Expr::Placeholder(Placeholder { ref id, .. }) => {
+                        match arg_index(id, args.len()) {
+                            Some(i) => Transformed::yes(args[i].clone()),
+                            None => Transformed::no(expr),
+                        }
+                    }
+                    _ => Transformed::no(expr),
+                })
+            })
+            .map(|transformed| transformed.data)
+    }
+}
+
+/// Returns the index of the argument of the `$n` placeholder `id`, if
+/// there is one among `num_args` arguments
+fn arg_index(id: &str, num_args: usize) -> Option<usize> {
+    let n = id.strip_prefix('$')?.parse::<usize>().ok()?;
+    n.checked_sub(1).filter(|i| *i < num_args)
+}
+
+impl ScalarUDFImpl for SqlFunction {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(self.return_type.clone())
+    }
+
+    fn invoke(&self, _args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        internal_err!("SQL function {} should have been inlined", self.name)
+    }
+}
+
+/// Replaces the calls to [`SqlFunction`]s by their bodies
+#[derive(Debug, Default)]
+pub struct InlineSqlFunctions {}
+
+impl FunctionRewrite for InlineSqlFunctions {
+    fn name(&self) -> &str {
+        "inline_sql_functions"
+    }
+
+    fn rewrite(
+        &self,
+        expr: Expr,
+        schema: &DFSchema,
+        config: &ConfigOptions,
+    ) -> Result<Transformed<Expr>> {
+        let Expr::ScalarFunction(ScalarFunction {
+            func_def: ScalarFunctionDefinition::UDF(udf),
+            args,
+        }) = &expr
+        else {
+            return Ok(Transformed::no(expr));
+        };
+        let inner = udf.inner();
+        let Some(function) = inner.as_any().downcast_ref::<SqlFunction>() else {
+            return Ok(Transformed::no(expr));
+        };
+        let inlined = function.inline(args.clone(), schema)?;
+        // the body may call other SQL functions
+        inlined
+            .transform_up(|expr| self.rewrite(expr, schema, config))
+            .map(|transformed| Transformed::yes(transformed.data))
+    }
+}
@@ -0,0 +1,153 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Rewrites of the logical plans of the SQL statements, before their
+//! optimization
+
+use datafusion_common::{Result, TableReference};
+use datafusion_expr::LogicalPlan;
+use datafusion_sql::parser::{ExplainStatement, Statement as DFStatement};
+use sqlparser::ast::Statement;
+
+use super::SessionState;
+
+/// The kind of a SQL statement, to which a [`StatementRewriter`] may apply
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum StatementKind {
+    /// `SELECT`, `VALUES` and the other queries
+    Query,
+    /// `INSERT INTO`
+    Insert,
+    /// `UPDATE`
+    Update,
+    /// `DELETE`
+    Delete,
+    /// `CREATE TABLE` and `CREATE EXTERNAL TABLE`
+    CreateTable,
+    /// `CREATE VIEW`
+    CreateView,
+    /// `COPY TO`
+    Copy,
+    /// Any other statement
+    Other,
+}
+
+impl StatementKind {
+    /// Returns the kind of `statement`, and whether it is explained. The
+    /// kind of `EXPLAIN <statement>` is the kind of `<statement>`.
+    fn of(statement: &DFStatement) -> (Self, bool) {
+        match statement {
+            DFStatement::Statement(statement) => Self::of_sql(statement),
+            DFStatement::CreateExternalTable(_) => (Self::CreateTable, false),
+            DFStatement::CopyTo(_) => (Self::Copy, false),
+            DFStatement::Explain(ExplainStatement { statement, .. }) => {
+                (Self::of(statement).0, true)
+            }
+            DFStatement::AnalyzeTable(_) | DFStatement::Reset(_) => (Self::Other, false),
+        }
+    }
+
+    fn of_sql(statement: &Statement) -> (Self, bool) {
+        let kind = match statement {
+            Statement::Explain { statement, .. } => {
+                return (Self::of_sql(statement).0, true)
+            }
+            Statement::Query(_) => Self::Query,
+            Statement::Insert { .. } => Self::Insert,
+            Statement::Update { .. } => Self::Update,
+            Statement::Delete { .. } => Self::Delete,
+            Statement::CreateTable { .. } => Self::CreateTable,
+            Statement::CreateView { .. } => Self::CreateView,
+            Statement::Copy { .. } => Self::Copy,
+            _ => Self::Other,
+        };
+        (kind, false)
+    }
+}
+
+/// The statement whose plan a [`StatementRewriter`] rewrites
+#[derive(Debug, Clone)]
+pub struct StatementMetadata {
+    kind: StatementKind,
+    explain: bool,
+    sql: String,
+    table_references: Vec<TableReference>,
+}
+
+impl StatementMetadata {
+    /// Create the metadata of `statement`, which refers to the tables
+    /// `table_references`
+    pub fn new(statement: &DFStatement, table_references: Vec<TableReference>) -> Self {
+        let (kind, explain) = StatementKind::of(statement);
+        Self {
+            kind,
+            explain,
+            sql: statement.to_string(),
+            table_references,
+        }
+    }
+
+    /// The kind of the statement, the kind of the explained statement for
+    /// `EXPLAIN`
+    pub fn kind(&self) -> StatementKind {
+        self.kind
+    }
+
+    /// Returns true if the statement is an `EXPLAIN`, whose plan wraps the
+    /// plan of the explained statement
+    pub fn is_explain(&self) -> bool {
+        self.explain
+    }
+
+    /// The SQL text of the statement
+    pub fn sql(&self) -> &str {
+        &self.sql
+    }
+
+    /// The tables the statement refers to, as written in the statement
+    pub fn table_references(&self) -> &[TableReference] {
+        &self.table_references
+    }
+}
+
+/// Rewrites the logical plans of the SQL statements of some kinds, before
+/// they are analyzed and optimized.
+///
+/// The rewriters registered with
+/// [`SessionState::add_statement_rewriter`] run, in the order they were
+/// registered, on the plan of each statement planned by
+/// [`SessionState::statement_to_plan`], e.g. to add the filters of a tenant
+/// to the queries, to rename the tables of an environment or to expand
+/// macros, without implementing a whole [`QueryPlanner`](super::QueryPlanner).
+pub trait StatementRewriter: Send + Sync {
+    /// A human readable name for this rewriter
+    fn name(&self) -> &str;
+
+    /// Returns true if this rewriter rewrites the plans of the statements of
+    /// the kind `kind`. Defaults to all the kinds.
+    fn applies_to(&self, _kind: StatementKind) -> bool {
+        true
+    }
+
+    /// Rewrite `plan`, the plan of the statement `statement`
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        statement: &StatementMetadata,
+        state: &SessionState,
+    ) -> Result<LogicalPlan>;
+}
@@ -0,0 +1,183 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Write transactions of a [`SessionContext`]
+
+use std::sync::Arc;
+
+use super::{DataFrame, Result, SessionContext};
+use crate::physical_plan::transaction::WriteTransaction;
+
+use datafusion_common::plan_err;
+use datafusion_expr::{TransactionConclusion, TransactionEnd, TransactionStart};
+
+impl SessionContext {
+    /// Starts a [`WriteTransaction`]: until it ends, the writes of the
+    /// statements are staged by their sinks, and only published by
+    /// [`Self::commit_write_transaction`], all together.
+    ///
+    /// This is what `BEGIN` does. The writes to the tables whose sinks don't
+    /// support staging fail while the transaction is active.
+    pub fn begin_write_transaction(&self) -> Result<()> {
+        let mut state = self.state.write();
+        if state.config().get_extension::<WriteTransaction>().is_some() {
+            return plan_err!("A write transaction is already active");
+        }
+        state
+            .config_mut()
+            .set_extension(Arc::new(WriteTransaction::new()));
+        Ok(())
+    }
+
+    /// Returns the active [`WriteTransaction`], if any
+    pub fn write_transaction(&self) -> Option<Arc<WriteTransaction>> {
+        self.state
+            .read()
+            .config()
+            .get_extension::<WriteTransaction>()
+    }
+
+    /// Ends the active [`WriteTransaction`], publishing all its writes, or
+    /// none of them if one fails to prepare. This is what `COMMIT` does.
+    pub async fn commit_write_transaction(&self) -> Result<()> {
+        self.end_write_transaction()?.commit().await
+    }
+
+    /// Ends the active [`WriteTransaction`], discarding all its writes. This
+    /// is what `ROLLBACK` does.
+    pub async fn rollback_write_transaction(&self) -> Result<()> {
+        self.end_write_transaction()?.rollback().await
+    }
+
+    fn end_write_transaction(&self) -> Result<Arc<WriteTransaction>> {
+        let transaction = self
+            .state
+            .write()
+            .config_mut()
+            .remove_extension::<WriteTransaction>();
+        match transaction {
+            Some(transaction) => Ok(transaction),
+            None => plan_err!("No write transaction is active"),
+        }
+    }
+
+    /// Starts a write transaction for `BEGIN`
+    pub(super) async fn transaction_start(
+        &self,
+        _stmt: TransactionStart,
+    ) -> Result<DataFrame> {
+        self.begin_write_transaction()?;
+        self.return_empty_dataframe()
+    }
+
+    /// Ends the active write transaction for `COMMIT` and `ROLLBACK`, and
+    /// starts a new one for `AND CHAIN`
+    pub(super) async fn transaction_end(
+        &self,
+        stmt: TransactionEnd,
+    ) -> Result<DataFrame> {
+        match stmt.conclusion {
+            TransactionConclusion::Commit => self.commit_write_transaction().await?,
+            TransactionConclusion::Rollback => self.rollback_write_transaction().await?,
+        }
+        if stmt.chain {
+            self.begin_write_transaction()?;
+        }
+        self.return_empty_dataframe()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::assert_batches_eq;
+
+    async fn count(ctx: &SessionContext, table: &str) -> Result<Vec<String>> {
+        let batches = ctx
+            .sql(&format!("SELECT count(*) AS n FROM {table}"))
+            .await?
+            .collect()
+            .await?;
+        Ok(arrow::util::pretty::pretty_format_batches(&batches)?
+            .to_string()
+            .lines()
+            .map(String::from)
+            .collect())
+    }
+
+    async fn context() -> Result<SessionContext> {
+        let ctx = SessionContext::new();
+        ctx.sql_script("CREATE TABLE a (x INT); CREATE TABLE b (x INT);")
+            .await?;
+        Ok(ctx)
+    }
+
+    #[tokio::test]
+    async fn commit_writes_to_several_tables() -> Result<()> {
+        let ctx = context().await?;
+        ctx.sql_script(
+            "BEGIN;
+            INSERT INTO a VALUES (1), (2);
+            INSERT INTO b VALUES (3);",
+        )
+        .await?;
+        assert_eq!(ctx.write_transaction().unwrap().num_staged(), 2);
+
+        // the staged writes are not visible until the commit
+        let empty = ["+---+", "| n |", "+---+", "| 0 |", "+---+"];
+        assert_eq!(count(&ctx, "a").await?, empty);
+
+        ctx.sql("COMMIT").await?;
+        assert!(ctx.write_transaction().is_none());
+        let results = ctx.sql("SELECT x FROM a UNION ALL SELECT x FROM b ORDER BY x");
+        let expected = [
+            "+---+", "| x |", "+---+", "| 1 |", "| 2 |", "| 3 |", "+---+",
+        ];
+        assert_batches_eq!(expected, &results.await?.collect().await?);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn rollback_writes() -> Result<()> {
+        let ctx = context().await?;
+        ctx.sql_script(
+            "BEGIN;
+            INSERT INTO a VALUES (1);
+            ROLLBACK AND CHAIN;
+            INSERT INTO b VALUES (2);
+            COMMIT;",
+        )
+        .await?;
+        let empty = ["+---+", "| n |", "+---+", "| 0 |", "+---+"];
+        assert_eq!(count(&ctx, "a").await?, empty);
+        let one = ["+---+", "| n |", "+---+", "| 1 |", "+---+"];
+        assert_eq!(count(&ctx, "b").await?, one);
+
+        // the transaction started by a failing script is rolled back
+        let script = "BEGIN; INSERT INTO a VALUES (1); SELECT * FROM missing";
+        assert!(ctx.sql_script(script).await.is_err());
+        assert!(ctx.write_transaction().is_none());
+        assert_eq!(count(&ctx, "a").await?, empty);
+
+        let err = ctx.sql("COMMIT").await.unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Error during planning: No write transaction is active"
+        );
+        Ok(())
+    }
+}
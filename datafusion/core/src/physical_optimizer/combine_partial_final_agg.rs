@@ -23,7 +23,10 @@ use std::sync::Arc;
 use crate::error::Result;
 use crate::physical_optimizer::PhysicalOptimizerRule;
 use crate::physical_plan::aggregates::{AggregateExec, AggregateMode, PhysicalGroupBy};
-use crate::physical_plan::ExecutionPlan;
+use crate::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
+use crate::physical_plan::{ExecutionPlan, ExecutionPlanProperties};
 
 use datafusion_common::config::ConfigOptions;
 use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
@@ -33,6 +36,11 @@ use datafusion_physical_expr::{AggregateExpr, PhysicalExpr};
 /// CombinePartialFinalAggregate optimizer rule combines the adjacent Partial and Final AggregateExecs
 /// into a Single AggregateExec if their grouping exprs and aggregate exprs equal.
 ///
+/// The AggregateExecs are also combined when they are only separated by operators
+/// exchanging rows between partitions which have a single input and output partition,
+/// such as a CoalescePartitionsExec of a single partition, as in the plans with
+/// `target_partitions = 1`.
+///
 /// This rule should be applied after the EnforceDistribution and EnforceSorting rules
 ///
 #[derive(Default)]
@@ -60,11 +68,10 @@ impl PhysicalOptimizerRule for CombinePartialFinalAggregate {
                             agg_exec.mode(),
                             AggregateMode::Final | AggregateMode::FinalPartitioned
                         ) {
-                            agg_exec
-                                .input()
-                                .as_any()
-                                .downcast_ref::<AggregateExec>()
-                                .and_then(|input_agg_exec| {
+                            let (input, skipped_exchanges) =
+                                skip_single_partition_exchanges(agg_exec.input());
+                            input.as_any().downcast_ref::<AggregateExec>().and_then(
+                                |input_agg_exec| {
                                     if matches!(
                                         input_agg_exec.mode(),
                                         AggregateMode::Partial
@@ -80,12 +87,15 @@ impl PhysicalOptimizerRule for CombinePartialFinalAggregate {
                                             input_agg_exec.filter_expr(),
                                         ),
                                     ) {
-                                        let mode =
-                                            if agg_exec.mode() == &AggregateMode::Final {
-                                                AggregateMode::Single
-                                            } else {
-                                                AggregateMode::SinglePartitioned
-                                            };
+                                        // Without the exchanges, the input has a
+                                        // single partition
+                                        let mode = if skipped_exchanges
+                                            || agg_exec.mode() == &AggregateMode::Final
+                                        {
+                                            AggregateMode::Single
+                                        } else {
+                                            AggregateMode::SinglePartitioned
+                                        };
                                         AggregateExec::try_new(
                                             mode,
                                             input_agg_exec.group_by().clone(),
@@ -102,7 +112,8 @@ impl PhysicalOptimizerRule for CombinePartialFinalAggregate {
                                     } else {
                                         None
                                     }
-                                })
+                                },
+                            )
                         } else {
                             None
                         }
@@ -126,6 +137,32 @@ impl PhysicalOptimizerRule for CombinePartialFinalAggregate {
     }
 }
 
+/// Returns the input of `plan` below the operators exchanging rows between partitions
+/// which have a single input and output partition, and whether there were any
+fn skip_single_partition_exchanges(
+    plan: &Arc<dyn ExecutionPlan>,
+) -> (Arc<dyn ExecutionPlan>, bool) {
+    let mut input = plan.clone();
+    let mut skipped = false;
+    loop {
+        let any = input.as_any();
+        let is_exchange = any.is::<CoalescePartitionsExec>()
+            || any.is::<RepartitionExec>()
+            || any
+                .downcast_ref::<SortPreservingMergeExec>()
+                .is_some_and(|merge| merge.fetch().is_none());
+        if !is_exchange || input.output_partitioning().partition_count() != 1 {
+            return (input, skipped);
+        }
+        let child = input.children().swap_remove(0);
+        if child.output_partitioning().partition_count() != 1 {
+            return (input, skipped);
+        }
+        input = child;
+        skipped = true;
+    }
+}
+
 type GroupExprsRef<'a> = (
     &'a PhysicalGroupBy,
     &'a [Arc<dyn AggregateExpr>],
@@ -256,6 +293,7 @@ mod tests {
                 table_partition_cols: vec![],
                 output_ordering: vec![],
                 column_hints: None,
+                listing_pruning: None,
             },
             None,
             None,
@@ -396,6 +434,34 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn aggregations_combined_through_single_partition_exchanges() -> Result<()> {
+        let schema = schema();
+        let aggr_expr = vec![Arc::new(Count::new(
+            lit(1i8),
+            "COUNT(1)".to_string(),
+            DataType::Int64,
+        )) as _];
+
+        let partial_agg = partial_aggregate_exec(
+            parquet_exec(&schema),
+            PhysicalGroupBy::default(),
+            aggr_expr.clone(),
+        );
+        let exchanges = Arc::new(CoalescePartitionsExec::new(Arc::new(
+            RepartitionExec::try_new(partial_agg, Partitioning::RoundRobinBatch(1))?,
+        )));
+        let plan = final_aggregate_exec(exchanges, PhysicalGroupBy::default(), aggr_expr);
+        // should combine the Partial/Final AggregateExecs to the Single AggregateExec
+        let expected = &[
+            "AggregateExec: mode=Single, gby=[], aggr=[COUNT(1)]",
+            "ParquetExec: file_groups={1 group: [[x]]}, projection=[a, b, c]",
+        ];
+
+        assert_optimized!(expected, plan);
+        Ok(())
+    }
+
     #[test]
     fn aggregations_with_group_combined() -> Result<()> {
         let schema = schema();
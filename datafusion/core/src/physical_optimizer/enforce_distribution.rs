@@ -41,7 +41,9 @@ use crate::physical_plan::projection::ProjectionExec;
 use crate::physical_plan::repartition::RepartitionExec;
 use crate::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
 use crate::physical_plan::tree_node::PlanContext;
-use crate::physical_plan::union::{can_interleave, InterleaveExec, UnionExec};
+use crate::physical_plan::union::{
+    can_interleave, can_interleave_partitions, InterleaveExec, UnionExec,
+};
 use crate::physical_plan::windows::WindowAggExec;
 use crate::physical_plan::{Distribution, ExecutionPlan, Partitioning};
 
@@ -199,7 +201,7 @@ impl PhysicalOptimizerRule for EnforceDistribution {
             let adjusted = plan_requirements
                 .transform_down(adjust_input_keys_ordering)
                 .data()?;
-            adjusted.plan
+            adjusted.node
         } else {
             // Run a bottom-up process
             plan.transform_up(|plan| {
@@ -215,7 +217,7 @@ impl PhysicalOptimizerRule for EnforceDistribution {
                 ensure_distribution(distribution_context, config)
             })
             .data()?;
-        Ok(distribution_context.plan)
+        Ok(distribution_context.node)
     }
 
     fn name(&self) -> &str {
@@ -272,7 +274,7 @@ impl PhysicalOptimizerRule for EnforceDistribution {
 fn adjust_input_keys_ordering(
     mut requirements: PlanWithKeyRequirements,
 ) -> Result<Transformed<PlanWithKeyRequirements>> {
-    let plan = requirements.plan.clone();
+    let plan = requirements.node.clone();
 
     if let Some(HashJoinExec {
         left,
@@ -430,7 +432,7 @@ where
 {
     let parent_required = &join_plan.data;
     let join_key_pairs = extract_join_keys(on);
-    let eq_properties = join_plan.plan.equivalence_properties();
+    let eq_properties = join_plan.node.equivalence_properties();
 
     let (
         JoinKeyPairs {
@@ -446,7 +448,7 @@ where
             let new_sort_options = (0..sort_options.len())
                 .map(|idx| sort_options[positions[idx]])
                 .collect();
-            join_plan.plan = join_constructor((new_join_on, new_sort_options))?;
+            join_plan.node = join_constructor((new_join_on, new_sort_options))?;
         }
     }
 
@@ -515,7 +517,7 @@ fn reorder_aggregate_keys(
                         agg_exec.input_schema(),
                     )?);
 
-                    agg_node.plan = new_final_agg.clone();
+                    agg_node.node = new_final_agg.clone();
                     agg_node.data.clear();
                     agg_node.children = vec![PlanWithKeyRequirements::new(
                         partial_agg as _,
@@ -557,19 +559,37 @@ fn shift_right_required(
 ) -> Option<Vec<Arc<dyn PhysicalExpr>>> {
     let new_right_required = parent_required
         .iter()
-        .filter_map(|r| {
-            r.as_any().downcast_ref::<Column>().and_then(|col| {
-                col.index()
-                    .checked_sub(left_columns_len)
-                    .map(|index| Arc::new(Column::new(col.name(), index)) as _)
-            })
-        })
+        .filter_map(|r| shift_right_expr(r, left_columns_len))
         .collect::<Vec<_>>();
 
     // if the parent required are all comming from the right side, the requirements can be pushdown
     (new_right_required.len() == parent_required.len()).then_some(new_right_required)
 }
 
+/// Returns `expr` with the indexes of its columns shifted by `left_columns_len`,
+/// if all its columns come from the right side
+fn shift_right_expr(
+    expr: &Arc<dyn PhysicalExpr>,
+    left_columns_len: usize,
+) -> Option<Arc<dyn PhysicalExpr>> {
+    let mut from_right = true;
+    let shifted = expr
+        .clone()
+        .transform_up(|expr| {
+            if let Some(col) = expr.as_any().downcast_ref::<Column>() {
+                if let Some(index) = col.index().checked_sub(left_columns_len) {
+                    let col = Arc::new(Column::new(col.name(), index));
+                    return Ok(Transformed::yes(col as _));
+                }
+                from_right = false;
+            }
+            Ok(Transformed::no(expr))
+        })
+        .data()
+        .ok()?;
+    from_right.then_some(shifted)
+}
+
 /// When the physical planner creates the Joins, the ordering of join keys is from the original query.
 /// That might not match with the output partitioning of the join node's children
 /// This method will try to change the ordering of the join keys to match with the
@@ -833,7 +853,7 @@ fn add_roundrobin_on_top(
     n_target: usize,
 ) -> Result<DistributionContext> {
     // Adding repartition is helpful:
-    if input.plan.output_partitioning().partition_count() < n_target {
+    if input.node.output_partitioning().partition_count() < n_target {
         // When there is an existing ordering, we preserve ordering
         // during repartition. This will be un-done in the future
         // If any of the following conditions is true
@@ -841,7 +861,7 @@ fn add_roundrobin_on_top(
         // - Usage of order preserving variants is not desirable
         // (determined by flag `config.optimizer.prefer_existing_sort`)
         let partitioning = Partitioning::RoundRobinBatch(n_target);
-        let repartition = RepartitionExec::try_new(input.plan.clone(), partitioning)?
+        let repartition = RepartitionExec::try_new(input.node.clone(), partitioning)?
             .with_preserve_order();
 
         let new_plan = Arc::new(repartition) as _;
@@ -876,20 +896,20 @@ fn add_hash_on_top(
 ) -> Result<DistributionContext> {
     // Early return if hash repartition is unnecessary
     // `RepartitionExec: partitioning=Hash([...], 1), input_partitions=1` is unnecessary.
-    if n_target == 1 && input.plan.output_partitioning().partition_count() == 1 {
+    if n_target == 1 && input.node.output_partitioning().partition_count() == 1 {
         return Ok(input);
     }
 
     let dist = Distribution::HashPartitioned(hash_exprs);
     let satisfied = input
-        .plan
+        .node
         .output_partitioning()
-        .satisfy(&dist, input.plan.equivalence_properties());
+        .satisfy(&dist, input.node.equivalence_properties());
 
     // Add hash repartitioning when:
     // - The hash distribution requirement is not satisfied, or
     // - We can increase parallelism by adding hash partitioning.
-    if !satisfied || n_target > input.plan.output_partitioning().partition_count() {
+    if !satisfied || n_target > input.node.output_partitioning().partition_count() {
         // When there is an existing ordering, we preserve ordering during
         // repartition. This will be rolled back in the future if any of the
         // following conditions is true:
@@ -898,7 +918,7 @@ fn add_hash_on_top(
         // - Usage of order preserving variants is not desirable (per the flag
         //   `config.optimizer.prefer_existing_sort`).
         let partitioning = dist.create_partitioning(n_target);
-        let repartition = RepartitionExec::try_new(input.plan.clone(), partitioning)?
+        let repartition = RepartitionExec::try_new(input.node.clone(), partitioning)?
             .with_preserve_order();
         let plan = Arc::new(repartition) as _;
 
@@ -921,22 +941,22 @@ fn add_hash_on_top(
 /// distribution is satisfied by adding [`SortPreservingMergeExec`].
 fn add_spm_on_top(input: DistributionContext) -> DistributionContext {
     // Add SortPreservingMerge only when partition count is larger than 1.
-    if input.plan.output_partitioning().partition_count() > 1 {
+    if input.node.output_partitioning().partition_count() > 1 {
         // When there is an existing ordering, we preserve ordering
         // when decreasing partitions. This will be un-done in the future
         // if any of the following conditions is true
         // - Preserving ordering is not helpful in terms of satisfying ordering requirements
         // - Usage of order preserving variants is not desirable
         // (determined by flag `config.optimizer.bounded_order_preserving_variants`)
-        let should_preserve_ordering = input.plan.output_ordering().is_some();
+        let should_preserve_ordering = input.node.output_ordering().is_some();
 
         let new_plan = if should_preserve_ordering {
             Arc::new(SortPreservingMergeExec::new(
-                input.plan.output_ordering().unwrap_or(&[]).to_vec(),
-                input.plan.clone(),
+                input.node.output_ordering().unwrap_or(&[]).to_vec(),
+                input.node.clone(),
             )) as _
         } else {
-            Arc::new(CoalescePartitionsExec::new(input.plan.clone())) as _
+            Arc::new(CoalescePartitionsExec::new(input.node.clone())) as _
         };
 
         DistributionContext::new(new_plan, true, vec![input])
@@ -965,9 +985,9 @@ fn add_spm_on_top(input: DistributionContext) -> DistributionContext {
 fn remove_dist_changing_operators(
     mut distribution_context: DistributionContext,
 ) -> Result<DistributionContext> {
-    while is_repartition(&distribution_context.plan)
-        || is_coalesce_partitions(&distribution_context.plan)
-        || is_sort_preserving_merge(&distribution_context.plan)
+    while is_repartition(&distribution_context.node)
+        || is_coalesce_partitions(&distribution_context.node)
+        || is_sort_preserving_merge(&distribution_context.node)
     {
         // All of above operators have a single child. First child is only child.
         // Remove any distribution changing operators at the beginning:
@@ -1011,23 +1031,23 @@ fn replace_order_preserving_variants(
         })
         .collect::<Result<Vec<_>>>()?;
 
-    if is_sort_preserving_merge(&context.plan) {
-        let child_plan = context.children[0].plan.clone();
-        context.plan = Arc::new(CoalescePartitionsExec::new(child_plan));
+    if is_sort_preserving_merge(&context.node) {
+        let child_plan = context.children[0].node.clone();
+        context.node = Arc::new(CoalescePartitionsExec::new(child_plan));
         return Ok(context);
     } else if let Some(repartition) =
-        context.plan.as_any().downcast_ref::<RepartitionExec>()
+        context.node.as_any().downcast_ref::<RepartitionExec>()
     {
         if repartition.preserve_order() {
-            context.plan = Arc::new(RepartitionExec::try_new(
-                context.children[0].plan.clone(),
+            context.node = Arc::new(RepartitionExec::try_new(
+                context.children[0].node.clone(),
                 repartition.partitioning().clone(),
             )?);
             return Ok(context);
         }
     }
 
-    context.update_plan_from_children()
+    context.update_node_from_children()
 }
 
 /// This function checks whether we need to add additional data exchange
@@ -1040,7 +1060,7 @@ fn ensure_distribution(
 ) -> Result<Transformed<DistributionContext>> {
     let dist_context = update_children(dist_context)?;
 
-    if dist_context.plan.children().is_empty() {
+    if dist_context.node.children().is_empty() {
         return Ok(Transformed::no(dist_context));
     }
 
@@ -1049,7 +1069,7 @@ fn ensure_distribution(
     let enable_round_robin = config.optimizer.enable_round_robin_repartition;
     let repartition_file_scans = config.optimizer.repartition_file_scans;
     let batch_size = config.execution.batch_size;
-    let is_unbounded = dist_context.plan.execution_mode().is_unbounded();
+    let is_unbounded = dist_context.node.execution_mode().is_unbounded();
     // Use order preserving variants either of the conditions true
     // - it is desired according to config
     // - when plan is unbounded
@@ -1058,7 +1078,7 @@ fn ensure_distribution(
 
     // Remove unnecessary repartition from the physical plan if any
     let DistributionContext {
-        mut plan,
+        node: mut plan,
         data,
         children,
     } = remove_dist_changing_operators(dist_context)?;
@@ -1096,7 +1116,7 @@ fn ensure_distribution(
     .map(
         |(mut child, requirement, required_input_ordering, would_benefit, maintains)| {
             // Don't need to apply when the returned row count is not greater than batch size
-            let num_rows = child.plan.statistics()?.num_rows;
+            let num_rows = child.node.statistics()?.num_rows;
             let repartition_beneficial_stats = if num_rows.is_exact().unwrap_or(false) {
                 num_rows
                     .get_value()
@@ -1110,15 +1130,15 @@ fn ensure_distribution(
                 // Operator benefits from partitioning (e.g. filter):
                 && (would_benefit && repartition_beneficial_stats)
                 // Unless partitioning increases the partition count, it is not beneficial:
-                && child.plan.output_partitioning().partition_count() < target_partitions;
+                && child.node.output_partitioning().partition_count() < target_partitions;
 
             // When `repartition_file_scans` is set, attempt to increase
             // parallelism at the source.
             if repartition_file_scans && repartition_beneficial_stats {
                 if let Some(new_child) =
-                    child.plan.repartitioned(target_partitions, config)?
+                    child.node.repartitioned(target_partitions, config)?
                 {
-                    child.plan = new_child;
+                    child.node = new_child;
                 }
             }
 
@@ -1150,7 +1170,7 @@ fn ensure_distribution(
                 // - Ordering requirement cannot be satisfied by preserving ordering through repartitions, or
                 // - using order preserving variant is not desirable.
                 let ordering_satisfied = child
-                    .plan
+                    .node
                     .equivalence_properties()
                     .ordering_satisfy_requirement(required_input_ordering);
                 if (!ordering_satisfied || !order_preserving_variants_desirable)
@@ -1191,8 +1211,11 @@ fn ensure_distribution(
     )
     .collect::<Result<Vec<_>>>()?;
 
-    let children_plans = children.iter().map(|c| c.plan.clone()).collect::<Vec<_>>();
-    plan = if plan.as_any().is::<UnionExec>() && can_interleave(children_plans.iter()) {
+    let children_plans = children.iter().map(|c| c.node.clone()).collect::<Vec<_>>();
+    let interleave_union = can_interleave(children_plans.iter())
+        || (config.optimizer.prefer_union_interleave
+            && can_interleave_partitions(children_plans.iter()));
+    plan = if plan.as_any().is::<UnionExec>() && interleave_union {
         // Add a special case for [`UnionExec`] since we want to "bubble up"
         // hash-partitioned data. So instead of
         //
@@ -1216,6 +1239,9 @@ fn ensure_distribution(
         //     - Agg:
         //         Repartition (hash):
         //           Data
+        //
+        // When `prefer_union_interleave` is set, inputs that only agree on
+        // their partition count are interleaved as well.
         Arc::new(InterleaveExec::try_new(children_plans)?)
     } else {
         plan.with_new_children(children_plans)?
@@ -1234,7 +1260,7 @@ type DistributionContext = PlanContext<bool>;
 
 fn update_children(mut dist_context: DistributionContext) -> Result<DistributionContext> {
     for child_context in dist_context.children.iter_mut() {
-        let child_plan_any = child_context.plan.as_any();
+        let child_plan_any = child_context.node.as_any();
         child_context.data =
             if let Some(repartition) = child_plan_any.downcast_ref::<RepartitionExec>() {
                 !matches!(
@@ -1244,10 +1270,10 @@ fn update_children(mut dist_context: DistributionContext) -> Result<Distribution
             } else {
                 child_plan_any.is::<SortPreservingMergeExec>()
                     || child_plan_any.is::<CoalescePartitionsExec>()
-                    || child_context.plan.children().is_empty()
+                    || child_context.node.children().is_empty()
                     || child_context.children[0].data
                     || child_context
-                        .plan
+                        .node
                         .required_input_distribution()
                         .iter()
                         .zip(child_context.children.iter())
@@ -1438,6 +1464,7 @@ pub(crate) mod tests {
                 table_partition_cols: vec![],
                 output_ordering,
                 column_hints: None,
+                listing_pruning: None,
             },
             None,
             None,
@@ -1467,6 +1494,7 @@ pub(crate) mod tests {
                 table_partition_cols: vec![],
                 output_ordering,
                 column_hints: None,
+                listing_pruning: None,
             },
             None,
             None,
@@ -1490,6 +1518,7 @@ pub(crate) mod tests {
                 table_partition_cols: vec![],
                 output_ordering,
                 column_hints: None,
+                listing_pruning: None,
             },
             false,
             b',',
@@ -1521,6 +1550,7 @@ pub(crate) mod tests {
                 table_partition_cols: vec![],
                 output_ordering,
                 column_hints: None,
+                listing_pruning: None,
             },
             false,
             b',',
@@ -1694,7 +1724,7 @@ pub(crate) mod tests {
         config.optimizer.repartition_file_scans = false;
         config.optimizer.repartition_file_min_size = 1024;
         config.optimizer.prefer_existing_sort = prefer_existing_sort;
-        ensure_distribution(distribution_context, &config).map(|item| item.data.plan)
+        ensure_distribution(distribution_context, &config).map(|item| item.data.node)
     }
 
     /// Test whether plan matches with expected plan
@@ -1766,7 +1796,7 @@ pub(crate) mod tests {
                         .data()
                         .and_then(check_integrity)?;
                     // TODO: End state payloads will be checked here.
-                    adjusted.plan
+                    adjusted.node
                 } else {
                     // Run reorder_join_keys_to_inputs rule
                     $PLAN.clone().transform_up(|plan| {
@@ -3106,6 +3136,47 @@ pub(crate) mod tests {
         Ok(())
     }
 
+    #[test]
+    fn union_to_interleave_by_partition_count() -> Result<()> {
+        // Neither input is hash partitioned, but both have the same number
+        // of partitions after round robin repartitioning
+        let plan = union_exec(vec![
+            filter_exec(parquet_exec_multiple()),
+            filter_exec(parquet_exec_multiple()),
+        ]);
+
+        let mut config = ConfigOptions::new();
+        config.execution.target_partitions = 10;
+        config.optimizer.repartition_file_scans = false;
+
+        let expected = &[
+            "UnionExec",
+            "FilterExec: c@2 = 0",
+            "RepartitionExec: partitioning=RoundRobinBatch(10), input_partitions=2",
+            "ParquetExec: file_groups={2 groups: [[x], [y]]}, projection=[a, b, c, d, e]",
+            "FilterExec: c@2 = 0",
+            "RepartitionExec: partitioning=RoundRobinBatch(10), input_partitions=2",
+            "ParquetExec: file_groups={2 groups: [[x], [y]]}, projection=[a, b, c, d, e]",
+        ];
+        let dist_plan = EnforceDistribution::new().optimize(plan.clone(), &config)?;
+        assert_plan_txt!(expected, dist_plan);
+
+        config.optimizer.prefer_union_interleave = true;
+        let expected = &[
+            "InterleaveExec",
+            "FilterExec: c@2 = 0",
+            "RepartitionExec: partitioning=RoundRobinBatch(10), input_partitions=2",
+            "ParquetExec: file_groups={2 groups: [[x], [y]]}, projection=[a, b, c, d, e]",
+            "FilterExec: c@2 = 0",
+            "RepartitionExec: partitioning=RoundRobinBatch(10), input_partitions=2",
+            "ParquetExec: file_groups={2 groups: [[x], [y]]}, projection=[a, b, c, d, e]",
+        ];
+        let dist_plan = EnforceDistribution::new().optimize(plan, &config)?;
+        assert_plan_txt!(expected, dist_plan);
+
+        Ok(())
+    }
+
     #[test]
     fn added_repartition_to_single_partition() -> Result<()> {
         let alias = vec![("a".to_string(), "a".to_string())];
@@ -3727,6 +3798,7 @@ pub(crate) mod tests {
                         table_partition_cols: vec![],
                         output_ordering: vec![],
                         column_hints: None,
+                        listing_pruning: None,
                     },
                     false,
                     b',',
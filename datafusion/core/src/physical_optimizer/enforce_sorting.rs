@@ -91,7 +91,7 @@ fn update_sort_ctx_children(
     data: bool,
 ) -> Result<PlanWithCorrespondingSort> {
     for child_node in node.children.iter_mut() {
-        let plan = &child_node.plan;
+        let plan = &child_node.node;
         child_node.data = if is_sort(plan) {
             // Initiate connection:
             true
@@ -115,7 +115,7 @@ fn update_sort_ctx_children(
     }
 
     node.data = data;
-    node.update_plan_from_children()
+    node.update_node_from_children()
 }
 
 /// This object is used within the [`EnforceSorting`] rule to track the closest
@@ -131,7 +131,7 @@ fn update_coalesce_ctx_children(
     coalesce_context.data = if children.is_empty() {
         // Plan has no children, it cannot be a `CoalescePartitionsExec`.
         false
-    } else if is_coalesce_partitions(&coalesce_context.plan) {
+    } else if is_coalesce_partitions(&coalesce_context.node) {
         // Initiate a connection:
         true
     } else {
@@ -140,7 +140,7 @@ fn update_coalesce_ctx_children(
             // and connected to some `CoalescePartitionsExec`:
             node.data
                 && !matches!(
-                    coalesce_context.plan.required_input_distribution()[idx],
+                    coalesce_context.node.required_input_distribution()[idx],
                     Distribution::SinglePartition
                 )
         })
@@ -163,13 +163,13 @@ impl PhysicalOptimizerRule for EnforceSorting {
         let adjusted = plan_requirements.transform_up(ensure_sorting)?.data;
         let new_plan = if config.optimizer.repartition_sorts {
             let plan_with_coalesce_partitions =
-                PlanWithCorrespondingCoalescePartitions::new_default(adjusted.plan);
+                PlanWithCorrespondingCoalescePartitions::new_default(adjusted.node);
             let parallel = plan_with_coalesce_partitions
                 .transform_up(parallelize_sorts)
                 .data()?;
-            parallel.plan
+            parallel.node
         } else {
-            adjusted.plan
+            adjusted.node
         };
 
         let plan_with_pipeline_fixer = OrderPreservationContext::new_default(new_plan);
@@ -186,12 +186,12 @@ impl PhysicalOptimizerRule for EnforceSorting {
 
         // Execute a top-down traversal to exploit sort push-down opportunities
         // missed by the bottom-up traversal:
-        let mut sort_pushdown = SortPushDown::new_default(updated_plan.plan);
+        let mut sort_pushdown = SortPushDown::new_default(updated_plan.node);
         assign_initial_requirements(&mut sort_pushdown);
         let adjusted = sort_pushdown.transform_down(pushdown_sorts)?.data;
 
         adjusted
-            .plan
+            .node
             .transform_up(|plan| Ok(Transformed::yes(replace_with_partial_sort(plan)?)))
             .data()
     }
@@ -266,12 +266,12 @@ fn parallelize_sorts(
         // all have a single child. Therefore, if the first child has no
         // connection, we can return immediately.
         Ok(Transformed::no(requirements))
-    } else if (is_sort(&requirements.plan)
-        || is_sort_preserving_merge(&requirements.plan))
-        && requirements.plan.output_partitioning().partition_count() <= 1
+    } else if (is_sort(&requirements.node)
+        || is_sort_preserving_merge(&requirements.node))
+        && requirements.node.output_partitioning().partition_count() <= 1
     {
         // Take the initial sort expressions and requirements
-        let (sort_exprs, fetch) = get_sort_exprs(&requirements.plan)?;
+        let (sort_exprs, fetch) = get_sort_exprs(&requirements.node)?;
         let sort_reqs = PhysicalSortRequirement::from_sort_exprs(sort_exprs);
         let sort_exprs = sort_exprs.to_vec();
 
@@ -287,7 +287,7 @@ fn parallelize_sorts(
 
         requirements = add_sort_above_with_check(requirements, sort_reqs, fetch);
 
-        let spm = SortPreservingMergeExec::new(sort_exprs, requirements.plan.clone());
+        let spm = SortPreservingMergeExec::new(sort_exprs, requirements.node.clone());
         Ok(Transformed::yes(
             PlanWithCorrespondingCoalescePartitions::new(
                 Arc::new(spm.with_fetch(fetch)),
@@ -295,7 +295,7 @@ fn parallelize_sorts(
                 vec![requirements],
             ),
         ))
-    } else if is_coalesce_partitions(&requirements.plan) {
+    } else if is_coalesce_partitions(&requirements.node) {
         // There is an unnecessary `CoalescePartitionsExec` in the plan.
         // This will handle the recursive `CoalescePartitionsExec` plans.
         requirements = remove_corresponding_coalesce_in_sub_plan(requirements)?;
@@ -304,7 +304,7 @@ fn parallelize_sorts(
 
         Ok(Transformed::yes(
             PlanWithCorrespondingCoalescePartitions::new(
-                Arc::new(CoalescePartitionsExec::new(requirements.plan.clone())),
+                Arc::new(CoalescePartitionsExec::new(requirements.node.clone())),
                 false,
                 vec![requirements],
             ),
@@ -332,7 +332,7 @@ fn ensure_sorting(
         return Ok(maybe_requirements);
     };
 
-    let plan = &requirements.plan;
+    let plan = &requirements.node;
     let mut updated_children = vec![];
     for (idx, (required_ordering, mut child)) in plan
         .required_input_ordering()
@@ -340,10 +340,10 @@ fn ensure_sorting(
         .zip(requirements.children.into_iter())
         .enumerate()
     {
-        let physical_ordering = child.plan.output_ordering();
+        let physical_ordering = child.node.output_ordering();
 
         if let Some(required) = required_ordering {
-            let eq_properties = child.plan.equivalence_properties();
+            let eq_properties = child.node.equivalence_properties();
             if !eq_properties.ordering_satisfy_requirement(&required) {
                 // Make sure we preserve the ordering requirements:
                 if physical_ordering.is_some() {
@@ -369,7 +369,7 @@ fn ensure_sorting(
     if is_window(plan) && child_node.data {
         return adjust_window_sort_removal(requirements).map(Transformed::yes);
     } else if is_sort_preserving_merge(plan)
-        && child_node.plan.output_partitioning().partition_count() <= 1
+        && child_node.node.output_partitioning().partition_count() <= 1
     {
         // This `SortPreservingMergeExec` is unnecessary, input already has a
         // single partition.
@@ -385,14 +385,14 @@ fn ensure_sorting(
 fn analyze_immediate_sort_removal(
     mut node: PlanWithCorrespondingSort,
 ) -> Transformed<PlanWithCorrespondingSort> {
-    if let Some(sort_exec) = node.plan.as_any().downcast_ref::<SortExec>() {
+    if let Some(sort_exec) = node.node.as_any().downcast_ref::<SortExec>() {
         let sort_input = sort_exec.input();
         // If this sort is unnecessary, we should remove it:
         if sort_input
             .equivalence_properties()
             .ordering_satisfy(sort_exec.properties().output_ordering().unwrap_or(&[]))
         {
-            node.plan = if !sort_exec.preserve_partitioning()
+            node.node = if !sort_exec.preserve_partitioning()
                 && sort_input.output_partitioning().partition_count() > 1
             {
                 // Replace the sort with a sort-preserving merge:
@@ -422,14 +422,14 @@ fn adjust_window_sort_removal(
     let child_node = remove_corresponding_sort_from_sub_plan(
         window_tree.children.swap_remove(0),
         matches!(
-            window_tree.plan.required_input_distribution()[0],
+            window_tree.node.required_input_distribution()[0],
             Distribution::SinglePartition
         ),
     )?;
     window_tree.children.push(child_node);
 
-    let plan = window_tree.plan.as_any();
-    let child_plan = &window_tree.children[0].plan;
+    let plan = window_tree.node.as_any();
+    let child_plan = &window_tree.children[0].node;
     let (window_expr, new_window) =
         if let Some(exec) = plan.downcast_ref::<WindowAggExec>() {
             let window_expr = exec.window_expr();
@@ -445,14 +445,14 @@ fn adjust_window_sort_removal(
             return plan_err!("Expected WindowAggExec or BoundedWindowAggExec");
         };
 
-    window_tree.plan = if let Some(new_window) = new_window {
+    window_tree.node = if let Some(new_window) = new_window {
         // We were able to change the window to accommodate the input, use it:
         new_window
     } else {
         // We were unable to change the window to accommodate the input, so we
         // will insert a sort.
         let reqs = window_tree
-            .plan
+            .node
             .required_input_ordering()
             .swap_remove(0)
             .unwrap_or_default();
@@ -460,7 +460,7 @@ fn adjust_window_sort_removal(
         // Satisfy the ordering requirement so that the window can run:
         let mut child_node = window_tree.children.swap_remove(0);
         child_node = add_sort_above(child_node, reqs, None);
-        let child_plan = child_node.plan.clone();
+        let child_plan = child_node.node.clone();
         window_tree.children.push(child_node);
 
         if window_expr.iter().all(|e| e.uses_bounded_memory()) {
@@ -487,13 +487,13 @@ fn adjust_window_sort_removal(
 fn remove_corresponding_coalesce_in_sub_plan(
     mut requirements: PlanWithCorrespondingCoalescePartitions,
 ) -> Result<PlanWithCorrespondingCoalescePartitions> {
-    let plan = &requirements.plan;
+    let plan = &requirements.node;
     let children = &mut requirements.children;
-    if is_coalesce_partitions(&children[0].plan) {
+    if is_coalesce_partitions(&children[0].node) {
         // We can safely use the 0th index since we have a `CoalescePartitionsExec`.
         let mut new_child_node = children[0].children.swap_remove(0);
-        while new_child_node.plan.output_partitioning() == plan.output_partitioning()
-            && is_repartition(&new_child_node.plan)
+        while new_child_node.node.output_partitioning() == plan.output_partitioning()
+            && is_repartition(&new_child_node.node)
             && is_repartition(plan)
         {
             new_child_node = new_child_node.children.swap_remove(0)
@@ -513,7 +513,7 @@ fn remove_corresponding_coalesce_in_sub_plan(
             .collect::<Result<_>>()?;
     }
 
-    requirements.update_plan_from_children()
+    requirements.update_node_from_children()
 }
 
 /// Updates child to remove the unnecessary sort below it.
@@ -539,11 +539,11 @@ fn remove_corresponding_sort_from_sub_plan(
     requires_single_partition: bool,
 ) -> Result<PlanWithCorrespondingSort> {
     // A `SortExec` is always at the bottom of the tree.
-    if is_sort(&node.plan) {
+    if is_sort(&node.node) {
         node = node.children.swap_remove(0);
     } else {
         let mut any_connection = false;
-        let required_dist = node.plan.required_input_distribution();
+        let required_dist = node.node.required_input_distribution();
         node.children = node
             .children
             .into_iter()
@@ -565,25 +565,25 @@ fn remove_corresponding_sort_from_sub_plan(
         }
 
         // Replace with variants that do not preserve order.
-        if is_sort_preserving_merge(&node.plan) {
+        if is_sort_preserving_merge(&node.node) {
             node.children = node.children.swap_remove(0).children;
-            node.plan = node.plan.children().swap_remove(0);
+            node.node = node.node.children().swap_remove(0);
         } else if let Some(repartition) =
-            node.plan.as_any().downcast_ref::<RepartitionExec>()
+            node.node.as_any().downcast_ref::<RepartitionExec>()
         {
-            node.plan = Arc::new(RepartitionExec::try_new(
-                node.children[0].plan.clone(),
+            node.node = Arc::new(RepartitionExec::try_new(
+                node.children[0].node.clone(),
                 repartition.properties().output_partitioning().clone(),
             )?) as _;
         }
     };
     // Deleting a merging sort may invalidate distribution requirements.
     // Ensure that we stay compliant with such requirements:
-    if requires_single_partition && node.plan.output_partitioning().partition_count() > 1
+    if requires_single_partition && node.node.output_partitioning().partition_count() > 1
     {
         // If there is existing ordering, to preserve ordering use
         // `SortPreservingMergeExec` instead of a `CoalescePartitionsExec`.
-        let plan = node.plan.clone();
+        let plan = node.node.clone();
         let plan = if let Some(ordering) = plan.output_ordering() {
             Arc::new(SortPreservingMergeExec::new(ordering.to_vec(), plan)) as _
         } else {
@@ -686,15 +686,15 @@ mod tests {
 
                 let new_plan = if state.config_options().optimizer.repartition_sorts {
                     let plan_with_coalesce_partitions =
-                        PlanWithCorrespondingCoalescePartitions::new_default(adjusted.plan);
+                        PlanWithCorrespondingCoalescePartitions::new_default(adjusted.node);
                     let parallel = plan_with_coalesce_partitions
                         .transform_up(parallelize_sorts)
                         .data()
                         .and_then(check_integrity)?;
                     // TODO: End state payloads will be checked here.
-                    parallel.plan
+                    parallel.node
                 } else {
-                    adjusted.plan
+                    adjusted.node
                 };
 
                 let plan_with_pipeline_fixer = OrderPreservationContext::new_default(new_plan);
@@ -711,7 +711,7 @@ mod tests {
                     .and_then(check_integrity)?;
                 // TODO: End state payloads will be checked here.
 
-                let mut sort_pushdown = SortPushDown::new_default(updated_plan.plan);
+                let mut sort_pushdown = SortPushDown::new_default(updated_plan.node);
                 assign_initial_requirements(&mut sort_pushdown);
                 sort_pushdown
                     .transform_down(pushdown_sorts)
@@ -28,13 +28,17 @@ use std::sync::Arc;
 use crate::config::ConfigOptions;
 use crate::error::Result;
 use crate::physical_optimizer::PhysicalOptimizerRule;
+use crate::physical_plan::coalesce_batches::CoalesceBatchesExec;
+use crate::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use crate::physical_plan::filter::FilterExec;
 use crate::physical_plan::joins::utils::{ColumnIndex, JoinFilter};
 use crate::physical_plan::joins::{
     CrossJoinExec, HashJoinExec, NestedLoopJoinExec, PartitionMode,
     StreamJoinPartitionMode, SymmetricHashJoinExec,
 };
 use crate::physical_plan::projection::ProjectionExec;
-use crate::physical_plan::{ExecutionPlan, ExecutionPlanProperties};
+use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::{ExecutionPlan, ExecutionPlanProperties, Statistics};
 
 use arrow_schema::Schema;
 use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
@@ -89,10 +93,11 @@ fn supports_collect_by_thresholds(
     plan: &dyn ExecutionPlan,
     threshold_byte_size: usize,
     threshold_num_rows: usize,
+    filter_selectivity: f64,
 ) -> bool {
     // Currently we do not trust the 0 value from stats, due to stats collection might have bug
     // TODO check the logic in datasource::get_statistics_with_limit()
-    let Ok(stats) = plan.statistics() else {
+    let Ok(stats) = estimated_statistics(plan, filter_selectivity) else {
         return false;
     };
 
@@ -105,6 +110,73 @@ fn supports_collect_by_thresholds(
     }
 }
 
+/// Estimates the statistics of `plan` after the filters it applies.
+///
+/// Plan statistics already account for filters whose selectivity can be
+/// derived from column statistics, but a filter they can't narrow down, such
+/// as an equality on a column without min/max values, is estimated to keep
+/// every row of its input. Such filters are assumed to keep
+/// `filter_selectivity` of their input instead, so that heavily filtered
+/// tables are recognized as small enough to be broadcast. A
+/// `filter_selectivity` of 1 returns the plan statistics unchanged.
+fn estimated_statistics(
+    plan: &dyn ExecutionPlan,
+    filter_selectivity: f64,
+) -> Result<Statistics> {
+    let stats = plan.statistics()?;
+    if filter_selectivity >= 1.0 {
+        return Ok(stats);
+    }
+    let selectivity = unestimated_filter_selectivity(plan, filter_selectivity)?;
+    if selectivity >= 1.0 {
+        return Ok(stats);
+    }
+
+    let mut stats = stats.into_inexact();
+    stats.num_rows = stats.num_rows.with_estimated_selectivity(selectivity);
+    stats.total_byte_size = stats
+        .total_byte_size
+        .with_estimated_selectivity(selectivity);
+    Ok(stats)
+}
+
+/// Returns the combined selectivity to apply for the filters below `plan`
+/// whose statistics keep every row of their input, looking through the
+/// operators that do not change the number of rows.
+fn unestimated_filter_selectivity(
+    plan: &dyn ExecutionPlan,
+    filter_selectivity: f64,
+) -> Result<f64> {
+    let plan_any = plan.as_any();
+    if let Some(filter) = plan_any.downcast_ref::<FilterExec>() {
+        let input = filter.input();
+        let selectivity =
+            unestimated_filter_selectivity(input.as_ref(), filter_selectivity)?;
+        let input_rows = input.statistics()?.num_rows;
+        let output_rows = filter.statistics()?.num_rows;
+        let estimated = matches!(
+            (output_rows.get_value(), input_rows.get_value()),
+            (Some(output), Some(input)) if output < input
+        );
+        Ok(if estimated {
+            selectivity
+        } else {
+            selectivity * filter_selectivity
+        })
+    } else if plan_any.is::<ProjectionExec>()
+        || plan_any.is::<CoalesceBatchesExec>()
+        || plan_any.is::<CoalescePartitionsExec>()
+        || plan_any.is::<RepartitionExec>()
+    {
+        match plan.children().as_slice() {
+            [input] => unestimated_filter_selectivity(input.as_ref(), filter_selectivity),
+            _ => Ok(1.0),
+        }
+    } else {
+        Ok(1.0)
+    }
+}
+
 /// Predicate that checks whether the given join type supports input swapping.
 fn supports_swap(join_type: JoinType) -> bool {
     matches!(
@@ -308,15 +380,24 @@ impl PhysicalOptimizerRule for JoinSelection {
         //   do not modify join sides.
         // - We will also swap left and right sides for cross joins so that the left
         //   side is the small side.
+        // - When `hash_join_broadcast_by_estimate` is enabled, the sizes compared
+        //   to the thresholds are estimated after filtering, see
+        //   [`estimated_statistics`].
         let config = &config.optimizer;
         let collect_threshold_byte_size = config.hash_join_single_partition_threshold;
         let collect_threshold_num_rows = config.hash_join_single_partition_threshold_rows;
+        let filter_selectivity = if config.hash_join_broadcast_by_estimate {
+            config.default_filter_selectivity as f64 / 100.0
+        } else {
+            1.0
+        };
         new_plan
             .transform_up(|plan| {
                 statistical_join_selection_subrule(
                     plan,
                     collect_threshold_byte_size,
                     collect_threshold_num_rows,
+                    filter_selectivity,
                 )
             })
             .data()
@@ -336,12 +417,13 @@ impl PhysicalOptimizerRule for JoinSelection {
 /// This function will first consider the given join type and check whether the
 /// `CollectLeft` mode is applicable. Otherwise, it will try to swap the join sides.
 /// When the `ignore_threshold` is false, this function will also check left
-/// and right sizes in bytes or rows.
+/// and right sizes in bytes or rows, estimated with [`estimated_statistics`].
 fn try_collect_left(
     hash_join: &HashJoinExec,
     ignore_threshold: bool,
     threshold_byte_size: usize,
     threshold_num_rows: usize,
+    filter_selectivity: f64,
 ) -> Result<Option<Arc<dyn ExecutionPlan>>> {
     let left = hash_join.left();
     let right = hash_join.right();
@@ -351,12 +433,14 @@ fn try_collect_left(
             &**left,
             threshold_byte_size,
             threshold_num_rows,
+            filter_selectivity,
         );
     let right_can_collect = ignore_threshold
         || supports_collect_by_thresholds(
             &**right,
             threshold_byte_size,
             threshold_num_rows,
+            filter_selectivity,
         );
 
     match (left_can_collect, right_can_collect) {
@@ -425,62 +509,72 @@ fn statistical_join_selection_subrule(
     plan: Arc<dyn ExecutionPlan>,
     collect_threshold_byte_size: usize,
     collect_threshold_num_rows: usize,
+    filter_selectivity: f64,
 ) -> Result<Transformed<Arc<dyn ExecutionPlan>>> {
-    let transformed =
-        if let Some(hash_join) = plan.as_any().downcast_ref::<HashJoinExec>() {
-            match hash_join.partition_mode() {
-                PartitionMode::Auto => try_collect_left(
-                    hash_join,
-                    false,
-                    collect_threshold_byte_size,
-                    collect_threshold_num_rows,
-                )?
+    // A null-aware anti join has to keep its inputs and its `CollectLeft` mode
+    if let Some(hash_join) = plan.as_any().downcast_ref::<HashJoinExec>() {
+        if hash_join.null_aware() {
+            return Ok(Transformed::no(plan));
+        }
+    }
+
+    let transformed = if let Some(hash_join) =
+        plan.as_any().downcast_ref::<HashJoinExec>()
+    {
+        match hash_join.partition_mode() {
+            PartitionMode::Auto => try_collect_left(
+                hash_join,
+                false,
+                collect_threshold_byte_size,
+                collect_threshold_num_rows,
+                filter_selectivity,
+            )?
+            .map_or_else(
+                || partitioned_hash_join(hash_join).map(Some),
+                |v| Ok(Some(v)),
+            )?,
+            PartitionMode::CollectLeft => try_collect_left(hash_join, true, 0, 0, 1.0)?
                 .map_or_else(
-                    || partitioned_hash_join(hash_join).map(Some),
-                    |v| Ok(Some(v)),
-                )?,
-                PartitionMode::CollectLeft => try_collect_left(hash_join, true, 0, 0)?
-                    .map_or_else(
-                        || partitioned_hash_join(hash_join).map(Some),
-                        |v| Ok(Some(v)),
-                    )?,
-                PartitionMode::Partitioned => {
-                    let left = hash_join.left();
-                    let right = hash_join.right();
-                    if should_swap_join_order(&**left, &**right)?
-                        && supports_swap(*hash_join.join_type())
-                    {
-                        swap_hash_join(hash_join, PartitionMode::Partitioned).map(Some)?
-                    } else {
-                        None
-                    }
+                || partitioned_hash_join(hash_join).map(Some),
+                |v| Ok(Some(v)),
+            )?,
+            PartitionMode::Partitioned => {
+                let left = hash_join.left();
+                let right = hash_join.right();
+                if should_swap_join_order(&**left, &**right)?
+                    && supports_swap(*hash_join.join_type())
+                {
+                    swap_hash_join(hash_join, PartitionMode::Partitioned).map(Some)?
+                } else {
+                    None
                 }
             }
-        } else if let Some(cross_join) = plan.as_any().downcast_ref::<CrossJoinExec>() {
-            let left = cross_join.left();
-            let right = cross_join.right();
-            if should_swap_join_order(&**left, &**right)? {
-                let new_join = CrossJoinExec::new(Arc::clone(right), Arc::clone(left));
-                // TODO avoid adding ProjectionExec again and again, only adding Final Projection
-                let proj: Arc<dyn ExecutionPlan> = Arc::new(ProjectionExec::try_new(
-                    swap_reverting_projection(&left.schema(), &right.schema()),
-                    Arc::new(new_join),
-                )?);
-                Some(proj)
-            } else {
-                None
-            }
-        } else if let Some(nl_join) = plan.as_any().downcast_ref::<NestedLoopJoinExec>() {
-            let left = nl_join.left();
-            let right = nl_join.right();
-            if should_swap_join_order(&**left, &**right)? {
-                swap_nl_join(nl_join).map(Some)?
-            } else {
-                None
-            }
+        }
+    } else if let Some(cross_join) = plan.as_any().downcast_ref::<CrossJoinExec>() {
+        let left = cross_join.left();
+        let right = cross_join.right();
+        if should_swap_join_order(&**left, &**right)? {
+            let new_join = CrossJoinExec::new(Arc::clone(right), Arc::clone(left));
+            // TODO avoid adding ProjectionExec again and again, only adding Final Projection
+            let proj: Arc<dyn ExecutionPlan> = Arc::new(ProjectionExec::try_new(
+                swap_reverting_projection(&left.schema(), &right.schema()),
+                Arc::new(new_join),
+            )?);
+            Some(proj)
         } else {
             None
-        };
+        }
+    } else if let Some(nl_join) = plan.as_any().downcast_ref::<NestedLoopJoinExec>() {
+        let left = nl_join.left();
+        let right = nl_join.right();
+        if should_swap_join_order(&**left, &**right)? {
+            swap_nl_join(nl_join).map(Some)?
+        } else {
+            None
+        }
+    } else {
+        None
+    };
 
     Ok(if let Some(transformed) = transformed {
         Transformed::yes(transformed)
@@ -517,7 +611,7 @@ fn hash_join_convert_symmetric_subrule(
         let left_unbounded = hash_join.left.execution_mode().is_unbounded();
         let right_unbounded = hash_join.right.execution_mode().is_unbounded();
         // Process only if both left and right sides are unbounded.
-        if left_unbounded && right_unbounded {
+        if left_unbounded && right_unbounded && !hash_join.null_aware() {
             // Determine the partition mode based on configuration.
             let mode = if config_options.optimizer.repartition_joins {
                 StreamJoinPartitionMode::Partitioned
@@ -646,6 +740,7 @@ fn hash_join_swap_subrule(
     if let Some(hash_join) = input.as_any().downcast_ref::<HashJoinExec>() {
         if hash_join.left.execution_mode().is_unbounded()
             && !hash_join.right.execution_mode().is_unbounded()
+            && !hash_join.null_aware()
             && matches!(
                 *hash_join.join_type(),
                 JoinType::Inner
@@ -712,7 +807,7 @@ mod tests_statistical {
     use arrow::datatypes::{DataType, Field};
     use datafusion_common::{stats::Precision, JoinType, ScalarValue};
     use datafusion_expr::Operator;
-    use datafusion_physical_expr::expressions::BinaryExpr;
+    use datafusion_physical_expr::expressions::{BinaryExpr, Literal};
     use datafusion_physical_expr::PhysicalExprRef;
 
     use rstest::rstest;
@@ -899,6 +994,7 @@ mod tests_statistical {
                 plan,
                 collect_left_threshold,
                 collect_threshold_num_rows,
+                1.0,
             )
         })?;
         Ok(())
@@ -1477,6 +1573,58 @@ mod tests_statistical {
         check_join_partition_mode(big, empty, join_on, false, PartitionMode::Partitioned);
     }
 
+    #[tokio::test]
+    async fn test_join_selection_collect_left_filtered_estimate() -> Result<()> {
+        let big = Arc::new(StatisticsExec::new(
+            big_statistics(),
+            Schema::new(vec![Field::new("big_col", DataType::Int32, false)]),
+        ));
+        let bigger = Arc::new(StatisticsExec::new(
+            bigger_statistics(),
+            Schema::new(vec![Field::new("bigger_col", DataType::Int32, false)]),
+        ));
+
+        // `big_col` has no min/max values, so the statistics of the filter
+        // keep every row of its input
+        let predicate = Arc::new(BinaryExpr::new(
+            Arc::new(Column::new_with_schema("big_col", &big.schema())?),
+            Operator::Eq,
+            Arc::new(Literal::new(ScalarValue::Int32(Some(5)))),
+        ));
+        let filtered = Arc::new(FilterExec::try_new(predicate, big)?);
+        let join_on = vec![(
+            Arc::new(Column::new_with_schema("big_col", &filtered.schema())?) as _,
+            Arc::new(Column::new_with_schema("bigger_col", &bigger.schema())?) as _,
+        )];
+        let join: Arc<dyn ExecutionPlan> = Arc::new(HashJoinExec::try_new(
+            filtered,
+            bigger,
+            join_on,
+            None,
+            &JoinType::Inner,
+            None,
+            PartitionMode::Auto,
+            false,
+        )?);
+
+        let partition_mode = |config: &ConfigOptions| -> Result<PartitionMode> {
+            let optimized = JoinSelection::new().optimize(join.clone(), config)?;
+            let hash_join = optimized
+                .as_any()
+                .downcast_ref::<HashJoinExec>()
+                .expect("The type of the plan should not be changed");
+            Ok(*hash_join.partition_mode())
+        };
+
+        let mut config = ConfigOptions::new();
+        assert_eq!(partition_mode(&config)?, PartitionMode::Partitioned);
+
+        // with the default selectivity the filtered input is below the thresholds
+        config.optimizer.hash_join_broadcast_by_estimate = true;
+        assert_eq!(partition_mode(&config)?, PartitionMode::CollectLeft);
+        Ok(())
+    }
+
     fn check_join_partition_mode(
         left: Arc<StatisticsExec>,
         right: Arc<StatisticsExec>,
@@ -31,9 +31,12 @@ pub mod join_selection;
 pub mod limited_distinct_aggregation;
 pub mod optimizer;
 pub mod output_requirements;
+#[cfg(feature = "parquet")]
+pub mod parquet_filter_pushdown;
 pub mod pipeline_checker;
 mod projection_pushdown;
 pub mod pruning;
+pub mod repartition_windows;
 pub mod replace_with_order_preserving_variants;
 mod sort_pushdown;
 pub mod topk_aggregation;
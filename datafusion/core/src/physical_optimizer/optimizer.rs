@@ -30,7 +30,10 @@ use crate::physical_optimizer::enforce_sorting::EnforceSorting;
 use crate::physical_optimizer::join_selection::JoinSelection;
 use crate::physical_optimizer::limited_distinct_aggregation::LimitedDistinctAggregation;
 use crate::physical_optimizer::output_requirements::OutputRequirements;
+#[cfg(feature = "parquet")]
+use crate::physical_optimizer::parquet_filter_pushdown::ParquetFilterPushdown;
 use crate::physical_optimizer::pipeline_checker::PipelineChecker;
+use crate::physical_optimizer::repartition_windows::RepartitionWindows;
 use crate::physical_optimizer::topk_aggregation::TopKAggregation;
 use crate::{error::Result, physical_plan::ExecutionPlan};
 
@@ -75,7 +78,8 @@ impl Default for PhysicalOptimizer {
 impl PhysicalOptimizer {
     /// Create a new optimizer using the recommended list of rules
     pub fn new() -> Self {
-        let rules: Vec<Arc<dyn PhysicalOptimizerRule + Send + Sync>> = vec![
+        #[allow(unused_mut)]
+        let mut rules: Vec<Arc<dyn PhysicalOptimizerRule + Send + Sync>> = vec![
             // If there is a output requirement of the query, make sure that
             // this information is not lost across different rules during optimization.
             Arc::new(OutputRequirements::new_add_mode()),
@@ -90,6 +94,10 @@ impl PhysicalOptimizer {
             // as that rule may inject other operations in between the different AggregateExecs.
             // Applying the rule early means only directly-connected AggregateExecs must be examined.
             Arc::new(LimitedDistinctAggregation::new()),
+            // The RepartitionWindows rule sets the partition keys of window operators so that
+            // they can run in parallel. It should run before the EnforceDistribution rule, which
+            // adds the repartitioning required by the partition keys.
+            Arc::new(RepartitionWindows::new()),
             // The EnforceDistribution rule is for adding essential repartitioning to satisfy distribution
             // requirements. Please make sure that the whole plan tree is determined before this rule.
             // This rule increases parallelism if doing so is beneficial to the physical plan; i.e. at
@@ -130,6 +138,11 @@ impl PhysicalOptimizer {
             // reduced by narrowing their input tables.
             Arc::new(ProjectionPushdown::new()),
         ];
+        // The ParquetFilterPushdown rule pushes the predicates of the filters into the
+        // parquet scans below them. It should run after the ProjectionPushdown rule, which
+        // merges the projections between the filters and the scans into the scans.
+        #[cfg(feature = "parquet")]
+        rules.push(Arc::new(ParquetFilterPushdown::new()));
 
         Self::with_rules(rules)
     }
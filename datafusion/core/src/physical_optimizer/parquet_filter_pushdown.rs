@@ -0,0 +1,348 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! ParquetFilterPushdown optimizer rule that pushes the predicates of the
+//! filters above parquet scans into the scans
+
+use std::sync::Arc;
+
+use crate::config::ConfigOptions;
+use crate::datasource::physical_plan::ParquetExec;
+use crate::error::Result;
+use crate::physical_optimizer::PhysicalOptimizerRule;
+use crate::physical_plan::coalesce_batches::CoalesceBatchesExec;
+use crate::physical_plan::expressions::{BinaryExpr, Column};
+use crate::physical_plan::filter::FilterExec;
+use crate::physical_plan::projection::ProjectionExec;
+use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::{ExecutionPlan, PhysicalExpr};
+
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_expr::{Operator, ScalarFunctionDefinition, Volatility};
+use datafusion_physical_expr::utils::split_conjunction;
+use datafusion_physical_expr::{physical_exprs_contains, ScalarFunctionExpr};
+
+/// Optimizer rule that pushes the predicates of [`FilterExec`]s into the
+/// [`ParquetExec`]s they read, which use them to prune row groups and pages
+/// and, with `datafusion.execution.parquet.pushdown_filters`, as row filters.
+///
+/// The logical optimizer pushes most filters into the scans, but the filters
+/// of the physical plans built by other means, and the filters it could not
+/// push, remain above the scans. Once the `ProjectionPushdown` rule merged
+/// the projections into the scans, the columns of these predicates refer to
+/// the merged projections: this rule resolves them again against the schema
+/// of the files, through the [`ProjectionExec`]s, [`RepartitionExec`]s and
+/// [`CoalesceBatchesExec`]s between the filters and the scans.
+///
+/// The pushed conjuncts are the deterministic ones which only refer to file
+/// columns and are not already part of the predicate of the scan. The
+/// filters are kept, since the scans do not apply their predicates to every
+/// row. The rule is enabled by the
+/// `datafusion.optimizer.push_down_parquet_filters` configuration option,
+/// and leaves the scans with pruning disabled unchanged.
+#[derive(Default)]
+pub struct ParquetFilterPushdown {}
+
+impl ParquetFilterPushdown {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl PhysicalOptimizerRule for ParquetFilterPushdown {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.optimizer.push_down_parquet_filters {
+            return Ok(plan);
+        }
+
+        plan.transform_down(|plan| {
+            let Some(filter) = plan.as_any().downcast_ref::<FilterExec>() else {
+                return Ok(Transformed::no(plan));
+            };
+            match push_down(filter.predicate(), filter.input())? {
+                Some(input) => Ok(Transformed::yes(plan.with_new_children(vec![input])?)),
+                None => Ok(Transformed::no(plan)),
+            }
+        })
+        .data()
+    }
+
+    fn name(&self) -> &str {
+        "ParquetFilterPushdown"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+/// Returns `input` with the conjuncts of `predicate`, which refers to the
+/// output of `input`, pushed into the parquet scan `input` reads, or `None`
+/// if no conjunct was pushed
+fn push_down(
+    predicate: &Arc<dyn PhysicalExpr>,
+    input: &Arc<dyn ExecutionPlan>,
+) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+    let any = input.as_any();
+    if let Some(scan) = any.downcast_ref::<ParquetExec>() {
+        let scan = push_down_into_scan(predicate, scan)?;
+        return Ok(scan.map(|scan| Arc::new(scan) as _));
+    }
+    let pushed = if let Some(projection) = any.downcast_ref::<ProjectionExec>() {
+        // refer to the input of the projection instead of its output
+        let predicate = predicate
+            .clone()
+            .transform_up(|expr| {
+                Ok(match expr.as_any().downcast_ref::<Column>() {
+                    Some(column) => {
+                        Transformed::yes(projection.expr()[column.index()].0.clone())
+                    }
+                    None => Transformed::no(expr),
+                })
+            })
+            .data()?;
+        push_down(&predicate, projection.input())?
+    } else if any.is::<RepartitionExec>() || any.is::<CoalesceBatchesExec>() {
+        push_down(predicate, &input.children()[0])?
+    } else {
+        None
+    };
+    pushed
+        .map(|child| input.clone().with_new_children(vec![child]))
+        .transpose()
+}
+
+/// Returns `scan` with the conjuncts of `predicate`, which refers to the
+/// output of `scan`, added to its predicate, or `None` if no conjunct was
+/// added
+fn push_down_into_scan(
+    predicate: &Arc<dyn PhysicalExpr>,
+    scan: &ParquetExec,
+) -> Result<Option<ParquetExec>> {
+    if !scan.table_parquet_options().global.pruning {
+        return Ok(None);
+    }
+    let config = scan.base_config();
+    let file_schema = &config.file_schema;
+    // the columns of the predicate of the scan refer to the columns of the
+    // table, the file columns followed by the partition columns
+    let projection = match &config.projection {
+        Some(projection) => projection.clone(),
+        None => (0..file_schema.fields().len()).collect(),
+    };
+
+    let existing: Vec<_> = scan
+        .predicate()
+        .map(|predicate| split_conjunction(predicate).into_iter().cloned().collect())
+        .unwrap_or_default();
+    let mut pushed = vec![];
+    for conjunct in split_conjunction(predicate) {
+        let mut resolved = true;
+        let conjunct = conjunct
+            .clone()
+            .transform_up(|expr| {
+                if is_volatile(&expr) {
+                    resolved = false;
+                }
+                let Some(column) = expr.as_any().downcast_ref::<Column>() else {
+                    return Ok(Transformed::no(expr));
+                };
+                match projection.get(column.index()) {
+                    Some(&index) if index < file_schema.fields().len() => {
+                        let name = file_schema.field(index).name();
+                        Ok(Transformed::yes(Arc::new(Column::new(name, index)) as _))
+                    }
+                    _ => {
+                        resolved = false;
+                        Ok(Transformed::no(expr))
+                    }
+                }
+            })
+            .data()?;
+        if resolved
+            && !physical_exprs_contains(&existing, &conjunct)
+            && !physical_exprs_contains(&pushed, &conjunct)
+        {
+            pushed.push(conjunct);
+        }
+    }
+    if pushed.is_empty() {
+        return Ok(None);
+    }
+
+    let predicate = existing
+        .into_iter()
+        .chain(pushed)
+        .reduce(|left, right| Arc::new(BinaryExpr::new(left, Operator::And, right)) as _);
+    Ok(Some(scan.with_predicate(predicate)))
+}
+
+/// Returns true if `expr` calls a volatile function, whose result would
+/// differ between the scan and the filter
+fn is_volatile(expr: &Arc<dyn PhysicalExpr>) -> bool {
+    expr.as_any()
+        .downcast_ref::<ScalarFunctionExpr>()
+        .is_some_and(|function| match function.fun() {
+            ScalarFunctionDefinition::UDF(udf) => {
+                udf.signature().volatility == Volatility::Volatile
+            }
+            ScalarFunctionDefinition::Name(_) => true,
+        })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::datasource::listing::PartitionedFile;
+    use crate::datasource::physical_plan::FileScanConfig;
+    use crate::physical_optimizer::test_utils::{filter_exec, repartition_exec};
+
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+    use datafusion_common::Statistics;
+    use datafusion_execution::object_store::ObjectStoreUrl;
+    use datafusion_physical_expr::expressions::{binary, col, lit};
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int64, true),
+            Field::new("b", DataType::Int64, true),
+            Field::new("c", DataType::Int64, true),
+        ]))
+    }
+
+    /// A scan of the columns `c` and `a` of the files, and of the partition
+    /// column `p`
+    fn parquet_exec(schema: &SchemaRef) -> Arc<dyn ExecutionPlan> {
+        Arc::new(ParquetExec::new(
+            FileScanConfig {
+                object_store_url: ObjectStoreUrl::parse("test:///").unwrap(),
+                file_schema: schema.clone(),
+                file_groups: vec![vec![PartitionedFile::new("x".to_string(), 100)]],
+                statistics: Statistics::new_unknown(schema),
+                projection: Some(vec![2, 0, 3]),
+                limit: None,
+                table_partition_cols: vec![Field::new("p", DataType::Int64, true)],
+                output_ordering: vec![],
+                column_hints: None,
+                listing_pruning: None,
+            },
+            None,
+            None,
+            Default::default(),
+        ))
+    }
+
+    /// Returns the predicate of the parquet scan of `plan` after optimizing it
+    fn optimized_scan_predicate(plan: Arc<dyn ExecutionPlan>) -> Result<String> {
+        let mut plan =
+            ParquetFilterPushdown::new().optimize(plan, &ConfigOptions::new())?;
+        while !plan.children().is_empty() {
+            plan = plan.children()[0].clone();
+        }
+        let scan = plan.as_any().downcast_ref::<ParquetExec>().unwrap();
+        Ok(scan
+            .predicate()
+            .map(|predicate| predicate.to_string())
+            .unwrap_or_default())
+    }
+
+    #[test]
+    fn push_down_through_projection() -> Result<()> {
+        let scan = parquet_exec(&schema());
+        // x = c + 1, y = a, z = p
+        let scan_schema = scan.schema();
+        let projection = Arc::new(ProjectionExec::try_new(
+            vec![
+                (
+                    binary(
+                        col("c", &scan_schema)?,
+                        Operator::Plus,
+                        lit(1i64),
+                        &scan_schema,
+                    )?,
+                    "x".to_string(),
+                ),
+                (col("a", &scan_schema)?, "y".to_string()),
+                (col("p", &scan_schema)?, "z".to_string()),
+            ],
+            repartition_exec(scan),
+        )?);
+        let schema = projection.schema();
+        let gt = |name, value: i64| {
+            binary(
+                col(name, &schema).unwrap(),
+                Operator::Gt,
+                lit(value),
+                &schema,
+            )
+            .unwrap()
+        };
+        let predicate = [gt("x", 1), gt("y", 2), gt("z", 3)]
+            .into_iter()
+            .reduce(|left, right| {
+                Arc::new(BinaryExpr::new(left, Operator::And, right)) as _
+            })
+            .unwrap();
+        let plan = filter_exec(predicate, projection);
+
+        // the predicate on the partition column is not pushed, and the
+        // columns refer to the file schema
+        assert_eq!(optimized_scan_predicate(plan)?, "c@2 + 1 > 1 AND a@0 > 2");
+        Ok(())
+    }
+
+    #[test]
+    fn keep_existing_predicate() -> Result<()> {
+        let scan = parquet_exec(&schema());
+        let scan_schema = scan.schema();
+        let eq = |name, value: i64| {
+            binary(
+                col(name, &scan_schema).unwrap(),
+                Operator::Eq,
+                lit(value),
+                &scan_schema,
+            )
+            .unwrap()
+        };
+        let plan = filter_exec(eq("a", 1), scan);
+        let optimized =
+            ParquetFilterPushdown::new().optimize(plan.clone(), &ConfigOptions::new())?;
+        assert_eq!(optimized_scan_predicate(plan)?, "a@0 = 1");
+
+        // the conjuncts already pushed are not pushed again
+        let plan = filter_exec(
+            Arc::new(BinaryExpr::new(eq("a", 1), Operator::And, eq("c", 2))),
+            optimized.children()[0].clone(),
+        );
+        assert_eq!(optimized_scan_predicate(plan)?, "a@0 = 1 AND c@2 = 2");
+
+        // the rule can be disabled
+        let mut config = ConfigOptions::new();
+        config.optimizer.push_down_parquet_filters = false;
+        let plan = filter_exec(eq("a", 1), parquet_exec(&schema()));
+        let optimized = ParquetFilterPushdown::new().optimize(plan, &config)?;
+        let scan = optimized.children()[0].clone();
+        let scan = scan.as_any().downcast_ref::<ParquetExec>().unwrap();
+        assert!(scan.predicate().is_none());
+        Ok(())
+    }
+}
@@ -46,7 +46,7 @@ use datafusion_common::config::ConfigOptions;
 use datafusion_common::tree_node::{
     Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
 };
-use datafusion_common::{DataFusionError, JoinSide};
+use datafusion_common::{DataFusionError, JoinSide, JoinType};
 use datafusion_physical_expr::expressions::{Column, Literal};
 use datafusion_physical_expr::{
     utils::collect_columns, Partitioning, PhysicalExpr, PhysicalExprRef,
@@ -138,9 +138,15 @@ pub fn remove_unnecessary_projections(
                 |e| Ok(Some(e)),
             )?
         } else if let Some(cross_join) = input.downcast_ref::<CrossJoinExec>() {
-            try_swapping_with_cross_join(projection, cross_join)?
+            try_swapping_with_cross_join(projection, cross_join)?.map_or_else(
+                || try_narrowing_cross_join_inputs(projection, cross_join),
+                |e| Ok(Some(e)),
+            )?
         } else if let Some(nl_join) = input.downcast_ref::<NestedLoopJoinExec>() {
-            try_swapping_with_nested_loop_join(projection, nl_join)?
+            try_swapping_with_nested_loop_join(projection, nl_join)?.map_or_else(
+                || try_narrowing_nested_loop_join_inputs(projection, nl_join),
+                |e| Ok(Some(e)),
+            )?
         } else if let Some(sm_join) = input.downcast_ref::<SortMergeJoinExec>() {
             try_swapping_with_sort_merge_join(projection, sm_join)?
         } else if let Some(sym_join) = input.downcast_ref::<SymmetricHashJoinExec>() {
@@ -665,16 +671,19 @@ fn try_pushdown_through_hash_join(
         hash_join.right(),
     )?;
 
-    Ok(Some(Arc::new(HashJoinExec::try_new(
-        Arc::new(new_left),
-        Arc::new(new_right),
-        new_on,
-        new_filter,
-        hash_join.join_type(),
-        hash_join.projection.clone(),
-        *hash_join.partition_mode(),
-        hash_join.null_equals_null,
-    )?)))
+    Ok(Some(Arc::new(
+        HashJoinExec::try_new(
+            Arc::new(new_left),
+            Arc::new(new_right),
+            new_on,
+            new_filter,
+            hash_join.join_type(),
+            hash_join.projection.clone(),
+            *hash_join.partition_mode(),
+            hash_join.null_equals_null,
+        )?
+        .with_null_aware(hash_join.null_aware())?,
+    )))
 }
 
 /// Tries to swap the projection with its input [`CrossJoinExec`]. If it can be done,
@@ -774,6 +783,187 @@ fn try_swapping_with_nested_loop_join(
     )?)))
 }
 
+/// Tries to push projections keeping only the columns `projection` uses into
+/// the inputs of `cross_join`, which would otherwise copy all the columns of
+/// its inputs for each pair of rows. If possible, returns the join with its
+/// narrowed inputs under the updated `projection`. Otherwise, returns `None`.
+fn try_narrowing_cross_join_inputs(
+    projection: &ProjectionExec,
+    cross_join: &CrossJoinExec,
+) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+    narrow_join_inputs(
+        projection,
+        cross_join.left(),
+        cross_join.right(),
+        None,
+        JoinType::Inner,
+        |left, right, _| Ok(Arc::new(CrossJoinExec::new(left, right))),
+    )
+}
+
+/// Tries to push projections keeping only the columns `projection` or the
+/// filter of `nl_join` use into the inputs of `nl_join`. If possible, returns
+/// the join with its narrowed inputs under the updated `projection`.
+/// Otherwise, returns `None`.
+fn try_narrowing_nested_loop_join_inputs(
+    projection: &ProjectionExec,
+    nl_join: &NestedLoopJoinExec,
+) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+    let join_type = *nl_join.join_type();
+    narrow_join_inputs(
+        projection,
+        nl_join.left(),
+        nl_join.right(),
+        nl_join.filter(),
+        join_type,
+        |left, right, filter| {
+            Ok(Arc::new(NestedLoopJoinExec::try_new(
+                left, right, filter, &join_type,
+            )?))
+        },
+    )
+}
+
+/// Projects the inputs `left` and `right` of a join of type `join_type` with
+/// the filter `filter` on the columns `projection` or `filter` use, creates
+/// the join of the projected inputs with `new_join`, and returns it under
+/// `projection` updated for its schema. Returns `None` if the join uses all
+/// the columns of its inputs.
+fn narrow_join_inputs(
+    projection: &ProjectionExec,
+    left: &Arc<dyn ExecutionPlan>,
+    right: &Arc<dyn ExecutionPlan>,
+    filter: Option<&JoinFilter>,
+    join_type: JoinType,
+    new_join: impl FnOnce(
+        Arc<dyn ExecutionPlan>,
+        Arc<dyn ExecutionPlan>,
+        Option<JoinFilter>,
+    ) -> Result<Arc<dyn ExecutionPlan>>,
+) -> Result<Option<Arc<dyn ExecutionPlan>>> {
+    let left_len = left.schema().fields().len();
+    let right_len = right.schema().fields().len();
+    // The side and the index in its input of each column of the join output
+    let output_columns = match join_type {
+        JoinType::LeftSemi | JoinType::LeftAnti => {
+            (0..left_len).map(|i| (JoinSide::Left, i)).collect_vec()
+        }
+        JoinType::RightSemi | JoinType::RightAnti => {
+            (0..right_len).map(|i| (JoinSide::Right, i)).collect_vec()
+        }
+        _ => (0..left_len)
+            .map(|i| (JoinSide::Left, i))
+            .chain((0..right_len).map(|i| (JoinSide::Right, i)))
+            .collect_vec(),
+    };
+
+    let mut left_required = vec![false; left_len];
+    let mut right_required = vec![false; right_len];
+    let projection_columns = collect_column_indices(projection.expr())
+        .into_iter()
+        .map(|index| output_columns[index]);
+    let filter_columns = filter
+        .into_iter()
+        .flat_map(|filter| filter.column_indices())
+        .map(|column| (column.side, column.index));
+    for (side, index) in projection_columns.chain(filter_columns) {
+        match side {
+            JoinSide::Left => left_required[index] = true,
+            JoinSide::Right => right_required[index] = true,
+        }
+    }
+    if !left_required.contains(&false) && !right_required.contains(&false) {
+        return Ok(None);
+    }
+
+    let (new_left, new_left_indices) = narrow_join_input(left, left_required)?;
+    let (new_right, new_right_indices) = narrow_join_input(right, right_required)?;
+    let new_filter = filter.map(|filter| {
+        JoinFilter::new(
+            filter.expression().clone(),
+            filter
+                .column_indices()
+                .iter()
+                .map(|column| ColumnIndex {
+                    index: match column.side {
+                        JoinSide::Left => new_left_indices[column.index],
+                        JoinSide::Right => new_right_indices[column.index],
+                    }
+                    .unwrap(),
+                    side: column.side,
+                })
+                .collect(),
+            filter.schema().clone(),
+        )
+    });
+    let new_left_len = new_left.schema().fields().len();
+    let new_join = new_join(new_left, new_right, new_filter)?;
+
+    // The columns of the new join output, at the indices of the columns of
+    // the join output, for the columns `projection` uses
+    let new_join_schema = new_join.schema();
+    let new_output_columns = output_columns
+        .iter()
+        .map(|(side, index)| {
+            let new_index = match (side, join_type) {
+                (JoinSide::Left, _) => new_left_indices[*index],
+                (JoinSide::Right, JoinType::RightSemi | JoinType::RightAnti) => {
+                    new_right_indices[*index]
+                }
+                (JoinSide::Right, _) => {
+                    new_right_indices[*index].map(|i| i + new_left_len)
+                }
+            };
+            // The columns which are not used are not referenced
+            let new_index = new_index.unwrap_or(0);
+            let name = new_join_schema.field(new_index).name();
+            (Arc::new(Column::new(name, new_index)) as _, name.clone())
+        })
+        .collect_vec();
+    let new_exprs = projection
+        .expr()
+        .iter()
+        .map(|(expr, alias)| {
+            let new_expr = update_expr(expr, &new_output_columns, true)?;
+            Ok((new_expr.unwrap_or_else(|| expr.clone()), alias.clone()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    let new_projection = Arc::new(ProjectionExec::try_new(new_exprs, new_join.clone())?);
+    if is_projection_removable(&new_projection) {
+        Ok(Some(new_join))
+    } else {
+        Ok(Some(new_projection))
+    }
+}
+
+/// Returns the projection of the input `input` of a join on its `required`
+/// columns, and the index in the projection of each column of `input`
+fn narrow_join_input(
+    input: &Arc<dyn ExecutionPlan>,
+    mut required: Vec<bool>,
+) -> Result<(Arc<dyn ExecutionPlan>, Vec<Option<usize>>)> {
+    // Keep a column for the batches of the input to keep their number of rows
+    if !required.contains(&true) {
+        if let Some(first) = required.first_mut() {
+            *first = true;
+        }
+    }
+    if !required.contains(&false) {
+        return Ok((input.clone(), (0..required.len()).map(Some).collect()));
+    }
+
+    let schema = input.schema();
+    let mut new_indices = vec![None; required.len()];
+    let mut exprs = vec![];
+    for index in (0..required.len()).filter(|index| required[*index]) {
+        new_indices[index] = Some(exprs.len());
+        let name = schema.field(index).name();
+        exprs.push((Arc::new(Column::new(name, index)) as _, name.clone()));
+    }
+    let projection = ProjectionExec::try_new(exprs, input.clone())?;
+    Ok((Arc::new(projection), new_indices))
+}
+
 /// Tries to swap the projection with its input [`SortMergeJoinExec`]. If it can be done,
 /// it returns the new swapped version having the [`SortMergeJoinExec`] as the top plan.
 /// Otherwise, it returns None.
@@ -1702,6 +1892,7 @@ mod tests {
                 table_partition_cols: vec![],
                 output_ordering: vec![vec![]],
                 column_hints: None,
+                listing_pruning: None,
             },
             false,
             0,
@@ -1729,6 +1920,7 @@ mod tests {
                 table_partition_cols: vec![],
                 output_ordering: vec![vec![]],
                 column_hints: None,
+                listing_pruning: None,
             },
             false,
             0,
@@ -2361,6 +2553,125 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_cross_join_after_projection() -> Result<()> {
+        let cross_join: Arc<dyn ExecutionPlan> = Arc::new(CrossJoinExec::new(
+            create_simple_csv_exec(),
+            create_simple_csv_exec(),
+        ));
+        let projection: Arc<dyn ExecutionPlan> = Arc::new(ProjectionExec::try_new(
+            vec![(
+                Arc::new(BinaryExpr::new(
+                    Arc::new(Column::new("c", 2)),
+                    Operator::Plus,
+                    Arc::new(Column::new("b", 6)),
+                )),
+                "x".to_string(),
+            )],
+            cross_join,
+        )?);
+        let initial = get_plan_string(&projection);
+        let expected_initial = [
+            "ProjectionExec: expr=[c@2 + b@6 as x]",
+            "  CrossJoinExec",
+            "    CsvExec: file_groups={1 group: [[x]]}, projection=[a, b, c, d, e], has_header=false",
+            "    CsvExec: file_groups={1 group: [[x]]}, projection=[a, b, c, d, e], has_header=false",
+        ];
+        assert_eq!(initial, expected_initial);
+
+        let after_optimize =
+            ProjectionPushdown::new().optimize(projection, &ConfigOptions::new())?;
+
+        let expected = [
+            "ProjectionExec: expr=[c@0 + b@1 as x]",
+            "  CrossJoinExec",
+            "    CsvExec: file_groups={1 group: [[x]]}, projection=[c], has_header=false",
+            "    CsvExec: file_groups={1 group: [[x]]}, projection=[b], has_header=false",
+        ];
+        assert_eq!(get_plan_string(&after_optimize), expected);
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_nested_loop_join_after_projection() -> Result<()> {
+        // b@1 > d@3, on the intermediate batch [left.b, right.d]
+        let filter = JoinFilter::new(
+            Arc::new(BinaryExpr::new(
+                Arc::new(Column::new("b", 0)),
+                Operator::Gt,
+                Arc::new(Column::new("d", 1)),
+            )),
+            vec![
+                ColumnIndex {
+                    index: 1,
+                    side: JoinSide::Left,
+                },
+                ColumnIndex {
+                    index: 3,
+                    side: JoinSide::Right,
+                },
+            ],
+            Schema::new(vec![
+                Field::new("b", DataType::Int32, true),
+                Field::new("d", DataType::Int32, true),
+            ]),
+        );
+        let nl_join: Arc<dyn ExecutionPlan> = Arc::new(NestedLoopJoinExec::try_new(
+            create_simple_csv_exec(),
+            create_simple_csv_exec(),
+            Some(filter),
+            &JoinType::Inner,
+        )?);
+        let projection: Arc<dyn ExecutionPlan> = Arc::new(ProjectionExec::try_new(
+            vec![
+                (Arc::new(Column::new("e", 9)), "e".to_string()),
+                (Arc::new(Column::new("a", 0)), "a".to_string()),
+            ],
+            nl_join,
+        )?);
+        let initial = get_plan_string(&projection);
+        let expected_initial = [
+            "ProjectionExec: expr=[e@9 as e, a@0 as a]",
+            "  NestedLoopJoinExec: join_type=Inner, filter=b@0 > d@1",
+            "    CsvExec: file_groups={1 group: [[x]]}, projection=[a, b, c, d, e], has_header=false",
+            "    CsvExec: file_groups={1 group: [[x]]}, projection=[a, b, c, d, e], has_header=false",
+        ];
+        assert_eq!(initial, expected_initial);
+
+        let after_optimize =
+            ProjectionPushdown::new().optimize(projection, &ConfigOptions::new())?;
+
+        // the columns of the filter are kept in the inputs
+        let expected = [
+            "ProjectionExec: expr=[e@3 as e, a@0 as a]",
+            "  NestedLoopJoinExec: join_type=Inner, filter=b@0 > d@1",
+            "    CsvExec: file_groups={1 group: [[x]]}, projection=[a, b], has_header=false",
+            "    CsvExec: file_groups={1 group: [[x]]}, projection=[d, e], has_header=false",
+        ];
+        assert_eq!(get_plan_string(&after_optimize), expected);
+        let nl_join = after_optimize.children()[0].clone();
+        let nl_join = nl_join
+            .as_any()
+            .downcast_ref::<NestedLoopJoinExec>()
+            .unwrap();
+        assert_eq!(
+            nl_join.filter().unwrap().column_indices(),
+            &[
+                ColumnIndex {
+                    index: 1,
+                    side: JoinSide::Left,
+                },
+                ColumnIndex {
+                    index: 0,
+                    side: JoinSide::Right,
+                },
+            ]
+        );
+
+        Ok(())
+    }
+
     #[test]
     fn test_collect_column_indices() -> Result<()> {
         let expr = Arc::new(BinaryExpr::new(
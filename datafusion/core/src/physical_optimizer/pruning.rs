@@ -19,11 +19,11 @@
 //! based on statistics (e.g. Parquet Row Groups)
 //!
 //! [`Expr`]: crate::prelude::Expr
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use crate::{
-    common::{Column, DFSchema},
+    common::{utils::quote_identifier, Column, DFSchema, TableReference},
     error::{DataFusionError, Result},
     logical_expr::Operator,
     physical_plan::{ColumnarValue, PhysicalExpr},
@@ -42,7 +42,9 @@ use datafusion_common::{
     ScalarValue,
 };
 use datafusion_physical_expr::utils::{collect_columns, Guarantee, LiteralGuarantee};
-use datafusion_physical_expr::{expressions as phys_expr, PhysicalExprRef};
+use datafusion_physical_expr::{
+    expressions as phys_expr, PhysicalExprRef, ScalarFunctionExpr,
+};
 
 use log::trace;
 
@@ -500,12 +502,27 @@ impl PruningPredicate {
     /// See the struct level documentation on [`PruningPredicate`] for more
     /// details.
     pub fn try_new(expr: Arc<dyn PhysicalExpr>, schema: SchemaRef) -> Result<Self> {
+        // prune spatial filters by the bounding boxes of the geometries
+        let spatial_expr = rewrite_spatial_filters(&expr, &schema)?;
+
+        // treat struct field accesses such as `s['x']` as leaf columns so they
+        // can be pruned like any other column
+        let (leaf_expr, leaf_schema, nested_leaves) =
+            rewrite_nested_leaves(&spatial_expr, &schema)?;
+
         // build predicate expression once
         let mut required_columns = RequiredColumns::new();
+        required_columns.nested_leaves = nested_leaves;
         let predicate_expr =
-            build_predicate_expression(&expr, schema.as_ref(), &mut required_columns);
+            build_predicate_expression(&leaf_expr, &leaf_schema, &mut required_columns);
 
-        let literal_guarantees = LiteralGuarantee::analyze(&expr);
+        let mut literal_guarantees = LiteralGuarantee::analyze(&leaf_expr);
+        for guarantee in &mut literal_guarantees {
+            if let Some(leaf) = required_columns.nested_leaves.get(&guarantee.column.name)
+            {
+                guarantee.column = leaf.clone();
+            }
+        }
 
         Ok(Self {
             schema,
@@ -611,21 +628,24 @@ impl PruningPredicate {
         &self.required_columns
     }
 
-    /// Names of the columns that are known to be / not be in a set
-    /// of literals (constants). These are the columns the that may be passed to
+    /// The columns that are known to be / not be in a set of literals
+    /// (constants). These are the columns the that may be passed to
     /// [`PruningStatistics::contained`] during pruning.
     ///
     /// This is useful to avoid fetching statistics for columns that will not be
     /// used in the predicate. For example, it can be used to avoid reading
     /// uneeded bloom filters (a non trivial operation).
-    pub fn literal_columns(&self) -> Vec<String> {
+    pub fn literal_columns(&self) -> Vec<Column> {
         let mut seen = HashSet::new();
         self.literal_guarantees
             .iter()
-            .map(|e| &e.column.name)
+            .map(|e| e.column.clone())
+            .chain(self.required_columns.iter().filter_map(|(c, t, _f)| {
+                matches!(t, StatisticsType::Contained(_))
+                    .then(|| self.required_columns.statistics_column(c))
+            }))
             // avoid duplicates
-            .filter(|name| seen.insert(*name))
-            .map(|s| s.to_string())
+            .filter(|column| seen.insert(column.clone()))
             .collect()
     }
 }
@@ -723,6 +743,12 @@ pub(crate) struct RequiredColumns {
     /// * The field the statistics value should be placed in for
     ///   pruning predicate evaluation (e.g. `min_value` or `max_value`)
     columns: Vec<(phys_expr::Column, StatisticsType, Field)>,
+    /// The literal sets referenced by [`StatisticsType::Contained`] entries
+    /// in `columns`
+    literal_sets: Vec<HashSet<ScalarValue>>,
+    /// The [`nested_leaf_column`] of the struct leaves referenced by `columns`,
+    /// by the name of their column
+    nested_leaves: HashMap<String, Column>,
 }
 
 impl RequiredColumns {
@@ -746,6 +772,14 @@ impl RequiredColumns {
         self.columns.iter()
     }
 
+    /// Returns the column whose statistics are those of `column`
+    fn statistics_column(&self, column: &phys_expr::Column) -> Column {
+        self.nested_leaves
+            .get(column.name())
+            .cloned()
+            .unwrap_or_else(|| Column::from_name(column.name()))
+    }
+
     fn find_stat_column(
         &self,
         column: &phys_expr::Column,
@@ -844,11 +878,53 @@ impl RequiredColumns {
             "row_count",
         )
     }
+
+    /// rewrite col --> col_contained_{n}, a boolean column holding the
+    /// result of [`PruningStatistics::contained`] for `col` and `literals`
+    fn contained_column_expr(
+        &mut self,
+        column: &phys_expr::Column,
+        literals: HashSet<ScalarValue>,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        // reuse the statistics column if the same set was already requested
+        let existing = self.columns.iter().find_map(|(c, t, _f)| match t {
+            StatisticsType::Contained(idx)
+                if c == column && self.literal_sets[*idx] == literals =>
+            {
+                Some(*idx)
+            }
+            _ => None,
+        });
+        let set_idx = match existing {
+            Some(idx) => idx,
+            None => {
+                self.literal_sets.push(literals);
+                self.literal_sets.len() - 1
+            }
+        };
+
+        let field = Field::new(column.name(), DataType::Boolean, true);
+        self.stat_column_expr(
+            column,
+            &(Arc::new(column.clone()) as _),
+            &field,
+            StatisticsType::Contained(set_idx),
+            &format!("contained_{set_idx}"),
+        )
+    }
+
+    /// Returns the literal set of a [`StatisticsType::Contained`] entry
+    fn literal_set(&self, idx: usize) -> &HashSet<ScalarValue> {
+        &self.literal_sets[idx]
+    }
 }
 
 impl From<Vec<(phys_expr::Column, StatisticsType, Field)>> for RequiredColumns {
     fn from(columns: Vec<(phys_expr::Column, StatisticsType, Field)>) -> Self {
-        Self { columns }
+        Self {
+            columns,
+            ..Default::default()
+        }
     }
 }
 
@@ -885,7 +961,7 @@ fn build_statistics_record_batch<S: PruningStatistics>(
     let mut arrays = Vec::<ArrayRef>::new();
     // For each needed statistics column:
     for (column, statistics_type, stat_field) in required_columns.iter() {
-        let column = Column::from_name(column.name());
+        let column = required_columns.statistics_column(column);
         let data_type = stat_field.data_type();
 
         let num_containers = statistics.num_containers();
@@ -895,6 +971,9 @@ fn build_statistics_record_batch<S: PruningStatistics>(
             StatisticsType::Max => statistics.max_values(&column),
             StatisticsType::NullCount => statistics.null_counts(&column),
             StatisticsType::RowCount => statistics.row_counts(&column),
+            StatisticsType::Contained(idx) => statistics
+                .contained(&column, required_columns.literal_set(*idx))
+                .map(|array| Arc::new(array) as ArrayRef),
         };
         let array = array.unwrap_or_else(|| new_null_array(data_type, num_containers));
 
@@ -1186,6 +1265,296 @@ fn rewrite_column_expr(
     .data()
 }
 
+/// Returns the [`Column`] whose statistics are those of the leaf `path` of a
+/// struct column, e.g. `["s", "x"]` for `s['x']`.
+///
+/// The relation of the column is the struct column and its name the path of
+/// the leaf in the struct, whose fields are quoted as needed and separated by
+/// `.`. Top level columns have no relation, so leaves never collide with them,
+/// even if their names contain a `.`.
+pub fn nested_leaf_column(path: &[String]) -> Column {
+    let name = path[1..]
+        .iter()
+        .map(|field| quote_identifier(field))
+        .collect::<Vec<_>>()
+        .join(".");
+    Column::new(Some(TableReference::bare(path[0].clone())), name)
+}
+
+/// Returns the path of the leaf of a struct column whose statistics are
+/// those of `column`, or `None` for a top level column. This is the inverse
+/// of [`nested_leaf_column`].
+pub fn nested_leaf_path(column: &Column) -> Option<Vec<String>> {
+    let mut path = vec![column.relation.as_ref()?.table().to_string()];
+    let mut chars = column.name.chars().peekable();
+    loop {
+        let mut field = String::new();
+        if chars.next_if_eq(&'"').is_some() {
+            // a quoted field, in which quotes are doubled
+            loop {
+                match chars.next()? {
+                    '"' if chars.next_if_eq(&'"').is_none() => break,
+                    c => field.push(c),
+                }
+            }
+        } else {
+            while let Some(c) = chars.next_if(|c| *c != '.') {
+                field.push(c);
+            }
+        }
+        path.push(field);
+        match chars.next() {
+            Some('.') => continue,
+            Some(_) => return None,
+            None => return Some(path),
+        }
+    }
+}
+
+/// Rewrites struct field accesses `get_field(s, 'x')` (`s['x']` in SQL) in
+/// `expr` into references to leaf columns, so that predicates on nested
+/// fields can use the statistics of those leaves.
+///
+/// Returns the rewritten expression along with `schema` extended by a field
+/// for each referenced leaf, and the [`nested_leaf_column`] of each leaf by
+/// the name of its field. The fields are named like `s.x`, made unique among
+/// the fields of `schema` if needed.
+fn rewrite_nested_leaves(
+    expr: &Arc<dyn PhysicalExpr>,
+    schema: &SchemaRef,
+) -> Result<(Arc<dyn PhysicalExpr>, SchemaRef, HashMap<String, Column>)> {
+    let num_fields = schema.fields().len();
+    let mut fields = schema.fields().iter().cloned().collect::<Vec<_>>();
+    // the path of each leaf added to `fields`
+    let mut paths: Vec<Vec<String>> = vec![];
+    let expr = expr
+        .clone()
+        .transform_up(|expr| {
+            let Some(func) = expr.as_any().downcast_ref::<ScalarFunctionExpr>() else {
+                return Ok(Transformed::no(expr));
+            };
+            let [base, name] = func.args() else {
+                return Ok(Transformed::no(expr));
+            };
+            let (Some(base), Some(name)) = (
+                base.as_any().downcast_ref::<phys_expr::Column>(),
+                name.as_any().downcast_ref::<phys_expr::Literal>(),
+            ) else {
+                return Ok(Transformed::no(expr));
+            };
+            let ScalarValue::Utf8(Some(name)) = name.value() else {
+                return Ok(Transformed::no(expr));
+            };
+            let Some(base_field) = fields.get(base.index()) else {
+                return Ok(Transformed::no(expr));
+            };
+            let child = match base_field.data_type() {
+                DataType::Struct(children) if func.name() == "get_field" => {
+                    match children.find(name) {
+                        Some((_, child)) => child.as_ref().clone().with_nullable(
+                            base_field.is_nullable() || child.is_nullable(),
+                        ),
+                        None => return Ok(Transformed::no(expr)),
+                    }
+                }
+                _ => return Ok(Transformed::no(expr)),
+            };
+
+            let mut path = match base.index().checked_sub(num_fields) {
+                Some(offset) => paths[offset].clone(),
+                None => vec![base.name().to_string()],
+            };
+            path.push(name.clone());
+            // only reuse leaves added by this function, never input columns
+            let index = match paths.iter().position(|p| *p == path) {
+                Some(offset) => num_fields + offset,
+                None => {
+                    let mut leaf_name = format!("{}.{}", base.name(), name);
+                    while fields.iter().any(|f| *f.name() == leaf_name) {
+                        leaf_name.push('_');
+                    }
+                    fields.push(Arc::new(child.with_name(leaf_name)));
+                    paths.push(path);
+                    fields.len() - 1
+                }
+            };
+            Ok(Transformed::yes(Arc::new(phys_expr::Column::new(
+                fields[index].name(),
+                index,
+            )) as _))
+        })
+        .data()?;
+
+    let leaves = fields[num_fields..]
+        .iter()
+        .zip(&paths)
+        .map(|(field, path)| (field.name().clone(), nested_leaf_column(path)))
+        .collect();
+    let schema = if fields.len() == num_fields {
+        schema.clone()
+    } else {
+        Arc::new(Schema::new_with_metadata(fields, schema.metadata().clone()))
+    };
+    Ok((expr, schema, leaves))
+}
+
+/// Rewrites the spatial filters of `expr` between a geometry column `geom`
+/// and a constant geometry into filters on the bounding box columns of `geom`
+/// (see [`bounding_box_columns`]), if `schema` has them as `Float64` columns:
+///
+/// * `st_contains(<constant>, geom)` requires the bounding box of `geom` to be
+///   inside the one of the constant
+/// * `st_contains(geom, <constant>)` requires the bounding box of `geom` to
+///   contain the one of the constant
+/// * `st_distance(geom, <constant>) <= d` (or `< d`, with the arguments in
+///   any order) requires the bounding box of `geom` to intersect the one of
+///   the constant grown by `d`
+///
+/// The rewritten filters are implied by the spatial ones, so the containers
+/// whose bounding box statistics do not match them can be pruned. This only
+/// holds for the conjuncts of `expr`, so the spatial filters below any other
+/// expression, such as a `NOT` or an `OR`, are left as they are.
+///
+/// [`bounding_box_columns`]: datafusion_functions::geo::bounding_box_columns
+#[cfg(feature = "geo_expressions")]
+fn rewrite_spatial_filters(
+    expr: &Arc<dyn PhysicalExpr>,
+    schema: &Schema,
+) -> Result<Arc<dyn PhysicalExpr>> {
+    if let Some(binary) = expr.as_any().downcast_ref::<phys_expr::BinaryExpr>() {
+        if *binary.op() == Operator::And {
+            return Ok(Arc::new(phys_expr::BinaryExpr::new(
+                rewrite_spatial_filters(binary.left(), schema)?,
+                Operator::And,
+                rewrite_spatial_filters(binary.right(), schema)?,
+            )));
+        }
+    }
+    Ok(spatial_bounding_box_filter(expr, schema).unwrap_or_else(|| expr.clone()))
+}
+
+#[cfg(not(feature = "geo_expressions"))]
+fn rewrite_spatial_filters(
+    expr: &Arc<dyn PhysicalExpr>,
+    _schema: &Schema,
+) -> Result<Arc<dyn PhysicalExpr>> {
+    Ok(expr.clone())
+}
+
+/// Returns the filter on the bounding box columns implied by the spatial
+/// filter `expr`, see [`rewrite_spatial_filters`]
+#[cfg(feature = "geo_expressions")]
+fn spatial_bounding_box_filter(
+    expr: &Arc<dyn PhysicalExpr>,
+    schema: &Schema,
+) -> Option<Arc<dyn PhysicalExpr>> {
+    use datafusion_functions::geo::bounding_box_columns;
+    use datafusion_functions::geo::wkb::{BoundingBox, Geometry};
+
+    // the arguments of `expr` if it calls the geo function `name`
+    let geo_args = |expr: &Arc<dyn PhysicalExpr>, name: &str| {
+        let func = expr.as_any().downcast_ref::<ScalarFunctionExpr>()?;
+        match func.args() {
+            [geometry, other] if func.name() == name => {
+                Some((geometry.clone(), other.clone()))
+            }
+            _ => None,
+        }
+    };
+    // the bounding box columns `[xmin, ymin, xmax, ymax]` of the geometry
+    // column `expr`
+    let bounding_box = |expr: &Arc<dyn PhysicalExpr>| {
+        let column = expr.as_any().downcast_ref::<phys_expr::Column>()?;
+        let columns = bounding_box_columns(column.name())
+            .iter()
+            .map(|name| {
+                let index = schema.index_of(name).ok()?;
+                (schema.field(index).data_type() == &DataType::Float64).then(|| {
+                    Arc::new(phys_expr::Column::new(name, index)) as Arc<dyn PhysicalExpr>
+                })
+            })
+            .collect::<Option<Vec<_>>>()?;
+        <[Arc<dyn PhysicalExpr>; 4]>::try_from(columns).ok()
+    };
+    // the bounding box of the constant geometry `expr`
+    let constant_bounding_box = |expr: &Arc<dyn PhysicalExpr>| {
+        let literal = expr.as_any().downcast_ref::<phys_expr::Literal>()?;
+        match literal.value() {
+            ScalarValue::Binary(Some(wkb)) | ScalarValue::LargeBinary(Some(wkb)) => {
+                Geometry::try_from_wkb(wkb).ok()?.bounding_box()
+            }
+            _ => None,
+        }
+    };
+    let compare = |column: &Arc<dyn PhysicalExpr>,
+                   op: Operator,
+                   value: f64|
+     -> Arc<dyn PhysicalExpr> {
+        let value = Arc::new(phys_expr::Literal::new(ScalarValue::from(value)));
+        Arc::new(phys_expr::BinaryExpr::new(column.clone(), op, value))
+    };
+    let conjunction = |filters: [Arc<dyn PhysicalExpr>; 4]| {
+        filters
+            .into_iter()
+            .reduce(|a, b| Arc::new(phys_expr::BinaryExpr::new(a, Operator::And, b)) as _)
+    };
+    // the bounding box columns intersecting `other` grown by `distance`
+    let within_distance = |[xmin, ymin, xmax, ymax]: [Arc<dyn PhysicalExpr>; 4],
+                           other: BoundingBox,
+                           distance: f64| {
+        let other = other.expand(distance);
+        conjunction([
+            compare(&xmin, Operator::LtEq, other.xmax),
+            compare(&ymin, Operator::LtEq, other.ymax),
+            compare(&xmax, Operator::GtEq, other.xmin),
+            compare(&ymax, Operator::GtEq, other.ymin),
+        ])
+    };
+
+    if let Some((geometry, other)) = geo_args(expr, "st_contains") {
+        if let (Some(outer), Some([xmin, ymin, xmax, ymax])) =
+            (constant_bounding_box(&geometry), bounding_box(&other))
+        {
+            return conjunction([
+                compare(&xmin, Operator::GtEq, outer.xmin),
+                compare(&ymin, Operator::GtEq, outer.ymin),
+                compare(&xmax, Operator::LtEq, outer.xmax),
+                compare(&ymax, Operator::LtEq, outer.ymax),
+            ]);
+        }
+        if let (Some([xmin, ymin, xmax, ymax]), Some(inner)) =
+            (bounding_box(&geometry), constant_bounding_box(&other))
+        {
+            return conjunction([
+                compare(&xmin, Operator::LtEq, inner.xmin),
+                compare(&ymin, Operator::LtEq, inner.ymin),
+                compare(&xmax, Operator::GtEq, inner.xmax),
+                compare(&ymax, Operator::GtEq, inner.ymax),
+            ]);
+        }
+        return None;
+    }
+
+    let binary = expr.as_any().downcast_ref::<phys_expr::BinaryExpr>()?;
+    let (distance, limit) = match binary.op() {
+        Operator::Lt | Operator::LtEq => (binary.left(), binary.right()),
+        Operator::Gt | Operator::GtEq => (binary.right(), binary.left()),
+        _ => return None,
+    };
+    let (geometry, other) = geo_args(distance, "st_distance")?;
+    let limit = match limit.as_any().downcast_ref::<phys_expr::Literal>()?.value() {
+        ScalarValue::Float64(Some(limit)) => *limit,
+        _ => return None,
+    };
+    match (bounding_box(&geometry), constant_bounding_box(&other)) {
+        (Some(columns), Some(other)) => within_distance(columns, other, limit),
+        _ => match (constant_bounding_box(&geometry), bounding_box(&other)) {
+            (Some(other), Some(columns)) => within_distance(columns, other, limit),
+            _ => None,
+        },
+    }
+}
+
 fn reverse_operator(op: Operator) -> Result<Operator> {
     op.swap().ok_or_else(|| {
         DataFusionError::Internal(format!(
@@ -1304,6 +1673,21 @@ fn build_predicate_expression(
     expr: &Arc<dyn PhysicalExpr>,
     schema: &Schema,
     required_columns: &mut RequiredColumns,
+) -> Arc<dyn PhysicalExpr> {
+    build_predicate_expression_impl(expr, schema, required_columns, false)
+}
+
+/// Implementation of [`build_predicate_expression`].
+///
+/// `in_conjunction` is true when `expr` is one of the conjuncts of an `AND`
+/// whose sides reference different columns. Column membership statistics
+/// are then evaluated as part of the pruning predicate itself, so that the
+/// whole conjunction is evaluated over the statistics of each container.
+fn build_predicate_expression_impl(
+    expr: &Arc<dyn PhysicalExpr>,
+    schema: &Schema,
+    required_columns: &mut RequiredColumns,
+    in_conjunction: bool,
 ) -> Arc<dyn PhysicalExpr> {
     // Returned for unsupported expressions. Such expressions are
     // converted to TRUE.
@@ -1364,7 +1748,12 @@ fn build_predicate_expression(
                 })
                 .reduce(|a, b| Arc::new(phys_expr::BinaryExpr::new(a, re_op, b)) as _)
                 .unwrap();
-            return build_predicate_expression(&change_expr, schema, required_columns);
+            return build_predicate_expression_impl(
+                &change_expr,
+                schema,
+                required_columns,
+                in_conjunction,
+            );
         } else {
             return unhandled;
         }
@@ -1383,8 +1772,21 @@ fn build_predicate_expression(
     };
 
     if op == Operator::And || op == Operator::Or {
-        let left_expr = build_predicate_expression(&left, schema, required_columns);
-        let right_expr = build_predicate_expression(&right, schema, required_columns);
+        // the sides of an `OR` are not conjuncts, even inside a conjunction
+        let in_conjunction = op == Operator::And
+            && (in_conjunction || collect_columns(&left) != collect_columns(&right));
+        let left_expr = build_predicate_expression_impl(
+            &left,
+            schema,
+            required_columns,
+            in_conjunction,
+        );
+        let right_expr = build_predicate_expression_impl(
+            &right,
+            schema,
+            required_columns,
+            in_conjunction,
+        );
         // simplify boolean expression if applicable
         let expr = match (&left_expr, op, &right_expr) {
             (left, Operator::And, _) if is_always_true(left) => right_expr,
@@ -1410,7 +1812,60 @@ fn build_predicate_expression(
         }
     };
 
-    build_statistics_expr(&mut expr_builder).unwrap_or(unhandled)
+    let statistics_expr = build_statistics_expr(&mut expr_builder).unwrap_or(unhandled);
+    if !in_conjunction {
+        return statistics_expr;
+    }
+    match build_contained_expr(&left, op, &right, required_columns) {
+        Some(contained_expr) if is_always_true(&statistics_expr) => contained_expr,
+        Some(contained_expr) => Arc::new(phys_expr::BinaryExpr::new(
+            statistics_expr,
+            Operator::And,
+            contained_expr,
+        )),
+        None => statistics_expr,
+    }
+}
+
+/// Builds a predicate over [`PruningStatistics::contained`] for `col = literal`
+/// and `col != literal`, returning `None` for any other expression.
+///
+/// For example, `s = 'foo'` becomes `s_contained_0` and `s != 'foo'` becomes
+/// `NOT s_contained_0`, where `s_contained_0` is whether the values of `s`
+/// are all / none of `{'foo'}`
+fn build_contained_expr(
+    left: &Arc<dyn PhysicalExpr>,
+    op: Operator,
+    right: &Arc<dyn PhysicalExpr>,
+    required_columns: &mut RequiredColumns,
+) -> Option<Arc<dyn PhysicalExpr>> {
+    if op != Operator::Eq && op != Operator::NotEq {
+        return None;
+    }
+    let (column, literal) = match (
+        left.as_any().downcast_ref::<phys_expr::Column>(),
+        right.as_any().downcast_ref::<phys_expr::Literal>(),
+        right.as_any().downcast_ref::<phys_expr::Column>(),
+        left.as_any().downcast_ref::<phys_expr::Literal>(),
+    ) {
+        (Some(column), Some(literal), _, _) | (_, _, Some(column), Some(literal)) => {
+            (column, literal)
+        }
+        _ => return None,
+    };
+    if literal.value().is_null() {
+        return None;
+    }
+
+    let literals = HashSet::from([literal.value().clone()]);
+    let contained_expr = required_columns
+        .contained_column_expr(column, literals)
+        .ok()?;
+    Some(if op == Operator::Eq {
+        contained_expr
+    } else {
+        Arc::new(phys_expr::NotExpr::new(contained_expr))
+    })
 }
 
 fn build_statistics_expr(
@@ -1545,16 +2000,20 @@ pub(crate) enum StatisticsType {
     Max,
     NullCount,
     RowCount,
+    /// [`PruningStatistics::contained`] for the literal set with this index
+    /// in [`RequiredColumns`]
+    Contained(usize),
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::assert_batches_eq;
+    use crate::functions::core::expr_fn::get_field;
     use crate::logical_expr::{col, lit};
     use arrow::array::Decimal128Array;
     use arrow::{
-        array::{BinaryArray, Int32Array, Int64Array, StringArray},
+        array::{BinaryArray, Float64Array, Int32Array, Int64Array, StringArray},
         datatypes::TimeUnit,
     };
     use arrow_array::UInt64Array;
@@ -1629,6 +2088,15 @@ mod tests {
                 .with_max(Arc::new(max.into_iter().collect::<Int32Array>()))
         }
 
+        fn new_f64(
+            min: impl IntoIterator<Item = Option<f64>>,
+            max: impl IntoIterator<Item = Option<f64>>,
+        ) -> Self {
+            Self::new()
+                .with_min(Arc::new(min.into_iter().collect::<Float64Array>()))
+                .with_max(Arc::new(max.into_iter().collect::<Float64Array>()))
+        }
+
         fn new_utf8<'a>(
             min: impl IntoIterator<Item = Option<&'a str>>,
             max: impl IntoIterator<Item = Option<&'a str>>,
@@ -1782,12 +2250,11 @@ mod tests {
             Self::default()
         }
 
-        fn with(
-            mut self,
-            name: impl Into<String>,
-            container_stats: ContainerStats,
-        ) -> Self {
-            let col = Column::from_name(name.into());
+        fn with(self, name: impl Into<String>, container_stats: ContainerStats) -> Self {
+            self.with_column(Column::from_name(name.into()), container_stats)
+        }
+
+        fn with_column(mut self, col: Column, container_stats: ContainerStats) -> Self {
             self.stats.insert(col, container_stats);
             self
         }
@@ -3682,6 +4149,22 @@ mod tests {
             &[true, true, true, true, true, true, true, true, true],
         );
 
+        // (s1 = 'foo' AND s2 = 'bar') OR (s1 != 'foo' AND s2 != 'bar')
+        prune_with_expr(
+            col("s1")
+                .eq(lit("foo"))
+                .and(col("s2").eq(lit("bar")))
+                .or(col("s1")
+                    .not_eq(lit("foo"))
+                    .and(col("s2").not_eq(lit("bar")))),
+            &schema,
+            &statistics,
+            // can rule out containers where we know both conjunctions are false:
+            // 1. s1 doesn't have the value 'foo' and s2 has only the value 'bar'
+            // 2. s1 has only the value 'foo' and s2 doesn't have the value 'bar'
+            &[true, false, true, false, true, true, true, true, true],
+        );
+
         // s1 = 'foo' AND s2 != 'bar'
         prune_with_expr(
             col("s1").eq(lit("foo")).and(col("s2").not_eq(lit("bar"))),
@@ -3842,6 +4325,228 @@ mod tests {
         );
     }
 
+    #[test]
+    fn prune_with_contained_in_conjunction_expr() {
+        let schema = Schema::new(vec![
+            Field::new("s1", DataType::Utf8, true),
+            Field::new("s2", DataType::Utf8, true),
+        ]);
+
+        // s1 = 'foo' AND s2 != 'bar'
+        let expr = col("s1").eq(lit("foo")).and(col("s2").not_eq(lit("bar")));
+        let expected_expr = "\
+            CASE \
+                WHEN s1_null_count@2 = s1_row_count@3 THEN false \
+                ELSE s1_min@0 <= foo AND foo <= s1_max@1 \
+            END AND s1_contained_0@4 \
+        AND CASE \
+                WHEN s2_null_count@7 = s2_row_count@8 THEN false \
+                ELSE s2_min@5 != bar OR bar != s2_max@6 \
+            END AND NOT s2_contained_1@9";
+        let mut required_columns = RequiredColumns::new();
+        let predicate_expr =
+            test_build_predicate_expression(&expr, &schema, &mut required_columns);
+        assert_eq!(predicate_expr.to_string(), expected_expr);
+        assert_eq!(
+            required_columns.literal_set(1),
+            &HashSet::from([ScalarValue::from("bar")])
+        );
+
+        let p = PruningPredicate::try_new(
+            logical2physical(&expr, &schema),
+            Arc::new(schema.clone()),
+        )
+        .unwrap();
+        assert_eq!(
+            p.literal_columns(),
+            vec![Column::from_name("s1"), Column::from_name("s2")]
+        );
+
+        // the sides of an OR are not conjuncts, so their membership is not
+        // needed
+        let expr = col("s1").eq(lit("foo")).or(col("s2").not_eq(lit("bar")));
+        let mut required_columns = RequiredColumns::new();
+        test_build_predicate_expression(&expr, &schema, &mut required_columns);
+        assert!(required_columns
+            .iter()
+            .all(|(_c, t, _f)| !matches!(t, StatisticsType::Contained(_))));
+    }
+
+    #[test]
+    fn prune_nested_field() {
+        // a top level column named like the leaf `s.x`
+        let schema = Arc::new(Schema::new(vec![
+            Field::new(
+                "s",
+                DataType::Struct(vec![Field::new("x", DataType::Int32, true)].into()),
+                true,
+            ),
+            Field::new("s.x", DataType::Int32, true),
+        ]));
+
+        // statistics for the leaf `s.x`, and for the top level column
+        let statistics = TestStatistics::new()
+            .with_column(
+                nested_leaf_column(&["s".into(), "x".into()]),
+                ContainerStats::new_i32(
+                    vec![Some(0), Some(4), Some(10), None], // min
+                    vec![Some(3), Some(6), Some(20), None], // max
+                ),
+            )
+            .with(
+                "s.x",
+                ContainerStats::new_i32(
+                    vec![Some(5), Some(5), Some(5), Some(5)], // min
+                    vec![Some(5), Some(5), Some(5), Some(5)], // max
+                ),
+            );
+
+        // s['x'] = 5
+        prune_with_expr(
+            get_field(col("s"), lit("x")).eq(lit(5)),
+            &schema,
+            &statistics,
+            &[false, true, false, true],
+        );
+
+        // s['x'] < 4 OR s['x'] > 15
+        prune_with_expr(
+            get_field(col("s"), lit("x"))
+                .lt(lit(4))
+                .or(get_field(col("s"), lit("x")).gt(lit(15))),
+            &schema,
+            &statistics,
+            &[true, false, true, true],
+        );
+
+        // "s.x" = 4
+        prune_with_expr(
+            col(Column::from_name("s.x")).eq(lit(4)),
+            &schema,
+            &statistics,
+            &[false, false, false, false],
+        );
+    }
+
+    #[test]
+    fn nested_leaf_paths() {
+        let paths = [
+            vec!["s", "x"],
+            vec!["s", "a.b", "c"],
+            vec!["S.t", "\"quoted\"", "X"],
+            vec!["s", ""],
+        ];
+        for path in paths {
+            let path = path.into_iter().map(String::from).collect::<Vec<_>>();
+            let column = nested_leaf_column(&path);
+            assert_eq!(nested_leaf_path(&column), Some(path));
+        }
+        assert_eq!(nested_leaf_path(&Column::from_name("s.x")), None);
+    }
+
+    #[cfg(feature = "geo_expressions")]
+    #[test]
+    fn prune_spatial_filters() {
+        use crate::functions::geo::expr_fn::{st_contains, st_distance};
+        use crate::functions::geo::wkb::{Coord, Geometry};
+
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("geom", DataType::Binary, true),
+            Field::new("geom_xmin", DataType::Float64, true),
+            Field::new("geom_ymin", DataType::Float64, true),
+            Field::new("geom_xmax", DataType::Float64, true),
+            Field::new("geom_ymax", DataType::Float64, true),
+        ]));
+
+        // the geometries of the first container are in [0, 2] x [0, 2], the
+        // ones of the second container in [5, 8] x [5, 8]
+        let statistics = TestStatistics::new()
+            .with(
+                "geom_xmin",
+                ContainerStats::new_f64(
+                    vec![Some(0.0), Some(5.0), None],
+                    vec![Some(1.0), Some(6.0), None],
+                ),
+            )
+            .with(
+                "geom_ymin",
+                ContainerStats::new_f64(
+                    vec![Some(0.0), Some(5.0), None],
+                    vec![Some(1.0), Some(6.0), None],
+                ),
+            )
+            .with(
+                "geom_xmax",
+                ContainerStats::new_f64(
+                    vec![Some(1.0), Some(7.0), None],
+                    vec![Some(2.0), Some(8.0), None],
+                ),
+            )
+            .with(
+                "geom_ymax",
+                ContainerStats::new_f64(
+                    vec![Some(1.0), Some(7.0), None],
+                    vec![Some(2.0), Some(8.0), None],
+                ),
+            );
+        let point = |x, y| lit(Geometry::point_to_wkb(Coord::new(x, y)));
+
+        // st_contains(geom, st_point(1, 1))
+        prune_with_expr(
+            st_contains(col("geom"), point(1.0, 1.0)),
+            &schema,
+            &statistics,
+            &[true, false, true],
+        );
+
+        // st_contains(<[0, 3] x [0, 3]>, geom)
+        let mut square = vec![1, 3, 0, 0, 0, 1, 0, 0, 0, 5, 0, 0, 0];
+        for (x, y) in [(0., 0.), (3., 0.), (3., 3.), (0., 3.), (0., 0.)] {
+            square.extend_from_slice(&f64::to_le_bytes(x));
+            square.extend_from_slice(&f64::to_le_bytes(y));
+        }
+        prune_with_expr(
+            st_contains(lit(square), col("geom")),
+            &schema,
+            &statistics,
+            &[true, false, true],
+        );
+
+        // st_distance(geom, st_point(10, 10)) <= 2.5
+        prune_with_expr(
+            st_distance(col("geom"), point(10.0, 10.0)).lt_eq(lit(2.5)),
+            &schema,
+            &statistics,
+            &[false, true, true],
+        );
+
+        // st_contains(geom, st_point(1, 1))
+        //   AND st_distance(geom, st_point(10, 10)) <= 2.5
+        prune_with_expr(
+            st_contains(col("geom"), point(1.0, 1.0))
+                .and(st_distance(col("geom"), point(10.0, 10.0)).lt_eq(lit(2.5))),
+            &schema,
+            &statistics,
+            &[false, false, true],
+        );
+
+        // NOT st_contains(geom, st_point(1, 1)) can not be pruned
+        prune_with_expr(
+            st_contains(col("geom"), point(1.0, 1.0)).not(),
+            &schema,
+            &statistics,
+            &[true, true, true],
+        );
+
+        // st_contains(geom, st_point(1, 1)) = false can not be pruned
+        prune_with_expr(
+            st_contains(col("geom"), point(1.0, 1.0)).eq(lit(false)),
+            &schema,
+            &statistics,
+            &[true, true, true],
+        );
+    }
+
     /// prunes the specified expr with the specified schema and statistics, and
     /// ensures it returns expected.
     ///
@@ -0,0 +1,227 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! RepartitionWindows optimizer rule that lets window operators run in
+//! parallel over their `PARTITION BY` keys
+
+use std::sync::Arc;
+
+use crate::config::ConfigOptions;
+use crate::error::Result;
+use crate::physical_optimizer::PhysicalOptimizerRule;
+use crate::physical_plan::windows::{BoundedWindowAggExec, WindowAggExec};
+use crate::physical_plan::{ExecutionPlan, PhysicalExpr};
+
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_physical_expr::physical_exprs_contains;
+use datafusion_physical_expr::window::WindowExpr;
+use datafusion_sql::utils::common_partition_keys;
+
+/// Optimizer rule that sets the partition keys of [`BoundedWindowAggExec`]
+/// and [`WindowAggExec`] operators that have none, but whose window
+/// expressions all share `PARTITION BY` keys.
+///
+/// A window operator without partition keys requires a single input
+/// partition, so a whole input is processed serially. With partition keys,
+/// the operator requires its input to be hash partitioned on them instead:
+/// the [`EnforceDistribution`] rule then repartitions the input by the
+/// window partition keys, and the [`EnforceSorting`] rule restores the
+/// ordering required above the window with an order-preserving merge.
+///
+/// The physical planner already sets the partition keys for the window
+/// operators it creates; this rule covers window operators built without
+/// them, e.g. by other planners or with a different configuration. It is
+/// enabled by the `datafusion.optimizer.repartition_windows` configuration
+/// option, and only has an effect when `target_partitions` is greater than 1.
+///
+/// [`EnforceDistribution`]: crate::physical_optimizer::enforce_distribution::EnforceDistribution
+/// [`EnforceSorting`]: crate::physical_optimizer::enforce_sorting::EnforceSorting
+#[derive(Default)]
+pub struct RepartitionWindows {}
+
+impl RepartitionWindows {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl PhysicalOptimizerRule for RepartitionWindows {
+    fn optimize(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        config: &ConfigOptions,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if !config.optimizer.repartition_windows
+            || config.execution.target_partitions <= 1
+        {
+            return Ok(plan);
+        }
+
+        plan.transform_up(|plan| {
+            if let Some(exec) = plan.as_any().downcast_ref::<BoundedWindowAggExec>() {
+                if exec.partition_keys.is_empty() {
+                    if let Some(keys) = window_partition_keys(exec.window_expr()) {
+                        return Ok(Transformed::yes(Arc::new(
+                            BoundedWindowAggExec::try_new(
+                                exec.window_expr().to_vec(),
+                                exec.input().clone(),
+                                keys,
+                                exec.input_order_mode.clone(),
+                            )?,
+                        )));
+                    }
+                }
+            } else if let Some(exec) = plan.as_any().downcast_ref::<WindowAggExec>() {
+                if exec.partition_keys.is_empty() {
+                    if let Some(keys) = window_partition_keys(exec.window_expr()) {
+                        return Ok(Transformed::yes(Arc::new(WindowAggExec::try_new(
+                            exec.window_expr().to_vec(),
+                            exec.input().clone(),
+                            keys,
+                        )?)));
+                    }
+                }
+            }
+            Ok(Transformed::no(plan))
+        })
+        .data()
+    }
+
+    fn name(&self) -> &str {
+        "RepartitionWindows"
+    }
+
+    fn schema_check(&self) -> bool {
+        true
+    }
+}
+
+/// Returns the common `PARTITION BY` of `window_exprs`, as found by the
+/// physical planner, if it is not empty and contained in the `PARTITION BY`
+/// of every window expression. Rows with equal values for these keys belong
+/// to the same partition of every window expression.
+fn window_partition_keys(
+    window_exprs: &[Arc<dyn WindowExpr>],
+) -> Option<Vec<Arc<dyn PhysicalExpr>>> {
+    let keys = common_partition_keys(window_exprs.iter().map(|expr| expr.partition_by()))
+        .ok()?;
+    // the window expressions of operators built by other planners may not
+    // share a sort key
+    let is_common = window_exprs.iter().all(|expr| {
+        keys.iter()
+            .all(|key| physical_exprs_contains(expr.partition_by(), key))
+    });
+    (!keys.is_empty() && is_common).then(|| keys.to_vec())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::physical_optimizer::test_utils::{
+        bounded_window_exec, memory_exec, sort_expr,
+    };
+    use crate::physical_plan::displayable;
+    use crate::physical_plan::windows::create_window_expr;
+
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+    use datafusion_expr::{AggregateFunction, WindowFrame, WindowFunctionDefinition};
+    use datafusion_physical_expr::expressions::col;
+    use datafusion_physical_plan::InputOrderMode;
+
+    fn schema() -> SchemaRef {
+        Arc::new(Schema::new(vec![
+            Field::new("device", DataType::Int64, true),
+            Field::new("ts", DataType::Int64, true),
+        ]))
+    }
+
+    /// `COUNT(ts) OVER (PARTITION BY device ORDER BY ts)`, planned without
+    /// partition keys
+    fn partitioned_window_exec(input: Arc<dyn ExecutionPlan>) -> Arc<dyn ExecutionPlan> {
+        let schema = input.schema();
+        let window_expr = create_window_expr(
+            &WindowFunctionDefinition::AggregateFunction(AggregateFunction::Count),
+            "count".to_owned(),
+            &[col("ts", &schema).unwrap()],
+            &[col("device", &schema).unwrap()],
+            &[sort_expr("ts", &schema)],
+            Arc::new(WindowFrame::new(Some(false))),
+            schema.as_ref(),
+            false,
+        )
+        .unwrap();
+        Arc::new(
+            BoundedWindowAggExec::try_new(
+                vec![window_expr],
+                input,
+                vec![],
+                InputOrderMode::Sorted,
+            )
+            .unwrap(),
+        )
+    }
+
+    fn optimize(plan: Arc<dyn ExecutionPlan>, target_partitions: usize) -> String {
+        let mut config = ConfigOptions::new();
+        config.execution.target_partitions = target_partitions;
+        let optimized = RepartitionWindows::new().optimize(plan, &config).unwrap();
+        let window = optimized
+            .as_any()
+            .downcast_ref::<BoundedWindowAggExec>()
+            .unwrap();
+        format!(
+            "{:?}",
+            window
+                .partition_keys
+                .iter()
+                .map(|key| key.to_string())
+                .collect::<Vec<_>>()
+        )
+    }
+
+    #[test]
+    fn sets_window_partition_keys() {
+        let plan = partitioned_window_exec(memory_exec(&schema()));
+        assert_eq!(optimize(plan.clone(), 4), "[\"device@0\"]");
+        // A single target partition leaves the window unchanged
+        assert_eq!(optimize(plan, 1), "[]");
+    }
+
+    #[test]
+    fn window_without_partition_by() {
+        let schema = schema();
+        let plan =
+            bounded_window_exec("ts", [sort_expr("ts", &schema)], memory_exec(&schema));
+        assert_eq!(optimize(plan, 4), "[]");
+    }
+
+    #[test]
+    fn repartition_windows_disabled() {
+        let plan = partitioned_window_exec(memory_exec(&schema()));
+        let mut config = ConfigOptions::new();
+        config.execution.target_partitions = 4;
+        config.optimizer.repartition_windows = false;
+        let optimized = RepartitionWindows::new()
+            .optimize(plan.clone(), &config)
+            .unwrap();
+        assert_eq!(
+            displayable(optimized.as_ref()).indent(true).to_string(),
+            displayable(plan.as_ref()).indent(true).to_string()
+        );
+    }
+}
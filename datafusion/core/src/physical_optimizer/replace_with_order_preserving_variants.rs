@@ -44,7 +44,7 @@ pub type OrderPreservationContext = PlanContext<bool>;
 /// Updates order-preservation data for all children of the given node.
 pub fn update_children(opc: &mut OrderPreservationContext) {
     for PlanContext {
-        plan,
+        node: plan,
         children,
         data,
     } in opc.children.iter_mut()
@@ -113,32 +113,32 @@ fn plan_with_order_preserving_variants(
         .collect::<Result<_>>()?;
     sort_input.data = false;
 
-    if is_repartition(&sort_input.plan)
-        && !sort_input.plan.maintains_input_order()[0]
+    if is_repartition(&sort_input.node)
+        && !sort_input.node.maintains_input_order()[0]
         && is_spr_better
     {
         // When a `RepartitionExec` doesn't preserve ordering, replace it with
         // a sort-preserving variant if appropriate:
-        let child = sort_input.children[0].plan.clone();
-        let partitioning = sort_input.plan.output_partitioning().clone();
-        sort_input.plan = Arc::new(
+        let child = sort_input.children[0].node.clone();
+        let partitioning = sort_input.node.output_partitioning().clone();
+        sort_input.node = Arc::new(
             RepartitionExec::try_new(child, partitioning)?.with_preserve_order(),
         ) as _;
         sort_input.children[0].data = true;
         return Ok(sort_input);
-    } else if is_coalesce_partitions(&sort_input.plan) && is_spm_better {
-        let child = &sort_input.children[0].plan;
+    } else if is_coalesce_partitions(&sort_input.node) && is_spm_better {
+        let child = &sort_input.children[0].node;
         if let Some(ordering) = child.output_ordering().map(Vec::from) {
             // When the input of a `CoalescePartitionsExec` has an ordering,
             // replace it with a `SortPreservingMergeExec` if appropriate:
             let spm = SortPreservingMergeExec::new(ordering, child.clone());
-            sort_input.plan = Arc::new(spm) as _;
+            sort_input.node = Arc::new(spm) as _;
             sort_input.children[0].data = true;
             return Ok(sort_input);
         }
     }
 
-    sort_input.update_plan_from_children()
+    sort_input.update_node_from_children()
 }
 
 /// Calculates the updated plan by replacing operators that preserve ordering
@@ -147,7 +147,7 @@ fn plan_with_order_preserving_variants(
 fn plan_with_order_breaking_variants(
     mut sort_input: OrderPreservationContext,
 ) -> Result<OrderPreservationContext> {
-    let plan = &sort_input.plan;
+    let plan = &sort_input.node;
     sort_input.children = izip!(
         sort_input.children,
         plan.maintains_input_order(),
@@ -159,7 +159,7 @@ fn plan_with_order_breaking_variants(
         if maintains
             && (is_sort_preserving_merge(plan)
                 || !required_ordering.map_or(false, |required_ordering| {
-                    node.plan
+                    node.node
                         .equivalence_properties()
                         .ordering_satisfy_requirement(&required_ordering)
                 }))
@@ -175,16 +175,16 @@ fn plan_with_order_breaking_variants(
     if is_repartition(plan) && plan.maintains_input_order()[0] {
         // When a `RepartitionExec` preserves ordering, replace it with a
         // non-sort-preserving variant:
-        let child = sort_input.children[0].plan.clone();
+        let child = sort_input.children[0].node.clone();
         let partitioning = plan.output_partitioning().clone();
-        sort_input.plan = Arc::new(RepartitionExec::try_new(child, partitioning)?) as _;
+        sort_input.node = Arc::new(RepartitionExec::try_new(child, partitioning)?) as _;
     } else if is_sort_preserving_merge(plan) {
         // Replace `SortPreservingMergeExec` with a `CoalescePartitionsExec`:
-        let child = sort_input.children[0].plan.clone();
+        let child = sort_input.children[0].node.clone();
         let coalesce = CoalescePartitionsExec::new(child);
-        sort_input.plan = Arc::new(coalesce) as _;
+        sort_input.node = Arc::new(coalesce) as _;
     } else {
-        return sort_input.update_plan_from_children();
+        return sort_input.update_node_from_children();
     }
 
     sort_input.children[0].data = false;
@@ -235,14 +235,14 @@ pub(crate) fn replace_with_order_preserving_variants(
     config: &ConfigOptions,
 ) -> Result<Transformed<OrderPreservationContext>> {
     update_children(&mut requirements);
-    if !(is_sort(&requirements.plan) && requirements.children[0].data) {
+    if !(is_sort(&requirements.node) && requirements.children[0].data) {
         return Ok(Transformed::no(requirements));
     }
 
     // For unbounded cases, we replace with the order-preserving variant in any
     // case, as doing so helps fix the pipeline. Also replace if config allows.
     let use_order_preserving_variant = config.optimizer.prefer_existing_sort
-        || !requirements.plan.execution_mode().pipeline_friendly();
+        || !requirements.node.execution_mode().pipeline_friendly();
 
     // Create an alternate plan with order-preserving variants:
     let mut alternate_plan = plan_with_order_preserving_variants(
@@ -253,9 +253,9 @@ pub(crate) fn replace_with_order_preserving_variants(
 
     // If the alternate plan makes this sort unnecessary, accept the alternate:
     if alternate_plan
-        .plan
+        .node
         .equivalence_properties()
-        .ordering_satisfy(requirements.plan.output_ordering().unwrap_or(&[]))
+        .ordering_satisfy(requirements.node.output_ordering().unwrap_or(&[]))
     {
         for child in alternate_plan.children.iter_mut() {
             child.data = false;
@@ -393,7 +393,7 @@ mod tests {
             let config = SessionConfig::new().with_prefer_existing_sort($PREFER_EXISTING_SORT);
             let plan_with_pipeline_fixer = OrderPreservationContext::new_default(physical_plan);
             let parallel = plan_with_pipeline_fixer.transform_up(|plan_with_pipeline_fixer| replace_with_order_preserving_variants(plan_with_pipeline_fixer, false, false, config.options())).data().and_then(check_integrity)?;
-            let optimized_physical_plan = parallel.plan;
+            let optimized_physical_plan = parallel.node;
 
             // Get string representation of the plan
             let actual = get_plan_string(&optimized_physical_plan);
@@ -1503,6 +1503,7 @@ mod tests {
                 table_partition_cols: vec![],
                 output_ordering: vec![sort_exprs],
                 column_hints: None,
+                listing_pruning: None,
             },
             true,
             0,
@@ -48,7 +48,7 @@ pub type SortPushDown = PlanContext<Option<Vec<PhysicalSortRequirement>>>;
 
 /// Assigns the ordering requirement of the root node to the its children.
 pub fn assign_initial_requirements(node: &mut SortPushDown) {
-    let reqs = node.plan.required_input_ordering();
+    let reqs = node.node.required_input_ordering();
     for (child, requirement) in node.children.iter_mut().zip(reqs) {
         child.data = requirement;
     }
@@ -57,7 +57,7 @@ pub fn assign_initial_requirements(node: &mut SortPushDown) {
 pub(crate) fn pushdown_sorts(
     mut requirements: SortPushDown,
 ) -> Result<Transformed<SortPushDown>> {
-    let plan = &requirements.plan;
+    let plan = &requirements.node;
     let parent_reqs = requirements.data.as_deref().unwrap_or(&[]);
     let satisfy_parent = plan
         .equivalence_properties()
@@ -80,7 +80,7 @@ pub(crate) fn pushdown_sorts(
         // We can safely get the 0th index as we are dealing with a `SortExec`.
         let mut child = requirements.children.swap_remove(0);
         if let Some(adjusted) =
-            pushdown_requirement_to_children(&child.plan, &required_ordering)?
+            pushdown_requirement_to_children(&child.node, &required_ordering)?
         {
             for (grand_child, order) in child.children.iter_mut().zip(adjusted) {
                 grand_child.data = order;
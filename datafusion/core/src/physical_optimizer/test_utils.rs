@@ -285,6 +285,7 @@ pub fn parquet_exec(schema: &SchemaRef) -> Arc<ParquetExec> {
             table_partition_cols: vec![],
             output_ordering: vec![],
             column_hints: None,
+            listing_pruning: None,
         },
         None,
         None,
@@ -310,6 +311,7 @@ pub fn parquet_exec_sorted(
             table_partition_cols: vec![],
             output_ordering: vec![sort_exprs],
             column_hints: None,
+            listing_pruning: None,
         },
         None,
         None,
@@ -383,14 +385,14 @@ pub fn sort_exec(
 pub fn check_integrity<T: Clone>(context: PlanContext<T>) -> Result<PlanContext<T>> {
     context
         .transform_up(|node| {
-            let children_plans = node.plan.children();
+            let children_plans = node.node.children();
             assert_eq!(node.children.len(), children_plans.len());
             for (child_plan, child_node) in
                 children_plans.iter().zip(node.children.iter())
             {
                 assert_eq!(
                     displayable(child_plan.as_ref()).one_line().to_string(),
-                    displayable(child_node.plan.as_ref()).one_line().to_string()
+                    displayable(child_node.node.as_ref()).one_line().to_string()
                 );
             }
             Ok(Transformed::no(node))
@@ -41,12 +41,12 @@ pub fn add_sort_above<T: Clone + Default>(
     let mut sort_expr = PhysicalSortRequirement::to_sort_exprs(sort_requirements);
     sort_expr.retain(|sort_expr| {
         !node
-            .plan
+            .node
             .equivalence_properties()
             .is_expr_constant(&sort_expr.expr)
     });
-    let mut new_sort = SortExec::new(sort_expr, node.plan.clone()).with_fetch(fetch);
-    if node.plan.output_partitioning().partition_count() > 1 {
+    let mut new_sort = SortExec::new(sort_expr, node.node.clone()).with_fetch(fetch);
+    if node.node.output_partitioning().partition_count() > 1 {
         new_sort = new_sort.with_preserve_partitioning(true);
     }
     PlanContext::new(Arc::new(new_sort), T::default(), vec![node])
@@ -61,7 +61,7 @@ pub fn add_sort_above_with_check<T: Clone + Default>(
     fetch: Option<usize>,
 ) -> PlanContext<T> {
     if !node
-        .plan
+        .node
         .equivalence_properties()
         .ordering_satisfy_requirement(&sort_requirements)
     {
@@ -20,6 +20,7 @@
 use std::borrow::Cow;
 use std::collections::HashMap;
 use std::fmt::Write;
+use std::ops::Bound;
 use std::sync::Arc;
 
 use crate::datasource::file_format::arrow::ArrowFormat;
@@ -30,13 +31,14 @@ use crate::datasource::file_format::json::JsonFormat;
 use crate::datasource::file_format::parquet::ParquetFormat;
 use crate::datasource::file_format::FileFormat;
 use crate::datasource::listing::ListingTableUrl;
-use crate::datasource::physical_plan::FileSinkConfig;
+use crate::datasource::physical_plan::{FileSinkConfig, ReadAuditExec};
 use crate::datasource::source_as_provider;
 use crate::error::{DataFusionError, Result};
 use crate::execution::context::{ExecutionProps, SessionState};
 use crate::logical_expr::utils::generate_sort_key;
 use crate::logical_expr::{
-    Aggregate, EmptyRelation, Join, Projection, Sort, TableScan, Unnest, Window,
+    Aggregate, EmptyRelation, Join, Projection, Sort, TableSample, TableScan, Unnest,
+    Window,
 };
 use crate::logical_expr::{
     Expr, LogicalPlan, Partitioning as LogicalPartitioning, PlanType, Repartition,
@@ -47,40 +49,50 @@ use crate::physical_expr::{create_physical_expr, create_physical_exprs};
 use crate::physical_optimizer::optimizer::PhysicalOptimizerRule;
 use crate::physical_plan::aggregates::{AggregateExec, AggregateMode, PhysicalGroupBy};
 use crate::physical_plan::analyze::AnalyzeExec;
+use crate::physical_plan::async_func::AsyncMapper;
 use crate::physical_plan::empty::EmptyExec;
 use crate::physical_plan::explain::ExplainExec;
 use crate::physical_plan::expressions::{Column, PhysicalSortExpr};
 use crate::physical_plan::filter::FilterExec;
+use crate::physical_plan::gap_fill::{GapFillExec, GapFillParams};
 use crate::physical_plan::joins::utils as join_utils;
 use crate::physical_plan::joins::{
-    CrossJoinExec, HashJoinExec, NestedLoopJoinExec, PartitionMode, SortMergeJoinExec,
+    AsOfJoinExec, CrossJoinExec, HashJoinExec, NestedLoopJoinExec, PartitionMode,
+    RangeCondition, RangeJoinExec, SortMergeJoinExec,
 };
 use crate::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
 use crate::physical_plan::memory::MemoryExec;
 use crate::physical_plan::projection::ProjectionExec;
 use crate::physical_plan::recursive_query::RecursiveQueryExec;
 use crate::physical_plan::repartition::RepartitionExec;
+use crate::physical_plan::sample::SampleExec;
+use crate::physical_plan::scalar_subquery::{ScalarSubquery, ScalarSubqueryExec};
+use crate::physical_plan::set_operation::SetOperationExec;
 use crate::physical_plan::sorts::sort::SortExec;
 use crate::physical_plan::union::UnionExec;
 use crate::physical_plan::unnest::UnnestExec;
 use crate::physical_plan::values::ValuesExec;
-use crate::physical_plan::windows::{BoundedWindowAggExec, WindowAggExec};
+use crate::physical_plan::windows::{
+    with_exact_partition_rows, BoundedWindowAggExec, WindowAggExec,
+};
 use crate::physical_plan::{
     aggregates, displayable, udaf, windows, AggregateExpr, ExecutionPlan,
     ExecutionPlanProperties, InputOrderMode, Partitioning, PhysicalExpr, WindowExpr,
 };
 
 use arrow::compute::SortOptions;
-use arrow::datatypes::{Schema, SchemaRef};
+use arrow::datatypes::{DataType, FieldRef, Schema, SchemaRef};
 use arrow_array::builder::StringBuilder;
 use arrow_array::RecordBatch;
-use datafusion_common::config::FormatOptions;
+use datafusion_common::config::{DecimalOverflowMode, FormatOptions};
 use datafusion_common::display::ToStringifiedPlan;
+use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
 use datafusion_common::{
     exec_err, internal_datafusion_err, internal_err, not_impl_err, plan_err, DFSchema,
-    FileType, ScalarValue,
+    DFSchemaRef, FileType, JoinType, ScalarValue,
 };
 use datafusion_expr::dml::CopyTo;
+use datafusion_expr::execution_props::ScalarSubqueryResult;
 use datafusion_expr::expr::{
     self, AggregateFunction, AggregateFunctionDefinition, Alias, Between, BinaryExpr,
     Cast, GetFieldAccess, GetIndexedField, GroupingSet, InList, Like, TryCast,
@@ -89,21 +101,57 @@ use datafusion_expr::expr::{
 use datafusion_expr::expr_rewriter::unnormalize_cols;
 use datafusion_expr::expr_vec_fmt;
 use datafusion_expr::logical_plan::builder::wrap_projection_for_join_if_necessary;
+use datafusion_expr::utils::{can_hash, find_valid_equijoin_key_pair, split_binary};
 use datafusion_expr::{
-    DescribeTable, DmlStatement, Extension, Filter, RecursiveQuery,
-    ScalarFunctionDefinition, StringifiedPlan, WindowFrame, WindowFrameBound, WriteOp,
+    AsOfJoin, DescribeTable, DmlStatement, ExprSchemable, Extension, Filter, Operator,
+    RecursiveQuery, ScalarFunctionDefinition, SetOperation, StringifiedPlan, Subquery,
+    WindowFrame, WindowFrameBound, WriteOp,
+};
+use datafusion_optimizer::analyzer::handle_gap_fill::GapFill;
+use datafusion_physical_expr::expressions::{Avg, Literal, Sum};
+use datafusion_physical_expr::window::{
+    PlainAggregateWindowExpr, SlidingAggregateWindowExpr,
 };
-use datafusion_physical_expr::expressions::Literal;
 use datafusion_physical_expr::LexOrdering;
 use datafusion_physical_plan::placeholder_row::PlaceholderRowExec;
 use datafusion_sql::utils::window_expr_common_partition_keys;
 
 use async_trait::async_trait;
-use futures::{StreamExt, TryStreamExt};
+use futures::future::BoxFuture;
+use futures::{FutureExt, StreamExt, TryStreamExt};
 use itertools::{multiunzip, Itertools};
 use log::{debug, trace};
 use sqlparser::ast::NullTreatment;
 use tokio::sync::Mutex;
+use tracing::{debug_span, info_span};
+
+/// Returns the distinct uncorrelated scalar subqueries referenced by the
+/// expressions of `plan` and its inputs, which do not have a result in
+/// `execution_props` yet
+fn uncorrelated_scalar_subqueries(
+    plan: &LogicalPlan,
+    execution_props: &ExecutionProps,
+) -> Result<Vec<Subquery>> {
+    let mut subqueries = vec![];
+    plan.apply(|node| {
+        node.apply_expressions(|expr| {
+            expr.apply(|expr| {
+                if let Expr::ScalarSubquery(subquery) = expr {
+                    if subquery.outer_ref_columns.is_empty()
+                        && !execution_props
+                            .scalar_subquery_results
+                            .contains_key(subquery)
+                        && !subqueries.contains(subquery)
+                    {
+                        subqueries.push(subquery.clone());
+                    }
+                }
+                Ok(TreeNodeRecursion::Continue)
+            })
+        })
+    })?;
+    Ok(subqueries)
+}
 
 fn create_function_physical_name(
     fun: &str,
@@ -337,8 +385,9 @@ fn create_physical_name(e: &Expr, is_first_expr: bool) -> Result<String> {
         Expr::InSubquery(_) => {
             not_impl_err!("IN subquery is not yet supported in the physical plan")
         }
-        Expr::ScalarSubquery(_) => {
-            not_impl_err!("Scalar subqueries are not yet supported in the physical plan")
+        // Uncorrelated scalar subqueries are evaluated once before the plan
+        Expr::ScalarSubquery(subquery) => {
+            Ok(subquery.subquery.schema().field(0).name().clone())
         }
         Expr::Between(Between {
             expr,
@@ -591,6 +640,20 @@ impl DefaultPhysicalPlanner {
         logical_plan: &LogicalPlan,
         session_state: &SessionState,
     ) -> Result<Arc<dyn ExecutionPlan>> {
+        let subqueries = uncorrelated_scalar_subqueries(
+            logical_plan,
+            session_state.execution_props(),
+        )?;
+        if !subqueries.is_empty() {
+            return self
+                .create_plan_with_scalar_subqueries(
+                    logical_plan,
+                    subqueries,
+                    session_state,
+                )
+                .await;
+        }
+
         // DFS the tree to flatten it into a Vec.
         // This will allow us to build the Physical Plan from the leaves up
         // to avoid recursion, and also to make it easier to build a valid
@@ -744,6 +807,41 @@ impl DefaultPhysicalPlanner {
         Ok(Some(plan))
     }
 
+    /// Create a physical plan for `logical_plan` evaluating each of its
+    /// uncorrelated scalar `subqueries` once, before executing the plan
+    ///
+    /// The expressions referencing a subquery read its value from a
+    /// [`ScalarSubqueryResult`] shared with the [`ScalarSubqueryExec`] at the
+    /// root of the plan.
+    fn create_plan_with_scalar_subqueries<'a>(
+        &'a self,
+        logical_plan: &'a LogicalPlan,
+        subqueries: Vec<Subquery>,
+        session_state: &'a SessionState,
+    ) -> BoxFuture<'a, Result<Arc<dyn ExecutionPlan>>> {
+        async move {
+            let mut state = session_state.clone();
+            let mut scalar_subqueries = Vec::with_capacity(subqueries.len());
+            for subquery in subqueries {
+                // Subqueries are not optimized with their outer query. Their
+                // own subqueries are evaluated by their own plans
+                let optimized = session_state.optimize(&subquery.subquery)?;
+                let plan = self.create_initial_plan(&optimized, session_state).await?;
+                let result = ScalarSubqueryResult::new();
+                state
+                    .execution_props_mut()
+                    .scalar_subquery_results
+                    .insert(subquery, result.clone());
+                scalar_subqueries.push(ScalarSubquery::new(plan, result));
+            }
+            let input = self.create_initial_plan(logical_plan, &state).await?;
+            let plan: Arc<dyn ExecutionPlan> =
+                Arc::new(ScalarSubqueryExec::new(input, scalar_subqueries));
+            Ok(plan)
+        }
+        .boxed()
+    }
+
     /// Given a single LogicalPlan node, map it to it's physical ExecutionPlan counterpart.
     async fn map_logical_node_to_physical(
         &self,
@@ -754,10 +852,13 @@ impl DefaultPhysicalPlanner {
         let exec_node: Arc<dyn ExecutionPlan> = match node {
             // Leaves (no children)
             LogicalPlan::TableScan(TableScan {
+                table_name,
                 source,
                 projection,
                 filters,
                 fetch,
+                sample,
+                version,
                 ..
             }) => {
                 let source = source_as_provider(source)?;
@@ -765,9 +866,141 @@ impl DefaultPhysicalPlanner {
                 // doesn't know (nor should care) how the relation was
                 // referred to in the query
                 let filters = unnormalize_cols(filters.iter().cloned());
-                source
-                    .scan(session_state, projection.as_ref(), &filters, *fetch)
-                    .await?
+                let scan = match (sample, version) {
+                    (None, Some(version)) => {
+                        source
+                            .scan_at_version(
+                                session_state,
+                                projection.as_ref(),
+                                &filters,
+                                *fetch,
+                                version,
+                            )
+                            .await?
+                    }
+                    (None, None) => {
+                        match session_state.table_snapshot(table_name.clone()) {
+                            Some(snapshot) => {
+                                source
+                                    .scan_snapshot(
+                                        session_state,
+                                        projection.as_ref(),
+                                        &filters,
+                                        *fetch,
+                                        snapshot,
+                                    )
+                                    .await?
+                            }
+                            None => {
+                                source
+                                    .scan(
+                                        session_state,
+                                        projection.as_ref(),
+                                        &filters,
+                                        *fetch,
+                                    )
+                                    .await?
+                            }
+                        }
+                    }
+                    (Some(sample), version) => {
+                        // Pick the seed once, so that all the partitions (and
+                        // the table provider) sample with the same one
+                        let seed = sample.seed.unwrap_or_else(rand::random);
+                        let sample = TableSample {
+                            seed: Some(seed),
+                            ..sample.clone()
+                        };
+                        // A past version is sampled from its rows
+                        let snapshot = match version {
+                            Some(_) => None,
+                            None => session_state.table_snapshot(table_name.clone()),
+                        };
+                        let sampled = match (version, snapshot) {
+                            (Some(_), _) => None,
+                            (None, Some(snapshot)) => {
+                                source
+                                    .scan_snapshot_sample(
+                                        session_state,
+                                        projection.as_ref(),
+                                        &filters,
+                                        &sample,
+                                        snapshot,
+                                    )
+                                    .await?
+                            }
+                            (None, None) => {
+                                source
+                                    .scan_sample(
+                                        session_state,
+                                        projection.as_ref(),
+                                        &filters,
+                                        &sample,
+                                    )
+                                    .await?
+                            }
+                        };
+                        match sampled {
+                            Some(plan) => plan,
+                            None => {
+                                // A limit applies to the sampled rows, so
+                                // the scan can not be limited
+                                let input = match (version, snapshot) {
+                                    (Some(version), _) => {
+                                        source
+                                            .scan_at_version(
+                                                session_state,
+                                                projection.as_ref(),
+                                                &filters,
+                                                None,
+                                                version,
+                                            )
+                                            .await?
+                                    }
+                                    (None, Some(snapshot)) => {
+                                        source
+                                            .scan_snapshot(
+                                                session_state,
+                                                projection.as_ref(),
+                                                &filters,
+                                                None,
+                                                snapshot,
+                                            )
+                                            .await?
+                                    }
+                                    (None, None) => {
+                                        source
+                                            .scan(
+                                                session_state,
+                                                projection.as_ref(),
+                                                &filters,
+                                                None,
+                                            )
+                                            .await?
+                                    }
+                                };
+                                Arc::new(SampleExec::new(
+                                    input,
+                                    sample.method,
+                                    sample.fraction(),
+                                    seed,
+                                ))
+                            }
+                        }
+                    }
+                };
+                if session_state.runtime_env().audit_sink.is_some() {
+                    Arc::new(ReadAuditExec::new(
+                        scan,
+                        session_state
+                            .identity()
+                            .map(|identity| identity.user().to_string()),
+                        table_name.to_string(),
+                        filters.iter().map(|filter| filter.to_string()).collect(),
+                    ))
+                } else {
+                    scan
+                }
             }
             LogicalPlan::Values(Values { values, schema }) => {
                 let exec_schema = schema.as_ref().to_owned().into();
@@ -801,7 +1034,15 @@ impl DefaultPhysicalPlanner {
                 output_schema,
             }) => {
                 let output_schema: Schema = output_schema.as_ref().into();
-                self.plan_describe(schema.clone(), Arc::new(output_schema))?
+                let describe_nested_fields = session_state
+                    .config_options()
+                    .execution
+                    .describe_nested_fields;
+                self.plan_describe(
+                    schema.clone(),
+                    Arc::new(output_schema),
+                    describe_nested_fields,
+                )?
             }
 
             // 1 Child
@@ -956,16 +1197,26 @@ impl DefaultPhysicalPlanner {
                 }
 
                 let logical_schema = node.schema();
+                let overflow_mode = session_state
+                    .config_options()
+                    .execution
+                    .aggregate
+                    .decimal_overflow_mode;
                 let window_expr = window_expr
                     .iter()
                     .map(|e| {
-                        create_window_expr(
+                        let window_expr = create_window_expr(
                             e,
                             logical_schema,
                             session_state.execution_props(),
-                        )
+                        )?;
+                        Ok(window_with_decimal_overflow_mode(
+                            window_expr,
+                            overflow_mode,
+                        ))
                     })
                     .collect::<Result<Vec<_>>>()?;
+                let window_expr = with_exact_partition_rows(window_expr, &input_exec)?;
 
                 let uses_bounded_memory =
                     window_expr.iter().all(|e| e.uses_bounded_memory());
@@ -1018,6 +1269,15 @@ impl DefaultPhysicalPlanner {
 
                 let (aggregates, filters, _order_bys): (Vec<_>, Vec<_>, Vec<_>) =
                     multiunzip(agg_filter);
+                let overflow_mode = session_state
+                    .config_options()
+                    .execution
+                    .aggregate
+                    .decimal_overflow_mode;
+                let aggregates = aggregates
+                    .into_iter()
+                    .map(|aggregate| with_decimal_overflow_mode(aggregate, overflow_mode))
+                    .collect::<Vec<_>>();
 
                 let initial_aggr = Arc::new(AggregateExec::try_new(
                     AggregateMode::Partial,
@@ -1089,8 +1349,31 @@ impl DefaultPhysicalPlanner {
                     .options()
                     .optimizer
                     .default_filter_selectivity;
-                let filter = FilterExec::try_new(runtime_expr, physical_input)?;
-                Arc::new(filter.with_default_selectivity(selectivity)?)
+
+                // Evaluate async functions of the predicate before the filter
+                let input_schema = physical_input.schema();
+                let mut async_mapper = AsyncMapper::new(input_schema.fields().len());
+                let runtime_expr = async_mapper.map_expr(runtime_expr)?;
+                if async_mapper.is_empty() {
+                    let filter = FilterExec::try_new(runtime_expr, physical_input)?;
+                    Arc::new(filter.with_default_selectivity(selectivity)?)
+                } else {
+                    let async_exec = async_mapper.into_exec(physical_input)?;
+                    let filter = FilterExec::try_new(runtime_expr, async_exec)?
+                        .with_default_selectivity(selectivity)?;
+                    // Remove the results of the async functions
+                    let projection = input_schema
+                        .fields()
+                        .iter()
+                        .enumerate()
+                        .map(|(index, field)| {
+                            let column: Arc<dyn PhysicalExpr> =
+                                Arc::new(Column::new(field.name(), index));
+                            (column, field.name().clone())
+                        })
+                        .collect();
+                    Arc::new(ProjectionExec::try_new(projection, Arc::new(filter))?)
+                }
             }
             LogicalPlan::Repartition(Repartition {
                 input,
@@ -1115,10 +1398,21 @@ impl DefaultPhysicalPlanner {
                             .collect::<Result<Vec<_>>>()?;
                         Partitioning::Hash(runtime_expr, *n)
                     }
-                    LogicalPartitioning::DistributeBy(_) => {
-                        return not_impl_err!(
-                            "Physical plan does not support DistributeBy partitioning"
-                        );
+                    // Distributes the rows in the target partitions by the hashes
+                    // of the expressions
+                    LogicalPartitioning::DistributeBy(expr) => {
+                        let runtime_expr = expr
+                            .iter()
+                            .map(|e| {
+                                self.create_physical_expr(
+                                    e,
+                                    input_dfschema,
+                                    session_state,
+                                )
+                            })
+                            .collect::<Result<Vec<_>>>()?;
+                        let n = session_state.config().target_partitions();
+                        Partitioning::Hash(runtime_expr, n)
                     }
                 };
                 Arc::new(RepartitionExec::try_new(
@@ -1199,6 +1493,34 @@ impl DefaultPhysicalPlanner {
 
                 let [physical_left, physical_right] = children.two()?;
 
+                if let Some((l, r)) = null_aware_anti_join_key(
+                    *join_type,
+                    keys,
+                    filter.as_ref(),
+                    left.schema(),
+                    right.schema(),
+                )? {
+                    // build the hash table on the subquery side, and probe it
+                    // with the rows the `NOT IN` predicate is evaluated for
+                    let execution_props = session_state.execution_props();
+                    let on = vec![(
+                        create_physical_expr(&r, right.schema(), execution_props)?,
+                        create_physical_expr(&l, left.schema(), execution_props)?,
+                    )];
+                    let join = HashJoinExec::try_new(
+                        physical_right,
+                        physical_left,
+                        on,
+                        None,
+                        &JoinType::RightAnti,
+                        None,
+                        PartitionMode::CollectLeft,
+                        false,
+                    )?
+                    .with_null_aware(true)?;
+                    return Ok(Arc::new(join));
+                }
+
                 // If join has expression equijoin keys, add physical projection.
                 let has_expr_join_key = keys.iter().any(|(l, r)| {
                     !(matches!(l, Expr::Column(_)) && matches!(r, Expr::Column(_)))
@@ -1389,7 +1711,28 @@ impl DefaultPhysicalPlanner {
                 let prefer_hash_join =
                     session_state.config_options().optimizer.prefer_hash_join;
 
-                let join: Arc<dyn ExecutionPlan> = if join_on.is_empty() {
+                let enable_range_join =
+                    session_state.config_options().optimizer.enable_range_join;
+                let range_join_filter = match (&join_filter, join_type) {
+                    (Some(filter), JoinType::Inner)
+                        if enable_range_join
+                            && join_on.is_empty()
+                            && RangeCondition::try_from_filter(filter).is_some() =>
+                    {
+                        Some(filter.clone())
+                    }
+                    _ => None,
+                };
+
+                let join: Arc<dyn ExecutionPlan> = if let Some(filter) = range_join_filter
+                {
+                    // the filter restricts the join to ranges, use the range join
+                    Arc::new(RangeJoinExec::try_new(
+                        physical_left,
+                        physical_right,
+                        filter,
+                    )?)
+                } else if join_on.is_empty() {
                     // there is no equal join condition, use the nested loop join
                     // TODO optimize the plan, and use the config of `target_partitions` and `repartition_joins`
                     Arc::new(NestedLoopJoinExec::try_new(
@@ -1420,7 +1763,12 @@ impl DefaultPhysicalPlanner {
                     && prefer_hash_join
                 {
                     let partition_mode = {
-                        if session_state.config().collect_statistics() {
+                        if session_state.config().collect_statistics()
+                            || session_state
+                                .config_options()
+                                .optimizer
+                                .hash_join_broadcast_by_estimate
+                        {
                             PartitionMode::Auto
                         } else {
                             PartitionMode::Partitioned
@@ -1475,6 +1823,83 @@ impl DefaultPhysicalPlanner {
 
             // N Children
             LogicalPlan::Union(_) => Arc::new(UnionExec::new(children.vec())),
+            LogicalPlan::Extension(Extension { node })
+                if node.as_any().is::<GapFill>() =>
+            {
+                let gap_fill = node.as_any().downcast_ref::<GapFill>().unwrap();
+                let physical_input = children.one()?;
+                let input_dfschema = gap_fill.input.schema();
+                let create_expr = |expr: &Expr| {
+                    self.create_physical_expr(expr, input_dfschema, session_state)
+                };
+                let column = |expr: &Expr| -> Result<Column> {
+                    match create_expr(expr)?.as_any().downcast_ref::<Column>() {
+                        Some(column) => Ok(column.clone()),
+                        None => internal_err!("GapFill expects a column, got {expr}"),
+                    }
+                };
+                let bound = |bound: &Bound<Expr>| -> Result<_> {
+                    Ok(match bound {
+                        Bound::Included(expr) => Bound::Included(create_expr(expr)?),
+                        Bound::Excluded(expr) => Bound::Excluded(create_expr(expr)?),
+                        Bound::Unbounded => Bound::Unbounded,
+                    })
+                };
+                let params = GapFillParams {
+                    stride: create_expr(&gap_fill.stride)?,
+                    origin: gap_fill.origin.as_ref().map(create_expr).transpose()?,
+                    start: bound(&gap_fill.start)?,
+                    end: bound(&gap_fill.end)?,
+                };
+                Arc::new(GapFillExec::try_new(
+                    physical_input,
+                    gap_fill.series.iter().map(column).collect::<Result<_>>()?,
+                    column(&gap_fill.time)?,
+                    gap_fill.locf.iter().map(column).collect::<Result<_>>()?,
+                    params,
+                )?)
+            }
+            LogicalPlan::Extension(Extension { node })
+                if node.as_any().is::<SetOperation>() =>
+            {
+                let set_operation = node.as_any().downcast_ref::<SetOperation>().unwrap();
+                let [left, right] = children.two()?;
+                Arc::new(SetOperationExec::try_new(left, right, set_operation.op)?)
+            }
+            LogicalPlan::Extension(Extension { node })
+                if node.as_any().is::<AsOfJoin>() =>
+            {
+                let join = node.as_any().downcast_ref::<AsOfJoin>().unwrap();
+                let [physical_left, physical_right] = children.two()?;
+                let left_schema = join.left.schema();
+                let right_schema = join.right.schema();
+                let on = join
+                    .on
+                    .iter()
+                    .map(|(l, r)| {
+                        Ok((
+                            self.create_physical_expr(l, left_schema, session_state)?,
+                            self.create_physical_expr(r, right_schema, session_state)?,
+                        ))
+                    })
+                    .collect::<Result<_>>()?;
+                Arc::new(AsOfJoinExec::try_new(
+                    physical_left,
+                    physical_right,
+                    on,
+                    self.create_physical_expr(
+                        &join.left_match,
+                        left_schema,
+                        session_state,
+                    )?,
+                    join.op,
+                    self.create_physical_expr(
+                        &join.right_match,
+                        right_schema,
+                        session_state,
+                    )?,
+                )?)
+            }
             LogicalPlan::Extension(Extension { node }) => {
                 let mut maybe_plan = None;
                 let children = children.vec();
@@ -1783,6 +2208,60 @@ fn get_physical_expr_pair(
     Ok((physical_expr, physical_name))
 }
 
+/// Returns the join key of a `LeftAnti` join whose only condition is
+/// `l = r OR l IS NULL OR r IS NULL`, the form of a `NOT IN` subquery, which
+/// a null-aware [`HashJoinExec`] evaluates without comparing every pair of rows.
+///
+/// The `IS NULL` test of a side that is not nullable may be missing.
+fn null_aware_anti_join_key(
+    join_type: JoinType,
+    keys: &[(Expr, Expr)],
+    filter: Option<&Expr>,
+    left_schema: &DFSchemaRef,
+    right_schema: &DFSchemaRef,
+) -> Result<Option<(Expr, Expr)>> {
+    let (JoinType::LeftAnti, true, Some(filter)) = (join_type, keys.is_empty(), filter)
+    else {
+        return Ok(None);
+    };
+
+    let mut eq_terms = vec![];
+    let mut is_null_terms = vec![];
+    for term in split_binary(filter, Operator::Or) {
+        match term {
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Operator::Eq,
+                right,
+            }) => eq_terms.push((left, right)),
+            Expr::IsNull(expr) => is_null_terms.push(expr.as_ref()),
+            _ => return Ok(None),
+        }
+    }
+    let [(left, right)] = eq_terms[..] else {
+        return Ok(None);
+    };
+    let Some((l, r)) = find_valid_equijoin_key_pair(
+        left,
+        right,
+        left_schema.clone(),
+        right_schema.clone(),
+    )?
+    else {
+        return Ok(None);
+    };
+    if !can_hash(&l.get_type(left_schema)?) || !can_hash(&r.get_type(right_schema)?) {
+        return Ok(None);
+    }
+
+    if is_null_terms.iter().any(|expr| *expr != &l && *expr != &r) {
+        return Ok(None);
+    }
+    let left_tested = is_null_terms.contains(&&l) || !l.nullable(left_schema)?;
+    let right_tested = is_null_terms.contains(&&r) || !r.nullable(right_schema)?;
+    Ok((left_tested && right_tested).then_some((l, r)))
+}
+
 /// Check if window bounds are valid after schema information is available, and
 /// window_frame bounds are casted to the corresponding column type.
 /// queries like:
@@ -1986,6 +2465,47 @@ pub fn create_aggregate_expr_and_maybe_filter(
     )
 }
 
+/// Sets how the `SUM` or `AVG` of decimals `aggregate` handles results that do
+/// not fit in its data type
+fn with_decimal_overflow_mode(
+    aggregate: Arc<dyn AggregateExpr>,
+    overflow_mode: DecimalOverflowMode,
+) -> Arc<dyn AggregateExpr> {
+    if let Some(sum) = aggregate.as_any().downcast_ref::<Sum>() {
+        Arc::new(sum.clone().with_overflow_mode(overflow_mode))
+    } else if let Some(avg) = aggregate.as_any().downcast_ref::<Avg>() {
+        Arc::new(avg.clone().with_overflow_mode(overflow_mode))
+    } else {
+        aggregate
+    }
+}
+
+/// Sets how the `SUM` or `AVG` of decimals of the aggregate window function
+/// `window_expr` handles results that do not fit in its data type
+fn window_with_decimal_overflow_mode(
+    window_expr: Arc<dyn WindowExpr>,
+    overflow_mode: DecimalOverflowMode,
+) -> Arc<dyn WindowExpr> {
+    let any = window_expr.as_any();
+    if let Some(expr) = any.downcast_ref::<PlainAggregateWindowExpr>() {
+        Arc::new(PlainAggregateWindowExpr::new(
+            with_decimal_overflow_mode(expr.get_aggregate_expr().clone(), overflow_mode),
+            expr.partition_by(),
+            expr.order_by(),
+            expr.get_window_frame().clone(),
+        ))
+    } else if let Some(expr) = any.downcast_ref::<SlidingAggregateWindowExpr>() {
+        Arc::new(SlidingAggregateWindowExpr::new(
+            with_decimal_overflow_mode(expr.get_aggregate_expr().clone(), overflow_mode),
+            expr.partition_by(),
+            expr.order_by(),
+            expr.get_window_frame().clone(),
+        ))
+    } else {
+        window_expr
+    }
+}
+
 /// Create a physical sort expression from a logical expression
 pub fn create_physical_sort_expr(
     e: &Expr,
@@ -2149,6 +2669,7 @@ impl DefaultPhysicalPlanner {
     where
         F: FnMut(&dyn ExecutionPlan, &dyn PhysicalOptimizerRule),
     {
+        let _span = info_span!("datafusion.optimize_physical_plan").entered();
         let optimizers = session_state.physical_optimizers();
         debug!(
             "Input physical plan:\n{}\n",
@@ -2161,6 +2682,11 @@ impl DefaultPhysicalPlanner {
 
         let mut new_plan = plan;
         for optimizer in optimizers {
+            let _span = debug_span!(
+                "datafusion.physical_optimizer_rule",
+                rule = optimizer.name()
+            )
+            .entered();
             let before_schema = new_plan.schema();
             new_plan = optimizer
                 .optimize(new_plan, session_state.config_options())
@@ -2199,16 +2725,30 @@ impl DefaultPhysicalPlanner {
         &self,
         table_schema: Arc<Schema>,
         output_schema: Arc<Schema>,
+        describe_nested_fields: bool,
     ) -> Result<Arc<dyn ExecutionPlan>> {
         let mut column_names = StringBuilder::new();
         let mut data_types = StringBuilder::new();
         let mut is_nullables = StringBuilder::new();
-        for field in table_schema.fields() {
-            column_names.append_value(field.name());
+        let mut fields = table_schema
+            .fields()
+            .iter()
+            .rev()
+            .map(|field| (0, field))
+            .collect::<Vec<_>>();
+        while let Some((depth, field)) = fields.pop() {
+            column_names.append_value(format!("{}{}", "  ".repeat(depth), field.name()));
 
             // "System supplied type" --> Use debug format of the datatype
             let data_type = field.data_type();
-            data_types.append_value(format!("{data_type:?}"));
+            if describe_nested_fields {
+                // the nested fields are listed in the following rows
+                let (name, nested) = describe_nested_type(data_type);
+                data_types.append_value(name);
+                fields.extend(nested.iter().rev().map(|field| (depth + 1, field)));
+            } else {
+                data_types.append_value(format!("{data_type:?}"));
+            }
 
             // "YES if the column is possibly nullable, NO if it is known not nullable. "
             let nullable_str = if field.is_nullable() { "YES" } else { "NO" };
@@ -2278,6 +2818,14 @@ impl DefaultPhysicalPlanner {
             })
             .collect::<Result<Vec<_>>>()?;
 
+        // Evaluate async functions before the projection
+        let mut async_mapper = AsyncMapper::new(input_exec.schema().fields().len());
+        let physical_exprs = physical_exprs
+            .into_iter()
+            .map(|(expr, name)| Ok((async_mapper.map_expr(expr)?, name)))
+            .collect::<Result<Vec<_>>>()?;
+        let input_exec = async_mapper.into_exec(input_exec)?;
+
         Ok(Arc::new(ProjectionExec::try_new(
             physical_exprs,
             input_exec,
@@ -2285,6 +2833,31 @@ impl DefaultPhysicalPlanner {
     }
 }
 
+/// Returns the name of `data_type` in the `DESCRIBE` output listing the
+/// nested fields, e.g. `List(Struct)`, and the fields of its structs
+fn describe_nested_type(data_type: &DataType) -> (String, &[FieldRef]) {
+    match data_type {
+        DataType::Struct(fields) => ("Struct".to_string(), &fields[..]),
+        DataType::List(field) => {
+            let (name, fields) = describe_nested_type(field.data_type());
+            (format!("List({name})"), fields)
+        }
+        DataType::LargeList(field) => {
+            let (name, fields) = describe_nested_type(field.data_type());
+            (format!("LargeList({name})"), fields)
+        }
+        DataType::FixedSizeList(field, size) => {
+            let (name, fields) = describe_nested_type(field.data_type());
+            (format!("FixedSizeList({name}, {size})"), fields)
+        }
+        DataType::Map(field, _) => {
+            let (name, fields) = describe_nested_type(field.data_type());
+            (format!("Map({name})"), fields)
+        }
+        _ => (format!("{data_type:?}"), &[][..]),
+    }
+}
+
 fn tuple_err<T, R>(value: (Result<T>, Result<R>)) -> Result<(T, R)> {
     match value {
         (Ok(e), Ok(e1)) => Ok((e, e1)),
@@ -207,6 +207,7 @@ pub fn partitioned_csv_config(
         table_partition_cols: vec![],
         output_ordering: vec![],
         column_hints: None,
+        listing_pruning: None,
     })
 }
 
@@ -294,6 +295,7 @@ pub fn csv_exec_sorted(
             table_partition_cols: vec![],
             output_ordering: vec![sort_exprs],
             column_hints: None,
+            listing_pruning: None,
         },
         false,
         0,
@@ -356,6 +358,7 @@ pub fn csv_exec_ordered(
             table_partition_cols: vec![],
             output_ordering: vec![sort_exprs],
             column_hints: None,
+            listing_pruning: None,
         },
         true,
         0,
@@ -159,6 +159,7 @@ impl TestParquetFile {
             table_partition_cols: vec![],
             output_ordering: vec![],
             column_hints: None,
+            listing_pruning: None,
         };
 
         let df_schema = self.schema.clone().to_dfschema_ref()?;
@@ -0,0 +1,393 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Scans of the versions of the tables resolved when the queries are planned
+
+use std::any::Any;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::{Arc, Mutex};
+
+use arrow::array::{Int32Array, StringArray};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use datafusion::assert_batches_eq;
+use datafusion::datasource::changes::{
+    TableChanges, CHANGE_TYPE_COLUMN, CHANGE_TYPE_DELETE, CHANGE_TYPE_INSERT,
+};
+use datafusion::datasource::{MemTable, SnapshotToken, TableProvider, TableType};
+use datafusion::error::Result;
+use datafusion::execution::context::{
+    AccessPolicyProvider, SessionContext, SessionIdentity, SessionState,
+};
+use datafusion::logical_expr::{col, lit, Expr, TablePolicy, TableVersion};
+use datafusion::physical_plan::ExecutionPlan;
+use datafusion_common::{plan_err, ResolvedTableReference, ScalarValue};
+
+use async_trait::async_trait;
+
+/// A table whose version `n` has `n + 1` rows. A new version is committed
+/// whenever the current one is resolved, as if the table was written
+/// concurrently.
+struct VersionedTable {
+    schema: SchemaRef,
+    versions: Mutex<Vec<Arc<MemTable>>>,
+    snapshots: AtomicUsize,
+}
+
+impl VersionedTable {
+    fn new() -> Result<Self> {
+        let schema = Arc::new(Schema::new(vec![Field::new("v", DataType::Int32, false)]));
+        let table = Self {
+            schema,
+            versions: Mutex::new(vec![]),
+            snapshots: AtomicUsize::new(0),
+        };
+        table.commit()?;
+        Ok(table)
+    }
+
+    fn commit(&self) -> Result<()> {
+        let mut versions = self.versions.lock().unwrap();
+        let rows = versions.len() as i32 + 1;
+        let batch = RecordBatch::try_new(
+            self.schema.clone(),
+            vec![Arc::new(Int32Array::from(vec![rows; rows as usize]))],
+        )?;
+        versions.push(Arc::new(MemTable::try_new(
+            self.schema.clone(),
+            vec![vec![batch]],
+        )?));
+        Ok(())
+    }
+
+    fn version(&self, index: usize) -> Result<Arc<MemTable>> {
+        match self.versions.lock().unwrap().get(index) {
+            Some(version) => Ok(version.clone()),
+            None => plan_err!("No version {index}"),
+        }
+    }
+}
+
+#[async_trait]
+impl TableProvider for VersionedTable {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+
+    fn table_type(&self) -> TableType {
+        TableType::Base
+    }
+
+    async fn scan(
+        &self,
+        state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let latest = self.versions.lock().unwrap().len() - 1;
+        let table = self.version(latest)?;
+        table.scan(state, projection, filters, limit).await
+    }
+
+    async fn snapshot(&self, _state: &SessionState) -> Result<Option<SnapshotToken>> {
+        self.snapshots.fetch_add(1, Ordering::SeqCst);
+        let current = self.versions.lock().unwrap().len() - 1;
+        self.commit()?;
+        Ok(Some(SnapshotToken::new(current.to_string())))
+    }
+
+    async fn scan_snapshot(
+        &self,
+        state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+        snapshot: &SnapshotToken,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let table = self.version(snapshot.as_str().parse().unwrap())?;
+        table.scan(state, projection, filters, limit).await
+    }
+
+    async fn scan_at_version(
+        &self,
+        state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+        version: &TableVersion,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let TableVersion::Version(ScalarValue::Int64(Some(version))) = version else {
+            return plan_err!("Unsupported version {version}");
+        };
+        let table = self.version(*version as usize)?;
+        table.scan(state, projection, filters, limit).await
+    }
+
+    /// The rows of each version replace all the rows of the previous one
+    async fn scan_changes(
+        &self,
+        state: &SessionState,
+        projection: Option<&Vec<usize>>,
+        filters: &[Expr],
+        limit: Option<usize>,
+        changes: &TableChanges,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let version = |version: Option<&TableVersion>| match version {
+            Some(TableVersion::Version(ScalarValue::Int64(Some(version)))) => {
+                Ok(*version as i32)
+            }
+            None => Ok(self.versions.lock().unwrap().len() as i32 - 1),
+            Some(version) => plan_err!("Unsupported version {version}"),
+        };
+        let (start, end) = (
+            version(Some(&changes.start))?,
+            version(changes.end.as_ref())?,
+        );
+
+        let mut fields = self.schema.fields().to_vec();
+        fields.push(Arc::new(Field::new(
+            CHANGE_TYPE_COLUMN,
+            DataType::Utf8,
+            false,
+        )));
+        let schema = Arc::new(Schema::new(fields));
+        let batches = (start + 1..=end)
+            .flat_map(|version| {
+                // the version `n` has `n + 1` rows of `n + 1`
+                [
+                    (version, CHANGE_TYPE_DELETE),
+                    (version + 1, CHANGE_TYPE_INSERT),
+                ]
+            })
+            .map(|(rows, change_type)| {
+                RecordBatch::try_new(
+                    schema.clone(),
+                    vec![
+                        Arc::new(Int32Array::from(vec![rows; rows as usize])),
+                        Arc::new(StringArray::from(vec![change_type; rows as usize])),
+                    ],
+                )
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+        let table = MemTable::try_new(schema, vec![batches])?;
+        table.scan(state, projection, filters, limit).await
+    }
+}
+
+const QUERY: &str = "SELECT max(v) AS a, (SELECT count(*) FROM t) AS b FROM t";
+
+#[tokio::test]
+async fn scans_read_the_same_version() -> Result<()> {
+    let table = Arc::new(VersionedTable::new()?);
+    let ctx = SessionContext::new();
+    ctx.register_table("t", table.clone())?;
+
+    let results = ctx.sql(QUERY).await?.collect().await?;
+    let expected = [
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 1 | 1 |",
+        "+---+---+",
+    ];
+    assert_batches_eq!(expected, &results);
+    assert_eq!(table.snapshots.load(Ordering::SeqCst), 1);
+
+    // the next query reads the version committed in between
+    let results = ctx.sql(QUERY).await?.collect().await?;
+    let expected = [
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 2 | 2 |",
+        "+---+---+",
+    ];
+    assert_batches_eq!(expected, &results);
+    Ok(())
+}
+
+#[tokio::test]
+async fn pinned_version() -> Result<()> {
+    let table = Arc::new(VersionedTable::new()?);
+    table.commit()?;
+    let ctx = SessionContext::new();
+    ctx.register_table("t", table.clone())?;
+    let state = ctx
+        .state()
+        .with_table_snapshot("t", SnapshotToken::new("0"));
+    let ctx = SessionContext::new_with_state(state);
+
+    let results = ctx.sql(QUERY).await?.collect().await?;
+    let expected = [
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 1 | 1 |",
+        "+---+---+",
+    ];
+    assert_batches_eq!(expected, &results);
+    assert_eq!(table.snapshots.load(Ordering::SeqCst), 0);
+    Ok(())
+}
+
+#[tokio::test]
+async fn sample_pinned_version() -> Result<()> {
+    let table = Arc::new(VersionedTable::new()?);
+    table.commit()?;
+    let ctx = SessionContext::new();
+    ctx.register_table("t", table.clone())?;
+    let state = ctx
+        .state()
+        .with_table_snapshot("t", SnapshotToken::new("0"));
+    let ctx = SessionContext::new_with_state(state);
+
+    let sql = "SELECT v FROM t TABLESAMPLE BERNOULLI (100)";
+    let results = ctx.sql(sql).await?.collect().await?;
+    let expected = ["+---+", "| v |", "+---+", "| 1 |", "+---+"];
+    assert_batches_eq!(expected, &results);
+    Ok(())
+}
+
+#[tokio::test]
+async fn scan_past_version() -> Result<()> {
+    let table = Arc::new(VersionedTable::new()?);
+    table.commit()?;
+    table.commit()?;
+    let ctx = SessionContext::new();
+    ctx.register_table("t", table.clone())?;
+
+    let sql = "SELECT max(v) AS a, (SELECT count(*) FROM t VERSION AS OF 1) AS b \
+               FROM t VERSION AS OF 0";
+    let results = ctx.sql(sql).await?.collect().await?;
+    let expected = [
+        "+---+---+",
+        "| a | b |",
+        "+---+---+",
+        "| 1 | 2 |",
+        "+---+---+",
+    ];
+    assert_batches_eq!(expected, &results);
+    // the current version is not resolved for the scans of past versions
+    assert_eq!(table.snapshots.load(Ordering::SeqCst), 0);
+
+    let err = ctx
+        .sql("SELECT * FROM t FOR SYSTEM_TIME AS OF '2024-01-01'")
+        .await?
+        .collect()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("Unsupported version"), "{err}");
+
+    // tables without versions can't be scanned at a version
+    ctx.register_table("m", table.version(0)?)?;
+    let err = ctx
+        .sql("SELECT * FROM m VERSION AS OF 0")
+        .await?
+        .collect()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("does not support"), "{err}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn scan_changes() -> Result<()> {
+    let table = Arc::new(VersionedTable::new()?);
+    table.commit()?;
+    table.commit()?;
+    let ctx = SessionContext::new();
+    ctx.register_table("t", table.clone())?;
+
+    let sql = "SELECT v, _change_type, count(*) AS n \
+               FROM table_changes('t', 0, 1 + 1) \
+               GROUP BY v, _change_type ORDER BY v, _change_type";
+    let results = ctx.sql(sql).await?.collect().await?;
+    let expected = [
+        "+---+--------------+---+",
+        "| v | _change_type | n |",
+        "+---+--------------+---+",
+        "| 1 | delete       | 1 |",
+        "| 2 | delete       | 2 |",
+        "| 2 | insert       | 2 |",
+        "| 3 | insert       | 3 |",
+        "+---+--------------+---+",
+    ];
+    assert_batches_eq!(expected, &results);
+    // the changes don't read the current version
+    assert_eq!(table.snapshots.load(Ordering::SeqCst), 0);
+
+    // up to the current version
+    let sql = "SELECT count(*) AS n FROM table_changes('t', 1)";
+    let results = ctx.sql(sql).await?.collect().await?;
+    let expected = ["+---+", "| n |", "+---+", "| 5 |", "+---+"];
+    assert_batches_eq!(expected, &results);
+
+    // tables without versions have no changes
+    ctx.register_table("m", table.version(0)?)?;
+    let err = ctx
+        .sql("SELECT * FROM table_changes('m', 0)")
+        .await?
+        .collect()
+        .await
+        .unwrap_err();
+    assert!(err.to_string().contains("does not support"), "{err}");
+    Ok(())
+}
+
+/// Only the rows whose value is 3 may be read
+struct ValuePolicy {}
+
+impl AccessPolicyProvider for ValuePolicy {
+    fn table_policy(
+        &self,
+        _identity: Option<&SessionIdentity>,
+        _table: &ResolvedTableReference,
+    ) -> Result<Option<TablePolicy>> {
+        Ok(Some(
+            TablePolicy::new().with_row_filter(col("v").eq(lit(3))),
+        ))
+    }
+}
+
+#[tokio::test]
+async fn scan_changes_with_policy() -> Result<()> {
+    let table = Arc::new(VersionedTable::new()?);
+    table.commit()?;
+    table.commit()?;
+    let ctx = SessionContext::new().with_access_policy_provider(Arc::new(ValuePolicy {}));
+    ctx.register_table("t", table.clone())?;
+
+    // the changes are filtered by the policy of the table
+    let sql = "SELECT v, _change_type, count(*) AS n \
+               FROM table_changes('t', 0, 2) \
+               GROUP BY v, _change_type ORDER BY v, _change_type";
+    let results = ctx.sql(sql).await?.collect().await?;
+    let expected = [
+        "+---+--------------+---+",
+        "| v | _change_type | n |",
+        "+---+--------------+---+",
+        "| 3 | insert       | 3 |",
+        "+---+--------------+---+",
+    ];
+    assert_batches_eq!(expected, &results);
+    Ok(())
+}
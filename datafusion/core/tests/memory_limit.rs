@@ -295,8 +295,10 @@ async fn sort_spill_reservation() {
     let config = base_config
         // reserve sufficient space up front for merge and this time,
         // which will force the spills to happen with less buffered
-        // input and thus with enough to merge.
-        .with_sort_spill_reservation_bytes(partition_size / 2);
+        // input and thus with enough to merge. The sort reserves the
+        // dictionary shared by both columns of a batch once, so it
+        // buffers more input than `partition_size` suggests.
+        .with_sort_spill_reservation_bytes(partition_size * 3 / 4);
 
     test.with_config(config).with_expected_success().run().await;
 }
@@ -0,0 +1,88 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Tests for the read audit events of parquet scans
+
+use std::sync::Mutex;
+
+use datafusion::execution::audit::{AuditSink, ReadAuditEvent};
+use datafusion::execution::context::SessionIdentity;
+use datafusion::execution::runtime_env::{RuntimeConfig, RuntimeEnv};
+use tempfile::TempDir;
+
+use super::*;
+
+#[derive(Debug, Default)]
+struct CollectingSink {
+    events: Mutex<Vec<ReadAuditEvent>>,
+}
+
+impl AuditSink for CollectingSink {
+    fn record_read(&self, event: ReadAuditEvent) {
+        self.events.lock().unwrap().push(event);
+    }
+}
+
+#[tokio::test]
+async fn audit_parquet_scan() {
+    let tmp_dir = TempDir::new().unwrap();
+    let path = tmp_dir.path().join("people.parquet");
+    let batch = RecordBatch::try_from_iter(vec![
+        ("id", Arc::new(Int32Array::from(vec![1, 2, 3])) as ArrayRef),
+        (
+            "ssn",
+            Arc::new(StringArray::from(vec!["111", "222", "333"])) as ArrayRef,
+        ),
+    ])
+    .unwrap();
+    let mut writer =
+        ArrowWriter::try_new(std::fs::File::create(&path).unwrap(), batch.schema(), None)
+            .unwrap();
+    writer.write(&batch).unwrap();
+    writer.close().unwrap();
+
+    let sink = Arc::new(CollectingSink::default());
+    let runtime =
+        RuntimeEnv::new(RuntimeConfig::new().with_audit_sink(sink.clone())).unwrap();
+    let config = SessionConfig::new().with_target_partitions(1);
+    let ctx = SessionContext::new_with_config_rt(config, Arc::new(runtime));
+    ctx.set_identity(SessionIdentity::new("alice"));
+    ctx.register_parquet("people", path.to_str().unwrap(), Default::default())
+        .await
+        .unwrap();
+
+    let batches = ctx
+        .sql("SELECT id FROM people WHERE id > 1")
+        .await
+        .unwrap()
+        .collect()
+        .await
+        .unwrap();
+    assert_eq!(batches.iter().map(|b| b.num_rows()).sum::<usize>(), 2);
+
+    let events = sink.events.lock().unwrap().clone();
+    assert_eq!(events.len(), 1, "{events:?}");
+    let event = &events[0];
+    assert_eq!(event.user.as_deref(), Some("alice"));
+    assert_eq!(event.table, "people");
+    assert_eq!(event.partition, 0);
+    assert_eq!(event.files.len(), 1);
+    assert!(event.files[0].ends_with("people.parquet"), "{event:?}");
+    assert_eq!(event.columns, vec!["id".to_string()]);
+    assert_eq!(event.filters, vec!["id > Int32(1)".to_string()]);
+    assert!(event.bytes_scanned > 0, "{event:?}");
+}
@@ -86,6 +86,7 @@ async fn route_data_access_ops_to_parquet_file_reader_factory() {
             table_partition_cols: vec![],
             output_ordering: vec![],
             column_hints: None,
+            listing_pruning: None,
         },
         None,
         None,
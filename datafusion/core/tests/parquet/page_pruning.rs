@@ -81,6 +81,7 @@ async fn get_parquet_exec(state: &SessionState, filter: Expr) -> ParquetExec {
             table_partition_cols: vec![],
             output_ordering: vec![],
             column_hints: None,
+            listing_pruning: None,
         },
         Some(predicate),
         None,
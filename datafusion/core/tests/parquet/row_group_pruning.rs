@@ -1323,3 +1323,22 @@ async fn test_row_group_with_null_values() {
         .test_row_group_prune()
         .await;
 }
+
+#[tokio::test]
+async fn test_pruning_metrics() {
+    let output = ContextWithParquet::new(Scenario::Int, RowGroup(5))
+        .await
+        .query("SELECT * FROM t where i8 = 1")
+        .await;
+
+    // The single file is kept, 1 of its 4 row groups of 5 rows is kept
+    // by its statistics and bloom filter
+    assert_eq!(output.metric_value("partitions_considered"), Some(0));
+    assert_eq!(output.metric_value("files_considered"), Some(1));
+    assert_eq!(output.metric_value("files_kept"), Some(1));
+    assert_eq!(output.metric_value("row_groups_considered"), Some(4));
+    assert_eq!(output.metric_value("row_groups_kept"), Some(1));
+    assert_eq!(output.metric_value("rows_considered"), Some(20));
+    assert_eq!(output.metric_value("rows_selected"), Some(5));
+    assert_eq!(output.result_rows, 1);
+}
@@ -21,7 +21,8 @@ use std::{collections::HashMap, fs, path::Path};
 use tempfile::TempDir;
 
 use super::*;
-use datafusion_common::assert_batches_sorted_eq;
+use datafusion::error::Result;
+use datafusion_common::{assert_batches_eq, assert_batches_sorted_eq};
 
 #[tokio::test]
 async fn schema_merge_ignores_metadata_by_default() {
@@ -177,6 +178,76 @@ fn make_meta(k: impl Into<String>, v: impl Into<String>) -> HashMap<String, Stri
     meta
 }
 
+#[tokio::test]
+async fn schema_merge_nested_fields() -> Result<()> {
+    use arrow::array::{ArrayRef, ListArray, StructArray};
+    use arrow::buffer::OffsetBuffer;
+
+    // the second file has more fields in its struct, and in the structs of
+    // its list
+    let tmp_dir = TempDir::new()?;
+    for (i, nested) in [vec!["a"], vec!["a", "b"]].into_iter().enumerate() {
+        let fields = nested
+            .iter()
+            .map(|name| Field::new(*name, DataType::Int32, true))
+            .collect::<Vec<_>>();
+        let columns = fields
+            .iter()
+            .map(|_| Arc::new(Int32Array::from(vec![i as i32])) as ArrayRef)
+            .collect::<Vec<_>>();
+        let s = StructArray::new(fields.into(), columns, None);
+        let l = ListArray::new(
+            Arc::new(Field::new("item", s.data_type().clone(), true)),
+            OffsetBuffer::from_lengths([1]),
+            Arc::new(s.clone()),
+            None,
+        );
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("id", DataType::Int32, true),
+            Field::new("s", s.data_type().clone(), true),
+            Field::new("l", l.data_type().clone(), true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from(vec![i as i32])),
+                Arc::new(s),
+                Arc::new(l),
+            ],
+        )?;
+        let file = fs::File::create(tmp_dir.path().join(format!("part-{i}.parquet")))?;
+        let mut writer = ArrowWriter::try_new(file, schema, None)?;
+        writer.write(&batch)?;
+        writer.close()?;
+    }
+    let table_path = tmp_dir.path().to_str().unwrap();
+
+    // the lists of different structs can't be merged by the format
+    let ctx = SessionContext::new();
+    let options = ParquetReadOptions::default();
+    assert!(ctx.read_parquet(table_path, options.clone()).await.is_err());
+
+    let config = SessionConfig::new()
+        .set_bool("datafusion.execution.listing_table_merge_schemas", true);
+    let ctx = SessionContext::new_with_config(config);
+    ctx.register_parquet("t", table_path, options).await?;
+    let actual = ctx
+        .sql("SELECT id, s['b'] AS b, l[1]['b'] AS l_b FROM t ORDER BY id")
+        .await?
+        .collect()
+        .await?;
+    let expected = [
+        "+----+---+-----+",
+        "| id | b | l_b |",
+        "+----+---+-----+",
+        "| 0  |   |     |",
+        "| 1  | 1 | 1   |",
+        "+----+---+-----+",
+    ];
+    assert_batches_eq!(expected, &actual);
+    Ok(())
+}
+
 /// Writes individual files with the specified schemas to temp_path)
 ///
 /// Assumes each schema has an int32 and a string column
@@ -70,6 +70,7 @@ async fn multi_parquet_coercion() {
             table_partition_cols: vec![],
             output_ordering: vec![],
             column_hints: None,
+            listing_pruning: None,
         },
         None,
         None,
@@ -135,6 +136,7 @@ async fn multi_parquet_coercion_projection() {
             table_partition_cols: vec![],
             output_ordering: vec![],
             column_hints: None,
+            listing_pruning: None,
         },
         None,
         None,
@@ -16,6 +16,7 @@
 // under the License.
 
 use datafusion::datasource::stream::{StreamConfig, StreamTable};
+use datafusion::logical_expr::{LogicalPlanBuilder, Operator};
 use datafusion::test_util::register_unbounded_file_with_ordering;
 
 use super::*;
@@ -231,3 +232,50 @@ async fn join_change_in_planner_without_sort_not_allowed() -> Result<()> {
     }
     Ok(())
 }
+
+#[tokio::test]
+async fn asof_join_from_logical_plan() -> Result<()> {
+    let ctx =
+        SessionContext::new_with_config(SessionConfig::new().with_target_partitions(4));
+    ctx.sql(
+        "CREATE TABLE trades(sym VARCHAR, ts BIGINT) AS VALUES
+        ('a', 5), ('a', 10), ('b', 7), ('c', 3)",
+    )
+    .await?
+    .collect()
+    .await?;
+    ctx.sql(
+        "CREATE TABLE quotes(sym VARCHAR, time BIGINT, price BIGINT) AS VALUES
+        ('a', 4, 1), ('a', 9, 2), ('a', 11, 3), ('b', 8, 4), ('c', 1, 5)",
+    )
+    .await?
+    .collect()
+    .await?;
+
+    let plan =
+        LogicalPlanBuilder::from(ctx.table("trades").await?.into_unoptimized_plan())
+            .asof_join(
+                ctx.table("quotes").await?.into_unoptimized_plan(),
+                (vec!["trades.sym"], vec!["quotes.sym"]),
+                (col("trades.ts"), Operator::GtEq, col("quotes.time")),
+            )?
+            .build()?;
+    let df = DataFrame::new(ctx.state(), plan);
+
+    let physical_plan = df.clone().create_physical_plan().await?;
+    let formatted = displayable(physical_plan.as_ref()).indent(true).to_string();
+    assert_contains!(formatted, "AsOfJoinExec");
+
+    let expected = [
+        "+-----+----+-----+------+-------+",
+        "| sym | ts | sym | time | price |",
+        "+-----+----+-----+------+-------+",
+        "| a   | 10 | a   | 9    | 2     |",
+        "| a   | 5  | a   | 4    | 1     |",
+        "| b   | 7  |     |      |       |",
+        "| c   | 3  | c   | 1    | 5     |",
+        "+-----+----+-----+------+-------+",
+    ];
+    assert_batches_sorted_eq!(expected, &df.collect().await?);
+    Ok(())
+}
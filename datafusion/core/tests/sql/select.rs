@@ -250,3 +250,39 @@ async fn test_parameter_invalid_types() -> Result<()> {
 );
     Ok(())
 }
+
+#[tokio::test]
+async fn scalar_subqueries_evaluated_once() -> Result<()> {
+    let config = SessionConfig::new()
+        .set_bool("datafusion.optimizer.evaluate_scalar_subqueries_once", true);
+    let ctx = SessionContext::new_with_config(config);
+    // the column is a BIGINT so that type coercion does not cast the result of
+    // the subquery compared with `a`, making it a different subquery
+    ctx.sql("CREATE TABLE t(a BIGINT) AS VALUES (1), (2), (3), (4)")
+        .await?
+        .collect()
+        .await?;
+
+    let dataframe = ctx
+        .sql("SELECT a, (SELECT max(a) FROM t) AS m FROM t WHERE a > (SELECT max(a) FROM t) - 2")
+        .await?;
+    let physical_plan = dataframe.clone().create_physical_plan().await?;
+    let formatted = displayable(physical_plan.as_ref()).indent(true).to_string();
+    // Both references share a single evaluation of the subquery, which is
+    // not joined with the outer query
+    assert_contains!(&formatted, "ScalarSubqueryExec: subqueries=1");
+    assert_eq!(formatted.matches("ScalarSubqueryExec").count(), 1);
+    assert_not_contains!(&formatted, "Join");
+
+    let results = dataframe.collect().await?;
+    let expected = vec![
+        "+---+---+",
+        "| a | m |",
+        "+---+---+",
+        "| 3 | 4 |",
+        "| 4 | 4 |",
+        "+---+---+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+    Ok(())
+}
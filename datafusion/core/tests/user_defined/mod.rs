@@ -29,3 +29,8 @@ mod user_defined_window_functions;
 
 /// Tests for User Defined Table Functions
 mod user_defined_table_functions;
+
+/// Tests for row filters and column masks of access policies
+mod user_defined_access_policy;
+
+mod user_defined_statement_rewriter;
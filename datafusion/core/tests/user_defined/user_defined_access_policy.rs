@@ -0,0 +1,297 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow::array::{Int32Array, StringArray};
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::Result;
+use datafusion::execution::context::{AccessPolicyProvider, SessionIdentity};
+use datafusion::prelude::SessionContext;
+use datafusion_common::{assert_batches_sorted_eq, ResolvedTableReference, ScalarValue};
+use datafusion_expr::{col, lit, TablePolicy};
+use std::sync::Arc;
+
+/// Users only see the rows of their tenant, and only admins see the
+/// social security numbers
+struct TenantPolicy {}
+
+impl AccessPolicyProvider for TenantPolicy {
+    fn table_policy(
+        &self,
+        identity: Option<&SessionIdentity>,
+        table: &ResolvedTableReference,
+    ) -> Result<Option<TablePolicy>> {
+        if table.table.as_ref() != "people" {
+            return Ok(None);
+        }
+        let Some(identity) = identity else {
+            // anonymous sessions see nothing
+            return Ok(Some(TablePolicy::new().with_row_filter(lit(false))));
+        };
+        let tenant = identity.user().trim_start_matches("tenant");
+        let mut policy = TablePolicy::new()
+            .with_row_filter(col("tenant").eq(lit(tenant.parse::<i32>().unwrap())));
+        if !identity.has_role("admin") {
+            policy = policy.with_column_mask("ssn", lit("***"));
+        }
+        Ok(Some(policy))
+    }
+}
+
+fn context() -> Result<SessionContext> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("tenant", DataType::Int32, false),
+        Field::new("name", DataType::Utf8, false),
+        Field::new("ssn", DataType::Utf8, false),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(Int32Array::from(vec![1, 1, 2])),
+            Arc::new(StringArray::from(vec!["alice", "bob", "carol"])),
+            Arc::new(StringArray::from(vec!["111", "222", "333"])),
+        ],
+    )?;
+    let ctx =
+        SessionContext::new().with_access_policy_provider(Arc::new(TenantPolicy {}));
+    ctx.register_batch("people", batch)?;
+    Ok(ctx)
+}
+
+#[tokio::test]
+async fn test_row_filter_and_column_mask() -> Result<()> {
+    let ctx = context()?;
+    ctx.set_identity(SessionIdentity::new("tenant1"));
+
+    let results = ctx.sql("SELECT * FROM people").await?.collect().await?;
+    let expected = [
+        "+--------+-------+-----+",
+        "| tenant | name  | ssn |",
+        "+--------+-------+-----+",
+        "| 1      | alice | *** |",
+        "| 1      | bob   | *** |",
+        "+--------+-------+-----+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+
+    // filtering on a masked column sees the masked values
+    let results = ctx
+        .sql("SELECT name FROM people WHERE ssn = '111' OR tenant = 2")
+        .await?
+        .collect()
+        .await?;
+    assert!(results.iter().all(|batch| batch.num_rows() == 0));
+
+    // the DataFrame API enforces the same policy
+    let results = ctx.table("people").await?.collect().await?;
+    assert_batches_sorted_eq!(expected, &results);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_policy_per_identity() -> Result<()> {
+    let ctx = context()?;
+    ctx.set_identity(SessionIdentity::new("tenant2").with_roles(["admin"]));
+
+    let results = ctx
+        .sql("SELECT p.name, p.ssn FROM people AS p")
+        .await?
+        .collect()
+        .await?;
+    let expected = [
+        "+-------+-----+",
+        "| name  | ssn |",
+        "+-------+-----+",
+        "| carol | 333 |",
+        "+-------+-----+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+
+    let anonymous = context()?;
+    let results = anonymous
+        .sql("SELECT count(*) AS n FROM people")
+        .await?
+        .collect()
+        .await?;
+    let expected = ["+---+", "| n |", "+---+", "| 0 |", "+---+"];
+    assert_batches_sorted_eq!(expected, &results);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_policy_with_table_sample() -> Result<()> {
+    let ctx = context()?;
+    ctx.set_identity(SessionIdentity::new("tenant1"));
+
+    // the sample is taken by the scan, below the policy
+    let results = ctx
+        .sql("SELECT name, ssn FROM people TABLESAMPLE BERNOULLI (100)")
+        .await?
+        .collect()
+        .await?;
+    let expected = [
+        "+-------+-----+",
+        "| name  | ssn |",
+        "+-------+-----+",
+        "| alice | *** |",
+        "| bob   | *** |",
+        "+-------+-----+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_analyze_table_with_policy() -> Result<()> {
+    let ctx = context()?;
+    ctx.set_identity(SessionIdentity::new("tenant1"));
+
+    let err = ctx.sql("ANALYZE TABLE people").await.unwrap_err();
+    assert!(err.to_string().contains("has an access policy"), "{err}");
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_copy_with_policy() -> Result<()> {
+    let ctx = context()?;
+    ctx.set_identity(SessionIdentity::new("tenant1"));
+
+    let dir = tempfile::tempdir()?;
+    let path = dir.path().join("people.parquet");
+    let path = path.to_str().unwrap();
+    ctx.sql(&format!("COPY people TO '{path}' STORED AS PARQUET"))
+        .await?
+        .collect()
+        .await?;
+
+    // only the rows and values the user may read are copied
+    let results = SessionContext::new()
+        .read_parquet(path, Default::default())
+        .await?
+        .collect()
+        .await?;
+    let expected = [
+        "+--------+-------+-----+",
+        "| tenant | name  | ssn |",
+        "+--------+-------+-----+",
+        "| 1      | alice | *** |",
+        "| 1      | bob   | *** |",
+        "+--------+-------+-----+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_dml_with_policy() -> Result<()> {
+    let ctx = context()?;
+    ctx.set_identity(SessionIdentity::new("tenant2").with_roles(["admin"]));
+
+    // only the rows of the tenant are deleted
+    let plan = ctx
+        .state()
+        .create_logical_plan("DELETE FROM people WHERE name <> 'carol'")
+        .await?;
+    let plan = format!("{}", plan.display_indent());
+    assert!(plan.contains("Filter: people.tenant = Int32(2)"), "{plan}");
+
+    let plan = ctx
+        .state()
+        .create_logical_plan("UPDATE people SET name = 'dave'")
+        .await?;
+    let plan = format!("{}", plan.display_indent());
+    assert!(plan.contains("Filter: people.tenant = Int32(2)"), "{plan}");
+
+    // the masked values can neither be read nor written back
+    ctx.set_identity(SessionIdentity::new("tenant1"));
+    for sql in [
+        "DELETE FROM people WHERE ssn = '111'",
+        "UPDATE people SET name = 'dave'",
+    ] {
+        let err = ctx.state().create_logical_plan(sql).await.unwrap_err();
+        assert!(
+            err.to_string().contains("its access policy masks columns"),
+            "{err}"
+        );
+    }
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_view_with_policy_of_reader() -> Result<()> {
+    let ctx = context()?;
+    ctx.set_identity(SessionIdentity::new("tenant2").with_roles(["admin"]));
+    ctx.sql("CREATE VIEW names AS SELECT name, ssn FROM people")
+        .await?
+        .collect()
+        .await?;
+
+    // the view reads the table with the policy of the user reading the view
+    ctx.set_identity(SessionIdentity::new("tenant1"));
+    let expected = [
+        "+-------+-----+",
+        "| name  | ssn |",
+        "+-------+-----+",
+        "| alice | *** |",
+        "| bob   | *** |",
+        "+-------+-----+",
+    ];
+    let results = ctx.sql("SELECT * FROM names").await?.collect().await?;
+    assert_batches_sorted_eq!(expected, &results);
+    let results = ctx.table("names").await?.collect().await?;
+    assert_batches_sorted_eq!(expected, &results);
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_prepared_statement_with_policy_of_executor() -> Result<()> {
+    let ctx = context()?;
+    ctx.set_identity(SessionIdentity::new("tenant2").with_roles(["admin"]));
+    let plan = ctx
+        .state()
+        .create_logical_plan(
+            "PREPARE names(INT) AS SELECT name, ssn FROM people WHERE tenant = $1",
+        )
+        .await?;
+
+    // the statement is executed with the policy of the current user
+    ctx.set_identity(SessionIdentity::new("tenant1"));
+    let results = ctx
+        .execute_logical_plan(plan.clone())
+        .await?
+        .with_param_values(vec![ScalarValue::from(1i32)])?
+        .collect()
+        .await?;
+    let expected = [
+        "+-------+-----+",
+        "| name  | ssn |",
+        "+-------+-----+",
+        "| alice | *** |",
+        "| bob   | *** |",
+        "+-------+-----+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+
+    let results = ctx
+        .execute_logical_plan(plan)
+        .await?
+        .with_param_values(vec![ScalarValue::from(2i32)])?
+        .collect()
+        .await?;
+    assert!(results.iter().all(|batch| batch.num_rows() == 0));
+    Ok(())
+}
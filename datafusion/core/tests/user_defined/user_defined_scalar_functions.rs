@@ -17,28 +17,33 @@
 
 use arrow::compute::kernels::numeric::add;
 use arrow_array::{
-    Array, ArrayRef, Float32Array, Float64Array, Int32Array, RecordBatch, UInt8Array,
+    Array, ArrayRef, Float32Array, Float64Array, Int32Array, Int64Array, RecordBatch,
+    UInt8Array,
 };
 use arrow_schema::DataType::Float64;
 use arrow_schema::{DataType, Field, Schema};
+use async_trait::async_trait;
 use datafusion::execution::context::{FunctionFactory, RegisterFunction, SessionState};
+use datafusion::physical_plan::{collect, displayable};
 use datafusion::prelude::*;
 use datafusion::{execution::registry::FunctionRegistry, test_util};
 use datafusion_common::tree_node::{Transformed, TreeNode};
 use datafusion_common::{
     assert_batches_eq, assert_batches_sorted_eq, cast::as_float64_array,
-    cast::as_int32_array, not_impl_err, plan_err, ExprSchema, Result, ScalarValue,
+    cast::as_int32_array, cast::as_int64_array, not_impl_err, plan_err, ExprSchema,
+    Result, ScalarValue,
 };
 use datafusion_common::{exec_err, internal_err, DataFusionError};
 use datafusion_execution::runtime_env::{RuntimeConfig, RuntimeEnv};
 use datafusion_expr::simplify::{ExprSimplifyResult, SimplifyInfo};
 use datafusion_expr::{
-    Accumulator, ColumnarValue, CreateFunction, ExprSchemable, LogicalPlanBuilder,
-    ScalarUDF, ScalarUDFImpl, Signature, Volatility,
+    Accumulator, AsyncScalarUDF, AsyncScalarUDFImpl, ColumnarValue, CreateFunction,
+    ExprSchemable, LogicalPlanBuilder, ScalarUDF, ScalarUDFImpl, Signature, Volatility,
 };
 use rand::{thread_rng, Rng};
 use std::any::Any;
 use std::iter;
+use std::sync::atomic::{AtomicUsize, Ordering};
 use std::sync::Arc;
 
 /// test that casting happens on udfs.
@@ -961,6 +966,131 @@ async fn create_scalar_function_from_sql_statement() -> Result<()> {
     Ok(())
 }
 
+/// Async function doubling its argument, invoked on at most two rows at a time
+#[derive(Debug)]
+struct AsyncDouble {
+    signature: Signature,
+    invocations: AtomicUsize,
+}
+
+impl AsyncDouble {
+    fn new() -> Self {
+        Self {
+            signature: Signature::exact(vec![DataType::Int64], Volatility::Volatile),
+            invocations: AtomicUsize::new(0),
+        }
+    }
+}
+
+impl ScalarUDFImpl for AsyncDouble {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "async_double"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Int64)
+    }
+
+    fn invoke(&self, _args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        internal_err!("async_double must be invoked asynchronously")
+    }
+}
+
+#[async_trait]
+impl AsyncScalarUDFImpl for AsyncDouble {
+    fn ideal_batch_size(&self) -> Option<usize> {
+        Some(2)
+    }
+
+    async fn invoke_async(
+        &self,
+        args: &[ColumnarValue],
+        num_rows: usize,
+    ) -> Result<ArrayRef> {
+        // Give way to other tasks, as a call to a remote service would
+        tokio::task::yield_now().await;
+        self.invocations.fetch_add(1, Ordering::Relaxed);
+        let values = args[0].clone().into_array(num_rows)?;
+        let doubled = as_int64_array(&values)?
+            .iter()
+            .map(|v| v.map(|v| v * 2))
+            .collect::<Int64Array>();
+        Ok(Arc::new(doubled))
+    }
+}
+
+#[tokio::test]
+async fn async_scalar_udf() -> Result<()> {
+    let ctx =
+        SessionContext::new_with_config(SessionConfig::new().with_target_partitions(1));
+    let batch = RecordBatch::try_from_iter(vec![(
+        "a",
+        Arc::new(Int64Array::from(vec![
+            Some(1),
+            Some(2),
+            None,
+            Some(4),
+            Some(5),
+        ])) as ArrayRef,
+    )])?;
+    ctx.register_batch("t", batch)?;
+    let async_double = Arc::new(AsyncDouble::new());
+    ctx.register_udf(AsyncScalarUDF::new(async_double.clone()).into_scalar_udf());
+
+    let sql = "SELECT a, async_double(a) + 1 AS b FROM t \
+               WHERE async_double(async_double(a)) > 4";
+    let plan = ctx.sql(sql).await?.create_physical_plan().await?;
+    let actual = displayable(plan.as_ref()).indent(false).to_string();
+    let expected = [
+        "ProjectionExec: expr=[a@0 as a, __async_fn_0@1 + 1 as b]",
+        "  AsyncFuncExec: async_expr=[async_double(a@0) as __async_fn_0]",
+        "    ProjectionExec: expr=[a@0 as a]",
+        "      CoalesceBatchesExec: target_batch_size=8192",
+        "        FilterExec: __async_fn_1@2 > 4",
+        "          AsyncFuncExec: async_expr=[async_double(a@0) as __async_fn_0, async_double(__async_fn_0@1) as __async_fn_1]",
+        "            MemoryExec: partitions=1, partition_sizes=[1]",
+    ];
+    assert_eq!(actual.trim().lines().collect::<Vec<_>>(), expected);
+
+    let result = collect(plan, ctx.task_ctx()).await?;
+    assert_batches_eq!(
+        &[
+            "+---+----+",
+            "| a | b  |",
+            "+---+----+",
+            "| 2 | 5  |",
+            "| 4 | 9  |",
+            "| 5 | 11 |",
+            "+---+----+",
+        ],
+        &result
+    );
+    // 5 rows, 2 rows per invocation: 3 invocations for each call of the
+    // filter, and 2 invocations for the 3 remaining rows of the projection
+    assert_eq!(async_double.invocations.load(Ordering::Relaxed), 8);
+
+    // Async functions can not be evaluated outside of AsyncFuncExec
+    let err = ctx
+        .sql("SELECT SUM(async_double(a)) FROM t")
+        .await?
+        .collect()
+        .await
+        .unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "This feature is not implemented: async_double is an async function and can only be evaluated by AsyncFuncExec"
+    );
+    Ok(())
+}
+
 fn create_udf_context() -> SessionContext {
     let ctx = SessionContext::new();
     // register a custom UDF
@@ -0,0 +1,160 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow::array::{Int32Array, StringArray};
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::arrow::record_batch::RecordBatch;
+use datafusion::error::Result;
+use datafusion::execution::context::{
+    SessionState, StatementKind, StatementMetadata, StatementRewriter,
+};
+use datafusion::prelude::SessionContext;
+use datafusion_common::tree_node::{Transformed, TreeNode};
+use datafusion_common::{assert_batches_sorted_eq, plan_err, TableReference};
+use datafusion_expr::{col, lit, LogicalPlan, LogicalPlanBuilder};
+use std::sync::{Arc, Mutex};
+
+/// Adds the filter of a tenant to the scans of the `people` table of the
+/// queries, and records the statements it saw
+#[derive(Default)]
+struct TenantFilter {
+    seen: Mutex<Vec<(StatementKind, bool, Vec<TableReference>)>>,
+}
+
+impl StatementRewriter for TenantFilter {
+    fn name(&self) -> &str {
+        "tenant_filter"
+    }
+
+    fn applies_to(&self, kind: StatementKind) -> bool {
+        kind == StatementKind::Query
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        statement: &StatementMetadata,
+        _state: &SessionState,
+    ) -> Result<LogicalPlan> {
+        self.seen.lock().unwrap().push((
+            statement.kind(),
+            statement.is_explain(),
+            statement.table_references().to_vec(),
+        ));
+        plan.transform_up(|plan| match &plan {
+            LogicalPlan::TableScan(scan) if scan.table_name.table() == "people" => {
+                let filtered = LogicalPlanBuilder::from(plan)
+                    .filter(col("tenant").eq(lit(1)))?
+                    .build()?;
+                Ok(Transformed::yes(filtered))
+            }
+            _ => Ok(Transformed::no(plan)),
+        })
+        .map(|transformed| transformed.data)
+    }
+}
+
+/// Rejects every statement
+struct Reject {}
+
+impl StatementRewriter for Reject {
+    fn name(&self) -> &str {
+        "reject"
+    }
+
+    fn rewrite(
+        &self,
+        _plan: LogicalPlan,
+        statement: &StatementMetadata,
+        _state: &SessionState,
+    ) -> Result<LogicalPlan> {
+        plan_err!("rejected {}", statement.sql())
+    }
+}
+
+fn context() -> Result<SessionContext> {
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("tenant", DataType::Int32, false),
+        Field::new("name", DataType::Utf8, false),
+    ]));
+    let batch = RecordBatch::try_new(
+        schema,
+        vec![
+            Arc::new(Int32Array::from(vec![1, 1, 2])),
+            Arc::new(StringArray::from(vec!["alice", "bob", "carol"])),
+        ],
+    )?;
+    let ctx = SessionContext::new();
+    ctx.register_batch("people", batch)?;
+    Ok(ctx)
+}
+
+#[tokio::test]
+async fn test_rewrite_queries() -> Result<()> {
+    let ctx = context()?;
+    let rewriter = Arc::new(TenantFilter::default());
+    ctx.add_statement_rewriter(rewriter.clone());
+
+    let results = ctx.sql("SELECT name FROM people").await?.collect().await?;
+    let expected = [
+        "+-------+",
+        "| name  |",
+        "+-------+",
+        "| alice |",
+        "| bob   |",
+        "+-------+",
+    ];
+    assert_batches_sorted_eq!(expected, &results);
+
+    // the explained queries are rewritten too
+    let results = ctx
+        .sql("EXPLAIN SELECT name FROM people")
+        .await?
+        .collect()
+        .await?;
+    let plan = arrow::util::pretty::pretty_format_batches(&results)?.to_string();
+    assert!(plan.contains("tenant = Int32(1)"), "{plan}");
+
+    // the other statements are not
+    ctx.sql("CREATE VIEW everyone AS SELECT name FROM people")
+        .await?;
+
+    let seen = rewriter.seen.lock().unwrap().clone();
+    let people = vec![TableReference::bare("people")];
+    assert_eq!(
+        seen,
+        [
+            (StatementKind::Query, false, people.clone()),
+            (StatementKind::Query, true, people),
+        ]
+    );
+    Ok(())
+}
+
+#[tokio::test]
+async fn test_rewriter_error() -> Result<()> {
+    let ctx = context()?;
+    ctx.add_statement_rewriter(Arc::new(Reject {}));
+
+    let err = ctx.sql("SELECT * FROM people").await.unwrap_err();
+    assert_eq!(
+        err.strip_backtrace(),
+        "Statement rewriter 'reject' failed\ncaused by\n\
+        Error during planning: rejected SELECT * FROM people"
+    );
+    Ok(())
+}
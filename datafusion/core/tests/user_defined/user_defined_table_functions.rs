@@ -19,18 +19,21 @@ use arrow::array::Int64Array;
 use arrow::csv::reader::Format;
 use arrow::csv::ReaderBuilder;
 use async_trait::async_trait;
-use datafusion::arrow::datatypes::SchemaRef;
+use datafusion::arrow::datatypes::{DataType, Field, Schema, SchemaRef};
 use datafusion::arrow::record_batch::RecordBatch;
-use datafusion::datasource::function::TableFunctionImpl;
+use datafusion::datasource::function::{LateralTableFunctionImpl, TableFunctionImpl};
+use datafusion::datasource::streaming::StreamingTable;
 use datafusion::datasource::TableProvider;
 use datafusion::error::Result;
 use datafusion::execution::context::SessionState;
-use datafusion::execution::TaskContext;
+use datafusion::execution::{SendableRecordBatchStream, TaskContext};
 use datafusion::physical_plan::memory::MemoryExec;
-use datafusion::physical_plan::{collect, ExecutionPlan};
-use datafusion::prelude::SessionContext;
-use datafusion_common::{assert_batches_eq, DFSchema, ScalarValue};
-use datafusion_expr::{EmptyRelation, Expr, LogicalPlan, Projection, TableType};
+use datafusion::physical_plan::stream::RecordBatchStreamAdapter;
+use datafusion::physical_plan::streaming::PartitionStream;
+use datafusion::physical_plan::{collect, displayable, ExecutionPlan};
+use datafusion::prelude::{SessionConfig, SessionContext};
+use datafusion_common::{assert_batches_eq, plan_err, DFSchema, ScalarValue};
+use datafusion_expr::{col, EmptyRelation, Expr, LogicalPlan, Projection, TableType};
 use std::fs::File;
 use std::io::Seek;
 use std::path::Path;
@@ -90,6 +93,185 @@ async fn test_simple_read_csv_udtf() -> Result<()> {
     Ok(())
 }
 
+/// test udtf returning an infinite stream
+#[tokio::test]
+async fn test_unbounded_udtf() -> Result<()> {
+    let config = SessionConfig::new().with_target_partitions(1);
+    let ctx = SessionContext::new_with_config(config);
+    ctx.register_udtf("series", Arc::new(SeriesFunc {}));
+
+    // the ordering declared by the table makes sorting the stream unnecessary
+    let df = ctx
+        .sql("SELECT value FROM series(5) ORDER BY value LIMIT 3")
+        .await?;
+    let plan = df.clone().create_physical_plan().await?;
+    let plan = displayable(plan.as_ref()).indent(false).to_string();
+    assert!(plan.contains("infinite_source=true"), "{plan}");
+    assert!(!plan.contains("SortExec"), "{plan}");
+
+    let rbs = df.collect().await?;
+    let expected = [
+        "+-------+",
+        "| value |",
+        "+-------+",
+        "| 5     |",
+        "| 6     |",
+        "| 7     |",
+        "+-------+",
+    ];
+    assert_batches_eq!(expected, &rbs);
+
+    // sorting the stream would never finish
+    let err = ctx
+        .sql("SELECT value FROM series(5) ORDER BY value DESC LIMIT 3")
+        .await?
+        .collect()
+        .await
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Cannot execute pipeline breaking queries"));
+
+    Ok(())
+}
+
+/// A table function returning the endless series of integers starting at
+/// its argument
+struct SeriesFunc {}
+
+impl TableFunctionImpl for SeriesFunc {
+    fn call(&self, args: &[Expr]) -> Result<Arc<dyn TableProvider>> {
+        let Some(Expr::Literal(ScalarValue::Int64(Some(start)))) = args.first() else {
+            return plan_err!("series expects a single integer argument");
+        };
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "value",
+            DataType::Int64,
+            false,
+        )]));
+        let partition = SeriesPartition {
+            schema: schema.clone(),
+            start: *start,
+        };
+        let table = StreamingTable::try_new(schema, vec![Arc::new(partition)])?
+            .with_infinite_table(true)
+            .with_sort_order(vec![vec![col("value").sort(true, false)]]);
+        Ok(Arc::new(table))
+    }
+}
+
+struct SeriesPartition {
+    schema: SchemaRef,
+    start: i64,
+}
+
+impl PartitionStream for SeriesPartition {
+    fn schema(&self) -> &SchemaRef {
+        &self.schema
+    }
+
+    fn execute(&self, _ctx: Arc<TaskContext>) -> SendableRecordBatchStream {
+        let schema = self.schema.clone();
+        let batches =
+            (self.start..)
+                .step_by(2)
+                .map(move |start| -> Result<RecordBatch> {
+                    let values = Int64Array::from(vec![start, start + 1]);
+                    Ok(RecordBatch::try_new(
+                        schema.clone(),
+                        vec![Arc::new(values)],
+                    )?)
+                });
+        Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            futures::stream::iter(batches),
+        ))
+    }
+}
+
+/// test udtf whose arguments reference the columns of a preceding table
+#[tokio::test]
+async fn test_lateral_udtf() -> Result<()> {
+    let ctx = SessionContext::new();
+    ctx.register_lateral_udtf("generate", Arc::new(GenerateFunc {}));
+    ctx.sql("CREATE TABLE t AS VALUES (1, 2), (2, 0), (3, 1), (4, NULL)")
+        .await?
+        .collect()
+        .await?;
+
+    let expected = [
+        "+---------+---+",
+        "| column1 | i |",
+        "+---------+---+",
+        "| 1       | 1 |",
+        "| 1       | 2 |",
+        "| 3       | 1 |",
+        "+---------+---+",
+    ];
+    for sql in [
+        "SELECT t.column1, g.i FROM t CROSS JOIN LATERAL generate(t.column2) AS g \
+         ORDER BY t.column1, g.i",
+        "SELECT t.column1, generate.i FROM t, LATERAL generate(column2) \
+         ORDER BY t.column1, generate.i",
+    ] {
+        let rbs = ctx.sql(sql).await?.collect().await?;
+        assert_batches_eq!(expected, &rbs);
+    }
+
+    // column aliases and expressions as arguments
+    let rbs = ctx
+        .sql(
+            "SELECT x FROM t CROSS JOIN LATERAL generate(t.column1 + 1) AS g(x) \
+             WHERE t.column1 = 2",
+        )
+        .await?
+        .collect()
+        .await?;
+    let expected = [
+        "+---+", "| x |", "+---+", "| 1 |", "| 2 |", "| 3 |", "+---+",
+    ];
+    assert_batches_eq!(expected, &rbs);
+
+    let err = ctx
+        .sql("SELECT * FROM t LEFT JOIN LATERAL generate(t.column2) ON true")
+        .await
+        .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Unsupported JOIN operator LeftOuter"));
+
+    Ok(())
+}
+
+/// A lateral table function returning a row for each of `1..=n`
+#[derive(Debug)]
+struct GenerateFunc {}
+
+impl LateralTableFunctionImpl for GenerateFunc {
+    fn return_schema(&self, arg_types: &[DataType]) -> Result<SchemaRef> {
+        if arg_types != [DataType::Int64] {
+            return plan_err!("generate expects a single Int64 argument");
+        }
+        Ok(Arc::new(Schema::new(vec![Field::new(
+            "i",
+            DataType::Int64,
+            false,
+        )])))
+    }
+
+    fn call_row(&self, args: &[ScalarValue]) -> Result<RecordBatch> {
+        let n = match &args[0] {
+            ScalarValue::Int64(n) => n.unwrap_or_default(),
+            _ => unreachable!(),
+        };
+        let schema = self.return_schema(&[DataType::Int64])?;
+        Ok(RecordBatch::try_new(
+            schema,
+            vec![Arc::new(Int64Array::from_iter_values(1..=n))],
+        )?)
+    }
+}
+
 struct SimpleCsvTable {
     schema: SchemaRef,
     exprs: Vec<Expr>,
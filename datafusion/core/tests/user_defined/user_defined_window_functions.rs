@@ -29,8 +29,11 @@ use std::{
 
 use arrow::array::AsArray;
 use arrow_array::{ArrayRef, Int64Array, RecordBatch, StringArray};
-use arrow_schema::DataType;
-use datafusion::{assert_batches_eq, prelude::SessionContext};
+use arrow_schema::{DataType, Field, Schema};
+use datafusion::assert_batches_eq;
+use datafusion::datasource::MemTable;
+use datafusion::physical_plan::displayable;
+use datafusion::prelude::{col, SessionConfig, SessionContext};
 use datafusion_common::{Result, ScalarValue};
 use datafusion_expr::{
     PartitionEvaluator, Signature, Volatility, WindowUDF, WindowUDFImpl,
@@ -51,6 +54,11 @@ const BOUNDED_WINDOW_QUERY:  &str  =
      odd_counter(val) OVER (PARTITION BY x ORDER BY y ROWS BETWEEN 1 PRECEDING AND 1 FOLLOWING) \
      from t ORDER BY x, y";
 
+/// A query with a window function ordered by a column with duplicate values
+const PEERS_WINDOW_QUERY: &str = "SELECT x, val, \
+     odd_counter(val) OVER (PARTITION BY x ORDER BY val) \
+     from t ORDER BY x, val";
+
 /// Test to show the contents of the setup
 #[tokio::test]
 async fn test_setup() {
@@ -376,6 +384,136 @@ async fn test_udwf_bounded_window_returns_null() {
     assert_eq!(test_state.evaluate_all_called(), 0);
 }
 
+/// user defined window function using the peer groups of the rows
+#[tokio::test]
+async fn test_udwf_include_peers() {
+    let test_state = TestState::new().with_include_peers();
+    let TestContext { ctx, test_state } = TestContext::new(test_state);
+
+    let expected = vec![
+    "+---+-----+-------------------------------------------------------------------------------------------------------------------------+",
+    "| x | val | odd_counter(t.val) PARTITION BY [t.x] ORDER BY [t.val ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW |",
+    "+---+-----+-------------------------------------------------------------------------------------------------------------------------+",
+    "| 1 | 0   | 1                                                                                                                       |",
+    "| 1 | 1   | 1                                                                                                                       |",
+    "| 1 | 2   | 1                                                                                                                       |",
+    "| 2 | 3   | 1                                                                                                                       |",
+    "| 2 | 4   | 1                                                                                                                       |",
+    "| 2 | 5   | 1                                                                                                                       |",
+    "| 2 | 6   | 4                                                                                                                       |",
+    "| 2 | 6   | 4                                                                                                                       |",
+    "| 2 | 6   | 4                                                                                                                       |",
+    "| 2 | 6   | 4                                                                                                                       |",
+    "+---+-----+-------------------------------------------------------------------------------------------------------------------------+",
+    ];
+    assert_batches_eq!(expected, &execute(&ctx, PEERS_WINDOW_QUERY).await.unwrap());
+    // evaluated for each input row
+    assert_eq!(test_state.evaluate_with_peers_called(), 10);
+    assert_eq!(test_state.evaluate_called(), 0);
+    assert_eq!(test_state.evaluate_all_called(), 0);
+}
+
+/// user defined window function using the peer groups of the rows, evaluated
+/// incrementally
+#[tokio::test]
+async fn test_udwf_bounded_include_peers() {
+    let test_state = TestState::new()
+        .with_include_peers()
+        .with_supports_bounded_execution();
+    let TestContext { ctx, test_state } = TestContext::new(test_state);
+
+    let expected = vec![
+    "+---+-----+-------------------------------------------------------------------------------------------------------------------------+",
+    "| x | val | odd_counter(t.val) PARTITION BY [t.x] ORDER BY [t.val ASC NULLS LAST] RANGE BETWEEN UNBOUNDED PRECEDING AND CURRENT ROW |",
+    "+---+-----+-------------------------------------------------------------------------------------------------------------------------+",
+    "| 1 | 0   | 1                                                                                                                       |",
+    "| 1 | 1   | 1                                                                                                                       |",
+    "| 1 | 2   | 1                                                                                                                       |",
+    "| 2 | 3   | 1                                                                                                                       |",
+    "| 2 | 4   | 1                                                                                                                       |",
+    "| 2 | 5   | 1                                                                                                                       |",
+    "| 2 | 6   | 4                                                                                                                       |",
+    "| 2 | 6   | 4                                                                                                                       |",
+    "| 2 | 6   | 4                                                                                                                       |",
+    "| 2 | 6   | 4                                                                                                                       |",
+    "+---+-----+-------------------------------------------------------------------------------------------------------------------------+",
+    ];
+    assert_batches_eq!(expected, &execute(&ctx, PEERS_WINDOW_QUERY).await.unwrap());
+    // evaluated once for each input row
+    assert_eq!(test_state.evaluate_with_peers_called(), 10);
+    assert_eq!(test_state.evaluate_called(), 0);
+    assert_eq!(test_state.evaluate_all_called(), 0);
+}
+
+/// user defined window function using the peer groups of the rows, evaluated
+/// incrementally over several batches, a peer group spanning the batches while
+/// the rows before it are pruned
+#[tokio::test]
+async fn test_udwf_bounded_include_peers_across_batches() {
+    let test_state = Arc::new(
+        TestState::new()
+            .with_include_peers()
+            .with_uses_window_frame()
+            .with_supports_bounded_execution(),
+    );
+    let schema = Arc::new(Schema::new(vec![
+        Field::new("x", DataType::Int64, false),
+        Field::new("val", DataType::Int64, false),
+    ]));
+    let batches = [vec![0, 1, 1], vec![1, 2], vec![2, 2, 3]]
+        .into_iter()
+        .map(|val| {
+            let x = Int64Array::from(vec![1; val.len()]);
+            let val = Int64Array::from(val);
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(x), Arc::new(val)])
+        })
+        .collect::<std::result::Result<Vec<_>, _>>()
+        .unwrap();
+    // the batches are streamed in order, without being sorted again
+    let table = MemTable::try_new(schema, vec![batches])
+        .unwrap()
+        .with_sort_order(vec![vec![
+            col("x").sort(true, false),
+            col("val").sort(true, false),
+        ]]);
+    let mut ctx =
+        SessionContext::new_with_config(SessionConfig::new().with_target_partitions(1));
+    ctx.register_table("t", Arc::new(table)).unwrap();
+    OddCounter::register(&mut ctx, Arc::clone(&test_state));
+
+    let sql = "SELECT x, val, \
+        odd_counter(val) OVER (PARTITION BY x ORDER BY val ROWS BETWEEN CURRENT ROW AND 1 FOLLOWING) \
+        from t";
+    let plan = ctx
+        .sql(sql)
+        .await
+        .unwrap()
+        .create_physical_plan()
+        .await
+        .unwrap();
+    let formatted = displayable(plan.as_ref()).indent(true).to_string();
+    assert!(formatted.contains("BoundedWindowAggExec"), "{formatted}");
+    assert!(!formatted.contains("SortExec"), "{formatted}");
+
+    // the value is the size of the peer group of each row
+    let expected = vec![
+    "+---+-----+----------------------------------------------------------------------------------------------------------------+",
+    "| x | val | odd_counter(t.val) PARTITION BY [t.x] ORDER BY [t.val ASC NULLS LAST] ROWS BETWEEN CURRENT ROW AND 1 FOLLOWING |",
+    "+---+-----+----------------------------------------------------------------------------------------------------------------+",
+    "| 1 | 0   | 1                                                                                                              |",
+    "| 1 | 1   | 3                                                                                                              |",
+    "| 1 | 1   | 3                                                                                                              |",
+    "| 1 | 1   | 3                                                                                                              |",
+    "| 1 | 2   | 3                                                                                                              |",
+    "| 1 | 2   | 3                                                                                                              |",
+    "| 1 | 2   | 3                                                                                                              |",
+    "| 1 | 3   | 1                                                                                                              |",
+    "+---+-----+----------------------------------------------------------------------------------------------------------------+",
+    ];
+    assert_batches_eq!(expected, &execute(&ctx, sql).await.unwrap());
+    assert_eq!(test_state.evaluate_with_peers_called(), 8);
+}
+
 async fn execute(ctx: &SessionContext, sql: &str) -> Result<Vec<RecordBatch>> {
     ctx.sql(sql).await?.collect().await
 }
@@ -436,12 +574,16 @@ struct TestState {
     evaluate_called: AtomicUsize,
     /// How many times was `evaluate_all_with_rank` called?
     evaluate_all_with_rank_called: AtomicUsize,
+    /// How many times was `evaluate_with_peers` called?
+    evaluate_with_peers_called: AtomicUsize,
     /// should the functions say they use the window frame?
     uses_window_frame: bool,
     /// should the functions say they support bounded execution
     supports_bounded_execution: bool,
     /// should the functions they need include rank
     include_rank: bool,
+    /// should the functions say they need peer groups
+    include_peers: bool,
     /// should the functions return NULL for 0s?
     null_for_zero: bool,
 }
@@ -469,6 +611,12 @@ impl TestState {
         self
     }
 
+    /// Set that this function should include peer groups
+    fn with_include_peers(mut self) -> Self {
+        self.include_peers = true;
+        self
+    }
+
     // Set that this function should return NULL instead of zero.
     fn with_null_for_zero(mut self) -> Self {
         self.null_for_zero = true;
@@ -505,6 +653,17 @@ impl TestState {
         self.evaluate_all_with_rank_called
             .fetch_add(1, Ordering::SeqCst);
     }
+
+    /// return the evaluate_with_peers_called counter
+    fn evaluate_with_peers_called(&self) -> usize {
+        self.evaluate_with_peers_called.load(Ordering::SeqCst)
+    }
+
+    /// update the evaluate_with_peers_called counter
+    fn inc_evaluate_with_peers_called(&self) {
+        self.evaluate_with_peers_called
+            .fetch_add(1, Ordering::SeqCst);
+    }
 }
 
 // Partition Evaluator that counts the number of odd numbers in the window frame using evaluate
@@ -620,6 +779,17 @@ impl PartitionEvaluator for OddCounter {
         Ok(Arc::new(array))
     }
 
+    fn evaluate_with_peers(
+        &mut self,
+        _values: &[ArrayRef],
+        _range: &Range<usize>,
+        peers: &Range<usize>,
+    ) -> Result<ScalarValue> {
+        self.test_state.inc_evaluate_with_peers_called();
+        // when evaluating with peers, just return the size of the peer group
+        Ok(ScalarValue::Int64(Some(peers.len() as i64)))
+    }
+
     fn supports_bounded_execution(&self) -> bool {
         self.test_state.supports_bounded_execution
     }
@@ -631,6 +801,10 @@ impl PartitionEvaluator for OddCounter {
     fn include_rank(&self) -> bool {
         self.test_state.include_rank
     }
+
+    fn include_peers(&self) -> bool {
+        self.test_state.include_peers
+    }
 }
 
 /// returns the number of entries in arr that are odd
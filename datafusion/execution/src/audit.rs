@@ -0,0 +1,57 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Audit events recording what the queries read
+
+use std::fmt::Debug;
+
+/// Records which data a partition of a table scan read.
+///
+/// One event is recorded per executed partition of the scan, when its stream
+/// is finished or dropped, so a scan interrupted by a `LIMIT` is audited too.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ReadAuditEvent {
+    /// The user on whose behalf the table was read, if the session has one
+    pub user: Option<String>,
+    /// The table, as referred to by the query
+    pub table: String,
+    /// The partition of the scan
+    pub partition: usize,
+    /// The files read by the partition, empty for tables that are not
+    /// backed by files
+    pub files: Vec<String>,
+    /// The columns read, including the paths of the nested fields
+    /// (`address.city`) when the scan reads only some of them
+    pub columns: Vec<String>,
+    /// The filters pushed down to the scan
+    pub filters: Vec<String>,
+    /// The number of bytes the partition read from storage, as reported by
+    /// the scan (`0` if it does not report it)
+    pub bytes_scanned: usize,
+}
+
+/// Receives the audit events of the table scans.
+///
+/// Registered with
+/// [`RuntimeConfig::with_audit_sink`](crate::runtime_env::RuntimeConfig::with_audit_sink),
+/// so that compliance reporting can tell who read which, possibly nested,
+/// fields. Events are recorded from the execution threads, so implementations
+/// should hand them off quickly, e.g. to a channel.
+pub trait AuditSink: Debug + Send + Sync {
+    /// Record that a partition of a table scan read data
+    fn record_read(&self, event: ReadAuditEvent);
+}
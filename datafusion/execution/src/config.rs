@@ -23,7 +23,7 @@ use std::{
 };
 
 use datafusion_common::{
-    config::{ConfigExtension, ConfigOptions},
+    config::{ConfigExtension, ConfigOptions, ConfigProfile},
     Result, ScalarValue,
 };
 
@@ -182,6 +182,26 @@ impl SessionConfig {
         self
     }
 
+    /// Set the options of the [`ConfigProfile`] `profile`
+    ///
+    /// ```
+    /// use datafusion_common::config::ConfigProfile;
+    /// use datafusion_execution::config::SessionConfig;
+    ///
+    /// let config = SessionConfig::new().with_profile(ConfigProfile::LowMemory);
+    /// assert!(!config.options().optimizer.prefer_hash_join);
+    /// ```
+    pub fn with_profile(mut self, profile: ConfigProfile) -> Self {
+        self.options.apply_profile(profile);
+        self
+    }
+
+    /// Check that the options are valid and consistent with each other, see
+    /// [`ConfigOptions::validate`]
+    pub fn validate(&self) -> Result<()> {
+        self.options.validate()
+    }
+
     /// Customize batch size
     pub fn with_batch_size(mut self, n: usize) -> Self {
         // batch size must be greater than zero
@@ -563,6 +583,18 @@ impl SessionConfig {
             .cloned()
             .map(|ext| Arc::downcast(ext).expect("TypeId unique"))
     }
+
+    /// Remove the extension for the specified type `T`, returning it if it
+    /// existed.
+    pub fn remove_extension<T>(&mut self) -> Option<Arc<T>>
+    where
+        T: Send + Sync + 'static,
+    {
+        let id = TypeId::of::<T>();
+        self.extensions
+            .remove(&id)
+            .map(|ext| Arc::downcast(ext).expect("TypeId unique"))
+    }
 }
 
 impl From<ConfigOptions> for SessionConfig {
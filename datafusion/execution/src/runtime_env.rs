@@ -19,6 +19,7 @@
 //! store, memory manager, disk manager.
 
 use crate::{
+    audit::AuditSink,
     disk_manager::{DiskManager, DiskManagerConfig},
     memory_pool::{GreedyMemoryPool, MemoryPool, UnboundedMemoryPool},
     object_store::{DefaultObjectStoreRegistry, ObjectStoreRegistry},
@@ -43,6 +44,7 @@ use url::Url;
 /// * [`DiskManager`]: Manage temporary files on local disk
 /// * [`CacheManager`]: Manage temporary cache data during the session lifetime
 /// * [`ObjectStoreRegistry`]: Manage mapping URLs to object store instances
+/// * [`AuditSink`]: Record what the table scans read
 pub struct RuntimeEnv {
     /// Runtime memory management
     pub memory_pool: Arc<dyn MemoryPool>,
@@ -52,6 +54,8 @@ pub struct RuntimeEnv {
     pub cache_manager: Arc<CacheManager>,
     /// Object Store Registry
     pub object_store_registry: Arc<dyn ObjectStoreRegistry>,
+    /// Receives the audit events of the table scans, if any
+    pub audit_sink: Option<Arc<dyn AuditSink>>,
 }
 
 impl Debug for RuntimeEnv {
@@ -68,6 +72,7 @@ impl RuntimeEnv {
             disk_manager,
             cache_manager,
             object_store_registry,
+            audit_sink,
         } = config;
 
         let memory_pool =
@@ -78,6 +83,7 @@ impl RuntimeEnv {
             disk_manager: DiskManager::try_new(disk_manager)?,
             cache_manager: CacheManager::try_new(&cache_manager)?,
             object_store_registry,
+            audit_sink,
         })
     }
 
@@ -126,6 +132,8 @@ pub struct RuntimeConfig {
     pub cache_manager: CacheManagerConfig,
     /// ObjectStoreRegistry to get object store based on url
     pub object_store_registry: Arc<dyn ObjectStoreRegistry>,
+    /// AuditSink to receive the audit events of the table scans
+    pub audit_sink: Option<Arc<dyn AuditSink>>,
 }
 
 impl Default for RuntimeConfig {
@@ -142,6 +150,7 @@ impl RuntimeConfig {
             memory_pool: Default::default(),
             cache_manager: Default::default(),
             object_store_registry: Arc::new(DefaultObjectStoreRegistry::default()),
+            audit_sink: None,
         }
     }
 
@@ -172,6 +181,12 @@ impl RuntimeConfig {
         self
     }
 
+    /// Customize audit sink
+    pub fn with_audit_sink(mut self, audit_sink: Arc<dyn AuditSink>) -> Self {
+        self.audit_sink = Some(audit_sink);
+        self
+    }
+
     /// Specify the total memory to use while running the DataFusion
     /// plan to `max_memory * memory_fraction` in bytes.
     ///
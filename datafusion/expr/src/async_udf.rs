@@ -0,0 +1,130 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`AsyncScalarUDF`]: scalar functions evaluated asynchronously
+
+use crate::{ColumnarValue, ScalarUDF, ScalarUDFImpl, Signature, Volatility};
+use arrow::array::ArrayRef;
+use arrow::datatypes::DataType;
+use async_trait::async_trait;
+use datafusion_common::{not_impl_err, Result};
+use std::any::Any;
+use std::fmt::Debug;
+use std::sync::Arc;
+
+/// A scalar function whose evaluation awaits, e.g. a call to a remote
+/// service.
+///
+/// Calling `.await` from [`ScalarUDFImpl::invoke`] is not possible, and
+/// blocking would stall a thread of the runtime. Functions implementing this
+/// trait are instead wrapped in an [`AsyncScalarUDF`], whose calls are planned
+/// in a dedicated `AsyncFuncExec` operator that invokes them on batches of
+/// rows, with a bounded number of invocations in flight (see the
+/// `datafusion.execution.async_udf_concurrency` configuration option).
+///
+/// [`ScalarUDFImpl::invoke`] of implementations is never called, and should
+/// return an error.
+#[async_trait]
+pub trait AsyncScalarUDFImpl: ScalarUDFImpl {
+    /// Returns the maximum number of rows to pass to a single call of
+    /// [`Self::invoke_async`], or `None` to pass whole batches
+    fn ideal_batch_size(&self) -> Option<usize> {
+        None
+    }
+
+    /// Invoke the function on `num_rows` rows of `args`, returning an array of
+    /// `num_rows` results
+    async fn invoke_async(
+        &self,
+        args: &[ColumnarValue],
+        num_rows: usize,
+    ) -> Result<ArrayRef>;
+}
+
+/// A [`ScalarUDFImpl`] for an [`AsyncScalarUDFImpl`]
+///
+/// Async functions are always [`Volatility::Volatile`], so that they are not
+/// evaluated while planning.
+#[derive(Debug)]
+pub struct AsyncScalarUDF {
+    inner: Arc<dyn AsyncScalarUDFImpl>,
+    signature: Signature,
+}
+
+impl AsyncScalarUDF {
+    /// Create a new [`AsyncScalarUDF`] for `inner`
+    pub fn new(inner: Arc<dyn AsyncScalarUDFImpl>) -> Self {
+        let signature = Signature::new(
+            inner.signature().type_signature.clone(),
+            Volatility::Volatile,
+        );
+        Self { inner, signature }
+    }
+
+    /// Returns the [`ScalarUDF`] to register this function with
+    pub fn into_scalar_udf(self) -> ScalarUDF {
+        ScalarUDF::new_from_impl(self)
+    }
+
+    /// Returns the wrapped [`AsyncScalarUDFImpl`]
+    pub fn inner(&self) -> &Arc<dyn AsyncScalarUDFImpl> {
+        &self.inner
+    }
+
+    /// See [`AsyncScalarUDFImpl::ideal_batch_size`]
+    pub fn ideal_batch_size(&self) -> Option<usize> {
+        self.inner.ideal_batch_size()
+    }
+
+    /// See [`AsyncScalarUDFImpl::invoke_async`]
+    pub async fn invoke_async(
+        &self,
+        args: &[ColumnarValue],
+        num_rows: usize,
+    ) -> Result<ArrayRef> {
+        self.inner.invoke_async(args, num_rows).await
+    }
+}
+
+impl ScalarUDFImpl for AsyncScalarUDF {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        self.inner.return_type(arg_types)
+    }
+
+    fn invoke(&self, _args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        not_impl_err!(
+            "{} is an async function and can only be evaluated by AsyncFuncExec",
+            self.name()
+        )
+    }
+
+    fn aliases(&self) -> &[String] {
+        self.inner.aliases()
+    }
+}
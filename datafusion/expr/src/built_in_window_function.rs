@@ -68,6 +68,10 @@ pub enum BuiltInWindowFunction {
     LastValue,
     /// returns value evaluated at the row that is the nth row of the window frame (counting from 1); null if no such row
     NthValue,
+    /// number of the session of the current row within its partition, counting from 1;
+    /// a new session starts when the first argument exceeds its value in the previous
+    /// row by more than the gap given as second argument
+    SessionWindow,
 }
 
 impl BuiltInWindowFunction {
@@ -85,6 +89,7 @@ impl BuiltInWindowFunction {
             FirstValue => "FIRST_VALUE",
             LastValue => "LAST_VALUE",
             NthValue => "NTH_VALUE",
+            SessionWindow => "SESSION_WINDOW",
         }
     }
 }
@@ -104,6 +109,7 @@ impl FromStr for BuiltInWindowFunction {
             "FIRST_VALUE" => BuiltInWindowFunction::FirstValue,
             "LAST_VALUE" => BuiltInWindowFunction::LastValue,
             "NTH_VALUE" => BuiltInWindowFunction::NthValue,
+            "SESSION_WINDOW" => BuiltInWindowFunction::SessionWindow,
             _ => return plan_err!("There is no built-in window function named {name}"),
         })
     }
@@ -134,7 +140,8 @@ impl BuiltInWindowFunction {
             BuiltInWindowFunction::RowNumber
             | BuiltInWindowFunction::Rank
             | BuiltInWindowFunction::DenseRank
-            | BuiltInWindowFunction::Ntile => Ok(DataType::UInt64),
+            | BuiltInWindowFunction::Ntile
+            | BuiltInWindowFunction::SessionWindow => Ok(DataType::UInt64),
             BuiltInWindowFunction::PercentRank | BuiltInWindowFunction::CumeDist => {
                 Ok(DataType::Float64)
             }
@@ -182,7 +189,9 @@ impl BuiltInWindowFunction {
                 ],
                 Volatility::Immutable,
             ),
-            BuiltInWindowFunction::NthValue => Signature::any(2, Volatility::Immutable),
+            BuiltInWindowFunction::NthValue | BuiltInWindowFunction::SessionWindow => {
+                Signature::any(2, Volatility::Immutable)
+            }
         }
     }
 }
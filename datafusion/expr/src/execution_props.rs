@@ -15,11 +15,13 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::logical_plan::Subquery;
 use crate::var_provider::{VarProvider, VarType};
 use chrono::{DateTime, TimeZone, Utc};
 use datafusion_common::alias::AliasGenerator;
+use datafusion_common::{internal_err, Result, ScalarValue};
 use std::collections::HashMap;
-use std::sync::Arc;
+use std::sync::{Arc, OnceLock};
 
 /// Holds per-query execution properties and data (such as statement
 /// starting timestamps).
@@ -37,6 +39,9 @@ pub struct ExecutionProps {
     pub alias_generator: Arc<AliasGenerator>,
     /// Providers for scalar variables
     pub var_providers: Option<HashMap<VarType, Arc<dyn VarProvider + Send + Sync>>>,
+    /// Results of the uncorrelated scalar subqueries evaluated once before
+    /// the query runs, instead of being joined with their outer query
+    pub scalar_subquery_results: HashMap<Subquery, ScalarSubqueryResult>,
 }
 
 impl Default for ExecutionProps {
@@ -54,6 +59,7 @@ impl ExecutionProps {
             query_execution_start_time: Utc.timestamp_nanos(0),
             alias_generator: Arc::new(AliasGenerator::new()),
             var_providers: None,
+            scalar_subquery_results: HashMap::new(),
         }
     }
 
@@ -101,12 +107,50 @@ impl ExecutionProps {
     }
 }
 
+/// The value of an uncorrelated scalar subquery, computed once before the
+/// query runs and shared by all the physical expressions referencing the
+/// subquery
+#[derive(Clone, Debug, Default)]
+pub struct ScalarSubqueryResult {
+    value: Arc<OnceLock<ScalarValue>>,
+}
+
+impl ScalarSubqueryResult {
+    /// Creates a result that is not computed yet
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the value of the subquery, if it was computed
+    pub fn value(&self) -> Option<&ScalarValue> {
+        self.value.get()
+    }
+
+    /// Sets the value of the subquery. Setting it again is only allowed
+    /// with the same value, e.g. when a plan is executed again
+    pub fn set_value(&self, value: ScalarValue) -> Result<()> {
+        match self.value.set(value) {
+            Ok(()) => Ok(()),
+            Err(value) if Some(&value) == self.value() => Ok(()),
+            Err(value) => internal_err!(
+                "Scalar subquery result already set to {:?}, got {value:?}",
+                self.value()
+            ),
+        }
+    }
+
+    /// Returns true if `self` and `other` are the same result
+    pub fn ptr_eq(&self, other: &Self) -> bool {
+        Arc::ptr_eq(&self.value, &other.value)
+    }
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
     #[test]
     fn debug() {
         let props = ExecutionProps::new();
-        assert_eq!("ExecutionProps { query_execution_start_time: 1970-01-01T00:00:00Z, alias_generator: AliasGenerator { next_id: 1 }, var_providers: None }", format!("{props:?}"));
+        assert_eq!("ExecutionProps { query_execution_start_time: 1970-01-01T00:00:00Z, alias_generator: AliasGenerator { next_id: 1 }, var_providers: None, scalar_subquery_results: {} }", format!("{props:?}"));
     }
 }
@@ -66,6 +66,9 @@ pub struct AccumulatorArgs<'a> {
     ///
     /// If no `ORDER BY` is specified, `sort_exprs`` will be empty.
     pub sort_exprs: &'a [Expr],
+
+    /// The types of the input arguments of the aggregate function.
+    pub input_types: &'a [DataType],
 }
 
 impl<'a> AccumulatorArgs<'a> {
@@ -74,12 +77,14 @@ impl<'a> AccumulatorArgs<'a> {
         schema: &'a Schema,
         ignore_nulls: bool,
         sort_exprs: &'a [Expr],
+        input_types: &'a [DataType],
     ) -> Self {
         Self {
             data_type,
             schema,
             ignore_nulls,
             sort_exprs,
+            input_types,
         }
     }
 }
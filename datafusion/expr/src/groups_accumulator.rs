@@ -18,7 +18,7 @@
 //! Vectorized [`GroupsAccumulator`]
 
 use arrow_array::{ArrayRef, BooleanArray};
-use datafusion_common::Result;
+use datafusion_common::{not_impl_err, Result};
 
 /// Describes how many rows should be emitted during grouping.
 #[derive(Debug, Clone, Copy)]
@@ -158,6 +158,34 @@ pub trait GroupsAccumulator: Send {
         total_num_groups: usize,
     ) -> Result<()>;
 
+    /// Converts a batch of input values into intermediate aggregate
+    /// states, one per input row, as if each row was aggregated into
+    /// its own group and [`Self::state`] was called.
+    ///
+    /// This lets a partial aggregation pass its input through to the
+    /// final aggregation without grouping it first, which is cheaper
+    /// when most groups contain a single row.
+    ///
+    /// * `values`: the input arguments to the accumulator
+    ///
+    /// * `opt_filter`: if present, rows for which `opt_filter[i]` is
+    /// not true must produce the state of a group that saw no values
+    ///
+    /// Only called if [`Self::supports_convert_to_state`] returns true.
+    fn convert_to_state(
+        &self,
+        _values: &[ArrayRef],
+        _opt_filter: Option<&BooleanArray>,
+    ) -> Result<Vec<ArrayRef>> {
+        not_impl_err!("Input batch conversion to state not implemented")
+    }
+
+    /// Returns true if [`Self::convert_to_state`] is implemented for
+    /// this accumulator
+    fn supports_convert_to_state(&self) -> bool {
+        false
+    }
+
     /// Amount of memory used to store the state of this accumulator,
     /// in bytes. This function is called once per batch, so it should
     /// be `O(n)` to compute, not `O(num_groups)`
@@ -26,12 +26,14 @@
 //! The [expr_fn] module contains functions for creating expressions.
 
 mod accumulator;
+mod async_udf;
 mod built_in_window_function;
 mod columnar_value;
 mod literal;
 mod operator;
 mod partition_evaluator;
 mod signature;
+mod table_policy;
 mod table_source;
 mod udaf;
 mod udf;
@@ -59,6 +61,7 @@ pub mod window_state;
 
 pub use accumulator::Accumulator;
 pub use aggregate_function::AggregateFunction;
+pub use async_udf::{AsyncScalarUDF, AsyncScalarUDFImpl};
 pub use built_in_window_function::BuiltInWindowFunction;
 pub use columnar_value::ColumnarValue;
 pub use expr::{
@@ -80,9 +83,10 @@ pub use signature::{
     ArrayFunctionSignature, FuncMonotonicity, Signature, TypeSignature, Volatility,
     TIMEZONE_WILDCARD,
 };
+pub use table_policy::TablePolicy;
 pub use table_source::{TableProviderFilterPushDown, TableSource, TableType};
 pub use udaf::{AggregateUDF, AggregateUDFImpl};
-pub use udf::{ScalarUDF, ScalarUDFImpl};
+pub use udf::{PreparedScalarUDF, ScalarUDF, ScalarUDFImpl};
 pub use udwf::{WindowUDF, WindowUDFImpl};
 pub use window_frame::{WindowFrame, WindowFrameBound, WindowFrameUnits};
 
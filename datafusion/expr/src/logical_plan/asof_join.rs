@@ -0,0 +1,167 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`AsOfJoin`] plans the ASOF joins
+
+use std::collections::HashSet;
+use std::fmt;
+use std::sync::Arc;
+
+use crate::logical_plan::builder::build_join_schema;
+use crate::{
+    Expr, ExprSchemable, JoinType, LogicalPlan, Operator, UserDefinedLogicalNodeCore,
+};
+
+use datafusion_common::{plan_err, DFSchemaRef, Result};
+
+/// An ASOF join, matching each row of the left input with the row of the
+/// right input with equal `on` keys whose match value is the nearest one
+/// satisfying `left_match <op> right_match`, e.g. the most recent event at or
+/// before a timestamp with `>=`.
+///
+/// Each row of the left input is output once, with nulls for the columns of
+/// the right input if no row matches, as in a left join.
+///
+/// Planned as an `AsOfJoinExec` by the default physical planner, which merges
+/// both inputs sorted on their keys followed by their match expression.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AsOfJoin {
+    /// The left input
+    pub left: Arc<LogicalPlan>,
+    /// The right input
+    pub right: Arc<LogicalPlan>,
+    /// The equijoin keys, as `(left, right)` pairs
+    pub on: Vec<(Expr, Expr)>,
+    /// The match expression of the left input
+    pub left_match: Expr,
+    /// The comparison of the match expressions, one of `>=`, `>`, `<=`, `<`
+    pub op: Operator,
+    /// The match expression of the right input
+    pub right_match: Expr,
+    /// The schema of the output, the columns of the right input being nullable
+    pub schema: DFSchemaRef,
+}
+
+impl AsOfJoin {
+    /// Create the ASOF join of `left` and `right` on the keys `on` and the
+    /// match condition `left_match <op> right_match`, whose expressions must
+    /// have the same types on both sides
+    pub fn try_new(
+        left: Arc<LogicalPlan>,
+        right: Arc<LogicalPlan>,
+        on: Vec<(Expr, Expr)>,
+        left_match: Expr,
+        op: Operator,
+        right_match: Expr,
+    ) -> Result<Self> {
+        if !matches!(
+            op,
+            Operator::Gt | Operator::GtEq | Operator::Lt | Operator::LtEq
+        ) {
+            return plan_err!(
+                "ASOF join match condition must use one of >=, >, <=, <, got {op}"
+            );
+        }
+        let match_pair = (left_match.clone(), right_match.clone());
+        for (l, r) in on.iter().chain(std::iter::once(&match_pair)) {
+            let left_type = l.get_type(left.schema())?;
+            let right_type = r.get_type(right.schema())?;
+            if left_type != right_type {
+                return plan_err!(
+                    "ASOF join expressions {l} and {r} have different types: {left_type} and {right_type}"
+                );
+            }
+        }
+        let schema = Arc::new(build_join_schema(
+            left.schema(),
+            right.schema(),
+            &JoinType::Left,
+        )?);
+        Ok(Self {
+            left,
+            right,
+            on,
+            left_match,
+            op,
+            right_match,
+            schema,
+        })
+    }
+}
+
+impl UserDefinedLogicalNodeCore for AsOfJoin {
+    fn name(&self) -> &str {
+        "AsOfJoin"
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        &self.schema
+    }
+
+    /// The keys, as `left, right` pairs, followed by the match expressions
+    fn expressions(&self) -> Vec<Expr> {
+        self.on
+            .iter()
+            .flat_map(|(l, r)| [l.clone(), r.clone()])
+            .chain([self.left_match.clone(), self.right_match.clone()])
+            .collect()
+    }
+
+    /// The predicates are not pushed down, as they would be pushed down to
+    /// both inputs, while the right input is joined as in a left join
+    fn prevent_predicate_push_down_columns(&self) -> HashSet<String> {
+        self.schema
+            .fields()
+            .iter()
+            .map(|field| field.name().clone())
+            .collect()
+    }
+
+    fn fmt_for_explain(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let on = self
+            .on
+            .iter()
+            .map(|(l, r)| format!("{l} = {r}"))
+            .collect::<Vec<_>>()
+            .join(", ");
+        write!(
+            f,
+            "AsOfJoin: on=[{on}], match_condition={} {} {}",
+            self.left_match, self.op, self.right_match
+        )
+    }
+
+    fn from_template(&self, exprs: &[Expr], inputs: &[LogicalPlan]) -> Self {
+        let (keys, matches) = exprs.split_at(exprs.len() - 2);
+        Self {
+            left: Arc::new(inputs[0].clone()),
+            right: Arc::new(inputs[1].clone()),
+            on: keys
+                .chunks(2)
+                .map(|pair| (pair[0].clone(), pair[1].clone()))
+                .collect(),
+            left_match: matches[0].clone(),
+            op: self.op,
+            right_match: matches[1].clone(),
+            schema: self.schema.clone(),
+        }
+    }
+}
@@ -31,12 +31,13 @@ use crate::expr_rewriter::{
     rewrite_sort_cols_by_aggs,
 };
 use crate::logical_plan::{
-    Aggregate, Analyze, CrossJoin, Distinct, DistinctOn, EmptyRelation, Explain, Filter,
-    Join, JoinConstraint, JoinType, Limit, LogicalPlan, Partitioning, PlanType, Prepare,
-    Projection, Repartition, Sort, SubqueryAlias, TableScan, Union, Unnest, Values,
-    Window,
+    Aggregate, Analyze, AsOfJoin, CrossJoin, Distinct, DistinctOn, EmptyRelation,
+    Explain, Extension, Filter, Join, JoinConstraint, JoinType, Limit, LogicalPlan,
+    Partitioning, PlanType, Prepare, Projection, Repartition, SetOperation,
+    SetOperationType, Sort, SubqueryAlias, TableSample, TableScan, TableVersion, Union,
+    Unnest, Values, Window,
 };
-use crate::type_coercion::binary::comparison_coercion;
+use crate::type_coercion::binary::{comparison_coercion, values_coercion};
 use crate::utils::{
     can_hash, columnize_expr, compare_sort_expr, expand_qualified_wildcard,
     expand_wildcard, find_valid_equijoin_key_pair, group_window_expr_by_sort_keys,
@@ -173,12 +174,7 @@ impl LogicalPlanBuilder {
             return plan_err!("Values list cannot be zero length");
         }
         let empty_schema = DFSchema::empty();
-        let mut field_types: Vec<Option<DataType>> = Vec::with_capacity(n_cols);
-        for _ in 0..n_cols {
-            field_types.push(None);
-        }
-        // hold all the null holes so that we can correct their data types later
-        let mut nulls: Vec<(usize, usize)> = Vec::new();
+        let mut field_types: Vec<Option<DataType>> = vec![None; n_cols];
         for (i, row) in values.iter().enumerate() {
             if row.len() != n_cols {
                 return plan_err!(
@@ -188,24 +184,25 @@ impl LogicalPlanBuilder {
                     n_cols
                 );
             }
-            field_types = row
-                .iter()
-                .enumerate()
-                .map(|(j, expr)| {
-                    if let Expr::Literal(ScalarValue::Null) = expr {
-                        nulls.push((i, j));
-                        Ok(field_types[j].clone())
-                    } else {
-                        let data_type = expr.get_type(&empty_schema)?;
-                        if let Some(prev_data_type) = &field_types[j] {
-                            if prev_data_type != &data_type {
+            for (j, expr) in row.iter().enumerate() {
+                if let Expr::Literal(ScalarValue::Null) = expr {
+                    continue;
+                }
+                let data_type = expr.get_type(&empty_schema)?;
+                // the values of a column, including nested ones such as
+                // structs and lists, are coerced to their common type
+                field_types[j] = match field_types[j].take() {
+                    Some(prev_data_type) => {
+                        match values_coercion(&prev_data_type, &data_type) {
+                            Some(common_type) => Some(common_type),
+                            None => {
                                 return plan_err!("Inconsistent data type across values list at row {i} column {j}. Was {prev_data_type} but found {data_type}")
                             }
                         }
-                        Ok(Some(data_type))
                     }
-                })
-                .collect::<Result<Vec<Option<DataType>>>>()?;
+                    None => Some(data_type),
+                };
+            }
         }
         let fields = field_types
             .iter()
@@ -216,8 +213,16 @@ impl LogicalPlanBuilder {
                 Field::new(name, data_type.clone().unwrap_or(DataType::Utf8), true)
             })
             .collect::<Vec<_>>();
-        for (i, j) in nulls {
-            values[i][j] = Expr::Literal(ScalarValue::try_from(fields[j].data_type())?);
+        for row in values.iter_mut() {
+            for (value, field) in row.iter_mut().zip(fields.iter()) {
+                *value = match std::mem::take(value) {
+                    // hold the null holes with nulls of the column type
+                    Expr::Literal(ScalarValue::Null) => {
+                        Expr::Literal(ScalarValue::try_from(field.data_type())?)
+                    }
+                    value => value.cast_to(field.data_type(), &empty_schema)?,
+                };
+            }
         }
         let dfschema = DFSchema::from_unqualifed_fields(fields.into(), HashMap::new())?;
         let schema = DFSchemaRef::new(dfschema);
@@ -316,6 +321,28 @@ impl LogicalPlanBuilder {
             .map(Self::from)
     }
 
+    /// Scan a random sample of the rows of the table scanned by this builder
+    /// (`TABLESAMPLE`)
+    pub fn sample(self, sample: TableSample) -> Result<Self> {
+        match self.plan {
+            LogicalPlan::TableScan(scan) if scan.fetch.is_none() => Ok(Self::from(
+                LogicalPlan::TableScan(scan.with_sample(Some(sample))),
+            )),
+            _ => plan_err!("TABLESAMPLE is only supported on tables"),
+        }
+    }
+
+    /// Scan a version of the table scanned by this builder rather than the
+    /// current one (`FOR SYSTEM_TIME AS OF` or `VERSION AS OF`)
+    pub fn version(self, version: TableVersion) -> Result<Self> {
+        match self.plan {
+            LogicalPlan::TableScan(scan) => Ok(Self::from(LogicalPlan::TableScan(
+                scan.with_version(Some(version)),
+            ))),
+            _ => plan_err!("Time travel is only supported on tables"),
+        }
+    }
+
     /// Wrap a plan in a window
     pub fn window_plan(
         input: LogicalPlan,
@@ -808,9 +835,13 @@ impl LogicalPlanBuilder {
             .into_iter()
             .zip(right_keys)
             .map(|(l, r)| (Expr::Column(l), Expr::Column(r)))
-            .collect();
-        let join_schema =
-            build_join_schema(self.plan.schema(), right.schema(), &join_type)?;
+            .collect::<Vec<_>>();
+        let join_schema = build_join_schema_with_keys(
+            self.plan.schema(),
+            right.schema(),
+            &join_type,
+            &on,
+        )?;
 
         Ok(Self::from(LogicalPlan::Join(Join {
             left: Arc::new(self.plan),
@@ -842,8 +873,6 @@ impl LogicalPlanBuilder {
             .collect::<Result<_>>()?;
 
         let on: Vec<(_, _)> = left_keys.into_iter().zip(right_keys).collect();
-        let join_schema =
-            build_join_schema(self.plan.schema(), right.schema(), &join_type)?;
         let mut join_on: Vec<(Expr, Expr)> = vec![];
         let mut filters: Option<Expr> = None;
         for (l, r) in &on {
@@ -876,6 +905,12 @@ impl LogicalPlanBuilder {
                 DataFusionError::Internal("filters should not be None here".to_string())
             })?)
         } else {
+            let join_schema = build_join_schema_with_keys(
+                self.plan.schema(),
+                right.schema(),
+                &join_type,
+                &join_on,
+            )?;
             Ok(Self::from(LogicalPlan::Join(Join {
                 left: Arc::new(self.plan),
                 right: Arc::new(right),
@@ -889,6 +924,50 @@ impl LogicalPlanBuilder {
         }
     }
 
+    /// Apply an ASOF join to `right`: each row is joined with the row of
+    /// `right` with equal `join_keys` whose match value is the nearest one
+    /// satisfying the match condition `(left_match, op, right_match)`, e.g.
+    /// the most recent event at or before a timestamp with `>=`.
+    ///
+    /// See [`AsOfJoin`] for details.
+    pub fn asof_join(
+        self,
+        right: LogicalPlan,
+        join_keys: (
+            Vec<impl Into<Column> + Clone>,
+            Vec<impl Into<Column> + Clone>,
+        ),
+        match_condition: (Expr, Operator, Expr),
+    ) -> Result<Self> {
+        if join_keys.0.len() != join_keys.1.len() {
+            return plan_err!("left_keys and right_keys were not the same length");
+        }
+        let on = join_keys
+            .0
+            .into_iter()
+            .zip(join_keys.1)
+            .map(|(l, r)| {
+                let l = Self::normalize(&self.plan, l)?;
+                let r = Self::normalize(&right, r)?;
+                Ok((Expr::Column(l), Expr::Column(r)))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let (left_match, op, right_match) = match_condition;
+        let left_match = normalize_col(left_match, &self.plan)?;
+        let right_match = normalize_col(right_match, &right)?;
+        let join = AsOfJoin::try_new(
+            Arc::new(self.plan),
+            Arc::new(right),
+            on,
+            left_match,
+            op,
+            right_match,
+        )?;
+        Ok(Self::from(LogicalPlan::Extension(Extension {
+            node: Arc::new(join),
+        })))
+    }
+
     /// Apply a cross join
     pub fn cross_join(self, right: LogicalPlan) -> Result<Self> {
         let join_schema =
@@ -1026,9 +1105,37 @@ impl LogicalPlanBuilder {
             })
             .unzip();
         if is_all {
-            LogicalPlanBuilder::from(left_plan)
-                .join_detailed(right_plan, join_type, join_keys, None, true)?
-                .build()
+            // the rows are counted, rather than joined, to keep each row of
+            // the left input the right number of times
+            let op = match join_type {
+                JoinType::LeftSemi => SetOperationType::IntersectAll,
+                _ => SetOperationType::ExceptAll,
+            };
+            let types = left_plan
+                .schema()
+                .fields()
+                .iter()
+                .zip(right_plan.schema().fields().iter())
+                .map(|(left_field, right_field)| {
+                    comparison_coercion(left_field.data_type(), right_field.data_type())
+                        .ok_or_else(|| {
+                            plan_datafusion_err!(
+                                "INTERSECT/EXCEPT query can't compare column {} of type {} with column {} of type {}",
+                                left_field.name(),
+                                left_field.data_type(),
+                                right_field.name(),
+                                right_field.data_type()
+                            )
+                        })
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(LogicalPlan::Extension(Extension {
+                node: Arc::new(SetOperation {
+                    left: Arc::new(cast_columns(left_plan, &types)?),
+                    right: Arc::new(cast_columns(right_plan, &types)?),
+                    op,
+                }),
+            }))
         } else {
             LogicalPlanBuilder::from(left_plan)
                 .distinct()?
@@ -1094,8 +1201,12 @@ impl LogicalPlanBuilder {
             })
             .collect::<Result<Vec<_>>>()?;
 
-        let join_schema =
-            build_join_schema(self.plan.schema(), right.schema(), &join_type)?;
+        let join_schema = build_join_schema_with_keys(
+            self.plan.schema(),
+            right.schema(),
+            &join_type,
+            &join_key_pairs,
+        )?;
 
         Ok(Self::from(LogicalPlan::Join(Join {
             left: Arc::new(self.plan),
@@ -1154,12 +1265,51 @@ pub fn change_redundant_column(fields: &Fields) -> Vec<Field> {
         })
         .collect()
 }
+
+/// Casts the columns of `plan` to `types`, keeping their names, if any of
+/// them is of another type
+fn cast_columns(plan: LogicalPlan, types: &[DataType]) -> Result<LogicalPlan> {
+    let schema = plan.schema().clone();
+    if zip(schema.fields().iter(), types).all(|(field, t)| field.data_type() == t) {
+        return Ok(plan);
+    }
+    let exprs = zip(schema.iter(), types)
+        .map(|((qualifier, field), data_type)| {
+            let column = Expr::Column(Column::new(qualifier.cloned(), field.name()));
+            if field.data_type() == data_type {
+                Ok(column)
+            } else {
+                Ok(column
+                    .cast_to(data_type, &schema)?
+                    .alias_qualified(qualifier.cloned(), field.name()))
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(LogicalPlan::Projection(Projection::try_new(
+        exprs,
+        Arc::new(plan),
+    )?))
+}
+
 /// Creates a schema for a join operation.
 /// The fields from the left side are first
 pub fn build_join_schema(
     left: &DFSchema,
     right: &DFSchema,
     join_type: &JoinType,
+) -> Result<DFSchema> {
+    build_join_schema_with_keys(left, right, join_type, &[])
+}
+
+/// Creates a schema for an equijoin operation on the keys `on`, like
+/// [`build_join_schema`]. The unique keys of a side remain unique keys of the
+/// join if the join keys of the side are a unique key of the other side, as
+/// its rows then match at most one row of the other side.
+pub fn build_join_schema_with_keys(
+    left: &DFSchema,
+    right: &DFSchema,
+    join_type: &JoinType,
+    on: &[(Expr, Expr)],
 ) -> Result<DFSchema> {
     fn nullify_fields<'a>(
         fields: impl Iterator<Item = (Option<&'a TableReference>, &'a Arc<Field>)>,
@@ -1223,11 +1373,24 @@ pub fn build_join_schema(
             right_fields.map(|(q, f)| (q.cloned(), f.clone())).collect()
         }
     };
-    let func_dependencies = left.functional_dependencies().join(
+    // The indices of the join keys which are columns of the left and right sides
+    let key_indices = on
+        .iter()
+        .filter_map(|(l, r)| match (l, r) {
+            (Expr::Column(l), Expr::Column(r)) => Some((
+                left.maybe_index_of_column(l)?,
+                right.maybe_index_of_column(r)?,
+            )),
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+    let mut func_dependencies = left.functional_dependencies().join_on(
         right.functional_dependencies(),
         join_type,
         left.fields().len(),
+        &key_indices,
     );
+    func_dependencies.extend_target_indices(qualified_fields.len());
     let mut metadata = left.metadata().clone();
     metadata.extend(right.metadata().clone());
     let dfschema = DFSchema::new_with_metadata(qualified_fields, metadata)?;
@@ -2025,6 +2188,32 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn plan_builder_intersect_all() -> Result<()> {
+        let plan1 = table_scan(
+            Some("t1"),
+            &Schema::new(vec![Field::new("a", DataType::Int32, false)]),
+            None,
+        )?;
+        let plan2 = table_scan(
+            Some("t2"),
+            &Schema::new(vec![Field::new("b", DataType::Int64, false)]),
+            None,
+        )?;
+
+        // the rows are compared with the common type of the columns
+        let plan = LogicalPlanBuilder::intersect(plan1.build()?, plan2.build()?, true)?;
+        let expected = "IntersectAll\
+        \n  Projection: CAST(t1.a AS Int64) AS a\
+        \n    TableScan: t1\
+        \n  TableScan: t2";
+        assert_eq!(expected, format!("{plan:?}"));
+        assert_eq!(plan.schema().field(0).name(), "a");
+        assert_eq!(plan.schema().field(0).data_type(), &DataType::Int64);
+
+        Ok(())
+    }
+
     #[test]
     fn plan_builder_unnest() -> Result<()> {
         // Unnesting a simple column should return the child plan.
@@ -196,6 +196,9 @@ pub struct CreateExternalTable {
     pub delimiter: char,
     /// Partition Columns
     pub table_partition_cols: Vec<String>,
+    /// The expressions deriving partition columns from the column `path`,
+    /// the path of each file relative to the location, by partition column
+    pub partition_transforms: HashMap<String, Expr>,
     /// Option to not error if table already exists
     pub if_not_exists: bool,
     /// SQL used to create the table, if available
@@ -224,6 +227,7 @@ impl Hash for CreateExternalTable {
         self.has_header.hash(state);
         self.delimiter.hash(state);
         self.table_partition_cols.hash(state);
+        self.partition_transforms.len().hash(state); // HashMap is not hashable
         self.if_not_exists.hash(state);
         self.definition.hash(state);
         self.file_compression_type.hash(state);
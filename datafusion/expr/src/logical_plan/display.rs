@@ -351,6 +351,8 @@ impl<'a, 'b> PgJsonVisitor<'a, 'b> {
                 ref table_name,
                 ref filters,
                 ref fetch,
+                ref sample,
+                ref version,
                 ..
             }) => {
                 let mut object = json!({
@@ -407,6 +409,14 @@ impl<'a, 'b> PgJsonVisitor<'a, 'b> {
                     object["Fetch"] = serde_json::Value::Number((*f).into());
                 }
 
+                if let Some(sample) = sample {
+                    object["Sample"] = serde_json::Value::String(sample.to_string());
+                }
+
+                if let Some(version) = version {
+                    object["Version"] = serde_json::Value::String(version.to_string());
+                }
+
                 object
             }
             LogicalPlan::Projection(Projection { ref expr, .. }) => {
@@ -15,18 +15,21 @@
 // specific language governing permissions and limitations
 // under the License.
 
+mod asof_join;
 pub mod builder;
 mod ddl;
 pub mod display;
 pub mod dml;
 mod extension;
 mod plan;
+mod set_operation;
 mod statement;
 pub mod tree_node;
 
+pub use asof_join::AsOfJoin;
 pub use builder::{
-    build_join_schema, table_scan, union, wrap_projection_for_join_if_necessary,
-    LogicalPlanBuilder, UNNAMED_TABLE,
+    build_join_schema, build_join_schema_with_keys, table_scan, union,
+    wrap_projection_for_join_if_necessary, LogicalPlanBuilder, UNNAMED_TABLE,
 };
 pub use ddl::{
     CreateCatalog, CreateCatalogSchema, CreateExternalTable, CreateFunction,
@@ -39,11 +42,13 @@ pub use plan::{
     DistinctOn, EmptyRelation, Explain, Extension, Filter, Join, JoinConstraint,
     JoinType, Limit, LogicalPlan, Partitioning, PlanType, Prepare, Projection,
     RecursiveQuery, Repartition, Sort, StringifiedPlan, Subquery, SubqueryAlias,
-    TableScan, ToStringifiedPlan, Union, Unnest, Values, Window,
+    TableSample, TableSampleMethod, TableScan, TableVersion, ToStringifiedPlan, Union,
+    Unnest, UsingJoinColumns, Values, Window,
 };
+pub use set_operation::{SetOperation, SetOperationType};
 pub use statement::{
-    SetVariable, Statement, TransactionAccessMode, TransactionConclusion, TransactionEnd,
-    TransactionIsolationLevel, TransactionStart,
+    AnalyzeTable, ResetVariable, SetVariable, Statement, TransactionAccessMode,
+    TransactionConclusion, TransactionEnd, TransactionIsolationLevel, TransactionStart,
 };
 
 pub use display::display_schema;
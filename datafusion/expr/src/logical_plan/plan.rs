@@ -35,19 +35,20 @@ use crate::utils::{
     grouping_set_expr_count, grouping_set_to_exprlist, split_conjunction,
 };
 use crate::{
-    build_join_schema, expr_vec_fmt, BinaryExpr, BuiltInWindowFunction,
+    build_join_schema_with_keys, expr_vec_fmt, BinaryExpr, BuiltInWindowFunction,
     CreateMemoryTable, CreateView, Expr, ExprSchemable, LogicalPlanBuilder, Operator,
     TableProviderFilterPushDown, TableSource, WindowFunctionDefinition,
 };
 
-use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, TimeUnit};
 use datafusion_common::tree_node::{
     Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
 };
 use datafusion_common::{
     aggregate_functional_dependencies, internal_err, plan_err, Column, Constraints,
     DFSchema, DFSchemaRef, DataFusionError, Dependency, FunctionalDependence,
-    FunctionalDependencies, ParamValues, Result, TableReference, UnnestOptions,
+    FunctionalDependencies, ParamValues, Result, ScalarValue, TableReference,
+    UnnestOptions,
 };
 
 // backwards compatibility
@@ -55,6 +56,10 @@ use crate::display::PgJsonVisitor;
 pub use datafusion_common::display::{PlanType, StringifiedPlan, ToStringifiedPlan};
 pub use datafusion_common::{JoinConstraint, JoinType};
 
+/// The `(left, right)` column pairs of a `Using` join, together with the type
+/// of the join, as returned by [`LogicalPlan::using_join_columns`]
+pub type UsingJoinColumns = (JoinType, Vec<(Column, Column)>);
+
 /// A LogicalPlan represents the different types of relational
 /// operators (such as Projection, Filter, etc) and can be created by
 /// the SQL query planner and the DataFrame API.
@@ -380,6 +385,36 @@ impl LogicalPlan {
         Ok(using_columns)
     }
 
+    /// returns all `Using` join columns in a logical plan as `(left, right)`
+    /// pairs, together with the type of the join that binds them.
+    ///
+    /// Unlike [`Self::using_columns`], this keeps track of which side each
+    /// column comes from, which determines the value of the merged column in
+    /// the join output (e.g. the right column of a `RIGHT JOIN ... USING`)
+    pub fn using_join_columns(&self) -> Result<Vec<UsingJoinColumns>> {
+        let mut using_columns = vec![];
+
+        self.apply_with_subqueries(|plan| {
+            if let LogicalPlan::Join(Join {
+                join_constraint: JoinConstraint::Using,
+                join_type,
+                on,
+                ..
+            }) = plan
+            {
+                // The join keys in using-join must be columns.
+                let columns = on
+                    .iter()
+                    .map(|(l, r)| Ok((l.try_into_col()?, r.try_into_col()?)))
+                    .collect::<Result<Vec<_>>>()?;
+                using_columns.push((*join_type, columns));
+            }
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+
+        Ok(using_columns)
+    }
+
     /// returns the first output expression of this `LogicalPlan` node.
     pub fn head_output_expr(&self) -> Result<Option<Expr>> {
         match self {
@@ -620,9 +655,6 @@ impl LogicalPlan {
                 null_equals_null,
                 ..
             }) => {
-                let schema =
-                    build_join_schema(inputs[0].schema(), inputs[1].schema(), join_type)?;
-
                 let equi_expr_count = on.len();
                 assert!(expr.len() >= equi_expr_count);
 
@@ -648,6 +680,12 @@ impl LogicalPlan {
                         )
                     }
                 }).collect::<Result<Vec<(Expr, Expr)>>>()?;
+                let schema = build_join_schema_with_keys(
+                    inputs[0].schema(),
+                    inputs[1].schema(),
+                    join_type,
+                    &new_on,
+                )?;
 
                 Ok(LogicalPlan::Join(Join {
                     left: Arc::new(inputs.swap_remove(0)),
@@ -1292,6 +1330,8 @@ impl LogicalPlan {
                         ref projection,
                         ref filters,
                         ref fetch,
+                        ref sample,
+                        ref version,
                         ..
                     }) => {
                         let projected_fields = match projection {
@@ -1359,6 +1399,14 @@ impl LogicalPlan {
                             write!(f, ", fetch={n}")?;
                         }
 
+                        if let Some(sample) = sample {
+                            write!(f, ", sample={sample}")?;
+                        }
+
+                        if let Some(version) = version {
+                            write!(f, ", version={version}")?;
+                        }
+
                         Ok(())
                     }
                     LogicalPlan::Projection(Projection { ref expr, .. }) => {
@@ -1940,6 +1988,11 @@ pub struct TableScan {
     pub filters: Vec<Expr>,
     /// Optional number of rows to read
     pub fetch: Option<usize>,
+    /// Optional random sample of the rows to read (`TABLESAMPLE`)
+    pub sample: Option<TableSample>,
+    /// Optional version of the table to read (`FOR SYSTEM_TIME AS OF` or
+    /// `VERSION AS OF`), instead of the current one
+    pub version: Option<TableVersion>,
 }
 
 impl PartialEq for TableScan {
@@ -1949,6 +2002,8 @@ impl PartialEq for TableScan {
             && self.projected_schema == other.projected_schema
             && self.filters == other.filters
             && self.fetch == other.fetch
+            && self.sample == other.sample
+            && self.version == other.version
     }
 }
 
@@ -1961,6 +2016,152 @@ impl Hash for TableScan {
         self.projected_schema.hash(state);
         self.filters.hash(state);
         self.fetch.hash(state);
+        self.sample.hash(state);
+        self.version.hash(state);
+    }
+}
+
+/// How a [`TableSample`] picks the rows of a table
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum TableSampleMethod {
+    /// Each row is kept independently with the sampling probability
+    Bernoulli,
+    /// Each block of rows (a file, a row group or a batch) is kept
+    /// independently with the sampling probability, which allows the table
+    /// provider to skip reading the blocks that are not kept
+    System,
+}
+
+impl Display for TableSampleMethod {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            TableSampleMethod::Bernoulli => write!(f, "BERNOULLI"),
+            TableSampleMethod::System => write!(f, "SYSTEM"),
+        }
+    }
+}
+
+/// A random sample of the rows of a table, as requested by
+/// `TABLESAMPLE BERNOULLI | SYSTEM (<percentage>) [REPEATABLE (<seed>)]`
+#[derive(Debug, Clone)]
+pub struct TableSample {
+    /// How rows are picked
+    pub method: TableSampleMethod,
+    /// The percentage of rows (or blocks) kept, between 0 and 100
+    pub percentage: f64,
+    /// The seed of the random number generator. Scanning the same data with
+    /// the same seed returns the same sample
+    pub seed: Option<u64>,
+}
+
+impl TableSample {
+    /// Create a new sample keeping `percentage` percent of the rows
+    pub fn try_new(
+        method: TableSampleMethod,
+        percentage: f64,
+        seed: Option<u64>,
+    ) -> Result<Self> {
+        if !(0.0..=100.0).contains(&percentage) {
+            return plan_err!(
+                "TABLESAMPLE percentage must be between 0 and 100, got {percentage}"
+            );
+        }
+        Ok(Self {
+            method,
+            percentage,
+            seed,
+        })
+    }
+
+    /// The probability of keeping a row (or block), between 0 and 1
+    pub fn fraction(&self) -> f64 {
+        self.percentage / 100.0
+    }
+}
+
+impl PartialEq for TableSample {
+    fn eq(&self, other: &Self) -> bool {
+        self.method == other.method
+            && self.percentage.to_bits() == other.percentage.to_bits()
+            && self.seed == other.seed
+    }
+}
+
+impl Eq for TableSample {}
+
+impl Hash for TableSample {
+    fn hash<H: Hasher>(&self, state: &mut H) {
+        self.method.hash(state);
+        self.percentage.to_bits().hash(state);
+        self.seed.hash(state);
+    }
+}
+
+impl Display for TableSample {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} ({}%)", self.method, self.percentage)?;
+        if let Some(seed) = self.seed {
+            write!(f, " REPEATABLE ({seed})")?;
+        }
+        Ok(())
+    }
+}
+
+/// A version of a table to scan instead of the current one (time travel), as
+/// requested by `FOR SYSTEM_TIME AS OF <timestamp>` or `VERSION AS OF <version>`
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum TableVersion {
+    /// The version current at the timestamp, a
+    /// [`ScalarValue::TimestampNanosecond`]
+    Timestamp(ScalarValue),
+    /// The version numbered or named by the table, e.g. a
+    /// [`ScalarValue::Int64`] or a [`ScalarValue::Utf8`]
+    Version(ScalarValue),
+}
+
+impl TableVersion {
+    /// Create the version current at `timestamp`, a timestamp or a string
+    /// cast to a timestamp
+    pub fn try_new_timestamp(timestamp: ScalarValue) -> Result<Self> {
+        let data_type = match timestamp.data_type() {
+            DataType::Timestamp(_, tz) => DataType::Timestamp(TimeUnit::Nanosecond, tz),
+            DataType::Utf8 | DataType::LargeUtf8 => {
+                DataType::Timestamp(TimeUnit::Nanosecond, None)
+            }
+            other => {
+                return plan_err!(
+                    "FOR SYSTEM_TIME AS OF expects a timestamp, got {other}"
+                )
+            }
+        };
+        let timestamp = timestamp.cast_to(&data_type)?;
+        if timestamp.is_null() {
+            return plan_err!("FOR SYSTEM_TIME AS OF expects a non null timestamp");
+        }
+        Ok(Self::Timestamp(timestamp))
+    }
+
+    /// Create the version `version`, numbered or named by the table
+    pub fn try_new_version(version: ScalarValue) -> Result<Self> {
+        if version.is_null() {
+            return plan_err!("VERSION AS OF expects a non null version");
+        }
+        Ok(Self::Version(version))
+    }
+}
+
+impl Display for TableVersion {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        match self {
+            TableVersion::Timestamp(timestamp) => {
+                // the timestamps are displayed as dates rather than numbers
+                let timestamp = timestamp
+                    .cast_to(&DataType::Utf8)
+                    .unwrap_or_else(|_| timestamp.clone());
+                write!(f, "SYSTEM_TIME AS OF {timestamp}")
+            }
+            TableVersion::Version(version) => write!(f, "VERSION AS OF {version}"),
+        }
     }
 }
 
@@ -2014,8 +2215,22 @@ impl TableScan {
             projected_schema,
             filters,
             fetch,
+            sample: None,
+            version: None,
         })
     }
+
+    /// Scan a random sample of the rows of the table
+    pub fn with_sample(mut self, sample: Option<TableSample>) -> Self {
+        self.sample = sample;
+        self
+    }
+
+    /// Scan a version of the table rather than the current one
+    pub fn with_version(mut self, version: Option<TableVersion>) -> Self {
+        self.version = version;
+        self
+    }
 }
 
 /// Apply Cross Join to two logical plans
@@ -2450,8 +2665,12 @@ impl Join {
             .zip(column_on.1)
             .map(|(l, r)| (Expr::Column(l), Expr::Column(r)))
             .collect();
-        let join_schema =
-            build_join_schema(left.schema(), right.schema(), &original_join.join_type)?;
+        let join_schema = build_join_schema_with_keys(
+            left.schema(),
+            right.schema(),
+            &original_join.join_type,
+            &on,
+        )?;
 
         Ok(Join {
             left,
@@ -3105,6 +3324,8 @@ digraph {
             projected_schema: schema.clone(),
             filters: vec![],
             fetch: None,
+            sample: None,
+            version: None,
         }));
         let col = schema.field_names()[0].clone();
 
@@ -3135,6 +3356,8 @@ digraph {
             projected_schema: unique_schema.clone(),
             filters: vec![],
             fetch: None,
+            sample: None,
+            version: None,
         }));
         let col = schema.field_names()[0].clone();
 
@@ -0,0 +1,106 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`SetOperation`] plans the `INTERSECT ALL` and `EXCEPT ALL` set operations
+
+use std::fmt;
+use std::sync::Arc;
+
+use crate::{Expr, LogicalPlan, UserDefinedLogicalNodeCore};
+
+use datafusion_common::DFSchemaRef;
+
+/// The type of a [`SetOperation`]
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SetOperationType {
+    /// `INTERSECT ALL`: each row of the left input is kept as many times as
+    /// it occurs in both inputs
+    IntersectAll,
+    /// `EXCEPT ALL`: each row of the left input is kept as many times as it
+    /// occurs in the left input more than in the right input
+    ExceptAll,
+}
+
+impl fmt::Display for SetOperationType {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            SetOperationType::IntersectAll => write!(f, "IntersectAll"),
+            SetOperationType::ExceptAll => write!(f, "ExceptAll"),
+        }
+    }
+}
+
+/// A set operation with bag semantics between two inputs with the same
+/// column types, comparing whole rows, nulls being equal to each other.
+/// Its schema is the schema of the left input.
+///
+/// Planned as a `SetOperationExec` by the default physical planner, which
+/// counts the rows of the right input in a hash table, instead of joining the
+/// inputs.
+///
+/// As the rows of both inputs are compared as a whole, the deterministic
+/// predicates on its output are pushed down to both inputs by
+/// `PushDownFilter`, the columns being replaced by the columns of the right
+/// input at the same positions.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct SetOperation {
+    /// The left input
+    pub left: Arc<LogicalPlan>,
+    /// The right input
+    pub right: Arc<LogicalPlan>,
+    /// The type of the set operation
+    pub op: SetOperationType,
+}
+
+impl UserDefinedLogicalNodeCore for SetOperation {
+    fn name(&self) -> &str {
+        "SetOperation"
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![self.left.as_ref(), self.right.as_ref()]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        self.left.schema()
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        vec![]
+    }
+
+    fn fmt_for_explain(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{}", self.op)
+    }
+
+    fn from_template(&self, _exprs: &[Expr], inputs: &[LogicalPlan]) -> Self {
+        Self {
+            left: Arc::new(inputs[0].clone()),
+            right: Arc::new(inputs[1].clone()),
+            op: self.op,
+        }
+    }
+
+    /// All the columns of both inputs are compared
+    fn necessary_children_exprs(
+        &self,
+        _output_columns: &[usize],
+    ) -> Option<Vec<Vec<usize>>> {
+        let indices = (0..self.left.schema().fields().len()).collect::<Vec<_>>();
+        Some(vec![indices.clone(), indices])
+    }
+}
@@ -15,7 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use datafusion_common::DFSchemaRef;
+use arrow::datatypes::{DataType, Field, Schema};
+use datafusion_common::{DFSchemaRef, TableReference};
 use std::fmt::{self, Display};
 
 /// Various types of Statements.
@@ -33,6 +34,10 @@ pub enum Statement {
     TransactionEnd(TransactionEnd),
     /// Set a Variable
     SetVariable(SetVariable),
+    /// Reset a Variable to its default value
+    ResetVariable(ResetVariable),
+    /// Compute and store the statistics of a table
+    AnalyzeTable(AnalyzeTable),
 }
 
 impl Statement {
@@ -42,6 +47,8 @@ impl Statement {
             Statement::TransactionStart(TransactionStart { schema, .. }) => schema,
             Statement::TransactionEnd(TransactionEnd { schema, .. }) => schema,
             Statement::SetVariable(SetVariable { schema, .. }) => schema,
+            Statement::ResetVariable(ResetVariable { schema, .. }) => schema,
+            Statement::AnalyzeTable(AnalyzeTable { schema, .. }) => schema,
         }
     }
 
@@ -52,6 +59,8 @@ impl Statement {
             Statement::TransactionStart(_) => "TransactionStart",
             Statement::TransactionEnd(_) => "TransactionEnd",
             Statement::SetVariable(_) => "SetVariable",
+            Statement::ResetVariable(_) => "ResetVariable",
+            Statement::AnalyzeTable(_) => "AnalyzeTable",
         }
     }
 
@@ -84,6 +93,25 @@ impl Statement {
                     }) => {
                         write!(f, "SetVariable: set {variable:?} to {value:?}")
                     }
+                    Statement::ResetVariable(ResetVariable { variable, .. }) => {
+                        match variable {
+                            Some(variable) => {
+                                write!(f, "ResetVariable: reset {variable:?}")
+                            }
+                            None => write!(f, "ResetVariable: reset all"),
+                        }
+                    }
+                    Statement::AnalyzeTable(AnalyzeTable {
+                        table_name,
+                        columns,
+                        ..
+                    }) => {
+                        write!(f, "AnalyzeTable: {table_name}")?;
+                        if !columns.is_empty() {
+                            write!(f, " columns=[{}]", columns.join(", "))?;
+                        }
+                        Ok(())
+                    }
                 }
             }
         }
@@ -147,3 +175,44 @@ pub struct SetVariable {
     /// Dummy schema
     pub schema: DFSchemaRef,
 }
+
+/// Reset a Variable's value to its default -- value in
+/// [`ConfigOptions`](datafusion_common::config::ConfigOptions)
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct ResetVariable {
+    /// The variable name, all the variables if `None`
+    pub variable: Option<String>,
+    /// Dummy schema
+    pub schema: DFSchemaRef,
+}
+
+/// Compute the statistics of a table, store them on the table with
+/// `TableProvider::set_statistics`, and return them with a row per column,
+/// see [`AnalyzeTable::output_schema`]
+#[derive(Clone, PartialEq, Eq, Hash)]
+pub struct AnalyzeTable {
+    /// The table to analyze
+    pub table_name: TableReference,
+    /// The columns to compute the statistics of, all columns if empty
+    pub columns: Vec<String>,
+    /// The schema of the returned statistics
+    pub schema: DFSchemaRef,
+}
+
+impl AnalyzeTable {
+    /// Returns the schema of the statistics returned by `ANALYZE TABLE`.
+    ///
+    /// There is a row for each analyzed column, and for each leaf column of
+    /// the analyzed struct columns, named with its path, e.g. `s.a`.
+    pub fn output_schema() -> Schema {
+        Schema::new(vec![
+            Field::new("column_name", DataType::Utf8, false),
+            Field::new("data_type", DataType::Utf8, false),
+            Field::new("row_count", DataType::UInt64, false),
+            Field::new("null_count", DataType::UInt64, false),
+            Field::new("min_value", DataType::Utf8, true),
+            Field::new("max_value", DataType::Utf8, true),
+            Field::new("distinct_count", DataType::UInt64, true),
+        ])
+    }
+}
@@ -680,6 +680,8 @@ impl LogicalPlan {
                 projected_schema,
                 filters,
                 fetch,
+                sample,
+                version,
             }) => filters
                 .into_iter()
                 .map_until_stop_and_collect(f)?
@@ -691,6 +693,8 @@ impl LogicalPlan {
                         projected_schema,
                         filters,
                         fetch,
+                        sample,
+                        version,
                     })
                 }),
             LogicalPlan::Distinct(Distinct::On(DistinctOn {
@@ -66,7 +66,7 @@ use crate::window_state::WindowAggState;
 ///
 /// Different methods on this trait will be called depending on the
 /// capabilities described by [`supports_bounded_execution`],
-/// [`uses_window_frame`], and [`include_rank`],
+/// [`uses_window_frame`], [`include_rank`] and [`include_peers`],
 ///
 /// When implementing a new `PartitionEvaluator`, implement
 /// corresponding evaluator according to table below.
@@ -80,12 +80,19 @@ use crate::window_state::WindowAggState;
 /// |false                |true/false                    |true              | [`evaluate_all_with_rank`] |
 /// |true                 |true/false                    |true/false        | [`evaluate`]               |
 ///
+/// Evaluators that need the peer group of each row, i.e. the rows with the
+/// same `ORDER BY` values, return true from [`include_peers`] and implement
+/// [`evaluate_with_peers`], which is then called instead of [`evaluate`]
+/// whatever the other capabilities.
+///
 /// [`evaluate`]: Self::evaluate
 /// [`evaluate_all`]: Self::evaluate_all
 /// [`evaluate_all_with_rank`]: Self::evaluate_all_with_rank
 /// [`uses_window_frame`]: Self::uses_window_frame
 /// [`include_rank`]: Self::include_rank
 /// [`supports_bounded_execution`]: Self::supports_bounded_execution
+/// [`include_peers`]: Self::include_peers
+/// [`evaluate_with_peers`]: Self::evaluate_with_peers
 pub trait PartitionEvaluator: Debug + Send {
     /// When the window frame has a fixed beginning (e.g UNBOUNDED
     /// PRECEDING), some functions such as FIRST_VALUE, LAST_VALUE and
@@ -196,6 +203,28 @@ pub trait PartitionEvaluator: Debug + Send {
         not_impl_err!("evaluate is not implemented by default")
     }
 
+    /// Evaluate window function on a range of rows in an input partition,
+    /// knowing the peer group of the current row.
+    ///
+    /// Called instead of [`Self::evaluate`] when [`Self::include_peers`] is
+    /// true. `range` is the window frame if [`Self::uses_window_frame`] is
+    /// true, and [`Self::get_range`] otherwise. `peers` is the range of the
+    /// rows with the same `ORDER BY` values as the current row (all rows
+    /// without an `ORDER BY`). As with [`Self::evaluate`], `values` contains
+    /// the evaluation results of the function arguments followed by the
+    /// `ORDER BY` expressions.
+    ///
+    /// During bounded execution, a row is only evaluated once its peer
+    /// group is complete.
+    fn evaluate_with_peers(
+        &mut self,
+        values: &[ArrayRef],
+        range: &Range<usize>,
+        _peers: &Range<usize>,
+    ) -> Result<ScalarValue> {
+        self.evaluate(values, range)
+    }
+
     /// [`PartitionEvaluator::evaluate_all_with_rank`] is called for window
     /// functions that only need the rank of a row within its window
     /// frame.
@@ -253,4 +282,11 @@ pub trait PartitionEvaluator: Debug + Send {
     fn include_rank(&self) -> bool {
         false
     }
+
+    /// Does the function need the peer group of each row?
+    ///
+    /// See [`Self::evaluate_with_peers`]
+    fn include_peers(&self) -> bool {
+        false
+    }
 }
@@ -0,0 +1,154 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Row filters and column masks enforced on the scans of a table
+
+use std::collections::BTreeMap;
+
+use crate::{Expr, LogicalPlan, LogicalPlanBuilder};
+
+use datafusion_common::{plan_err, Column, Result};
+
+/// The access rules enforced on the scans of a table: a filter on the rows
+/// that may be read and expressions replacing the values of masked columns.
+///
+/// Both refer to the columns of the table by their unqualified names. For
+/// example, the following policy only returns the rows of tenant `42` and
+/// hides the `ssn` column:
+///
+/// ```
+/// # use datafusion_expr::{col, lit, TablePolicy};
+/// let policy = TablePolicy::new()
+///     .with_row_filter(col("tenant_id").eq(lit(42)))
+///     .with_column_mask("ssn", lit("***"));
+/// ```
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct TablePolicy {
+    /// Only the rows for which this predicate is true are read
+    pub row_filter: Option<Expr>,
+    /// The expressions replacing the values of the masked columns, by
+    /// column name
+    pub column_masks: BTreeMap<String, Expr>,
+}
+
+impl TablePolicy {
+    /// Create a policy that neither filters nor masks anything
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Only read the rows for which `predicate` is true. The predicate is
+    /// combined with any previous row filter with `AND`.
+    pub fn with_row_filter(mut self, predicate: Expr) -> Self {
+        self.row_filter = Some(match self.row_filter {
+            Some(row_filter) => row_filter.and(predicate),
+            None => predicate,
+        });
+        self
+    }
+
+    /// Replace the values of the column `name` by `mask`, which may refer to
+    /// any column of the table, including `name` itself
+    pub fn with_column_mask(mut self, name: impl Into<String>, mask: Expr) -> Self {
+        self.column_masks.insert(name.into(), mask);
+        self
+    }
+
+    /// Returns true if the policy neither filters nor masks anything
+    pub fn is_empty(&self) -> bool {
+        self.row_filter.is_none() && self.column_masks.is_empty()
+    }
+
+    /// Enforce the policy on `plan`, a scan of the table. The row filter is
+    /// applied first, so it sees the unmasked values. The result has the
+    /// columns of `plan`, the masked ones having the type of their mask.
+    pub fn apply(&self, plan: LogicalPlan) -> Result<LogicalPlan> {
+        let mut builder = LogicalPlanBuilder::from(plan);
+        if let Some(row_filter) = &self.row_filter {
+            builder = builder.filter(row_filter.clone())?;
+        }
+        if self.column_masks.is_empty() {
+            return builder.build();
+        }
+
+        let schema = builder.schema().clone();
+        if let Some(name) = self
+            .column_masks
+            .keys()
+            .find(|name| !schema.has_column_with_unqualified_name(name))
+        {
+            return plan_err!("Cannot mask column {name}: no such column");
+        }
+        let exprs = schema.iter().map(|(qualifier, field)| {
+            match self.column_masks.get(field.name()) {
+                Some(mask) => mask
+                    .clone()
+                    .alias_qualified(qualifier.cloned(), field.name()),
+                None => Expr::Column(Column::from((qualifier, field))),
+            }
+        });
+        builder.project(exprs)?.build()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::logical_plan::table_scan;
+    use crate::{col, lit};
+    use datafusion_common::TableReference;
+
+    use arrow::datatypes::{DataType, Field, Schema};
+
+    fn scan() -> Result<LogicalPlan> {
+        let schema = Schema::new(vec![
+            Field::new("tenant_id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, false),
+            Field::new("ssn", DataType::Utf8, false),
+        ]);
+        table_scan(Some("people"), &schema, None)?.build()
+    }
+
+    #[test]
+    fn apply_policy() -> Result<()> {
+        let policy = TablePolicy::new()
+            .with_row_filter(col("tenant_id").eq(lit(42)))
+            .with_column_mask("ssn", lit("***"));
+        let plan = policy.apply(scan()?)?;
+        let expected = "Projection: people.tenant_id, people.name, Utf8(\"***\") AS ssn\
+        \n  Filter: people.tenant_id = Int32(42)\
+        \n    TableScan: people";
+        assert_eq!(expected, format!("{plan:?}"));
+        let (qualifier, field) = plan.schema().qualified_field(2);
+        assert_eq!(qualifier, Some(&TableReference::bare("people")));
+        assert_eq!(field.name(), "ssn");
+
+        let plan = TablePolicy::new().apply(scan()?)?;
+        assert_eq!("TableScan: people", format!("{plan:?}"));
+        Ok(())
+    }
+
+    #[test]
+    fn mask_unknown_column() -> Result<()> {
+        let policy = TablePolicy::new().with_column_mask("email", lit("***"));
+        let err = policy.apply(scan()?).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("Cannot mask column email: no such column"));
+        Ok(())
+    }
+}
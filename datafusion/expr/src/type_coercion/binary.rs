@@ -303,6 +303,90 @@ pub fn comparison_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Option<D
         .or_else(|| string_numeric_coercion(lhs_type, rhs_type))
         .or_else(|| string_temporal_coercion(lhs_type, rhs_type))
         .or_else(|| binary_coercion(lhs_type, rhs_type))
+        .or_else(|| struct_coercion(lhs_type, rhs_type, comparison_coercion))
+}
+
+/// Coerce `lhs_type` and `rhs_type` to a common type for the values of a
+/// column of a `VALUES` list.
+///
+/// Unlike [`comparison_coercion`], strings and numbers are not coerced to
+/// each other, while the fields of structs and the elements of lists are
+/// coerced recursively.
+pub fn values_coercion(lhs_type: &DataType, rhs_type: &DataType) -> Option<DataType> {
+    if lhs_type == rhs_type {
+        return Some(lhs_type.clone());
+    }
+    // the lists are coerced before the strings, which keep the type of the
+    // first list
+    comparison_binary_numeric_coercion(lhs_type, rhs_type)
+        .or_else(|| temporal_coercion(lhs_type, rhs_type))
+        .or_else(|| struct_coercion(lhs_type, rhs_type, values_coercion))
+        .or_else(|| list_coercion(lhs_type, rhs_type, values_coercion))
+        .or_else(|| string_coercion(lhs_type, rhs_type))
+        .or_else(|| binary_coercion(lhs_type, rhs_type))
+        .or_else(|| null_coercion(lhs_type, rhs_type))
+}
+
+/// Coerce two structs with the same field names to a struct whose fields have
+/// the common types found by `coerce_fn`
+fn struct_coercion(
+    lhs_type: &DataType,
+    rhs_type: &DataType,
+    coerce_fn: fn(&DataType, &DataType) -> Option<DataType>,
+) -> Option<DataType> {
+    use arrow::datatypes::DataType::*;
+    match (lhs_type, rhs_type) {
+        (Struct(lhs_fields), Struct(rhs_fields))
+            if lhs_fields.len() == rhs_fields.len() =>
+        {
+            let fields = lhs_fields
+                .iter()
+                .zip(rhs_fields.iter())
+                .map(|(lhs_field, rhs_field)| {
+                    if lhs_field.name() != rhs_field.name() {
+                        return None;
+                    }
+                    let data_type =
+                        coerce_fn(lhs_field.data_type(), rhs_field.data_type())?;
+                    Some(Field::new(
+                        lhs_field.name(),
+                        data_type,
+                        lhs_field.is_nullable() || rhs_field.is_nullable(),
+                    ))
+                })
+                .collect::<Option<Vec<_>>>()?;
+            Some(Struct(fields.into()))
+        }
+        _ => None,
+    }
+}
+
+/// Coerce two lists to a list whose elements have the common type found by
+/// `coerce_fn`. A `List` and a `LargeList` are coerced to a `LargeList`.
+fn list_coercion(
+    lhs_type: &DataType,
+    rhs_type: &DataType,
+    coerce_fn: fn(&DataType, &DataType) -> Option<DataType>,
+) -> Option<DataType> {
+    use arrow::datatypes::DataType::*;
+    let coerce_field = |lhs_field: &Field, rhs_field: &Field| {
+        let data_type = coerce_fn(lhs_field.data_type(), rhs_field.data_type())?;
+        Some(Arc::new(Field::new(
+            lhs_field.name(),
+            data_type,
+            lhs_field.is_nullable() || rhs_field.is_nullable(),
+        )))
+    };
+    match (lhs_type, rhs_type) {
+        (List(lhs_field), List(rhs_field)) => {
+            Some(List(coerce_field(lhs_field, rhs_field)?))
+        }
+        (
+            List(lhs_field) | LargeList(lhs_field),
+            List(rhs_field) | LargeList(rhs_field),
+        ) => Some(LargeList(coerce_field(lhs_field, rhs_field)?)),
+        _ => None,
+    }
 }
 
 /// Coerce `lhs_type` and `rhs_type` to a common type for the purposes of a comparison operation
@@ -1521,4 +1605,54 @@ mod tests {
         );
         Ok(())
     }
+
+    #[test]
+    fn test_values_coercion() {
+        let struct_type = |c1: DataType| {
+            DataType::Struct(
+                vec![
+                    Field::new("c0", DataType::Utf8, true),
+                    Field::new("c1", c1, true),
+                ]
+                .into(),
+            )
+        };
+        let list_type =
+            |item: DataType| DataType::List(Arc::new(Field::new("item", item, true)));
+
+        assert_eq!(
+            values_coercion(&DataType::Int64, &DataType::Float64),
+            Some(DataType::Float64)
+        );
+        assert_eq!(values_coercion(&DataType::Int64, &DataType::Utf8), None);
+        assert_eq!(
+            values_coercion(
+                &struct_type(DataType::Int64),
+                &struct_type(DataType::Float64)
+            ),
+            Some(struct_type(DataType::Float64))
+        );
+        assert_eq!(
+            values_coercion(&struct_type(DataType::Null), &struct_type(DataType::Int32)),
+            Some(struct_type(DataType::Int32))
+        );
+        assert_eq!(
+            values_coercion(
+                &list_type(struct_type(DataType::Int64)),
+                &list_type(struct_type(DataType::Float64))
+            ),
+            Some(list_type(struct_type(DataType::Float64)))
+        );
+        // structs with different field names are not coerced
+        let other_struct_type =
+            DataType::Struct(vec![Field::new("x", DataType::Int64, true)].into());
+        assert_eq!(
+            values_coercion(&struct_type(DataType::Int64), &other_struct_type),
+            None
+        );
+        assert_eq!(
+            values_coercion(&struct_type(DataType::Int64), &struct_type(DataType::Utf8)),
+            None
+        );
+    }
 }
@@ -413,6 +413,22 @@ fn coerced_from<'a>(
         {
             Some(type_into.clone())
         }
+        // Structs can be coerced into structs with the same field names when
+        // each field can be coerced into the matching field
+        (Struct(fields_into), Struct(fields_from))
+            if fields_into.len() == fields_from.len()
+                && fields_into.iter().zip(fields_from.iter()).all(
+                    |(field_into, field_from)| {
+                        field_into.name() == field_from.name()
+                            && can_coerce_from(
+                                field_into.data_type(),
+                                field_from.data_type(),
+                            )
+                    },
+                ) =>
+        {
+            Some(type_into.clone())
+        }
         // should be able to coerce wildcard fixed size list to non wildcard fixed size list
         (FixedSizeList(f_into, FIXED_SIZE_LIST_WILDCARD), _) => match type_from {
             FixedSizeList(f_from, size_from) => {
@@ -23,7 +23,7 @@ use crate::{
     ScalarFunctionImplementation, Signature,
 };
 use arrow::datatypes::DataType;
-use datafusion_common::{ExprSchema, Result};
+use datafusion_common::{ExprSchema, Result, ScalarValue};
 use std::any::Any;
 use std::fmt;
 use std::fmt::Debug;
@@ -198,6 +198,17 @@ impl ScalarUDF {
     pub fn short_circuits(&self) -> bool {
         self.inner.short_circuits()
     }
+
+    /// Prepare the function for the invocations of a call with the constant
+    /// arguments `const_args`.
+    ///
+    /// See [`ScalarUDFImpl::prepare`] for more details.
+    pub fn prepare(
+        &self,
+        const_args: &[Option<ScalarValue>],
+    ) -> Result<Option<Arc<dyn PreparedScalarUDF>>> {
+        self.inner.prepare(const_args)
+    }
 }
 
 impl<F> From<F> for ScalarUDF
@@ -388,6 +399,35 @@ pub trait ScalarUDFImpl: Debug + Send + Sync {
     fn short_circuits(&self) -> bool {
         false
     }
+
+    /// Prepare the invocations of a call of this function, once, from its
+    /// constant arguments.
+    ///
+    /// `const_args` holds the value of each argument of the call that is a
+    /// literal, and `None` for the other arguments. The physical planner calls
+    /// this function once per call, and when it returns a
+    /// [`PreparedScalarUDF`] invokes it instead of [`Self::invoke`] for every
+    /// batch. This allows functions to, for example, compile a regular
+    /// expression or a JSON path given as a literal once rather than for
+    /// every batch.
+    ///
+    /// The prepared function must produce the same results as [`Self::invoke`]
+    /// (which is still used when the function is not prepared). Defaults to
+    /// `None`, i.e. no preparation.
+    fn prepare(
+        &self,
+        _const_args: &[Option<ScalarValue>],
+    ) -> Result<Option<Arc<dyn PreparedScalarUDF>>> {
+        Ok(None)
+    }
+}
+
+/// A call of a [`ScalarUDF`] prepared from its constant arguments, see
+/// [`ScalarUDFImpl::prepare`]
+pub trait PreparedScalarUDF: Debug + Send + Sync {
+    /// Invoke the function on `args` (all arguments of the call, including
+    /// the constant ones), returning the appropriate result
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue>;
 }
 
 /// ScalarUDF that adds an alias to the underlying function. It is better to
@@ -433,6 +473,13 @@ impl ScalarUDFImpl for AliasedScalarUDFImpl {
     fn aliases(&self) -> &[String] {
         &self.aliases
     }
+
+    fn prepare(
+        &self,
+        const_args: &[Option<ScalarValue>],
+    ) -> Result<Option<Arc<dyn PreparedScalarUDF>>> {
+        self.inner.prepare(const_args)
+    }
 }
 
 /// Implementation of [`ScalarUDFImpl`] that wraps the function style pointers
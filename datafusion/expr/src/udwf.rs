@@ -257,6 +257,14 @@ pub trait WindowUDFImpl: Debug + Send + Sync {
     fn return_type(&self, arg_types: &[DataType]) -> Result<DataType>;
 
     /// Invoke the function, returning the [`PartitionEvaluator`] instance
+    ///
+    /// The capabilities of the evaluator determine how the function is
+    /// evaluated: whether it needs the full partition or supports bounded,
+    /// incremental execution ([`PartitionEvaluator::supports_bounded_execution`]),
+    /// whether it is evaluated over a sliding window frame
+    /// ([`PartitionEvaluator::uses_window_frame`]), and whether it needs the
+    /// ranks ([`PartitionEvaluator::include_rank`]) or peer groups
+    /// ([`PartitionEvaluator::include_peers`]) of the rows.
     fn partition_evaluator(&self) -> Result<Box<dyn PartitionEvaluator>>;
 
     /// Returns any aliases (alternate names) for this function.
@@ -21,17 +21,19 @@ use std::cmp::Ordering;
 use std::collections::HashSet;
 use std::sync::Arc;
 
-use crate::expr::{Alias, Sort, WindowFunction};
+use crate::expr::{Alias, Case, Sort, WindowFunction};
 use crate::expr_rewriter::strip_outer_reference;
 use crate::logical_plan::Aggregate;
 use crate::signature::{Signature, TypeSignature};
 use crate::{
-    and, BinaryExpr, Cast, Expr, ExprSchemable, Filter, GroupingSet, LogicalPlan,
-    Operator, TryCast,
+    and, BinaryExpr, Cast, Expr, ExprSchemable, Filter, GroupingSet, JoinType,
+    LogicalPlan, Operator, TryCast,
 };
 
 use arrow::datatypes::{DataType, Field, Schema, TimeUnit};
-use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
+use datafusion_common::tree_node::{
+    Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
+};
 use datafusion_common::utils::get_at_indices;
 use datafusion_common::{
     internal_err, plan_datafusion_err, plan_err, Column, DFSchema, DFSchemaRef, Result,
@@ -44,6 +46,11 @@ use sqlparser::ast::{ExceptSelectItem, ExcludeSelectItem, WildcardAdditionalOpti
 ///  `COUNT(<constant>)` expressions
 pub const COUNT_STAR_EXPANSION: ScalarValue = ScalarValue::Int64(Some(1));
 
+/// The [`Field`] metadata key holding the name of the collation used to
+/// compare, sort and group the values of a string column
+/// (`CREATE TABLE t (name VARCHAR COLLATE "de-DE")`)
+pub const COLLATION_METADATA_KEY: &str = "datafusion.collation";
+
 /// Recursively walk a list of expression trees, collecting the unique set of columns
 /// referenced in the expression
 pub fn exprlist_to_columns(expr: &[Expr], accum: &mut HashSet<Column>) -> Result<()> {
@@ -372,33 +379,132 @@ fn get_exprs_except_skipped(
     }
 }
 
+/// The single output column that a `USING` join (or a chain of them) merges
+/// its equally named join columns into
+#[derive(Debug)]
+struct MergedUsingColumn {
+    /// The join column that takes the place of the merged column in the output
+    column: Column,
+    /// The value of the merged column, per the SQL standard: the left column
+    /// for inner and left joins, the right column for right joins and
+    /// `COALESCE(left, right)` for full joins
+    expr: Expr,
+    /// All the join columns merged into this one, including `column`
+    sources: Vec<Column>,
+}
+
+/// Returns the `USING` join columns of `plan` that are visible in `schema`,
+/// merged into one output column per join key
+fn merged_using_columns(
+    schema: &DFSchema,
+    plan: &LogicalPlan,
+) -> Result<Vec<MergedUsingColumn>> {
+    let mut merged: Vec<MergedUsingColumn> = vec![];
+    // joins are listed top-down; merge the innermost ones first so that the
+    // keys of chained joins merge into the same column
+    for (join_type, on) in plan.using_join_columns()?.into_iter().rev() {
+        for (l, r) in on {
+            if !schema.has_column(&l) || !schema.has_column(&r) {
+                continue;
+            }
+            let mut take =
+                |c: &Column| match merged.iter().position(|m| m.sources.contains(c)) {
+                    Some(idx) => merged.swap_remove(idx),
+                    None => MergedUsingColumn {
+                        column: c.clone(),
+                        expr: Expr::Column(c.clone()),
+                        sources: vec![c.clone()],
+                    },
+                };
+            let left = take(&l);
+            let right = take(&r);
+            let (column, expr) = match join_type {
+                JoinType::Right => (right.column, right.expr),
+                JoinType::Full => {
+                    let expr = Expr::Case(Case::new(
+                        None,
+                        vec![(
+                            Box::new(left.expr.clone().is_not_null()),
+                            Box::new(left.expr),
+                        )],
+                        Some(Box::new(right.expr)),
+                    ));
+                    (left.column, expr)
+                }
+                _ => (left.column, left.expr),
+            };
+            let mut sources = left.sources;
+            sources.extend(right.sources);
+            merged.push(MergedUsingColumn {
+                column,
+                expr,
+                sources,
+            });
+        }
+    }
+    Ok(merged)
+}
+
+/// Rewrites the unqualified references to `USING` join columns in `expr` into
+/// the value of the merged join column, e.g. `k` in
+/// `SELECT k FROM a FULL JOIN b USING (k)` into `COALESCE(a.k, b.k)`.
+///
+/// A reference that makes up the whole of `expr` keeps its name.
+pub fn rewrite_using_column_references(
+    expr: Expr,
+    schema: &DFSchema,
+    plan: &LogicalPlan,
+) -> Result<Expr> {
+    let merged = merged_using_columns(schema, plan)?;
+    if merged.is_empty() {
+        return Ok(expr);
+    }
+    let resolve = |name: &str| {
+        let candidates = schema.columns_with_unqualified_name(name);
+        merged.iter().find(|m| {
+            m.column.name == name && candidates.iter().all(|c| m.sources.contains(c))
+        })
+    };
+    if let Expr::Column(Column {
+        relation: None,
+        name,
+    }) = &expr
+    {
+        return Ok(match resolve(name).map(|m| &m.expr) {
+            Some(merged_expr @ Expr::Column(_)) => merged_expr.clone(),
+            Some(merged_expr) => merged_expr.clone().alias(name),
+            None => expr,
+        });
+    }
+    expr.transform_up(|expr| {
+        Ok(match &expr {
+            Expr::Column(Column {
+                relation: None,
+                name,
+            }) => match resolve(name) {
+                Some(m) => Transformed::yes(m.expr.clone()),
+                None => Transformed::no(expr),
+            },
+            _ => Transformed::no(expr),
+        })
+    })
+    .data()
+}
+
 /// Resolves an `Expr::Wildcard` to a collection of `Expr::Column`'s.
+///
+/// The join columns of each `USING` join are expanded to a single column
+/// holding the merged join key.
 pub fn expand_wildcard(
     schema: &DFSchema,
     plan: &LogicalPlan,
     wildcard_options: Option<&WildcardAdditionalOptions>,
 ) -> Result<Vec<Expr>> {
-    let using_columns = plan.using_columns()?;
-    let mut columns_to_skip = using_columns
-        .into_iter()
+    let merged = merged_using_columns(schema, plan)?;
+    let mut columns_to_skip = merged
+        .iter()
         // For each USING JOIN condition, only expand to one of each join column in projection
-        .flat_map(|cols| {
-            let mut cols = cols.into_iter().collect::<Vec<_>>();
-            // sort join columns to make sure we consistently keep the same
-            // qualified column
-            cols.sort();
-            let mut out_column_names: HashSet<String> = HashSet::new();
-            cols.into_iter()
-                .filter_map(|c| {
-                    if out_column_names.contains(&c.name) {
-                        Some(c)
-                    } else {
-                        out_column_names.insert(c.name);
-                        None
-                    }
-                })
-                .collect::<Vec<_>>()
-        })
+        .flat_map(|m| m.sources.iter().filter(|c| **c != m.column).cloned())
         .collect::<HashSet<_>>();
     let excluded_columns = if let Some(WildcardAdditionalOptions {
         opt_exclude,
@@ -412,7 +518,17 @@ pub fn expand_wildcard(
     };
     // Add each excluded `Column` to columns_to_skip
     columns_to_skip.extend(excluded_columns);
-    Ok(get_exprs_except_skipped(schema, columns_to_skip))
+    let exprs = get_exprs_except_skipped(schema, columns_to_skip)
+        .into_iter()
+        .map(|expr| match &expr {
+            Expr::Column(c) => match merged.iter().find(|m| m.column == *c) {
+                Some(m) if m.expr != expr => m.expr.clone().alias(&c.name),
+                _ => expr,
+            },
+            _ => expr,
+        })
+        .collect();
+    Ok(exprs)
 }
 
 /// Resolves an `Expr::Wildcard` to a collection of qualified `Expr::Column`'s.
@@ -207,6 +207,14 @@ impl WindowFrame {
         self.causal
     }
 
+    /// Get whether window frame spans the whole partition, from `UNBOUNDED
+    /// PRECEDING` to `UNBOUNDED FOLLOWING`, i.e. is the same for all the rows
+    /// of a partition
+    pub fn is_whole_partition(&self) -> bool {
+        matches!(&self.start_bound, WindowFrameBound::Preceding(value) if value.is_null())
+            && matches!(&self.end_bound, WindowFrameBound::Following(value) if value.is_null())
+    }
+
     /// Initializes window frame from units (type), start bound and end bound.
     pub fn new_bounds(
         units: WindowFrameUnits,
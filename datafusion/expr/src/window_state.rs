@@ -50,6 +50,9 @@ pub struct WindowAggState {
     pub n_row_result_missing: usize,
     /// flag indicating whether we have received all data for this partition
     pub is_end: bool,
+    /// The start of the peer group of the next row to calculate, for window
+    /// functions including peers. The rows of this group must not be pruned.
+    pub peer_group_start: Option<usize>,
 }
 
 impl WindowAggState {
@@ -60,6 +63,9 @@ impl WindowAggState {
         };
         self.last_calculated_index -= n_prune;
         self.offset_pruned_rows += n_prune;
+        if let Some(peer_group_start) = self.peer_group_start.as_mut() {
+            *peer_group_start -= n_prune;
+        }
 
         match self.window_frame_ctx.as_mut() {
             // Rows have no state do nothing
@@ -107,6 +113,7 @@ impl WindowAggState {
             out_col: empty_out_col,
             n_row_result_missing: 0,
             is_end: false,
+            peer_group_start: None,
         })
     }
 }
@@ -0,0 +1,327 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the APPROX_QUANTILES aggregation of several approximate quantiles
+//! of the values of a group, estimated by a t-digest.
+
+use arrow::array::{Array, ArrayRef, AsArray, Float64Array};
+use arrow::compute::cast;
+use arrow::datatypes::{DataType, Field, Float64Type};
+use datafusion_common::utils::array_into_list_array;
+use datafusion_common::{exec_err, plan_err, Result, ScalarValue};
+use datafusion_expr::function::AccumulatorArgs;
+use datafusion_expr::utils::format_state_name;
+use datafusion_expr::{Accumulator, AggregateUDFImpl, Expr, Signature, Volatility};
+use datafusion_physical_expr_common::aggregate::tdigest::{TDigest, DEFAULT_MAX_SIZE};
+use sqlparser::ast::NullTreatment;
+use std::any::Any;
+use std::mem::{size_of, size_of_val};
+use std::sync::Arc;
+
+make_udaf_function!(
+    ApproxQuantiles,
+    approx_quantiles,
+    "Returns the approximate quantiles of the values.",
+    approx_quantiles_udaf
+);
+
+/// `APPROX_QUANTILES(value, quantiles)` returns the list of the approximate
+/// quantiles of the numeric values of a group, one per element of the constant
+/// list of `quantiles` between 0 and 1, estimated by a [`TDigest`]:
+///
+/// ```sql
+/// SELECT approx_quantiles(latency, [0.5, 0.9, 0.99]) FROM requests
+/// ```
+///
+/// The quantiles are of the type of the values, as by
+/// `APPROX_PERCENTILE_CONT(value, quantile)`, computed from a single digest.
+/// The state is the serialized digest, see [`TDigest::to_scalar_state()`],
+/// with the quantiles, so that the partial states can be merged by a final
+/// aggregation which has no arguments, possibly in another process.
+#[derive(Debug)]
+pub struct ApproxQuantiles {
+    signature: Signature,
+}
+
+impl Default for ApproxQuantiles {
+    fn default() -> Self {
+        Self {
+            signature: Signature::any(2, Volatility::Immutable),
+        }
+    }
+}
+
+impl AggregateUDFImpl for ApproxQuantiles {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "approx_quantiles"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        if !is_quantile_type(&arg_types[0]) {
+            return plan_err!(
+                "APPROX_QUANTILES does not support the values of type {}",
+                arg_types[0]
+            );
+        }
+        match &arg_types[1] {
+            DataType::List(field) if field.data_type().is_numeric() => {}
+            other => {
+                return plan_err!(
+                    "APPROX_QUANTILES expects a list of numeric quantiles, got {other}"
+                )
+            }
+        }
+        Ok(DataType::new_list(arg_types[0].clone(), true))
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        let [value_type, _] = acc_args.input_types else {
+            return exec_err!("APPROX_QUANTILES expects 2 arguments");
+        };
+        Ok(Box::new(ApproxQuantilesAccumulator::new(
+            value_type.clone(),
+        )))
+    }
+
+    fn state_fields(
+        &self,
+        name: &str,
+        _value_type: DataType,
+        _ordering_fields: Vec<Field>,
+    ) -> Result<Vec<Field>> {
+        Ok(vec![
+            Field::new(format_state_name(name, "max_size"), DataType::UInt64, false),
+            Field::new(format_state_name(name, "sum"), DataType::Float64, false),
+            Field::new(format_state_name(name, "count"), DataType::Float64, false),
+            Field::new(format_state_name(name, "max"), DataType::Float64, false),
+            Field::new(format_state_name(name, "min"), DataType::Float64, false),
+            Field::new_list(
+                format_state_name(name, "centroids"),
+                Field::new("item", DataType::Float64, true),
+                false,
+            ),
+            Field::new_list(
+                format_state_name(name, "quantiles"),
+                Field::new("item", DataType::Float64, true),
+                true,
+            ),
+        ])
+    }
+}
+
+/// Returns true if the quantiles of the values of `data_type` can be estimated
+fn is_quantile_type(data_type: &DataType) -> bool {
+    matches!(
+        data_type,
+        DataType::Int8
+            | DataType::Int16
+            | DataType::Int32
+            | DataType::Int64
+            | DataType::UInt8
+            | DataType::UInt16
+            | DataType::UInt32
+            | DataType::UInt64
+            | DataType::Float32
+            | DataType::Float64
+    )
+}
+
+/// [`Accumulator`] of [`ApproxQuantiles`]
+#[derive(Debug)]
+struct ApproxQuantilesAccumulator {
+    digest: TDigest,
+    /// The quantiles, set by the first row of the arguments or of the states
+    quantiles: Option<Vec<f64>>,
+    value_type: DataType,
+}
+
+impl ApproxQuantilesAccumulator {
+    fn new(value_type: DataType) -> Self {
+        Self {
+            digest: TDigest::new(DEFAULT_MAX_SIZE),
+            quantiles: None,
+            value_type,
+        }
+    }
+
+    /// Sets the quantiles from the non null lists of `quantiles`, which must be
+    /// the same for all the rows
+    fn set_quantiles(&mut self, quantiles: &ArrayRef) -> Result<()> {
+        let lists = quantiles.as_list::<i32>();
+        let values = cast(lists.values(), &DataType::Float64)?;
+        let values = values.as_primitive::<Float64Type>();
+        for (index, offsets) in lists.value_offsets().windows(2).enumerate() {
+            if lists.is_null(index) {
+                continue;
+            }
+            let (start, end) = (offsets[0] as usize, offsets[1] as usize);
+            let row = &values.values()[start..end];
+            let valid = (start..end).all(|index| values.is_valid(index))
+                && row.iter().all(|q| (0.0..=1.0).contains(q));
+            if !valid || self.quantiles.as_ref().is_some_and(|q| q != row) {
+                return exec_err!(
+                    "APPROX_QUANTILES expects constant quantiles between 0.0 and 1.0"
+                );
+            }
+            if self.quantiles.is_none() {
+                self.quantiles = Some(row.to_vec());
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for ApproxQuantilesAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        self.set_quantiles(&values[1])?;
+        let values = cast(&values[0], &DataType::Float64)?;
+        let mut values = values
+            .as_primitive::<Float64Type>()
+            .iter()
+            .flatten()
+            .collect::<Vec<_>>();
+        values.sort_by(|a, b| a.total_cmp(b));
+        self.digest = self.digest.merge_sorted_f64(&values);
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let return_type = DataType::new_list(self.value_type.clone(), true);
+        let Some(quantiles) = &self.quantiles else {
+            return ScalarValue::try_from(return_type);
+        };
+        if self.digest.count() == 0.0 {
+            return ScalarValue::try_from(return_type);
+        }
+        let estimates = quantiles
+            .iter()
+            .map(|q| self.digest.estimate_quantile(*q))
+            .collect::<Float64Array>();
+        let estimates = cast(&estimates, &self.value_type)?;
+        Ok(ScalarValue::List(Arc::new(array_into_list_array(
+            estimates,
+        ))))
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self) + self.digest.size() - size_of_val(&self.digest)
+            + self
+                .quantiles
+                .as_ref()
+                .map_or(0, |q| q.capacity() * size_of::<f64>())
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let quantiles = match &self.quantiles {
+            Some(quantiles) => {
+                let quantiles = Float64Array::from(quantiles.clone());
+                ScalarValue::List(Arc::new(array_into_list_array(Arc::new(quantiles))))
+            }
+            None => ScalarValue::try_from(DataType::new_list(DataType::Float64, true))?,
+        };
+        let mut state = self.digest.to_scalar_state();
+        state.push(quantiles);
+        Ok(state)
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let [digests @ .., quantiles] = states else {
+            return exec_err!("APPROX_QUANTILES expects a non empty state");
+        };
+        self.set_quantiles(quantiles)?;
+        let digests = (0..quantiles.len())
+            .map(|index| {
+                digests
+                    .iter()
+                    .map(|array| ScalarValue::try_from_array(array, index))
+                    .collect::<Result<Vec<_>>>()
+                    .map(|state| TDigest::from_scalar_state(&state))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        self.digest = TDigest::merge_digests(digests.iter().chain([&self.digest]));
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int64Array, ListArray};
+    use arrow::datatypes::Int64Type;
+
+    fn quantiles(rows: usize) -> ArrayRef {
+        Arc::new(ListArray::from_iter_primitive::<Float64Type, _, _>(
+            (0..rows).map(|_| Some(vec![Some(0.1), Some(0.5), Some(0.9)])),
+        ))
+    }
+
+    #[test]
+    fn approx_quantiles_merge() -> Result<()> {
+        // The partial states of 1..=500 and 501..=1000 are merged
+        let mut result = ApproxQuantilesAccumulator::new(DataType::Int64);
+        for values in [1..=500, 501..=1000] {
+            let mut partial = ApproxQuantilesAccumulator::new(DataType::Int64);
+            let values: ArrayRef = Arc::new(values.map(Some).collect::<Int64Array>());
+            partial.update_batch(&[values.clone(), quantiles(values.len())])?;
+            let state = partial
+                .state()?
+                .iter()
+                .map(|state| state.to_array())
+                .collect::<Result<Vec<_>>>()?;
+            result.merge_batch(&state)?;
+        }
+        let ScalarValue::List(result) = result.evaluate()? else {
+            unreachable!()
+        };
+        let result = result.value(0);
+        let result = result.as_primitive::<Int64Type>();
+        for (estimate, expected) in result.values().iter().zip([100, 500, 900]) {
+            assert!(
+                (estimate - expected).abs() <= 10,
+                "{estimate} != {expected}"
+            );
+        }
+
+        // The quantiles of no rows are null
+        let mut empty = ApproxQuantilesAccumulator::new(DataType::Int64);
+        let return_type = DataType::new_list(DataType::Int64, true);
+        assert_eq!(empty.evaluate()?, ScalarValue::try_from(return_type)?);
+        Ok(())
+    }
+
+    #[test]
+    fn approx_quantiles_invalid_quantiles() -> Result<()> {
+        let mut accumulator = ApproxQuantilesAccumulator::new(DataType::Int64);
+        let values: ArrayRef = Arc::new(Int64Array::from(vec![1, 2]));
+        let quantiles: ArrayRef =
+            Arc::new(ListArray::from_iter_primitive::<Float64Type, _, _>(vec![
+                Some(vec![Some(0.5)]),
+                Some(vec![Some(1.5)]),
+            ]));
+        let err = accumulator.update_batch(&[values, quantiles]).unwrap_err();
+        assert!(err.to_string().contains("constant quantiles"), "{err}");
+        Ok(())
+    }
+}
@@ -0,0 +1,306 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the ARG_MAX/ARG_MIN aggregations of the value of the row of each
+//! group with the largest or smallest key.
+
+use arrow::array::{Array, ArrayRef, AsArray, BooleanArray, UInt32Array};
+use arrow::compute::take;
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::{exec_err, Result, ScalarValue};
+use datafusion_expr::function::AccumulatorArgs;
+use datafusion_expr::utils::format_state_name;
+use datafusion_expr::{
+    Accumulator, AggregateUDFImpl, EmitTo, Expr, GroupsAccumulator, Signature, Volatility,
+};
+use sqlparser::ast::NullTreatment;
+use std::any::Any;
+use std::mem::size_of;
+
+use crate::top_k::TopKByGroupsAccumulator;
+
+make_udaf_function!(
+    ArgMax,
+    arg_max,
+    "Returns the value of the row with the largest key.",
+    arg_max_udaf
+);
+
+make_udaf_function!(
+    ArgMin,
+    arg_min,
+    "Returns the value of the row with the smallest key.",
+    arg_min_udaf
+);
+
+/// `ARG_MAX(value, key)`
+pub type ArgMax = ArgMinMax<true>;
+
+/// `ARG_MIN(value, key)`
+pub type ArgMin = ArgMinMax<false>;
+
+/// Returns the value of the row of a group with the largest key, if `MAX`, or
+/// the smallest key otherwise. The rows with a null key are ignored.
+///
+/// The value may be the struct of all the columns of the row, to select the
+/// latest row of each group without joining the table with the latest keys:
+///
+/// ```sql
+/// SELECT user_id, arg_max(struct(*), updated_at) FROM users GROUP BY user_id
+/// ```
+///
+/// Only the value of the selected row of each group is kept, as by
+/// `MAX_BY(value, key, 1)`.
+#[derive(Debug)]
+pub struct ArgMinMax<const MAX: bool> {
+    signature: Signature,
+}
+
+impl<const MAX: bool> Default for ArgMinMax<MAX> {
+    fn default() -> Self {
+        Self {
+            signature: Signature::any(2, Volatility::Immutable),
+        }
+    }
+}
+
+impl<const MAX: bool> AggregateUDFImpl for ArgMinMax<MAX> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        if MAX {
+            "arg_max"
+        } else {
+            "arg_min"
+        }
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Ok(arg_types[0].clone())
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(ArgMinMaxAccumulator {
+            inner: self.groups_accumulator(&acc_args)?,
+        }))
+    }
+
+    fn state_fields(
+        &self,
+        name: &str,
+        value_type: DataType,
+        _ordering_fields: Vec<Field>,
+    ) -> Result<Vec<Field>> {
+        Ok(vec![
+            Field::new_list(
+                format_state_name(name, "values"),
+                Field::new("item", value_type, true),
+                true,
+            ),
+            Field::new_list(
+                format_state_name(name, "keys"),
+                Field::new("item", DataType::Binary, true),
+                true,
+            ),
+        ])
+    }
+
+    fn groups_accumulator_supported(&self) -> bool {
+        true
+    }
+
+    fn create_groups_accumulator(
+        &self,
+        acc_args: AccumulatorArgs,
+    ) -> Result<Box<dyn GroupsAccumulator>> {
+        Ok(Box::new(self.groups_accumulator(&acc_args)?))
+    }
+}
+
+impl<const MAX: bool> ArgMinMax<MAX> {
+    fn groups_accumulator(
+        &self,
+        acc_args: &AccumulatorArgs,
+    ) -> Result<ArgMinMaxGroupsAccumulator> {
+        let [value_type, key_type] = acc_args.input_types else {
+            return exec_err!("{} expects 2 arguments", self.name());
+        };
+        let inner = TopKByGroupsAccumulator::try_new(value_type, key_type, MAX)?;
+        Ok(ArgMinMaxGroupsAccumulator {
+            inner: inner.with_k(1),
+        })
+    }
+}
+
+/// [`GroupsAccumulator`] of [`ArgMinMax`], the top row of each group
+#[derive(Debug)]
+struct ArgMinMaxGroupsAccumulator {
+    inner: TopKByGroupsAccumulator,
+}
+
+impl GroupsAccumulator for ArgMinMaxGroupsAccumulator {
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        self.inner
+            .update_batch(values, group_indices, opt_filter, total_num_groups)
+    }
+
+    fn evaluate(&mut self, emit_to: EmitTo) -> Result<ArrayRef> {
+        // The lists of the values of the groups have at most 1 value
+        let lists = self.inner.evaluate(emit_to)?;
+        let lists = lists.as_list::<i32>();
+        let indices = lists
+            .value_offsets()
+            .iter()
+            .enumerate()
+            .take(lists.len())
+            .map(|(index, offset)| lists.is_valid(index).then_some(*offset as u32))
+            .collect::<UInt32Array>();
+        Ok(take(lists.values(), &indices, None)?)
+    }
+
+    fn state(&mut self, emit_to: EmitTo) -> Result<Vec<ArrayRef>> {
+        self.inner.state(emit_to)
+    }
+
+    fn merge_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        self.inner
+            .merge_batch(values, group_indices, opt_filter, total_num_groups)
+    }
+
+    fn size(&self) -> usize {
+        self.inner.size()
+    }
+}
+
+/// [`Accumulator`] of [`ArgMinMax`], the [`ArgMinMaxGroupsAccumulator`] of a
+/// single group
+#[derive(Debug)]
+struct ArgMinMaxAccumulator {
+    inner: ArgMinMaxGroupsAccumulator,
+}
+
+impl Accumulator for ArgMinMaxAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let group_indices = vec![0; values[0].len()];
+        self.inner.update_batch(values, &group_indices, None, 1)
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let values = self.inner.evaluate(EmitTo::All)?;
+        ScalarValue::try_from_array(&values, 0)
+    }
+
+    fn size(&self) -> usize {
+        size_of::<Self>() + self.inner.size()
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        self.inner
+            .state(EmitTo::All)?
+            .iter()
+            .map(|state| ScalarValue::try_from_array(state, 0))
+            .collect()
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let group_indices = vec![0; states[0].len()];
+        self.inner.merge_batch(states, &group_indices, None, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int64Array, StringArray, StructArray};
+    use arrow::datatypes::Fields;
+    use std::sync::Arc;
+
+    #[test]
+    fn arg_max_struct() -> Result<()> {
+        let fields = Fields::from(vec![
+            Field::new("name", DataType::Utf8, true),
+            Field::new("ts", DataType::Int64, true),
+        ]);
+        let rows = StructArray::new(
+            fields.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["a", "b", "c", "d"])),
+                Arc::new(Int64Array::from(vec![Some(1), Some(3), Some(2), None])),
+            ],
+            None,
+        );
+        let rows: ArrayRef = Arc::new(rows);
+        let keys = rows.as_struct().column(1).clone();
+
+        let value_type = DataType::Struct(fields);
+        let accumulator = |max| -> Result<ArgMinMaxGroupsAccumulator> {
+            let inner =
+                TopKByGroupsAccumulator::try_new(&value_type, &DataType::Int64, max)?;
+            Ok(ArgMinMaxGroupsAccumulator {
+                inner: inner.with_k(1),
+            })
+        };
+
+        // The partial states of rows 0..2 and 2..4 are merged
+        let mut result = accumulator(true)?;
+        for offset in [0, 2] {
+            let mut partial = accumulator(true)?;
+            partial.update_batch(
+                &[rows.slice(offset, 2), keys.slice(offset, 2)],
+                &[0, 1],
+                None,
+                2,
+            )?;
+            result.merge_batch(&partial.state(EmitTo::All)?, &[0, 1], None, 2)?;
+        }
+        let result = result.evaluate(EmitTo::All)?;
+        // Group 0 has the rows "a" and "c", group 1 "b" and "d" with a null key
+        assert_eq!(
+            result.as_ref(),
+            take(&rows, &UInt32Array::from(vec![2, 1]), None)?.as_ref()
+        );
+
+        let mut result = accumulator(false)?;
+        result.update_batch(&[rows.clone(), keys], &[0, 0, 1, 1], None, 3)?;
+        let result = result.evaluate(EmitTo::All)?;
+        let expected = take(
+            &rows,
+            &UInt32Array::from(vec![Some(0), Some(2), None]),
+            None,
+        )?;
+        assert_eq!(result.as_ref(), expected.as_ref());
+        Ok(())
+    }
+}
@@ -55,7 +55,10 @@
 #[macro_use]
 pub mod macros;
 
+pub mod approx_quantiles;
+pub mod arg_min_max;
 pub mod first_last;
+pub mod top_k;
 
 use datafusion_common::Result;
 use datafusion_execution::FunctionRegistry;
@@ -65,12 +68,24 @@ use std::sync::Arc;
 
 /// Fluent-style API for creating `Expr`s
 pub mod expr_fn {
+    pub use super::approx_quantiles::approx_quantiles;
+    pub use super::arg_min_max::arg_max;
+    pub use super::arg_min_max::arg_min;
     pub use super::first_last::first_value;
+    pub use super::top_k::max_by;
+    pub use super::top_k::min_by;
 }
 
 /// Registers all enabled packages with a [`FunctionRegistry`]
 pub fn register_all(registry: &mut dyn FunctionRegistry) -> Result<()> {
-    let functions: Vec<Arc<AggregateUDF>> = vec![first_last::first_value_udaf()];
+    let functions: Vec<Arc<AggregateUDF>> = vec![
+        approx_quantiles::approx_quantiles_udaf(),
+        arg_min_max::arg_max_udaf(),
+        arg_min_max::arg_min_udaf(),
+        first_last::first_value_udaf(),
+        top_k::max_by_udaf(),
+        top_k::min_by_udaf(),
+    ];
 
     functions.into_iter().try_for_each(|udf| {
         let existing_udaf = registry.register_udaf(udf)?;
@@ -0,0 +1,587 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the MAX_BY/MIN_BY aggregations of the values of the top `k` rows
+//! of each group by a key.
+
+use arrow::array::{
+    new_empty_array, Array, ArrayRef, AsArray, BinaryBuilder, BooleanArray,
+    BooleanBufferBuilder, ListArray, UInt64Array,
+};
+use arrow::buffer::{NullBuffer, OffsetBuffer};
+use arrow::compute::{cast, SortOptions};
+use arrow::datatypes::{DataType, Field, Int64Type, UInt64Type};
+use arrow::row::{RowConverter, SortField};
+use datafusion_common::{exec_err, plan_err, Result, ScalarValue};
+use datafusion_expr::function::AccumulatorArgs;
+use datafusion_expr::utils::format_state_name;
+use datafusion_expr::{
+    Accumulator, AggregateUDFImpl, EmitTo, Expr, GroupsAccumulator, Signature, Volatility,
+};
+use sqlparser::ast::NullTreatment;
+use std::any::Any;
+use std::cmp::Ordering;
+use std::collections::BinaryHeap;
+use std::mem::size_of;
+use std::sync::Arc;
+
+make_udaf_function!(
+    MaxBy,
+    max_by,
+    "Returns the values of the `k` rows with the largest keys, by descending key.",
+    max_by_udaf
+);
+
+make_udaf_function!(
+    MinBy,
+    min_by,
+    "Returns the values of the `k` rows with the smallest keys, by ascending key.",
+    min_by_udaf
+);
+
+/// `MAX_BY(value, key, k)`, also named `TOP_K_BY`
+pub type MaxBy = TopKBy<true>;
+
+/// `MIN_BY(value, key, k)`
+pub type MinBy = TopKBy<false>;
+
+/// Returns the list of the values of the `k` rows of a group with the largest
+/// keys, if `DESCENDING`, or the smallest keys otherwise, ordered by their
+/// keys. The rows with a null key are ignored.
+///
+/// The top rows of each group are kept in a heap of at most `k` rows, instead
+/// of sorting all the rows of the groups as a window function does:
+///
+/// ```sql
+/// SELECT day, max_by(campaign, clicks, 10) FROM stats GROUP BY day
+/// ```
+#[derive(Debug)]
+pub struct TopKBy<const DESCENDING: bool> {
+    signature: Signature,
+    aliases: Vec<String>,
+}
+
+impl<const DESCENDING: bool> Default for TopKBy<DESCENDING> {
+    fn default() -> Self {
+        let aliases = if DESCENDING {
+            vec![String::from("top_k_by")]
+        } else {
+            vec![]
+        };
+        Self {
+            signature: Signature::any(3, Volatility::Immutable),
+            aliases,
+        }
+    }
+}
+
+impl<const DESCENDING: bool> AggregateUDFImpl for TopKBy<DESCENDING> {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        if DESCENDING {
+            "max_by"
+        } else {
+            "min_by"
+        }
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        if !arg_types[2].is_integer() {
+            return plan_err!(
+                "{} expects an integer k, got {}",
+                self.name(),
+                arg_types[2]
+            );
+        }
+        Ok(list_type(&arg_types[0]))
+    }
+
+    fn accumulator(&self, acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(TopKByAccumulator {
+            inner: self.groups_accumulator(&acc_args)?,
+        }))
+    }
+
+    fn state_fields(
+        &self,
+        name: &str,
+        value_type: DataType,
+        _ordering_fields: Vec<Field>,
+    ) -> Result<Vec<Field>> {
+        Ok(vec![
+            Field::new(format_state_name(name, "values"), value_type, true),
+            Field::new(
+                format_state_name(name, "keys"),
+                list_type(&DataType::Binary),
+                true,
+            ),
+            Field::new(format_state_name(name, "k"), DataType::UInt64, true),
+        ])
+    }
+
+    fn groups_accumulator_supported(&self) -> bool {
+        true
+    }
+
+    fn create_groups_accumulator(
+        &self,
+        acc_args: AccumulatorArgs,
+    ) -> Result<Box<dyn GroupsAccumulator>> {
+        Ok(Box::new(self.groups_accumulator(&acc_args)?))
+    }
+
+    fn aliases(&self) -> &[String] {
+        &self.aliases
+    }
+}
+
+impl<const DESCENDING: bool> TopKBy<DESCENDING> {
+    fn groups_accumulator(
+        &self,
+        acc_args: &AccumulatorArgs,
+    ) -> Result<TopKByGroupsAccumulator> {
+        let [value_type, key_type, _] = acc_args.input_types else {
+            return exec_err!("{} expects 3 arguments", self.name());
+        };
+        TopKByGroupsAccumulator::try_new(value_type, key_type, DESCENDING)
+    }
+}
+
+/// The type of the lists of `item_type`
+fn list_type(item_type: &DataType) -> DataType {
+    DataType::List(Arc::new(Field::new("item", item_type.clone(), true)))
+}
+
+/// A row of the top `k` rows of a group
+#[derive(Debug)]
+struct TopKRow {
+    /// The key, in the row format
+    key: Box<[u8]>,
+    value: ScalarValue,
+}
+
+impl PartialEq for TopKRow {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl Eq for TopKRow {}
+
+impl PartialOrd for TopKRow {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl Ord for TopKRow {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.key.cmp(&other.key)
+    }
+}
+
+/// The top `k` rows of a group: the rows with the smallest keys in the row
+/// format, whose order is the order of the output
+#[derive(Debug, Default)]
+struct TopKHeap {
+    /// Max heap of the rows, whose top is replaced by the smaller rows
+    heap: BinaryHeap<TopKRow>,
+    /// The memory size of the rows
+    size: usize,
+}
+
+impl TopKHeap {
+    /// Adds the row of `key` to the top `k` rows, if it is one of them, with
+    /// the value returned by `value`
+    fn push(
+        &mut self,
+        key: &[u8],
+        k: usize,
+        value: impl FnOnce() -> Result<ScalarValue>,
+    ) -> Result<()> {
+        if self.heap.len() >= k {
+            match self.heap.peek() {
+                Some(top) if key < top.key.as_ref() => {
+                    let top = self.heap.pop().unwrap();
+                    self.size -= top.key.len() + top.value.size();
+                }
+                _ => return Ok(()),
+            }
+        }
+        let row = TopKRow {
+            key: key.into(),
+            value: value()?,
+        };
+        self.size += row.key.len() + row.value.size();
+        self.heap.push(row);
+        Ok(())
+    }
+}
+
+/// [`GroupsAccumulator`] of [`TopKBy`], keeping the top `k` rows of each
+/// group in a [`TopKHeap`].
+///
+/// `k` is the third argument, and the third state field, unless it is fixed
+/// by [`Self::with_k`].
+#[derive(Debug)]
+pub(crate) struct TopKByGroupsAccumulator {
+    groups: Vec<TopKHeap>,
+    /// The number of rows kept per group, once known from the arguments
+    k: Option<usize>,
+    /// Whether `k` is fixed instead of being an argument
+    fixed_k: bool,
+    /// The type of the values
+    value_type: DataType,
+    /// Converts the keys to the row format, ordered as the output
+    converter: RowConverter,
+    /// The memory size of the rows of `groups`
+    size: usize,
+}
+
+impl TopKByGroupsAccumulator {
+    pub(crate) fn try_new(
+        value_type: &DataType,
+        key_type: &DataType,
+        descending: bool,
+    ) -> Result<Self> {
+        let sort_field = SortField::new_with_options(
+            key_type.clone(),
+            SortOptions {
+                descending,
+                nulls_first: false,
+            },
+        );
+        Ok(Self {
+            groups: vec![],
+            k: None,
+            fixed_k: false,
+            value_type: value_type.clone(),
+            converter: RowConverter::new(vec![sort_field])?,
+            size: 0,
+        })
+    }
+
+    /// Keeps the top `k` rows of each group, without a `k` argument
+    pub(crate) fn with_k(mut self, k: usize) -> Self {
+        self.k = Some(k);
+        self.fixed_k = true;
+        self
+    }
+
+    /// Sets the number of rows kept per group from `k`, which must be a
+    /// positive integer, the same for all the rows
+    fn set_k(&mut self, k: &ArrayRef) -> Result<Option<usize>> {
+        let k = cast(k, &DataType::Int64)?;
+        let k = k.as_primitive::<Int64Type>();
+        if k.is_empty() {
+            return Ok(self.k);
+        }
+        let value = k.value(0);
+        if k.null_count() > 0
+            || value <= 0
+            || k.values().iter().any(|other| *other != value)
+        {
+            return exec_err!("MAX_BY/MIN_BY expect a constant positive k");
+        }
+        self.k = Some(value as usize);
+        Ok(self.k)
+    }
+
+    /// Adds the row of `key` and of the value at `index` of `values` to the
+    /// top rows of `group_index`
+    fn push(
+        &mut self,
+        group_index: usize,
+        key: &[u8],
+        k: usize,
+        values: &ArrayRef,
+        index: usize,
+    ) -> Result<()> {
+        let group = &mut self.groups[group_index];
+        let size = group.size;
+        group.push(key, k, || ScalarValue::try_from_array(values, index))?;
+        self.size = self.size + group.size - size;
+        Ok(())
+    }
+
+    /// Removes the groups of `emit_to`, returning the lists of the values and
+    /// the keys of their top rows, null for the groups without rows
+    fn emit(&mut self, emit_to: EmitTo) -> Result<(ArrayRef, ArrayRef)> {
+        let groups = emit_to.take_needed(&mut self.groups);
+        let mut lengths = Vec::with_capacity(groups.len());
+        let mut nulls = BooleanBufferBuilder::new(groups.len());
+        let mut values = vec![];
+        let mut keys = BinaryBuilder::new();
+        for group in groups {
+            self.size -= group.size;
+            let rows = group.heap.into_sorted_vec();
+            nulls.append(!rows.is_empty());
+            lengths.push(rows.len());
+            for row in rows {
+                keys.append_value(&row.key);
+                values.push(row.value);
+            }
+        }
+        let values = if values.is_empty() {
+            new_empty_array(&self.value_type)
+        } else {
+            ScalarValue::iter_to_array(values)?
+        };
+        let offsets = OffsetBuffer::from_lengths(lengths);
+        let nulls = Some(NullBuffer::new(nulls.finish()));
+        let values = ListArray::try_new(
+            Arc::new(Field::new("item", self.value_type.clone(), true)),
+            offsets.clone(),
+            values,
+            nulls.clone(),
+        )?;
+        let keys = ListArray::try_new(
+            Arc::new(Field::new("item", DataType::Binary, true)),
+            offsets,
+            Arc::new(keys.finish()),
+            nulls,
+        )?;
+        Ok((Arc::new(values), Arc::new(keys)))
+    }
+}
+
+impl GroupsAccumulator for TopKByGroupsAccumulator {
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        self.groups.resize_with(total_num_groups, TopKHeap::default);
+        let k = if self.fixed_k {
+            self.k
+        } else {
+            self.set_k(&values[2])?
+        };
+        let Some(k) = k else {
+            return Ok(());
+        };
+        let keys = self.converter.convert_columns(&values[1..2])?;
+        for (index, group_index) in group_indices.iter().enumerate() {
+            if values[1].is_null(index)
+                || opt_filter.is_some_and(|filter| !filter.value(index))
+            {
+                continue;
+            }
+            self.push(*group_index, keys.row(index).as_ref(), k, &values[0], index)?;
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self, emit_to: EmitTo) -> Result<ArrayRef> {
+        Ok(self.emit(emit_to)?.0)
+    }
+
+    fn state(&mut self, emit_to: EmitTo) -> Result<Vec<ArrayRef>> {
+        let (values, keys) = self.emit(emit_to)?;
+        if self.fixed_k {
+            return Ok(vec![values, keys]);
+        }
+        let k = self.k.map(|k| k as u64);
+        let k = UInt64Array::from(vec![k; values.len()]);
+        Ok(vec![values, keys, Arc::new(k)])
+    }
+
+    fn merge_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        self.groups.resize_with(total_num_groups, TopKHeap::default);
+        let value_lists = values[0].as_list::<i32>();
+        let key_lists = values[1].as_list::<i32>();
+        let ks = (!self.fixed_k).then(|| values[2].as_primitive::<UInt64Type>());
+        for (index, group_index) in group_indices.iter().enumerate() {
+            let k = match ks {
+                // The partial groups without rows don't know k
+                Some(ks) => ks.is_valid(index).then(|| ks.value(index) as usize),
+                None => self.k,
+            };
+            let Some(k) = k else {
+                continue;
+            };
+            if key_lists.is_null(index)
+                || opt_filter.is_some_and(|filter| !filter.value(index))
+            {
+                continue;
+            }
+            self.k = Some(k);
+            let group_values = value_lists.value(index);
+            let group_keys = key_lists.value(index);
+            let group_keys = group_keys.as_binary::<i32>();
+            for row in 0..group_keys.len() {
+                self.push(*group_index, group_keys.value(row), k, &group_values, row)?;
+            }
+        }
+        Ok(())
+    }
+
+    fn size(&self) -> usize {
+        self.size + self.groups.capacity() * size_of::<TopKHeap>() + self.converter.size()
+    }
+}
+
+/// [`Accumulator`] of [`TopKBy`], the [`TopKByGroupsAccumulator`] of a
+/// single group
+#[derive(Debug)]
+struct TopKByAccumulator {
+    inner: TopKByGroupsAccumulator,
+}
+
+impl Accumulator for TopKByAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let group_indices = vec![0; values[0].len()];
+        self.inner.update_batch(values, &group_indices, None, 1)
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let values = self.inner.evaluate(EmitTo::All)?;
+        ScalarValue::try_from_array(&values, 0)
+    }
+
+    fn size(&self) -> usize {
+        size_of::<Self>() + self.inner.size()
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        self.inner
+            .state(EmitTo::All)?
+            .iter()
+            .map(|state| ScalarValue::try_from_array(state, 0))
+            .collect()
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let group_indices = vec![0; states[0].len()];
+        self.inner.merge_batch(states, &group_indices, None, 1)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::{Int64Array, StringArray};
+
+    fn groups_accumulator(descending: bool) -> Result<TopKByGroupsAccumulator> {
+        TopKByGroupsAccumulator::try_new(&DataType::Utf8, &DataType::Int64, descending)
+    }
+
+    fn batch(values: Vec<&str>, keys: Vec<Option<i64>>) -> Vec<ArrayRef> {
+        let k = Int64Array::from(vec![2; values.len()]);
+        vec![
+            Arc::new(StringArray::from(values)),
+            Arc::new(Int64Array::from(keys)),
+            Arc::new(k),
+        ]
+    }
+
+    fn lists(array: &ArrayRef) -> Vec<Option<Vec<Option<String>>>> {
+        array
+            .as_list::<i32>()
+            .iter()
+            .map(|list| {
+                list.map(|list| {
+                    list.as_string::<i32>()
+                        .iter()
+                        .map(|value| value.map(String::from))
+                        .collect()
+                })
+            })
+            .collect()
+    }
+
+    fn some(values: &[&str]) -> Option<Vec<Option<String>>> {
+        Some(values.iter().map(|value| Some(value.to_string())).collect())
+    }
+
+    #[test]
+    fn top_k_by_groups() -> Result<()> {
+        let values = batch(
+            vec!["a", "b", "c", "d", "e", "f"],
+            vec![Some(1), Some(5), Some(3), Some(4), None, Some(2)],
+        );
+        let group_indices = [0, 0, 0, 1, 2, 1];
+
+        let mut max_by = groups_accumulator(true)?;
+        max_by.update_batch(&values, &group_indices, None, 3)?;
+        let result = max_by.evaluate(EmitTo::All)?;
+        assert_eq!(
+            lists(&result),
+            vec![some(&["b", "c"]), some(&["d", "f"]), None]
+        );
+        assert_eq!(max_by.size, 0);
+
+        let mut min_by = groups_accumulator(false)?;
+        min_by.update_batch(&values, &group_indices, None, 3)?;
+        let result = min_by.evaluate(EmitTo::All)?;
+        assert_eq!(
+            lists(&result),
+            vec![some(&["a", "c"]), some(&["f", "d"]), None]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn top_k_by_merge() -> Result<()> {
+        let mut partial1 = groups_accumulator(true)?;
+        partial1.update_batch(
+            &batch(vec!["a", "b", "c"], vec![Some(1), Some(5), Some(3)]),
+            &[0, 0, 0],
+            None,
+            1,
+        )?;
+        let mut partial2 = groups_accumulator(true)?;
+        partial2.update_batch(
+            &batch(vec!["d", "e"], vec![Some(4), Some(2)]),
+            &[0, 0],
+            None,
+            2,
+        )?;
+
+        let mut fin = groups_accumulator(true)?;
+        fin.merge_batch(&partial1.state(EmitTo::All)?, &[0], None, 1)?;
+        fin.merge_batch(&partial2.state(EmitTo::All)?, &[0, 1], None, 2)?;
+        let result = fin.evaluate(EmitTo::All)?;
+        assert_eq!(lists(&result), vec![some(&["b", "d"]), None]);
+        Ok(())
+    }
+
+    #[test]
+    fn top_k_by_invalid_k() -> Result<()> {
+        let mut max_by = groups_accumulator(true)?;
+        let mut values = batch(vec!["a"], vec![Some(1)]);
+        values[2] = Arc::new(Int64Array::from(vec![0]));
+        let err = max_by.update_batch(&values, &[0], None, 1).unwrap_err();
+        assert!(err.to_string().contains("constant positive k"));
+        Ok(())
+    }
+}
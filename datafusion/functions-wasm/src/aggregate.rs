@@ -0,0 +1,252 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Aggregate functions compiled to WebAssembly
+
+use std::any::Any;
+use std::mem::size_of_val;
+
+use arrow::array::ArrayRef;
+use arrow::datatypes::DataType;
+use datafusion_common::{plan_err, Result, ScalarValue};
+use datafusion_expr::function::AccumulatorArgs;
+use datafusion_expr::{
+    Accumulator, AggregateUDF, AggregateUDFImpl, Signature, Volatility,
+};
+use wasmtime::Val;
+
+use crate::{array_value, call, scalar_to_value, value_to_scalar, WasmModule};
+
+impl WasmModule {
+    /// Create an aggregate function `name` folding its argument into a state
+    /// with the functions `update` and `merge` of this module
+    ///
+    /// The state starts as `initial`, and has its type, which is also the
+    /// return type of the aggregate. `update(state, value) -> state` adds a
+    /// non NULL value of `arg_type` to the state, and `merge(state, state) ->
+    /// state` combines two partial states.
+    pub fn aggregate_udf(
+        &self,
+        name: impl Into<String>,
+        arg_type: DataType,
+        initial: ScalarValue,
+        update: impl Into<String>,
+        merge: impl Into<String>,
+    ) -> Result<AggregateUDF> {
+        let (update, merge) = (update.into(), merge.into());
+        let state_type = initial.data_type();
+        if initial.is_null() {
+            return plan_err!("The initial state of a WASM aggregate can not be NULL");
+        }
+        self.check_signature(
+            &update,
+            &[state_type.clone(), arg_type.clone()],
+            &state_type,
+        )?;
+        self.check_signature(
+            &merge,
+            &[state_type.clone(), state_type.clone()],
+            &state_type,
+        )?;
+        Ok(AggregateUDF::new_from_impl(WasmAggregateUDF {
+            name: name.into(),
+            signature: Signature::exact(vec![arg_type], Volatility::Immutable),
+            module: self.clone(),
+            initial,
+            update,
+            merge,
+        }))
+    }
+}
+
+/// An [`AggregateUDFImpl`] calling functions of a [`WasmModule`]
+#[derive(Debug)]
+struct WasmAggregateUDF {
+    name: String,
+    signature: Signature,
+    module: WasmModule,
+    initial: ScalarValue,
+    update: String,
+    merge: String,
+}
+
+impl AggregateUDFImpl for WasmAggregateUDF {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(self.initial.data_type())
+    }
+
+    fn accumulator(&self, _acc_args: AccumulatorArgs) -> Result<Box<dyn Accumulator>> {
+        Ok(Box::new(WasmAccumulator {
+            module: self.module.clone(),
+            state_type: self.initial.data_type(),
+            state: scalar_to_value(&self.initial)?,
+            update: self.update.clone(),
+            merge: self.merge.clone(),
+        }))
+    }
+}
+
+/// Accumulator of a [`WasmAggregateUDF`]
+#[derive(Debug)]
+struct WasmAccumulator {
+    module: WasmModule,
+    state_type: DataType,
+    state: Val,
+    update: String,
+    merge: String,
+}
+
+impl WasmAccumulator {
+    /// Fold the non NULL values of `values` into the state with `export`
+    fn fold(&mut self, export: &str, values: &ArrayRef) -> Result<()> {
+        if values.null_count() == values.len() {
+            return Ok(());
+        }
+        let (mut store, func) = self.module.instantiate(export)?;
+        for row in 0..values.len() {
+            if let Some(value) = array_value(values, row)? {
+                self.state = call(&mut store, &func, &[self.state.clone(), value])?;
+            }
+        }
+        Ok(())
+    }
+}
+
+impl Accumulator for WasmAccumulator {
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let update = self.update.clone();
+        self.fold(&update, &values[0])
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        let merge = self.merge.clone();
+        self.fold(&merge, &states[0])
+    }
+
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        Ok(vec![self.evaluate()?])
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        value_to_scalar(&self.state_type, &self.state)
+    }
+
+    fn size(&self) -> usize {
+        size_of_val(self) + self.update.capacity() + self.merge.capacity()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WasmLimits;
+
+    use arrow::array::Int64Array;
+    use datafusion::arrow::util::pretty::pretty_format_batches;
+    use datafusion::prelude::SessionContext;
+    use std::sync::Arc;
+
+    /// Sum of squares, and a scalar function doubling its argument
+    const MODULE: &str = r#"(module
+      (func (export "update") (param i64 i64) (result i64)
+        local.get 0
+        local.get 1
+        local.get 1
+        i64.mul
+        i64.add)
+      (func (export "merge") (param i64 i64) (result i64)
+        local.get 0
+        local.get 1
+        i64.add)
+      (func (export "double") (param i64) (result i64)
+        local.get 0
+        i64.const 2
+        i64.mul))"#;
+
+    fn sum_squares() -> Result<AggregateUDF> {
+        let module = WasmModule::try_new(MODULE, WasmLimits::default())?;
+        module.aggregate_udf(
+            "sum_squares",
+            DataType::Int64,
+            ScalarValue::Int64(Some(0)),
+            "update",
+            "merge",
+        )
+    }
+
+    #[test]
+    fn accumulator() -> Result<()> {
+        let udaf = sum_squares()?;
+        let inner = udaf.inner();
+        let inner = inner.as_any().downcast_ref::<WasmAggregateUDF>().unwrap();
+        let mut accumulator = WasmAccumulator {
+            module: inner.module.clone(),
+            state_type: DataType::Int64,
+            state: Val::I64(0),
+            update: inner.update.clone(),
+            merge: inner.merge.clone(),
+        };
+        let values: ArrayRef = Arc::new(Int64Array::from(vec![Some(1), None, Some(3)]));
+        accumulator.update_batch(&[values])?;
+        assert_eq!(accumulator.evaluate()?, ScalarValue::Int64(Some(10)));
+
+        let states: ArrayRef = Arc::new(Int64Array::from(vec![5, 7]));
+        accumulator.merge_batch(&[states])?;
+        assert_eq!(accumulator.evaluate()?, ScalarValue::Int64(Some(22)));
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn register_functions() -> Result<()> {
+        let module = WasmModule::try_new(MODULE, WasmLimits::default())?;
+        let ctx = SessionContext::new();
+        ctx.register_udf(module.scalar_udf(
+            "double",
+            "double",
+            vec![DataType::Int64],
+            DataType::Int64,
+            Volatility::Immutable,
+        )?);
+        ctx.register_udaf(sum_squares()?);
+
+        let batches = ctx
+            .sql(
+                "SELECT sum_squares(double(column1)) AS s \
+                 FROM (VALUES (1), (2), (NULL), (3)) AS t",
+            )
+            .await?
+            .collect()
+            .await?;
+        assert_eq!(
+            pretty_format_batches(&batches)?.to_string(),
+            "+----+\n| s  |\n+----+\n| 56 |\n+----+"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,322 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! User defined functions compiled to WebAssembly
+//!
+//! A [`WasmModule`] loads a WebAssembly module, in binary or text format, and
+//! creates [`ScalarUDF`]s and [`AggregateUDF`]s from the functions it
+//! exports, which can then be registered like any other user defined
+//! function, e.g. with [`FunctionRegistry::register_udf`].
+//!
+//! Functions exchange values of the following types:
+//!
+//! | Arrow type | WebAssembly type |
+//! |------------|------------------|
+//! | `Int32`    | `i32`            |
+//! | `Int64`    | `i64`            |
+//! | `Float32`  | `f32`            |
+//! | `Float64`  | `f64`            |
+//!
+//! * A scalar function is an export taking one parameter per argument and
+//!   returning the result. It is called once per row, and returns NULL
+//!   without being called when any argument is NULL.
+//!
+//! * An aggregate function is a pair of exports `update(state, value) -> state`
+//!   and `merge(state, state) -> state` over a state that starts with a
+//!   given initial value, and is the result of the aggregate. NULL inputs are
+//!   skipped.
+//!
+//! # Sandboxing
+//!
+//! Modules can not import any function, so functions have no access to the
+//! host beyond their arguments. Every batch is evaluated with a fresh
+//! instance of the module, whose memory is limited by
+//! [`WasmLimits::with_max_memory`], and whose execution time can be limited
+//! by [`WasmLimits::with_fuel`].
+//!
+//! # Example
+//!
+//! ```
+//! # use datafusion_common::Result;
+//! # use datafusion_expr::Volatility;
+//! # use arrow::datatypes::DataType;
+//! use datafusion_functions_wasm::{WasmLimits, WasmModule};
+//!
+//! # fn main() -> Result<()> {
+//! let module = WasmModule::try_new(
+//!     r#"(module
+//!       (func (export "add_one") (param i64) (result i64)
+//!         local.get 0
+//!         i64.const 1
+//!         i64.add))"#,
+//!     WasmLimits::default(),
+//! )?;
+//! let add_one = module.scalar_udf(
+//!     "add_one",
+//!     "add_one",
+//!     vec![DataType::Int64],
+//!     DataType::Int64,
+//!     Volatility::Immutable,
+//! )?;
+//! # Ok(())
+//! # }
+//! ```
+//!
+//! [`ScalarUDF`]: datafusion_expr::ScalarUDF
+//! [`AggregateUDF`]: datafusion_expr::AggregateUDF
+//! [`FunctionRegistry::register_udf`]: datafusion_execution::registry::FunctionRegistry::register_udf
+
+mod aggregate;
+mod scalar;
+
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use arrow::array::{
+    Array, ArrayRef, AsArray, Float32Array, Float64Array, Int32Array, Int64Array,
+};
+use arrow::datatypes::{DataType, Float32Type, Float64Type, Int32Type, Int64Type};
+use datafusion_common::{
+    exec_datafusion_err, not_impl_err, plan_datafusion_err, plan_err, Result, ScalarValue,
+};
+use wasmtime::{
+    Config, Engine, Func, Instance, Module, Store, StoreLimits, StoreLimitsBuilder, Val,
+    ValType,
+};
+
+/// Default [`WasmLimits::max_memory`]: 16 MiB
+const DEFAULT_MAX_MEMORY: usize = 16 * 1024 * 1024;
+
+/// Resource limits of the instances of a [`WasmModule`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WasmLimits {
+    max_memory: usize,
+    fuel: Option<u64>,
+}
+
+impl Default for WasmLimits {
+    fn default() -> Self {
+        Self {
+            max_memory: DEFAULT_MAX_MEMORY,
+            fuel: None,
+        }
+    }
+}
+
+impl WasmLimits {
+    /// Limit the linear memory of an instance to `bytes`
+    pub fn with_max_memory(mut self, bytes: usize) -> Self {
+        self.max_memory = bytes;
+        self
+    }
+
+    /// Limit the number of instructions an instance may execute while
+    /// evaluating a single batch. Evaluation fails once the fuel is exhausted.
+    pub fn with_fuel(mut self, fuel: u64) -> Self {
+        self.fuel = Some(fuel);
+        self
+    }
+
+    /// Returns the maximum size of the linear memory of an instance, in bytes
+    pub fn max_memory(&self) -> usize {
+        self.max_memory
+    }
+
+    /// Returns the fuel available to evaluate a single batch, if limited
+    pub fn fuel(&self) -> Option<u64> {
+        self.fuel
+    }
+}
+
+/// A compiled WebAssembly module exporting user defined functions, see the
+/// [crate documentation](crate)
+#[derive(Clone)]
+pub struct WasmModule {
+    engine: Engine,
+    module: Module,
+    limits: WasmLimits,
+}
+
+impl Debug for WasmModule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        f.debug_struct("WasmModule")
+            .field("name", &self.module.name())
+            .field("limits", &self.limits)
+            .finish()
+    }
+}
+
+impl WasmModule {
+    /// Compile the WebAssembly module `bytes`, in binary or text format
+    pub fn try_new(bytes: impl AsRef<[u8]>, limits: WasmLimits) -> Result<Self> {
+        let mut config = Config::new();
+        config.consume_fuel(limits.fuel.is_some());
+        let engine = Engine::new(&config)
+            .map_err(|e| exec_datafusion_err!("Failed to create WASM engine: {e}"))?;
+        let module = Module::new(&engine, bytes)
+            .map_err(|e| plan_datafusion_err!("Invalid WASM module: {e}"))?;
+        if let Some(import) = module.imports().next() {
+            return plan_err!(
+                "WASM modules can not import functions, but the module imports {}::{}",
+                import.module(),
+                import.name()
+            );
+        }
+        Ok(Self {
+            engine,
+            module,
+            limits,
+        })
+    }
+
+    /// Returns the resource limits of the instances of this module
+    pub fn limits(&self) -> &WasmLimits {
+        &self.limits
+    }
+
+    /// Create a new instance of this module, and return its function `export`
+    fn instantiate(&self, export: &str) -> Result<(Store<StoreLimits>, Func)> {
+        let limits = StoreLimitsBuilder::new()
+            .memory_size(self.limits.max_memory)
+            .instances(1)
+            .build();
+        let mut store = Store::new(&self.engine, limits);
+        store.limiter(|limits| limits);
+        if let Some(fuel) = self.limits.fuel {
+            store
+                .set_fuel(fuel)
+                .map_err(|e| exec_datafusion_err!("{e}"))?;
+        }
+        let instance = Instance::new(&mut store, &self.module, &[]).map_err(|e| {
+            exec_datafusion_err!("Failed to instantiate WASM module: {e}")
+        })?;
+        let func = instance.get_func(&mut store, export).ok_or_else(|| {
+            plan_datafusion_err!("WASM module has no function {export}")
+        })?;
+        Ok((store, func))
+    }
+
+    /// Check that `export` is a function with the WebAssembly types of
+    /// `params` and `result`
+    fn check_signature(
+        &self,
+        export: &str,
+        params: &[DataType],
+        result: &DataType,
+    ) -> Result<()> {
+        let expected_params = params
+            .iter()
+            .map(|data_type| Ok(wasm_type(data_type)?.to_string()))
+            .collect::<Result<Vec<_>>>()?;
+        let expected_results = vec![wasm_type(result)?.to_string()];
+
+        let (store, func) = self.instantiate(export)?;
+        let ty = func.ty(&store);
+        let params = ty.params().map(|ty| ty.to_string()).collect::<Vec<_>>();
+        let results = ty.results().map(|ty| ty.to_string()).collect::<Vec<_>>();
+        if params != expected_params || results != expected_results {
+            return plan_err!(
+                "WASM function {export} has signature {params:?} -> {results:?}, \
+                expected {expected_params:?} -> {expected_results:?}"
+            );
+        }
+        Ok(())
+    }
+}
+
+/// Returns the WebAssembly type of values of `data_type`
+fn wasm_type(data_type: &DataType) -> Result<ValType> {
+    match data_type {
+        DataType::Int32 => Ok(ValType::I32),
+        DataType::Int64 => Ok(ValType::I64),
+        DataType::Float32 => Ok(ValType::F32),
+        DataType::Float64 => Ok(ValType::F64),
+        _ => not_impl_err!("WASM functions do not support {data_type} values"),
+    }
+}
+
+/// Returns the value of `array` at `index`, or `None` if it is NULL
+fn array_value(array: &ArrayRef, index: usize) -> Result<Option<Val>> {
+    if array.is_null(index) {
+        return Ok(None);
+    }
+    let value = match array.data_type() {
+        DataType::Int32 => Val::I32(array.as_primitive::<Int32Type>().value(index)),
+        DataType::Int64 => Val::I64(array.as_primitive::<Int64Type>().value(index)),
+        DataType::Float32 => Val::from(array.as_primitive::<Float32Type>().value(index)),
+        DataType::Float64 => Val::from(array.as_primitive::<Float64Type>().value(index)),
+        data_type => {
+            return not_impl_err!("WASM functions do not support {data_type} values")
+        }
+    };
+    Ok(Some(value))
+}
+
+/// Returns an array of `data_type` with `values`
+fn values_to_array(data_type: &DataType, values: &[Option<Val>]) -> Result<ArrayRef> {
+    let array: ArrayRef = match data_type {
+        DataType::Int32 => Arc::new(
+            values
+                .iter()
+                .map(|value| value.as_ref().and_then(Val::i32))
+                .collect::<Int32Array>(),
+        ),
+        DataType::Int64 => Arc::new(
+            values
+                .iter()
+                .map(|value| value.as_ref().and_then(Val::i64))
+                .collect::<Int64Array>(),
+        ),
+        DataType::Float32 => Arc::new(
+            values
+                .iter()
+                .map(|value| value.as_ref().and_then(Val::f32))
+                .collect::<Float32Array>(),
+        ),
+        DataType::Float64 => Arc::new(
+            values
+                .iter()
+                .map(|value| value.as_ref().and_then(Val::f64))
+                .collect::<Float64Array>(),
+        ),
+        _ => return not_impl_err!("WASM functions do not support {data_type} values"),
+    };
+    Ok(array)
+}
+
+/// Returns the WebAssembly value of `scalar`, which must not be NULL
+fn scalar_to_value(scalar: &ScalarValue) -> Result<Val> {
+    match array_value(&scalar.to_array()?, 0)? {
+        Some(value) => Ok(value),
+        None => plan_err!("WASM function values can not be NULL"),
+    }
+}
+
+/// Returns `value` as a scalar of `data_type`
+fn value_to_scalar(data_type: &DataType, value: &Val) -> Result<ScalarValue> {
+    let array = values_to_array(data_type, &[Some(value.clone())])?;
+    ScalarValue::try_from_array(&array, 0)
+}
+
+/// Call `func` with `params`, returning its single result
+fn call(store: &mut Store<StoreLimits>, func: &Func, params: &[Val]) -> Result<Val> {
+    let mut results = [Val::I32(0)];
+    func.call(store, params, &mut results)
+        .map_err(|e| exec_datafusion_err!("WASM function failed: {e:#}"))?;
+    let [result] = results;
+    Ok(result)
+}
@@ -0,0 +1,272 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Scalar functions compiled to WebAssembly
+
+use std::any::Any;
+
+use arrow::datatypes::DataType;
+use datafusion_common::{plan_err, Result, ScalarValue};
+use datafusion_expr::{ColumnarValue, ScalarUDF, ScalarUDFImpl, Signature, Volatility};
+use wasmtime::Val;
+
+use crate::{array_value, call, values_to_array, WasmModule};
+
+impl WasmModule {
+    /// Create a scalar function `name` calling the function `export` of this
+    /// module on every row
+    ///
+    /// `export` must take one parameter of the WebAssembly type of each of
+    /// `arg_types`, and return a value of the WebAssembly type of
+    /// `return_type`.
+    pub fn scalar_udf(
+        &self,
+        name: impl Into<String>,
+        export: impl Into<String>,
+        arg_types: Vec<DataType>,
+        return_type: DataType,
+        volatility: Volatility,
+    ) -> Result<ScalarUDF> {
+        let export = export.into();
+        if arg_types.is_empty() {
+            return plan_err!("WASM scalar function {export} must have arguments");
+        }
+        self.check_signature(&export, &arg_types, &return_type)?;
+        Ok(ScalarUDF::new_from_impl(WasmScalarUDF {
+            name: name.into(),
+            signature: Signature::exact(arg_types, volatility),
+            return_type,
+            module: self.clone(),
+            export,
+        }))
+    }
+}
+
+/// A [`ScalarUDFImpl`] calling a function of a [`WasmModule`]
+#[derive(Debug)]
+struct WasmScalarUDF {
+    name: String,
+    signature: Signature,
+    return_type: DataType,
+    module: WasmModule,
+    export: String,
+}
+
+impl ScalarUDFImpl for WasmScalarUDF {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(self.return_type.clone())
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        let all_scalars = args
+            .iter()
+            .all(|arg| matches!(arg, ColumnarValue::Scalar(_)));
+        let arrays = ColumnarValue::values_to_arrays(args)?;
+        let num_rows = arrays[0].len();
+
+        let (mut store, func) = self.module.instantiate(&self.export)?;
+        let mut params = Vec::with_capacity(arrays.len());
+        let mut results = Vec::with_capacity(num_rows);
+        for row in 0..num_rows {
+            params.clear();
+            for array in &arrays {
+                match array_value(array, row)? {
+                    Some(value) => params.push(value),
+                    None => break,
+                }
+            }
+            let result: Option<Val> = if params.len() == arrays.len() {
+                Some(call(&mut store, &func, &params)?)
+            } else {
+                None
+            };
+            results.push(result);
+        }
+
+        let result = values_to_array(&self.return_type, &results)?;
+        if all_scalars {
+            Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+                &result, 0,
+            )?))
+        } else {
+            Ok(ColumnarValue::Array(result))
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::WasmLimits;
+
+    use arrow::array::{ArrayRef, Float64Array, Int64Array};
+    use std::sync::Arc;
+
+    const MODULE: &str = r#"(module
+      (memory 1)
+      (func (export "hypot") (param f64 f64) (result f64)
+        local.get 0
+        local.get 0
+        f64.mul
+        local.get 1
+        local.get 1
+        f64.mul
+        f64.add
+        f64.sqrt)
+      (func (export "spin") (param i64) (result i64)
+        (loop $l (br $l))
+        local.get 0)
+      (func (export "grow") (param i64) (result i64)
+        i32.const 1000
+        memory.grow
+        i64.extend_i32_s))"#;
+
+    fn invoke(udf: &ScalarUDF, args: Vec<ArrayRef>) -> Result<ArrayRef> {
+        let args = args
+            .into_iter()
+            .map(ColumnarValue::Array)
+            .collect::<Vec<_>>();
+        udf.invoke(&args)?.into_array(1)
+    }
+
+    #[test]
+    fn scalar_udf() -> Result<()> {
+        let module = WasmModule::try_new(MODULE, WasmLimits::default())?;
+        let hypot = module.scalar_udf(
+            "hypot",
+            "hypot",
+            vec![DataType::Float64, DataType::Float64],
+            DataType::Float64,
+            Volatility::Immutable,
+        )?;
+        let result = invoke(
+            &hypot,
+            vec![
+                Arc::new(Float64Array::from(vec![Some(3.0), None, Some(5.0)])),
+                Arc::new(Float64Array::from(vec![Some(4.0), Some(1.0), Some(12.0)])),
+            ],
+        )?;
+        let expected: ArrayRef =
+            Arc::new(Float64Array::from(vec![Some(5.0), None, Some(13.0)]));
+        assert_eq!(&result, &expected);
+
+        let scalar = hypot.invoke(&[
+            ColumnarValue::Scalar(ScalarValue::Float64(Some(6.0))),
+            ColumnarValue::Scalar(ScalarValue::Float64(Some(8.0))),
+        ])?;
+        let ColumnarValue::Scalar(scalar) = scalar else {
+            panic!("expected a scalar")
+        };
+        assert_eq!(scalar, ScalarValue::Float64(Some(10.0)));
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_signature() -> Result<()> {
+        let module = WasmModule::try_new(MODULE, WasmLimits::default())?;
+        let err = module
+            .scalar_udf(
+                "hypot",
+                "hypot",
+                vec![DataType::Int64],
+                DataType::Float64,
+                Volatility::Immutable,
+            )
+            .unwrap_err();
+        assert!(err.to_string().starts_with(
+            "Error during planning: WASM function hypot has signature [\"f64\", \"f64\"] -> [\"f64\"]"
+        ));
+
+        let err = module
+            .scalar_udf(
+                "missing",
+                "missing",
+                vec![DataType::Int64],
+                DataType::Int64,
+                Volatility::Immutable,
+            )
+            .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: WASM module has no function missing"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn imports_are_rejected() {
+        let err = WasmModule::try_new(
+            r#"(module (import "env" "sleep" (func (param i64))))"#,
+            WasmLimits::default(),
+        )
+        .unwrap_err();
+        assert_eq!(
+            err.to_string(),
+            "Error during planning: WASM modules can not import functions, but the module imports env::sleep"
+        );
+    }
+
+    #[test]
+    fn fuel_limit() -> Result<()> {
+        let module =
+            WasmModule::try_new(MODULE, WasmLimits::default().with_fuel(10_000))?;
+        let spin = module.scalar_udf(
+            "spin",
+            "spin",
+            vec![DataType::Int64],
+            DataType::Int64,
+            Volatility::Immutable,
+        )?;
+        let err = invoke(&spin, vec![Arc::new(Int64Array::from(vec![1]))]).unwrap_err();
+        assert!(err
+            .to_string()
+            .starts_with("Execution error: WASM function failed"));
+        Ok(())
+    }
+
+    #[test]
+    fn memory_limit() -> Result<()> {
+        // Growing the memory beyond the limit fails, returning -1
+        let module = WasmModule::try_new(
+            MODULE,
+            WasmLimits::default().with_max_memory(1024 * 1024),
+        )?;
+        let grow = module.scalar_udf(
+            "grow",
+            "grow",
+            vec![DataType::Int64],
+            DataType::Int64,
+            Volatility::Immutable,
+        )?;
+        let result = invoke(&grow, vec![Arc::new(Int64Array::from(vec![1]))])?;
+        let expected: ArrayRef = Arc::new(Int64Array::from(vec![-1]));
+        assert_eq!(&result, &expected);
+        Ok(())
+    }
+}
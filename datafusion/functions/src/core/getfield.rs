@@ -15,12 +15,16 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use arrow::array::{Scalar, StringArray};
+use arrow::array::{Array, Scalar, StringArray};
 use arrow::datatypes::DataType;
 use datafusion_common::cast::{as_map_array, as_struct_array};
 use datafusion_common::{exec_err, ExprSchema, Result, ScalarValue};
+use datafusion_expr::expr::ScalarFunction;
 use datafusion_expr::field_util::GetFieldAccessSchema;
-use datafusion_expr::{ColumnarValue, Expr, ExprSchemable};
+use datafusion_expr::simplify::{ExprSimplifyResult, SimplifyInfo};
+use datafusion_expr::{
+    ColumnarValue, Expr, ExprSchemable, FuncMonotonicity, ScalarFunctionDefinition,
+};
 use datafusion_expr::{ScalarUDFImpl, Signature, Volatility};
 use std::any::Any;
 
@@ -132,4 +136,173 @@ impl ScalarUDFImpl for GetFieldFunc {
                                          with utf8 indexes. Tried {dt:?} with {name:?} index"),
             }
     }
+
+    /// A field of a struct is not ordered with the struct, which is ordered by
+    /// its first field only, so the orderings of the field accesses come from
+    /// the orderings of their own expressions, which are projected with the
+    /// struct
+    fn monotonicity(&self) -> Result<Option<FuncMonotonicity>> {
+        Ok(Some(vec![None, None]))
+    }
+
+    fn simplify(
+        &self,
+        args: Vec<Expr>,
+        _info: &dyn SimplifyInfo,
+    ) -> Result<ExprSimplifyResult> {
+        let field = match args.as_slice() {
+            [base, Expr::Literal(ScalarValue::Utf8(Some(name)))] => {
+                get_constructed_field(base, name)?
+            }
+            _ => None,
+        };
+        Ok(match field {
+            Some(expr) => ExprSimplifyResult::Simplified(expr),
+            None => ExprSimplifyResult::Original(args),
+        })
+    }
+}
+
+/// Returns the expression of the field `name` of `base`, if `base` is a
+/// struct literal or a call to `struct` or `named_struct`, so that accessing
+/// the field does not require building the struct at all.
+///
+/// Chains of field accesses are folded one level at a time, as the simplifier
+/// rewrites the expression tree bottom-up.
+fn get_constructed_field(base: &Expr, name: &str) -> Result<Option<Expr>> {
+    match base {
+        Expr::Literal(ScalarValue::Struct(array)) => {
+            let Some(column) = array.column_by_name(name) else {
+                return Ok(None);
+            };
+            let value = if array.is_null(0) {
+                ScalarValue::try_from(column.data_type())?
+            } else {
+                ScalarValue::try_from_array(column, 0)?
+            };
+            Ok(Some(Expr::Literal(value)))
+        }
+        Expr::ScalarFunction(ScalarFunction {
+            func_def: ScalarFunctionDefinition::UDF(udf),
+            args,
+        }) => match udf.name() {
+            // named_struct('name1', value1, 'name2', value2, ...)
+            "named_struct" => Ok(args.chunks_exact(2).find_map(|pair| match &pair[0] {
+                Expr::Literal(ScalarValue::Utf8(Some(field_name)))
+                    if field_name == name =>
+                {
+                    Some(pair[1].clone())
+                }
+                _ => None,
+            })),
+            // struct(value0, value1, ...) names its fields c0, c1, ...
+            "struct" => Ok(args
+                .iter()
+                .enumerate()
+                .find(|(i, _)| format!("c{i}") == name)
+                .map(|(_, arg)| arg.clone())),
+            _ => Ok(None),
+        },
+        _ => Ok(None),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::compute::SortOptions;
+    use arrow::datatypes::{DataType, Field, Fields, Schema, SchemaRef};
+    use datafusion_common::{DFSchema, Result};
+    use datafusion_expr::{col, lit, ScalarUDF};
+    use datafusion_physical_expr::equivalence::ProjectionMapping;
+    use datafusion_physical_expr::expressions::{
+        col as physical_col, lit as physical_lit,
+    };
+    use datafusion_physical_expr::udf::create_physical_expr;
+    use datafusion_physical_expr::{
+        EquivalenceProperties, PhysicalExpr, PhysicalSortExpr,
+    };
+
+    use super::GetFieldFunc;
+
+    /// Returns `get_field(<base>, <name>)` on `schema`
+    fn get_field(
+        base: &str,
+        name: &str,
+        schema: &Schema,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        create_physical_expr(
+            &ScalarUDF::from(GetFieldFunc::new()),
+            &[physical_col(base, schema)?, physical_lit(name)],
+            schema,
+            &[col(base), lit(name)],
+            &DFSchema::try_from(schema.clone())?,
+        )
+    }
+
+    /// Returns the equivalence properties of the projection `proj_exprs` of
+    /// the input with the properties `eq_properties`
+    fn project(
+        eq_properties: &EquivalenceProperties,
+        proj_exprs: &[(Arc<dyn PhysicalExpr>, String)],
+        output_schema: &SchemaRef,
+    ) -> Result<EquivalenceProperties> {
+        let projection_mapping =
+            ProjectionMapping::try_new(proj_exprs, eq_properties.schema())?;
+        Ok(eq_properties.project(&projection_mapping, output_schema.clone()))
+    }
+
+    #[test]
+    fn project_ordering_of_field_access() -> Result<()> {
+        let struct_type = DataType::Struct(Fields::from(vec![
+            Field::new("x", DataType::Int32, true),
+            Field::new("y", DataType::Int32, true),
+        ]));
+        let schema = Arc::new(Schema::new(vec![Field::new(
+            "s",
+            struct_type.clone(),
+            true,
+        )]));
+        let options = SortOptions::default();
+        let sorted_by = |expr| vec![PhysicalSortExpr { expr, options }];
+
+        // [get_field(s, 'y') ASC]
+        let mut eq_properties = EquivalenceProperties::new(schema.clone());
+        eq_properties.add_new_orderings([sorted_by(get_field("s", "y", &schema)?)]);
+
+        // SELECT s AS s2: the ordering survives the field access of the
+        // renamed struct
+        let output_schema =
+            Arc::new(Schema::new(vec![Field::new("s2", struct_type, true)]));
+        let proj_exprs = [(physical_col("s", &schema)?, "s2".to_string())];
+        let projected = project(&eq_properties, &proj_exprs, &output_schema)?;
+        assert!(projected.ordering_satisfy(&sorted_by(get_field(
+            "s2",
+            "y",
+            &output_schema
+        )?)));
+        assert!(!projected.ordering_satisfy(&sorted_by(get_field(
+            "s2",
+            "x",
+            &output_schema
+        )?)));
+
+        // SELECT get_field(s, 'y') AS y
+        let output_schema =
+            Arc::new(Schema::new(vec![Field::new("y", DataType::Int32, true)]));
+        let proj_exprs = [(get_field("s", "y", &schema)?, "y".to_string())];
+        let projected = project(&eq_properties, &proj_exprs, &output_schema)?;
+        assert!(
+            projected.ordering_satisfy(&sorted_by(physical_col("y", &output_schema)?))
+        );
+
+        // [s ASC] does not order the fields of s
+        let mut eq_properties = EquivalenceProperties::new(schema.clone());
+        eq_properties.add_new_orderings([sorted_by(physical_col("s", &schema)?)]);
+        assert!(
+            !eq_properties.ordering_satisfy(&sorted_by(get_field("s", "y", &schema)?))
+        );
+        Ok(())
+    }
 }
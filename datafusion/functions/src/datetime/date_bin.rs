@@ -18,7 +18,8 @@
 use std::any::Any;
 use std::sync::Arc;
 
-use arrow::array::temporal_conversions::NANOSECONDS;
+use arrow::array::temporal_conversions::{as_datetime_with_timezone, NANOSECONDS};
+use arrow::array::timezone::Tz;
 use arrow::array::types::{
     ArrowTimestampType, IntervalDayTimeType, IntervalMonthDayNanoType,
     TimestampMicrosecondType, TimestampMillisecondType, TimestampNanosecondType,
@@ -29,10 +30,14 @@ use arrow::datatypes::DataType::{Null, Timestamp, Utf8};
 use arrow::datatypes::IntervalUnit::{DayTime, MonthDayNano};
 use arrow::datatypes::TimeUnit::{Microsecond, Millisecond, Nanosecond, Second};
 use arrow::datatypes::{DataType, TimeUnit};
-use chrono::{DateTime, Datelike, Duration, Months, TimeDelta, Utc};
+use chrono::{
+    DateTime, Datelike, Duration, LocalResult, Months, TimeDelta, TimeZone, Utc,
+};
 
 use datafusion_common::cast::as_primitive_array;
-use datafusion_common::{exec_err, not_impl_err, plan_err, Result, ScalarValue};
+use datafusion_common::{
+    exec_err, not_impl_err, plan_err, DataFusionError, Result, ScalarValue,
+};
 use datafusion_expr::TypeSignature::Exact;
 use datafusion_expr::{
     ColumnarValue, FuncMonotonicity, ScalarUDFImpl, Signature, Volatility,
@@ -151,12 +156,26 @@ impl ScalarUDFImpl for DateBinFunc {
     }
 }
 
+const NANOS_PER_DAY: i64 = 24 * 60 * 60 * NANOSECONDS;
+
 enum Interval {
     Nanoseconds(i64),
     Months(i64),
 }
 
 impl Interval {
+    /// Returns true if the interval is a whole number of days or months.
+    ///
+    /// Such intervals are binned in the local time of timestamps with a
+    /// timezone, so that bins start at local midnight even when daylight
+    /// saving time changes the UTC offset in between.
+    fn is_calendar(&self) -> bool {
+        match self {
+            Interval::Nanoseconds(nanos) => nanos % NANOS_PER_DAY == 0,
+            Interval::Months(_) => true,
+        }
+    }
+
     /// Returns (`stride_nanos`, `fn`) where
     ///
     /// 1. `stride_nanos` is a width, in nanoseconds
@@ -235,13 +254,45 @@ fn to_utc_date_time(nanos: i64) -> DateTime<Utc> {
     DateTime::from_timestamp(secs, nsec).unwrap()
 }
 
+// return time in nanoseconds that the source timestamp falls into, binning the wall clock
+// time of `tz` instead of UTC. Returns `None` if the timestamps are out of range
+fn date_bin_local(
+    tz: Tz,
+    stride: i64,
+    stride_fn: fn(i64, i64, i64) -> i64,
+    source: i64,
+    origin: i64,
+) -> Option<i64> {
+    let to_local = |nanos: i64| {
+        as_datetime_with_timezone::<TimestampNanosecondType>(nanos, tz)?
+            .naive_local()
+            .and_utc()
+            .timestamp_nanos_opt()
+    };
+    let local = to_utc_date_time(stride_fn(stride, to_local(source)?, to_local(origin)?))
+        .naive_utc();
+
+    let datetime = match tz.from_local_datetime(&local) {
+        LocalResult::Single(datetime) => datetime,
+        // The bin starts at the first of the repeated wall clock times
+        LocalResult::Ambiguous(earliest, _) => earliest,
+        // The bin starts in a wall clock time skipped by daylight saving time,
+        // use the end of the gap instead (see date_trunc)
+        LocalResult::None => (local - TimeDelta::try_hours(3).unwrap())
+            .and_local_timezone(tz)
+            .single()?
+            .checked_add_signed(TimeDelta::try_hours(3).unwrap())?,
+    };
+    datetime.timestamp_nanos_opt()
+}
+
 // Supported intervals:
 //  1. IntervalDayTime: this means that the stride is in days, hours, minutes, seconds and milliseconds
 //     We will assume month interval won't be converted into this type
 //     TODO (my next PR): without `INTERVAL` keyword, the stride was converted into ScalarValue::IntervalDayTime somwhere
 //             for month interval. I need to find that and make it ScalarValue::IntervalMonthDayNano instead
 // 2. IntervalMonthDayNano
-fn date_bin_impl(
+pub(crate) fn date_bin_impl(
     stride: &ColumnarValue,
     array: &ColumnarValue,
     origin: &ColumnarValue,
@@ -309,6 +360,7 @@ fn date_bin_impl(
         }
     };
 
+    let is_calendar = stride.is_calendar();
     let (stride, stride_fn) = stride.bin_fn();
 
     // Return error if stride is 0
@@ -316,10 +368,21 @@ fn date_bin_impl(
         return exec_err!("DATE_BIN stride must be non-zero");
     }
 
+    // The timezone to bin in, if the source has one and the stride is a calendar interval
+    let local_tz = |tz_opt: &Option<Arc<str>>| -> Result<Option<Tz>> {
+        match tz_opt {
+            Some(tz) if is_calendar => {
+                Ok(Some(tz.parse::<Tz>().map_err(DataFusionError::from)?))
+            }
+            _ => Ok(None),
+        }
+    };
+
     fn stride_map_fn<T: ArrowTimestampType>(
         origin: i64,
         stride: i64,
         stride_fn: fn(i64, i64, i64) -> i64,
+        tz: Option<Tz>,
     ) -> impl Fn(i64) -> i64 {
         let scale = match T::UNIT {
             Nanosecond => 1,
@@ -327,37 +390,58 @@ fn date_bin_impl(
             Millisecond => NANOSECONDS / 1_000,
             Second => NANOSECONDS,
         };
-        move |x: i64| stride_fn(stride, x * scale, origin) / scale
+        move |x: i64| {
+            let source = x * scale;
+            tz.and_then(|tz| date_bin_local(tz, stride, stride_fn, source, origin))
+                .unwrap_or_else(|| stride_fn(stride, source, origin))
+                / scale
+        }
     }
 
     Ok(match array {
         ColumnarValue::Scalar(ScalarValue::TimestampNanosecond(v, tz_opt)) => {
-            let apply_stride_fn =
-                stride_map_fn::<TimestampNanosecondType>(origin, stride, stride_fn);
+            let apply_stride_fn = stride_map_fn::<TimestampNanosecondType>(
+                origin,
+                stride,
+                stride_fn,
+                local_tz(tz_opt)?,
+            );
             ColumnarValue::Scalar(ScalarValue::TimestampNanosecond(
                 v.map(apply_stride_fn),
                 tz_opt.clone(),
             ))
         }
         ColumnarValue::Scalar(ScalarValue::TimestampMicrosecond(v, tz_opt)) => {
-            let apply_stride_fn =
-                stride_map_fn::<TimestampMicrosecondType>(origin, stride, stride_fn);
+            let apply_stride_fn = stride_map_fn::<TimestampMicrosecondType>(
+                origin,
+                stride,
+                stride_fn,
+                local_tz(tz_opt)?,
+            );
             ColumnarValue::Scalar(ScalarValue::TimestampMicrosecond(
                 v.map(apply_stride_fn),
                 tz_opt.clone(),
             ))
         }
         ColumnarValue::Scalar(ScalarValue::TimestampMillisecond(v, tz_opt)) => {
-            let apply_stride_fn =
-                stride_map_fn::<TimestampMillisecondType>(origin, stride, stride_fn);
+            let apply_stride_fn = stride_map_fn::<TimestampMillisecondType>(
+                origin,
+                stride,
+                stride_fn,
+                local_tz(tz_opt)?,
+            );
             ColumnarValue::Scalar(ScalarValue::TimestampMillisecond(
                 v.map(apply_stride_fn),
                 tz_opt.clone(),
             ))
         }
         ColumnarValue::Scalar(ScalarValue::TimestampSecond(v, tz_opt)) => {
-            let apply_stride_fn =
-                stride_map_fn::<TimestampSecondType>(origin, stride, stride_fn);
+            let apply_stride_fn = stride_map_fn::<TimestampSecondType>(
+                origin,
+                stride,
+                stride_fn,
+                local_tz(tz_opt)?,
+            );
             ColumnarValue::Scalar(ScalarValue::TimestampSecond(
                 v.map(apply_stride_fn),
                 tz_opt.clone(),
@@ -371,12 +455,13 @@ fn date_bin_impl(
                 stride_fn: fn(i64, i64, i64) -> i64,
                 array: &ArrayRef,
                 tz_opt: &Option<Arc<str>>,
+                tz: Option<Tz>,
             ) -> Result<ColumnarValue>
             where
                 T: ArrowTimestampType,
             {
                 let array = as_primitive_array::<T>(array)?;
-                let apply_stride_fn = stride_map_fn::<T>(origin, stride, stride_fn);
+                let apply_stride_fn = stride_map_fn::<T>(origin, stride, stride_fn, tz);
                 let array: PrimitiveArray<T> = array
                     .unary(apply_stride_fn)
                     .with_timezone_opt(tz_opt.clone());
@@ -387,22 +472,42 @@ fn date_bin_impl(
             match array.data_type() {
                 Timestamp(Nanosecond, tz_opt) => {
                     transform_array_with_stride::<TimestampNanosecondType>(
-                        origin, stride, stride_fn, array, tz_opt,
+                        origin,
+                        stride,
+                        stride_fn,
+                        array,
+                        tz_opt,
+                        local_tz(tz_opt)?,
                     )?
                 }
                 Timestamp(Microsecond, tz_opt) => {
                     transform_array_with_stride::<TimestampMicrosecondType>(
-                        origin, stride, stride_fn, array, tz_opt,
+                        origin,
+                        stride,
+                        stride_fn,
+                        array,
+                        tz_opt,
+                        local_tz(tz_opt)?,
                     )?
                 }
                 Timestamp(Millisecond, tz_opt) => {
                     transform_array_with_stride::<TimestampMillisecondType>(
-                        origin, stride, stride_fn, array, tz_opt,
+                        origin,
+                        stride,
+                        stride_fn,
+                        array,
+                        tz_opt,
+                        local_tz(tz_opt)?,
                     )?
                 }
                 Timestamp(Second, tz_opt) => {
                     transform_array_with_stride::<TimestampSecondType>(
-                        origin, stride, stride_fn, array, tz_opt,
+                        origin,
+                        stride,
+                        stride_fn,
+                        array,
+                        tz_opt,
+                        local_tz(tz_opt)?,
                     )?
                 }
                 _ => {
@@ -710,6 +815,65 @@ mod tests {
             });
     }
 
+    #[test]
+    fn test_date_bin_daylight_saving_time() {
+        let tz_opt: Option<Arc<str>> = Some("America/New_York".into());
+        // Local midnight in winter (EST)
+        let origin = string_to_timestamp_nanos("1970-01-01T05:00:00Z").unwrap();
+        let cases = vec![
+            // 1 day: summer bins start at local midnight (EDT) too
+            (
+                ScalarValue::new_interval_dt(1, 0),
+                "2020-07-15T12:00:00Z",
+                "2020-07-15T04:00:00Z",
+            ),
+            (
+                ScalarValue::new_interval_dt(1, 0),
+                "2020-11-02T12:00:00Z",
+                "2020-11-02T05:00:00Z",
+            ),
+            // 1 month
+            (
+                ScalarValue::new_interval_mdn(1, 0, 0),
+                "2020-07-15T12:00:00Z",
+                "2020-07-01T04:00:00Z",
+            ),
+            // 1 hour is binned in UTC
+            (
+                ScalarValue::new_interval_dt(0, 3_600_000),
+                "2020-07-15T12:30:00Z",
+                "2020-07-15T12:00:00Z",
+            ),
+        ];
+
+        for (stride, source, expected) in cases {
+            let result = DateBinFunc::new()
+                .invoke(&[
+                    ColumnarValue::Scalar(stride),
+                    ColumnarValue::Scalar(ScalarValue::TimestampNanosecond(
+                        Some(string_to_timestamp_nanos(source).unwrap()),
+                        tz_opt.clone(),
+                    )),
+                    ColumnarValue::Scalar(ScalarValue::TimestampNanosecond(
+                        Some(origin),
+                        tz_opt.clone(),
+                    )),
+                ])
+                .unwrap();
+            let ColumnarValue::Scalar(result) = result else {
+                panic!("unexpected column type");
+            };
+            assert_eq!(
+                result,
+                ScalarValue::TimestampNanosecond(
+                    Some(string_to_timestamp_nanos(expected).unwrap()),
+                    tz_opt.clone(),
+                ),
+                "{source}"
+            );
+        }
+    }
+
     #[test]
     fn test_date_bin_single() {
         let cases = vec![
@@ -0,0 +1,279 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::types::{IntervalDayTimeType, IntervalMonthDayNanoType};
+use arrow::array::{Array, ArrayRef, Int64Array, ListArray};
+use arrow::buffer::OffsetBuffer;
+use arrow::compute::cast;
+use arrow::datatypes::DataType::{Int64, List, Timestamp};
+use arrow::datatypes::TimeUnit::{Microsecond, Millisecond, Nanosecond, Second};
+use arrow::datatypes::{DataType, Field};
+
+use datafusion_common::cast::as_int64_array;
+use datafusion_common::{exec_err, not_impl_err, plan_err, Result, ScalarValue};
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature, Volatility};
+
+use crate::datetime::tumble::window_signatures;
+
+/// `hop(source, slide, size)`: the starts of the time windows of length
+/// `size`, starting every `slide`, that contain `source`, in ascending order.
+/// Windows are aligned to the unix epoch.
+///
+/// Each row belongs to several hopping (sliding) windows, so aggregating over
+/// them requires expanding the rows with `unnest(hop(ts, slide, size))` in a
+/// subquery. For windows that do not overlap, use `tumble` instead.
+#[derive(Debug)]
+pub struct HopFunc {
+    signature: Signature,
+}
+
+impl Default for HopFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl HopFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::one_of(
+                window_signatures(|source, interval| {
+                    vec![source, interval.clone(), interval]
+                }),
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for HopFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "hop"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        match &arg_types[0] {
+            Timestamp(unit, tz_opt) => Ok(List(Arc::new(Field::new(
+                "item",
+                Timestamp(unit.clone(), tz_opt.clone()),
+                true,
+            )))),
+            _ => {
+                plan_err!("The hop function can only accept timestamp as the first arg.")
+            }
+        }
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        if args.len() != 3 {
+            return exec_err!("HOP expected three arguments");
+        }
+        let slide = interval_nanos(&args[1], "slide")?;
+        let size = interval_nanos(&args[2], "size")?;
+        let is_scalar = matches!(args[0], ColumnarValue::Scalar(_));
+        let source = args[0].clone().into_array(1)?;
+        let Timestamp(unit, _) = source.data_type() else {
+            return exec_err!(
+                "HOP expects source argument to be a TIMESTAMP but got {}",
+                source.data_type()
+            );
+        };
+        let scale = match unit {
+            Nanosecond => 1,
+            Microsecond => 1_000,
+            Millisecond => 1_000_000,
+            Second => 1_000_000_000,
+        };
+        if slide <= 0 || size <= 0 || slide % scale != 0 || size % scale != 0 {
+            return exec_err!(
+                "HOP slide and size must be positive multiples of the source precision"
+            );
+        }
+        let (slide, size) = (slide / scale, size / scale);
+
+        let hops = hop_windows(&source, slide, size)?;
+        Ok(if is_scalar {
+            ColumnarValue::Scalar(ScalarValue::try_from_array(&hops, 0)?)
+        } else {
+            ColumnarValue::Array(hops)
+        })
+    }
+}
+
+/// Returns the length of the constant interval `arg` in nanoseconds
+fn interval_nanos(arg: &ColumnarValue, name: &str) -> Result<i64> {
+    match arg {
+        ColumnarValue::Scalar(ScalarValue::IntervalDayTime(Some(v))) => {
+            let (days, ms) = IntervalDayTimeType::to_parts(*v);
+            // Cannot overflow, as both parts are i32
+            Ok((days as i64 * 86_400_000 + ms as i64) * 1_000_000)
+        }
+        ColumnarValue::Scalar(ScalarValue::IntervalMonthDayNano(Some(v))) => {
+            let (months, days, nanos) = IntervalMonthDayNanoType::to_parts(*v);
+            if months != 0 {
+                return not_impl_err!("HOP {name} does not support month intervals");
+            }
+            (days as i64)
+                .checked_mul(86_400_000_000_000)
+                .and_then(|days| days.checked_add(nanos))
+                .map_or_else(
+                    || exec_err!("HOP {name} overflows a 64-bit count of nanoseconds"),
+                    Ok,
+                )
+        }
+        ColumnarValue::Scalar(v) => {
+            exec_err!(
+                "HOP expects {name} argument to be a non-NULL INTERVAL but got {}",
+                v.data_type()
+            )
+        }
+        ColumnarValue::Array(_) => {
+            not_impl_err!(
+                "HOP only supports literal values for the {name} argument, not arrays"
+            )
+        }
+    }
+}
+
+/// Returns, for every timestamp of `source`, the list of the starts of the
+/// windows of length `size` starting every `slide` that contain it
+fn hop_windows(source: &ArrayRef, slide: i64, size: i64) -> Result<ArrayRef> {
+    let timestamps = cast(source, &Int64)?;
+    let timestamps = as_int64_array(&timestamps)?;
+
+    let mut offsets = Vec::with_capacity(timestamps.len() + 1);
+    offsets.push(0);
+    let mut starts = vec![];
+    // The start of the last window starting at or before `ts`
+    let window_start = |ts: i64| ts.div_euclid(slide).checked_mul(slide);
+    for ts in timestamps.iter() {
+        if let Some(ts) = ts {
+            // The first window starts after the one that ends at or before `ts`
+            let first = ts
+                .checked_sub(size)
+                .and_then(window_start)
+                .and_then(|start| start.checked_add(slide));
+            let (Some(first), Some(last)) = (first, window_start(ts)) else {
+                return exec_err!("HOP windows of {ts} overflow the timestamp range");
+            };
+            starts.extend((first..=last).step_by(slide as usize));
+        }
+        offsets.push(starts.len() as i32);
+    }
+
+    let starts = cast(&Int64Array::from(starts), source.data_type())?;
+    let field = Arc::new(Field::new("item", source.data_type().clone(), true));
+    Ok(Arc::new(ListArray::try_new(
+        field,
+        OffsetBuffer::new(offsets.into()),
+        starts,
+        source.nulls().cloned(),
+    )?))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Array, ArrayRef, TimestampSecondArray};
+    use arrow::datatypes::IntervalDayTimeType;
+
+    use datafusion_common::cast::as_list_array;
+    use datafusion_common::ScalarValue;
+    use datafusion_expr::{ColumnarValue, ScalarUDFImpl};
+
+    use crate::datetime::hop::HopFunc;
+
+    #[test]
+    fn test_hop() {
+        let timestamps: ArrayRef = Arc::new(TimestampSecondArray::from(vec![
+            Some(0),
+            Some(75),
+            None,
+            Some(-1),
+        ]));
+        let interval = |secs| {
+            ColumnarValue::Scalar(ScalarValue::IntervalDayTime(Some(
+                IntervalDayTimeType::make_value(0, secs * 1_000),
+            )))
+        };
+        let res = HopFunc::new()
+            .invoke(&[ColumnarValue::Array(timestamps), interval(30), interval(90)])
+            .unwrap();
+        let ColumnarValue::Array(res) = res else {
+            panic!("Expected an array");
+        };
+        let res = as_list_array(&res).unwrap();
+        let windows = |idx| {
+            let windows = res.value(idx);
+            windows
+                .as_any()
+                .downcast_ref::<TimestampSecondArray>()
+                .unwrap()
+                .values()
+                .to_vec()
+        };
+        assert_eq!(windows(0), [-60, -30, 0]);
+        assert_eq!(windows(1), [0, 30, 60]);
+        assert!(res.is_null(2));
+        assert_eq!(windows(3), [-90, -60, -30]);
+    }
+
+    #[test]
+    fn test_hop_invalid_windows() {
+        let interval = |ms| {
+            ColumnarValue::Scalar(ScalarValue::IntervalDayTime(Some(
+                IntervalDayTimeType::make_value(0, ms),
+            )))
+        };
+        let hop = |ts, slide, size| {
+            let timestamps: ArrayRef = Arc::new(TimestampSecondArray::from(vec![ts]));
+            HopFunc::new()
+                .invoke(&[
+                    ColumnarValue::Array(timestamps),
+                    interval(slide),
+                    interval(size),
+                ])
+                .unwrap_err()
+                .to_string()
+        };
+        // the windows must be whole seconds
+        let err = hop(0, 1_500, 3_000);
+        assert!(
+            err.contains("positive multiples of the source precision"),
+            "{err}"
+        );
+        let err = hop(0, 1_000, 2_500);
+        assert!(
+            err.contains("positive multiples of the source precision"),
+            "{err}"
+        );
+        let err = hop(i64::MIN + 1, 1_000, 3_000);
+        assert!(err.contains("overflow the timestamp range"), "{err}");
+    }
+}
@@ -28,12 +28,15 @@ pub mod date_bin;
 pub mod date_part;
 pub mod date_trunc;
 pub mod from_unixtime;
+pub mod hop;
 pub mod make_date;
 pub mod now;
+pub mod time_bucket_gapfill;
 pub mod to_char;
 pub mod to_date;
 pub mod to_timestamp;
 pub mod to_unixtime;
+pub mod tumble;
 
 // create UDFs
 make_udf_function!(current_date::CurrentDateFunc, CURRENT_DATE, current_date);
@@ -47,10 +50,18 @@ make_udf_function!(
     FROM_UNIXTIME,
     from_unixtime
 );
+make_udf_function!(hop::HopFunc, HOP, hop);
+make_udf_function!(time_bucket_gapfill::LocfFunc, LOCF, locf);
 make_udf_function!(now::NowFunc, NOW, now);
 make_udf_function!(to_char::ToCharFunc, TO_CHAR, to_char);
 make_udf_function!(to_date::ToDateFunc, TO_DATE, to_date);
 make_udf_function!(to_unixtime::ToUnixtimeFunc, TO_UNIXTIME, to_unixtime);
+make_udf_function!(
+    time_bucket_gapfill::TimeBucketGapfillFunc,
+    TIME_BUCKET_GAPFILL,
+    time_bucket_gapfill
+);
+make_udf_function!(tumble::TumbleFunc, TUMBLE, tumble);
 make_udf_function!(to_timestamp::ToTimestampFunc, TO_TIMESTAMP, to_timestamp);
 make_udf_function!(
     to_timestamp::ToTimestampSecondsFunc,
@@ -109,6 +120,16 @@ pub mod expr_fn {
         super::from_unixtime().call(vec![unixtime])
     }
 
+    #[doc = "returns the starts of the windows of length size, starting every slide, that contain a timestamp"]
+    pub fn hop(source: Expr, slide: Expr, size: Expr) -> Expr {
+        super::hop().call(vec![source, slide, size])
+    }
+
+    #[doc = "fills missing values with the last value of the group, in queries grouped by time_bucket_gapfill"]
+    pub fn locf(arg: Expr) -> Expr {
+        super::locf().call(vec![arg])
+    }
+
     #[doc = "make a date from year, month and day component parts"]
     pub fn make_date(year: Expr, month: Expr, day: Expr) -> Expr {
         super::make_date().call(vec![year, month, day])
@@ -258,6 +279,16 @@ pub mod expr_fn {
         super::to_timestamp().call(args)
     }
 
+    #[doc = "bins a timestamp like date_bin, filling missing buckets when used as a grouping expression"]
+    pub fn time_bucket_gapfill(stride: Expr, source: Expr, origin: Expr) -> Expr {
+        super::time_bucket_gapfill().call(vec![stride, source, origin])
+    }
+
+    #[doc = "returns the start of the non-overlapping window of the given size that contains a timestamp"]
+    pub fn tumble(source: Expr, size: Expr) -> Expr {
+        super::tumble().call(vec![source, size])
+    }
+
     #[doc = "converts a string and optional formats to a `Timestamp(Seconds, None)`"]
     pub fn to_timestamp_seconds(args: Vec<Expr>) -> Expr {
         super::to_timestamp_seconds().call(args)
@@ -288,6 +319,8 @@ pub fn functions() -> Vec<Arc<ScalarUDF>> {
         date_part(),
         date_trunc(),
         from_unixtime(),
+        hop(),
+        locf(),
         make_date(),
         now(),
         to_char(),
@@ -298,5 +331,7 @@ pub fn functions() -> Vec<Arc<ScalarUDF>> {
         to_timestamp_millis(),
         to_timestamp_micros(),
         to_timestamp_nanos(),
+        time_bucket_gapfill(),
+        tumble(),
     ]
 }
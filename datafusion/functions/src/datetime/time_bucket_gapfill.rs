@@ -0,0 +1,120 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+
+use arrow::datatypes::DataType;
+
+use datafusion_common::{exec_err, Result};
+use datafusion_expr::{
+    ColumnarValue, FuncMonotonicity, ScalarUDFImpl, Signature, Volatility,
+};
+
+use crate::datetime::date_bin::DateBinFunc;
+
+/// `time_bucket_gapfill(stride, source[, origin])`: bins timestamps like
+/// `date_bin`, and additionally marks the grouping expression of an aggregate
+/// whose missing buckets should be filled.
+///
+/// When used as a `GROUP BY` expression, the analyzer inserts a gap filling
+/// operator above the aggregate that produces a row for every bucket between
+/// the time bounds of the `WHERE` clause. The aggregates of those rows are
+/// `NULL`, unless they are wrapped in [`LocfFunc`].
+#[derive(Debug, Default)]
+pub struct TimeBucketGapfillFunc {
+    date_bin: DateBinFunc,
+}
+
+impl TimeBucketGapfillFunc {
+    pub fn new() -> Self {
+        Self::default()
+    }
+}
+
+impl ScalarUDFImpl for TimeBucketGapfillFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "time_bucket_gapfill"
+    }
+
+    fn signature(&self) -> &Signature {
+        self.date_bin.signature()
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        self.date_bin.return_type(arg_types)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        self.date_bin.invoke(args)
+    }
+
+    fn monotonicity(&self) -> Result<Option<FuncMonotonicity>> {
+        self.date_bin.monotonicity()
+    }
+}
+
+/// `locf(expr)`: fills the rows produced by `time_bucket_gapfill`, and `NULL`
+/// values of `expr`, with the last value of `expr` observed in the same group
+/// ("last observation carried forward").
+///
+/// The function is removed by the analyzer, it can not be evaluated.
+#[derive(Debug)]
+pub struct LocfFunc {
+    signature: Signature,
+}
+
+impl Default for LocfFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl LocfFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::any(1, Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for LocfFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "locf"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        Ok(arg_types[0].clone())
+    }
+
+    fn invoke(&self, _args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        exec_err!(
+            "locf can only be used on an aggregate of a query grouped by time_bucket_gapfill"
+        )
+    }
+}
@@ -0,0 +1,162 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+
+use arrow::datatypes::DataType;
+use arrow::datatypes::DataType::{Interval, Timestamp};
+use arrow::datatypes::IntervalUnit::{DayTime, MonthDayNano};
+use arrow::datatypes::TimeUnit::{Microsecond, Millisecond, Nanosecond, Second};
+
+use datafusion_common::{exec_err, plan_err, Result, ScalarValue};
+use datafusion_expr::TypeSignature::Exact;
+use datafusion_expr::{
+    ColumnarValue, FuncMonotonicity, ScalarUDFImpl, Signature, TypeSignature, Volatility,
+    TIMEZONE_WILDCARD,
+};
+
+use crate::datetime::date_bin::date_bin_impl;
+
+/// `tumble(source, size)`: the start of the fixed-size, non-overlapping time
+/// window `source` falls into, with windows aligned to the unix epoch.
+///
+/// Grouping by `tumble` is equivalent to grouping by `date_bin(size, source)`.
+/// As the result increases with `source`, aggregates grouped by `tumble` over
+/// an input ordered by `source` emit each window once it is complete, which
+/// allows them to run on unbounded inputs.
+#[derive(Debug)]
+pub struct TumbleFunc {
+    signature: Signature,
+}
+
+impl Default for TumbleFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl TumbleFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::one_of(
+                window_signatures(|source, interval| vec![source, interval]),
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+/// Signatures of time window functions taking a timestamp and intervals,
+/// with `make_args` building the arguments from the timestamp and interval
+/// types
+pub(crate) fn window_signatures(
+    make_args: impl Fn(DataType, DataType) -> Vec<DataType>,
+) -> Vec<TypeSignature> {
+    let mut signatures = vec![];
+    for unit in [Nanosecond, Microsecond, Millisecond, Second] {
+        for tz in [None, Some(TIMEZONE_WILDCARD.into())] {
+            for interval in [MonthDayNano, DayTime] {
+                signatures.push(Exact(make_args(
+                    Timestamp(unit.clone(), tz.clone()),
+                    Interval(interval),
+                )));
+            }
+        }
+    }
+    signatures
+}
+
+impl ScalarUDFImpl for TumbleFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "tumble"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, arg_types: &[DataType]) -> Result<DataType> {
+        match &arg_types[0] {
+            Timestamp(unit, tz_opt) => Ok(Timestamp(unit.clone(), tz_opt.clone())),
+            _ => plan_err!(
+                "The tumble function can only accept timestamp as the first arg."
+            ),
+        }
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        if args.len() != 2 {
+            return exec_err!("TUMBLE expected two arguments");
+        }
+        // Windows are aligned to the unix epoch
+        let origin = ColumnarValue::Scalar(ScalarValue::TimestampNanosecond(
+            Some(0),
+            Some("+00:00".into()),
+        ));
+        date_bin_impl(&args[1], &args[0], &origin)
+    }
+
+    fn monotonicity(&self) -> Result<Option<FuncMonotonicity>> {
+        Ok(Some(vec![Some(true), None]))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{ArrayRef, TimestampSecondArray};
+    use arrow::datatypes::IntervalDayTimeType;
+
+    use datafusion_common::ScalarValue;
+    use datafusion_expr::{ColumnarValue, ScalarUDFImpl};
+
+    use crate::datetime::tumble::TumbleFunc;
+
+    #[test]
+    fn test_tumble() {
+        let timestamps: ArrayRef = Arc::new(TimestampSecondArray::from(vec![
+            Some(0),
+            Some(59),
+            Some(60),
+            None,
+            Some(-1),
+        ]));
+        let size = IntervalDayTimeType::make_value(0, 60_000);
+        let res = TumbleFunc::new()
+            .invoke(&[
+                ColumnarValue::Array(timestamps),
+                ColumnarValue::Scalar(ScalarValue::IntervalDayTime(Some(size))),
+            ])
+            .unwrap();
+        let ColumnarValue::Array(res) = res else {
+            panic!("Expected an array");
+        };
+        let expected: ArrayRef = Arc::new(TimestampSecondArray::from(vec![
+            Some(0),
+            Some(0),
+            Some(60),
+            None,
+            Some(-60),
+        ]));
+        assert_eq!(&res, &expected);
+    }
+}
@@ -0,0 +1,65 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use arrow::array::{Array, ArrayRef};
+use arrow::datatypes::DataType;
+
+use datafusion_common::cast::as_generic_binary_array;
+use datafusion_common::{exec_err, Result};
+
+use crate::geo::wkb::Geometry;
+
+/// The types of the geometry arguments of the functions, holding WKB
+pub(super) fn geometry_types() -> Vec<DataType> {
+    vec![DataType::Binary, DataType::LargeBinary]
+}
+
+/// The geometries of the WKB array `array`
+pub(super) fn geometry_values(
+    array: &ArrayRef,
+    name: &str,
+) -> Result<Vec<Option<Geometry>>> {
+    match array.data_type() {
+        DataType::Binary => as_generic_binary_array::<i32>(array)?
+            .iter()
+            .map(|value| value.map(Geometry::try_from_wkb).transpose())
+            .collect(),
+        DataType::LargeBinary => as_generic_binary_array::<i64>(array)?
+            .iter()
+            .map(|value| value.map(Geometry::try_from_wkb).transpose())
+            .collect(),
+        other => exec_err!("Unsupported data type {other:?} for function {name}"),
+    }
+}
+
+/// The geometry of `geometries` at the row `index`, the only one of a
+/// constant argument
+pub(super) fn geometry_at(
+    geometries: &[Option<Geometry>],
+    index: usize,
+) -> Option<&Geometry> {
+    match geometries {
+        [geometry] => geometry.as_ref(),
+        geometries => geometries[index].as_ref(),
+    }
+}
+
+/// The number of rows of the result of a function of `args`, some of which
+/// may be singular constants
+pub(super) fn row_count(args: &[ArrayRef]) -> usize {
+    args.iter().map(|arg| arg.len()).max().unwrap_or(0)
+}
@@ -0,0 +1,73 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! "geo" DataFusion functions, operating on planar geometries
+//!
+//! Geometries are accepted as their well-known binary (WKB) representation,
+//! in `Binary` or `LargeBinary` values.
+//!
+//! A geometry column `geom` may come with the columns `geom_xmin`,
+//! `geom_ymin`, `geom_xmax` and `geom_ymax` holding the bounding box of
+//! each geometry (see [`bounding_box_columns`]): the pruning of files and
+//! row groups then skips the ones whose bounding boxes cannot match a spatial
+//! filter of a constant geometry.
+
+use std::sync::Arc;
+
+use datafusion_expr::ScalarUDF;
+
+mod common;
+mod st_contains;
+mod st_distance;
+mod st_point;
+pub mod wkb;
+
+// create UDFs
+make_udf_function!(st_contains::StContainsFunc, ST_CONTAINS, st_contains);
+make_udf_function!(st_distance::StDistanceFunc, ST_DISTANCE, st_distance);
+make_udf_function!(st_point::StPointFunc, ST_POINT, st_point);
+
+/// The names of the columns holding the bounding box of each geometry of
+/// the column `column`: the minimum x, the minimum y, the maximum x and the
+/// maximum y
+pub fn bounding_box_columns(column: &str) -> [String; 4] {
+    ["xmin", "ymin", "xmax", "ymax"].map(|suffix| format!("{column}_{suffix}"))
+}
+
+pub mod expr_fn {
+    use datafusion_expr::Expr;
+
+    #[doc = "Returns true if the second geometry lies in the first one"]
+    pub fn st_contains(geometry: Expr, other: Expr) -> Expr {
+        super::st_contains().call(vec![geometry, other])
+    }
+
+    #[doc = "Returns the minimum planar distance between two geometries"]
+    pub fn st_distance(geometry: Expr, other: Expr) -> Expr {
+        super::st_distance().call(vec![geometry, other])
+    }
+
+    #[doc = "Returns the WKB of the point of coordinates x and y"]
+    pub fn st_point(x: Expr, y: Expr) -> Expr {
+        super::st_point().call(vec![x, y])
+    }
+}
+
+///   Return a list of all functions in this package
+pub fn functions() -> Vec<Arc<ScalarUDF>> {
+    vec![st_contains(), st_distance(), st_point()]
+}
@@ -0,0 +1,121 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, Float64Array};
+use arrow::datatypes::DataType;
+
+use datafusion_common::Result;
+use datafusion_expr::TypeSignature::Exact;
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature, Volatility};
+use datafusion_physical_expr::functions::Hint;
+
+use crate::geo::common::{geometry_at, geometry_types, geometry_values, row_count};
+use crate::utils::make_scalar_function;
+
+/// Returns the minimum planar distance between two geometries, 0 if they
+/// intersect, null if one of them is empty.
+/// st_distance(st_point(0.0, 0.0), st_point(3.0, 4.0)) = 5.0
+#[derive(Debug)]
+pub struct StDistanceFunc {
+    signature: Signature,
+}
+
+impl StDistanceFunc {
+    pub fn new() -> Self {
+        let types = geometry_types();
+        Self {
+            signature: Signature::one_of(
+                types
+                    .iter()
+                    .flat_map(|a| types.iter().map(|b| Exact(vec![a.clone(), b.clone()])))
+                    .collect(),
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for StDistanceFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "st_distance"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Float64)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        // a constant geometry is read only once
+        make_scalar_function(
+            st_distance,
+            vec![Hint::AcceptsSingular, Hint::AcceptsSingular],
+        )(args)
+    }
+}
+
+fn st_distance(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let geometries = geometry_values(&args[0], "st_distance")?;
+    let others = geometry_values(&args[1], "st_distance")?;
+    let result = (0..row_count(args))
+        .map(|index| {
+            match (geometry_at(&geometries, index), geometry_at(&others, index)) {
+                (Some(geometry), Some(other)) => geometry.distance(other),
+                _ => None,
+            }
+        })
+        .collect::<Float64Array>();
+    Ok(Arc::new(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Array, BinaryArray};
+
+    use super::*;
+    use crate::geo::wkb::{Coord, Geometry};
+
+    #[test]
+    fn test_st_distance() -> Result<()> {
+        let origin = Geometry::point_to_wkb(Coord::new(0.0, 0.0));
+        let origin: ArrayRef = Arc::new(BinaryArray::from_vec(vec![origin.as_slice()]));
+        let points =
+            [Coord::new(3.0, 4.0), Coord::new(0.0, -2.0)].map(Geometry::point_to_wkb);
+        let points: ArrayRef = Arc::new(BinaryArray::from_opt_vec(vec![
+            Some(points[0].as_slice()),
+            Some(points[1].as_slice()),
+            None,
+        ]));
+        let result = st_distance(&[origin, points])?;
+        let result = result.as_any().downcast_ref::<Float64Array>().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(5.0), Some(2.0), None]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,105 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, BinaryBuilder};
+use arrow::datatypes::DataType;
+
+use datafusion_common::cast::as_float64_array;
+use datafusion_common::Result;
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature, Volatility};
+
+use crate::geo::wkb::{Coord, Geometry};
+use crate::utils::make_scalar_function;
+
+/// Returns the WKB of the point of coordinates x and y.
+/// st_point(1.0, 2.0) = 0101000000000000000000f03f0000000000000040
+#[derive(Debug)]
+pub struct StPointFunc {
+    signature: Signature,
+}
+
+impl StPointFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::exact(
+                vec![DataType::Float64, DataType::Float64],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for StPointFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "st_point"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Binary)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(st_point, vec![])(args)
+    }
+}
+
+fn st_point(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let xs = as_float64_array(&args[0])?;
+    let ys = as_float64_array(&args[1])?;
+    let mut builder = BinaryBuilder::with_capacity(xs.len(), xs.len() * 21);
+    for (x, y) in xs.iter().zip(ys.iter()) {
+        match (x, y) {
+            (Some(x), Some(y)) => {
+                builder.append_value(Geometry::point_to_wkb(Coord::new(x, y)))
+            }
+            _ => builder.append_null(),
+        }
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Array, BinaryArray, Float64Array};
+
+    use super::*;
+
+    #[test]
+    fn test_st_point() -> Result<()> {
+        let xs: ArrayRef = Arc::new(Float64Array::from(vec![Some(1.0), None]));
+        let ys: ArrayRef = Arc::new(Float64Array::from(vec![Some(2.0), Some(3.0)]));
+        let result = st_point(&[xs, ys])?;
+        let result = result.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(
+            Geometry::try_from_wkb(result.value(0))?,
+            Geometry::Point(Coord::new(1.0, 2.0))
+        );
+        assert!(result.is_null(1));
+        Ok(())
+    }
+}
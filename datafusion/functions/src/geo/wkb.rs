@@ -0,0 +1,667 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Well-known binary (WKB) geometries and the planar algorithms of the geo
+//! functions
+//!
+//! Both the ISO and the extended (PostGIS) WKB dialects are read. The Z and M
+//! coordinates are ignored, all the algorithms are in the plane.
+
+use datafusion_common::{exec_datafusion_err, exec_err, Result};
+
+const WKB_POINT: u32 = 1;
+const WKB_LINESTRING: u32 = 2;
+const WKB_POLYGON: u32 = 3;
+const WKB_MULTIPOINT: u32 = 4;
+const WKB_MULTILINESTRING: u32 = 5;
+const WKB_MULTIPOLYGON: u32 = 6;
+const WKB_GEOMETRYCOLLECTION: u32 = 7;
+
+/// The maximum nesting depth of the collections of a geometry, so that
+/// reading crafted WKB does not overflow the stack
+const MAX_NESTING_DEPTH: usize = 64;
+
+/// A point of the plane
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Coord {
+    pub x: f64,
+    pub y: f64,
+}
+
+impl Coord {
+    pub fn new(x: f64, y: f64) -> Self {
+        Self { x, y }
+    }
+
+    fn distance(&self, other: &Coord) -> f64 {
+        (self.x - other.x).hypot(self.y - other.y)
+    }
+}
+
+/// The bounding box of a geometry, with inclusive bounds
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct BoundingBox {
+    pub xmin: f64,
+    pub ymin: f64,
+    pub xmax: f64,
+    pub ymax: f64,
+}
+
+impl BoundingBox {
+    fn of_coord(coord: Coord) -> Self {
+        Self {
+            xmin: coord.x,
+            ymin: coord.y,
+            xmax: coord.x,
+            ymax: coord.y,
+        }
+    }
+
+    fn union(&self, other: &BoundingBox) -> Self {
+        Self {
+            xmin: self.xmin.min(other.xmin),
+            ymin: self.ymin.min(other.ymin),
+            xmax: self.xmax.max(other.xmax),
+            ymax: self.ymax.max(other.ymax),
+        }
+    }
+
+    /// Returns the bounding box grown by `distance` on every side
+    pub fn expand(&self, distance: f64) -> Self {
+        Self {
+            xmin: self.xmin - distance,
+            ymin: self.ymin - distance,
+            xmax: self.xmax + distance,
+            ymax: self.ymax + distance,
+        }
+    }
+
+    /// Returns true if `other` is inside this bounding box
+    pub fn contains(&self, other: &BoundingBox) -> bool {
+        self.xmin <= other.xmin
+            && self.ymin <= other.ymin
+            && self.xmax >= other.xmax
+            && self.ymax >= other.ymax
+    }
+}
+
+/// A geometry read from its well-known binary representation
+#[derive(Debug, Clone, PartialEq)]
+pub enum Geometry {
+    Point(Coord),
+    LineString(Vec<Coord>),
+    /// The exterior ring followed by the holes, each ring being closed
+    Polygon(Vec<Vec<Coord>>),
+    /// A multi point, multi line string, multi polygon or geometry
+    /// collection
+    Collection(Vec<Geometry>),
+}
+
+impl Geometry {
+    /// Reads a geometry from its well-known binary representation
+    pub fn try_from_wkb(wkb: &[u8]) -> Result<Self> {
+        let mut reader = WkbReader {
+            wkb,
+            offset: 0,
+            little_endian: true,
+        };
+        let geometry = reader.read_geometry(0)?;
+        if reader.offset != wkb.len() {
+            return exec_err!(
+                "Invalid WKB geometry: {} trailing bytes",
+                wkb.len() - reader.offset
+            );
+        }
+        Ok(geometry)
+    }
+
+    /// Returns the little endian well-known binary representation of the
+    /// point `coord`
+    pub fn point_to_wkb(coord: Coord) -> Vec<u8> {
+        let mut wkb = Vec::with_capacity(21);
+        wkb.push(1);
+        wkb.extend_from_slice(&WKB_POINT.to_le_bytes());
+        wkb.extend_from_slice(&coord.x.to_le_bytes());
+        wkb.extend_from_slice(&coord.y.to_le_bytes());
+        wkb
+    }
+
+    /// Returns the bounding box of the geometry, `None` if it is empty
+    pub fn bounding_box(&self) -> Option<BoundingBox> {
+        let parts = self.parts();
+        parts
+            .coords()
+            .map(BoundingBox::of_coord)
+            .reduce(|bbox, other| bbox.union(&other))
+    }
+
+    /// Returns true if `other` lies in this geometry and the interiors of the
+    /// geometries intersect, as `ST_Contains`
+    pub fn contains(&self, other: &Geometry) -> bool {
+        let (Some(bbox), Some(other_bbox)) = (self.bounding_box(), other.bounding_box())
+        else {
+            return false;
+        };
+        if !bbox.contains(&other_bbox) {
+            return false;
+        }
+        let parts = self.parts();
+        let other_parts = other.parts();
+
+        // every point of `other` is covered
+        let samples = other_parts.samples();
+        if !samples.iter().all(|coord| parts.covers(*coord)) {
+            return false;
+        }
+        // `other` does not leave the polygons through their boundaries
+        if !parts.polygons.is_empty() {
+            let edges = parts.segments();
+            let leaves = other_parts
+                .segments()
+                .iter()
+                .any(|segment| edges.iter().any(|edge| crosses(segment, edge)));
+            if leaves {
+                return false;
+            }
+        }
+        // the boundary of `self`, e.g. a hole, is not inside `other`
+        if parts
+            .coords()
+            .any(|coord| other_parts.in_polygons_interior(coord))
+        {
+            return false;
+        }
+        !other_parts.polygons.is_empty()
+            || samples.iter().any(|coord| parts.interior_contains(*coord))
+    }
+
+    /// Returns the minimum distance between the points of the geometries, 0
+    /// if they intersect, `None` if one of them is empty
+    pub fn distance(&self, other: &Geometry) -> Option<f64> {
+        let parts = self.parts();
+        let other_parts = other.parts();
+        if parts.coords().next().is_none() || other_parts.coords().next().is_none() {
+            return None;
+        }
+        if other_parts.coords().any(|coord| parts.in_polygons(coord))
+            || parts.coords().any(|coord| other_parts.in_polygons(coord))
+        {
+            return Some(0.0);
+        }
+        let segments = parts.segments();
+        let other_segments = other_parts.segments();
+        segments
+            .iter()
+            .flat_map(|segment| {
+                other_segments
+                    .iter()
+                    .map(move |other| segment_distance(segment, other))
+            })
+            .reduce(f64::min)
+    }
+
+    fn parts(&self) -> Parts<'_> {
+        let mut parts = Parts::default();
+        self.collect_parts(&mut parts);
+        parts
+    }
+
+    fn collect_parts<'a>(&'a self, parts: &mut Parts<'a>) {
+        match self {
+            Geometry::Point(coord) => parts.points.push(*coord),
+            Geometry::LineString(coords) => match coords.as_slice() {
+                [] => {}
+                [coord] => parts.points.push(*coord),
+                coords => parts.lines.push(coords),
+            },
+            Geometry::Polygon(rings) => {
+                if rings.first().is_some_and(|ring| !ring.is_empty()) {
+                    parts.polygons.push(rings)
+                }
+            }
+            Geometry::Collection(geometries) => geometries
+                .iter()
+                .for_each(|geometry| geometry.collect_parts(parts)),
+        }
+    }
+}
+
+/// A segment of the plane, a point if both ends are equal
+type Segment = (Coord, Coord);
+
+/// The points, the lines and the polygons making up a geometry
+#[derive(Default)]
+struct Parts<'a> {
+    points: Vec<Coord>,
+    lines: Vec<&'a [Coord]>,
+    polygons: Vec<&'a [Vec<Coord>]>,
+}
+
+impl Parts<'_> {
+    /// The vertices of the geometry
+    fn coords(&self) -> impl Iterator<Item = Coord> + '_ {
+        let lines = self.lines.iter().flat_map(|line| line.iter().copied());
+        let rings = self
+            .polygons
+            .iter()
+            .flat_map(|rings| rings.iter().flat_map(|ring| ring.iter().copied()));
+        self.points.iter().copied().chain(lines).chain(rings)
+    }
+
+    /// The segments of the geometry, its points being degenerate segments
+    fn segments(&self) -> Vec<Segment> {
+        let lines = self.lines.iter().copied();
+        let rings = self
+            .polygons
+            .iter()
+            .flat_map(|rings| rings.iter().map(|ring| ring.as_slice()));
+        let segments = lines
+            .chain(rings)
+            .flat_map(|coords| coords.windows(2).map(|pair| (pair[0], pair[1])));
+        self.points
+            .iter()
+            .map(|point| (*point, *point))
+            .chain(segments)
+            .collect()
+    }
+
+    /// The vertices of the geometry and the middles of its segments
+    fn samples(&self) -> Vec<Coord> {
+        self.segments()
+            .into_iter()
+            .flat_map(|(start, end)| {
+                let middle = Coord::new((start.x + end.x) / 2.0, (start.y + end.y) / 2.0);
+                [start, middle, end]
+            })
+            .collect()
+    }
+
+    /// Returns true if `coord` is in the geometry, possibly on its boundary
+    fn covers(&self, coord: Coord) -> bool {
+        self.segments()
+            .iter()
+            .any(|segment| point_segment_distance(coord, segment) == 0.0)
+            || self.in_polygons(coord)
+    }
+
+    /// Returns true if `coord` is in the interior of the geometry
+    fn interior_contains(&self, coord: Coord) -> bool {
+        let on_line = |line: &&[Coord]| {
+            let closed = line.first() == line.last();
+            let endpoint = !closed && (line[0] == coord || line[line.len() - 1] == coord);
+            !endpoint
+                && line
+                    .windows(2)
+                    .any(|pair| point_segment_distance(coord, &(pair[0], pair[1])) == 0.0)
+        };
+        self.points.contains(&coord)
+            || self.lines.iter().any(on_line)
+            || self.in_polygons_interior(coord)
+    }
+
+    /// Returns true if `coord` is strictly inside one of the polygons of the
+    /// geometry
+    fn in_polygons_interior(&self, coord: Coord) -> bool {
+        self.polygons.iter().any(|rings| {
+            let on_boundary = rings.iter().any(|ring| {
+                ring.windows(2)
+                    .any(|pair| point_segment_distance(coord, &(pair[0], pair[1])) == 0.0)
+            });
+            !on_boundary && polygon_contains(rings, coord)
+        })
+    }
+
+    /// Returns true if `coord` is inside one of the polygons of the geometry,
+    /// possibly on its boundary
+    fn in_polygons(&self, coord: Coord) -> bool {
+        self.polygons.iter().any(|rings| {
+            let on_boundary = rings.iter().any(|ring| {
+                ring.windows(2)
+                    .any(|pair| point_segment_distance(coord, &(pair[0], pair[1])) == 0.0)
+            });
+            on_boundary || polygon_contains(rings, coord)
+        })
+    }
+}
+
+/// Returns true if `coord` is strictly inside the exterior ring of a polygon
+/// and outside of its holes, ignoring the boundaries
+fn polygon_contains(rings: &[Vec<Coord>], coord: Coord) -> bool {
+    match rings.split_first() {
+        Some((exterior, holes)) => {
+            ring_contains(exterior, coord)
+                && !holes.iter().any(|hole| ring_contains(hole, coord))
+        }
+        None => false,
+    }
+}
+
+/// Ray casting: returns true if a horizontal ray from `coord` crosses the
+/// closed ring `ring` an odd number of times
+fn ring_contains(ring: &[Coord], coord: Coord) -> bool {
+    ring.windows(2).fold(false, |inside, pair| {
+        let (a, b) = (pair[0], pair[1]);
+        if (a.y > coord.y) != (b.y > coord.y)
+            && coord.x < (b.x - a.x) * (coord.y - a.y) / (b.y - a.y) + a.x
+        {
+            !inside
+        } else {
+            inside
+        }
+    })
+}
+
+/// The cross product of `b - a` and `c - a`: positive if `c` is on the left of
+/// `a -> b`, negative if on the right, 0 if the points are aligned
+fn orientation(a: Coord, b: Coord, c: Coord) -> f64 {
+    (b.x - a.x) * (c.y - a.y) - (b.y - a.y) * (c.x - a.x)
+}
+
+/// Returns true if the segments cross at a single point interior to both
+fn crosses(segment: &Segment, other: &Segment) -> bool {
+    let (a, b) = *segment;
+    let (c, d) = *other;
+    orientation(a, b, c) * orientation(a, b, d) < 0.0
+        && orientation(c, d, a) * orientation(c, d, b) < 0.0
+}
+
+/// Returns true if the segments share at least one point
+fn intersects(segment: &Segment, other: &Segment) -> bool {
+    crosses(segment, other)
+        || point_segment_distance(segment.0, other) == 0.0
+        || point_segment_distance(segment.1, other) == 0.0
+        || point_segment_distance(other.0, segment) == 0.0
+        || point_segment_distance(other.1, segment) == 0.0
+}
+
+fn point_segment_distance(coord: Coord, segment: &Segment) -> f64 {
+    let (a, b) = *segment;
+    let (dx, dy) = (b.x - a.x, b.y - a.y);
+    let length = dx * dx + dy * dy;
+    if length == 0.0 {
+        return coord.distance(&a);
+    }
+    let t = (((coord.x - a.x) * dx + (coord.y - a.y) * dy) / length).clamp(0.0, 1.0);
+    coord.distance(&Coord::new(a.x + t * dx, a.y + t * dy))
+}
+
+fn segment_distance(segment: &Segment, other: &Segment) -> f64 {
+    if intersects(segment, other) {
+        return 0.0;
+    }
+    point_segment_distance(segment.0, other)
+        .min(point_segment_distance(segment.1, other))
+        .min(point_segment_distance(other.0, segment))
+        .min(point_segment_distance(other.1, segment))
+}
+
+struct WkbReader<'a> {
+    wkb: &'a [u8],
+    offset: usize,
+    little_endian: bool,
+}
+
+impl WkbReader<'_> {
+    fn read_bytes<const N: usize>(&mut self) -> Result<[u8; N]> {
+        let bytes = self
+            .wkb
+            .get(self.offset..self.offset + N)
+            .ok_or_else(|| exec_datafusion_err!("Invalid WKB geometry: truncated"))?;
+        self.offset += N;
+        Ok(bytes.try_into().unwrap())
+    }
+
+    fn read_u32(&mut self) -> Result<u32> {
+        let bytes = self.read_bytes()?;
+        Ok(if self.little_endian {
+            u32::from_le_bytes(bytes)
+        } else {
+            u32::from_be_bytes(bytes)
+        })
+    }
+
+    fn read_f64(&mut self) -> Result<f64> {
+        let bytes = self.read_bytes()?;
+        Ok(if self.little_endian {
+            f64::from_le_bytes(bytes)
+        } else {
+            f64::from_be_bytes(bytes)
+        })
+    }
+
+    /// Reads a coordinate of `dimensions` values, keeping only X and Y
+    fn read_coord(&mut self, dimensions: usize) -> Result<Coord> {
+        let coord = Coord::new(self.read_f64()?, self.read_f64()?);
+        for _ in 2..dimensions {
+            self.read_f64()?;
+        }
+        Ok(coord)
+    }
+
+    fn read_coords(&mut self, dimensions: usize) -> Result<Vec<Coord>> {
+        let len = self.read_u32()? as usize;
+        // every coordinate is at least 16 bytes long
+        if len > (self.wkb.len() - self.offset) / 16 {
+            return exec_err!("Invalid WKB geometry: truncated");
+        }
+        (0..len).map(|_| self.read_coord(dimensions)).collect()
+    }
+
+    /// Reads a geometry nested in `depth` collections
+    fn read_geometry(&mut self, depth: usize) -> Result<Geometry> {
+        if depth > MAX_NESTING_DEPTH {
+            return exec_err!(
+                "Invalid WKB geometry: more than {MAX_NESTING_DEPTH} nested collections"
+            );
+        }
+        let [byte_order] = self.read_bytes()?;
+        self.little_endian = match byte_order {
+            0 => false,
+            1 => true,
+            other => return exec_err!("Invalid WKB geometry: byte order {other}"),
+        };
+        let type_id = self.read_u32()?;
+
+        // the extended WKB flags, then the ISO dimensions
+        let mut dimensions = 2;
+        if type_id & 0x8000_0000 != 0 {
+            dimensions += 1;
+        }
+        if type_id & 0x4000_0000 != 0 {
+            dimensions += 1;
+        }
+        if type_id & 0x2000_0000 != 0 {
+            // the SRID
+            self.read_u32()?;
+        }
+        let type_id = type_id & 0x0fff_ffff;
+        dimensions += match type_id / 1000 {
+            0 => 0,
+            1 | 2 => 1,
+            3 => 2,
+            _ => return exec_err!("Invalid WKB geometry: type {type_id}"),
+        };
+
+        match type_id % 1000 {
+            WKB_POINT => {
+                let coord = self.read_coord(dimensions)?;
+                // an empty point has NaN coordinates
+                Ok(if coord.x.is_nan() && coord.y.is_nan() {
+                    Geometry::Collection(vec![])
+                } else {
+                    Geometry::Point(coord)
+                })
+            }
+            WKB_LINESTRING => Ok(Geometry::LineString(self.read_coords(dimensions)?)),
+            WKB_POLYGON => {
+                let len = self.read_u32()?;
+                let rings = (0..len)
+                    .map(|_| self.read_coords(dimensions))
+                    .collect::<Result<_>>()?;
+                Ok(Geometry::Polygon(rings))
+            }
+            WKB_MULTIPOINT
+            | WKB_MULTILINESTRING
+            | WKB_MULTIPOLYGON
+            | WKB_GEOMETRYCOLLECTION => {
+                let len = self.read_u32()? as usize;
+                // every geometry is at least 5 bytes long
+                if len > (self.wkb.len() - self.offset) / 5 {
+                    return exec_err!("Invalid WKB geometry: truncated");
+                }
+                let geometries = (0..len)
+                    .map(|_| self.read_geometry(depth + 1))
+                    .collect::<Result<_>>()?;
+                Ok(Geometry::Collection(geometries))
+            }
+            other => exec_err!("Unsupported WKB geometry type {other}"),
+        }
+    }
+}
+
+#[cfg(test)]
+pub(crate) mod tests {
+    use super::*;
+
+    /// Returns the little endian WKB of a polygon
+    pub(crate) fn polygon_wkb(rings: &[&[(f64, f64)]]) -> Vec<u8> {
+        let mut wkb = vec![1];
+        wkb.extend_from_slice(&WKB_POLYGON.to_le_bytes());
+        wkb.extend_from_slice(&(rings.len() as u32).to_le_bytes());
+        for ring in rings {
+            wkb.extend_from_slice(&(ring.len() as u32).to_le_bytes());
+            for (x, y) in *ring {
+                wkb.extend_from_slice(&x.to_le_bytes());
+                wkb.extend_from_slice(&y.to_le_bytes());
+            }
+        }
+        wkb
+    }
+
+    fn line_string(coords: &[(f64, f64)]) -> Geometry {
+        Geometry::LineString(coords.iter().map(|(x, y)| Coord::new(*x, *y)).collect())
+    }
+
+    fn point(x: f64, y: f64) -> Geometry {
+        Geometry::Point(Coord::new(x, y))
+    }
+
+    fn square() -> Geometry {
+        let exterior: &[(f64, f64)] = &[(0., 0.), (4., 0.), (4., 4.), (0., 4.), (0., 0.)];
+        let hole: &[(f64, f64)] = &[(1., 1.), (2., 1.), (2., 2.), (1., 2.), (1., 1.)];
+        Geometry::try_from_wkb(&polygon_wkb(&[exterior, hole])).unwrap()
+    }
+
+    #[test]
+    fn read_wkb() -> Result<()> {
+        let wkb = Geometry::point_to_wkb(Coord::new(1.5, -2.0));
+        assert_eq!(Geometry::try_from_wkb(&wkb)?, point(1.5, -2.0));
+
+        // a big endian 3D (ISO) multi point of a single point
+        let mut wkb = vec![0];
+        wkb.extend_from_slice(&4u32.to_be_bytes());
+        wkb.extend_from_slice(&1u32.to_be_bytes());
+        wkb.push(0);
+        wkb.extend_from_slice(&1001u32.to_be_bytes());
+        for value in [1.0f64, 2.0, 3.0] {
+            wkb.extend_from_slice(&value.to_be_bytes());
+        }
+        assert_eq!(
+            Geometry::try_from_wkb(&wkb)?,
+            Geometry::Collection(vec![point(1.0, 2.0)])
+        );
+
+        assert_eq!(
+            square().bounding_box(),
+            Some(BoundingBox {
+                xmin: 0.0,
+                ymin: 0.0,
+                xmax: 4.0,
+                ymax: 4.0
+            })
+        );
+
+        assert!(Geometry::try_from_wkb(&wkb[..wkb.len() - 1]).is_err());
+        assert!(Geometry::try_from_wkb(&[2, 1, 0, 0, 0]).is_err());
+
+        // geometry collections nested in each other
+        let nested = |depth: usize| {
+            let mut wkb = vec![];
+            for _ in 0..depth {
+                wkb.push(1);
+                wkb.extend_from_slice(&WKB_GEOMETRYCOLLECTION.to_le_bytes());
+                wkb.extend_from_slice(&1u32.to_le_bytes());
+            }
+            wkb.extend_from_slice(&Geometry::point_to_wkb(Coord::new(1.0, 2.0)));
+            wkb
+        };
+        assert!(Geometry::try_from_wkb(&nested(MAX_NESTING_DEPTH)).is_ok());
+        let err = Geometry::try_from_wkb(&nested(100_000)).unwrap_err();
+        assert!(err.to_string().contains("nested collections"), "{err}");
+        Ok(())
+    }
+
+    #[test]
+    fn contains() {
+        let square = square();
+        assert!(square.contains(&point(3.0, 3.0)));
+        // in the hole, on the boundary, outside
+        assert!(!square.contains(&point(1.5, 1.5)));
+        assert!(!square.contains(&point(4.0, 2.0)));
+        assert!(!square.contains(&point(5.0, 2.0)));
+
+        assert!(square.contains(&line_string(&[(3., 0.5), (3., 3.5)])));
+        // crossing the hole
+        assert!(!square.contains(&line_string(&[(0.5, 1.5), (3., 1.5)])));
+        assert!(!square.contains(&line_string(&[(3., 3.), (5., 3.)])));
+        assert!(square.contains(&square));
+        // around the hole
+        let around_hole: &[(f64, f64)] =
+            &[(0.5, 0.5), (2.5, 0.5), (2.5, 2.5), (0.5, 2.5), (0.5, 0.5)];
+        let around_hole = Geometry::try_from_wkb(&polygon_wkb(&[around_hole])).unwrap();
+        assert!(!square.contains(&around_hole));
+
+        let line = line_string(&[(0., 0.), (2., 2.)]);
+        assert!(line.contains(&point(1.0, 1.0)));
+        assert!(!line.contains(&point(0.0, 0.0)));
+        assert!(point(1.0, 1.0).contains(&point(1.0, 1.0)));
+        assert!(!point(1.0, 1.0).contains(&Geometry::Collection(vec![])));
+    }
+
+    #[test]
+    fn distance() {
+        let square = square();
+        assert_eq!(square.distance(&point(7.0, 8.0)), Some(5.0));
+        assert_eq!(square.distance(&point(3.0, 3.0)), Some(0.0));
+        // in the hole
+        assert_eq!(square.distance(&point(1.5, 1.75)), Some(0.25));
+        assert_eq!(
+            square.distance(&line_string(&[(-1., 5.), (5., 5.)])),
+            Some(1.0)
+        );
+        assert_eq!(
+            line_string(&[(0., 0.), (2., 2.)])
+                .distance(&line_string(&[(0., 2.), (2., 0.)])),
+            Some(0.0)
+        );
+        assert_eq!(point(0.0, 0.0).distance(&point(3.0, 4.0)), Some(5.0));
+        assert_eq!(
+            point(0.0, 0.0).distance(&Geometry::Collection(vec![])),
+            None
+        );
+    }
+}
@@ -108,12 +108,26 @@ make_stub_package!(datetime, "datetime_expressions");
 pub mod encoding;
 make_stub_package!(encoding, "encoding_expressions");
 
+/// Geospatial functions.
+/// Contains planar functions of WKB geometries such as `st_contains`
+/// Enabled via feature flag `geo_expressions`
+#[cfg(feature = "geo_expressions")]
+pub mod geo;
+make_stub_package!(geo, "geo_expressions");
+
 /// Mathematical functions.
 /// Enabled via feature flag `math_expressions`
 #[cfg(feature = "math_expressions")]
 pub mod math;
 make_stub_package!(math, "math_expressions");
 
+/// Network functions.
+/// Contains IPv4 and IPv6 address functions such as `ip_in_cidr`
+/// Enabled via feature flag `network_expressions`
+#[cfg(feature = "network_expressions")]
+pub mod network;
+make_stub_package!(network, "network_expressions");
+
 /// Regular expression functions.
 /// Enabled via feature flag `regex_expressions`
 #[cfg(feature = "regex_expressions")]
@@ -140,8 +154,12 @@ pub mod expr_fn {
     pub use super::datetime::expr_fn::*;
     #[cfg(feature = "encoding_expressions")]
     pub use super::encoding::expr_fn::*;
+    #[cfg(feature = "geo_expressions")]
+    pub use super::geo::expr_fn::*;
     #[cfg(feature = "math_expressions")]
     pub use super::math::expr_fn::*;
+    #[cfg(feature = "network_expressions")]
+    pub use super::network::expr_fn::*;
     #[cfg(feature = "regex_expressions")]
     pub use super::regex::expr_fn::*;
     #[cfg(feature = "string_expressions")]
@@ -156,7 +174,9 @@ pub fn register_all(registry: &mut dyn FunctionRegistry) -> Result<()> {
         .into_iter()
         .chain(datetime::functions())
         .chain(encoding::functions())
+        .chain(geo::functions())
         .chain(math::functions())
+        .chain(network::functions())
         .chain(regex::functions())
         .chain(crypto::functions())
         .chain(unicode::functions())
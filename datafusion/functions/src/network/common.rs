@@ -0,0 +1,75 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr};
+
+use arrow::array::{Array, ArrayRef};
+use arrow::datatypes::DataType;
+
+use datafusion_common::cast::{as_fixed_size_binary_array, as_generic_string_array};
+use datafusion_common::{exec_datafusion_err, exec_err, Result};
+
+/// The types of the IP address arguments of the functions
+pub(super) fn ip_types() -> Vec<DataType> {
+    use DataType::*;
+    vec![Utf8, LargeUtf8, FixedSizeBinary(4), FixedSizeBinary(16)]
+}
+
+/// Parses an IPv4 or IPv6 address, mapping IPv4 addresses into IPv6
+pub(super) fn parse_ip(value: &str) -> Result<Ipv6Addr> {
+    value
+        .trim()
+        .parse::<IpAddr>()
+        .map(|ip| match ip {
+            IpAddr::V4(ip) => ip.to_ipv6_mapped(),
+            IpAddr::V6(ip) => ip,
+        })
+        .map_err(|_| exec_datafusion_err!("Invalid IP address '{value}'"))
+}
+
+/// The IP addresses of `array`, mapping IPv4 addresses into IPv6
+pub(super) fn ip_values(array: &ArrayRef, name: &str) -> Result<Vec<Option<Ipv6Addr>>> {
+    match array.data_type() {
+        DataType::Utf8 => as_generic_string_array::<i32>(array)?
+            .iter()
+            .map(|value| value.map(parse_ip).transpose())
+            .collect(),
+        DataType::LargeUtf8 => as_generic_string_array::<i64>(array)?
+            .iter()
+            .map(|value| value.map(parse_ip).transpose())
+            .collect(),
+        DataType::FixedSizeBinary(4) => Ok(as_fixed_size_binary_array(array)?
+            .iter()
+            .map(|value| {
+                value.map(|value| {
+                    Ipv4Addr::new(value[0], value[1], value[2], value[3]).to_ipv6_mapped()
+                })
+            })
+            .collect()),
+        DataType::FixedSizeBinary(16) => Ok(as_fixed_size_binary_array(array)?
+            .iter()
+            .map(|value| {
+                value.map(|value| {
+                    let mut octets = [0; 16];
+                    octets.copy_from_slice(value);
+                    Ipv6Addr::from(octets)
+                })
+            })
+            .collect()),
+        other => exec_err!("Unsupported data type {other:?} for function {name}"),
+    }
+}
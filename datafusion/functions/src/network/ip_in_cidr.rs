@@ -0,0 +1,200 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::net::{IpAddr, Ipv6Addr};
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, BooleanArray};
+use arrow::datatypes::DataType;
+
+use datafusion_common::cast::as_string_array;
+use datafusion_common::{exec_datafusion_err, Result};
+use datafusion_expr::TypeSignature::Exact;
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature, Volatility};
+use datafusion_physical_expr::functions::Hint;
+
+use crate::network::common::{ip_types, ip_values};
+use crate::utils::make_scalar_function;
+
+/// Returns true if an IP address is in a CIDR block. A block without prefix
+/// length contains a single address.
+/// ip_in_cidr('192.168.0.1', '192.168.0.0/16') = true
+#[derive(Debug)]
+pub struct IpInCidrFunc {
+    signature: Signature,
+}
+
+impl IpInCidrFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::one_of(
+                ip_types()
+                    .into_iter()
+                    .map(|ip_type| Exact(vec![ip_type, DataType::Utf8]))
+                    .collect(),
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for IpInCidrFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "ip_in_cidr"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        // a constant block is parsed only once
+        make_scalar_function(ip_in_cidr, vec![Hint::Pad, Hint::AcceptsSingular])(args)
+    }
+}
+
+/// A CIDR block, in the IPv6 address space
+#[derive(Debug, PartialEq)]
+struct Cidr {
+    network: u128,
+    mask: u128,
+}
+
+impl Cidr {
+    fn try_new(value: &str) -> Result<Self> {
+        let invalid = || exec_datafusion_err!("Invalid CIDR block '{value}'");
+        let (address, prefix_len) = match value.split_once('/') {
+            Some((address, prefix_len)) => (address, Some(prefix_len)),
+            None => (value, None),
+        };
+        let (address, max_prefix_len) =
+            match address.trim().parse::<IpAddr>().map_err(|_| invalid())? {
+                IpAddr::V4(address) => (address.to_ipv6_mapped(), 32),
+                IpAddr::V6(address) => (address, 128),
+            };
+        let prefix_len = match prefix_len {
+            Some(prefix_len) => prefix_len
+                .trim()
+                .parse::<u32>()
+                .ok()
+                .filter(|prefix_len| *prefix_len <= max_prefix_len)
+                .ok_or_else(invalid)?,
+            None => max_prefix_len,
+        };
+
+        // IPv4 blocks only contain IPv4 (mapped) addresses
+        let prefix_len = prefix_len + 128 - max_prefix_len;
+        let mask = u128::MAX.checked_shl(128 - prefix_len).unwrap_or(0);
+        Ok(Self {
+            network: u128::from(address) & mask,
+            mask,
+        })
+    }
+
+    fn contains(&self, ip: Ipv6Addr) -> bool {
+        u128::from(ip) & self.mask == self.network
+    }
+}
+
+fn ip_in_cidr(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let ips = ip_values(&args[0], "ip_in_cidr")?;
+    let cidrs = as_string_array(&args[1])?;
+
+    let result = if cidrs.len() == 1 && ips.len() != 1 {
+        match cidrs.iter().next().flatten() {
+            Some(cidr) => {
+                let cidr = Cidr::try_new(cidr)?;
+                ips.into_iter()
+                    .map(|ip| ip.map(|ip| cidr.contains(ip)))
+                    .collect::<BooleanArray>()
+            }
+            None => BooleanArray::new_null(ips.len()),
+        }
+    } else {
+        ips.into_iter()
+            .zip(cidrs.iter())
+            .map(|(ip, cidr)| match (ip, cidr) {
+                (Some(ip), Some(cidr)) => Ok(Some(Cidr::try_new(cidr)?.contains(ip))),
+                _ => Ok(None),
+            })
+            .collect::<Result<BooleanArray>>()?
+    };
+    Ok(Arc::new(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Array, StringArray};
+
+    use super::*;
+
+    #[test]
+    fn test_ip_in_cidr() -> Result<()> {
+        let ips: ArrayRef = Arc::new(StringArray::from(vec![
+            Some("10.1.2.3"),
+            Some("11.0.0.1"),
+            Some("::ffff:10.0.0.1"),
+            Some("2001:db8::1"),
+            None,
+        ]));
+        let cidrs: ArrayRef = Arc::new(StringArray::from(vec!["10.0.0.0/8"]));
+        let result = ip_in_cidr(&[ips.clone(), cidrs])?;
+        let result = result.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(true), Some(false), Some(true), Some(false), None]
+        );
+
+        let cidrs: ArrayRef = Arc::new(StringArray::from(vec![
+            "10.1.2.3",
+            "0.0.0.0/0",
+            "::/0",
+            "2001:db8::/32",
+            "::/0",
+        ]));
+        let result = ip_in_cidr(&[ips, cidrs])?;
+        let result = result.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(true), Some(true), Some(true), Some(true), None]
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn invalid_cidr() {
+        assert!(Cidr::try_new("10.0.0.0/33").is_err());
+        assert!(Cidr::try_new("10.0.0/8").is_err());
+        assert!(Cidr::try_new("::/129").is_err());
+        assert_eq!(
+            Cidr::try_new("::/0").unwrap(),
+            Cidr {
+                network: 0,
+                mask: 0
+            }
+        );
+    }
+}
@@ -0,0 +1,78 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, FixedSizeBinaryBuilder};
+use arrow::datatypes::DataType;
+
+use datafusion_common::Result;
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature, Volatility};
+
+use crate::network::common::{ip_types, ip_values};
+use crate::utils::make_scalar_function;
+
+/// Parses an IP address into its 16 byte IPv6 representation, mapping IPv4
+/// addresses to `::ffff:a.b.c.d`.
+/// ip_parse('192.168.0.1') = 00000000000000000000ffffc0a80001
+#[derive(Debug)]
+pub struct IpParseFunc {
+    signature: Signature,
+}
+
+impl IpParseFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::uniform(1, ip_types(), Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for IpParseFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "ip_parse"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::FixedSizeBinary(16))
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(ip_parse, vec![])(args)
+    }
+}
+
+fn ip_parse(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let ips = ip_values(&args[0], "ip_parse")?;
+    let mut builder = FixedSizeBinaryBuilder::with_capacity(ips.len(), 16);
+    for ip in ips {
+        match ip {
+            Some(ip) => builder.append_value(ip.octets())?,
+            None => builder.append_null(),
+        }
+    }
+    Ok(Arc::new(builder.finish()))
+}
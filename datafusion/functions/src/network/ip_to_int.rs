@@ -0,0 +1,79 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, Int64Array};
+use arrow::datatypes::DataType;
+
+use datafusion_common::{exec_err, Result};
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature, Volatility};
+
+use crate::network::common::{ip_types, ip_values};
+use crate::utils::make_scalar_function;
+
+/// Converts an IPv4 address (or an IPv4 mapped IPv6 address) to an integer.
+/// ip_to_int('192.168.0.1') = 3232235521
+#[derive(Debug)]
+pub struct IpToIntFunc {
+    signature: Signature,
+}
+
+impl IpToIntFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::uniform(1, ip_types(), Volatility::Immutable),
+        }
+    }
+}
+
+impl ScalarUDFImpl for IpToIntFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "ip_to_int"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Int64)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        make_scalar_function(ip_to_int, vec![])(args)
+    }
+}
+
+fn ip_to_int(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let result = ip_values(&args[0], "ip_to_int")?
+        .into_iter()
+        .map(|ip| {
+            ip.map(|ip| match ip.to_ipv4_mapped() {
+                Some(ip) => Ok(u32::from(ip) as i64),
+                None => exec_err!("ip_to_int only supports IPv4 addresses, got {ip}"),
+            })
+            .transpose()
+        })
+        .collect::<Result<Int64Array>>()?;
+    Ok(Arc::new(result))
+}
@@ -0,0 +1,68 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! "network" DataFusion functions, operating on IPv4 and IPv6 addresses
+//!
+//! Addresses are accepted as strings (`'192.168.0.1'`, `'2001:db8::1'`), as
+//! 4 byte IPv4 addresses (`FixedSizeBinary(4)`) or as 16 byte IPv6 addresses
+//! (`FixedSizeBinary(16)`), in which IPv4 addresses are mapped to
+//! `::ffff:a.b.c.d`.
+
+use std::sync::Arc;
+
+use datafusion_expr::ScalarUDF;
+
+mod common;
+mod ip_format;
+mod ip_in_cidr;
+mod ip_parse;
+mod ip_to_int;
+
+// create UDFs
+make_udf_function!(ip_format::IpFormatFunc, IP_FORMAT, ip_format);
+make_udf_function!(ip_in_cidr::IpInCidrFunc, IP_IN_CIDR, ip_in_cidr);
+make_udf_function!(ip_parse::IpParseFunc, IP_PARSE, ip_parse);
+make_udf_function!(ip_to_int::IpToIntFunc, IP_TO_INT, ip_to_int);
+
+pub mod expr_fn {
+    use datafusion_expr::Expr;
+
+    #[doc = "Formats an IP address as a string"]
+    pub fn ip_format(ip: Expr) -> Expr {
+        super::ip_format().call(vec![ip])
+    }
+
+    #[doc = "Returns true if the IP address is in the CIDR block"]
+    pub fn ip_in_cidr(ip: Expr, cidr: Expr) -> Expr {
+        super::ip_in_cidr().call(vec![ip, cidr])
+    }
+
+    #[doc = "Parses an IP address string into its 16 byte IPv6 representation"]
+    pub fn ip_parse(ip: Expr) -> Expr {
+        super::ip_parse().call(vec![ip])
+    }
+
+    #[doc = "Converts an IPv4 address to an integer"]
+    pub fn ip_to_int(ip: Expr) -> Expr {
+        super::ip_to_int().call(vec![ip])
+    }
+}
+
+///   Return a list of all functions in this package
+pub fn functions() -> Vec<Arc<ScalarUDF>> {
+    vec![ip_format(), ip_in_cidr(), ip_parse(), ip_to_int()]
+}
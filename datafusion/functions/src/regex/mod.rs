@@ -17,12 +17,18 @@
 
 //! "regx" DataFusion functions
 
+pub mod regexpextractgroups;
 pub mod regexplike;
 pub mod regexpmatch;
 pub mod regexpreplace;
 // create UDFs
 make_udf_function!(regexpmatch::RegexpMatchFunc, REGEXP_MATCH, regexp_match);
 make_udf_function!(regexplike::RegexpLikeFunc, REGEXP_LIKE, regexp_like);
+make_udf_function!(
+    regexpextractgroups::RegexpExtractGroupsFunc,
+    REGEXP_EXTRACT_GROUPS,
+    regexp_extract_groups
+);
 make_udf_function!(
     regexpreplace::RegexpReplaceFunc,
     REGEXP_REPLACE,
@@ -36,4 +42,8 @@ export_functions!((
     regexp_like,
     input_arg1 input_arg2,
     "Returns true if a has at least one match in a string,false otherwise."
-),(regexp_replace, arg1 arg2 arg3 arg4, "Replaces substrings in a string that match"));
+),(regexp_replace, arg1 arg2 arg3 arg4, "Replaces substrings in a string that match"),(
+    regexp_extract_groups,
+    input_arg1 input_arg2,
+    "Extracts the named capture groups of a regular expression match into a struct."
+));
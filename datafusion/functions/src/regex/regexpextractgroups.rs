@@ -0,0 +1,296 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Regex expressions
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, GenericStringBuilder, OffsetSizeTrait, StructArray};
+use arrow::buffer::NullBuffer;
+use arrow::datatypes::{DataType, Field, Fields};
+use regex::Regex;
+
+use datafusion_common::cast::as_generic_string_array;
+use datafusion_common::{
+    exec_err, internal_err, plan_datafusion_err, plan_err, ExprSchema, Result,
+    ScalarValue,
+};
+use datafusion_expr::TypeSignature::*;
+use datafusion_expr::{
+    ColumnarValue, Expr, PreparedScalarUDF, ScalarUDFImpl, Signature, Volatility,
+};
+
+/// Extracts the named capture groups of the first match of a regular
+/// expression into a struct with a (nullable string) field per group.
+///
+/// The fields of the struct are derived from the pattern when the query is
+/// planned, so the pattern must be a literal:
+///
+/// ```text
+/// regexp_extract_groups('2024-01-31', '(?P<year>\d{4})-(?P<month>\d{2})')
+///   = {year: 2024, month: 01}
+/// ```
+///
+/// The struct is null for values that do not match.
+#[derive(Debug)]
+pub struct RegexpExtractGroupsFunc {
+    signature: Signature,
+}
+
+impl Default for RegexpExtractGroupsFunc {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl RegexpExtractGroupsFunc {
+    pub fn new() -> Self {
+        use DataType::*;
+        Self {
+            signature: Signature::one_of(
+                vec![Exact(vec![Utf8, Utf8]), Exact(vec![LargeUtf8, Utf8])],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for RegexpExtractGroupsFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "regexp_extract_groups"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        internal_err!(
+            "regexp_extract_groups: return_type called instead of return_type_from_exprs"
+        )
+    }
+
+    fn return_type_from_exprs(
+        &self,
+        args: &[Expr],
+        _schema: &dyn ExprSchema,
+        arg_types: &[DataType],
+    ) -> Result<DataType> {
+        let pattern = match args.get(1) {
+            Some(Expr::Literal(
+                ScalarValue::Utf8(Some(pattern)) | ScalarValue::LargeUtf8(Some(pattern)),
+            )) => pattern,
+            _ => {
+                return plan_err!(
+                    "The pattern of regexp_extract_groups must be a string literal"
+                )
+            }
+        };
+        let value_type = match arg_types.first() {
+            Some(DataType::LargeUtf8) => DataType::LargeUtf8,
+            _ => DataType::Utf8,
+        };
+        let regex = compile(pattern)?;
+        Ok(DataType::Struct(group_fields(&regex, &value_type)?))
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        let pattern = match &args[1] {
+            ColumnarValue::Scalar(
+                ScalarValue::Utf8(Some(pattern)) | ScalarValue::LargeUtf8(Some(pattern)),
+            ) => pattern,
+            _ => {
+                return exec_err!(
+                    "The pattern of regexp_extract_groups must be a string literal"
+                )
+            }
+        };
+        invoke_with_regex(&args[0], &compile(pattern)?)
+    }
+
+    fn prepare(
+        &self,
+        const_args: &[Option<ScalarValue>],
+    ) -> Result<Option<Arc<dyn PreparedScalarUDF>>> {
+        match const_args.get(1) {
+            Some(Some(
+                ScalarValue::Utf8(Some(pattern)) | ScalarValue::LargeUtf8(Some(pattern)),
+            )) => Ok(Some(Arc::new(PreparedExtractGroups {
+                regex: compile(pattern)?,
+            }))),
+            _ => Ok(None),
+        }
+    }
+}
+
+/// `regexp_extract_groups` with its pattern compiled once
+#[derive(Debug)]
+struct PreparedExtractGroups {
+    regex: Regex,
+}
+
+impl PreparedScalarUDF for PreparedExtractGroups {
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        invoke_with_regex(&args[0], &self.regex)
+    }
+}
+
+fn invoke_with_regex(value: &ColumnarValue, regex: &Regex) -> Result<ColumnarValue> {
+    match value {
+        ColumnarValue::Array(array) => {
+            Ok(ColumnarValue::Array(extract_groups(array, regex)?))
+        }
+        ColumnarValue::Scalar(scalar) => {
+            let array = extract_groups(&scalar.to_array()?, regex)?;
+            Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+                &array, 0,
+            )?))
+        }
+    }
+}
+
+fn compile(pattern: &str) -> Result<Regex> {
+    Regex::new(pattern)
+        .map_err(|e| plan_datafusion_err!("Invalid regular expression '{pattern}': {e}"))
+}
+
+/// One field per named capture group of `regex`, in the order of the groups
+fn group_fields(regex: &Regex, value_type: &DataType) -> Result<Fields> {
+    let fields = regex
+        .capture_names()
+        .flatten()
+        .map(|name| Field::new(name, value_type.clone(), true))
+        .collect::<Fields>();
+    if fields.is_empty() {
+        return plan_err!(
+            "The pattern of regexp_extract_groups must have named capture groups, \
+             such as (?P<name>...), got '{}'",
+            regex.as_str()
+        );
+    }
+    Ok(fields)
+}
+
+fn extract_groups(array: &ArrayRef, regex: &Regex) -> Result<ArrayRef> {
+    match array.data_type() {
+        DataType::Utf8 => extract_groups_impl::<i32>(array, regex, DataType::Utf8),
+        DataType::LargeUtf8 => {
+            extract_groups_impl::<i64>(array, regex, DataType::LargeUtf8)
+        }
+        other => exec_err!(
+            "Unsupported data type {other:?} for function regexp_extract_groups"
+        ),
+    }
+}
+
+fn extract_groups_impl<T: OffsetSizeTrait>(
+    array: &ArrayRef,
+    regex: &Regex,
+    value_type: DataType,
+) -> Result<ArrayRef> {
+    let values = as_generic_string_array::<T>(array)?;
+    let fields = group_fields(regex, &value_type)?;
+    // indices of the named groups in the captures
+    let group_indices = regex
+        .capture_names()
+        .enumerate()
+        .filter_map(|(i, name)| name.map(|_| i))
+        .collect::<Vec<_>>();
+
+    let mut builders = group_indices
+        .iter()
+        .map(|_| GenericStringBuilder::<T>::new())
+        .collect::<Vec<_>>();
+    let mut validity = Vec::with_capacity(values.len());
+    for value in values.iter() {
+        let captures = value.and_then(|value| regex.captures(value));
+        validity.push(captures.is_some());
+        for (builder, &index) in builders.iter_mut().zip(&group_indices) {
+            builder.append_option(
+                captures
+                    .as_ref()
+                    .and_then(|captures| captures.get(index))
+                    .map(|group| group.as_str()),
+            );
+        }
+    }
+
+    let columns = builders
+        .into_iter()
+        .map(|mut builder| Arc::new(builder.finish()) as ArrayRef)
+        .collect();
+    Ok(Arc::new(StructArray::try_new(
+        fields,
+        columns,
+        Some(NullBuffer::from(validity)),
+    )?))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{AsArray, StringArray};
+
+    use super::*;
+
+    #[test]
+    fn test_extract_groups() -> Result<()> {
+        let regex = compile(r"(?P<year>\d{4})-(?P<month>\d{2})(-(?P<day>\d{2}))?")?;
+        let values: ArrayRef = Arc::new(StringArray::from(vec![
+            Some("2024-01-31"),
+            Some("2024-02"),
+            Some("no date"),
+            None,
+        ]));
+        let result = extract_groups(&values, &regex)?;
+        let result = result.as_struct();
+        assert_eq!(result.column_names(), vec!["year", "month", "day"],);
+        assert_eq!(result.nulls().unwrap().null_count(), 2);
+
+        let day = result.column_by_name("day").unwrap().as_string::<i32>();
+        assert_eq!(day.value(0), "31");
+        assert!(day.is_null(1));
+        Ok(())
+    }
+
+    #[test]
+    fn test_prepare() -> Result<()> {
+        let func = RegexpExtractGroupsFunc::new();
+        assert!(func.prepare(&[None, None])?.is_none());
+
+        let pattern = ScalarValue::from(r"(?P<key>\w+)=(?P<value>\w+)");
+        let prepared = func.prepare(&[None, Some(pattern.clone())])?.unwrap();
+        let values: ArrayRef = Arc::new(StringArray::from(vec!["a=1", "b"]));
+        let args = [ColumnarValue::Array(values), ColumnarValue::Scalar(pattern)];
+        let (ColumnarValue::Array(expected), ColumnarValue::Array(result)) =
+            (func.invoke(&args)?, prepared.invoke(&args)?)
+        else {
+            unreachable!()
+        };
+        assert_eq!(&expected, &result);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pattern_without_named_groups() {
+        let regex = compile(r"(\d{4})").unwrap();
+        assert!(group_fields(&regex, &DataType::Utf8).is_err());
+    }
+}
@@ -0,0 +1,137 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Collations used to compare strings in a locale (or otherwise non binary)
+//! aware manner.
+//!
+//! A collation is applied by mapping each string to a *sort key*: a byte
+//! string whose binary ordering matches the collation ordering of the
+//! original strings, and which is equal for (and only for) strings the
+//! collation considers equal. Sort keys can therefore be sorted, compared,
+//! hashed and grouped with the existing binary kernels.
+
+use arrow::array::{Array, ArrayRef, BinaryBuilder, GenericStringArray, OffsetSizeTrait};
+
+#[cfg(feature = "icu_collation")]
+use datafusion_common::exec_datafusion_err;
+#[cfg(not(feature = "icu_collation"))]
+use datafusion_common::exec_err;
+use datafusion_common::Result;
+
+/// A collation that can be used to compare strings
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Collation {
+    /// Compare the UTF-8 bytes of the strings. This is the default
+    /// collation of all strings (`binary`, `ucs_basic`, `c` or `posix`)
+    Binary,
+    /// Compare the strings ignoring case differences (`nocase`)
+    NoCase,
+    /// Compare the strings using the ICU collation rules for a locale,
+    /// such as `de-DE` or `sv`. `unicode` selects the root locale
+    #[cfg(feature = "icu_collation")]
+    Icu(String),
+}
+
+impl Collation {
+    /// Resolve a collation from its name
+    pub fn try_new(name: &str) -> Result<Self> {
+        match name.to_ascii_lowercase().as_str() {
+            "binary" | "ucs_basic" | "c" | "posix" => Ok(Self::Binary),
+            "nocase" => Ok(Self::NoCase),
+            #[cfg(feature = "icu_collation")]
+            "unicode" => Ok(Self::Icu("und".to_string())),
+            #[cfg(feature = "icu_collation")]
+            _ => {
+                // fail early for unknown locales
+                rust_icu_ucol::UCollator::try_from(name).map_err(|e| {
+                    exec_datafusion_err!("Unsupported collation '{name}': {e}")
+                })?;
+                Ok(Self::Icu(name.to_string()))
+            }
+            #[cfg(not(feature = "icu_collation"))]
+            _ => exec_err!(
+                "Unsupported collation '{name}'. Locale collations require the \
+                 'icu_collation' feature"
+            ),
+        }
+    }
+
+    /// Compute the sort keys of `array`. Null strings have null sort keys
+    pub fn sort_keys<T: OffsetSizeTrait>(
+        &self,
+        array: &GenericStringArray<T>,
+    ) -> Result<ArrayRef> {
+        let mut builder =
+            BinaryBuilder::with_capacity(array.len(), array.value_data().len());
+        match self {
+            Self::Binary => array.iter().for_each(|value| builder.append_option(value)),
+            Self::NoCase => array.iter().for_each(|value| {
+                builder.append_option(value.map(|value| value.to_lowercase()))
+            }),
+            #[cfg(feature = "icu_collation")]
+            Self::Icu(locale) => {
+                let collator = rust_icu_ucol::UCollator::try_from(locale.as_str())
+                    .map_err(|e| {
+                        exec_datafusion_err!("Unsupported collation '{locale}': {e}")
+                    })?;
+                for value in array.iter() {
+                    match value {
+                        Some(value) => {
+                            let value = rust_icu_ustring::UChar::try_from(value)
+                                .map_err(|e| exec_datafusion_err!("{e}"))?;
+                            builder.append_value(collator.get_sort_key(&value));
+                        }
+                        None => builder.append_null(),
+                    }
+                }
+            }
+        }
+        Ok(std::sync::Arc::new(builder.finish()))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{BinaryArray, StringArray};
+
+    use super::*;
+
+    #[test]
+    fn nocase_sort_keys() -> Result<()> {
+        let collation = Collation::try_new("NOCASE")?;
+        let array = StringArray::from(vec![Some("b"), Some("A"), None, Some("a")]);
+        let keys = collation.sort_keys(&array)?;
+        let keys = keys.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(keys.value(1), keys.value(3));
+        assert!(keys.value(1) < keys.value(0));
+        assert!(keys.is_null(2));
+
+        // binary collation keeps upper case letters before lower case ones
+        let keys = Collation::Binary.sort_keys(&array)?;
+        let keys = keys.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert!(keys.value(1) < keys.value(3));
+        assert!(keys.value(1) < keys.value(0));
+        Ok(())
+    }
+
+    #[test]
+    fn unknown_collation() {
+        assert!(Collation::try_new("binary").is_ok());
+        #[cfg(not(feature = "icu_collation"))]
+        assert!(Collation::try_new("de-DE").is_err());
+    }
+}
@@ -0,0 +1,142 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+
+use arrow::array::ArrayRef;
+use arrow::datatypes::DataType;
+
+use datafusion_common::cast::as_generic_string_array;
+use datafusion_common::{exec_err, Result, ScalarValue};
+use datafusion_expr::{
+    ColumnarValue, ScalarUDFImpl, Signature, TypeSignature, Volatility,
+};
+
+use crate::string::collation::Collation;
+
+/// `collation_key(string, collation)`: the sort key of `string` in
+/// `collation`, as a binary value.
+///
+/// Comparing, sorting or grouping the keys of two strings has the same
+/// result as comparing, sorting or grouping the strings in the collation.
+#[derive(Debug)]
+pub struct CollationKeyFunc {
+    signature: Signature,
+}
+
+impl CollationKeyFunc {
+    pub fn new() -> Self {
+        use DataType::*;
+        Self {
+            signature: Signature::one_of(
+                vec![
+                    TypeSignature::Exact(vec![Utf8, Utf8]),
+                    TypeSignature::Exact(vec![LargeUtf8, Utf8]),
+                ],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for CollationKeyFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "collation_key"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Binary)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        let collation = match &args[1] {
+            ColumnarValue::Scalar(ScalarValue::Utf8(Some(name))) => {
+                Collation::try_new(name)?
+            }
+            other => {
+                return exec_err!(
+                "The collation of collation_key must be a string literal, got {other:?}"
+            )
+            }
+        };
+
+        match &args[0] {
+            ColumnarValue::Array(array) => {
+                Ok(ColumnarValue::Array(sort_keys(&collation, array)?))
+            }
+            ColumnarValue::Scalar(scalar) => {
+                let keys = sort_keys(&collation, &scalar.to_array()?)?;
+                Ok(ColumnarValue::Scalar(ScalarValue::try_from_array(
+                    &keys, 0,
+                )?))
+            }
+        }
+    }
+}
+
+fn sort_keys(collation: &Collation, array: &ArrayRef) -> Result<ArrayRef> {
+    match array.data_type() {
+        DataType::Utf8 => collation.sort_keys(as_generic_string_array::<i32>(array)?),
+        DataType::LargeUtf8 => {
+            collation.sort_keys(as_generic_string_array::<i64>(array)?)
+        }
+        other => exec_err!("Unsupported data type {other:?} for function collation_key"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::sync::Arc;
+
+    use arrow::array::{Array, BinaryArray, StringArray};
+
+    use super::*;
+
+    #[test]
+    fn collation_key_nocase() -> Result<()> {
+        let input: ArrayRef = Arc::new(StringArray::from(vec![
+            Some("Zürich"),
+            Some("zürich"),
+            None,
+        ]));
+        let args = [
+            ColumnarValue::Array(input),
+            ColumnarValue::Scalar(ScalarValue::from("nocase")),
+        ];
+        let ColumnarValue::Array(result) = CollationKeyFunc::new().invoke(&args)? else {
+            unreachable!()
+        };
+        let result = result.as_any().downcast_ref::<BinaryArray>().unwrap();
+        assert_eq!(result.value(0), result.value(1));
+        assert!(result.is_null(2));
+
+        let args = [
+            ColumnarValue::Scalar(ScalarValue::from("a")),
+            ColumnarValue::Scalar(ScalarValue::from("no_such_collation")),
+        ];
+        assert!(CollationKeyFunc::new().invoke(&args).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,197 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::collections::HashSet;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, BooleanArray};
+use arrow::datatypes::DataType;
+
+use datafusion_common::cast::as_generic_string_array;
+use datafusion_common::{exec_err, Result};
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature, Volatility};
+use datafusion_physical_expr::functions::Hint;
+
+use crate::utils::make_scalar_function;
+
+/// Splits `text` into its terms for full-text search: its runs of
+/// alphanumeric characters, in lowercase.
+///
+/// The text indexes evaluating `matches` in place of a scan must tokenize
+/// the values and the queries the same way.
+pub fn tokenize(text: &str) -> impl Iterator<Item = String> + '_ {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|term| !term.is_empty())
+        .map(|term| term.to_lowercase())
+}
+
+/// Returns true if the text contains all the terms of the query, see
+/// [`tokenize`]. A query without terms matches any text.
+/// matches('The quick brown fox', 'FOX quick') = true
+#[derive(Debug)]
+pub struct MatchesFunc {
+    signature: Signature,
+}
+
+impl MatchesFunc {
+    pub fn new() -> Self {
+        use DataType::*;
+        Self {
+            signature: Signature::uniform(
+                2,
+                vec![Utf8, LargeUtf8],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for MatchesFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "matches"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Boolean)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        // a constant query is tokenized only once
+        make_scalar_function(matches, vec![Hint::Pad, Hint::AcceptsSingular])(args)
+    }
+}
+
+/// The terms of a query, all of which a matching text contains
+struct Query {
+    terms: HashSet<String>,
+}
+
+impl Query {
+    fn new(query: &str) -> Self {
+        Self {
+            terms: tokenize(query).collect(),
+        }
+    }
+
+    fn matches(&self, text: &str) -> bool {
+        let mut missing = self.terms.len();
+        let mut found = HashSet::new();
+        for term in tokenize(text) {
+            if missing == 0 {
+                break;
+            }
+            if self.terms.contains(&term) && found.insert(term) {
+                missing -= 1;
+            }
+        }
+        missing == 0
+    }
+}
+
+fn string_values(array: &ArrayRef) -> Result<Vec<Option<&str>>> {
+    match array.data_type() {
+        DataType::Utf8 => Ok(as_generic_string_array::<i32>(array)?.iter().collect()),
+        DataType::LargeUtf8 => {
+            Ok(as_generic_string_array::<i64>(array)?.iter().collect())
+        }
+        other => exec_err!("Unsupported data type {other:?} for function matches"),
+    }
+}
+
+fn matches(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let texts = string_values(&args[0])?;
+    let queries = string_values(&args[1])?;
+
+    let result = if queries.len() == 1 && texts.len() != 1 {
+        match queries[0] {
+            Some(query) => {
+                let query = Query::new(query);
+                texts
+                    .into_iter()
+                    .map(|text| text.map(|text| query.matches(text)))
+                    .collect::<BooleanArray>()
+            }
+            None => BooleanArray::new_null(texts.len()),
+        }
+    } else {
+        texts
+            .into_iter()
+            .zip(queries)
+            .map(|(text, query)| match (text, query) {
+                (Some(text), Some(query)) => Some(Query::new(query).matches(text)),
+                _ => None,
+            })
+            .collect::<BooleanArray>()
+    };
+    Ok(Arc::new(result))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::StringArray;
+
+    use super::*;
+
+    #[test]
+    fn test_tokenize() {
+        assert_eq!(
+            tokenize("The quick-brown  FOX, 2nd!").collect::<Vec<_>>(),
+            vec!["the", "quick", "brown", "fox", "2nd"]
+        );
+        assert_eq!(tokenize(" ,; ").count(), 0);
+    }
+
+    #[test]
+    fn test_matches() -> Result<()> {
+        let texts: ArrayRef = Arc::new(StringArray::from(vec![
+            Some("The quick brown fox"),
+            Some("A quick movement of the enemy"),
+            Some("Foxes are quick"),
+            None,
+        ]));
+        let queries: ArrayRef = Arc::new(StringArray::from(vec!["QUICK fox"]));
+        let result = matches(&[texts.clone(), queries])?;
+        let result = result.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(true), Some(false), Some(false), None]
+        );
+
+        let queries: ArrayRef = Arc::new(StringArray::from(vec![
+            Some("brown"),
+            Some("enemy quick quick"),
+            Some(""),
+            Some("fox"),
+        ]));
+        let result = matches(&[texts, queries])?;
+        let result = result.as_any().downcast_ref::<BooleanArray>().unwrap();
+        assert_eq!(
+            result.iter().collect::<Vec<_>>(),
+            vec![Some(true), Some(true), Some(true), None]
+        );
+        Ok(())
+    }
+}
@@ -25,6 +25,9 @@ mod ascii;
 mod bit_length;
 mod btrim;
 mod chr;
+mod collate;
+mod collation;
+mod collation_key;
 mod common;
 mod concat;
 mod concat_ws;
@@ -33,6 +36,7 @@ mod initcap;
 mod levenshtein;
 mod lower;
 mod ltrim;
+mod matches;
 mod octet_length;
 mod overlay;
 mod repeat;
@@ -43,18 +47,31 @@ mod starts_with;
 mod to_hex;
 mod upper;
 mod uuid;
+mod uuid_format;
+mod uuid_parse;
+mod uuid_version;
+
+pub use collation::Collation;
+pub use matches::tokenize;
 
 // create UDFs
 make_udf_function!(ascii::AsciiFunc, ASCII, ascii);
 make_udf_function!(bit_length::BitLengthFunc, BIT_LENGTH, bit_length);
 make_udf_function!(btrim::BTrimFunc, BTRIM, btrim);
 make_udf_function!(chr::ChrFunc, CHR, chr);
+make_udf_function!(collate::CollateFunc, COLLATE, collate);
+make_udf_function!(
+    collation_key::CollationKeyFunc,
+    COLLATION_KEY,
+    collation_key
+);
 make_udf_function!(concat::ConcatFunc, CONCAT, concat);
 make_udf_function!(concat_ws::ConcatWsFunc, CONCAT_WS, concat_ws);
 make_udf_function!(ends_with::EndsWithFunc, ENDS_WITH, ends_with);
 make_udf_function!(initcap::InitcapFunc, INITCAP, initcap);
 make_udf_function!(levenshtein::LevenshteinFunc, LEVENSHTEIN, levenshtein);
 make_udf_function!(ltrim::LtrimFunc, LTRIM, ltrim);
+make_udf_function!(matches::MatchesFunc, MATCHES, matches);
 make_udf_function!(lower::LowerFunc, LOWER, lower);
 make_udf_function!(octet_length::OctetLengthFunc, OCTET_LENGTH, octet_length);
 make_udf_function!(overlay::OverlayFunc, OVERLAY, overlay);
@@ -66,6 +83,9 @@ make_udf_function!(split_part::SplitPartFunc, SPLIT_PART, split_part);
 make_udf_function!(to_hex::ToHexFunc, TO_HEX, to_hex);
 make_udf_function!(upper::UpperFunc, UPPER, upper);
 make_udf_function!(uuid::UuidFunc, UUID, uuid);
+make_udf_function!(uuid_format::UuidFormatFunc, UUID_FORMAT, uuid_format);
+make_udf_function!(uuid_parse::UuidParseFunc, UUID_PARSE, uuid_parse);
+make_udf_function!(uuid_version::UuidVersionFunc, UUID_VERSION, uuid_version);
 
 pub mod expr_fn {
     use datafusion_expr::Expr;
@@ -90,6 +110,16 @@ pub mod expr_fn {
         super::chr().call(vec![arg])
     }
 
+    #[doc = "Marks `string` as using the `collation` in comparisons, sorts and groupings"]
+    pub fn collate(string: Expr, collation: Expr) -> Expr {
+        super::collate().call(vec![string, collation])
+    }
+
+    #[doc = "Returns the binary sort key of `string` in the `collation`"]
+    pub fn collation_key(string: Expr, collation: Expr) -> Expr {
+        super::collation_key().call(vec![string, collation])
+    }
+
     #[doc = "Concatenates the text representations of all the arguments. NULL arguments are ignored"]
     pub fn concat(args: Vec<Expr>) -> Expr {
         super::concat().call(args)
@@ -127,6 +157,11 @@ pub mod expr_fn {
         super::ltrim().call(args)
     }
 
+    #[doc = "Returns true if the `text` contains all the terms of the full-text `query`"]
+    pub fn matches(text: Expr, query: Expr) -> Expr {
+        super::matches().call(vec![text, query])
+    }
+
     #[doc = "returns the number of bytes of a string"]
     pub fn octet_length(args: Vec<Expr>) -> Expr {
         super::octet_length().call(args)
@@ -181,6 +216,21 @@ pub mod expr_fn {
     pub fn uuid() -> Expr {
         super::uuid().call(vec![])
     }
+
+    #[doc = "Formats the 16 bytes of a UUID as a hyphenated string"]
+    pub fn uuid_format(uuid: Expr) -> Expr {
+        super::uuid_format().call(vec![uuid])
+    }
+
+    #[doc = "Parses a UUID string into its 16 bytes"]
+    pub fn uuid_parse(string: Expr) -> Expr {
+        super::uuid_parse().call(vec![string])
+    }
+
+    #[doc = "Returns the version number of a UUID"]
+    pub fn uuid_version(uuid: Expr) -> Expr {
+        super::uuid_version().call(vec![uuid])
+    }
 }
 
 ///   Return a list of all functions in this package
@@ -190,6 +240,8 @@ pub fn functions() -> Vec<Arc<ScalarUDF>> {
         bit_length(),
         btrim(),
         chr(),
+        collate(),
+        collation_key(),
         concat(),
         concat_ws(),
         ends_with(),
@@ -197,6 +249,7 @@ pub fn functions() -> Vec<Arc<ScalarUDF>> {
         levenshtein(),
         lower(),
         ltrim(),
+        matches(),
         octet_length(),
         overlay(),
         repeat(),
@@ -207,5 +260,8 @@ pub fn functions() -> Vec<Arc<ScalarUDF>> {
         to_hex(),
         upper(),
         uuid(),
+        uuid_format(),
+        uuid_parse(),
+        uuid_version(),
     ]
 }
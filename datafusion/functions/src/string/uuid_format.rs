@@ -0,0 +1,102 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, StringBuilder};
+use arrow::datatypes::DataType;
+
+use datafusion_common::cast::as_fixed_size_binary_array;
+use datafusion_common::{exec_datafusion_err, exec_err, Result};
+use datafusion_expr::{
+    ColumnarValue, ScalarUDFImpl, Signature, TypeSignature, Volatility,
+};
+use uuid::Uuid;
+
+use crate::utils::make_scalar_function;
+
+/// Formats the 16 bytes of a UUID as a hyphenated lower case string.
+/// uuid_format(uuid_parse('A0EEBC99-9C0B-4EF8-BB6D-6BB9BD380A11')) = 'a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11'
+#[derive(Debug)]
+pub struct UuidFormatFunc {
+    signature: Signature,
+}
+
+impl UuidFormatFunc {
+    pub fn new() -> Self {
+        Self {
+            signature: Signature::one_of(
+                vec![
+                    TypeSignature::Exact(vec![DataType::FixedSizeBinary(16)]),
+                    TypeSignature::Exact(vec![DataType::Null]),
+                ],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for UuidFormatFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "uuid_format"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Utf8)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        match args[0].data_type() {
+            DataType::FixedSizeBinary(16) => {
+                make_scalar_function(uuid_format, vec![])(args)
+            }
+            DataType::Null => {
+                let uuids = args[0].cast_to(&DataType::FixedSizeBinary(16), None)?;
+                make_scalar_function(uuid_format, vec![])(&[uuids])
+            }
+            other => {
+                exec_err!("Unsupported data type {other:?} for function uuid_format")
+            }
+        }
+    }
+}
+
+fn uuid_format(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let uuids = as_fixed_size_binary_array(&args[0])?;
+    let mut builder = StringBuilder::with_capacity(uuids.len(), uuids.len() * 36);
+    let mut buffer = Uuid::encode_buffer();
+    for value in uuids.iter() {
+        match value {
+            Some(value) => {
+                let uuid = Uuid::from_slice(value)
+                    .map_err(|e| exec_datafusion_err!("Invalid UUID: {e}"))?;
+                builder.append_value(uuid.hyphenated().encode_lower(&mut buffer));
+            }
+            None => builder.append_null(),
+        }
+    }
+    Ok(Arc::new(builder.finish()))
+}
@@ -0,0 +1,125 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{Array, ArrayRef, FixedSizeBinaryBuilder, OffsetSizeTrait};
+use arrow::datatypes::DataType;
+
+use datafusion_common::cast::as_generic_string_array;
+use datafusion_common::{exec_datafusion_err, exec_err, Result};
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature, Volatility};
+use uuid::Uuid;
+
+use crate::utils::make_scalar_function;
+
+/// Parses a UUID string into its 16 bytes.
+/// uuid_parse('a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11') = a0eebc999c0b4ef8bb6d6bb9bd380a11
+#[derive(Debug)]
+pub struct UuidParseFunc {
+    signature: Signature,
+}
+
+impl UuidParseFunc {
+    pub fn new() -> Self {
+        use DataType::*;
+        Self {
+            signature: Signature::uniform(
+                1,
+                vec![Utf8, LargeUtf8],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for UuidParseFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "uuid_parse"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::FixedSizeBinary(16))
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        match args[0].data_type() {
+            DataType::Utf8 => make_scalar_function(uuid_parse::<i32>, vec![])(args),
+            DataType::LargeUtf8 => make_scalar_function(uuid_parse::<i64>, vec![])(args),
+            other => exec_err!("Unsupported data type {other:?} for function uuid_parse"),
+        }
+    }
+}
+
+/// Parses a UUID in any of the hyphenated, simple, braced or URN formats
+pub(crate) fn parse_uuid(value: &str) -> Result<Uuid> {
+    Uuid::parse_str(value.trim())
+        .map_err(|e| exec_datafusion_err!("Invalid UUID '{value}': {e}"))
+}
+
+fn uuid_parse<T: OffsetSizeTrait>(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let strings = as_generic_string_array::<T>(&args[0])?;
+    let mut builder = FixedSizeBinaryBuilder::with_capacity(strings.len(), 16);
+    for value in strings.iter() {
+        match value {
+            Some(value) => builder.append_value(parse_uuid(value)?.as_bytes())?,
+            None => builder.append_null(),
+        }
+    }
+    Ok(Arc::new(builder.finish()))
+}
+
+#[cfg(test)]
+mod tests {
+    use arrow::array::{Array, FixedSizeBinaryArray, StringArray};
+
+    use super::*;
+
+    #[test]
+    fn test_uuid_parse() -> Result<()> {
+        let input: ArrayRef = Arc::new(StringArray::from(vec![
+            Some("a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11"),
+            Some("{A0EEBC99-9C0B-4EF8-BB6D-6BB9BD380A11}"),
+            None,
+        ]));
+        let result = uuid_parse::<i32>(&[input])?;
+        let result = result
+            .as_any()
+            .downcast_ref::<FixedSizeBinaryArray>()
+            .unwrap();
+        let expected = [
+            0xa0, 0xee, 0xbc, 0x99, 0x9c, 0x0b, 0x4e, 0xf8, 0xbb, 0x6d, 0x6b, 0xb9, 0xbd,
+            0x38, 0x0a, 0x11,
+        ];
+        assert_eq!(result.value(0), expected);
+        assert_eq!(result.value(1), expected);
+        assert!(result.is_null(2));
+
+        let input: ArrayRef = Arc::new(StringArray::from(vec!["not-a-uuid"]));
+        assert!(uuid_parse::<i32>(&[input]).is_err());
+        Ok(())
+    }
+}
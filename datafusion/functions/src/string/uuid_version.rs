@@ -0,0 +1,113 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::any::Any;
+use std::sync::Arc;
+
+use arrow::array::{ArrayRef, Int32Array, OffsetSizeTrait};
+use arrow::datatypes::DataType;
+
+use datafusion_common::cast::{as_fixed_size_binary_array, as_generic_string_array};
+use datafusion_common::{exec_datafusion_err, exec_err, Result};
+use datafusion_expr::{ColumnarValue, ScalarUDFImpl, Signature, Volatility};
+use uuid::Uuid;
+
+use crate::string::uuid_parse::parse_uuid;
+use crate::utils::make_scalar_function;
+
+/// Returns the version number of a UUID, given as a string or as its 16 bytes.
+/// uuid_version('a0eebc99-9c0b-4ef8-bb6d-6bb9bd380a11') = 4
+#[derive(Debug)]
+pub struct UuidVersionFunc {
+    signature: Signature,
+}
+
+impl UuidVersionFunc {
+    pub fn new() -> Self {
+        use DataType::*;
+        Self {
+            signature: Signature::uniform(
+                1,
+                vec![Utf8, LargeUtf8, FixedSizeBinary(16)],
+                Volatility::Immutable,
+            ),
+        }
+    }
+}
+
+impl ScalarUDFImpl for UuidVersionFunc {
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn name(&self) -> &str {
+        "uuid_version"
+    }
+
+    fn signature(&self) -> &Signature {
+        &self.signature
+    }
+
+    fn return_type(&self, _arg_types: &[DataType]) -> Result<DataType> {
+        Ok(DataType::Int32)
+    }
+
+    fn invoke(&self, args: &[ColumnarValue]) -> Result<ColumnarValue> {
+        match args[0].data_type() {
+            DataType::Utf8 => {
+                make_scalar_function(uuid_version_utf8::<i32>, vec![])(args)
+            }
+            DataType::LargeUtf8 => {
+                make_scalar_function(uuid_version_utf8::<i64>, vec![])(args)
+            }
+            DataType::FixedSizeBinary(16) => {
+                make_scalar_function(uuid_version_binary, vec![])(args)
+            }
+            other => {
+                exec_err!("Unsupported data type {other:?} for function uuid_version")
+            }
+        }
+    }
+}
+
+fn uuid_version_utf8<T: OffsetSizeTrait>(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let result = as_generic_string_array::<T>(&args[0])?
+        .iter()
+        .map(|value| {
+            value
+                .map(|value| Ok(parse_uuid(value)?.get_version_num() as i32))
+                .transpose()
+        })
+        .collect::<Result<Int32Array>>()?;
+    Ok(Arc::new(result))
+}
+
+fn uuid_version_binary(args: &[ArrayRef]) -> Result<ArrayRef> {
+    let result = as_fixed_size_binary_array(&args[0])?
+        .iter()
+        .map(|value| {
+            value
+                .map(|value| {
+                    Uuid::from_slice(value)
+                        .map(|uuid| uuid.get_version_num() as i32)
+                        .map_err(|e| exec_datafusion_err!("Invalid UUID: {e}"))
+                })
+                .transpose()
+        })
+        .collect::<Result<Int32Array>>()?;
+    Ok(Arc::new(result))
+}
@@ -0,0 +1,556 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`ApplyCollations`] compares, sorts and groups string expressions in
+//! their collation
+
+use std::sync::Arc;
+
+use arrow::datatypes::DataType;
+
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{plan_err, Column, DFSchema, Result, ScalarValue};
+use datafusion_expr::expr::{AggregateFunction, Alias, InList, ScalarFunction, Sort};
+use datafusion_expr::utils::{merge_schema, COLLATION_METADATA_KEY};
+use datafusion_expr::{
+    aggregate_function, lit, Aggregate, Between, BinaryExpr, Cast, Expr, ExprSchemable,
+    Join, LogicalPlan, Operator, Projection, ScalarFunctionDefinition, ScalarUDF,
+    TryCast,
+};
+
+use crate::analyzer::AnalyzerRule;
+use crate::utils::NamePreserver;
+
+/// Name of the function marking an expression with an explicit collation
+/// (`expr COLLATE "de-DE"`)
+pub const COLLATE: &str = "collate";
+
+/// Rewrites the comparisons, sorts and groupings of string expressions that
+/// have a collation to operate on the collation keys of the strings.
+///
+/// The collation of an expression is either explicit, using the `collate`
+/// function (`name COLLATE "de-DE"`), or implicit, from the
+/// [`COLLATION_METADATA_KEY`] metadata of the column it refers to. An explicit
+/// collation takes precedence over an implicit one.
+///
+/// ```text
+/// t.name < 'b'                      => collation_key(t.name, 'de-DE') < collation_key('b', 'de-DE')
+/// ORDER BY t.name                   => ORDER BY collation_key(t.name, 'de-DE')
+/// GROUP BY t.name                   => GROUP BY collation_key(t.name, 'de-DE'),
+///                                      reporting FIRST_VALUE(t.name) of each group
+/// ```
+///
+/// The rule is constructed with the `collation_key` function, which maps
+/// a string and the name of a collation to a binary sort key.
+pub struct ApplyCollations {
+    collation_key: Arc<ScalarUDF>,
+}
+
+impl ApplyCollations {
+    pub fn new(collation_key: Arc<ScalarUDF>) -> Self {
+        Self { collation_key }
+    }
+
+    /// The collation key of `expr` in `collation`
+    fn key(&self, expr: Expr, collation: &str) -> Expr {
+        Expr::ScalarFunction(ScalarFunction::new_udf(
+            self.collation_key.clone(),
+            vec![expr, lit(collation)],
+        ))
+    }
+
+    fn rewrite_plan(&self, plan: LogicalPlan) -> Result<Transformed<LogicalPlan>> {
+        let mut schema = merge_schema(plan.inputs());
+
+        // the filters of an optimized table scan refer to its source
+        if let LogicalPlan::TableScan(ts) = &plan {
+            let source_schema = DFSchema::try_from_qualified_schema(
+                ts.table_name.clone(),
+                &ts.source.schema(),
+            )?;
+            schema.merge(&source_schema);
+        }
+
+        if let LogicalPlan::Sort(_) = plan {
+            // sort expressions have no name to preserve
+            return plan.map_expressions(|expr| {
+                self.rewrite_comparisons(expr, &schema)?
+                    .transform_data(|expr| self.rewrite_sort_expr(expr, &schema))
+            });
+        }
+
+        let name_preserver = NamePreserver::new(&plan);
+        plan.map_expressions(|expr| {
+            let original_name = name_preserver.save(&expr)?;
+            self.rewrite_comparisons(expr, &schema)?
+                .map_data(|expr| original_name.restore(expr))
+        })?
+        .transform_data(|plan| match plan {
+            LogicalPlan::Join(join) => self.rewrite_join(join, &schema),
+            LogicalPlan::Aggregate(aggregate) => {
+                self.rewrite_aggregate(aggregate, &schema)
+            }
+            LogicalPlan::Projection(projection) => {
+                annotate_projection(projection, &schema)
+            }
+            plan => Ok(Transformed::no(plan)),
+        })
+    }
+
+    /// Compare the operands of comparisons in their common collation
+    fn rewrite_comparisons(
+        &self,
+        expr: Expr,
+        schema: &DFSchema,
+    ) -> Result<Transformed<Expr>> {
+        expr.transform_up(|expr| match expr {
+            Expr::BinaryExpr(BinaryExpr { left, op, right }) if is_comparison(op) => {
+                match common_collation(&[&left, &right], schema)? {
+                    Some(collation) => {
+                        Ok(Transformed::yes(Expr::BinaryExpr(BinaryExpr::new(
+                            Box::new(self.key(*left, &collation)),
+                            op,
+                            Box::new(self.key(*right, &collation)),
+                        ))))
+                    }
+                    None => Ok(Transformed::no(Expr::BinaryExpr(BinaryExpr {
+                        left,
+                        op,
+                        right,
+                    }))),
+                }
+            }
+            Expr::Between(Between {
+                expr,
+                negated,
+                low,
+                high,
+            }) => match common_collation(&[&expr, &low, &high], schema)? {
+                Some(collation) => Ok(Transformed::yes(Expr::Between(Between::new(
+                    Box::new(self.key(*expr, &collation)),
+                    negated,
+                    Box::new(self.key(*low, &collation)),
+                    Box::new(self.key(*high, &collation)),
+                )))),
+                None => Ok(Transformed::no(Expr::Between(Between {
+                    expr,
+                    negated,
+                    low,
+                    high,
+                }))),
+            },
+            Expr::InList(InList {
+                expr,
+                list,
+                negated,
+            }) => {
+                let operands = std::iter::once(expr.as_ref())
+                    .chain(list.iter())
+                    .collect::<Vec<_>>();
+                match common_collation(&operands, schema)? {
+                    Some(collation) => Ok(Transformed::yes(Expr::InList(InList::new(
+                        Box::new(self.key(*expr, &collation)),
+                        list.into_iter()
+                            .map(|expr| self.key(expr, &collation))
+                            .collect(),
+                        negated,
+                    )))),
+                    None => Ok(Transformed::no(Expr::InList(InList {
+                        expr,
+                        list,
+                        negated,
+                    }))),
+                }
+            }
+            expr => Ok(Transformed::no(expr)),
+        })
+    }
+
+    /// Sort a string expression by its collation key
+    fn rewrite_sort_expr(
+        &self,
+        expr: Expr,
+        schema: &DFSchema,
+    ) -> Result<Transformed<Expr>> {
+        match expr {
+            Expr::Sort(Sort {
+                expr,
+                asc,
+                nulls_first,
+            }) => match common_collation(&[&expr], schema)? {
+                Some(collation) => Ok(Transformed::yes(Expr::Sort(Sort::new(
+                    Box::new(self.key(*expr, &collation)),
+                    asc,
+                    nulls_first,
+                )))),
+                None => Ok(Transformed::no(Expr::Sort(Sort {
+                    expr,
+                    asc,
+                    nulls_first,
+                }))),
+            },
+            expr => Ok(Transformed::no(expr)),
+        }
+    }
+
+    /// Compare the equijoin keys (`JOIN ... USING`) in their common collation
+    fn rewrite_join(
+        &self,
+        join: Join,
+        schema: &DFSchema,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let collations = join
+            .on
+            .iter()
+            .map(|(left, right)| common_collation(&[left, right], schema))
+            .collect::<Result<Vec<_>>>()?;
+        if collations.iter().all(Option::is_none) {
+            return Ok(Transformed::no(LogicalPlan::Join(join)));
+        }
+
+        let on = join
+            .on
+            .into_iter()
+            .zip(collations)
+            .map(|((left, right), collation)| match collation {
+                Some(collation) => {
+                    (self.key(left, &collation), self.key(right, &collation))
+                }
+                None => (left, right),
+            })
+            .collect();
+        Ok(Transformed::yes(LogicalPlan::Join(Join { on, ..join })))
+    }
+
+    /// Group string expressions by their collation keys.
+    ///
+    /// As the keys are not the values the aggregate must output, the first
+    /// value of each group is reported instead, and a projection restores
+    /// the schema of the original aggregate:
+    ///
+    /// ```text
+    /// Aggregate: groupBy=[[t.name]], aggr=[[COUNT(t.id)]]
+    /// =>
+    /// Projection: __collation_group_0 AS t.name, COUNT(t.id)
+    ///   Aggregate: groupBy=[[collation_key(t.name, 'de-DE')]],
+    ///              aggr=[[COUNT(t.id), FIRST_VALUE(t.name) AS __collation_group_0]]
+    /// ```
+    fn rewrite_aggregate(
+        &self,
+        aggregate: Aggregate,
+        schema: &DFSchema,
+    ) -> Result<Transformed<LogicalPlan>> {
+        // collations are not applied to grouping sets
+        if matches!(aggregate.group_expr.first(), Some(Expr::GroupingSet(_))) {
+            return Ok(Transformed::no(LogicalPlan::Aggregate(aggregate)));
+        }
+        let collations = aggregate
+            .group_expr
+            .iter()
+            .map(|expr| common_collation(&[expr], schema))
+            .collect::<Result<Vec<_>>>()?;
+        if collations.iter().all(Option::is_none) {
+            return Ok(Transformed::no(LogicalPlan::Aggregate(aggregate)));
+        }
+
+        let group_len = aggregate.group_expr.len();
+        let mut aggr_expr = aggregate.aggr_expr.clone();
+        let mut group_expr = Vec::with_capacity(group_len);
+        // index in the output of the new aggregate of each group expression
+        let mut output_indices = Vec::with_capacity(group_len);
+        for (i, (expr, collation)) in
+            aggregate.group_expr.iter().zip(collations).enumerate()
+        {
+            match collation {
+                Some(collation) => {
+                    output_indices.push(group_len + aggr_expr.len());
+                    aggr_expr.push(
+                        Expr::AggregateFunction(AggregateFunction::new(
+                            aggregate_function::AggregateFunction::FirstValue,
+                            vec![expr.clone()],
+                            false,
+                            None,
+                            None,
+                            None,
+                        ))
+                        .alias(format!("__collation_group_{i}")),
+                    );
+                    group_expr.push(self.key(expr.clone(), &collation));
+                }
+                None => {
+                    output_indices.push(i);
+                    group_expr.push(expr.clone());
+                }
+            }
+        }
+        let aggr_len = aggregate.aggr_expr.len();
+        let new_aggregate =
+            Aggregate::try_new(aggregate.input.clone(), group_expr, aggr_expr)?;
+
+        let exprs = output_indices
+            .into_iter()
+            .chain(group_len..group_len + aggr_len)
+            .enumerate()
+            .map(|(i, new_index)| {
+                let (qualifier, field) = aggregate.schema.qualified_field(i);
+                let column = Expr::Column(Column::from(
+                    new_aggregate.schema.qualified_field(new_index),
+                ));
+                if new_index == i {
+                    column
+                } else {
+                    column.alias_qualified(qualifier.cloned(), field.name())
+                }
+            })
+            .collect();
+        let projection = Projection::try_new_with_schema(
+            exprs,
+            Arc::new(LogicalPlan::Aggregate(new_aggregate)),
+            aggregate.schema,
+        )?;
+        Ok(Transformed::yes(LogicalPlan::Projection(projection)))
+    }
+}
+
+impl AnalyzerRule for ApplyCollations {
+    fn analyze(&self, plan: LogicalPlan, _: &ConfigOptions) -> Result<LogicalPlan> {
+        plan.transform_up_with_subqueries(|plan| self.rewrite_plan(plan))
+            .data()
+    }
+
+    fn name(&self) -> &str {
+        "apply_collations"
+    }
+}
+
+/// How the collation of an expression was determined
+enum Derivation {
+    /// From the metadata of a column
+    Implicit(String),
+    /// From a `COLLATE` clause
+    Explicit(String),
+}
+
+fn derivation(expr: &Expr, schema: &DFSchema) -> Result<Option<Derivation>> {
+    Ok(match expr {
+        Expr::ScalarFunction(ScalarFunction {
+            func_def: ScalarFunctionDefinition::UDF(fun),
+            args,
+        }) if fun.name() == COLLATE => match args.get(1) {
+            Some(Expr::Literal(ScalarValue::Utf8(Some(collation)))) => {
+                Some(Derivation::Explicit(collation.clone()))
+            }
+            _ => None,
+        },
+        Expr::Column(_) => expr
+            .metadata(schema)?
+            .get(COLLATION_METADATA_KEY)
+            .map(|collation| Derivation::Implicit(collation.clone())),
+        Expr::Alias(Alias { expr, .. })
+        | Expr::Cast(Cast { expr, .. })
+        | Expr::TryCast(TryCast { expr, .. }) => derivation(expr, schema)?,
+        _ => None,
+    })
+}
+
+/// The collation in which the string expressions `exprs` must be compared,
+/// if any. Returns an error if the expressions have conflicting collations.
+fn common_collation(exprs: &[&Expr], schema: &DFSchema) -> Result<Option<String>> {
+    for expr in exprs {
+        if !matches!(expr.get_type(schema)?, DataType::Utf8 | DataType::LargeUtf8) {
+            return Ok(None);
+        }
+    }
+
+    let mut explicit: Option<String> = None;
+    let mut implicit: Option<String> = None;
+    let mut conflicting_implicit = None;
+    for expr in exprs {
+        match derivation(expr, schema)? {
+            Some(Derivation::Explicit(collation)) => match &explicit {
+                Some(other) if !other.eq_ignore_ascii_case(&collation) => {
+                    return plan_err!(
+                        "Conflicting collations '{other}' and '{collation}'"
+                    );
+                }
+                _ => explicit = Some(collation),
+            },
+            Some(Derivation::Implicit(collation)) => match &implicit {
+                Some(other) if !other.eq_ignore_ascii_case(&collation) => {
+                    conflicting_implicit = Some((other.clone(), collation));
+                }
+                _ => implicit = Some(collation),
+            },
+            None => {}
+        }
+    }
+
+    match (explicit, conflicting_implicit) {
+        (Some(collation), _) => Ok(Some(collation)),
+        (None, Some((left, right))) => plan_err!(
+            "Cannot compare strings with collations '{left}' and '{right}', \
+             use COLLATE to choose one"
+        ),
+        (None, None) => Ok(implicit),
+    }
+}
+
+fn is_comparison(op: Operator) -> bool {
+    matches!(
+        op,
+        Operator::Eq
+            | Operator::NotEq
+            | Operator::Lt
+            | Operator::LtEq
+            | Operator::Gt
+            | Operator::GtEq
+            | Operator::IsDistinctFrom
+            | Operator::IsNotDistinctFrom
+    )
+}
+
+/// Record the explicit collations of the projected expressions in the
+/// metadata of the output fields, so that they apply to the expressions
+/// referring to them
+fn annotate_projection(
+    projection: Projection,
+    schema: &DFSchema,
+) -> Result<Transformed<LogicalPlan>> {
+    let mut annotated = false;
+    let fields = projection
+        .expr
+        .iter()
+        .zip(projection.schema.iter())
+        .map(|(expr, (qualifier, field))| {
+            let field = match derivation(expr, schema)? {
+                Some(Derivation::Explicit(collation)) => {
+                    annotated = true;
+                    let mut metadata = field.metadata().clone();
+                    metadata.insert(COLLATION_METADATA_KEY.to_string(), collation);
+                    Arc::new(field.as_ref().clone().with_metadata(metadata))
+                }
+                _ => field.clone(),
+            };
+            Ok((qualifier.cloned(), field))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    if !annotated {
+        return Ok(Transformed::no(LogicalPlan::Projection(projection)));
+    }
+
+    let output_schema =
+        DFSchema::new_with_metadata(fields, projection.schema.metadata().clone())?
+            .with_functional_dependencies(
+                projection.schema.functional_dependencies().clone(),
+            )?;
+    let projection = Projection::try_new_with_schema(
+        projection.expr,
+        projection.input,
+        Arc::new(output_schema),
+    )?;
+    Ok(Transformed::yes(LogicalPlan::Projection(projection)))
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use arrow::datatypes::{Field, Schema};
+    use datafusion_expr::logical_plan::builder::table_scan;
+    use datafusion_expr::{
+        col, count, create_udf, ColumnarValue, LogicalPlanBuilder, Volatility,
+    };
+
+    use super::*;
+    use crate::analyzer::Analyzer;
+    use crate::test::*;
+
+    /// Stand-in for `collation_key` of `datafusion-functions`
+    fn collation_key() -> Arc<ScalarUDF> {
+        Arc::new(create_udf(
+            "collation_key",
+            vec![DataType::Utf8, DataType::Utf8],
+            Arc::new(DataType::Binary),
+            Volatility::Immutable,
+            Arc::new(|args: &[ColumnarValue]| Ok(args[0].clone())),
+        ))
+    }
+
+    fn collated_table_scan(collation: &str) -> Result<LogicalPlan> {
+        let metadata =
+            HashMap::from([(COLLATION_METADATA_KEY.to_string(), collation.to_string())]);
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", DataType::Utf8, true).with_metadata(metadata),
+        ]);
+        table_scan(Some("t"), &schema, None)?.build()
+    }
+
+    #[test]
+    fn compare_and_sort_in_column_collation() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(collated_table_scan("nocase")?)
+            .filter(col("name").eq(lit("a")))?
+            .sort(vec![col("name").sort(true, false)])?
+            .build()?;
+        let expected = "Sort: collation_key(t.name, Utf8(\"nocase\")) ASC NULLS LAST\
+        \n  Filter: collation_key(t.name, Utf8(\"nocase\")) = collation_key(Utf8(\"a\"), Utf8(\"nocase\"))\
+        \n    TableScan: t";
+        assert_analyzed_plan_eq(
+            Arc::new(ApplyCollations::new(collation_key())),
+            &plan,
+            expected,
+        )
+    }
+
+    #[test]
+    fn group_by_column_collation() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(collated_table_scan("nocase")?)
+            .aggregate(vec![col("name")], vec![count(col("id"))])?
+            .build()?;
+        let expected = "Projection: __collation_group_0 AS name, COUNT(t.id)\
+        \n  Aggregate: groupBy=[[collation_key(t.name, Utf8(\"nocase\"))]], aggr=[[COUNT(t.id), FIRST_VALUE(t.name) AS __collation_group_0]]\
+        \n    TableScan: t";
+        assert_analyzed_plan_eq(
+            Arc::new(ApplyCollations::new(collation_key())),
+            &plan,
+            expected,
+        )
+    }
+
+    #[test]
+    fn conflicting_collations() -> Result<()> {
+        let scan = collated_table_scan("nocase")?;
+        let other = LogicalPlanBuilder::from(collated_table_scan("de-DE")?)
+            .alias("u")?
+            .build()?;
+        let plan = LogicalPlanBuilder::from(scan)
+            .cross_join(other)?
+            .filter(col("t.name").eq(col("u.name")))?
+            .build()?;
+        let err =
+            Analyzer::with_rules(vec![Arc::new(ApplyCollations::new(collation_key()))])
+                .execute_and_check(&plan, &ConfigOptions::default(), |_, _| {})
+                .unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Cannot compare strings with collations 'nocase' and 'de-DE'"),
+            "{err}"
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,553 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`HandleGapFill`] plans the filling of the time buckets missing from the
+//! output of aggregates grouped by `time_bucket_gapfill`
+
+use std::collections::HashSet;
+use std::fmt;
+use std::ops::Bound;
+use std::sync::Arc;
+
+use crate::analyzer::AnalyzerRule;
+
+use datafusion_common::config::ConfigOptions;
+use datafusion_common::tree_node::{
+    Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
+};
+use datafusion_common::{plan_err, Column, DFSchemaRef, Result};
+use datafusion_expr::expr::{Between, BinaryExpr, ScalarFunction};
+use datafusion_expr::logical_plan::{
+    Aggregate, Extension, Filter, Projection, UserDefinedLogicalNodeCore,
+};
+use datafusion_expr::utils::split_conjunction;
+use datafusion_expr::{Expr, LogicalPlan, Operator, ScalarFunctionDefinition};
+
+/// Name of the function marking the grouping expression to fill
+const TIME_BUCKET_GAPFILL: &str = "time_bucket_gapfill";
+
+/// Name of the function filling values with the last observed value
+const LOCF: &str = "locf";
+
+/// Fills the time buckets missing from the output of an aggregate, see
+/// [`HandleGapFill`]
+///
+/// Planned as a `GapFillExec` by the default physical planner.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct GapFill {
+    /// The aggregate
+    pub input: Arc<LogicalPlan>,
+    /// The grouping columns other than the time bucket
+    pub series: Vec<Expr>,
+    /// The time bucket column
+    pub time: Expr,
+    /// The interval between the starts of two buckets
+    pub stride: Expr,
+    /// The timestamp the buckets are aligned to, the unix epoch if `None`
+    pub origin: Option<Expr>,
+    /// The start of the time range to fill
+    pub start: Bound<Expr>,
+    /// The end of the time range to fill
+    pub end: Bound<Expr>,
+    /// The columns filled with the last observed value of the series
+    pub locf: Vec<Expr>,
+}
+
+impl GapFill {
+    /// The expressions of the parameters of the node, in the order of
+    /// [`UserDefinedLogicalNodeCore::expressions`]
+    fn params(&self) -> impl Iterator<Item = &Expr> {
+        std::iter::once(&self.stride)
+            .chain(self.origin.as_ref())
+            .chain(bound_expr(&self.start))
+            .chain(bound_expr(&self.end))
+    }
+}
+
+fn bound_expr(bound: &Bound<Expr>) -> Option<&Expr> {
+    match bound {
+        Bound::Included(expr) | Bound::Excluded(expr) => Some(expr),
+        Bound::Unbounded => None,
+    }
+}
+
+/// Returns `bound` with its expression replaced by the next of `exprs`
+fn with_bound_expr(
+    bound: &Bound<Expr>,
+    exprs: &mut impl Iterator<Item = Expr>,
+) -> Bound<Expr> {
+    match bound {
+        Bound::Included(_) => Bound::Included(exprs.next().unwrap()),
+        Bound::Excluded(_) => Bound::Excluded(exprs.next().unwrap()),
+        Bound::Unbounded => Bound::Unbounded,
+    }
+}
+
+impl UserDefinedLogicalNodeCore for GapFill {
+    fn name(&self) -> &str {
+        "GapFill"
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![self.input.as_ref()]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        self.input.schema()
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        self.series
+            .iter()
+            .chain(std::iter::once(&self.time))
+            .chain(self.params())
+            .chain(&self.locf)
+            .cloned()
+            .collect()
+    }
+
+    fn fmt_for_explain(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        let series = self
+            .series
+            .iter()
+            .map(|e| e.to_string())
+            .collect::<Vec<_>>();
+        write!(
+            f,
+            "GapFill: series=[{}], time={}, stride={}",
+            series.join(", "),
+            self.time,
+            self.stride
+        )?;
+        if let Some(origin) = &self.origin {
+            write!(f, ", origin={origin}")?;
+        }
+        match &self.start {
+            Bound::Included(expr) => write!(f, ", range=[{expr}, ")?,
+            Bound::Excluded(expr) => write!(f, ", range=({expr}, ")?,
+            Bound::Unbounded => write!(f, ", range=(, ")?,
+        }
+        match &self.end {
+            Bound::Included(expr) => write!(f, "{expr}]")?,
+            Bound::Excluded(expr) => write!(f, "{expr})")?,
+            Bound::Unbounded => write!(f, ")")?,
+        }
+        if !self.locf.is_empty() {
+            let locf = self.locf.iter().map(|e| e.to_string()).collect::<Vec<_>>();
+            write!(f, ", locf=[{}]", locf.join(", "))?;
+        }
+        Ok(())
+    }
+
+    fn from_template(&self, exprs: &[Expr], inputs: &[LogicalPlan]) -> Self {
+        let mut exprs = exprs.iter().cloned();
+        let series = exprs.by_ref().take(self.series.len()).collect();
+        let time = exprs.next().unwrap();
+        let stride = exprs.next().unwrap();
+        let origin = self.origin.as_ref().map(|_| exprs.next().unwrap());
+        let start = with_bound_expr(&self.start, &mut exprs);
+        let end = with_bound_expr(&self.end, &mut exprs);
+        Self {
+            input: Arc::new(inputs[0].clone()),
+            series,
+            time,
+            stride,
+            origin,
+            start,
+            end,
+            locf: exprs.collect(),
+        }
+    }
+}
+
+/// Plans the filling of the time buckets missing from the output of
+/// aggregates grouped by `time_bucket_gapfill(stride, time[, origin])`.
+///
+/// The time range to fill is taken from the bounds of `time` in the `WHERE`
+/// clause of the query, which must contain both a lower bound (`time > x`,
+/// `time >= x`) and an upper bound (`time < y`, `time <= y`), or
+/// `time BETWEEN x AND y`. A [`GapFill`] node is inserted above the aggregate.
+///
+/// In the projection above the aggregate, `locf(aggregate)` is replaced with
+/// `aggregate`, which the [`GapFill`] fills with its last observed value.
+/// If `aggregate` is also used without `locf`, a copy of it is filled instead.
+///
+/// ```text
+/// SELECT time_bucket_gapfill(INTERVAL '1 minute', time) AS minute, locf(avg(v))
+/// FROM t WHERE time >= '2020-01-01T00:00:00' AND time < '2020-01-01T01:00:00'
+/// GROUP BY minute
+/// ```
+#[derive(Default)]
+pub struct HandleGapFill {}
+
+impl HandleGapFill {
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl AnalyzerRule for HandleGapFill {
+    fn analyze(&self, plan: LogicalPlan, _: &ConfigOptions) -> Result<LogicalPlan> {
+        plan.transform_up_with_subqueries(|plan| match plan {
+            LogicalPlan::Aggregate(aggregate) => plan_gap_fill(aggregate),
+            LogicalPlan::Projection(projection) => plan_locf(projection),
+            _ => Ok(Transformed::no(plan)),
+        })
+        .data()
+    }
+
+    fn name(&self) -> &str {
+        "handle_gap_fill"
+    }
+}
+
+/// Returns the arguments of `expr` if it calls the function `name`
+fn function_args<'a>(expr: &'a Expr, name: &str) -> Option<&'a [Expr]> {
+    match expr {
+        Expr::ScalarFunction(ScalarFunction {
+            func_def: ScalarFunctionDefinition::UDF(udf),
+            args,
+        }) if udf.name() == name => Some(args),
+        _ => None,
+    }
+}
+
+fn plan_gap_fill(aggregate: Aggregate) -> Result<Transformed<LogicalPlan>> {
+    let mut gap_fill_exprs =
+        aggregate
+            .group_expr
+            .iter()
+            .enumerate()
+            .filter_map(|(i, expr)| {
+                let expr = match expr {
+                    Expr::Alias(alias) => alias.expr.as_ref(),
+                    expr => expr,
+                };
+                Some((i, function_args(expr, TIME_BUCKET_GAPFILL)?))
+            });
+    let Some((time_index, args)) = gap_fill_exprs.next() else {
+        return Ok(Transformed::no(LogicalPlan::Aggregate(aggregate)));
+    };
+    if gap_fill_exprs.next().is_some() {
+        return plan_err!("Only one {TIME_BUCKET_GAPFILL} is allowed per aggregate");
+    }
+    let [stride, time, origin @ ..] = args else {
+        return plan_err!("{TIME_BUCKET_GAPFILL} expects a stride and a timestamp");
+    };
+    if !is_constant(stride) || !origin.iter().all(is_constant) {
+        return plan_err!("{TIME_BUCKET_GAPFILL} expects a constant stride and origin");
+    }
+    let (start, end) = time_range(&aggregate.input, time)?;
+    if matches!(start, Bound::Unbounded) || matches!(end, Bound::Unbounded) {
+        return plan_err!(
+            "{TIME_BUCKET_GAPFILL} requires a lower and an upper bound of {time} in the WHERE clause"
+        );
+    }
+
+    let schema = aggregate.schema.clone();
+    let column = |i: usize| Expr::Column(Column::from(schema.qualified_field(i)));
+    let gap_fill = GapFill {
+        series: (0..aggregate.group_expr.len())
+            .filter(|i| *i != time_index)
+            .map(column)
+            .collect(),
+        time: column(time_index),
+        stride: stride.clone(),
+        origin: origin.first().cloned(),
+        start,
+        end,
+        locf: vec![],
+        input: Arc::new(LogicalPlan::Aggregate(aggregate)),
+    };
+    Ok(Transformed::yes(LogicalPlan::Extension(Extension {
+        node: Arc::new(gap_fill),
+    })))
+}
+
+/// Whether `expr` has the same value for all rows
+fn is_constant(expr: &Expr) -> bool {
+    !expr
+        .exists(|expr| {
+            Ok(matches!(
+                expr,
+                Expr::Column(_)
+                    | Expr::OuterReferenceColumn(_, _)
+                    | Expr::ScalarSubquery(_)
+                    | Expr::Exists(_)
+                    | Expr::InSubquery(_)
+            ))
+        })
+        .unwrap_or(true)
+}
+
+/// Returns the bounds of `time` in the filter below the aggregate
+fn time_range(input: &LogicalPlan, time: &Expr) -> Result<(Bound<Expr>, Bound<Expr>)> {
+    let mut start = Bound::Unbounded;
+    let mut end = Bound::Unbounded;
+    let LogicalPlan::Filter(filter) = input else {
+        return Ok((start, end));
+    };
+    for predicate in split_conjunction(&filter.predicate) {
+        match predicate {
+            Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+                let (op, bound) = if left.as_ref() == time && is_constant(right) {
+                    (*op, right.as_ref().clone())
+                } else if right.as_ref() == time && is_constant(left) {
+                    match op.swap() {
+                        Some(op) => (op, left.as_ref().clone()),
+                        None => continue,
+                    }
+                } else {
+                    continue;
+                };
+                match op {
+                    Operator::Gt => start = Bound::Excluded(bound),
+                    Operator::GtEq => start = Bound::Included(bound),
+                    Operator::Lt => end = Bound::Excluded(bound),
+                    Operator::LtEq => end = Bound::Included(bound),
+                    _ => {}
+                }
+            }
+            Expr::Between(Between {
+                expr,
+                negated: false,
+                low,
+                high,
+            }) if expr.as_ref() == time && is_constant(low) && is_constant(high) => {
+                start = Bound::Included(low.as_ref().clone());
+                end = Bound::Included(high.as_ref().clone());
+            }
+            _ => {}
+        }
+    }
+    Ok((start, end))
+}
+
+/// Replaces `locf(column)` in a projection above a [`GapFill`] with `column`,
+/// and fills `column` with its last observed value. If `column` is also used
+/// without `locf`, it is replaced with a filled copy of its aggregate instead.
+fn plan_locf(projection: Projection) -> Result<Transformed<LogicalPlan>> {
+    let mut has_locf = false;
+    for expr in &projection.expr {
+        has_locf |= expr.exists(|expr| Ok(function_args(expr, LOCF).is_some()))?;
+    }
+    if !has_locf {
+        return Ok(Transformed::no(LogicalPlan::Projection(projection)));
+    }
+
+    // The projection may be above the HAVING filter of the aggregate
+    let (filter, gap_fill) = match projection.input.as_ref() {
+        LogicalPlan::Filter(filter) => (Some(filter), filter.input.as_ref()),
+        input => (None, input),
+    };
+    let gap_fill = match gap_fill {
+        LogicalPlan::Extension(Extension { node }) => {
+            node.as_any().downcast_ref::<GapFill>()
+        }
+        _ => None,
+    };
+    let Some(gap_fill) = gap_fill else {
+        return plan_err!(
+            "{LOCF} can only be used on an aggregate of a query grouped by {TIME_BUCKET_GAPFILL}"
+        );
+    };
+
+    let mut unfilled = HashSet::new();
+    for expr in &projection.expr {
+        columns_outside_locf(expr, &mut unfilled)?;
+    }
+    if let Some(filter) = filter {
+        columns_outside_locf(&filter.predicate, &mut unfilled)?;
+    }
+
+    let mut gap_fill = gap_fill.clone();
+    let exprs = projection
+        .expr
+        .into_iter()
+        .map(|expr| {
+            let name = expr.display_name()?;
+            let transformed = expr.transform_up(|expr| {
+                let Some(args) = function_args(&expr, LOCF) else {
+                    return Ok(Transformed::no(expr));
+                };
+                match args {
+                    [column @ Expr::Column(_)]
+                        if !gap_fill.series.contains(column)
+                            && &gap_fill.time != column =>
+                    {
+                        let filled = match column {
+                            Expr::Column(c) if unfilled.contains(c) => {
+                                filled_copy(&mut gap_fill, c)?
+                            }
+                            _ => column.clone(),
+                        };
+                        if !gap_fill.locf.contains(&filled) {
+                            gap_fill.locf.push(filled.clone());
+                        }
+                        Ok(Transformed::yes(filled))
+                    }
+                    _ => {
+                        plan_err!("{LOCF} expects an aggregate of the query, got {expr}")
+                    }
+                }
+            })?;
+            if transformed.transformed {
+                transformed.data.alias_if_changed(name)
+            } else {
+                Ok(transformed.data)
+            }
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    let mut input = LogicalPlan::Extension(Extension {
+        node: Arc::new(gap_fill),
+    });
+    if let Some(filter) = filter {
+        input = LogicalPlan::Filter(Filter::try_new(
+            filter.predicate.clone(),
+            Arc::new(input),
+        )?);
+    }
+    Ok(Transformed::yes(LogicalPlan::Projection(
+        Projection::try_new(exprs, Arc::new(input))?,
+    )))
+}
+
+/// Collects the columns referred to by `expr` outside of `locf`
+fn columns_outside_locf(expr: &Expr, columns: &mut HashSet<Column>) -> Result<()> {
+    expr.apply(|expr| {
+        if function_args(expr, LOCF).is_some() {
+            return Ok(TreeNodeRecursion::Jump);
+        }
+        if let Expr::Column(column) = expr {
+            columns.insert(column.clone());
+        }
+        Ok(TreeNodeRecursion::Continue)
+    })
+    .map(|_| ())
+}
+
+/// Adds a copy of the aggregate expression of `column` to the aggregate below
+/// `gap_fill`, so that it can be filled while `column` is not. Returns the
+/// column of the copy.
+fn filled_copy(gap_fill: &mut GapFill, column: &Column) -> Result<Expr> {
+    let LogicalPlan::Aggregate(aggregate) = gap_fill.input.as_ref() else {
+        return plan_err!("{LOCF} expects an aggregate of the query, got {column}");
+    };
+    let name = format!("{LOCF}({})", column.flat_name());
+    let copy = Column::from_name(&name);
+    if aggregate.schema.has_column(&copy) {
+        return Ok(Expr::Column(copy));
+    }
+
+    let index = aggregate.schema.index_of_column(column)?;
+    let Some(aggr_expr) = index
+        .checked_sub(aggregate.group_expr_len()?)
+        .and_then(|i| aggregate.aggr_expr.get(i))
+    else {
+        return plan_err!("{LOCF} expects an aggregate of the query, got {column}");
+    };
+    let mut aggr_exprs = aggregate.aggr_expr.clone();
+    aggr_exprs.push(aggr_expr.clone().unalias().alias(&name));
+    gap_fill.input = Arc::new(LogicalPlan::Aggregate(Aggregate::try_new(
+        aggregate.input.clone(),
+        aggregate.group_expr.clone(),
+        aggr_exprs,
+    )?));
+    Ok(Expr::Column(copy))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::analyzer::Analyzer;
+    use crate::test::*;
+    use arrow::datatypes::DataType;
+    use datafusion_expr::{
+        col, create_udf, lit, max, ColumnarValue, LogicalPlanBuilder, ScalarUDF,
+        Volatility,
+    };
+
+    /// Stand-in for the functions of `datafusion-functions`
+    fn udf(name: &str, num_args: usize) -> Arc<ScalarUDF> {
+        Arc::new(create_udf(
+            name,
+            vec![DataType::UInt32; num_args],
+            Arc::new(DataType::UInt32),
+            Volatility::Immutable,
+            Arc::new(|args: &[ColumnarValue]| Ok(args[args.len() - 1].clone())),
+        ))
+    }
+
+    fn gap_fill_plan(predicate: Expr) -> Result<LogicalPlan> {
+        let bucket = udf(TIME_BUCKET_GAPFILL, 2).call(vec![lit(2u32), col("test.b")]);
+        LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(predicate)?
+            .aggregate(vec![col("a"), bucket.clone()], vec![max(col("c"))])?
+            .project(vec![
+                col("a"),
+                Expr::Column(Column::from_name(bucket.display_name()?)),
+                udf(LOCF, 1).call(vec![Expr::Column(Column::from_name("MAX(test.c)"))]),
+            ])?
+            .build()
+    }
+
+    #[test]
+    fn gap_fill() -> Result<()> {
+        let plan = gap_fill_plan(col("b").gt_eq(lit(1u32)).and(col("b").lt(lit(10u32))))?;
+        let expected = "Projection: test.a, time_bucket_gapfill(UInt32(2),test.b), MAX(test.c) AS locf(MAX(test.c))\
+        \n  GapFill: series=[test.a], time=time_bucket_gapfill(UInt32(2),test.b), stride=UInt32(2), range=[UInt32(1), UInt32(10)), locf=[MAX(test.c)]\
+        \n    Aggregate: groupBy=[[test.a, time_bucket_gapfill(UInt32(2), test.b)]], aggr=[[MAX(test.c)]]\
+        \n      Filter: test.b >= UInt32(1) AND test.b < UInt32(10)\
+        \n        TableScan: test";
+        assert_analyzed_plan_eq(Arc::new(HandleGapFill::new()), &plan, expected)
+    }
+
+    #[test]
+    fn gap_fill_copy_of_unfilled_aggregate() -> Result<()> {
+        let max_c = Expr::Column(Column::from_name("MAX(test.c)"));
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .filter(col("b").gt_eq(lit(1u32)).and(col("b").lt(lit(10u32))))?
+            .aggregate(
+                vec![udf(TIME_BUCKET_GAPFILL, 2).call(vec![lit(2u32), col("test.b")])],
+                vec![max(col("c"))],
+            )?
+            .project(vec![max_c.clone(), udf(LOCF, 1).call(vec![max_c])])?
+            .build()?;
+        let expected = "Projection: MAX(test.c), locf(MAX(test.c))\
+        \n  GapFill: series=[], time=time_bucket_gapfill(UInt32(2),test.b), stride=UInt32(2), range=[UInt32(1), UInt32(10)), locf=[locf(MAX(test.c))]\
+        \n    Aggregate: groupBy=[[time_bucket_gapfill(UInt32(2), test.b)]], aggr=[[MAX(test.c), MAX(test.c) AS locf(MAX(test.c))]]\
+        \n      Filter: test.b >= UInt32(1) AND test.b < UInt32(10)\
+        \n        TableScan: test";
+        assert_analyzed_plan_eq(Arc::new(HandleGapFill::new()), &plan, expected)
+    }
+
+    #[test]
+    fn gap_fill_without_upper_bound() -> Result<()> {
+        let plan = gap_fill_plan(col("b").gt_eq(lit(1u32)))?;
+        let err = Analyzer::with_rules(vec![Arc::new(HandleGapFill::new())])
+            .execute_and_check(&plan, &ConfigOptions::default(), |_, _| {})
+            .unwrap_err();
+        assert!(
+            err.to_string().contains(
+                "time_bucket_gapfill requires a lower and an upper bound of test.b"
+            ),
+            "{err}"
+        );
+        Ok(())
+    }
+}
@@ -55,12 +55,16 @@ fn analyze_internal(plan: LogicalPlan) -> Result<Transformed<LogicalPlan>> {
         match plan {
             // Match only on scans without filter / projection / fetch
             // Views and DataFrames won't have those added
-            // during the early stage of planning
+            // during the early stage of planning. Sampled views are left to
+            // the table provider, which samples the rows of the view, and so
+            // are the scans of a version of a view.
             LogicalPlan::TableScan(TableScan {
                 table_name,
                 source,
                 projection,
                 filters,
+                sample: None,
+                version: None,
                 ..
             }) if filters.is_empty() && source.get_logical_plan().is_some() => {
                 let sub_plan = source.get_logical_plan().unwrap();
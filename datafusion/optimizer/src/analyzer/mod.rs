@@ -18,10 +18,9 @@
 //! [`Analyzer`] and [`AnalyzerRule`]
 use std::sync::Arc;
 
-use log::debug;
+use tracing::{debug_span, info_span};
 
 use datafusion_common::config::ConfigOptions;
-use datafusion_common::instant::Instant;
 use datafusion_common::tree_node::{TreeNode, TreeNodeRecursion};
 use datafusion_common::{DataFusionError, Result};
 use datafusion_expr::expr::Exists;
@@ -30,6 +29,7 @@ use datafusion_expr::expr_rewriter::FunctionRewrite;
 use datafusion_expr::{Expr, LogicalPlan};
 
 use crate::analyzer::count_wildcard_rule::CountWildcardRule;
+use crate::analyzer::handle_gap_fill::HandleGapFill;
 use crate::analyzer::inline_table_scan::InlineTableScan;
 use crate::analyzer::subquery::check_subquery_expr;
 use crate::analyzer::type_coercion::TypeCoercion;
@@ -37,8 +37,10 @@ use crate::utils::log_plan;
 
 use self::function_rewrite::ApplyFunctionRewrites;
 
+pub mod collation;
 pub mod count_wildcard_rule;
 pub mod function_rewrite;
+pub mod handle_gap_fill;
 pub mod inline_table_scan;
 pub mod subquery;
 pub mod type_coercion;
@@ -91,6 +93,7 @@ impl Analyzer {
             Arc::new(InlineTableScan::new()),
             Arc::new(TypeCoercion::new()),
             Arc::new(CountWildcardRule::new()),
+            Arc::new(HandleGapFill::new()),
         ];
         Self::with_rules(rules)
     }
@@ -122,7 +125,7 @@ impl Analyzer {
     where
         F: FnMut(&LogicalPlan, &dyn AnalyzerRule),
     {
-        let start_time = Instant::now();
+        let _span = info_span!("datafusion.analyze").entered();
         let mut new_plan = plan.clone();
 
         // Create an analyzer pass that rewrites `Expr`s to function_calls, as
@@ -137,6 +140,8 @@ impl Analyzer {
 
         // TODO add common rule executor for Analyzer and Optimizer
         for rule in rules {
+            let _span =
+                debug_span!("datafusion.analyzer_rule", rule = rule.name()).entered();
             new_plan = rule.analyze(new_plan, config).map_err(|e| {
                 DataFusionError::Context(rule.name().to_string(), Box::new(e))
             })?;
@@ -148,7 +153,6 @@ impl Analyzer {
             DataFusionError::Context("check_analyzed_plan".to_string(), Box::new(e))
         })?;
         log_plan("Final analyzed plan", &new_plan);
-        debug!("Analyzer took {} ms", start_time.elapsed().as_millis());
         Ok(new_plan)
     }
 }
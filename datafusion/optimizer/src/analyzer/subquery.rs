@@ -15,8 +15,6 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::ops::Deref;
-
 use crate::analyzer::check_plan;
 use crate::utils::collect_subquery_cols;
 
@@ -25,8 +23,7 @@ use datafusion_common::{plan_err, Result};
 use datafusion_expr::expr_rewriter::strip_outer_reference;
 use datafusion_expr::utils::split_conjunction;
 use datafusion_expr::{
-    Aggregate, BinaryExpr, Cast, Expr, Filter, Join, JoinType, LogicalPlan, Operator,
-    Window,
+    Aggregate, BinaryExpr, Expr, Filter, Join, JoinType, LogicalPlan, Operator, Window,
 };
 
 /// Do necessary check on subquery expressions and fail the invalid plan
@@ -291,7 +288,9 @@ fn get_correlated_expressions(inner_plan: &LogicalPlan) -> Result<Vec<Expr>> {
     Ok(exprs)
 }
 
-/// Check whether the expression can pull up over the aggregation without change the result of the query
+/// Check whether the expression can pull up over the aggregation without change the result of the query,
+/// i.e. whether it compares a deterministic expression of the inner columns, such as `d.s['k']`,
+/// with an expression of the outer references
 fn can_pullup_over_aggregation(expr: &Expr) -> bool {
     if let Expr::BinaryExpr(BinaryExpr {
         left,
@@ -299,28 +298,20 @@ fn can_pullup_over_aggregation(expr: &Expr) -> bool {
         right,
     }) = expr
     {
-        match (left.deref(), right.deref()) {
-            (Expr::Column(_), right) if right.to_columns().unwrap().is_empty() => true,
-            (left, Expr::Column(_)) if left.to_columns().unwrap().is_empty() => true,
-            (Expr::Cast(Cast { expr, .. }), right)
-                if matches!(expr.deref(), Expr::Column(_))
-                    && right.to_columns().unwrap().is_empty() =>
-            {
-                true
-            }
-            (left, Expr::Cast(Cast { expr, .. }))
-                if matches!(expr.deref(), Expr::Column(_))
-                    && left.to_columns().unwrap().is_empty() =>
-            {
-                true
-            }
-            (_, _) => false,
-        }
+        (is_inner_expr(left) && right.to_columns().unwrap().is_empty())
+            || (is_inner_expr(right) && left.to_columns().unwrap().is_empty())
     } else {
         false
     }
 }
 
+/// Check whether the expression is a deterministic expression of the inner columns only
+fn is_inner_expr(expr: &Expr) -> bool {
+    !expr.to_columns().unwrap().is_empty()
+        && !expr.contains_outer()
+        && !expr.is_volatile().unwrap_or(true)
+}
+
 /// Check whether the window expressions contain a mixture of out reference columns and inner columns
 fn check_mixed_out_refer_in_window(window: &Window) -> Result<()> {
     let mixed = window.window_expr.iter().any(|win_expr| {
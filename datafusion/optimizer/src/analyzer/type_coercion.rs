@@ -21,8 +21,10 @@ use std::sync::Arc;
 
 use arrow::datatypes::{DataType, IntervalUnit};
 
-use datafusion_common::config::ConfigOptions;
-use datafusion_common::tree_node::{Transformed, TreeNodeRewriter};
+use datafusion_common::config::{ConfigOptions, DecimalOverflowMode};
+use datafusion_common::tree_node::{
+    Transformed, TransformedResult, TreeNode, TreeNodeRewriter,
+};
 use datafusion_common::{
     exec_err, internal_err, plan_datafusion_err, plan_err, DFSchema, DFSchemaRef,
     DataFusionError, Result, ScalarValue,
@@ -66,8 +68,93 @@ impl AnalyzerRule for TypeCoercion {
         "type_coercion"
     }
 
-    fn analyze(&self, plan: LogicalPlan, _: &ConfigOptions) -> Result<LogicalPlan> {
-        analyze_internal(&DFSchema::empty(), &plan)
+    fn analyze(&self, plan: LogicalPlan, config: &ConfigOptions) -> Result<LogicalPlan> {
+        let plan = analyze_internal(&DFSchema::empty(), &plan)?;
+        match config.execution.aggregate.decimal_overflow_mode {
+            DecimalOverflowMode::Promote => promote_decimal_aggregates(plan),
+            DecimalOverflowMode::Unchecked
+            | DecimalOverflowMode::Error
+            | DecimalOverflowMode::Saturate => Ok(plan),
+        }
+    }
+}
+
+/// Casts the `Decimal128` arguments of `SUM` and `AVG`, including as window
+/// functions, to `Decimal256`, so that they are aggregated as `Decimal256`
+/// values, for [`DecimalOverflowMode::Promote`]
+fn promote_decimal_aggregates(plan: LogicalPlan) -> Result<LogicalPlan> {
+    plan.transform_up_with_subqueries(|plan| {
+        let inputs = plan.inputs().into_iter().cloned().collect::<Vec<_>>();
+        let exprs = match &plan {
+            LogicalPlan::Aggregate(_) | LogicalPlan::Window(_) => plan
+                .expressions()
+                .into_iter()
+                .map(|expr| promote_decimal_aggregate(expr, inputs[0].schema()))
+                .collect::<Result<Vec<_>>>()?,
+            _ => plan.expressions(),
+        };
+        // The schemas of all the plans above a promoted aggregate change, so
+        // they are recomputed
+        let new_plan = plan.with_new_exprs(exprs, inputs)?;
+        if new_plan.schema() == plan.schema() {
+            Ok(Transformed::no(plan))
+        } else {
+            Ok(Transformed::yes(new_plan))
+        }
+    })
+    .data()
+}
+
+fn promote_decimal_aggregate(expr: Expr, schema: &DFSchema) -> Result<Expr> {
+    let name = expr.display_name()?;
+    let promote_arg = |args: &mut Vec<Expr>| -> Result<bool> {
+        let arg_type = args.first().map(|arg| arg.get_type(schema));
+        match arg_type.transpose()? {
+            Some(DataType::Decimal128(precision, scale)) => {
+                let arg = args[0].clone();
+                args[0] = arg.cast_to(&DataType::Decimal256(precision, scale), schema)?;
+                Ok(true)
+            }
+            _ => Ok(false),
+        }
+    };
+    let promoted = expr.transform_up(|expr| match expr {
+        Expr::AggregateFunction(mut aggregate)
+            if matches!(
+                aggregate.func_def,
+                AggregateFunctionDefinition::BuiltIn(
+                    AggregateFunction::Sum | AggregateFunction::Avg
+                )
+            ) =>
+        {
+            let promoted = promote_arg(&mut aggregate.args)?;
+            let expr = Expr::AggregateFunction(aggregate);
+            Ok(match promoted {
+                true => Transformed::yes(expr),
+                false => Transformed::no(expr),
+            })
+        }
+        Expr::WindowFunction(mut window)
+            if matches!(
+                window.fun,
+                expr::WindowFunctionDefinition::AggregateFunction(
+                    AggregateFunction::Sum | AggregateFunction::Avg
+                )
+            ) =>
+        {
+            let promoted = promote_arg(&mut window.args)?;
+            let expr = Expr::WindowFunction(window);
+            Ok(match promoted {
+                true => Transformed::yes(expr),
+                false => Transformed::no(expr),
+            })
+        }
+        _ => Ok(Transformed::no(expr)),
+    })?;
+    if promoted.transformed {
+        promoted.data.alias_if_changed(name)
+    } else {
+        Ok(promoted.data)
     }
 }
 
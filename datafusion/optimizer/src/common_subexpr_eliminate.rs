@@ -63,6 +63,19 @@ impl ExprSet {
         self.map.entry(key)
     }
 
+    /// Removes the expressions for which `f` returns `false`
+    fn retain(&mut self, mut f: impl FnMut(&Expr) -> Result<bool>) -> Result<()> {
+        let mut result = Ok(());
+        self.map.retain(|_, (expr, ..)| match f(expr) {
+            Ok(keep) => keep,
+            Err(e) => {
+                result = Err(e);
+                false
+            }
+        });
+        result
+    }
+
     fn populate_expr_set(
         &mut self,
         expr: &[Expr],
@@ -124,8 +137,14 @@ type Identifier = String;
 /// appear more than once and reusing those results rather than re-computing the
 /// same value
 ///
-/// Currently only common sub-expressions within a single `LogicalPlan` are
-/// eliminated.
+/// Common sub-expressions are eliminated within a single `LogicalPlan`, within
+/// a chain of consecutive `Window`s, and across a `Projection` and the chain of
+/// `Window`s below it, e.g. for an expression that is both projected and used
+/// as a `PARTITION BY` key.
+///
+/// Short-circuiting expressions such as `CASE` or `COALESCE` are only
+/// eliminated as a whole: their arguments may not be evaluated for every row,
+/// so they are never extracted on their own.
 ///
 /// # Example
 ///
@@ -201,9 +220,12 @@ impl CommonSubexprEliminate {
         Ok((rewrite_exprs, new_input))
     }
 
+    /// Eliminates common sub-expressions of the chain of consecutive `Window`s
+    /// starting at `window`, and of the `projection` on top of it, if any.
     fn try_optimize_window(
         &self,
         window: &Window,
+        projection: Option<&Projection>,
         config: &dyn OptimizerConfig,
     ) -> Result<LogicalPlan> {
         let mut window_exprs = vec![];
@@ -231,14 +253,45 @@ impl CommonSubexprEliminate {
             window_exprs.push(window_expr);
         }
 
-        let mut window_exprs = window_exprs
+        let mut exprs_list = window_exprs
             .iter()
             .map(|expr| expr.as_slice())
             .collect::<Vec<_>>();
 
+        if let Some(projection) = projection {
+            // The projected expressions are evaluated on top of the window
+            // operators: only the ones that can be computed from the input of
+            // the first window operator are shared with the window expressions.
+            expr_set.populate_expr_set(
+                &projection.expr,
+                Arc::clone(projection.input.schema()),
+                ExprMask::Normal,
+            )?;
+            let input_schema = Arc::clone(plan.schema());
+            expr_set.retain(|expr| {
+                let is_window_expr =
+                    expr.exists(|e| Ok(matches!(e, Expr::WindowFunction(_))))?;
+                Ok(!is_window_expr
+                    && expr
+                        .to_columns()?
+                        .iter()
+                        .all(|c| input_schema.has_column(c)))
+            })?;
+            exprs_list.push(&projection.expr);
+        }
+
         let (mut new_expr, new_input) =
-            self.rewrite_expr(&window_exprs, &plan, &expr_set, config)?;
-        assert_eq!(window_exprs.len(), new_expr.len());
+            self.rewrite_expr(&exprs_list, &plan, &expr_set, config)?;
+        assert_eq!(exprs_list.len(), new_expr.len());
+
+        let new_proj_expr = match projection {
+            Some(_) => {
+                exprs_list.pop();
+                Some(pop_expr(&mut new_expr)?)
+            }
+            None => None,
+        };
+        let mut window_exprs = exprs_list;
 
         // Construct consecutive window operator, with their corresponding new window expressions.
         plan = new_input;
@@ -260,6 +313,33 @@ impl CommonSubexprEliminate {
             plan = LogicalPlan::Window(Window::try_new(new_window_expr, Arc::new(plan))?);
         }
 
+        if let (Some(projection), Some(new_proj_expr)) = (projection, new_proj_expr) {
+            // Eliminate the remaining common sub-expressions of the projection,
+            // which refer to the output of the window operators.
+            let mut expr_set = ExprSet::default();
+            expr_set.populate_expr_set(
+                &new_proj_expr,
+                Arc::clone(plan.schema()),
+                ExprMask::Normal,
+            )?;
+            let mut affected_id = BTreeSet::<Identifier>::new();
+            let mut new_proj_expr =
+                self.rewrite_exprs_list(&[&new_proj_expr], &expr_set, &mut affected_id)?;
+            if !affected_id.is_empty() {
+                plan = build_common_expr_project_plan(plan, affected_id, &expr_set)?;
+            }
+
+            let new_proj_expr = pop_expr(&mut new_proj_expr)?
+                .into_iter()
+                .zip(projection.expr.iter())
+                .map(|(new_expr, expr)| new_expr.alias_if_changed(expr.name_for_alias()?))
+                .collect::<Result<Vec<_>>>()?;
+            plan = LogicalPlan::Projection(Projection::try_new(
+                new_proj_expr,
+                Arc::new(plan),
+            )?);
+        }
+
         Ok(plan)
     }
 
@@ -398,11 +478,17 @@ impl OptimizerRule for CommonSubexprEliminate {
         config: &dyn OptimizerConfig,
     ) -> Result<Option<LogicalPlan>> {
         let optimized_plan = match plan {
-            LogicalPlan::Projection(_)
-            | LogicalPlan::Sort(_)
-            | LogicalPlan::Filter(_) => Some(self.try_unary_plan(plan, config)?),
+            LogicalPlan::Projection(projection) => match projection.input.as_ref() {
+                LogicalPlan::Window(window) => {
+                    Some(self.try_optimize_window(window, Some(projection), config)?)
+                }
+                _ => Some(self.try_unary_plan(plan, config)?),
+            },
+            LogicalPlan::Sort(_) | LogicalPlan::Filter(_) => {
+                Some(self.try_unary_plan(plan, config)?)
+            }
             LogicalPlan::Window(window) => {
-                Some(self.try_optimize_window(window, config)?)
+                Some(self.try_optimize_window(window, None, config)?)
             }
             LogicalPlan::Aggregate(aggregate) => {
                 Some(self.try_optimize_aggregate(aggregate, config)?)
@@ -716,7 +802,7 @@ impl TreeNodeRewriter for CommonSubexprRewriter<'_> {
         // The `CommonSubexprRewriter` relies on `ExprIdentifierVisitor` to generate
         // the `id_array`, which records the expr's identifier used to rewrite expr. So if we
         // skip an expr in `ExprIdentifierVisitor`, we should skip it here, too.
-        if expr.short_circuits() || expr.is_volatile()? {
+        if expr.is_volatile()? {
             return Ok(Transformed::new(expr, false, TreeNodeRecursion::Jump));
         }
 
@@ -739,14 +825,25 @@ impl TreeNodeRewriter for CommonSubexprRewriter<'_> {
                         TreeNodeRecursion::Jump,
                     ))
                 } else {
-                    Ok(Transformed::no(expr))
+                    Ok(skip_short_circuit_args(expr))
                 }
             }
-            None => Ok(Transformed::no(expr)),
+            None => Ok(skip_short_circuit_args(expr)),
         }
     }
 }
 
+/// Short-circuiting expressions may not evaluate all their arguments, so
+/// their sub-expressions are not rewritten (and are not visited by the
+/// [`ExprIdentifierVisitor`] either).
+fn skip_short_circuit_args(expr: Expr) -> Transformed<Expr> {
+    if expr.short_circuits() {
+        Transformed::new(expr, false, TreeNodeRecursion::Jump)
+    } else {
+        Transformed::no(expr)
+    }
+}
+
 /// Replace common sub-expression in `expr` with the corresponding temporary
 /// column name, updating `affected_id` with any replaced expressions
 fn replace_common_expr(
@@ -770,8 +867,9 @@ mod test {
     use datafusion_expr::logical_plan::{table_scan, JoinType};
     use datafusion_expr::{avg, lit, logical_plan::builder::LogicalPlanBuilder, sum};
     use datafusion_expr::{
-        grouping_set, AccumulatorFactoryFunction, AggregateUDF, Signature,
-        SimpleAggregateUDF, Volatility,
+        expr, grouping_set, when, AccumulatorFactoryFunction, AggregateUDF,
+        BuiltInWindowFunction, Signature, SimpleAggregateUDF, Volatility, WindowFrame,
+        WindowFunctionDefinition,
     };
 
     use crate::optimizer::OptimizerContext;
@@ -1028,6 +1126,53 @@ mod test {
         Ok(())
     }
 
+    #[test]
+    fn short_circuit_subexpr() -> Result<()> {
+        let table_scan = test_table_scan()?;
+
+        let case_expr = when(col("a").eq(lit(1)), col("b")).otherwise(col("c"))?;
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .project(vec![case_expr.clone() * lit(2), case_expr + lit(1)])?
+            .build()?;
+
+        let expected = "Projection: CASE WHEN test.a = Int32(1) THEN test.b ELSE test.c END AS CASE WHEN test.a = Int32(1) THEN test.b ELSE test.c END * Int32(2), CASE WHEN test.a = Int32(1) THEN test.b ELSE test.c END AS CASE WHEN test.a = Int32(1) THEN test.b ELSE test.c END + Int32(1)\
+        \n  Projection: CASE WHEN test.a = Int32(1) THEN test.b ELSE test.c END AS CASE WHEN test.a = Int32(1) THEN test.b ELSE test.c END, test.a, test.b, test.c\
+        \n    TableScan: test";
+
+        assert_optimized_plan_eq(expected, &plan);
+        Ok(())
+    }
+
+    #[test]
+    fn projection_and_window_subexpr() -> Result<()> {
+        let table_scan = test_table_scan()?;
+
+        let row_number = Expr::WindowFunction(expr::WindowFunction::new(
+            WindowFunctionDefinition::BuiltInWindowFunction(
+                BuiltInWindowFunction::RowNumber,
+            ),
+            vec![],
+            vec![col("test.a") + col("test.b")],
+            vec![],
+            WindowFrame::new(None),
+            None,
+        ));
+        let row_number_col = col(row_number.display_name()?);
+
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .window(vec![row_number])?
+            .project(vec![col("a") + col("b"), row_number_col])?
+            .build()?;
+
+        let expected = "Projection: test.a + test.btest.btest.a AS test.a + test.b, ROW_NUMBER() PARTITION BY [test.a + test.b] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING\
+        \n  WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [test.a + test.btest.btest.a AS test.a + test.b] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS ROW_NUMBER() PARTITION BY [test.a + test.b] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING]]\
+        \n    Projection: test.a + test.b AS test.a + test.btest.btest.a, test.a, test.b, test.c\
+        \n      TableScan: test";
+
+        assert_optimized_plan_eq(expected, &plan);
+        Ok(())
+    }
+
     #[test]
     fn redundant_project_fields() {
         let table_scan = test_table_scan().unwrap();
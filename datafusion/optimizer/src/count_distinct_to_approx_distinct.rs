@@ -0,0 +1,239 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`CountDistinctToApproxDistinct`] replaces `COUNT(DISTINCT ..)` with `approx_distinct(..)`
+
+use std::sync::Arc;
+
+use crate::optimizer::ApplyOrder;
+use crate::{OptimizerConfig, OptimizerRule};
+
+use arrow::datatypes::DataType;
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::{internal_err, Column, DFSchema, Result};
+use datafusion_expr::expr::{AggregateFunction, AggregateFunctionDefinition};
+use datafusion_expr::logical_plan::{Aggregate, LogicalPlan, Projection};
+use datafusion_expr::{aggregate_function, approx_distinct, cast, Expr, ExprSchemable};
+
+/// Optimizer rule that replaces exact `COUNT(DISTINCT x)` aggregates with
+/// `approx_distinct(x)`, when the `datafusion.optimizer.prefer_approx_distinct`
+/// option is enabled.
+///
+/// ```text
+///    Before:
+///    SELECT a, COUNT(DISTINCT b)
+///    FROM t
+///    GROUP BY a
+///
+///    After:
+///    SELECT a, approx_distinct(b) AS "COUNT(DISTINCT t.b)"
+///    FROM t
+///    GROUP BY a
+///  ```
+///
+/// Instead of keeping every distinct value of each group, `approx_distinct`
+/// keeps a fixed size HyperLogLog sketch, which is merged between partial and
+/// final aggregation. The estimates have the type of `COUNT`, and keep the
+/// names of the original aggregates.
+///
+/// Only the types supported by `approx_distinct` are rewritten.
+#[derive(Default)]
+pub struct CountDistinctToApproxDistinct {}
+
+impl CountDistinctToApproxDistinct {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+/// Returns the argument of `expr` if it is a `COUNT(DISTINCT x)` aggregate
+/// that can be replaced by `approx_distinct(x)`
+fn count_distinct_arg<'a>(expr: &'a Expr, schema: &DFSchema) -> Result<Option<&'a Expr>> {
+    match expr {
+        Expr::AggregateFunction(AggregateFunction {
+            func_def:
+                AggregateFunctionDefinition::BuiltIn(
+                    aggregate_function::AggregateFunction::Count,
+                ),
+            args,
+            distinct: true,
+            filter: None,
+            order_by: None,
+            ..
+        }) if args.len() == 1 => {
+            let supported = matches!(
+                args[0].get_type(schema)?,
+                DataType::Int8
+                    | DataType::Int16
+                    | DataType::Int32
+                    | DataType::Int64
+                    | DataType::UInt8
+                    | DataType::UInt16
+                    | DataType::UInt32
+                    | DataType::UInt64
+                    | DataType::Utf8
+                    | DataType::LargeUtf8
+                    | DataType::Binary
+                    | DataType::LargeBinary
+            );
+            Ok(supported.then_some(&args[0]))
+        }
+        _ => Ok(None),
+    }
+}
+
+impl OptimizerRule for CountDistinctToApproxDistinct {
+    fn try_optimize(
+        &self,
+        _plan: &LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Option<LogicalPlan>> {
+        internal_err!("Should have called CountDistinctToApproxDistinct::rewrite")
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::BottomUp)
+    }
+
+    /// SingleDistinctToGroupBy rewrites the COUNT(DISTINCT ..) aggregates
+    fn run_before(&self) -> Vec<&str> {
+        vec!["single_distinct_aggregation_to_group_by"]
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        if !config.options().optimizer.prefer_approx_distinct {
+            return Ok(Transformed::no(plan));
+        }
+        let LogicalPlan::Aggregate(aggregate) = plan else {
+            return Ok(Transformed::no(plan));
+        };
+
+        let mut rewritten = false;
+        let aggr_expr = aggregate
+            .aggr_expr
+            .iter()
+            .map(|expr| {
+                Ok(match count_distinct_arg(expr, aggregate.input.schema())? {
+                    Some(arg) => {
+                        rewritten = true;
+                        approx_distinct(arg.clone())
+                    }
+                    None => expr.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>>>()?;
+        if !rewritten {
+            return Ok(Transformed::no(LogicalPlan::Aggregate(aggregate)));
+        }
+
+        let new_aggregate = Aggregate::try_new(
+            aggregate.input.clone(),
+            aggregate.group_expr.clone(),
+            aggr_expr,
+        )?;
+        // Restore the names and types of the rewritten aggregates
+        let exprs = aggregate
+            .schema
+            .iter()
+            .zip(new_aggregate.schema.iter())
+            .map(|((qualifier, field), new_column)| {
+                let expr = Expr::Column(Column::from(new_column));
+                if new_column.1.name() == field.name() {
+                    expr
+                } else if new_column.1.data_type() == field.data_type() {
+                    expr.alias_qualified(qualifier.cloned(), field.name())
+                } else {
+                    cast(expr, field.data_type().clone())
+                        .alias_qualified(qualifier.cloned(), field.name())
+                }
+            })
+            .collect();
+        Ok(Transformed::yes(LogicalPlan::Projection(
+            Projection::try_new(exprs, Arc::new(LogicalPlan::Aggregate(new_aggregate)))?,
+        )))
+    }
+
+    fn name(&self) -> &str {
+        "count_distinct_to_approx_distinct"
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+
+    use datafusion_expr::{col, count, count_distinct, LogicalPlanBuilder};
+
+    use crate::optimizer::Optimizer;
+    use crate::OptimizerContext;
+
+    fn optimize(plan: LogicalPlan, prefer_approx_distinct: bool) -> Result<String> {
+        let config = OptimizerContext::new()
+            .with_max_passes(1)
+            .with_prefer_approx_distinct(prefer_approx_distinct);
+        let optimizer =
+            Optimizer::with_rules(vec![Arc::new(CountDistinctToApproxDistinct::new())]);
+        let optimized = optimizer.optimize(plan, &config, |_, _| {})?;
+        Ok(format!("{}", optimized.display_indent_schema()))
+    }
+
+    #[test]
+    fn count_distinct_to_approx_distinct() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(
+                vec![col("a")],
+                vec![count_distinct(col("b")), count(col("c"))],
+            )?
+            .build()?;
+
+        let expected = "Projection: test.a, APPROX_DISTINCT(test.b) AS COUNT(DISTINCT test.b), COUNT(test.c) [a:UInt32, COUNT(DISTINCT test.b):Int64;N, COUNT(test.c):Int64;N]\
+        \n  Aggregate: groupBy=[[test.a]], aggr=[[APPROX_DISTINCT(test.b), COUNT(test.c)]] [a:UInt32, APPROX_DISTINCT(test.b):Int64;N, COUNT(test.c):Int64;N]\
+        \n    TableScan: test [a:UInt32, b:UInt32, c:UInt32]";
+        assert_eq!(optimize(plan.clone(), true)?, expected);
+
+        // The rewrite is disabled by default
+        let expected = "Aggregate: groupBy=[[test.a]], aggr=[[COUNT(DISTINCT test.b), COUNT(test.c)]] [a:UInt32, COUNT(DISTINCT test.b):Int64;N, COUNT(test.c):Int64;N]\
+        \n  TableScan: test [a:UInt32, b:UInt32, c:UInt32]";
+        assert_eq!(optimize(plan, false)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn count_distinct_unsupported_type() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(
+                Vec::<Expr>::new(),
+                vec![count_distinct(cast(col("b"), DataType::Float64))],
+            )?
+            .build()?;
+
+        let expected = "Aggregate: groupBy=[[]], aggr=[[COUNT(DISTINCT CAST(test.b AS Float64))]] [COUNT(DISTINCT test.b):Int64;N]\
+        \n  TableScan: test [a:UInt32, b:UInt32, c:UInt32]";
+        assert_eq!(optimize(plan, true)?, expected);
+        Ok(())
+    }
+}
@@ -30,7 +30,10 @@ use datafusion_common::{plan_err, Column, DFSchemaRef, Result, ScalarValue};
 use datafusion_expr::expr::{AggregateFunctionDefinition, Alias};
 use datafusion_expr::simplify::SimplifyContext;
 use datafusion_expr::utils::{conjunction, find_join_exprs, split_conjunction};
-use datafusion_expr::{expr, EmptyRelation, Expr, LogicalPlan, LogicalPlanBuilder};
+use datafusion_expr::{
+    expr, BinaryExpr, Cast, EmptyRelation, Expr, LogicalPlan, LogicalPlanBuilder,
+    Operator,
+};
 use datafusion_physical_expr::execution_props::ExecutionProps;
 
 /// This struct rewrite the sub query plan by pull up the correlated
@@ -216,11 +219,22 @@ impl TreeNodeRewriter for PullUpCorrelatedExpr {
                     &self.correlated_subquery_cols_map,
                     &mut local_correlated_cols,
                 );
+                // group by the correlated expressions of the input, instead of
+                // by their columns
+                let correlated_group_exprs = self.pull_up_correlated_exprs(
+                    aggregate.input.schema(),
+                    &mut local_correlated_cols,
+                )?;
                 // add missing columns to Aggregation's group expressions
                 let mut missing_exprs = self.collect_missing_exprs(
                     &aggregate.group_expr,
                     &local_correlated_cols,
                 )?;
+                let correlated_group_cols = correlated_group_exprs
+                    .iter()
+                    .map(|expr| Ok(Column::from_name(expr.display_name()?)))
+                    .collect::<Result<BTreeSet<_>>>()?;
+                missing_exprs.extend(correlated_group_exprs);
 
                 // if the original group expressions are empty, need to handle the Count bug
                 let mut expr_result_map_for_count_bug = HashMap::new();
@@ -245,6 +259,10 @@ impl TreeNodeRewriter for PullUpCorrelatedExpr {
                 let new_plan = LogicalPlanBuilder::from((*aggregate.input).clone())
                     .aggregate(missing_exprs, aggregate.aggr_expr.to_vec())?
                     .build()?;
+                if !correlated_group_cols.is_empty() {
+                    self.correlated_subquery_cols_map
+                        .insert(new_plan.clone(), correlated_group_cols);
+                }
                 if !expr_result_map_for_count_bug.is_empty() {
                     self.collected_count_expr_map
                         .insert(new_plan.clone(), expr_result_map_for_count_bug);
@@ -334,6 +352,90 @@ impl PullUpCorrelatedExpr {
         }
         Ok(missing_exprs)
     }
+
+    /// Rewrites the join filters comparing an expression of the columns of
+    /// `schema` with an expression of the outer columns, such as
+    /// `d.s['k'] = t.k`, to compare a column of the expression instead, and
+    /// returns the aliased expressions, which must be grouped by the
+    /// aggregation over `schema`.
+    ///
+    /// Grouping by the columns of the expressions, `d.s`, instead would split
+    /// the rows matching the same outer row into several groups. The columns
+    /// which are no longer used by the join filters are removed from
+    /// `local_correlated_cols`.
+    fn pull_up_correlated_exprs(
+        &mut self,
+        schema: &DFSchemaRef,
+        local_correlated_cols: &mut BTreeSet<Column>,
+    ) -> Result<Vec<Expr>> {
+        let mut group_exprs = vec![];
+        let mut replaced_cols = BTreeSet::new();
+        for (i, join_filter) in self.join_filters.iter_mut().enumerate() {
+            let Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: Operator::Eq,
+                right,
+            }) = join_filter
+            else {
+                continue;
+            };
+            let inner = if is_correlated_inner_expr(left, schema)?
+                && !has_inner_columns(right, schema)?
+            {
+                left
+            } else if is_correlated_inner_expr(right, schema)?
+                && !has_inner_columns(left, schema)?
+            {
+                right
+            } else {
+                continue;
+            };
+            let alias = format!("__correlated_expr_{}", i + 1);
+            replaced_cols.extend(inner.to_columns()?);
+            let expr = std::mem::replace(
+                inner.as_mut(),
+                Expr::Column(Column::from_name(alias.clone())),
+            );
+            group_exprs.push(expr.alias(alias));
+        }
+        if group_exprs.is_empty() {
+            return Ok(group_exprs);
+        }
+
+        // the columns still compared by the join filters are kept
+        for join_filter in &self.join_filters {
+            for col in join_filter.to_columns()? {
+                replaced_cols.remove(&col);
+            }
+        }
+        local_correlated_cols.retain(|col| !replaced_cols.contains(col));
+        for cols in self.correlated_subquery_cols_map.values_mut() {
+            cols.retain(|col| !replaced_cols.contains(col));
+        }
+        Ok(group_exprs)
+    }
+}
+
+/// Returns true if `expr` is a deterministic expression of the columns of
+/// `schema` other than a column or a cast column, which are grouped by as is
+fn is_correlated_inner_expr(expr: &Expr, schema: &DFSchemaRef) -> Result<bool> {
+    if let Expr::Column(_) = expr {
+        return Ok(false);
+    }
+    if let Expr::Cast(Cast { expr, .. }) = expr {
+        if let Expr::Column(_) = expr.as_ref() {
+            return Ok(false);
+        }
+    }
+    let columns = expr.to_columns()?;
+    Ok(!columns.is_empty()
+        && columns.iter().all(|col| schema.has_column(col))
+        && !expr.is_volatile()?)
+}
+
+/// Returns true if `expr` refers to any of the columns of `schema`
+fn has_inner_columns(expr: &Expr, schema: &DFSchemaRef) -> Result<bool> {
+    Ok(expr.to_columns()?.iter().any(|col| schema.has_column(col)))
 }
 
 fn collect_local_correlated_cols(
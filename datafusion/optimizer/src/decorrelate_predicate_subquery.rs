@@ -27,14 +27,14 @@ use crate::{OptimizerConfig, OptimizerRule};
 
 use datafusion_common::alias::AliasGenerator;
 use datafusion_common::tree_node::{TransformedResult, TreeNode};
-use datafusion_common::{plan_err, Result};
+use datafusion_common::{plan_err, DFSchema, Result};
 use datafusion_expr::expr::{Exists, InSubquery};
 use datafusion_expr::expr_rewriter::create_col_from_scalar_expr;
 use datafusion_expr::logical_plan::{JoinType, Subquery};
 use datafusion_expr::utils::{conjunction, split_conjunction};
 use datafusion_expr::{
-    exists, in_subquery, not_exists, not_in_subquery, BinaryExpr, Expr, Filter,
-    LogicalPlan, LogicalPlanBuilder, Operator,
+    exists, in_subquery, not_exists, not_in_subquery, BinaryExpr, Expr, ExprSchemable,
+    Filter, LogicalPlan, LogicalPlanBuilder, Operator,
 };
 
 use log::debug;
@@ -118,9 +118,12 @@ impl OptimizerRule for DecorrelatePredicateSubquery {
                 // iterate through all exists clauses in predicate, turning each into a join
                 let mut cur_input = filter.input.as_ref().clone();
                 for subquery in subqueries {
-                    if let Some(plan) =
-                        build_join(&subquery, &cur_input, config.alias_generator())?
-                    {
+                    if let Some(plan) = build_join(
+                        &subquery,
+                        &cur_input,
+                        config.alias_generator(),
+                        config.options().optimizer.deduplicate_semi_join_build_side,
+                    )? {
                         cur_input = plan;
                     } else {
                         // If the subquery can not be converted to a Join, reconstruct the subquery expression and add it to the Filter
@@ -200,10 +203,25 @@ impl OptimizerRule for DecorrelatePredicateSubquery {
 ///       Projection: t2.id
 ///         TableScan: t2
 /// ```
+///
+/// When `deduplicate_build_side` is true, the output of an uncorrelated `IN`
+/// subquery is deduplicated before it is joined, as only its distinct values
+/// can match:
+///
+/// ```text
+/// Projection: t1.id
+///   LeftSemi Join:  Filter: t1.id = __correlated_sq_1.id
+///     TableScan: t1
+///     SubqueryAlias: __correlated_sq_1
+///       Aggregate: groupBy=[[t2.id]], aggrExprs=[]
+///         Projection: t2.id
+///           TableScan: t2
+/// ```
 fn build_join(
     query_info: &SubqueryInfo,
     left: &LogicalPlan,
     alias: Arc<AliasGenerator>,
+    deduplicate_build_side: bool,
 ) -> Result<Option<LogicalPlan>> {
     let where_in_expr_opt = &query_info.where_in_expr;
     let in_predicate_opt = where_in_expr_opt
@@ -237,6 +255,27 @@ fn build_join(
         return Ok(None);
     }
 
+    let new_plan = if deduplicate_build_side
+        && !query_info.negated
+        && in_predicate_opt.is_some()
+        && pull_up.join_filters.is_empty()
+        && !matches!(
+            new_plan,
+            LogicalPlan::Aggregate(_) | LogicalPlan::Distinct(_)
+        ) {
+        let group_expr = new_plan
+            .schema()
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+        LogicalPlanBuilder::from(new_plan)
+            .aggregate(group_expr, Vec::<Expr>::new())?
+            .build()?
+    } else {
+        new_plan
+    };
+
     let sub_query_alias = LogicalPlanBuilder::from(new_plan)
         .alias(subquery_alias.to_string())?
         .build()?;
@@ -253,6 +292,7 @@ fn build_join(
                 .map(Option::Some)
         })?;
 
+    let outer_schema = left.schema();
     if let Some(join_filter) = match (join_filter_opt, in_predicate_opt) {
         (
             Some(join_filter),
@@ -263,7 +303,13 @@ fn build_join(
             })),
         ) => {
             let right_col = create_col_from_scalar_expr(right.deref(), subquery_alias)?;
-            let in_predicate = Expr::eq(left.deref().clone(), Expr::Column(right_col));
+            let in_predicate = in_subquery_join_predicate(
+                left.deref().clone(),
+                Expr::Column(right_col),
+                query_info.negated,
+                outer_schema,
+                sub_query_alias.schema(),
+            )?;
             Some(in_predicate.and(join_filter))
         }
         (Some(join_filter), _) => Some(join_filter),
@@ -276,7 +322,13 @@ fn build_join(
             })),
         ) => {
             let right_col = create_col_from_scalar_expr(right.deref(), subquery_alias)?;
-            let in_predicate = Expr::eq(left.deref().clone(), Expr::Column(right_col));
+            let in_predicate = in_subquery_join_predicate(
+                left.deref().clone(),
+                Expr::Column(right_col),
+                query_info.negated,
+                outer_schema,
+                sub_query_alias.schema(),
+            )?;
             Some(in_predicate)
         }
         _ => None,
@@ -299,6 +351,30 @@ fn build_join(
     }
 }
 
+/// Builds the join condition of `in_expr [NOT] IN (subquery)`.
+///
+/// `NOT IN` is not true for a row whose value is `null` unless the subquery is
+/// empty, nor for any row once the subquery returns a `null`. The anti join
+/// rejects those rows by treating them as matches.
+fn in_subquery_join_predicate(
+    in_expr: Expr,
+    subquery_expr: Expr,
+    negated: bool,
+    outer_schema: &DFSchema,
+    subquery_schema: &DFSchema,
+) -> Result<Expr> {
+    let mut predicate = in_expr.clone().eq(subquery_expr.clone());
+    if negated {
+        if in_expr.nullable(outer_schema)? {
+            predicate = predicate.or(in_expr.is_null());
+        }
+        if subquery_expr.nullable(subquery_schema)? {
+            predicate = predicate.or(subquery_expr.is_null());
+        }
+    }
+    Ok(predicate)
+}
+
 struct SubqueryInfo {
     query: Subquery,
     where_in_expr: Option<Expr>,
@@ -330,7 +406,7 @@ mod tests {
     use super::*;
     use crate::test::*;
 
-    use arrow::datatypes::DataType;
+    use arrow::datatypes::{DataType, Field, Schema};
     use datafusion_expr::{and, binary_expr, col, lit, or, out_ref_col};
 
     fn assert_optimized_plan_equal(plan: LogicalPlan, expected: &str) -> Result<()> {
@@ -1031,6 +1107,39 @@ mod tests {
         Ok(())
     }
 
+    /// Test for deduplicating the build side of an uncorrelated IN subquery
+    #[test]
+    fn in_subquery_deduplicate_build_side() -> Result<()> {
+        let table_scan = test_table_scan()?;
+        let subquery = Subquery {
+            subquery: test_subquery_with_name("sq")?,
+            outer_ref_columns: vec![],
+        };
+        let query_info = SubqueryInfo::new_with_in_expr(subquery, col("test.c"), false);
+        let alias = Arc::new(AliasGenerator::new());
+
+        let plan = build_join(&query_info, &table_scan, alias.clone(), true)?.unwrap();
+        let expected = "LeftSemi Join:  Filter: test.c = __correlated_sq_1.c [a:UInt32, b:UInt32, c:UInt32]\
+        \n  TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n  SubqueryAlias: __correlated_sq_1 [c:UInt32]\
+        \n    Aggregate: groupBy=[[sq.c]], aggr=[[]] [c:UInt32]\
+        \n      Projection: sq.c [c:UInt32]\
+        \n        TableScan: sq [a:UInt32, b:UInt32, c:UInt32]";
+        assert_eq!(plan.display_indent_schema().to_string(), expected);
+
+        // NOT IN subqueries are left unchanged
+        let query_info =
+            SubqueryInfo::new_with_in_expr(query_info.query.clone(), col("test.c"), true);
+        let plan = build_join(&query_info, &table_scan, alias, true)?.unwrap();
+        let expected = "LeftAnti Join:  Filter: test.c = __correlated_sq_2.c [a:UInt32, b:UInt32, c:UInt32]\
+        \n  TableScan: test [a:UInt32, b:UInt32, c:UInt32]\
+        \n  SubqueryAlias: __correlated_sq_2 [c:UInt32]\
+        \n    Projection: sq.c [c:UInt32]\
+        \n      TableScan: sq [a:UInt32, b:UInt32, c:UInt32]";
+        assert_eq!(plan.display_indent_schema().to_string(), expected);
+        Ok(())
+    }
+
     /// Test for single IN subquery filter
     #[test]
     fn in_subquery_simple() -> Result<()> {
@@ -1079,6 +1188,39 @@ mod tests {
         Ok(())
     }
 
+    /// Test for NOT IN subquery filter over nullable columns
+    #[test]
+    fn not_in_subquery_nullable() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::UInt32, true),
+            Field::new("b", DataType::UInt32, false),
+        ]);
+        let table_scan = scan_empty(Some("t"), &schema, None)?.build()?;
+        let subquery =
+            LogicalPlanBuilder::from(scan_empty(Some("sq"), &schema, None)?.build()?)
+                .project(vec![col("a")])?
+                .build()?;
+        let plan = LogicalPlanBuilder::from(table_scan)
+            .filter(not_in_subquery(col("b"), Arc::new(subquery)))?
+            .project(vec![col("t.b")])?
+            .build()?;
+
+        // the non-nullable outer value needs no IS NULL test
+        let expected = "Projection: t.b [b:UInt32]\
+        \n  LeftAnti Join:  Filter: t.b = __correlated_sq_1.a OR __correlated_sq_1.a IS NULL [a:UInt32;N, b:UInt32]\
+        \n    TableScan: t [a:UInt32;N, b:UInt32]\
+        \n    SubqueryAlias: __correlated_sq_1 [a:UInt32;N]\
+        \n      Projection: sq.a [a:UInt32;N]\
+        \n        TableScan: sq [a:UInt32;N, b:UInt32]";
+
+        assert_optimized_plan_eq_display_indent(
+            Arc::new(DecorrelatePredicateSubquery::new()),
+            plan,
+            expected,
+        );
+        Ok(())
+    }
+
     #[test]
     fn in_subquery_both_side_expr() -> Result<()> {
         let table_scan = test_table_scan()?;
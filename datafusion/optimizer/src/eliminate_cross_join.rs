@@ -158,6 +158,10 @@ fn try_flatten_join_inputs(
                 // issue: https://github.com/apache/datafusion/issues/4844
                 return Ok(false);
             }
+            if join.null_equals_null {
+                // The rebuilt joins would not match the null keys
+                return Ok(false);
+            }
             possible_join_keys.extend(join.on.clone());
             let left = &*(join.left);
             let right = &*(join.right);
@@ -564,6 +568,28 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn eliminate_cross_not_possible_null_equals_null_join() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        // could not eliminate to inner join matching null keys
+        let plan = LogicalPlanBuilder::from(t1)
+            .join_detailed(
+                t2,
+                JoinType::Inner,
+                (vec!["t1.a"], vec!["t2.a"]),
+                None,
+                true,
+            )?
+            .filter(col("t1.b").gt(lit(15u32)))?
+            .build()?;
+
+        assert_optimization_rule_fails(&plan);
+
+        Ok(())
+    }
+
     #[test]
     /// ```txt
     /// filter: a.id = b.id and a.id = c.id
@@ -46,6 +46,10 @@ impl OptimizerRule for EliminateOneUnion {
         "eliminate_one_union"
     }
 
+    fn run_after(&self) -> Vec<&str> {
+        vec!["propagate_empty_relation"]
+    }
+
     fn supports_rewrite(&self) -> bool {
         true
     }
@@ -0,0 +1,246 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`EliminateUniqueGroupBy`] replaces the aggregations grouping on a unique
+//! key without aggregate functions with projections
+
+use crate::optimizer::ApplyOrder;
+use crate::{OptimizerConfig, OptimizerRule};
+
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::{internal_err, Result};
+use datafusion_expr::logical_plan::{Aggregate, LogicalPlan, Projection};
+use datafusion_expr::Expr;
+
+/// Replaces an `Aggregate` without aggregate functions, e.g. of a `DISTINCT`,
+/// whose group by expressions include a unique key of its input with a
+/// `Projection` of the group by expressions, as each of its groups is a single
+/// row of its input:
+///
+/// ```text
+/// Aggregate: groupBy=[[t.id, t.name]], aggr=[[]]
+///   TableScan: t
+/// ```
+///
+/// becomes
+///
+/// ```text
+/// Projection: t.id, t.name
+///   TableScan: t
+/// ```
+///
+/// The unique keys are the `PRIMARY KEY`s of the tables, propagated through
+/// the plan by the functional dependencies of the schemas. As they are not
+/// enforced, the rule only applies with
+/// `datafusion.optimizer.eliminate_unique_group_by`.
+#[derive(Default)]
+pub struct EliminateUniqueGroupBy {}
+
+impl EliminateUniqueGroupBy {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for EliminateUniqueGroupBy {
+    fn try_optimize(
+        &self,
+        _plan: &LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Option<LogicalPlan>> {
+        internal_err!("Should have called EliminateUniqueGroupBy::rewrite")
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::BottomUp)
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        if !config.options().optimizer.eliminate_unique_group_by {
+            return Ok(Transformed::no(plan));
+        }
+        match plan {
+            LogicalPlan::Aggregate(aggregate)
+                if aggregate.aggr_expr.is_empty() && groups_unique_key(&aggregate) =>
+            {
+                let projection =
+                    Projection::try_new(aggregate.group_expr, aggregate.input)?;
+                Ok(Transformed::yes(LogicalPlan::Projection(projection)))
+            }
+            _ => Ok(Transformed::no(plan)),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "eliminate_unique_group_by"
+    }
+}
+
+/// Returns true if the group by expressions of `aggregate` include columns
+/// which are a unique key of its input
+fn groups_unique_key(aggregate: &Aggregate) -> bool {
+    let schema = aggregate.input.schema();
+    let mut indices = vec![];
+    for expr in &aggregate.group_expr {
+        match expr {
+            // The rows of a grouping set are in several groups
+            Expr::GroupingSet(_) => return false,
+            Expr::Column(column) => indices.extend(schema.maybe_index_of_column(column)),
+            _ => {}
+        }
+    }
+    !indices.is_empty() && schema.functional_dependencies().is_unique_key(&indices)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::any::Any;
+    use std::sync::Arc;
+
+    use super::*;
+    use crate::optimizer::Optimizer;
+    use crate::OptimizerContext;
+
+    use arrow::datatypes::{DataType, Field, Schema, SchemaRef};
+    use datafusion_common::{Constraint, Constraints};
+    use datafusion_expr::{col, JoinType, LogicalPlanBuilder, TableSource};
+
+    /// A table `t(id, name, ref_id)` whose primary key is `id`
+    struct PrimaryKeyTable {
+        schema: SchemaRef,
+        constraints: Constraints,
+    }
+
+    impl TableSource for PrimaryKeyTable {
+        fn as_any(&self) -> &dyn Any {
+            self
+        }
+
+        fn schema(&self) -> SchemaRef {
+            self.schema.clone()
+        }
+
+        fn constraints(&self) -> Option<&Constraints> {
+            Some(&self.constraints)
+        }
+    }
+
+    fn scan(name: &str) -> Result<LogicalPlanBuilder> {
+        let table = PrimaryKeyTable {
+            schema: Arc::new(Schema::new(vec![
+                Field::new("id", DataType::Int32, false),
+                Field::new("name", DataType::Utf8, true),
+                Field::new("ref_id", DataType::Int32, true),
+            ])),
+            constraints: Constraints::new_unverified(vec![Constraint::PrimaryKey(vec![
+                0,
+            ])]),
+        };
+        LogicalPlanBuilder::scan(name, Arc::new(table), None)
+    }
+
+    fn optimize(plan: LogicalPlan, eliminate_unique_group_by: bool) -> Result<String> {
+        let config = OptimizerContext::new()
+            .with_max_passes(1)
+            .with_eliminate_unique_group_by(eliminate_unique_group_by);
+        let optimizer =
+            Optimizer::with_rules(vec![Arc::new(EliminateUniqueGroupBy::new())]);
+        let optimized = optimizer.optimize(plan, &config, |_, _| {})?;
+        Ok(format!("{optimized:?}"))
+    }
+
+    fn assert_optimized_plan_equal(plan: LogicalPlan, expected: &str) -> Result<()> {
+        assert_eq!(optimize(plan, true)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn eliminate_group_by_primary_key() -> Result<()> {
+        let plan = scan("t")?
+            .aggregate(vec![col("name"), col("id")], Vec::<Expr>::new())?
+            .build()?;
+        // The builder groups by the columns depending on the primary key too
+        let expected = "Projection: t.name, t.id, t.ref_id\
+        \n  TableScan: t";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn keep_group_by_primary_key_by_default() -> Result<()> {
+        let plan = scan("t")?
+            .aggregate(vec![col("name"), col("id")], Vec::<Expr>::new())?
+            .build()?;
+        let expected = "Aggregate: groupBy=[[t.name, t.id, t.ref_id]], aggr=[[]]\
+        \n  TableScan: t";
+        assert_eq!(optimize(plan, false)?, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn keep_group_by_non_unique_columns() -> Result<()> {
+        let plan = scan("t")?
+            .aggregate(vec![col("name")], Vec::<Expr>::new())?
+            .build()?;
+        let expected = "Aggregate: groupBy=[[t.name]], aggr=[[]]\
+        \n  TableScan: t";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn eliminate_group_by_primary_key_through_join() -> Result<()> {
+        // Each row of `l` matches at most one row of `r` on its primary key
+        let plan = scan("l")?
+            .join(
+                scan("r")?.build()?,
+                JoinType::Inner,
+                (vec!["l.ref_id"], vec!["r.id"]),
+                None,
+            )?
+            .aggregate(vec![col("l.id"), col("r.name")], Vec::<Expr>::new())?
+            .build()?;
+        let expected = "Projection: l.id, r.name, l.name, l.ref_id, r.id, r.ref_id\
+        \n  Inner Join: l.ref_id = r.id\
+        \n    TableScan: l\
+        \n    TableScan: r";
+        assert_optimized_plan_equal(plan, expected)?;
+
+        // The rows of `r` may match multiple rows of `l`
+        let plan = scan("l")?
+            .join(
+                scan("r")?.build()?,
+                JoinType::Inner,
+                (vec!["l.ref_id"], vec!["r.id"]),
+                None,
+            )?
+            .aggregate(vec![col("r.id")], Vec::<Expr>::new())?
+            .build()?;
+        let expected = "Aggregate: groupBy=[[r.id, r.name, r.ref_id]], aggr=[[]]\
+        \n  Inner Join: l.ref_id = r.id\
+        \n    TableScan: l\
+        \n    TableScan: r";
+        assert_optimized_plan_equal(plan, expected)
+    }
+}
@@ -23,7 +23,10 @@ use datafusion_common::Result;
 use datafusion_common::{internal_err, DFSchema};
 use datafusion_expr::utils::split_conjunction_owned;
 use datafusion_expr::utils::{can_hash, find_valid_equijoin_key_pair};
-use datafusion_expr::{BinaryExpr, Expr, ExprSchemable, Join, LogicalPlan, Operator};
+use datafusion_expr::{
+    build_join_schema_with_keys, BinaryExpr, Expr, ExprSchemable, Join, LogicalPlan,
+    Operator,
+};
 use std::sync::Arc;
 // equijoin predicate
 type EquijoinPredicate = (Expr, Expr);
@@ -87,12 +90,11 @@ impl OptimizerRule for ExtractEquijoinPredicate {
             }) => {
                 let left_schema = left.schema();
                 let right_schema = right.schema();
-                let (equijoin_predicates, non_equijoin_expr) =
+                let (equijoin_predicates, null_aware_predicates, non_equijoin_expr) =
                     split_eq_and_noneq_join_predicate(expr, left_schema, right_schema)?;
 
-                if !equijoin_predicates.is_empty() {
-                    on.extend(equijoin_predicates);
-                    Ok(Transformed::yes(LogicalPlan::Join(Join {
+                if equijoin_predicates.is_empty() && null_aware_predicates.is_empty() {
+                    return Ok(Transformed::no(LogicalPlan::Join(Join {
                         left,
                         right,
                         on,
@@ -101,39 +103,74 @@ impl OptimizerRule for ExtractEquijoinPredicate {
                         join_constraint,
                         schema,
                         null_equals_null,
-                    })))
-                } else {
-                    Ok(Transformed::no(LogicalPlan::Join(Join {
-                        left,
-                        right,
-                        on,
-                        filter: non_equijoin_expr,
-                        join_type,
-                        join_constraint,
-                        schema,
-                        null_equals_null,
-                    })))
+                    })));
+                }
+
+                // The join compares all of its keys with the same null semantics,
+                // so once a `IS NOT DISTINCT FROM` key is added the `=` keys are
+                // guarded against matching nulls by a filter instead
+                let mut guarded_keys = vec![];
+                if !null_equals_null && !null_aware_predicates.is_empty() {
+                    guarded_keys.extend(on.iter());
+                }
+                if null_equals_null || !null_aware_predicates.is_empty() {
+                    guarded_keys.extend(equijoin_predicates.iter());
                 }
+                let mut guards = vec![];
+                for (left_expr, _) in guarded_keys {
+                    if left_expr.nullable(left_schema)? {
+                        guards.push(left_expr.clone().is_not_null());
+                    }
+                }
+                let filter = non_equijoin_expr
+                    .into_iter()
+                    .chain(guards)
+                    .reduce(Expr::and);
+
+                let null_equals_null =
+                    null_equals_null || !null_aware_predicates.is_empty();
+                on.extend(equijoin_predicates);
+                on.extend(null_aware_predicates);
+                // The unique keys of the join may change with its keys
+                let schema = Arc::new(build_join_schema_with_keys(
+                    left_schema,
+                    right_schema,
+                    &join_type,
+                    &on,
+                )?);
+                Ok(Transformed::yes(LogicalPlan::Join(Join {
+                    left,
+                    right,
+                    on,
+                    filter,
+                    join_type,
+                    join_constraint,
+                    schema,
+                    null_equals_null,
+                })))
             }
             _ => Ok(Transformed::no(plan)),
         }
     }
 }
 
+/// Splits a join filter into the `=` join keys, the `IS NOT DISTINCT FROM`
+/// join keys and the remaining predicates
 fn split_eq_and_noneq_join_predicate(
     filter: Expr,
     left_schema: &Arc<DFSchema>,
     right_schema: &Arc<DFSchema>,
-) -> Result<(Vec<EquijoinPredicate>, Option<Expr>)> {
+) -> Result<(Vec<EquijoinPredicate>, Vec<EquijoinPredicate>, Option<Expr>)> {
     let exprs = split_conjunction_owned(filter);
 
     let mut accum_join_keys: Vec<(Expr, Expr)> = vec![];
+    let mut accum_null_aware_join_keys: Vec<(Expr, Expr)> = vec![];
     let mut accum_filters: Vec<Expr> = vec![];
     for expr in exprs {
         match expr {
             Expr::BinaryExpr(BinaryExpr {
                 ref left,
-                op: Operator::Eq,
+                op: op @ (Operator::Eq | Operator::IsNotDistinctFrom),
                 ref right,
             }) => {
                 let join_key_pair = find_valid_equijoin_key_pair(
@@ -147,10 +184,12 @@ fn split_eq_and_noneq_join_predicate(
                     let left_expr_type = left_expr.get_type(left_schema)?;
                     let right_expr_type = right_expr.get_type(right_schema)?;
 
-                    if can_hash(&left_expr_type) && can_hash(&right_expr_type) {
+                    if !can_hash(&left_expr_type) || !can_hash(&right_expr_type) {
+                        accum_filters.push(expr);
+                    } else if op == Operator::Eq {
                         accum_join_keys.push((left_expr, right_expr));
                     } else {
-                        accum_filters.push(expr);
+                        accum_null_aware_join_keys.push((left_expr, right_expr));
                     }
                 } else {
                     accum_filters.push(expr);
@@ -161,16 +200,17 @@ fn split_eq_and_noneq_join_predicate(
     }
 
     let result_filter = accum_filters.into_iter().reduce(Expr::and);
-    Ok((accum_join_keys, result_filter))
+    Ok((accum_join_keys, accum_null_aware_join_keys, result_filter))
 }
 
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::test::*;
-    use arrow::datatypes::DataType;
+    use crate::OptimizerContext;
+    use arrow::datatypes::{DataType, Field, Schema};
     use datafusion_expr::{
-        col, lit, logical_plan::builder::LogicalPlanBuilder, JoinType,
+        binary_expr, col, lit, logical_plan::builder::LogicalPlanBuilder, JoinType,
     };
 
     fn assert_plan_eq(plan: LogicalPlan, expected: &str) -> Result<()> {
@@ -386,4 +426,68 @@ mod tests {
 
         assert_plan_eq(plan, expected)
     }
+
+    fn extract(plan: LogicalPlan) -> Result<Join> {
+        let optimized = ExtractEquijoinPredicate::new()
+            .rewrite(plan, &OptimizerContext::new())?
+            .data;
+        match optimized {
+            LogicalPlan::Join(join) => Ok(join),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn join_with_only_null_aware_predicate() -> Result<()> {
+        let t1 = test_table_scan_with_name("t1")?;
+        let t2 = test_table_scan_with_name("t2")?;
+
+        let plan = LogicalPlanBuilder::from(t1)
+            .join_on(
+                t2,
+                JoinType::Inner,
+                Some(binary_expr(
+                    col("t1.a"),
+                    Operator::IsNotDistinctFrom,
+                    col("t2.a"),
+                )),
+            )?
+            .build()?;
+        let expected = "Inner Join: t1.a = t2.a [a:UInt32, b:UInt32, c:UInt32, a:UInt32, b:UInt32, c:UInt32]\
+            \n  TableScan: t1 [a:UInt32, b:UInt32, c:UInt32]\
+            \n  TableScan: t2 [a:UInt32, b:UInt32, c:UInt32]";
+        assert_plan_eq(plan.clone(), expected)?;
+
+        assert!(extract(plan)?.null_equals_null);
+        Ok(())
+    }
+
+    #[test]
+    fn join_with_eq_and_null_aware_predicates() -> Result<()> {
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::UInt32, true),
+            Field::new("b", DataType::UInt32, true),
+        ]);
+        let t1 = scan_empty(Some("t1"), &schema, None)?.build()?;
+        let t2 = scan_empty(Some("t2"), &schema, None)?.build()?;
+
+        let plan = LogicalPlanBuilder::from(t1)
+            .join_on(
+                t2,
+                JoinType::Left,
+                Some(col("t1.a").eq(col("t2.a")).and(binary_expr(
+                    col("t1.b"),
+                    Operator::IsNotDistinctFrom,
+                    col("t2.b"),
+                ))),
+            )?
+            .build()?;
+        let expected = "Left Join: t1.a = t2.a, t1.b = t2.b Filter: t1.a IS NOT NULL [a:UInt32;N, b:UInt32;N, a:UInt32;N, b:UInt32;N]\
+            \n  TableScan: t1 [a:UInt32;N, b:UInt32;N]\
+            \n  TableScan: t2 [a:UInt32;N, b:UInt32;N]";
+        assert_plan_eq(plan.clone(), expected)?;
+
+        assert!(extract(plan)?.null_equals_null);
+        Ok(())
+    }
 }
@@ -30,6 +30,7 @@
 //! [`TypeCoercion`]: analyzer::type_coercion::TypeCoercion
 pub mod analyzer;
 pub mod common_subexpr_eliminate;
+pub mod count_distinct_to_approx_distinct;
 pub mod decorrelate;
 pub mod decorrelate_predicate_subquery;
 pub mod eliminate_cross_join;
@@ -40,8 +41,11 @@ pub mod eliminate_limit;
 pub mod eliminate_nested_union;
 pub mod eliminate_one_union;
 pub mod eliminate_outer_join;
+pub mod eliminate_unique_group_by;
 pub mod extract_equijoin_predicate;
 pub mod filter_null_join_keys;
+pub mod merge_union_scans;
+pub mod normalize_join_keys;
 pub mod optimize_projections;
 pub mod optimizer;
 pub mod propagate_empty_relation;
@@ -59,7 +63,9 @@ pub mod utils;
 pub mod test;
 
 pub use analyzer::{Analyzer, AnalyzerRule};
-pub use optimizer::{Optimizer, OptimizerConfig, OptimizerContext, OptimizerRule};
+pub use optimizer::{
+    Optimizer, OptimizerConfig, OptimizerContext, OptimizerRule, OptimizerRuleRun,
+};
 pub use utils::optimize_children;
 
 mod plan_signature;
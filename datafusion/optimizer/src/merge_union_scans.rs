@@ -0,0 +1,304 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`MergeUnionScans`] merges the inputs of a `UNION ALL` scanning the same
+//! table with the same filters into a single scan
+
+use std::sync::Arc;
+
+use crate::optimizer::ApplyOrder;
+use crate::{OptimizerConfig, OptimizerRule};
+
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::{internal_err, Column, DFSchema, Result};
+use datafusion_expr::expr::Case;
+use datafusion_expr::logical_plan::{
+    Filter, LogicalPlan, LogicalPlanBuilder, Projection, TableScan, Union,
+};
+use datafusion_expr::{col, lit, Expr, ExprSchemable};
+
+/// The name of the column numbering the inputs of a merged union
+const BRANCH_COLUMN: &str = "__union_branch";
+
+/// Merges the inputs of a `UNION ALL` which project the same filtered table
+/// scan, even with different projections, into a single scan. Each row of the
+/// scan is output once per input, with the projection of the input, by a
+/// cross join with the numbers of the inputs:
+///
+/// ```text
+/// Union
+///   Projection: t.a
+///     Filter: t.c > 1
+///       TableScan: t
+///   Projection: t.b AS a
+///     Filter: t.c > 1
+///       TableScan: t
+/// ```
+///
+/// becomes
+///
+/// ```text
+/// Projection: CASE __union_branch WHEN 0 THEN t.a ELSE t.b END AS a
+///   CrossJoin:
+///     Projection: column1 AS __union_branch
+///       Values: (0), (1)
+///     Filter: t.c > 1
+///       TableScan: t
+/// ```
+///
+/// The inputs with volatile filters are not merged.
+#[derive(Default)]
+pub struct MergeUnionScans {}
+
+impl MergeUnionScans {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for MergeUnionScans {
+    fn try_optimize(
+        &self,
+        _plan: &LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Option<LogicalPlan>> {
+        internal_err!("Should have called MergeUnionScans::rewrite")
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::BottomUp)
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        if !config.options().optimizer.merge_union_scans {
+            return Ok(Transformed::no(plan));
+        }
+        let LogicalPlan::Union(union) = plan else {
+            return Ok(Transformed::no(plan));
+        };
+
+        // The inputs scanning a table, grouped by their scan, with their
+        // projection of the scan
+        let mut groups: Vec<(LogicalPlan, Vec<(&Arc<LogicalPlan>, Vec<Expr>)>)> = vec![];
+        let mut other_inputs = vec![];
+        for input in &union.inputs {
+            let Some((exprs, scan)) = split_scan_projection(input)? else {
+                other_inputs.push(input.clone());
+                continue;
+            };
+            match groups
+                .iter_mut()
+                .find(|(group_scan, _)| *group_scan == scan)
+            {
+                Some((_, inputs)) => inputs.push((input, exprs)),
+                None => groups.push((scan, vec![(input, exprs)])),
+            }
+        }
+        if groups.iter().all(|(_, inputs)| inputs.len() < 2) {
+            return Ok(Transformed::no(LogicalPlan::Union(union)));
+        }
+
+        let mut inputs = vec![];
+        for (scan, group_inputs) in groups {
+            if let [(input, _)] = group_inputs.as_slice() {
+                inputs.push(Arc::clone(input));
+                continue;
+            }
+            let exprs = group_inputs.into_iter().map(|(_, exprs)| exprs).collect();
+            inputs.push(Arc::new(merge_projections(scan, exprs, &union.schema)?));
+        }
+        inputs.extend(other_inputs);
+
+        if inputs.len() == 1 {
+            return Ok(Transformed::yes(inputs[0].as_ref().clone()));
+        }
+        Ok(Transformed::yes(LogicalPlan::Union(Union {
+            inputs,
+            schema: union.schema.clone(),
+        })))
+    }
+
+    fn name(&self) -> &str {
+        "merge_union_scans"
+    }
+}
+
+/// Splits `input`, an input of a union, into its projection and the filtered
+/// table scan it projects, the scan reading all the columns of the table.
+/// Returns `None` if `input` does not project a filtered table scan, or if the
+/// filters are volatile.
+fn split_scan_projection(
+    input: &LogicalPlan,
+) -> Result<Option<(Vec<Expr>, LogicalPlan)>> {
+    let (exprs, scan) = match input {
+        LogicalPlan::Projection(projection) => (
+            projection
+                .expr
+                .iter()
+                .map(|expr| expr.clone().unalias())
+                .collect(),
+            projection.input.as_ref(),
+        ),
+        _ => (
+            input
+                .schema()
+                .columns()
+                .into_iter()
+                .map(Expr::Column)
+                .collect(),
+            input,
+        ),
+    };
+    Ok(full_scan(scan)?.map(|scan| (exprs, scan)))
+}
+
+/// Returns `plan`, filters over a table scan, with the scan reading all the
+/// columns of the table, or `None` if `plan` is not such a plan or if the
+/// filters are volatile
+fn full_scan(plan: &LogicalPlan) -> Result<Option<LogicalPlan>> {
+    match plan {
+        LogicalPlan::Filter(filter) => {
+            if filter.predicate.is_volatile()? {
+                return Ok(None);
+            }
+            let Some(input) = full_scan(&filter.input)? else {
+                return Ok(None);
+            };
+            let filter = Filter::try_new(filter.predicate.clone(), Arc::new(input))?;
+            Ok(Some(LogicalPlan::Filter(filter)))
+        }
+        LogicalPlan::TableScan(scan) if scan.fetch.is_none() => {
+            for filter in &scan.filters {
+                if filter.is_volatile()? {
+                    return Ok(None);
+                }
+            }
+            let scan = TableScan::try_new(
+                scan.table_name.clone(),
+                scan.source.clone(),
+                None,
+                scan.filters.clone(),
+                None,
+            )?;
+            Ok(Some(LogicalPlan::TableScan(scan)))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// Returns the projections `exprs` of `scan`, one per input of a union whose
+/// schema is `schema`, as a single projection of `scan` cross joined with the
+/// numbers of the inputs
+fn merge_projections(
+    scan: LogicalPlan,
+    exprs: Vec<Vec<Expr>>,
+    schema: &DFSchema,
+) -> Result<LogicalPlan> {
+    let branches = (0..exprs.len())
+        .map(|branch| vec![lit(branch as i64)])
+        .collect();
+    let input = LogicalPlanBuilder::values(branches)?
+        .project(vec![col("column1").alias(BRANCH_COLUMN)])?
+        .cross_join(scan)?
+        .build()?;
+
+    let branch = Expr::Column(Column::from_name(BRANCH_COLUMN));
+    let projection = schema
+        .iter()
+        .enumerate()
+        .map(|(i, (qualifier, field))| {
+            let mut when_then = exprs
+                .iter()
+                .enumerate()
+                .map(|(branch, exprs)| {
+                    let then = exprs[i]
+                        .clone()
+                        .cast_to(field.data_type(), input.schema())?;
+                    Ok((Box::new(lit(branch as i64)), Box::new(then)))
+                })
+                .collect::<Result<Vec<_>>>()?;
+            let (_, else_expr) = when_then.pop().unwrap();
+            let case =
+                Case::new(Some(Box::new(branch.clone())), when_then, Some(else_expr));
+            Ok(Expr::Case(case).alias_qualified(qualifier.cloned(), field.name()))
+        })
+        .collect::<Result<Vec<_>>>()?;
+    Ok(LogicalPlan::Projection(Projection::try_new(
+        projection,
+        Arc::new(input),
+    )?))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::*;
+
+    use crate::optimizer::Optimizer;
+    use crate::OptimizerContext;
+
+    fn optimize(plan: LogicalPlan) -> Result<String> {
+        let config = OptimizerContext::new()
+            .with_max_passes(1)
+            .with_merge_union_scans(true);
+        let optimizer = Optimizer::with_rules(vec![Arc::new(MergeUnionScans::new())]);
+        let optimized = optimizer.optimize(plan, &config, |_, _| {})?;
+        Ok(format!("{}", optimized.display_indent()))
+    }
+
+    fn filtered_scan(predicate: Expr) -> Result<LogicalPlanBuilder> {
+        LogicalPlanBuilder::from(test_table_scan()?).filter(predicate)
+    }
+
+    #[test]
+    fn merge_union_scans() -> Result<()> {
+        let plan = filtered_scan(col("a").gt(lit(1u32)))?
+            .project(vec![col("b")])?
+            .union(
+                filtered_scan(col("a").gt(lit(1u32)))?
+                    .project(vec![col("c")])?
+                    .build()?,
+            )?
+            .union(
+                filtered_scan(col("a").gt(lit(2u32)))?
+                    .project(vec![col("a")])?
+                    .build()?,
+            )?
+            .build()?;
+        let expected = "Union\
+        \n  Projection: CASE __union_branch WHEN Int64(0) THEN test.b ELSE test.c END AS b\
+        \n    CrossJoin:\
+        \n      Projection: column1 AS __union_branch\
+        \n        Values: (Int64(0)), (Int64(1))\
+        \n      Filter: test.a > UInt32(1)\
+        \n        TableScan: test\
+        \n  Projection: test.a AS b\
+        \n    Filter: test.a > UInt32(2)\
+        \n      TableScan: test";
+        assert_eq!(optimize(plan)?, expected);
+        Ok(())
+    }
+}
@@ -0,0 +1,252 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`NormalizeJoinKeys`] removes casts that only change the dictionary encoding
+//! of equijoin keys
+
+use crate::optimizer::ApplyOrder;
+use crate::{OptimizerConfig, OptimizerRule};
+use arrow::datatypes::DataType;
+use datafusion_common::tree_node::Transformed;
+use datafusion_common::{internal_err, DFSchema, Result};
+use datafusion_expr::{Cast, Expr, ExprSchemable, LogicalPlan};
+
+/// The NormalizeJoinKeys rule removes casts from equijoin keys when the two
+/// sides of a key only differ in their dictionary encoding.
+///
+/// Type coercion casts both sides of a comparison to a common type, so joining
+/// a `Dictionary(Int32, Utf8)` column with a `Utf8` expression (for example a
+/// nested struct field) casts the `Utf8` side to a dictionary for every probed
+/// batch, and joining two dictionaries with different key types unpacks both
+/// of them. Neither cast is needed by the hash join: a dictionary array hashes
+/// to the same values as its plain representation, and keys are compared on
+/// their dictionary values. Without the casts each side keeps its encoding and
+/// dictionary keys are hashed directly.
+///
+/// Any other non-column key, such as a widening numeric cast, is left in place
+/// and evaluated once per input by the projection the physical planner adds
+/// below the join.
+///
+/// Sort merge joins can only compare keys of the same type, so this rule does
+/// nothing unless `datafusion.optimizer.prefer_hash_join` is enabled.
+#[derive(Default)]
+pub struct NormalizeJoinKeys {}
+
+impl NormalizeJoinKeys {
+    #[allow(missing_docs)]
+    pub fn new() -> Self {
+        Self {}
+    }
+}
+
+impl OptimizerRule for NormalizeJoinKeys {
+    fn try_optimize(
+        &self,
+        _plan: &LogicalPlan,
+        _config: &dyn OptimizerConfig,
+    ) -> Result<Option<LogicalPlan>> {
+        internal_err!("Should have called NormalizeJoinKeys::rewrite")
+    }
+
+    fn supports_rewrite(&self) -> bool {
+        true
+    }
+
+    fn apply_order(&self) -> Option<ApplyOrder> {
+        Some(ApplyOrder::BottomUp)
+    }
+
+    fn rewrite(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+    ) -> Result<Transformed<LogicalPlan>> {
+        let options = &config.options().optimizer;
+        if !options.normalize_join_keys || !options.prefer_hash_join {
+            return Ok(Transformed::no(plan));
+        }
+
+        match plan {
+            LogicalPlan::Join(mut join) => {
+                let mut transformed = false;
+                let on = std::mem::take(&mut join.on)
+                    .into_iter()
+                    .map(|(l, r)| {
+                        match normalize_key_pair(
+                            &l,
+                            &r,
+                            join.left.schema(),
+                            join.right.schema(),
+                        )? {
+                            Some(pair) => {
+                                transformed = true;
+                                Ok(pair)
+                            }
+                            None => Ok((l, r)),
+                        }
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                join.on = on;
+
+                let plan = LogicalPlan::Join(join);
+                Ok(if transformed {
+                    Transformed::yes(plan)
+                } else {
+                    Transformed::no(plan)
+                })
+            }
+            _ => Ok(Transformed::no(plan)),
+        }
+    }
+
+    fn name(&self) -> &str {
+        "normalize_join_keys"
+    }
+}
+
+/// Returns the key pair without its encoding casts, or `None` if neither side
+/// has one or the uncast sides would not share the same value type
+fn normalize_key_pair(
+    left: &Expr,
+    right: &Expr,
+    left_schema: &DFSchema,
+    right_schema: &DFSchema,
+) -> Result<Option<(Expr, Expr)>> {
+    let left_key = strip_encoding_cast(left, left_schema)?;
+    let right_key = strip_encoding_cast(right, right_schema)?;
+    if left_key.is_none() && right_key.is_none() {
+        return Ok(None);
+    }
+
+    let left_key = left_key.unwrap_or_else(|| left.clone());
+    let right_key = right_key.unwrap_or_else(|| right.clone());
+    let left_type = left_key.get_type(left_schema)?;
+    let right_type = right_key.get_type(right_schema)?;
+    if value_type(&left_type) != value_type(&right_type) {
+        return Ok(None);
+    }
+    Ok(Some((left_key, right_key)))
+}
+
+/// Returns the input of `expr` if it is a cast that leaves the value type
+/// unchanged, i.e. one that only adds or removes a dictionary encoding
+fn strip_encoding_cast(expr: &Expr, schema: &DFSchema) -> Result<Option<Expr>> {
+    match expr {
+        Expr::Cast(Cast { expr, data_type }) => {
+            let input_type = expr.get_type(schema)?;
+            Ok((value_type(&input_type) == value_type(data_type))
+                .then(|| expr.as_ref().clone()))
+        }
+        _ => Ok(None),
+    }
+}
+
+/// The type of the values of a dictionary, or the type itself otherwise
+fn value_type(data_type: &DataType) -> &DataType {
+    match data_type {
+        DataType::Dictionary(_, value_type) => value_type,
+        other => other,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::assert_optimized_plan_eq;
+    use arrow::datatypes::{Field, Schema};
+    use datafusion_expr::logical_plan::table_scan;
+    use datafusion_expr::{cast, col, JoinType, LogicalPlanBuilder};
+    use std::sync::Arc;
+
+    fn assert_optimized_plan_equal(plan: LogicalPlan, expected: &str) -> Result<()> {
+        assert_optimized_plan_eq(Arc::new(NormalizeJoinKeys::new()), plan, expected)
+    }
+
+    fn dictionary(key_type: DataType) -> DataType {
+        DataType::Dictionary(Box::new(key_type), Box::new(DataType::Utf8))
+    }
+
+    fn scan(name: &str, key_type: DataType) -> Result<LogicalPlan> {
+        let schema = Schema::new(vec![
+            Field::new("id", DataType::Int32, false),
+            Field::new("name", key_type, true),
+        ]);
+        table_scan(Some(name), &schema, None)?.build()
+    }
+
+    fn join_on(
+        left: LogicalPlan,
+        right: LogicalPlan,
+        left_key: Expr,
+        right_key: Expr,
+    ) -> Result<LogicalPlan> {
+        LogicalPlanBuilder::from(left)
+            .join_with_expr_keys(
+                right,
+                JoinType::Inner,
+                (vec![left_key], vec![right_key]),
+                None,
+            )?
+            .build()
+    }
+
+    #[test]
+    fn dictionary_and_plain_key() -> Result<()> {
+        let key_type = dictionary(DataType::Int32);
+        let plan = join_on(
+            scan("t1", key_type.clone())?,
+            scan("t2", DataType::Utf8)?,
+            col("t1.name"),
+            cast(col("t2.name"), key_type),
+        )?;
+
+        let expected = "Inner Join: t1.name = t2.name\
+        \n  TableScan: t1\
+        \n  TableScan: t2";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn dictionaries_with_different_key_types() -> Result<()> {
+        let plan = join_on(
+            scan("t1", dictionary(DataType::Int8))?,
+            scan("t2", dictionary(DataType::Int32))?,
+            cast(col("t1.name"), DataType::Utf8),
+            cast(col("t2.name"), DataType::Utf8),
+        )?;
+
+        let expected = "Inner Join: t1.name = t2.name\
+        \n  TableScan: t1\
+        \n  TableScan: t2";
+        assert_optimized_plan_equal(plan, expected)
+    }
+
+    #[test]
+    fn value_changing_cast_is_kept() -> Result<()> {
+        let plan = join_on(
+            scan("t1", dictionary(DataType::Int32))?,
+            scan("t2", DataType::Utf8)?,
+            cast(col("t1.id"), DataType::Int64),
+            cast(col("t2.id"), DataType::Int64),
+        )?;
+
+        let expected = "Inner Join: CAST(t1.id AS Int64) = CAST(t2.id AS Int64)\
+        \n  TableScan: t1\
+        \n  TableScan: t2";
+        assert_optimized_plan_equal(plan, expected)
+    }
+}
@@ -354,6 +354,8 @@ fn optimize_projections(
                 table_scan.filters.clone(),
                 table_scan.fetch,
             )
+            .map(|table| table.with_sample(table_scan.sample.clone()))
+            .map(|table| table.with_version(table_scan.version.clone()))
             .map(|table| Some(LogicalPlan::TableScan(table)));
         }
     };
@@ -449,8 +451,17 @@ fn merge_consecutive_projections(proj: &Projection) -> Result<Option<Projection>
         let new_exprs = new_exprs
             .into_iter()
             .zip(proj.expr.iter())
-            .map(|(new_expr, old_expr)| {
-                new_expr.alias_if_changed(old_expr.name_for_alias()?)
+            .map(|(new_expr, old_expr)| match old_expr {
+                // A qualified column keeps its qualifier when it is replaced
+                // by an expression of the previous projection
+                Expr::Column(column) if column.relation.is_some() => Ok(match new_expr {
+                    Expr::Column(new_column) if new_column == *column => {
+                        Expr::Column(new_column)
+                    }
+                    new_expr => trim_expr(new_expr)
+                        .alias_qualified(column.relation.clone(), &column.name),
+                }),
+                _ => new_expr.alias_if_changed(old_expr.name_for_alias()?),
             })
             .collect::<Result<Vec<_>>>()?;
         Projection::try_new(new_exprs, prev_projection.input.clone()).map(Some)
@@ -17,20 +17,24 @@
 
 //! [`Optimizer`] and [`OptimizerRule`]
 
-use std::collections::HashSet;
+use std::cmp::Reverse;
+use std::collections::{BinaryHeap, HashSet};
+use std::fmt::{self, Display, Formatter};
 use std::sync::Arc;
+use std::time::{Duration, Instant};
 
 use chrono::{DateTime, Utc};
 use log::{debug, warn};
+use tracing::{debug_span, info_span};
 
 use datafusion_common::alias::AliasGenerator;
 use datafusion_common::config::ConfigOptions;
-use datafusion_common::instant::Instant;
 use datafusion_common::tree_node::{Transformed, TreeNode, TreeNodeRewriter};
-use datafusion_common::{internal_err, DFSchema, DataFusionError, Result};
+use datafusion_common::{internal_err, plan_err, DFSchema, DataFusionError, Result};
 use datafusion_expr::logical_plan::LogicalPlan;
 
 use crate::common_subexpr_eliminate::CommonSubexprEliminate;
+use crate::count_distinct_to_approx_distinct::CountDistinctToApproxDistinct;
 use crate::decorrelate_predicate_subquery::DecorrelatePredicateSubquery;
 use crate::eliminate_cross_join::EliminateCrossJoin;
 use crate::eliminate_duplicated_expr::EliminateDuplicatedExpr;
@@ -40,8 +44,11 @@ use crate::eliminate_limit::EliminateLimit;
 use crate::eliminate_nested_union::EliminateNestedUnion;
 use crate::eliminate_one_union::EliminateOneUnion;
 use crate::eliminate_outer_join::EliminateOuterJoin;
+use crate::eliminate_unique_group_by::EliminateUniqueGroupBy;
 use crate::extract_equijoin_predicate::ExtractEquijoinPredicate;
 use crate::filter_null_join_keys::FilterNullJoinKeys;
+use crate::merge_union_scans::MergeUnionScans;
+use crate::normalize_join_keys::NormalizeJoinKeys;
 use crate::optimize_projections::OptimizeProjections;
 use crate::plan_signature::LogicalPlanSignature;
 use crate::propagate_empty_relation::PropagateEmptyRelation;
@@ -92,6 +99,20 @@ pub trait OptimizerRule {
         None
     }
 
+    /// The names of the rules this rule must run after, in each optimizer
+    /// pass. The rule runs after all the registered instances of these rules,
+    /// see [`Optimizer::ordered_rules`].
+    fn run_after(&self) -> Vec<&str> {
+        vec![]
+    }
+
+    /// The names of the rules this rule must run before, in each optimizer
+    /// pass. The rule runs before all the registered instances of these
+    /// rules, see [`Optimizer::ordered_rules`].
+    fn run_before(&self) -> Vec<&str> {
+        vec![]
+    }
+
     /// Does this rule support rewriting owned plans (rather than by reference)?
     fn supports_rewrite(&self) -> bool {
         false
@@ -156,6 +177,30 @@ impl OptimizerContext {
         self
     }
 
+    /// Specify whether to replace `COUNT(DISTINCT ..)` aggregates with
+    /// `approx_distinct(..)`
+    pub fn with_prefer_approx_distinct(mut self, prefer_approx_distinct: bool) -> Self {
+        self.options.optimizer.prefer_approx_distinct = prefer_approx_distinct;
+        self
+    }
+
+    /// Specify whether to merge the inputs of `UNION ALL` scanning the same
+    /// table with the same filters
+    pub fn with_merge_union_scans(mut self, merge_union_scans: bool) -> Self {
+        self.options.optimizer.merge_union_scans = merge_union_scans;
+        self
+    }
+
+    /// Specify whether to remove the aggregations grouping on a primary key
+    /// without aggregate functions
+    pub fn with_eliminate_unique_group_by(
+        mut self,
+        eliminate_unique_group_by: bool,
+    ) -> Self {
+        self.options.optimizer.eliminate_unique_group_by = eliminate_unique_group_by;
+        self
+    }
+
     /// Specify whether the optimizer should skip rules that produce
     /// errors, or fail the query
     pub fn with_query_execution_start_time(
@@ -252,10 +297,18 @@ impl Optimizer {
             // Must be after PropagateEmptyRelation
             Arc::new(EliminateOneUnion::new()),
             Arc::new(FilterNullJoinKeys::default()),
+            Arc::new(NormalizeJoinKeys::new()),
             Arc::new(EliminateOuterJoin::new()),
+            // Must be after the rules rewriting the joins, whose keys and types
+            // determine the unique keys of their outputs
+            Arc::new(EliminateUniqueGroupBy::new()),
             // Filters can't be pushed down past Limits, we should do PushDownFilter after PushDownLimit
             Arc::new(PushDownLimit::new()),
             Arc::new(PushDownFilter::new()),
+            Arc::new(MergeUnionScans::new()),
+            // Must be before SingleDistinctToGroupBy, which rewrites the
+            // COUNT(DISTINCT ..) aggregates
+            Arc::new(CountDistinctToApproxDistinct::new()),
             Arc::new(SingleDistinctToGroupBy::new()),
             // The previous optimizations added expressions and projections,
             // that might benefit from the following rules
@@ -272,6 +325,82 @@ impl Optimizer {
     pub fn with_rules(rules: Vec<Arc<dyn OptimizerRule + Send + Sync>>) -> Self {
         Self { rules }
     }
+
+    /// Returns the rules in the order they run in each pass.
+    ///
+    /// The rules run in the order they were registered in, as far as the
+    /// constraints of their [`OptimizerRule::run_after`] and
+    /// [`OptimizerRule::run_before`] allow: whenever several rules may run
+    /// next, the first registered one does. The constraints naming rules
+    /// which are not registered are ignored.
+    ///
+    /// Returns an error if the constraints are cyclic.
+    pub fn ordered_rules(&self) -> Result<Vec<Arc<dyn OptimizerRule + Send + Sync>>> {
+        let indices = |name: &str| {
+            self.rules
+                .iter()
+                .enumerate()
+                .filter(|(_, rule)| rule.name() == name)
+                .map(|(i, _)| i)
+                .collect::<Vec<_>>()
+        };
+        // The rules that must run after each rule, and the number of rules
+        // each rule must run after
+        let mut successors = vec![vec![]; self.rules.len()];
+        let mut predecessor_counts = vec![0; self.rules.len()];
+        for (i, rule) in self.rules.iter().enumerate() {
+            let edges = rule
+                .run_after()
+                .into_iter()
+                .filter(|name| *name != rule.name())
+                .flat_map(|name| indices(name).into_iter().map(move |before| (before, i)))
+                .chain(
+                    rule.run_before()
+                        .into_iter()
+                        .filter(|name| *name != rule.name())
+                        .flat_map(|name| {
+                            indices(name).into_iter().map(move |after| (i, after))
+                        }),
+                );
+            for (before, after) in edges {
+                successors[before].push(after);
+                predecessor_counts[after] += 1;
+            }
+        }
+
+        // Topological sort running the first registered rule among the ones
+        // whose predecessors all ran
+        let mut ready: BinaryHeap<Reverse<usize>> = predecessor_counts
+            .iter()
+            .enumerate()
+            .filter(|(_, count)| **count == 0)
+            .map(|(i, _)| Reverse(i))
+            .collect();
+        let mut ordered = Vec::with_capacity(self.rules.len());
+        while let Some(Reverse(i)) = ready.pop() {
+            ordered.push(Arc::clone(&self.rules[i]));
+            for &successor in &successors[i] {
+                predecessor_counts[successor] -= 1;
+                if predecessor_counts[successor] == 0 {
+                    ready.push(Reverse(successor));
+                }
+            }
+        }
+
+        if ordered.len() < self.rules.len() {
+            let cyclic = predecessor_counts
+                .iter()
+                .enumerate()
+                .filter(|(_, count)| **count > 0)
+                .map(|(i, _)| self.rules[i].name())
+                .collect::<Vec<_>>();
+            return plan_err!(
+                "Cyclic ordering constraints between the optimizer rules {}",
+                cyclic.join(", ")
+            );
+        }
+        Ok(ordered)
+    }
 }
 
 /// Recursively rewrites LogicalPlans
@@ -340,26 +469,66 @@ impl Optimizer {
     /// Optimizes the logical plan by applying optimizer rules, and
     /// invoking observer function after each call
     pub fn optimize<F>(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+        observer: F,
+    ) -> Result<LogicalPlan>
+    where
+        F: FnMut(&LogicalPlan, &dyn OptimizerRule),
+    {
+        self.optimize_impl(plan, config, observer, None)
+    }
+
+    /// Same as [`Self::optimize`], also recording in `runs` the time each
+    /// rule took in each pass and the changes it made to the plan.
+    ///
+    /// The runs are recorded up to the failing rule if the optimization
+    /// fails.
+    pub fn optimize_with_runs<F>(
+        &self,
+        plan: LogicalPlan,
+        config: &dyn OptimizerConfig,
+        observer: F,
+        runs: &mut Vec<OptimizerRuleRun>,
+    ) -> Result<LogicalPlan>
+    where
+        F: FnMut(&LogicalPlan, &dyn OptimizerRule),
+    {
+        self.optimize_impl(plan, config, observer, Some(runs))
+    }
+
+    fn optimize_impl<F>(
         &self,
         plan: LogicalPlan,
         config: &dyn OptimizerConfig,
         mut observer: F,
+        mut runs: Option<&mut Vec<OptimizerRuleRun>>,
     ) -> Result<LogicalPlan>
     where
         F: FnMut(&LogicalPlan, &dyn OptimizerRule),
     {
-        let start_time = Instant::now();
+        let _span = info_span!("datafusion.optimize").entered();
         let options = config.options();
+        let rules = self.ordered_rules()?;
         let mut new_plan = plan;
+        // The displayed plan, to diff the plans the rules change
+        let mut displayed_plan = runs
+            .is_some()
+            .then(|| new_plan.display_indent().to_string());
 
         let mut previous_plans = HashSet::with_capacity(16);
         previous_plans.insert(LogicalPlanSignature::new(&new_plan));
 
         let mut i = 0;
         while i < options.optimizer.max_passes {
+            let _pass_span = debug_span!("datafusion.optimizer_pass", pass = i).entered();
             log_plan(&format!("Optimizer input (pass {i})"), &new_plan);
 
-            for rule in &self.rules {
+            for rule in &rules {
+                let _rule_span =
+                    debug_span!("datafusion.optimizer_rule", rule = rule.name())
+                        .entered();
                 // If skipping failed rules, copy plan before attempting to rewrite
                 // as rewriting is destructive
                 let prev_plan = options
@@ -368,6 +537,7 @@ impl Optimizer {
                     .then(|| new_plan.clone());
 
                 let starting_schema = new_plan.schema().clone();
+                let start = runs.is_some().then(Instant::now);
 
                 let result = match rule.apply_order() {
                     // optimizer handles recursion
@@ -384,6 +554,7 @@ impl Optimizer {
                     assert_schema_is_the_same(rule.name(), &starting_schema, &tnr.data)?;
                     Ok(tnr)
                 });
+                let elapsed = start.map(|start| start.elapsed()).unwrap_or_default();
 
                 // Handle results
                 match (result, prev_plan) {
@@ -396,6 +567,24 @@ impl Optimizer {
                     ) => {
                         new_plan = data;
                         observer(&new_plan, rule.as_ref());
+                        if let Some(runs) = runs.as_deref_mut() {
+                            let diff = if transformed {
+                                let displayed = new_plan.display_indent().to_string();
+                                let previous = displayed_plan
+                                    .replace(displayed.clone())
+                                    .unwrap_or_default();
+                                (previous != displayed)
+                                    .then(|| diff_lines(&previous, &displayed))
+                            } else {
+                                None
+                            };
+                            runs.push(OptimizerRuleRun {
+                                pass: i,
+                                rule: rule.name().to_string(),
+                                elapsed,
+                                diff,
+                            });
+                        }
                         if transformed {
                             log_plan(rule.name(), &new_plan);
                         } else {
@@ -418,6 +607,14 @@ impl Optimizer {
                             e
                         );
                         new_plan = orig_plan;
+                        if let Some(runs) = runs.as_deref_mut() {
+                            runs.push(OptimizerRuleRun {
+                                pass: i,
+                                rule: rule.name().to_string(),
+                                elapsed,
+                                diff: None,
+                            });
+                        }
                     }
                     // OptimizerRule was unsuccessful, but skipped failed rules is off, return error
                     (Err(e), None) => {
@@ -441,11 +638,75 @@ impl Optimizer {
             i += 1;
         }
         log_plan("Final optimized plan", &new_plan);
-        debug!("Optimizer took {} ms", start_time.elapsed().as_millis());
         Ok(new_plan)
     }
 }
 
+/// The run of an [`OptimizerRule`] in an optimizer pass, recorded by
+/// [`Optimizer::optimize_with_runs`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct OptimizerRuleRun {
+    /// The optimizer pass, starting from 0
+    pub pass: usize,
+    /// The name of the rule
+    pub rule: String,
+    /// The time the rule took
+    pub elapsed: Duration,
+    /// The diff of the indented plan, if the rule changed it: the unchanged
+    /// lines start with a space, the removed lines with `-` and the added
+    /// lines with `+`
+    pub diff: Option<String>,
+}
+
+impl Display for OptimizerRuleRun {
+    fn fmt(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "{} (pass {}): {:?}", self.rule, self.pass, self.elapsed)?;
+        if let Some(diff) = &self.diff {
+            for line in diff.lines() {
+                write!(f, "\n  {line}")?;
+            }
+        }
+        Ok(())
+    }
+}
+
+/// Returns the line diff of `before` and `after`, computed from their longest
+/// common subsequence of lines
+fn diff_lines(before: &str, after: &str) -> String {
+    let before: Vec<&str> = before.lines().collect();
+    let after: Vec<&str> = after.lines().collect();
+    // lcs[i][j] is the length of the longest common subsequence of
+    // before[i..] and after[j..]
+    let mut lcs = vec![vec![0; after.len() + 1]; before.len() + 1];
+    for i in (0..before.len()).rev() {
+        for j in (0..after.len()).rev() {
+            lcs[i][j] = if before[i] == after[j] {
+                lcs[i + 1][j + 1] + 1
+            } else {
+                lcs[i + 1][j].max(lcs[i][j + 1])
+            };
+        }
+    }
+
+    let (mut i, mut j) = (0, 0);
+    let mut diff = vec![];
+    while i < before.len() || j < after.len() {
+        if i < before.len() && j < after.len() && before[i] == after[j] {
+            diff.push(format!(" {}", before[i]));
+            i += 1;
+            j += 1;
+        } else if i < before.len() && (j == after.len() || lcs[i + 1][j] >= lcs[i][j + 1])
+        {
+            diff.push(format!("-{}", before[i]));
+            i += 1;
+        } else {
+            diff.push(format!("+{}", after[j]));
+            j += 1;
+        }
+    }
+    diff.join("\n")
+}
+
 /// Returns an error if `new_plan`'s schema is different than `prev_schema`
 ///
 /// It ignores metadata and nullability.
@@ -632,6 +893,89 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn optimizer_records_rule_runs() -> Result<()> {
+        let opt = Optimizer::with_rules(vec![Arc::new(RotateProjectionRule::new(false))]);
+        let config = OptimizerContext::new().with_max_passes(16);
+
+        let initial_plan = LogicalPlanBuilder::empty(false)
+            .project([lit(1), lit(2), lit(3)])?
+            .project([lit(100)])? // to not trigger changed schema error
+            .build()?;
+
+        let mut runs = vec![];
+        opt.optimize_with_runs(initial_plan, &config, observe, &mut runs)?;
+
+        // the third pass gets the initial plan back
+        let passes: Vec<_> = runs.iter().map(|run| run.pass).collect();
+        assert_eq!(passes, [0, 1, 2]);
+        assert_eq!(runs[0].rule, "rotate_projection");
+        let expected = [
+            " Projection: Int32(100)",
+            "-  Projection: Int32(1), Int32(2), Int32(3)",
+            "+  Projection: Int32(2), Int32(3), Int32(1)",
+            "     EmptyRelation",
+        ];
+        let diff = runs[0].diff.as_deref().unwrap();
+        assert_eq!(diff.lines().collect::<Vec<_>>(), expected);
+        assert!(runs[0]
+            .to_string()
+            .starts_with("rotate_projection (pass 0): "));
+
+        Ok(())
+    }
+
+    #[test]
+    fn optimizer_orders_rules() -> Result<()> {
+        let opt = Optimizer::with_rules(vec![
+            Arc::new(OrderedRule::new("a", &[], &[])),
+            Arc::new(OrderedRule::new("b", &["d"], &[])),
+            Arc::new(OrderedRule::new("c", &[], &[])),
+            Arc::new(OrderedRule::new("d", &[], &[])),
+            Arc::new(OrderedRule::new("e", &[], &["a", "unknown"])),
+            Arc::new(OrderedRule::new("b", &["d"], &[])),
+        ]);
+        let names = opt
+            .ordered_rules()?
+            .iter()
+            .map(|rule| rule.name().to_string())
+            .collect::<Vec<_>>();
+        assert_eq!(names, ["c", "d", "b", "e", "a", "b"]);
+
+        // the recommended rules are registered in a consistent order
+        let opt = Optimizer::new();
+        let names = |rules: &[Arc<dyn OptimizerRule + Send + Sync>]| {
+            rules
+                .iter()
+                .map(|rule| rule.name().to_string())
+                .collect::<Vec<_>>()
+        };
+        assert_eq!(names(&opt.ordered_rules()?), names(&opt.rules));
+        Ok(())
+    }
+
+    #[test]
+    fn optimizer_rejects_cyclic_rule_orders() {
+        let opt = Optimizer::with_rules(vec![
+            Arc::new(OrderedRule::new("a", &[], &[])),
+            Arc::new(OrderedRule::new("b", &["c"], &[])),
+            Arc::new(OrderedRule::new("c", &["b"], &[])),
+        ]);
+        let err = opt.ordered_rules().err().unwrap();
+        assert_eq!(
+            "Error during planning: Cyclic ordering constraints between the optimizer rules b, c",
+            err.strip_backtrace()
+        );
+
+        let plan = LogicalPlan::EmptyRelation(EmptyRelation {
+            produce_one_row: false,
+            schema: Arc::new(DFSchema::empty()),
+        });
+        assert!(opt
+            .optimize(plan, &OptimizerContext::new(), observe)
+            .is_err());
+    }
+
     fn add_metadata_to_fields(schema: &DFSchema) -> DFSchemaRef {
         let new_fields = schema
             .iter()
@@ -736,4 +1080,47 @@ mod tests {
             "rotate_projection"
         }
     }
+
+    /// A rule doing nothing, with ordering constraints
+    struct OrderedRule {
+        name: &'static str,
+        after: Vec<&'static str>,
+        before: Vec<&'static str>,
+    }
+
+    impl OrderedRule {
+        fn new(
+            name: &'static str,
+            after: &[&'static str],
+            before: &[&'static str],
+        ) -> Self {
+            Self {
+                name,
+                after: after.to_vec(),
+                before: before.to_vec(),
+            }
+        }
+    }
+
+    impl OptimizerRule for OrderedRule {
+        fn try_optimize(
+            &self,
+            _: &LogicalPlan,
+            _: &dyn OptimizerConfig,
+        ) -> Result<Option<LogicalPlan>> {
+            Ok(None)
+        }
+
+        fn name(&self) -> &str {
+            self.name
+        }
+
+        fn run_after(&self) -> Vec<&str> {
+            self.after.clone()
+        }
+
+        fn run_before(&self) -> Vec<&str> {
+            self.before.clone()
+        }
+    }
 }
@@ -27,15 +27,15 @@ use datafusion_common::{
     internal_err, plan_datafusion_err, qualified_name, Column, DFSchema, DFSchemaRef,
     JoinConstraint, Result,
 };
-use datafusion_expr::expr::Alias;
+use datafusion_expr::expr::{Alias, GroupingSet, WindowFunction};
 use datafusion_expr::expr_rewriter::replace_col;
 use datafusion_expr::logical_plan::{
     CrossJoin, Join, JoinType, LogicalPlan, TableScan, Union,
 };
 use datafusion_expr::utils::{conjunction, split_conjunction, split_conjunction_owned};
 use datafusion_expr::{
-    and, build_join_schema, or, BinaryExpr, Expr, Filter, LogicalPlanBuilder, Operator,
-    ScalarFunctionDefinition, TableProviderFilterPushDown,
+    and, build_join_schema, or, BinaryExpr, Expr, Extension, Filter, LogicalPlanBuilder,
+    Operator, ScalarFunctionDefinition, SetOperation, TableProviderFilterPushDown,
 };
 
 use itertools::Itertools;
@@ -619,6 +619,11 @@ impl OptimizerRule for PushDownFilter {
         Some(ApplyOrder::TopDown)
     }
 
+    /// Filters can't be pushed down past Limits
+    fn run_after(&self) -> Vec<&str> {
+        vec!["push_down_limit"]
+    }
+
     fn try_optimize(
         &self,
         plan: &LogicalPlan,
@@ -773,37 +778,36 @@ impl OptimizerRule for PushDownFilter {
             }
             LogicalPlan::Aggregate(agg) => {
                 // We can push down Predicate which in groupby_expr.
-                let group_expr_columns = agg
-                    .group_expr
-                    .iter()
-                    .map(|e| Ok(Column::from_qualified_name(e.display_name()?)))
-                    .collect::<Result<HashSet<_>>>()?;
+                // The columns of the predicates are matched with the group
+                // expressions by their position in the output of the
+                // aggregate, as the names of expressions such as nested field
+                // accesses don't parse back to the same columns.
+                let mut group_exprs = HashMap::new();
+                for (i, expr) in group_keys(&agg.group_expr).into_iter().enumerate() {
+                    if let Some(expr) = expr {
+                        let column = Column::from(agg.schema.qualified_field(i));
+                        group_exprs.insert(column.flat_name(), expr);
+                    }
+                }
 
                 let predicates = split_conjunction_owned(filter.predicate.clone());
 
+                // As for plan Filter: Column(a+b) > 0 -- Agg: groupby:[Column(a)+Column(b)]
+                // After push, we need to replace `a+b` with Column(a)+Column(b)
                 let mut keep_predicates = vec![];
-                let mut push_predicates = vec![];
+                let mut replaced_push_predicates = vec![];
                 for expr in predicates {
-                    let cols = expr.to_columns()?;
-                    if cols.iter().all(|c| group_expr_columns.contains(c)) {
-                        push_predicates.push(expr);
-                    } else {
+                    // A volatile predicate is evaluated once per group, not per row
+                    if expr.is_volatile()? {
                         keep_predicates.push(expr);
+                        continue;
+                    }
+                    match replace_group_exprs(&expr, &group_exprs)? {
+                        Some(replaced) => replaced_push_predicates.push(replaced),
+                        None => keep_predicates.push(expr),
                     }
                 }
 
-                // As for plan Filter: Column(a+b) > 0 -- Agg: groupby:[Column(a)+Column(b)]
-                // After push, we need to replace `a+b` with Column(a)+Column(b)
-                // So we need create a replace_map, add {`a+b` --> Expr(Column(a)+Column(b))}
-                let mut replace_map = HashMap::new();
-                for expr in &agg.group_expr {
-                    replace_map.insert(expr.display_name()?, expr.clone());
-                }
-                let replaced_push_predicates = push_predicates
-                    .iter()
-                    .map(|expr| replace_cols_by_name(expr.clone(), &replace_map))
-                    .collect::<Result<Vec<_>>>()?;
-
                 let child = match conjunction(replaced_push_predicates) {
                     Some(predicate) => LogicalPlan::Filter(Filter::try_new(
                         predicate,
@@ -822,6 +826,47 @@ impl OptimizerRule for PushDownFilter {
                     None => new_agg,
                 }
             }
+            LogicalPlan::Window(window) => {
+                // We can push down the predicates which only refer to columns
+                // every window expression is partitioned by: they keep or
+                // remove whole window partitions.
+                let partition_keys = window_partition_keys(&window.window_expr);
+
+                let mut keep_predicates = vec![];
+                let mut push_predicates = vec![];
+                for expr in split_conjunction_owned(filter.predicate.clone()) {
+                    // A volatile predicate may keep only some rows of a partition
+                    if expr.is_volatile()? {
+                        keep_predicates.push(expr);
+                        continue;
+                    }
+                    let cols = expr.to_columns()?;
+                    if !cols.is_empty() && cols.iter().all(|c| partition_keys.contains(c))
+                    {
+                        push_predicates.push(expr);
+                    } else {
+                        keep_predicates.push(expr);
+                    }
+                }
+
+                let child = match conjunction(push_predicates) {
+                    Some(predicate) => LogicalPlan::Filter(Filter::try_new(
+                        predicate,
+                        window.input.clone(),
+                    )?),
+                    None => return Ok(None),
+                };
+                let new_window = filter
+                    .input
+                    .with_new_exprs(filter.input.expressions(), vec![child])?;
+                match conjunction(keep_predicates) {
+                    Some(predicate) => LogicalPlan::Filter(Filter::try_new(
+                        predicate,
+                        Arc::new(new_window),
+                    )?),
+                    None => new_window,
+                }
+            }
             LogicalPlan::Join(join) => {
                 match push_down_join(&filter.input, join, Some(&filter.predicate))? {
                     Some(optimized_plan) => optimized_plan,
@@ -883,6 +928,8 @@ impl OptimizerRule for PushDownFilter {
                     table_name: scan.table_name.clone(),
                     filters: new_scan_filters,
                     fetch: scan.fetch,
+                    sample: scan.sample.clone(),
+                    version: scan.version.clone(),
                 });
 
                 match conjunction(new_predicate) {
@@ -893,6 +940,52 @@ impl OptimizerRule for PushDownFilter {
                     None => new_scan,
                 }
             }
+            LogicalPlan::Extension(Extension { node })
+                if node.as_any().is::<SetOperation>() =>
+            {
+                // Equal rows of both inputs satisfy the same deterministic
+                // predicates, so that filtering both inputs filters the output
+                let mut keep_predicates = vec![];
+                let mut push_predicates = vec![];
+                for expr in split_conjunction_owned(filter.predicate.clone()) {
+                    if expr.is_volatile()? {
+                        keep_predicates.push(expr);
+                    } else {
+                        push_predicates.push(expr);
+                    }
+                }
+                let Some(push_predicate) = conjunction(push_predicates) else {
+                    return Ok(None);
+                };
+
+                let mut inputs = Vec::with_capacity(2);
+                for input in node.inputs() {
+                    let mut replace_map = HashMap::new();
+                    for (i, (qualifier, field)) in input.schema().iter().enumerate() {
+                        let (output_qualifier, output_field) =
+                            node.schema().qualified_field(i);
+                        replace_map.insert(
+                            qualified_name(output_qualifier, output_field.name()),
+                            Expr::Column(Column::new(qualifier.cloned(), field.name())),
+                        );
+                    }
+                    let predicate =
+                        replace_cols_by_name(push_predicate.clone(), &replace_map)?;
+                    inputs.push(LogicalPlan::Filter(Filter::try_new(
+                        predicate,
+                        Arc::new(input.clone()),
+                    )?));
+                }
+                let new_node =
+                    child_plan.with_new_exprs(child_plan.expressions(), inputs)?;
+                match conjunction(keep_predicates) {
+                    Some(predicate) => LogicalPlan::Filter(Filter::try_new(
+                        predicate,
+                        Arc::new(new_node),
+                    )?),
+                    None => new_node,
+                }
+            }
             LogicalPlan::Extension(extension_plan) => {
                 let prevent_cols =
                     extension_plan.node.prevent_predicate_push_down_columns();
@@ -949,6 +1042,33 @@ impl PushDownFilter {
     }
 }
 
+/// Returns the columns every window expression of `window_exprs` is
+/// partitioned by
+fn window_partition_keys(window_exprs: &[Expr]) -> HashSet<Column> {
+    let mut keys: Option<HashSet<Column>> = None;
+    for expr in window_exprs {
+        let expr = match expr {
+            Expr::Alias(Alias { expr, .. }) => expr.as_ref(),
+            expr => expr,
+        };
+        let Expr::WindowFunction(WindowFunction { partition_by, .. }) = expr else {
+            return HashSet::new();
+        };
+        let columns = partition_by
+            .iter()
+            .filter_map(|e| match e {
+                Expr::Column(column) => Some(column.clone()),
+                _ => None,
+            })
+            .collect::<HashSet<_>>();
+        keys = Some(match keys {
+            Some(keys) => keys.intersection(&columns).cloned().collect(),
+            None => columns,
+        });
+    }
+    keys.unwrap_or_default()
+}
+
 /// Converts the given cross join to an inner join with an empty equality
 /// predicate and an empty filter condition.
 fn convert_cross_join_to_inner_join(cross_join: CrossJoin) -> Result<Join> {
@@ -1003,6 +1123,70 @@ pub fn replace_cols_by_name(
     .data()
 }
 
+/// Rewrites `predicate`, over the output of an aggregate, to a predicate over
+/// its input, replacing the columns of the group expressions `group_exprs`,
+/// given by their flat names, with the group expressions. The
+/// sub-expressions equal to a group expression are kept as they are.
+///
+/// Returns `None` if the predicate refers to other columns, e.g. to the
+/// aggregate expressions.
+fn replace_group_exprs(
+    predicate: &Expr,
+    group_exprs: &HashMap<String, Expr>,
+) -> Result<Option<Expr>> {
+    let mut replaced_all = true;
+    let replaced = predicate
+        .clone()
+        .transform_down(|expr| {
+            if group_exprs.values().any(|group_expr| group_expr == &expr) {
+                return Ok(Transformed::new(expr, false, TreeNodeRecursion::Jump));
+            }
+            if let Expr::Column(column) = &expr {
+                match group_exprs.get(&column.flat_name()) {
+                    Some(group_expr) => {
+                        return Ok(Transformed::new(
+                            group_expr.clone(),
+                            true,
+                            TreeNodeRecursion::Jump,
+                        ))
+                    }
+                    None => replaced_all = false,
+                }
+            }
+            Ok(Transformed::no(expr))
+        })
+        .data()?;
+    Ok(replaced_all.then_some(replaced))
+}
+
+/// Returns the group expressions of an aggregate by their position in its
+/// output.
+///
+/// The expressions of a grouping set which are not in all of its sets are
+/// `None`: the rows of the sets without them have nulls instead of their
+/// values, so that the predicates on them can't be evaluated on the input.
+/// `ROLLUP` and `CUBE` have an empty set.
+fn group_keys(group_expr: &[Expr]) -> Vec<Option<Expr>> {
+    match group_expr {
+        [Expr::GroupingSet(grouping_set)] => {
+            let sets = match grouping_set {
+                GroupingSet::GroupingSets(sets) => sets.as_slice(),
+                GroupingSet::Rollup(_) | GroupingSet::Cube(_) => &[],
+            };
+            grouping_set
+                .distinct_expr()
+                .into_iter()
+                .map(|expr| {
+                    let in_all_sets =
+                        !sets.is_empty() && sets.iter().all(|set| set.contains(&expr));
+                    in_all_sets.then_some(expr)
+                })
+                .collect()
+        }
+        _ => group_expr.iter().cloned().map(Some).collect(),
+    }
+}
+
 /// check whether the expression uses the columns in `check_map`.
 fn contain(e: &Expr, check_map: &HashMap<String, Expr>) -> bool {
     let mut is_contain = false;
@@ -1039,9 +1223,9 @@ mod tests {
     use datafusion_expr::expr::ScalarFunction;
     use datafusion_expr::logical_plan::table_scan;
     use datafusion_expr::{
-        col, in_list, in_subquery, lit, sum, ColumnarValue, Extension, ScalarUDF,
-        ScalarUDFImpl, Signature, TableSource, TableType, UserDefinedLogicalNodeCore,
-        Volatility,
+        col, in_list, in_subquery, lit, sum, BuiltInWindowFunction, ColumnarValue,
+        Extension, ScalarUDF, ScalarUDFImpl, Signature, TableSource, TableType,
+        UserDefinedLogicalNodeCore, Volatility, WindowFrame, WindowFunctionDefinition,
     };
 
     use async_trait::async_trait;
@@ -1167,6 +1351,36 @@ mod tests {
         assert_optimized_plan_eq(plan, expected)
     }
 
+    #[test]
+    fn filter_move_agg_nested_field() -> Result<()> {
+        let person = Field::new_struct(
+            "person",
+            vec![Field::new("age", DataType::UInt32, false)],
+            false,
+        );
+        let schema = Schema::new(vec![
+            Field::new_struct("s", vec![person], false),
+            Field::new("b", DataType::UInt32, false),
+        ]);
+        let plan = table_scan(Some("test"), &schema, None)?
+            .aggregate(
+                vec![col("s").field("person").field("age")],
+                vec![sum(col("b")).alias("total")],
+            )?
+            .filter(
+                col("test.s[person][age]")
+                    .gt(lit(30u32))
+                    .and(col("total").gt(lit(10u64))),
+            )?
+            .build()?;
+        // the predicate on the nested field key is pushed with the key
+        let expected = "\
+            Filter: total > UInt64(10)\
+            \n  Aggregate: groupBy=[[((test.s)[person])[age]]], aggr=[[SUM(test.b) AS total]]\
+            \n    TableScan: test, full_filters=[((test.s)[person])[age] > UInt32(30)]";
+        assert_optimized_plan_eq(plan, expected)
+    }
+
     #[test]
     fn filter_keep_agg() -> Result<()> {
         let table_scan = test_table_scan()?;
@@ -1182,6 +1396,110 @@ mod tests {
         assert_optimized_plan_eq(plan, expected)
     }
 
+    #[test]
+    fn filter_move_agg_grouping_sets() -> Result<()> {
+        let grouping_set = Expr::GroupingSet(GroupingSet::GroupingSets(vec![
+            vec![col("a"), col("b")],
+            vec![col("a")],
+        ]));
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(vec![grouping_set], vec![sum(col("c")).alias("total")])?
+            .filter(col("a").gt(lit(10u32)).and(col("b").gt(lit(20u32))))?
+            .build()?;
+        // `b` is null in the rows of the set `(a)`
+        let expected = "\
+            Filter: test.b > UInt32(20)\
+            \n  Aggregate: groupBy=[[GROUPING SETS ((test.a, test.b), (test.a))]], aggr=[[SUM(test.c) AS total]]\
+            \n    TableScan: test, full_filters=[test.a > UInt32(10)]";
+        assert_optimized_plan_eq(plan, expected)?;
+
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(
+                vec![Expr::GroupingSet(GroupingSet::Rollup(vec![col("a")]))],
+                vec![sum(col("c")).alias("total")],
+            )?
+            .filter(col("a").gt(lit(10u32)))?
+            .build()?;
+        // The rows of the empty set of the rollup aggregate all the rows
+        let expected = "\
+            Filter: test.a > UInt32(10)\
+            \n  Aggregate: groupBy=[[ROLLUP (test.a)]], aggr=[[SUM(test.c) AS total]]\
+            \n    TableScan: test";
+        assert_optimized_plan_eq(plan, expected)
+    }
+
+    /// `ROW_NUMBER() OVER (PARTITION BY <partition_by>)`
+    fn row_number(partition_by: Vec<Expr>) -> Expr {
+        Expr::WindowFunction(WindowFunction::new(
+            WindowFunctionDefinition::BuiltInWindowFunction(
+                BuiltInWindowFunction::RowNumber,
+            ),
+            vec![],
+            partition_by,
+            vec![],
+            WindowFrame::new(None),
+            None,
+        ))
+    }
+
+    #[test]
+    fn filter_move_window() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .window(vec![
+                row_number(vec![col("a"), col("b")]).alias("r1"),
+                row_number(vec![col("a")]).alias("r2"),
+            ])?
+            .filter(
+                col("a")
+                    .eq(lit(1u32))
+                    .and(col("b").eq(lit(2u32)))
+                    .and(col("r1").eq(lit(1u64))),
+            )?
+            .build()?;
+        // only the predicate on the common partition key is pushed
+        let expected = "\
+            Filter: test.b = UInt32(2) AND r1 = UInt64(1)\
+            \n  WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [test.a, test.b] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS r1, ROW_NUMBER() PARTITION BY [test.a] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS r2]]\
+            \n    TableScan: test, full_filters=[test.a = UInt32(1)]";
+        assert_optimized_plan_eq(plan, expected)
+    }
+
+    #[test]
+    fn filter_keep_window() -> Result<()> {
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .window(vec![
+                row_number(vec![col("a")]).alias("r1"),
+                row_number(vec![col("b")]).alias("r2"),
+            ])?
+            .filter(col("a").eq(lit(1u32)))?
+            .build()?;
+        // `a` is not a partition key of every window expression
+        let expected = "\
+            Filter: test.a = UInt32(1)\
+            \n  WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [test.a] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS r1, ROW_NUMBER() PARTITION BY [test.b] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS r2]]\
+            \n    TableScan: test";
+        assert_optimized_plan_eq(plan, expected)
+    }
+
+    #[test]
+    fn filter_keep_window_volatile() -> Result<()> {
+        let fun = ScalarUDF::new_from_impl(TestScalarUDF {
+            signature: Signature::exact(vec![], Volatility::Volatile),
+        });
+        let expr = Expr::ScalarFunction(ScalarFunction::new_udf(Arc::new(fun), vec![]));
+
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .window(vec![row_number(vec![col("a")]).alias("r1")])?
+            .filter(col("a").eq(lit(1u32)).and(col("a").gt(expr)))?
+            .build()?;
+        // The volatile predicate may remove some rows of a window partition
+        let expected = "\
+            Filter: test.a > TestScalarUDF()\
+            \n  WindowAggr: windowExpr=[[ROW_NUMBER() PARTITION BY [test.a] ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING AS r1]]\
+            \n    TableScan: test, full_filters=[test.a = UInt32(1)]";
+        assert_optimized_plan_eq(plan, expected)
+    }
+
     /// verifies that a filter is pushed to before a projection, the filter expression is correctly re-written
     #[test]
     fn alias() -> Result<()> {
@@ -2266,6 +2584,8 @@ mod tests {
             projection: None,
             source: Arc::new(test_provider),
             fetch: None,
+            sample: None,
+            version: None,
         });
 
         LogicalPlanBuilder::from(table_scan)
@@ -2338,6 +2658,8 @@ mod tests {
             projection: Some(vec![0]),
             source: Arc::new(test_provider),
             fetch: None,
+            sample: None,
+            version: None,
         });
 
         let plan = LogicalPlanBuilder::from(table_scan)
@@ -2367,6 +2689,8 @@ mod tests {
             projection: Some(vec![0]),
             source: Arc::new(test_provider),
             fetch: None,
+            sample: None,
+            version: None,
         });
 
         let plan = LogicalPlanBuilder::from(table_scan)
@@ -2906,6 +3230,50 @@ Projection: a, b
         assert_optimized_plan_eq(plan, expected_after)
     }
 
+    #[test]
+    fn test_keep_volatile_predicate_on_group_key() -> Result<()> {
+        let fun = ScalarUDF::new_from_impl(TestScalarUDF {
+            signature: Signature::exact(vec![], Volatility::Volatile),
+        });
+        let expr = Expr::ScalarFunction(ScalarFunction::new_udf(Arc::new(fun), vec![]));
+
+        let plan = LogicalPlanBuilder::from(test_table_scan()?)
+            .aggregate(vec![col("a")], vec![sum(col("b"))])?
+            .filter(col("a").gt(expr))?
+            .build()?;
+        // The volatile predicate is evaluated once per group, not once per row
+        let expected = "\
+            Filter: test.a > TestScalarUDF()\
+            \n  Aggregate: groupBy=[[test.a]], aggr=[[SUM(test.b)]]\
+            \n    TableScan: test";
+        assert_optimized_plan_eq(plan, expected)
+    }
+
+    #[test]
+    fn push_down_set_operation() -> Result<()> {
+        let fun = ScalarUDF::new_from_impl(TestScalarUDF {
+            signature: Signature::exact(vec![], Volatility::Volatile),
+        });
+        let expr = Expr::ScalarFunction(ScalarFunction::new_udf(Arc::new(fun), vec![]));
+
+        let intersect = LogicalPlanBuilder::intersect(
+            test_table_scan()?,
+            test_table_scan_with_name("test2")?,
+            true,
+        )?;
+        let plan = LogicalPlanBuilder::from(intersect)
+            .filter(col("a").eq(lit(1u32)).and(col("b").gt(expr)))?
+            .build()?;
+        // The predicate on the columns of the output is pushed down to the
+        // columns of the right input at the same position
+        let expected = "\
+            Filter: test.b > TestScalarUDF()\
+            \n  IntersectAll\
+            \n    TableScan: test, full_filters=[test.a = UInt32(1)]\
+            \n    TableScan: test2, full_filters=[test2.a = UInt32(1)]";
+        assert_optimized_plan_eq(plan, expected)
+    }
+
     #[test]
     fn test_push_down_volatile_function_in_join() -> Result<()> {
         // SELECT t.a, t.r FROM (SELECT test1.a AS a, TestScalarUDF() AS r FROM test1 join test2 ON test1.a = test2.a) AS t WHERE t.r > 0.5;
@@ -110,7 +110,8 @@ impl OptimizerRule for PushDownLimit {
         let skip = limit.skip;
 
         match limit.input.as_ref() {
-            LogicalPlan::TableScan(scan) => {
+            // a limit applies to the sampled rows, not to the rows sampled from
+            LogicalPlan::TableScan(scan) if scan.sample.is_none() => {
                 let limit = if fetch != 0 { fetch + skip } else { 0 };
                 let new_fetch = scan.fetch.map(|x| min(x, limit)).or(Some(limit));
                 if new_fetch == scan.fetch {
@@ -123,6 +124,8 @@ impl OptimizerRule for PushDownLimit {
                         filters: scan.filters.clone(),
                         fetch: scan.fetch.map(|x| min(x, limit)).or(Some(limit)),
                         projected_schema: scan.projected_schema.clone(),
+                        sample: None,
+                        version: scan.version.clone(),
                     });
                     plan.with_new_exprs(plan.expressions(), vec![new_input])
                         .map(Some)
@@ -49,16 +49,19 @@ impl ScalarSubqueryToJoin {
     ///
     /// # Arguments
     /// * `predicate` - A conjunction to split and search
+    /// * `config` - The optimizer configuration, deciding whether uncorrelated
+    ///   subqueries are left in place to be evaluated once
     ///
     /// Returns a tuple (subqueries, rewrite expression)
     fn extract_subquery_exprs(
         &self,
         predicate: &Expr,
-        alias_gen: Arc<AliasGenerator>,
+        config: &dyn OptimizerConfig,
     ) -> Result<(Vec<(Subquery, String)>, Expr)> {
         let mut extract = ExtractScalarSubQuery {
             sub_query_info: vec![],
-            alias_gen,
+            alias_gen: config.alias_generator(),
+            skip_uncorrelated: config.options().optimizer.evaluate_scalar_subqueries_once,
         };
         predicate
             .clone()
@@ -76,10 +79,8 @@ impl OptimizerRule for ScalarSubqueryToJoin {
     ) -> Result<Option<LogicalPlan>> {
         match plan {
             LogicalPlan::Filter(filter) => {
-                let (subqueries, mut rewrite_expr) = self.extract_subquery_exprs(
-                    &filter.predicate,
-                    config.alias_generator(),
-                )?;
+                let (subqueries, mut rewrite_expr) =
+                    self.extract_subquery_exprs(&filter.predicate, config)?;
 
                 if subqueries.is_empty() {
                     // regular filter, no subquery exists clause here
@@ -127,7 +128,7 @@ impl OptimizerRule for ScalarSubqueryToJoin {
                 let mut subquery_to_expr_map = HashMap::new();
                 for expr in projection.expr.iter() {
                     let (subqueries, rewrite_exprs) =
-                        self.extract_subquery_exprs(expr, config.alias_generator())?;
+                        self.extract_subquery_exprs(expr, config)?;
                     for (subquery, _) in &subqueries {
                         subquery_to_expr_map.insert(subquery.clone(), expr.clone());
                     }
@@ -209,6 +210,9 @@ impl OptimizerRule for ScalarSubqueryToJoin {
 struct ExtractScalarSubQuery {
     sub_query_info: Vec<(Subquery, String)>,
     alias_gen: Arc<AliasGenerator>,
+    /// Leave uncorrelated subqueries in place, to be evaluated once by the
+    /// physical plan
+    skip_uncorrelated: bool,
 }
 
 impl TreeNodeRewriter for ExtractScalarSubQuery {
@@ -216,6 +220,11 @@ impl TreeNodeRewriter for ExtractScalarSubQuery {
 
     fn f_down(&mut self, expr: Expr) -> Result<Transformed<Expr>> {
         match expr {
+            Expr::ScalarSubquery(ref subquery)
+                if self.skip_uncorrelated && subquery.outer_ref_columns.is_empty() =>
+            {
+                Ok(Transformed::new(expr, false, TreeNodeRecursion::Jump))
+            }
             Expr::ScalarSubquery(subquery) => {
                 let subqry_alias = self.alias_gen.next("__scalar_sq");
                 self.sub_query_info
@@ -736,6 +745,46 @@ mod tests {
         Ok(())
     }
 
+    /// Test for correlated scalar subquery in the projection comparing an
+    /// expression of the inner columns
+    #[test]
+    fn scalar_subquery_correlated_expr_in_projection() -> Result<()> {
+        let sq = Arc::new(
+            LogicalPlanBuilder::from(scan_tpch_table("orders"))
+                .filter(
+                    col("orders.o_custkey")
+                        .add(lit(1))
+                        .eq(out_ref_col(DataType::Int64, "customer.c_custkey")),
+                )?
+                .aggregate(Vec::<Expr>::new(), vec![max(col("orders.o_totalprice"))])?
+                .project(vec![max(col("orders.o_totalprice"))])?
+                .build()?,
+        );
+
+        let plan = LogicalPlanBuilder::from(scan_tpch_table("customer"))
+            .project(vec![
+                col("customer.c_custkey"),
+                scalar_subquery(sq).alias("max_price"),
+            ])?
+            .build()?;
+
+        // grouped by `orders.o_custkey + 1`, not by `orders.o_custkey`
+        let expected = "Projection: customer.c_custkey, __scalar_sq_1.MAX(orders.o_totalprice) AS max_price [c_custkey:Int64, max_price:Float64;N]\
+        \n  Left Join:  Filter: __scalar_sq_1.__correlated_expr_1 = customer.c_custkey [c_custkey:Int64, c_name:Utf8, MAX(orders.o_totalprice):Float64;N, __correlated_expr_1:Int64;N]\
+        \n    TableScan: customer [c_custkey:Int64, c_name:Utf8]\
+        \n    SubqueryAlias: __scalar_sq_1 [MAX(orders.o_totalprice):Float64;N, __correlated_expr_1:Int64]\
+        \n      Projection: MAX(orders.o_totalprice), __correlated_expr_1 [MAX(orders.o_totalprice):Float64;N, __correlated_expr_1:Int64]\
+        \n        Aggregate: groupBy=[[orders.o_custkey + Int32(1) AS __correlated_expr_1]], aggr=[[MAX(orders.o_totalprice)]] [__correlated_expr_1:Int64, MAX(orders.o_totalprice):Float64;N]\
+        \n          TableScan: orders [o_orderkey:Int64, o_custkey:Int64, o_orderstatus:Utf8, o_totalprice:Float64;N]";
+
+        assert_multi_rules_optimized_plan_eq_display_indent(
+            vec![Arc::new(ScalarSubqueryToJoin::new())],
+            plan,
+            expected,
+        );
+        Ok(())
+    }
+
     /// Test for correlated scalar subquery multiple projected columns
     #[test]
     fn scalar_subquery_multi_col() -> Result<()> {
@@ -49,6 +49,7 @@ use crate::simplify_expressions::regex::simplify_regex_expr;
 use crate::simplify_expressions::SimplifyInfo;
 
 use super::inlist_simplifier::ShortenInListSimplifier;
+use super::predicate_merger::merge_predicates;
 use super::utils::*;
 
 /// This structure handles API for expression simplification
@@ -1548,6 +1549,24 @@ impl<'a, S: SimplifyInfo> TreeNodeRewriter for Simplifier<'a, S> {
                 }
             }
 
+            // Merge the equalities and the ranges on the same expressions of a
+            // chain of AND or OR expressions, even if they are not next to
+            // each other
+            //
+            // i.e. `a = 1 OR b = 2 OR a = 3` -> `a IN (1, 3) OR b = 2`
+            //      `a > 1 AND b = 2 AND a > 3` -> `a > 3 AND b = 2`
+            Expr::BinaryExpr(BinaryExpr {
+                left,
+                op: op @ (Operator::And | Operator::Or),
+                right,
+            }) => {
+                let expr = Expr::BinaryExpr(BinaryExpr { left, op, right });
+                match merge_predicates(&expr, op)? {
+                    Some(merged) => Transformed::yes(merged),
+                    None => Transformed::no(expr),
+                }
+            }
+
             // no additional rewrites possible
             expr => Transformed::no(expr),
         })
@@ -3405,6 +3424,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn simplify_or_chain_and_ranges() {
+        // c3 = 1 OR c4 = 2 OR c3 = 3 OR c3 = 4 OR c3 = 5 -> c3 IN (1, 3, 4, 5) OR c4 = 2
+        let expr = col("c3")
+            .eq(lit(1i64))
+            .or(col("c4").eq(lit(2u32)))
+            .or(col("c3").eq(lit(3i64)))
+            .or(col("c3").eq(lit(4i64)))
+            .or(col("c3").eq(lit(5i64)));
+        assert_eq!(
+            simplify(expr),
+            in_list(
+                col("c3"),
+                vec![lit(1i64), lit(3i64), lit(4i64), lit(5i64)],
+                false
+            )
+            .or(col("c4").eq(lit(2u32))),
+        );
+
+        // c3 > 1 AND c4 = 2 AND c3 >= 3 AND c3 < 10 -> c3 >= 3 AND c4 = 2 AND c3 < 10
+        let expr = col("c3")
+            .gt(lit(1i64))
+            .and(col("c4").eq(lit(2u32)))
+            .and(col("c3").gt_eq(lit(3i64)))
+            .and(col("c3").lt(lit(10i64)));
+        assert_eq!(
+            simplify(expr),
+            col("c3")
+                .gt_eq(lit(3i64))
+                .and(col("c4").eq(lit(2u32)))
+                .and(col("c3").lt(lit(10i64))),
+        );
+    }
+
     #[test]
     fn simplify_expr_bool_and() {
         // col & true is always col
@@ -21,6 +21,7 @@
 pub mod expr_simplifier;
 mod guarantees;
 mod inlist_simplifier;
+mod predicate_merger;
 mod regex;
 pub mod simplify_exprs;
 mod utils;
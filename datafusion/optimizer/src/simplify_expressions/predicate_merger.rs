@@ -0,0 +1,306 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! This module merges the equalities and the ranges on the same expressions
+//! of the chains of `AND` and `OR` expressions
+
+use std::cmp::Ordering;
+
+use datafusion_common::{Result, ScalarValue};
+use datafusion_expr::expr::{BinaryExpr, InList};
+use datafusion_expr::utils::split_binary;
+use datafusion_expr::{Expr, Operator};
+
+/// A predicate which can be merged with the other predicates on its
+/// expression
+#[derive(Debug)]
+enum Mergeable<'a> {
+    /// `expr IN (list)`, or `expr = literal`, merged in a chain of `OR`
+    InList { expr: &'a Expr, list: Vec<&'a Expr> },
+    /// `expr op value`, with `op` one of `<`, `<=`, `>` and `>=`
+    Bound {
+        expr: &'a Expr,
+        op: Operator,
+        value: &'a ScalarValue,
+    },
+}
+
+impl<'a> Mergeable<'a> {
+    /// Returns `predicate` as a [`Mergeable`] in a chain of `chain_op`, if it
+    /// compares a non volatile expression with literals
+    fn try_new(predicate: &'a Expr, chain_op: Operator) -> Result<Option<Self>> {
+        let mergeable = match predicate {
+            Expr::InList(InList {
+                expr,
+                list,
+                negated: false,
+            }) if chain_op == Operator::Or
+                && list.iter().all(|e| matches!(e, Expr::Literal(_))) =>
+            {
+                Self::InList {
+                    expr,
+                    list: list.iter().collect(),
+                }
+            }
+            Expr::BinaryExpr(BinaryExpr { left, op, right }) => {
+                let (expr, op, literal) = match (left.as_ref(), right.as_ref()) {
+                    (_, Expr::Literal(_)) => (left.as_ref(), *op, right.as_ref()),
+                    (Expr::Literal(_), _) => match op.swap() {
+                        Some(op) => (right.as_ref(), op, left.as_ref()),
+                        None => return Ok(None),
+                    },
+                    _ => return Ok(None),
+                };
+                let Expr::Literal(value) = literal else {
+                    return Ok(None);
+                };
+                if value.is_null() {
+                    return Ok(None);
+                }
+                match op {
+                    Operator::Eq if chain_op == Operator::Or => Self::InList {
+                        expr,
+                        list: vec![literal],
+                    },
+                    Operator::Lt | Operator::LtEq | Operator::Gt | Operator::GtEq => {
+                        Self::Bound { expr, op, value }
+                    }
+                    _ => return Ok(None),
+                }
+            }
+            _ => return Ok(None),
+        };
+        let expr = match &mergeable {
+            Self::InList { expr, .. } | Self::Bound { expr, .. } => *expr,
+        };
+        if matches!(expr, Expr::Literal(_)) || expr.is_volatile()? {
+            return Ok(None);
+        }
+        Ok(Some(mergeable))
+    }
+
+    /// Merges `other` into this predicate, in a chain of `chain_op`. Returns
+    /// false if the predicates can't be merged.
+    fn merge(&mut self, other: &Mergeable<'a>, chain_op: Operator) -> bool {
+        match (self, other) {
+            // a IN (1, 2) OR a = 3 --> a IN (1, 2, 3)
+            (
+                Self::InList { expr, list },
+                Self::InList {
+                    expr: other_expr,
+                    list: other_list,
+                },
+            ) if *expr == *other_expr => {
+                for item in other_list {
+                    if !list.contains(item) {
+                        list.push(*item);
+                    }
+                }
+                true
+            }
+            // a > 1 AND a > 3 --> a > 3
+            // a > 1 OR a > 3 --> a > 1
+            (
+                Self::Bound { expr, op, value },
+                Self::Bound {
+                    expr: other_expr,
+                    op: other_op,
+                    value: other_value,
+                },
+            ) if *expr == *other_expr
+                && is_upper_bound(*op) == is_upper_bound(*other_op) =>
+            {
+                let Some(ordering) = (*value).partial_cmp(*other_value) else {
+                    return false;
+                };
+                let other_is_tighter = match ordering {
+                    Ordering::Less => !is_upper_bound(*op),
+                    Ordering::Greater => is_upper_bound(*op),
+                    Ordering::Equal => matches!(other_op, Operator::Lt | Operator::Gt),
+                };
+                if other_is_tighter == (chain_op == Operator::And) {
+                    *op = *other_op;
+                    *value = *other_value;
+                }
+                true
+            }
+            _ => false,
+        }
+    }
+
+    fn into_expr(self) -> Expr {
+        match self {
+            Self::InList { expr, list } if list.len() == 1 => {
+                Expr::BinaryExpr(BinaryExpr::new(
+                    Box::new(expr.clone()),
+                    Operator::Eq,
+                    Box::new(list[0].clone()),
+                ))
+            }
+            Self::InList { expr, list } => Expr::InList(InList::new(
+                Box::new(expr.clone()),
+                list.into_iter().cloned().collect(),
+                false,
+            )),
+            Self::Bound { expr, op, value } => Expr::BinaryExpr(BinaryExpr::new(
+                Box::new(expr.clone()),
+                op,
+                Box::new(Expr::Literal(value.clone())),
+            )),
+        }
+    }
+}
+
+fn is_upper_bound(op: Operator) -> bool {
+    matches!(op, Operator::Lt | Operator::LtEq)
+}
+
+/// Merges the predicates on the same expressions of `expr`, a chain of
+/// `chain_op`, which is either `AND` or `OR`, even if they are not next to
+/// each other. The merged predicates replace the first of them.
+///
+/// In a chain of `OR`, the equalities and the `IN` lists become a single `IN`
+/// list, and the lower or upper bounds the loosest of them:
+///
+/// `a = 1 OR b = 2 OR a = 3` --> `a IN (1, 3) OR b = 2`
+/// `a > 1 OR b = 2 OR a >= 3` --> `a > 1 OR b = 2`
+///
+/// In a chain of `AND`, the lower or upper bounds become the tightest of them:
+///
+/// `a < 5 AND b = 2 AND a <= 3` --> `a <= 3 AND b = 2`
+///
+/// Returns `None` if no predicates were merged.
+pub(super) fn merge_predicates(expr: &Expr, chain_op: Operator) -> Result<Option<Expr>> {
+    let predicates = split_binary(expr, chain_op);
+    // each predicate, with its merged predicate if it can be merged
+    let mut merged: Vec<(&Expr, Option<Mergeable>)> = vec![];
+    let mut changed = false;
+    for predicate in predicates {
+        let Some(mergeable) = Mergeable::try_new(predicate, chain_op)? else {
+            merged.push((predicate, None));
+            continue;
+        };
+        let merged_into = merged.iter_mut().any(|(_, previous)| {
+            previous
+                .as_mut()
+                .is_some_and(|previous| previous.merge(&mergeable, chain_op))
+        });
+        if merged_into {
+            changed = true;
+        } else {
+            merged.push((predicate, Some(mergeable)));
+        }
+    }
+    if !changed {
+        return Ok(None);
+    }
+
+    let merged = merged
+        .into_iter()
+        .map(|(predicate, mergeable)| match mergeable {
+            Some(mergeable) => mergeable.into_expr(),
+            None => predicate.clone(),
+        })
+        .reduce(|acc, e| {
+            Expr::BinaryExpr(BinaryExpr::new(Box::new(acc), chain_op, Box::new(e)))
+        });
+    Ok(merged)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use datafusion_expr::{col, in_list, lit};
+
+    fn merge(expr: Expr) -> Option<Expr> {
+        let op = match &expr {
+            Expr::BinaryExpr(BinaryExpr { op, .. }) => *op,
+            _ => unreachable!(),
+        };
+        merge_predicates(&expr, op).unwrap()
+    }
+
+    #[test]
+    fn merge_disjunctions() {
+        // a = 1 OR b = 2 OR a = 3 OR 4 = a --> a IN (1, 3, 4) OR b = 2
+        let expr = col("a")
+            .eq(lit(1))
+            .or(col("b").eq(lit(2)))
+            .or(col("a").eq(lit(3)))
+            .or(lit(4).eq(col("a")));
+        assert_eq!(
+            merge(expr),
+            Some(
+                in_list(col("a"), vec![lit(1), lit(3), lit(4)], false)
+                    .or(col("b").eq(lit(2)))
+            )
+        );
+
+        // a IN (1, 2) OR a = 2 --> a IN (1, 2)
+        let expr = in_list(col("a"), vec![lit(1), lit(2)], false).or(col("a").eq(lit(2)));
+        assert_eq!(
+            merge(expr),
+            Some(in_list(col("a"), vec![lit(1), lit(2)], false))
+        );
+
+        // a > 5 OR b = 2 OR a >= 3 OR a < 1 --> a >= 3 OR b = 2 OR a < 1
+        let expr = col("a")
+            .gt(lit(5))
+            .or(col("b").eq(lit(2)))
+            .or(col("a").gt_eq(lit(3)))
+            .or(col("a").lt(lit(1)));
+        assert_eq!(
+            merge(expr),
+            Some(
+                col("a")
+                    .gt_eq(lit(3))
+                    .or(col("b").eq(lit(2)))
+                    .or(col("a").lt(lit(1)))
+            )
+        );
+
+        // nothing to merge
+        let expr = col("a").eq(lit(1)).or(col("b").eq(lit(2)));
+        assert_eq!(merge(expr), None);
+        let expr = col("a").gt(lit(1)).or(col("a").gt(lit("x")));
+        assert_eq!(merge(expr), None);
+    }
+
+    #[test]
+    fn merge_conjunctions() {
+        // a < 5 AND b = 2 AND a <= 3 AND 1 < a AND a >= 1 --> a <= 3 AND b = 2 AND a > 1
+        let expr = col("a")
+            .lt(lit(5))
+            .and(col("b").eq(lit(2)))
+            .and(col("a").lt_eq(lit(3)))
+            .and(lit(1).lt(col("a")))
+            .and(col("a").gt_eq(lit(1)));
+        assert_eq!(
+            merge(expr),
+            Some(
+                col("a")
+                    .lt_eq(lit(3))
+                    .and(col("b").eq(lit(2)))
+                    .and(col("a").gt(lit(1)))
+            )
+        );
+
+        // the equalities are not merged in a chain of AND
+        let expr = col("a").eq(lit(1)).and(col("a").eq(lit(2)));
+        assert_eq!(merge(expr), None);
+    }
+}
@@ -15,6 +15,7 @@
 // specific language governing permissions and limitations
 // under the License.
 
+pub mod tdigest;
 pub mod utils;
 
 use arrow::datatypes::{DataType, Field, Schema};
@@ -64,6 +65,7 @@ pub fn create_aggregate_expr(
         fun: fun.clone(),
         args: input_phy_exprs.to_vec(),
         data_type: fun.return_type(&input_exprs_types)?,
+        input_types: input_exprs_types,
         name: name.into(),
         schema: schema.clone(),
         sort_exprs: sort_exprs.to_vec(),
@@ -153,6 +155,8 @@ pub struct AggregateFunctionExpr {
     args: Vec<Arc<dyn PhysicalExpr>>,
     /// Output / return type of this aggregate
     data_type: DataType,
+    /// The types of the arguments
+    input_types: Vec<DataType>,
     name: String,
     schema: Schema,
     // The logical order by expressions
@@ -168,6 +172,17 @@ impl AggregateFunctionExpr {
     pub fn fun(&self) -> &AggregateUDF {
         &self.fun
     }
+
+    /// The arguments of the accumulators of the `AggregateUDF`
+    fn accumulator_args(&self) -> AccumulatorArgs {
+        AccumulatorArgs::new(
+            &self.data_type,
+            &self.schema,
+            self.ignore_nulls,
+            &self.sort_exprs,
+            &self.input_types,
+        )
+    }
 }
 
 impl AggregateExpr for AggregateFunctionExpr {
@@ -193,14 +208,7 @@ impl AggregateExpr for AggregateFunctionExpr {
     }
 
     fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
-        let acc_args = AccumulatorArgs::new(
-            &self.data_type,
-            &self.schema,
-            self.ignore_nulls,
-            &self.sort_exprs,
-        );
-
-        self.fun.accumulator(acc_args)
+        self.fun.accumulator(self.accumulator_args())
     }
 
     fn create_sliding_accumulator(&self) -> Result<Box<dyn Accumulator>> {
@@ -267,7 +275,7 @@ impl AggregateExpr for AggregateFunctionExpr {
     }
 
     fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
-        self.fun.create_groups_accumulator()
+        self.fun.create_groups_accumulator(self.accumulator_args())
     }
 
     fn order_bys(&self) -> Option<&[PhysicalSortExpr]> {
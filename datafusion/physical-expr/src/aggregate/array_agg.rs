@@ -20,7 +20,7 @@
 use crate::aggregate::utils::down_cast_any_ref;
 use crate::expressions::format_state_name;
 use crate::{AggregateExpr, PhysicalExpr};
-use arrow::array::ArrayRef;
+use arrow::array::{make_array, ArrayRef, MutableArrayData};
 use arrow::datatypes::{DataType, Field};
 use arrow_array::Array;
 use datafusion_common::cast::as_list_array;
@@ -134,8 +134,9 @@ impl Accumulator for ArrayAggAccumulator {
             return Ok(());
         }
         assert!(values.len() == 1, "array_agg can only take 1 param!");
-        let val = values[0].clone();
-        self.values.push(val);
+        if !values[0].is_empty() {
+            self.values.push(compact(&values[0]));
+        }
         Ok(())
     }
 
@@ -148,7 +149,9 @@ impl Accumulator for ArrayAggAccumulator {
 
         let list_arr = as_list_array(&states[0])?;
         for arr in list_arr.iter().flatten() {
-            self.values.push(arr);
+            if !arr.is_empty() {
+                self.values.push(compact(&arr));
+            }
         }
         Ok(())
     }
@@ -187,6 +190,16 @@ impl Accumulator for ArrayAggAccumulator {
     }
 }
 
+/// Copies `array` into buffers of its own, as it may be a slice of the larger
+/// buffers of a batch, e.g. the values of a group, which would be kept alive
+/// and accounted for by the accumulator of each group of the batch
+fn compact(array: &ArrayRef) -> ArrayRef {
+    let data = array.to_data();
+    let mut copy = MutableArrayData::new(vec![&data], false, data.len());
+    copy.extend(0, 0, data.len());
+    make_array(copy.freeze())
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -304,4 +317,19 @@ mod tests {
             DataType::List(Arc::new(Field::new("item", DataType::Int32, true,)))
         )
     }
+
+    #[test]
+    fn array_agg_compacts_slices() -> Result<()> {
+        let array: ArrayRef = Arc::new(Int32Array::from_iter_values(0..10_000));
+        let mut accumulator = ArrayAggAccumulator::try_new(&DataType::Int32)?;
+        accumulator.update_batch(&[array.slice(10, 2)])?;
+
+        // Only the values of the slice are kept, not the whole array
+        assert!(accumulator.size() < 1000);
+        let expected = Int32Array::from(vec![10, 11]);
+        let expected =
+            ScalarValue::List(Arc::new(array_into_list_array(Arc::new(expected))));
+        assert_eq!(accumulator.evaluate()?, expected);
+        Ok(())
+    }
 }
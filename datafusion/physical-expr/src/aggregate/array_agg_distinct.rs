@@ -23,17 +23,20 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 use arrow::array::ArrayRef;
+use arrow::compute::{sort, SortOptions};
 use arrow::datatypes::{DataType, Field};
 use arrow_array::cast::AsArray;
 
 use crate::aggregate::utils::down_cast_any_ref;
 use crate::expressions::format_state_name;
-use crate::{AggregateExpr, PhysicalExpr};
+use crate::{AggregateExpr, LexOrdering, PhysicalExpr, PhysicalSortExpr};
 
+use datafusion_common::utils::array_into_list_array;
 use datafusion_common::{Result, ScalarValue};
 use datafusion_expr::Accumulator;
 
-/// Expression for a ARRAY_AGG(DISTINCT) aggregation.
+/// Expression for a ARRAY_AGG(DISTINCT) aggregation, whose distinct values
+/// may be ordered by themselves, as `ARRAY_AGG(DISTINCT a ORDER BY a DESC)`.
 #[derive(Debug)]
 pub struct DistinctArrayAgg {
     /// Column name
@@ -44,6 +47,9 @@ pub struct DistinctArrayAgg {
     expr: Arc<dyn PhysicalExpr>,
     /// If the input expression can have NULLs
     nullable: bool,
+    /// The ordering of the distinct values by the input expression, empty if
+    /// they are unordered
+    ordering_req: LexOrdering,
 }
 
 impl DistinctArrayAgg {
@@ -60,8 +66,22 @@ impl DistinctArrayAgg {
             input_data_type,
             expr,
             nullable,
+            ordering_req: vec![],
         }
     }
+
+    /// Orders the distinct values by `ordering_req`, which only sorts the
+    /// input expression
+    pub fn with_ordering_req(mut self, ordering_req: LexOrdering) -> Self {
+        self.ordering_req = ordering_req;
+        self
+    }
+
+    /// The ordering of the distinct values, which is not an ordering
+    /// requirement on the input as the values are sorted once aggregated
+    pub fn ordering_req(&self) -> &[PhysicalSortExpr] {
+        &self.ordering_req
+    }
 }
 
 impl AggregateExpr for DistinctArrayAgg {
@@ -82,6 +102,7 @@ impl AggregateExpr for DistinctArrayAgg {
     fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
         Ok(Box::new(DistinctArrayAggAccumulator::try_new(
             &self.input_data_type,
+            self.ordering_req.first().map(|sort_expr| sort_expr.options),
         )?))
     }
 
@@ -110,6 +131,7 @@ impl PartialEq<dyn Any> for DistinctArrayAgg {
                 self.name == x.name
                     && self.input_data_type == x.input_data_type
                     && self.expr.eq(&x.expr)
+                    && self.ordering_req == x.ordering_req
             })
             .unwrap_or(false)
     }
@@ -119,20 +141,29 @@ impl PartialEq<dyn Any> for DistinctArrayAgg {
 struct DistinctArrayAggAccumulator {
     values: HashSet<ScalarValue>,
     datatype: DataType,
+    /// How the values are sorted once evaluated, if they are
+    sort_options: Option<SortOptions>,
 }
 
 impl DistinctArrayAggAccumulator {
-    pub fn try_new(datatype: &DataType) -> Result<Self> {
+    pub fn try_new(
+        datatype: &DataType,
+        sort_options: Option<SortOptions>,
+    ) -> Result<Self> {
         Ok(Self {
             values: HashSet::new(),
             datatype: datatype.clone(),
+            sort_options,
         })
     }
 }
 
 impl Accumulator for DistinctArrayAggAccumulator {
     fn state(&mut self) -> Result<Vec<ScalarValue>> {
-        Ok(vec![self.evaluate()?])
+        // The partial values are merged as a set, they don't need to be sorted
+        let values: Vec<ScalarValue> = self.values.iter().cloned().collect();
+        let arr = ScalarValue::new_list(&values, &self.datatype);
+        Ok(vec![ScalarValue::List(arr)])
     }
 
     fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
@@ -163,8 +194,17 @@ impl Accumulator for DistinctArrayAggAccumulator {
 
     fn evaluate(&mut self) -> Result<ScalarValue> {
         let values: Vec<ScalarValue> = self.values.iter().cloned().collect();
-        let arr = ScalarValue::new_list(&values, &self.datatype);
-        Ok(ScalarValue::List(arr))
+        match self.sort_options {
+            Some(options) if !values.is_empty() => {
+                let array = ScalarValue::iter_to_array(values)?;
+                let array = sort(&array, Some(options))?;
+                Ok(ScalarValue::List(Arc::new(array_into_list_array(array))))
+            }
+            _ => {
+                let arr = ScalarValue::new_list(&values, &self.datatype);
+                Ok(ScalarValue::List(arr))
+            }
+        }
     }
 
     fn size(&self) -> usize {
@@ -434,4 +474,36 @@ mod tests {
 
         check_merge_distinct_array_agg(input1, input2, expected, DataType::Int32)
     }
+
+    #[test]
+    fn merge_ordered_distinct_array_agg() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+        let expr = col("a", &schema)?;
+        let agg = DistinctArrayAgg::new(expr.clone(), "bla", DataType::Int32, true)
+            .with_ordering_req(vec![PhysicalSortExpr {
+                expr,
+                options: SortOptions {
+                    descending: true,
+                    nulls_first: true,
+                },
+            }]);
+
+        let mut accum1 = agg.create_accumulator()?;
+        let mut accum2 = agg.create_accumulator()?;
+        accum1.update_batch(&[Arc::new(Int32Array::from(vec![
+            Some(1),
+            Some(5),
+            None,
+            Some(1),
+        ]))])?;
+        accum2.update_batch(&[Arc::new(Int32Array::from(vec![3, 5, 2]))])?;
+
+        let array = accum2.state()?[0].raw_data()?;
+        accum1.merge_batch(&[array])?;
+
+        let expected = Int32Array::from(vec![None, Some(5), Some(3), Some(2), Some(1)]);
+        let expected = array_into_list_array(Arc::new(expected));
+        assert_eq!(accum1.evaluate()?, ScalarValue::List(Arc::new(expected)));
+        Ok(())
+    }
 }
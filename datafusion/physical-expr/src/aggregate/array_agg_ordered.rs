@@ -292,9 +292,12 @@ impl Accumulator for OrderSensitiveArrayAggAccumulator {
     }
 
     fn state(&mut self) -> Result<Vec<ScalarValue>> {
-        let mut result = vec![self.evaluate()?];
-        result.push(self.evaluate_orderings()?);
-        Ok(result)
+        // The values stay in the order of `ordering_req`, as their orderings,
+        // even when running in reverse: only the final result is reversed.
+        let values = self.values.clone();
+        let array =
+            ScalarValue::new_list_from_iter(values.into_iter(), &self.datatypes[0]);
+        Ok(vec![ScalarValue::List(array), self.evaluate_orderings()?])
     }
 
     fn evaluate(&mut self) -> Result<ScalarValue> {
@@ -542,12 +545,17 @@ mod tests {
     use std::collections::VecDeque;
     use std::sync::Arc;
 
-    use crate::aggregate::array_agg_ordered::merge_ordered_arrays;
+    use crate::aggregate::array_agg_ordered::{
+        merge_ordered_arrays, OrderSensitiveArrayAggAccumulator,
+    };
+    use crate::expressions::col;
+    use crate::PhysicalSortExpr;
 
-    use arrow_array::{Array, ArrayRef, Int64Array};
-    use arrow_schema::SortOptions;
+    use arrow_array::{Array, ArrayRef, Int64Array, StringArray};
+    use arrow_schema::{DataType, Field, Schema, SortOptions};
     use datafusion_common::utils::get_row_at_idx;
     use datafusion_common::{Result, ScalarValue};
+    use datafusion_expr::Accumulator;
 
     #[test]
     fn test_merge_asc() -> Result<()> {
@@ -681,4 +689,54 @@ mod tests {
         assert_eq!(&merged_ts, &expected_ts);
         Ok(())
     }
+
+    #[test]
+    fn test_merge_reverse_states() -> Result<()> {
+        // `ARRAY_AGG(v ORDER BY ts)` running in reverse over inputs ordered by
+        // `ts DESC`
+        let schema = Schema::new(vec![Field::new("ts", DataType::Int64, false)]);
+        let ordering_req = vec![PhysicalSortExpr {
+            expr: col("ts", &schema)?,
+            options: SortOptions {
+                descending: true,
+                nulls_first: true,
+            },
+        }];
+        let accumulator = || {
+            OrderSensitiveArrayAggAccumulator::try_new(
+                &DataType::Utf8,
+                &[DataType::Int64],
+                ordering_req.clone(),
+                true,
+            )
+        };
+
+        let mut partial1 = accumulator()?;
+        partial1.update_batch(&[
+            Arc::new(StringArray::from(vec!["c", "a"])),
+            Arc::new(Int64Array::from(vec![3, 1])),
+        ])?;
+        let mut partial2 = accumulator()?;
+        partial2.update_batch(&[
+            Arc::new(StringArray::from(vec!["d", "b"])),
+            Arc::new(Int64Array::from(vec![4, 2])),
+        ])?;
+
+        let mut result = accumulator()?;
+        for partial in [&mut partial1, &mut partial2] {
+            let states = partial
+                .state()?
+                .iter()
+                .map(|state| state.to_array())
+                .collect::<Result<Vec<_>>>()?;
+            result.merge_batch(&states)?;
+        }
+
+        let expected = ScalarValue::List(ScalarValue::new_list_from_iter(
+            ["a", "b", "c", "d"].map(ScalarValue::from).into_iter(),
+            &DataType::Utf8,
+        ));
+        assert_eq!(result.evaluate()?, expected);
+        Ok(())
+    }
 }
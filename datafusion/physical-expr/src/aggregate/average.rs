@@ -24,8 +24,8 @@ use std::any::Any;
 use std::fmt::Debug;
 use std::sync::Arc;
 
-use crate::aggregate::groups_accumulator::accumulate::NullState;
-use crate::aggregate::utils::down_cast_any_ref;
+use crate::aggregate::groups_accumulator::accumulate::{filtered_null_mask, NullState};
+use crate::aggregate::utils::{down_cast_any_ref, DecimalOverflowChecker};
 use crate::expressions::format_state_name;
 use crate::{AggregateExpr, PhysicalExpr};
 use arrow::compute::sum;
@@ -35,10 +35,9 @@ use arrow::{
     datatypes::Field,
 };
 use arrow_array::types::{Decimal256Type, DecimalType};
-use arrow_array::{
-    Array, ArrowNativeTypeOp, ArrowNumericType, ArrowPrimitiveType, PrimitiveArray,
-};
+use arrow_array::{Array, ArrowNumericType, ArrowPrimitiveType, PrimitiveArray};
 use arrow_buffer::{i256, ArrowNativeType};
+use datafusion_common::config::DecimalOverflowMode;
 use datafusion_common::{not_impl_err, Result, ScalarValue};
 use datafusion_expr::type_coercion::aggregates::avg_return_type;
 use datafusion_expr::{Accumulator, EmitTo, GroupsAccumulator};
@@ -52,6 +51,9 @@ pub struct Avg {
     expr: Arc<dyn PhysicalExpr>,
     input_data_type: DataType,
     result_data_type: DataType,
+    /// How decimal sums and averages that do not fit in their data type are
+    /// handled
+    overflow_mode: DecimalOverflowMode,
 }
 
 impl Avg {
@@ -68,8 +70,16 @@ impl Avg {
             expr,
             input_data_type: data_type,
             result_data_type,
+            overflow_mode: DecimalOverflowMode::default(),
         }
     }
+
+    /// Set how decimal sums and averages that do not fit in their data type
+    /// are handled
+    pub fn with_overflow_mode(mut self, overflow_mode: DecimalOverflowMode) -> Self {
+        self.overflow_mode = overflow_mode;
+        self
+    }
 }
 
 impl AggregateExpr for Avg {
@@ -97,6 +107,7 @@ impl AggregateExpr for Avg {
                 sum_precision: *sum_precision,
                 target_precision: *target_precision,
                 target_scale: *target_scale,
+                overflow_mode: self.overflow_mode,
             })),
 
             (
@@ -109,6 +120,7 @@ impl AggregateExpr for Avg {
                 sum_precision: *sum_precision,
                 target_precision: *target_precision,
                 target_scale: *target_scale,
+                overflow_mode: self.overflow_mode,
             })),
             _ => not_impl_err!(
                 "AvgAccumulator for ({} --> {})",
@@ -152,7 +164,10 @@ impl AggregateExpr for Avg {
     fn groups_accumulator_supported(&self) -> bool {
         use DataType::*;
 
-        matches!(&self.result_data_type, Float64 | Decimal128(_, _))
+        matches!(
+            &self.result_data_type,
+            Float64 | Decimal128(_, _) | Decimal256(_, _)
+        )
     }
 
     fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
@@ -160,10 +175,11 @@ impl AggregateExpr for Avg {
         // instantiate specialized accumulator based for the type
         match (&self.input_data_type, &self.result_data_type) {
             (Float64, Float64) => {
-                Ok(Box::new(AvgGroupsAccumulator::<Float64Type, _>::new(
+                Ok(Box::new(AvgGroupsAccumulator::<Float64Type, _, _>::new(
                     &self.input_data_type,
                     &self.result_data_type,
                     |sum: f64, count: u64| Ok(sum / count as f64),
+                    |sum: f64, value: f64| Ok(sum + value),
                 )))
             }
             (
@@ -176,13 +192,23 @@ impl AggregateExpr for Avg {
                     *target_scale,
                 )?;
 
-                let avg_fn =
-                    move |sum: i128, count: u64| decimal_averager.avg(sum, count as i128);
+                let target_checker = DecimalOverflowChecker::<Decimal128Type>::new(
+                    *target_precision,
+                    self.overflow_mode,
+                );
+                let avg_fn = move |sum: i128, count: u64| {
+                    target_checker.avg(&decimal_averager, sum, count as i128)
+                };
+                let sum_checker = DecimalOverflowChecker::<Decimal128Type>::new_native(
+                    self.overflow_mode,
+                );
+                let sum_fn = move |sum: i128, value: i128| sum_checker.add(sum, value);
 
-                Ok(Box::new(AvgGroupsAccumulator::<Decimal128Type, _>::new(
+                Ok(Box::new(AvgGroupsAccumulator::<Decimal128Type, _, _>::new(
                     &self.input_data_type,
                     &self.result_data_type,
                     avg_fn,
+                    sum_fn,
                 )))
             }
 
@@ -196,14 +222,27 @@ impl AggregateExpr for Avg {
                     *target_scale,
                 )?;
 
+                let target_checker = DecimalOverflowChecker::<Decimal256Type>::new(
+                    *target_precision,
+                    self.overflow_mode,
+                );
                 let avg_fn = move |sum: i256, count: u64| {
-                    decimal_averager.avg(sum, i256::from_usize(count as usize).unwrap())
+                    target_checker.avg(
+                        &decimal_averager,
+                        sum,
+                        i256::from_usize(count as usize).unwrap(),
+                    )
                 };
+                let sum_checker = DecimalOverflowChecker::<Decimal256Type>::new_native(
+                    self.overflow_mode,
+                );
+                let sum_fn = move |sum: i256, value: i256| sum_checker.add(sum, value);
 
-                Ok(Box::new(AvgGroupsAccumulator::<Decimal256Type, _>::new(
+                Ok(Box::new(AvgGroupsAccumulator::<Decimal256Type, _, _>::new(
                     &self.input_data_type,
                     &self.result_data_type,
                     avg_fn,
+                    sum_fn,
                 )))
             }
 
@@ -224,6 +263,7 @@ impl PartialEq<dyn Any> for Avg {
                 self.name == x.name
                     && self.input_data_type == x.input_data_type
                     && self.result_data_type == x.result_data_type
+                    && self.overflow_mode == x.overflow_mode
                     && self.expr.eq(&x.expr)
             })
             .unwrap_or(false)
@@ -298,6 +338,7 @@ struct DecimalAvgAccumulator<T: DecimalType + ArrowNumericType> {
     sum_precision: u8,
     target_precision: u8,
     target_scale: i8,
+    overflow_mode: DecimalOverflowMode,
 }
 
 impl<T: DecimalType + ArrowNumericType> Debug for DecimalAvgAccumulator<T> {
@@ -309,6 +350,7 @@ impl<T: DecimalType + ArrowNumericType> Debug for DecimalAvgAccumulator<T> {
             .field("sum_precision", &self.sum_precision)
             .field("target_precision", &self.target_precision)
             .field("target_scale", &self.target_scale)
+            .field("overflow_mode", &self.overflow_mode)
             .finish()
     }
 }
@@ -328,19 +370,21 @@ impl<T: DecimalType + ArrowNumericType> Accumulator for DecimalAvgAccumulator<T>
         let values = values[0].as_primitive::<T>();
 
         self.count += (values.len() - values.null_count()) as u64;
-        if let Some(x) = sum(values) {
-            let v = self.sum.get_or_insert(T::Native::default());
-            self.sum = Some(v.add_wrapping(x));
-        }
-        Ok(())
+        self.add_values(values)
     }
 
     fn retract_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
         let values = values[0].as_primitive::<T>();
         self.count -= (values.len() - values.null_count()) as u64;
-        if let Some(x) = sum(values) {
-            self.sum = Some(self.sum.unwrap().sub_wrapping(x));
+        if values.null_count() == values.len() {
+            return Ok(());
         }
+        let checker = DecimalOverflowChecker::<T>::new_native(self.overflow_mode);
+        let mut sum = self.sum.unwrap_or_default();
+        for value in values.iter().flatten() {
+            sum = checker.sub_exact(sum, value)?;
+        }
+        self.sum = Some(sum);
         Ok(())
     }
 
@@ -349,23 +393,27 @@ impl<T: DecimalType + ArrowNumericType> Accumulator for DecimalAvgAccumulator<T>
         self.count += sum(states[0].as_primitive::<UInt64Type>()).unwrap_or_default();
 
         // sums are summed
-        if let Some(x) = sum(states[1].as_primitive::<T>()) {
-            let v = self.sum.get_or_insert(T::Native::default());
-            self.sum = Some(v.add_wrapping(x));
-        }
-        Ok(())
+        self.add_values(states[1].as_primitive::<T>())
     }
 
     fn evaluate(&mut self) -> Result<ScalarValue> {
         let v = self
             .sum
             .map(|v| {
-                DecimalAverager::<T>::try_new(
+                let averager = DecimalAverager::<T>::try_new(
                     self.sum_scale,
                     self.target_precision,
                     self.target_scale,
-                )?
-                .avg(v, T::Native::from_usize(self.count as usize).unwrap())
+                )?;
+                DecimalOverflowChecker::<T>::new(
+                    self.target_precision,
+                    self.overflow_mode,
+                )
+                .avg(
+                    &averager,
+                    v,
+                    T::Native::from_usize(self.count as usize).unwrap(),
+                )
             })
             .transpose()?;
 
@@ -383,16 +431,38 @@ impl<T: DecimalType + ArrowNumericType> Accumulator for DecimalAvgAccumulator<T>
     }
 }
 
+impl<T: DecimalType + ArrowNumericType> DecimalAvgAccumulator<T> {
+    /// Adds the valid `values` to the sum, checking for overflows
+    ///
+    /// The sum is not saturated, as values are retracted from it in sliding
+    /// windows, only the average is.
+    fn add_values(&mut self, values: &PrimitiveArray<T>) -> Result<()> {
+        if values.null_count() == values.len() {
+            return Ok(());
+        }
+        let checker = DecimalOverflowChecker::<T>::new_native(self.overflow_mode);
+        let mut sum = self.sum.unwrap_or_default();
+        for value in values.iter().flatten() {
+            sum = checker.add_exact(sum, value)?;
+        }
+        self.sum = Some(sum);
+        Ok(())
+    }
+}
+
 /// An accumulator to compute the average of `[PrimitiveArray<T>]`.
 /// Stores values as native types, and does overflow checking
 ///
 /// F: Function that calculates the average value from a sum of
 /// T::Native and a total count
+///
+/// S: Function that adds a value to a sum of T::Native
 #[derive(Debug)]
-struct AvgGroupsAccumulator<T, F>
+struct AvgGroupsAccumulator<T, F, S>
 where
     T: ArrowNumericType + Send,
     F: Fn(T::Native, u64) -> Result<T::Native> + Send,
+    S: Fn(T::Native, T::Native) -> Result<T::Native> + Send + Sync,
 {
     /// The type of the internal sum
     sum_data_type: DataType,
@@ -411,14 +481,23 @@ where
 
     /// Function that computes the final average (value / count)
     avg_fn: F,
+
+    /// Function that adds a value to a sum
+    sum_fn: S,
 }
 
-impl<T, F> AvgGroupsAccumulator<T, F>
+impl<T, F, S> AvgGroupsAccumulator<T, F, S>
 where
     T: ArrowNumericType + Send,
     F: Fn(T::Native, u64) -> Result<T::Native> + Send,
+    S: Fn(T::Native, T::Native) -> Result<T::Native> + Send + Sync,
 {
-    pub fn new(sum_data_type: &DataType, return_data_type: &DataType, avg_fn: F) -> Self {
+    pub fn new(
+        sum_data_type: &DataType,
+        return_data_type: &DataType,
+        avg_fn: F,
+        sum_fn: S,
+    ) -> Self {
         debug!(
             "AvgGroupsAccumulator ({}, sum type: {sum_data_type:?}) --> {return_data_type:?}",
             std::any::type_name::<T>()
@@ -431,14 +510,16 @@ where
             sums: vec![],
             null_state: NullState::new(),
             avg_fn,
+            sum_fn,
         }
     }
 }
 
-impl<T, F> GroupsAccumulator for AvgGroupsAccumulator<T, F>
+impl<T, F, S> GroupsAccumulator for AvgGroupsAccumulator<T, F, S>
 where
     T: ArrowNumericType + Send,
     F: Fn(T::Native, u64) -> Result<T::Native> + Send,
+    S: Fn(T::Native, T::Native) -> Result<T::Native> + Send + Sync,
 {
     fn update_batch(
         &mut self,
@@ -453,6 +534,7 @@ where
         // increment counts, update sums
         self.counts.resize(total_num_groups, 0);
         self.sums.resize(total_num_groups, T::default_value());
+        let mut result = Ok(());
         self.null_state.accumulate(
             group_indices,
             values,
@@ -460,13 +542,16 @@ where
             total_num_groups,
             |group_index, new_value| {
                 let sum = &mut self.sums[group_index];
-                *sum = sum.add_wrapping(new_value);
+                match (self.sum_fn)(*sum, new_value) {
+                    Ok(new_sum) => *sum = new_sum,
+                    Err(e) => result = Err(e),
+                }
 
                 self.counts[group_index] += 1;
             },
         );
 
-        Ok(())
+        result
     }
 
     fn merge_batch(
@@ -494,6 +579,7 @@ where
 
         // update sums
         self.sums.resize(total_num_groups, T::default_value());
+        let mut result = Ok(());
         self.null_state.accumulate(
             group_indices,
             partial_sums,
@@ -501,11 +587,14 @@ where
             total_num_groups,
             |group_index, new_value: <T as ArrowPrimitiveType>::Native| {
                 let sum = &mut self.sums[group_index];
-                *sum = sum.add_wrapping(new_value);
+                match (self.sum_fn)(*sum, new_value) {
+                    Ok(new_sum) => *sum = new_sum,
+                    Err(e) => result = Err(e),
+                }
             },
         );
 
-        Ok(())
+        result
     }
 
     fn evaluate(&mut self, emit_to: EmitTo) -> Result<ArrayRef> {
@@ -562,6 +651,31 @@ where
         ])
     }
 
+    fn convert_to_state(
+        &self,
+        values: &[ArrayRef],
+        opt_filter: Option<&arrow_array::BooleanArray>,
+    ) -> Result<Vec<ArrayRef>> {
+        assert_eq!(values.len(), 1, "single argument to convert_to_state");
+        let values = values[0].as_primitive::<T>();
+
+        // Each row is a group with a count of one and its value as sum,
+        // both null if the row is null or filtered out
+        let nulls = filtered_null_mask(opt_filter, values);
+        let counts = UInt64Array::new(vec![1; values.len()].into(), nulls.clone());
+        let sums = PrimitiveArray::<T>::new(values.values().clone(), nulls)
+            .with_data_type(self.sum_data_type.clone());
+
+        Ok(vec![
+            Arc::new(counts) as ArrayRef,
+            Arc::new(sums) as ArrayRef,
+        ])
+    }
+
+    fn supports_convert_to_state(&self) -> bool {
+        true
+    }
+
     fn size(&self) -> usize {
         self.counts.capacity() * std::mem::size_of::<u64>()
             + self.sums.capacity() * std::mem::size_of::<T>()
@@ -30,7 +30,7 @@ use std::sync::Arc;
 
 use arrow::datatypes::Schema;
 
-use datafusion_common::{exec_err, not_impl_err, Result};
+use datafusion_common::{exec_err, not_impl_err, plan_err, Result};
 use datafusion_expr::AggregateFunction;
 
 use crate::aggregate::regr::RegrType;
@@ -135,19 +135,26 @@ pub fn create_aggregate_expr(
             }
         }
         (AggregateFunction::ArrayAgg, true) => {
-            if !ordering_req.is_empty() {
-                return not_impl_err!(
-                    "ARRAY_AGG(DISTINCT ORDER BY a ASC) order-sensitive aggregations are not available"
+            let expr = input_phy_exprs[0].clone();
+            // The distinct values can only be ordered by themselves
+            if ordering_req
+                .iter()
+                .any(|sort_expr| !sort_expr.expr.eq(&expr))
+            {
+                return plan_err!(
+                    "In an aggregate with DISTINCT, ORDER BY expressions must appear in argument list"
                 );
             }
-            let expr = input_phy_exprs[0].clone();
             let is_expr_nullable = expr.nullable(input_schema)?;
-            Arc::new(expressions::DistinctArrayAgg::new(
-                expr,
-                name,
-                data_type,
-                is_expr_nullable,
-            ))
+            Arc::new(
+                expressions::DistinctArrayAgg::new(
+                    expr,
+                    name,
+                    data_type,
+                    is_expr_nullable,
+                )
+                .with_ordering_req(ordering_req.to_vec()),
+            )
         }
         (AggregateFunction::Min, _) => Arc::new(expressions::Min::new(
             input_phy_exprs[0].clone(),
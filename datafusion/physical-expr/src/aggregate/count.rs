@@ -38,7 +38,7 @@ use datafusion_expr::{Accumulator, EmitTo, GroupsAccumulator};
 
 use crate::expressions::format_state_name;
 
-use super::groups_accumulator::accumulate::accumulate_indices;
+use super::groups_accumulator::accumulate::{accumulate_indices, filtered_null_mask};
 
 /// COUNT aggregate expression
 /// Returns the amount of non-null values of the given expression.
@@ -186,6 +186,29 @@ impl GroupsAccumulator for CountGroupsAccumulator {
         Ok(vec![Arc::new(counts) as ArrayRef])
     }
 
+    fn convert_to_state(
+        &self,
+        values: &[ArrayRef],
+        opt_filter: Option<&arrow_array::BooleanArray>,
+    ) -> Result<Vec<ArrayRef>> {
+        assert_eq!(values.len(), 1, "single argument to convert_to_state");
+        let values = &values[0];
+
+        // Each row counts as one unless it is null or filtered out.
+        // Intermediate counts are always non null
+        let counts: Int64Array = match filtered_null_mask(opt_filter, values) {
+            Some(nulls) => {
+                Int64Array::from_iter_values(nulls.iter().map(|is_valid| is_valid as i64))
+            }
+            None => vec![1; values.len()].into(),
+        };
+        Ok(vec![Arc::new(counts) as ArrayRef])
+    }
+
+    fn supports_convert_to_state(&self) -> bool {
+        true
+    }
+
     fn size(&self) -> usize {
         self.counts.capacity() * std::mem::size_of::<usize>()
     }
@@ -442,6 +442,24 @@ pub fn accumulate_indices<F>(
     }
 }
 
+/// Returns the validity mask of `input` combined with `opt_filter`: a
+/// row is valid only if it is not null and passes the filter.
+///
+/// This is the validity of the intermediate state produced by
+/// [`GroupsAccumulator::convert_to_state`] for the rows of `input`.
+///
+/// [`GroupsAccumulator::convert_to_state`]: datafusion_expr::GroupsAccumulator::convert_to_state
+pub fn filtered_null_mask(
+    opt_filter: Option<&BooleanArray>,
+    input: &dyn Array,
+) -> Option<NullBuffer> {
+    let filter_nulls = opt_filter.map(|filter| match filter.nulls() {
+        Some(nulls) => NullBuffer::new(filter.values() & nulls.inner()),
+        None => NullBuffer::new(filter.values().clone()),
+    });
+    NullBuffer::union(input.logical_nulls().as_ref(), filter_nulls.as_ref())
+}
+
 /// Ensures that `builder` contains a `BooleanBufferBuilder with at
 /// least `total_num_groups`.
 ///
@@ -23,7 +23,7 @@ use arrow_schema::DataType;
 use datafusion_common::Result;
 use datafusion_expr::{EmitTo, GroupsAccumulator};
 
-use super::accumulate::NullState;
+use super::accumulate::{filtered_null_mask, NullState};
 
 /// An accumulator that implements a single operation over
 /// [`ArrowPrimitiveType`] where the accumulated state is the same as
@@ -134,6 +134,31 @@ where
         self.update_batch(values, group_indices, opt_filter, total_num_groups)
     }
 
+    fn convert_to_state(
+        &self,
+        values: &[ArrayRef],
+        opt_filter: Option<&BooleanArray>,
+    ) -> Result<Vec<ArrayRef>> {
+        assert_eq!(values.len(), 1, "single argument to convert_to_state");
+        let values = values[0].as_primitive::<T>();
+
+        // The state of each row is the starting value updated with the
+        // row value, or null if the row is null or filtered out
+        let states = values.values().iter().map(|value| {
+            let mut state = self.starting_value;
+            (self.prim_fn)(&mut state, *value);
+            state
+        });
+        let nulls = filtered_null_mask(opt_filter, values);
+        let states = PrimitiveArray::<T>::new(states.collect(), nulls)
+            .with_data_type(self.data_type.clone());
+        Ok(vec![Arc::new(states)])
+    }
+
+    fn supports_convert_to_state(&self) -> bool {
+        true
+    }
+
     fn size(&self) -> usize {
         self.values.capacity() * std::mem::size_of::<T::Native>() + self.null_state.size()
     }
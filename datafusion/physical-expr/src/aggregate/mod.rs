@@ -21,8 +21,7 @@ use crate::expressions::{NthValueAgg, OrderSensitiveArrayAgg};
 
 pub use datafusion_physical_expr_common::aggregate::AggregateExpr;
 
-mod hyperloglog;
-mod tdigest;
+use datafusion_physical_expr_common::aggregate::tdigest;
 
 pub(crate) mod approx_distinct;
 pub(crate) mod approx_median;
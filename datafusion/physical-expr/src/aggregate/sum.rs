@@ -20,8 +20,9 @@
 use std::any::Any;
 use std::sync::Arc;
 
+use super::groups_accumulator::accumulate::{filtered_null_mask, NullState};
 use super::groups_accumulator::prim_op::PrimitiveGroupsAccumulator;
-use crate::aggregate::utils::down_cast_any_ref;
+use crate::aggregate::utils::{down_cast_any_ref, DecimalOverflowChecker};
 use crate::expressions::format_state_name;
 use crate::{AggregateExpr, PhysicalExpr};
 use arrow::compute::sum;
@@ -29,13 +30,16 @@ use arrow::datatypes::DataType;
 use arrow::{array::ArrayRef, datatypes::Field};
 use arrow_array::cast::AsArray;
 use arrow_array::types::{
-    Decimal128Type, Decimal256Type, Float64Type, Int64Type, UInt64Type,
+    Decimal128Type, Decimal256Type, DecimalType, Float64Type, Int64Type, UInt64Type,
+};
+use arrow_array::{
+    Array, ArrowNativeTypeOp, ArrowNumericType, BooleanArray, PrimitiveArray,
 };
-use arrow_array::{Array, ArrowNativeTypeOp, ArrowNumericType};
 use arrow_buffer::ArrowNativeType;
+use datafusion_common::config::DecimalOverflowMode;
 use datafusion_common::{not_impl_err, Result, ScalarValue};
 use datafusion_expr::type_coercion::aggregates::sum_return_type;
-use datafusion_expr::{Accumulator, GroupsAccumulator};
+use datafusion_expr::{Accumulator, EmitTo, GroupsAccumulator};
 
 /// SUM aggregate expression
 #[derive(Debug, Clone)]
@@ -47,6 +51,8 @@ pub struct Sum {
     return_type: DataType,
     expr: Arc<dyn PhysicalExpr>,
     nullable: bool,
+    /// How decimal sums that do not fit in `return_type` are handled
+    overflow_mode: DecimalOverflowMode,
 }
 
 impl Sum {
@@ -63,8 +69,15 @@ impl Sum {
             return_type,
             expr,
             nullable: true,
+            overflow_mode: DecimalOverflowMode::default(),
         }
     }
+
+    /// Set how decimal sums that do not fit in their data type are handled
+    pub fn with_overflow_mode(mut self, overflow_mode: DecimalOverflowMode) -> Self {
+        self.overflow_mode = overflow_mode;
+        self
+    }
 }
 
 /// Sum only supports a subset of numeric types, instead relying on type coercion
@@ -101,12 +114,28 @@ impl AggregateExpr for Sum {
     }
 
     fn create_accumulator(&self) -> Result<Box<dyn Accumulator>> {
-        macro_rules! helper {
-            ($t:ty, $dt:expr) => {
-                Ok(Box::new(SumAccumulator::<$t>::new($dt.clone())))
-            };
+        match self.return_type {
+            DataType::Decimal128(precision, _) => {
+                Ok(Box::new(DecimalSumAccumulator::<Decimal128Type>::new(
+                    self.return_type.clone(),
+                    DecimalOverflowChecker::new(precision, self.overflow_mode),
+                )))
+            }
+            DataType::Decimal256(precision, _) => {
+                Ok(Box::new(DecimalSumAccumulator::<Decimal256Type>::new(
+                    self.return_type.clone(),
+                    DecimalOverflowChecker::new(precision, self.overflow_mode),
+                )))
+            }
+            _ => {
+                macro_rules! helper {
+                    ($t:ty, $dt:expr) => {
+                        Ok(Box::new(SumAccumulator::<$t>::new($dt.clone())))
+                    };
+                }
+                downcast_sum!(self, helper)
+            }
         }
-        downcast_sum!(self, helper)
     }
 
     fn state_fields(&self) -> Result<Vec<Field>> {
@@ -130,15 +159,31 @@ impl AggregateExpr for Sum {
     }
 
     fn create_groups_accumulator(&self) -> Result<Box<dyn GroupsAccumulator>> {
-        macro_rules! helper {
-            ($t:ty, $dt:expr) => {
-                Ok(Box::new(PrimitiveGroupsAccumulator::<$t, _>::new(
-                    &$dt,
-                    |x, y| *x = x.add_wrapping(y),
-                )))
-            };
+        match self.return_type {
+            DataType::Decimal128(precision, _) => Ok(Box::new(
+                DecimalSumGroupsAccumulator::<Decimal128Type>::new(
+                    &self.return_type,
+                    DecimalOverflowChecker::new(precision, self.overflow_mode),
+                ),
+            )),
+            DataType::Decimal256(precision, _) => Ok(Box::new(
+                DecimalSumGroupsAccumulator::<Decimal256Type>::new(
+                    &self.return_type,
+                    DecimalOverflowChecker::new(precision, self.overflow_mode),
+                ),
+            )),
+            _ => {
+                macro_rules! helper {
+                    ($t:ty, $dt:expr) => {
+                        Ok(Box::new(PrimitiveGroupsAccumulator::<$t, _>::new(
+                            &$dt,
+                            |x, y| *x = x.add_wrapping(y),
+                        )))
+                    };
+                }
+                downcast_sum!(self, helper)
+            }
         }
-        downcast_sum!(self, helper)
     }
 
     fn reverse_expr(&self) -> Option<Arc<dyn AggregateExpr>> {
@@ -146,12 +191,28 @@ impl AggregateExpr for Sum {
     }
 
     fn create_sliding_accumulator(&self) -> Result<Box<dyn Accumulator>> {
-        macro_rules! helper {
-            ($t:ty, $dt:expr) => {
-                Ok(Box::new(SlidingSumAccumulator::<$t>::new($dt.clone())))
-            };
+        match self.return_type {
+            DataType::Decimal128(precision, _) => Ok(Box::new(
+                SlidingDecimalSumAccumulator::<Decimal128Type>::new(
+                    self.return_type.clone(),
+                    DecimalOverflowChecker::new(precision, self.overflow_mode),
+                ),
+            )),
+            DataType::Decimal256(precision, _) => Ok(Box::new(
+                SlidingDecimalSumAccumulator::<Decimal256Type>::new(
+                    self.return_type.clone(),
+                    DecimalOverflowChecker::new(precision, self.overflow_mode),
+                ),
+            )),
+            _ => {
+                macro_rules! helper {
+                    ($t:ty, $dt:expr) => {
+                        Ok(Box::new(SlidingSumAccumulator::<$t>::new($dt.clone())))
+                    };
+                }
+                downcast_sum!(self, helper)
+            }
         }
-        downcast_sum!(self, helper)
     }
 }
 
@@ -163,6 +224,7 @@ impl PartialEq<dyn Any> for Sum {
                 self.name == x.name
                     && self.data_type == x.data_type
                     && self.nullable == x.nullable
+                    && self.overflow_mode == x.overflow_mode
                     && self.expr.eq(&x.expr)
             })
             .unwrap_or(false)
@@ -217,6 +279,178 @@ impl<T: ArrowNumericType> Accumulator for SumAccumulator<T> {
     }
 }
 
+/// This accumulator computes SUM of decimals incrementally, checking that the
+/// sum fits in its data type
+struct DecimalSumAccumulator<T: DecimalType> {
+    sum: Option<T::Native>,
+    data_type: DataType,
+    checker: DecimalOverflowChecker<T>,
+}
+
+impl<T: DecimalType> std::fmt::Debug for DecimalSumAccumulator<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "DecimalSumAccumulator({})", self.data_type)
+    }
+}
+
+impl<T: DecimalType> DecimalSumAccumulator<T> {
+    fn new(data_type: DataType, checker: DecimalOverflowChecker<T>) -> Self {
+        Self {
+            sum: None,
+            data_type,
+            checker,
+        }
+    }
+}
+
+impl<T: DecimalType> Accumulator for DecimalSumAccumulator<T> {
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        Ok(vec![ScalarValue::new_primitive::<T>(
+            self.sum,
+            &self.data_type,
+        )?])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = values[0].as_primitive::<T>();
+        if values.null_count() == values.len() {
+            return Ok(());
+        }
+        let mut sum = self.sum.unwrap_or(T::Native::usize_as(0));
+        for value in values.iter().flatten() {
+            sum = self.checker.add(sum, value)?;
+        }
+        self.sum = Some(sum);
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        self.update_batch(states)
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let sum = self.sum.map(|sum| self.checker.check(sum)).transpose()?;
+        ScalarValue::new_primitive::<T>(sum, &self.data_type)
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+}
+
+/// A [`GroupsAccumulator`] that computes SUM of decimals, checking that the
+/// sums fit in their data type
+#[derive(Debug)]
+struct DecimalSumGroupsAccumulator<T: DecimalType> {
+    /// Sums per group, stored as the native type
+    sums: Vec<T::Native>,
+
+    /// The output type, with its precision and scale
+    data_type: DataType,
+
+    /// Track nulls in the input / filters
+    null_state: NullState,
+
+    checker: DecimalOverflowChecker<T>,
+}
+
+impl<T: DecimalType> DecimalSumGroupsAccumulator<T> {
+    fn new(data_type: &DataType, checker: DecimalOverflowChecker<T>) -> Self {
+        Self {
+            sums: vec![],
+            data_type: data_type.clone(),
+            null_state: NullState::new(),
+            checker,
+        }
+    }
+}
+
+impl<T: DecimalType> GroupsAccumulator for DecimalSumGroupsAccumulator<T> {
+    fn update_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        assert_eq!(values.len(), 1, "single argument to update_batch");
+        let values = values[0].as_primitive::<T>();
+
+        self.sums.resize(total_num_groups, T::Native::usize_as(0));
+        let mut result = Ok(());
+        self.null_state.accumulate(
+            group_indices,
+            values,
+            opt_filter,
+            total_num_groups,
+            |group_index, new_value| {
+                if result.is_ok() {
+                    let sum = &mut self.sums[group_index];
+                    match self.checker.add(*sum, new_value) {
+                        Ok(new_sum) => *sum = new_sum,
+                        Err(e) => result = Err(e),
+                    }
+                }
+            },
+        );
+        result
+    }
+
+    fn evaluate(&mut self, emit_to: EmitTo) -> Result<ArrayRef> {
+        let sums = self.state_array(emit_to);
+        Ok(Arc::new(self.checker.check_array(&sums)?))
+    }
+
+    fn state(&mut self, emit_to: EmitTo) -> Result<Vec<ArrayRef>> {
+        Ok(vec![Arc::new(self.state_array(emit_to))])
+    }
+
+    fn merge_batch(
+        &mut self,
+        values: &[ArrayRef],
+        group_indices: &[usize],
+        opt_filter: Option<&BooleanArray>,
+        total_num_groups: usize,
+    ) -> Result<()> {
+        // update / merge are the same
+        self.update_batch(values, group_indices, opt_filter, total_num_groups)
+    }
+
+    fn convert_to_state(
+        &self,
+        values: &[ArrayRef],
+        opt_filter: Option<&BooleanArray>,
+    ) -> Result<Vec<ArrayRef>> {
+        assert_eq!(values.len(), 1, "single argument to convert_to_state");
+        let values = values[0].as_primitive::<T>();
+
+        // Each row is the sum of its own group, null if the row is null or
+        // filtered out
+        let nulls = filtered_null_mask(opt_filter, values);
+        let sums = PrimitiveArray::<T>::new(values.values().clone(), nulls)
+            .with_data_type(self.data_type.clone());
+        Ok(vec![Arc::new(sums)])
+    }
+
+    fn supports_convert_to_state(&self) -> bool {
+        true
+    }
+
+    fn size(&self) -> usize {
+        self.sums.capacity() * std::mem::size_of::<T::Native>() + self.null_state.size()
+    }
+}
+
+impl<T: DecimalType> DecimalSumGroupsAccumulator<T> {
+    /// Returns the sums of the groups to emit, without checking them
+    fn state_array(&mut self, emit_to: EmitTo) -> PrimitiveArray<T> {
+        let sums = emit_to.take_needed(&mut self.sums);
+        let nulls = self.null_state.build(emit_to);
+        PrimitiveArray::<T>::new(sums.into(), Some(nulls)) // no copy
+            .with_data_type(self.data_type.clone())
+    }
+}
+
 /// This accumulator incrementally computes sums over a sliding window
 ///
 /// This is separate from [`SumAccumulator`] as requires additional state
@@ -290,10 +524,95 @@ impl<T: ArrowNumericType> Accumulator for SlidingSumAccumulator<T> {
     }
 }
 
+/// This accumulator incrementally computes sums of decimals over a sliding
+/// window, checking that the sums fit in their data type
+///
+/// The sums are only saturated when evaluated, as values are retracted from
+/// them.
+struct SlidingDecimalSumAccumulator<T: DecimalType> {
+    sum: T::Native,
+    count: u64,
+    data_type: DataType,
+    checker: DecimalOverflowChecker<T>,
+}
+
+impl<T: DecimalType> std::fmt::Debug for SlidingDecimalSumAccumulator<T> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "SlidingDecimalSumAccumulator({})", self.data_type)
+    }
+}
+
+impl<T: DecimalType> SlidingDecimalSumAccumulator<T> {
+    fn new(data_type: DataType, checker: DecimalOverflowChecker<T>) -> Self {
+        Self {
+            sum: T::Native::usize_as(0),
+            count: 0,
+            data_type,
+            checker,
+        }
+    }
+}
+
+impl<T: DecimalType> Accumulator for SlidingDecimalSumAccumulator<T> {
+    fn state(&mut self) -> Result<Vec<ScalarValue>> {
+        let sum = (self.count != 0).then_some(self.sum);
+        Ok(vec![
+            ScalarValue::new_primitive::<T>(sum, &self.data_type)?,
+            self.count.into(),
+        ])
+    }
+
+    fn update_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = values[0].as_primitive::<T>();
+        self.count += (values.len() - values.null_count()) as u64;
+        for value in values.iter().flatten() {
+            self.sum = self.checker.add_exact(self.sum, value)?;
+        }
+        Ok(())
+    }
+
+    fn merge_batch(&mut self, states: &[ArrayRef]) -> Result<()> {
+        for value in states[0].as_primitive::<T>().iter().flatten() {
+            self.sum = self.checker.add_exact(self.sum, value)?;
+        }
+        if let Some(x) = sum(states[1].as_primitive::<UInt64Type>()) {
+            self.count += x;
+        }
+        Ok(())
+    }
+
+    fn evaluate(&mut self) -> Result<ScalarValue> {
+        let sum = (self.count != 0)
+            .then(|| self.checker.check(self.sum))
+            .transpose()?;
+        ScalarValue::new_primitive::<T>(sum, &self.data_type)
+    }
+
+    fn size(&self) -> usize {
+        std::mem::size_of_val(self)
+    }
+
+    fn retract_batch(&mut self, values: &[ArrayRef]) -> Result<()> {
+        let values = values[0].as_primitive::<T>();
+        for value in values.iter().flatten() {
+            self.sum = self.checker.sub_exact(self.sum, value)?;
+        }
+        self.count -= (values.len() - values.null_count()) as u64;
+        Ok(())
+    }
+
+    fn supports_retract_batch(&self) -> bool {
+        true
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::expressions::tests::assert_aggregate;
+    use crate::expressions::col;
+    use crate::expressions::tests::{aggregate, aggregate_new, assert_aggregate};
+    use arrow::datatypes::Schema;
+    use arrow::record_batch::RecordBatch;
     use arrow_array::*;
     use datafusion_expr::AggregateFunction;
 
@@ -355,6 +674,40 @@ mod tests {
         );
     }
 
+    #[test]
+    fn sum_decimal_overflow() -> Result<()> {
+        // The sum overflows the native i128 of Decimal128(38, 0)
+        let value = 9 * 10_i128.pow(37);
+        let array: ArrayRef = Arc::new(
+            Decimal128Array::from(vec![value, value]).with_precision_and_scale(38, 0)?,
+        );
+        let schema = Schema::new(vec![Field::new("a", array.data_type().clone(), true)]);
+        let batch = RecordBatch::try_new(Arc::new(schema.clone()), vec![array])?;
+        let sum = Sum::new(col("a", &schema)?, "sum", DataType::Decimal128(38, 0));
+
+        // the sums wrap around by default
+        assert_eq!(
+            aggregate(&batch, Arc::new(sum.clone()))?,
+            ScalarValue::Decimal128(Some(value.wrapping_add(value)), 38, 0)
+        );
+
+        let sum = sum.with_overflow_mode(DecimalOverflowMode::Error);
+        let err = aggregate(&batch, Arc::new(sum.clone())).unwrap_err();
+        assert!(err.to_string().contains("Arithmetic Overflow"), "{err}");
+        let err = aggregate_new(&batch, Arc::new(sum.clone())).unwrap_err();
+        assert!(err.to_string().contains("Arithmetic Overflow"), "{err}");
+
+        let sum = sum.with_overflow_mode(DecimalOverflowMode::Saturate);
+        let max = 10_i128.pow(38) - 1;
+        assert_eq!(
+            aggregate(&batch, Arc::new(sum.clone()))?,
+            ScalarValue::Decimal128(Some(max), 38, 0)
+        );
+        let result = aggregate_new(&batch, Arc::new(sum))?;
+        assert_eq!(result.as_primitive::<Decimal128Type>().value(0), max);
+        Ok(())
+    }
+
     #[test]
     fn sum_i32() {
         let a: ArrayRef = Arc::new(Int32Array::from(vec![1, 2, 3, 4, 5]));
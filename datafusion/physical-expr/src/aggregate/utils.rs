@@ -24,7 +24,7 @@ pub use datafusion_physical_expr_common::aggregate::utils::{
     down_cast_any_ref, get_sort_options, ordering_fields,
 };
 
-use arrow::array::{ArrayRef, ArrowNativeTypeOp};
+use arrow::array::{Array, ArrayRef, ArrowNativeTypeOp, PrimitiveArray};
 use arrow_array::cast::AsArray;
 use arrow_array::types::{
     Decimal128Type, DecimalType, TimestampMicrosecondType, TimestampMillisecondType,
@@ -32,6 +32,7 @@ use arrow_array::types::{
 };
 use arrow_buffer::{ArrowNativeType, ToByteSlice};
 use arrow_schema::DataType;
+use datafusion_common::config::DecimalOverflowMode;
 use datafusion_common::{exec_err, DataFusionError, Result};
 use datafusion_expr::Accumulator;
 
@@ -126,6 +127,139 @@ impl<T: DecimalType> DecimalAverager<T> {
     }
 }
 
+/// Checks that `Decimal128`/`Decimal256` aggregates fit in their data type,
+/// according to a [`DecimalOverflowMode`]
+///
+/// Values that do not fit either cause an error or are saturated to the
+/// largest (or smallest) value of the precision, unless the mode is
+/// [`DecimalOverflowMode::Unchecked`], whose sums wrap around the native type.
+/// [`DecimalOverflowMode::Promote`] is handled during planning, by
+/// aggregating `Decimal128` values as `Decimal256`, so it reports errors like
+/// [`DecimalOverflowMode::Error`].
+#[derive(Debug)]
+pub(crate) struct DecimalOverflowChecker<T: DecimalType> {
+    mode: DecimalOverflowMode,
+    /// The largest value of the precision, `10^precision - 1`
+    max: T::Native,
+}
+
+impl<T: DecimalType> DecimalOverflowChecker<T> {
+    /// Create a new `DecimalOverflowChecker` for values of `precision`
+    pub fn new(precision: u8, mode: DecimalOverflowMode) -> Self {
+        let max = T::Native::usize_as(10)
+            .pow_wrapping(precision as u32)
+            .sub_wrapping(T::Native::usize_as(1));
+        Self { mode, max }
+    }
+
+    /// Create a new `DecimalOverflowChecker` that only checks for overflows
+    /// of the native type, i.e. for values of the maximum precision
+    pub fn new_native(mode: DecimalOverflowMode) -> Self {
+        Self::new(T::MAX_PRECISION, mode)
+    }
+
+    fn saturates(&self) -> bool {
+        self.mode == DecimalOverflowMode::Saturate
+    }
+
+    fn unchecked(&self) -> bool {
+        self.mode == DecimalOverflowMode::Unchecked
+    }
+
+    /// Returns the largest value of the precision if `value` is positive,
+    /// and the smallest one otherwise
+    pub fn bound(&self, value: T::Native) -> T::Native {
+        if value.is_lt(T::Native::usize_as(0)) {
+            self.max.neg_wrapping()
+        } else {
+            self.max
+        }
+    }
+
+    /// Returns `sum + value`
+    ///
+    /// When saturating, the result is saturated to the precision. Otherwise
+    /// it may exceed the precision, which is only checked by [`Self::check`],
+    /// but errors if it overflows the native type.
+    #[inline]
+    pub fn add(&self, sum: T::Native, value: T::Native) -> Result<T::Native> {
+        if self.unchecked() {
+            return Ok(sum.add_wrapping(value));
+        }
+        match sum.add_checked(value) {
+            Ok(sum) if self.saturates() => Ok(self.check_saturating(sum)),
+            Ok(sum) => Ok(sum),
+            Err(_) if self.saturates() => Ok(self.bound(value)),
+            Err(_) => exec_err!("Arithmetic Overflow in decimal aggregate"),
+        }
+    }
+
+    /// Returns `sum + value` without saturating it, for the sums from which
+    /// values are retracted, erroring if it overflows the native type
+    #[inline]
+    pub fn add_exact(&self, sum: T::Native, value: T::Native) -> Result<T::Native> {
+        if self.unchecked() {
+            return Ok(sum.add_wrapping(value));
+        }
+        sum.add_checked(value)
+            .or_else(|_| exec_err!("Arithmetic Overflow in decimal aggregate"))
+    }
+
+    /// Returns `sum - value`, like [`Self::add_exact`]
+    #[inline]
+    pub fn sub_exact(&self, sum: T::Native, value: T::Native) -> Result<T::Native> {
+        if self.unchecked() {
+            return Ok(sum.sub_wrapping(value));
+        }
+        sum.sub_checked(value)
+            .or_else(|_| exec_err!("Arithmetic Overflow in decimal aggregate"))
+    }
+
+    fn check_saturating(&self, value: T::Native) -> T::Native {
+        if value.is_gt(self.max) || value.is_lt(self.max.neg_wrapping()) {
+            self.bound(value)
+        } else {
+            value
+        }
+    }
+
+    /// Returns `value`, or an error or the saturated value if it does not fit
+    /// in the precision
+    #[inline]
+    pub fn check(&self, value: T::Native) -> Result<T::Native> {
+        let checked = self.check_saturating(value);
+        if self.unchecked() {
+            Ok(value)
+        } else if checked.is_eq(value) || self.saturates() {
+            Ok(checked)
+        } else {
+            exec_err!("Arithmetic Overflow in decimal aggregate")
+        }
+    }
+
+    /// Returns the average computed by `averager`, or the saturated value if it
+    /// does not fit in the precision and this checker saturates
+    #[inline]
+    pub fn avg(
+        &self,
+        averager: &DecimalAverager<T>,
+        sum: T::Native,
+        count: T::Native,
+    ) -> Result<T::Native> {
+        match averager.avg(sum, count) {
+            Err(_) if self.saturates() => Ok(self.bound(sum)),
+            result => result,
+        }
+    }
+
+    /// Applies [`Self::check`] to the valid values of `array`
+    pub fn check_array(&self, array: &PrimitiveArray<T>) -> Result<PrimitiveArray<T>> {
+        Ok(array
+            .try_unary::<_, T, DataFusionError>(|value| self.check(value))?
+            .with_data_type(array.data_type().clone()))
+    }
+}
+
 /// Adjust array type metadata if needed
 ///
 /// Since `Decimal128Arrays` created from `Vec<NativeType>` have
@@ -19,7 +19,8 @@ use std::sync::Arc;
 
 use super::{add_offset_to_expr, collapse_lex_req, ProjectionMapping};
 use crate::{
-    expressions::Column, physical_expr::deduplicate_physical_exprs,
+    expressions::{Column, Literal},
+    physical_expr::deduplicate_physical_exprs,
     physical_exprs_bag_equal, physical_exprs_contains, LexOrdering, LexOrderingRef,
     LexRequirement, LexRequirementRef, PhysicalExpr, PhysicalExprRef, PhysicalSortExpr,
     PhysicalSortRequirement,
@@ -369,8 +370,10 @@ impl EquivalenceGroup {
         // Project a non-leaf expression by projecting its children.
         let children = expr.children();
         if children.is_empty() {
-            // Leaf expression should be inside mapping.
-            return None;
+            // Literals do not depend on the input, e.g. the field names of
+            // nested field accesses like `get_field(s, 'x')`, and are kept
+            // as is. Other leaf expressions should be inside mapping.
+            return expr.as_any().is::<Literal>().then(|| expr.clone());
         }
         children
             .into_iter()
@@ -128,7 +128,7 @@ mod tests {
         output_schema,
     };
     use crate::equivalence::EquivalenceProperties;
-    use crate::expressions::{col, BinaryExpr};
+    use crate::expressions::{col, lit, BinaryExpr};
     use crate::udf::create_physical_expr;
     use crate::utils::tests::TestScalarUDF;
     use crate::PhysicalSortExpr;
@@ -967,6 +967,47 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn project_orderings_with_literals() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("b", DataType::Int32, true),
+        ]));
+        let col_a = &col("a", &schema)?;
+        let col_b = &col("b", &schema)?;
+        // The literals of the expressions, e.g. the field names of nested
+        // field accesses, do not need to be projected
+        let a_plus_1 = Arc::new(BinaryExpr::new(col_a.clone(), Operator::Plus, lit(1)))
+            as Arc<dyn PhysicalExpr>;
+
+        let option_asc = SortOptions {
+            descending: false,
+            nulls_first: false,
+        };
+
+        let proj_exprs = vec![
+            (col_b.clone(), "b_new".to_string()),
+            (col_a.clone(), "a_new".to_string()),
+        ];
+        let projection_mapping = ProjectionMapping::try_new(&proj_exprs, &schema)?;
+        let output_schema = output_schema(&projection_mapping, &schema)?;
+        let col_a_new = &col("a_new", &output_schema)?;
+        let a_new_plus_1 =
+            Arc::new(BinaryExpr::new(col_a_new.clone(), Operator::Plus, lit(1)))
+                as Arc<dyn PhysicalExpr>;
+
+        let mut eq_properties = EquivalenceProperties::new(schema.clone());
+        // [a + 1 ASC]
+        eq_properties
+            .add_new_orderings(convert_to_orderings(&[vec![(&a_plus_1, option_asc)]]));
+        let projected_eq = eq_properties.project(&projection_mapping, output_schema);
+
+        // [a_new + 1 ASC]
+        let expected = convert_to_orderings(&[vec![(&a_new_plus_1, option_asc)]]);
+        assert_eq!(projected_eq.oeq_class().orderings, expected);
+        Ok(())
+    }
+
     #[test]
     fn project_orderings_random() -> Result<()> {
         const N_RANDOM_SCHEMA: usize = 20;
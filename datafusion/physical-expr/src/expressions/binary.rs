@@ -32,7 +32,7 @@ use arrow::compute::kernels::cmp::*;
 use arrow::compute::kernels::comparison::regexp_is_match_utf8;
 use arrow::compute::kernels::comparison::regexp_is_match_utf8_scalar;
 use arrow::compute::kernels::concat_elements::concat_elements_utf8;
-use arrow::compute::{cast, ilike, like, nilike, nlike};
+use arrow::compute::{cast, ilike, like, nilike, nlike, prep_null_mask_filter};
 use arrow::datatypes::*;
 
 use datafusion_common::cast::as_boolean_array;
@@ -258,7 +258,10 @@ impl PhysicalExpr for BinaryExpr {
         use arrow::compute::kernels::numeric::*;
 
         let lhs = self.left.evaluate(batch)?;
-        let rhs = self.right.evaluate(batch)?;
+        let Some(rhs) = self.evaluate_right(&lhs, batch)? else {
+            // the left side determines the result of all the rows
+            return Ok(lhs);
+        };
         let left_data_type = lhs.data_type();
         let right_data_type = rhs.data_type();
 
@@ -496,7 +499,71 @@ fn to_result_type_array(
     }
 }
 
+/// The maximum fraction of the rows of a batch which the left side of an
+/// `AND` or an `OR` leaves undetermined for its right side to be evaluated on
+/// these rows only. Above it, filtering the batch costs more than evaluating
+/// the right side on the rows which don't need it.
+const PRE_SELECTION_THRESHOLD: f64 = 0.2;
+
 impl BinaryExpr {
+    /// Evaluates the right side of the expression, given the value `lhs` of
+    /// its left side.
+    ///
+    /// The result of `AND` is false where the left side is false, and the
+    /// result of `OR` true where the left side is true, so the right side
+    /// of `AND` and `OR` is only evaluated on the other rows when they are
+    /// few, and the rows it is not evaluated on are null. Returns `None` if
+    /// the left side determines the result of all the rows.
+    fn evaluate_right(
+        &self,
+        lhs: &ColumnarValue,
+        batch: &RecordBatch,
+    ) -> Result<Option<ColumnarValue>> {
+        let short_circuit = match self.op {
+            Operator::And => false,
+            Operator::Or => true,
+            _ => return self.right.evaluate(batch).map(Some),
+        };
+        let array = match lhs {
+            ColumnarValue::Scalar(ScalarValue::Boolean(Some(value)))
+                if *value == short_circuit =>
+            {
+                return Ok(None)
+            }
+            ColumnarValue::Array(array) if array.data_type() == &DataType::Boolean => {
+                as_boolean_array(array)?
+            }
+            _ => return self.right.evaluate(batch).map(Some),
+        };
+
+        let num_rows = array.len();
+        let determined = if short_circuit {
+            array.true_count()
+        } else {
+            array.false_count()
+        };
+        if num_rows > 0 && determined == num_rows {
+            return Ok(None);
+        }
+        let undetermined = num_rows - determined;
+        if undetermined as f64 > num_rows as f64 * PRE_SELECTION_THRESHOLD {
+            return self.right.evaluate(batch).map(Some);
+        }
+
+        // the rows where the left side is not `short_circuit`, including nulls
+        let determined = if short_circuit {
+            array.clone()
+        } else {
+            not(array)?
+        };
+        let determined = match determined.null_count() {
+            0 => determined,
+            _ => prep_null_mask_filter(&determined),
+        };
+        let selection = not(&determined)?;
+        self.right.evaluate_selection(batch, &selection).map(Some)
+    }
+
     /// Evaluate the expression of the left input is an array and
     /// right is literal - use scalar operations
     fn evaluate_array_scalar(
@@ -3961,4 +4028,61 @@ mod tests {
                 .unwrap();
         assert_eq!(&casted, &dictionary);
     }
+
+    #[test]
+    fn and_or_short_circuit() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::Int32, false),
+            Field::new("b", DataType::Int32, false),
+        ]));
+        let mut b = vec![0; 10];
+        b[0] = 1;
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(Int32Array::from((0..10).collect::<Vec<_>>())),
+                Arc::new(Int32Array::from(b)),
+            ],
+        )?;
+        // 10 / b > 1, which fails on all the rows but the first
+        let right = binary_op(
+            binary_op(lit(10i32), Operator::Divide, col("b", &schema)?, &schema)?,
+            Operator::Gt,
+            lit(1i32),
+            &schema,
+        )?;
+        let evaluate = |left: Arc<dyn PhysicalExpr>, op: Operator| {
+            binary_op(left, op, right.clone(), &schema)?
+                .evaluate(&batch)?
+                .into_array(batch.num_rows())
+        };
+
+        // a = 0 AND 10 / b > 1: the right side is only evaluated on the first row
+        let left = binary_op(col("a", &schema)?, Operator::Eq, lit(0i32), &schema)?;
+        let result = evaluate(left, Operator::And)?;
+        let mut expected = vec![false; 10];
+        expected[0] = true;
+        assert_eq!(as_boolean_array(&result)?, &BooleanArray::from(expected));
+
+        // a > 100 AND 10 / b > 1: the right side is not evaluated
+        let left = binary_op(col("a", &schema)?, Operator::Gt, lit(100i32), &schema)?;
+        let result = evaluate(left, Operator::And)?;
+        assert_eq!(
+            as_boolean_array(&result)?,
+            &BooleanArray::from(vec![false; 10])
+        );
+
+        // a > 0 OR 10 / b > 1: the right side is only evaluated on the first row
+        let left = binary_op(col("a", &schema)?, Operator::Gt, lit(0i32), &schema)?;
+        let result = evaluate(left, Operator::Or)?;
+        assert_eq!(
+            as_boolean_array(&result)?,
+            &BooleanArray::from(vec![true; 10])
+        );
+
+        // a > 5 AND 10 / b > 1: the right side is evaluated on all the rows
+        let left = binary_op(col("a", &schema)?, Operator::Gt, lit(5i32), &schema)?;
+        evaluate(left, Operator::And).unwrap_err();
+        Ok(())
+    }
 }
@@ -133,6 +133,10 @@ impl CaseExpr {
         // We only consider non-null values while comparing with whens
         let mut remainder = not(&base_nulls)?;
         for i in 0..self.when_then_expr.len() {
+            // When all rows are matched, skip the remaining branches
+            if remainder.true_count() == 0 {
+                break;
+            }
             let when_value = self.when_then_expr[i]
                 .0
                 .evaluate_selection(batch, &remainder)?;
@@ -177,6 +181,10 @@ impl CaseExpr {
                 .unwrap_or_else(|_| e.clone());
             // null and unmatched tuples should be assigned else value
             remainder = or(&base_nulls, &remainder)?;
+            // When all rows are matched, skip the else clause
+            if remainder.true_count() == 0 {
+                return Ok(ColumnarValue::Array(current_value));
+            }
             let else_ = expr
                 .evaluate_selection(batch, &remainder)?
                 .into_array(batch.num_rows())?;
@@ -200,6 +208,10 @@ impl CaseExpr {
         let mut current_value = new_null_array(&return_type, batch.num_rows());
         let mut remainder = BooleanArray::from(vec![true; batch.num_rows()]);
         for i in 0..self.when_then_expr.len() {
+            // When all rows are matched, skip the remaining branches
+            if remainder.true_count() == 0 {
+                break;
+            }
             let when_value = self.when_then_expr[i]
                 .0
                 .evaluate_selection(batch, &remainder)?;
@@ -245,6 +257,10 @@ impl CaseExpr {
         }
 
         if let Some(e) = &self.else_expr {
+            // When all rows are matched, skip the else clause
+            if remainder.true_count() == 0 {
+                return Ok(ColumnarValue::Array(current_value));
+            }
             // keep `else_expr`'s data type and return type consistent
             let expr = try_cast(e.clone(), &batch.schema(), return_type.clone())
                 .unwrap_or_else(|_| e.clone());
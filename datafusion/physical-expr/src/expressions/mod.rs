@@ -31,6 +31,7 @@ mod literal;
 mod negative;
 mod no_op;
 mod not;
+mod scalar_subquery;
 mod try_cast;
 
 /// Module with some convenient methods used in expression building
@@ -74,6 +75,7 @@ pub use crate::window::ntile::Ntile;
 pub use crate::window::rank::{dense_rank, percent_rank, rank};
 pub use crate::window::rank::{Rank, RankType};
 pub use crate::window::row_number::RowNumber;
+pub use crate::window::session_window::SessionWindow;
 pub use crate::PhysicalSortExpr;
 pub use datafusion_functions_aggregate::first_last::{
     FirstValuePhysicalExpr as FirstValue, LastValuePhysicalExpr as LastValue,
@@ -93,6 +95,7 @@ pub use literal::{lit, Literal};
 pub use negative::{negative, NegativeExpr};
 pub use no_op::NoOp;
 pub use not::{not, NotExpr};
+pub use scalar_subquery::ScalarSubqueryExpr;
 pub use try_cast::{try_cast, TryCastExpr};
 
 #[cfg(test)]
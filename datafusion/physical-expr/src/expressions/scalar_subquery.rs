@@ -0,0 +1,160 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+//! Scalar subquery expression for physical operations
+
+use std::any::Any;
+use std::hash::{Hash, Hasher};
+use std::sync::Arc;
+
+use crate::physical_expr::down_cast_any_ref;
+use crate::sort_properties::SortProperties;
+use crate::PhysicalExpr;
+
+use arrow::datatypes::{DataType, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::{internal_err, Result};
+use datafusion_expr::execution_props::ScalarSubqueryResult;
+use datafusion_expr::ColumnarValue;
+
+/// The value of an uncorrelated scalar subquery, evaluated once before the
+/// query runs.
+///
+/// The plan computing the subquery must set the [`ScalarSubqueryResult`]
+/// before this expression is evaluated.
+#[derive(Debug)]
+pub struct ScalarSubqueryExpr {
+    /// Name of the subquery, for display
+    name: String,
+    data_type: DataType,
+    nullable: bool,
+    result: ScalarSubqueryResult,
+}
+
+impl ScalarSubqueryExpr {
+    /// Create a new scalar subquery expression reading `result`
+    pub fn new(
+        name: impl Into<String>,
+        data_type: DataType,
+        nullable: bool,
+        result: ScalarSubqueryResult,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            data_type,
+            nullable,
+            result,
+        }
+    }
+
+    /// Get the result of the subquery
+    pub fn result(&self) -> &ScalarSubqueryResult {
+        &self.result
+    }
+}
+
+impl std::fmt::Display for ScalarSubqueryExpr {
+    fn fmt(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        write!(f, "{}", self.name)
+    }
+}
+
+impl PhysicalExpr for ScalarSubqueryExpr {
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn data_type(&self, _input_schema: &Schema) -> Result<DataType> {
+        Ok(self.data_type.clone())
+    }
+
+    fn nullable(&self, _input_schema: &Schema) -> Result<bool> {
+        Ok(self.nullable)
+    }
+
+    fn evaluate(&self, _batch: &RecordBatch) -> Result<ColumnarValue> {
+        match self.result.value() {
+            Some(value) => Ok(ColumnarValue::Scalar(value.clone())),
+            None => internal_err!("Scalar subquery {} was not evaluated", self.name),
+        }
+    }
+
+    fn children(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _children: Vec<Arc<dyn PhysicalExpr>>,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        Ok(self)
+    }
+
+    fn dyn_hash(&self, state: &mut dyn Hasher) {
+        let mut s = state;
+        self.name.hash(&mut s);
+        self.data_type.hash(&mut s);
+    }
+
+    fn get_ordering(&self, _children: &[SortProperties]) -> SortProperties {
+        SortProperties::Singleton
+    }
+}
+
+impl PartialEq<dyn Any> for ScalarSubqueryExpr {
+    fn eq(&self, other: &dyn Any) -> bool {
+        down_cast_any_ref(other)
+            .downcast_ref::<Self>()
+            .map(|x| self.result.ptr_eq(&x.result))
+            .unwrap_or(false)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use arrow::array::Int32Array;
+    use arrow::datatypes::Field;
+    use datafusion_common::ScalarValue;
+
+    #[test]
+    fn scalar_subquery_value() -> Result<()> {
+        let schema = Schema::new(vec![Field::new("a", DataType::Int32, true)]);
+        let a = Int32Array::from(vec![1, 2, 3]);
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![Arc::new(a)])?;
+
+        let result = ScalarSubqueryResult::new();
+        let expr = ScalarSubqueryExpr::new(
+            "(<subquery>)",
+            DataType::Int64,
+            true,
+            result.clone(),
+        );
+        assert!(expr.evaluate(&batch).is_err());
+
+        result.set_value(ScalarValue::Int64(Some(42)))?;
+        let ColumnarValue::Scalar(value) = expr.evaluate(&batch)? else {
+            panic!("expected a scalar value");
+        };
+        assert_eq!(value, ScalarValue::Int64(Some(42)));
+
+        // The value can only be set again to the same value
+        result.set_value(ScalarValue::Int64(Some(42)))?;
+        assert!(result.set_value(ScalarValue::Int64(Some(1))).is_err());
+        Ok(())
+    }
+}
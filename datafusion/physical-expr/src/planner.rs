@@ -32,7 +32,7 @@ use datafusion_expr::{
 };
 
 use crate::{
-    expressions::{self, binary, like, Column, Literal},
+    expressions::{self, binary, like, Column, Literal, ScalarSubqueryExpr},
     udf, PhysicalExpr,
 };
 
@@ -139,6 +139,23 @@ pub fn create_physical_expr(
                 }
             }
         }
+        Expr::ScalarSubquery(subquery) => {
+            match execution_props.scalar_subquery_results.get(subquery) {
+                Some(result) => {
+                    let data_type = subquery.subquery.schema().field(0).data_type();
+                    // A subquery returning no rows evaluates to NULL
+                    Ok(Arc::new(ScalarSubqueryExpr::new(
+                        e.to_string(),
+                        data_type.clone(),
+                        true,
+                        result.clone(),
+                    )))
+                }
+                None => not_impl_err!(
+                    "Physical plan does not support logical expression {e:?}"
+                ),
+            }
+        }
         Expr::IsTrue(expr) => {
             let binary_op = binary_expr(
                 expr.as_ref().clone(),
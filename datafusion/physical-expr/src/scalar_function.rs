@@ -39,9 +39,11 @@ use arrow::record_batch::RecordBatch;
 
 use datafusion_common::{internal_err, Result};
 use datafusion_expr::{
-    expr_vec_fmt, ColumnarValue, FuncMonotonicity, ScalarFunctionDefinition,
+    expr_vec_fmt, ColumnarValue, FuncMonotonicity, PreparedScalarUDF,
+    ScalarFunctionDefinition,
 };
 
+use crate::expressions::Literal;
 use crate::functions::out_ordering;
 use crate::physical_expr::{down_cast_any_ref, physical_exprs_equal};
 use crate::sort_properties::SortProperties;
@@ -60,6 +62,8 @@ pub struct ScalarFunctionExpr {
     monotonicity: Option<FuncMonotonicity>,
     // Whether this function can be invoked with zero arguments
     supports_zero_argument: bool,
+    // The function prepared from the literal arguments, if any
+    prepared: Option<Arc<dyn PreparedScalarUDF>>,
 }
 
 impl Debug for ScalarFunctionExpr {
@@ -71,6 +75,7 @@ impl Debug for ScalarFunctionExpr {
             .field("return_type", &self.return_type)
             .field("monotonicity", &self.monotonicity)
             .field("supports_zero_argument", &self.supports_zero_argument)
+            .field("prepared", &self.prepared.is_some())
             .finish()
     }
 }
@@ -92,9 +97,31 @@ impl ScalarFunctionExpr {
             return_type,
             monotonicity,
             supports_zero_argument,
+            prepared: None,
         }
     }
 
+    /// Prepare the function from the literal arguments of this expression
+    /// (see [`ScalarUDFImpl::prepare`]), so that what it prepares is reused
+    /// by every evaluation
+    ///
+    /// [`ScalarUDFImpl::prepare`]: datafusion_expr::ScalarUDFImpl::prepare
+    pub fn prepared(mut self) -> Result<Self> {
+        if let ScalarFunctionDefinition::UDF(fun) = &self.fun {
+            let const_args = self
+                .args
+                .iter()
+                .map(|arg| {
+                    arg.as_any()
+                        .downcast_ref::<Literal>()
+                        .map(|literal| literal.value().clone())
+                })
+                .collect::<Vec<_>>();
+            self.prepared = fun.prepare(&const_args)?;
+        }
+        Ok(self)
+    }
+
     /// Get the scalar function implementation
     pub fn fun(&self) -> &ScalarFunctionDefinition {
         &self.fun
@@ -159,6 +186,9 @@ impl PhysicalExpr for ScalarFunctionExpr {
         };
 
         // evaluate the function
+        if let Some(prepared) = &self.prepared {
+            return prepared.invoke(&inputs);
+        }
         match self.fun {
             ScalarFunctionDefinition::UDF(ref fun) => fun.invoke(&inputs),
             ScalarFunctionDefinition::Name(_) => {
@@ -177,14 +207,20 @@ impl PhysicalExpr for ScalarFunctionExpr {
         self: Arc<Self>,
         children: Vec<Arc<dyn PhysicalExpr>>,
     ) -> Result<Arc<dyn PhysicalExpr>> {
-        Ok(Arc::new(ScalarFunctionExpr::new(
+        let expr = ScalarFunctionExpr::new(
             &self.name,
             self.fun.clone(),
             children,
             self.return_type().clone(),
             self.monotonicity.clone(),
             self.supports_zero_argument,
-        )))
+        );
+        // the literal arguments may have changed
+        let expr = match self.prepared {
+            Some(_) => expr.prepared()?,
+            None => expr,
+        };
+        Ok(Arc::new(expr))
     }
 
     fn dyn_hash(&self, state: &mut dyn Hasher) {
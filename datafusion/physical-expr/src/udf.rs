@@ -51,14 +51,17 @@ pub fn create_physical_expr(
         fun.return_type_from_exprs(args, input_dfschema, &input_expr_types)?;
 
     let fun_def = ScalarFunctionDefinition::UDF(Arc::new(fun.clone()));
-    Ok(Arc::new(ScalarFunctionExpr::new(
-        fun.name(),
-        fun_def,
-        input_phy_exprs.to_vec(),
-        return_type,
-        fun.monotonicity()?,
-        fun.signature().type_signature.supports_zero_argument(),
-    )))
+    Ok(Arc::new(
+        ScalarFunctionExpr::new(
+            fun.name(),
+            fun_def,
+            input_phy_exprs.to_vec(),
+            return_type,
+            fun.monotonicity()?,
+            fun.signature().type_signature.supports_zero_argument(),
+        )
+        .prepared()?,
+    ))
 }
 
 #[cfg(test)]
@@ -19,7 +19,7 @@ mod guarantee;
 pub use guarantee::{Guarantee, LiteralGuarantee};
 
 use std::borrow::Borrow;
-use std::collections::{HashMap, HashSet};
+use std::collections::HashSet;
 use std::sync::Arc;
 
 use crate::expressions::{BinaryExpr, Column};
@@ -78,29 +78,38 @@ fn split_impl<'a>(
 // Specifically, `ProjectionExec` changes index of `Column`s in the schema of its input executor.
 // This function changes requirement given according to ProjectionExec schema to the requirement
 // according to schema of input executor to the ProjectionExec.
-// For instance, Column{"a", 0} would turn to Column{"a", 1}. Please note that this function assumes that
-// name of the Column is unique. If we have a requirement such that Column{"a", 0}, Column{"a", 1}.
-// This function will produce incorrect result (It will only emit single Column as a result).
+// For instance, Column{"a", 0} would turn to Column{"a", 1}. The columns of the requirements
+// are replaced by the expressions of the projection, which may be any expressions, e.g.
+// Column{"k", 0} would turn to `COALESCE(a@1, b@2)` for the projection `COALESCE(a, b) AS k`.
+// The requirements with columns which are not in the projection are dropped.
 pub fn map_columns_before_projection(
     parent_required: &[Arc<dyn PhysicalExpr>],
     proj_exprs: &[(Arc<dyn PhysicalExpr>, String)],
 ) -> Vec<Arc<dyn PhysicalExpr>> {
-    let column_mapping = proj_exprs
-        .iter()
-        .filter_map(|(expr, name)| {
-            expr.as_any()
-                .downcast_ref::<Column>()
-                .map(|column| (name.clone(), column.clone()))
-        })
-        .collect::<HashMap<_, _>>();
     parent_required
         .iter()
         .filter_map(|r| {
-            r.as_any()
-                .downcast_ref::<Column>()
-                .and_then(|c| column_mapping.get(c.name()))
+            let mut mapped = true;
+            let new_required = r
+                .clone()
+                .transform_up(|expr| {
+                    let Some(column) = expr.as_any().downcast_ref::<Column>() else {
+                        return Ok(Transformed::no(expr));
+                    };
+                    match proj_exprs.get(column.index()) {
+                        Some((proj_expr, name)) if name == column.name() => {
+                            Ok(Transformed::yes(proj_expr.clone()))
+                        }
+                        _ => {
+                            mapped = false;
+                            Ok(Transformed::no(expr))
+                        }
+                    }
+                })
+                .data()
+                .ok()?;
+            mapped.then_some(new_required)
         })
-        .map(|e| Arc::new(e.clone()) as _)
         .collect()
 }
 
@@ -261,6 +270,7 @@ pub(crate) mod tests {
 
     use super::*;
     use crate::expressions::{binary, cast, col, in_list, lit, Literal};
+    use crate::physical_exprs_equal;
 
     use arrow_schema::{DataType, Field, Schema};
     use datafusion_common::{exec_err, DataFusionError, ScalarValue};
@@ -549,4 +559,31 @@ pub(crate) mod tests {
         assert_eq!(collect_columns(&expr3), expected);
         Ok(())
     }
+
+    #[test]
+    fn test_map_columns_before_projection() {
+        let a = Arc::new(Column::new("a", 1)) as Arc<dyn PhysicalExpr>;
+        let b = Arc::new(Column::new("b", 2)) as Arc<dyn PhysicalExpr>;
+        let a_plus_b =
+            Arc::new(BinaryExpr::new(a.clone(), Operator::Plus, b.clone())) as _;
+        let proj_exprs = vec![(a_plus_b, "k".to_string()), (b.clone(), "b".to_string())];
+
+        // k@0 * b@1 --> (a@1 + b@2) * b@2
+        let required = Arc::new(BinaryExpr::new(
+            Arc::new(Column::new("k", 0)),
+            Operator::Multiply,
+            Arc::new(Column::new("b", 1)),
+        )) as _;
+        let expected = Arc::new(BinaryExpr::new(
+            Arc::new(BinaryExpr::new(a, Operator::Plus, b.clone())),
+            Operator::Multiply,
+            b,
+        )) as Arc<dyn PhysicalExpr>;
+        let mapped = map_columns_before_projection(&[required], &proj_exprs);
+        assert!(physical_exprs_equal(&mapped, &[expected]));
+
+        // c@2 is not in the projection
+        let required = Arc::new(Column::new("c", 2)) as _;
+        assert!(map_columns_before_projection(&[required], &proj_exprs).is_empty());
+    }
 }
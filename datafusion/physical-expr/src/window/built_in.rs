@@ -32,7 +32,7 @@ use arrow::compute::SortOptions;
 use arrow::datatypes::Field;
 use arrow::record_batch::RecordBatch;
 use datafusion_common::utils::evaluate_partition_ranges;
-use datafusion_common::{Result, ScalarValue};
+use datafusion_common::{exec_err, Result, ScalarValue};
 use datafusion_expr::window_state::{WindowAggState, WindowFrameContext};
 use datafusion_expr::WindowFrame;
 
@@ -43,6 +43,8 @@ pub struct BuiltInWindowExpr {
     partition_by: Vec<Arc<dyn PhysicalExpr>>,
     order_by: Vec<PhysicalSortExpr>,
     window_frame: Arc<WindowFrame>,
+    /// The number of rows of the partitions, if known before reading them
+    partition_rows: Option<usize>,
 }
 
 impl BuiltInWindowExpr {
@@ -58,6 +60,19 @@ impl BuiltInWindowExpr {
             partition_by: partition_by.to_vec(),
             order_by: order_by.to_vec(),
             window_frame,
+            partition_rows: None,
+        }
+    }
+
+    /// Returns an error if a partition of `num_rows` rows doesn't have the
+    /// number of rows this expression was created for
+    fn check_partition_rows(&self, num_rows: usize) -> Result<()> {
+        match self.partition_rows {
+            Some(partition_rows) if partition_rows != num_rows => exec_err!(
+                "{} expected partitions of {partition_rows} rows, got {num_rows}",
+                self.expr.name()
+            ),
+            _ => Ok(()),
         }
     }
 
@@ -125,13 +140,20 @@ impl WindowExpr for BuiltInWindowExpr {
     fn evaluate(&self, batch: &RecordBatch) -> Result<ArrayRef> {
         let mut evaluator = self.expr.create_evaluator()?;
         let num_rows = batch.num_rows();
-        if evaluator.uses_window_frame() {
+        self.check_partition_rows(num_rows)?;
+        if evaluator.uses_window_frame() || evaluator.include_peers() {
             let sort_options: Vec<SortOptions> =
                 self.order_by.iter().map(|o| o.options).collect();
             let mut row_wise_results = vec![];
 
             let mut values = self.evaluate_args(batch)?;
-            let order_bys = get_orderby_values(self.order_by_columns(batch)?);
+            let order_by_columns = self.order_by_columns(batch)?;
+            let peer_groups = if evaluator.include_peers() {
+                evaluate_partition_ranges(num_rows, &order_by_columns)?
+            } else {
+                vec![]
+            };
+            let order_bys = get_orderby_values(order_by_columns);
             let n_args = values.len();
             values.extend(order_bys);
             let order_bys_ref = &values[n_args..];
@@ -141,13 +163,22 @@ impl WindowExpr for BuiltInWindowExpr {
             let mut last_range = Range { start: 0, end: 0 };
             // We iterate on each row to calculate window frame range and and window function result
             for idx in 0..num_rows {
-                let range = window_frame_ctx.calculate_range(
-                    order_bys_ref,
-                    &last_range,
-                    num_rows,
-                    idx,
-                )?;
-                let value = evaluator.evaluate(&values, &range)?;
+                let range = if evaluator.uses_window_frame() {
+                    window_frame_ctx.calculate_range(
+                        order_bys_ref,
+                        &last_range,
+                        num_rows,
+                        idx,
+                    )?
+                } else {
+                    evaluator.get_range(idx, num_rows)?
+                };
+                let value = match peer_group(&peer_groups, idx) {
+                    Some(peers) => {
+                        evaluator.evaluate_with_peers(&values, &range, peers)?
+                    }
+                    None => evaluator.evaluate(&values, &range)?,
+                };
                 row_wise_results.push(value);
                 last_range = range;
             }
@@ -192,9 +223,18 @@ impl WindowExpr for BuiltInWindowExpr {
             let state = &mut window_state.state;
 
             let batch_ref = &partition_batch_state.record_batch;
+            let num_rows = batch_ref.num_rows();
             let mut values = self.evaluate_args(batch_ref)?;
-            let order_bys = if evaluator.uses_window_frame() || evaluator.include_rank() {
-                get_orderby_values(self.order_by_columns(batch_ref)?)
+            let mut peer_groups = vec![];
+            let order_bys = if evaluator.uses_window_frame()
+                || evaluator.include_rank()
+                || evaluator.include_peers()
+            {
+                let order_by_columns = self.order_by_columns(batch_ref)?;
+                if evaluator.include_peers() {
+                    peer_groups = evaluate_partition_ranges(num_rows, &order_by_columns)?;
+                }
+                get_orderby_values(order_by_columns)
             } else {
                 vec![]
             };
@@ -203,8 +243,6 @@ impl WindowExpr for BuiltInWindowExpr {
             let order_bys_ref = &values[n_args..];
 
             // We iterate on each row to perform a running calculation.
-            let record_batch = &partition_batch_state.record_batch;
-            let num_rows = record_batch.num_rows();
             let mut row_wise_results: Vec<ScalarValue> = vec![];
             let is_causal = if evaluator.uses_window_frame() {
                 self.window_frame.is_causal()
@@ -239,10 +277,24 @@ impl WindowExpr for BuiltInWindowExpr {
                 {
                     break;
                 }
+                let peers = peer_group(&peer_groups, idx);
+                // Exit if the peer group may continue in the next batch:
+                if matches!(peers, Some(peers) if peers.end == num_rows)
+                    && !partition_batch_state.is_end
+                {
+                    break;
+                }
                 // Update last range
                 state.window_frame_range = frame_range;
-                row_wise_results
-                    .push(evaluator.evaluate(&values, &state.window_frame_range)?);
+                let value = match peers {
+                    Some(peers) => evaluator.evaluate_with_peers(
+                        &values,
+                        &state.window_frame_range,
+                        peers,
+                    )?,
+                    None => evaluator.evaluate(&values, &state.window_frame_range)?,
+                };
+                row_wise_results.push(value);
             }
             let out_col = if row_wise_results.is_empty() {
                 new_empty_array(out_type)
@@ -251,6 +303,16 @@ impl WindowExpr for BuiltInWindowExpr {
             };
 
             state.update(&out_col, partition_batch_state)?;
+            if partition_batch_state.is_end {
+                self.check_partition_rows(state.offset_pruned_rows + num_rows)?;
+            }
+            if evaluator.include_peers() {
+                // Keep the peer group of the next row when pruning, as it may
+                // start before its window frame
+                state.peer_group_start =
+                    peer_group(&peer_groups, state.last_calculated_index)
+                        .map(|peers| peers.start);
+            }
             if self.window_frame.start_bound.is_unbounded() {
                 evaluator.memoize(state)?;
             }
@@ -273,6 +335,19 @@ impl WindowExpr for BuiltInWindowExpr {
         })
     }
 
+    fn with_partition_rows(&self, num_rows: usize) -> Option<Arc<dyn WindowExpr>> {
+        self.expr.with_partition_rows(num_rows).map(|expr| {
+            let mut window_expr = BuiltInWindowExpr::new(
+                expr,
+                &self.partition_by,
+                &self.order_by,
+                self.window_frame.clone(),
+            );
+            window_expr.partition_rows = Some(num_rows);
+            Arc::new(window_expr) as _
+        })
+    }
+
     fn uses_bounded_memory(&self) -> bool {
         if let Ok(evaluator) = self.expr.create_evaluator() {
             evaluator.supports_bounded_execution()
@@ -283,3 +358,10 @@ impl WindowExpr for BuiltInWindowExpr {
         }
     }
 }
+
+/// Returns the range of `peer_groups` containing row `idx`, or `None` if
+/// there are no peer groups
+fn peer_group(peer_groups: &[Range<usize>], idx: usize) -> Option<&Range<usize>> {
+    let position = peer_groups.partition_point(|peers| peers.end <= idx);
+    peer_groups.get(position)
+}
@@ -91,4 +91,19 @@ pub trait BuiltInWindowFunctionExpr: Send + Sync + std::fmt::Debug {
     fn get_result_ordering(&self, _schema: &SchemaRef) -> Option<PhysicalSortExpr> {
         None
     }
+
+    /// Construct a new [`BuiltInWindowFunctionExpr`] that produces the same
+    /// result as this function on partitions of `num_rows` rows, known before
+    /// reading them, and that can then be evaluated with bounded memory.
+    ///
+    /// Returns `None` (the default) if the function doesn't need the number of
+    /// rows of its partitions, or still can't be evaluated with bounded memory.
+    ///
+    /// For example, `NTILE` is computed from the row number and `num_rows`.
+    fn with_partition_rows(
+        &self,
+        _num_rows: usize,
+    ) -> Option<Arc<dyn BuiltInWindowFunctionExpr>> {
+        None
+    }
 }
@@ -23,7 +23,7 @@ use crate::PhysicalExpr;
 use arrow::array::ArrayRef;
 use arrow::array::Float64Array;
 use arrow::datatypes::{DataType, Field};
-use datafusion_common::Result;
+use datafusion_common::{exec_err, Result, ScalarValue};
 use datafusion_expr::PartitionEvaluator;
 use std::any::Any;
 use std::iter;
@@ -36,6 +36,8 @@ pub struct CumeDist {
     name: String,
     /// Output data type
     data_type: DataType,
+    /// The number of rows of the partitions, if known before reading them
+    partition_rows: Option<usize>,
 }
 
 /// Create a cume_dist window function
@@ -43,6 +45,7 @@ pub fn cume_dist(name: String, data_type: &DataType) -> CumeDist {
     CumeDist {
         name,
         data_type: data_type.clone(),
+        partition_rows: None,
     }
 }
 
@@ -66,14 +69,57 @@ impl BuiltInWindowFunctionExpr for CumeDist {
     }
 
     fn create_evaluator(&self) -> Result<Box<dyn PartitionEvaluator>> {
-        Ok(Box::new(CumeDistEvaluator {}))
+        Ok(Box::new(CumeDistEvaluator {
+            partition_rows: self.partition_rows,
+            n_rows: 0,
+        }))
+    }
+
+    fn with_partition_rows(
+        &self,
+        num_rows: usize,
+    ) -> Option<Arc<dyn BuiltInWindowFunctionExpr>> {
+        Some(Arc::new(Self {
+            name: self.name.clone(),
+            data_type: self.data_type.clone(),
+            partition_rows: Some(num_rows),
+        }))
     }
 }
 
 #[derive(Debug)]
-pub(crate) struct CumeDistEvaluator;
+pub(crate) struct CumeDistEvaluator {
+    /// The number of rows of the partition, if known before reading it
+    partition_rows: Option<usize>,
+    /// The number of rows evaluated so far
+    n_rows: usize,
+}
 
 impl PartitionEvaluator for CumeDistEvaluator {
+    fn is_causal(&self) -> bool {
+        // The rows are evaluated once their peer groups are complete
+        self.partition_rows.is_some()
+    }
+
+    /// Evaluates the row at `range.start`, the rows up to the end of its
+    /// peer group `peers` having the same cumulative distribution
+    fn evaluate_with_peers(
+        &mut self,
+        _values: &[ArrayRef],
+        range: &Range<usize>,
+        peers: &Range<usize>,
+    ) -> Result<ScalarValue> {
+        let Some(num_rows) = self.partition_rows else {
+            return exec_err!("Can not execute CUME_DIST in a streaming fashion");
+        };
+        let n_rows = self.n_rows + peers.end - range.start;
+        if n_rows > num_rows {
+            return exec_err!("CUME_DIST expected partitions of {num_rows} rows");
+        }
+        self.n_rows += 1;
+        Ok(ScalarValue::Float64(Some(n_rows as f64 / num_rows as f64)))
+    }
+
     fn evaluate_all_with_rank(
         &self,
         num_rows: usize,
@@ -95,9 +141,17 @@ impl PartitionEvaluator for CumeDistEvaluator {
         Ok(Arc::new(result))
     }
 
+    fn supports_bounded_execution(&self) -> bool {
+        self.partition_rows.is_some()
+    }
+
     fn include_rank(&self) -> bool {
         true
     }
+
+    fn include_peers(&self) -> bool {
+        self.partition_rows.is_some()
+    }
 }
 
 #[cfg(test)]
@@ -239,6 +239,10 @@ impl PartitionEvaluator for WindowShiftEvaluator {
                 // How many rows needed previous than the current row to get necessary lag result
                 let offset: usize = self.non_null_offsets.iter().sum();
                 idx.saturating_sub(offset + 1)
+            } else if !self.ignore_nulls {
+                // Only the `shift_offset` rows before the current row are needed,
+                // so earlier rows of the partition don't need to be kept
+                idx.saturating_sub(self.shift_offset as usize)
             } else {
                 0
             };
@@ -249,6 +253,10 @@ impl PartitionEvaluator for WindowShiftEvaluator {
                 // How many rows needed further than the current row to get necessary lead result
                 let offset: usize = self.non_null_offsets.iter().sum();
                 min(idx + offset + 1, n_rows)
+            } else if !self.ignore_nulls {
+                // Only the `-shift_offset` rows after the current row are needed,
+                // so the result can be emitted before the end of the partition
+                min(idx + (-self.shift_offset) as usize + 1, n_rows)
             } else {
                 n_rows
             };
@@ -486,4 +494,34 @@ mod tests {
         )?;
         Ok(())
     }
+
+    #[test]
+    fn lead_lag_get_range() -> Result<()> {
+        let lag = lag(
+            "lag".to_owned(),
+            DataType::Int32,
+            Arc::new(Column::new("c3", 0)),
+            Some(2),
+            ScalarValue::Null.cast_to(&DataType::Int32)?,
+            false,
+        )
+        .create_evaluator()?;
+        // Only the two previous rows are needed
+        assert_eq!(lag.get_range(0, 10)?, 0..1);
+        assert_eq!(lag.get_range(5, 10)?, 3..6);
+
+        let lead = lead(
+            "lead".to_owned(),
+            DataType::Int32,
+            Arc::new(Column::new("c3", 0)),
+            Some(2),
+            ScalarValue::Null.cast_to(&DataType::Int32)?,
+            false,
+        )
+        .create_evaluator()?;
+        // Only the two next rows are needed
+        assert_eq!(lead.get_range(5, 10)?, 5..8);
+        assert_eq!(lead.get_range(8, 10)?, 8..10);
+        Ok(())
+    }
 }
@@ -25,10 +25,11 @@ use crate::{PhysicalExpr, PhysicalSortExpr};
 use arrow::array::{ArrayRef, UInt64Array};
 use arrow::datatypes::Field;
 use arrow_schema::{DataType, SchemaRef, SortOptions};
-use datafusion_common::Result;
+use datafusion_common::{exec_err, Result, ScalarValue};
 use datafusion_expr::PartitionEvaluator;
 
 use std::any::Any;
+use std::ops::Range;
 use std::sync::Arc;
 
 #[derive(Debug)]
@@ -37,6 +38,8 @@ pub struct Ntile {
     n: u64,
     /// Output data type
     data_type: DataType,
+    /// The number of rows of the partitions, if known before reading them
+    partition_rows: Option<usize>,
 }
 
 impl Ntile {
@@ -45,6 +48,7 @@ impl Ntile {
             name,
             n,
             data_type: data_type.clone(),
+            partition_rows: None,
         }
     }
 
@@ -72,7 +76,11 @@ impl BuiltInWindowFunctionExpr for Ntile {
     }
 
     fn create_evaluator(&self) -> Result<Box<dyn PartitionEvaluator>> {
-        Ok(Box::new(NtileEvaluator { n: self.n }))
+        Ok(Box::new(NtileEvaluator {
+            n: self.n,
+            partition_rows: self.partition_rows,
+            n_rows: 0,
+        }))
     }
 
     fn get_result_ordering(&self, schema: &SchemaRef) -> Option<PhysicalSortExpr> {
@@ -86,26 +94,72 @@ impl BuiltInWindowFunctionExpr for Ntile {
             PhysicalSortExpr { expr, options }
         })
     }
+
+    fn with_partition_rows(
+        &self,
+        num_rows: usize,
+    ) -> Option<Arc<dyn BuiltInWindowFunctionExpr>> {
+        Some(Arc::new(Self {
+            name: self.name.clone(),
+            n: self.n,
+            data_type: self.data_type.clone(),
+            partition_rows: Some(num_rows),
+        }))
+    }
 }
 
 #[derive(Debug)]
 pub(crate) struct NtileEvaluator {
     n: u64,
+    /// The number of rows of the partition, if known before reading it
+    partition_rows: Option<usize>,
+    /// The number of rows evaluated so far
+    n_rows: usize,
+}
+
+impl NtileEvaluator {
+    /// The bucket of row `idx` of a partition of `num_rows` rows
+    fn bucket(&self, idx: u64, num_rows: u64) -> u64 {
+        let n = u64::min(self.n, num_rows);
+        idx * n / num_rows + 1
+    }
 }
 
 impl PartitionEvaluator for NtileEvaluator {
+    fn is_causal(&self) -> bool {
+        // With the number of rows of the partition, the bucket of a row only
+        // depends on its row number
+        self.partition_rows.is_some()
+    }
+
+    fn evaluate(
+        &mut self,
+        _values: &[ArrayRef],
+        _range: &Range<usize>,
+    ) -> Result<ScalarValue> {
+        let Some(num_rows) = self.partition_rows else {
+            return exec_err!("Can not execute NTILE in a streaming fashion");
+        };
+        if self.n_rows >= num_rows {
+            return exec_err!("NTILE expected partitions of {num_rows} rows");
+        }
+        let bucket = self.bucket(self.n_rows as u64, num_rows as u64);
+        self.n_rows += 1;
+        Ok(ScalarValue::UInt64(Some(bucket)))
+    }
+
     fn evaluate_all(
         &mut self,
         _values: &[ArrayRef],
         num_rows: usize,
     ) -> Result<ArrayRef> {
         let num_rows = num_rows as u64;
-        let mut vec: Vec<u64> = Vec::new();
-        let n = u64::min(self.n, num_rows);
-        for i in 0..num_rows {
-            let res = i * n / num_rows;
-            vec.push(res + 1)
-        }
-        Ok(Arc::new(UInt64Array::from(vec)))
+        Ok(Arc::new(UInt64Array::from_iter_values(
+            (0..num_rows).map(|i| self.bucket(i, num_rows)),
+        )))
+    }
+
+    fn supports_bounded_execution(&self) -> bool {
+        self.partition_rows.is_some()
     }
 }
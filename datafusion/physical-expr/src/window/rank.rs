@@ -43,6 +43,8 @@ pub struct Rank {
     rank_type: RankType,
     /// Output data type
     data_type: DataType,
+    /// The number of rows of the partitions, if known before reading them
+    partition_rows: Option<usize>,
 }
 
 impl Rank {
@@ -65,6 +67,7 @@ pub fn rank(name: String, data_type: &DataType) -> Rank {
         name,
         rank_type: RankType::Basic,
         data_type: data_type.clone(),
+        partition_rows: None,
     }
 }
 
@@ -74,6 +77,7 @@ pub fn dense_rank(name: String, data_type: &DataType) -> Rank {
         name,
         rank_type: RankType::Dense,
         data_type: data_type.clone(),
+        partition_rows: None,
     }
 }
 
@@ -83,6 +87,7 @@ pub fn percent_rank(name: String, data_type: &DataType) -> Rank {
         name,
         rank_type: RankType::Percent,
         data_type: data_type.clone(),
+        partition_rows: None,
     }
 }
 
@@ -109,6 +114,7 @@ impl BuiltInWindowFunctionExpr for Rank {
         Ok(Box::new(RankEvaluator {
             state: RankState::default(),
             rank_type: self.rank_type,
+            partition_rows: self.partition_rows,
         }))
     }
 
@@ -123,17 +129,38 @@ impl BuiltInWindowFunctionExpr for Rank {
             PhysicalSortExpr { expr, options }
         })
     }
+
+    fn with_partition_rows(
+        &self,
+        num_rows: usize,
+    ) -> Option<Arc<dyn BuiltInWindowFunctionExpr>> {
+        // Only PERCENT_RANK depends on the number of rows of the partition
+        match self.rank_type {
+            RankType::Percent => Some(Arc::new(Self {
+                name: self.name.clone(),
+                rank_type: self.rank_type,
+                data_type: self.data_type.clone(),
+                partition_rows: Some(num_rows),
+            })),
+            RankType::Basic | RankType::Dense => None,
+        }
+    }
 }
 
 #[derive(Debug)]
 pub(crate) struct RankEvaluator {
     state: RankState,
     rank_type: RankType,
+    /// The number of rows of the partition, if known before reading it
+    partition_rows: Option<usize>,
 }
 
 impl PartitionEvaluator for RankEvaluator {
     fn is_causal(&self) -> bool {
-        matches!(self.rank_type, RankType::Basic | RankType::Dense)
+        match self.rank_type {
+            RankType::Basic | RankType::Dense => true,
+            RankType::Percent => self.partition_rows.is_some(),
+        }
     }
 
     /// Evaluates the window function inside the given range.
@@ -169,7 +196,14 @@ impl PartitionEvaluator for RankEvaluator {
             ))),
             RankType::Dense => Ok(ScalarValue::UInt64(Some(self.state.n_rank as u64))),
             RankType::Percent => {
-                exec_err!("Can not execute PERCENT_RANK in a streaming fashion")
+                let Some(num_rows) = self.partition_rows else {
+                    return exec_err!(
+                        "Can not execute PERCENT_RANK in a streaming fashion"
+                    );
+                };
+                let value = self.state.last_rank_boundary as f64
+                    / (num_rows as f64 - 1.0).max(1.0);
+                Ok(ScalarValue::Float64(Some(value)))
             }
         }
     }
@@ -222,7 +256,7 @@ impl PartitionEvaluator for RankEvaluator {
     }
 
     fn supports_bounded_execution(&self) -> bool {
-        matches!(self.rank_type, RankType::Basic | RankType::Dense)
+        self.is_causal()
     }
 
     fn include_rank(&self) -> bool {
@@ -0,0 +1,253 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines physical expression for `session_window` that can evaluated
+//! at runtime during query execution
+
+use crate::window::BuiltInWindowFunctionExpr;
+use crate::PhysicalExpr;
+
+use arrow::array::{Array, ArrayRef, BooleanArray, UInt64Array};
+use arrow::compute::kernels::cmp::gt;
+use arrow::compute::kernels::numeric::add;
+use arrow::datatypes::{DataType, Field};
+use datafusion_common::{Result, ScalarValue};
+use datafusion_expr::PartitionEvaluator;
+
+use std::any::Any;
+use std::ops::Range;
+use std::sync::Arc;
+
+/// session_window expression: numbers the sessions of a partition, where a
+/// new session starts whenever the time since the previous row, in the
+/// window ordering, exceeds a gap.
+#[derive(Debug)]
+pub struct SessionWindow {
+    name: String,
+    /// Output data type
+    data_type: DataType,
+    /// Time (or other ordering) expression
+    expr: Arc<dyn PhysicalExpr>,
+    /// Maximum gap between two rows of the same session
+    gap: ScalarValue,
+}
+
+impl SessionWindow {
+    /// Create a new SESSION_WINDOW function
+    pub fn new(
+        name: impl Into<String>,
+        data_type: &DataType,
+        expr: Arc<dyn PhysicalExpr>,
+        gap: ScalarValue,
+    ) -> Self {
+        Self {
+            name: name.into(),
+            data_type: data_type.clone(),
+            expr,
+            gap,
+        }
+    }
+
+    /// Get the gap of the session window expression
+    pub fn get_gap(&self) -> &ScalarValue {
+        &self.gap
+    }
+}
+
+impl BuiltInWindowFunctionExpr for SessionWindow {
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn field(&self) -> Result<Field> {
+        let nullable = false;
+        Ok(Field::new(self.name(), self.data_type.clone(), nullable))
+    }
+
+    fn expressions(&self) -> Vec<Arc<dyn PhysicalExpr>> {
+        vec![self.expr.clone()]
+    }
+
+    fn name(&self) -> &str {
+        &self.name
+    }
+
+    fn create_evaluator(&self) -> Result<Box<dyn PartitionEvaluator>> {
+        Ok(Box::new(SessionWindowEvaluator {
+            gap: self.gap.clone(),
+            session: 0,
+        }))
+    }
+}
+
+/// Returns, for every pair of `previous` and `current` values, whether
+/// `current` is more than `gap` after `previous`. NULL values never start a
+/// new session.
+fn exceeds_gap(
+    previous: &ArrayRef,
+    current: &ArrayRef,
+    gap: &ScalarValue,
+) -> Result<BooleanArray> {
+    let bound = add(previous, &gap.to_scalar()?)?;
+    Ok(gt(current, &bound)?)
+}
+
+#[derive(Debug)]
+pub(crate) struct SessionWindowEvaluator {
+    gap: ScalarValue,
+    /// Number of the session of the last evaluated row, 0 before the first row
+    session: u64,
+}
+
+impl PartitionEvaluator for SessionWindowEvaluator {
+    fn get_range(&self, idx: usize, _n_rows: usize) -> Result<Range<usize>> {
+        // The previous row is enough to know whether a session ends
+        Ok(Range {
+            start: idx.saturating_sub(1),
+            end: idx + 1,
+        })
+    }
+
+    fn is_causal(&self) -> bool {
+        true
+    }
+
+    fn evaluate(
+        &mut self,
+        values: &[ArrayRef],
+        range: &Range<usize>,
+    ) -> Result<ScalarValue> {
+        let current = range.end - 1;
+        if self.session == 0 {
+            self.session = 1;
+        } else if range.start < current {
+            let values = &values[0];
+            let new_session = exceeds_gap(
+                &values.slice(range.start, 1),
+                &values.slice(current, 1),
+                &self.gap,
+            )?;
+            if new_session.is_valid(0) && new_session.value(0) {
+                self.session += 1;
+            }
+        }
+        Ok(ScalarValue::UInt64(Some(self.session)))
+    }
+
+    fn evaluate_all(&mut self, values: &[ArrayRef], num_rows: usize) -> Result<ArrayRef> {
+        if num_rows == 0 {
+            return Ok(Arc::new(UInt64Array::from(Vec::<u64>::new())));
+        }
+        let values = &values[0];
+        let new_session = exceeds_gap(
+            &values.slice(0, num_rows - 1),
+            &values.slice(1, num_rows - 1),
+            &self.gap,
+        )?;
+        let mut session = 1;
+        let sessions = std::iter::once(session)
+            .chain(new_session.iter().map(|new_session| {
+                if new_session == Some(true) {
+                    session += 1;
+                }
+                session
+            }))
+            .collect::<Vec<_>>();
+        Ok(Arc::new(UInt64Array::from(sessions)))
+    }
+
+    fn supports_bounded_execution(&self) -> bool {
+        true
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::expressions::Column;
+
+    use arrow::array::{Int64Array, TimestampSecondArray};
+    use arrow::datatypes::Schema;
+    use arrow::record_batch::RecordBatch;
+    use datafusion_common::cast::as_uint64_array;
+
+    fn test_sessions(
+        values: ArrayRef,
+        gap: ScalarValue,
+        expected: Vec<u64>,
+    ) -> Result<()> {
+        let schema =
+            Schema::new(vec![Field::new("ts", values.data_type().clone(), true)]);
+        let batch = RecordBatch::try_new(Arc::new(schema), vec![values])?;
+        let expr = SessionWindow::new(
+            "session_window",
+            &DataType::UInt64,
+            Arc::new(Column::new("ts", 0)),
+            gap,
+        );
+        let values = expr.evaluate_args(&batch)?;
+        let num_rows = batch.num_rows();
+
+        // Evaluate the whole partition at once
+        let result = expr.create_evaluator()?.evaluate_all(&values, num_rows)?;
+        assert_eq!(as_uint64_array(&result)?.values(), expected.as_slice());
+
+        // Evaluate row by row, as done for bounded execution
+        let mut evaluator = expr.create_evaluator()?;
+        let result = (0..num_rows)
+            .map(|idx| {
+                let range = evaluator.get_range(idx, num_rows)?;
+                evaluator.evaluate(&values, &range)
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let expected = expected
+            .into_iter()
+            .map(|session| ScalarValue::UInt64(Some(session)))
+            .collect::<Vec<_>>();
+        assert_eq!(result, expected);
+        Ok(())
+    }
+
+    #[test]
+    fn session_window_integers() -> Result<()> {
+        test_sessions(
+            Arc::new(Int64Array::from(vec![
+                Some(1),
+                Some(3),
+                Some(10),
+                Some(11),
+                None,
+                Some(30),
+                Some(36),
+            ])),
+            ScalarValue::Int64(Some(5)),
+            vec![1, 1, 2, 2, 2, 2, 3],
+        )
+    }
+
+    #[test]
+    fn session_window_timestamps() -> Result<()> {
+        test_sessions(
+            Arc::new(TimestampSecondArray::from(vec![
+                0, 60, 120, 1000, 1030, 5000,
+            ])),
+            ScalarValue::new_interval_dt(0, 5 * 60 * 1000),
+            vec![1, 1, 1, 2, 2, 3],
+        )
+    }
+}
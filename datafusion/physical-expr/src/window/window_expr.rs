@@ -128,6 +128,12 @@ pub trait WindowExpr: Send + Sync + Debug {
 
     /// Get the reverse expression of this [WindowExpr].
     fn get_reverse_expr(&self) -> Option<Arc<dyn WindowExpr>>;
+
+    /// Get a version of this [WindowExpr] for partitions of `num_rows` rows,
+    /// known before reading them, which can run with bounded memory, if any.
+    fn with_partition_rows(&self, _num_rows: usize) -> Option<Arc<dyn WindowExpr>> {
+        None
+    }
 }
 
 /// Extension trait that adds common functionality to [`AggregateWindowExpr`]s
@@ -240,8 +246,28 @@ pub trait AggregateWindowExpr: WindowExpr {
             .transpose()?
             .map(get_orderby_values);
 
-        // We iterate on each row to perform a running calculation.
         let length = values[0].len();
+        // A frame spanning the whole partition is the same for all its rows:
+        // the aggregate is computed once the partition ended, and repeated
+        if self.get_window_frame().is_whole_partition() {
+            if not_end || idx >= length {
+                return Ok(new_empty_array(self.field()?.data_type()));
+            }
+            let cur_range = Range {
+                start: 0,
+                end: length,
+            };
+            let value = self.get_aggregate_result_inside_range(
+                last_range,
+                &cur_range,
+                &values,
+                accumulator,
+            )?;
+            *last_range = cur_range;
+            return value.to_array_of_size(length - idx);
+        }
+
+        // We iterate on each row to perform a running calculation.
         let mut row_wise_results: Vec<ScalarValue> = vec![];
         let is_causal = self.get_window_frame().is_causal();
         while idx < length {
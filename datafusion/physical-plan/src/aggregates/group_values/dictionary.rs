@@ -0,0 +1,154 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::marker::PhantomData;
+use std::sync::Arc;
+
+use crate::aggregates::group_values::{new_group_values, GroupValues};
+use arrow::compute::{cast, take};
+use arrow_array::cast::AsArray;
+use arrow_array::types::ArrowDictionaryKeyType;
+use arrow_array::{new_null_array, Array, ArrayRef, RecordBatch, UInt32Array};
+use arrow_buffer::ArrowNativeType;
+use arrow_schema::{DataType, Field, Schema};
+use datafusion_common::Result;
+use datafusion_execution::memory_pool::proxy::VecAllocExt;
+use datafusion_expr::EmitTo;
+
+/// A [`GroupValues`] for a single dictionary column, grouping the values of
+/// the dictionaries instead of each row.
+///
+/// Each value of the dictionary of a batch which is used by its keys is
+/// interned once, and the group of each row is then looked up by its key.
+/// The groups of the values are kept while the batches share the same
+/// dictionary, as the batches sliced from the same array do.
+pub struct GroupValuesDictionary<K: ArrowDictionaryKeyType> {
+    /// The dictionary type of the group column
+    data_type: DataType,
+    /// The groups of the values of the dictionaries
+    values: Box<dyn GroupValues>,
+    /// The dictionary `value_groups` refers to
+    dictionary: Option<ArrayRef>,
+    /// The group of each value of `dictionary`, if it was interned
+    value_groups: Vec<Option<usize>>,
+    /// Buffer reused to store the groups of the interned values
+    groups_buffer: Vec<usize>,
+    phantom: PhantomData<fn() -> K>,
+}
+
+impl<K: ArrowDictionaryKeyType> GroupValuesDictionary<K> {
+    pub fn try_new(data_type: DataType, value_type: &DataType) -> Result<Self> {
+        let schema = Schema::new(vec![Field::new("value", value_type.clone(), true)]);
+        Ok(Self {
+            data_type,
+            values: new_group_values(Arc::new(schema))?,
+            dictionary: None,
+            value_groups: vec![],
+            groups_buffer: vec![],
+            phantom: PhantomData,
+        })
+    }
+
+    /// Forgets the groups of the values of the last dictionary, e.g. once
+    /// the group indexes changed
+    fn reset_dictionary(&mut self) {
+        self.dictionary = None;
+        self.value_groups.clear();
+    }
+}
+
+impl<K: ArrowDictionaryKeyType> GroupValues for GroupValuesDictionary<K> {
+    fn intern(&mut self, cols: &[ArrayRef], groups: &mut Vec<usize>) -> Result<()> {
+        assert_eq!(cols.len(), 1);
+        let array = cols[0].as_dictionary::<K>();
+        let values = array.values();
+
+        let same_dictionary = self
+            .dictionary
+            .as_ref()
+            .is_some_and(|dictionary| Arc::ptr_eq(dictionary, values));
+        if !same_dictionary {
+            self.dictionary = Some(Arc::clone(values));
+            self.value_groups.clear();
+            self.value_groups.resize(values.len(), None);
+        }
+
+        // The values used by the keys which were not interned yet
+        let mut new_values = vec![];
+        let mut is_new = vec![false; values.len()];
+        for key in array.keys().iter().flatten() {
+            let key = key.as_usize();
+            if self.value_groups[key].is_none() && !is_new[key] {
+                is_new[key] = true;
+                new_values.push(key as u32);
+            }
+        }
+        if !new_values.is_empty() {
+            let new_values = UInt32Array::from(new_values);
+            let interned = take(values.as_ref(), &new_values, None)?;
+            self.values.intern(&[interned], &mut self.groups_buffer)?;
+            for (key, group) in new_values.values().iter().zip(&self.groups_buffer) {
+                self.value_groups[*key as usize] = Some(*group);
+            }
+        }
+
+        // The rows with a null key are in the group of the null value
+        let null_group = if array.keys().null_count() > 0 {
+            let null = new_null_array(values.data_type(), 1);
+            self.values.intern(&[null], &mut self.groups_buffer)?;
+            Some(self.groups_buffer[0])
+        } else {
+            None
+        };
+
+        groups.clear();
+        groups.extend(array.keys().iter().map(|key| match key {
+            Some(key) => self.value_groups[key.as_usize()].unwrap(),
+            None => null_group.unwrap(),
+        }));
+        Ok(())
+    }
+
+    fn size(&self) -> usize {
+        self.values.size()
+            + self.value_groups.allocated_size()
+            + self.groups_buffer.allocated_size()
+    }
+
+    fn is_empty(&self) -> bool {
+        self.values.is_empty()
+    }
+
+    fn len(&self) -> usize {
+        self.values.len()
+    }
+
+    fn emit(&mut self, emit_to: EmitTo) -> Result<Vec<ArrayRef>> {
+        // The emitted groups are removed, and the groups after them renumbered
+        self.reset_dictionary();
+        let mut output = self.values.emit(emit_to)?;
+        output[0] = cast(output[0].as_ref(), &self.data_type)?;
+        Ok(output)
+    }
+
+    fn clear_shrink(&mut self, batch: &RecordBatch) {
+        self.reset_dictionary();
+        self.values.clear_shrink(batch);
+        self.groups_buffer.clear();
+        self.groups_buffer.shrink_to(batch.num_rows());
+    }
+}
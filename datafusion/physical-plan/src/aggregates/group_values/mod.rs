@@ -16,6 +16,10 @@
 // under the License.
 
 use arrow::record_batch::RecordBatch;
+use arrow_array::types::{
+    Int16Type, Int32Type, Int64Type, Int8Type, UInt16Type, UInt32Type, UInt64Type,
+    UInt8Type,
+};
 use arrow_array::{downcast_primitive, ArrayRef};
 use arrow_schema::{DataType, SchemaRef};
 use datafusion_common::Result;
@@ -29,7 +33,10 @@ use row::GroupValuesRows;
 
 mod bytes;
 use bytes::GroupValuesByes;
+
+mod dictionary;
 use datafusion_physical_expr::binary_map::OutputType;
+use dictionary::GroupValuesDictionary;
 
 /// An interning store for group keys
 pub trait GroupValues: Send {
@@ -79,6 +86,28 @@ pub fn new_group_values(schema: SchemaRef) -> Result<Box<dyn GroupValues>> {
         if let DataType::LargeBinary = d {
             return Ok(Box::new(GroupValuesByes::<i64>::new(OutputType::Binary)));
         }
+        if let DataType::Dictionary(key_type, value_type) = d {
+            macro_rules! dictionary_helper {
+                ($t:ty) => {
+                    return Ok(Box::new(GroupValuesDictionary::<$t>::try_new(
+                        d.clone(),
+                        value_type,
+                    )?))
+                };
+            }
+
+            match key_type.as_ref() {
+                DataType::Int8 => dictionary_helper!(Int8Type),
+                DataType::Int16 => dictionary_helper!(Int16Type),
+                DataType::Int32 => dictionary_helper!(Int32Type),
+                DataType::Int64 => dictionary_helper!(Int64Type),
+                DataType::UInt8 => dictionary_helper!(UInt8Type),
+                DataType::UInt16 => dictionary_helper!(UInt16Type),
+                DataType::UInt32 => dictionary_helper!(UInt32Type),
+                DataType::UInt64 => dictionary_helper!(UInt64Type),
+                _ => {}
+            }
+        }
     }
 
     Ok(Box::new(GroupValuesRows::try_new(schema)?))
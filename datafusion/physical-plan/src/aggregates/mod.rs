@@ -1197,9 +1197,9 @@ mod tests {
     use crate::test::exec::{assert_strong_count_converges_to_zero, BlockingExec};
     use crate::RecordBatchStream;
 
-    use arrow::array::{Float64Array, UInt32Array};
+    use arrow::array::{AsArray, Float64Array, UInt32Array};
     use arrow::compute::{concat_batches, SortOptions};
-    use arrow::datatypes::DataType;
+    use arrow::datatypes::{DataType, Float64Type, UInt32Type};
     use datafusion_common::{
         assert_batches_eq, assert_batches_sorted_eq, internal_err, DataFusionError,
         ScalarValue,
@@ -1208,7 +1208,8 @@ mod tests {
     use datafusion_execution::memory_pool::FairSpillPool;
     use datafusion_execution::runtime_env::{RuntimeConfig, RuntimeEnv};
     use datafusion_physical_expr::expressions::{
-        lit, ApproxDistinct, Count, FirstValue, LastValue, Median, OrderSensitiveArrayAgg,
+        lit, ApproxDistinct, ArrayAgg, Count, FirstValue, LastValue, Median,
+        OrderSensitiveArrayAgg,
     };
     use datafusion_physical_expr::{reverse_order_bys, PhysicalSortExpr};
 
@@ -1862,6 +1863,151 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn spill_list_states_of_few_groups() -> Result<()> {
+        // 16 groups whose lists of all their values don't fit in memory
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("a", DataType::UInt32, false),
+            Field::new("b", DataType::Float64, false),
+        ]));
+        let batches = (0..100)
+            .map(|i| {
+                RecordBatch::try_new(
+                    schema.clone(),
+                    vec![
+                        Arc::new(UInt32Array::from_iter_values((0..100).map(|j| j % 16))),
+                        Arc::new(Float64Array::from_iter_values(
+                            (0..100).map(|j| (i * 100 + j) as f64),
+                        )),
+                    ],
+                )
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        let input = Arc::new(MemoryExec::try_new(&[batches], schema.clone(), None)?);
+
+        let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![Arc::new(ArrayAgg::new(
+            col("b", &schema)?,
+            "ARRAY_AGG(b)",
+            DataType::Float64,
+            false,
+        ))];
+        let aggregate_exec = Arc::new(AggregateExec::try_new(
+            AggregateMode::Single,
+            PhysicalGroupBy::new_single(vec![(col("a", &schema)?, "a".to_string())]),
+            aggregates,
+            vec![None],
+            input,
+            schema.clone(),
+        )?);
+
+        let task_ctx = new_spill_ctx(100, 64_000);
+        let result = common::collect(aggregate_exec.execute(0, task_ctx)?).await?;
+        let metrics = aggregate_exec.metrics().unwrap();
+        assert!(metrics.spill_count().unwrap() > 0);
+
+        let result = concat_batches(&aggregate_exec.schema(), &result)?;
+        assert_eq!(result.num_rows(), 16);
+        let groups = result.column(0).as_primitive::<UInt32Type>();
+        let lists = result.column(1).as_list::<i32>();
+        for (group, list) in groups.values().iter().zip(lists.iter()) {
+            let list = list.unwrap();
+            let mut values = list.as_primitive::<Float64Type>().values().to_vec();
+            values.sort_by(f64::total_cmp);
+            let expected = (0..10_000)
+                .filter(|value| value % 100 % 16 == *group)
+                .map(|value| value as f64)
+                .collect::<Vec<_>>();
+            assert_eq!(values, expected);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn skip_partial_aggregation() -> Result<()> {
+        let (schema, batches) = some_data();
+        let input = Arc::new(MemoryExec::try_new(&[batches], schema.clone(), None)?);
+
+        let grouping_set =
+            PhysicalGroupBy::new_single(vec![(col("a", &schema)?, "a".to_string())]);
+        let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![
+            Arc::new(Count::new(
+                col("b", &schema)?,
+                "COUNT(b)".to_string(),
+                DataType::Int64,
+            )),
+            Arc::new(Avg::new(
+                col("b", &schema)?,
+                "AVG(b)".to_string(),
+                DataType::Float64,
+            )),
+        ];
+
+        // The first batch has 3 groups for 4 rows, which is over the ratio
+        let mut session_config = SessionConfig::new();
+        let options = &mut session_config.options_mut().execution.aggregate;
+        options.skip_partial_aggregation_probe_rows_threshold = 4;
+        options.skip_partial_aggregation_probe_ratio_threshold = 0.5;
+        let task_ctx =
+            Arc::new(TaskContext::default().with_session_config(session_config));
+
+        let partial_aggregate = Arc::new(AggregateExec::try_new(
+            AggregateMode::Partial,
+            grouping_set,
+            aggregates.clone(),
+            vec![None, None],
+            input,
+            schema.clone(),
+        )?);
+        let result =
+            common::collect(partial_aggregate.execute(0, task_ctx.clone())?).await?;
+
+        // The second batch is passed through, one state per row
+        let expected = vec![
+            "+---+-----------------+---------------+-------------+",
+            "| a | COUNT(b)[count] | AVG(b)[count] | AVG(b)[sum] |",
+            "+---+-----------------+---------------+-------------+",
+            "| 2 | 1               | 1             | 1.0         |",
+            "| 2 | 1               | 1             | 1.0         |",
+            "| 3 | 1               | 1             | 2.0         |",
+            "| 3 | 1               | 1             | 2.0         |",
+            "| 3 | 1               | 1             | 3.0         |",
+            "| 4 | 1               | 1             | 4.0         |",
+            "| 4 | 2               | 2             | 7.0         |",
+            "+---+-----------------+---------------+-------------+",
+        ];
+        assert_batches_sorted_eq!(expected, &result);
+
+        let metrics = partial_aggregate.metrics().unwrap();
+        assert_eq!(
+            metrics
+                .sum_by_name("skipped_aggregation_rows")
+                .map(|m| m.as_usize()),
+            Some(4)
+        );
+
+        let final_aggregate = Arc::new(AggregateExec::try_new(
+            AggregateMode::Final,
+            PhysicalGroupBy::new_single(vec![(col("a", &schema)?, "a".to_string())]),
+            aggregates,
+            vec![None, None],
+            partial_aggregate,
+            schema,
+        )?);
+        let result = common::collect(final_aggregate.execute(0, task_ctx)?).await?;
+
+        let expected = vec![
+            "+---+----------+--------------------+",
+            "| a | COUNT(b) | AVG(b)             |",
+            "+---+----------+--------------------+",
+            "| 2 | 2        | 1.0                |",
+            "| 3 | 3        | 2.3333333333333335 |",
+            "| 4 | 3        | 3.6666666666666665 |",
+            "+---+----------+--------------------+",
+        ];
+        assert_batches_sorted_eq!(expected, &result);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_drop_cancel_without_groups() -> Result<()> {
         let task_ctx = Arc::new(TaskContext::default());
@@ -24,11 +24,11 @@ use std::vec;
 use crate::aggregates::group_values::{new_group_values, GroupValues};
 use crate::aggregates::order::GroupOrderingFull;
 use crate::aggregates::{
-    evaluate_group_by, evaluate_many, evaluate_optional, group_schema, AggregateMode,
-    PhysicalGroupBy,
+    create_schema, evaluate_group_by, evaluate_many, evaluate_optional, group_schema,
+    AggregateMode, PhysicalGroupBy,
 };
-use crate::common::IPCWriter;
-use crate::metrics::{BaselineMetrics, RecordOutput};
+use crate::common::{spill_write_options, IPCWriter};
+use crate::metrics::{BaselineMetrics, Count, MetricBuilder, RecordOutput};
 use crate::sorts::sort::{read_spill_as_stream, sort_batch};
 use crate::sorts::streaming_merge;
 use crate::stream::RecordBatchStreamAdapter;
@@ -38,7 +38,8 @@ use crate::{RecordBatchStream, SendableRecordBatchStream};
 use arrow::array::*;
 use arrow::datatypes::SchemaRef;
 use arrow_schema::SortOptions;
-use datafusion_common::{DataFusionError, Result};
+use datafusion_common::config::SpillCompression;
+use datafusion_common::{internal_datafusion_err, DataFusionError, Result};
 use datafusion_execution::disk_manager::RefCountedTempFile;
 use datafusion_execution::memory_pool::proxy::VecAllocExt;
 use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
@@ -61,6 +62,10 @@ pub(crate) enum ExecutionState {
     /// When producing output, the remaining rows to output are stored
     /// here and are sliced off as needed in batch_size chunks
     ProducingOutput(RecordBatch),
+    /// Partial aggregation found that grouping does not reduce its
+    /// input enough, and converts each input batch directly into
+    /// intermediate states, one per input row
+    SkippingAggregation,
     Done,
 }
 
@@ -87,6 +92,99 @@ struct SpillState {
 
     /// GROUP BY expressions for merging spilled data
     merging_group_by: PhysicalGroupBy,
+
+    /// Compression codec of the spill files
+    spill_compression: SpillCompression,
+
+    /// count of spills during the execution of the operator
+    spill_count: Count,
+
+    /// total spilled bytes (in memory, before compression) during the
+    /// execution of the operator
+    spilled_bytes: Count,
+
+    /// total spilled rows during the execution of the operator
+    spilled_rows: Count,
+
+    /// total size of the spill files on disk, after any compression
+    spill_file_bytes: Count,
+
+    /// The largest memory size of the input batches, which the spilled
+    /// batches don't exceed so that merging them back holds about one input
+    /// batch per spill file
+    max_batch_memory: usize,
+
+    /// The number of rows of the batches of the merged spills, the fewest
+    /// rows of the spilled batches, not to hold more rows while merging
+    merge_batch_size: usize,
+}
+
+/// Tracks the ratio of groups to input rows of a partial aggregation,
+/// to decide whether it should stop grouping its input.
+///
+/// When most groups consist of a single row, partial aggregation
+/// spends time and memory building a hash table that hardly reduces the
+/// number of rows sent to the final aggregation. Once
+/// `probe_rows_threshold` rows have been read, if the number of groups
+/// divided by the number of input rows is at least
+/// `probe_ratio_threshold`, the remaining input is passed through using
+/// [`GroupsAccumulator::convert_to_state`] instead.
+struct SkipAggregationProbe {
+    /// Number of input rows seen so far
+    input_rows: usize,
+    /// Number of groups after the last input batch
+    num_groups: usize,
+    /// Number of input rows after which the decision is made
+    probe_rows_threshold: usize,
+    /// Ratio of groups to input rows from which aggregation is skipped
+    probe_ratio_threshold: f64,
+    /// True once the decision has been made, it is never revisited
+    is_locked: bool,
+    /// Whether aggregation should be skipped
+    should_skip: bool,
+    /// Number of input rows passed through without being aggregated
+    skipped_aggregation_rows: Count,
+}
+
+impl SkipAggregationProbe {
+    fn new(
+        probe_rows_threshold: usize,
+        probe_ratio_threshold: f64,
+        skipped_aggregation_rows: Count,
+    ) -> Self {
+        Self {
+            input_rows: 0,
+            num_groups: 0,
+            probe_rows_threshold,
+            probe_ratio_threshold,
+            is_locked: false,
+            should_skip: false,
+            skipped_aggregation_rows,
+        }
+    }
+
+    /// Records that `input_rows` more rows were aggregated into a total
+    /// of `num_groups` groups
+    fn update_state(&mut self, input_rows: usize, num_groups: usize) {
+        if self.is_locked {
+            return;
+        }
+        self.input_rows += input_rows;
+        self.num_groups = num_groups;
+        if self.input_rows >= self.probe_rows_threshold {
+            self.should_skip = self.num_groups as f64 / self.input_rows as f64
+                >= self.probe_ratio_threshold;
+            self.is_locked = true;
+        }
+    }
+
+    fn should_skip(&self) -> bool {
+        self.should_skip
+    }
+
+    fn record_skipped(&mut self, batch: &RecordBatch) {
+        self.skipped_aggregation_rows.add(batch.num_rows());
+    }
 }
 
 /// HashTable based Grouping Aggregator
@@ -274,6 +372,11 @@ pub(crate) struct GroupedHashAggregateStream {
     /// the `GroupedHashAggregateStream` operation immediately switches to
     /// output mode and emits all groups.
     group_values_soft_limit: Option<usize>,
+
+    /// Decides whether a partial aggregation should stop grouping its
+    /// input. `None` if aggregation can not be skipped, e.g. because an
+    /// accumulator does not support [`GroupsAccumulator::convert_to_state`]
+    skip_aggregation_probe: Option<SkipAggregationProbe>,
 }
 
 impl GroupedHashAggregateStream {
@@ -325,6 +428,16 @@ impl GroupedHashAggregateStream {
             .map(create_group_accumulator)
             .collect::<Result<_>>()?;
 
+        // Spilled data is the intermediate states of the groups, whatever the
+        // mode, so that it is merged back like the output of a partial aggregation
+        let partial_agg_schema = create_schema(
+            &agg.input().schema(),
+            &agg_group_by.expr,
+            &aggregate_exprs,
+            false,
+            AggregateMode::Partial,
+        )?;
+
         let group_schema = group_schema(&agg_schema, agg_group_by.expr.len());
         let spill_expr = group_schema
             .fields
@@ -350,6 +463,29 @@ impl GroupedHashAggregateStream {
             ordering.as_slice(),
         )?;
 
+        // Partial aggregation can only pass its input through when the
+        // input is not ordered by the group keys, there is no limit and
+        // every accumulator can convert input rows into states
+        let execution_options = &context.session_config().options().execution;
+        let options = &execution_options.aggregate;
+        let skip_aggregation_probe = if matches!(agg.mode, AggregateMode::Partial)
+            && matches!(group_ordering, GroupOrdering::None)
+            && agg.limit.is_none()
+            && agg_group_by.is_single()
+            && accumulators
+                .iter()
+                .all(|acc| acc.supports_convert_to_state())
+        {
+            Some(SkipAggregationProbe::new(
+                options.skip_partial_aggregation_probe_rows_threshold,
+                options.skip_partial_aggregation_probe_ratio_threshold,
+                MetricBuilder::new(&agg.metrics)
+                    .counter("skipped_aggregation_rows", partition),
+            ))
+        } else {
+            None
+        };
+
         let group_values = new_group_values(group_schema)?;
         timer.done();
 
@@ -358,10 +494,18 @@ impl GroupedHashAggregateStream {
         let spill_state = SpillState {
             spills: vec![],
             spill_expr,
-            spill_schema: agg_schema.clone(),
+            spill_schema: Arc::new(partial_agg_schema),
             is_stream_merging: false,
             merging_aggregate_arguments,
             merging_group_by: PhysicalGroupBy::new_single(agg_group_by.expr.clone()),
+            spill_compression: execution_options.spill_compression,
+            spill_count: MetricBuilder::new(&agg.metrics).spill_count(partition),
+            spilled_bytes: MetricBuilder::new(&agg.metrics).spilled_bytes(partition),
+            spilled_rows: MetricBuilder::new(&agg.metrics).spilled_rows(partition),
+            spill_file_bytes: MetricBuilder::new(&agg.metrics)
+                .counter("spill_file_bytes", partition),
+            max_batch_memory: 0,
+            merge_batch_size: batch_size,
         };
 
         Ok(GroupedHashAggregateStream {
@@ -383,6 +527,7 @@ impl GroupedHashAggregateStream {
             runtime: context.runtime_env(),
             spill_state,
             group_values_soft_limit: agg.limit,
+            skip_aggregation_probe,
         })
     }
 }
@@ -433,6 +578,7 @@ impl Stream for GroupedHashAggregateStream {
                         // new batch to aggregate
                         Some(Ok(batch)) => {
                             let timer = elapsed_compute.timer();
+                            let input_rows = batch.num_rows();
                             // Make sure we have enough capacity for `batch`, otherwise spill
                             extract_ok!(self.spill_previous_if_necessary(&batch));
 
@@ -460,6 +606,17 @@ impl Stream for GroupedHashAggregateStream {
                                 break 'reading_input;
                             }
 
+                            // If grouping does not reduce the input enough, emit
+                            // all groups and pass the remaining input through
+                            self.update_skip_aggregation_probe(input_rows);
+                            if self.should_skip_aggregation() {
+                                let batch = extract_ok!(self.emit(EmitTo::All, false));
+                                self.exec_state = ExecutionState::ProducingOutput(batch);
+                                timer.done();
+                                // make sure the exec_state just set is not overwritten below
+                                break 'reading_input;
+                            }
+
                             extract_ok!(self.emit_early_if_necessary());
 
                             timer.done();
@@ -483,6 +640,8 @@ impl Stream for GroupedHashAggregateStream {
                         (
                             if self.input_done {
                                 ExecutionState::Done
+                            } else if self.should_skip_aggregation() {
+                                ExecutionState::SkippingAggregation
                             } else {
                                 ExecutionState::ReadingInput
                             },
@@ -501,6 +660,27 @@ impl Stream for GroupedHashAggregateStream {
                     )));
                 }
 
+                ExecutionState::SkippingAggregation => {
+                    match ready!(self.input.poll_next_unpin(cx)) {
+                        Some(Ok(batch)) => {
+                            let _timer = elapsed_compute.timer();
+                            let states = extract_ok!(self.transform_to_states(batch));
+                            return Poll::Ready(Some(Ok(
+                                states.record_output(&self.baseline_metrics)
+                            )));
+                        }
+                        Some(Err(e)) => {
+                            // inner had error, return to caller
+                            return Poll::Ready(Some(Err(e)));
+                        }
+                        None => {
+                            // inner is done, all states were already emitted
+                            self.input_done = true;
+                            self.exec_state = ExecutionState::Done;
+                        }
+                    }
+                }
+
                 ExecutionState::Done => {
                     // release the memory reservation since sending back output batch itself needs
                     // some memory reservation, so make some room for it.
@@ -602,9 +782,10 @@ impl GroupedHashAggregateStream {
 
         match self.update_memory_reservation() {
             // Here we can ignore `insufficient_capacity_err` because we will spill later,
-            // but at least one batch should fit in the memory
+            // but at least one batch should fit in the memory, unless the groups can be
+            // spilled: a few groups may hold large states, e.g. the lists of ARRAY_AGG
             Err(DataFusionError::ResourcesExhausted(_))
-                if self.group_values.len() >= self.batch_size =>
+                if self.group_values.len() >= self.batch_size || self.can_spill() =>
             {
                 Ok(())
             }
@@ -665,41 +846,77 @@ impl GroupedHashAggregateStream {
     /// (~ 1 [`RecordBatch`]) for simplicity. In such cases, spill the data to disk and clear the
     /// memory. Currently only [`GroupOrdering::None`] is supported for spilling.
     fn spill_previous_if_necessary(&mut self, batch: &RecordBatch) -> Result<()> {
-        // TODO: support group_ordering for spilling
-        if self.group_values.len() > 0
-            && batch.num_rows() > 0
-            && matches!(self.group_ordering, GroupOrdering::None)
-            && !matches!(self.mode, AggregateMode::Partial)
-            && !self.spill_state.is_stream_merging
+        self.spill_state.max_batch_memory = self
+            .spill_state
+            .max_batch_memory
+            .max(batch.get_array_memory_size());
+        if batch.num_rows() > 0
+            && self.can_spill()
             && self.update_memory_reservation().is_err()
         {
-            // Use input batch (Partial mode) schema for spilling because
-            // the spilled data will be merged and re-evaluated later.
-            self.spill_state.spill_schema = batch.schema();
             self.spill()?;
             self.clear_shrink(batch);
         }
         Ok(())
     }
 
+    /// Returns true if the groups can be spilled to release their memory.
+    fn can_spill(&self) -> bool {
+        // TODO: support group_ordering for spilling
+        !self.group_values.is_empty()
+            && matches!(self.group_ordering, GroupOrdering::None)
+            && !matches!(self.mode, AggregateMode::Partial)
+            && !self.spill_state.is_stream_merging
+    }
+
+    /// Returns the number of rows of the batches `sorted` is spilled in: at
+    /// most `batch_size`, and fewer when its rows are large, e.g. the states
+    /// of a few groups with large lists, so that each batch is about as large
+    /// as an input batch.
+    fn spill_batch_rows(&self, sorted: &RecordBatch) -> usize {
+        let row_memory = sorted.get_array_memory_size() / sorted.num_rows().max(1);
+        let rows = self.spill_state.max_batch_memory / row_memory.max(1);
+        rows.clamp(1, self.batch_size)
+    }
+
     /// Emit all rows, sort them, and store them on disk.
     fn spill(&mut self) -> Result<()> {
         let emit = self.emit(EmitTo::All, true)?;
-        let sorted = sort_batch(&emit, &self.spill_state.spill_expr, None)?;
+        self.spill_batch(&emit)
+    }
+
+    /// Sort the emitted rows `emit` and store them on disk.
+    fn spill_batch(&mut self, emit: &RecordBatch) -> Result<()> {
+        let sorted = sort_batch(emit, &self.spill_state.spill_expr, None)?;
         let spillfile = self.runtime.disk_manager.create_tmp_file("HashAggSpill")?;
-        let mut writer = IPCWriter::new(spillfile.path(), &emit.schema())?;
+        let mut writer = IPCWriter::new_with_options(
+            spillfile.path(),
+            &emit.schema(),
+            spill_write_options(self.spill_state.spill_compression)?,
+        )?;
         // TODO: slice large `sorted` and write to multiple files in parallel
         let mut offset = 0;
         let total_rows = sorted.num_rows();
+        let batch_rows = self.spill_batch_rows(&sorted);
+        self.spill_state.merge_batch_size =
+            self.spill_state.merge_batch_size.min(batch_rows);
 
         while offset < total_rows {
-            let length = std::cmp::min(total_rows - offset, self.batch_size);
+            let length = std::cmp::min(total_rows - offset, batch_rows);
             let batch = sorted.slice(offset, length);
             offset += batch.num_rows();
             writer.write(&batch)?;
         }
 
         writer.finish()?;
+        self.spill_state.spill_count.add(1);
+        self.spill_state
+            .spilled_bytes
+            .add(sorted.get_array_memory_size());
+        self.spill_state.spilled_rows.add(writer.num_rows as usize);
+        self.spill_state
+            .spill_file_bytes
+            .add(writer.file_size()? as usize);
         self.spill_state.spills.push(spillfile);
         Ok(())
     }
@@ -734,23 +951,29 @@ impl GroupedHashAggregateStream {
     }
 
     /// At this point, all the inputs are read and there are some spills.
-    /// Emit the remaining rows and create a batch.
+    /// Emit the remaining rows and create a batch. If it is larger than the input batches,
+    /// e.g. because of the large states of a few groups, spill it too, so that all the rows
+    /// are read back in batches about as large as the input batches.
     /// Conduct a streaming merge sort between the batch and spilled data. Since the stream is fully
     /// sorted, set `self.group_ordering` to Full, then later we can read with [`EmitTo::First`].
     fn update_merged_stream(&mut self) -> Result<()> {
         let batch = self.emit(EmitTo::All, true)?;
+        let mut streams: Vec<SendableRecordBatchStream> = vec![];
+        let schema = self.spill_state.spill_schema.clone();
+        if batch.get_array_memory_size() > self.spill_state.max_batch_memory {
+            self.spill_batch(&batch)?;
+        } else {
+            let expr = self.spill_state.spill_expr.clone();
+            streams.push(Box::pin(RecordBatchStreamAdapter::new(
+                schema.clone(),
+                futures::stream::once(futures::future::lazy(move |_| {
+                    sort_batch(&batch, &expr, None)
+                })),
+            )));
+        }
         // clear up memory for streaming_merge
         self.clear_all();
         self.update_memory_reservation()?;
-        let mut streams: Vec<SendableRecordBatchStream> = vec![];
-        let expr = self.spill_state.spill_expr.clone();
-        let schema = batch.schema();
-        streams.push(Box::pin(RecordBatchStreamAdapter::new(
-            schema.clone(),
-            futures::stream::once(futures::future::lazy(move |_| {
-                sort_batch(&batch, &expr, None)
-            })),
-        )));
         for spill in self.spill_state.spills.drain(..) {
             let stream = read_spill_as_stream(spill, schema.clone())?;
             streams.push(stream);
@@ -761,7 +984,7 @@ impl GroupedHashAggregateStream {
             schema,
             &self.spill_state.spill_expr,
             self.baseline_metrics.clone(),
-            self.batch_size,
+            self.spill_state.merge_batch_size,
             None,
             self.reservation.new_empty(),
         )?;
@@ -779,6 +1002,51 @@ impl GroupedHashAggregateStream {
         group_values_soft_limit <= self.group_values.len()
     }
 
+    /// Updates the skip aggregation probe with the rows of the last
+    /// input batch and the resulting number of groups
+    fn update_skip_aggregation_probe(&mut self, input_rows: usize) {
+        if let Some(probe) = self.skip_aggregation_probe.as_mut() {
+            probe.update_state(input_rows, self.group_values.len());
+        }
+    }
+
+    /// Returns true if the partial aggregation should pass its input
+    /// through instead of grouping it
+    fn should_skip_aggregation(&self) -> bool {
+        self.skip_aggregation_probe
+            .as_ref()
+            .is_some_and(|probe| probe.should_skip())
+    }
+
+    /// Converts an input batch into a batch of intermediate states, one
+    /// per input row, with the schema of the partial aggregation output
+    fn transform_to_states(&mut self, batch: RecordBatch) -> Result<RecordBatch> {
+        let group_values = evaluate_group_by(&self.group_by, &batch)?;
+        let input_values = evaluate_many(&self.aggregate_arguments, &batch)?;
+        let filter_values = evaluate_optional(&self.filter_expressions, &batch)?;
+
+        let mut output = group_values.into_iter().next().ok_or_else(|| {
+            internal_datafusion_err!(
+                "Expected a single grouping set when skipping aggregation"
+            )
+        })?;
+        let iter = self
+            .accumulators
+            .iter()
+            .zip(input_values.iter())
+            .zip(filter_values.iter());
+        for ((acc, values), opt_filter) in iter {
+            let opt_filter = opt_filter.as_ref().map(|filter| filter.as_boolean());
+            output.extend(acc.convert_to_state(values, opt_filter)?);
+        }
+
+        let states = RecordBatch::try_new(self.schema(), output)?;
+        if let Some(probe) = self.skip_aggregation_probe.as_mut() {
+            probe.record_skipped(&states);
+        }
+        Ok(states)
+    }
+
     /// common function for signalling end of processing of the input stream
     fn set_input_done_and_produce_output(&mut self) -> Result<()> {
         self.input_done = true;
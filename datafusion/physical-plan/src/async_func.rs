@@ -0,0 +1,409 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the execution plan evaluating async scalar functions, see
+//! [`AsyncScalarUDF`]
+
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use super::metrics::{
+    BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet, RecordOutput,
+};
+use super::stream::RecordBatchStreamAdapter;
+use super::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, ExecutionPlanProperties, PlanProperties,
+    SendableRecordBatchStream,
+};
+use crate::expressions::Column;
+use crate::PhysicalExpr;
+
+use arrow::array::ArrayRef;
+use arrow::compute::concat;
+use arrow::datatypes::{Field, Fields, Schema};
+use arrow::record_batch::RecordBatch;
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{internal_err, DataFusionError, Result};
+use datafusion_execution::TaskContext;
+use datafusion_expr::{
+    AsyncScalarUDF, ColumnarValue, ScalarFunctionDefinition, ScalarUDFImpl,
+};
+use datafusion_physical_expr::equivalence::ProjectionMapping;
+use datafusion_physical_expr::ScalarFunctionExpr;
+
+use futures::stream::TryStreamExt;
+
+/// Returns the implementation of the function called by `expr`, if it is an
+/// [`AsyncScalarUDF`]
+fn async_udf(expr: &Arc<dyn PhysicalExpr>) -> Option<Arc<dyn ScalarUDFImpl>> {
+    let func = expr.as_any().downcast_ref::<ScalarFunctionExpr>()?;
+    let ScalarFunctionDefinition::UDF(udf) = func.fun() else {
+        return None;
+    };
+    let inner = udf.inner();
+    inner.as_any().is::<AsyncScalarUDF>().then_some(inner)
+}
+
+/// A call to an [`AsyncScalarUDF`], evaluated by an [`AsyncFuncExec`] into a
+/// column named `name`
+#[derive(Debug, Clone)]
+pub struct AsyncFuncExpr {
+    name: String,
+    func: Arc<dyn PhysicalExpr>,
+    udf: Arc<dyn ScalarUDFImpl>,
+}
+
+impl AsyncFuncExpr {
+    /// Create a new [`AsyncFuncExpr`] for `func`, which must be a
+    /// [`ScalarFunctionExpr`] calling an [`AsyncScalarUDF`]
+    pub fn try_new(name: impl Into<String>, func: Arc<dyn PhysicalExpr>) -> Result<Self> {
+        let Some(udf) = async_udf(&func) else {
+            return internal_err!("{func} is not a call to an async scalar function");
+        };
+        Ok(Self {
+            name: name.into(),
+            func,
+            udf,
+        })
+    }
+
+    /// Returns the name of the column of the results
+    pub fn name(&self) -> &str {
+        &self.name
+    }
+
+    /// Returns the function call
+    pub fn func(&self) -> &Arc<dyn PhysicalExpr> {
+        &self.func
+    }
+
+    /// Returns the field of the column of the results, given the schema of
+    /// the batches it is evaluated on
+    pub fn field(&self, input_schema: &Schema) -> Result<Field> {
+        Ok(Field::new(
+            &self.name,
+            self.func.data_type(input_schema)?,
+            self.func.nullable(input_schema)?,
+        ))
+    }
+
+    /// Evaluate the function call on `batch`
+    pub async fn evaluate(&self, batch: &RecordBatch) -> Result<ArrayRef> {
+        let Some(udf) = self.udf.as_any().downcast_ref::<AsyncScalarUDF>() else {
+            return internal_err!("{} is not an async scalar function", self.udf.name());
+        };
+        let Some(func) = self.func.as_any().downcast_ref::<ScalarFunctionExpr>() else {
+            return internal_err!("{} is not a scalar function", self.func);
+        };
+        let num_rows = batch.num_rows();
+        let batch_size = udf.ideal_batch_size().unwrap_or(num_rows).max(1);
+        let mut results = Vec::with_capacity(num_rows.div_ceil(batch_size));
+        for offset in (0..num_rows.max(1)).step_by(batch_size) {
+            let chunk = batch.slice(offset, batch_size.min(num_rows - offset));
+            let args = func
+                .args()
+                .iter()
+                .map(|arg| arg.evaluate(&chunk))
+                .collect::<Result<Vec<ColumnarValue>>>()?;
+            let result = udf.invoke_async(&args, chunk.num_rows()).await?;
+            if result.len() != chunk.num_rows() {
+                return internal_err!(
+                    "{} returned {} rows, expected {}",
+                    udf.name(),
+                    result.len(),
+                    chunk.num_rows()
+                );
+            }
+            results.push(result);
+        }
+        match results.as_slice() {
+            [result] => Ok(result.clone()),
+            results => {
+                let results = results.iter().map(|r| r.as_ref()).collect::<Vec<_>>();
+                Ok(concat(&results)?)
+            }
+        }
+    }
+}
+
+impl fmt::Display for AsyncFuncExpr {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{} as {}", self.func, self.name)
+    }
+}
+
+/// Rewrites the calls to [`AsyncScalarUDF`]s in expressions to columns
+/// appended to the input by an [`AsyncFuncExec`]
+///
+/// ```text
+/// ProjectionExec: expr=[enrich(a@0) + 1]
+/// ```
+///
+/// becomes
+///
+/// ```text
+/// ProjectionExec: expr=[__async_fn_0@1 + 1]
+///   AsyncFuncExec: async_expr=[enrich(a@0) as __async_fn_0]
+/// ```
+#[derive(Debug)]
+pub struct AsyncMapper {
+    /// Number of columns of the input
+    num_input_columns: usize,
+    /// The calls to evaluate, in order
+    async_exprs: Vec<Arc<AsyncFuncExpr>>,
+}
+
+impl AsyncMapper {
+    /// Create a new [`AsyncMapper`] for expressions over an input with
+    /// `num_input_columns` columns
+    pub fn new(num_input_columns: usize) -> Self {
+        Self {
+            num_input_columns,
+            async_exprs: vec![],
+        }
+    }
+
+    /// Returns true if no async function call has been found
+    pub fn is_empty(&self) -> bool {
+        self.async_exprs.is_empty()
+    }
+
+    /// Returns the async function calls found so far
+    pub fn async_exprs(&self) -> &[Arc<AsyncFuncExpr>] {
+        &self.async_exprs
+    }
+
+    /// Returns `expr` with its async function calls replaced by columns,
+    /// recording the calls to evaluate
+    ///
+    /// Nested calls are evaluated first, so outer calls see their results.
+    pub fn map_expr(
+        &mut self,
+        expr: Arc<dyn PhysicalExpr>,
+    ) -> Result<Arc<dyn PhysicalExpr>> {
+        expr.transform_up(|expr| {
+            if async_udf(&expr).is_none() {
+                return Ok(Transformed::no(expr));
+            }
+            let position = self
+                .async_exprs
+                .iter()
+                .position(|async_expr| async_expr.func.eq(&expr));
+            let position = match position {
+                Some(position) => position,
+                None => {
+                    let name = format!("__async_fn_{}", self.async_exprs.len());
+                    self.async_exprs
+                        .push(Arc::new(AsyncFuncExpr::try_new(name, expr)?));
+                    self.async_exprs.len() - 1
+                }
+            };
+            Ok(Transformed::yes(Arc::new(Column::new(
+                &self.async_exprs[position].name,
+                self.num_input_columns + position,
+            ))))
+        })
+        .data()
+    }
+
+    /// Returns an [`AsyncFuncExec`] evaluating the async function calls found
+    /// over `input`, or `input` if there is none
+    pub fn into_exec(
+        self,
+        input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if self.is_empty() {
+            Ok(input)
+        } else {
+            Ok(Arc::new(AsyncFuncExec::try_new(self.async_exprs, input)?))
+        }
+    }
+}
+
+/// Execution plan appending the results of calls to [`AsyncScalarUDF`]s to
+/// its input batches
+///
+/// Each expression is evaluated on the input columns and the results of the
+/// previous expressions. Up to `datafusion.execution.async_udf_concurrency`
+/// batches of each partition are evaluated concurrently, and output in the
+/// order of the input.
+#[derive(Debug)]
+pub struct AsyncFuncExec {
+    /// The async function calls to evaluate
+    async_exprs: Vec<Arc<AsyncFuncExpr>>,
+    /// The input plan
+    input: Arc<dyn ExecutionPlan>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl AsyncFuncExec {
+    /// Create a new [`AsyncFuncExec`] evaluating `async_exprs` on `input`
+    pub fn try_new(
+        async_exprs: Vec<Arc<AsyncFuncExpr>>,
+        input: Arc<dyn ExecutionPlan>,
+    ) -> Result<Self> {
+        let input_schema = input.schema();
+        let mut fields = input_schema.fields().iter().cloned().collect::<Vec<_>>();
+        for async_expr in &async_exprs {
+            let schema = Schema::new(fields.clone());
+            fields.push(Arc::new(async_expr.field(&schema)?));
+        }
+        let schema = Arc::new(Schema::new_with_metadata(
+            Fields::from(fields),
+            input_schema.metadata().clone(),
+        ));
+
+        // The input columns keep their indices, so the properties of the
+        // input remain valid
+        let mapping = input_schema
+            .fields()
+            .iter()
+            .enumerate()
+            .map(|(index, field)| {
+                let column: Arc<dyn PhysicalExpr> =
+                    Arc::new(Column::new(field.name(), index));
+                (column, field.name().clone())
+            })
+            .collect::<Vec<_>>();
+        let mapping = ProjectionMapping::try_new(&mapping, &input_schema)?;
+        let eq_properties = input.equivalence_properties().project(&mapping, schema);
+        let cache = PlanProperties::new(
+            eq_properties,
+            input.output_partitioning().clone(),
+            input.execution_mode(),
+        );
+
+        Ok(Self {
+            async_exprs,
+            input,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        })
+    }
+
+    /// The async function calls to evaluate
+    pub fn async_exprs(&self) -> &[Arc<AsyncFuncExpr>] {
+        &self.async_exprs
+    }
+
+    /// The input plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+}
+
+impl DisplayAs for AsyncFuncExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let exprs = self
+                    .async_exprs
+                    .iter()
+                    .map(|expr| expr.to_string())
+                    .collect::<Vec<_>>();
+                write!(f, "AsyncFuncExec: async_expr=[{}]", exprs.join(", "))
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for AsyncFuncExec {
+    fn name(&self) -> &'static str {
+        "AsyncFuncExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        mut children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(Self::try_new(
+            self.async_exprs.clone(),
+            children.swap_remove(0),
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let concurrency = context
+            .session_config()
+            .options()
+            .execution
+            .async_udf_concurrency
+            .max(1);
+        let input = self.input.execute(partition, context)?;
+        let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
+        let async_exprs = self.async_exprs.clone();
+        let schema = self.schema();
+
+        let stream = input
+            .map_ok(move |batch| {
+                let async_exprs = async_exprs.clone();
+                let schema = schema.clone();
+                let baseline_metrics = baseline_metrics.clone();
+                async move {
+                    let num_input_columns = batch.num_columns();
+                    let mut batch = batch;
+                    for (i, async_expr) in async_exprs.iter().enumerate() {
+                        let result = async_expr.evaluate(&batch).await?;
+                        let mut columns = batch.columns().to_vec();
+                        columns.push(result);
+                        let schema = if i + 1 == async_exprs.len() {
+                            schema.clone()
+                        } else {
+                            let indices =
+                                (0..num_input_columns + i + 1).collect::<Vec<_>>();
+                            Arc::new(schema.project(&indices)?)
+                        };
+                        batch = RecordBatch::try_new(schema, columns)?;
+                    }
+                    Ok::<_, DataFusionError>(batch.record_output(&baseline_metrics))
+                }
+            })
+            .try_buffered(concurrency);
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+}
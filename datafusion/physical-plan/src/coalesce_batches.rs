@@ -29,6 +29,7 @@ use crate::{
     DisplayFormatType, ExecutionPlan, RecordBatchStream, SendableRecordBatchStream,
 };
 
+use arrow::array::Array;
 use arrow::datatypes::SchemaRef;
 use arrow::error::Result as ArrowResult;
 use arrow::record_batch::RecordBatch;
@@ -144,10 +145,20 @@ impl ExecutionPlan for CoalesceBatchesExec {
         partition: usize,
         context: Arc<TaskContext>,
     ) -> Result<SendableRecordBatchStream> {
+        let options = &context.session_config().options().execution;
+        let adaptive_batch_size =
+            (options.coalesce_batches_target_bytes > 0).then(|| {
+                AdaptiveBatchSize::new(
+                    options.coalesce_batches_target_bytes,
+                    options.coalesce_batches_min_rows,
+                    options.coalesce_batches_max_rows,
+                )
+            });
         Ok(Box::pin(CoalesceBatchesStream {
             input: self.input.execute(partition, context)?,
             schema: self.input.schema(),
             target_batch_size: self.target_batch_size,
+            adaptive_batch_size,
             buffer: Vec::new(),
             buffered_rows: 0,
             is_closed: false,
@@ -171,6 +182,8 @@ struct CoalesceBatchesStream {
     schema: SchemaRef,
     /// Minimum number of rows for coalesces batches
     target_batch_size: usize,
+    /// Adapts `target_batch_size` to the size of the rows, if set
+    adaptive_batch_size: Option<AdaptiveBatchSize>,
     /// Buffered batches
     buffer: Vec<RecordBatch>,
     /// Buffered row count
@@ -181,6 +194,55 @@ struct CoalesceBatchesStream {
     baseline_metrics: BaselineMetrics,
 }
 
+/// The number of rows of the batches of a [`CoalesceBatchesStream`] for them
+/// to have about `target_bytes` bytes, estimated from the average size of the
+/// rows of its input
+#[derive(Debug)]
+struct AdaptiveBatchSize {
+    /// The target number of bytes of the batches
+    target_bytes: usize,
+    /// The minimum number of rows of the batches
+    min_rows: usize,
+    /// The maximum number of rows of the batches
+    max_rows: usize,
+    /// The number of bytes of the input batches so far
+    input_bytes: usize,
+    /// The number of rows of the input batches so far
+    input_rows: usize,
+}
+
+impl AdaptiveBatchSize {
+    fn new(target_bytes: usize, min_rows: usize, max_rows: usize) -> Self {
+        Self {
+            target_bytes,
+            min_rows,
+            max_rows: max_rows.max(min_rows),
+            input_bytes: 0,
+            input_rows: 0,
+        }
+    }
+
+    /// Accounts for the input batch `batch`, and returns the number of rows
+    /// of the batches
+    fn update(&mut self, batch: &RecordBatch) -> usize {
+        // the sizes of the slices of the buffers of the columns, as the batch
+        // may be a slice of a much larger one
+        self.input_bytes += batch
+            .columns()
+            .iter()
+            .map(|column| {
+                column
+                    .to_data()
+                    .get_slice_memory_size()
+                    .unwrap_or_else(|_| column.get_array_memory_size())
+            })
+            .sum::<usize>();
+        self.input_rows += batch.num_rows();
+        let row_bytes = (self.input_bytes / self.input_rows.max(1)).max(1);
+        (self.target_bytes / row_bytes).clamp(self.min_rows, self.max_rows)
+    }
+}
+
 impl Stream for CoalesceBatchesStream {
     type Item = Result<RecordBatch>;
 
@@ -216,6 +278,10 @@ impl CoalesceBatchesStream {
             match input_batch {
                 Poll::Ready(x) => match x {
                     Some(Ok(batch)) => {
+                        if let Some(adaptive) = self.adaptive_batch_size.as_mut() {
+                            let target_batch_size = adaptive.update(&batch);
+                            self.target_batch_size = target_batch_size;
+                        }
                         if batch.num_rows() >= self.target_batch_size
                             && self.buffer.is_empty()
                         {
@@ -319,6 +385,31 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_adaptive_batch_size() {
+        let batch = create_batch(&test_schema());
+        // the size of a UInt32 value
+        let row_bytes = 4;
+        let mut batch_size = AdaptiveBatchSize::new(row_bytes * 20, 4, 100);
+        assert_eq!(batch_size.update(&batch), 20);
+        assert_eq!(batch_size.update(&batch), 20);
+
+        // only the rows of a slice of a larger batch are counted
+        let large = RecordBatch::try_new(
+            test_schema(),
+            vec![Arc::new(UInt32Array::from_iter_values(0..1000))],
+        )
+        .unwrap();
+        let mut batch_size = AdaptiveBatchSize::new(row_bytes * 20, 4, 100);
+        assert_eq!(batch_size.update(&large.slice(10, 8)), 20);
+
+        // bounded by the minimum and maximum number of rows
+        let mut batch_size = AdaptiveBatchSize::new(row_bytes * 20, 30, 100);
+        assert_eq!(batch_size.update(&batch), 30);
+        let mut batch_size = AdaptiveBatchSize::new(row_bytes * 20, 4, 10);
+        assert_eq!(batch_size.update(&batch), 10);
+    }
+
     fn test_schema() -> Arc<Schema> {
         Arc::new(Schema::new(vec![Field::new("c0", DataType::UInt32, false)]))
     }
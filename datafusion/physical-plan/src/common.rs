@@ -17,6 +17,7 @@
 
 //! Defines common code used in execution plans
 
+use std::collections::HashSet;
 use std::fs;
 use std::fs::{metadata, File};
 use std::path::{Path, PathBuf};
@@ -26,10 +27,14 @@ use super::{ExecutionPlanProperties, SendableRecordBatchStream};
 use crate::stream::RecordBatchReceiverStream;
 use crate::{ColumnStatistics, ExecutionPlan, Statistics};
 
+use arrow::array::ArrayData;
 use arrow::datatypes::Schema;
 use arrow::ipc::writer::{FileWriter, IpcWriteOptions};
+use arrow::ipc::CompressionType;
 use arrow::record_batch::RecordBatch;
 use arrow_array::Array;
+use arrow_buffer::Buffer;
+use datafusion_common::config::SpillCompression;
 use datafusion_common::stats::Precision;
 use datafusion_common::{plan_err, DataFusionError, Result};
 use datafusion_execution::memory_pool::MemoryReservation;
@@ -321,6 +326,23 @@ impl IPCWriter {
     pub fn path(&self) -> &Path {
         &self.path
     }
+
+    /// Size of the written file in bytes, after any compression. Should be
+    /// called after [`Self::finish`]
+    pub fn file_size(&self) -> Result<u64> {
+        Ok(metadata(&self.path)?.len())
+    }
+}
+
+/// Returns the [`IpcWriteOptions`] for writing spill files with the given
+/// compression
+pub fn spill_write_options(compression: SpillCompression) -> Result<IpcWriteOptions> {
+    let compression = match compression {
+        SpillCompression::Uncompressed => None,
+        SpillCompression::Lz4Frame => Some(CompressionType::LZ4_FRAME),
+        SpillCompression::Zstd => Some(CompressionType::ZSTD),
+    };
+    Ok(IpcWriteOptions::default().try_with_compression(compression)?)
 }
 
 /// Checks if the given projection is valid for the given schema.
@@ -349,6 +371,91 @@ pub fn can_project(
     }
 }
 
+/// Returns the memory size of the buffers of `batch`, counting the buffers
+/// shared by several of its arrays once, e.g. the buffers of the children of
+/// a struct array which are also columns of the batch.
+///
+/// [`RecordBatch::get_array_memory_size`] instead sums the sizes of the
+/// buffers of each array.
+pub fn get_record_batch_memory_size(batch: &RecordBatch) -> usize {
+    SharedBufferTracker::default().add(batch)
+}
+
+/// Tracks the buffers of the batches an operator keeps in memory, for the
+/// buffers shared by several batches, such as the columns of the batches
+/// projected from the same batch, to be accounted for in the memory
+/// reservation of the operator once.
+///
+/// The buffers are identified by the address of their first byte, as arrow
+/// does not expose the allocations they are sliced from: the slices of a
+/// buffer starting at different offsets are accounted for separately.
+#[derive(Debug, Default)]
+pub struct SharedBufferTracker {
+    /// The addresses of the tracked buffers
+    buffers: HashSet<usize>,
+    /// The size of the tracked buffers
+    size: usize,
+}
+
+impl SharedBufferTracker {
+    /// Tracks the buffers of `batch`, and returns the size of the buffers
+    /// which were not tracked yet
+    pub fn add(&mut self, batch: &RecordBatch) -> usize {
+        let mut size = 0;
+        for_each_buffer(batch, &mut |buffer| {
+            if self.buffers.insert(buffer.as_ptr() as usize) {
+                size += buffer.capacity();
+            }
+        });
+        self.size += size;
+        size
+    }
+
+    /// Returns the size of the buffers of `batch` which are not tracked,
+    /// without tracking them
+    pub fn untracked_size(&self, batch: &RecordBatch) -> usize {
+        let mut untracked = HashSet::new();
+        let mut size = 0;
+        for_each_buffer(batch, &mut |buffer| {
+            let address = buffer.as_ptr() as usize;
+            if !self.buffers.contains(&address) && untracked.insert(address) {
+                size += buffer.capacity();
+            }
+        });
+        size
+    }
+
+    /// The size of the tracked buffers
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Stops tracking all the buffers
+    pub fn clear(&mut self) {
+        self.buffers.clear();
+        self.size = 0;
+    }
+}
+
+/// Calls `f` with each buffer of the arrays of `batch`, including the buffers
+/// of their children and their validity buffers
+fn for_each_buffer(batch: &RecordBatch, f: &mut dyn FnMut(&Buffer)) {
+    fn visit(data: &ArrayData, f: &mut dyn FnMut(&Buffer)) {
+        for buffer in data.buffers() {
+            f(buffer);
+        }
+        if let Some(nulls) = data.nulls() {
+            f(nulls.buffer());
+        }
+        for child in data.child_data() {
+            visit(child, f);
+        }
+    }
+    for column in batch.columns() {
+        visit(&column.to_data(), f);
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use std::ops::Not;
@@ -360,7 +467,9 @@ mod tests {
 
     use arrow::compute::SortOptions;
     use arrow::{
-        array::{Float32Array, Float64Array, UInt64Array},
+        array::{
+            ArrayRef, Float32Array, Float64Array, Int64Array, StructArray, UInt64Array,
+        },
         datatypes::{DataType, Field},
     };
     use datafusion_expr::Operator;
@@ -690,12 +799,14 @@ mod tests {
                     max_value: Precision::Absent,
                     min_value: Precision::Absent,
                     null_count: Precision::Exact(0),
+                    distinct_sketch: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Absent,
                     min_value: Precision::Absent,
                     null_count: Precision::Exact(0),
+                    distinct_sketch: None,
                 },
             ],
         };
@@ -704,6 +815,33 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn test_shared_buffer_memory_size() -> Result<()> {
+        let a: ArrayRef = Arc::new(Int64Array::from_iter_values(0..1024));
+        let s: ArrayRef = Arc::new(StructArray::from(vec![(
+            Arc::new(Field::new("a", DataType::Int64, false)),
+            a.clone(),
+        )]));
+        let batch = RecordBatch::try_from_iter(vec![("a", a.clone()), ("s", s)])?;
+
+        // the buffer of `a` is counted once
+        let size = a.to_data().buffers()[0].capacity();
+        assert_eq!(get_record_batch_memory_size(&batch), size);
+        assert!(size < batch.get_array_memory_size());
+
+        // the slices of the batch with the same offset share its buffers
+        let mut tracker = SharedBufferTracker::default();
+        assert_eq!(tracker.untracked_size(&batch.slice(0, 10)), size);
+        assert_eq!(tracker.add(&batch.slice(0, 10)), size);
+        assert_eq!(tracker.untracked_size(&batch.slice(0, 20)), 0);
+        assert_eq!(tracker.add(&batch.slice(0, 20)), 0);
+        assert_eq!(tracker.add(&batch.slice(10, 10)), size);
+        assert_eq!(tracker.size(), 2 * size);
+        tracker.clear();
+        assert_eq!(tracker.size(), 0);
+        Ok(())
+    }
+
     #[test]
     fn test_transpose() -> Result<()> {
         let in_data = vec![vec![1, 2, 3], vec![4, 5, 6]];
@@ -22,6 +22,7 @@ use std::any::Any;
 use std::pin::Pin;
 use std::sync::Arc;
 use std::task::{Context, Poll};
+use std::time::{Duration, Instant};
 
 use super::{
     ColumnStatistics, DisplayAs, ExecutionPlanProperties, PlanProperties,
@@ -40,7 +41,9 @@ use datafusion_common::stats::Precision;
 use datafusion_common::{plan_err, DataFusionError, Result};
 use datafusion_execution::TaskContext;
 use datafusion_expr::Operator;
-use datafusion_physical_expr::expressions::BinaryExpr;
+use datafusion_physical_expr::expressions::{
+    BinaryExpr, Column, InListExpr, IsNotNullExpr, IsNullExpr, Literal, NotExpr,
+};
 use datafusion_physical_expr::intervals::utils::check_support;
 use datafusion_physical_expr::utils::collect_columns;
 use datafusion_physical_expr::{
@@ -270,9 +273,18 @@ impl ExecutionPlan for FilterExec {
     ) -> Result<SendableRecordBatchStream> {
         trace!("Start FilterExec::execute for partition {} of context session_id {} and task_id {:?}", partition, context.session_id(), context.task_id());
         let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
+        let reorder_interval = context
+            .session_config()
+            .options()
+            .execution
+            .filter_reorder_interval;
+        let conjuncts = split_conjunction(&self.predicate);
+        let adaptive_conjuncts = (reorder_interval > 0 && conjuncts.len() > 1)
+            .then(|| AdaptiveConjuncts::new(&conjuncts, reorder_interval));
         Ok(Box::pin(FilterExecStream {
             schema: self.input.schema(),
             predicate: self.predicate.clone(),
+            adaptive_conjuncts,
             input: self.input.execute(partition, context)?,
             baseline_metrics,
         }))
@@ -320,6 +332,7 @@ fn collect_new_statistics(
                     max_value,
                     min_value,
                     distinct_count: distinct_count.to_inexact(),
+                    distinct_sketch: None,
                 }
             },
         )
@@ -333,6 +346,8 @@ struct FilterExecStream {
     schema: SchemaRef,
     /// The expression to filter on. This expression must evaluate to a boolean value.
     predicate: Arc<dyn PhysicalExpr>,
+    /// The conjuncts of `predicate`, if they are evaluated one after the other
+    adaptive_conjuncts: Option<AdaptiveConjuncts>,
     /// The input partition to filter.
     input: SendableRecordBatchStream,
     /// runtime metrics recording
@@ -353,6 +368,144 @@ pub(crate) fn batch_filter(
         })
 }
 
+/// The conjuncts of the predicate of a [`FilterExecStream`], evaluated one
+/// after the other, each on the rows selected by the previous ones.
+///
+/// Every `reorder_interval` batches, the conjuncts are reordered by the time
+/// they took per row divided by the fraction of the rows they filtered out, so
+/// that the cheap and selective conjuncts filter the rows before the expensive
+/// ones, such as regular expressions or accesses to nested fields, evaluate
+/// them.
+///
+/// The conjuncts that may fail keep their position, so that they are never
+/// evaluated on the rows filtered out by the conjuncts written before them,
+/// e.g. `a / b > 1` is evaluated after `b <> 0` in `b <> 0 AND a / b > 1`.
+#[derive(Debug)]
+struct AdaptiveConjuncts {
+    /// The conjuncts, in the order they are evaluated
+    conjuncts: Vec<ConjunctStatistics>,
+    /// The number of batches between the reorderings of the conjuncts
+    reorder_interval: usize,
+    /// The number of batches filtered since the last reordering
+    num_batches: usize,
+}
+
+/// A conjunct of an [`AdaptiveConjuncts`], with what was measured of its
+/// evaluation since the last reordering
+#[derive(Debug)]
+struct ConjunctStatistics {
+    expr: Arc<dyn PhysicalExpr>,
+    /// Whether `expr` may fail, see [`can_fail`]
+    can_fail: bool,
+    input_rows: usize,
+    output_rows: usize,
+    elapsed: Duration,
+}
+
+impl ConjunctStatistics {
+    /// The rank of the conjunct in the evaluation order: the lower ranks are
+    /// evaluated first
+    fn rank(&self) -> f64 {
+        if self.input_rows == 0 {
+            // not evaluated since the last reordering
+            return 0.0;
+        }
+        let cost = self.elapsed.as_secs_f64() / self.input_rows as f64;
+        let filtered = 1.0 - self.output_rows as f64 / self.input_rows as f64;
+        if filtered > 0.0 {
+            cost / filtered
+        } else {
+            f64::INFINITY
+        }
+    }
+}
+
+impl AdaptiveConjuncts {
+    fn new(conjuncts: &[&Arc<dyn PhysicalExpr>], reorder_interval: usize) -> Self {
+        let conjuncts = conjuncts
+            .iter()
+            .map(|expr| ConjunctStatistics {
+                expr: Arc::clone(expr),
+                can_fail: can_fail(expr),
+                input_rows: 0,
+                output_rows: 0,
+                elapsed: Duration::ZERO,
+            })
+            .collect();
+        Self {
+            conjuncts,
+            reorder_interval,
+            num_batches: 0,
+        }
+    }
+
+    /// Returns the rows of `batch` for which all the conjuncts are true
+    fn filter(&mut self, mut batch: RecordBatch) -> Result<RecordBatch> {
+        for conjunct in &mut self.conjuncts {
+            if batch.num_rows() == 0 {
+                break;
+            }
+            let start = Instant::now();
+            let input_rows = batch.num_rows();
+            batch = batch_filter(&batch, &conjunct.expr)?;
+            conjunct.elapsed += start.elapsed();
+            conjunct.input_rows += input_rows;
+            conjunct.output_rows += batch.num_rows();
+        }
+
+        self.num_batches += 1;
+        if self.num_batches >= self.reorder_interval {
+            self.reorder();
+        }
+        Ok(batch)
+    }
+
+    /// Reorders the conjuncts between the ones that may fail by their rank, and
+    /// starts measuring them again
+    fn reorder(&mut self) {
+        for conjuncts in self.conjuncts.split_mut(|conjunct| conjunct.can_fail) {
+            conjuncts.sort_by(|a, b| a.rank().total_cmp(&b.rank()));
+        }
+        for conjunct in &mut self.conjuncts {
+            conjunct.input_rows = 0;
+            conjunct.output_rows = 0;
+            conjunct.elapsed = Duration::ZERO;
+        }
+        self.num_batches = 0;
+    }
+}
+
+/// Returns true if evaluating `expr` may fail, e.g. with a division by zero, a
+/// cast or a function call. Only comparisons and boolean operators of columns
+/// and literals are known not to fail.
+fn can_fail(expr: &Arc<dyn PhysicalExpr>) -> bool {
+    let any = expr.as_any();
+    let infallible = match any.downcast_ref::<BinaryExpr>() {
+        Some(binary) => matches!(
+            binary.op(),
+            Operator::Eq
+                | Operator::NotEq
+                | Operator::Lt
+                | Operator::LtEq
+                | Operator::Gt
+                | Operator::GtEq
+                | Operator::IsDistinctFrom
+                | Operator::IsNotDistinctFrom
+                | Operator::And
+                | Operator::Or
+        ),
+        None => {
+            any.is::<Column>()
+                || any.is::<Literal>()
+                || any.is::<IsNullExpr>()
+                || any.is::<IsNotNullExpr>()
+                || any.is::<NotExpr>()
+                || any.is::<InListExpr>()
+        }
+    };
+    !infallible || expr.children().iter().any(can_fail)
+}
+
 impl Stream for FilterExecStream {
     type Item = Result<RecordBatch>;
 
@@ -360,13 +513,18 @@ impl Stream for FilterExecStream {
         mut self: Pin<&mut Self>,
         cx: &mut Context<'_>,
     ) -> Poll<Option<Self::Item>> {
+        // Get a clone (uses same underlying atomic) as self gets borrowed below
+        let elapsed_compute = self.baseline_metrics.elapsed_compute().clone();
         let poll;
         loop {
             match self.input.poll_next_unpin(cx) {
                 Poll::Ready(value) => match value {
                     Some(Ok(batch)) => {
-                        let timer = self.baseline_metrics.elapsed_compute().timer();
-                        let filtered_batch = batch_filter(&batch, &self.predicate)?;
+                        let timer = elapsed_compute.timer();
+                        let filtered_batch = match self.adaptive_conjuncts.as_mut() {
+                            Some(conjuncts) => conjuncts.filter(batch)?,
+                            None => batch_filter(&batch, &self.predicate)?,
+                        };
                         // skip entirely filtered batches
                         if filtered_batch.num_rows() == 0 {
                             continue;
@@ -440,9 +598,11 @@ mod tests {
     use crate::test::exec::StatisticsExec;
 
     use crate::empty::EmptyExec;
-    use arrow::datatypes::{Field, Schema};
+    use arrow::array::AsArray;
+    use arrow::datatypes::{Field, Int32Type, Schema};
     use arrow_schema::{UnionFields, UnionMode};
     use datafusion_common::ScalarValue;
+    use datafusion_execution::config::SessionConfig;
 
     #[tokio::test]
     async fn collect_columns_predicates() -> Result<()> {
@@ -1012,6 +1172,7 @@ mod tests {
                 min_value: Precision::Inexact(ScalarValue::Int32(Some(5))),
                 max_value: Precision::Inexact(ScalarValue::Int32(Some(10))),
                 distinct_count: Precision::Absent,
+                distinct_sketch: None,
             }],
         };
 
@@ -1124,4 +1285,127 @@ mod tests {
 
         Ok(())
     }
+
+    #[test]
+    fn test_adaptive_conjuncts_reorder() -> Result<()> {
+        let values = (0..100).collect::<Vec<_>>();
+        let batch = test::build_table_i32(("a", &values), ("b", &values), ("c", &values));
+        let schema = batch.schema();
+        // a >= 0 filters out no rows, b = 3 all but one
+        let predicate = binary(
+            binary(col("a", &schema)?, Operator::GtEq, lit(0i32), &schema)?,
+            Operator::And,
+            binary(col("b", &schema)?, Operator::Eq, lit(3i32), &schema)?,
+            &schema,
+        )?;
+        let mut conjuncts = AdaptiveConjuncts::new(&split_conjunction(&predicate), 2);
+
+        let filtered = conjuncts.filter(batch.clone())?;
+        assert_eq!(filtered.num_rows(), 1);
+        assert_eq!(conjuncts.conjuncts[0].expr.to_string(), "a@0 >= 0");
+        assert_eq!(conjuncts.conjuncts[0].input_rows, 100);
+        assert_eq!(conjuncts.conjuncts[1].input_rows, 100);
+
+        // reordered after the second batch
+        let filtered = conjuncts.filter(batch.clone())?;
+        assert_eq!(filtered.num_rows(), 1);
+        assert_eq!(conjuncts.conjuncts[0].expr.to_string(), "b@1 = 3");
+        assert_eq!(conjuncts.num_batches, 0);
+
+        // the conjuncts after the first are only evaluated on its output
+        let filtered = conjuncts.filter(batch)?;
+        assert_eq!(filtered.num_rows(), 1);
+        assert_eq!(conjuncts.conjuncts[0].input_rows, 100);
+        assert_eq!(conjuncts.conjuncts[1].input_rows, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_adaptive_conjuncts_keep_fallible_position() -> Result<()> {
+        let values = (0..100).collect::<Vec<_>>();
+        let divisors = (0..100).map(|v| v % 5).collect::<Vec<_>>();
+        let batch =
+            test::build_table_i32(("a", &values), ("b", &divisors), ("c", &values));
+        let schema = batch.schema();
+        // c >= 0 AND b <> 0 AND a / b > 50 AND c < 90
+        let predicate = [
+            binary(col("c", &schema)?, Operator::GtEq, lit(0i32), &schema)?,
+            binary(col("b", &schema)?, Operator::NotEq, lit(0i32), &schema)?,
+            binary(
+                binary(
+                    col("a", &schema)?,
+                    Operator::Divide,
+                    col("b", &schema)?,
+                    &schema,
+                )?,
+                Operator::Gt,
+                lit(50i32),
+                &schema,
+            )?,
+            binary(col("c", &schema)?, Operator::Lt, lit(90i32), &schema)?,
+        ]
+        .into_iter()
+        .reduce(|a, b| Arc::new(BinaryExpr::new(a, Operator::And, b)))
+        .unwrap();
+        let mut conjuncts = AdaptiveConjuncts::new(&split_conjunction(&predicate), 1);
+        let order = |conjuncts: &AdaptiveConjuncts| {
+            conjuncts
+                .conjuncts
+                .iter()
+                .map(|conjunct| conjunct.expr.to_string())
+                .collect::<Vec<_>>()
+        };
+
+        for _ in 0..3 {
+            // the division never sees a zero divisor
+            let filtered = conjuncts.filter(batch.clone())?;
+            assert_eq!(filtered.num_rows(), 8);
+        }
+        // only the conjuncts before the division are reordered
+        assert_eq!(
+            order(&conjuncts),
+            ["b@1 != 0", "c@2 >= 0", "a@0 / b@1 > 50", "c@2 < 90"]
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_filter_with_reordered_conjuncts() -> Result<()> {
+        let values = (0..100).collect::<Vec<_>>();
+        let input =
+            test::build_table_scan_i32(("a", &values), ("b", &values), ("c", &values));
+        let schema = input.schema();
+        // a < 90 AND b % 10 = 0 AND c > 5
+        let predicate = binary(
+            binary(
+                binary(col("a", &schema)?, Operator::Lt, lit(90i32), &schema)?,
+                Operator::And,
+                binary(
+                    binary(col("b", &schema)?, Operator::Modulo, lit(10i32), &schema)?,
+                    Operator::Eq,
+                    lit(0i32),
+                    &schema,
+                )?,
+                &schema,
+            )?,
+            Operator::And,
+            binary(col("c", &schema)?, Operator::Gt, lit(5i32), &schema)?,
+            &schema,
+        )?;
+        let filter = Arc::new(FilterExec::try_new(predicate, input)?);
+
+        let config = SessionConfig::new()
+            .set_usize("datafusion.execution.filter_reorder_interval", 1);
+        let task_ctx = Arc::new(TaskContext::default().with_session_config(config));
+        let batches = crate::collect(filter, task_ctx).await?;
+        let a = batches
+            .iter()
+            .flat_map(|batch| {
+                let a = batch.column(0).as_primitive::<Int32Type>();
+                a.values().to_vec()
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(a, vec![10, 20, 30, 40, 50, 60, 70, 80]);
+        Ok(())
+    }
 }
@@ -0,0 +1,797 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the gap filling plan, producing the time buckets missing from the
+//! output of an aggregate grouped by time buckets
+
+use std::any::Any;
+use std::fmt;
+use std::ops::{Bound, Range};
+use std::sync::Arc;
+
+use crate::expressions::Column;
+use crate::stream::RecordBatchStreamAdapter;
+use crate::{
+    common, DisplayAs, DisplayFormatType, Distribution, ExecutionMode, ExecutionPlan,
+    ExecutionPlanProperties, Partitioning, PhysicalExpr, PlanProperties,
+    SendableRecordBatchStream, Statistics,
+};
+
+use arrow::array::timezone::Tz;
+use arrow::array::types::{IntervalDayTimeType, IntervalMonthDayNanoType};
+use arrow::array::{Array, ArrayRef, Int64Array, UInt32Array};
+use arrow::compute::kernels::cast_utils::string_to_timestamp_nanos;
+use arrow::compute::{cast, concat_batches, take, SortColumn, SortOptions};
+use arrow::datatypes::{DataType, Schema, TimeUnit};
+use arrow::record_batch::{RecordBatch, RecordBatchOptions};
+use arrow_schema::SchemaRef;
+use chrono::{DateTime, Datelike, LocalResult, Months, NaiveDateTime, TimeZone};
+use datafusion_common::cast::as_int64_array;
+use datafusion_common::utils::evaluate_partition_ranges;
+use datafusion_common::{
+    exec_datafusion_err, exec_err, internal_err, not_impl_err, DataFusionError, Result,
+    ScalarValue,
+};
+use datafusion_execution::TaskContext;
+use datafusion_physical_expr::PhysicalSortRequirement;
+
+use futures::stream;
+
+const NANOS_PER_DAY: i64 = 24 * 60 * 60 * 1_000_000_000;
+
+/// The parameters of a [`GapFillExec`], constant expressions evaluated when
+/// the plan is executed
+#[derive(Debug, Clone)]
+pub struct GapFillParams {
+    /// The interval between the starts of two buckets
+    pub stride: Arc<dyn PhysicalExpr>,
+    /// The timestamp the buckets are aligned to, the unix epoch if `None`
+    pub origin: Option<Arc<dyn PhysicalExpr>>,
+    /// The start of the time range to fill
+    pub start: Bound<Arc<dyn PhysicalExpr>>,
+    /// The end of the time range to fill
+    pub end: Bound<Arc<dyn PhysicalExpr>>,
+}
+
+/// Execution plan filling the time buckets missing from the output of an
+/// aggregate grouped by a time bucket, such as `time_bucket_gapfill`
+///
+/// For each series, i.e. distinct values of the `series` columns, a row is
+/// produced for every bucket between the start and end of the time range
+/// that has no row in the input. The `time` column of such a row is the
+/// start of the bucket, its `series` columns are those of the series, and its
+/// other columns are `NULL`, except the `locf` columns, which take the last
+/// non-`NULL` value of the series (and also replace `NULL` values of the input).
+///
+/// The input must be sorted by the `series` columns and then by the `time`
+/// column. Buckets of a whole number of days or months are computed in the
+/// local time of the timezone of the `time` column, like `date_bin`.
+#[derive(Debug)]
+pub struct GapFillExec {
+    /// The aggregate output
+    input: Arc<dyn ExecutionPlan>,
+    /// The grouping columns other than the time bucket
+    series: Vec<Column>,
+    /// The time bucket column
+    time: Column,
+    /// The columns filled with the last observed value of the series
+    locf: Vec<Column>,
+    /// The buckets to fill
+    params: GapFillParams,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl GapFillExec {
+    /// Create a new [`GapFillExec`]
+    pub fn try_new(
+        input: Arc<dyn ExecutionPlan>,
+        series: Vec<Column>,
+        time: Column,
+        locf: Vec<Column>,
+        params: GapFillParams,
+    ) -> Result<Self> {
+        let schema = input.schema();
+        match schema.field(time.index()).data_type() {
+            DataType::Timestamp(_, _) => {}
+            other => {
+                return internal_err!(
+                    "GapFillExec expects a timestamp time column, got {other}"
+                )
+            }
+        }
+        let cache = Self::compute_properties(&input);
+        Ok(Self {
+            input,
+            series,
+            time,
+            locf,
+            params,
+            cache,
+        })
+    }
+
+    /// The input plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// The grouping columns other than the time bucket
+    pub fn series(&self) -> &[Column] {
+        &self.series
+    }
+
+    /// The time bucket column
+    pub fn time(&self) -> &Column {
+        &self.time
+    }
+
+    /// The columns filled with the last observed value of the series
+    pub fn locf(&self) -> &[Column] {
+        &self.locf
+    }
+
+    /// The buckets to fill
+    pub fn params(&self) -> &GapFillParams {
+        &self.params
+    }
+
+    /// This function creates the cache object that stores the plan properties such as equivalence properties, partitioning, ordering, etc.
+    fn compute_properties(input: &Arc<dyn ExecutionPlan>) -> PlanProperties {
+        // The whole input is buffered
+        let mode = match input.execution_mode() {
+            ExecutionMode::Bounded => ExecutionMode::Bounded,
+            _ => ExecutionMode::PipelineBreaking,
+        };
+        PlanProperties::new(
+            input.equivalence_properties().clone(),
+            Partitioning::UnknownPartitioning(1),
+            mode,
+        )
+    }
+
+    /// The ordering of the input and output
+    fn sort_columns(&self) -> impl Iterator<Item = &Column> {
+        self.series.iter().chain(std::iter::once(&self.time))
+    }
+}
+
+impl DisplayAs for GapFillExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let series = self
+                    .series
+                    .iter()
+                    .map(|c| c.to_string())
+                    .collect::<Vec<_>>();
+                write!(
+                    f,
+                    "GapFillExec: series=[{}], time={}, stride={}, range={}",
+                    series.join(", "),
+                    self.time,
+                    self.params.stride,
+                    fmt_range(&self.params.start, &self.params.end)
+                )?;
+                if !self.locf.is_empty() {
+                    let locf =
+                        self.locf.iter().map(|c| c.to_string()).collect::<Vec<_>>();
+                    write!(f, ", locf=[{}]", locf.join(", "))?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+/// Formats a time range like `[start, end)`
+fn fmt_range(
+    start: &Bound<Arc<dyn PhysicalExpr>>,
+    end: &Bound<Arc<dyn PhysicalExpr>>,
+) -> String {
+    let start = match start {
+        Bound::Included(expr) => format!("[{expr}"),
+        Bound::Excluded(expr) => format!("({expr}"),
+        Bound::Unbounded => "(".to_string(),
+    };
+    let end = match end {
+        Bound::Included(expr) => format!("{expr}]"),
+        Bound::Excluded(expr) => format!("{expr})"),
+        Bound::Unbounded => ")".to_string(),
+    };
+    format!("{start}, {end}")
+}
+
+impl ExecutionPlan for GapFillExec {
+    fn name(&self) -> &'static str {
+        "GapFillExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![Distribution::SinglePartition]
+    }
+
+    fn required_input_ordering(&self) -> Vec<Option<Vec<PhysicalSortRequirement>>> {
+        let ordering = self
+            .sort_columns()
+            .map(|column| {
+                PhysicalSortRequirement::new(
+                    Arc::new(column.clone()),
+                    Some(SortOptions::default()),
+                )
+            })
+            .collect();
+        vec![Some(ordering)]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true]
+    }
+
+    fn benefits_from_input_partitioning(&self) -> Vec<bool> {
+        vec![false]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(Self::try_new(
+            children[0].clone(),
+            self.series.clone(),
+            self.time.clone(),
+            self.locf.clone(),
+            self.params.clone(),
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        if 0 != partition {
+            return internal_err!("GapFillExec invalid partition {partition}");
+        }
+        if 1 != self.input.output_partitioning().partition_count() {
+            return internal_err!("GapFillExec requires a single input partition");
+        }
+
+        let schema = self.schema();
+        let input = self.input.execute(0, context)?;
+        let filler = GapFiller {
+            schema: schema.clone(),
+            series: self.series.iter().map(|c| c.index()).collect(),
+            time: self.time.index(),
+            locf: self.locf.iter().map(|c| c.index()).collect(),
+            params: self.params.clone(),
+        };
+        let stream = stream::once(async move {
+            let batches = common::collect(input).await?;
+            let batch = concat_batches(&filler.schema, &batches)?;
+            filler.fill(&batch)
+        });
+        Ok(Box::pin(RecordBatchStreamAdapter::new(schema, stream)))
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        Ok(Statistics::new_unknown(&self.schema()))
+    }
+}
+
+/// Fills the gaps of a sorted batch
+struct GapFiller {
+    schema: SchemaRef,
+    series: Vec<usize>,
+    time: usize,
+    locf: Vec<usize>,
+    params: GapFillParams,
+}
+
+impl GapFiller {
+    fn fill(&self, batch: &RecordBatch) -> Result<RecordBatch> {
+        // There is no series to fill
+        if batch.num_rows() == 0 {
+            return Ok(batch.clone());
+        }
+
+        let time = batch.column(self.time);
+        let (unit, tz) = match time.data_type() {
+            DataType::Timestamp(unit, tz) => (unit.clone(), tz.clone()),
+            other => return exec_err!("Unexpected time bucket type {other}"),
+        };
+        let scale = match unit {
+            TimeUnit::Second => 1_000_000_000,
+            TimeUnit::Millisecond => 1_000_000,
+            TimeUnit::Microsecond => 1_000,
+            TimeUnit::Nanosecond => 1,
+        };
+
+        let stride = Stride::try_from(evaluate_constant(&self.params.stride)?)?;
+        let origin = match &self.params.origin {
+            Some(origin) => timestamp_nanos(evaluate_constant(origin)?)?,
+            None => 0,
+        };
+        let tz = match tz {
+            Some(tz) if stride.is_calendar() => Some(tz.parse::<Tz>()?),
+            _ => None,
+        };
+        let buckets = Buckets::try_new(stride, origin, tz)?;
+
+        let start = match &self.params.start {
+            Bound::Included(expr) | Bound::Excluded(expr) => {
+                timestamp_nanos(evaluate_constant(expr)?)?
+            }
+            Bound::Unbounded => {
+                return not_impl_err!("Gap filling requires the start of the time range")
+            }
+        };
+        let first = buckets.index_of(start)?;
+        let last = match &self.params.end {
+            Bound::Included(expr) => {
+                buckets.index_of(timestamp_nanos(evaluate_constant(expr)?)?)?
+            }
+            Bound::Excluded(expr) => {
+                let end = timestamp_nanos(evaluate_constant(expr)?)?;
+                let last = buckets.index_of(end)?;
+                if buckets.start(last)? == end {
+                    last - 1
+                } else {
+                    last
+                }
+            }
+            Bound::Unbounded => {
+                return not_impl_err!("Gap filling requires the end of the time range")
+            }
+        };
+        let bucket_times = (first..=last)
+            .map(|index| Ok(buckets.start(index)?.div_euclid(scale)))
+            .collect::<Result<Vec<_>>>()?;
+
+        let times = cast(time, &DataType::Int64)?;
+        let times = as_int64_array(&times)?;
+        let sort_columns = self
+            .series
+            .iter()
+            .map(|&index| SortColumn {
+                values: batch.column(index).clone(),
+                options: None,
+            })
+            .collect::<Vec<_>>();
+
+        let mut rows = FilledRows::new(self.locf.len());
+        for series in evaluate_partition_ranges(batch.num_rows(), &sort_columns)? {
+            rows.fill_series(batch, &self.locf, times, series, &bucket_times);
+        }
+        self.output(batch, rows)
+    }
+
+    /// Builds the output batch from the rows of the input
+    fn output(&self, batch: &RecordBatch, rows: FilledRows) -> Result<RecordBatch> {
+        let series = UInt32Array::from(rows.series);
+        let times: ArrayRef = Arc::new(Int64Array::from(rows.times));
+        let values = UInt32Array::from(rows.values);
+        let locf = rows
+            .locf
+            .into_iter()
+            .map(UInt32Array::from)
+            .collect::<Vec<_>>();
+        let columns = batch
+            .columns()
+            .iter()
+            .enumerate()
+            .map(|(index, column)| {
+                if index == self.time {
+                    Ok(cast(&times, column.data_type())?)
+                } else if self.series.contains(&index) {
+                    Ok(take(column, &series, None)?)
+                } else if let Some(i) = self.locf.iter().position(|&c| c == index) {
+                    Ok(take(column, &locf[i], None)?)
+                } else {
+                    Ok(take(column, &values, None)?)
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let options = RecordBatchOptions::new().with_row_count(Some(series.len()));
+        Ok(RecordBatch::try_new_with_options(
+            self.schema.clone(),
+            columns,
+            &options,
+        )?)
+    }
+}
+
+/// The rows of the output, as indices of input rows
+struct FilledRows {
+    /// The input row of the series of each output row
+    series: Vec<u32>,
+    /// The time of each output row
+    times: Vec<Option<i64>>,
+    /// The input row of each output row, `None` for the filled rows
+    values: Vec<Option<u32>>,
+    /// For each `locf` column, the last input row of the series with a value
+    locf: Vec<Vec<Option<u32>>>,
+}
+
+impl FilledRows {
+    fn new(num_locf: usize) -> Self {
+        Self {
+            series: vec![],
+            times: vec![],
+            values: vec![],
+            locf: vec![vec![]; num_locf],
+        }
+    }
+
+    /// Adds the rows of a series, from the input `rows` and the missing
+    /// buckets, ordered by time
+    fn fill_series(
+        &mut self,
+        batch: &RecordBatch,
+        locf: &[usize],
+        times: &Int64Array,
+        rows: Range<usize>,
+        buckets: &[i64],
+    ) {
+        let mut last_values = vec![None; locf.len()];
+        let mut push = |row: Option<usize>, time: Option<i64>| {
+            self.series.push(row.unwrap_or(rows.start) as u32);
+            self.times.push(time);
+            self.values.push(row.map(|row| row as u32));
+            for ((column, last_value), output) in
+                locf.iter().zip(&mut last_values).zip(&mut self.locf)
+            {
+                if let Some(row) = row {
+                    if batch.column(*column).is_valid(row) {
+                        *last_value = Some(row as u32);
+                    }
+                }
+                output.push(*last_value);
+            }
+        };
+
+        let time = |row: usize| times.is_valid(row).then(|| times.value(row));
+        let mut row = rows.start;
+        for &bucket in buckets {
+            // Rows before the bucket, including NULL times
+            while row < rows.end && time(row).map_or(true, |time| time < bucket) {
+                push(Some(row), time(row));
+                row += 1;
+            }
+            if row < rows.end && time(row) == Some(bucket) {
+                while row < rows.end && time(row) == Some(bucket) {
+                    push(Some(row), time(row));
+                    row += 1;
+                }
+            } else {
+                push(None, Some(bucket));
+            }
+        }
+        for row in row..rows.end {
+            push(Some(row), time(row));
+        }
+    }
+}
+
+/// Evaluates a constant expression
+fn evaluate_constant(expr: &Arc<dyn PhysicalExpr>) -> Result<ScalarValue> {
+    let options = RecordBatchOptions::new().with_row_count(Some(1));
+    let batch =
+        RecordBatch::try_new_with_options(Arc::new(Schema::empty()), vec![], &options)?;
+    expr.evaluate(&batch)?
+        .into_array(1)
+        .and_then(|array| ScalarValue::try_from_array(&array, 0))
+}
+
+/// Returns the nanoseconds since the unix epoch of a timestamp
+fn timestamp_nanos(value: ScalarValue) -> Result<i64> {
+    let (value, scale) = match &value {
+        ScalarValue::TimestampSecond(v, _) => (*v, 1_000_000_000),
+        ScalarValue::TimestampMillisecond(v, _) => (*v, 1_000_000),
+        ScalarValue::TimestampMicrosecond(v, _) => (*v, 1_000),
+        ScalarValue::TimestampNanosecond(v, _) => (*v, 1),
+        ScalarValue::Utf8(Some(v)) => (Some(string_to_timestamp_nanos(v)?), 1),
+        _ => return exec_err!("Gap filling expects a timestamp, got {value:?}"),
+    };
+    value
+        .ok_or_else(|| exec_datafusion_err!("Gap filling bounds can not be NULL"))?
+        .checked_mul(scale)
+        .ok_or_else(|| exec_datafusion_err!("Timestamp {value:?} out of range"))
+}
+
+/// The interval between the starts of two buckets
+#[derive(Debug, Clone, Copy)]
+enum Stride {
+    Nanoseconds(i64),
+    Months(i64),
+}
+
+impl Stride {
+    /// Whether buckets are computed in local time, see `date_bin`
+    fn is_calendar(&self) -> bool {
+        match self {
+            Stride::Nanoseconds(nanos) => nanos % NANOS_PER_DAY == 0,
+            Stride::Months(_) => true,
+        }
+    }
+}
+
+impl TryFrom<ScalarValue> for Stride {
+    type Error = DataFusionError;
+
+    fn try_from(value: ScalarValue) -> Result<Self> {
+        let stride = match value {
+            ScalarValue::IntervalDayTime(Some(v)) => {
+                let (days, millis) = IntervalDayTimeType::to_parts(v);
+                Stride::Nanoseconds(
+                    days as i64 * NANOS_PER_DAY + millis as i64 * 1_000_000,
+                )
+            }
+            ScalarValue::IntervalMonthDayNano(Some(v)) => {
+                match IntervalMonthDayNanoType::to_parts(v) {
+                    (0, days, nanos) => {
+                        Stride::Nanoseconds(days as i64 * NANOS_PER_DAY + nanos)
+                    }
+                    (months, 0, 0) => Stride::Months(months as i64),
+                    _ => {
+                        return not_impl_err!(
+                            "Gap filling does not support strides combining months with days or nanoseconds"
+                        )
+                    }
+                }
+            }
+            ScalarValue::IntervalYearMonth(Some(months)) => Stride::Months(months as i64),
+            other => {
+                return exec_err!("Gap filling expects an interval stride, got {other:?}")
+            }
+        };
+        match stride {
+            Stride::Nanoseconds(v) | Stride::Months(v) if v > 0 => Ok(stride),
+            _ => exec_err!("Gap filling stride must be positive"),
+        }
+    }
+}
+
+/// The local time of a timestamp, in `tz` or in UTC
+fn to_local(nanos: i64, tz: Option<Tz>) -> Result<NaiveDateTime> {
+    let datetime = DateTime::from_timestamp(
+        nanos.div_euclid(1_000_000_000),
+        nanos.rem_euclid(1_000_000_000) as u32,
+    )
+    .ok_or_else(|| exec_datafusion_err!("Timestamp {nanos} out of range"))?;
+    Ok(match tz {
+        Some(tz) => datetime.with_timezone(&tz).naive_local(),
+        None => datetime.naive_utc(),
+    })
+}
+
+/// The buckets aligned to an origin, numbered from the bucket starting at
+/// the origin
+struct Buckets {
+    stride: Stride,
+    /// The origin, in local time if `tz` is set
+    origin: NaiveDateTime,
+    /// The timezone buckets are computed in, `None` for UTC
+    tz: Option<Tz>,
+}
+
+impl Buckets {
+    fn try_new(stride: Stride, origin: i64, tz: Option<Tz>) -> Result<Self> {
+        Ok(Self {
+            stride,
+            origin: to_local(origin, tz)?,
+            tz,
+        })
+    }
+
+    /// The timestamp of a local time
+    fn from_local(&self, local: NaiveDateTime) -> Result<i64> {
+        let nanos = match self.tz {
+            Some(tz) => match tz.from_local_datetime(&local) {
+                LocalResult::Single(datetime) => datetime.timestamp_nanos_opt(),
+                // The bucket starts at the first of the repeated wall clock times
+                LocalResult::Ambiguous(earliest, _) => earliest.timestamp_nanos_opt(),
+                // The bucket starts in a wall clock time skipped by daylight
+                // saving time, use the end of the gap instead (see date_trunc)
+                LocalResult::None => {
+                    let shift = chrono::TimeDelta::try_hours(3).unwrap();
+                    tz.from_local_datetime(&(local - shift))
+                        .single()
+                        .and_then(|datetime| datetime.checked_add_signed(shift))
+                        .and_then(|datetime| datetime.timestamp_nanos_opt())
+                }
+            },
+            None => local.and_utc().timestamp_nanos_opt(),
+        };
+        nanos.ok_or_else(|| exec_datafusion_err!("Time bucket {local} out of range"))
+    }
+
+    /// The start of the bucket `index`
+    fn start(&self, index: i64) -> Result<i64> {
+        let local = match self.stride {
+            Stride::Nanoseconds(stride) => index
+                .checked_mul(stride)
+                .map(chrono::TimeDelta::nanoseconds)
+                .and_then(|delta| self.origin.checked_add_signed(delta)),
+            Stride::Months(stride) => {
+                let months = index.checked_mul(stride).and_then(|months| {
+                    u32::try_from(months.unsigned_abs())
+                        .ok()
+                        .map(|m| (months, m))
+                });
+                months.and_then(|(months, m)| {
+                    if months < 0 {
+                        self.origin.checked_sub_months(Months::new(m))
+                    } else {
+                        self.origin.checked_add_months(Months::new(m))
+                    }
+                })
+            }
+        };
+        let local = local
+            .ok_or_else(|| exec_datafusion_err!("Time bucket {index} out of range"))?;
+        self.from_local(local)
+    }
+
+    /// The index of the bucket containing a timestamp
+    fn index_of(&self, nanos: i64) -> Result<i64> {
+        let local = to_local(nanos, self.tz)?;
+        let mut index = match self.stride {
+            Stride::Nanoseconds(stride) => {
+                let diff = (local - self.origin).num_nanoseconds().ok_or_else(|| {
+                    exec_datafusion_err!("Timestamp {nanos} out of range")
+                })?;
+                diff.div_euclid(stride)
+            }
+            Stride::Months(stride) => {
+                let months = (local.year() - self.origin.year()) as i64 * 12
+                    + local.month() as i64
+                    - self.origin.month() as i64;
+                months.div_euclid(stride)
+            }
+        };
+        // Local times are not monotonic around daylight saving time changes,
+        // and months do not all start at the same day of the month
+        while self.start(index)? > nanos {
+            index -= 1;
+        }
+        while self.start(index + 1)? <= nanos {
+            index += 1;
+        }
+        Ok(index)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::memory::MemoryExec;
+
+    use arrow::array::{StringArray, TimestampSecondArray};
+    use arrow::datatypes::Field;
+    use datafusion_common::assert_batches_eq;
+    use datafusion_physical_expr::expressions::lit;
+
+    fn gap_fill_exec(start: i64, end: Bound<i64>) -> Result<Arc<dyn ExecutionPlan>> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("series", DataType::Utf8, false),
+            Field::new("time", DataType::Timestamp(TimeUnit::Second, None), true),
+            Field::new("v", DataType::Int64, true),
+            Field::new("w", DataType::Int64, true),
+        ]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![
+                Arc::new(StringArray::from(vec!["a", "a", "b"])),
+                Arc::new(TimestampSecondArray::from(vec![60, 180, 120])),
+                Arc::new(Int64Array::from(vec![Some(1), Some(2), Some(3)])),
+                Arc::new(Int64Array::from(vec![Some(10), None, Some(30)])),
+            ],
+        )?;
+        let input = Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None)?);
+        let timestamp =
+            |seconds: i64| lit(ScalarValue::TimestampSecond(Some(seconds), None));
+        let params = GapFillParams {
+            stride: lit(ScalarValue::new_interval_dt(0, 60_000)),
+            origin: None,
+            start: Bound::Included(timestamp(start)),
+            end: match end {
+                Bound::Included(end) => Bound::Included(timestamp(end)),
+                Bound::Excluded(end) => Bound::Excluded(timestamp(end)),
+                Bound::Unbounded => Bound::Unbounded,
+            },
+        };
+        Ok(Arc::new(GapFillExec::try_new(
+            input,
+            vec![Column::new("series", 0)],
+            Column::new("time", 1),
+            vec![Column::new("w", 3)],
+            params,
+        )?))
+    }
+
+    #[tokio::test]
+    async fn gap_fill() -> Result<()> {
+        let plan = gap_fill_exec(0, Bound::Excluded(300))?;
+        let batches =
+            common::collect(plan.execute(0, Arc::new(TaskContext::default()))?).await?;
+        let expected = vec![
+            "+--------+---------------------+---+----+",
+            "| series | time                | v | w  |",
+            "+--------+---------------------+---+----+",
+            "| a      | 1970-01-01T00:00:00 |   |    |",
+            "| a      | 1970-01-01T00:01:00 | 1 | 10 |",
+            "| a      | 1970-01-01T00:02:00 |   | 10 |",
+            "| a      | 1970-01-01T00:03:00 | 2 | 10 |",
+            "| a      | 1970-01-01T00:04:00 |   | 10 |",
+            "| b      | 1970-01-01T00:00:00 |   |    |",
+            "| b      | 1970-01-01T00:01:00 |   |    |",
+            "| b      | 1970-01-01T00:02:00 | 3 | 30 |",
+            "| b      | 1970-01-01T00:03:00 |   | 30 |",
+            "| b      | 1970-01-01T00:04:00 |   | 30 |",
+            "+--------+---------------------+---+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn gap_fill_unaligned_range() -> Result<()> {
+        // The buckets containing the start and the end are filled
+        let plan = gap_fill_exec(90, Bound::Included(150))?;
+        let batches =
+            common::collect(plan.execute(0, Arc::new(TaskContext::default()))?).await?;
+        let expected = vec![
+            "+--------+---------------------+---+----+",
+            "| series | time                | v | w  |",
+            "+--------+---------------------+---+----+",
+            "| a      | 1970-01-01T00:01:00 | 1 | 10 |",
+            "| a      | 1970-01-01T00:02:00 |   | 10 |",
+            "| a      | 1970-01-01T00:03:00 | 2 | 10 |",
+            "| b      | 1970-01-01T00:01:00 |   |    |",
+            "| b      | 1970-01-01T00:02:00 | 3 | 30 |",
+            "+--------+---------------------+---+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[test]
+    fn month_buckets_in_local_time() -> Result<()> {
+        let tz = Some("America/New_York".parse::<Tz>()?);
+        // 1970-01-01T00:00:00 in New York
+        let buckets = Buckets::try_new(Stride::Months(1), 5 * 3_600_000_000_000, tz)?;
+        // 2020-07-15T12:00:00Z
+        let index = buckets.index_of(1_594_814_400_000_000_000)?;
+        assert_eq!(index, 606);
+        // 2020-07-01T04:00:00Z, midnight in New York
+        assert_eq!(buckets.start(index)?, 1_593_576_000_000_000_000);
+        Ok(())
+    }
+}
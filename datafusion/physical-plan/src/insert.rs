@@ -28,12 +28,13 @@ use super::{
 };
 use crate::metrics::MetricsSet;
 use crate::stream::RecordBatchStreamAdapter;
+use crate::transaction::{StagedWrite, WriteTransaction};
 
 use arrow::datatypes::SchemaRef;
 use arrow::record_batch::RecordBatch;
 use arrow_array::{ArrayRef, UInt64Array};
 use arrow_schema::{DataType, Field, Schema};
-use datafusion_common::{exec_err, internal_err, Result};
+use datafusion_common::{exec_err, internal_err, not_impl_err, Result};
 use datafusion_execution::TaskContext;
 use datafusion_physical_expr::{
     Distribution, EquivalenceProperties, PhysicalSortRequirement,
@@ -72,6 +73,21 @@ pub trait DataSink: DisplayAs + Debug + Send + Sync {
         data: SendableRecordBatchStream,
         context: &Arc<TaskContext>,
     ) -> Result<u64>;
+
+    /// Writes the data to the sink without publishing it, returns the number
+    /// of values written and the staged write, published or discarded with
+    /// the other writes of the [`WriteTransaction`] the statement runs in.
+    ///
+    /// This method is called instead of [`Self::write_all`] when the
+    /// statement runs in a [`WriteTransaction`]. The sinks which don't
+    /// support staging their writes can't be written in a transaction.
+    async fn stage_all(
+        &self,
+        _data: SendableRecordBatchStream,
+        _context: &Arc<TaskContext>,
+    ) -> Result<(u64, Arc<dyn StagedWrite>)> {
+        not_impl_err!("{self:?} can't be written in a write transaction")
+    }
 }
 
 #[deprecated(since = "38.0.0", note = "Use [`DataSinkExec`] instead")]
@@ -280,7 +296,15 @@ impl ExecutionPlan for DataSinkExec {
         let sink = self.sink.clone();
 
         let stream = futures::stream::once(async move {
-            sink.write_all(data, &context).await.map(make_count_batch)
+            let count = match WriteTransaction::of(&context) {
+                Some(transaction) => {
+                    let (count, staged) = sink.stage_all(data, &context).await?;
+                    transaction.add(staged);
+                    count
+                }
+                None => sink.write_all(data, &context).await?,
+            };
+            Ok(make_count_batch(count))
         })
         .boxed();
 
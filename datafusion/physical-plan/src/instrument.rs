@@ -0,0 +1,246 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! InstrumentedExec records the execution of an operator in `tracing` spans
+
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use super::{
+    DisplayAs, DisplayFormatType, ExecutionPlan, PlanProperties, RecordBatchStream,
+    SendableRecordBatchStream, Statistics,
+};
+use crate::metrics::MetricsSet;
+
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::Result;
+use datafusion_execution::TaskContext;
+
+use futures::{Stream, StreamExt};
+use tracing::{info_span, Span};
+
+/// Wraps every operator of `plan` in an [`InstrumentedExec`], so that the
+/// execution of each of its partitions is recorded in a span, child of
+/// `parent`.
+///
+/// The operators are numbered in pre-order, the order in which `EXPLAIN`
+/// displays them, starting from `0` for the root of the plan.
+pub fn instrument_plan(
+    plan: Arc<dyn ExecutionPlan>,
+    parent: &Span,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    instrument_node(plan, &mut 0, parent)
+}
+
+fn instrument_node(
+    plan: Arc<dyn ExecutionPlan>,
+    next_id: &mut usize,
+    parent: &Span,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    let node_id = *next_id;
+    *next_id += 1;
+    let children = plan
+        .children()
+        .into_iter()
+        .map(|child| instrument_node(child, next_id, parent))
+        .collect::<Result<Vec<_>>>()?;
+    let plan = if children.is_empty() {
+        plan
+    } else {
+        plan.with_new_children(children)?
+    };
+    Ok(Arc::new(InstrumentedExec::new(
+        plan,
+        node_id,
+        parent.clone(),
+    )))
+}
+
+/// Records the execution of each partition of an operator in a
+/// `datafusion.operator` span, with the name of the operator, its id in the
+/// plan and the partition.
+///
+/// Created by [`instrument_plan`].
+#[derive(Debug)]
+pub struct InstrumentedExec {
+    /// The instrumented operator
+    input: Arc<dyn ExecutionPlan>,
+    /// The id of the operator in the plan
+    node_id: usize,
+    /// The parent of the spans
+    parent: Span,
+    cache: PlanProperties,
+}
+
+impl InstrumentedExec {
+    /// Create a new InstrumentedExec recording the execution of `input`
+    pub fn new(input: Arc<dyn ExecutionPlan>, node_id: usize, parent: Span) -> Self {
+        let cache = input.properties().clone();
+        Self {
+            input,
+            node_id,
+            parent,
+            cache,
+        }
+    }
+
+    /// The instrumented operator
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// The id of the operator in the plan
+    pub fn node_id(&self) -> usize {
+        self.node_id
+    }
+}
+
+impl DisplayAs for InstrumentedExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "InstrumentedExec: node_id={}", self.node_id)
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for InstrumentedExec {
+    fn name(&self) -> &'static str {
+        "InstrumentedExec"
+    }
+
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true]
+    }
+
+    fn benefits_from_input_partitioning(&self) -> Vec<bool> {
+        vec![false]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(InstrumentedExec::new(
+            children[0].clone(),
+            self.node_id,
+            self.parent.clone(),
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let span = info_span!(
+            parent: &self.parent,
+            "datafusion.operator",
+            operator = self.input.name(),
+            node_id = self.node_id,
+            partition,
+        );
+        let input = span.in_scope(|| self.input.execute(partition, context))?;
+        Ok(Box::pin(InstrumentedStream { input, span }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        None
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        self.input.statistics()
+    }
+}
+
+/// Enters the span of the partition whenever it is polled
+struct InstrumentedStream {
+    input: SendableRecordBatchStream,
+    span: Span,
+}
+
+impl Stream for InstrumentedStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        let this = &mut *self;
+        let _entered = this.span.enter();
+        this.input.poll_next_unpin(cx)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.input.size_hint()
+    }
+}
+
+impl RecordBatchStream for InstrumentedStream {
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::coalesce_partitions::CoalescePartitionsExec;
+    use crate::test::scan_partitioned;
+    use crate::{collect, displayable};
+
+    #[tokio::test]
+    async fn instrument_all_operators() -> Result<()> {
+        let plan = Arc::new(CoalescePartitionsExec::new(scan_partitioned(2)));
+        let instrumented = instrument_plan(plan, &Span::none())?;
+
+        let expected = [
+            "InstrumentedExec: node_id=0",
+            "  CoalescePartitionsExec",
+            "    InstrumentedExec: node_id=1",
+            "      MemoryExec: partitions=2, partition_sizes=[1, 1]",
+        ];
+        let actual = displayable(instrumented.as_ref()).indent(true).to_string();
+        assert_eq!(actual.trim().lines().collect::<Vec<_>>(), expected);
+
+        let batches = collect(instrumented, Arc::new(TaskContext::default())).await?;
+        let rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+        assert_eq!(rows, 200);
+        Ok(())
+    }
+}
@@ -0,0 +1,748 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the ASOF join execution plan.
+//!
+//! An ASOF join matches every row of the left input with the single row of
+//! the right input that has the same equi-join keys and whose "match"
+//! value is the nearest one satisfying a comparison, e.g. the most recent
+//! event at or before a timestamp. ASOF join is currently experimental.
+
+use std::any::Any;
+use std::cmp::Ordering;
+use std::fmt::Formatter;
+use std::sync::Arc;
+
+use crate::expressions::PhysicalSortExpr;
+use crate::joins::utils::{
+    build_join_schema, check_join_is_valid, partitioned_join_output_partitioning, JoinOn,
+    JoinOnRef,
+};
+use crate::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use crate::stream::RecordBatchStreamAdapter;
+use crate::{
+    execution_mode_from_children, DisplayAs, DisplayFormatType, Distribution,
+    ExecutionPlan, ExecutionPlanProperties, PlanProperties, SendableRecordBatchStream,
+    Statistics,
+};
+
+use arrow::array::{new_null_array, Array, ArrayRef, UInt32Builder};
+use arrow::compute::{concat_batches, take, SortOptions};
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use arrow::row::{RowConverter, Rows, SortField};
+use datafusion_common::{
+    internal_err, plan_err, DataFusionError, JoinSide, JoinType, Result,
+};
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::TaskContext;
+use datafusion_expr::Operator;
+use datafusion_physical_expr::equivalence::join_equivalence_properties;
+use datafusion_physical_expr::{PhysicalExprRef, PhysicalSortRequirement};
+
+use futures::{StreamExt, TryStreamExt};
+
+/// ASOF join execution plan.
+///
+/// For every row of the left input, finds the row of the right input with
+/// equal `on` keys for which `left_match <operator> right_match` holds and
+/// whose match value is the nearest to the left one:
+///
+/// * `>=` / `>`: the right row with the greatest match value that is less
+///   than or equal to (strictly less than) the left one, i.e. the most
+///   recent row "as of" the left row.
+/// * `<=` / `<`: the right row with the smallest match value that is
+///   greater than or equal to (strictly greater than) the left one.
+///
+/// Every left row is emitted exactly once; the right columns are NULL when
+/// there is no match (like a left outer join). Rows with NULL keys or match
+/// values never match.
+///
+/// Both inputs must be sorted on the `on` keys followed by the match
+/// expression (ascending, nulls first). The join then performs a single
+/// merge pass over the two inputs: the right input of each partition is
+/// buffered in memory, and the left input is streamed while a cursor over
+/// the right rows only moves forward.
+///
+/// Planned from the `AsOfJoin` logical node, e.g. built with
+/// `LogicalPlanBuilder::asof_join`.
+#[derive(Debug)]
+pub struct AsOfJoinExec {
+    /// Left (streamed) input
+    left: Arc<dyn ExecutionPlan>,
+    /// Right (buffered) input
+    right: Arc<dyn ExecutionPlan>,
+    /// Equi-join keys
+    on: JoinOn,
+    /// Match expression evaluated against the left input
+    left_match: PhysicalExprRef,
+    /// Comparison between the left and right match expressions
+    operator: Operator,
+    /// Match expression evaluated against the right input
+    right_match: PhysicalExprRef,
+    /// The schema once the join is applied
+    schema: SchemaRef,
+    /// Ordering required from the left input
+    left_sort_exprs: Vec<PhysicalSortExpr>,
+    /// Ordering required from the right input
+    right_sort_exprs: Vec<PhysicalSortExpr>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl AsOfJoinExec {
+    /// Tries to create a new [`AsOfJoinExec`] joining on the `on` keys and
+    /// the match condition `left_match <operator> right_match`.
+    ///
+    /// # Error
+    /// This function errors when `operator` is not one of `>=`, `>`, `<=`
+    /// or `<`, or when the key or match expressions of both sides have
+    /// different types.
+    pub fn try_new(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        on: JoinOn,
+        left_match: PhysicalExprRef,
+        operator: Operator,
+        right_match: PhysicalExprRef,
+    ) -> Result<Self> {
+        let left_schema = left.schema();
+        let right_schema = right.schema();
+
+        if !matches!(
+            operator,
+            Operator::Gt | Operator::GtEq | Operator::Lt | Operator::LtEq
+        ) {
+            return plan_err!(
+                "ASOF join match condition must use one of >=, >, <=, <, got {operator}"
+            );
+        }
+        check_join_is_valid(&left_schema, &right_schema, &on)?;
+        let match_pair = (left_match.clone(), right_match.clone());
+        for (l, r) in on.iter().chain(std::iter::once(&match_pair)) {
+            let left_type = l.data_type(&left_schema)?;
+            let right_type = r.data_type(&right_schema)?;
+            if left_type != right_type {
+                return plan_err!(
+                    "ASOF join expressions {l} and {r} have different types: \
+                     {left_type} and {right_type}"
+                );
+            }
+        }
+
+        let sort_exprs = |exprs: Vec<PhysicalExprRef>| {
+            exprs
+                .into_iter()
+                .map(|expr| PhysicalSortExpr {
+                    expr,
+                    options: SortOptions::default(),
+                })
+                .collect::<Vec<_>>()
+        };
+        let (mut left_keys, mut right_keys): (Vec<_>, Vec<_>) =
+            on.iter().cloned().unzip();
+        left_keys.push(left_match.clone());
+        right_keys.push(right_match.clone());
+        let left_sort_exprs = sort_exprs(left_keys);
+        let right_sort_exprs = sort_exprs(right_keys);
+
+        let schema =
+            Arc::new(build_join_schema(&left_schema, &right_schema, &JoinType::Left).0);
+        let cache = Self::compute_properties(&left, &right, schema.clone(), &on);
+        Ok(Self {
+            left,
+            right,
+            on,
+            left_match,
+            operator,
+            right_match,
+            schema,
+            left_sort_exprs,
+            right_sort_exprs,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        })
+    }
+
+    /// Left (streamed) input
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// Right (buffered) input
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
+    /// Set of common columns used to join on
+    pub fn on(&self) -> &[(PhysicalExprRef, PhysicalExprRef)] {
+        &self.on
+    }
+
+    /// The match condition as `(left_match, operator, right_match)`
+    pub fn match_condition(&self) -> (&PhysicalExprRef, Operator, &PhysicalExprRef) {
+        (&self.left_match, self.operator, &self.right_match)
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        left: &Arc<dyn ExecutionPlan>,
+        right: &Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+        join_on: JoinOnRef,
+    ) -> PlanProperties {
+        // Calculate equivalence properties:
+        let eq_properties = join_equivalence_properties(
+            left.equivalence_properties().clone(),
+            right.equivalence_properties().clone(),
+            &JoinType::Left,
+            schema,
+            &[true, false],
+            Some(JoinSide::Left),
+            join_on,
+        );
+
+        // Get output partitioning:
+        let left_columns_len = left.schema().fields.len();
+        let output_partitioning = partitioned_join_output_partitioning(
+            JoinType::Left,
+            left.output_partitioning(),
+            right.output_partitioning(),
+            left_columns_len,
+        );
+
+        // Determine execution mode:
+        let mode = execution_mode_from_children([left, right]);
+
+        PlanProperties::new(eq_properties, output_partitioning, mode)
+    }
+}
+
+impl DisplayAs for AsOfJoinExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                let on = self
+                    .on
+                    .iter()
+                    .map(|(c1, c2)| format!("({}, {})", c1, c2))
+                    .collect::<Vec<String>>()
+                    .join(", ");
+                write!(
+                    f,
+                    "AsOfJoinExec: on=[{}], match_condition={} {} {}",
+                    on, self.left_match, self.operator, self.right_match
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for AsOfJoinExec {
+    fn name(&self) -> &'static str {
+        "AsOfJoinExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        if self.on.is_empty() {
+            return vec![Distribution::SinglePartition, Distribution::SinglePartition];
+        }
+        let (left_expr, right_expr) =
+            self.on.iter().map(|(l, r)| (l.clone(), r.clone())).unzip();
+        vec![
+            Distribution::HashPartitioned(left_expr),
+            Distribution::HashPartitioned(right_expr),
+        ]
+    }
+
+    fn required_input_ordering(&self) -> Vec<Option<Vec<PhysicalSortRequirement>>> {
+        vec![
+            Some(PhysicalSortRequirement::from_sort_exprs(
+                &self.left_sort_exprs,
+            )),
+            Some(PhysicalSortRequirement::from_sort_exprs(
+                &self.right_sort_exprs,
+            )),
+        ]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true, false]
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.left.clone(), self.right.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        match &children[..] {
+            [left, right] => Ok(Arc::new(AsOfJoinExec::try_new(
+                left.clone(),
+                right.clone(),
+                self.on.clone(),
+                self.left_match.clone(),
+                self.operator,
+                self.right_match.clone(),
+            )?)),
+            _ => internal_err!("AsOfJoinExec wrong number of children"),
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let left_partitions = self.left.output_partitioning().partition_count();
+        let right_partitions = self.right.output_partitioning().partition_count();
+        if left_partitions != right_partitions {
+            return internal_err!(
+                "Invalid AsOfJoinExec, partition count mismatch {left_partitions}!={right_partitions},\
+                 consider using RepartitionExec"
+            );
+        }
+
+        let left = self.left.execute(partition, context.clone())?;
+        let right = self.right.execute(partition, context.clone())?;
+        let reservation = MemoryConsumer::new(format!("AsOfJoinStream[{partition}]"))
+            .register(context.memory_pool());
+        let baseline_metrics = BaselineMetrics::new(&self.metrics, partition);
+
+        let (on_left, on_right): (Vec<_>, Vec<_>) = self.on.iter().cloned().unzip();
+        let left_keys = AsOfJoinKeys {
+            on: on_left,
+            match_expr: self.left_match.clone(),
+        };
+        let right_keys = AsOfJoinKeys {
+            on: on_right,
+            match_expr: self.right_match.clone(),
+        };
+        let schema = self.schema.clone();
+        let operator = self.operator;
+
+        let stream = futures::stream::once(async move {
+            let buffered = BufferedSide::try_new(right, &right_keys, reservation).await?;
+            let mut joiner = AsOfJoiner {
+                schema,
+                keys: left_keys,
+                operator,
+                buffered,
+                cursor: 0,
+            };
+            Ok::<_, DataFusionError>(left.map(move |batch| {
+                let _timer = baseline_metrics.elapsed_compute().timer();
+                let batch = joiner.join_batch(&batch?)?;
+                baseline_metrics.record_output(batch.num_rows());
+                Ok(batch)
+            }))
+        })
+        .try_flatten();
+
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema.clone(),
+            stream,
+        )))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        // Every left row produces exactly one output row
+        let mut statistics = Statistics::new_unknown(&self.schema);
+        statistics.num_rows = self.left.statistics()?.num_rows;
+        Ok(statistics)
+    }
+}
+
+/// The key and match expressions of one side of the join
+struct AsOfJoinKeys {
+    on: Vec<PhysicalExprRef>,
+    match_expr: PhysicalExprRef,
+}
+
+impl AsOfJoinKeys {
+    /// Evaluates the key expressions followed by the match expression
+    fn evaluate(&self, batch: &RecordBatch) -> Result<Vec<ArrayRef>> {
+        self.on
+            .iter()
+            .chain(std::iter::once(&self.match_expr))
+            .map(|expr| expr.evaluate(batch)?.into_array(batch.num_rows()))
+            .collect()
+    }
+}
+
+/// Converts the evaluated keys of a side into comparable rows
+struct KeyRows {
+    /// Rows of the key columns followed by the match column
+    full: Rows,
+    /// Rows of the key columns only
+    keys: Option<Rows>,
+    /// Whether any key or match value of the row is NULL
+    has_null: Vec<bool>,
+}
+
+impl KeyRows {
+    fn try_new(
+        full_converter: &RowConverter,
+        key_converter: Option<&RowConverter>,
+        columns: &[ArrayRef],
+        num_rows: usize,
+    ) -> Result<Self> {
+        let full = full_converter.convert_columns(columns)?;
+        let keys = key_converter
+            .map(|converter| converter.convert_columns(&columns[..columns.len() - 1]))
+            .transpose()?;
+        let has_null = (0..num_rows)
+            .map(|row| columns.iter().any(|column| column.is_null(row)))
+            .collect();
+        Ok(Self {
+            full,
+            keys,
+            has_null,
+        })
+    }
+
+    fn same_keys(&self, row: usize, other: &KeyRows, other_row: usize) -> bool {
+        match (&self.keys, &other.keys) {
+            (Some(keys), Some(other_keys)) => keys.row(row) == other_keys.row(other_row),
+            _ => true,
+        }
+    }
+}
+
+/// The right input of the join, buffered in memory
+struct BufferedSide {
+    batch: RecordBatch,
+    rows: KeyRows,
+    full_converter: RowConverter,
+    key_converter: Option<RowConverter>,
+    /// Memory reservation for the buffered batch
+    _reservation: MemoryReservation,
+}
+
+impl BufferedSide {
+    async fn try_new(
+        input: SendableRecordBatchStream,
+        keys: &AsOfJoinKeys,
+        mut reservation: MemoryReservation,
+    ) -> Result<Self> {
+        let schema = input.schema();
+        let batches = input
+            .and_then(|batch| {
+                let result = reservation
+                    .try_grow(batch.get_array_memory_size())
+                    .map(|_| batch);
+                futures::future::ready(result)
+            })
+            .try_collect::<Vec<_>>()
+            .await?;
+        let batch = concat_batches(&schema, &batches)?;
+
+        let columns = keys.evaluate(&batch)?;
+        let fields = columns
+            .iter()
+            .map(|column| SortField::new(column.data_type().clone()))
+            .collect::<Vec<_>>();
+        let full_converter = RowConverter::new(fields.clone())?;
+        let key_converter = (!keys.on.is_empty())
+            .then(|| RowConverter::new(fields[..fields.len() - 1].to_vec()))
+            .transpose()?;
+        let rows = KeyRows::try_new(
+            &full_converter,
+            key_converter.as_ref(),
+            &columns,
+            batch.num_rows(),
+        )?;
+        Ok(Self {
+            batch,
+            rows,
+            full_converter,
+            key_converter,
+            _reservation: reservation,
+        })
+    }
+}
+
+/// Joins the batches of the sorted left input against the buffered right
+/// input, keeping a cursor over the right rows across batches
+struct AsOfJoiner {
+    schema: SchemaRef,
+    keys: AsOfJoinKeys,
+    operator: Operator,
+    buffered: BufferedSide,
+    /// Index of the first right row that has not been passed yet
+    cursor: usize,
+}
+
+impl AsOfJoiner {
+    fn join_batch(&mut self, batch: &RecordBatch) -> Result<RecordBatch> {
+        let num_rows = batch.num_rows();
+        let columns = self.keys.evaluate(batch)?;
+        let rows = KeyRows::try_new(
+            &self.buffered.full_converter,
+            self.buffered.key_converter.as_ref(),
+            &columns,
+            num_rows,
+        )?;
+
+        // Right rows comparing as `advance_past` (or less) to a left row are
+        // passed by the cursor
+        let advance_past = match self.operator {
+            Operator::GtEq | Operator::Lt => Ordering::Equal,
+            _ => Ordering::Less,
+        };
+        // Whether the match is the last passed row or the next one
+        let match_before = matches!(self.operator, Operator::GtEq | Operator::Gt);
+
+        let right = &self.buffered.rows;
+        let right_len = self.buffered.batch.num_rows();
+        let mut indices = UInt32Builder::with_capacity(num_rows);
+        for row in 0..num_rows {
+            if rows.has_null[row] {
+                indices.append_null();
+                continue;
+            }
+            let left_row = rows.full.row(row);
+            while self.cursor < right_len
+                && right.full.row(self.cursor).cmp(&left_row) <= advance_past
+            {
+                self.cursor += 1;
+            }
+            let candidate = if match_before {
+                self.cursor.checked_sub(1)
+            } else {
+                (self.cursor < right_len).then_some(self.cursor)
+            };
+            match candidate
+                .filter(|&idx| !right.has_null[idx] && rows.same_keys(row, right, idx))
+            {
+                Some(idx) => indices.append_value(idx as u32),
+                None => indices.append_null(),
+            }
+        }
+        let indices = indices.finish();
+
+        let mut output = batch.columns().to_vec();
+        for (idx, column) in self.buffered.batch.columns().iter().enumerate() {
+            if right_len == 0 {
+                let field = self.schema.field(batch.num_columns() + idx);
+                output.push(new_null_array(field.data_type(), num_rows));
+            } else {
+                output.push(take(column, &indices, None)?);
+            }
+        }
+        Ok(RecordBatch::try_new(self.schema.clone(), output)?)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common;
+    use crate::expressions::col;
+    use crate::memory::MemoryExec;
+    use crate::test::build_table_i32;
+
+    use arrow::array::Int32Array;
+    use datafusion_common::assert_batches_eq;
+
+    /// Builds a sorted table from `(a, b, c)` columns, sorted on `(a, b)`
+    fn build_sorted_table(
+        a: (&str, &Vec<i32>),
+        b: (&str, &Vec<i32>),
+        c: (&str, &Vec<i32>),
+    ) -> Arc<dyn ExecutionPlan> {
+        let batch = build_table_i32(a, b, c);
+        let schema = batch.schema();
+        let sort_exprs = vec![
+            PhysicalSortExpr {
+                expr: col(a.0, &schema).unwrap(),
+                options: SortOptions::default(),
+            },
+            PhysicalSortExpr {
+                expr: col(b.0, &schema).unwrap(),
+                options: SortOptions::default(),
+            },
+        ];
+        Arc::new(
+            MemoryExec::try_new(&[vec![batch]], schema, None)
+                .unwrap()
+                .with_sort_information(vec![sort_exprs]),
+        )
+    }
+
+    async fn join_collect(operator: Operator) -> Result<Vec<RecordBatch>> {
+        // (device, ts, value)
+        let left = build_sorted_table(
+            ("d1", &vec![1, 1, 1, 2, 3]),
+            ("t1", &vec![1, 5, 10, 5, 5]),
+            ("v1", &vec![10, 50, 100, 50, 50]),
+        );
+        let right = build_sorted_table(
+            ("d2", &vec![1, 1, 1, 2]),
+            ("t2", &vec![0, 5, 8, 7]),
+            ("v2", &vec![0, 5, 8, 7]),
+        );
+        let on = vec![(col("d1", &left.schema())?, col("d2", &right.schema())?)];
+        let left_match = col("t1", &left.schema())?;
+        let right_match = col("t2", &right.schema())?;
+        let join = Arc::new(AsOfJoinExec::try_new(
+            left,
+            right,
+            on,
+            left_match,
+            operator,
+            right_match,
+        )?);
+
+        let stream = join.execute(0, Arc::new(TaskContext::default()))?;
+        common::collect(stream).await
+    }
+
+    #[tokio::test]
+    async fn asof_join_most_recent() -> Result<()> {
+        let batches = join_collect(Operator::GtEq).await?;
+        let expected = [
+            "+----+----+-----+----+----+----+",
+            "| d1 | t1 | v1  | d2 | t2 | v2 |",
+            "+----+----+-----+----+----+----+",
+            "| 1  | 1  | 10  | 1  | 0  | 0  |",
+            "| 1  | 5  | 50  | 1  | 5  | 5  |",
+            "| 1  | 10 | 100 | 1  | 8  | 8  |",
+            "| 2  | 5  | 50  |    |    |    |",
+            "| 3  | 5  | 50  |    |    |    |",
+            "+----+----+-----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+
+        let batches = join_collect(Operator::Gt).await?;
+        let expected = [
+            "+----+----+-----+----+----+----+",
+            "| d1 | t1 | v1  | d2 | t2 | v2 |",
+            "+----+----+-----+----+----+----+",
+            "| 1  | 1  | 10  | 1  | 0  | 0  |",
+            "| 1  | 5  | 50  | 1  | 0  | 0  |",
+            "| 1  | 10 | 100 | 1  | 8  | 8  |",
+            "| 2  | 5  | 50  |    |    |    |",
+            "| 3  | 5  | 50  |    |    |    |",
+            "+----+----+-----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn asof_join_next() -> Result<()> {
+        let batches = join_collect(Operator::LtEq).await?;
+        let expected = [
+            "+----+----+-----+----+----+----+",
+            "| d1 | t1 | v1  | d2 | t2 | v2 |",
+            "+----+----+-----+----+----+----+",
+            "| 1  | 1  | 10  | 1  | 5  | 5  |",
+            "| 1  | 5  | 50  | 1  | 5  | 5  |",
+            "| 1  | 10 | 100 |    |    |    |",
+            "| 2  | 5  | 50  | 2  | 7  | 7  |",
+            "| 3  | 5  | 50  |    |    |    |",
+            "+----+----+-----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+
+        let batches = join_collect(Operator::Lt).await?;
+        let expected = [
+            "+----+----+-----+----+----+----+",
+            "| d1 | t1 | v1  | d2 | t2 | v2 |",
+            "+----+----+-----+----+----+----+",
+            "| 1  | 1  | 10  | 1  | 5  | 5  |",
+            "| 1  | 5  | 50  | 1  | 8  | 8  |",
+            "| 1  | 10 | 100 |    |    |    |",
+            "| 2  | 5  | 50  | 2  | 7  | 7  |",
+            "| 3  | 5  | 50  |    |    |    |",
+            "+----+----+-----+----+----+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn asof_join_empty_right() -> Result<()> {
+        let left = build_sorted_table(
+            ("d1", &vec![1, 2]),
+            ("t1", &vec![1, 5]),
+            ("v1", &vec![10, 50]),
+        );
+        let right_batch =
+            build_table_i32(("d2", &vec![]), ("t2", &vec![]), ("v2", &vec![]));
+        let right_schema = right_batch.schema();
+        let right = Arc::new(MemoryExec::try_new(
+            &[vec![right_batch]],
+            right_schema,
+            None,
+        )?);
+        let on = vec![(col("d1", &left.schema())?, col("d2", &right.schema())?)];
+        let left_match = col("t1", &left.schema())?;
+        let right_match = col("t2", &right.schema())?;
+        let join = AsOfJoinExec::try_new(
+            left,
+            right,
+            on,
+            left_match,
+            Operator::GtEq,
+            right_match,
+        )?;
+
+        let stream = join.execute(0, Arc::new(TaskContext::default()))?;
+        let batches = common::collect(stream).await?;
+        let v2 = batches[0].column(5);
+        assert_eq!(v2.null_count(), 2);
+        assert!(v2.as_any().downcast_ref::<Int32Array>().is_some());
+        Ok(())
+    }
+
+    #[test]
+    fn asof_join_invalid_operator() -> Result<()> {
+        let left = build_sorted_table(("a1", &vec![]), ("b1", &vec![]), ("c1", &vec![]));
+        let right = build_sorted_table(("a2", &vec![]), ("b2", &vec![]), ("c2", &vec![]));
+        let left_match = col("b1", &left.schema())?;
+        let right_match = col("b2", &right.schema())?;
+        let err = AsOfJoinExec::try_new(
+            left,
+            right,
+            vec![],
+            left_match,
+            Operator::Eq,
+            right_match,
+        )
+        .unwrap_err();
+        assert!(err.to_string().contains("must use one of"));
+        Ok(())
+    }
+}
@@ -26,6 +26,7 @@ use super::utils::{
 };
 use crate::coalesce_batches::concat_batches;
 use crate::coalesce_partitions::CoalescePartitionsExec;
+use crate::common::SharedBufferTracker;
 use crate::metrics::{ExecutionPlanMetricsSet, MetricsSet};
 use crate::{
     execution_mode_from_children, handle_state, ColumnStatistics, DisplayAs,
@@ -155,12 +156,18 @@ async fn load_left_input(
     let stream = merge.execute(0, context)?;
 
     // Load all batches and count the rows
-    let (batches, num_rows, _, reservation) = stream
+    let (batches, num_rows, _, reservation, _) = stream
         .try_fold(
-            (Vec::new(), 0usize, metrics, reservation),
+            (
+                Vec::new(),
+                0usize,
+                metrics,
+                reservation,
+                SharedBufferTracker::default(),
+            ),
             |mut acc, batch| async {
-                let batch_size = batch.get_array_memory_size();
-                // Reserve memory for incoming batch
+                // Reserve memory for the buffers not shared with the batches before
+                let batch_size = acc.4.add(&batch);
                 acc.3.try_grow(batch_size)?;
                 // Update metrics
                 acc.2.build_mem_used.add(batch_size);
@@ -301,12 +308,14 @@ fn stats_cartesian_product(
             distinct_count: s.distinct_count,
             min_value: s.min_value,
             max_value: s.max_value,
+            distinct_sketch: s.distinct_sketch,
         })
         .chain(right_col_stats.into_iter().map(|s| ColumnStatistics {
             null_count: s.null_count.multiply(&left_row_count),
             distinct_count: s.distinct_count,
             min_value: s.min_value,
             max_value: s.max_value,
+            distinct_sketch: s.distinct_sketch,
         }))
         .collect();
 
@@ -526,12 +535,14 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    distinct_sketch: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Exact(3),
+                    distinct_sketch: None,
                 },
             ],
         };
@@ -544,6 +555,7 @@ mod tests {
                 max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
                 min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
                 null_count: Precision::Exact(2),
+                distinct_sketch: None,
             }],
         };
 
@@ -558,18 +570,21 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    distinct_sketch: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Exact(3 * right_row_count),
+                    distinct_sketch: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(3),
                     max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
                     null_count: Precision::Exact(2 * left_row_count),
+                    distinct_sketch: None,
                 },
             ],
         };
@@ -590,12 +605,14 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    distinct_sketch: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Exact(3),
+                    distinct_sketch: None,
                 },
             ],
         };
@@ -608,6 +625,7 @@ mod tests {
                 max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
                 min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
                 null_count: Precision::Exact(2),
+                distinct_sketch: None,
             }],
         };
 
@@ -622,18 +640,21 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Absent, // we don't know the row count on the right
+                    distinct_sketch: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Absent, // we don't know the row count on the right
+                    distinct_sketch: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(3),
                     max_value: Precision::Exact(ScalarValue::Int64(Some(12))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(0))),
                     null_count: Precision::Exact(2 * left_row_count),
+                    distinct_sketch: None,
                 },
             ],
         };
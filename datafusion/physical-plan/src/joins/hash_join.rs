@@ -31,9 +31,9 @@ use super::{
 use crate::ExecutionPlanProperties;
 use crate::{
     coalesce_partitions::CoalescePartitionsExec,
-    common::can_project,
+    common::{can_project, SharedBufferTracker},
     execution_mode_from_children, handle_state,
-    hash_utils::create_hashes,
+    hash_utils::{create_hashes, HashState, SeededHashState},
     joins::utils::{
         adjust_indices_by_join_type, adjust_right_output_partitioning,
         apply_join_filter_to_indices, build_batch_from_indices, build_join_schema,
@@ -53,7 +53,9 @@ use arrow::array::{
     UInt64Array,
 };
 use arrow::compute::kernels::cmp::{eq, not_distinct};
-use arrow::compute::{and, concat_batches, take, FilterBuilder};
+use arrow::compute::{
+    and, concat_batches, filter, filter_record_batch, is_not_null, take, FilterBuilder,
+};
 use arrow::datatypes::{Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
 use arrow::util::bit_util;
@@ -71,7 +73,6 @@ use datafusion_physical_expr::equivalence::{
 use datafusion_physical_expr::expressions::UnKnownColumn;
 use datafusion_physical_expr::{PhysicalExpr, PhysicalExprRef};
 
-use ahash::RandomState;
 use futures::{ready, Stream, StreamExt, TryStreamExt};
 use parking_lot::Mutex;
 
@@ -308,8 +309,6 @@ pub struct HashJoinExec {
     join_schema: SchemaRef,
     /// Future that consumes left input and builds the hash table
     left_fut: OnceAsync<JoinLeftData>,
-    /// Shared the `RandomState` for the hashing algorithm
-    random_state: RandomState,
     /// Partitioning mode to use
     pub mode: PartitionMode,
     /// Execution metrics
@@ -323,6 +322,11 @@ pub struct HashJoinExec {
     /// Otherwise, rows that have `null`s in the join columns will not be
     /// matched and thus will not appear in the output.
     pub null_equals_null: bool,
+    /// If `null_aware` is true, a `RightAnti` join returns the probe side rows
+    /// kept by a `NOT IN` predicate over the build side keys: none once a build
+    /// side key is `null`, and rows with a `null` key only if the build side is
+    /// empty. See [`Self::with_null_aware`].
+    null_aware: bool,
     /// Cache holding plan properties like equivalences, output partitioning etc.
     cache: PlanProperties,
 }
@@ -354,8 +358,6 @@ impl HashJoinExec {
         let (join_schema, column_indices) =
             build_join_schema(&left_schema, &right_schema, join_type);
 
-        let random_state = RandomState::with_seeds(0, 0, 0, 0);
-
         let join_schema = Arc::new(join_schema);
 
         //  check if the projection is valid
@@ -379,16 +381,38 @@ impl HashJoinExec {
             join_type: *join_type,
             join_schema,
             left_fut: Default::default(),
-            random_state,
             mode: partition_mode,
             metrics: ExecutionPlanMetricsSet::new(),
             projection,
             column_indices,
             null_equals_null,
+            null_aware: false,
             cache,
         })
     }
 
+    /// Returns this join with `null_aware` set, making a `RightAnti` join
+    /// follow the semantics of `<probe key> NOT IN (<build keys>)`.
+    ///
+    /// A null-aware join must be a `RightAnti` join on a single key without a
+    /// filter, and it must run in [`PartitionMode::CollectLeft`] so that every
+    /// probe partition sees the whole build side.
+    pub fn with_null_aware(mut self, null_aware: bool) -> Result<Self> {
+        if null_aware
+            && (self.join_type != JoinType::RightAnti
+                || self.on.len() != 1
+                || self.filter.is_some()
+                || self.mode != PartitionMode::CollectLeft)
+        {
+            return plan_err!(
+                "Null-aware HashJoinExec requires a RightAnti join on a single key \
+                 without a filter in CollectLeft mode"
+            );
+        }
+        self.null_aware = null_aware;
+        Ok(self)
+    }
+
     /// left (build) side which gets hashed
     pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
         &self.left
@@ -424,6 +448,11 @@ impl HashJoinExec {
         self.null_equals_null
     }
 
+    /// Get null_aware
+    pub fn null_aware(&self) -> bool {
+        self.null_aware
+    }
+
     /// Calculate order preservation flags for this hash join.
     fn maintains_input_order(join_type: JoinType) -> Vec<bool> {
         vec![
@@ -466,7 +495,8 @@ impl HashJoinExec {
             projection,
             self.mode,
             self.null_equals_null,
-        )
+        )?
+        .with_null_aware(self.null_aware)
     }
 
     /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
@@ -600,10 +630,20 @@ impl DisplayAs for HashJoinExec {
                     .map(|(c1, c2)| format!("({}, {})", c1, c2))
                     .collect::<Vec<String>>()
                     .join(", ");
+                let display_null_aware = if self.null_aware {
+                    ", null_aware=true"
+                } else {
+                    ""
+                };
                 write!(
                     f,
-                    "HashJoinExec: mode={:?}, join_type={:?}, on=[{}]{}{}",
-                    self.mode, self.join_type, on, display_filter, display_projections
+                    "HashJoinExec: mode={:?}, join_type={:?}, on=[{}]{}{}{}",
+                    self.mode,
+                    self.join_type,
+                    on,
+                    display_filter,
+                    display_projections,
+                    display_null_aware
                 )
             }
         }
@@ -691,16 +731,19 @@ impl ExecutionPlan for HashJoinExec {
         self: Arc<Self>,
         children: Vec<Arc<dyn ExecutionPlan>>,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        Ok(Arc::new(HashJoinExec::try_new(
-            children[0].clone(),
-            children[1].clone(),
-            self.on.clone(),
-            self.filter.clone(),
-            &self.join_type,
-            self.projection.clone(),
-            self.mode,
-            self.null_equals_null,
-        )?))
+        Ok(Arc::new(
+            HashJoinExec::try_new(
+                children[0].clone(),
+                children[1].clone(),
+                self.on.clone(),
+                self.filter.clone(),
+                &self.join_type,
+                self.projection.clone(),
+                self.mode,
+                self.null_equals_null,
+            )?
+            .with_null_aware(self.null_aware)?,
+        ))
     }
 
     fn execute(
@@ -721,6 +764,9 @@ impl ExecutionPlan for HashJoinExec {
             );
         }
 
+        let options = &context.session_config().options().execution;
+        let random_state = SeededHashState::new(options.hash_function, options.hash_seed);
+
         let join_metrics = BuildProbeJoinMetrics::new(partition, &self.metrics);
         let left_fut = match self.mode {
             PartitionMode::CollectLeft => self.left_fut.once(|| {
@@ -728,7 +774,7 @@ impl ExecutionPlan for HashJoinExec {
                     MemoryConsumer::new("HashJoinInput").register(context.memory_pool());
                 collect_left_input(
                     None,
-                    self.random_state.clone(),
+                    random_state.clone(),
                     self.left.clone(),
                     on_left.clone(),
                     context.clone(),
@@ -745,7 +791,7 @@ impl ExecutionPlan for HashJoinExec {
 
                 OnceFut::new(collect_left_input(
                     Some(partition),
-                    self.random_state.clone(),
+                    random_state.clone(),
                     self.left.clone(),
                     on_left.clone(),
                     context.clone(),
@@ -786,9 +832,10 @@ impl ExecutionPlan for HashJoinExec {
             join_type: self.join_type,
             right: right_stream,
             column_indices: column_indices_after_projection,
-            random_state: self.random_state.clone(),
+            random_state,
             join_metrics,
             null_equals_null: self.null_equals_null,
+            null_aware: self.null_aware,
             state: HashJoinStreamState::WaitBuildSide,
             build_side: BuildSide::Initial(BuildSideInitialState { left_fut }),
             batch_size,
@@ -830,7 +877,7 @@ impl ExecutionPlan for HashJoinExec {
 #[allow(clippy::too_many_arguments)]
 async fn collect_left_input(
     partition: Option<usize>,
-    random_state: RandomState,
+    random_state: SeededHashState,
     left: Arc<dyn ExecutionPlan>,
     on_left: Vec<PhysicalExprRef>,
     context: Arc<TaskContext>,
@@ -855,11 +902,17 @@ async fn collect_left_input(
     // This operation performs 2 steps at once:
     // 1. creates a [JoinHashMap] of all batches from the stream
     // 2. stores the batches in a vector.
-    let initial = (Vec::new(), 0, metrics, reservation);
-    let (batches, num_rows, metrics, mut reservation) = stream
+    let initial = (
+        Vec::new(),
+        0,
+        metrics,
+        reservation,
+        SharedBufferTracker::default(),
+    );
+    let (batches, num_rows, metrics, mut reservation, _) = stream
         .try_fold(initial, |mut acc, batch| async {
-            let batch_size = batch.get_array_memory_size();
-            // Reserve memory for incoming batch
+            // Reserve memory for the buffers not shared with the batches before
+            let batch_size = acc.4.add(&batch);
             acc.3.try_grow(batch_size)?;
             // Update metrics
             acc.2.build_mem_used.add(batch_size);
@@ -948,18 +1001,19 @@ async fn collect_left_input(
 /// which allows to keep either first (if set to true) or last (if set to false) row index
 /// as a chain head for rows with equal hash values.
 #[allow(clippy::too_many_arguments)]
-pub fn update_hash<T>(
+pub fn update_hash<T, S>(
     on: &[PhysicalExprRef],
     batch: &RecordBatch,
     hash_map: &mut T,
     offset: usize,
-    random_state: &RandomState,
+    random_state: &S,
     hashes_buffer: &mut Vec<u64>,
     deleted_offset: usize,
     fifo_hashmap: bool,
 ) -> Result<()>
 where
     T: JoinHashMapType,
+    S: HashState,
 {
     // evaluate the keys
     let keys_values = on
@@ -1117,13 +1171,15 @@ struct HashJoinStream {
     /// right (probe) input
     right: SendableRecordBatchStream,
     /// Random state used for hashing initialization
-    random_state: RandomState,
+    random_state: SeededHashState,
     /// Metrics
     join_metrics: BuildProbeJoinMetrics,
     /// Information of index and left / right placement of columns
     column_indices: Vec<ColumnIndex>,
     /// If null_equals_null is true, null == null else null != null
     null_equals_null: bool,
+    /// If null_aware is true, the anti join follows the semantics of `NOT IN`
+    null_aware: bool,
     /// State of the stream
     state: HashJoinStreamState,
     /// Build side
@@ -1335,7 +1391,19 @@ impl HashJoinStream {
             .get_shared(cx))?;
         build_timer.done();
 
-        self.state = HashJoinStreamState::FetchProbeBatch;
+        // `NOT IN` returns no rows once the build side contains a null key
+        let build_batch = left_data.batch();
+        self.state = if self.null_aware
+            && self.on_left[0]
+                .evaluate(build_batch)?
+                .into_array(build_batch.num_rows())?
+                .null_count()
+                > 0
+        {
+            HashJoinStreamState::ExhaustedProbeSide
+        } else {
+            HashJoinStreamState::FetchProbeBatch
+        };
         self.build_side = BuildSide::Ready(BuildSideReadyState { left_data });
 
         Poll::Ready(Ok(StatefulStreamResult::Continue))
@@ -1353,14 +1421,27 @@ impl HashJoinStream {
             None => {
                 self.state = HashJoinStreamState::ExhaustedProbeSide;
             }
-            Some(Ok(batch)) => {
+            Some(Ok(mut batch)) => {
                 // Precalculate hash values for fetched batch
-                let keys_values = self
+                let mut keys_values = self
                     .on_right
                     .iter()
                     .map(|c| c.evaluate(&batch)?.into_array(batch.num_rows()))
                     .collect::<Result<Vec<_>>>()?;
 
+                // `NOT IN` rejects null probe keys unless the build side is empty
+                if self.null_aware
+                    && self.build_side.try_as_ready()?.left_data.batch().num_rows() > 0
+                    && keys_values[0].null_count() > 0
+                {
+                    let mask = is_not_null(keys_values[0].as_ref())?;
+                    batch = filter_record_batch(&batch, &mask)?;
+                    keys_values = keys_values
+                        .iter()
+                        .map(|keys| filter(keys.as_ref(), &mask))
+                        .collect::<Result<Vec<_>, ArrowError>>()?;
+                }
+
                 self.hashes_buffer.clear();
                 self.hashes_buffer.resize(batch.num_rows(), 0);
                 create_hashes(&keys_values, &self.random_state, &mut self.hashes_buffer)?;
@@ -1563,8 +1644,9 @@ mod tests {
         test::build_table_i32, test::exec::MockExec,
     };
 
-    use arrow::array::{Date32Array, Int32Array, UInt32Builder, UInt64Builder};
-    use arrow::datatypes::{DataType, Field};
+    use ahash::RandomState;
+    use arrow::array::{AsArray, Date32Array, Int32Array, UInt32Builder, UInt64Builder};
+    use arrow::datatypes::{DataType, Field, Int32Type};
     use datafusion_common::{
         assert_batches_eq, assert_batches_sorted_eq, assert_contains, exec_err,
         ScalarValue,
@@ -3185,6 +3267,62 @@ mod tests {
         Ok(())
     }
 
+    fn build_nullable_key_table(keys: Vec<Option<i32>>) -> Arc<dyn ExecutionPlan> {
+        let schema = Arc::new(Schema::new(vec![Field::new("k", DataType::Int32, true)]));
+        let batch =
+            RecordBatch::try_new(schema.clone(), vec![Arc::new(Int32Array::from(keys))])
+                .unwrap();
+        Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None).unwrap())
+    }
+
+    #[tokio::test]
+    async fn join_right_anti_null_aware() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let cases = [
+            // an empty build side keeps every row, including null keys
+            (vec![], vec![Some(1), Some(2), None]),
+            // rows with a null key are only kept for an empty build side
+            (vec![Some(2), Some(3)], vec![Some(1)]),
+            // a null build side key rejects every row
+            (vec![Some(3), None], vec![]),
+        ];
+        for (build_keys, expected) in cases {
+            let left = build_nullable_key_table(build_keys);
+            let right = build_nullable_key_table(vec![Some(1), Some(2), None]);
+            let on = vec![(
+                Arc::new(Column::new_with_schema("k", &left.schema())?) as _,
+                Arc::new(Column::new_with_schema("k", &right.schema())?) as _,
+            )];
+            let join = join(left, right, on, &JoinType::RightAnti, false)?
+                .with_null_aware(true)?;
+
+            let stream = join.execute(0, task_ctx.clone())?;
+            let batches = common::collect(stream).await?;
+            let keys = batches
+                .iter()
+                .flat_map(|batch| batch.column(0).as_primitive::<Int32Type>().iter())
+                .collect::<Vec<_>>();
+            assert_eq!(keys, expected);
+        }
+
+        Ok(())
+    }
+
+    #[test]
+    fn null_aware_requires_right_anti() -> Result<()> {
+        let left = build_nullable_key_table(vec![Some(1)]);
+        let right = build_nullable_key_table(vec![Some(1)]);
+        let on = vec![(
+            Arc::new(Column::new_with_schema("k", &left.schema())?) as _,
+            Arc::new(Column::new_with_schema("k", &right.schema())?) as _,
+        )];
+        let err = join(left, right, on, &JoinType::LeftAnti, false)?
+            .with_null_aware(true)
+            .unwrap_err();
+        assert_contains!(err.to_string(), "Null-aware HashJoinExec requires");
+        Ok(())
+    }
+
     fn prepare_join_filter() -> JoinFilter {
         let column_indices = vec![
             ColumnIndex {
@@ -27,6 +27,7 @@ use std::task::Poll;
 
 use crate::coalesce_batches::concat_batches;
 use crate::coalesce_partitions::CoalescePartitionsExec;
+use crate::common::SharedBufferTracker;
 use crate::joins::utils::{
     adjust_indices_by_join_type, adjust_right_output_partitioning,
     apply_join_filter_to_indices, build_batch_from_indices, build_join_schema,
@@ -377,12 +378,18 @@ async fn collect_left_input(
     let stream = merge.execute(0, context)?;
 
     // Load all batches and count the rows
-    let (batches, num_rows, metrics, mut reservation) = stream
+    let (batches, num_rows, metrics, mut reservation, _) = stream
         .try_fold(
-            (Vec::new(), 0usize, join_metrics, reservation),
+            (
+                Vec::new(),
+                0usize,
+                join_metrics,
+                reservation,
+                SharedBufferTracker::default(),
+            ),
             |mut acc, batch| async {
-                let batch_size = batch.get_array_memory_size();
-                // Reserve memory for incoming batch
+                // Reserve memory for the buffers not shared with the batches before
+                let batch_size = acc.4.add(&batch);
                 acc.3.try_grow(batch_size)?;
                 // Update metrics
                 acc.2.build_mem_used.add(batch_size);
@@ -0,0 +1,954 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Defines the range join plan, an inner join whose filter only contains
+//! range predicates such as `l.ts BETWEEN r.start AND r.end`.
+
+use std::any::Any;
+use std::fmt::Formatter;
+use std::sync::Arc;
+use std::task::Poll;
+
+use crate::coalesce_batches::concat_batches;
+use crate::coalesce_partitions::CoalescePartitionsExec;
+use crate::joins::utils::{
+    adjust_right_output_partitioning, apply_join_filter_to_indices,
+    build_batch_from_indices, build_join_schema, check_join_is_valid,
+    estimate_join_statistics, BuildProbeJoinMetrics, ColumnIndex, JoinFilter, OnceAsync,
+    OnceFut,
+};
+use crate::metrics::{ExecutionPlanMetricsSet, MetricsSet};
+use crate::{
+    execution_mode_from_children, DisplayAs, DisplayFormatType, Distribution,
+    ExecutionMode, ExecutionPlan, ExecutionPlanProperties, PlanProperties,
+    RecordBatchStream, SendableRecordBatchStream,
+};
+
+use arrow::array::{Array, ArrayRef, UInt32Array, UInt32Builder, UInt64Builder};
+use arrow::compute::{
+    cast_with_options, sort_to_indices, take, CastOptions, SortOptions,
+};
+use arrow::datatypes::{DataType, SchemaRef};
+use arrow::record_batch::RecordBatch;
+use arrow::row::{Row, RowConverter, Rows, SortField};
+use datafusion_common::tree_node::{Transformed, TransformedResult, TreeNode};
+use datafusion_common::{internal_err, plan_err, JoinSide, Result, Statistics};
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::TaskContext;
+use datafusion_expr::{JoinType, Operator};
+use datafusion_physical_expr::equivalence::join_equivalence_properties;
+use datafusion_physical_expr::expressions::{BinaryExpr, Column};
+use datafusion_physical_expr::utils::{collect_columns, split_conjunction};
+use datafusion_physical_expr::PhysicalExprRef;
+
+use futures::{ready, Stream, StreamExt, TryStreamExt};
+
+/// One bound of a [`RangeCondition`]
+#[derive(Debug, Clone)]
+pub struct RangeBound {
+    /// Bound expression, evaluated against the right input
+    pub expr: PhysicalExprRef,
+    /// Whether rows equal to the bound are inside the range
+    pub inclusive: bool,
+}
+
+/// A range predicate `lower < (<=) build_expr < (<=) upper` extracted from
+/// the filter of a join, where `build_expr` is evaluated against the left
+/// input and the bounds against the right input. At least one of the
+/// bounds is present.
+#[derive(Debug, Clone)]
+pub struct RangeCondition {
+    /// Expression evaluated against the left input
+    pub build_expr: PhysicalExprRef,
+    /// Lower bound of `build_expr`, if any
+    pub lower: Option<RangeBound>,
+    /// Upper bound of `build_expr`, if any
+    pub upper: Option<RangeBound>,
+}
+
+impl RangeCondition {
+    /// Extracts a range condition from the conjuncts of `filter`.
+    ///
+    /// Returns `None` unless the filter restricts an expression of one input
+    /// to a range bounded on both ends by expressions of the other input,
+    /// e.g. `l.ts >= r.start AND l.ts <= r.end` or
+    /// `r.ts >= l.start AND r.ts <= l.end`. In the latter case the left
+    /// input is sorted on the lower bound of the interval (`l.start`), and
+    /// only the upper bound of the sorted expression prunes candidates.
+    ///
+    /// Other conjuncts, including comparisons between expressions of
+    /// different types, are not part of the returned condition; they are
+    /// still evaluated as part of the join filter.
+    pub fn try_from_filter(filter: &JoinFilter) -> Option<Self> {
+        let mut left_ranges: Vec<RangeCondition> = vec![];
+        let mut right_ranges: Vec<RangeCondition> = vec![];
+
+        for conjunct in split_conjunction(filter.expression()) {
+            let Some(binary) = conjunct.as_any().downcast_ref::<BinaryExpr>() else {
+                continue;
+            };
+            if !matches!(
+                binary.op(),
+                Operator::Lt | Operator::LtEq | Operator::Gt | Operator::GtEq
+            ) {
+                continue;
+            }
+            // Bounds are compared with the sorted expression in its own type,
+            // leave comparisons between different types to the nested loop join
+            match (
+                binary.left().data_type(filter.schema()),
+                binary.right().data_type(filter.schema()),
+            ) {
+                (Ok(left_type), Ok(right_type)) if left_type == right_type => {}
+                _ => continue,
+            }
+            let (Some(lhs), Some(rhs)) = (
+                filter_expr_side(binary.left(), filter),
+                filter_expr_side(binary.right(), filter),
+            ) else {
+                continue;
+            };
+            // Normalize to `left_expr <op> right_expr`
+            let (left_expr, op, right_expr) = match (lhs, rhs) {
+                ((JoinSide::Left, l), (JoinSide::Right, r)) => (l, *binary.op(), r),
+                ((JoinSide::Right, r), (JoinSide::Left, l)) => {
+                    (l, binary.op().swap().unwrap(), r)
+                }
+                _ => continue,
+            };
+            let inclusive = matches!(op, Operator::LtEq | Operator::GtEq);
+            let left_is_lower_bounded = matches!(op, Operator::Gt | Operator::GtEq);
+            add_bound(
+                &mut left_ranges,
+                &left_expr,
+                &right_expr,
+                inclusive,
+                left_is_lower_bounded,
+            );
+            add_bound(
+                &mut right_ranges,
+                &right_expr,
+                &left_expr,
+                inclusive,
+                !left_is_lower_bounded,
+            );
+        }
+
+        if !left_ranges
+            .iter()
+            .chain(right_ranges.iter())
+            .any(|range| range.is_bounded())
+        {
+            return None;
+        }
+
+        // Prefer a range on a left expression; otherwise sort the left input on
+        // an expression that has an upper bound, which is the start of an
+        // interval containing a right expression.
+        let position = left_ranges
+            .iter()
+            .position(|range| range.is_bounded())
+            .or_else(|| left_ranges.iter().position(|range| range.upper.is_some()))
+            .unwrap_or(0);
+        Some(left_ranges.swap_remove(position))
+    }
+
+    /// Returns true if the range has both a lower and an upper bound
+    pub fn is_bounded(&self) -> bool {
+        self.lower.is_some() && self.upper.is_some()
+    }
+}
+
+impl std::fmt::Display for RangeCondition {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let op = |bound: &RangeBound| if bound.inclusive { "<=" } else { "<" };
+        if let Some(lower) = &self.lower {
+            write!(f, "{} {} ", lower.expr, op(lower))?;
+        }
+        write!(f, "{}", self.build_expr)?;
+        if let Some(upper) = &self.upper {
+            write!(f, " {} {}", op(upper), upper.expr)?;
+        }
+        Ok(())
+    }
+}
+
+/// Records `bound` as a lower or upper bound of `expr` in `ranges`. Only the
+/// first lower and upper bound of an expression are kept.
+fn add_bound(
+    ranges: &mut Vec<RangeCondition>,
+    expr: &PhysicalExprRef,
+    bound: &PhysicalExprRef,
+    inclusive: bool,
+    is_lower: bool,
+) {
+    let index = match ranges.iter().position(|range| range.build_expr.eq(expr)) {
+        Some(index) => index,
+        None => {
+            ranges.push(RangeCondition {
+                build_expr: expr.clone(),
+                lower: None,
+                upper: None,
+            });
+            ranges.len() - 1
+        }
+    };
+    let range = &mut ranges[index];
+    let slot = if is_lower {
+        &mut range.lower
+    } else {
+        &mut range.upper
+    };
+    slot.get_or_insert_with(|| RangeBound {
+        expr: bound.clone(),
+        inclusive,
+    });
+}
+
+/// If all columns of the filter expression `expr` come from the same join
+/// input, returns that side and `expr` rewritten against the input schema.
+fn filter_expr_side(
+    expr: &PhysicalExprRef,
+    filter: &JoinFilter,
+) -> Option<(JoinSide, PhysicalExprRef)> {
+    let column_indices = filter.column_indices();
+    let mut sides = collect_columns(expr)
+        .into_iter()
+        .map(|column| column_indices[column.index()].side);
+    let side = sides.next()?;
+    if sides.any(|other| other != side) {
+        return None;
+    }
+    let expr = expr
+        .clone()
+        .transform_up(|e| match e.as_any().downcast_ref::<Column>() {
+            Some(column) => Ok(Transformed::yes(Arc::new(Column::new(
+                column.name(),
+                column_indices[column.index()].index,
+            )) as _)),
+            None => Ok(Transformed::no(e)),
+        })
+        .data()
+        .ok()?;
+    Some((side, expr))
+}
+
+/// RangeJoinExec is an inner join whose filter contains a range predicate
+/// (see [`RangeCondition`]), such as `l.ts BETWEEN r.start AND r.end`.
+///
+/// The left input is collected into a single batch and sorted on the range
+/// expression. For every row of the right input, the rows of the left input
+/// that fall within the bounds are found by binary search, and the full join
+/// filter is then evaluated on those candidates only. Compared to
+/// [`NestedLoopJoinExec`](crate::joins::NestedLoopJoinExec), which evaluates
+/// the filter on the full cross product, this only evaluates the filter on
+/// the rows within the range.
+#[derive(Debug)]
+pub struct RangeJoinExec {
+    /// left (build) side
+    left: Arc<dyn ExecutionPlan>,
+    /// right (probe) side
+    right: Arc<dyn ExecutionPlan>,
+    /// Filter applied to the candidate rows
+    filter: JoinFilter,
+    /// Range predicate extracted from `filter`
+    condition: RangeCondition,
+    /// The schema once the join is applied
+    schema: SchemaRef,
+    /// Sorted build-side data
+    build_side: OnceAsync<RangeJoinBuildData>,
+    /// Information of index and left / right placement of columns
+    column_indices: Vec<ColumnIndex>,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl RangeJoinExec {
+    /// Tries to create a new [`RangeJoinExec`].
+    ///
+    /// # Error
+    /// This function errors when no range predicate can be extracted from
+    /// `filter`, see [`RangeCondition::try_from_filter`].
+    pub fn try_new(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        filter: JoinFilter,
+    ) -> Result<Self> {
+        let left_schema = left.schema();
+        let right_schema = right.schema();
+        check_join_is_valid(&left_schema, &right_schema, &[])?;
+        let Some(condition) = RangeCondition::try_from_filter(&filter) else {
+            return plan_err!(
+                "RangeJoinExec requires a range predicate in the join filter, got {}",
+                filter.expression()
+            );
+        };
+        let (schema, column_indices) =
+            build_join_schema(&left_schema, &right_schema, &JoinType::Inner);
+        let schema = Arc::new(schema);
+        let cache = Self::compute_properties(&left, &right, schema.clone());
+
+        Ok(Self {
+            left,
+            right,
+            filter,
+            condition,
+            schema,
+            build_side: Default::default(),
+            column_indices,
+            metrics: Default::default(),
+            cache,
+        })
+    }
+
+    /// left side
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// right side
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
+    /// Filters applied before join output
+    pub fn filter(&self) -> &JoinFilter {
+        &self.filter
+    }
+
+    /// Range predicate used to find candidate rows
+    pub fn condition(&self) -> &RangeCondition {
+        &self.condition
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        left: &Arc<dyn ExecutionPlan>,
+        right: &Arc<dyn ExecutionPlan>,
+        schema: SchemaRef,
+    ) -> PlanProperties {
+        let eq_properties = join_equivalence_properties(
+            left.equivalence_properties().clone(),
+            right.equivalence_properties().clone(),
+            &JoinType::Inner,
+            schema,
+            &[false, false],
+            None,
+            &[],
+        );
+
+        let output_partitioning = adjust_right_output_partitioning(
+            right.output_partitioning(),
+            left.schema().fields().len(),
+        );
+
+        let mut mode = execution_mode_from_children([left, right]);
+        if mode.is_unbounded() {
+            mode = ExecutionMode::PipelineBreaking;
+        }
+
+        PlanProperties::new(eq_properties, output_partitioning, mode)
+    }
+}
+
+impl DisplayAs for RangeJoinExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "RangeJoinExec: range={}, filter={}",
+                    self.condition,
+                    self.filter.expression()
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for RangeJoinExec {
+    fn name(&self) -> &'static str {
+        "RangeJoinExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![
+            Distribution::SinglePartition,
+            Distribution::UnspecifiedDistribution,
+        ]
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.left.clone(), self.right.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        match &children[..] {
+            [left, right] => Ok(Arc::new(RangeJoinExec::try_new(
+                left.clone(),
+                right.clone(),
+                self.filter.clone(),
+            )?)),
+            _ => internal_err!("RangeJoinExec wrong number of children"),
+        }
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let join_metrics = BuildProbeJoinMetrics::new(partition, &self.metrics);
+
+        // Initialization reservation for load of the build side
+        let load_reservation = MemoryConsumer::new(format!("RangeJoinLoad[{partition}]"))
+            .register(context.memory_pool());
+
+        let build_side = self.build_side.once(|| {
+            collect_build_side(
+                self.left.clone(),
+                self.condition.build_expr.clone(),
+                context.clone(),
+                join_metrics.clone(),
+                load_reservation,
+            )
+        });
+        let probe = self.right.execute(partition, context)?;
+
+        Ok(Box::pin(RangeJoinStream {
+            schema: self.schema.clone(),
+            filter: self.filter.clone(),
+            condition: self.condition.clone(),
+            probe,
+            build_side,
+            column_indices: self.column_indices.clone(),
+            join_metrics,
+        }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        estimate_join_statistics(
+            self.left.clone(),
+            self.right.clone(),
+            vec![],
+            &JoinType::Inner,
+            &self.schema,
+        )
+    }
+}
+
+/// Build side of a [`RangeJoinExec`], sorted on the range expression
+struct RangeJoinBuildData {
+    /// Build-side data collected to single batch
+    batch: RecordBatch,
+    /// Indices of the rows of `batch` sorted on the range expression.
+    /// Rows for which the expression is NULL are excluded as they never match.
+    sorted_indices: UInt32Array,
+    /// Values of the range expression, in the order of `sorted_indices`
+    sorted_keys: Rows,
+    /// Converter used for `sorted_keys`, used to convert the bounds
+    converter: RowConverter,
+    /// Type of the range expression
+    key_type: DataType,
+    /// Memory reservation for the build side, freed on drop
+    #[allow(dead_code)]
+    reservation: MemoryReservation,
+}
+
+impl RangeJoinBuildData {
+    /// Evaluates `bound` against `batch` and converts the result into the row
+    /// format of the sorted keys
+    fn convert_bound(
+        &self,
+        bound: Option<&RangeBound>,
+        batch: &RecordBatch,
+    ) -> Result<Option<(ArrayRef, Rows)>> {
+        let Some(bound) = bound else {
+            return Ok(None);
+        };
+        let mut values = bound.expr.evaluate(batch)?.into_array(batch.num_rows())?;
+        if values.data_type() != &self.key_type {
+            // A bound that does not fit the key type must not silently become
+            // NULL, which would drop the rows of the probe side
+            let options = CastOptions {
+                safe: false,
+                ..Default::default()
+            };
+            values = cast_with_options(&values, &self.key_type, &options)?;
+        }
+        let rows = self.converter.convert_columns(&[values.clone()])?;
+        Ok(Some((values, rows)))
+    }
+
+    /// Returns the first position in `sorted_keys` for which `pred` is false,
+    /// assuming `pred` holds for a prefix of the sorted keys
+    fn partition_point(&self, pred: impl Fn(Row) -> bool) -> usize {
+        let (mut low, mut high) = (0, self.sorted_keys.num_rows());
+        while low < high {
+            let mid = low + (high - low) / 2;
+            if pred(self.sorted_keys.row(mid)) {
+                low = mid + 1;
+            } else {
+                high = mid;
+            }
+        }
+        low
+    }
+}
+
+/// Asynchronously collects the build side into a single batch and sorts it
+/// on `build_expr`
+async fn collect_build_side(
+    input: Arc<dyn ExecutionPlan>,
+    build_expr: PhysicalExprRef,
+    context: Arc<TaskContext>,
+    join_metrics: BuildProbeJoinMetrics,
+    reservation: MemoryReservation,
+) -> Result<RangeJoinBuildData> {
+    let schema = input.schema();
+    let merge = if input.output_partitioning().partition_count() != 1 {
+        Arc::new(CoalescePartitionsExec::new(input))
+    } else {
+        input
+    };
+    let stream = merge.execute(0, context)?;
+
+    // Load all batches and count the rows
+    let (batches, num_rows, metrics, mut reservation) = stream
+        .try_fold(
+            (Vec::new(), 0usize, join_metrics, reservation),
+            |mut acc, batch| async {
+                let batch_size = batch.get_array_memory_size();
+                // Reserve memory for incoming batch
+                acc.3.try_grow(batch_size)?;
+                // Update metrics
+                acc.2.build_mem_used.add(batch_size);
+                acc.2.build_input_batches.add(1);
+                acc.2.build_input_rows.add(batch.num_rows());
+                // Update rowcount
+                acc.1 += batch.num_rows();
+                // Push batch to output
+                acc.0.push(batch);
+                Ok(acc)
+            },
+        )
+        .await?;
+
+    let batch = concat_batches(&schema, &batches, num_rows)?;
+
+    // Sort on the range expression; NULLs are sorted last and dropped
+    let keys = build_expr.evaluate(&batch)?.into_array(batch.num_rows())?;
+    let options = SortOptions {
+        descending: false,
+        nulls_first: false,
+    };
+    let sorted_indices = sort_to_indices(&keys, Some(options), None)?;
+    let sorted_indices = sorted_indices.slice(0, keys.len() - keys.null_count());
+    let sorted_values = take(&keys, &sorted_indices, None)?;
+
+    let key_type = keys.data_type().clone();
+    let converter = RowConverter::new(vec![SortField::new(key_type.clone())])?;
+    let sorted_keys = converter.convert_columns(&[sorted_values])?;
+
+    let size = sorted_indices.get_array_memory_size() + sorted_keys.size();
+    reservation.try_grow(size)?;
+    metrics.build_mem_used.add(size);
+
+    Ok(RangeJoinBuildData {
+        batch,
+        sorted_indices,
+        sorted_keys,
+        converter,
+        key_type,
+        reservation,
+    })
+}
+
+/// Finds the rows of the build side matching every row of `probe_batch`
+fn join_probe_batch(
+    build: &RangeJoinBuildData,
+    probe_batch: &RecordBatch,
+    condition: &RangeCondition,
+    filter: &JoinFilter,
+    column_indices: &[ColumnIndex],
+    schema: &SchemaRef,
+) -> Result<RecordBatch> {
+    let lower = build.convert_bound(condition.lower.as_ref(), probe_batch)?;
+    let upper = build.convert_bound(condition.upper.as_ref(), probe_batch)?;
+    let lower_inclusive = condition.lower.as_ref().is_some_and(|b| b.inclusive);
+    let upper_inclusive = condition.upper.as_ref().is_some_and(|b| b.inclusive);
+
+    let mut build_indices = UInt64Builder::new();
+    let mut probe_indices = UInt32Builder::new();
+    for row in 0..probe_batch.num_rows() {
+        let start = match &lower {
+            Some((values, _)) if values.is_null(row) => continue,
+            Some((_, rows)) => {
+                let bound = rows.row(row);
+                if lower_inclusive {
+                    build.partition_point(|key| key < bound)
+                } else {
+                    build.partition_point(|key| key <= bound)
+                }
+            }
+            None => 0,
+        };
+        let end = match &upper {
+            Some((values, _)) if values.is_null(row) => continue,
+            Some((_, rows)) => {
+                let bound = rows.row(row);
+                if upper_inclusive {
+                    build.partition_point(|key| key <= bound)
+                } else {
+                    build.partition_point(|key| key < bound)
+                }
+            }
+            None => build.sorted_keys.num_rows(),
+        };
+        for position in start..end {
+            build_indices.append_value(build.sorted_indices.value(position) as u64);
+            probe_indices.append_value(row as u32);
+        }
+    }
+
+    let (build_indices, probe_indices) = apply_join_filter_to_indices(
+        &build.batch,
+        probe_batch,
+        build_indices.finish(),
+        probe_indices.finish(),
+        filter,
+        JoinSide::Left,
+    )?;
+    build_batch_from_indices(
+        schema,
+        &build.batch,
+        probe_batch,
+        &build_indices,
+        &probe_indices,
+        column_indices,
+        JoinSide::Left,
+    )
+}
+
+/// A stream that joins the [RecordBatch]es of the right input, as they
+/// arrive, with the sorted left input
+struct RangeJoinStream {
+    /// Output schema
+    schema: SchemaRef,
+    /// join filter
+    filter: JoinFilter,
+    /// Range predicate of the join filter
+    condition: RangeCondition,
+    /// right (probe) input
+    probe: SendableRecordBatchStream,
+    /// sorted left (build) input
+    build_side: OnceFut<RangeJoinBuildData>,
+    /// Information of index and left / right placement of columns
+    column_indices: Vec<ColumnIndex>,
+    /// Join execution metrics
+    join_metrics: BuildProbeJoinMetrics,
+}
+
+impl RangeJoinStream {
+    fn poll_next_impl(
+        &mut self,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Result<RecordBatch>>> {
+        let build_timer = self.join_metrics.build_time.timer();
+        let build = match ready!(self.build_side.get_shared(cx)) {
+            Ok(build) => build,
+            Err(e) => return Poll::Ready(Some(Err(e))),
+        };
+        build_timer.done();
+
+        self.probe
+            .poll_next_unpin(cx)
+            .map(|maybe_batch| match maybe_batch {
+                Some(Ok(probe_batch)) => {
+                    self.join_metrics.input_batches.add(1);
+                    self.join_metrics.input_rows.add(probe_batch.num_rows());
+                    let timer = self.join_metrics.join_time.timer();
+
+                    let result = join_probe_batch(
+                        &build,
+                        &probe_batch,
+                        &self.condition,
+                        &self.filter,
+                        &self.column_indices,
+                        &self.schema,
+                    );
+
+                    if let Ok(batch) = &result {
+                        timer.done();
+                        self.join_metrics.output_batches.add(1);
+                        self.join_metrics.output_rows.add(batch.num_rows());
+                    }
+                    Some(result)
+                }
+                other => other,
+            })
+    }
+}
+
+impl Stream for RangeJoinStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: std::pin::Pin<&mut Self>,
+        cx: &mut std::task::Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        self.poll_next_impl(cx)
+    }
+}
+
+impl RecordBatchStream for RangeJoinStream {
+    fn schema(&self) -> SchemaRef {
+        self.schema.clone()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common;
+    use crate::expressions::{binary, col};
+    use crate::memory::MemoryExec;
+    use crate::test::build_table_i32;
+
+    use arrow::datatypes::{Field, Schema};
+    use datafusion_common::assert_batches_sorted_eq;
+
+    fn build_table(
+        a: (&str, &Vec<i32>),
+        b: (&str, &Vec<i32>),
+        c: (&str, &Vec<i32>),
+    ) -> Arc<dyn ExecutionPlan> {
+        let batch = build_table_i32(a, b, c);
+        let schema = batch.schema();
+        Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None).unwrap())
+    }
+
+    /// Builds the filter `<lhs> <lower_op> <rhs_lower> AND <lhs> <upper_op> <rhs_upper>`
+    /// over the intermediate schema `(l_ts, r_start, r_end)` or, with
+    /// `interval_on_left`, `(l_start, l_end, r_ts)`
+    fn range_filter(
+        interval_on_left: bool,
+        lower: Operator,
+        upper: Operator,
+    ) -> JoinFilter {
+        let (fields, column_indices) = if interval_on_left {
+            (
+                vec!["l_start", "l_end", "r_ts"],
+                JoinFilter::build_column_indices(vec![0, 1], vec![0]),
+            )
+        } else {
+            (
+                vec!["l_ts", "r_start", "r_end"],
+                JoinFilter::build_column_indices(vec![0], vec![0, 1]),
+            )
+        };
+        let schema = Schema::new(
+            fields
+                .iter()
+                .map(|name| Field::new(*name, DataType::Int32, true))
+                .collect::<Vec<_>>(),
+        );
+        let column = |name| col(name, &schema).unwrap();
+        let (point, start, end) = if interval_on_left {
+            (column("r_ts"), column("l_start"), column("l_end"))
+        } else {
+            (column("l_ts"), column("r_start"), column("r_end"))
+        };
+        let expression = binary(
+            binary(point.clone(), lower, start, &schema).unwrap(),
+            Operator::And,
+            binary(point, upper, end, &schema).unwrap(),
+            &schema,
+        )
+        .unwrap();
+        JoinFilter::new(expression, column_indices, schema)
+    }
+
+    async fn join_collect(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        filter: JoinFilter,
+    ) -> Result<Vec<RecordBatch>> {
+        let join = RangeJoinExec::try_new(left, right, filter)?;
+        let stream = join.execute(0, Arc::new(TaskContext::default()))?;
+        common::collect(stream).await
+    }
+
+    #[test]
+    fn extract_range_condition() {
+        let filter = range_filter(false, Operator::GtEq, Operator::Lt);
+        let condition = RangeCondition::try_from_filter(&filter).unwrap();
+        assert!(condition.is_bounded());
+        assert_eq!(condition.to_string(), "r_start@0 <= l_ts@0 < r_end@1");
+
+        let filter = range_filter(true, Operator::GtEq, Operator::LtEq);
+        let condition = RangeCondition::try_from_filter(&filter).unwrap();
+        assert!(!condition.is_bounded());
+        assert_eq!(condition.to_string(), "l_start@0 <= r_ts@0");
+
+        // A single inequality is not a range
+        let schema = Schema::new(vec![
+            Field::new("l_ts", DataType::Int32, true),
+            Field::new("r_start", DataType::Int32, true),
+        ]);
+        let expression = binary(
+            col("l_ts", &schema).unwrap(),
+            Operator::Gt,
+            col("r_start", &schema).unwrap(),
+            &schema,
+        )
+        .unwrap();
+        let filter = JoinFilter::new(
+            expression,
+            JoinFilter::build_column_indices(vec![0], vec![0]),
+            schema,
+        );
+        assert!(RangeCondition::try_from_filter(&filter).is_none());
+
+        // Bounds of another type than the sorted expression are not a range
+        let schema = Schema::new(vec![
+            Field::new("l_ts", DataType::Int32, true),
+            Field::new("r_start", DataType::Int64, true),
+            Field::new("r_end", DataType::Int64, true),
+        ]);
+        let column = |name| col(name, &schema).unwrap();
+        let expression = Arc::new(BinaryExpr::new(
+            Arc::new(BinaryExpr::new(
+                column("l_ts"),
+                Operator::GtEq,
+                column("r_start"),
+            )),
+            Operator::And,
+            Arc::new(BinaryExpr::new(
+                column("l_ts"),
+                Operator::Lt,
+                column("r_end"),
+            )),
+        ));
+        let filter = JoinFilter::new(
+            expression,
+            JoinFilter::build_column_indices(vec![0], vec![0, 1]),
+            schema,
+        );
+        assert!(RangeCondition::try_from_filter(&filter).is_none());
+    }
+
+    #[tokio::test]
+    async fn range_join_point_on_left() -> Result<()> {
+        let left = build_table(
+            ("l_ts", &vec![1, 5, 10, 7, 3]),
+            ("l_id", &vec![1, 2, 3, 4, 5]),
+            ("l_v", &vec![10, 50, 100, 70, 30]),
+        );
+        let right = build_table(
+            ("r_start", &vec![0, 4, 20]),
+            ("r_end", &vec![3, 7, 30]),
+            ("r_id", &vec![1, 2, 3]),
+        );
+        let filter = range_filter(false, Operator::GtEq, Operator::Lt);
+        let batches = join_collect(left, right, filter).await?;
+
+        let expected = [
+            "+------+------+-----+---------+-------+------+",
+            "| l_ts | l_id | l_v | r_start | r_end | r_id |",
+            "+------+------+-----+---------+-------+------+",
+            "| 1    | 1    | 10  | 0       | 3     | 1    |",
+            "| 5    | 2    | 50  | 4       | 7     | 2    |",
+            "+------+------+-----+---------+-------+------+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn range_join_interval_on_left() -> Result<()> {
+        let left = build_table(
+            ("l_start", &vec![0, 4, 20, 2]),
+            ("l_end", &vec![3, 7, 30, 5]),
+            ("l_id", &vec![1, 2, 3, 4]),
+        );
+        let right = build_table(
+            ("r_ts", &vec![1, 5, 10, 3]),
+            ("r_id", &vec![1, 2, 3, 4]),
+            ("r_v", &vec![10, 50, 100, 30]),
+        );
+        let filter = range_filter(true, Operator::GtEq, Operator::LtEq);
+        let batches = join_collect(left, right, filter).await?;
+
+        let expected = [
+            "+---------+-------+------+------+------+-----+",
+            "| l_start | l_end | l_id | r_ts | r_id | r_v |",
+            "+---------+-------+------+------+------+-----+",
+            "| 0       | 3     | 1    | 1    | 1    | 10  |",
+            "| 0       | 3     | 1    | 3    | 4    | 30  |",
+            "| 2       | 5     | 4    | 3    | 4    | 30  |",
+            "| 2       | 5     | 4    | 5    | 2    | 50  |",
+            "| 4       | 7     | 2    | 5    | 2    | 50  |",
+            "+---------+-------+------+------+------+-----+",
+        ];
+        assert_batches_sorted_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[test]
+    fn range_join_requires_range() {
+        let left = build_table(("a", &vec![1]), ("b", &vec![1]), ("c", &vec![1]));
+        let right = build_table(("x", &vec![1]), ("y", &vec![1]), ("z", &vec![1]));
+        let schema = Schema::new(vec![
+            Field::new("a", DataType::Int32, true),
+            Field::new("x", DataType::Int32, true),
+        ]);
+        let expression = binary(
+            col("a", &schema).unwrap(),
+            Operator::Eq,
+            col("x", &schema).unwrap(),
+            &schema,
+        )
+        .unwrap();
+        let filter = JoinFilter::new(
+            expression,
+            JoinFilter::build_column_indices(vec![0], vec![0]),
+            schema,
+        );
+        let err = RangeJoinExec::try_new(left, right, filter).unwrap_err();
+        assert!(err
+            .to_string()
+            .contains("RangeJoinExec requires a range predicate"));
+    }
+}
@@ -1767,6 +1767,7 @@ mod tests {
             min_value: min.map(ScalarValue::from),
             max_value: max.map(ScalarValue::from),
             null_count,
+            distinct_sketch: None,
         }
     }
 
@@ -46,6 +46,7 @@ mod visitor;
 
 pub mod aggregates;
 pub mod analyze;
+pub mod async_func;
 pub mod coalesce_batches;
 pub mod coalesce_partitions;
 pub mod common;
@@ -53,7 +54,9 @@ pub mod display;
 pub mod empty;
 pub mod explain;
 pub mod filter;
+pub mod gap_fill;
 pub mod insert;
+pub mod instrument;
 pub mod joins;
 pub mod limit;
 pub mod memory;
@@ -62,9 +65,14 @@ pub mod placeholder_row;
 pub mod projection;
 pub mod recursive_query;
 pub mod repartition;
+pub mod sample;
+pub mod scalar_subquery;
+pub mod scheduler;
+pub mod set_operation;
 pub mod sorts;
 pub mod stream;
 pub mod streaming;
+pub mod transaction;
 pub mod tree_node;
 pub mod union;
 pub mod unnest;
@@ -726,7 +734,14 @@ pub async fn collect_partitioned(
     context: Arc<TaskContext>,
 ) -> Result<Vec<Vec<RecordBatch>>> {
     let streams = execute_stream_partitioned(plan, context)?;
+    collect_streams(streams).await
+}
 
+/// Drive each of `streams` to completion on its own task and collect the
+/// results in memory, keeping them in the same order as `streams`
+pub(crate) async fn collect_streams(
+    streams: Vec<SendableRecordBatchStream>,
+) -> Result<Vec<Vec<RecordBatch>>> {
     let mut join_set = JoinSet::new();
     // Execute the plan and collect the results into batches.
     streams.into_iter().enumerate().for_each(|(idx, stream)| {
@@ -30,7 +30,7 @@ use super::{
 
 use arrow::datatypes::SchemaRef;
 use arrow::record_batch::RecordBatch;
-use datafusion_common::{internal_err, project_schema, Result};
+use datafusion_common::{internal_err, project_schema, ColumnStatistics, Result};
 use datafusion_execution::memory_pool::MemoryReservation;
 use datafusion_execution::TaskContext;
 use datafusion_physical_expr::{EquivalenceProperties, LexOrdering};
@@ -52,6 +52,9 @@ pub struct MemoryExec {
     cache: PlanProperties,
     /// if partition sizes should be displayed
     show_sizes: bool,
+    /// Estimated statistics of the columns of the data before projection,
+    /// for the statistics not computed from the batches
+    column_statistics: Option<Vec<ColumnStatistics>>,
 }
 
 impl fmt::Debug for MemoryExec {
@@ -147,11 +150,26 @@ impl ExecutionPlan for MemoryExec {
 
     /// We recompute the statistics dynamically from the arrow metadata as it is pretty cheap to do so
     fn statistics(&self) -> Result<Statistics> {
-        Ok(common::compute_record_batch_statistics(
+        let mut statistics = common::compute_record_batch_statistics(
             &self.partitions,
             &self.schema,
             self.projection.clone(),
-        ))
+        );
+        if let Some(column_statistics) = &self.column_statistics {
+            let projection = match &self.projection {
+                Some(projection) => projection.clone(),
+                None => (0..self.schema.fields().len()).collect(),
+            };
+            for (statistics, index) in
+                statistics.column_statistics.iter_mut().zip(projection)
+            {
+                let estimates = &column_statistics[index];
+                statistics.min_value = estimates.min_value.clone().to_inexact();
+                statistics.max_value = estimates.max_value.clone().to_inexact();
+                statistics.distinct_count = estimates.distinct_count.clone().to_inexact();
+            }
+        }
+        Ok(statistics)
     }
 }
 
@@ -173,6 +191,7 @@ impl MemoryExec {
             sort_information: vec![],
             cache,
             show_sizes: true,
+            column_statistics: None,
         })
     }
 
@@ -182,6 +201,24 @@ impl MemoryExec {
         self
     }
 
+    /// Set the estimated statistics of the columns of the data before
+    /// projection, whose min and max values and distinct counts are returned
+    /// as inexact statistics, as they are not computed from the batches
+    pub fn with_column_statistics(
+        mut self,
+        column_statistics: Vec<ColumnStatistics>,
+    ) -> Result<Self> {
+        if column_statistics.len() != self.schema.fields().len() {
+            return internal_err!(
+                "Expected statistics for {} columns, got {}",
+                self.schema.fields().len(),
+                column_statistics.len()
+            );
+        }
+        self.column_statistics = Some(column_statistics);
+        Ok(self)
+    }
+
     pub fn partitions(&self) -> &[Vec<RecordBatch>] {
         &self.partitions
     }
@@ -30,7 +30,7 @@ use super::{
     DisplayAs, ExecutionPlanProperties, RecordBatchStream, SendableRecordBatchStream,
 };
 use crate::common::transpose;
-use crate::hash_utils::create_hashes;
+use crate::hash_utils::{create_hashes, SeededHashState};
 use crate::metrics::BaselineMetrics;
 use crate::repartition::distributor_channels::{
     channels, partition_aware_channels, DistributionReceiver, DistributionSender,
@@ -181,7 +181,7 @@ pub struct BatchPartitioner {
 
 enum BatchPartitionerState {
     Hash {
-        random_state: ahash::RandomState,
+        random_state: SeededHashState,
         exprs: Vec<Arc<dyn PhysicalExpr>>,
         num_partitions: usize,
         hash_buffer: Vec<u64>,
@@ -208,7 +208,7 @@ impl BatchPartitioner {
                 exprs,
                 num_partitions,
                 // Use fixed random hash
-                random_state: ahash::RandomState::with_seeds(0, 0, 0, 0),
+                random_state: SeededHashState::default(),
                 hash_buffer: vec![],
             },
             other => return not_impl_err!("Unsupported repartitioning scheme {other:?}"),
@@ -217,6 +217,15 @@ impl BatchPartitioner {
         Ok(Self { state, timer })
     }
 
+    /// Hash the rows with `hash_state` instead of ahash with a zero seed, e.g.
+    /// for the rows to be partitioned the same way by different processes
+    pub fn with_hash_state(mut self, hash_state: SeededHashState) -> Self {
+        if let BatchPartitionerState::Hash { random_state, .. } = &mut self.state {
+            *random_state = hash_state;
+        }
+        self
+    }
+
     /// Partition the provided [`RecordBatch`] into one or more partitioned [`RecordBatch`]
     /// based on the [`Partitioning`] specified on construction
     ///
@@ -763,8 +772,11 @@ impl RepartitionExec {
         metrics: RepartitionMetrics,
         context: Arc<TaskContext>,
     ) -> Result<()> {
+        let options = &context.session_config().options().execution;
+        let hash_state = SeededHashState::new(options.hash_function, options.hash_seed);
         let mut partitioner =
-            BatchPartitioner::try_new(partitioning, metrics.repartition_time.clone())?;
+            BatchPartitioner::try_new(partitioning, metrics.repartition_time.clone())?
+                .with_hash_state(hash_state);
 
         // execute the child operator
         let timer = metrics.fetch_time.timer();
@@ -0,0 +1,359 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! SampleExec keeps a random sample of the rows of its input, as requested by
+//! `TABLESAMPLE`
+
+use std::any::Any;
+use std::pin::Pin;
+use std::sync::Arc;
+use std::task::{Context, Poll};
+
+use super::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
+use super::{DisplayAs, ExecutionPlanProperties, PlanProperties, Statistics};
+use crate::{
+    DisplayFormatType, ExecutionPlan, RecordBatchStream, SendableRecordBatchStream,
+};
+
+use arrow::array::BooleanArray;
+use arrow::compute::filter_record_batch;
+use arrow::datatypes::SchemaRef;
+use arrow::record_batch::RecordBatch;
+use datafusion_common::Result;
+use datafusion_execution::TaskContext;
+use datafusion_expr::TableSampleMethod;
+
+use futures::stream::{Stream, StreamExt};
+use rand::rngs::StdRng;
+use rand::{Rng, SeedableRng};
+
+/// SampleExec keeps a random sample of the rows of its input.
+///
+/// With [`TableSampleMethod::Bernoulli`] each row is kept independently with
+/// probability `fraction`, with [`TableSampleMethod::System`] each batch is.
+/// Each partition draws from its own random number generator seeded from
+/// `seed`, so that sampling the same input with the same seed returns the
+/// same rows.
+#[derive(Debug)]
+pub struct SampleExec {
+    /// The input plan
+    input: Arc<dyn ExecutionPlan>,
+    /// How rows are picked
+    method: TableSampleMethod,
+    /// The probability of keeping a row (or batch)
+    fraction: f64,
+    /// The seed of the random number generators
+    seed: u64,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    cache: PlanProperties,
+}
+
+impl SampleExec {
+    /// Create a new SampleExec
+    pub fn new(
+        input: Arc<dyn ExecutionPlan>,
+        method: TableSampleMethod,
+        fraction: f64,
+        seed: u64,
+    ) -> Self {
+        let cache = Self::compute_properties(&input);
+        Self {
+            input,
+            method,
+            fraction,
+            seed,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        }
+    }
+
+    /// The input plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// How rows are picked
+    pub fn method(&self) -> TableSampleMethod {
+        self.method
+    }
+
+    /// The probability of keeping a row (or batch)
+    pub fn fraction(&self) -> f64 {
+        self.fraction
+    }
+
+    /// The seed of the random number generators
+    pub fn seed(&self) -> u64 {
+        self.seed
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(input: &Arc<dyn ExecutionPlan>) -> PlanProperties {
+        // Sampling drops rows but does not reorder or repartition them
+        PlanProperties::new(
+            input.equivalence_properties().clone(), // Equivalence Properties
+            input.output_partitioning().clone(),    // Output Partitioning
+            input.execution_mode(),                 // Execution Mode
+        )
+    }
+}
+
+impl DisplayAs for SampleExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "SampleExec: method={}, fraction={}, seed={}",
+                    self.method, self.fraction, self.seed
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for SampleExec {
+    fn name(&self) -> &'static str {
+        "SampleExec"
+    }
+
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![true]
+    }
+
+    fn benefits_from_input_partitioning(&self) -> Vec<bool> {
+        // Each partition is sampled with its own seed, so repartitioning the
+        // input would change the sampled rows
+        vec![false]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(SampleExec::new(
+            children[0].clone(),
+            self.method,
+            self.fraction,
+            self.seed,
+        )))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        Ok(Box::pin(SampleStream {
+            input: self.input.execute(partition, context)?,
+            method: self.method,
+            fraction: self.fraction,
+            rng: StdRng::seed_from_u64(self.seed.wrapping_add(partition as u64)),
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+        }))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        let mut stats = self.input.statistics()?.into_inexact();
+        stats.num_rows = stats.num_rows.with_estimated_selectivity(self.fraction);
+        stats.total_byte_size = stats
+            .total_byte_size
+            .with_estimated_selectivity(self.fraction);
+        Ok(stats)
+    }
+}
+
+struct SampleStream {
+    /// The input plan
+    input: SendableRecordBatchStream,
+    /// How rows are picked
+    method: TableSampleMethod,
+    /// The probability of keeping a row (or batch)
+    fraction: f64,
+    /// The random number generator of this partition
+    rng: StdRng,
+    /// Execution metrics
+    baseline_metrics: BaselineMetrics,
+}
+
+impl SampleStream {
+    /// Returns the sampled rows of `batch`, or `None` if none are kept
+    fn sample(&mut self, batch: RecordBatch) -> Result<Option<RecordBatch>> {
+        let fraction = self.fraction;
+        match self.method {
+            TableSampleMethod::System => {
+                Ok((self.rng.gen::<f64>() < fraction).then_some(batch))
+            }
+            TableSampleMethod::Bernoulli => {
+                let mask = (0..batch.num_rows())
+                    .map(|_| Some(self.rng.gen::<f64>() < fraction))
+                    .collect::<BooleanArray>();
+                let batch = filter_record_batch(&batch, &mask)?;
+                Ok((batch.num_rows() > 0).then_some(batch))
+            }
+        }
+    }
+}
+
+impl Stream for SampleStream {
+    type Item = Result<RecordBatch>;
+
+    fn poll_next(
+        mut self: Pin<&mut Self>,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Self::Item>> {
+        // Get a clone (uses same underlying atomic) as self gets borrowed below
+        let elapsed_compute = self.baseline_metrics.elapsed_compute().clone();
+        let poll = loop {
+            match self.input.poll_next_unpin(cx) {
+                Poll::Ready(Some(Ok(batch))) => {
+                    let timer = elapsed_compute.timer();
+                    let sampled = self.sample(batch);
+                    timer.done();
+                    match sampled {
+                        Ok(Some(batch)) => break Poll::Ready(Some(Ok(batch))),
+                        Ok(None) => continue,
+                        Err(e) => break Poll::Ready(Some(Err(e))),
+                    }
+                }
+                other => break other,
+            }
+        };
+        self.baseline_metrics.record_poll(poll)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        // sampling may drop any number of batches
+        (0, self.input.size_hint().1)
+    }
+}
+
+impl RecordBatchStream for SampleStream {
+    fn schema(&self) -> SchemaRef {
+        self.input.schema()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{collect, memory::MemoryExec};
+
+    use arrow::datatypes::{DataType, Field, Schema};
+    use arrow_array::UInt32Array;
+
+    fn test_input(num_batches: usize) -> Result<Arc<dyn ExecutionPlan>> {
+        let schema =
+            Arc::new(Schema::new(vec![Field::new("c0", DataType::UInt32, false)]));
+        let batches = (0..num_batches)
+            .map(|i| {
+                let values = (0..100).map(|v| (i * 100 + v) as u32);
+                RecordBatch::try_new(
+                    schema.clone(),
+                    vec![Arc::new(UInt32Array::from_iter_values(values))],
+                )
+            })
+            .collect::<std::result::Result<Vec<_>, _>>()?;
+        Ok(Arc::new(MemoryExec::try_new(&[batches], schema, None)?))
+    }
+
+    async fn sampled_values(
+        method: TableSampleMethod,
+        fraction: f64,
+        seed: u64,
+    ) -> Result<Vec<u32>> {
+        let sample = Arc::new(SampleExec::new(test_input(10)?, method, fraction, seed));
+        let batches = collect(sample, Arc::new(TaskContext::default())).await?;
+        Ok(batches
+            .iter()
+            .flat_map(|batch| {
+                batch
+                    .column(0)
+                    .as_any()
+                    .downcast_ref::<UInt32Array>()
+                    .unwrap()
+                    .values()
+                    .to_vec()
+            })
+            .collect())
+    }
+
+    #[tokio::test]
+    async fn test_bernoulli_sample() -> Result<()> {
+        let sampled = sampled_values(TableSampleMethod::Bernoulli, 0.2, 7).await?;
+        // 1000 rows, each kept with probability 0.2
+        assert!(
+            sampled.len() > 100 && sampled.len() < 300,
+            "{}",
+            sampled.len()
+        );
+        // the same seed returns the same sample
+        assert_eq!(
+            sampled,
+            sampled_values(TableSampleMethod::Bernoulli, 0.2, 7).await?
+        );
+        assert_ne!(
+            sampled,
+            sampled_values(TableSampleMethod::Bernoulli, 0.2, 8).await?
+        );
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_system_sample() -> Result<()> {
+        let sampled = sampled_values(TableSampleMethod::System, 0.5, 7).await?;
+        // whole batches are kept or dropped
+        assert_eq!(sampled.len() % 100, 0);
+        assert_eq!(
+            sampled,
+            sampled_values(TableSampleMethod::System, 0.5, 7).await?
+        );
+
+        assert!(sampled_values(TableSampleMethod::System, 0.0, 7)
+            .await?
+            .is_empty());
+        assert_eq!(
+            sampled_values(TableSampleMethod::System, 1.0, 7)
+                .await?
+                .len(),
+            1000
+        );
+        Ok(())
+    }
+}
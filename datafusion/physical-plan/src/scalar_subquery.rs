@@ -0,0 +1,348 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+//! Defines the execution plan evaluating uncorrelated scalar subqueries once,
+//! before executing its input
+
+use std::any::Any;
+use std::fmt;
+use std::sync::Arc;
+
+use super::joins::utils::OnceAsync;
+use super::stream::RecordBatchStreamAdapter;
+use super::{
+    collect, DisplayAs, DisplayFormatType, ExecutionPlan, PlanProperties,
+    SendableRecordBatchStream, Statistics,
+};
+
+use arrow::record_batch::RecordBatch;
+use arrow_schema::SchemaRef;
+use datafusion_common::{exec_err, internal_err, Result, ScalarValue};
+use datafusion_execution::TaskContext;
+use datafusion_expr::execution_props::ScalarSubqueryResult;
+
+use futures::future::{poll_fn, try_join_all};
+use futures::stream::{self, TryStreamExt};
+
+/// An uncorrelated scalar subquery evaluated by a [`ScalarSubqueryExec`]
+#[derive(Debug, Clone)]
+pub struct ScalarSubquery {
+    /// The plan computing the subquery
+    plan: Arc<dyn ExecutionPlan>,
+    /// Where the value of the subquery is stored
+    result: ScalarSubqueryResult,
+}
+
+impl ScalarSubquery {
+    /// Create a new [`ScalarSubquery`] storing the value computed by `plan`
+    /// in `result`
+    pub fn new(plan: Arc<dyn ExecutionPlan>, result: ScalarSubqueryResult) -> Self {
+        Self { plan, result }
+    }
+
+    /// The plan computing the subquery
+    pub fn plan(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.plan
+    }
+
+    /// Where the value of the subquery is stored
+    pub fn result(&self) -> &ScalarSubqueryResult {
+        &self.result
+    }
+
+    /// Executes the subquery and stores its value
+    async fn evaluate(self, context: Arc<TaskContext>) -> Result<()> {
+        let batches = collect(self.plan.clone(), context).await?;
+        let value = scalar_value(&self.plan.schema(), &batches)?;
+        self.result.set_value(value)
+    }
+}
+
+/// Returns the value of a scalar subquery returning `batches`: the value of
+/// its single row, or NULL if it returns no row
+fn scalar_value(schema: &SchemaRef, batches: &[RecordBatch]) -> Result<ScalarValue> {
+    if schema.fields().len() != 1 {
+        return internal_err!(
+            "Scalar subquery should return a single column, got {}",
+            schema.fields().len()
+        );
+    }
+    let num_rows = batches.iter().map(|batch| batch.num_rows()).sum::<usize>();
+    match num_rows {
+        0 => ScalarValue::try_from(schema.field(0).data_type()),
+        1 => {
+            let batch = batches.iter().find(|batch| batch.num_rows() == 1).unwrap();
+            ScalarValue::try_from_array(batch.column(0), 0)
+        }
+        _ => exec_err!("Scalar subquery returned more than one row"),
+    }
+}
+
+/// Execution plan evaluating uncorrelated scalar subqueries once, before
+/// executing its input
+///
+/// Each subquery is executed a single time for all the partitions of the
+/// input, and its value is stored in a [`ScalarSubqueryResult`] shared with
+/// the [`ScalarSubqueryExpr`]s of the input referencing it. The subquery
+/// plans are children of this plan, after its input.
+///
+/// [`ScalarSubqueryExpr`]: datafusion_physical_expr::expressions::ScalarSubqueryExpr
+#[derive(Debug)]
+pub struct ScalarSubqueryExec {
+    /// The input plan, referencing the subqueries
+    input: Arc<dyn ExecutionPlan>,
+    /// The subqueries to evaluate
+    subqueries: Vec<ScalarSubquery>,
+    /// Evaluates the subqueries once for all partitions
+    evaluated: OnceAsync<()>,
+    /// Cache holding plan properties like equivalences, output partitioning etc.
+    cache: PlanProperties,
+}
+
+impl ScalarSubqueryExec {
+    /// Create a new [`ScalarSubqueryExec`] evaluating `subqueries` before
+    /// executing `input`
+    pub fn new(input: Arc<dyn ExecutionPlan>, subqueries: Vec<ScalarSubquery>) -> Self {
+        let cache = input.properties().clone();
+        Self {
+            input,
+            subqueries,
+            evaluated: OnceAsync::default(),
+            cache,
+        }
+    }
+
+    /// The input plan
+    pub fn input(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.input
+    }
+
+    /// The subqueries to evaluate
+    pub fn subqueries(&self) -> &[ScalarSubquery] {
+        &self.subqueries
+    }
+}
+
+impl DisplayAs for ScalarSubqueryExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut fmt::Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "ScalarSubqueryExec: subqueries={}",
+                    self.subqueries.len()
+                )
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for ScalarSubqueryExec {
+    fn name(&self) -> &'static str {
+        "ScalarSubqueryExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        std::iter::once(self.input.clone())
+            .chain(self.subqueries.iter().map(|subquery| subquery.plan.clone()))
+            .collect()
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        let mut maintains = vec![false; self.subqueries.len() + 1];
+        maintains[0] = true;
+        maintains
+    }
+
+    fn benefits_from_input_partitioning(&self) -> Vec<bool> {
+        vec![false; self.subqueries.len() + 1]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        if children.len() != self.subqueries.len() + 1 {
+            return internal_err!(
+                "ScalarSubqueryExec expects {} children, got {}",
+                self.subqueries.len() + 1,
+                children.len()
+            );
+        }
+        let mut children = children.into_iter();
+        let input = children.next().unwrap();
+        let subqueries = self
+            .subqueries
+            .iter()
+            .zip(children)
+            .map(|(subquery, plan)| ScalarSubquery::new(plan, subquery.result.clone()))
+            .collect();
+        Ok(Arc::new(Self::new(input, subqueries)))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let subqueries = self.subqueries.clone();
+        let subqueries_context = context.clone();
+        let mut evaluated = self.evaluated.once(|| async move {
+            try_join_all(
+                subqueries
+                    .into_iter()
+                    .map(|subquery| subquery.evaluate(subqueries_context.clone())),
+            )
+            .await?;
+            Ok(())
+        });
+
+        // The input is only executed once all the subqueries have a value
+        let input = self.input.clone();
+        let stream = stream::once(async move {
+            poll_fn(|cx| evaluated.get_shared(cx)).await?;
+            input.execute(partition, context)
+        })
+        .try_flatten();
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            self.schema(),
+            stream,
+        )))
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        self.input.statistics()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common;
+    use crate::memory::MemoryExec;
+    use crate::projection::ProjectionExec;
+    use crate::ExecutionPlanProperties;
+
+    use arrow::array::Int64Array;
+    use arrow::datatypes::{DataType, Field, Schema};
+    use datafusion_common::assert_batches_eq;
+    use datafusion_physical_expr::expressions::{col, ScalarSubqueryExpr};
+
+    fn int64_exec(name: &str, partitions: Vec<Vec<i64>>) -> Arc<dyn ExecutionPlan> {
+        let schema = Arc::new(Schema::new(vec![Field::new(name, DataType::Int64, true)]));
+        let partitions = partitions
+            .into_iter()
+            .map(|values| {
+                vec![RecordBatch::try_new(
+                    schema.clone(),
+                    vec![Arc::new(Int64Array::from(values))],
+                )
+                .unwrap()]
+            })
+            .collect::<Vec<_>>();
+        Arc::new(MemoryExec::try_new(&partitions, schema, None).unwrap())
+    }
+
+    /// `SELECT a, (<subquery>) FROM t`, with the subquery returning `values`
+    fn subquery_plan(values: Vec<i64>) -> Result<Arc<dyn ExecutionPlan>> {
+        let input = int64_exec("a", vec![vec![1, 2], vec![3]]);
+        let result = ScalarSubqueryResult::new();
+        let expr = Arc::new(ScalarSubqueryExpr::new(
+            "(<subquery>)",
+            DataType::Int64,
+            true,
+            result.clone(),
+        ));
+        let projection = Arc::new(ProjectionExec::try_new(
+            vec![
+                (col("a", &input.schema())?, "a".to_string()),
+                (expr, "sq".to_string()),
+            ],
+            input,
+        )?);
+        let subquery = ScalarSubquery::new(int64_exec("b", vec![values]), result);
+        Ok(Arc::new(ScalarSubqueryExec::new(
+            projection,
+            vec![subquery],
+        )))
+    }
+
+    async fn collect_partitions(
+        plan: Arc<dyn ExecutionPlan>,
+    ) -> Result<Vec<RecordBatch>> {
+        let context = Arc::new(TaskContext::default());
+        let mut batches = vec![];
+        for partition in 0..plan.output_partitioning().partition_count() {
+            let stream = plan.execute(partition, context.clone())?;
+            batches.extend(common::collect(stream).await?);
+        }
+        Ok(batches)
+    }
+
+    #[tokio::test]
+    async fn scalar_subquery_exec() -> Result<()> {
+        let plan = subquery_plan(vec![10])?;
+        let batches = collect_partitions(plan).await?;
+        let expected = vec![
+            "+---+----+",
+            "| a | sq |",
+            "+---+----+",
+            "| 1 | 10 |",
+            "| 2 | 10 |",
+            "| 3 | 10 |",
+            "+---+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn scalar_subquery_exec_no_rows() -> Result<()> {
+        let plan = subquery_plan(vec![])?;
+        let batches = collect_partitions(plan).await?;
+        let expected = vec![
+            "+---+----+",
+            "| a | sq |",
+            "+---+----+",
+            "| 1 |    |",
+            "| 2 |    |",
+            "| 3 |    |",
+            "+---+----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn scalar_subquery_exec_more_than_one_row() -> Result<()> {
+        let plan = subquery_plan(vec![10, 20])?;
+        let err = collect_partitions(plan).await.unwrap_err();
+        assert!(
+            err.to_string()
+                .contains("Scalar subquery returned more than one row"),
+            "{err}"
+        );
+        Ok(())
+    }
+}
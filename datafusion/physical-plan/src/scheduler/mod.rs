@@ -0,0 +1,229 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Pluggable strategies for driving the execution of an [`ExecutionPlan`]
+//!
+//! By default DataFusion executes a plan by calling [`ExecutionPlan::execute`]
+//! once per output partition, and operators such as [`RepartitionExec`] and
+//! [`CoalescePartitionsExec`] spawn one tokio task per input partition. An
+//! [`ExecutionScheduler`] allows a different strategy to be plugged in (for
+//! example, a morsel-driven pool of workers) without changing any operator.
+//!
+//! [`RepartitionExec`]: crate::repartition::RepartitionExec
+//! [`CoalescePartitionsExec`]: crate::coalesce_partitions::CoalescePartitionsExec
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use crate::instrument::instrument_plan;
+use crate::{common, ExecutionPlan, SendableRecordBatchStream};
+
+use arrow::record_batch::RecordBatch;
+use datafusion_common::Result;
+use datafusion_execution::TaskContext;
+use tracing::info_span;
+
+#[cfg(feature = "work_stealing_scheduler")]
+mod work_stealing;
+
+#[cfg(feature = "work_stealing_scheduler")]
+pub use work_stealing::WorkStealingScheduler;
+
+/// Drives the execution of an [`ExecutionPlan`], turning its output
+/// partitions into [`SendableRecordBatchStream`]s.
+///
+/// Implementations decide how (and on which tasks) the partitions of the
+/// plan are polled. All implementations must preserve the partitioning of
+/// the plan: the `i`-th stream returned by
+/// [`Self::execute_stream_partitioned`] must produce exactly the rows of
+/// partition `i`.
+pub trait ExecutionScheduler: Debug + Send + Sync {
+    /// Short name of this scheduler, used for display and debugging
+    fn name(&self) -> &str;
+
+    /// Execute `plan` and return one stream per output partition
+    ///
+    /// # Aborting Execution
+    ///
+    /// Dropping all the streams must abort the execution of the query, and
+    /// free up any allocated resources
+    fn execute_stream_partitioned(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        context: Arc<TaskContext>,
+    ) -> Result<Vec<SendableRecordBatchStream>>;
+
+    /// Execute `plan` and return a single stream of `RecordBatch`es.
+    ///
+    /// The default implementation merges the partitions with a
+    /// [`CoalescePartitionsExec`](crate::coalesce_partitions::CoalescePartitionsExec),
+    /// see [`crate::execute_stream`].
+    fn execute_stream(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        crate::execute_stream(plan, context)
+    }
+}
+
+/// The default [`ExecutionScheduler`]: every partition is executed by
+/// calling [`ExecutionPlan::execute`] and is driven by whichever task polls
+/// the resulting stream.
+#[derive(Debug, Default, Clone, Copy)]
+pub struct DefaultScheduler;
+
+impl ExecutionScheduler for DefaultScheduler {
+    fn name(&self) -> &str {
+        "default"
+    }
+
+    fn execute_stream_partitioned(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        context: Arc<TaskContext>,
+    ) -> Result<Vec<SendableRecordBatchStream>> {
+        crate::execute_stream_partitioned(plan, context)
+    }
+}
+
+/// An [`ExecutionScheduler`] recording the execution of the plans in
+/// `tracing` spans, then executing them with another scheduler.
+///
+/// Each execution is recorded in a `datafusion.execute` span, parent of the
+/// `datafusion.operator` spans of the partitions of the operators, see
+/// [`instrument_plan`].
+#[derive(Debug)]
+pub struct TracingScheduler {
+    /// The scheduler executing the plans
+    inner: Arc<dyn ExecutionScheduler>,
+    /// The id of the external trace the executions are part of
+    trace_parent: Option<String>,
+}
+
+impl TracingScheduler {
+    /// Create a new TracingScheduler executing the plans with `inner`, as
+    /// part of the external trace `trace_parent` if any
+    pub fn new(inner: Arc<dyn ExecutionScheduler>, trace_parent: Option<String>) -> Self {
+        Self {
+            inner,
+            trace_parent,
+        }
+    }
+
+    fn instrument(&self, plan: Arc<dyn ExecutionPlan>) -> Result<Arc<dyn ExecutionPlan>> {
+        let span = info_span!(
+            "datafusion.execute",
+            scheduler = self.inner.name(),
+            trace_parent = self.trace_parent.as_deref(),
+        );
+        instrument_plan(plan, &span)
+    }
+}
+
+impl ExecutionScheduler for TracingScheduler {
+    fn name(&self) -> &str {
+        self.inner.name()
+    }
+
+    fn execute_stream_partitioned(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        context: Arc<TaskContext>,
+    ) -> Result<Vec<SendableRecordBatchStream>> {
+        self.inner
+            .execute_stream_partitioned(self.instrument(plan)?, context)
+    }
+
+    fn execute_stream(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        self.inner.execute_stream(self.instrument(plan)?, context)
+    }
+}
+
+/// Execute `plan` using `scheduler` and collect the results in memory
+pub async fn collect_with_scheduler(
+    scheduler: &dyn ExecutionScheduler,
+    plan: Arc<dyn ExecutionPlan>,
+    context: Arc<TaskContext>,
+) -> Result<Vec<RecordBatch>> {
+    let stream = scheduler.execute_stream(plan, context)?;
+    common::collect(stream).await
+}
+
+/// Execute `plan` using `scheduler` and collect the results in memory,
+/// maintaining the output partitioning of the plan
+pub async fn collect_partitioned_with_scheduler(
+    scheduler: &dyn ExecutionScheduler,
+    plan: Arc<dyn ExecutionPlan>,
+    context: Arc<TaskContext>,
+) -> Result<Vec<Vec<RecordBatch>>> {
+    let streams = scheduler.execute_stream_partitioned(plan, context)?;
+    crate::collect_streams(streams).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::test::scan_partitioned;
+
+    #[tokio::test]
+    async fn default_scheduler_preserves_partitions() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let plan = scan_partitioned(4);
+
+        let partitions =
+            collect_partitioned_with_scheduler(&DefaultScheduler, plan, task_ctx).await?;
+        assert_eq!(partitions.len(), 4);
+        for batches in partitions {
+            let rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+            assert_eq!(rows, 100);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn default_scheduler_collect() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let plan = scan_partitioned(3);
+
+        let batches = collect_with_scheduler(&DefaultScheduler, plan, task_ctx).await?;
+        let rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+        assert_eq!(rows, 300);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn tracing_scheduler_preserves_partitions() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let plan = scan_partitioned(4);
+        let scheduler =
+            TracingScheduler::new(Arc::new(DefaultScheduler), Some("trace-1".into()));
+
+        let partitions =
+            collect_partitioned_with_scheduler(&scheduler, plan, task_ctx).await?;
+        assert_eq!(partitions.len(), 4);
+        for batches in partitions {
+            let rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+            assert_eq!(rows, 100);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,397 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! A morsel-driven [`ExecutionScheduler`] that multiplexes the partitions of
+//! a plan over a fixed pool of worker tasks
+
+use std::collections::{HashMap, VecDeque};
+use std::panic::AssertUnwindSafe;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Arc;
+
+use super::ExecutionScheduler;
+use crate::stream::RecordBatchStreamAdapter;
+use crate::{ExecutionPlan, ExecutionPlanProperties, SendableRecordBatchStream};
+
+use arrow::record_batch::RecordBatch;
+use datafusion_common::Result;
+use datafusion_execution::TaskContext;
+
+use futures::{FutureExt, StreamExt};
+use parking_lot::Mutex;
+use tokio::sync::mpsc::error::TrySendError;
+use tokio::sync::{mpsc, Notify};
+use tokio::task::JoinSet;
+
+/// Items sent from the workers to the output streams. A panic while polling
+/// an input is forwarded as `Err` and resumed on the consumer side.
+type MorselResult = std::thread::Result<Result<RecordBatch>>;
+
+/// Experimental [`ExecutionScheduler`] that executes the partitions of a plan
+/// using a fixed pool of workers instead of one task per partition.
+///
+/// Each partition is a unit of work: a worker takes the next partition from
+/// a shared queue, polls it for a single `RecordBatch` (a "morsel"), hands
+/// the batch to the output stream of that partition and puts the partition
+/// back at the end of the queue. Idle workers therefore steal work from
+/// partitions that would otherwise wait for their own task to be scheduled,
+/// and the number of concurrently running tasks is bounded by the number of
+/// workers regardless of the number of partitions.
+///
+/// A worker never waits for the output stream of a partition to accept a
+/// batch: a partition whose output is full is parked, with its pending batch,
+/// until its consumer reads from it, so that the workers keep driving the
+/// other partitions whatever the order in which the partitions are consumed.
+#[derive(Debug, Clone)]
+pub struct WorkStealingScheduler {
+    /// Number of workers, defaults to `target_partitions`
+    num_workers: Option<usize>,
+    /// Number of batches buffered for each output partition
+    channel_capacity: usize,
+}
+
+impl Default for WorkStealingScheduler {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl WorkStealingScheduler {
+    /// Create a new scheduler using `target_partitions` workers
+    pub fn new() -> Self {
+        Self {
+            num_workers: None,
+            channel_capacity: 2,
+        }
+    }
+
+    /// Use a fixed number of workers instead of `target_partitions`
+    pub fn with_num_workers(mut self, num_workers: usize) -> Self {
+        self.num_workers = Some(num_workers);
+        self
+    }
+
+    /// Set the number of batches buffered for each output partition
+    pub fn with_channel_capacity(mut self, channel_capacity: usize) -> Self {
+        self.channel_capacity = channel_capacity.max(1);
+        self
+    }
+}
+
+impl ExecutionScheduler for WorkStealingScheduler {
+    fn name(&self) -> &str {
+        "work_stealing"
+    }
+
+    fn execute_stream_partitioned(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        context: Arc<TaskContext>,
+    ) -> Result<Vec<SendableRecordBatchStream>> {
+        let schema = plan.schema();
+        let num_partitions = plan.output_partitioning().partition_count();
+        let num_workers = self
+            .num_workers
+            .unwrap_or_else(|| context.session_config().target_partitions())
+            .clamp(1, num_partitions.max(1));
+
+        let queue = Arc::new(WorkQueue::new(num_partitions));
+        let mut receivers = Vec::with_capacity(num_partitions);
+        for partition in 0..num_partitions {
+            let input = plan.execute(partition, context.clone())?;
+            let (output, rx) = mpsc::channel(self.channel_capacity);
+            queue.push(Morsel {
+                partition,
+                input,
+                output,
+                pending: None,
+            });
+            receivers.push(Output {
+                partition,
+                rx,
+                queue: Arc::clone(&queue),
+            });
+        }
+
+        let mut workers = JoinSet::new();
+        for _ in 0..num_workers {
+            workers.spawn(run_worker(Arc::clone(&queue)));
+        }
+        // The workers are aborted once every output stream has been dropped
+        let workers = Arc::new(Mutex::new(workers));
+
+        Ok(receivers
+            .into_iter()
+            .map(|output| {
+                let stream = futures::stream::unfold(
+                    (output, Arc::clone(&workers)),
+                    |(mut output, workers)| async move {
+                        match output.recv().await {
+                            Some(Ok(item)) => Some((item, (output, workers))),
+                            Some(Err(panic)) => std::panic::resume_unwind(panic),
+                            None => None,
+                        }
+                    },
+                );
+                Box::pin(RecordBatchStreamAdapter::new(schema.clone(), stream))
+                    as SendableRecordBatchStream
+            })
+            .collect())
+    }
+
+    fn execute_stream(
+        &self,
+        plan: Arc<dyn ExecutionPlan>,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let schema = plan.schema();
+        let mut streams = self.execute_stream_partitioned(plan, context)?;
+        if streams.len() == 1 {
+            return Ok(streams.remove(0));
+        }
+        // The workers already drive every partition, so the partitions can
+        // simply be merged in the order batches become available
+        let merged = futures::stream::select_all(streams);
+        Ok(Box::pin(RecordBatchStreamAdapter::new(schema, merged)))
+    }
+}
+
+/// A partially consumed partition
+struct Morsel {
+    partition: usize,
+    input: SendableRecordBatchStream,
+    output: mpsc::Sender<MorselResult>,
+    /// The item read from `input` which did not fit in `output` yet
+    pending: Option<MorselResult>,
+}
+
+/// The receiving end of the output of a partition, which unparks the
+/// partition whenever it makes room for another item
+struct Output {
+    partition: usize,
+    rx: mpsc::Receiver<MorselResult>,
+    queue: Arc<WorkQueue>,
+}
+
+impl Output {
+    async fn recv(&mut self) -> Option<MorselResult> {
+        let item = self.rx.recv().await;
+        self.queue.unpark(self.partition);
+        item
+    }
+}
+
+impl Drop for Output {
+    fn drop(&mut self) {
+        // The worker unparking the partition finds the output closed and
+        // finishes the partition
+        self.rx.close();
+        self.queue.unpark(self.partition);
+    }
+}
+
+/// Queue of partitions shared by all the workers
+struct WorkQueue {
+    queue: Mutex<VecDeque<Morsel>>,
+    /// Partitions whose output is full, by partition index
+    parked: Mutex<HashMap<usize, Morsel>>,
+    /// Number of partitions that are not finished yet, either queued or
+    /// parked or currently held by a worker
+    remaining: AtomicUsize,
+    notify: Notify,
+}
+
+impl WorkQueue {
+    fn new(num_partitions: usize) -> Self {
+        Self {
+            queue: Mutex::new(VecDeque::with_capacity(num_partitions)),
+            parked: Mutex::new(HashMap::new()),
+            remaining: AtomicUsize::new(num_partitions),
+            notify: Notify::new(),
+        }
+    }
+
+    fn push(&self, morsel: Morsel) {
+        self.queue.lock().push_back(morsel);
+        self.notify.notify_one();
+    }
+
+    /// Park a partition whose output is full until its consumer reads from it
+    fn park(&self, morsel: Morsel) {
+        let mut parked = self.parked.lock();
+        // The consumer may have made room, or gone away, since the output
+        // was found full, before the partition could be found parked
+        if morsel.output.capacity() > 0 || morsel.output.is_closed() {
+            drop(parked);
+            self.push(morsel);
+        } else {
+            parked.insert(morsel.partition, morsel);
+        }
+    }
+
+    /// Queue a partition again if it was parked
+    fn unpark(&self, partition: usize) {
+        let morsel = self.parked.lock().remove(&partition);
+        if let Some(morsel) = morsel {
+            self.push(morsel);
+        }
+    }
+
+    /// Mark a partition as finished, waking up all idle workers when it was
+    /// the last one so that they can exit
+    fn finish(&self) {
+        if self.remaining.fetch_sub(1, Ordering::AcqRel) == 1 {
+            self.notify.notify_waiters();
+        }
+    }
+
+    /// Returns the next partition to poll, or `None` once all the
+    /// partitions are finished
+    async fn pop(&self) -> Option<Morsel> {
+        loop {
+            // Register interest before checking the queue so that a
+            // concurrent `push` or `finish` can not be missed
+            let notified = self.notify.notified();
+            let next = self.queue.lock().pop_front();
+            if next.is_some() {
+                return next;
+            }
+            if self.remaining.load(Ordering::Acquire) == 0 {
+                return None;
+            }
+            notified.await;
+        }
+    }
+}
+
+async fn run_worker(queue: Arc<WorkQueue>) {
+    while let Some(mut morsel) = queue.pop().await {
+        let item = match morsel.pending.take() {
+            Some(item) => item,
+            None => match AssertUnwindSafe(morsel.input.next()).catch_unwind().await {
+                Ok(Some(item)) => Ok(item),
+                Ok(None) => {
+                    queue.finish();
+                    continue;
+                }
+                Err(panic) => {
+                    log::debug!("Partition {} panicked", morsel.partition);
+                    Err(panic)
+                }
+            },
+        };
+        // Stop driving a partition once it has failed or panicked
+        let last = !matches!(item, Ok(Ok(_)));
+        // Waiting for room in the output could block the worker on a
+        // partition nobody reads yet, while its consumer waits for another one
+        match morsel.output.try_send(item) {
+            Ok(()) if last => queue.finish(),
+            Ok(()) => queue.push(morsel),
+            Err(TrySendError::Full(item)) => {
+                morsel.pending = Some(item);
+                queue.park(morsel);
+            }
+            Err(TrySendError::Closed(_)) => queue.finish(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::collect;
+    use crate::memory::MemoryExec;
+    use crate::scheduler::collect_partitioned_with_scheduler;
+    use crate::test::exec::PanicExec;
+    use crate::test::{make_partition, scan_partitioned};
+
+    use arrow_schema::{DataType, Field, Schema};
+
+    #[tokio::test]
+    async fn preserves_partitions() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let scheduler = WorkStealingScheduler::new().with_num_workers(2);
+
+        let partitions =
+            collect_partitioned_with_scheduler(&scheduler, scan_partitioned(7), task_ctx)
+                .await?;
+        assert_eq!(partitions.len(), 7);
+        for batches in partitions {
+            let rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+            assert_eq!(rows, 100);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn merges_partitions() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let scheduler = WorkStealingScheduler::new().with_num_workers(3);
+
+        let stream = scheduler.execute_stream(scan_partitioned(5), task_ctx)?;
+        let batches = collect(stream).await?;
+        let rows: usize = batches.iter().map(|b| b.num_rows()).sum();
+        assert_eq!(rows, 500);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn no_partitions() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let scheduler = WorkStealingScheduler::new();
+
+        let schema = Arc::new(Schema::new(vec![Field::new("i", DataType::Int32, true)]));
+        let plan = Arc::new(MemoryExec::try_new(&[], schema, None)?);
+        let streams = scheduler.execute_stream_partitioned(plan, task_ctx)?;
+        assert!(streams.is_empty());
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn consumes_partitions_one_after_another() -> Result<()> {
+        // more batches per partition than the capacity of the outputs
+        let partitions: Vec<Vec<_>> = (0..4)
+            .map(|_| (0..5).map(|_| make_partition(10)).collect())
+            .collect();
+        let schema = partitions[0][0].schema();
+        let plan = Arc::new(MemoryExec::try_new(&partitions, schema, None)?);
+        let task_ctx = Arc::new(TaskContext::default());
+        let scheduler = WorkStealingScheduler::new()
+            .with_num_workers(1)
+            .with_channel_capacity(2);
+
+        // the single worker must not block on a partition not read yet
+        let streams = scheduler.execute_stream_partitioned(plan, task_ctx)?;
+        for stream in streams {
+            let batches = collect(stream).await?;
+            assert_eq!(batches.len(), 5);
+        }
+        Ok(())
+    }
+
+    #[tokio::test]
+    #[should_panic(expected = "PanickingStream did panic")]
+    async fn propagates_panics() {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let plan = Arc::new(PanicExec::new(schema, 2).with_partition_panic(1, 1));
+        let task_ctx = Arc::new(TaskContext::default());
+        let scheduler = WorkStealingScheduler::new().with_num_workers(1);
+
+        let stream = scheduler.execute_stream(plan, task_ctx).unwrap();
+        collect(stream).await.unwrap();
+    }
+}
@@ -0,0 +1,681 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! SetOperationExec executes the `INTERSECT ALL` and `EXCEPT ALL` set
+//! operations by counting the rows of their right input
+
+use std::any::Any;
+use std::collections::HashMap;
+use std::mem::size_of;
+use std::sync::Arc;
+
+use super::metrics::{
+    BaselineMetrics, Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
+};
+use super::{DisplayAs, ExecutionPlanProperties, PlanProperties, Statistics};
+use crate::common::{spill_write_options, IPCWriter};
+use crate::sorts::sort::read_spill_as_stream;
+use crate::stream::RecordBatchStreamAdapter;
+use crate::{
+    DisplayFormatType, Distribution, ExecutionMode, ExecutionPlan,
+    SendableRecordBatchStream,
+};
+
+use ahash::RandomState;
+use arrow::array::{ArrayRef, AsArray, BooleanArray, UInt32Array, UInt64Array};
+use arrow::compute::{filter_record_batch, take};
+use arrow::datatypes::{DataType, Field, Schema, SchemaRef, UInt64Type};
+use arrow::record_batch::RecordBatch;
+use arrow::row::{RowConverter, Rows, SortField};
+use datafusion_common::config::SpillCompression;
+use datafusion_common::{internal_err, Result};
+use datafusion_execution::disk_manager::RefCountedTempFile;
+use datafusion_execution::memory_pool::{MemoryConsumer, MemoryReservation};
+use datafusion_execution::runtime_env::RuntimeEnv;
+use datafusion_execution::TaskContext;
+use datafusion_expr::SetOperationType;
+use datafusion_physical_expr::expressions::Column;
+use datafusion_physical_expr::{EquivalenceProperties, PhysicalExpr};
+
+use futures::{stream, StreamExt, TryStreamExt};
+
+/// The number of partitions the inputs are split into once the counts of the
+/// rows of the right input don't fit in memory
+const SPILL_PARTITIONS: usize = 16;
+
+/// The name of the column of the counts of the rows of the right input in its
+/// spill files
+const COUNT_COLUMN: &str = "__count";
+
+/// SetOperationExec executes `INTERSECT ALL` and `EXCEPT ALL`, with
+/// [`SetOperationType`], between two inputs with the same column types.
+///
+/// The occurrences of each row of the right input are counted in a hash
+/// table, then the rows of the left input are kept, in their order, while the
+/// count of the matching row is:
+///
+/// * `INTERSECT ALL`: positive, the count being decremented, so that each row
+///   is kept as many times as it occurs in both inputs
+/// * `EXCEPT ALL`: zero, the positive counts being decremented instead, so
+///   that each row is kept as many times as it occurs in the left input more
+///   than in the right input
+///
+/// The rows are compared as a whole, nulls being equal to each other. Both
+/// inputs must be hash partitioned on all their columns.
+///
+/// If the counts don't fit in memory, both inputs are split by the hashes of
+/// their rows into spill files, which are processed one after the other.
+#[derive(Debug)]
+pub struct SetOperationExec {
+    /// The left input, whose rows are output
+    left: Arc<dyn ExecutionPlan>,
+    /// The right input, whose rows are counted
+    right: Arc<dyn ExecutionPlan>,
+    /// The type of the set operation
+    op: SetOperationType,
+    /// Execution metrics
+    metrics: ExecutionPlanMetricsSet,
+    cache: PlanProperties,
+}
+
+impl SetOperationExec {
+    /// Create a new SetOperationExec
+    pub fn try_new(
+        left: Arc<dyn ExecutionPlan>,
+        right: Arc<dyn ExecutionPlan>,
+        op: SetOperationType,
+    ) -> Result<Self> {
+        let left_schema = left.schema();
+        let right_schema = right.schema();
+        let left_types = left_schema.fields().iter().map(|f| f.data_type());
+        let right_types = right_schema.fields().iter().map(|f| f.data_type());
+        if !left_types.eq(right_types) {
+            return internal_err!(
+                "SetOperationExec requires inputs with the same column types, got {left_schema:?} and {right_schema:?}"
+            );
+        }
+        let cache = Self::compute_properties(&left, &right);
+        Ok(Self {
+            left,
+            right,
+            op,
+            metrics: ExecutionPlanMetricsSet::new(),
+            cache,
+        })
+    }
+
+    /// The left input, whose rows are output
+    pub fn left(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.left
+    }
+
+    /// The right input, whose rows are counted
+    pub fn right(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.right
+    }
+
+    /// The type of the set operation
+    pub fn op(&self) -> SetOperationType {
+        self.op
+    }
+
+    /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
+    fn compute_properties(
+        left: &Arc<dyn ExecutionPlan>,
+        right: &Arc<dyn ExecutionPlan>,
+    ) -> PlanProperties {
+        // The right input is consumed before any row is output
+        let mode = if right.execution_mode().is_unbounded() {
+            ExecutionMode::PipelineBreaking
+        } else {
+            left.execution_mode()
+        };
+        // The rows are reordered once spilled
+        PlanProperties::new(
+            EquivalenceProperties::new(left.schema()),
+            left.output_partitioning().clone(),
+            mode,
+        )
+    }
+}
+
+/// Returns the distribution of `schema` hash partitioned on all its columns
+fn hash_partitioned(schema: &SchemaRef) -> Distribution {
+    if schema.fields().is_empty() {
+        return Distribution::SinglePartition;
+    }
+    let exprs = schema
+        .fields()
+        .iter()
+        .enumerate()
+        .map(|(i, field)| Arc::new(Column::new(field.name(), i)) as Arc<dyn PhysicalExpr>)
+        .collect();
+    Distribution::HashPartitioned(exprs)
+}
+
+impl DisplayAs for SetOperationExec {
+    fn fmt_as(
+        &self,
+        t: DisplayFormatType,
+        f: &mut std::fmt::Formatter,
+    ) -> std::fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(f, "SetOperationExec: op={}", self.op)
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for SetOperationExec {
+    fn name(&self) -> &'static str {
+        "SetOperationExec"
+    }
+
+    /// Return a reference to Any that can be used for downcasting
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn required_input_distribution(&self) -> Vec<Distribution> {
+        vec![
+            hash_partitioned(&self.left.schema()),
+            hash_partitioned(&self.right.schema()),
+        ]
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.left.clone(), self.right.clone()]
+    }
+
+    fn maintains_input_order(&self) -> Vec<bool> {
+        vec![false, false]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(SetOperationExec::try_new(
+            children[0].clone(),
+            children[1].clone(),
+            self.op,
+        )?))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        let left_partitions = self.left.output_partitioning().partition_count();
+        let right_partitions = self.right.output_partitioning().partition_count();
+        if left_partitions != right_partitions {
+            return internal_err!(
+                "Invalid SetOperationExec, partition count mismatch {left_partitions}!={right_partitions},\
+                 consider using RepartitionExec"
+            );
+        }
+
+        let schema = self.schema();
+        let reservation = MemoryConsumer::new(format!("SetOperationExec[{partition}]"))
+            .with_can_spill(true)
+            .register(context.memory_pool());
+        let state = SetOperationPartition {
+            op: self.op,
+            schema: schema.clone(),
+            reservation,
+            runtime: context.runtime_env(),
+            spill_compression: context
+                .session_config()
+                .options()
+                .execution
+                .spill_compression,
+            baseline_metrics: BaselineMetrics::new(&self.metrics, partition),
+            spill_count: MetricBuilder::new(&self.metrics).spill_count(partition),
+            spilled_rows: MetricBuilder::new(&self.metrics).spilled_rows(partition),
+            spilled_bytes: MetricBuilder::new(&self.metrics).spilled_bytes(partition),
+        };
+        let left = self.left.execute(partition, context.clone())?;
+        let right = self.right.execute(partition, context)?;
+        let stream = stream::once(state.execute(left, right)).try_flatten();
+        Ok(Box::pin(RecordBatchStreamAdapter::new(schema, stream)))
+    }
+
+    fn metrics(&self) -> Option<MetricsSet> {
+        Some(self.metrics.clone_inner())
+    }
+
+    fn statistics(&self) -> Result<Statistics> {
+        // At most all the rows of the left input are output
+        Ok(self.left.statistics()?.into_inexact())
+    }
+}
+
+/// The execution of a partition of a [`SetOperationExec`]
+struct SetOperationPartition {
+    /// The type of the set operation
+    op: SetOperationType,
+    /// The schema of the output, and of the left input
+    schema: SchemaRef,
+    /// The reservation of the memory of the counts of the rows
+    reservation: MemoryReservation,
+    runtime: Arc<RuntimeEnv>,
+    spill_compression: SpillCompression,
+    /// Execution metrics
+    baseline_metrics: BaselineMetrics,
+    spill_count: Count,
+    spilled_rows: Count,
+    spilled_bytes: Count,
+}
+
+impl SetOperationPartition {
+    /// Counts the rows of `right`, then returns the kept rows of `left`
+    async fn execute(
+        mut self,
+        mut left: SendableRecordBatchStream,
+        mut right: SendableRecordBatchStream,
+    ) -> Result<SendableRecordBatchStream> {
+        let right_schema = right.schema();
+        let mut counts = RowCounts::try_new(&self.schema)?;
+        let mut right_spill: Option<SpillPartitions> = None;
+        while let Some(batch) = right.next().await {
+            let batch = batch?;
+            let timer = self.baseline_metrics.elapsed_compute().timer();
+            let rows = counts.convert(batch.columns())?;
+            match &mut right_spill {
+                Some(spill) => {
+                    let ones = UInt64Array::from_value(1, batch.num_rows());
+                    spill.write(&with_counts(&batch, ones)?, &rows)?;
+                }
+                None => {
+                    counts.add(&rows, None);
+                    if self.reservation.try_resize(counts.size()).is_err() {
+                        let schema = counted_schema(&right_schema);
+                        let mut spill = self.spill_partitions(&schema)?;
+                        let (batch, rows) = counts.drain(&right_schema)?;
+                        spill.write(&batch, &rows)?;
+                        self.reservation.free();
+                        right_spill = Some(spill);
+                    }
+                }
+            }
+            timer.done();
+        }
+
+        let Some(right_spill) = right_spill else {
+            return Ok(filter_rows(
+                self.op,
+                left,
+                counts,
+                self.reservation,
+                self.baseline_metrics,
+            ));
+        };
+
+        // The counts didn't fit in memory: the rows of both inputs are split
+        // by their hashes, and the matching partitions processed one by one
+        let mut left_spill = self.spill_partitions(&self.schema)?;
+        while let Some(batch) = left.next().await {
+            let batch = batch?;
+            let timer = self.baseline_metrics.elapsed_compute().timer();
+            let rows = counts.convert(batch.columns())?;
+            left_spill.write(&batch, &rows)?;
+            timer.done();
+        }
+        let partitions = right_spill.finish()?.into_iter().zip(left_spill.finish()?);
+
+        let Self {
+            op,
+            schema,
+            runtime,
+            baseline_metrics,
+            ..
+        } = self;
+        let output_schema = schema.clone();
+        let stream = stream::iter(partitions)
+            .then(move |(right_file, left_file)| {
+                filter_spilled_rows(
+                    op,
+                    schema.clone(),
+                    right_schema.clone(),
+                    right_file,
+                    left_file,
+                    runtime.clone(),
+                    baseline_metrics.clone(),
+                )
+            })
+            .try_flatten();
+        Ok(Box::pin(RecordBatchStreamAdapter::new(
+            output_schema,
+            stream,
+        )))
+    }
+
+    /// Creates the spill files of `schema` the rows are split into
+    fn spill_partitions(&self, schema: &SchemaRef) -> Result<SpillPartitions> {
+        let writers = (0..SPILL_PARTITIONS)
+            .map(|_| {
+                let file = self.runtime.disk_manager.create_tmp_file("SetOperation")?;
+                let writer = IPCWriter::new_with_options(
+                    file.path(),
+                    schema,
+                    spill_write_options(self.spill_compression)?,
+                )?;
+                Ok((file, writer))
+            })
+            .collect::<Result<Vec<_>>>()?;
+        Ok(SpillPartitions {
+            writers,
+            random_state: RandomState::with_seeds(
+                'S' as u64, 'P' as u64, 'I' as u64, 'L' as u64,
+            ),
+            spill_count: self.spill_count.clone(),
+            spilled_rows: self.spilled_rows.clone(),
+            spilled_bytes: self.spilled_bytes.clone(),
+        })
+    }
+}
+
+/// Returns the rows of `left` kept by `counts`, according to `op`. The
+/// memory of `counts` is reserved by `reservation`.
+fn filter_rows(
+    op: SetOperationType,
+    left: SendableRecordBatchStream,
+    mut counts: RowCounts,
+    reservation: MemoryReservation,
+    baseline_metrics: BaselineMetrics,
+) -> SendableRecordBatchStream {
+    let schema = left.schema();
+    let stream = left.map(move |batch| {
+        // released once the stream is dropped
+        let _reservation = &reservation;
+        let timer = baseline_metrics.elapsed_compute().timer();
+        let batch = counts.filter(op, &batch?)?;
+        timer.done();
+        baseline_metrics.record_output(batch.num_rows());
+        Ok(batch)
+    });
+    Box::pin(RecordBatchStreamAdapter::new(schema, stream))
+}
+
+/// Counts the rows of the spill file of the right input `right_file`, then
+/// returns the kept rows of the matching spill file of the left input
+/// `left_file`, of `schema`
+async fn filter_spilled_rows(
+    op: SetOperationType,
+    schema: SchemaRef,
+    right_schema: SchemaRef,
+    right_file: RefCountedTempFile,
+    left_file: RefCountedTempFile,
+    runtime: Arc<RuntimeEnv>,
+    baseline_metrics: BaselineMetrics,
+) -> Result<SendableRecordBatchStream> {
+    let mut counts = RowCounts::try_new(&schema)?;
+    let mut reservation =
+        MemoryConsumer::new("SetOperationExec[spilled]").register(&runtime.memory_pool);
+    let mut right = read_spill_as_stream(right_file, counted_schema(&right_schema))?;
+    while let Some(batch) = right.next().await {
+        let batch = batch?;
+        let (columns, batch_counts) = batch.columns().split_at(schema.fields().len());
+        let rows = counts.convert(columns)?;
+        counts.add(&rows, Some(batch_counts[0].as_primitive::<UInt64Type>()));
+        // the counts of a partition must fit in memory
+        reservation.try_resize(counts.size())?;
+    }
+    let left = read_spill_as_stream(left_file, schema)?;
+    Ok(filter_rows(op, left, counts, reservation, baseline_metrics))
+}
+
+/// The number of occurrences of the rows of the right input, by their row
+/// format
+struct RowCounts {
+    converter: RowConverter,
+    counts: HashMap<Box<[u8]>, u64>,
+    /// The size of the rows of `counts`
+    rows_size: usize,
+}
+
+impl RowCounts {
+    fn try_new(schema: &Schema) -> Result<Self> {
+        let fields = schema
+            .fields()
+            .iter()
+            .map(|field| SortField::new(field.data_type().clone()))
+            .collect();
+        Ok(Self {
+            converter: RowConverter::new(fields)?,
+            counts: HashMap::new(),
+            rows_size: 0,
+        })
+    }
+
+    /// Returns `columns` in the row format of the counts
+    fn convert(&mut self, columns: &[ArrayRef]) -> Result<Rows> {
+        Ok(self.converter.convert_columns(columns)?)
+    }
+
+    /// Counts `rows`, each occurring the number of times at its index in
+    /// `counts`, or once
+    fn add(&mut self, rows: &Rows, counts: Option<&UInt64Array>) {
+        for (i, row) in rows.iter().enumerate() {
+            let n = counts.map_or(1, |counts| counts.value(i));
+            match self.counts.get_mut(row.as_ref()) {
+                Some(count) => *count += n,
+                None => {
+                    self.rows_size += row.as_ref().len();
+                    self.counts.insert(row.as_ref().into(), n);
+                }
+            }
+        }
+    }
+
+    /// Returns the rows of `batch` kept according to `op`, decrementing the
+    /// counts of the matched rows
+    fn filter(
+        &mut self,
+        op: SetOperationType,
+        batch: &RecordBatch,
+    ) -> Result<RecordBatch> {
+        let rows = self.converter.convert_columns(batch.columns())?;
+        let mask = rows
+            .iter()
+            .map(|row| {
+                let matched = match self.counts.get_mut(row.as_ref()) {
+                    Some(count) if *count > 0 => {
+                        *count -= 1;
+                        true
+                    }
+                    _ => false,
+                };
+                Some(match op {
+                    SetOperationType::IntersectAll => matched,
+                    SetOperationType::ExceptAll => !matched,
+                })
+            })
+            .collect::<BooleanArray>();
+        Ok(filter_record_batch(batch, &mask)?)
+    }
+
+    /// Removes all the counts, returning the counted rows as a batch of
+    /// `schema` with their counts in an additional last column, and in the
+    /// row format
+    fn drain(&mut self, schema: &SchemaRef) -> Result<(RecordBatch, Rows)> {
+        let parser = self.converter.parser();
+        let mut rows = self.converter.empty_rows(self.counts.len(), self.rows_size);
+        let mut counts = Vec::with_capacity(self.counts.len());
+        for (row, count) in self.counts.drain() {
+            rows.push(parser.parse(&row));
+            counts.push(count);
+        }
+        self.counts.shrink_to_fit();
+        self.rows_size = 0;
+        let columns = self.converter.convert_rows(&rows)?;
+        let batch = RecordBatch::try_new(schema.clone(), columns)?;
+        Ok((with_counts(&batch, UInt64Array::from(counts))?, rows))
+    }
+
+    /// The memory size of the counts
+    fn size(&self) -> usize {
+        self.converter.size()
+            + self.rows_size
+            + self.counts.capacity() * (size_of::<(Box<[u8]>, u64)>() + 1)
+    }
+}
+
+/// Returns the schema of the spill files of the right input, `schema` with an
+/// additional column of the counts of the rows
+fn counted_schema(schema: &SchemaRef) -> SchemaRef {
+    let mut fields = schema.fields().to_vec();
+    fields.push(Arc::new(Field::new(COUNT_COLUMN, DataType::UInt64, false)));
+    Arc::new(Schema::new(fields))
+}
+
+/// Returns `batch` with the additional column `counts`
+fn with_counts(batch: &RecordBatch, counts: UInt64Array) -> Result<RecordBatch> {
+    let mut columns = batch.columns().to_vec();
+    columns.push(Arc::new(counts));
+    Ok(RecordBatch::try_new(
+        counted_schema(&batch.schema()),
+        columns,
+    )?)
+}
+
+/// Spill files the rows of an input are split into by the hashes of their
+/// row format
+struct SpillPartitions {
+    /// The spill files, with their writers
+    writers: Vec<(RefCountedTempFile, IPCWriter)>,
+    random_state: RandomState,
+    spill_count: Count,
+    spilled_rows: Count,
+    spilled_bytes: Count,
+}
+
+impl SpillPartitions {
+    /// Writes the rows of `batch`, `rows` in the row format, to the spill
+    /// files of the hashes of `rows`
+    fn write(&mut self, batch: &RecordBatch, rows: &Rows) -> Result<()> {
+        let num_partitions = self.writers.len();
+        let mut indices = vec![vec![]; num_partitions];
+        for (i, row) in rows.iter().enumerate() {
+            let hash = self.random_state.hash_one(row.as_ref());
+            indices[hash as usize % num_partitions].push(i as u32);
+        }
+        for ((_, writer), indices) in self.writers.iter_mut().zip(indices) {
+            if indices.is_empty() {
+                continue;
+            }
+            let indices = UInt32Array::from(indices);
+            let columns = batch
+                .columns()
+                .iter()
+                .map(|column| take(column.as_ref(), &indices, None))
+                .collect::<Result<Vec<_>, _>>()?;
+            writer.write(&RecordBatch::try_new(batch.schema(), columns)?)?;
+        }
+        Ok(())
+    }
+
+    /// Finishes writing the spill files, and returns them
+    fn finish(self) -> Result<Vec<RefCountedTempFile>> {
+        self.writers
+            .into_iter()
+            .map(|(file, mut writer)| {
+                writer.finish()?;
+                self.spill_count.add(1);
+                self.spilled_rows.add(writer.num_rows as usize);
+                self.spilled_bytes.add(writer.num_bytes as usize);
+                Ok(file)
+            })
+            .collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::common::collect;
+    use crate::memory::MemoryExec;
+
+    use arrow::array::Int32Array;
+    use datafusion_common::assert_batches_eq;
+    use datafusion_execution::runtime_env::RuntimeConfig;
+
+    fn values_exec(values: Vec<Option<i32>>) -> Result<Arc<dyn ExecutionPlan>> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let batch = RecordBatch::try_new(
+            schema.clone(),
+            vec![Arc::new(Int32Array::from(values))],
+        )?;
+        Ok(Arc::new(MemoryExec::try_new(&[vec![batch]], schema, None)?))
+    }
+
+    #[tokio::test]
+    async fn set_operations_keep_duplicates() -> Result<()> {
+        let left = values_exec(vec![Some(1), Some(1), Some(1), Some(2), None, None])?;
+        let right = values_exec(vec![Some(1), Some(1), Some(3), None])?;
+
+        let exec = SetOperationExec::try_new(
+            left.clone(),
+            right.clone(),
+            SetOperationType::IntersectAll,
+        )?;
+        let batches = collect(exec.execute(0, Arc::new(TaskContext::default()))?).await?;
+        let expected = [
+            "+---+", "| a |", "+---+", "| 1 |", "| 1 |", "|   |", "+---+",
+        ];
+        assert_batches_eq!(expected, &batches);
+
+        let exec = SetOperationExec::try_new(left, right, SetOperationType::ExceptAll)?;
+        let batches = collect(exec.execute(0, Arc::new(TaskContext::default()))?).await?;
+        let expected = [
+            "+---+", "| a |", "+---+", "| 1 |", "| 2 |", "|   |", "+---+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn set_operations_spill() -> Result<()> {
+        // Each value twice on the left, once or twice on the right
+        let left = values_exec((0..200).chain(0..200).map(Some).collect())?;
+        let right = values_exec((0..200).chain(0..100).map(Some).collect())?;
+
+        for (op, expected_rows) in [
+            (SetOperationType::IntersectAll, 300),
+            (SetOperationType::ExceptAll, 100),
+        ] {
+            let runtime =
+                RuntimeEnv::new(RuntimeConfig::new().with_memory_limit(2000, 1.0))?;
+            let task_ctx = TaskContext::default().with_runtime(Arc::new(runtime));
+            let exec = SetOperationExec::try_new(left.clone(), right.clone(), op)?;
+            let batches = collect(exec.execute(0, Arc::new(task_ctx))?).await?;
+            let rows: usize = batches.iter().map(|batch| batch.num_rows()).sum();
+            assert_eq!(rows, expected_rows);
+
+            let metrics = exec.metrics().unwrap();
+            assert_eq!(metrics.spill_count().unwrap(), 2 * SPILL_PARTITIONS);
+            assert_eq!(metrics.spilled_rows().unwrap(), 200 + 400);
+        }
+        Ok(())
+    }
+}
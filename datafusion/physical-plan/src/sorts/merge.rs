@@ -27,9 +27,11 @@ use arrow::datatypes::SchemaRef;
 use arrow::record_batch::RecordBatch;
 use datafusion_common::Result;
 use datafusion_execution::memory_pool::MemoryReservation;
-use futures::Stream;
+use futures::{FutureExt, Stream};
 use std::pin::Pin;
 use std::task::{ready, Context, Poll};
+use std::time::Duration;
+use tokio::time::Sleep;
 
 /// A fallible [`PartitionedStream`] of [`Cursor`] and [`RecordBatch`]
 type CursorStream<C> = Box<dyn PartitionedStream<Output = Result<(C, RecordBatch)>>>;
@@ -96,6 +98,14 @@ pub(crate) struct SortPreservingMergeStream<C: CursorValues> {
 
     /// number of rows produced
     produced: usize,
+
+    /// Optional upper bound on how long merged rows are buffered, while
+    /// waiting for input, before they are emitted in a smaller batch
+    max_batch_delay: Option<Duration>,
+
+    /// Fires `max_batch_delay` after the first row of the in progress batch
+    /// was merged
+    batch_deadline: Option<Pin<Box<Sleep>>>,
 }
 
 impl<C: CursorValues> SortPreservingMergeStream<C> {
@@ -120,9 +130,22 @@ impl<C: CursorValues> SortPreservingMergeStream<C> {
             batch_size,
             fetch,
             produced: 0,
+            max_batch_delay: None,
+            batch_deadline: None,
         }
     }
 
+    /// Emit the rows merged so far when they have been buffered for
+    /// `max_batch_delay` while waiting for input, instead of only once
+    /// `batch_size` rows are merged
+    pub(crate) fn with_max_batch_delay(
+        mut self,
+        max_batch_delay: Option<Duration>,
+    ) -> Self {
+        self.max_batch_delay = max_batch_delay;
+        self
+    }
+
     /// If the stream at the given index is not exhausted, and the last cursor for the
     /// stream is finished, poll the stream for the next RecordBatch and create a new
     /// cursor for the stream from the returned result
@@ -175,9 +198,13 @@ impl<C: CursorValues> SortPreservingMergeStream<C> {
             // Adjust the loser tree if necessary, returning control if needed
             if !self.loser_tree_adjusted {
                 let winner = self.loser_tree[0];
-                if let Err(e) = ready!(self.maybe_poll_stream(cx, winner)) {
-                    self.aborted = true;
-                    return Poll::Ready(Some(Err(e)));
+                match self.maybe_poll_stream(cx, winner) {
+                    Poll::Ready(Ok(())) => {}
+                    Poll::Ready(Err(e)) => {
+                        self.aborted = true;
+                        return Poll::Ready(Some(Err(e)));
+                    }
+                    Poll::Pending => return self.poll_batch_deadline(cx),
                 }
                 self.update_loser_tree();
             }
@@ -186,6 +213,11 @@ impl<C: CursorValues> SortPreservingMergeStream<C> {
             if self.advance(stream_idx) {
                 self.loser_tree_adjusted = false;
                 self.in_progress.push_row(stream_idx);
+                if self.in_progress.len() == 1 {
+                    self.batch_deadline = self
+                        .max_batch_delay
+                        .map(|delay| Box::pin(tokio::time::sleep(delay)));
+                }
 
                 // stop sorting if fetch has been reached
                 if self.fetch_reached() {
@@ -195,10 +227,29 @@ impl<C: CursorValues> SortPreservingMergeStream<C> {
                 }
             }
 
-            self.produced += self.in_progress.len();
+            return Poll::Ready(self.emit_in_progress());
+        }
+    }
 
-            return Poll::Ready(self.in_progress.build_record_batch().transpose());
+    /// Called while waiting for input: emits the rows merged so far if the
+    /// batch deadline has passed, and otherwise returns `Poll::Pending`
+    fn poll_batch_deadline(
+        &mut self,
+        cx: &mut Context<'_>,
+    ) -> Poll<Option<Result<RecordBatch>>> {
+        if let Some(deadline) = self.batch_deadline.as_mut() {
+            if deadline.poll_unpin(cx).is_ready() {
+                return Poll::Ready(self.emit_in_progress());
+            }
         }
+        Poll::Pending
+    }
+
+    /// Builds a [`RecordBatch`] from the rows merged so far
+    fn emit_in_progress(&mut self) -> Option<Result<RecordBatch>> {
+        self.batch_deadline = None;
+        self.produced += self.in_progress.len();
+        self.in_progress.build_record_batch().transpose()
     }
 
     fn fetch_reached(&mut self) -> bool {
@@ -27,7 +27,10 @@ use std::io::BufReader;
 use std::path::{Path, PathBuf};
 use std::sync::Arc;
 
-use crate::common::{spawn_buffered, IPCWriter};
+use crate::common::{
+    get_record_batch_memory_size, spawn_buffered, spill_write_options, IPCWriter,
+    SharedBufferTracker,
+};
 use crate::expressions::PhysicalSortExpr;
 use crate::metrics::{
     BaselineMetrics, Count, ExecutionPlanMetricsSet, MetricBuilder, MetricsSet,
@@ -48,6 +51,7 @@ use arrow::record_batch::RecordBatch;
 use arrow::row::{RowConverter, SortField};
 use arrow_array::{Array, RecordBatchOptions, UInt32Array};
 use arrow_schema::DataType;
+use datafusion_common::config::SpillCompression;
 use datafusion_common::{exec_err, DataFusionError, Result};
 use datafusion_common_runtime::SpawnedTask;
 use datafusion_execution::disk_manager::RefCountedTempFile;
@@ -74,6 +78,9 @@ struct ExternalSorterMetrics {
 
     /// total spilled rows during the execution of the operator
     spilled_rows: Count,
+
+    /// total size of the spill files on disk, after any compression
+    spill_file_bytes: Count,
 }
 
 impl ExternalSorterMetrics {
@@ -83,6 +90,8 @@ impl ExternalSorterMetrics {
             spill_count: MetricBuilder::new(metrics).spill_count(partition),
             spilled_bytes: MetricBuilder::new(metrics).spilled_bytes(partition),
             spilled_rows: MetricBuilder::new(metrics).spilled_rows(partition),
+            spill_file_bytes: MetricBuilder::new(metrics)
+                .counter("spill_file_bytes", partition),
         }
     }
 }
@@ -223,6 +232,9 @@ struct ExternalSorter {
     fetch: Option<usize>,
     /// Reservation for in_mem_batches
     reservation: MemoryReservation,
+    /// The buffers of in_mem_batches, for the buffers shared by several
+    /// batches to be reserved once
+    in_mem_buffers: SharedBufferTracker,
     /// Reservation for the merging of in-memory batches. If the sort
     /// might spill, `sort_spill_reservation_bytes` will be
     /// pre-reserved to ensure there is some space for this sort/merge.
@@ -238,6 +250,8 @@ struct ExternalSorter {
     /// the data will be concatenated and sorted in place rather than
     /// sort/merged.
     sort_in_place_threshold_bytes: usize,
+    /// Compression codec of the spill files
+    spill_compression: SpillCompression,
 }
 
 impl ExternalSorter {
@@ -252,6 +266,7 @@ impl ExternalSorter {
         fetch: Option<usize>,
         sort_spill_reservation_bytes: usize,
         sort_in_place_threshold_bytes: usize,
+        spill_compression: SpillCompression,
         metrics: &ExecutionPlanMetricsSet,
         runtime: Arc<RuntimeEnv>,
     ) -> Self {
@@ -273,11 +288,13 @@ impl ExternalSorter {
             metrics,
             fetch,
             reservation,
+            in_mem_buffers: SharedBufferTracker::default(),
             merge_reservation,
             runtime,
             batch_size,
             sort_spill_reservation_bytes,
             sort_in_place_threshold_bytes,
+            spill_compression,
         }
     }
 
@@ -290,10 +307,11 @@ impl ExternalSorter {
         }
         self.reserve_memory_for_merge()?;
 
-        let size = input.get_array_memory_size();
+        let mut size = self.in_mem_buffers.untracked_size(&input);
         if self.reservation.try_grow(size).is_err() {
             let before = self.reservation.size();
             self.in_mem_sort().await?;
+            size = self.in_mem_buffers.untracked_size(&input);
             // Sorting may have freed memory, especially if fetch is `Some`
             //
             // As such we check again, and if the memory usage has dropped by
@@ -307,10 +325,12 @@ impl ExternalSorter {
                 || self.reservation.try_grow(size).is_err()
             {
                 self.spill().await?;
+                size = self.in_mem_buffers.untracked_size(&input);
                 self.reservation.try_grow(size)?
             }
         }
 
+        self.in_mem_buffers.add(&input);
         self.in_mem_batches.push(input);
         self.in_mem_batches_sorted = false;
         Ok(())
@@ -401,12 +421,19 @@ impl ExternalSorter {
 
         let spill_file = self.runtime.disk_manager.create_tmp_file("Sorting")?;
         let batches = std::mem::take(&mut self.in_mem_batches);
-        let spilled_rows =
-            spill_sorted_batches(batches, spill_file.path(), self.schema.clone()).await?;
+        let (spilled_rows, spill_file_bytes) = spill_sorted_batches(
+            batches,
+            spill_file.path(),
+            self.schema.clone(),
+            self.spill_compression,
+        )
+        .await?;
         let used = self.reservation.free();
+        self.in_mem_buffers.clear();
         self.metrics.spill_count.add(1);
         self.metrics.spilled_bytes.add(used);
         self.metrics.spilled_rows.add(spilled_rows as usize);
+        self.metrics.spill_file_bytes.add(spill_file_bytes as usize);
         self.spills.push(spill_file);
         Ok(used)
     }
@@ -427,11 +454,11 @@ impl ExternalSorter {
             .try_collect()
             .await?;
 
-        let size: usize = self
-            .in_mem_batches
-            .iter()
-            .map(|x| x.get_array_memory_size())
-            .sum();
+        self.in_mem_buffers.clear();
+        for batch in &self.in_mem_batches {
+            self.in_mem_buffers.add(batch);
+        }
+        let size = self.in_mem_buffers.size();
 
         // Reserve headroom for next sort/merge
         self.reserve_memory_for_merge()?;
@@ -504,6 +531,7 @@ impl ExternalSorter {
         metrics: BaselineMetrics,
     ) -> Result<SendableRecordBatchStream> {
         assert_ne!(self.in_mem_batches.len(), 0);
+        self.in_mem_buffers.clear();
         if self.in_mem_batches.len() == 1 {
             let batch = self.in_mem_batches.remove(0);
             let reservation = self.reservation.take();
@@ -515,16 +543,19 @@ impl ExternalSorter {
             // Concatenate memory batches together and sort
             let batch = concat_batches(&self.schema, &self.in_mem_batches)?;
             self.in_mem_batches.clear();
-            self.reservation.try_resize(batch.get_array_memory_size())?;
+            self.reservation
+                .try_resize(get_record_batch_memory_size(&batch))?;
             let reservation = self.reservation.take();
             return self.sort_batch_stream(batch, metrics, reservation);
         }
 
+        // The buffers shared with the batches before are reserved with them
+        let mut buffers = SharedBufferTracker::default();
         let streams = std::mem::take(&mut self.in_mem_batches)
             .into_iter()
             .map(|batch| {
                 let metrics = self.metrics.baseline.intermediate();
-                let reservation = self.reservation.split(batch.get_array_memory_size());
+                let reservation = self.reservation.split(buffers.add(&batch));
                 let input = self.sort_batch_stream(batch, metrics, reservation)?;
                 Ok(spawn_buffered(input, 1))
             })
@@ -551,7 +582,7 @@ impl ExternalSorter {
         metrics: BaselineMetrics,
         reservation: MemoryReservation,
     ) -> Result<SendableRecordBatchStream> {
-        assert_eq!(batch.get_array_memory_size(), reservation.size());
+        debug_assert!(reservation.size() <= batch.get_array_memory_size());
         let schema = batch.schema();
 
         let fetch = self.fetch;
@@ -669,14 +700,17 @@ pub(crate) fn lexsort_to_indices_multi_columns(
 
 /// Spills sorted `in_memory_batches` to disk.
 ///
-/// Returns number of the rows spilled to disk.
+/// Returns number of the rows spilled to disk and the size of the spill file.
 async fn spill_sorted_batches(
     batches: Vec<RecordBatch>,
     path: &Path,
     schema: SchemaRef,
-) -> Result<u64> {
+    compression: SpillCompression,
+) -> Result<(u64, u64)> {
     let path: PathBuf = path.into();
-    let task = SpawnedTask::spawn_blocking(move || write_sorted(batches, path, schema));
+    let task = SpawnedTask::spawn_blocking(move || {
+        write_sorted(batches, path, schema, compression)
+    });
     match task.join().await {
         Ok(r) => r,
         Err(e) => exec_err!("Error occurred while spilling {e}"),
@@ -705,19 +739,26 @@ fn write_sorted(
     batches: Vec<RecordBatch>,
     path: PathBuf,
     schema: SchemaRef,
-) -> Result<u64> {
-    let mut writer = IPCWriter::new(path.as_ref(), schema.as_ref())?;
+    compression: SpillCompression,
+) -> Result<(u64, u64)> {
+    let mut writer = IPCWriter::new_with_options(
+        path.as_ref(),
+        schema.as_ref(),
+        spill_write_options(compression)?,
+    )?;
     for batch in batches {
         writer.write(&batch)?;
     }
     writer.finish()?;
+    let file_size = writer.file_size()?;
     debug!(
-        "Spilled {} batches of total {} rows to disk, memory released {}",
+        "Spilled {} batches of total {} rows to disk ({} on disk), memory released {}",
         writer.num_batches,
         writer.num_rows,
+        human_readable_size(file_size as usize),
         human_readable_size(writer.num_bytes as usize),
     );
-    Ok(writer.num_rows)
+    Ok((writer.num_rows, file_size))
 }
 
 fn read_spill(sender: Sender<Result<RecordBatch>>, path: &Path) -> Result<()> {
@@ -966,6 +1007,7 @@ impl ExecutionPlan for SortExec {
                 self.fetch,
                 execution_options.sort_spill_reservation_bytes,
                 execution_options.sort_in_place_threshold_bytes,
+                execution_options.spill_compression,
                 &self.metrics_set,
                 context.runtime_env(),
             );
@@ -1084,9 +1126,9 @@ mod tests {
 
         assert_eq!(metrics.output_rows().unwrap(), 10000);
         assert!(metrics.elapsed_compute().unwrap() > 0);
-        assert_eq!(metrics.spill_count().unwrap(), 4);
-        assert_eq!(metrics.spilled_bytes().unwrap(), 38784);
-        assert_eq!(metrics.spilled_rows().unwrap(), 9600);
+        assert_eq!(metrics.spill_count().unwrap(), 3);
+        assert_eq!(metrics.spilled_bytes().unwrap(), 36000);
+        assert_eq!(metrics.spilled_rows().unwrap(), 9000);
 
         let columns = result[0].columns();
 
@@ -1103,6 +1145,70 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_sort_spill_compression() -> Result<()> {
+        let mut spill_file_bytes = vec![];
+        for compression in [
+            SpillCompression::Uncompressed,
+            SpillCompression::Lz4Frame,
+            SpillCompression::Zstd,
+        ] {
+            let mut session_config = SessionConfig::new();
+            session_config.options_mut().execution.spill_compression = compression;
+            let sort_spill_reservation_bytes = session_config
+                .options()
+                .execution
+                .sort_spill_reservation_bytes;
+            let rt_config = RuntimeConfig::new()
+                .with_memory_limit(sort_spill_reservation_bytes + 12288, 1.0);
+            let runtime = Arc::new(RuntimeEnv::new(rt_config)?);
+            let task_ctx = Arc::new(
+                TaskContext::default()
+                    .with_session_config(session_config)
+                    .with_runtime(runtime),
+            );
+
+            let input = test::scan_partitioned(100);
+            let schema = input.schema();
+            let sort_exec = Arc::new(SortExec::new(
+                vec![PhysicalSortExpr {
+                    expr: col("i", &schema)?,
+                    options: SortOptions::default(),
+                }],
+                Arc::new(CoalescePartitionsExec::new(input)),
+            ));
+
+            let result = collect(sort_exec.clone(), task_ctx).await?;
+            assert_eq!(result.len(), 2);
+            let i = as_primitive_array::<Int32Type>(result[0].column(0))?;
+            assert_eq!(i.value(0), 0);
+            assert_eq!(i.value(i.len() - 1), 81);
+
+            // the compression does not change what is spilled
+            let metrics = sort_exec.metrics().unwrap();
+            assert_eq!(metrics.output_rows().unwrap(), 10000);
+            assert_eq!(metrics.spill_count().unwrap(), 3);
+            assert_eq!(metrics.spilled_bytes().unwrap(), 36000);
+            assert_eq!(metrics.spilled_rows().unwrap(), 9000);
+            spill_file_bytes.push(
+                metrics
+                    .sum_by_name("spill_file_bytes")
+                    .map(|v| v.as_usize())
+                    .unwrap(),
+            );
+        }
+
+        // but makes the spill files smaller
+        let [uncompressed, lz4, zstd] = spill_file_bytes[..] else {
+            unreachable!()
+        };
+        assert!(uncompressed > 0);
+        assert!(lz4 < uncompressed, "{lz4} < {uncompressed}");
+        assert!(zstd < uncompressed, "{zstd} < {uncompressed}");
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_sort_fetch_memory_calculation() -> Result<()> {
         // This test mirrors down the size from the example above.
@@ -19,11 +19,12 @@
 
 use std::any::Any;
 use std::sync::Arc;
+use std::time::Duration;
 
 use crate::common::spawn_buffered;
 use crate::expressions::PhysicalSortExpr;
 use crate::metrics::{BaselineMetrics, ExecutionPlanMetricsSet, MetricsSet};
-use crate::sorts::streaming_merge;
+use crate::sorts::streaming_merge::streaming_merge_with_max_batch_delay;
 use crate::{
     DisplayAs, DisplayFormatType, Distribution, ExecutionPlan, ExecutionPlanProperties,
     Partitioning, PlanProperties, SendableRecordBatchStream, Statistics,
@@ -233,7 +234,13 @@ impl ExecutionPlan for SortPreservingMergeExec {
 
                 debug!("Done setting up sender-receiver for SortPreservingMergeExec::execute");
 
-                let result = streaming_merge(
+                let max_batch_delay = context
+                    .session_config()
+                    .options()
+                    .execution
+                    .sort_preserving_merge_max_batch_delay_ms
+                    .map(Duration::from_millis);
+                let result = streaming_merge_with_max_batch_delay(
                     receivers,
                     schema,
                     &self.expr,
@@ -241,6 +248,7 @@ impl ExecutionPlan for SortPreservingMergeExec {
                     context.session_config().batch_size(),
                     self.fetch,
                     reservation,
+                    max_batch_delay,
                 )?;
 
                 debug!("Got stream result from SortPreservingMergeStream::new_from_receivers");
@@ -268,6 +276,7 @@ mod tests {
     use crate::memory::MemoryExec;
     use crate::metrics::{MetricValue, Timestamp};
     use crate::sorts::sort::SortExec;
+    use crate::sorts::streaming_merge;
     use crate::stream::RecordBatchReceiverStream;
     use crate::test::exec::{assert_strong_count_converges_to_zero, BlockingExec};
     use crate::test::{self, assert_is_pending, make_partition};
@@ -860,6 +869,70 @@ mod tests {
         Ok(())
     }
 
+    #[tokio::test]
+    async fn test_max_batch_delay() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int32, true)]));
+        let sort = vec![PhysicalSortExpr {
+            expr: col("a", &schema)?,
+            options: SortOptions::default(),
+        }];
+
+        // Each input produces a single batch and then waits for more input
+        // forever, so no more than the first 3 rows can be merged
+        let make_streams = || {
+            [vec![1, 2, 3], vec![10]]
+                .into_iter()
+                .map(|values| {
+                    let mut builder =
+                        RecordBatchReceiverStream::builder(schema.clone(), 1);
+                    let sender = builder.tx();
+                    let batch = RecordBatch::try_new(
+                        schema.clone(),
+                        vec![Arc::new(Int32Array::from(values))],
+                    );
+                    builder.spawn(async move {
+                        sender.send(batch.map_err(Into::into)).await.unwrap();
+                        futures::future::pending::<()>().await;
+                        Ok(())
+                    });
+                    builder.build()
+                })
+                .collect::<Vec<_>>()
+        };
+        let merge = |max_batch_delay| {
+            let metrics = ExecutionPlanMetricsSet::new();
+            let reservation =
+                MemoryConsumer::new("test").register(&task_ctx.runtime_env().memory_pool);
+            streaming_merge_with_max_batch_delay(
+                make_streams(),
+                schema.clone(),
+                &sort,
+                BaselineMetrics::new(&metrics, 0),
+                task_ctx.session_config().batch_size(),
+                None,
+                reservation,
+                max_batch_delay,
+            )
+        };
+
+        // without a delay, the merge waits for a full batch
+        let mut merge_stream = merge(None)?;
+        let next = tokio::time::timeout(Duration::from_millis(100), merge_stream.next());
+        assert!(next.await.is_err());
+
+        // with a delay, the merged rows are emitted after it elapsed
+        let mut merge_stream = merge(Some(Duration::from_millis(10)))?;
+        let next = tokio::time::timeout(Duration::from_secs(10), merge_stream.next());
+        let batch = next.await.expect("merge timed out").unwrap()?;
+        assert_batches_eq!(
+            &["+---+", "| a |", "+---+", "| 1 |", "| 2 |", "| 3 |", "+---+"],
+            &[batch]
+        );
+
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_merge_metrics() {
         let task_ctx = Arc::new(TaskContext::default());
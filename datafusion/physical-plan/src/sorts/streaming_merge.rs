@@ -28,6 +28,7 @@ use arrow::datatypes::{DataType, SchemaRef};
 use arrow_array::*;
 use datafusion_common::{internal_err, Result};
 use datafusion_execution::memory_pool::MemoryReservation;
+use std::time::Duration;
 
 macro_rules! primitive_merge_helper {
     ($t:ty, $($v:ident),+) => {
@@ -36,16 +37,19 @@ macro_rules! primitive_merge_helper {
 }
 
 macro_rules! merge_helper {
-    ($t:ty, $sort:ident, $streams:ident, $schema:ident, $tracking_metrics:ident, $batch_size:ident, $fetch:ident, $reservation:ident) => {{
+    ($t:ty, $sort:ident, $streams:ident, $schema:ident, $tracking_metrics:ident, $batch_size:ident, $fetch:ident, $reservation:ident, $max_batch_delay:ident) => {{
         let streams = FieldCursorStream::<$t>::new($sort, $streams);
-        return Ok(Box::pin(SortPreservingMergeStream::new(
-            Box::new(streams),
-            $schema,
-            $tracking_metrics,
-            $batch_size,
-            $fetch,
-            $reservation,
-        )));
+        return Ok(Box::pin(
+            SortPreservingMergeStream::new(
+                Box::new(streams),
+                $schema,
+                $tracking_metrics,
+                $batch_size,
+                $fetch,
+                $reservation,
+            )
+            .with_max_batch_delay($max_batch_delay),
+        ));
     }};
 }
 
@@ -59,6 +63,32 @@ pub fn streaming_merge(
     batch_size: usize,
     fetch: Option<usize>,
     reservation: MemoryReservation,
+) -> Result<SendableRecordBatchStream> {
+    streaming_merge_with_max_batch_delay(
+        streams,
+        schema,
+        expressions,
+        metrics,
+        batch_size,
+        fetch,
+        reservation,
+        None,
+    )
+}
+
+/// Perform a streaming merge like [`streaming_merge`], additionally emitting
+/// merged rows in a batch smaller than `batch_size` once they have been
+/// buffered for `max_batch_delay` while waiting for input
+#[allow(clippy::too_many_arguments)]
+pub fn streaming_merge_with_max_batch_delay(
+    streams: Vec<SendableRecordBatchStream>,
+    schema: SchemaRef,
+    expressions: &[PhysicalSortExpr],
+    metrics: BaselineMetrics,
+    batch_size: usize,
+    fetch: Option<usize>,
+    reservation: MemoryReservation,
+    max_batch_delay: Option<Duration>,
 ) -> Result<SendableRecordBatchStream> {
     // If there are no sort expressions, preserving the order
     // doesn't mean anything (and result in infinite loops)
@@ -70,11 +100,11 @@ pub fn streaming_merge(
         let sort = expressions[0].clone();
         let data_type = sort.expr.data_type(schema.as_ref())?;
         downcast_primitive! {
-            data_type => (primitive_merge_helper, sort, streams, schema, metrics, batch_size, fetch, reservation),
-            DataType::Utf8 => merge_helper!(StringArray, sort, streams, schema, metrics, batch_size, fetch, reservation)
-            DataType::LargeUtf8 => merge_helper!(LargeStringArray, sort, streams, schema, metrics, batch_size, fetch, reservation)
-            DataType::Binary => merge_helper!(BinaryArray, sort, streams, schema, metrics, batch_size, fetch, reservation)
-            DataType::LargeBinary => merge_helper!(LargeBinaryArray, sort, streams, schema, metrics, batch_size, fetch, reservation)
+            data_type => (primitive_merge_helper, sort, streams, schema, metrics, batch_size, fetch, reservation, max_batch_delay),
+            DataType::Utf8 => merge_helper!(StringArray, sort, streams, schema, metrics, batch_size, fetch, reservation, max_batch_delay)
+            DataType::LargeUtf8 => merge_helper!(LargeStringArray, sort, streams, schema, metrics, batch_size, fetch, reservation, max_batch_delay)
+            DataType::Binary => merge_helper!(BinaryArray, sort, streams, schema, metrics, batch_size, fetch, reservation, max_batch_delay)
+            DataType::LargeBinary => merge_helper!(LargeBinaryArray, sort, streams, schema, metrics, batch_size, fetch, reservation, max_batch_delay)
             _ => {}
         }
     }
@@ -86,12 +116,15 @@ pub fn streaming_merge(
         reservation.new_empty(),
     )?;
 
-    Ok(Box::pin(SortPreservingMergeStream::new(
-        Box::new(streams),
-        schema,
-        metrics,
-        batch_size,
-        fetch,
-        reservation,
-    )))
+    Ok(Box::pin(
+        SortPreservingMergeStream::new(
+            Box::new(streams),
+            schema,
+            metrics,
+            batch_size,
+            fetch,
+            reservation,
+        )
+        .with_max_batch_delay(max_batch_delay),
+    ))
 }
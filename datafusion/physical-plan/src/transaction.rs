@@ -0,0 +1,205 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! [`WriteTransaction`] publishing the writes to several [`DataSink`]s
+//! atomically
+//!
+//! [`DataSink`]: crate::insert::DataSink
+
+use std::fmt::Debug;
+use std::sync::Arc;
+
+use datafusion_common::Result;
+use datafusion_execution::TaskContext;
+
+use async_trait::async_trait;
+use parking_lot::Mutex;
+
+/// The data written to a [`DataSink`] in a [`WriteTransaction`], which is
+/// not visible until the transaction commits.
+///
+/// [`DataSink`]: crate::insert::DataSink
+#[async_trait]
+pub trait StagedWrite: Debug + Send + Sync {
+    /// Checks that the write can be published, e.g. that its table was not
+    /// changed concurrently. This is the first phase of the commit of the
+    /// transaction: if a write fails to prepare, all the writes of the
+    /// transaction are aborted.
+    async fn prepare(&self) -> Result<()> {
+        Ok(())
+    }
+
+    /// Publishes the data of the write. This is the second phase of the
+    /// commit of the transaction, which should not fail once all the writes
+    /// are prepared.
+    async fn commit(&self) -> Result<()>;
+
+    /// Discards the data of the write
+    async fn abort(&self) -> Result<()>;
+}
+
+/// Writes to several [`DataSink`]s which are published together, or not at
+/// all, with a two-phase commit.
+///
+/// While the transaction is set as an extension of the [`SessionConfig`],
+/// the [`DataSinkExec`]s stage the writes to their sinks with
+/// [`DataSink::stage_all`] instead of writing them, and add them to the
+/// transaction. The staged writes are not visible, even to the queries of
+/// the transaction, until [`commit`](Self::commit).
+///
+/// [`DataSink`]: crate::insert::DataSink
+/// [`DataSink::stage_all`]: crate::insert::DataSink::stage_all
+/// [`DataSinkExec`]: crate::insert::DataSinkExec
+/// [`SessionConfig`]: datafusion_execution::config::SessionConfig
+#[derive(Debug, Default)]
+pub struct WriteTransaction {
+    staged: Mutex<Vec<Arc<dyn StagedWrite>>>,
+}
+
+impl WriteTransaction {
+    /// Create a transaction with no writes
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Returns the transaction the task runs in, if any
+    pub fn of(context: &TaskContext) -> Option<Arc<Self>> {
+        context.session_config().get_extension::<Self>()
+    }
+
+    /// Add the staged write `write` to the transaction
+    pub fn add(&self, write: Arc<dyn StagedWrite>) {
+        self.staged.lock().push(write);
+    }
+
+    /// The number of writes staged in the transaction
+    pub fn num_staged(&self) -> usize {
+        self.staged.lock().len()
+    }
+
+    /// Publishes all the staged writes, once they are all prepared. If a
+    /// write fails to prepare, all the writes are aborted and its error is
+    /// returned.
+    pub async fn commit(&self) -> Result<()> {
+        let staged = std::mem::take(&mut *self.staged.lock());
+        for write in &staged {
+            if let Err(e) = write.prepare().await {
+                abort_all(&staged).await?;
+                return Err(e);
+            }
+        }
+        for write in &staged {
+            write.commit().await?;
+        }
+        Ok(())
+    }
+
+    /// Discards all the staged writes
+    pub async fn rollback(&self) -> Result<()> {
+        let staged = std::mem::take(&mut *self.staged.lock());
+        abort_all(&staged).await
+    }
+}
+
+/// Aborts all the writes `staged`, returning the first error once they all
+/// were aborted
+async fn abort_all(staged: &[Arc<dyn StagedWrite>]) -> Result<()> {
+    let mut result = Ok(());
+    for write in staged {
+        let aborted = write.abort().await;
+        if result.is_ok() {
+            result = aborted;
+        }
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use datafusion_common::exec_err;
+
+    /// Records the phases it went through in a shared log
+    #[derive(Debug)]
+    struct LoggedWrite {
+        name: &'static str,
+        fail_prepare: bool,
+        log: Arc<Mutex<Vec<String>>>,
+    }
+
+    #[async_trait]
+    impl StagedWrite for LoggedWrite {
+        async fn prepare(&self) -> Result<()> {
+            self.log.lock().push(format!("prepare {}", self.name));
+            if self.fail_prepare {
+                return exec_err!("{} changed concurrently", self.name);
+            }
+            Ok(())
+        }
+
+        async fn commit(&self) -> Result<()> {
+            self.log.lock().push(format!("commit {}", self.name));
+            Ok(())
+        }
+
+        async fn abort(&self) -> Result<()> {
+            self.log.lock().push(format!("abort {}", self.name));
+            Ok(())
+        }
+    }
+
+    fn transaction(fail_prepare: &[bool]) -> (WriteTransaction, Arc<Mutex<Vec<String>>>) {
+        let log = Arc::new(Mutex::new(vec![]));
+        let transaction = WriteTransaction::new();
+        for (name, fail_prepare) in ["a", "b"].into_iter().zip(fail_prepare) {
+            transaction.add(Arc::new(LoggedWrite {
+                name,
+                fail_prepare: *fail_prepare,
+                log: log.clone(),
+            }));
+        }
+        (transaction, log)
+    }
+
+    #[tokio::test]
+    async fn commit_all_writes() -> Result<()> {
+        let (transaction, log) = transaction(&[false, false]);
+        assert_eq!(transaction.num_staged(), 2);
+        transaction.commit().await?;
+        assert_eq!(
+            *log.lock(),
+            ["prepare a", "prepare b", "commit a", "commit b"]
+        );
+        assert_eq!(transaction.num_staged(), 0);
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn abort_all_writes_on_failed_prepare() {
+        let (transaction, log) = transaction(&[false, true]);
+        let err = transaction.commit().await.unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Execution error: b changed concurrently"
+        );
+        assert_eq!(
+            *log.lock(),
+            ["prepare a", "prepare b", "abort a", "abort b"]
+        );
+    }
+}
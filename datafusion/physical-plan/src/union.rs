@@ -34,14 +34,16 @@ use super::{
     SendableRecordBatchStream, Statistics,
 };
 use crate::metrics::BaselineMetrics;
+use crate::sorts::streaming_merge::streaming_merge;
 use crate::stream::ObservedStream;
 
 use arrow::datatypes::{Field, Schema, SchemaRef};
 use arrow::record_batch::RecordBatch;
 use datafusion_common::stats::Precision;
 use datafusion_common::{exec_err, internal_err, Result};
+use datafusion_execution::memory_pool::MemoryConsumer;
 use datafusion_execution::TaskContext;
-use datafusion_physical_expr::EquivalenceProperties;
+use datafusion_physical_expr::{EquivalenceProperties, PhysicalSortExpr};
 
 use futures::Stream;
 use itertools::Itertools;
@@ -285,7 +287,15 @@ impl ExecutionPlan for UnionExec {
 
 /// Combines multiple input streams by interleaving them.
 ///
-/// This only works if all inputs have the same hash-partitioning.
+/// Output partition `i` combines partition `i` of every input, so unlike
+/// [`UnionExec`] the partition count of the inputs is preserved. This only
+/// works if all inputs have the same number of partitions. When all inputs
+/// also have the same hash-partitioning, it is preserved as well.
+///
+/// If all inputs share an output ordering, the corresponding partitions are
+/// lazily merged with a sort-preserving merge so that every output partition
+/// keeps that ordering. Otherwise batches are emitted in the order they become
+/// available.
 ///
 /// # Data Flow
 /// ```text
@@ -319,6 +329,9 @@ impl ExecutionPlan for UnionExec {
 pub struct InterleaveExec {
     /// Input execution plan
     inputs: Vec<Arc<dyn ExecutionPlan>>,
+    /// Ordering shared by all the inputs, preserved by merging the
+    /// corresponding input partitions
+    sort_exprs: Option<Vec<PhysicalSortExpr>>,
     /// Execution metrics
     metrics: ExecutionPlanMetricsSet,
     /// Cache holding plan properties like equivalences, output partitioning etc.
@@ -328,14 +341,16 @@ pub struct InterleaveExec {
 impl InterleaveExec {
     /// Create a new InterleaveExec
     pub fn try_new(inputs: Vec<Arc<dyn ExecutionPlan>>) -> Result<Self> {
-        if !can_interleave(inputs.iter()) {
+        if !can_interleave_partitions(inputs.iter()) {
             return internal_err!(
-                "Not all InterleaveExec children have a consistent hash partitioning"
+                "Not all InterleaveExec children have the same number of partitions"
             );
         }
-        let cache = Self::compute_properties(&inputs);
+        let sort_exprs = common_output_ordering(&inputs);
+        let cache = Self::compute_properties(&inputs, sort_exprs.as_deref());
         Ok(InterleaveExec {
             inputs,
+            sort_exprs,
             metrics: ExecutionPlanMetricsSet::new(),
             cache,
         })
@@ -346,12 +361,31 @@ impl InterleaveExec {
         &self.inputs
     }
 
+    /// The ordering shared by all the inputs, if any. When present, every
+    /// output partition is produced in this order.
+    pub fn sort_exprs(&self) -> Option<&[PhysicalSortExpr]> {
+        self.sort_exprs.as_deref()
+    }
+
     /// This function creates the cache object that stores the plan properties such as schema, equivalence properties, ordering, partitioning, etc.
-    fn compute_properties(inputs: &[Arc<dyn ExecutionPlan>]) -> PlanProperties {
+    fn compute_properties(
+        inputs: &[Arc<dyn ExecutionPlan>],
+        sort_exprs: Option<&[PhysicalSortExpr]>,
+    ) -> PlanProperties {
         let schema = union_schema(inputs);
-        let eq_properties = EquivalenceProperties::new(schema);
-        // Get output partitioning:
-        let output_partitioning = inputs[0].output_partitioning().clone();
+        let mut eq_properties = EquivalenceProperties::new(schema);
+        if let Some(sort_exprs) = sort_exprs {
+            eq_properties.add_new_orderings([sort_exprs.to_vec()]);
+        }
+        // Get output partitioning, the hash partitioning is only kept when
+        // all the inputs agree on it:
+        let output_partitioning = if can_interleave(inputs.iter()) {
+            inputs[0].output_partitioning().clone()
+        } else {
+            Partitioning::UnknownPartitioning(
+                inputs[0].output_partitioning().partition_count(),
+            )
+        };
         // Determine execution mode:
         let mode = execution_mode_from_children(inputs.iter());
 
@@ -367,7 +401,15 @@ impl DisplayAs for InterleaveExec {
     ) -> std::fmt::Result {
         match t {
             DisplayFormatType::Default | DisplayFormatType::Verbose => {
-                write!(f, "InterleaveExec")
+                write!(f, "InterleaveExec")?;
+                if let Some(sort_exprs) = self.sort_exprs() {
+                    write!(
+                        f,
+                        ": preserve_order=true, sort_exprs={}",
+                        PhysicalSortExpr::format_list(sort_exprs)
+                    )?;
+                }
+                Ok(())
             }
         }
     }
@@ -392,7 +434,7 @@ impl ExecutionPlan for InterleaveExec {
     }
 
     fn maintains_input_order(&self) -> Vec<bool> {
-        vec![false; self.inputs().len()]
+        vec![self.sort_exprs.is_some(); self.inputs().len()]
     }
 
     fn with_new_children(
@@ -424,6 +466,20 @@ impl ExecutionPlan for InterleaveExec {
             }
         }
         if input_stream_vec.len() == self.inputs.len() {
+            if let Some(sort_exprs) = self.sort_exprs() {
+                let reservation =
+                    MemoryConsumer::new(format!("InterleaveExec[{partition}]"))
+                        .register(&context.runtime_env().memory_pool);
+                return streaming_merge(
+                    input_stream_vec,
+                    self.schema(),
+                    sort_exprs,
+                    baseline_metrics,
+                    context.session_config().batch_size(),
+                    None,
+                    reservation,
+                );
+            }
             let stream = Box::pin(CombinedRecordBatchStream::new(
                 self.schema(),
                 input_stream_vec,
@@ -478,6 +534,35 @@ pub fn can_interleave<T: Borrow<Arc<dyn ExecutionPlan>>>(
             .all(|partition| partition == *reference)
 }
 
+/// Returns true if all the inputs have the same, non zero, number of output
+/// partitions, in which case they can be combined by an [`InterleaveExec`]
+/// regardless of how they are partitioned.
+pub fn can_interleave_partitions<T: Borrow<Arc<dyn ExecutionPlan>>>(
+    mut inputs: impl Iterator<Item = T>,
+) -> bool {
+    let Some(first) = inputs.next() else {
+        return false;
+    };
+
+    let partition_count = first.borrow().output_partitioning().partition_count();
+    partition_count > 0
+        && inputs.all(|plan| {
+            plan.borrow().output_partitioning().partition_count() == partition_count
+        })
+}
+
+/// Returns the output ordering of the first input if every other input
+/// satisfies it as well
+fn common_output_ordering(
+    inputs: &[Arc<dyn ExecutionPlan>],
+) -> Option<Vec<PhysicalSortExpr>> {
+    let ordering = inputs.first()?.output_ordering()?;
+    inputs[1..]
+        .iter()
+        .all(|input| input.equivalence_properties().ordering_satisfy(ordering))
+        .then(|| ordering.to_vec())
+}
+
 fn union_schema(inputs: &[Arc<dyn ExecutionPlan>]) -> SchemaRef {
     let fields: Vec<Field> = (0..inputs[0].schema().fields().len())
         .map(|i| {
@@ -572,7 +657,14 @@ fn col_stats_union(
     mut left: ColumnStatistics,
     right: ColumnStatistics,
 ) -> ColumnStatistics {
-    left.distinct_count = Precision::Absent;
+    left.distinct_sketch = match (&left.distinct_sketch, &right.distinct_sketch) {
+        (Some(left), Some(right)) => Some(left.merge(right)),
+        _ => None,
+    };
+    left.distinct_count = match &left.distinct_sketch {
+        Some(sketch) => Precision::Inexact(sketch.count()),
+        None => Precision::Absent,
+    };
     left.min_value = left.min_value.min(&right.min_value);
     left.max_value = left.max_value.max(&right.max_value);
     left.null_count = left.null_count.add(&right.null_count);
@@ -667,18 +759,21 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(21))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(0),
+                    distinct_sketch: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Exact(1),
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Exact(3),
+                    distinct_sketch: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Exact(ScalarValue::Float32(Some(1.1))),
                     min_value: Precision::Exact(ScalarValue::Float32(Some(0.1))),
                     null_count: Precision::Absent,
+                    distinct_sketch: None,
                 },
             ],
         };
@@ -692,18 +787,21 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(34))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(1))),
                     null_count: Precision::Exact(1),
+                    distinct_sketch: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Exact(ScalarValue::from("c")),
                     min_value: Precision::Exact(ScalarValue::from("b")),
                     null_count: Precision::Absent,
+                    distinct_sketch: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Absent,
                     min_value: Precision::Absent,
                     null_count: Precision::Absent,
+                    distinct_sketch: None,
                 },
             ],
         };
@@ -718,18 +816,21 @@ mod tests {
                     max_value: Precision::Exact(ScalarValue::Int64(Some(34))),
                     min_value: Precision::Exact(ScalarValue::Int64(Some(-4))),
                     null_count: Precision::Exact(1),
+                    distinct_sketch: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Exact(ScalarValue::from("x")),
                     min_value: Precision::Exact(ScalarValue::from("a")),
                     null_count: Precision::Absent,
+                    distinct_sketch: None,
                 },
                 ColumnStatistics {
                     distinct_count: Precision::Absent,
                     max_value: Precision::Absent,
                     min_value: Precision::Absent,
                     null_count: Precision::Absent,
+                    distinct_sketch: None,
                 },
             ],
         };
@@ -837,4 +938,87 @@ mod tests {
         }
         Ok(())
     }
+
+    #[tokio::test]
+    async fn test_interleave_partitions() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+
+        // Inputs are not hash partitioned, but have the same partition count
+        let csv = test::scan_partitioned(3);
+        let csv2 = test::scan_partitioned(3);
+
+        let interleave = Arc::new(InterleaveExec::try_new(vec![csv, csv2])?);
+        assert!(matches!(
+            interleave.properties().output_partitioning(),
+            Partitioning::UnknownPartitioning(3)
+        ));
+
+        for partition in 0..3 {
+            let stream = interleave.execute(partition, task_ctx.clone())?;
+            let batches = crate::common::collect(stream).await?;
+            assert_eq!(batches.len(), 2);
+        }
+
+        // Inputs with different partition counts can not be interleaved
+        let csv = test::scan_partitioned(3);
+        let csv2 = test::scan_partitioned(4);
+        assert!(InterleaveExec::try_new(vec![csv, csv2]).is_err());
+
+        Ok(())
+    }
+
+    #[tokio::test]
+    async fn test_interleave_preserves_ordering() -> Result<()> {
+        let task_ctx = Arc::new(TaskContext::default());
+        let schema = test::make_partition(0).schema();
+        let sort_exprs = vec![PhysicalSortExpr {
+            expr: col("i", &schema)?,
+            options: SortOptions::default(),
+        }];
+
+        let sorted_input = |values: Vec<Vec<i32>>| -> Result<Arc<dyn ExecutionPlan>> {
+            let partitions = values
+                .into_iter()
+                .map(|values| {
+                    let array = Arc::new(arrow::array::Int32Array::from(values));
+                    Ok(vec![RecordBatch::try_new(schema.clone(), vec![array])?])
+                })
+                .collect::<Result<Vec<_>>>()?;
+            Ok(Arc::new(
+                MemoryExec::try_new(&partitions, schema.clone(), None)?
+                    .with_sort_information(vec![sort_exprs.clone()]),
+            ))
+        };
+        let input1 = sorted_input(vec![vec![1, 4, 7], vec![2, 2]])?;
+        let input2 = sorted_input(vec![vec![0, 5], vec![1, 3, 8]])?;
+
+        let interleave = Arc::new(InterleaveExec::try_new(vec![input1, input2])?);
+        assert_eq!(interleave.sort_exprs(), Some(sort_exprs.as_slice()));
+        assert_eq!(interleave.maintains_input_order(), vec![true, true]);
+        assert_eq!(
+            interleave.properties().output_ordering(),
+            Some(sort_exprs.as_slice())
+        );
+
+        let expected = [vec![0, 1, 4, 5, 7], vec![1, 2, 2, 3, 8]];
+        for (partition, expected) in expected.iter().enumerate() {
+            let stream = interleave.execute(partition, task_ctx.clone())?;
+            let batches = crate::common::collect(stream).await?;
+            let values = batches
+                .iter()
+                .flat_map(|batch| {
+                    batch
+                        .column(0)
+                        .as_any()
+                        .downcast_ref::<arrow::array::Int32Array>()
+                        .unwrap()
+                        .values()
+                        .to_vec()
+                })
+                .collect::<Vec<_>>();
+            assert_eq!(&values, expected);
+        }
+
+        Ok(())
+    }
 }
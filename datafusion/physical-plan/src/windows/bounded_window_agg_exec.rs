@@ -1037,8 +1037,11 @@ impl BoundedWindowAggStream {
         for window_agg_state in self.window_agg_states.iter_mut() {
             window_agg_state.retain(|_, WindowState { state, .. }| !state.is_end);
             for (partition_row, WindowState { state: value, .. }) in window_agg_state {
-                let n_prune =
+                let mut n_prune =
                     min(value.window_frame_range.start, value.last_calculated_index);
+                if let Some(peer_group_start) = value.peer_group_start {
+                    n_prune = min(n_prune, peer_group_start);
+                }
                 if let Some(current) = n_prune_each_partition.get_mut(partition_row) {
                     if n_prune < *current {
                         *current = n_prune;
@@ -1180,7 +1183,10 @@ mod tests {
     use crate::memory::MemoryExec;
     use crate::projection::ProjectionExec;
     use crate::streaming::{PartitionStream, StreamingTableExec};
-    use crate::windows::{create_window_expr, BoundedWindowAggExec, InputOrderMode};
+    use crate::windows::{
+        create_window_expr, with_exact_partition_rows, BoundedWindowAggExec,
+        InputOrderMode, WindowExpr,
+    };
     use crate::{execute_stream, get_plan_string, ExecutionPlan};
 
     use arrow_array::builder::{Int64Builder, UInt64Builder};
@@ -1197,7 +1203,9 @@ mod tests {
         AggregateFunction, WindowFrame, WindowFrameBound, WindowFrameUnits,
         WindowFunctionDefinition,
     };
-    use datafusion_physical_expr::expressions::{col, Column, NthValue};
+    use datafusion_physical_expr::expressions::{
+        col, cume_dist, percent_rank, Column, NthValue, Ntile,
+    };
     use datafusion_physical_expr::window::{
         BuiltInWindowExpr, BuiltInWindowFunctionExpr,
     };
@@ -1756,4 +1764,116 @@ mod tests {
 
         Ok(())
     }
+
+    // With the exact number of rows of the input, the window functions which
+    // depend on the number of rows of their partition run with bounded memory
+    #[tokio::test]
+    async fn bounded_window_exec_exact_partition_rows() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+        let batch = |a: Vec<i64>| {
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![Arc::new(arrow_array::Int64Array::from(a))],
+            )
+        };
+        // The peer group of `a = 2` spans both batches
+        let input = Arc::new(MemoryExec::try_new(
+            &[vec![batch(vec![1, 2, 2])?, batch(vec![2, 3])?]],
+            schema.clone(),
+            None,
+        )?) as Arc<dyn ExecutionPlan>;
+
+        let order_by = vec![PhysicalSortExpr {
+            expr: col("a", &schema)?,
+            options: SortOptions::default(),
+        }];
+        let window_frame = Arc::new(WindowFrame::new(Some(false)));
+        let window_expr = |expr: Arc<dyn BuiltInWindowFunctionExpr>| {
+            Arc::new(BuiltInWindowExpr::new(
+                expr,
+                &[],
+                &order_by,
+                window_frame.clone(),
+            )) as Arc<dyn WindowExpr>
+        };
+        let window_exprs = vec![
+            window_expr(Arc::new(Ntile::new("ntile".into(), 2, &DataType::UInt64))),
+            window_expr(Arc::new(percent_rank(
+                "percent_rank".into(),
+                &DataType::Float64,
+            ))),
+            window_expr(Arc::new(cume_dist("cume_dist".into(), &DataType::Float64))),
+        ];
+        assert!(window_exprs.iter().all(|e| !e.uses_bounded_memory()));
+        let window_exprs = with_exact_partition_rows(window_exprs, &input)?;
+        assert!(window_exprs.iter().all(|e| e.uses_bounded_memory()));
+
+        let window = Arc::new(BoundedWindowAggExec::try_new(
+            window_exprs,
+            input,
+            vec![],
+            InputOrderMode::Sorted,
+        )?);
+        let batches = collect(window.execute(0, task_context())?).await?;
+        let expected = [
+            "+---+-------+--------------+-----------+",
+            "| a | ntile | percent_rank | cume_dist |",
+            "+---+-------+--------------+-----------+",
+            "| 1 | 1     | 0.0          | 0.2       |",
+            "| 2 | 1     | 0.25         | 0.8       |",
+            "| 2 | 1     | 0.25         | 0.8       |",
+            "| 2 | 2     | 0.25         | 0.8       |",
+            "| 3 | 2     | 1.0          | 1.0       |",
+            "+---+-------+--------------+-----------+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
+    // The partition must have the number of rows the window functions were
+    // created for
+    #[tokio::test]
+    async fn bounded_window_exec_unexpected_partition_rows() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, false)]));
+        let input = |a: Vec<i64>| -> Result<Arc<dyn ExecutionPlan>> {
+            let batch = RecordBatch::try_new(
+                schema.clone(),
+                vec![Arc::new(arrow_array::Int64Array::from(a))],
+            )?;
+            Ok(Arc::new(MemoryExec::try_new(
+                &[vec![batch]],
+                schema.clone(),
+                None,
+            )?))
+        };
+
+        let order_by = vec![PhysicalSortExpr {
+            expr: col("a", &schema)?,
+            options: SortOptions::default(),
+        }];
+        let window_exprs = vec![Arc::new(BuiltInWindowExpr::new(
+            Arc::new(Ntile::new("ntile".into(), 2, &DataType::UInt64)),
+            &[],
+            &order_by,
+            Arc::new(WindowFrame::new(Some(false))),
+        )) as Arc<dyn WindowExpr>];
+        let window_exprs =
+            with_exact_partition_rows(window_exprs, &input(vec![1, 2, 3])?)?;
+        assert!(window_exprs.iter().all(|e| e.uses_bounded_memory()));
+
+        let window = Arc::new(BoundedWindowAggExec::try_new(
+            window_exprs,
+            input(vec![1, 2])?,
+            vec![],
+            InputOrderMode::Sorted,
+        )?);
+        let err = collect(window.execute(0, task_context())?)
+            .await
+            .unwrap_err();
+        assert_eq!(
+            err.strip_backtrace(),
+            "Execution error: ntile expected partitions of 3 rows, got 2"
+        );
+        Ok(())
+    }
 }
@@ -24,13 +24,14 @@ use crate::{
     aggregates,
     expressions::{
         cume_dist, dense_rank, lag, lead, percent_rank, rank, Literal, NthValue, Ntile,
-        PhysicalSortExpr, RowNumber,
+        PhysicalSortExpr, RowNumber, SessionWindow,
     },
     udaf, ExecutionPlan, ExecutionPlanProperties, InputOrderMode, PhysicalExpr,
 };
 
 use arrow::datatypes::Schema;
 use arrow_schema::{DataType, Field, SchemaRef};
+use datafusion_common::stats::Precision;
 use datafusion_common::{exec_err, DataFusionError, Result, ScalarValue};
 use datafusion_expr::{
     BuiltInWindowFunction, PartitionEvaluator, WindowFrame, WindowFunctionDefinition,
@@ -280,6 +281,25 @@ fn create_built_in_window_expr(
                 ignore_nulls,
             ))
         }
+        BuiltInWindowFunction::SessionWindow => {
+            let arg = args[0].clone();
+            let gap = get_scalar_value_from_args(args, 1)?
+                .filter(|gap| !gap.is_null())
+                .ok_or_else(|| {
+                    DataFusionError::Execution(
+                        "SESSION_WINDOW requires a non-NULL constant gap".to_string(),
+                    )
+                })?;
+            // Numeric gaps are compared in the type of the ordering column,
+            // temporal columns use interval or duration gaps as is
+            let arg_type = arg.data_type(input_schema)?;
+            let gap = if arg_type.is_numeric() {
+                gap.cast_to(&arg_type)?
+            } else {
+                gap
+            };
+            Arc::new(SessionWindow::new(name, out_data_type, arg, gap))
+        }
     })
 }
 
@@ -423,6 +443,37 @@ pub(crate) fn window_equivalence_properties(
     window_eq_properties
 }
 
+/// Returns `window_exprs`, without `PARTITION BY`, evaluated knowing the
+/// number of rows of their single partition when `input`, e.g. a sorted scan,
+/// has an exact number of rows, if they can then all run with bounded memory.
+/// For example, `NTILE`, `PERCENT_RANK` and `CUME_DIST` then don't need to
+/// buffer the whole partition. Their execution fails if the partition turns
+/// out to have another number of rows.
+pub fn with_exact_partition_rows(
+    window_exprs: Vec<Arc<dyn WindowExpr>>,
+    input: &Arc<dyn ExecutionPlan>,
+) -> Result<Vec<Arc<dyn WindowExpr>>> {
+    if window_exprs.iter().all(|e| e.uses_bounded_memory())
+        || window_exprs.iter().any(|e| !e.partition_by().is_empty())
+    {
+        return Ok(window_exprs);
+    }
+    let Precision::Exact(num_rows) = input.statistics()?.num_rows else {
+        return Ok(window_exprs);
+    };
+    let counted_exprs = window_exprs
+        .iter()
+        .map(|e| {
+            if e.uses_bounded_memory() {
+                Some(e.clone())
+            } else {
+                e.with_partition_rows(num_rows)
+            }
+        })
+        .collect::<Option<Vec<_>>>();
+    Ok(counted_exprs.unwrap_or(window_exprs))
+}
+
 /// Constructs the best-fitting windowing operator (a `WindowAggExec` or a
 /// `BoundedWindowExec`) for the given `input` according to the specifications
 /// of `window_exprs` and `physical_partition_keys`. Here, best-fitting means
@@ -475,6 +526,7 @@ pub fn get_best_fitting_window(
     } else {
         window_exprs.to_vec()
     };
+    let window_expr = with_exact_partition_rows(window_expr, input)?;
 
     // If all window expressions can run with bounded memory, choose the
     // bounded window variant:
@@ -553,12 +605,17 @@ mod tests {
     use crate::aggregates::AggregateFunction;
     use crate::collect;
     use crate::expressions::col;
+    use crate::memory::MemoryExec;
     use crate::streaming::StreamingTableExec;
     use crate::test::assert_is_pending;
     use crate::test::exec::{assert_strong_count_converges_to_zero, BlockingExec};
 
     use arrow::compute::SortOptions;
+    use arrow::record_batch::RecordBatch;
+    use arrow_array::Int64Array;
+    use datafusion_common::assert_batches_eq;
     use datafusion_execution::TaskContext;
+    use datafusion_expr::{WindowFrameBound, WindowFrameUnits};
 
     use futures::FutureExt;
 
@@ -724,6 +781,83 @@ mod tests {
         Ok(())
     }
 
+    // The aggregates over whole partition frames are computed once each
+    // partition ended, and repeated for all its rows
+    #[tokio::test]
+    async fn test_window_agg_exec_whole_partition_frame() -> Result<()> {
+        let schema = Arc::new(Schema::new(vec![
+            Field::new("p", DataType::Int64, false),
+            Field::new("v", DataType::Int64, false),
+        ]));
+        let batch = |p: Vec<i64>, v: Vec<i64>| {
+            RecordBatch::try_new(
+                schema.clone(),
+                vec![Arc::new(Int64Array::from(p)), Arc::new(Int64Array::from(v))],
+            )
+        };
+        let partition_by = vec![col("p", &schema)?];
+        let input = Arc::new(
+            MemoryExec::try_new(
+                &[vec![
+                    batch(vec![1, 1, 2], vec![1, 2, 3])?,
+                    batch(vec![2, 2, 3], vec![4, 5, 6])?,
+                ]],
+                schema.clone(),
+                None,
+            )?
+            .with_sort_information(vec![vec![PhysicalSortExpr {
+                expr: partition_by[0].clone(),
+                options: SortOptions::default(),
+            }]]),
+        );
+
+        // ROWS BETWEEN UNBOUNDED PRECEDING AND UNBOUNDED FOLLOWING
+        let window_frame = Arc::new(WindowFrame::new_bounds(
+            WindowFrameUnits::Rows,
+            WindowFrameBound::Preceding(ScalarValue::UInt64(None)),
+            WindowFrameBound::Following(ScalarValue::UInt64(None)),
+        ));
+        let window_expr = |fun: AggregateFunction, name: &str| {
+            create_window_expr(
+                &WindowFunctionDefinition::AggregateFunction(fun),
+                name.to_string(),
+                &[col("v", &schema)?],
+                &partition_by,
+                &[],
+                window_frame.clone(),
+                &schema,
+                false,
+            )
+        };
+        let window_exprs = vec![
+            window_expr(AggregateFunction::Sum, "sum")?,
+            window_expr(AggregateFunction::Max, "max")?,
+        ];
+        // such frames are never bounded, and run in WindowAggExec
+        assert!(window_exprs.iter().all(|expr| !expr.uses_bounded_memory()));
+        let window = Arc::new(WindowAggExec::try_new(
+            window_exprs,
+            input,
+            partition_by.clone(),
+        )?);
+
+        let batches = collect(window, Arc::new(TaskContext::default())).await?;
+        let expected = [
+            "+---+---+-----+-----+",
+            "| p | v | sum | max |",
+            "+---+---+-----+-----+",
+            "| 1 | 1 | 3   | 2   |",
+            "| 1 | 2 | 3   | 2   |",
+            "| 2 | 3 | 12  | 5   |",
+            "| 2 | 4 | 12  | 5   |",
+            "| 2 | 5 | 12  | 5   |",
+            "| 3 | 6 | 6   | 6   |",
+            "+---+---+-----+-----+",
+        ];
+        assert_batches_eq!(expected, &batches);
+        Ok(())
+    }
+
     #[tokio::test]
     async fn test_satisfiy_nullable() -> Result<()> {
         let schema = create_test_schema()?;
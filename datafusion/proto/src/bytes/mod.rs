@@ -24,6 +24,10 @@ use crate::physical_plan::{
     AsExecutionPlan, DefaultPhysicalExtensionCodec, PhysicalExtensionCodec,
 };
 use crate::protobuf;
+use crate::version::{
+    decode_logical_plan, decode_physical_plan, encode_logical_plan, encode_physical_plan,
+    PLAN_FORMAT_VERSION,
+};
 use datafusion_common::{plan_datafusion_err, Result};
 use datafusion_expr::{
     create_udaf, create_udf, create_udwf, AggregateUDF, Expr, LogicalPlan, Volatility,
@@ -205,14 +209,22 @@ pub fn logical_plan_to_json(plan: &LogicalPlan) -> Result<String> {
 pub fn logical_plan_to_bytes_with_extension_codec(
     plan: &LogicalPlan,
     extension_codec: &dyn LogicalExtensionCodec,
+) -> Result<Bytes> {
+    logical_plan_to_bytes_with_format_version(plan, extension_codec, PLAN_FORMAT_VERSION)
+}
+
+/// Serialize a LogicalPlan as bytes in the format version `version`, which
+/// the older releases supporting at most this version can deserialize
+///
+/// See [`version`](crate::version) for the versioning of the plans.
+pub fn logical_plan_to_bytes_with_format_version(
+    plan: &LogicalPlan,
+    extension_codec: &dyn LogicalExtensionCodec,
+    version: u32,
 ) -> Result<Bytes> {
     let protobuf =
         protobuf::LogicalPlanNode::try_from_logical_plan(plan, extension_codec)?;
-    let mut buffer = BytesMut::new();
-    protobuf
-        .encode(&mut buffer)
-        .map_err(|e| plan_datafusion_err!("Error encoding protobuf as bytes: {e}"))?;
-    Ok(buffer.into())
+    encode_logical_plan(&protobuf, version)
 }
 
 /// Deserialize a LogicalPlan from JSON
@@ -233,14 +245,14 @@ pub fn logical_plan_from_bytes(
     logical_plan_from_bytes_with_extension_codec(bytes, ctx, &extension_codec)
 }
 
-/// Deserialize a LogicalPlan from bytes
+/// Deserialize a LogicalPlan from bytes, serialized with any format version
+/// up to [`PLAN_FORMAT_VERSION`]
 pub fn logical_plan_from_bytes_with_extension_codec(
     bytes: &[u8],
     ctx: &SessionContext,
     extension_codec: &dyn LogicalExtensionCodec,
 ) -> Result<LogicalPlan> {
-    let protobuf = protobuf::LogicalPlanNode::decode(bytes)
-        .map_err(|e| plan_datafusion_err!("Error decoding expr as protobuf: {e}"))?;
+    let protobuf = decode_logical_plan(bytes)?;
     protobuf.try_into_logical_plan(ctx, extension_codec)
 }
 
@@ -265,14 +277,22 @@ pub fn physical_plan_to_json(plan: Arc<dyn ExecutionPlan>) -> Result<String> {
 pub fn physical_plan_to_bytes_with_extension_codec(
     plan: Arc<dyn ExecutionPlan>,
     extension_codec: &dyn PhysicalExtensionCodec,
+) -> Result<Bytes> {
+    physical_plan_to_bytes_with_format_version(plan, extension_codec, PLAN_FORMAT_VERSION)
+}
+
+/// Serialize a PhysicalPlan as bytes in the format version `version`, which
+/// the older releases supporting at most this version can deserialize
+///
+/// See [`version`](crate::version) for the versioning of the plans.
+pub fn physical_plan_to_bytes_with_format_version(
+    plan: Arc<dyn ExecutionPlan>,
+    extension_codec: &dyn PhysicalExtensionCodec,
+    version: u32,
 ) -> Result<Bytes> {
     let protobuf =
         protobuf::PhysicalPlanNode::try_from_physical_plan(plan, extension_codec)?;
-    let mut buffer = BytesMut::new();
-    protobuf
-        .encode(&mut buffer)
-        .map_err(|e| plan_datafusion_err!("Error encoding protobuf as bytes: {e}"))?;
-    Ok(buffer.into())
+    encode_physical_plan(&protobuf, version)
 }
 
 /// Deserialize a PhysicalPlan from JSON
@@ -296,13 +316,13 @@ pub fn physical_plan_from_bytes(
     physical_plan_from_bytes_with_extension_codec(bytes, ctx, &extension_codec)
 }
 
-/// Deserialize a PhysicalPlan from bytes
+/// Deserialize a PhysicalPlan from bytes, serialized with any format
+/// version up to [`PLAN_FORMAT_VERSION`]
 pub fn physical_plan_from_bytes_with_extension_codec(
     bytes: &[u8],
     ctx: &SessionContext,
     extension_codec: &dyn PhysicalExtensionCodec,
 ) -> Result<Arc<dyn ExecutionPlan>> {
-    let protobuf = protobuf::PhysicalPlanNode::decode(bytes)
-        .map_err(|e| plan_datafusion_err!("Error decoding expr as protobuf: {e}"))?;
+    let protobuf = decode_physical_plan(bytes)?;
     protobuf.try_into_physical_plan(ctx, &ctx.runtime_env(), extension_codec)
 }
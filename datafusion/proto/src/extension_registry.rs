@@ -0,0 +1,294 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Registry of the encoders and decoders of the extension nodes, so that
+//! custom [`UserDefinedLogicalNode`]s and [`ExecutionPlan`]s can be
+//! serialized without implementing a whole codec.
+
+use std::collections::HashMap;
+use std::fmt::Debug;
+use std::sync::{Arc, OnceLock, RwLock};
+
+use crate::logical_plan::LogicalExtensionCodec;
+use crate::physical_plan::PhysicalExtensionCodec;
+
+use arrow::datatypes::SchemaRef;
+use datafusion::datasource::TableProvider;
+use datafusion::execution::context::SessionContext;
+use datafusion::execution::FunctionRegistry;
+use datafusion::physical_plan::ExecutionPlan;
+use datafusion_common::{internal_datafusion_err, not_impl_err, Result};
+use datafusion_expr::{Extension, LogicalPlan, UserDefinedLogicalNode};
+use prost::Message;
+
+/// Encodes a logical extension node into a buffer
+pub type LogicalNodeEncoder =
+    Arc<dyn Fn(&dyn UserDefinedLogicalNode, &mut Vec<u8>) -> Result<()> + Send + Sync>;
+
+/// Decodes a logical extension node, given its inputs
+pub type LogicalNodeDecoder = Arc<
+    dyn Fn(
+            &[u8],
+            &[LogicalPlan],
+            &SessionContext,
+        ) -> Result<Arc<dyn UserDefinedLogicalNode>>
+        + Send
+        + Sync,
+>;
+
+/// Encodes an execution plan into a buffer
+pub type ExecutionPlanEncoder =
+    Arc<dyn Fn(&dyn ExecutionPlan, &mut Vec<u8>) -> Result<()> + Send + Sync>;
+
+/// Decodes an execution plan, given its children
+pub type ExecutionPlanDecoder = Arc<
+    dyn Fn(
+            &[u8],
+            &[Arc<dyn ExecutionPlan>],
+            &dyn FunctionRegistry,
+        ) -> Result<Arc<dyn ExecutionPlan>>
+        + Send
+        + Sync,
+>;
+
+/// The encoded form of a registered node: its name, to find its decoder, and
+/// the buffer its encoder wrote
+#[derive(Clone, PartialEq, Message)]
+struct RegisteredNode {
+    #[prost(string, tag = "1")]
+    name: String,
+    #[prost(bytes = "vec", tag = "2")]
+    payload: Vec<u8>,
+}
+
+impl RegisteredNode {
+    fn decode_from(buf: &[u8]) -> Result<Self> {
+        Self::decode(buf)
+            .map_err(|e| internal_datafusion_err!("Failed to decode extension node: {e}"))
+    }
+
+    fn encode_into(self, buf: &mut Vec<u8>) -> Result<()> {
+        self.encode(buf)
+            .map_err(|e| internal_datafusion_err!("Failed to encode extension node: {e}"))
+    }
+}
+
+/// Registry of the encoders and decoders of the extension nodes, by name.
+///
+/// The logical nodes are registered under their
+/// [`UserDefinedLogicalNode::name`], the execution plans under their
+/// [`ExecutionPlan::name`]. The registry is itself a
+/// [`LogicalExtensionCodec`] and a [`PhysicalExtensionCodec`], which write
+/// the name of the node next to its encoded form to find its decoder.
+///
+/// The [`DefaultLogicalExtensionCodec`] and the
+/// [`DefaultPhysicalExtensionCodec`] dispatch through the
+/// [`global`](Self::global) registry, so that registered nodes round trip
+/// with the functions of [`bytes`](crate::bytes):
+///
+/// ```
+/// # use std::sync::Arc;
+/// # use datafusion::execution::FunctionRegistry;
+/// # use datafusion::physical_plan::ExecutionPlan;
+/// # use datafusion_common::{not_impl_err, Result};
+/// # use datafusion_proto::extension_registry::ExtensionCodecRegistry;
+/// ExtensionCodecRegistry::global().register_physical(
+///     "MyExec",
+///     Arc::new(|node: &dyn ExecutionPlan, buf: &mut Vec<u8>| -> Result<()> {
+///         // write the state of `node` to `buf`
+///         Ok(())
+///     }),
+///     Arc::new(
+///         |buf: &[u8],
+///          children: &[Arc<dyn ExecutionPlan>],
+///          registry: &dyn FunctionRegistry|
+///          -> Result<Arc<dyn ExecutionPlan>> {
+///             // create a `MyExec` from `buf` and `children`
+///             not_impl_err!("MyExec")
+///         },
+///     ),
+/// );
+/// ```
+///
+/// [`DefaultLogicalExtensionCodec`]: crate::logical_plan::DefaultLogicalExtensionCodec
+/// [`DefaultPhysicalExtensionCodec`]: crate::physical_plan::DefaultPhysicalExtensionCodec
+#[derive(Default)]
+pub struct ExtensionCodecRegistry {
+    logical: RwLock<HashMap<String, (LogicalNodeEncoder, LogicalNodeDecoder)>>,
+    physical: RwLock<HashMap<String, (ExecutionPlanEncoder, ExecutionPlanDecoder)>>,
+}
+
+impl ExtensionCodecRegistry {
+    /// Create an empty registry
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// The registry the default codecs dispatch through
+    pub fn global() -> &'static ExtensionCodecRegistry {
+        static GLOBAL: OnceLock<ExtensionCodecRegistry> = OnceLock::new();
+        GLOBAL.get_or_init(ExtensionCodecRegistry::new)
+    }
+
+    /// Register the encoder and the decoder of the logical extension nodes
+    /// named `name`, replacing the previous ones if any
+    pub fn register_logical(
+        &self,
+        name: impl Into<String>,
+        encode: LogicalNodeEncoder,
+        decode: LogicalNodeDecoder,
+    ) {
+        self.logical
+            .write()
+            .unwrap()
+            .insert(name.into(), (encode, decode));
+    }
+
+    /// Register the encoder and the decoder of the execution plans named
+    /// `name`, replacing the previous ones if any
+    pub fn register_physical(
+        &self,
+        name: impl Into<String>,
+        encode: ExecutionPlanEncoder,
+        decode: ExecutionPlanDecoder,
+    ) {
+        self.physical
+            .write()
+            .unwrap()
+            .insert(name.into(), (encode, decode));
+    }
+
+    /// Remove the encoder and the decoder of the logical extension nodes
+    /// named `name`, returns `true` if they were registered
+    pub fn deregister_logical(&self, name: &str) -> bool {
+        self.logical.write().unwrap().remove(name).is_some()
+    }
+
+    /// Remove the encoder and the decoder of the execution plans named
+    /// `name`, returns `true` if they were registered
+    pub fn deregister_physical(&self, name: &str) -> bool {
+        self.physical.write().unwrap().remove(name).is_some()
+    }
+
+    fn logical_codec(
+        &self,
+        name: &str,
+    ) -> Option<(LogicalNodeEncoder, LogicalNodeDecoder)> {
+        self.logical.read().unwrap().get(name).cloned()
+    }
+
+    fn physical_codec(
+        &self,
+        name: &str,
+    ) -> Option<(ExecutionPlanEncoder, ExecutionPlanDecoder)> {
+        self.physical.read().unwrap().get(name).cloned()
+    }
+}
+
+impl Debug for ExtensionCodecRegistry {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        let mut logical: Vec<_> = self.logical.read().unwrap().keys().cloned().collect();
+        logical.sort();
+        let mut physical: Vec<_> =
+            self.physical.read().unwrap().keys().cloned().collect();
+        physical.sort();
+        f.debug_struct("ExtensionCodecRegistry")
+            .field("logical", &logical)
+            .field("physical", &physical)
+            .finish()
+    }
+}
+
+impl LogicalExtensionCodec for ExtensionCodecRegistry {
+    fn try_decode(
+        &self,
+        buf: &[u8],
+        inputs: &[LogicalPlan],
+        ctx: &SessionContext,
+    ) -> Result<Extension> {
+        let RegisteredNode { name, payload } = RegisteredNode::decode_from(buf)?;
+        let Some((_, decode)) = self.logical_codec(&name) else {
+            return not_impl_err!(
+                "No decoder registered for the logical extension node {name}"
+            );
+        };
+        let node = decode(&payload, inputs, ctx)?;
+        Ok(Extension { node })
+    }
+
+    fn try_encode(&self, node: &Extension, buf: &mut Vec<u8>) -> Result<()> {
+        let name = node.node.name();
+        let Some((encode, _)) = self.logical_codec(name) else {
+            return not_impl_err!(
+                "No encoder registered for the logical extension node {name}"
+            );
+        };
+        let mut payload = vec![];
+        encode(node.node.as_ref(), &mut payload)?;
+        RegisteredNode {
+            name: name.to_string(),
+            payload,
+        }
+        .encode_into(buf)
+    }
+
+    fn try_decode_table_provider(
+        &self,
+        _buf: &[u8],
+        _schema: SchemaRef,
+        _ctx: &SessionContext,
+    ) -> Result<Arc<dyn TableProvider>> {
+        not_impl_err!("LogicalExtensionCodec is not provided")
+    }
+
+    fn try_encode_table_provider(
+        &self,
+        _node: Arc<dyn TableProvider>,
+        _buf: &mut Vec<u8>,
+    ) -> Result<()> {
+        not_impl_err!("LogicalExtensionCodec is not provided")
+    }
+}
+
+impl PhysicalExtensionCodec for ExtensionCodecRegistry {
+    fn try_decode(
+        &self,
+        buf: &[u8],
+        inputs: &[Arc<dyn ExecutionPlan>],
+        registry: &dyn FunctionRegistry,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let RegisteredNode { name, payload } = RegisteredNode::decode_from(buf)?;
+        let Some((_, decode)) = self.physical_codec(&name) else {
+            return not_impl_err!("No decoder registered for the execution plan {name}");
+        };
+        decode(&payload, inputs, registry)
+    }
+
+    fn try_encode(&self, node: Arc<dyn ExecutionPlan>, buf: &mut Vec<u8>) -> Result<()> {
+        let name = node.name();
+        let Some((encode, _)) = self.physical_codec(name) else {
+            return not_impl_err!("No encoder registered for the execution plan {name}");
+        };
+        let mut payload = vec![];
+        encode(node.as_ref(), &mut payload)?;
+        RegisteredNode {
+            name: name.to_string(),
+            payload,
+        }
+        .encode_into(buf)
+    }
+}
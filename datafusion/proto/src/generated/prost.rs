@@ -39,7 +39,7 @@ pub struct DfSchema {
 pub struct LogicalPlanNode {
     #[prost(
         oneof = "logical_plan_node::LogicalPlanType",
-        tags = "1, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29"
+        tags = "1, 3, 4, 5, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31"
     )]
     pub logical_plan_type: ::core::option::Option<logical_plan_node::LogicalPlanType>,
 }
@@ -104,6 +104,10 @@ pub mod logical_plan_node {
         DistinctOn(::prost::alloc::boxed::Box<super::DistinctOnNode>),
         #[prost(message, tag = "29")]
         CopyTo(::prost::alloc::boxed::Box<super::CopyToNode>),
+        #[prost(message, tag = "30")]
+        SetOperation(::prost::alloc::boxed::Box<super::SetOperationNode>),
+        #[prost(message, tag = "31")]
+        AsofJoin(::prost::alloc::boxed::Box<super::AsOfJoinNode>),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -164,6 +168,13 @@ pub struct ListingTableScanNode {
     pub target_partitions: u32,
     #[prost(message, repeated, tag = "13")]
     pub file_sort_order: ::prost::alloc::vec::Vec<LogicalExprNodeCollection>,
+    #[prost(map = "string, message", tag = "15")]
+    pub partition_transforms: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        LogicalExprNode,
+    >,
+    #[prost(message, optional, tag = "16")]
+    pub sample: ::core::option::Option<TableSample>,
     #[prost(oneof = "listing_table_scan_node::FileFormatType", tags = "10, 11, 12")]
     pub file_format_type: ::core::option::Option<
         listing_table_scan_node::FileFormatType,
@@ -195,6 +206,8 @@ pub struct ViewTableScanNode {
     pub projection: ::core::option::Option<ProjectionColumns>,
     #[prost(string, tag = "5")]
     pub definition: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "7")]
+    pub sample: ::core::option::Option<TableSample>,
 }
 /// Logical Plan to Scan a CustomTableProvider registered at runtime
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -210,6 +223,19 @@ pub struct CustomTableScanNode {
     pub filters: ::prost::alloc::vec::Vec<LogicalExprNode>,
     #[prost(bytes = "vec", tag = "5")]
     pub custom_table_data: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "7")]
+    pub sample: ::core::option::Option<TableSample>,
+}
+/// The TABLESAMPLE clause of a table scan
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct TableSample {
+    #[prost(enumeration = "TableSampleMethod", tag = "1")]
+    pub method: i32,
+    #[prost(double, tag = "2")]
+    pub percentage: f64,
+    #[prost(uint64, optional, tag = "3")]
+    pub seed: ::core::option::Option<u64>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -356,6 +382,11 @@ pub struct CreateExternalTableNode {
         ::prost::alloc::string::String,
         LogicalExprNode,
     >,
+    #[prost(map = "string, message", tag = "18")]
+    pub partition_transforms: ::std::collections::HashMap<
+        ::prost::alloc::string::String,
+        LogicalExprNode,
+    >,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -542,6 +573,34 @@ pub struct CrossJoinNode {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetOperationNode {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub left: ::core::option::Option<::prost::alloc::boxed::Box<LogicalPlanNode>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub right: ::core::option::Option<::prost::alloc::boxed::Box<LogicalPlanNode>>,
+    #[prost(enumeration = "SetOperationType", tag = "3")]
+    pub op: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AsOfJoinNode {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub left: ::core::option::Option<::prost::alloc::boxed::Box<LogicalPlanNode>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub right: ::core::option::Option<::prost::alloc::boxed::Box<LogicalPlanNode>>,
+    #[prost(message, repeated, tag = "3")]
+    pub left_join_key: ::prost::alloc::vec::Vec<LogicalExprNode>,
+    #[prost(message, repeated, tag = "4")]
+    pub right_join_key: ::prost::alloc::vec::Vec<LogicalExprNode>,
+    #[prost(message, optional, tag = "5")]
+    pub left_match: ::core::option::Option<LogicalExprNode>,
+    #[prost(string, tag = "6")]
+    pub op: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "7")]
+    pub right_match: ::core::option::Option<LogicalExprNode>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct LimitNode {
     #[prost(message, optional, boxed, tag = "1")]
     pub input: ::core::option::Option<::prost::alloc::boxed::Box<LogicalPlanNode>>,
@@ -1478,7 +1537,7 @@ pub struct OptimizedPhysicalPlanType {
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
 pub struct PlanType {
-    #[prost(oneof = "plan_type::PlanTypeEnum", tags = "1, 7, 8, 2, 3, 4, 9, 5, 6, 10")]
+    #[prost(oneof = "plan_type::PlanTypeEnum", tags = "1, 7, 8, 2, 3, 4, 9, 5, 6, 10, 11")]
     pub plan_type_enum: ::core::option::Option<plan_type::PlanTypeEnum>,
 }
 /// Nested message and enum types in `PlanType`.
@@ -1506,6 +1565,8 @@ pub mod plan_type {
         FinalPhysicalPlan(super::EmptyMessage),
         #[prost(message, tag = "10")]
         FinalPhysicalPlanWithStats(super::EmptyMessage),
+        #[prost(message, tag = "11")]
+        OptimizerRuleRuns(super::EmptyMessage),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1567,7 +1628,7 @@ pub mod table_reference {
 pub struct PhysicalPlanNode {
     #[prost(
         oneof = "physical_plan_node::PhysicalPlanType",
-        tags = "1, 2, 3, 4, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29"
+        tags = "1, 2, 3, 4, 6, 7, 8, 9, 10, 11, 12, 13, 14, 15, 16, 17, 18, 19, 20, 21, 22, 23, 24, 25, 26, 27, 28, 29, 30, 31, 32, 33"
     )]
     pub physical_plan_type: ::core::option::Option<physical_plan_node::PhysicalPlanType>,
 }
@@ -1634,6 +1695,14 @@ pub mod physical_plan_node {
         CsvSink(::prost::alloc::boxed::Box<super::CsvSinkExecNode>),
         #[prost(message, tag = "29")]
         ParquetSink(::prost::alloc::boxed::Box<super::ParquetSinkExecNode>),
+        #[prost(message, tag = "30")]
+        RangeJoin(::prost::alloc::boxed::Box<super::RangeJoinExecNode>),
+        #[prost(message, tag = "31")]
+        SetOperation(::prost::alloc::boxed::Box<super::SetOperationExecNode>),
+        #[prost(message, tag = "32")]
+        AsofJoin(::prost::alloc::boxed::Box<super::AsOfJoinExecNode>),
+        #[prost(message, tag = "33")]
+        Flight(super::FlightExecNode),
     }
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
@@ -1922,6 +1991,12 @@ pub struct ParquetOptions {
     /// default = 2
     #[prost(uint64, tag = "25")]
     pub maximum_buffered_record_batches_per_stream: u64,
+    /// default = false
+    #[prost(bool, tag = "26")]
+    pub distinct_sketches_enabled: bool,
+    /// default = false
+    #[prost(bool, tag = "27")]
+    pub split_row_groups: bool,
     #[prost(uint64, tag = "12")]
     pub dictionary_page_size_limit: u64,
     #[prost(uint64, tag = "18")]
@@ -2388,6 +2463,8 @@ pub struct HashJoinExecNode {
     pub filter: ::core::option::Option<JoinFilter>,
     #[prost(uint32, repeated, tag = "9")]
     pub projection: ::prost::alloc::vec::Vec<u32>,
+    #[prost(bool, tag = "10")]
+    pub null_aware: bool,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2619,6 +2696,52 @@ pub struct NestedLoopJoinExecNode {
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RangeJoinExecNode {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub left: ::core::option::Option<::prost::alloc::boxed::Box<PhysicalPlanNode>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub right: ::core::option::Option<::prost::alloc::boxed::Box<PhysicalPlanNode>>,
+    #[prost(message, optional, tag = "3")]
+    pub filter: ::core::option::Option<JoinFilter>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct SetOperationExecNode {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub left: ::core::option::Option<::prost::alloc::boxed::Box<PhysicalPlanNode>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub right: ::core::option::Option<::prost::alloc::boxed::Box<PhysicalPlanNode>>,
+    #[prost(enumeration = "SetOperationType", tag = "3")]
+    pub op: i32,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct AsOfJoinExecNode {
+    #[prost(message, optional, boxed, tag = "1")]
+    pub left: ::core::option::Option<::prost::alloc::boxed::Box<PhysicalPlanNode>>,
+    #[prost(message, optional, boxed, tag = "2")]
+    pub right: ::core::option::Option<::prost::alloc::boxed::Box<PhysicalPlanNode>>,
+    #[prost(message, repeated, tag = "3")]
+    pub on: ::prost::alloc::vec::Vec<JoinOn>,
+    #[prost(message, optional, tag = "4")]
+    pub left_match: ::core::option::Option<PhysicalExprNode>,
+    #[prost(string, tag = "5")]
+    pub op: ::prost::alloc::string::String,
+    #[prost(message, optional, tag = "6")]
+    pub right_match: ::core::option::Option<PhysicalExprNode>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct FlightExecNode {
+    #[prost(string, tag = "1")]
+    pub endpoint: ::prost::alloc::string::String,
+    #[prost(bytes = "vec", tag = "2")]
+    pub ticket: ::prost::alloc::vec::Vec<u8>,
+    #[prost(message, optional, tag = "3")]
+    pub schema: ::core::option::Option<Schema>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
 pub struct CoalesceBatchesExecNode {
     #[prost(message, optional, boxed, tag = "1")]
     pub input: ::core::option::Option<::prost::alloc::boxed::Box<PhysicalPlanNode>>,
@@ -2691,6 +2814,18 @@ pub struct PartitionedFile {
     pub partition_values: ::prost::alloc::vec::Vec<ScalarValue>,
     #[prost(message, optional, tag = "5")]
     pub range: ::core::option::Option<FileRange>,
+    /// The only rows of the file to scan, see `SelectedRows`
+    #[prost(message, optional, tag = "6")]
+    pub selected_rows: ::core::option::Option<RowPositions>,
+    /// The rows of the file deleted by a table format, see `DeletedRows`
+    #[prost(message, optional, tag = "7")]
+    pub deleted_rows: ::core::option::Option<RowPositions>,
+}
+#[allow(clippy::derive_partial_eq_without_eq)]
+#[derive(Clone, PartialEq, ::prost::Message)]
+pub struct RowPositions {
+    #[prost(uint64, repeated, tag = "1")]
+    pub positions: ::prost::alloc::vec::Vec<u64>,
 }
 #[allow(clippy::derive_partial_eq_without_eq)]
 #[derive(Clone, PartialEq, ::prost::Message)]
@@ -2744,6 +2879,32 @@ pub struct ColumnStats {
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum TableSampleMethod {
+    Bernoulli = 0,
+    System = 1,
+}
+impl TableSampleMethod {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            TableSampleMethod::Bernoulli => "BERNOULLI",
+            TableSampleMethod::System => "SYSTEM",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "BERNOULLI" => Some(Self::Bernoulli),
+            "SYSTEM" => Some(Self::System),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum JoinType {
     Inner = 0,
     Left = 1,
@@ -2814,6 +2975,32 @@ impl JoinConstraint {
 }
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
 #[repr(i32)]
+pub enum SetOperationType {
+    IntersectAll = 0,
+    ExceptAll = 1,
+}
+impl SetOperationType {
+    /// String value of the enum field names used in the ProtoBuf definition.
+    ///
+    /// The values are not transformed in any way and thus are considered stable
+    /// (if the ProtoBuf definition does not change) and safe for programmatic use.
+    pub fn as_str_name(&self) -> &'static str {
+        match self {
+            SetOperationType::IntersectAll => "INTERSECT_ALL",
+            SetOperationType::ExceptAll => "EXCEPT_ALL",
+        }
+    }
+    /// Creates an enum from field names used in the ProtoBuf definition.
+    pub fn from_str_name(value: &str) -> ::core::option::Option<Self> {
+        match value {
+            "INTERSECT_ALL" => Some(Self::IntersectAll),
+            "EXCEPT_ALL" => Some(Self::ExceptAll),
+            _ => None,
+        }
+    }
+}
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash, PartialOrd, Ord, ::prost::Enumeration)]
+#[repr(i32)]
 pub enum AggregateFunction {
     Min = 0,
     Max = 1,
@@ -2963,6 +3150,7 @@ pub enum BuiltInWindowFunction {
     FirstValue = 8,
     LastValue = 9,
     NthValue = 10,
+    SessionWindow = 11,
 }
 impl BuiltInWindowFunction {
     /// String value of the enum field names used in the ProtoBuf definition.
@@ -2982,6 +3170,7 @@ impl BuiltInWindowFunction {
             BuiltInWindowFunction::FirstValue => "FIRST_VALUE",
             BuiltInWindowFunction::LastValue => "LAST_VALUE",
             BuiltInWindowFunction::NthValue => "NTH_VALUE",
+            BuiltInWindowFunction::SessionWindow => "SESSION_WINDOW",
         }
     }
     /// Creates an enum from field names used in the ProtoBuf definition.
@@ -2998,6 +3187,7 @@ impl BuiltInWindowFunction {
             "FIRST_VALUE" => Some(Self::FirstValue),
             "LAST_VALUE" => Some(Self::LastValue),
             "NTH_VALUE" => Some(Self::NthValue),
+            "SESSION_WINDOW" => Some(Self::SessionWindow),
             _ => None,
         }
     }
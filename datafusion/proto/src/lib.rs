@@ -36,9 +36,21 @@
 //!
 //! # Version Compatibility
 //!
-//! The serialized form are not guaranteed to be compatible across
-//! DataFusion versions. A plan serialized with one version of DataFusion
-//! may not be able to deserialized with a different version.
+//! The plans serialized to bytes start with the version of their format,
+//! [`PLAN_FORMAT_VERSION`]. A plan serialized by an older version of
+//! DataFusion is upgraded to the current format when it is deserialized,
+//! so that the newer workers of a cluster can run the plans of an older
+//! coordinator during a rolling upgrade. A newer coordinator can keep
+//! producing the plans of the format its older workers support with
+//! [`logical_plan_to_bytes_with_format_version`] and
+//! [`physical_plan_to_bytes_with_format_version`].
+//!
+//! The expressions and the JSON form of the plans are not versioned, and
+//! are not guaranteed to be compatible across DataFusion versions.
+//!
+//! [`PLAN_FORMAT_VERSION`]: version::PLAN_FORMAT_VERSION
+//! [`logical_plan_to_bytes_with_format_version`]: bytes::logical_plan_to_bytes_with_format_version
+//! [`physical_plan_to_bytes_with_format_version`]: bytes::physical_plan_to_bytes_with_format_version
 //!
 //! # See Also
 //!
@@ -113,9 +125,11 @@
 //! ```
 pub mod bytes;
 pub mod common;
+pub mod extension_registry;
 pub mod generated;
 pub mod logical_plan;
 pub mod physical_plan;
+pub mod version;
 
 pub use generated::datafusion as protobuf;
 
@@ -42,8 +42,8 @@ use datafusion_expr::{
     AggregateFunction, Between, BinaryExpr, BuiltInWindowFunction, Case, Cast, Expr,
     GetFieldAccess, GetIndexedField, GroupingSet,
     GroupingSet::GroupingSets,
-    JoinConstraint, JoinType, Like, Operator, TryCast, WindowFrame, WindowFrameBound,
-    WindowFrameUnits,
+    JoinConstraint, JoinType, Like, Operator, SetOperationType, TableSample,
+    TableSampleMethod, TryCast, WindowFrame, WindowFrameBound, WindowFrameUnits,
 };
 
 use crate::protobuf::{
@@ -52,7 +52,7 @@ use crate::protobuf::{
         AnalyzedLogicalPlan, FinalAnalyzedLogicalPlan, FinalLogicalPlan,
         FinalPhysicalPlan, FinalPhysicalPlanWithStats, InitialLogicalPlan,
         InitialPhysicalPlan, InitialPhysicalPlanWithStats, OptimizedLogicalPlan,
-        OptimizedPhysicalPlan,
+        OptimizedPhysicalPlan, OptimizerRuleRuns,
     },
     AnalyzedLogicalPlanType, CubeNode, GroupingSetNode, OptimizedLogicalPlanType,
     OptimizedPhysicalPlanType, PlaceholderNode, RollupNode,
@@ -395,6 +395,7 @@ impl From<&protobuf::StringifiedPlan> for StringifiedPlan {
                         optimizer_name: optimizer_name.clone(),
                     }
                 }
+                OptimizerRuleRuns(_) => PlanType::OptimizerRuleRuns,
                 FinalLogicalPlan(_) => PlanType::FinalLogicalPlan,
                 InitialPhysicalPlan(_) => PlanType::InitialPhysicalPlan,
                 InitialPhysicalPlanWithStats(_) => PlanType::InitialPhysicalPlanWithStats,
@@ -472,6 +473,7 @@ impl From<protobuf::BuiltInWindowFunction> for BuiltInWindowFunction {
             protobuf::BuiltInWindowFunction::CumeDist => Self::CumeDist,
             protobuf::BuiltInWindowFunction::Ntile => Self::Ntile,
             protobuf::BuiltInWindowFunction::NthValue => Self::NthValue,
+            protobuf::BuiltInWindowFunction::SessionWindow => Self::SessionWindow,
             protobuf::BuiltInWindowFunction::LastValue => Self::LastValue,
         }
     }
@@ -734,6 +736,30 @@ impl TryFrom<protobuf::WindowFrame> for WindowFrame {
     }
 }
 
+impl From<protobuf::TableSampleMethod> for TableSampleMethod {
+    fn from(method: protobuf::TableSampleMethod) -> Self {
+        match method {
+            protobuf::TableSampleMethod::Bernoulli => Self::Bernoulli,
+            protobuf::TableSampleMethod::System => Self::System,
+        }
+    }
+}
+
+impl TryFrom<&protobuf::TableSample> for TableSample {
+    type Error = Error;
+
+    fn try_from(sample: &protobuf::TableSample) -> Result<Self, Self::Error> {
+        let method = protobuf::TableSampleMethod::try_from(sample.method)
+            .map_err(|_| Error::unknown("TableSampleMethod", sample.method))?
+            .into();
+        Ok(TableSample::try_new(
+            method,
+            sample.percentage,
+            sample.seed,
+        )?)
+    }
+}
+
 impl TryFrom<protobuf::WindowFrameBound> for WindowFrameBound {
     type Error = Error;
 
@@ -793,6 +819,15 @@ impl From<protobuf::JoinType> for JoinType {
     }
 }
 
+impl From<protobuf::SetOperationType> for SetOperationType {
+    fn from(t: protobuf::SetOperationType) -> Self {
+        match t {
+            protobuf::SetOperationType::IntersectAll => SetOperationType::IntersectAll,
+            protobuf::SetOperationType::ExceptAll => SetOperationType::ExceptAll,
+        }
+    }
+}
+
 impl From<protobuf::JoinConstraint> for JoinConstraint {
     fn from(t: protobuf::JoinConstraint) -> Self {
         match t {
@@ -20,6 +20,7 @@ use std::fmt::Debug;
 use std::sync::Arc;
 
 use crate::common::proto_error;
+use crate::extension_registry::ExtensionCodecRegistry;
 use crate::protobuf::logical_plan_node::LogicalPlanType::CustomScan;
 use crate::protobuf::{CustomTableScanNode, LogicalExprNodeCollection};
 use crate::{
@@ -56,7 +57,8 @@ use datafusion_expr::{
         EmptyRelation, Extension, Join, JoinConstraint, Limit, Prepare, Projection,
         Repartition, Sort, SubqueryAlias, TableScan, Values, Window,
     },
-    DistinctOn, DropView, Expr, LogicalPlan, LogicalPlanBuilder, ScalarUDF,
+    AsOfJoin, DistinctOn, DropView, Expr, LogicalPlan, LogicalPlanBuilder, ScalarUDF,
+    SetOperation,
 };
 
 use prost::bytes::BufMut;
@@ -135,21 +137,30 @@ pub trait LogicalExtensionCodec: Debug + Send + Sync {
     }
 }
 
+/// The codec used when none is provided.
+///
+/// The extension nodes are (de)serialized with the encoders and decoders
+/// registered in the [`ExtensionCodecRegistry::global`] registry.
 #[derive(Debug, Clone)]
 pub struct DefaultLogicalExtensionCodec {}
 
 impl LogicalExtensionCodec for DefaultLogicalExtensionCodec {
     fn try_decode(
         &self,
-        _buf: &[u8],
-        _inputs: &[LogicalPlan],
-        _ctx: &SessionContext,
+        buf: &[u8],
+        inputs: &[LogicalPlan],
+        ctx: &SessionContext,
     ) -> Result<Extension> {
-        not_impl_err!("LogicalExtensionCodec is not provided")
+        LogicalExtensionCodec::try_decode(
+            ExtensionCodecRegistry::global(),
+            buf,
+            inputs,
+            ctx,
+        )
     }
 
-    fn try_encode(&self, _node: &Extension, _buf: &mut Vec<u8>) -> Result<()> {
-        not_impl_err!("LogicalExtensionCodec is not provided")
+    fn try_encode(&self, node: &Extension, buf: &mut Vec<u8>) -> Result<()> {
+        LogicalExtensionCodec::try_encode(ExtensionCodecRegistry::global(), node, buf)
     }
 
     fn try_decode_table_provider(
@@ -194,6 +205,17 @@ fn from_table_reference(
     Ok(table_ref.clone().try_into()?)
 }
 
+/// Samples the table scanned by `builder` if the scan has a `TABLESAMPLE`
+fn with_sample(
+    builder: LogicalPlanBuilder,
+    sample: Option<&protobuf::TableSample>,
+) -> Result<LogicalPlanBuilder> {
+    match sample {
+        Some(sample) => builder.sample(sample.try_into()?),
+        None => Ok(builder),
+    }
+}
+
 impl AsLogicalPlan for LogicalPlanNode {
     fn try_decode(buf: &[u8]) -> Result<Self>
     where
@@ -343,6 +365,13 @@ impl AsLogicalPlan for LogicalPlanNode {
                     all_sort_orders.push(file_sort_order)
                 }
 
+                let mut partition_transforms =
+                    HashMap::with_capacity(scan.partition_transforms.len());
+                for (col_name, expr) in &scan.partition_transforms {
+                    let expr = from_proto::parse_expr(expr, ctx, extension_codec)?;
+                    partition_transforms.insert(col_name.clone(), expr);
+                }
+
                 let file_format: Arc<dyn FileFormat> =
                     match scan.file_format_type.as_ref().ok_or_else(|| {
                         proto_error(format!(
@@ -391,6 +420,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                             })
                             .collect(),
                     )
+                    .with_partition_transforms(partition_transforms)
                     .with_collect_stat(scan.collect_stat)
                     .with_target_partitions(scan.target_partitions as usize)
                     .with_file_sort_order(all_sort_orders);
@@ -410,13 +440,13 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let table_name =
                     from_table_reference(scan.table_name.as_ref(), "ListingTableScan")?;
 
-                LogicalPlanBuilder::scan_with_filters(
+                let builder = LogicalPlanBuilder::scan_with_filters(
                     table_name,
                     provider_as_source(Arc::new(provider)),
                     projection,
                     filters,
-                )?
-                .build()
+                )?;
+                with_sample(builder, scan.sample.as_ref())?.build()
             }
             LogicalPlanType::CustomScan(scan) => {
                 let schema: Schema = convert_required!(scan.schema)?;
@@ -445,13 +475,13 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let table_name =
                     from_table_reference(scan.table_name.as_ref(), "CustomScan")?;
 
-                LogicalPlanBuilder::scan_with_filters(
+                let builder = LogicalPlanBuilder::scan_with_filters(
                     table_name,
                     provider_as_source(provider),
                     projection,
                     filters,
-                )?
-                .build()
+                )?;
+                with_sample(builder, scan.sample.as_ref())?.build()
             }
             LogicalPlanType::Sort(sort) => {
                 let input: LogicalPlan =
@@ -539,6 +569,14 @@ impl AsLogicalPlan for LogicalPlanNode {
                     column_defaults.insert(col_name.clone(), expr);
                 }
 
+                let mut partition_transforms = HashMap::with_capacity(
+                    create_extern_table.partition_transforms.len(),
+                );
+                for (col_name, expr) in &create_extern_table.partition_transforms {
+                    let expr = from_proto::parse_expr(expr, ctx, extension_codec)?;
+                    partition_transforms.insert(col_name.clone(), expr);
+                }
+
                 let file_compression_type = protobuf::CompressionTypeVariant::try_from(
                     create_extern_table.file_compression_type,
                 )
@@ -561,6 +599,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                     table_partition_cols: create_extern_table
                         .table_partition_cols
                         .clone(),
+                    partition_transforms,
                     order_exprs,
                     if_not_exists: create_extern_table.if_not_exists,
                     file_compression_type: file_compression_type.into(),
@@ -745,6 +784,64 @@ impl AsLogicalPlan for LogicalPlanNode {
 
                 LogicalPlanBuilder::from(left).cross_join(right)?.build()
             }
+            LogicalPlanType::SetOperation(set_operation) => {
+                let left = into_logical_plan!(set_operation.left, ctx, extension_codec)?;
+                let right =
+                    into_logical_plan!(set_operation.right, ctx, extension_codec)?;
+                let op = protobuf::SetOperationType::try_from(set_operation.op)
+                    .map_err(|_| {
+                        proto_error(format!(
+                            "Received a SetOperationNode message with unknown SetOperationType {}",
+                            set_operation.op
+                        ))
+                    })?;
+                Ok(LogicalPlan::Extension(Extension {
+                    node: Arc::new(SetOperation {
+                        left: Arc::new(left),
+                        right: Arc::new(right),
+                        op: op.into(),
+                    }),
+                }))
+            }
+            LogicalPlanType::AsofJoin(join) => {
+                let left = into_logical_plan!(join.left, ctx, extension_codec)?;
+                let right = into_logical_plan!(join.right, ctx, extension_codec)?;
+                let on = join
+                    .left_join_key
+                    .iter()
+                    .zip(&join.right_join_key)
+                    .map(|(l, r)| {
+                        Ok((
+                            from_proto::parse_expr(l, ctx, extension_codec)?,
+                            from_proto::parse_expr(r, ctx, extension_codec)?,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                let parse_match =
+                    |expr: &Option<protobuf::LogicalExprNode>| -> Result<Expr> {
+                        expr.as_ref()
+                            .map(|expr| {
+                                from_proto::parse_expr(expr, ctx, extension_codec)
+                            })
+                            .transpose()?
+                            .ok_or_else(|| {
+                                DataFusionError::Internal(
+                                    "expression required".to_string(),
+                                )
+                            })
+                    };
+                let join = AsOfJoin::try_new(
+                    Arc::new(left),
+                    Arc::new(right),
+                    on,
+                    parse_match(&join.left_match)?,
+                    from_proto::from_proto_binary_op(&join.op)?,
+                    parse_match(&join.right_match)?,
+                )?;
+                Ok(LogicalPlan::Extension(Extension {
+                    node: Arc::new(join),
+                }))
+            }
             LogicalPlanType::Extension(LogicalExtensionNode { node, inputs }) => {
                 let input_plans: Vec<LogicalPlan> = inputs
                     .iter()
@@ -816,12 +913,12 @@ impl AsLogicalPlan for LogicalPlanNode {
                 let table_name =
                     from_table_reference(scan.table_name.as_ref(), "ViewScan")?;
 
-                LogicalPlanBuilder::scan(
+                let builder = LogicalPlanBuilder::scan(
                     table_name,
                     provider_as_source(Arc::new(provider)),
                     projection,
-                )?
-                .build()
+                )?;
+                with_sample(builder, scan.sample.as_ref())?.build()
             }
             LogicalPlanType::Prepare(prepare) => {
                 let input: LogicalPlan =
@@ -892,8 +989,15 @@ impl AsLogicalPlan for LogicalPlanNode {
                 source,
                 filters,
                 projection,
+                sample,
+                version,
                 ..
             }) => {
+                if version.is_some() {
+                    return not_impl_err!(
+                        "LogicalPlan serde is not yet implemented for time travel"
+                    );
+                }
                 let provider = source_as_provider(source)?;
                 let schema = provider.schema();
                 let source = provider.as_any();
@@ -916,6 +1020,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                     .iter()
                     .map(|filter| serialize_expr(filter, extension_codec))
                     .collect::<Result<Vec<_>, _>>()?;
+                let sample = sample.as_ref().map(protobuf::TableSample::from);
 
                 if let Some(listing_table) = source.downcast_ref::<ListingTable>() {
                     let any = listing_table.options().format.as_any();
@@ -967,6 +1072,15 @@ impl AsLogicalPlan for LogicalPlanNode {
                         exprs_vec.push(expr_vec);
                     }
 
+                    let mut partition_transforms =
+                        HashMap::with_capacity(options.partition_transforms.len());
+                    for (col_name, expr) in &options.partition_transforms {
+                        partition_transforms.insert(
+                            col_name.clone(),
+                            serialize_expr(expr, extension_codec)?,
+                        );
+                    }
+
                     Ok(protobuf::LogicalPlanNode {
                         logical_plan_type: Some(LogicalPlanType::ListingScan(
                             protobuf::ListingTableScanNode {
@@ -989,6 +1103,8 @@ impl AsLogicalPlan for LogicalPlanNode {
                                 filters,
                                 target_partitions: options.target_partitions as u32,
                                 file_sort_order: exprs_vec,
+                                partition_transforms,
+                                sample,
                             },
                         )),
                     })
@@ -1009,6 +1125,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                                     .definition()
                                     .map(|s| s.to_string())
                                     .unwrap_or_default(),
+                                sample,
                             },
                         ))),
                     })
@@ -1023,6 +1140,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                         schema: Some(schema),
                         filters,
                         custom_table_data: bytes,
+                        sample,
                     });
                     let node = LogicalPlanNode {
                         logical_plan_type: Some(scan),
@@ -1331,6 +1449,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                     delimiter,
                     schema: df_schema,
                     table_partition_cols,
+                    partition_transforms,
                     if_not_exists,
                     definition,
                     file_compression_type,
@@ -1359,6 +1478,13 @@ impl AsLogicalPlan for LogicalPlanNode {
                         .insert(col_name.clone(), serialize_expr(expr, extension_codec)?);
                 }
 
+                let mut converted_partition_transforms =
+                    HashMap::with_capacity(partition_transforms.len());
+                for (col_name, expr) in partition_transforms {
+                    converted_partition_transforms
+                        .insert(col_name.clone(), serialize_expr(expr, extension_codec)?);
+                }
+
                 let file_compression_type =
                     protobuf::CompressionTypeVariant::from(file_compression_type);
 
@@ -1380,6 +1506,7 @@ impl AsLogicalPlan for LogicalPlanNode {
                             options: options.clone(),
                             constraints: Some(constraints.clone().into()),
                             column_defaults: converted_column_defaults,
+                            partition_transforms: converted_partition_transforms,
                         },
                     )),
                 })
@@ -1493,6 +1620,73 @@ impl AsLogicalPlan for LogicalPlanNode {
                     ))),
                 })
             }
+            LogicalPlan::Extension(Extension { node })
+                if node.as_any().is::<SetOperation>() =>
+            {
+                let set_operation = node.as_any().downcast_ref::<SetOperation>().unwrap();
+                let left = protobuf::LogicalPlanNode::try_from_logical_plan(
+                    set_operation.left.as_ref(),
+                    extension_codec,
+                )?;
+                let right = protobuf::LogicalPlanNode::try_from_logical_plan(
+                    set_operation.right.as_ref(),
+                    extension_codec,
+                )?;
+                let op: protobuf::SetOperationType = set_operation.op.into();
+                Ok(protobuf::LogicalPlanNode {
+                    logical_plan_type: Some(LogicalPlanType::SetOperation(Box::new(
+                        protobuf::SetOperationNode {
+                            left: Some(Box::new(left)),
+                            right: Some(Box::new(right)),
+                            op: op.into(),
+                        },
+                    ))),
+                })
+            }
+            LogicalPlan::Extension(Extension { node })
+                if node.as_any().is::<AsOfJoin>() =>
+            {
+                let join = node.as_any().downcast_ref::<AsOfJoin>().unwrap();
+                let left = protobuf::LogicalPlanNode::try_from_logical_plan(
+                    join.left.as_ref(),
+                    extension_codec,
+                )?;
+                let right = protobuf::LogicalPlanNode::try_from_logical_plan(
+                    join.right.as_ref(),
+                    extension_codec,
+                )?;
+                let (left_join_key, right_join_key) = join
+                    .on
+                    .iter()
+                    .map(|(l, r)| {
+                        Ok((
+                            serialize_expr(l, extension_codec)?,
+                            serialize_expr(r, extension_codec)?,
+                        ))
+                    })
+                    .collect::<Result<Vec<_>, to_proto::Error>>()?
+                    .into_iter()
+                    .unzip();
+                Ok(protobuf::LogicalPlanNode {
+                    logical_plan_type: Some(LogicalPlanType::AsofJoin(Box::new(
+                        protobuf::AsOfJoinNode {
+                            left: Some(Box::new(left)),
+                            right: Some(Box::new(right)),
+                            left_join_key,
+                            right_join_key,
+                            left_match: Some(serialize_expr(
+                                &join.left_match,
+                                extension_codec,
+                            )?),
+                            op: format!("{:?}", join.op),
+                            right_match: Some(serialize_expr(
+                                &join.right_match,
+                                extension_codec,
+                            )?),
+                        },
+                    ))),
+                })
+            }
             LogicalPlan::Extension(extension) => {
                 let mut buf: Vec<u8> = vec![];
                 extension_codec.try_encode(extension, &mut buf)?;
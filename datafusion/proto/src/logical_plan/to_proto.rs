@@ -41,8 +41,9 @@ use datafusion_expr::expr::{
 };
 use datafusion_expr::{
     logical_plan::PlanType, logical_plan::StringifiedPlan, AggregateFunction,
-    BuiltInWindowFunction, Expr, JoinConstraint, JoinType, TryCast, WindowFrame,
-    WindowFrameBound, WindowFrameUnits, WindowFunctionDefinition,
+    BuiltInWindowFunction, Expr, JoinConstraint, JoinType, SetOperationType, TableSample,
+    TableSampleMethod, TryCast, WindowFrame, WindowFrameBound, WindowFrameUnits,
+    WindowFunctionDefinition,
 };
 
 use crate::protobuf::{
@@ -52,7 +53,7 @@ use crate::protobuf::{
         AnalyzedLogicalPlan, FinalAnalyzedLogicalPlan, FinalLogicalPlan,
         FinalPhysicalPlan, FinalPhysicalPlanWithStats, InitialLogicalPlan,
         InitialPhysicalPlan, InitialPhysicalPlanWithStats, OptimizedLogicalPlan,
-        OptimizedPhysicalPlan,
+        OptimizedPhysicalPlan, OptimizerRuleRuns,
     },
     AnalyzedLogicalPlanType, CubeNode, EmptyMessage, GroupingSetNode, LogicalExprList,
     OptimizedLogicalPlanType, OptimizedPhysicalPlanType, PlaceholderNode, RollupNode,
@@ -324,6 +325,9 @@ impl From<&StringifiedPlan> for protobuf::StringifiedPlan {
                         )),
                     })
                 }
+                PlanType::OptimizerRuleRuns => Some(protobuf::PlanType {
+                    plan_type_enum: Some(OptimizerRuleRuns(EmptyMessage {})),
+                }),
                 PlanType::FinalLogicalPlan => Some(protobuf::PlanType {
                     plan_type_enum: Some(FinalLogicalPlan(EmptyMessage {})),
                 }),
@@ -404,6 +408,7 @@ impl From<&BuiltInWindowFunction> for protobuf::BuiltInWindowFunction {
             BuiltInWindowFunction::FirstValue => Self::FirstValue,
             BuiltInWindowFunction::LastValue => Self::LastValue,
             BuiltInWindowFunction::NthValue => Self::NthValue,
+            BuiltInWindowFunction::SessionWindow => Self::SessionWindow,
             BuiltInWindowFunction::Ntile => Self::Ntile,
             BuiltInWindowFunction::CumeDist => Self::CumeDist,
             BuiltInWindowFunction::PercentRank => Self::PercentRank,
@@ -448,6 +453,25 @@ impl TryFrom<&WindowFrameBound> for protobuf::WindowFrameBound {
     }
 }
 
+impl From<TableSampleMethod> for protobuf::TableSampleMethod {
+    fn from(method: TableSampleMethod) -> Self {
+        match method {
+            TableSampleMethod::Bernoulli => Self::Bernoulli,
+            TableSampleMethod::System => Self::System,
+        }
+    }
+}
+
+impl From<&TableSample> for protobuf::TableSample {
+    fn from(sample: &TableSample) -> Self {
+        Self {
+            method: protobuf::TableSampleMethod::from(sample.method).into(),
+            percentage: sample.percentage,
+            seed: sample.seed,
+        }
+    }
+}
+
 impl TryFrom<&WindowFrame> for protobuf::WindowFrame {
     type Error = Error;
 
@@ -1455,6 +1479,15 @@ impl From<JoinType> for protobuf::JoinType {
     }
 }
 
+impl From<SetOperationType> for protobuf::SetOperationType {
+    fn from(t: SetOperationType) -> Self {
+        match t {
+            SetOperationType::IntersectAll => protobuf::SetOperationType::IntersectAll,
+            SetOperationType::ExceptAll => protobuf::SetOperationType::ExceptAll,
+        }
+    }
+}
+
 impl From<JoinConstraint> for protobuf::JoinConstraint {
     fn from(t: JoinConstraint) -> Self {
         match t {
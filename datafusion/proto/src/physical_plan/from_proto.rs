@@ -17,6 +17,7 @@
 
 //! Serde code to convert from protocol buffers to Rust data structures.
 
+use std::any::Any;
 use std::collections::HashMap;
 use std::sync::Arc;
 
@@ -30,9 +31,12 @@ use datafusion::datasource::file_format::csv::CsvSink;
 use datafusion::datasource::file_format::json::JsonSink;
 #[cfg(feature = "parquet")]
 use datafusion::datasource::file_format::parquet::ParquetSink;
-use datafusion::datasource::listing::{FileRange, ListingTableUrl, PartitionedFile};
+use datafusion::datasource::listing::{
+    FileRange, ListingTableUrl, PartitionedFile, SelectedRows,
+};
 use datafusion::datasource::object_store::ObjectStoreUrl;
 use datafusion::datasource::physical_plan::{FileScanConfig, FileSinkConfig};
+use datafusion::datasource::table_format::DeletedRows;
 use datafusion::execution::FunctionRegistry;
 use datafusion::logical_expr::WindowFunctionDefinition;
 use datafusion::physical_expr::{PhysicalSortExpr, ScalarFunctionExpr};
@@ -347,14 +351,17 @@ pub fn parse_physical_expr(
 
             let args = parse_physical_exprs(&e.args, registry, input_schema, codec)?;
 
-            Arc::new(ScalarFunctionExpr::new(
-                e.name.as_str(),
-                scalar_fun_def,
-                args,
-                convert_required!(e.return_type)?,
-                None,
-                signature.type_signature.supports_zero_argument(),
-            ))
+            Arc::new(
+                ScalarFunctionExpr::new(
+                    e.name.as_str(),
+                    scalar_fun_def,
+                    args,
+                    convert_required!(e.return_type)?,
+                    None,
+                    signature.type_signature.supports_zero_argument(),
+                )
+                .prepared()?,
+            )
         }
         ExprType::LikeExpr(like_expr) => Arc::new(LikeExpr::new(
             like_expr.negated,
@@ -517,6 +524,7 @@ pub fn parse_protobuf_file_scan_config(
         table_partition_cols,
         output_ordering,
         column_hints: None,
+        listing_pruning: None,
     })
 }
 
@@ -524,6 +532,22 @@ impl TryFrom<&protobuf::PartitionedFile> for PartitionedFile {
     type Error = DataFusionError;
 
     fn try_from(val: &protobuf::PartitionedFile) -> Result<Self, Self::Error> {
+        let extensions = match (&val.selected_rows, &val.deleted_rows) {
+            (Some(rows), None) => {
+                Some(Arc::new(SelectedRows::new(rows.positions.clone()))
+                    as Arc<dyn Any + Send + Sync>)
+            }
+            (None, Some(rows)) => {
+                Some(Arc::new(DeletedRows::new(rows.positions.clone()))
+                    as Arc<dyn Any + Send + Sync>)
+            }
+            (None, None) => None,
+            (Some(_), Some(_)) => {
+                return Err(proto_error(
+                    "A PartitionedFile cannot have both selected and deleted rows",
+                ))
+            }
+        };
         Ok(PartitionedFile {
             object_meta: ObjectMeta {
                 location: Path::from(val.path.as_str()),
@@ -538,7 +562,7 @@ impl TryFrom<&protobuf::PartitionedFile> for PartitionedFile {
                 .map(|v| v.try_into())
                 .collect::<Result<Vec<_>, _>>()?,
             range: val.range.as_ref().map(|v| v.try_into()).transpose()?,
-            extensions: None,
+            extensions,
         })
     }
 }
@@ -588,6 +612,7 @@ impl From<&protobuf::ColumnStats> for ColumnStatistics {
             } else {
                 Precision::Absent
             },
+            distinct_sketch: None,
         }
     }
 }
@@ -898,6 +923,8 @@ impl TryFrom<&protobuf::ParquetOptions> for ParquetOptions {
             allow_single_file_parallelism: value.allow_single_file_parallelism,
             maximum_parallel_row_group_writers: value.maximum_parallel_row_group_writers as usize,
             maximum_buffered_record_batches_per_stream: value.maximum_buffered_record_batches_per_stream as usize,
+            distinct_sketches_enabled: value.distinct_sketches_enabled,
+            split_row_groups: value.split_row_groups,
 
         })
     }
@@ -28,6 +28,8 @@ use datafusion::datasource::file_format::file_compression_type::FileCompressionT
 use datafusion::datasource::file_format::json::JsonSink;
 #[cfg(feature = "parquet")]
 use datafusion::datasource::file_format::parquet::ParquetSink;
+#[cfg(feature = "flight")]
+use datafusion::datasource::flight::{FlightExec, FlightTicket};
 #[cfg(feature = "parquet")]
 use datafusion::datasource::physical_plan::ParquetExec;
 use datafusion::datasource::physical_plan::{AvroExec, CsvExec};
@@ -46,13 +48,15 @@ use datafusion::physical_plan::filter::FilterExec;
 use datafusion::physical_plan::insert::DataSinkExec;
 use datafusion::physical_plan::joins::utils::{ColumnIndex, JoinFilter};
 use datafusion::physical_plan::joins::{
-    CrossJoinExec, NestedLoopJoinExec, StreamJoinPartitionMode, SymmetricHashJoinExec,
+    AsOfJoinExec, CrossJoinExec, NestedLoopJoinExec, RangeJoinExec,
+    StreamJoinPartitionMode, SymmetricHashJoinExec,
 };
 use datafusion::physical_plan::joins::{HashJoinExec, PartitionMode};
 use datafusion::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
 use datafusion::physical_plan::placeholder_row::PlaceholderRowExec;
 use datafusion::physical_plan::projection::ProjectionExec;
 use datafusion::physical_plan::repartition::RepartitionExec;
+use datafusion::physical_plan::set_operation::SetOperationExec;
 use datafusion::physical_plan::sorts::sort::SortExec;
 use datafusion::physical_plan::sorts::sort_preserving_merge::SortPreservingMergeExec;
 use datafusion::physical_plan::union::{InterleaveExec, UnionExec};
@@ -66,6 +70,8 @@ use datafusion_expr::ScalarUDF;
 
 use crate::common::{byte_to_string, proto_error, str_to_byte};
 use crate::convert_required;
+use crate::extension_registry::ExtensionCodecRegistry;
+use crate::logical_plan::from_proto::from_proto_binary_op;
 use crate::physical_plan::from_proto::{
     parse_physical_expr, parse_physical_sort_expr, parse_physical_sort_exprs,
     parse_physical_window_expr, parse_protobuf_file_scan_config,
@@ -83,6 +89,7 @@ use crate::protobuf::{self, window_agg_exec_node};
 use self::to_proto::serialize_physical_expr;
 
 pub mod from_proto;
+pub mod stages;
 pub mod to_proto;
 
 impl AsExecutionPlan for protobuf::PhysicalPlanNode {
@@ -651,16 +658,19 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
                 } else {
                     None
                 };
-                Ok(Arc::new(HashJoinExec::try_new(
-                    left,
-                    right,
-                    on,
-                    filter,
-                    &join_type.into(),
-                    projection,
-                    partition_mode,
-                    hashjoin.null_equals_null,
-                )?))
+                Ok(Arc::new(
+                    HashJoinExec::try_new(
+                        left,
+                        right,
+                        on,
+                        filter,
+                        &join_type.into(),
+                        projection,
+                        partition_mode,
+                        hashjoin.null_equals_null,
+                    )?
+                    .with_null_aware(hashjoin.null_aware)?,
+                ))
             }
             PhysicalPlanType::SymmetricHashJoin(sym_join) => {
                 let left = into_physical_plan(
@@ -959,39 +969,8 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
                 let filter = join
                     .filter
                     .as_ref()
-                    .map(|f| {
-                        let schema = f
-                            .schema
-                            .as_ref()
-                            .ok_or_else(|| proto_error("Missing JoinFilter schema"))?
-                            .try_into()?;
-
-                        let expression = parse_physical_expr(
-                            f.expression.as_ref().ok_or_else(|| {
-                                proto_error("Unexpected empty filter expression")
-                            })?,
-                            registry, &schema,
-                            extension_codec,
-                        )?;
-                        let column_indices = f.column_indices
-                            .iter()
-                            .map(|i| {
-                                let side = protobuf::JoinSide::try_from(i.side)
-                                    .map_err(|_| proto_error(format!(
-                                        "Received a NestedLoopJoinExecNode message with JoinSide in Filter {}",
-                                        i.side))
-                                    )?;
-
-                                Ok(ColumnIndex {
-                                    index: i.index as usize,
-                                    side: side.into(),
-                                })
-                            })
-                            .collect::<Result<Vec<_>>>()?;
-
-                        Ok(JoinFilter::new(expression, column_indices, schema))
-                    })
-                    .map_or(Ok(None), |v: Result<JoinFilter>| v.map(Some))?;
+                    .map(|f| parse_join_filter(f, registry, extension_codec))
+                    .transpose()?;
 
                 Ok(Arc::new(NestedLoopJoinExec::try_new(
                     left,
@@ -1000,6 +979,96 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
                     &join_type.into(),
                 )?))
             }
+            PhysicalPlanType::RangeJoin(join) => {
+                let left: Arc<dyn ExecutionPlan> =
+                    into_physical_plan(&join.left, registry, runtime, extension_codec)?;
+                let right: Arc<dyn ExecutionPlan> =
+                    into_physical_plan(&join.right, registry, runtime, extension_codec)?;
+                let filter = parse_join_filter(
+                    join.filter
+                        .as_ref()
+                        .ok_or_else(|| proto_error("Missing RangeJoinExecNode filter"))?,
+                    registry,
+                    extension_codec,
+                )?;
+                Ok(Arc::new(RangeJoinExec::try_new(left, right, filter)?))
+            }
+            PhysicalPlanType::SetOperation(set_operation) => {
+                let left: Arc<dyn ExecutionPlan> = into_physical_plan(
+                    &set_operation.left,
+                    registry,
+                    runtime,
+                    extension_codec,
+                )?;
+                let right: Arc<dyn ExecutionPlan> = into_physical_plan(
+                    &set_operation.right,
+                    registry,
+                    runtime,
+                    extension_codec,
+                )?;
+                let op = protobuf::SetOperationType::try_from(set_operation.op)
+                    .map_err(|_| {
+                        proto_error(format!(
+                            "Received a SetOperationExecNode message with unknown SetOperationType {}",
+                            set_operation.op
+                        ))
+                    })?;
+                Ok(Arc::new(SetOperationExec::try_new(left, right, op.into())?))
+            }
+            PhysicalPlanType::AsofJoin(join) => {
+                let left: Arc<dyn ExecutionPlan> =
+                    into_physical_plan(&join.left, registry, runtime, extension_codec)?;
+                let right: Arc<dyn ExecutionPlan> =
+                    into_physical_plan(&join.right, registry, runtime, extension_codec)?;
+                let left_schema = left.schema();
+                let right_schema = right.schema();
+                let parse_expr = |expr: Option<&protobuf::PhysicalExprNode>,
+                                  schema: &SchemaRef|
+                 -> Result<PhysicalExprRef> {
+                    let expr = expr.ok_or_else(|| {
+                        proto_error(
+                            "Received an AsOfJoinExecNode message without an expression",
+                        )
+                    })?;
+                    parse_physical_expr(expr, registry, schema, extension_codec)
+                };
+                let on = join
+                    .on
+                    .iter()
+                    .map(|on| {
+                        Ok((
+                            parse_expr(on.left.as_ref(), &left_schema)?,
+                            parse_expr(on.right.as_ref(), &right_schema)?,
+                        ))
+                    })
+                    .collect::<Result<_>>()?;
+                Ok(Arc::new(AsOfJoinExec::try_new(
+                    left,
+                    right,
+                    on,
+                    parse_expr(join.left_match.as_ref(), &left_schema)?,
+                    from_proto_binary_op(&join.op)?,
+                    parse_expr(join.right_match.as_ref(), &right_schema)?,
+                )?))
+            }
+            #[cfg(feature = "flight")]
+            PhysicalPlanType::Flight(flight) => {
+                let ticket =
+                    FlightTicket::decode(flight.ticket.as_slice()).map_err(|e| {
+                        proto_error(format!(
+                        "Received a FlightExecNode message with an invalid ticket: {e}"
+                    ))
+                    })?;
+                Ok(Arc::new(FlightExec::new(
+                    flight.endpoint.clone(),
+                    ticket,
+                    Arc::new(convert_required!(flight.schema)?),
+                )))
+            }
+            #[cfg(not(feature = "flight"))]
+            PhysicalPlanType::Flight(_) => {
+                not_impl_err!("Decoding a FlightExecNode requires the flight feature")
+            }
             PhysicalPlanType::Analyze(analyze) => {
                 let input: Arc<dyn ExecutionPlan> = into_physical_plan(
                     &analyze.input,
@@ -1295,6 +1364,7 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
                         projection: exec.projection.as_ref().map_or_else(Vec::new, |v| {
                             v.iter().map(|x| *x as u32).collect::<Vec<u32>>()
                         }),
+                        null_aware: exec.null_aware(),
                     },
                 ))),
             });
@@ -1601,6 +1671,19 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
             });
         }
 
+        #[cfg(feature = "flight")]
+        if let Some(exec) = plan.downcast_ref::<FlightExec>() {
+            return Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::Flight(
+                    protobuf::FlightExecNode {
+                        endpoint: exec.endpoint().to_string(),
+                        ticket: exec.ticket().encode_to_vec(),
+                        schema: Some(exec.schema().as_ref().try_into()?),
+                    },
+                )),
+            });
+        }
+
         if let Some(exec) = plan.downcast_ref::<AvroExec>() {
             return Ok(protobuf::PhysicalPlanNode {
                 physical_plan_type: Some(PhysicalPlanType::AvroScan(
@@ -1782,30 +1865,8 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
             let filter = exec
                 .filter()
                 .as_ref()
-                .map(|f| {
-                    let expression = serialize_physical_expr(
-                        f.expression().to_owned(),
-                        extension_codec,
-                    )?;
-                    let column_indices = f
-                        .column_indices()
-                        .iter()
-                        .map(|i| {
-                            let side: protobuf::JoinSide = i.side.to_owned().into();
-                            protobuf::ColumnIndex {
-                                index: i.index as u32,
-                                side: side.into(),
-                            }
-                        })
-                        .collect();
-                    let schema = f.schema().try_into()?;
-                    Ok(protobuf::JoinFilter {
-                        expression: Some(expression),
-                        column_indices,
-                        schema: Some(schema),
-                    })
-                })
-                .map_or(Ok(None), |v: Result<protobuf::JoinFilter>| v.map(Some))?;
+                .map(|f| serialize_join_filter(f, extension_codec))
+                .transpose()?;
 
             return Ok(protobuf::PhysicalPlanNode {
                 physical_plan_type: Some(PhysicalPlanType::NestedLoopJoin(Box::new(
@@ -1819,6 +1880,91 @@ impl AsExecutionPlan for protobuf::PhysicalPlanNode {
             });
         }
 
+        if let Some(exec) = plan.downcast_ref::<RangeJoinExec>() {
+            let left = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.left().to_owned(),
+                extension_codec,
+            )?;
+            let right = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.right().to_owned(),
+                extension_codec,
+            )?;
+            let filter = serialize_join_filter(exec.filter(), extension_codec)?;
+
+            return Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::RangeJoin(Box::new(
+                    protobuf::RangeJoinExecNode {
+                        left: Some(Box::new(left)),
+                        right: Some(Box::new(right)),
+                        filter: Some(filter),
+                    },
+                ))),
+            });
+        }
+
+        if let Some(exec) = plan.downcast_ref::<SetOperationExec>() {
+            let left = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.left().to_owned(),
+                extension_codec,
+            )?;
+            let right = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.right().to_owned(),
+                extension_codec,
+            )?;
+            let op: protobuf::SetOperationType = exec.op().into();
+
+            return Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::SetOperation(Box::new(
+                    protobuf::SetOperationExecNode {
+                        left: Some(Box::new(left)),
+                        right: Some(Box::new(right)),
+                        op: op.into(),
+                    },
+                ))),
+            });
+        }
+
+        if let Some(exec) = plan.downcast_ref::<AsOfJoinExec>() {
+            let left = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.left().to_owned(),
+                extension_codec,
+            )?;
+            let right = protobuf::PhysicalPlanNode::try_from_physical_plan(
+                exec.right().to_owned(),
+                extension_codec,
+            )?;
+            let on = exec
+                .on()
+                .iter()
+                .map(|(l, r)| {
+                    Ok(protobuf::JoinOn {
+                        left: Some(serialize_physical_expr(l.clone(), extension_codec)?),
+                        right: Some(serialize_physical_expr(r.clone(), extension_codec)?),
+                    })
+                })
+                .collect::<Result<_>>()?;
+            let (left_match, op, right_match) = exec.match_condition();
+
+            return Ok(protobuf::PhysicalPlanNode {
+                physical_plan_type: Some(PhysicalPlanType::AsofJoin(Box::new(
+                    protobuf::AsOfJoinExecNode {
+                        left: Some(Box::new(left)),
+                        right: Some(Box::new(right)),
+                        on,
+                        left_match: Some(serialize_physical_expr(
+                            left_match.clone(),
+                            extension_codec,
+                        )?),
+                        op: format!("{op:?}"),
+                        right_match: Some(serialize_physical_expr(
+                            right_match.clone(),
+                            extension_codec,
+                        )?),
+                    },
+                ))),
+            });
+        }
+
         if let Some(exec) = plan.downcast_ref::<WindowAggExec>() {
             let input = protobuf::PhysicalPlanNode::try_from_physical_plan(
                 exec.input().to_owned(),
@@ -2037,25 +2183,30 @@ pub trait PhysicalExtensionCodec: Debug + Send + Sync {
     }
 }
 
+/// The codec used when none is provided.
+///
+/// The custom execution plans are (de)serialized with the encoders and
+/// decoders registered in the [`ExtensionCodecRegistry::global`] registry.
 #[derive(Debug)]
 pub struct DefaultPhysicalExtensionCodec {}
 
 impl PhysicalExtensionCodec for DefaultPhysicalExtensionCodec {
     fn try_decode(
         &self,
-        _buf: &[u8],
-        _inputs: &[Arc<dyn ExecutionPlan>],
-        _registry: &dyn FunctionRegistry,
+        buf: &[u8],
+        inputs: &[Arc<dyn ExecutionPlan>],
+        registry: &dyn FunctionRegistry,
     ) -> Result<Arc<dyn ExecutionPlan>> {
-        not_impl_err!("PhysicalExtensionCodec is not provided")
+        PhysicalExtensionCodec::try_decode(
+            ExtensionCodecRegistry::global(),
+            buf,
+            inputs,
+            registry,
+        )
     }
 
-    fn try_encode(
-        &self,
-        _node: Arc<dyn ExecutionPlan>,
-        _buf: &mut Vec<u8>,
-    ) -> Result<()> {
-        not_impl_err!("PhysicalExtensionCodec is not provided")
+    fn try_encode(&self, node: Arc<dyn ExecutionPlan>, buf: &mut Vec<u8>) -> Result<()> {
+        PhysicalExtensionCodec::try_encode(ExtensionCodecRegistry::global(), node, buf)
     }
 }
 
@@ -2071,3 +2222,71 @@ fn into_physical_plan(
         Err(proto_error("Missing required field in protobuf"))
     }
 }
+
+/// Parses the [`JoinFilter`] of a nested loop or range join
+fn parse_join_filter(
+    filter: &protobuf::JoinFilter,
+    registry: &dyn FunctionRegistry,
+    extension_codec: &dyn PhysicalExtensionCodec,
+) -> Result<JoinFilter> {
+    let schema = filter
+        .schema
+        .as_ref()
+        .ok_or_else(|| proto_error("Missing JoinFilter schema"))?
+        .try_into()?;
+
+    let expression = parse_physical_expr(
+        filter
+            .expression
+            .as_ref()
+            .ok_or_else(|| proto_error("Unexpected empty filter expression"))?,
+        registry,
+        &schema,
+        extension_codec,
+    )?;
+    let column_indices = filter
+        .column_indices
+        .iter()
+        .map(|i| {
+            let side = protobuf::JoinSide::try_from(i.side).map_err(|_| {
+                proto_error(format!(
+                    "Received a JoinFilter message with unknown JoinSide {}",
+                    i.side
+                ))
+            })?;
+
+            Ok(ColumnIndex {
+                index: i.index as usize,
+                side: side.into(),
+            })
+        })
+        .collect::<Result<Vec<_>>>()?;
+
+    Ok(JoinFilter::new(expression, column_indices, schema))
+}
+
+/// Serializes the [`JoinFilter`] of a nested loop or range join
+fn serialize_join_filter(
+    filter: &JoinFilter,
+    extension_codec: &dyn PhysicalExtensionCodec,
+) -> Result<protobuf::JoinFilter> {
+    let expression =
+        serialize_physical_expr(filter.expression().to_owned(), extension_codec)?;
+    let column_indices = filter
+        .column_indices()
+        .iter()
+        .map(|i| {
+            let side: protobuf::JoinSide = i.side.to_owned().into();
+            protobuf::ColumnIndex {
+                index: i.index as u32,
+                side: side.into(),
+            }
+        })
+        .collect();
+    let schema = filter.schema().try_into()?;
+    Ok(protobuf::JoinFilter {
+        expression: Some(expression),
+        column_indices,
+        schema: Some(schema),
+    })
+}
@@ -0,0 +1,438 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Split physical plans into stages that exchange data through shuffles
+//!
+//! Distributed engines execute a physical plan as a graph of stages: each
+//! stage runs on its own set of workers and hands its output, partitioned
+//! as the next stage requires, to a shuffle service. [`split_into_stages`]
+//! cuts an optimized physical plan at its [`RepartitionExec`]s, replacing each
+//! of them with a [`StageInputExec`] placeholder. Stages can then be sent to
+//! workers with [`ExecutionStage::to_bytes`] and [`stage_plan_from_bytes`],
+//! and made executable with [`ExecutionStage::assemble`], which plugs in the
+//! shuffle readers and writers provided by a [`ShuffleExchange`].
+
+use std::any::Any;
+use std::fmt::{self, Debug, Formatter};
+use std::sync::Arc;
+
+use prost::bytes::Bytes;
+use prost::Message;
+
+use datafusion::arrow::datatypes::{Schema, SchemaRef};
+use datafusion::common::tree_node::{
+    Transformed, TransformedResult, TreeNode, TreeNodeRecursion,
+};
+use datafusion::error::{DataFusionError, Result};
+use datafusion::execution::{FunctionRegistry, TaskContext};
+use datafusion::physical_expr::EquivalenceProperties;
+use datafusion::physical_plan::repartition::RepartitionExec;
+use datafusion::physical_plan::{
+    DisplayAs, DisplayFormatType, ExecutionMode, ExecutionPlan, Partitioning,
+    PlanProperties, SendableRecordBatchStream,
+};
+use datafusion::prelude::SessionContext;
+use datafusion_common::{internal_err, plan_datafusion_err};
+
+use crate::physical_plan::from_proto::{parse_physical_exprs, parse_physical_sort_exprs};
+use crate::physical_plan::to_proto::{
+    serialize_physical_exprs, serialize_physical_sort_exprs,
+};
+use crate::physical_plan::{AsExecutionPlan, PhysicalExtensionCodec};
+use crate::protobuf;
+use crate::version::{decode_physical_plan, encode_physical_plan, PLAN_FORMAT_VERSION};
+
+/// A stage of a physical plan split by [`split_into_stages`]
+#[derive(Debug, Clone)]
+pub struct ExecutionStage {
+    id: usize,
+    plan: Arc<dyn ExecutionPlan>,
+    output_partitioning: Option<Partitioning>,
+}
+
+impl ExecutionStage {
+    /// Create a stage executing `plan`, whose output is shuffled by
+    /// `output_partitioning` for other stages, or returned as the result of
+    /// the query if `None`
+    pub fn new(
+        id: usize,
+        plan: Arc<dyn ExecutionPlan>,
+        output_partitioning: Option<Partitioning>,
+    ) -> Self {
+        Self {
+            id,
+            plan,
+            output_partitioning,
+        }
+    }
+
+    /// The id of the stage, unique within the split plan
+    pub fn id(&self) -> usize {
+        self.id
+    }
+
+    /// The plan of the stage, reading the output of other stages through
+    /// [`StageInputExec`]s
+    pub fn plan(&self) -> &Arc<dyn ExecutionPlan> {
+        &self.plan
+    }
+
+    /// The partitioning of the output of the stage, `None` for the final
+    /// stage
+    pub fn output_partitioning(&self) -> Option<&Partitioning> {
+        self.output_partitioning.as_ref()
+    }
+
+    /// The ids of the stages this stage reads from
+    pub fn input_stages(&self) -> Result<Vec<usize>> {
+        let mut input_stages = vec![];
+        self.plan.apply(|plan| {
+            if let Some(input) = plan.as_any().downcast_ref::<StageInputExec>() {
+                input_stages.push(input.stage_id());
+            }
+            Ok(TreeNodeRecursion::Continue)
+        })?;
+        Ok(input_stages)
+    }
+
+    /// Serialize the plan of the stage, using `codec` for the plans and
+    /// functions that are not natively supported
+    pub fn to_bytes(&self, codec: &dyn PhysicalExtensionCodec) -> Result<Bytes> {
+        stage_plan_to_bytes(self.plan.clone(), codec)
+    }
+
+    /// Returns the executable plan of the stage: its [`StageInputExec`]s are
+    /// replaced by shuffle readers, and its output is written by a shuffle
+    /// writer unless this is the final stage
+    pub fn assemble(
+        &self,
+        exchange: &dyn ShuffleExchange,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        let plan = self
+            .plan
+            .clone()
+            .transform_up(|plan| {
+                Ok(match plan.as_any().downcast_ref::<StageInputExec>() {
+                    Some(input) => Transformed::yes(exchange.create_reader(input)?),
+                    None => Transformed::no(plan),
+                })
+            })
+            .data()?;
+        match &self.output_partitioning {
+            Some(partitioning) => exchange.create_writer(self.id, plan, partitioning),
+            None => Ok(plan),
+        }
+    }
+}
+
+/// Creates the operators that exchange data between the stages of a plan,
+/// typically through a shuffle service
+pub trait ShuffleExchange: Debug + Send + Sync {
+    /// Returns a plan that executes `input`, the plan of stage `stage_id`,
+    /// and writes its output partitioned by `partitioning`
+    fn create_writer(
+        &self,
+        stage_id: usize,
+        input: Arc<dyn ExecutionPlan>,
+        partitioning: &Partitioning,
+    ) -> Result<Arc<dyn ExecutionPlan>>;
+
+    /// Returns a plan that reads the output of the stage `input` refers to.
+    /// The returned plan must have the schema and partitioning of `input`,
+    /// and preserve its ordering, if any
+    fn create_reader(&self, input: &StageInputExec) -> Result<Arc<dyn ExecutionPlan>>;
+}
+
+/// Splits `plan` into stages at its [`RepartitionExec`]s
+///
+/// The input of every [`RepartitionExec`] becomes a stage whose output
+/// partitioning is that of the [`RepartitionExec`], which is replaced by a
+/// [`StageInputExec`] reading the output of that stage. Stages are returned
+/// so that every stage comes after the stages it reads from; the last stage
+/// is the final stage, producing the result of `plan`.
+pub fn split_into_stages(plan: Arc<dyn ExecutionPlan>) -> Result<Vec<ExecutionStage>> {
+    let mut stages = vec![];
+    let plan = plan
+        .transform_up(|plan| {
+            let Some(repartition) = plan.as_any().downcast_ref::<RepartitionExec>()
+            else {
+                return Ok(Transformed::no(plan));
+            };
+            let stage_id = stages.len();
+            stages.push(ExecutionStage::new(
+                stage_id,
+                repartition.input().clone(),
+                Some(repartition.partitioning().clone()),
+            ));
+            Ok(Transformed::yes(Arc::new(StageInputExec::new(
+                stage_id,
+                repartition.properties().clone(),
+                repartition.preserve_order(),
+            )) as _))
+        })
+        .data()?;
+    stages.push(ExecutionStage::new(stages.len(), plan, None));
+    Ok(stages)
+}
+
+/// Reads the output of another stage of a plan split by
+/// [`split_into_stages`]
+///
+/// This is a placeholder that cannot be executed: [`ExecutionStage::assemble`]
+/// replaces it with a shuffle reader.
+#[derive(Debug)]
+pub struct StageInputExec {
+    stage_id: usize,
+    preserve_order: bool,
+    cache: PlanProperties,
+}
+
+impl StageInputExec {
+    /// Create a new StageInputExec reading the output of stage `stage_id`,
+    /// with the given properties. If `preserve_order` is true, the ordering
+    /// of the partitions of that output must be preserved.
+    pub fn new(
+        stage_id: usize,
+        properties: PlanProperties,
+        preserve_order: bool,
+    ) -> Self {
+        Self {
+            stage_id,
+            preserve_order,
+            cache: properties,
+        }
+    }
+
+    /// The id of the stage this plan reads from
+    pub fn stage_id(&self) -> usize {
+        self.stage_id
+    }
+
+    /// Whether the ordering of the output of the stage must be preserved
+    pub fn preserve_order(&self) -> bool {
+        self.preserve_order
+    }
+}
+
+impl DisplayAs for StageInputExec {
+    fn fmt_as(&self, t: DisplayFormatType, f: &mut Formatter) -> fmt::Result {
+        match t {
+            DisplayFormatType::Default | DisplayFormatType::Verbose => {
+                write!(
+                    f,
+                    "StageInputExec: stage={}, partitioning={}",
+                    self.stage_id,
+                    self.cache.output_partitioning()
+                )?;
+                if self.preserve_order {
+                    write!(f, ", preserve_order=true")?;
+                }
+                Ok(())
+            }
+        }
+    }
+}
+
+impl ExecutionPlan for StageInputExec {
+    fn name(&self) -> &'static str {
+        "StageInputExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        &self.cache
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        _: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(self)
+    }
+
+    fn execute(
+        &self,
+        _partition: usize,
+        _context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        internal_err!(
+            "StageInputExec for stage {} must be replaced by a shuffle reader before execution",
+            self.stage_id
+        )
+    }
+}
+
+/// Serialize the plan of a stage, using `codec` for the plans and functions
+/// that are not natively supported
+pub fn stage_plan_to_bytes(
+    plan: Arc<dyn ExecutionPlan>,
+    codec: &dyn PhysicalExtensionCodec,
+) -> Result<Bytes> {
+    let codec = StageExtensionCodec { inner: codec };
+    let protobuf = protobuf::PhysicalPlanNode::try_from_physical_plan(plan, &codec)?;
+    encode_physical_plan(&protobuf, PLAN_FORMAT_VERSION)
+}
+
+/// Deserialize the plan of a stage serialized by [`stage_plan_to_bytes`]
+pub fn stage_plan_from_bytes(
+    bytes: &[u8],
+    ctx: &SessionContext,
+    codec: &dyn PhysicalExtensionCodec,
+) -> Result<Arc<dyn ExecutionPlan>> {
+    let codec = StageExtensionCodec { inner: codec };
+    let protobuf = decode_physical_plan(bytes)?;
+    protobuf.try_into_physical_plan(ctx, &ctx.runtime_env(), &codec)
+}
+
+/// Protobuf representation of a [`StageInputExec`]
+#[derive(Clone, PartialEq, ::prost::Message)]
+struct StageInputExecNode {
+    #[prost(uint64, tag = "1")]
+    stage_id: u64,
+    #[prost(message, optional, tag = "2")]
+    schema: Option<protobuf::Schema>,
+    /// Hash expressions, if the stage output is hash partitioned
+    #[prost(message, repeated, tag = "3")]
+    hash_expr: Vec<protobuf::PhysicalExprNode>,
+    #[prost(uint64, tag = "4")]
+    partition_count: u64,
+    #[prost(message, repeated, tag = "5")]
+    output_ordering: Vec<protobuf::PhysicalSortExprNode>,
+    #[prost(bool, tag = "6")]
+    preserve_order: bool,
+}
+
+/// Marks the extension nodes encoded by [`StageExtensionCodec`] itself
+const STAGE_INPUT_MARKER: u8 = 0;
+/// Marks the extension nodes encoded by the wrapped codec
+const INNER_CODEC_MARKER: u8 = 1;
+
+/// Encodes [`StageInputExec`]s, delegating other extension nodes to `inner`
+#[derive(Debug)]
+struct StageExtensionCodec<'a> {
+    inner: &'a dyn PhysicalExtensionCodec,
+}
+
+impl PhysicalExtensionCodec for StageExtensionCodec<'_> {
+    fn try_decode(
+        &self,
+        buf: &[u8],
+        inputs: &[Arc<dyn ExecutionPlan>],
+        registry: &dyn FunctionRegistry,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        match buf.split_first() {
+            Some((&STAGE_INPUT_MARKER, buf)) => {
+                let node = StageInputExecNode::decode(buf).map_err(|e| {
+                    plan_datafusion_err!("Error decoding StageInputExec: {e}")
+                })?;
+                let schema: SchemaRef =
+                    Arc::new(Schema::try_from(node.schema.as_ref().ok_or_else(
+                        || plan_datafusion_err!("Missing schema in StageInputExec"),
+                    )?)?);
+                let partition_count = node.partition_count as usize;
+                let partitioning = if node.hash_expr.is_empty() {
+                    Partitioning::UnknownPartitioning(partition_count)
+                } else {
+                    let exprs =
+                        parse_physical_exprs(&node.hash_expr, registry, &schema, self)?;
+                    Partitioning::Hash(exprs, partition_count)
+                };
+                let ordering = parse_physical_sort_exprs(
+                    &node.output_ordering,
+                    registry,
+                    &schema,
+                    self,
+                )?;
+                let eq_properties = if ordering.is_empty() {
+                    EquivalenceProperties::new(schema)
+                } else {
+                    EquivalenceProperties::new_with_orderings(schema, &[ordering])
+                };
+                let properties = PlanProperties::new(
+                    eq_properties,
+                    partitioning,
+                    ExecutionMode::Bounded,
+                );
+                Ok(Arc::new(StageInputExec::new(
+                    node.stage_id as usize,
+                    properties,
+                    node.preserve_order,
+                )))
+            }
+            Some((&INNER_CODEC_MARKER, buf)) => {
+                self.inner.try_decode(buf, inputs, registry)
+            }
+            _ => internal_err!("Unexpected extension node in stage plan"),
+        }
+    }
+
+    fn try_encode(&self, node: Arc<dyn ExecutionPlan>, buf: &mut Vec<u8>) -> Result<()> {
+        let Some(input) = node.as_any().downcast_ref::<StageInputExec>() else {
+            buf.push(INNER_CODEC_MARKER);
+            return self.inner.try_encode(node, buf);
+        };
+        let properties = input.properties();
+        let hash_expr = match properties.output_partitioning() {
+            Partitioning::Hash(exprs, _) => {
+                serialize_physical_exprs(exprs.clone(), self)?
+            }
+            _ => vec![],
+        };
+        let output_ordering = serialize_physical_sort_exprs(
+            properties
+                .output_ordering()
+                .unwrap_or_default()
+                .iter()
+                .cloned(),
+            self,
+        )?;
+        let node = StageInputExecNode {
+            stage_id: input.stage_id as u64,
+            schema: Some(input.schema().try_into()?),
+            hash_expr,
+            partition_count: properties.output_partitioning().partition_count() as u64,
+            output_ordering,
+            preserve_order: input.preserve_order,
+        };
+        buf.push(STAGE_INPUT_MARKER);
+        node.encode(buf)
+            .map_err(|e| DataFusionError::Internal(format!("{e:?}")))
+    }
+
+    fn try_decode_udf(
+        &self,
+        name: &str,
+        buf: &[u8],
+    ) -> Result<Arc<datafusion_expr::ScalarUDF>> {
+        self.inner.try_decode_udf(name, buf)
+    }
+
+    fn try_encode_udf(
+        &self,
+        node: &datafusion_expr::ScalarUDF,
+        buf: &mut Vec<u8>,
+    ) -> Result<()> {
+        self.inner.try_encode_udf(node, buf)
+    }
+}
@@ -29,8 +29,8 @@ use datafusion::physical_plan::expressions::{
     DistinctArrayAgg, DistinctBitXor, DistinctCount, DistinctSum, FirstValue, Grouping,
     InListExpr, IsNotNullExpr, IsNullExpr, LastValue, Literal, Max, Median, Min,
     NegativeExpr, NotExpr, NthValue, NthValueAgg, Ntile, OrderSensitiveArrayAgg, Rank,
-    RankType, Regr, RegrType, RowNumber, Stddev, StddevPop, StringAgg, Sum, TryCastExpr,
-    Variance, VariancePop, WindowShift,
+    RankType, Regr, RegrType, RowNumber, SessionWindow, Stddev, StddevPop, StringAgg,
+    Sum, TryCastExpr, Variance, VariancePop, WindowShift,
 };
 use datafusion::physical_plan::udaf::AggregateFunctionExpr;
 use datafusion::physical_plan::windows::{BuiltInWindowExpr, PlainAggregateWindowExpr};
@@ -40,8 +40,9 @@ use datafusion::physical_plan::{
 use datafusion::{
     datasource::{
         file_format::{csv::CsvSink, json::JsonSink},
-        listing::{FileRange, PartitionedFile},
+        listing::{FileRange, PartitionedFile, SelectedRows},
         physical_plan::{FileScanConfig, FileSinkConfig},
+        table_format::DeletedRows,
     },
     physical_plan::expressions::LikeExpr,
 };
@@ -72,7 +73,12 @@ pub fn serialize_physical_aggr_expr(
     codec: &dyn PhysicalExtensionCodec,
 ) -> Result<protobuf::PhysicalExprNode> {
     let expressions = serialize_physical_exprs(aggr_expr.expressions(), codec)?;
-    let ordering_req = aggr_expr.order_bys().unwrap_or(&[]).to_vec();
+    let ordering_req = match aggr_expr.as_any().downcast_ref::<DistinctArrayAgg>() {
+        // The ordering of the distinct values is not an ordering requirement
+        Some(distinct_array_agg) => distinct_array_agg.ordering_req(),
+        None => aggr_expr.order_bys().unwrap_or(&[]),
+    }
+    .to_vec();
     let ordering_req = serialize_physical_sort_exprs(ordering_req, codec)?;
 
     if let Some(a) = aggr_expr.as_any().downcast_ref::<AggregateFunctionExpr>() {
@@ -175,6 +181,14 @@ pub fn serialize_physical_window_expr(
                     protobuf::BuiltInWindowFunction::NthValue
                 }
             }
+        } else if let Some(session_window_expr) =
+            built_in_fn_expr.downcast_ref::<SessionWindow>()
+        {
+            args.insert(
+                1,
+                Arc::new(Literal::new(session_window_expr.get_gap().clone())),
+            );
+            protobuf::BuiltInWindowFunction::SessionWindow
         } else {
             return not_impl_err!("BuiltIn function not supported: {expr:?}");
         };
@@ -607,6 +621,26 @@ impl TryFrom<&PartitionedFile> for protobuf::PartitionedFile {
                 "Invalid timestamp on PartitionedFile::ObjectMeta: {last_modified}"
             ))
         })? as u64;
+        // the extensions changing the rows read from the file must survive
+        // the serialization, other extensions are ignored by the scans
+        let mut selected_rows = None;
+        let mut deleted_rows = None;
+        if let Some(extensions) = &pf.extensions {
+            if let Some(rows) = extensions.downcast_ref::<SelectedRows>() {
+                selected_rows = Some(protobuf::RowPositions {
+                    positions: rows.positions().to_vec(),
+                });
+            } else if let Some(rows) = extensions.downcast_ref::<DeletedRows>() {
+                deleted_rows = Some(protobuf::RowPositions {
+                    positions: rows.positions().to_vec(),
+                });
+            } else {
+                return not_impl_err!(
+                    "Cannot serialize the extensions of the file {}",
+                    pf.object_meta.location
+                );
+            }
+        }
         Ok(protobuf::PartitionedFile {
             path: pf.object_meta.location.as_ref().to_owned(),
             size: pf.object_meta.size as u64,
@@ -617,6 +651,8 @@ impl TryFrom<&PartitionedFile> for protobuf::PartitionedFile {
                 .map(|v| v.try_into())
                 .collect::<Result<Vec<_>, _>>()?,
             range: pf.range.as_ref().map(|r| r.try_into()).transpose()?,
+            selected_rows,
+            deleted_rows,
         })
     }
 }
@@ -917,6 +953,8 @@ impl TryFrom<&ParquetOptions> for protobuf::ParquetOptions {
             allow_single_file_parallelism: value.allow_single_file_parallelism,
             maximum_parallel_row_group_writers: value.maximum_parallel_row_group_writers as u64,
             maximum_buffered_record_batches_per_stream: value.maximum_buffered_record_batches_per_stream as u64,
+            distinct_sketches_enabled: value.distinct_sketches_enabled,
+            split_row_groups: value.split_row_groups,
         })
     }
 }
@@ -0,0 +1,155 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Versioning of the serialized plans.
+//!
+//! The serialized plans start with a header: the magic bytes `DFPB`
+//! followed by the version of the format, as a little endian `u32`. The
+//! plans serialized before the header was introduced, which are the encoded
+//! protobuf message alone, are of the format version `0`.
+//!
+//! A plan is read by upgrading it, one format version at a time, to the
+//! current [`PLAN_FORMAT_VERSION`], so that the plans serialized by an older
+//! release can be executed by a newer one. When the protobuf messages change
+//! in a way older plans can't be decoded as is, [`PLAN_FORMAT_VERSION`] is
+//! incremented and a shim upgrading the plans of the previous version is
+//! added to the upgrades of the logical and of the physical plans.
+
+use crate::protobuf;
+
+use datafusion_common::{plan_datafusion_err, plan_err, Result};
+use prost::bytes::Bytes;
+use prost::Message;
+
+/// The version of the format of the plans serialized by this release
+pub const PLAN_FORMAT_VERSION: u32 = 1;
+
+/// The magic bytes starting the header of the serialized plans.
+///
+/// `D` is a tag with the wire type "end group", which can't start an encoded
+/// protobuf message, so that plans of the format version `0` are never
+/// mistaken for plans with a header.
+const MAGIC: &[u8; 4] = b"DFPB";
+
+/// The length of the header, the magic bytes and the version
+const HEADER_LEN: usize = MAGIC.len() + std::mem::size_of::<u32>();
+
+/// Upgrades a decoded plan of a format version to the next one
+type Upgrade<T> = fn(&mut T) -> Result<()>;
+
+/// The upgrades of the logical plans, indexed by the version they upgrade from
+const LOGICAL_PLAN_UPGRADES: &[Upgrade<protobuf::LogicalPlanNode>] = &[
+    // 0 -> 1
+    header_only,
+];
+
+/// The upgrades of the physical plans, indexed by the version they upgrade from
+const PHYSICAL_PLAN_UPGRADES: &[Upgrade<protobuf::PhysicalPlanNode>] = &[
+    // 0 -> 1
+    header_only,
+];
+
+const _: () = assert!(LOGICAL_PLAN_UPGRADES.len() == PLAN_FORMAT_VERSION as usize);
+const _: () = assert!(PHYSICAL_PLAN_UPGRADES.len() == PLAN_FORMAT_VERSION as usize);
+
+/// The format version `1` only added the header to the format version `0`
+fn header_only<T>(_plan: &mut T) -> Result<()> {
+    Ok(())
+}
+
+/// Returns the format version of the serialized plan `bytes`
+pub fn plan_format_version(bytes: &[u8]) -> Result<u32> {
+    split_header(bytes).map(|(version, _)| version)
+}
+
+/// Returns the format version of the serialized plan `bytes` and the
+/// encoded plan following the header
+fn split_header(bytes: &[u8]) -> Result<(u32, &[u8])> {
+    if !bytes.starts_with(MAGIC) {
+        return Ok((0, bytes));
+    }
+    if bytes.len() < HEADER_LEN {
+        return plan_err!("Serialized plan has a truncated header");
+    }
+    let (header, plan) = bytes.split_at(HEADER_LEN);
+    let version = u32::from_le_bytes(header[MAGIC.len()..].try_into().unwrap());
+    if version > PLAN_FORMAT_VERSION {
+        return plan_err!(
+            "Plan was serialized with the format version {version}, \
+            this release supports the versions up to {PLAN_FORMAT_VERSION}"
+        );
+    }
+    Ok((version, plan))
+}
+
+/// Encodes `plan` in the format version `version`
+fn encode_plan<T: Message>(plan: &T, version: u32) -> Result<Bytes> {
+    if version > PLAN_FORMAT_VERSION {
+        return plan_err!(
+            "Can't serialize a plan with the format version {version}, \
+            this release supports the versions up to {PLAN_FORMAT_VERSION}"
+        );
+    }
+    let mut buffer = Vec::with_capacity(HEADER_LEN + plan.encoded_len());
+    if version > 0 {
+        buffer.extend_from_slice(MAGIC);
+        buffer.extend_from_slice(&version.to_le_bytes());
+    }
+    plan.encode(&mut buffer)
+        .map_err(|e| plan_datafusion_err!("Error encoding protobuf as bytes: {e}"))?;
+    Ok(buffer.into())
+}
+
+/// Decodes the plan `bytes` of any supported format version, upgrading it
+/// to the current format
+fn decode_plan<T: Message + Default>(bytes: &[u8], upgrades: &[Upgrade<T>]) -> Result<T> {
+    let (version, bytes) = split_header(bytes)?;
+    let mut plan = T::decode(bytes)
+        .map_err(|e| plan_datafusion_err!("Error decoding plan as protobuf: {e}"))?;
+    for upgrade in &upgrades[version as usize..] {
+        upgrade(&mut plan)?;
+    }
+    Ok(plan)
+}
+
+/// Encodes the logical `plan` in the format version `version`
+pub(crate) fn encode_logical_plan(
+    plan: &protobuf::LogicalPlanNode,
+    version: u32,
+) -> Result<Bytes> {
+    encode_plan(plan, version)
+}
+
+/// Decodes the logical plan `bytes` serialized with any supported format
+/// version
+pub(crate) fn decode_logical_plan(bytes: &[u8]) -> Result<protobuf::LogicalPlanNode> {
+    decode_plan(bytes, LOGICAL_PLAN_UPGRADES)
+}
+
+/// Encodes the physical `plan` in the format version `version`
+pub(crate) fn encode_physical_plan(
+    plan: &protobuf::PhysicalPlanNode,
+    version: u32,
+) -> Result<Bytes> {
+    encode_plan(plan, version)
+}
+
+/// Decodes the physical plan `bytes` serialized with any supported format
+/// version
+pub(crate) fn decode_physical_plan(bytes: &[u8]) -> Result<protobuf::PhysicalPlanNode> {
+    decode_plan(bytes, PHYSICAL_PLAN_UPGRADES)
+}
@@ -0,0 +1,215 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Round trips of custom nodes through the default codecs, with the
+//! encoders and decoders of the global registry
+
+use std::any::Any;
+use std::fmt::{self, Formatter};
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Field, Schema};
+use datafusion::execution::{FunctionRegistry, TaskContext};
+use datafusion::physical_plan::empty::EmptyExec;
+use datafusion::physical_plan::{
+    displayable, DisplayAs, DisplayFormatType, ExecutionPlan, PlanProperties,
+    SendableRecordBatchStream,
+};
+use datafusion::prelude::{CsvReadOptions, SessionContext};
+use datafusion_common::{DFSchemaRef, Result};
+use datafusion_expr::{
+    Expr, Extension, LogicalPlan, UserDefinedLogicalNode, UserDefinedLogicalNodeCore,
+};
+use datafusion_proto::bytes::{
+    logical_plan_from_bytes, logical_plan_to_bytes, physical_plan_from_bytes,
+    physical_plan_to_bytes,
+};
+use datafusion_proto::extension_registry::ExtensionCodecRegistry;
+
+/// A logical node labelling its input
+#[derive(Debug, PartialEq, Eq, Hash)]
+struct LabelNode {
+    label: String,
+    input: LogicalPlan,
+}
+
+impl UserDefinedLogicalNodeCore for LabelNode {
+    fn name(&self) -> &str {
+        "RegistryLabel"
+    }
+
+    fn inputs(&self) -> Vec<&LogicalPlan> {
+        vec![&self.input]
+    }
+
+    fn schema(&self) -> &DFSchemaRef {
+        self.input.schema()
+    }
+
+    fn expressions(&self) -> Vec<Expr> {
+        vec![]
+    }
+
+    fn fmt_for_explain(&self, f: &mut Formatter) -> fmt::Result {
+        write!(f, "RegistryLabel: {}", self.label)
+    }
+
+    fn from_template(&self, _exprs: &[Expr], inputs: &[LogicalPlan]) -> Self {
+        Self {
+            label: self.label.clone(),
+            input: inputs[0].clone(),
+        }
+    }
+}
+
+/// An execution plan labelling its input
+#[derive(Debug)]
+struct LabelExec {
+    label: String,
+    input: Arc<dyn ExecutionPlan>,
+}
+
+impl DisplayAs for LabelExec {
+    fn fmt_as(&self, _t: DisplayFormatType, f: &mut Formatter) -> fmt::Result {
+        write!(f, "LabelExec: {}", self.label)
+    }
+}
+
+impl ExecutionPlan for LabelExec {
+    fn name(&self) -> &'static str {
+        "LabelExec"
+    }
+
+    fn as_any(&self) -> &dyn Any {
+        self
+    }
+
+    fn properties(&self) -> &PlanProperties {
+        self.input.properties()
+    }
+
+    fn children(&self) -> Vec<Arc<dyn ExecutionPlan>> {
+        vec![self.input.clone()]
+    }
+
+    fn with_new_children(
+        self: Arc<Self>,
+        children: Vec<Arc<dyn ExecutionPlan>>,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(LabelExec {
+            label: self.label.clone(),
+            input: children[0].clone(),
+        }))
+    }
+
+    fn execute(
+        &self,
+        partition: usize,
+        context: Arc<TaskContext>,
+    ) -> Result<SendableRecordBatchStream> {
+        self.input.execute(partition, context)
+    }
+}
+
+#[tokio::test]
+async fn roundtrip_registered_logical_node() -> Result<()> {
+    ExtensionCodecRegistry::global().register_logical(
+        "RegistryLabel",
+        Arc::new(
+            |node: &dyn UserDefinedLogicalNode, buf: &mut Vec<u8>| -> Result<()> {
+                let node = node.as_any().downcast_ref::<LabelNode>().unwrap();
+                buf.extend_from_slice(node.label.as_bytes());
+                Ok(())
+            },
+        ),
+        Arc::new(
+            |buf: &[u8],
+             inputs: &[LogicalPlan],
+             _ctx: &SessionContext|
+             -> Result<Arc<dyn UserDefinedLogicalNode>> {
+                Ok(Arc::new(LabelNode {
+                    label: String::from_utf8(buf.to_vec()).unwrap(),
+                    input: inputs[0].clone(),
+                }))
+            },
+        ),
+    );
+
+    let ctx = SessionContext::new();
+    ctx.register_csv("t1", "tests/testdata/test.csv", CsvReadOptions::default())
+        .await?;
+    let input = ctx.table("t1").await?.into_optimized_plan()?;
+    let plan = LogicalPlan::Extension(Extension {
+        node: Arc::new(LabelNode {
+            label: "deep projection".to_string(),
+            input,
+        }),
+    });
+
+    let bytes = logical_plan_to_bytes(&plan)?;
+    let round_trip = logical_plan_from_bytes(&bytes, &ctx)?;
+    assert_eq!(format!("{plan:?}"), format!("{round_trip:?}"));
+    Ok(())
+}
+
+#[test]
+fn roundtrip_registered_execution_plan() -> Result<()> {
+    ExtensionCodecRegistry::global().register_physical(
+        "LabelExec",
+        Arc::new(
+            |node: &dyn ExecutionPlan, buf: &mut Vec<u8>| -> Result<()> {
+                let node = node.as_any().downcast_ref::<LabelExec>().unwrap();
+                buf.extend_from_slice(node.label.as_bytes());
+                Ok(())
+            },
+        ),
+        Arc::new(
+            |buf: &[u8],
+             children: &[Arc<dyn ExecutionPlan>],
+             _registry: &dyn FunctionRegistry|
+             -> Result<Arc<dyn ExecutionPlan>> {
+                Ok(Arc::new(LabelExec {
+                    label: String::from_utf8(buf.to_vec()).unwrap(),
+                    input: children[0].clone(),
+                }))
+            },
+        ),
+    );
+
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+    let plan: Arc<dyn ExecutionPlan> = Arc::new(LabelExec {
+        label: "deep projection".to_string(),
+        input: Arc::new(EmptyExec::new(schema)),
+    });
+
+    let bytes = physical_plan_to_bytes(plan.clone())?;
+    let round_trip = physical_plan_from_bytes(&bytes, &SessionContext::new())?;
+    assert_eq!(
+        displayable(plan.as_ref()).indent(true).to_string(),
+        displayable(round_trip.as_ref()).indent(true).to_string()
+    );
+
+    // the plans without registered codec still fail to serialize
+    assert!(ExtensionCodecRegistry::global().deregister_physical("LabelExec"));
+    let err = physical_plan_to_bytes(plan).unwrap_err();
+    assert!(
+        err.to_string()
+            .contains("No encoder registered for the execution plan LabelExec"),
+        "{err}"
+    );
+    Ok(())
+}
@@ -15,6 +15,9 @@
 // specific language governing permissions and limitations
 // under the License.
 
+mod extension_registry;
+mod plan_versions;
 mod roundtrip_logical_plan;
 mod roundtrip_physical_plan;
 mod serialize;
+mod stages;
@@ -0,0 +1,152 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+//! Deserialization of the plans serialized by previous releases.
+//!
+//! The fixtures in `tests/testdata/plans` are named after the format version
+//! they were serialized with, and must never be regenerated:
+//!
+//! * the plans of the version `0`, without a header, were serialized by
+//!   DataFusion 37.1.0 with `logical_plan_to_bytes` and
+//!   `physical_plan_to_bytes`
+//! * the plans of the version `1` were serialized by the first release
+//!   writing the header, and pin the current format
+
+use std::sync::Arc;
+
+use arrow::datatypes::Schema;
+use datafusion::physical_plan::expressions::lit;
+use datafusion::physical_plan::placeholder_row::PlaceholderRowExec;
+use datafusion::physical_plan::projection::ProjectionExec;
+use datafusion::physical_plan::{displayable, ExecutionPlan};
+use datafusion::prelude::SessionContext;
+use datafusion_common::Result;
+use datafusion_expr::{lit as logical_lit, LogicalPlanBuilder};
+use datafusion_proto::bytes::{
+    logical_plan_from_bytes, logical_plan_to_bytes,
+    logical_plan_to_bytes_with_format_version, physical_plan_from_bytes,
+    physical_plan_to_bytes, physical_plan_to_bytes_with_format_version,
+};
+use datafusion_proto::logical_plan::DefaultLogicalExtensionCodec;
+use datafusion_proto::physical_plan::DefaultPhysicalExtensionCodec;
+use datafusion_proto::version::{plan_format_version, PLAN_FORMAT_VERSION};
+
+fn fixture(name: &str) -> Vec<u8> {
+    std::fs::read(format!("tests/testdata/plans/{name}")).unwrap()
+}
+
+fn physical_projection() -> Result<Arc<dyn ExecutionPlan>> {
+    let input = Arc::new(PlaceholderRowExec::new(Arc::new(Schema::empty())));
+    Ok(Arc::new(ProjectionExec::try_new(
+        vec![(lit(1i64), "one".to_string())],
+        input,
+    )?))
+}
+
+#[test]
+fn read_logical_plan_fixtures() -> Result<()> {
+    let ctx = SessionContext::new();
+    for (name, version) in [
+        ("projection_v0.logical.pb", 0),
+        ("projection_v1.logical.pb", 1),
+    ] {
+        let bytes = fixture(name);
+        assert_eq!(plan_format_version(&bytes)?, version);
+        let plan = logical_plan_from_bytes(&bytes, &ctx)?;
+        assert_eq!(
+            format!("{}", plan.display_indent()),
+            "Projection: Int64(1)\n  EmptyRelation",
+            "{name}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn read_physical_plan_fixtures() -> Result<()> {
+    let ctx = SessionContext::new();
+    for (name, version) in [
+        ("projection_v0.physical.pb", 0),
+        ("projection_v1.physical.pb", 1),
+    ] {
+        let bytes = fixture(name);
+        assert_eq!(plan_format_version(&bytes)?, version);
+        let plan = physical_plan_from_bytes(&bytes, &ctx)?;
+        assert_eq!(
+            displayable(plan.as_ref()).indent(true).to_string(),
+            "ProjectionExec: expr=[1 as one]\n  PlaceholderRowExec\n",
+            "{name}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn write_logical_plan_versions() -> Result<()> {
+    let plan = LogicalPlanBuilder::empty(true)
+        .project(vec![logical_lit(1i64)])?
+        .build()?;
+
+    let bytes = logical_plan_to_bytes(&plan)?;
+    assert_eq!(plan_format_version(&bytes)?, PLAN_FORMAT_VERSION);
+    assert_eq!(bytes.as_ref(), fixture("projection_v1.logical.pb"));
+
+    let codec = DefaultLogicalExtensionCodec {};
+    let bytes = logical_plan_to_bytes_with_format_version(&plan, &codec, 0)?;
+    assert_eq!(bytes.as_ref(), fixture("projection_v0.logical.pb"));
+    Ok(())
+}
+
+#[test]
+fn write_physical_plan_versions() -> Result<()> {
+    let plan = physical_projection()?;
+
+    let bytes = physical_plan_to_bytes(plan.clone())?;
+    assert_eq!(plan_format_version(&bytes)?, PLAN_FORMAT_VERSION);
+    assert_eq!(bytes.as_ref(), fixture("projection_v1.physical.pb"));
+
+    let codec = DefaultPhysicalExtensionCodec {};
+    let bytes = physical_plan_to_bytes_with_format_version(plan, &codec, 0)?;
+    assert_eq!(bytes.as_ref(), fixture("projection_v0.physical.pb"));
+    Ok(())
+}
+
+#[test]
+fn reject_newer_format_versions() -> Result<()> {
+    let mut bytes = b"DFPB".to_vec();
+    bytes.extend_from_slice(&(PLAN_FORMAT_VERSION + 1).to_le_bytes());
+    bytes.extend_from_slice(&fixture("projection_v0.physical.pb"));
+
+    let err = physical_plan_from_bytes(&bytes, &SessionContext::new()).unwrap_err();
+    assert!(
+        err.to_string().contains(&format!(
+            "Plan was serialized with the format version {}",
+            PLAN_FORMAT_VERSION + 1
+        )),
+        "{err}"
+    );
+
+    let codec = DefaultPhysicalExtensionCodec {};
+    let err = physical_plan_to_bytes_with_format_version(
+        physical_projection()?,
+        &codec,
+        PLAN_FORMAT_VERSION + 1,
+    )
+    .unwrap_err();
+    assert!(err.to_string().contains("Can't serialize a plan"), "{err}");
+    Ok(())
+}
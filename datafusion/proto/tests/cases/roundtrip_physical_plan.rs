@@ -29,13 +29,15 @@ use datafusion::arrow::datatypes::{DataType, Field, IntervalUnit, Schema};
 use datafusion::datasource::file_format::csv::CsvSink;
 use datafusion::datasource::file_format::json::JsonSink;
 use datafusion::datasource::file_format::parquet::ParquetSink;
-use datafusion::datasource::listing::{ListingTableUrl, PartitionedFile};
+use datafusion::datasource::listing::{ListingTableUrl, PartitionedFile, SelectedRows};
 use datafusion::datasource::object_store::ObjectStoreUrl;
 use datafusion::datasource::physical_plan::{
     wrap_partition_type_in_dict, wrap_partition_value_in_dict, FileScanConfig,
     FileSinkConfig, ParquetExec,
 };
+use datafusion::datasource::table_format::DeletedRows;
 use datafusion::execution::FunctionRegistry;
+use datafusion::functions_aggregate::approx_quantiles::approx_quantiles_udaf;
 use datafusion::logical_expr::{create_udf, JoinType, Operator, Volatility};
 use datafusion::physical_expr::expressions::{Count, Max, NthValueAgg};
 use datafusion::physical_expr::window::SlidingAggregateWindowExpr;
@@ -51,13 +53,16 @@ use datafusion::physical_plan::expressions::{
 };
 use datafusion::physical_plan::filter::FilterExec;
 use datafusion::physical_plan::insert::DataSinkExec;
+use datafusion::physical_plan::joins::utils::JoinFilter;
 use datafusion::physical_plan::joins::{
-    HashJoinExec, NestedLoopJoinExec, PartitionMode, StreamJoinPartitionMode,
+    AsOfJoinExec, HashJoinExec, NestedLoopJoinExec, PartitionMode, RangeJoinExec,
+    StreamJoinPartitionMode,
 };
 use datafusion::physical_plan::limit::{GlobalLimitExec, LocalLimitExec};
 use datafusion::physical_plan::placeholder_row::PlaceholderRowExec;
 use datafusion::physical_plan::projection::ProjectionExec;
 use datafusion::physical_plan::repartition::RepartitionExec;
+use datafusion::physical_plan::set_operation::SetOperationExec;
 use datafusion::physical_plan::sorts::sort::SortExec;
 use datafusion::physical_plan::union::{InterleaveExec, UnionExec};
 use datafusion::physical_plan::windows::{
@@ -76,8 +81,8 @@ use datafusion_common::stats::Precision;
 use datafusion_common::{not_impl_err, plan_err, DataFusionError, Result};
 use datafusion_expr::{
     Accumulator, AccumulatorFactoryFunction, AggregateUDF, ColumnarValue,
-    ScalarFunctionDefinition, ScalarUDF, ScalarUDFImpl, Signature, SimpleAggregateUDF,
-    WindowFrame, WindowFrameBound,
+    ScalarFunctionDefinition, ScalarUDF, ScalarUDFImpl, SetOperationType, Signature,
+    SimpleAggregateUDF, WindowFrame, WindowFrameBound,
 };
 use datafusion_proto::physical_plan::{
     AsExecutionPlan, DefaultPhysicalExtensionCodec, PhysicalExtensionCodec,
@@ -250,6 +255,102 @@ fn roundtrip_nested_loop_join() -> Result<()> {
     Ok(())
 }
 
+#[test]
+fn roundtrip_range_join() -> Result<()> {
+    let schema_left =
+        Arc::new(Schema::new(vec![Field::new("ts", DataType::Int64, true)]));
+    let schema_right = Arc::new(Schema::new(vec![
+        Field::new("start", DataType::Int64, true),
+        Field::new("end", DataType::Int64, true),
+    ]));
+    let filter_schema = Schema::new(vec![
+        Field::new("ts", DataType::Int64, true),
+        Field::new("start", DataType::Int64, true),
+        Field::new("end", DataType::Int64, true),
+    ]);
+    let expression = binary(
+        binary(
+            col("ts", &filter_schema)?,
+            Operator::GtEq,
+            col("start", &filter_schema)?,
+            &filter_schema,
+        )?,
+        Operator::And,
+        binary(
+            col("ts", &filter_schema)?,
+            Operator::Lt,
+            col("end", &filter_schema)?,
+            &filter_schema,
+        )?,
+        &filter_schema,
+    )?;
+    let filter = JoinFilter::new(
+        expression,
+        JoinFilter::build_column_indices(vec![0], vec![0, 1]),
+        filter_schema,
+    );
+    roundtrip_test(Arc::new(RangeJoinExec::try_new(
+        Arc::new(EmptyExec::new(schema_left)),
+        Arc::new(EmptyExec::new(schema_right)),
+        filter,
+    )?))
+}
+
+#[test]
+fn roundtrip_asof_join() -> Result<()> {
+    let schema_left = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, true),
+        Field::new("ts", DataType::Int64, true),
+    ]));
+    let schema_right = Arc::new(Schema::new(vec![
+        Field::new("id", DataType::Int64, true),
+        Field::new("time", DataType::Int64, true),
+    ]));
+    for op in [Operator::GtEq, Operator::Lt] {
+        roundtrip_test(Arc::new(AsOfJoinExec::try_new(
+            Arc::new(EmptyExec::new(schema_left.clone())),
+            Arc::new(EmptyExec::new(schema_right.clone())),
+            vec![(col("id", &schema_left)?, col("id", &schema_right)?)],
+            col("ts", &schema_left)?,
+            op,
+            col("time", &schema_right)?,
+        )?))?;
+    }
+    Ok(())
+}
+
+#[cfg(feature = "flight")]
+#[test]
+fn roundtrip_flight() -> Result<()> {
+    use datafusion::datasource::flight::{FlightExec, FlightTicket};
+
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+    let ticket = FlightTicket {
+        table: "remote".to_string(),
+        columns: vec!["a".to_string()],
+        filters: vec![r#"("a" > 1)"#.to_string()],
+        limit: Some(10),
+    };
+    roundtrip_test(Arc::new(FlightExec::new(
+        "grpc://localhost:50051",
+        ticket,
+        schema,
+    )))
+}
+
+#[test]
+fn roundtrip_set_operation() -> Result<()> {
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+    for op in [SetOperationType::IntersectAll, SetOperationType::ExceptAll] {
+        roundtrip_test(Arc::new(SetOperationExec::try_new(
+            Arc::new(EmptyExec::new(schema.clone())),
+            Arc::new(EmptyExec::new(schema.clone())),
+            op,
+        )?))?;
+    }
+    Ok(())
+}
+
 #[test]
 fn roundtrip_window() -> Result<()> {
     let field_a = Field::new("a", DataType::Int64, false);
@@ -442,6 +543,40 @@ fn roundtrip_aggregate_udaf() -> Result<()> {
     )
 }
 
+#[test]
+fn roundtrip_approx_quantiles_udaf() -> Result<()> {
+    let field_a = Field::new("a", DataType::Int64, false);
+    let field_b = Field::new("b", DataType::Int64, false);
+    let schema = Arc::new(Schema::new(vec![field_a, field_b]));
+
+    let groups: Vec<(Arc<dyn PhysicalExpr>, String)> =
+        vec![(col("a", &schema)?, "unused".to_string())];
+
+    let quantiles = ScalarValue::List(ScalarValue::new_list(
+        &[ScalarValue::from(0.5), ScalarValue::from(0.99)],
+        &DataType::Float64,
+    ));
+    let aggregates: Vec<Arc<dyn AggregateExpr>> = vec![udaf::create_aggregate_expr(
+        &approx_quantiles_udaf(),
+        &[col("b", &schema)?, lit(quantiles)],
+        &[],
+        &[],
+        &schema,
+        "approx_quantiles_agg",
+        false,
+    )?];
+
+    // The final aggregation merges the serialized digests of the partial states
+    roundtrip_test(Arc::new(AggregateExec::try_new(
+        AggregateMode::Final,
+        PhysicalGroupBy::new_single(groups),
+        aggregates,
+        vec![None],
+        Arc::new(EmptyExec::new(schema.clone())),
+        schema,
+    )?))
+}
+
 #[test]
 fn roundtrip_filter_with_not_and_in_list() -> Result<()> {
     let field_a = Field::new("a", DataType::Boolean, false);
@@ -550,6 +685,7 @@ fn roundtrip_parquet_exec_with_pruning_predicate() -> Result<()> {
         table_partition_cols: vec![],
         output_ordering: vec![],
         column_hints: None,
+        listing_pruning: None,
     };
 
     let predicate = Arc::new(BinaryExpr::new(
@@ -587,6 +723,7 @@ async fn roundtrip_parquet_exec_with_table_partition_cols() -> Result<()> {
         )],
         output_ordering: vec![],
         column_hints: None,
+        listing_pruning: None,
     };
 
     roundtrip_test(Arc::new(ParquetExec::new(
@@ -597,6 +734,64 @@ async fn roundtrip_parquet_exec_with_table_partition_cols() -> Result<()> {
     )))
 }
 
+/// Serializes and deserializes a parquet scan of a file with the extensions
+/// `extensions`, returning the extensions of the file after the roundtrip
+fn roundtrip_file_extensions(
+    extensions: Arc<dyn Any + Send + Sync>,
+) -> Result<Option<Arc<dyn Any + Send + Sync>>> {
+    let mut file = PartitionedFile::new("/path/to/file.parquet".to_string(), 1024);
+    file.extensions = Some(extensions);
+    let schema = Arc::new(Schema::new(vec![Field::new("col", DataType::Utf8, false)]));
+    let scan_config = FileScanConfig {
+        object_store_url: ObjectStoreUrl::local_filesystem(),
+        file_groups: vec![vec![file]],
+        statistics: Statistics::new_unknown(&schema),
+        file_schema: schema,
+        projection: None,
+        limit: None,
+        table_partition_cols: vec![],
+        output_ordering: vec![],
+        column_hints: None,
+        listing_pruning: None,
+    };
+    let exec_plan: Arc<dyn ExecutionPlan> = Arc::new(ParquetExec::new(
+        scan_config,
+        None,
+        None,
+        Default::default(),
+    ));
+
+    let ctx = SessionContext::new();
+    let codec = DefaultPhysicalExtensionCodec {};
+    let proto = protobuf::PhysicalPlanNode::try_from_physical_plan(exec_plan, &codec)?;
+    let plan = proto.try_into_physical_plan(&ctx, ctx.runtime_env().deref(), &codec)?;
+    let exec = plan.as_any().downcast_ref::<ParquetExec>().unwrap();
+    Ok(exec.base_config().file_groups[0][0].extensions.clone())
+}
+
+#[test]
+fn roundtrip_parquet_exec_with_selected_rows() -> Result<()> {
+    let rows = SelectedRows::new(vec![3, 1, 4]);
+    let extensions = roundtrip_file_extensions(Arc::new(rows.clone()))?.unwrap();
+    assert_eq!(extensions.downcast_ref::<SelectedRows>(), Some(&rows));
+
+    // the scans of files with other extensions cannot be serialized
+    let err = roundtrip_file_extensions(Arc::new(42u64)).unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("Cannot serialize the extensions of the file"));
+    Ok(())
+}
+
+#[test]
+fn roundtrip_parquet_exec_with_deleted_rows() -> Result<()> {
+    // the deleted rows of a table format snapshot must not reappear
+    let rows = DeletedRows::new(vec![0, 2]);
+    let extensions = roundtrip_file_extensions(Arc::new(rows.clone()))?.unwrap();
+    assert_eq!(extensions.downcast_ref::<DeletedRows>(), Some(&rows));
+    Ok(())
+}
+
 #[test]
 fn roundtrip_scalar_udf() -> Result<()> {
     let field_a = Field::new("a", DataType::Int64, false);
@@ -0,0 +1,125 @@
+// Licensed to the Apache Software Foundation (ASF) under one
+// or more contributor license agreements.  See the NOTICE file
+// distributed with this work for additional information
+// regarding copyright ownership.  The ASF licenses this file
+// to you under the Apache License, Version 2.0 (the
+// "License"); you may not use this file except in compliance
+// with the License.  You may obtain a copy of the License at
+//
+//   http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing,
+// software distributed under the License is distributed on an
+// "AS IS" BASIS, WITHOUT WARRANTIES OR CONDITIONS OF ANY
+// KIND, either express or implied.  See the License for the
+// specific language governing permissions and limitations
+// under the License.
+
+use std::sync::Arc;
+
+use datafusion::arrow::datatypes::{DataType, Field, Schema};
+use datafusion::physical_plan::coalesce_partitions::CoalescePartitionsExec;
+use datafusion::physical_plan::displayable;
+use datafusion::physical_plan::empty::EmptyExec;
+use datafusion::physical_plan::expressions::col;
+use datafusion::physical_plan::repartition::RepartitionExec;
+use datafusion::physical_plan::{ExecutionPlan, ExecutionPlanProperties, Partitioning};
+use datafusion::prelude::SessionContext;
+use datafusion_common::Result;
+use datafusion_proto::physical_plan::stages::{
+    split_into_stages, stage_plan_from_bytes, ShuffleExchange, StageInputExec,
+};
+use datafusion_proto::physical_plan::DefaultPhysicalExtensionCodec;
+
+/// Reads stage outputs from empty partitions, and writes them by
+/// repartitioning in memory
+#[derive(Debug)]
+struct TestExchange {}
+
+impl ShuffleExchange for TestExchange {
+    fn create_writer(
+        &self,
+        _stage_id: usize,
+        input: Arc<dyn ExecutionPlan>,
+        partitioning: &Partitioning,
+    ) -> Result<Arc<dyn ExecutionPlan>> {
+        Ok(Arc::new(RepartitionExec::try_new(
+            input,
+            partitioning.clone(),
+        )?))
+    }
+
+    fn create_reader(&self, input: &StageInputExec) -> Result<Arc<dyn ExecutionPlan>> {
+        let partitions = input.properties().output_partitioning().partition_count();
+        Ok(Arc::new(
+            EmptyExec::new(input.schema()).with_partitions(partitions),
+        ))
+    }
+}
+
+fn plan() -> Result<Arc<dyn ExecutionPlan>> {
+    let schema = Arc::new(Schema::new(vec![Field::new("a", DataType::Int64, true)]));
+    let repartition = RepartitionExec::try_new(
+        Arc::new(EmptyExec::new(schema.clone())),
+        Partitioning::Hash(vec![col("a", &schema)?], 4),
+    )?;
+    Ok(Arc::new(CoalescePartitionsExec::new(Arc::new(repartition))))
+}
+
+fn format(plan: &Arc<dyn ExecutionPlan>) -> String {
+    displayable(plan.as_ref()).indent(true).to_string()
+}
+
+#[test]
+fn split_plan_into_stages() -> Result<()> {
+    let stages = split_into_stages(plan()?)?;
+    assert_eq!(stages.len(), 2);
+
+    assert_eq!(stages[0].id(), 0);
+    assert_eq!(format(stages[0].plan()), "EmptyExec\n");
+    assert_eq!(
+        stages[0].output_partitioning().map(|p| p.to_string()),
+        Some("Hash([a@0], 4)".to_string())
+    );
+    assert!(stages[0].input_stages()?.is_empty());
+
+    assert_eq!(stages[1].id(), 1);
+    assert_eq!(
+        format(stages[1].plan()),
+        "CoalescePartitionsExec\n  StageInputExec: stage=0, partitioning=Hash([a@0], 4)\n"
+    );
+    assert!(stages[1].output_partitioning().is_none());
+    assert_eq!(stages[1].input_stages()?, vec![0]);
+    Ok(())
+}
+
+#[test]
+fn roundtrip_stages() -> Result<()> {
+    let ctx = SessionContext::new();
+    let codec = DefaultPhysicalExtensionCodec {};
+    for stage in split_into_stages(plan()?)? {
+        let bytes = stage.to_bytes(&codec)?;
+        let plan = stage_plan_from_bytes(&bytes, &ctx, &codec)?;
+        assert_eq!(format(&plan), format(stage.plan()));
+        assert_eq!(
+            plan.output_partitioning().partition_count(),
+            stage.plan().output_partitioning().partition_count()
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn assemble_stages() -> Result<()> {
+    let exchange = TestExchange {};
+    let stages = split_into_stages(plan()?)?;
+    assert_eq!(
+        format(&stages[0].assemble(&exchange)?),
+        "RepartitionExec: partitioning=Hash([a@0], 4), input_partitions=1\n  EmptyExec\n"
+    );
+    assert_eq!(
+        format(&stages[1].assemble(&exchange)?),
+        "CoalescePartitionsExec\n  EmptyExec\n"
+    );
+    Ok(())
+}
@@ -0,0 +1,2 @@
+
+R8
\ No newline at end of file
@@ -23,7 +23,8 @@ use datafusion_common::{
 };
 use datafusion_expr::window_frame::{check_window_frame, regularize_window_order_by};
 use datafusion_expr::{
-    expr, AggregateFunction, Expr, ExprSchemable, WindowFrame, WindowFunctionDefinition,
+    expr, lit, AggregateFunction, Expr, ExprSchemable, WindowFrame,
+    WindowFunctionDefinition,
 };
 use datafusion_expr::{
     expr::{ScalarFunction, Unnest},
@@ -112,6 +113,29 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
         // user-defined function (UDF) should have precedence
         if let Some(fm) = self.context_provider.get_function_meta(&name) {
+            // `struct(*)` is the struct of all the columns, named as them
+            if name == "struct"
+                && matches!(
+                    args.as_slice(),
+                    [FunctionArg::Unnamed(FunctionArgExpr::Wildcard)]
+                )
+            {
+                if let Some(named_struct) =
+                    self.context_provider.get_function_meta("named_struct")
+                {
+                    let args = schema
+                        .columns()
+                        .into_iter()
+                        .flat_map(|column| {
+                            [lit(column.name.clone()), Expr::Column(column)]
+                        })
+                        .collect();
+                    return Ok(Expr::ScalarFunction(ScalarFunction::new_udf(
+                        named_struct,
+                        args,
+                    )));
+                }
+            }
             let args = self.function_args_to_expr(args, schema, planner_context)?;
             return Ok(Expr::ScalarFunction(ScalarFunction::new_udf(fm, args)));
         }
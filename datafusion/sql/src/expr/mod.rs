@@ -15,6 +15,8 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use std::collections::HashMap;
+
 use arrow_schema::DataType;
 use arrow_schema::TimeUnit;
 use sqlparser::ast::{ArrayAgg, Expr as SQLExpr, JsonOperator, TrimWhereField, Value};
@@ -32,7 +34,8 @@ use datafusion_expr::{
     GetFieldAccess, GetIndexedField, Like, Literal, Operator, TryCast,
 };
 
-use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use crate::planner::{collation_name, ContextProvider, PlannerContext, SqlToRel};
+use crate::utils::resolve_aliases_to_exprs;
 
 mod binary_op;
 mod function;
@@ -130,6 +133,23 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         Ok(expr)
     }
 
+    /// Generate a relational expression from a SQL expression of a SELECT item,
+    /// which may reference the `lateral_aliases` of the earlier SELECT items
+    pub(crate) fn sql_to_expr_with_lateral_aliases(
+        &self,
+        sql: SQLExpr,
+        schema: &DFSchema,
+        lateral_aliases: &HashMap<String, Expr>,
+        planner_context: &mut PlannerContext,
+    ) -> Result<Expr> {
+        let mut expr = self.sql_expr_to_logical_expr(sql, schema, planner_context)?;
+        expr = self.rewrite_partial_qualifier(expr, schema);
+        expr = resolve_aliases_to_exprs(&expr, lateral_aliases)?;
+        self.validate_schema_satisfies_exprs(schema, &[expr.clone()])?;
+        let expr = expr.infer_placeholder_types(schema)?;
+        Ok(expr)
+    }
+
     /// Rewrite aliases which are not-complete (e.g. ones that only include only table qualifier in a schema.table qualified relation)
     fn rewrite_partial_qualifier(&self, expr: Expr, schema: &DFSchema) -> Expr {
         match expr {
@@ -573,6 +593,21 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 )?),
                 DataType::Timestamp(TimeUnit::Nanosecond, Some(time_zone.into())),
             ))),
+            SQLExpr::Collate { expr, collation } => {
+                let collate = self
+                    .context_provider
+                    .get_function_meta("collate")
+                    .ok_or_else(|| {
+                        internal_datafusion_err!(
+                            "Unable to find expected 'collate' function"
+                        )
+                    })?;
+                let args = vec![
+                    self.sql_expr_to_logical_expr(*expr, schema, planner_context)?,
+                    lit(collation_name(&collation)),
+                ];
+                Ok(Expr::ScalarFunction(ScalarFunction::new_udf(collate, args)))
+            }
             _ => not_impl_err!("Unsupported ast node in sqltorel: {sql:?}"),
         }
     }
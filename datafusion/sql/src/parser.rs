@@ -19,16 +19,18 @@
 
 use std::collections::{HashMap, VecDeque};
 use std::fmt;
+use std::iter::Peekable;
 use std::str::FromStr;
 
 use datafusion_common::parsers::CompressionTypeVariant;
 use sqlparser::{
     ast::{
-        ColumnDef, ColumnOptionDef, ObjectName, OrderByExpr, Query,
-        Statement as SQLStatement, TableConstraint, Value,
+        ArrayElemTypeDef, ColumnDef, ColumnOptionDef, DataType, Expr, Ident, ObjectName,
+        OrderByExpr, Query, Statement as SQLStatement, StructField, TableConstraint,
+        Value,
     },
     dialect::{keywords::Keyword, Dialect, GenericDialect},
-    parser::{Parser, ParserError},
+    parser::{IsOptional, Parser, ParserError},
     tokenizer::{Token, TokenWithLocation, Tokenizer, Word},
 };
 
@@ -39,6 +41,273 @@ macro_rules! parser_err {
     };
 }
 
+/// The name of the table hint that carries a `TABLESAMPLE` clause, see
+/// [`rewrite_table_clauses`]
+pub(crate) const TABLESAMPLE_HINT: &str = "TABLESAMPLE";
+
+/// The name of the table hint that carries a `FOR SYSTEM_TIME AS OF` or
+/// `VERSION AS OF` clause, see [`rewrite_table_clauses`]
+pub(crate) const TABLE_VERSION_HINT: &str = "TABLE_VERSION";
+
+/// The name of the function that carries the `MATCH_CONDITION` of an
+/// `ASOF JOIN` in the `ON` clause of the join, see [`rewrite_asof_joins`]
+pub(crate) const ASOF_MATCH_CONDITION: &str = "ASOF_MATCH_CONDITION";
+
+fn is_word(token: &Token, word: &str) -> bool {
+    matches!(
+        token,
+        Token::Word(w) if w.quote_style.is_none() && w.value.eq_ignore_ascii_case(word)
+    )
+}
+
+fn expect_number(token: Option<Token>, expected: &str) -> Result<Token, ParserError> {
+    match token {
+        Some(token @ Token::Number(..)) => Ok(token),
+        Some(token) => parser_err!(format!("Expected {expected}, found: {token}")),
+        None => parser_err!(format!("Expected {expected}, found: EOF")),
+    }
+}
+
+fn expect_token(token: Option<Token>, expected: Token) -> Result<(), ParserError> {
+    match token {
+        Some(token) if token == expected => Ok(()),
+        Some(token) => parser_err!(format!("Expected {expected}, found: {token}")),
+        None => parser_err!(format!("Expected {expected}, found: EOF")),
+    }
+}
+
+fn expect_word(token: Option<Token>, expected: &str) -> Result<(), ParserError> {
+    match token {
+        Some(token) if is_word(&token, expected) => Ok(()),
+        Some(token) => parser_err!(format!("Expected {expected}, found: {token}")),
+        None => parser_err!(format!("Expected {expected}, found: EOF")),
+    }
+}
+
+fn hint_name(name: &str) -> Token {
+    Token::Word(Word {
+        value: name.to_string(),
+        quote_style: None,
+        keyword: Keyword::NoKeyword,
+    })
+}
+
+/// Rewrites the clauses following table names which [`sqlparser`] does not
+/// support into table hints, all in a single `WITH (...)` clause:
+///
+/// * `TABLESAMPLE BERNOULLI | SYSTEM (<percentage> [PERCENT])
+///   [REPEATABLE (<seed>)]` into `TABLESAMPLE('<method>', <percentage>[, <seed>])`,
+///   planned as a sampled table scan
+/// * `FOR SYSTEM_TIME AS OF <timestamp>` and `VERSION AS OF <version>` into
+///   `TABLE_VERSION('SYSTEM_TIME' | 'VERSION', <value>)`, planned as a scan of
+///   a version of the table. The value is a number, a string or a
+///   `TIMESTAMP '<timestamp>'` literal.
+fn rewrite_table_clauses(tokens: Vec<Token>) -> Result<Vec<Token>, ParserError> {
+    if !tokens.iter().any(|t| {
+        is_word(t, TABLESAMPLE_HINT) || is_word(t, "SYSTEM_TIME") || is_word(t, "VERSION")
+    }) {
+        return Ok(tokens);
+    }
+
+    let mut rewritten = vec![];
+    // The length of `rewritten` after the `WITH (...)` clause of the hints
+    // added last, which the hints of the next clause of the table are added to
+    let mut hints_end = None;
+    let mut tokens = tokens
+        .into_iter()
+        .filter(|t| !matches!(t, Token::Whitespace(_)))
+        .peekable();
+    while let Some(token) = tokens.next() {
+        let hint = if is_word(&token, TABLESAMPLE_HINT) {
+            // `TABLESAMPLE(` is the hint itself, e.g. from a displayed statement
+            if tokens.peek() == Some(&Token::LParen) {
+                rewritten.push(token);
+                continue;
+            }
+            table_sample_hint(&mut tokens)?
+        } else if is_word(&token, "FOR")
+            && tokens.peek().map_or(false, |t| is_word(t, "SYSTEM_TIME"))
+        {
+            tokens.next();
+            expect_word(tokens.next(), "AS")?;
+            expect_word(tokens.next(), "OF")?;
+            table_version_hint("SYSTEM_TIME", &mut tokens)?
+        } else if is_word(&token, "VERSION")
+            && tokens.peek().map_or(false, |t| is_word(t, "AS"))
+        {
+            // `VERSION AS <alias>` is an aliased column
+            let as_token = tokens.next().unwrap();
+            if !tokens.peek().map_or(false, |t| is_word(t, "OF")) {
+                rewritten.extend([token, as_token]);
+                continue;
+            }
+            tokens.next();
+            table_version_hint("VERSION", &mut tokens)?
+        } else {
+            rewritten.push(token);
+            continue;
+        };
+
+        if hints_end == Some(rewritten.len()) {
+            rewritten.pop();
+            rewritten.push(Token::Comma);
+        } else {
+            rewritten.extend([Token::make_keyword("WITH"), Token::LParen]);
+        }
+        rewritten.extend(hint);
+        rewritten.push(Token::RParen);
+        hints_end = Some(rewritten.len());
+    }
+    Ok(rewritten)
+}
+
+/// Parses the rest of a `TABLESAMPLE` clause into the `TABLESAMPLE` hint
+fn table_sample_hint(
+    tokens: &mut Peekable<impl Iterator<Item = Token>>,
+) -> Result<Vec<Token>, ParserError> {
+    let method = match tokens.next() {
+        Some(Token::Word(w))
+            if w.value.eq_ignore_ascii_case("BERNOULLI")
+                || w.value.eq_ignore_ascii_case("SYSTEM") =>
+        {
+            w.value.to_uppercase()
+        }
+        Some(token) => {
+            return parser_err!(format!("Expected BERNOULLI or SYSTEM, found: {token}"))
+        }
+        None => return parser_err!("Expected BERNOULLI or SYSTEM, found: EOF"),
+    };
+    expect_token(tokens.next(), Token::LParen)?;
+    let percentage = expect_number(tokens.next(), "sample percentage")?;
+    if tokens.peek().map_or(false, |t| is_word(t, "PERCENT")) {
+        tokens.next();
+    }
+    expect_token(tokens.next(), Token::RParen)?;
+    let seed = if tokens.peek().map_or(false, |t| is_word(t, "REPEATABLE")) {
+        tokens.next();
+        expect_token(tokens.next(), Token::LParen)?;
+        let seed = expect_number(tokens.next(), "sample seed")?;
+        expect_token(tokens.next(), Token::RParen)?;
+        Some(seed)
+    } else {
+        None
+    };
+
+    let mut hint = vec![
+        hint_name(TABLESAMPLE_HINT),
+        Token::LParen,
+        Token::SingleQuotedString(method),
+        Token::Comma,
+        percentage,
+    ];
+    if let Some(seed) = seed {
+        hint.extend([Token::Comma, seed]);
+    }
+    hint.push(Token::RParen);
+    Ok(hint)
+}
+
+/// Parses the value of a `FOR SYSTEM_TIME AS OF` or `VERSION AS OF` clause
+/// into the `TABLE_VERSION` hint
+fn table_version_hint(
+    kind: &str,
+    tokens: &mut impl Iterator<Item = Token>,
+) -> Result<Vec<Token>, ParserError> {
+    let mut hint = vec![
+        hint_name(TABLE_VERSION_HINT),
+        Token::LParen,
+        Token::SingleQuotedString(kind.to_string()),
+        Token::Comma,
+    ];
+    match tokens.next() {
+        Some(token @ (Token::Number(..) | Token::SingleQuotedString(_))) => {
+            hint.push(token)
+        }
+        Some(token) if is_word(&token, "TIMESTAMP") => match tokens.next() {
+            Some(value @ Token::SingleQuotedString(_)) => hint.extend([token, value]),
+            Some(value) => {
+                return parser_err!(format!("Expected timestamp, found: {value}"))
+            }
+            None => return parser_err!("Expected timestamp, found: EOF"),
+        },
+        Some(token) => {
+            return parser_err!(format!("Expected version or timestamp, found: {token}"))
+        }
+        None => return parser_err!("Expected version or timestamp, found: EOF"),
+    }
+    hint.push(Token::RParen);
+    Ok(hint)
+}
+
+/// Rewrites the `ASOF JOIN`s, which [`sqlparser`] does not support, into left
+/// joins whose `ON` clause starts with the match condition:
+///
+/// `ASOF JOIN <relation> MATCH_CONDITION (<condition>) [ON <keys>]` into
+/// `LEFT JOIN <relation> ON ASOF_MATCH_CONDITION(<condition>) [AND <keys>]`,
+/// planned as an ASOF join
+fn rewrite_asof_joins(tokens: Vec<Token>) -> Result<Vec<Token>, ParserError> {
+    if !tokens.iter().any(|t| is_word(t, "ASOF")) {
+        return Ok(tokens);
+    }
+
+    let mut rewritten = vec![];
+    // The parenthesis depths of the ASOF joins whose match condition is not
+    // rewritten yet, the relation joined being before it
+    let mut pending = vec![];
+    let mut depth = 0;
+    let mut tokens = tokens
+        .into_iter()
+        .filter(|t| !matches!(t, Token::Whitespace(_)))
+        .peekable();
+    while let Some(token) = tokens.next() {
+        match token {
+            Token::LParen => depth += 1,
+            Token::RParen => depth -= 1,
+            _ => {}
+        }
+        if is_word(&token, "ASOF") && tokens.peek().map_or(false, |t| is_word(t, "JOIN"))
+        {
+            rewritten.extend([Token::make_keyword("LEFT"), tokens.next().unwrap()]);
+            pending.push(depth);
+        } else if is_word(&token, "MATCH_CONDITION") && pending.last() == Some(&depth) {
+            pending.pop();
+            expect_token(tokens.next(), Token::LParen)?;
+            rewritten.extend([
+                Token::make_keyword("ON"),
+                hint_name(ASOF_MATCH_CONDITION),
+                Token::LParen,
+            ]);
+            let mut condition_depth = 1;
+            loop {
+                let token = match tokens.next() {
+                    Some(token) => token,
+                    None => return parser_err!("Expected ), found: EOF"),
+                };
+                match token {
+                    Token::LParen => condition_depth += 1,
+                    Token::RParen => condition_depth -= 1,
+                    _ => {}
+                }
+                if condition_depth == 0 {
+                    break;
+                }
+                rewritten.push(token);
+            }
+            rewritten.push(Token::RParen);
+            if tokens.peek().map_or(false, |t| is_word(t, "ON")) {
+                tokens.next();
+                rewritten.push(Token::make_keyword("AND"));
+            }
+        } else {
+            rewritten.push(token);
+        }
+    }
+    if !pending.is_empty() {
+        return parser_err!("Expected MATCH_CONDITION after the relation of ASOF JOIN");
+    }
+    Ok(rewritten)
+}
+
 fn parse_file_type(s: &str) -> Result<String, ParserError> {
     Ok(s.to_uppercase())
 }
@@ -143,6 +412,56 @@ impl fmt::Display for CopyToStatement {
     }
 }
 
+/// DataFusion extension statement for `ANALYZE TABLE`, which computes the
+/// statistics of a table and stores them on the table.
+///
+/// Syntax:
+///
+/// ```text
+/// ANALYZE TABLE <table_name> [COLUMNS (<column_name>, ...)]
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct AnalyzeTableStatement {
+    /// Table name
+    pub table_name: ObjectName,
+    /// Columns to compute the statistics of, all columns if empty
+    pub columns: Vec<Ident>,
+}
+
+impl fmt::Display for AnalyzeTableStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "ANALYZE TABLE {}", self.table_name)?;
+        if !self.columns.is_empty() {
+            let columns: Vec<_> = self.columns.iter().map(|c| c.to_string()).collect();
+            write!(f, " COLUMNS ({})", columns.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// DataFusion extension statement `RESET`, which restores the default value of
+/// a configuration option, or of all of them.
+///
+/// Syntax:
+///
+/// ```text
+/// RESET { <variable> | ALL }
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ResetStatement {
+    /// The option to reset, all the options if `None`
+    pub variable: Option<ObjectName>,
+}
+
+impl fmt::Display for ResetStatement {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.variable {
+            Some(variable) => write!(f, "RESET {variable}"),
+            None => write!(f, "RESET ALL"),
+        }
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub enum CopyToSource {
     /// `COPY <table> TO ...`
@@ -175,13 +494,15 @@ pub(crate) type LexOrdering = Vec<OrderByExpr>;
 /// [ WITH HEADER ROW ]
 /// [ DELIMITER <char> ]
 /// [ COMPRESSION TYPE <GZIP | BZIP2 | XZ | ZSTD> ]
-/// [ PARTITIONED BY (<column_definition list> | <column list>) ]
+/// [ PARTITIONED BY (<partition_column_definition list> | <column list>) ]
 /// [ WITH ORDER (<ordered column list>)
 /// [ OPTIONS (<key_value_list>) ]
 /// LOCATION <literal>
 ///
 /// <column_definition> := (<column_name> <data_type>, ...)
 ///
+/// <partition_column_definition> := (<column_name> <data_type> [FROM <expr>], ...)
+///
 /// <column_list> := (<column_name>, ...)
 ///
 /// <ordered_column_list> := (<column_name> <sort_clause>, ...)
@@ -204,6 +525,9 @@ pub struct CreateExternalTable {
     pub location: String,
     /// Partition Columns
     pub table_partition_cols: Vec<String>,
+    /// The expressions of the partition columns derived from the paths of
+    /// the files, by partition column
+    pub partition_transforms: HashMap<String, Expr>,
     /// Ordered expressions
     pub order_exprs: Vec<LexOrdering>,
     /// Option to not error if table already exists
@@ -247,6 +571,10 @@ pub enum Statement {
     CopyTo(CopyToStatement),
     /// EXPLAIN for extensions
     Explain(ExplainStatement),
+    /// Extension: `ANALYZE TABLE`
+    AnalyzeTable(AnalyzeTableStatement),
+    /// Extension: `RESET`
+    Reset(ResetStatement),
 }
 
 impl fmt::Display for Statement {
@@ -256,6 +584,8 @@ impl fmt::Display for Statement {
             Statement::CreateExternalTable(stmt) => write!(f, "{stmt}"),
             Statement::CopyTo(stmt) => write!(f, "{stmt}"),
             Statement::Explain(stmt) => write!(f, "{stmt}"),
+            Statement::AnalyzeTable(stmt) => write!(f, "{stmt}"),
+            Statement::Reset(stmt) => write!(f, "{stmt}"),
         }
     }
 }
@@ -296,7 +626,7 @@ impl<'a> DFParser<'a> {
         dialect: &'a dyn Dialect,
     ) -> Result<Self, ParserError> {
         let mut tokenizer = Tokenizer::new(dialect, sql);
-        let tokens = tokenizer.tokenize()?;
+        let tokens = rewrite_asof_joins(rewrite_table_clauses(tokenizer.tokenize()?)?)?;
 
         Ok(DFParser {
             parser: Parser::new(dialect).with_tokens(tokens),
@@ -366,6 +696,14 @@ impl<'a> DFParser<'a> {
                         self.parser.next_token(); // EXPLAIN
                         self.parse_explain()
                     }
+                    Keyword::ANALYZE => {
+                        self.parser.next_token(); // ANALYZE
+                        self.parse_analyze_table()
+                    }
+                    _ if w.value.eq_ignore_ascii_case("RESET") => {
+                        self.parser.next_token(); // RESET
+                        self.parse_reset()
+                    }
                     _ => {
                         // use sqlparser-rs parser
                         Ok(Statement::Statement(Box::from(
@@ -527,6 +865,33 @@ impl<'a> DFParser<'a> {
         }))
     }
 
+    /// Parse a SQL `ANALYZE TABLE` statement
+    pub fn parse_analyze_table(&mut self) -> Result<Statement, ParserError> {
+        self.parser.expect_keyword(Keyword::TABLE)?;
+        let table_name = self.parser.parse_object_name(true)?;
+        let columns = if self.parser.parse_keyword(Keyword::COLUMNS) {
+            self.parser
+                .parse_parenthesized_column_list(IsOptional::Mandatory, false)?
+        } else {
+            vec![]
+        };
+
+        Ok(Statement::AnalyzeTable(AnalyzeTableStatement {
+            table_name,
+            columns,
+        }))
+    }
+
+    /// Parse a SQL `RESET` statement
+    pub fn parse_reset(&mut self) -> Result<Statement, ParserError> {
+        let variable = if self.parser.parse_keyword(Keyword::ALL) {
+            None
+        } else {
+            Some(self.parser.parse_object_name(false)?)
+        };
+        Ok(Statement::Reset(ResetStatement { variable }))
+    }
+
     /// Parse a SQL `CREATE` statement handling `CREATE EXTERNAL TABLE`
     pub fn parse_create(&mut self) -> Result<Statement, ParserError> {
         if self.parser.parse_keyword(Keyword::EXTERNAL) {
@@ -650,9 +1015,54 @@ impl<'a> DFParser<'a> {
         Ok((columns, constraints))
     }
 
+    /// Parses the column definitions of a `PARTITIONED BY` clause, and the
+    /// expressions `FROM <expr>` of the columns derived from the paths of the
+    /// files
+    fn parse_partition_columns(
+        &mut self,
+    ) -> Result<(Vec<ColumnDef>, HashMap<String, Expr>), ParserError> {
+        let mut columns = vec![];
+        let mut transforms = HashMap::new();
+        if !self.parser.consume_token(&Token::LParen)
+            || self.parser.consume_token(&Token::RParen)
+        {
+            return Ok((columns, transforms));
+        }
+
+        loop {
+            if self.parser.parse_optional_table_constraint()?.is_some() {
+                return parser_err!("Constraints on Partition Columns are not supported");
+            } else if let Token::Word(_) = self.parser.peek_token().token {
+                let column_def = self.parse_column_def()?;
+                if self.parser.parse_keyword(Keyword::FROM) {
+                    let expr = self.parser.parse_expr()?;
+                    transforms.insert(column_def.name.to_string(), expr);
+                }
+                columns.push(column_def);
+            } else {
+                return self.expected("column name", self.parser.peek_token());
+            }
+            let comma = self.parser.consume_token(&Token::Comma);
+            if self.parser.consume_token(&Token::RParen) {
+                // allow a trailing comma, even though it's not in standard
+                break;
+            } else if !comma {
+                return self.expected(
+                    "',' or ')' after column definition",
+                    self.parser.peek_token(),
+                );
+            }
+        }
+
+        Ok((columns, transforms))
+    }
+
     fn parse_column_def(&mut self) -> Result<ColumnDef, ParserError> {
         let name = self.parser.parse_identifier(false)?;
-        let data_type = self.parser.parse_data_type()?;
+        let data_type = match self.parse_nested_data_type()? {
+            (_, true) => return parser_err!("unmatched > after parsing data type"),
+            (data_type, false) => data_type,
+        };
         let collation = if self.parser.parse_keyword(Keyword::COLLATE) {
             Some(self.parser.parse_object_name(false)?)
         } else {
@@ -684,6 +1094,62 @@ impl<'a> DFParser<'a> {
         })
     }
 
+    /// Parses a data type, including the `STRUCT<name type, ...>` and
+    /// `ARRAY<type>` types nesting each other, which sqlparser only parses for
+    /// some dialects. The returned flag is true if the closing `>>` of the type
+    /// also closes the type enclosing it.
+    fn parse_nested_data_type(&mut self) -> Result<(DataType, bool), ParserError> {
+        let nested = |keyword| {
+            matches!(
+                &self.parser.peek_token().token,
+                Token::Word(w) if w.quote_style.is_none() && w.keyword == keyword
+            ) && self.parser.peek_nth_token(1).token == Token::Lt
+        };
+        if nested(Keyword::STRUCT) {
+            self.parser.next_token();
+            self.parser.next_token();
+            let mut fields = vec![];
+            loop {
+                let field_name = self.parser.parse_identifier(false)?;
+                let (field_type, closed) = self.parse_nested_data_type()?;
+                fields.push(StructField {
+                    field_name: Some(field_name),
+                    field_type,
+                });
+                if closed {
+                    return Ok((DataType::Struct(fields), false));
+                }
+                if !self.parser.consume_token(&Token::Comma) {
+                    let closed = self.parse_closing_angle_bracket()?;
+                    return Ok((DataType::Struct(fields), closed));
+                }
+            }
+        } else if nested(Keyword::ARRAY) {
+            self.parser.next_token();
+            self.parser.next_token();
+            let (inner, closed) = self.parse_nested_data_type()?;
+            let data_type = DataType::Array(ArrayElemTypeDef::AngleBracket(inner.into()));
+            if closed {
+                Ok((data_type, false))
+            } else {
+                Ok((data_type, self.parse_closing_angle_bracket()?))
+            }
+        } else {
+            Ok((self.parser.parse_data_type()?, false))
+        }
+    }
+
+    /// Parses the `>` closing a nested data type, returning true if it was
+    /// part of a `>>` also closing the enclosing type
+    fn parse_closing_angle_bracket(&mut self) -> Result<bool, ParserError> {
+        let token = self.parser.next_token();
+        match token.token {
+            Token::Gt => Ok(false),
+            Token::ShiftRight => Ok(true),
+            _ => self.expected("> after nested data type", token),
+        }
+    }
+
     fn parse_create_external_table(
         &mut self,
         unbounded: bool,
@@ -703,6 +1169,7 @@ impl<'a> DFParser<'a> {
             delimiter: Option<char>,
             file_compression_type: Option<CompressionTypeVariant>,
             table_partition_cols: Option<Vec<String>>,
+            partition_transforms: HashMap<String, Expr>,
             order_exprs: Vec<LexOrdering>,
             options: Option<HashMap<String, String>>,
         }
@@ -764,19 +1231,13 @@ impl<'a> DFParser<'a> {
                             builder.table_partition_cols = Some(self.parse_partitions()?)
                         } else {
                             // list of column defs
-                            let (cols, cons) = self.parse_columns()?;
+                            let (cols, transforms) = self.parse_partition_columns()?;
                             builder.table_partition_cols = Some(
                                 cols.iter().map(|col| col.name.to_string()).collect(),
                             );
+                            builder.partition_transforms = transforms;
 
                             columns.extend(cols);
-
-                            if !cons.is_empty() {
-                                return Err(ParserError::ParserError(
-                                    "Constraints on Partition Columns are not supported"
-                                        .to_string(),
-                                ));
-                            }
                         }
                     }
                     Keyword::OPTIONS => {
@@ -819,6 +1280,7 @@ impl<'a> DFParser<'a> {
             delimiter: builder.delimiter.unwrap_or(','),
             location: builder.location.unwrap(),
             table_partition_cols: builder.table_partition_cols.unwrap_or(vec![]),
+            partition_transforms: builder.partition_transforms,
             order_exprs: builder.order_exprs,
             if_not_exists,
             file_compression_type: builder
@@ -973,6 +1435,7 @@ mod tests {
             delimiter: ',',
             location: "foo.csv".into(),
             table_partition_cols: vec![],
+            partition_transforms: HashMap::new(),
             order_exprs: vec![],
             if_not_exists: false,
             file_compression_type: UNCOMPRESSED,
@@ -992,6 +1455,7 @@ mod tests {
             delimiter: ',',
             location: "foo.csv".into(),
             table_partition_cols: vec![],
+            partition_transforms: HashMap::new(),
             order_exprs: vec![],
             if_not_exists: false,
             file_compression_type: UNCOMPRESSED,
@@ -1012,6 +1476,7 @@ mod tests {
             delimiter: ',',
             location: "foo.csv".into(),
             table_partition_cols: vec![],
+            partition_transforms: HashMap::new(),
             order_exprs: vec![],
             if_not_exists: false,
             file_compression_type: UNCOMPRESSED,
@@ -1032,6 +1497,7 @@ mod tests {
             delimiter: '|',
             location: "foo.csv".into(),
             table_partition_cols: vec![],
+            partition_transforms: HashMap::new(),
             order_exprs: vec![],
             if_not_exists: false,
             file_compression_type: UNCOMPRESSED,
@@ -1052,6 +1518,7 @@ mod tests {
             delimiter: ',',
             location: "foo.csv".into(),
             table_partition_cols: vec!["p1".to_string(), "p2".to_string()],
+            partition_transforms: HashMap::new(),
             order_exprs: vec![],
             if_not_exists: false,
             file_compression_type: UNCOMPRESSED,
@@ -1075,6 +1542,7 @@ mod tests {
                 delimiter: ',',
                 location: "foo.csv".into(),
                 table_partition_cols: vec![],
+                partition_transforms: HashMap::new(),
                 order_exprs: vec![],
                 if_not_exists: false,
                 file_compression_type: UNCOMPRESSED,
@@ -1101,6 +1569,7 @@ mod tests {
                 delimiter: ',',
                 location: "foo.csv".into(),
                 table_partition_cols: vec![],
+                partition_transforms: HashMap::new(),
                 order_exprs: vec![],
                 if_not_exists: false,
                 file_compression_type: CompressionTypeVariant::from_str(
@@ -1123,6 +1592,7 @@ mod tests {
             delimiter: ',',
             location: "foo.parquet".into(),
             table_partition_cols: vec![],
+            partition_transforms: HashMap::new(),
             order_exprs: vec![],
             if_not_exists: false,
             file_compression_type: UNCOMPRESSED,
@@ -1142,6 +1612,7 @@ mod tests {
             delimiter: ',',
             location: "foo.parquet".into(),
             table_partition_cols: vec![],
+            partition_transforms: HashMap::new(),
             order_exprs: vec![],
             if_not_exists: false,
             file_compression_type: UNCOMPRESSED,
@@ -1161,6 +1632,7 @@ mod tests {
             delimiter: ',',
             location: "foo.avro".into(),
             table_partition_cols: vec![],
+            partition_transforms: HashMap::new(),
             order_exprs: vec![],
             if_not_exists: false,
             file_compression_type: UNCOMPRESSED,
@@ -1181,6 +1653,7 @@ mod tests {
             delimiter: ',',
             location: "foo.parquet".into(),
             table_partition_cols: vec![],
+            partition_transforms: HashMap::new(),
             order_exprs: vec![],
             if_not_exists: true,
             file_compression_type: UNCOMPRESSED,
@@ -1204,6 +1677,7 @@ mod tests {
             delimiter: ',',
             location: "foo.csv".into(),
             table_partition_cols: vec!["p1".to_string()],
+            partition_transforms: HashMap::new(),
             order_exprs: vec![],
             if_not_exists: false,
             file_compression_type: UNCOMPRESSED,
@@ -1213,6 +1687,36 @@ mod tests {
         });
         expect_parse_ok(sql, expected)?;
 
+        // positive case: partition columns derived from the paths of the files
+        let sql = "CREATE EXTERNAL TABLE t(c1 int) STORED AS CSV \
+            PARTITIONED BY (d DATE FROM to_date(split_part(path, '/', 1)), p1 int) \
+            LOCATION 'foo/'";
+        let Statement::CreateExternalTable(create) = &DFParser::parse_sql(sql)?[0] else {
+            panic!("Expected a CREATE EXTERNAL TABLE statement");
+        };
+        assert_eq!(create.table_partition_cols, ["d", "p1"]);
+        assert_eq!(create.partition_transforms.len(), 1);
+        assert_eq!(
+            create.partition_transforms["d"].to_string(),
+            "to_date(split_part(path, '/', 1))"
+        );
+
+        // positive case: nested struct and array column types
+        let sql =
+            "CREATE EXTERNAL TABLE t(s STRUCT<a INT, \"B\" ARRAY<STRUCT<c VARCHAR>>>) \
+            STORED AS JSON LOCATION 'foo/'";
+        let Statement::CreateExternalTable(create) = &DFParser::parse_sql(sql)?[0] else {
+            panic!("Expected a CREATE EXTERNAL TABLE statement");
+        };
+        assert_eq!(
+            create.columns[0].data_type.to_string(),
+            "STRUCT<a INT, \"B\" ARRAY<STRUCT<c VARCHAR>>>"
+        );
+
+        // negative case: unmatched `>` after a nested column type
+        let sql = "CREATE EXTERNAL TABLE t(a ARRAY<INT>>) STORED AS JSON LOCATION 'foo/'";
+        expect_parse_error(sql, "sql parser error: unmatched > after parsing data type");
+
         // negative case: mixed column defs and column names in `PARTITIONED BY` clause
         let sql =
             "CREATE EXTERNAL TABLE t(c1 int) STORED AS CSV PARTITIONED BY (p1 int, c1) LOCATION 'foo.csv'";
@@ -1234,6 +1738,7 @@ mod tests {
             delimiter: ',',
             location: "blahblah".into(),
             table_partition_cols: vec![],
+            partition_transforms: HashMap::new(),
             order_exprs: vec![],
             if_not_exists: false,
             file_compression_type: UNCOMPRESSED,
@@ -1254,6 +1759,7 @@ mod tests {
             delimiter: ',',
             location: "blahblah".into(),
             table_partition_cols: vec![],
+            partition_transforms: HashMap::new(),
             order_exprs: vec![],
             if_not_exists: false,
             file_compression_type: UNCOMPRESSED,
@@ -1296,6 +1802,7 @@ mod tests {
                 delimiter: ',',
                 location: "foo.csv".into(),
                 table_partition_cols: vec![],
+                partition_transforms: HashMap::new(),
                 order_exprs: vec![vec![OrderByExpr {
                     expr: Identifier(Ident {
                         value: "c1".to_owned(),
@@ -1327,6 +1834,7 @@ mod tests {
             delimiter: ',',
             location: "foo.csv".into(),
             table_partition_cols: vec![],
+            partition_transforms: HashMap::new(),
             order_exprs: vec![vec![
                 OrderByExpr {
                     expr: Identifier(Ident {
@@ -1367,6 +1875,7 @@ mod tests {
             delimiter: ',',
             location: "foo.csv".into(),
             table_partition_cols: vec![],
+            partition_transforms: HashMap::new(),
             order_exprs: vec![vec![OrderByExpr {
                 expr: Expr::BinaryOp {
                     left: Box::new(Identifier(Ident {
@@ -1413,6 +1922,7 @@ mod tests {
             delimiter: '*',
             location: "foo.parquet".into(),
             table_partition_cols: vec!["c1".into()],
+            partition_transforms: HashMap::new(),
             order_exprs: vec![vec![OrderByExpr {
                 expr: Expr::BinaryOp {
                     left: Box::new(Identifier(Ident {
@@ -1444,6 +1954,52 @@ mod tests {
         Ok(())
     }
 
+    #[test]
+    fn analyze_table() -> Result<(), ParserError> {
+        let sql = "ANALYZE TABLE t";
+        let expected = Statement::AnalyzeTable(AnalyzeTableStatement {
+            table_name: ObjectName(vec![Ident::new("t")]),
+            columns: vec![],
+        });
+        expect_parse_ok(sql, expected)?;
+
+        let sql = "ANALYZE TABLE s.t COLUMNS (a, \"B\")";
+        let expected = Statement::AnalyzeTable(AnalyzeTableStatement {
+            table_name: ObjectName(vec![Ident::new("s"), Ident::new("t")]),
+            columns: vec![Ident::new("a"), Ident::with_quote('"', "B")],
+        });
+        expect_parse_ok(sql, expected)?;
+        assert_eq!(
+            DFParser::parse_sql(sql)?[0].to_string(),
+            "ANALYZE TABLE s.t COLUMNS (a, \"B\")"
+        );
+
+        expect_parse_error("ANALYZE t", "Expected TABLE");
+        expect_parse_error("ANALYZE TABLE t COLUMNS ()", "Expected identifier");
+        Ok(())
+    }
+
+    #[test]
+    fn reset() -> Result<(), ParserError> {
+        let sql = "RESET datafusion.execution.batch_size";
+        let expected = Statement::Reset(ResetStatement {
+            variable: Some(ObjectName(vec![
+                Ident::new("datafusion"),
+                Ident::new("execution"),
+                Ident::new("batch_size"),
+            ])),
+        });
+        expect_parse_ok(sql, expected)?;
+
+        let sql = "reset all";
+        let expected = Statement::Reset(ResetStatement { variable: None });
+        expect_parse_ok(sql, expected)?;
+        assert_eq!(DFParser::parse_sql(sql)?[0].to_string(), "RESET ALL");
+
+        expect_parse_error("RESET", "Expected identifier");
+        Ok(())
+    }
+
     #[test]
     fn copy_to_table_to_table() -> Result<(), ParserError> {
         // positive case
@@ -1602,6 +2158,86 @@ mod tests {
 
     // For error cases, see: `copy.slt`
 
+    #[test]
+    fn tablesample() {
+        one_statement_parses_to(
+            "SELECT * FROM t TABLESAMPLE SYSTEM (10)",
+            "SELECT * FROM t WITH (TABLESAMPLE('SYSTEM', 10))",
+        );
+        one_statement_parses_to(
+            "SELECT * FROM t AS x tablesample bernoulli (2.5 PERCENT) repeatable (42) WHERE a > 1",
+            "SELECT * FROM t AS x WITH (TABLESAMPLE('BERNOULLI', 2.5, 42)) WHERE a > 1",
+        );
+        // a quoted identifier is not a TABLESAMPLE clause
+        verified_stmt("SELECT \"tablesample\" FROM t");
+
+        expect_parse_error(
+            "SELECT * FROM t TABLESAMPLE RESERVOIR (10)",
+            "Expected BERNOULLI or SYSTEM, found: RESERVOIR",
+        );
+        expect_parse_error(
+            "SELECT * FROM t TABLESAMPLE SYSTEM (10) REPEATABLE (x)",
+            "Expected sample seed, found: x",
+        );
+        expect_parse_error(
+            "SELECT * FROM t TABLESAMPLE SYSTEM 10",
+            "Expected (, found: 10",
+        );
+    }
+
+    #[test]
+    fn table_version() {
+        one_statement_parses_to(
+            "SELECT * FROM t VERSION AS OF 3",
+            "SELECT * FROM t WITH (TABLE_VERSION('VERSION', 3))",
+        );
+        one_statement_parses_to(
+            "SELECT * FROM t AS x for system_time as of TIMESTAMP '2024-01-01' WHERE a > 1",
+            "SELECT * FROM t AS x WITH (TABLE_VERSION('SYSTEM_TIME', TIMESTAMP '2024-01-01')) WHERE a > 1",
+        );
+        // the hints of a table are in the same clause
+        one_statement_parses_to(
+            "SELECT * FROM t VERSION AS OF 'v1' TABLESAMPLE SYSTEM (10)",
+            "SELECT * FROM t WITH (TABLE_VERSION('VERSION', 'v1'), TABLESAMPLE('SYSTEM', 10))",
+        );
+        // a column named version
+        verified_stmt("SELECT version AS v FROM t");
+
+        expect_parse_error(
+            "SELECT * FROM t VERSION AS OF x",
+            "Expected version or timestamp, found: x",
+        );
+        expect_parse_error(
+            "SELECT * FROM t FOR SYSTEM_TIME '2024-01-01'",
+            "Expected AS, found: '2024-01-01'",
+        );
+    }
+
+    #[test]
+    fn asof_join() {
+        one_statement_parses_to(
+            "SELECT * FROM t ASOF JOIN u MATCH_CONDITION (t.ts >= u.ts)",
+            "SELECT * FROM t LEFT JOIN u ON ASOF_MATCH_CONDITION(t.ts >= u.ts)",
+        );
+        one_statement_parses_to(
+            "SELECT * FROM t asof join (SELECT * FROM u) AS v match_condition ((t.ts) < v.ts) \
+            ON t.k = v.k AND t.l = v.l WHERE t.a > 1",
+            "SELECT * FROM t LEFT JOIN (SELECT * FROM u) AS v ON ASOF_MATCH_CONDITION((t.ts) < v.ts) \
+            AND t.k = v.k AND t.l = v.l WHERE t.a > 1",
+        );
+        // a column named asof
+        verified_stmt("SELECT asof FROM t");
+
+        expect_parse_error(
+            "SELECT * FROM t ASOF JOIN u ON t.k = u.k",
+            "Expected MATCH_CONDITION after the relation of ASOF JOIN",
+        );
+        expect_parse_error(
+            "SELECT * FROM t ASOF JOIN u MATCH_CONDITION t.ts >= u.ts",
+            "Expected (, found: t",
+        );
+    }
+
     fn object_name(name: &str) -> CopyToSource {
         CopyToSource::Relation(ObjectName(vec![Ident::new(name)]))
     }
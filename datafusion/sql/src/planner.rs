@@ -37,9 +37,9 @@ use datafusion_common::{
     Result,
 };
 use datafusion_expr::logical_plan::{LogicalPlan, LogicalPlanBuilder};
-use datafusion_expr::utils::find_column_exprs;
-use datafusion_expr::TableSource;
+use datafusion_expr::utils::{find_column_exprs, COLLATION_METADATA_KEY};
 use datafusion_expr::{col, AggregateUDF, Expr, ScalarUDF};
+use datafusion_expr::{TablePolicy, TableSource};
 
 use crate::utils::make_decimal_type;
 
@@ -61,6 +61,23 @@ pub trait ContextProvider {
         not_impl_err!("Table Functions are not supported")
     }
 
+    /// Getter for a lateral table function, whose arguments may reference
+    /// the columns of the preceding items of the `FROM` clause
+    /// (`FROM t CROSS JOIN LATERAL f(t.a)`).
+    ///
+    /// The function is returned as a scalar function producing, for each
+    /// input row, a list of structs with the rows of the table function
+    /// for that row. The planner unnests the list and expands the structs.
+    fn get_lateral_table_function(&self, _name: &str) -> Option<Arc<ScalarUDF>> {
+        None
+    }
+
+    /// Getter for the row filter and column masks to enforce on the scans of
+    /// the table `name`
+    fn get_table_policy(&self, _name: &TableReference) -> Result<Option<TablePolicy>> {
+        Ok(None)
+    }
+
     /// This provides a worktable (an intermediate table that is used to store the results of a CTE during execution)
     /// We don't directly implement this in the logical plan's ['SqlToRel`]
     /// because the sql code needs access to a table that contains execution-related types that can't be a direct dependency
@@ -252,11 +269,18 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 .options
                 .iter()
                 .any(|x| x.option == ColumnOption::NotNull);
-            fields.push(Field::new(
+            let mut field = Field::new(
                 self.normalizer.normalize(column.name),
                 data_type,
                 !not_nullable,
-            ));
+            );
+            if let Some(collation) = &column.collation {
+                field = field.with_metadata(HashMap::from([(
+                    COLLATION_METADATA_KEY.to_string(),
+                    collation_name(collation),
+                )]));
+            }
+            fields.push(field);
         }
 
         Ok(Schema::new(fields))
@@ -374,6 +398,22 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             SQLDataType::Array(ArrayElemTypeDef::None) => {
                 not_impl_err!("Arrays with unspecified type is not supported")
             }
+            SQLDataType::Struct(fields) => {
+                let fields = fields
+                    .iter()
+                    .enumerate()
+                    .map(|(i, field)| {
+                        let data_type = self.convert_data_type(&field.field_type)?;
+                        // the unnamed fields are named like the fields of `struct(..)`
+                        let name = match &field.field_name {
+                            Some(name) => self.normalizer.normalize(name.clone()),
+                            None => format!("c{i}"),
+                        };
+                        Ok(Field::new(name, data_type, true))
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(DataType::Struct(fields.into()))
+            }
             other => self.convert_simple_data_type(other),
         }
     }
@@ -491,6 +531,17 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     }
 }
 
+/// The name of a collation (`COLLATE "de-DE"`). Collation names are matched
+/// case insensitively, so they are not normalized
+pub(crate) fn collation_name(collation: &ObjectName) -> String {
+    collation
+        .0
+        .iter()
+        .map(|ident| ident.value.as_str())
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
 /// Create a [`TableReference`] after normalizing the specified ObjectName
 ///
 /// Examples
@@ -15,10 +15,16 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use super::is_lateral_function;
+use crate::parser::ASOF_MATCH_CONDITION;
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use datafusion_common::{not_impl_err, Column, Result};
-use datafusion_expr::{JoinType, LogicalPlan, LogicalPlanBuilder};
-use sqlparser::ast::{Join, JoinConstraint, JoinOperator, TableWithJoins};
+use datafusion_common::{internal_err, not_impl_err, plan_err, Column, Result};
+use datafusion_expr::expr::BinaryExpr;
+use datafusion_expr::{Expr, JoinType, LogicalPlan, LogicalPlanBuilder, Operator};
+use sqlparser::ast::{
+    BinaryOperator, Expr as SQLExpr, FunctionArg, FunctionArgExpr, Join, JoinConstraint,
+    JoinOperator, TableWithJoins,
+};
 use std::collections::HashSet;
 
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
@@ -57,14 +63,47 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         }
     }
 
+    /// Plan `t`, whose relation is a `LATERAL` table function called with
+    /// the values of each row of `left`
+    pub(crate) fn plan_lateral_table_with_joins(
+        &self,
+        left: LogicalPlan,
+        t: TableWithJoins,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let origin_planner_context = planner_context.clone();
+        let mut left = self.plan_lateral_relation(left, t.relation, planner_context)?;
+        for join in t.joins {
+            *planner_context = origin_planner_context.clone();
+            left = self.parse_relation_join(left, join, planner_context)?;
+        }
+        *planner_context = origin_planner_context;
+        Ok(left)
+    }
+
     fn parse_relation_join(
         &self,
         left: LogicalPlan,
         join: Join,
         planner_context: &mut PlannerContext,
     ) -> Result<LogicalPlan> {
+        if is_lateral_function(&join.relation) {
+            return match join.join_operator {
+                JoinOperator::CrossJoin => {
+                    self.plan_lateral_relation(left, join.relation, planner_context)
+                }
+                other => not_impl_err!(
+                    "Unsupported JOIN operator {other:?} for a LATERAL table function"
+                ),
+            };
+        }
         let right = self.create_relation(join.relation, planner_context)?;
         match join.join_operator {
+            JoinOperator::LeftOuter(JoinConstraint::On(sql_expr))
+                if is_asof_join(&sql_expr) =>
+            {
+                self.parse_asof_join(left, right, sql_expr, planner_context)
+            }
             JoinOperator::LeftOuter(constraint) => {
                 self.parse_join(left, right, constraint, JoinType::Left, planner_context)
             }
@@ -118,6 +157,84 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         LogicalPlanBuilder::from(left).cross_join(right)?.build()
     }
 
+    /// Plan the `ON` clause of an `ASOF JOIN`, rewritten by the parser into
+    /// the match condition followed by the equalities of the join keys
+    fn parse_asof_join(
+        &self,
+        left: LogicalPlan,
+        right: LogicalPlan,
+        sql_expr: SQLExpr,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let join_schema = left.schema().join(right.schema())?;
+        let mut match_condition = None;
+        let mut left_keys = vec![];
+        let mut right_keys = vec![];
+        for sql_expr in split_conjunction(sql_expr) {
+            match sql_expr {
+                SQLExpr::Function(f) if f.name.to_string() == ASOF_MATCH_CONDITION => {
+                    let arg = match f.args.as_slice() {
+                        [FunctionArg::Unnamed(FunctionArgExpr::Expr(arg))] => arg.clone(),
+                        _ => return internal_err!("Invalid ASOF join match condition"),
+                    };
+                    let expr = self.sql_to_expr(arg, &join_schema, planner_context)?;
+                    match_condition = Some(match expr {
+                        Expr::BinaryExpr(BinaryExpr {
+                            left: l,
+                            op,
+                            right: r,
+                        }) => orient_join_condition(&left, &right, *l, op, *r)?,
+                        _ => return plan_err!(
+                            "ASOF join match condition must be a comparison, got {expr}"
+                        ),
+                    });
+                }
+                sql_expr => {
+                    let expr =
+                        self.sql_to_expr(sql_expr, &join_schema, planner_context)?;
+                    let (l, r) = match expr {
+                        Expr::BinaryExpr(BinaryExpr {
+                            left: l,
+                            op: Operator::Eq,
+                            right: r,
+                        }) => {
+                            let (l, _, r) = orient_join_condition(
+                                &left,
+                                &right,
+                                *l,
+                                Operator::Eq,
+                                *r,
+                            )?;
+                            (l, r)
+                        }
+                        _ => {
+                            return plan_err!(
+                                "ASOF join keys must be equalities, got {expr}"
+                            )
+                        }
+                    };
+                    match (l, r) {
+                        (Expr::Column(l), Expr::Column(r)) => {
+                            left_keys.push(l);
+                            right_keys.push(r);
+                        }
+                        (l, r) => {
+                            return plan_err!(
+                                "ASOF join keys must be columns, got {l} = {r}"
+                            )
+                        }
+                    }
+                }
+            }
+        }
+        let Some(match_condition) = match_condition else {
+            return internal_err!("ASOF join without a match condition");
+        };
+        LogicalPlanBuilder::from(left)
+            .asof_join(right, (left_keys, right_keys), match_condition)?
+            .build()
+    }
+
     fn parse_join(
         &self,
         left: LogicalPlan,
@@ -167,3 +284,61 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         }
     }
 }
+
+/// Whether the `ON` clause of a left join is the one of an `ASOF JOIN`, which
+/// the parser rewrites to start with the match condition
+fn is_asof_join(expr: &SQLExpr) -> bool {
+    match expr {
+        SQLExpr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            ..
+        } => is_asof_join(left),
+        SQLExpr::Function(f) => f.name.to_string() == ASOF_MATCH_CONDITION,
+        _ => false,
+    }
+}
+
+/// Splits the `AND`s of `expr` into their operands
+fn split_conjunction(expr: SQLExpr) -> Vec<SQLExpr> {
+    match expr {
+        SQLExpr::BinaryOp {
+            left,
+            op: BinaryOperator::And,
+            right,
+        } => {
+            let mut exprs = split_conjunction(*left);
+            exprs.extend(split_conjunction(*right));
+            exprs
+        }
+        SQLExpr::Nested(expr) => split_conjunction(*expr),
+        expr => vec![expr],
+    }
+}
+
+/// Returns the condition `l <op> r` of the join of `left` and `right` with
+/// the expression of `left` first
+fn orient_join_condition(
+    left: &LogicalPlan,
+    right: &LogicalPlan,
+    l: Expr,
+    op: Operator,
+    r: Expr,
+) -> Result<(Expr, Operator, Expr)> {
+    let is_from = |expr: &Expr, plan: &LogicalPlan| -> Result<bool> {
+        Ok(expr
+            .to_columns()?
+            .iter()
+            .all(|column| plan.schema().has_column(column)))
+    };
+    if is_from(&l, left)? && is_from(&r, right)? {
+        Ok((l, op, r))
+    } else if is_from(&l, right)? && is_from(&r, left)? {
+        match op.swap() {
+            Some(op) => Ok((r, op, l)),
+            None => plan_err!("Unsupported ASOF join condition {l} {op} {r}"),
+        }
+    } else {
+        plan_err!("ASOF join condition {l} {op} {r} must compare both inputs")
+    }
+}
@@ -15,10 +15,20 @@
 // specific language governing permissions and limitations
 // under the License.
 
+use crate::parser::{TABLESAMPLE_HINT, TABLE_VERSION_HINT};
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
-use datafusion_common::{not_impl_err, plan_err, DFSchema, Result, TableReference};
-use datafusion_expr::{expr::Unnest, Expr, LogicalPlan, LogicalPlanBuilder};
-use sqlparser::ast::{FunctionArg, FunctionArgExpr, TableFactor};
+use arrow_schema::DataType;
+use datafusion_common::{
+    internal_err, not_impl_err, plan_err, DFSchema, Result, TableReference, UnnestOptions,
+};
+use datafusion_expr::expr::{Alias, Cast, Unnest};
+use datafusion_expr::{
+    col, Expr, LogicalPlan, LogicalPlanBuilder, TableSample, TableSampleMethod,
+    TableVersion,
+};
+use sqlparser::ast::{
+    self, Expr as SQLExpr, FunctionArg, FunctionArgExpr, TableFactor, Value,
+};
 
 mod join;
 
@@ -31,9 +41,22 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     ) -> Result<LogicalPlan> {
         let (plan, alias) = match relation {
             TableFactor::Table {
-                name, alias, args, ..
+                name,
+                alias,
+                args,
+                with_hints,
+                version,
+                ..
             } => {
-                if let Some(func_args) = args {
+                let version =
+                    self.table_version(version, &with_hints, planner_context)?;
+                let mut sample = self.table_sample_from_hints(with_hints)?;
+                let (plan, alias) = if let Some(func_args) = args {
+                    if version.is_some() {
+                        return plan_err!(
+                            "Time travel is not supported on table functions"
+                        );
+                    }
                     let tbl_func_name = name.0.first().unwrap().value.to_string();
                     let args = func_args
                         .into_iter()
@@ -72,15 +95,40 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                             cte,
                             self.context_provider.get_table_source(table_ref.clone()),
                         ) {
+                            (Some(_), _) if version.is_some() => plan_err!(
+                                "Time travel is not supported on common table expressions"
+                            ),
                             (Some(cte_plan), _) => Ok(cte_plan.clone()),
                             (_, Ok(provider)) => {
-                                LogicalPlanBuilder::scan(table_ref, provider, None)?
-                                    .build()
+                                let policy =
+                                    self.context_provider.get_table_policy(&table_ref)?;
+                                // the policy applies to the rows of the version
+                                // and of the sample, read by the scan
+                                let mut builder =
+                                    LogicalPlanBuilder::scan(table_ref, provider, None)?;
+                                if let Some(version) = version {
+                                    builder = builder.version(version)?;
+                                }
+                                if let Some(sample) = sample.take() {
+                                    builder = builder.sample(sample)?;
+                                }
+                                let plan = builder.build()?;
+                                match policy {
+                                    Some(policy) => policy.apply(plan),
+                                    None => Ok(plan),
+                                }
                             }
                             (None, Err(e)) => Err(e),
                         }?,
                         alias,
                     )
+                };
+                match sample {
+                    Some(sample) => (
+                        LogicalPlanBuilder::from(plan).sample(sample)?.build()?,
+                        alias,
+                    ),
+                    None => (plan, alias),
                 }
             }
             TableFactor::Derived {
@@ -130,6 +178,11 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     "UNNEST table factor with offset is not supported yet"
                 );
             }
+            TableFactor::Function { lateral: true, .. } => {
+                // Without preceding FROM items, the arguments are constant
+                let input = LogicalPlanBuilder::empty(true).build()?;
+                return self.plan_lateral_relation(input, relation, planner_context);
+            }
             // @todo Support TableFactory::TableFunction?
             _ => {
                 return not_impl_err!(
@@ -143,4 +196,213 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             Ok(plan)
         }
     }
+
+    /// Returns the sample requested by a `TABLESAMPLE` clause, which the
+    /// parser rewrites into a `WITH (TABLESAMPLE('method', percentage[, seed]))`
+    /// table hint
+    fn table_sample_from_hints(
+        &self,
+        with_hints: Vec<SQLExpr>,
+    ) -> Result<Option<TableSample>> {
+        let Some(args) = with_hints.into_iter().find_map(|hint| match hint {
+            SQLExpr::Function(f) if f.name.to_string() == TABLESAMPLE_HINT => {
+                Some(f.args)
+            }
+            _ => None,
+        }) else {
+            return Ok(None);
+        };
+        let args = args
+            .into_iter()
+            .map(|arg| match arg {
+                FunctionArg::Unnamed(FunctionArgExpr::Expr(SQLExpr::Value(value))) => {
+                    Ok(value)
+                }
+                _ => plan_err!("Unsupported TABLESAMPLE argument: {arg}"),
+            })
+            .collect::<Result<Vec<_>>>()?;
+        let (method, percentage, seed) = match args.as_slice() {
+            [Value::SingleQuotedString(method), Value::Number(percentage, _)] => {
+                (method, percentage, None)
+            }
+            [Value::SingleQuotedString(method), Value::Number(percentage, _), Value::Number(seed, _)] => {
+                (method, percentage, Some(seed))
+            }
+            _ => return plan_err!("Invalid TABLESAMPLE arguments: {args:?}"),
+        };
+        let method = match method.to_uppercase().as_str() {
+            "BERNOULLI" => TableSampleMethod::Bernoulli,
+            "SYSTEM" => TableSampleMethod::System,
+            _ => return plan_err!("Unsupported TABLESAMPLE method: {method}"),
+        };
+        let Ok(percentage) = percentage.parse::<f64>() else {
+            return plan_err!("Invalid TABLESAMPLE percentage: {percentage}");
+        };
+        let seed = match seed {
+            Some(seed) => match seed.parse::<u64>() {
+                Ok(seed) => Some(seed),
+                Err(_) => return plan_err!("Invalid TABLESAMPLE seed: {seed}"),
+            },
+            None => None,
+        };
+        TableSample::try_new(method, percentage, seed).map(Some)
+    }
+
+    /// Returns the version of a table requested by a `FOR SYSTEM_TIME AS OF`
+    /// or `VERSION AS OF` clause, which is either parsed by the dialect as
+    /// `version` or rewritten by the parser into a
+    /// `WITH (TABLE_VERSION('SYSTEM_TIME' | 'VERSION', value))` table hint
+    fn table_version(
+        &self,
+        version: Option<ast::TableVersion>,
+        with_hints: &[SQLExpr],
+        planner_context: &mut PlannerContext,
+    ) -> Result<Option<TableVersion>> {
+        let (kind, value) = match version {
+            Some(ast::TableVersion::ForSystemTimeAsOf(value)) => {
+                ("SYSTEM_TIME".to_string(), value)
+            }
+            None => {
+                let Some(args) = with_hints.iter().find_map(|hint| match hint {
+                    SQLExpr::Function(f) if f.name.to_string() == TABLE_VERSION_HINT => {
+                        Some(&f.args)
+                    }
+                    _ => None,
+                }) else {
+                    return Ok(None);
+                };
+                match args.as_slice() {
+                    [FunctionArg::Unnamed(FunctionArgExpr::Expr(SQLExpr::Value(
+                        Value::SingleQuotedString(kind),
+                    ))), FunctionArg::Unnamed(FunctionArgExpr::Expr(value))] => {
+                        (kind.clone(), value.clone())
+                    }
+                    _ => return plan_err!("Invalid TABLE_VERSION arguments: {args:?}"),
+                }
+            }
+        };
+
+        // the value is a literal, possibly cast to a timestamp
+        let value =
+            self.sql_expr_to_logical_expr(value, &DFSchema::empty(), planner_context)?;
+        let value = match value {
+            Expr::Literal(value) => value,
+            Expr::Cast(Cast { expr, data_type }) => match *expr {
+                Expr::Literal(value) => value.cast_to(&data_type)?,
+                expr => return plan_err!("Expected a constant version, got {expr}"),
+            },
+            value => return plan_err!("Expected a constant version, got {value}"),
+        };
+        match kind.as_str() {
+            "SYSTEM_TIME" => TableVersion::try_new_timestamp(value).map(Some),
+            "VERSION" => TableVersion::try_new_version(value).map(Some),
+            _ => plan_err!("Unsupported table version: {kind}"),
+        }
+    }
+
+    /// Plan the `LATERAL` table function `relation`, called with the values
+    /// of each row of `input`, returning the columns of `input` followed by
+    /// the columns of the function for every row it produces
+    ///
+    /// The function is planned as a projection computing the rows of the
+    /// function as a list of structs, followed by an unnest of the list and a
+    /// projection expanding the structs.
+    pub(crate) fn plan_lateral_relation(
+        &self,
+        input: LogicalPlan,
+        relation: TableFactor,
+        planner_context: &mut PlannerContext,
+    ) -> Result<LogicalPlan> {
+        let TableFactor::Function {
+            lateral: true,
+            name,
+            args,
+            alias,
+        } = relation
+        else {
+            return internal_err!("Expected a LATERAL table function, got {relation:?}");
+        };
+        let func_name = name.0.first().unwrap().value.to_string();
+        let Some(udf) = self.context_provider.get_lateral_table_function(&func_name)
+        else {
+            return plan_err!("Invalid lateral table function: {func_name}");
+        };
+
+        let schema = input.schema().clone();
+        let args = args
+            .into_iter()
+            .map(|arg| {
+                if let FunctionArg::Unnamed(FunctionArgExpr::Expr(expr)) = arg {
+                    self.sql_expr_to_logical_expr(expr, &schema, planner_context)
+                } else {
+                    plan_err!("Unsupported function argument type: {:?}", arg)
+                }
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let rows_column = format!("__lateral_{}", schema.fields().len());
+        let input_columns = schema
+            .columns()
+            .into_iter()
+            .map(Expr::Column)
+            .collect::<Vec<_>>();
+        let plan = LogicalPlanBuilder::from(input)
+            .project(
+                input_columns
+                    .iter()
+                    .cloned()
+                    .chain([udf.call(args).alias(&rows_column)]),
+            )?
+            .unnest_column_with_options(
+                rows_column.as_str(),
+                UnnestOptions::new().with_preserve_nulls(false),
+            )?
+            .build()?;
+
+        let DataType::Struct(fields) = plan
+            .schema()
+            .field_with_unqualified_name(&rows_column)?
+            .data_type()
+            .clone()
+        else {
+            return plan_err!(
+                "Lateral table function {func_name} must return a list of structs"
+            );
+        };
+        let (qualifier, column_names) = match alias {
+            Some(alias) => (self.normalizer.normalize(alias.name), alias.columns),
+            None => (func_name, vec![]),
+        };
+        let column_names = if column_names.is_empty() {
+            fields.iter().map(|field| field.name().clone()).collect()
+        } else if column_names.len() == fields.len() {
+            column_names
+                .into_iter()
+                .map(|ident| self.normalizer.normalize(ident))
+                .collect::<Vec<_>>()
+        } else {
+            return plan_err!(
+                "Source table contains {} columns but only {} names given as column alias",
+                fields.len(),
+                column_names.len()
+            );
+        };
+        let function_columns =
+            fields.iter().zip(column_names).map(|(field, column_name)| {
+                Expr::Alias(Alias::new(
+                    col(&rows_column).field(field.name()),
+                    Some(TableReference::bare(qualifier.clone())),
+                    column_name,
+                ))
+            });
+        LogicalPlanBuilder::from(plan)
+            .project(input_columns.into_iter().chain(function_columns))?
+            .build()
+    }
+}
+
+/// Returns true if `relation` is a `LATERAL` table function, e.g.
+/// `LATERAL f(t.a)`
+pub(crate) fn is_lateral_function(relation: &TableFactor) -> bool {
+    matches!(relation, TableFactor::Function { lateral: true, .. })
 }
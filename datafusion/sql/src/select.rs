@@ -15,10 +15,11 @@
 // specific language governing permissions and limitations
 // under the License.
 
-use std::collections::HashSet;
+use std::collections::{HashMap, HashSet};
 use std::sync::Arc;
 
 use crate::planner::{ContextProvider, PlannerContext, SqlToRel};
+use crate::relation::is_lateral_function;
 use crate::utils::{
     check_columns_satisfy_exprs, extract_aliases, rebase_expr, resolve_aliases_to_exprs,
     resolve_columns, resolve_positions_to_exprs,
@@ -33,7 +34,7 @@ use datafusion_expr::expr_rewriter::{
 };
 use datafusion_expr::utils::{
     expand_qualified_wildcard, expand_wildcard, expr_as_column_expr, expr_to_columns,
-    find_aggregate_exprs, find_window_exprs,
+    find_aggregate_exprs, find_window_exprs, rewrite_using_column_references,
 };
 use datafusion_expr::{
     Expr, Filter, GroupingSet, LogicalPlan, LogicalPlanBuilder, Partitioning,
@@ -150,6 +151,12 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     for f in base_plan.schema().fields() {
                         alias_map.remove(f.name());
                     }
+                    let group_by_expr = rewrite_using_column_references(
+                        group_by_expr,
+                        base_plan.schema(),
+                        &base_plan,
+                    )?
+                    .unalias();
                     let group_by_expr =
                         resolve_aliases_to_exprs(&group_by_expr, &alias_map)?;
                     let group_by_expr =
@@ -360,6 +367,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
                 let filter_expr =
                     self.sql_to_expr(predicate_expr, plan.schema(), planner_context)?;
+                // filter predicates should not be aliased
+                let filter_expr =
+                    rewrite_using_column_references(filter_expr, plan.schema(), &plan)?
+                        .unalias();
                 let mut using_columns = HashSet::new();
                 expr_to_columns(&filter_expr, &mut using_columns)?;
                 let filter_expr = normalize_col_with_schemas_and_ambiguity_check(
@@ -389,16 +400,20 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 self.plan_table_with_joins(from, planner_context)
             }
             _ => {
-                let mut plans = from
-                    .into_iter()
-                    .map(|t| self.plan_table_with_joins(t, planner_context));
-
-                let mut left = LogicalPlanBuilder::from(plans.next().unwrap()?);
-
-                for right in plans {
-                    left = left.cross_join(right?)?;
+                let mut from = from.into_iter();
+                let mut left =
+                    self.plan_table_with_joins(from.next().unwrap(), planner_context)?;
+
+                for t in from {
+                    left = if is_lateral_function(&t.relation) {
+                        // `LATERAL` table functions see the preceding tables
+                        self.plan_lateral_table_with_joins(left, t, planner_context)?
+                    } else {
+                        let right = self.plan_table_with_joins(t, planner_context)?;
+                        LogicalPlanBuilder::from(left).cross_join(right)?.build()?
+                    };
                 }
-                Ok(left.build()?)
+                Ok(left)
             }
         }
     }
@@ -406,6 +421,10 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     /// Returns the `Expr`'s corresponding to a SQL query's SELECT expressions.
     ///
     /// Wildcards are expanded into the concrete list of columns.
+    ///
+    /// A SELECT expression may reference the alias of an earlier one (a
+    /// lateral column alias), as in `SELECT a + 1 AS x, x * 2 AS y FROM t`,
+    /// unless the input has a column of the same name.
     fn prepare_select_exprs(
         &self,
         plan: &LogicalPlan,
@@ -413,14 +432,23 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         empty_from: bool,
         planner_context: &mut PlannerContext,
     ) -> Result<Vec<Expr>> {
-        projection
-            .into_iter()
-            .map(|expr| self.sql_select_to_rex(expr, plan, empty_from, planner_context))
-            .flat_map(|result| match result {
-                Ok(vec) => vec.into_iter().map(Ok).collect(),
-                Err(err) => vec![Err(err)],
-            })
-            .collect::<Result<Vec<Expr>>>()
+        let mut select_exprs = vec![];
+        for item in projection {
+            // aliases from the projection can conflict with same-named expressions in the input
+            let mut lateral_aliases = extract_aliases(&select_exprs);
+            for f in plan.schema().fields() {
+                lateral_aliases.remove(f.name());
+            }
+            let exprs = self.sql_select_to_rex(
+                item,
+                plan,
+                empty_from,
+                &lateral_aliases,
+                planner_context,
+            )?;
+            select_exprs.extend(exprs);
+        }
+        Ok(select_exprs)
     }
 
     /// Generate a relational expression from a select SQL expression
@@ -429,11 +457,18 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         sql: SelectItem,
         plan: &LogicalPlan,
         empty_from: bool,
+        lateral_aliases: &HashMap<String, Expr>,
         planner_context: &mut PlannerContext,
     ) -> Result<Vec<Expr>> {
         match sql {
             SelectItem::UnnamedExpr(expr) => {
-                let expr = self.sql_to_expr(expr, plan.schema(), planner_context)?;
+                let expr = self.sql_to_expr_with_lateral_aliases(
+                    expr,
+                    plan.schema(),
+                    lateral_aliases,
+                    planner_context,
+                )?;
+                let expr = rewrite_using_column_references(expr, plan.schema(), plan)?;
                 let col = normalize_col_with_schemas_and_ambiguity_check(
                     expr,
                     &[&[plan.schema()]],
@@ -442,8 +477,15 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 Ok(vec![col])
             }
             SelectItem::ExprWithAlias { expr, alias } => {
+                let select_expr = self.sql_to_expr_with_lateral_aliases(
+                    expr,
+                    plan.schema(),
+                    lateral_aliases,
+                    planner_context,
+                )?;
                 let select_expr =
-                    self.sql_to_expr(expr, plan.schema(), planner_context)?;
+                    rewrite_using_column_references(select_expr, plan.schema(), plan)?
+                        .unalias();
                 let col = normalize_col_with_schemas_and_ambiguity_check(
                     select_expr,
                     &[&[plan.schema()]],
@@ -547,6 +589,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                         SelectItem::UnnamedExpr(item.expr.clone()),
                         plan,
                         empty_from,
+                        &HashMap::new(),
                         planner_context,
                     )?[0]
                         .clone();
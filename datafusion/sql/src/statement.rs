@@ -21,16 +21,17 @@ use std::str::FromStr;
 use std::sync::Arc;
 
 use crate::parser::{
-    CopyToSource, CopyToStatement, CreateExternalTable, DFParser, ExplainStatement,
-    LexOrdering, Statement as DFStatement,
+    AnalyzeTableStatement, CopyToSource, CopyToStatement, CreateExternalTable, DFParser,
+    ExplainStatement, LexOrdering, ResetStatement, Statement as DFStatement,
 };
 use crate::planner::{
     object_name_to_qualifier, ContextProvider, PlannerContext, SqlToRel,
 };
 use crate::utils::normalize_ident;
 
-use arrow_schema::{DataType, Fields};
+use arrow_schema::{DataType, Field, Fields};
 use datafusion_common::parsers::CompressionTypeVariant;
+use datafusion_common::tree_node::{Transformed, TreeNode};
 use datafusion_common::{
     exec_err, not_impl_err, plan_datafusion_err, plan_err, schema_err,
     unqualified_field_not_found, Column, Constraints, DFSchema, DFSchemaRef,
@@ -38,19 +39,20 @@ use datafusion_common::{
     TableReference, ToDFSchema,
 };
 use datafusion_expr::dml::CopyTo;
+use datafusion_expr::expr::Placeholder;
 use datafusion_expr::expr_rewriter::normalize_col_with_schemas_and_ambiguity_check;
 use datafusion_expr::logical_plan::builder::project;
 use datafusion_expr::logical_plan::DdlStatement;
 use datafusion_expr::utils::expr_to_columns;
 use datafusion_expr::{
-    cast, col, Analyze, CreateCatalog, CreateCatalogSchema,
+    cast, col, Analyze, AnalyzeTable, CreateCatalog, CreateCatalogSchema,
     CreateExternalTable as PlanCreateExternalTable, CreateFunction, CreateFunctionBody,
     CreateMemoryTable, CreateView, DescribeTable, DmlStatement, DropCatalogSchema,
     DropFunction, DropTable, DropView, EmptyRelation, Explain, ExprSchemable, Filter,
-    LogicalPlan, LogicalPlanBuilder, OperateFunctionArg, PlanType, Prepare, SetVariable,
-    Statement as PlanStatement, ToStringifiedPlan, TransactionAccessMode,
-    TransactionConclusion, TransactionEnd, TransactionIsolationLevel, TransactionStart,
-    Volatility, WriteOp,
+    LogicalPlan, LogicalPlanBuilder, OperateFunctionArg, PlanType, Prepare,
+    ResetVariable, SetVariable, Statement as PlanStatement, ToStringifiedPlan,
+    TransactionAccessMode, TransactionConclusion, TransactionEnd,
+    TransactionIsolationLevel, TransactionStart, Volatility, WriteOp,
 };
 use sqlparser::ast;
 use sqlparser::ast::{
@@ -60,6 +62,7 @@ use sqlparser::ast::{
     TableWithJoins, TransactionMode, UnaryOperator, Value,
 };
 use sqlparser::parser::ParserError::ParserError;
+use sqlparser::tokenizer::Token;
 
 fn ident_to_string(ident: &Ident) -> String {
     normalize_ident(ident.to_owned())
@@ -153,6 +156,17 @@ fn calc_inline_constraints_from_columns(columns: &[ColumnDef]) -> Vec<TableConst
     constraints
 }
 
+/// Returns the name of the configuration option `variable` refers to
+fn variable_name(variable: &ObjectName) -> String {
+    let variable = object_name_to_string(variable).to_lowercase();
+    if variable == "timezone" || variable == "time.zone" {
+        // we could introduce alias in OptionDefinition if this string matching thing grows
+        "datafusion.execution.time_zone".to_string()
+    } else {
+        variable
+    }
+}
+
 impl<'a, S: ContextProvider> SqlToRel<'a, S> {
     /// Generate a logical plan from an DataFusion SQL statement
     pub fn statement_to_plan(&self, statement: DFStatement) -> Result<LogicalPlan> {
@@ -165,6 +179,8 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 analyze,
                 statement,
             }) => self.explain_to_plan(verbose, analyze, *statement),
+            DFStatement::AnalyzeTable(s) => self.analyze_table_to_plan(s),
+            DFStatement::Reset(s) => self.reset_variable_to_plan(s),
         }
     }
 
@@ -240,6 +256,11 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                         let input_schema = plan.schema();
 
                         let plan = if !columns.is_empty() {
+                            if columns.iter().any(|c| c.collation.is_some()) {
+                                return not_impl_err!(
+                                    "COLLATE is not supported in the columns of CREATE TABLE AS"
+                                );
+                            }
                             let schema = self.build_schema(columns)?.to_dfschema_ref()?;
                             if schema.fields().len() != input_schema.fields().len() {
                                 return plan_err!(
@@ -572,7 +593,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
 
             Statement::StartTransaction {
                 modes,
-                begin: false,
+                begin: _,
                 modifier,
             } => {
                 if let Some(modifier) = modifier {
@@ -702,14 +723,73 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 let mut planner_context = PlannerContext::new()
                     .with_prepare_param_data_types(arg_types.unwrap_or_default());
 
-                let result_expression = match params.return_ {
-                    Some(r) => Some(self.sql_to_expr(
-                        r,
-                        &DFSchema::empty(),
-                        &mut planner_context,
-                    )?),
-                    None => None,
+                // Named arguments are referred to by name in the body of the
+                // function, which is planned with them replaced by their
+                // positional `$n` placeholders
+                let named_args = args
+                    .iter()
+                    .flatten()
+                    .enumerate()
+                    .filter_map(|(i, arg)| {
+                        let name = self.normalizer.normalize(arg.name.clone()?);
+                        Some((name, i, arg.data_type.clone()))
+                    })
+                    .collect::<Vec<_>>();
+                let args_schema = DFSchema::from_unqualifed_fields(
+                    named_args
+                        .iter()
+                        .map(|(name, _, data_type)| {
+                            Field::new(name, data_type.clone(), true)
+                        })
+                        .collect::<Vec<_>>()
+                        .into(),
+                    HashMap::new(),
+                )?;
+
+                // `AS '<definition>'` is the body of the function unless it is
+                // written in another language than SQL
+                let is_sql = params
+                    .language
+                    .as_ref()
+                    .map_or(true, |language| language.value.eq_ignore_ascii_case("sql"));
+                let body = match (params.return_, &params.as_) {
+                    (Some(r), _) => Some(r),
+                    (None, Some(definition)) if is_sql => {
+                        let (ast::FunctionDefinition::SingleQuotedDef(sql)
+                        | ast::FunctionDefinition::DoubleDollarDef(sql)) = definition;
+                        let mut parser = DFParser::new(sql)?.parser;
+                        let body = parser.parse_expr()?;
+                        parser.expect_token(&Token::EOF)?;
+                        Some(body)
+                    }
+                    (None, _) => None,
                 };
+                let result_expression = body
+                    .map(|body| {
+                        self.sql_to_expr(body, &args_schema, &mut planner_context)?
+                            .transform(|expr| {
+                                let datafusion_expr::Expr::Column(Column {
+                                    relation: None,
+                                    name,
+                                }) = &expr
+                                else {
+                                    return Ok(Transformed::no(expr));
+                                };
+                                Ok(match named_args.iter().find(|(n, ..)| n == name) {
+                                    Some((_, i, data_type)) => Transformed::yes(
+                                        datafusion_expr::Expr::Placeholder(
+                                            Placeholder::new(
+                                                format!("${}", i + 1),
+                                                Some(data_type.clone()),
+                                            ),
+                                        ),
+                                    ),
+                                    None => Transformed::no(expr),
+                                })
+                            })
+                            .map(|transformed| transformed.data)
+                    })
+                    .transpose()?;
 
                 let params = CreateFunctionBody {
                     language: params.language,
@@ -828,6 +908,36 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         }))
     }
 
+    fn analyze_table_to_plan(
+        &self,
+        statement: AnalyzeTableStatement,
+    ) -> Result<LogicalPlan> {
+        let table_ref = self.object_name_to_table_reference(statement.table_name)?;
+        let table_source = self.context_provider.get_table_source(table_ref.clone())?;
+        let schema = table_source.schema();
+
+        let columns = statement
+            .columns
+            .into_iter()
+            .map(|ident| {
+                let name = self.normalizer.normalize(ident);
+                if schema.column_with_name(&name).is_none() {
+                    return plan_err!("Column {name} not found in table {table_ref}");
+                }
+                Ok(name)
+            })
+            .collect::<Result<Vec<_>>>()?;
+
+        let output_schema = DFSchema::try_from(AnalyzeTable::output_schema())?;
+        Ok(LogicalPlan::Statement(PlanStatement::AnalyzeTable(
+            AnalyzeTable {
+                table_name: table_ref,
+                columns,
+                schema: Arc::new(output_schema),
+            },
+        )))
+    }
+
     fn copy_to_plan(&self, statement: CopyToStatement) -> Result<LogicalPlan> {
         // determine if source is table or query and handle accordingly
         let copy_source = statement.source;
@@ -839,6 +949,11 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                     self.context_provider.get_table_source(table_ref.clone())?;
                 let plan =
                     LogicalPlanBuilder::scan(table_name, table_source, None)?.build()?;
+                // only the rows and values the user may read are copied
+                let plan = match self.context_provider.get_table_policy(&table_ref)? {
+                    Some(policy) => policy.apply(plan)?,
+                    None => plan,
+                };
                 let input_schema = plan.schema().clone();
                 (plan, input_schema, Some(table_ref))
             }
@@ -972,6 +1087,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             delimiter,
             location,
             table_partition_cols,
+            partition_transforms,
             if_not_exists,
             file_compression_type,
             order_exprs,
@@ -1000,6 +1116,39 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             .into_iter()
             .collect();
 
+        // the transforms of the partition columns refer to the path of the
+        // files relative to the location
+        let path_schema = DFSchema::from_unqualifed_fields(
+            vec![Field::new("path", DataType::Utf8, false)].into(),
+            HashMap::new(),
+        )?;
+        // the partition columns are named as written, while the fields of the
+        // schema are normalized
+        let normalized_names = columns
+            .iter()
+            .map(|column| {
+                (
+                    column.name.to_string(),
+                    self.normalizer.normalize(column.name.clone()),
+                )
+            })
+            .collect::<HashMap<_, _>>();
+        let normalize_partition_col = |name: String| match normalized_names.get(&name) {
+            Some(normalized) => normalized.clone(),
+            None => self.normalizer.normalize(Ident::new(name)),
+        };
+        let table_partition_cols = table_partition_cols
+            .into_iter()
+            .map(&normalize_partition_col)
+            .collect();
+        let partition_transforms = partition_transforms
+            .into_iter()
+            .map(|(name, expr)| {
+                let expr = self.sql_to_expr(expr, &path_schema, &mut planner_context)?;
+                Ok((normalize_partition_col(name), expr))
+            })
+            .collect::<Result<_>>()?;
+
         let schema = self.build_schema(columns)?;
         let df_schema = schema.to_dfschema_ref()?;
         df_schema.check_names()?;
@@ -1020,6 +1169,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
                 has_header,
                 delimiter,
                 table_partition_cols,
+                partition_transforms,
                 if_not_exists,
                 definition,
                 file_compression_type,
@@ -1097,7 +1247,12 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             // we could introduce alias in OptionDefinition if this string matching thing grows
             format!("{base_query} WHERE name = 'datafusion.execution.time_zone'")
         } else {
-            format!("{base_query} WHERE name = '{variable}'")
+            // a namespace, such as `datafusion.execution.parquet`, shows all
+            // its options
+            format!(
+                "{base_query} WHERE name = '{variable}' \
+                OR name LIKE '{variable}.%' ORDER BY name"
+            )
         };
 
         let mut rewrite = DFParser::parse_sql(&query)?;
@@ -1121,13 +1276,7 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             return not_impl_err!("HIVEVAR is not supported");
         }
 
-        let variable = object_name_to_string(variable);
-        let mut variable_lower = variable.to_lowercase();
-
-        if variable_lower == "timezone" || variable_lower == "time.zone" {
-            // we could introduce alias in OptionDefinition if this string matching thing grows
-            variable_lower = "datafusion.execution.time_zone".to_string();
-        }
+        let variable_lower = variable_name(variable);
 
         // parse value string from Expr
         let value_string = match &value[0] {
@@ -1171,6 +1320,15 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         Ok(LogicalPlan::Statement(statement))
     }
 
+    fn reset_variable_to_plan(&self, statement: ResetStatement) -> Result<LogicalPlan> {
+        let statement = PlanStatement::ResetVariable(ResetVariable {
+            variable: statement.variable.as_ref().map(variable_name),
+            schema: DFSchemaRef::new(DFSchema::empty()),
+        });
+
+        Ok(LogicalPlan::Statement(statement))
+    }
+
     fn delete_to_plan(
         &self,
         table_name: ObjectName,
@@ -1187,6 +1345,12 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             None,
         )?
         .build()?;
+        let scan = match self.dml_table_policy(&table_ref, "DELETE FROM")? {
+            Some(row_filter) => {
+                LogicalPlanBuilder::from(scan).filter(row_filter)?.build()?
+            }
+            None => scan,
+        };
         let mut planner_context = PlannerContext::new();
 
         let source = match predicate_expr {
@@ -1215,6 +1379,24 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
         Ok(plan)
     }
 
+    /// Returns the row filter of the policy of `table_ref`, the table modified
+    /// by the DML statement `statement`: only the rows the user may read are
+    /// modified. Tables whose policy masks columns cannot be modified, as
+    /// the statement would read or write the masked values.
+    fn dml_table_policy(
+        &self,
+        table_ref: &TableReference,
+        statement: &str,
+    ) -> Result<Option<datafusion_expr::Expr>> {
+        match self.context_provider.get_table_policy(table_ref)? {
+            Some(policy) if !policy.column_masks.is_empty() => plan_err!(
+                "Cannot {statement} table {table_ref}: its access policy masks columns"
+            ),
+            Some(policy) => Ok(policy.row_filter),
+            None => Ok(None),
+        }
+    }
+
     fn update_to_plan(
         &self,
         table: TableWithJoins,
@@ -1234,6 +1416,8 @@ impl<'a, S: ContextProvider> SqlToRel<'a, S> {
             table_name.clone(),
             &table_source.schema(),
         )?);
+        // the row filter of the policy is applied to the scan of the table below
+        self.dml_table_policy(&table_name, "UPDATE")?;
 
         // Overwrite with assignment expressions
         let mut planner_context = PlannerContext::new();
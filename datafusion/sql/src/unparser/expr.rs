@@ -16,7 +16,7 @@
 // under the License.
 
 use arrow_array::{Date32Array, Date64Array};
-use arrow_schema::DataType;
+use arrow_schema::{DataType, TimeUnit};
 use datafusion_common::{
     internal_datafusion_err, not_impl_err, plan_err, Column, Result, ScalarValue,
 };
@@ -287,6 +287,9 @@ impl Unparser<'_> {
                 asc: _,
                 nulls_first: _,
             }) => self.expr_to_sql(expr),
+            Expr::IsNull(expr) => {
+                Ok(ast::Expr::IsNull(Box::new(self.expr_to_sql(expr)?)))
+            }
             Expr::IsNotNull(expr) => {
                 Ok(ast::Expr::IsNotNull(Box::new(self.expr_to_sql(expr)?)))
             }
@@ -322,7 +325,6 @@ impl Unparser<'_> {
             Expr::ScalarVariable(_, _) => {
                 not_impl_err!("Unsupported Expr conversion: {expr:?}")
             }
-            Expr::IsNull(_) => not_impl_err!("Unsupported Expr conversion: {expr:?}"),
             Expr::IsNotFalse(_) => not_impl_err!("Unsupported Expr conversion: {expr:?}"),
             Expr::GetIndexedField(_) => {
                 not_impl_err!("Unsupported Expr conversion: {expr:?}")
@@ -635,7 +637,12 @@ impl Unparser<'_> {
         }
     }
 
-    fn arrow_dtype_to_ast_dtype(&self, data_type: &DataType) -> Result<ast::DataType> {
+    /// Convert the Arrow type `data_type` to the SQL type it is planned
+    /// from, e.g. `STRUCT<"a" INT>` for a struct with an `Int32` field
+    pub fn arrow_dtype_to_ast_dtype(
+        &self,
+        data_type: &DataType,
+    ) -> Result<ast::DataType> {
         match data_type {
             DataType::Null => {
                 not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
@@ -654,6 +661,11 @@ impl Unparser<'_> {
             }
             DataType::Float32 => Ok(ast::DataType::Float(None)),
             DataType::Float64 => Ok(ast::DataType::Double),
+            // TIMESTAMP is planned with nanoseconds, the other units and the
+            // time zones can't be written in SQL
+            DataType::Timestamp(TimeUnit::Nanosecond, None) => {
+                Ok(ast::DataType::Timestamp(None, ast::TimezoneInfo::None))
+            }
             DataType::Timestamp(_, _) => {
                 not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
             }
@@ -671,9 +683,7 @@ impl Unparser<'_> {
             DataType::Interval(_) => {
                 not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
             }
-            DataType::Binary => {
-                not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
-            }
+            DataType::Binary => Ok(ast::DataType::Bytea),
             DataType::FixedSizeBinary(_) => {
                 not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
             }
@@ -688,8 +698,11 @@ impl Unparser<'_> {
             DataType::Utf8View => {
                 not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
             }
-            DataType::List(_) => {
-                not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
+            DataType::List(field) => {
+                let element_type = self.arrow_dtype_to_ast_dtype(field.data_type())?;
+                Ok(ast::DataType::Array(ast::ArrayElemTypeDef::AngleBracket(
+                    Box::new(element_type),
+                )))
             }
             DataType::FixedSizeList(_, _) => {
                 not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
@@ -703,8 +716,18 @@ impl Unparser<'_> {
             DataType::LargeListView(_) => {
                 not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
             }
-            DataType::Struct(_) => {
-                not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
+            DataType::Struct(fields) => {
+                let fields = fields
+                    .iter()
+                    .map(|field| {
+                        Ok(ast::StructField {
+                            field_name: Some(self.new_ident(field.name().to_string())),
+                            field_type: self
+                                .arrow_dtype_to_ast_dtype(field.data_type())?,
+                        })
+                    })
+                    .collect::<Result<Vec<_>>>()?;
+                Ok(ast::DataType::Struct(fields))
             }
             DataType::Union(_, _) => {
                 not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
@@ -712,8 +735,16 @@ impl Unparser<'_> {
             DataType::Dictionary(_, _) => {
                 not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
             }
-            DataType::Decimal128(_, _) => {
-                not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
+            DataType::Decimal128(precision, scale) => {
+                // the negative scales can't be written in SQL
+                match u64::try_from(*scale) {
+                    Ok(scale) => Ok(ast::DataType::Decimal(
+                        ast::ExactNumberInfo::PrecisionAndScale(*precision as u64, scale),
+                    )),
+                    Err(_) => {
+                        not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
+                    }
+                }
             }
             DataType::Decimal256(_, _) => {
                 not_impl_err!("Unsupported DataType: conversion: {data_type:?}")
@@ -819,6 +850,22 @@ mod tests {
                 }),
                 r#"CAST("a" AS INTEGER UNSIGNED)"#,
             ),
+            (
+                Expr::Cast(Cast {
+                    expr: Box::new(col("a")),
+                    data_type: DataType::new_list(
+                        DataType::Struct(
+                            vec![
+                                Field::new("b", DataType::Int32, true),
+                                Field::new("C", DataType::Decimal128(10, 2), true),
+                            ]
+                            .into(),
+                        ),
+                        true,
+                    ),
+                }),
+                r#"CAST("a" AS ARRAY<STRUCT<"b" INTEGER, "C" DECIMAL(10,2)>>)"#,
+            ),
             (
                 col("a").in_list(vec![lit(1), lit(2), lit(3)], false),
                 r#""a" IN (1, 2, 3)"#,
@@ -901,6 +948,7 @@ mod tests {
                 }),
                 "COUNT(DISTINCT *)",
             ),
+            (col("a").is_null(), r#""a" IS NULL"#),
             (col("a").is_not_null(), r#""a" IS NOT NULL"#),
             (
                 (col("a") + col("b")).gt(lit(4)).is_true(),
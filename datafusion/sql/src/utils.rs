@@ -207,13 +207,20 @@ pub fn window_expr_common_partition_keys(window_exprs: &[Expr]) -> Result<&[Expr
             expr => exec_err!("Impossibly got non-window expr {expr:?}"),
         })
         .collect::<Result<Vec<_>>>()?;
-    let result = all_partition_keys
-        .iter()
+    common_partition_keys(all_partition_keys.into_iter().map(Vec::as_slice))
+}
+
+/// given the partition keys of window expressions sharing the same sort key, find their
+/// common partition keys, which are the shortest ones.
+pub fn common_partition_keys<'a, T>(
+    all_partition_keys: impl IntoIterator<Item = &'a [T]>,
+) -> Result<&'a [T]> {
+    all_partition_keys
+        .into_iter()
         .min_by_key(|s| s.len())
         .ok_or_else(|| {
             DataFusionError::Execution("No window expressions found".to_owned())
-        })?;
-    Ok(result)
+        })
 }
 
 /// Returns a validated `DataType` for the specified precision and
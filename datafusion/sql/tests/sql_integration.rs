@@ -707,6 +707,78 @@ fn select_filter_cannot_use_alias() {
     assert_field_not_found(err, "x");
 }
 
+#[test]
+fn select_from_table_sample() {
+    let sql = "SELECT id FROM person TABLESAMPLE SYSTEM (10) REPEATABLE (42) WHERE age > 20";
+    let expected = "Projection: person.id\
+                        \n  Filter: person.age > Int64(20)\
+                        \n    TableScan: person, sample=SYSTEM (10%) REPEATABLE (42)";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_from_aliased_table_sample() {
+    let sql = "SELECT p.id FROM person p TABLESAMPLE BERNOULLI (0.5 PERCENT)";
+    let expected = "Projection: p.id\
+                        \n  SubqueryAlias: p\
+                        \n    TableScan: person, sample=BERNOULLI (0.5%)";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_from_table_version() {
+    let sql = "SELECT id FROM person VERSION AS OF 3 WHERE age > 20";
+    let expected = "Projection: person.id\
+                        \n  Filter: person.age > Int64(20)\
+                        \n    TableScan: person, version=VERSION AS OF 3";
+    quick_test(sql, expected);
+
+    let sql = "SELECT p.id FROM person p FOR SYSTEM_TIME AS OF '2024-01-01 10:00:00'";
+    let expected = "Projection: p.id\
+                        \n  SubqueryAlias: p\
+                        \n    TableScan: person, version=SYSTEM_TIME AS OF 2024-01-01T10:00:00";
+    quick_test(sql, expected);
+
+    let sql = "WITH c AS (SELECT id FROM person) SELECT * FROM c VERSION AS OF 3";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_eq!(
+        "Error during planning: Time travel is not supported on common table expressions",
+        err.strip_backtrace()
+    );
+}
+
+#[test]
+fn select_lateral_column_alias() {
+    let sql = "SELECT age + 1 AS x, x * 2 AS y, y - 1 FROM person";
+    let expected = "Projection: person.age + Int64(1) AS x, (person.age + Int64(1)) * Int64(2) AS y, (person.age + Int64(1)) * Int64(2) - Int64(1)\
+                        \n  TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_lateral_column_alias_input_column_takes_precedence() {
+    let sql = "SELECT first_name AS age, age + 1 AS next_age FROM person";
+    let expected = "Projection: person.first_name AS age, person.age + Int64(1) AS next_age\
+                        \n  TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_lateral_column_alias_of_aggregate() {
+    let sql = "SELECT state, MAX(age) AS m, m + 1 AS n FROM person GROUP BY state";
+    let expected = "Projection: person.state, MAX(person.age) AS m, MAX(person.age) + Int64(1) AS n\
+                        \n  Aggregate: groupBy=[[person.state]], aggr=[[MAX(person.age)]]\
+                        \n    TableScan: person";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn select_lateral_column_alias_must_precede_reference() {
+    let sql = "SELECT x + 1 AS y, age AS x FROM person";
+    let err = logical_plan(sql).expect_err("query should have failed");
+    assert_field_not_found(err, "x");
+}
+
 #[test]
 fn select_neg_filter() {
     let sql = "SELECT id, first_name, last_name \
@@ -912,7 +984,7 @@ fn natural_left_join() {
 #[test]
 fn natural_right_join() {
     let sql = "SELECT l_item_id FROM lineitem a NATURAL RIGHT JOIN lineitem b";
-    let expected = "Projection: a.l_item_id\
+    let expected = "Projection: b.l_item_id\
                         \n  Right Join: Using a.l_item_id = b.l_item_id, a.l_description = b.l_description, a.price = b.price\
                         \n    SubqueryAlias: a\
                         \n      TableScan: lineitem\
@@ -921,6 +993,18 @@ fn natural_right_join() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn natural_full_join() {
+    let sql = "SELECT l_item_id FROM lineitem a NATURAL FULL JOIN lineitem b";
+    let expected = "Projection: CASE WHEN a.l_item_id IS NOT NULL THEN a.l_item_id ELSE b.l_item_id END AS l_item_id\
+                        \n  Full Join: Using a.l_item_id = b.l_item_id, a.l_description = b.l_description, a.price = b.price\
+                        \n    SubqueryAlias: a\
+                        \n      TableScan: lineitem\
+                        \n    SubqueryAlias: b\
+                        \n      TableScan: lineitem";
+    quick_test(sql, expected);
+}
+
 #[test]
 fn natural_join_no_common_becomes_cross_join() {
     let sql = "SELECT * FROM person a NATURAL JOIN lineitem b";
@@ -2159,6 +2243,50 @@ fn project_wildcard_on_join_with_using() {
     quick_test(sql, expected);
 }
 
+#[test]
+fn project_wildcard_on_right_join_with_using() {
+    let sql = "SELECT * \
+            FROM lineitem \
+            RIGHT JOIN lineitem as lineitem2 \
+            USING (l_item_id)";
+    let expected = "Projection: lineitem.l_description, lineitem.price, lineitem2.l_item_id, lineitem2.l_description, lineitem2.price\
+        \n  Right Join: Using lineitem.l_item_id = lineitem2.l_item_id\
+        \n    TableScan: lineitem\
+        \n    SubqueryAlias: lineitem2\
+        \n      TableScan: lineitem";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn project_wildcard_on_full_join_with_using() {
+    let sql = "SELECT * \
+            FROM lineitem \
+            FULL JOIN lineitem as lineitem2 \
+            USING (l_item_id)";
+    let expected = "Projection: CASE WHEN lineitem.l_item_id IS NOT NULL THEN lineitem.l_item_id ELSE lineitem2.l_item_id END AS l_item_id, lineitem.l_description, lineitem.price, lineitem2.l_description, lineitem2.price\
+        \n  Full Join: Using lineitem.l_item_id = lineitem2.l_item_id\
+        \n    TableScan: lineitem\
+        \n    SubqueryAlias: lineitem2\
+        \n      TableScan: lineitem";
+    quick_test(sql, expected);
+}
+
+#[test]
+fn filter_on_full_join_with_using() {
+    let sql = "SELECT lineitem.price \
+            FROM lineitem \
+            FULL JOIN lineitem as lineitem2 \
+            USING (l_item_id) \
+            WHERE l_item_id > 1";
+    let expected = "Projection: lineitem.price\
+        \n  Filter: CASE WHEN lineitem.l_item_id IS NOT NULL THEN lineitem.l_item_id ELSE lineitem2.l_item_id END > Int64(1)\
+        \n    Full Join: Using lineitem.l_item_id = lineitem2.l_item_id\
+        \n      TableScan: lineitem\
+        \n      SubqueryAlias: lineitem2\
+        \n        TableScan: lineitem";
+    quick_test(sql, expected);
+}
+
 #[test]
 fn equijoin_explicit_syntax_3_tables() {
     let sql = "SELECT id, order_id, l_description \
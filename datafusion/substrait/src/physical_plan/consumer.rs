@@ -112,7 +112,8 @@ pub async fn from_substrait_rel(
                         limit: None,
                         table_partition_cols: vec![],
                         output_ordering: vec![],
-                        column_hints: None
+                        column_hints: None,
+                        listing_pruning: None,
                     };
 
                     if let Some(MaskExpression { select, .. }) = &read.projection {
@@ -50,6 +50,7 @@ async fn parquet_exec() -> Result<()> {
         table_partition_cols: vec![],
         output_ordering: vec![],
         column_hints: None,
+        listing_pruning: None,
     };
     let parquet_exec: Arc<dyn ExecutionPlan> = Arc::new(ParquetExec::new(
         scan_config,